@@ -0,0 +1,291 @@
+//! Anthropic Messages API over a host [`Transport`]
+//!
+//! A lean, non-streaming counterpart of `uira_providers::AnthropicClient` for
+//! hosts without reqwest or tokio. API keys only; OAuth stays native.
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use uira_core::{
+    ContentBlock, Message, MessageContent, ModelResponse, Role, StopReason, TokenUsage, ToolSpec,
+};
+
+use crate::{CoreError, HttpRequest, Model, Transport};
+
+const ANTHROPIC_VERSION: &str = "2023-06-01";
+const DEFAULT_BASE_URL: &str = "https://api.anthropic.com";
+const DEFAULT_MAX_OUTPUT_TOKENS: usize = 8192;
+const CONTEXT_WINDOW: usize = 200_000;
+
+pub struct AnthropicModel<T> {
+    transport: T,
+    api_key: String,
+    model: String,
+    base_url: String,
+    max_output_tokens: usize,
+    headers: Vec<(String, String)>,
+}
+
+#[derive(Debug, Deserialize)]
+struct WireResponse {
+    id: String,
+    model: String,
+    content: Vec<ContentBlock>,
+    #[serde(default)]
+    stop_reason: Option<String>,
+    usage: WireUsage,
+}
+
+#[derive(Debug, Deserialize)]
+struct WireUsage {
+    input_tokens: u64,
+    output_tokens: u64,
+    #[serde(default)]
+    cache_read_input_tokens: Option<u64>,
+    #[serde(default)]
+    cache_creation_input_tokens: Option<u64>,
+}
+
+impl<T: Transport> AnthropicModel<T> {
+    pub fn new(transport: T, api_key: impl Into<String>, model: impl Into<String>) -> Self {
+        Self {
+            transport,
+            api_key: api_key.into(),
+            model: model.into(),
+            base_url: DEFAULT_BASE_URL.to_string(),
+            max_output_tokens: DEFAULT_MAX_OUTPUT_TOKENS,
+            headers: Vec::new(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_max_output_tokens(mut self, max_output_tokens: usize) -> Self {
+        self.max_output_tokens = max_output_tokens;
+        self
+    }
+
+    /// Extra header on every request, such as
+    /// `anthropic-dangerous-direct-browser-access` for calls from a browser
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    fn request_body(&self, messages: &[Message], tools: &[ToolSpec]) -> Value {
+        let system: Vec<&str> = messages
+            .iter()
+            .filter(|message| message.role == Role::System)
+            .filter_map(message_text)
+            .collect();
+        let messages: Vec<Value> = messages
+            .iter()
+            .filter(|message| message.role != Role::System)
+            .map(convert_message)
+            .collect();
+
+        let mut body = json!({
+            "model": self.model,
+            "max_tokens": self.max_output_tokens,
+            "messages": messages,
+        });
+        if !system.is_empty() {
+            body["system"] = json!(system.join("\n\n"));
+        }
+        if !tools.is_empty() {
+            body["tools"] = json!(tools);
+        }
+        body
+    }
+}
+
+fn message_text(message: &Message) -> Option<&str> {
+    match &message.content {
+        MessageContent::Text(text) => Some(text),
+        _ => None,
+    }
+}
+
+fn convert_message(message: &Message) -> Value {
+    let role = match message.role {
+        Role::Assistant => "assistant",
+        Role::User | Role::Tool | Role::System => "user",
+    };
+    let content = match (&message.content, &message.tool_call_id) {
+        (MessageContent::Text(text), Some(id)) if message.role == Role::Tool => {
+            json!([ContentBlock::tool_result(id, text)])
+        }
+        (MessageContent::Text(text), _) => json!(text),
+        (MessageContent::Blocks(blocks), _) => json!(blocks),
+        (MessageContent::ToolCalls(calls), _) => json!(calls
+            .iter()
+            .map(|call| ContentBlock::tool_use(&call.id, &call.name, call.input.clone()))
+            .collect::<Vec<_>>()),
+    };
+    json!({ "role": role, "content": content })
+}
+
+fn convert_response(response: WireResponse) -> ModelResponse {
+    let stop_reason = response.stop_reason.map(|reason| match reason.as_str() {
+        "max_tokens" => StopReason::MaxTokens,
+        "stop_sequence" => StopReason::StopSequence,
+        "tool_use" => StopReason::ToolUse,
+        "refusal" => StopReason::ContentFilter,
+        _ => StopReason::EndTurn,
+    });
+    ModelResponse {
+        id: response.id,
+        model: response.model,
+        content: response.content,
+        stop_reason,
+        usage: TokenUsage {
+            input_tokens: response.usage.input_tokens,
+            output_tokens: response.usage.output_tokens,
+            cache_read_tokens: response.usage.cache_read_input_tokens.unwrap_or(0),
+            cache_creation_tokens: response.usage.cache_creation_input_tokens.unwrap_or(0),
+        },
+    }
+}
+
+/// The `error.message` of an API error body, or the raw body
+fn error_message(body: &str) -> String {
+    serde_json::from_str::<Value>(body)
+        .ok()
+        .and_then(|value| value["error"]["message"].as_str().map(str::to_string))
+        .unwrap_or_else(|| body.to_string())
+}
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl<T: Transport> Model for AnthropicModel<T> {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        tools: &[ToolSpec],
+    ) -> Result<ModelResponse, CoreError> {
+        let mut request = HttpRequest::post_json(
+            format!("{}/v1/messages", self.base_url),
+            &self.request_body(messages, tools),
+        )
+        .header("x-api-key", &self.api_key)
+        .header("anthropic-version", ANTHROPIC_VERSION);
+        for (name, value) in &self.headers {
+            request = request.header(name, value);
+        }
+
+        let response = self.transport.send(request).await?;
+        if !response.is_success() {
+            return Err(CoreError::Api {
+                status: response.status,
+                message: error_message(&response.text()),
+            });
+        }
+        let wire: WireResponse = serde_json::from_slice(&response.body)
+            .map_err(|e| CoreError::InvalidResponse(e.to_string()))?;
+        Ok(convert_response(wire))
+    }
+
+    fn max_tokens(&self) -> usize {
+        CONTEXT_WINDOW
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{HttpResponse, TransportError};
+    use futures::executor::block_on;
+    use std::sync::Mutex;
+
+    struct FakeTransport {
+        response: HttpResponse,
+        sent: Mutex<Vec<HttpRequest>>,
+    }
+
+    impl FakeTransport {
+        fn new(status: u16, body: Value) -> Self {
+            Self {
+                response: HttpResponse {
+                    status,
+                    headers: Vec::new(),
+                    body: body.to_string().into_bytes(),
+                },
+                sent: Mutex::new(Vec::new()),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Transport for &FakeTransport {
+        async fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+            self.sent.lock().unwrap().push(request);
+            Ok(self.response.clone())
+        }
+    }
+
+    #[test]
+    fn test_builds_request_and_parses_tool_use() {
+        let transport = FakeTransport::new(
+            200,
+            json!({
+                "id": "msg_1",
+                "model": "claude-test",
+                "content": [
+                    {"type": "text", "text": "Reading it"},
+                    {"type": "tool_use", "id": "tu_1", "name": "read_file", "input": {"path": "a.rs"}}
+                ],
+                "stop_reason": "tool_use",
+                "usage": {"input_tokens": 12, "output_tokens": 7, "cache_read_input_tokens": 3}
+            }),
+        );
+        let model = AnthropicModel::new(&transport, "sk-test", "claude-test")
+            .with_base_url("https://proxy.example.com/")
+            .with_header("anthropic-dangerous-direct-browser-access", "true");
+        let messages = vec![
+            Message::system("Be brief"),
+            Message::user("Open a.rs"),
+            Message::tool_result("tu_0", "earlier output"),
+        ];
+
+        let response = block_on(model.complete(&messages, &[])).unwrap();
+        assert_eq!(response.stop_reason, Some(StopReason::ToolUse));
+        assert_eq!(response.tool_calls()[0].name, "read_file");
+        assert_eq!(response.usage.cache_read_tokens, 3);
+
+        let sent = transport.sent.lock().unwrap();
+        assert_eq!(sent[0].url, "https://proxy.example.com/v1/messages");
+        assert!(sent[0]
+            .headers
+            .contains(&("x-api-key".to_string(), "sk-test".to_string())));
+        assert_eq!(sent[0].headers.len(), 4);
+        let body: Value = serde_json::from_slice(sent[0].body.as_ref().unwrap()).unwrap();
+        assert_eq!(body["system"], "Be brief");
+        assert_eq!(body["messages"].as_array().unwrap().len(), 2);
+        assert_eq!(body["messages"][1]["content"][0]["type"], "tool_result");
+        assert_eq!(body["messages"][1]["content"][0]["tool_use_id"], "tu_0");
+        assert!(body.get("tools").is_none());
+    }
+
+    #[test]
+    fn test_reports_api_errors() {
+        let transport = FakeTransport::new(
+            401,
+            json!({"type": "error", "error": {"type": "authentication_error", "message": "invalid x-api-key"}}),
+        );
+        let model = AnthropicModel::new(&transport, "bad", "claude-test");
+        match block_on(model.complete(&[Message::user("hi")], &[])) {
+            Err(CoreError::Api { status, message }) => {
+                assert_eq!(status, 401);
+                assert_eq!(message, "invalid x-api-key");
+            }
+            other => panic!("unexpected result: {:?}", other.map(|r| r.id)),
+        }
+    }
+}
@@ -0,0 +1,39 @@
+//! Context error types
+
+use thiserror::Error;
+use uira_core::{ErrorKind, UiraError};
+
+#[derive(Debug, Error)]
+pub enum ContextError {
+    #[error("context window exceeded: {used} tokens used, {limit} limit")]
+    ContextExceeded { used: u64, limit: u64 },
+
+    #[error("compaction failed: {0}")]
+    CompactionFailed(String),
+
+    #[error("invalid message: {0}")]
+    InvalidMessage(String),
+
+    #[error("history empty")]
+    HistoryEmpty,
+}
+
+impl From<&ContextError> for UiraError {
+    fn from(error: &ContextError) -> Self {
+        let message = error.to_string();
+        match error {
+            ContextError::ContextExceeded { .. } => {
+                UiraError::new(ErrorKind::Budget, "context.exceeded", message).with_remediation(
+                    "Start a fresh session with /new, or /fork from an earlier message",
+                )
+            }
+            ContextError::CompactionFailed(_) => {
+                UiraError::internal("context.compaction_failed", message).with_retryable(true)
+            }
+            ContextError::InvalidMessage(_) => {
+                UiraError::internal("context.invalid_message", message)
+            }
+            ContextError::HistoryEmpty => UiraError::internal("context.history_empty", message),
+        }
+    }
+}
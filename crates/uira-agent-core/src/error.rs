@@ -0,0 +1,23 @@
+//! Error types for runtime-free turns
+
+use thiserror::Error;
+
+use crate::{ContextError, TransportError};
+
+#[derive(Debug, Error)]
+pub enum CoreError {
+    #[error("transport error: {0}")]
+    Transport(#[from] TransportError),
+
+    #[error("model API error ({status}): {message}")]
+    Api { status: u16, message: String },
+
+    #[error("invalid model response: {0}")]
+    InvalidResponse(String),
+
+    #[error("context error: {0}")]
+    Context(#[from] ContextError),
+
+    #[error("max turns exceeded: {turns}")]
+    MaxTurnsExceeded { turns: usize },
+}
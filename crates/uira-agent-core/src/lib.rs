@@ -0,0 +1,28 @@
+//! Uira Agent Core - Runtime-free turn logic
+//!
+//! The parts of the agent loop that need no filesystem, processes or async
+//! runtime, so the same turns run natively and on wasm32 (a web playground,
+//! edge workers):
+//! - Context window management
+//! - Turn stepping over a [`Model`] and a [`ToolHost`]
+//! - A [`Transport`] the host implements with its own HTTP client
+//! - An Anthropic Messages API model over any transport
+//!
+//! The native agent in `uira-agent` builds on the same context manager and
+//! turn steps, adding tools, approvals, sessions and streaming.
+
+pub mod anthropic;
+pub mod context;
+mod error;
+pub mod model;
+pub mod transport;
+pub mod turn;
+
+pub use anthropic::AnthropicModel;
+pub use context::*;
+pub use error::CoreError;
+pub use model::{Model, NoTools, ToolHost};
+pub use transport::{HttpRequest, HttpResponse, MaybeSend, MaybeSync, Transport, TransportError};
+pub use turn::{
+    assistant_message, next_step, tool_results_message, TurnLoop, TurnOutcome, TurnStep,
+};
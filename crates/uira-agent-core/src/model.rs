@@ -0,0 +1,48 @@
+//! The model and tools a turn runs against
+
+use async_trait::async_trait;
+use uira_core::{Message, ModelResponse, ToolCall, ToolSpec};
+
+use crate::{CoreError, MaybeSend, MaybeSync};
+
+/// A model that answers complete requests
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Model: MaybeSend + MaybeSync {
+    async fn complete(
+        &self,
+        messages: &[Message],
+        tools: &[ToolSpec],
+    ) -> Result<ModelResponse, CoreError>;
+
+    /// Context window size in tokens
+    fn max_tokens(&self) -> usize;
+
+    fn model(&self) -> &str;
+}
+
+/// Tools offered to the model, executed by the host
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait ToolHost: MaybeSend + MaybeSync {
+    fn specs(&self) -> Vec<ToolSpec>;
+
+    /// Run a tool call; `Err` is reported to the model as a failed tool result
+    async fn call(&self, call: &ToolCall) -> Result<String, String>;
+}
+
+/// A host without tools, for plain chat
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoTools;
+
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+impl ToolHost for NoTools {
+    fn specs(&self) -> Vec<ToolSpec> {
+        Vec::new()
+    }
+
+    async fn call(&self, call: &ToolCall) -> Result<String, String> {
+        Err(format!("tool not found: {}", call.name))
+    }
+}
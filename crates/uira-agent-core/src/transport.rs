@@ -0,0 +1,117 @@
+//! HTTP transport supplied by the host
+//!
+//! Models in this crate build requests and parse responses; sending them is
+//! left to a [`Transport`]: reqwest natively (`uira_providers::ReqwestTransport`),
+//! `fetch` in a browser or worker.
+
+use async_trait::async_trait;
+use thiserror::Error;
+
+/// `Send` on native targets; nothing on wasm32, where JS futures aren't `Send`
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSend: Send {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Send + ?Sized> MaybeSend for T {}
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSend {}
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSend for T {}
+
+/// `Sync` on native targets; nothing on wasm32
+#[cfg(not(target_arch = "wasm32"))]
+pub trait MaybeSync: Sync {}
+#[cfg(not(target_arch = "wasm32"))]
+impl<T: Sync + ?Sized> MaybeSync for T {}
+#[cfg(target_arch = "wasm32")]
+pub trait MaybeSync {}
+#[cfg(target_arch = "wasm32")]
+impl<T: ?Sized> MaybeSync for T {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpRequest {
+    pub method: String,
+    pub url: String,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<Vec<u8>>,
+}
+
+impl HttpRequest {
+    /// A POST request with a JSON body
+    pub fn post_json(url: impl Into<String>, body: &serde_json::Value) -> Self {
+        Self {
+            method: "POST".to_string(),
+            url: url.into(),
+            headers: vec![("content-type".to_string(), "application/json".to_string())],
+            body: Some(body.to_string().into_bytes()),
+        }
+    }
+
+    pub fn header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HttpResponse {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl HttpResponse {
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// First header with this name, compared case-insensitively
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn text(&self) -> String {
+        String::from_utf8_lossy(&self.body).into_owned()
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum TransportError {
+    #[error("request failed: {0}")]
+    Request(String),
+
+    #[error("request timed out")]
+    Timeout,
+}
+
+/// Sends complete HTTP requests and returns complete responses
+#[cfg_attr(not(target_arch = "wasm32"), async_trait)]
+#[cfg_attr(target_arch = "wasm32", async_trait(?Send))]
+pub trait Transport: MaybeSend + MaybeSync {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_request_and_response_headers() {
+        let request = HttpRequest::post_json("https://example.com", &serde_json::json!({"a": 1}))
+            .header("x-api-key", "key");
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.body.as_deref(), Some(br#"{"a":1}"#.as_slice()));
+        assert_eq!(request.headers.len(), 2);
+
+        let response = HttpResponse {
+            status: 429,
+            headers: vec![("Retry-After".to_string(), "3".to_string())],
+            body: b"slow down".to_vec(),
+        };
+        assert!(!response.is_success());
+        assert_eq!(response.header("retry-after"), Some("3"));
+        assert_eq!(response.text(), "slow down");
+    }
+}
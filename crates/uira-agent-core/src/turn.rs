@@ -0,0 +1,226 @@
+//! Turn stepping shared by the native agent and runtime-free hosts
+
+use uira_core::{ContentBlock, Message, ModelResponse, Role, TokenUsage, ToolCall};
+
+use crate::{ContextManager, CoreError, Model, ToolHost};
+
+const DEFAULT_MAX_TURNS: usize = 100;
+
+/// What a model response asks for next
+#[derive(Debug, Clone)]
+pub enum TurnStep {
+    /// Run these tools and send their results back
+    ExecuteTools(Vec<ToolCall>),
+    /// The model is done; its final text
+    Finish(String),
+}
+
+pub fn next_step(response: &ModelResponse) -> TurnStep {
+    if response.has_tool_calls() {
+        TurnStep::ExecuteTools(response.tool_calls())
+    } else {
+        TurnStep::Finish(response.text())
+    }
+}
+
+/// The assistant message recording a model response in the context
+pub fn assistant_message(response: &ModelResponse) -> Message {
+    Message::with_blocks(Role::Assistant, response.content.clone())
+}
+
+/// The user message carrying tool results back to the model
+pub fn tool_results_message(results: Vec<ContentBlock>) -> Message {
+    Message::with_blocks(Role::User, results)
+}
+
+#[derive(Debug, Clone)]
+pub struct TurnOutcome {
+    pub output: String,
+    pub turns: usize,
+    pub usage: TokenUsage,
+}
+
+/// Runs turns against a model until it stops calling tools
+pub struct TurnLoop<'a> {
+    model: &'a dyn Model,
+    tools: &'a dyn ToolHost,
+    max_turns: usize,
+}
+
+impl<'a> TurnLoop<'a> {
+    pub fn new(model: &'a dyn Model, tools: &'a dyn ToolHost) -> Self {
+        Self {
+            model,
+            tools,
+            max_turns: DEFAULT_MAX_TURNS,
+        }
+    }
+
+    pub fn with_max_turns(mut self, max_turns: usize) -> Self {
+        self.max_turns = max_turns;
+        self
+    }
+
+    /// Add `prompt` to the context and run turns until the model finishes
+    pub async fn run(
+        &self,
+        context: &mut ContextManager,
+        prompt: &str,
+    ) -> Result<TurnOutcome, CoreError> {
+        context.add_message(Message::user(prompt))?;
+        let specs = self.tools.specs();
+        let mut usage = TokenUsage::default();
+
+        for turn in 1..=self.max_turns {
+            let response = self.model.complete(context.messages(), &specs).await?;
+            usage += response.usage.clone();
+            context.record_usage(response.usage.clone());
+            context.add_message(assistant_message(&response))?;
+
+            match next_step(&response) {
+                TurnStep::ExecuteTools(calls) => {
+                    let mut results = Vec::with_capacity(calls.len());
+                    for call in &calls {
+                        results.push(match self.tools.call(call).await {
+                            Ok(output) => ContentBlock::tool_result(&call.id, output),
+                            Err(error) => ContentBlock::tool_error(&call.id, error),
+                        });
+                    }
+                    context.add_message(tool_results_message(results))?;
+                }
+                TurnStep::Finish(output) => {
+                    return Ok(TurnOutcome {
+                        output,
+                        turns: turn,
+                        usage,
+                    });
+                }
+            }
+        }
+
+        Err(CoreError::MaxTurnsExceeded {
+            turns: self.max_turns,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::NoTools;
+    use async_trait::async_trait;
+    use futures::executor::block_on;
+    use std::sync::Mutex;
+    use uira_core::{StopReason, ToolSpec};
+
+    struct ScriptedModel {
+        responses: Mutex<Vec<Vec<ContentBlock>>>,
+    }
+
+    impl ScriptedModel {
+        fn new(mut responses: Vec<Vec<ContentBlock>>) -> Self {
+            responses.reverse();
+            Self {
+                responses: Mutex::new(responses),
+            }
+        }
+    }
+
+    #[async_trait]
+    impl Model for ScriptedModel {
+        async fn complete(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolSpec],
+        ) -> Result<ModelResponse, CoreError> {
+            let content = self.responses.lock().unwrap().pop().expect("scripted");
+            Ok(ModelResponse {
+                id: "msg".to_string(),
+                model: "scripted".to_string(),
+                content,
+                stop_reason: Some(StopReason::EndTurn),
+                usage: TokenUsage {
+                    input_tokens: 10,
+                    output_tokens: 5,
+                    ..TokenUsage::default()
+                },
+            })
+        }
+
+        fn max_tokens(&self) -> usize {
+            100_000
+        }
+
+        fn model(&self) -> &str {
+            "scripted"
+        }
+    }
+
+    struct Echo;
+
+    #[async_trait]
+    impl ToolHost for Echo {
+        fn specs(&self) -> Vec<ToolSpec> {
+            vec![ToolSpec::new(
+                "echo",
+                "Echo the input",
+                uira_core::JsonSchema::object(),
+            )]
+        }
+
+        async fn call(&self, call: &ToolCall) -> Result<String, String> {
+            Ok(call.input.to_string())
+        }
+    }
+
+    #[test]
+    fn test_runs_tools_until_final_text() {
+        let model = ScriptedModel::new(vec![
+            vec![ContentBlock::tool_use(
+                "t1",
+                "echo",
+                serde_json::json!({"text": "hi"}),
+            )],
+            vec![ContentBlock::text("done")],
+        ]);
+        let mut context = ContextManager::new(model.max_tokens());
+
+        let outcome = block_on(TurnLoop::new(&model, &Echo).run(&mut context, "go")).unwrap();
+        assert_eq!(outcome.output, "done");
+        assert_eq!(outcome.turns, 2);
+        assert_eq!(outcome.usage.output_tokens, 10);
+
+        // prompt, tool call, tool result, final answer
+        let messages = context.messages();
+        assert_eq!(messages.len(), 4);
+        match &messages[2].content {
+            uira_core::MessageContent::Blocks(blocks) => assert!(matches!(
+                &blocks[0],
+                ContentBlock::ToolResult { content, is_error: false, .. } if content == r#"{"text":"hi"}"#
+            )),
+            other => panic!("unexpected content: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_stops_at_max_turns_and_reports_missing_tools() {
+        let call = || vec![ContentBlock::tool_use("t", "echo", serde_json::json!({}))];
+        let model = ScriptedModel::new(vec![call(), call()]);
+        let mut context = ContextManager::new(model.max_tokens());
+
+        let result = block_on(
+            TurnLoop::new(&model, &NoTools)
+                .with_max_turns(2)
+                .run(&mut context, "go"),
+        );
+        assert!(matches!(
+            result,
+            Err(CoreError::MaxTurnsExceeded { turns: 2 })
+        ));
+        assert!(matches!(
+            &context.messages()[2].content,
+            uira_core::MessageContent::Blocks(blocks)
+                if matches!(&blocks[0], ContentBlock::ToolResult { is_error: true, .. })
+        ));
+    }
+}
@@ -1,27 +1,41 @@
 //! Main agent implementation
 
-use crate::telemetry::{SessionSpan, TurnSpan};
+use crate::telemetry::{SessionSpan, TurnSpan, TurnStats};
 use chrono::{DateTime, Utc};
 use futures::StreamExt;
-use std::collections::{HashMap, VecDeque};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::process::Command;
-use std::sync::atomic::Ordering;
 use std::sync::Arc;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tokio::time::timeout;
+use tokio_util::sync::CancellationToken;
+use uira_agent_core::turn::{self, TurnStep};
 use uira_core::{
-    AgentError, AgentState, ApprovalRequirement, ContentBlock, ExecutionResult, Item, Message,
-    MessageContent, Role, SessionId, ThreadEvent, ToolCall,
+    AgentError, AgentState, ApprovalRequirement, CommandExplanationEvent, ContentBlock,
+    ExecutionResult, Item, Message, MessageContent, Role, SessionId, ThreadEvent, ToolCall,
+    ToolOutput, ToolOutputContent, UiraError,
 };
 use uira_core::{Event, EventBus, SessionEndReason};
+use uira_orchestration::features::consensus::{judge_prompt, parse_verdict, task_text, Candidate};
+use uira_orchestration::hooks::hooks::auto_slash_command::{
+    expand_slash_command, SlashCommandExpansion,
+};
+use uira_orchestration::hooks::hooks::autopilot::{AutopilotHook, AutopilotState, AutopilotStep};
 use uira_orchestration::hooks::hooks::keyword_detector::KeywordDetectorHook;
-use uira_providers::ModelClient;
+use uira_orchestration::hooks::hooks::notepad::NotepadHook;
+use uira_orchestration::hooks::hooks::think_mode::{ThinkLevel, ThinkModeHook};
+use uira_orchestration::model_routing::escalation_reason;
+use uira_orchestration::tools::command_explainer::{self, CommandExplanation};
+use uira_orchestration::ToolClass;
+use uira_orchestration::{EditTool, ToolContext, ToolError, WorkspaceIndex};
+use uira_providers::{count_retries, payload_log, ModelClient, ReasoningOverride};
 
 use crate::{
     approval::{approval_channel, ApprovalReceiver, ApprovalSender},
     events::{EventSender, EventStream},
+    privacy::PiiScrubber,
     session::{extract_messages, get_last_turn, get_total_usage, SessionMetaLine, SessionRecorder},
     streaming::StreamController,
     AgentCommand, AgentConfig, AgentControl, AgentLoopError, BranchInfo, CommandReceiver,
@@ -30,6 +44,70 @@ use crate::{
 
 /// Timeout for approval requests (5 minutes)
 const APPROVAL_TIMEOUT: Duration = Duration::from_secs(300);
+/// How long an approval prompt waits on the explaining model before falling
+/// back to the local explanation
+const EXPLAIN_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Build a copy of `client` with the provider-native reasoning controls for
+/// `level`, or None when the provider or model has no such controls
+fn think_client(client: &Arc<dyn ModelClient>, level: ThinkLevel) -> Option<Arc<dyn ModelClient>> {
+    let thinking =
+        ThinkModeHook::get_thinking_config_for_level(client.provider(), client.model(), level)?;
+    client.with_reasoning(&ReasoningOverride {
+        budget_tokens: thinking.budget_tokens(),
+        effort: thinking.reasoning_effort().map(str::to_string),
+        max_tokens: thinking.max_tokens().map(|tokens| tokens as usize),
+    })
+}
+
+/// Text content of a user message, ignoring non-text blocks
+fn message_text(message: &Message) -> Option<String> {
+    match &message.content {
+        MessageContent::Text(text) => Some(text.clone()),
+        MessageContent::Blocks(blocks) => {
+            let texts: Vec<String> = blocks
+                .iter()
+                .filter_map(|block| {
+                    if let ContentBlock::Text { text } = block {
+                        Some(text.clone())
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+            if texts.is_empty() {
+                None
+            } else {
+                Some(texts.join("\n"))
+            }
+        }
+        MessageContent::ToolCalls(_) => None,
+    }
+}
+
+/// Put `prefix` ahead of a user message's text
+fn prefix_message(message: Message, prefix: &str) -> Message {
+    match message.content {
+        MessageContent::Text(original_text) => Message {
+            role: message.role,
+            content: MessageContent::Text(format!("{}\n\n{}", prefix, original_text)),
+            name: message.name,
+            tool_call_id: message.tool_call_id,
+        },
+        MessageContent::Blocks(blocks) => {
+            let mut new_blocks = Vec::with_capacity(blocks.len() + 1);
+            new_blocks.push(ContentBlock::text(format!("{}\n\n", prefix)));
+            new_blocks.extend(blocks);
+            Message {
+                role: message.role,
+                content: MessageContent::Blocks(new_blocks),
+                name: message.name,
+                tool_call_id: message.tool_call_id,
+            }
+        }
+        MessageContent::ToolCalls(_) => message,
+    }
+}
 
 fn get_git_branch() -> String {
     Command::new("git")
@@ -76,6 +154,29 @@ pub struct Agent {
     max_continuations: usize,
     /// Number of continuation injections in the current run
     continuation_count: usize,
+    /// Timings collected for the turn in progress
+    turn_stats: Option<TurnStats>,
+    /// PII scrubber applied to provider requests and the session log
+    privacy: Option<PiiScrubber>,
+    /// Higher-tier client a speculatively routed run escalates to when its
+    /// first response looks inadequate
+    speculative_fallback: Option<Arc<dyn ModelClient>>,
+    /// Panel and judge for the `consensus` execution strategy
+    consensus: Option<ConsensusClients>,
+    /// Low-tier client that explains risky commands in approval prompts
+    command_explainer: Option<Arc<dyn ModelClient>>,
+    /// Tools whose last call failed schema validation and was already sent
+    /// back for repair
+    schema_repairs: HashSet<String>,
+    /// Whether this run's final message already broke the output contract
+    /// once and was sent back for repair
+    contract_repaired: bool,
+}
+
+/// Clients a consensus run queries besides the session's own client
+struct ConsensusClients {
+    panel: Vec<Arc<dyn ModelClient>>,
+    judge: Arc<dyn ModelClient>,
 }
 
 impl Agent {
@@ -88,8 +189,15 @@ impl Agent {
         client: Arc<dyn ModelClient>,
         executor: Option<Arc<dyn uira_orchestration::AgentExecutor>>,
     ) -> Self {
+        let session = Session::new_with_executor(config, client, executor);
+        let privacy = session
+            .config
+            .privacy
+            .enabled
+            .then(|| PiiScrubber::new(&session.config.privacy, &session.cwd));
+
         Self {
-            session: Session::new_with_executor(config, client, executor),
+            session,
             branches: HashMap::new(),
             current_branch: get_git_branch(),
             current_branch_parent: None,
@@ -108,6 +216,13 @@ impl Agent {
             continuation_enabled: true,
             max_continuations: 3,
             continuation_count: 0,
+            turn_stats: None,
+            privacy,
+            speculative_fallback: None,
+            consensus: None,
+            command_explainer: None,
+            schema_repairs: HashSet::new(),
+            contract_repaired: false,
         }
     }
 
@@ -118,6 +233,37 @@ impl Agent {
         (self, stream)
     }
 
+    /// Run the first turn speculatively: if the first response trips the
+    /// escalation heuristics, switch to `fallback` and replay the request
+    pub fn with_speculative_fallback(mut self, fallback: Arc<dyn ModelClient>) -> Self {
+        self.speculative_fallback = Some(fallback);
+        self
+    }
+
+    /// Have `panel` answer final responses too, replacing them with the
+    /// `judge`'s reconciliation
+    pub fn with_consensus(
+        mut self,
+        panel: Vec<Arc<dyn ModelClient>>,
+        judge: Arc<dyn ModelClient>,
+    ) -> Self {
+        self.consensus = Some(ConsensusClients { panel, judge });
+        self
+    }
+
+    /// Ask `explainer` to explain risky commands before they are put to the
+    /// user, instead of explaining them locally
+    pub fn with_command_explainer(mut self, explainer: Arc<dyn ModelClient>) -> Self {
+        self.command_explainer = Some(explainer);
+        self
+    }
+
+    /// Cancel along with `parent`, for a subagent working on its behalf
+    pub fn with_parent_cancellation(mut self, parent: &CancellationToken) -> Self {
+        self.control = std::mem::take(&mut self.control).with_parent(parent);
+        self
+    }
+
     /// Set event sender directly (for child agents sharing parent's channel)
     pub fn with_event_sender(mut self, sender: EventSender) -> Self {
         self.event_sender = Some(sender);
@@ -126,13 +272,21 @@ impl Agent {
 
     /// Enable session recording for session persistence
     pub fn with_session_recording(mut self) -> Result<Self, AgentLoopError> {
-        let meta = SessionMetaLine::new(
+        let mut meta = SessionMetaLine::new(
             self.session.id.to_string(),
             self.session.client.model(),
             self.session.client.provider(),
             self.session.cwd.clone(),
             format!("{:?}", self.session.config.sandbox_policy),
         );
+        meta.agent = self.session.config.agent.clone().or_else(|| {
+            self.session
+                .config
+                .delegation
+                .as_ref()
+                .map(|delegation| delegation.agent.clone())
+        });
+        meta.experiments = self.session.config.experiments.clone();
 
         let recorder = SessionRecorder::new(meta).map_err(|e| AgentLoopError::Io(e.to_string()))?;
 
@@ -206,6 +360,12 @@ impl Agent {
                 let result = self.handle_switch_branch(branch_name);
                 let _ = response_tx.send(result);
             }
+            AgentCommand::MergeNotes {
+                branch_name,
+                response_tx,
+            } => {
+                let _ = response_tx.send(self.handle_merge_notes(branch_name));
+            }
             AgentCommand::ListBranches { response_tx } => {
                 let _ = response_tx.send(Ok(self.list_branches()));
             }
@@ -226,6 +386,26 @@ impl Agent {
         }
     }
 
+    /// Start the shared workspace index watcher for this session's root.
+    /// Delegated sessions query the index their parent started.
+    fn start_workspace_index(&self) {
+        let Some(settings) = self.session.config.workspace_index.clone() else {
+            return;
+        };
+        if !settings.enabled || self.session.config.delegation.is_some() {
+            return;
+        }
+        let embedder = self
+            .session
+            .memory_system
+            .as_ref()
+            .filter(|_| settings.embeddings)
+            .map(|memory| memory.embedder.clone());
+        let index = WorkspaceIndex::shared(&self.session.cwd);
+        index.configure(settings, embedder);
+        index.start_watching();
+    }
+
     pub async fn run_interactive(&mut self) -> Result<(), AgentLoopError> {
         let mut input_rx = self
             .input_rx
@@ -234,7 +414,9 @@ impl Agent {
 
         let mut command_rx = self.command_rx.take();
         let mut deferred_commands = VecDeque::new();
+        let session_cancel = self.control.cancel_signal().session_token();
 
+        self.start_workspace_index();
         self.state = AgentState::WaitingForUser;
         self.emit_event(ThreadEvent::WaitingForInput {
             prompt: "Ready for input...".to_string(),
@@ -265,6 +447,10 @@ impl Agent {
                     }
                     continue;
                 }
+                _ = session_cancel.cancelled() => {
+                    tracing::info!("Session shut down, exiting interactive mode");
+                    break;
+                }
                 input = input_rx.recv() => {
                     match input {
                         Some(message) => message,
@@ -316,7 +502,7 @@ impl Agent {
                         if let Some(command) = deferred_commands.pop_front() {
                             match command {
                                 AgentCommand::Interrupt => {
-                                    cancel_signal.store(true, Ordering::SeqCst);
+                                    cancel_signal.cancel();
                                 }
                                 other => pending_commands.push_back(other),
                             }
@@ -333,7 +519,7 @@ impl Agent {
                             } => {
                                 match command {
                                     Some(AgentCommand::Interrupt) => {
-                                        cancel_signal.store(true, Ordering::SeqCst);
+                                        cancel_signal.cancel();
                                     }
                                     Some(other) => pending_commands.push_back(other),
                                     None => {
@@ -367,12 +553,21 @@ impl Agent {
                         self.emit_event(ThreadEvent::Error {
                             message: e.to_string(),
                             recoverable: true,
+                            detail: Some(UiraError::from(&e)),
                         })
                         .await;
                         (true, false)
                     }
                 };
 
+                // Autopilot: keep working the current phase or start the next one
+                if !was_error && !was_cancel {
+                    if let Some(prompt) = self.advance_autopilot().await {
+                        current_input = InteractiveInput::Prompt(prompt);
+                        continue;
+                    }
+                }
+
                 // Check todo continuation: if incomplete todos remain, auto-inject prompt
                 let should_continue = self.session.config.todo_continuation
                     && !was_error
@@ -419,6 +614,11 @@ impl Agent {
                 break;
             }
 
+            if session_cancel.is_cancelled() {
+                break;
+            }
+
+            self.sync_session_log();
             self.state = AgentState::WaitingForUser;
             self.emit_event(ThreadEvent::WaitingForInput {
                 prompt: "Ready for input...".to_string(),
@@ -426,18 +626,90 @@ impl Agent {
             .await;
         }
 
+        self.flush_state().await;
         Ok(())
     }
 
+    /// Persist approval decisions, todos and the session log so a restarted
+    /// process can resume where this one stopped
+    pub async fn flush_state(&mut self) {
+        self.session.save_approval_cache().await;
+        let session_id = self.session.id.to_string();
+        if let Err(e) = self.session.todo_store.flush(&session_id).await {
+            tracing::warn!(error = %e, "failed to flush todos");
+        }
+        self.sync_session_log();
+    }
+
+    /// Push buffered session log lines to disk
+    fn sync_session_log(&mut self) {
+        if let Some(ref mut recorder) = self.session_recorder {
+            if let Err(e) = recorder.sync() {
+                tracing::warn!(error = %e, "failed to sync session log");
+            }
+        }
+    }
+
+    /// Run the autopilot state machine over the last response, returning the
+    /// prompt for the next autopilot turn if it should keep going
+    async fn advance_autopilot(&mut self) -> Option<String> {
+        let directory = self.session.cwd.to_string_lossy().to_string();
+        let response = self.last_assistant_text().unwrap_or_default();
+        let session_id = self.session.id.to_string();
+
+        match AutopilotHook::process_response(&directory, Some(&session_id), &response) {
+            AutopilotStep::Idle => None,
+            AutopilotStep::Continue { state } => Some(AutopilotHook::continuation_prompt(&state)),
+            AutopilotStep::Advanced { from, state } => {
+                self.emit_autopilot_phase(from.as_str(), &state, false)
+                    .await;
+                if state.active {
+                    Some(AutopilotHook::get_phase_prompt(&state))
+                } else {
+                    None
+                }
+            }
+            AutopilotStep::AwaitingApproval { state } => {
+                self.emit_autopilot_phase(state.phase.as_str(), &state, true)
+                    .await;
+                None
+            }
+        }
+    }
+
+    async fn emit_autopilot_phase(
+        &self,
+        from: &str,
+        state: &AutopilotState,
+        awaiting_approval: bool,
+    ) {
+        let to = match state.pending_phase {
+            Some(pending) if awaiting_approval => pending,
+            _ => state.phase,
+        };
+        self.emit_event(ThreadEvent::AutopilotPhaseChanged {
+            from: from.to_string(),
+            to: to.as_str().to_string(),
+            iteration: state.iteration,
+            max_iterations: state.max_iterations,
+            awaiting_approval,
+        })
+        .await;
+    }
+
     /// Resume from a session file
     pub fn resume_from_session(
         config: AgentConfig,
         client: Arc<dyn ModelClient>,
         session_path: PathBuf,
     ) -> Result<Self, AgentLoopError> {
-        // Load items from session log
-        let items =
-            SessionRecorder::load(&session_path).map_err(|e| AgentLoopError::Io(e.to_string()))?;
+        // Open for appending first: this recovers a log torn by a crash
+        let recorder =
+            SessionRecorder::open(session_path).map_err(|e| AgentLoopError::Io(e.to_string()))?;
+
+        // Load items from session log (decompressed by `open` if it was gzipped)
+        let items = SessionRecorder::load(recorder.path())
+            .map_err(|e| AgentLoopError::Io(e.to_string()))?;
 
         // Create agent
         let mut agent = Self::new(config, client);
@@ -445,6 +717,10 @@ impl Agent {
         // Restore messages to context
         let messages = extract_messages(&items);
         for msg in messages {
+            let msg = match agent.privacy.as_ref() {
+                Some(scrubber) => scrubber.restore_message(&msg),
+                None => msg,
+            };
             agent
                 .session
                 .context
@@ -456,9 +732,6 @@ impl Agent {
         agent.session.turn = get_last_turn(&items);
         agent.session.usage = get_total_usage(&items);
 
-        // Open session log for appending
-        let recorder =
-            SessionRecorder::open(session_path).map_err(|e| AgentLoopError::Io(e.to_string()))?;
         agent.session_recorder = Some(recorder);
 
         Ok(agent)
@@ -515,11 +788,21 @@ impl Agent {
             return Err(format!("Branch '{}' already exists", branch_name));
         }
 
-        let forked_session = match message_count {
+        let mut forked_session = match message_count {
             Some(count) => self.session.fork_at_message(count),
             None => self.session.fork(),
         };
 
+        // The branch starts from a copy of the parent's notes
+        if !NotepadHook::fork_branch(
+            &self.session.cwd.to_string_lossy(),
+            self.session.notepad_branch.as_deref(),
+            &branch_name,
+        ) {
+            tracing::warn!("Failed to copy notepad to branch '{}'", branch_name);
+        }
+        forked_session.notepad_branch = Some(branch_name.clone());
+
         let forked_session_id = forked_session.id.to_string();
         let parent_branch = self.current_branch.clone();
         let parent_session_id = self.session.id.to_string();
@@ -609,6 +892,36 @@ impl Agent {
         })
     }
 
+    /// Fold another branch's notepad into the current branch's notepad
+    fn handle_merge_notes(&mut self, branch_name: String) -> Result<String, String> {
+        let branch_name = branch_name.trim().to_string();
+        if branch_name == self.current_branch {
+            return Err("Cannot merge a branch's notes into itself".to_string());
+        }
+
+        let source = self
+            .branches
+            .get(&branch_name)
+            .ok_or_else(|| format!("Branch '{}' not found", branch_name))?
+            .session
+            .notepad_branch
+            .clone();
+
+        if !NotepadHook::merge_branch(
+            &self.session.cwd.to_string_lossy(),
+            source.as_deref(),
+            self.session.notepad_branch.as_deref(),
+        ) {
+            return Err("Failed to write merged notepad".to_string());
+        }
+        self.session.notepad_reinject = true;
+
+        Ok(format!(
+            "Merged notes from '{}' into '{}'",
+            branch_name, self.current_branch
+        ))
+    }
+
     fn list_branches(&self) -> Vec<BranchInfo> {
         let mut infos = Vec::with_capacity(self.branches.len() + 1);
         infos.push(BranchInfo {
@@ -771,7 +1084,15 @@ impl Agent {
         })
         .await;
 
-        let effective_message = self.apply_keyword_detection_to_message(message).await;
+        let think_level =
+            message_text(&message).and_then(|text| ThinkModeHook::detect_think_level(&text));
+        let mut effective_message = self.apply_keyword_detection_to_message(message).await;
+        if let Some(notepad_context) = self.take_notepad_context() {
+            effective_message = prefix_message(effective_message, &notepad_context);
+        }
+        if let Some(stale_context) = self.take_stale_file_context() {
+            effective_message = prefix_message(effective_message, &stale_context);
+        }
 
         self.record_message(effective_message.clone());
         self.session
@@ -779,33 +1100,11 @@ impl Agent {
             .add_message(effective_message)
             .map_err(AgentLoopError::Context)?;
 
-        self.run_turn_loop().await
+        self.run_turn_loop_at(think_level).await
     }
 
     async fn apply_keyword_detection_to_message(&mut self, message: Message) -> Message {
-        let text_content = match &message.content {
-            MessageContent::Text(text) => Some(text.clone()),
-            MessageContent::Blocks(blocks) => {
-                let texts: Vec<String> = blocks
-                    .iter()
-                    .filter_map(|block| {
-                        if let ContentBlock::Text { text } = block {
-                            Some(text.clone())
-                        } else {
-                            None
-                        }
-                    })
-                    .collect();
-                if texts.is_empty() {
-                    None
-                } else {
-                    Some(texts.join("\n"))
-                }
-            }
-            MessageContent::ToolCalls(_) => None,
-        };
-
-        let Some(text) = text_content else {
+        let Some(text) = message_text(&message) else {
             return message;
         };
 
@@ -818,29 +1117,72 @@ impl Agent {
         })
         .await;
 
-        match message.content {
-            MessageContent::Text(original_text) => Message {
-                role: message.role,
-                content: MessageContent::Text(format!("{}\n\n{}", keyword_msg, original_text)),
-                name: message.name,
-                tool_call_id: message.tool_call_id,
-            },
-            MessageContent::Blocks(blocks) => {
-                let mut new_blocks = Vec::with_capacity(blocks.len() + 1);
-                new_blocks.push(ContentBlock::text(format!("{}\n\n", keyword_msg)));
-                new_blocks.extend(blocks);
-                Message {
-                    role: message.role,
-                    content: MessageContent::Blocks(new_blocks),
-                    name: message.name,
-                    tool_call_id: message.tool_call_id,
-                }
-            }
-            MessageContent::ToolCalls(_) => message,
+        prefix_message(message, keyword_msg)
+    }
+
+    /// Priority notes for the current branch, if they are due for re-injection
+    fn take_notepad_context(&mut self) -> Option<String> {
+        if !std::mem::take(&mut self.session.notepad_reinject) {
+            return None;
+        }
+        NotepadHook::format_branch_notepad_context(
+            &self.session.cwd.to_string_lossy(),
+            self.session.notepad_branch.as_deref(),
+        )
+    }
+
+    /// Warning naming files that changed on disk since the agent read them,
+    /// so it re-reads them instead of editing from a stale view
+    fn take_stale_file_context(&self) -> Option<String> {
+        let changed = self.session.file_tracker.take_changed();
+        if changed.is_empty() {
+            return None;
         }
+        let files: Vec<String> = changed
+            .iter()
+            .map(|path| format!("- {}", path.display()))
+            .collect();
+        Some(format!(
+            "[FILES CHANGED ON DISK]\nThese files changed outside this session since you read them, possibly in the user's editor:\n{}\nRead them again before editing; writes based on the old content will be refused.",
+            files.join("\n")
+        ))
     }
 
     pub async fn run(&mut self, prompt: &str) -> Result<ExecutionResult, AgentLoopError> {
+        let expansion = expand_slash_command(prompt, &self.session.cwd);
+        let invoked_skill = expansion
+            .as_ref()
+            .and_then(|expansion| self.activate_invoked_skill(expansion));
+        let prompt = expansion
+            .as_ref()
+            .map_or(prompt, |expansion| expansion.prompt.as_str());
+
+        let result = self.run_prompt(prompt).await;
+        if let Some(skill) = invoked_skill {
+            self.session.orchestrator.deactivate_skill(&skill);
+        }
+        result
+    }
+
+    /// Apply the restrictions a `/uira:*` command or skill declares, unless
+    /// the session already applies them. Returns the skill to deactivate
+    /// when the run ends.
+    fn activate_invoked_skill(&self, expansion: &SlashCommandExpansion) -> Option<String> {
+        let permissions = expansion.permissions.clone()?;
+        let orchestrator = &self.session.orchestrator;
+        if orchestrator.active_skills().contains(&expansion.command) {
+            return None;
+        }
+        match orchestrator.activate_skill(&expansion.command, permissions) {
+            Ok(()) => Some(expansion.command.clone()),
+            Err(error) => {
+                tracing::warn!(skill = %expansion.command, %error, "skill_activation_failed");
+                None
+            }
+        }
+    }
+
+    async fn run_prompt(&mut self, prompt: &str) -> Result<ExecutionResult, AgentLoopError> {
         self.reset_continuation_state();
         self.state = AgentState::Thinking;
 
@@ -863,6 +1205,14 @@ impl Agent {
             } else {
                 prompt.to_string()
             };
+        let effective_prompt = match self.take_notepad_context() {
+            Some(notepad_context) => format!("{}\n\n{}", notepad_context, effective_prompt),
+            None => effective_prompt,
+        };
+        let effective_prompt = match self.take_stale_file_context() {
+            Some(stale_context) => format!("{}\n\n{}", stale_context, effective_prompt),
+            None => effective_prompt,
+        };
 
         let user_message = Message::user_prompt(&effective_prompt);
         self.record_message(user_message.clone());
@@ -871,7 +1221,29 @@ impl Agent {
             .add_message(user_message)
             .map_err(AgentLoopError::Context)?;
 
-        self.run_turn_loop().await
+        self.run_turn_loop_at(ThinkModeHook::detect_think_level(prompt))
+            .await
+    }
+
+    /// Run the turn loop with the reasoning controls for `level` applied to
+    /// the model client, restoring the original client afterwards
+    async fn run_turn_loop_at(
+        &mut self,
+        level: Option<ThinkLevel>,
+    ) -> Result<ExecutionResult, AgentLoopError> {
+        let Some(client) = level.and_then(|level| think_client(&self.session.client, level)) else {
+            return self.run_turn_loop().await;
+        };
+
+        tracing::debug!(
+            provider = client.provider(),
+            model = client.model(),
+            "Applying think-mode reasoning controls"
+        );
+        let base_client = std::mem::replace(&mut self.session.client, client);
+        let result = self.run_turn_loop().await;
+        self.session.client = base_client;
+        result
     }
 
     async fn run_prompt_owned(
@@ -883,6 +1255,7 @@ impl Agent {
 
     fn reset_continuation_state(&mut self) {
         self.continuation_count = 0;
+        self.contract_repaired = false;
     }
 
     async fn run_turn_loop(&mut self) -> Result<ExecutionResult, AgentLoopError> {
@@ -910,29 +1283,21 @@ impl Agent {
             let turn_number = self.session.start_turn();
             let turn_span = TurnSpan::new(turn_number);
             let _turn_guard = turn_span.enter();
+            self.turn_stats = Some(TurnStats::start(self.session.context.current_tokens()));
 
             self.emit_event(ThreadEvent::TurnStarted { turn_number })
                 .await;
 
             // Get model response (streaming or blocking)
             let tool_specs = self.session.tool_specs();
-            let response = if self.streaming_enabled {
-                self.get_response_streaming(&tool_specs).await?
-            } else {
-                self.session
-                    .client
-                    .chat(self.session.context.messages(), &tool_specs)
-                    .await
-                    .map_err(AgentLoopError::Provider)?
-            };
+            let response = self.get_response(&tool_specs).await?;
 
             // Record usage
             self.session.record_usage(response.usage.clone());
             self.record_turn(turn_number, response.usage.clone());
 
             // Add assistant message to context
-            let assistant_message =
-                Message::with_blocks(uira_core::Role::Assistant, response.content.clone());
+            let assistant_message = turn::assistant_message(&response);
             self.record_message(assistant_message.clone());
             self.session
                 .context
@@ -947,114 +1312,360 @@ impl Agent {
             .await;
 
             // Check if we should continue (tool calls) or stop
-            if response.has_tool_calls() {
-                // Process tool calls
-                self.state = AgentState::ExecutingTool;
-
-                let tool_calls = response.tool_calls();
-                let tool_results = self.execute_tool_calls(&tool_calls).await?;
+            match turn::next_step(&response) {
+                TurnStep::ExecuteTools(tool_calls) => {
+                    // Process tool calls
+                    self.state = AgentState::ExecutingTool;
 
-                // Add tool results to context
-                let tool_result_message = Message::with_blocks(Role::User, tool_results);
-                self.record_message(tool_result_message.clone());
-                self.session
-                    .context
-                    .add_message(tool_result_message)
-                    .map_err(AgentLoopError::Context)?;
+                    let tool_results = self.execute_tool_calls(&tool_calls).await?;
 
-                self.state = AgentState::Thinking;
-            } else {
-                // No tool calls — check for todo continuation before stopping
-                let response_text = response.text();
-                let output = if response_text.is_empty() {
-                    self.last_tool_output.take().unwrap_or_default()
-                } else {
-                    response_text
-                };
+                    // Add tool results to context
+                    let tool_result_message = turn::tool_results_message(tool_results);
+                    self.record_message(tool_result_message.clone());
+                    self.session
+                        .context
+                        .add_message(tool_result_message)
+                        .map_err(AgentLoopError::Context)?;
+                    self.finish_turn_stats(turn_number).await;
 
-                // Check if we should inject a continuation message
-                if self.continuation_enabled
-                    && self.continuation_count < self.max_continuations
-                    && crate::continuation::is_completion_signal(&output)
-                    && self
-                        .session
-                        .todo_store
-                        .has_incomplete(&self.session.id.to_string())
-                        .await
-                {
-                    self.continuation_count += 1;
+                    self.state = AgentState::Thinking;
+                }
+                TurnStep::Finish(response_text) => {
+                    self.finish_turn_stats(turn_number).await;
+
+                    // No tool calls — check for todo continuation before stopping
+                    let output = if response_text.is_empty() {
+                        self.last_tool_output.take().unwrap_or_default()
+                    } else {
+                        response_text
+                    };
 
-                    let incomplete_items = self
-                        .session
-                        .todo_store
-                        .incomplete_items(&self.session.id.to_string())
-                        .await;
-                    let summaries: Vec<String> =
-                        incomplete_items.iter().map(|t| t.content.clone()).collect();
-                    let continuation = crate::continuation::generate_continuation(
-                        incomplete_items.len(),
-                        &summaries,
-                    );
+                    // Check if we should inject a continuation message
+                    if self.continuation_enabled
+                        && self.continuation_count < self.max_continuations
+                        && crate::continuation::is_completion_signal(&output)
+                        && self
+                            .session
+                            .todo_store
+                            .has_incomplete(&self.session.id.to_string())
+                            .await
+                    {
+                        self.continuation_count += 1;
 
-                    if let Some(system_injection) = continuation.system_injection.clone() {
-                        let system_message = Message::with_blocks(
-                            Role::System,
-                            vec![ContentBlock::text(system_injection)],
+                        let todos = self
+                            .session
+                            .todo_store
+                            .get(&self.session.id.to_string())
+                            .await;
+                        let incomplete_items: Vec<&uira_core::TodoItem> =
+                            todos.iter().filter(|t| t.is_incomplete()).collect();
+                        // Unblocked items lead so the model picks up work it can actually start
+                        let mut summaries: Vec<String> = uira_core::actionable_todos(&todos)
+                            .iter()
+                            .map(|t| t.content.clone())
+                            .collect();
+                        summaries.extend(
+                            incomplete_items
+                                .iter()
+                                .filter(|t| t.is_blocked(&todos))
+                                .map(|t| format!("{} (blocked)", t.content)),
+                        );
+                        let continuation = crate::continuation::generate_continuation(
+                            incomplete_items.len(),
+                            &summaries,
                         );
-                        self.record_message(system_message.clone());
-                        self.session
-                            .context
-                            .add_message(system_message)
-                            .map_err(AgentLoopError::Context)?;
+
+                        if let Some(system_injection) = continuation.system_injection.clone() {
+                            let system_message = Message::with_blocks(
+                                Role::System,
+                                vec![ContentBlock::text(system_injection)],
+                            );
+                            self.record_message(system_message.clone());
+                            self.session
+                                .context
+                                .add_message(system_message)
+                                .map_err(AgentLoopError::Context)?;
+                        }
+
+                        if let Some(injection) = continuation.user_injection {
+                            tracing::info!(
+                                "Todo continuation {}/{}: injecting prompt for {} incomplete items",
+                                self.continuation_count,
+                                self.max_continuations,
+                                incomplete_items.len()
+                            );
+
+                            let continuation_message = Message::with_blocks(
+                                Role::User,
+                                vec![ContentBlock::text(injection)],
+                            );
+                            self.record_message(continuation_message.clone());
+                            self.session
+                                .context
+                                .add_message(continuation_message)
+                                .map_err(AgentLoopError::Context)?;
+
+                            self.state = AgentState::Thinking;
+                            continue;
+                        }
                     }
 
-                    if let Some(injection) = continuation.user_injection {
-                        tracing::info!(
-                            "Todo continuation {}/{}: injecting prompt for {} incomplete items",
-                            self.continuation_count,
-                            self.max_continuations,
-                            incomplete_items.len()
-                        );
+                    let contract = &self.session.config.output_contract;
+                    let output = match crate::output_contract::check(contract, &output) {
+                        Ok(output) => output,
+                        Err(violation) if !self.contract_repaired => {
+                            tracing::info!(
+                                contract = contract.name(),
+                                %violation,
+                                "Final message broke the output contract; asking for a repair"
+                            );
+                            self.contract_repaired = true;
+                            let repair_message = Message::with_blocks(
+                                Role::User,
+                                vec![ContentBlock::text(crate::output_contract::repair_prompt(
+                                    contract, &violation,
+                                ))],
+                            );
+                            self.record_message(repair_message.clone());
+                            self.session
+                                .context
+                                .add_message(repair_message)
+                                .map_err(AgentLoopError::Context)?;
+                            self.state = AgentState::Thinking;
+                            continue;
+                        }
+                        Err(violation) => {
+                            self.state = AgentState::Failed;
+                            return Ok(ExecutionResult::failure(
+                                AgentError::OutputContractViolated {
+                                    contract: contract.name().to_string(),
+                                    violation,
+                                },
+                                self.session.turn,
+                                self.session.usage.clone(),
+                            ));
+                        }
+                    };
+
+                    // No continuation needed — complete normally
+                    self.state = AgentState::Complete;
+                    self.emit_event(ThreadEvent::ThreadCompleted {
+                        usage: self.session.usage.clone(),
+                    })
+                    .await;
+                    return Ok(ExecutionResult::success(
+                        output,
+                        self.session.turn,
+                        self.session.usage.clone(),
+                    ));
+                }
+            }
+        }
+    }
 
-                        let continuation_message =
-                            Message::with_blocks(Role::User, vec![ContentBlock::text(injection)]);
-                        self.record_message(continuation_message.clone());
-                        self.session
-                            .context
-                            .add_message(continuation_message)
-                            .map_err(AgentLoopError::Context)?;
+    /// Get the model response for the current context, escalating to the
+    /// speculative fallback client when the first response calls for it and
+    /// reconciling final answers with the consensus panel
+    async fn get_response(
+        &mut self,
+        tool_specs: &[uira_core::ToolSpec],
+    ) -> Result<uira_core::ModelResponse, AgentLoopError> {
+        let response = self.request_response(tool_specs).await?;
+        let response = self.escalate_if_needed(response, tool_specs).await?;
+        // Plans and reviews are final answers, so tool-calling turns skip the panel
+        if self.consensus.is_some() && response.tool_calls().is_empty() {
+            return Ok(self.reconcile_with_panel(response, tool_specs).await);
+        }
+        Ok(response)
+    }
 
-                        self.state = AgentState::Thinking;
-                        continue;
+    /// Replay the request on the speculative fallback client when the first
+    /// response of the run trips the escalation heuristics
+    async fn escalate_if_needed(
+        &mut self,
+        response: uira_core::ModelResponse,
+        tool_specs: &[uira_core::ToolSpec],
+    ) -> Result<uira_core::ModelResponse, AgentLoopError> {
+        // Only the first response of a run decides escalation
+        let Some(fallback) = self.speculative_fallback.take() else {
+            return Ok(response);
+        };
+        let tool_names: Vec<&str> = tool_specs.iter().map(|spec| spec.name.as_str()).collect();
+        let Some(reason) = escalation_reason(&response, &tool_names) else {
+            return Ok(response);
+        };
+
+        // The discarded response was still billed
+        self.session.record_usage(response.usage);
+        let model = fallback.model().to_string();
+        let provider = fallback.provider().to_string();
+        tracing::info!(
+            "Escalating from {} to {} ({})",
+            self.session.client.model(),
+            model,
+            reason
+        );
+        // Swap the client only: `set_client` would also reset the context,
+        // which never received the discarded response and is replayed as is
+        self.session.client = fallback;
+        self.emit_event(ThreadEvent::ModelSwitched { model, provider })
+            .await;
+
+        self.request_response(tool_specs).await
+    }
+
+    /// Query the consensus panel in parallel and replace `response` with the
+    /// judge's reconciliation, recording where the answers disagreed. Falls
+    /// back to `response` when the panel or the judge has nothing usable.
+    async fn reconcile_with_panel(
+        &mut self,
+        response: uira_core::ModelResponse,
+        tool_specs: &[uira_core::ToolSpec],
+    ) -> uira_core::ModelResponse {
+        let Some(consensus) = self.consensus.as_ref() else {
+            return response;
+        };
+        let panel = consensus.panel.clone();
+        let judge = consensus.judge.clone();
+        let session_id = self.session.id.to_string();
+
+        let scrubbed = self
+            .privacy
+            .as_mut()
+            .map(|scrubber| scrubber.scrub_messages(self.session.context.messages()));
+        let messages = scrubbed
+            .as_deref()
+            .unwrap_or(self.session.context.messages());
+        let answers = futures::future::join_all(panel.iter().map(|client| {
+            payload_log::with_session(session_id.clone(), client.chat(messages, tool_specs))
+        }))
+        .await;
+
+        let mut candidates = vec![Candidate {
+            model: self.session.client.model().to_string(),
+            answer: response.text(),
+        }];
+        for (client, answer) in panel.iter().zip(answers) {
+            match answer {
+                Ok(answer) => {
+                    self.session.usage += answer.usage.clone();
+                    let answer = match self.privacy.as_ref() {
+                        Some(scrubber) => scrubber.restore_response(answer),
+                        None => answer,
+                    };
+                    let text = answer.text();
+                    if !text.trim().is_empty() {
+                        candidates.push(Candidate {
+                            model: client.model().to_string(),
+                            answer: text,
+                        });
                     }
                 }
+                Err(e) => tracing::warn!("Consensus panel model {} failed: {}", client.model(), e),
+            }
+        }
+        if candidates.len() < 2 {
+            return response;
+        }
 
-                // No continuation needed — complete normally
-                self.state = AgentState::Complete;
-                self.emit_event(ThreadEvent::ThreadCompleted {
-                    usage: self.session.usage.clone(),
-                })
-                .await;
-                return Ok(ExecutionResult::success(
-                    output,
-                    self.session.turn,
-                    self.session.usage.clone(),
-                ));
+        let request = vec![Message::user(judge_prompt(
+            &task_text(self.session.context.messages()),
+            &candidates,
+        ))];
+        let request = match self.privacy.as_mut() {
+            Some(scrubber) => scrubber.scrub_messages(&request),
+            None => request,
+        };
+        let verdict = match payload_log::with_session(session_id, judge.chat(&request, &[])).await {
+            Ok(judged) => {
+                self.session.usage += judged.usage.clone();
+                let judged = match self.privacy.as_ref() {
+                    Some(scrubber) => scrubber.restore_response(judged),
+                    None => judged,
+                };
+                parse_verdict(&judged.text())
+            }
+            Err(e) => {
+                tracing::warn!("Consensus judge {} failed: {}", judge.model(), e);
+                return response;
             }
+        };
+        if verdict.answer.is_empty() {
+            return response;
+        }
+
+        let event = ThreadEvent::ConsensusReached {
+            models: candidates.into_iter().map(|c| c.model).collect(),
+            judge: judge.model().to_string(),
+            disagreements: verdict.disagreements,
+        };
+        self.record_event(event.clone());
+        self.emit_event(event).await;
+
+        uira_core::ModelResponse {
+            content: vec![ContentBlock::text(verdict.answer)],
+            ..response
+        }
+    }
+
+    /// Request a model response, timing the provider call and counting its
+    /// retries into the turn stats
+    async fn request_response(
+        &mut self,
+        tool_specs: &[uira_core::ToolSpec],
+    ) -> Result<uira_core::ModelResponse, AgentLoopError> {
+        let started = Instant::now();
+        let scrubbed = self
+            .privacy
+            .as_mut()
+            .map(|scrubber| scrubber.scrub_messages(self.session.context.messages()));
+        let (response, retries) = if self.streaming_enabled {
+            count_retries(self.get_response_streaming(scrubbed.as_deref(), tool_specs)).await
+        } else {
+            let client = self.session.client.clone();
+            let messages = scrubbed
+                .as_deref()
+                .unwrap_or(self.session.context.messages());
+            let session_id = self.session.id.to_string();
+            let (response, retries) = count_retries(payload_log::with_session(
+                session_id,
+                client.chat(messages, tool_specs),
+            ))
+            .await;
+            (response.map_err(AgentLoopError::Provider), retries)
+        };
+        if let Some(stats) = self.turn_stats.as_mut() {
+            stats.record_provider(started.elapsed(), retries);
         }
+        match self.privacy.as_ref() {
+            Some(scrubber) => response.map(|response| scrubber.restore_response(response)),
+            None => response,
+        }
+    }
+
+    /// Emit and record the summary for the turn in progress
+    async fn finish_turn_stats(&mut self, turn_number: usize) {
+        let Some(stats) = self.turn_stats.take() else {
+            return;
+        };
+        let event = stats.finish(turn_number, self.session.context.current_tokens());
+        self.record_event(event.clone());
+        self.emit_event(event).await;
     }
 
     /// Get model response with streaming, emitting ContentDelta events
     async fn get_response_streaming(
         &mut self,
+        scrubbed: Option<&[Message]>,
         tool_specs: &[uira_core::ToolSpec],
     ) -> Result<uira_core::ModelResponse, AgentLoopError> {
-        let stream = self
-            .session
-            .client
-            .chat_stream(self.session.context.messages(), tool_specs)
-            .await
-            .map_err(AgentLoopError::Provider)?;
+        let messages = scrubbed.unwrap_or(self.session.context.messages());
+        let stream = payload_log::with_session(
+            self.session.id.to_string(),
+            self.session.client.chat_stream(messages, tool_specs),
+        )
+        .await
+        .map_err(AgentLoopError::Provider)?;
 
         let mut controller = StreamController::new();
         let mut stream = std::pin::pin!(stream);
@@ -1079,6 +1690,11 @@ impl Agent {
             for output in outputs {
                 match output {
                     crate::streaming::StreamOutput::Text(line) => {
+                        // Deltas are whole lines, so placeholders are never split
+                        let line = match self.privacy.as_ref() {
+                            Some(scrubber) => scrubber.restore(&line),
+                            None => line,
+                        };
                         self.emit_event(ThreadEvent::ContentDelta {
                             delta: format!("{}\n", line),
                         })
@@ -1088,6 +1704,18 @@ impl Agent {
                         self.emit_event(ThreadEvent::ThinkingDelta { thinking })
                             .await;
                     }
+                    crate::streaming::StreamOutput::ToolInput { id, name, input } => {
+                        let input = match self.privacy.as_ref() {
+                            Some(scrubber) => scrubber.restore_value(&input),
+                            None => input,
+                        };
+                        self.emit_event(ThreadEvent::ToolInputPreview {
+                            tool_call_id: id,
+                            tool_name: name,
+                            input,
+                        })
+                        .await;
+                    }
                 }
             }
         }
@@ -1121,20 +1749,13 @@ impl Agent {
 
                 // Start a new turn
                 let turn_number = self.session.start_turn();
+                self.turn_stats = Some(TurnStats::start(self.session.context.current_tokens()));
                 self.emit_event(ThreadEvent::TurnStarted { turn_number })
                     .await;
 
                 // Get model response (streaming or blocking)
                 let tool_specs = self.session.tool_specs();
-                let response = if self.streaming_enabled {
-                    self.get_response_streaming(&tool_specs).await?
-                } else {
-                    self.session
-                        .client
-                        .chat(self.session.context.messages(), &tool_specs)
-                        .await
-                        .map_err(AgentLoopError::Provider)?
-                };
+                let response = self.get_response(&tool_specs).await?;
 
                 // Record usage
                 self.session.record_usage(response.usage.clone());
@@ -1164,6 +1785,7 @@ impl Agent {
                     self.state = AgentState::ExecutingTool;
                 } else {
                     // No tool calls, we're done
+                    self.finish_turn_stats(turn_number).await;
                     self.state = AgentState::Complete;
                     self.emit_event(ThreadEvent::ThreadCompleted {
                         usage: self.session.usage.clone(),
@@ -1185,6 +1807,7 @@ impl Agent {
                         .context
                         .add_message(tool_result_message)
                         .map_err(AgentLoopError::Context)?;
+                    self.finish_turn_stats(self.session.turn).await;
 
                     // Go back to thinking for next turn
                     self.state = AgentState::Thinking;
@@ -1338,6 +1961,10 @@ impl Agent {
     /// Record a message to the session log
     fn record_message(&mut self, message: Message) {
         if let Some(ref mut recorder) = self.session_recorder {
+            let message = match self.privacy.as_mut() {
+                Some(scrubber) => scrubber.scrub_message(&message),
+                None => message,
+            };
             if let Err(e) = recorder.record_message(message) {
                 tracing::warn!("Failed to record message to session log: {}", e);
             }
@@ -1347,7 +1974,11 @@ impl Agent {
     /// Record a tool call to the session log
     fn record_tool_call(&mut self, id: &str, name: &str, input: &serde_json::Value) {
         if let Some(ref mut recorder) = self.session_recorder {
-            if let Err(e) = recorder.record_tool_call(id, name, input.clone()) {
+            let input = match self.privacy.as_mut() {
+                Some(scrubber) => scrubber.scrub_value(input),
+                None => input.clone(),
+            };
+            if let Err(e) = recorder.record_tool_call(id, name, input) {
                 tracing::warn!("Failed to record tool call to session log: {}", e);
             }
         }
@@ -1356,6 +1987,10 @@ impl Agent {
     /// Record a tool result to the session log
     fn record_tool_result(&mut self, id: &str, output: &str, is_error: bool) {
         if let Some(ref mut recorder) = self.session_recorder {
+            let output = match self.privacy.as_mut() {
+                Some(scrubber) => scrubber.scrub(output),
+                None => output.to_string(),
+            };
             if let Err(e) = recorder.record_tool_result(id, output, is_error) {
                 tracing::warn!("Failed to record tool result to session log: {}", e);
             }
@@ -1371,6 +2006,97 @@ impl Agent {
         }
     }
 
+    /// Explain a risky command for its approval prompt, recording the
+    /// explanation to the session log. Uses the explainer model when one is
+    /// set and answers in time, and the local explanation otherwise.
+    async fn explain_command(
+        &mut self,
+        tool_call_id: &str,
+        command: &str,
+        class: ToolClass,
+    ) -> CommandExplanation {
+        let mut explained = None;
+        if let Some(explainer) = self.command_explainer.clone() {
+            let request = vec![Message::user(command_explainer::explain_prompt(command))];
+            let request = match self.privacy.as_mut() {
+                Some(scrubber) => scrubber.scrub_messages(&request),
+                None => request,
+            };
+            let session_id = self.session.id.to_string();
+            match timeout(
+                EXPLAIN_TIMEOUT,
+                payload_log::with_session(session_id, explainer.chat(&request, &[])),
+            )
+            .await
+            {
+                Ok(Ok(reply)) => {
+                    self.session.usage += reply.usage.clone();
+                    let reply = match self.privacy.as_ref() {
+                        Some(scrubber) => scrubber.restore_response(reply),
+                        None => reply,
+                    };
+                    explained = command_explainer::parse_explanation(&reply.text())
+                        .map(|explanation| (explanation, explainer.model().to_string()));
+                }
+                Ok(Err(e)) => {
+                    tracing::warn!("Command explainer {} failed: {}", explainer.model(), e)
+                }
+                Err(_) => tracing::warn!("Command explainer {} timed out", explainer.model()),
+            }
+        }
+        let (explanation, model) = match explained {
+            Some((explanation, model)) => (explanation, Some(model)),
+            None => (command_explainer::explain(command), None),
+        };
+
+        let event = ThreadEvent::CommandExplained(Box::new(CommandExplanationEvent {
+            tool_call_id: tool_call_id.to_string(),
+            command: command.to_string(),
+            class: class.to_string(),
+            summary: explanation.summary.clone(),
+            effects: explanation.effects.clone(),
+            model,
+        }));
+        self.record_event(event.clone());
+        self.emit_event(event).await;
+        explanation
+    }
+
+    /// Check `call`'s input against its tool's schema, returning the error to
+    /// send back when it does not match. The first failure asks the model to
+    /// repair the call; if the repaired call fails as well the error is
+    /// returned without another repair prompt.
+    fn invalid_input_error(
+        &mut self,
+        call: &ToolCall,
+        specs: &[uira_core::ToolSpec],
+    ) -> Option<String> {
+        let spec = specs.iter().find(|spec| spec.name == call.name)?;
+        let Err(violations) = spec.input_schema.validate(&call.input) else {
+            self.schema_repairs.remove(&call.name);
+            return None;
+        };
+        tracing::debug!(
+            tool = %call.name,
+            violations = violations.len(),
+            "tool_input_invalid"
+        );
+
+        let mut error = serde_json::json!({
+            "error": "invalid_tool_input",
+            "tool": call.name,
+            "violations": violations,
+        });
+        if self.schema_repairs.insert(call.name.clone()) {
+            error["repair"] = serde_json::Value::String(format!(
+                "The call was not executed. Call {} again with arguments that match its input \
+                 schema, fixing the violations listed above.",
+                call.name
+            ));
+        }
+        Some(error.to_string())
+    }
+
     /// Record a thread event to the session log
     fn record_event(&mut self, event: ThreadEvent) {
         if let Some(ref mut recorder) = self.session_recorder {
@@ -1500,11 +2226,16 @@ impl Agent {
         tool_calls: &[ToolCall],
     ) -> Result<Vec<ContentBlock>, AgentLoopError> {
         let mut results = Vec::new();
-        let ctx = self.session.tool_context();
+        let cancel = self.control.cancel_signal();
+        let ctx = self
+            .session
+            .tool_context()
+            .with_cancellation(cancel.turn_token(), cancel.session_token());
 
         // Phase 1: Check approvals sequentially (requires user interaction)
         // Collect approved calls for parallel execution
         let mut approved_calls: Vec<(String, String, serde_json::Value)> = Vec::new();
+        let specs = self.session.tool_specs();
 
         for call in tool_calls {
             // Check for cancellation between approval checks
@@ -1512,6 +2243,21 @@ impl Agent {
                 return Err(AgentLoopError::Cancelled);
             }
 
+            // Malformed input goes back to the model instead of to the tool
+            if let Some(error_msg) = self.invalid_input_error(call, &specs) {
+                results.push(ContentBlock::tool_error(&call.id, &error_msg));
+                self.record_tool_result(&call.id, &error_msg, true);
+                self.emit_event(ThreadEvent::ItemCompleted {
+                    item: Item::ToolResult {
+                        tool_call_id: call.id.clone(),
+                        output: error_msg,
+                        is_error: true,
+                    },
+                })
+                .await;
+                continue;
+            }
+
             if let Some(permission_action) = self
                 .session
                 .orchestrator
@@ -1520,7 +2266,14 @@ impl Agent {
                 use uira_security::Action as PermAction;
                 match permission_action {
                     PermAction::Deny => {
-                        let error_msg = format!("Permission denied for tool: {}", call.name);
+                        let error_msg = self
+                            .session
+                            .orchestrator
+                            .skill_denial_reason(&call.name, &call.input)
+                            .map(|reason| format!("Permission denied: {}", reason))
+                            .unwrap_or_else(|| {
+                                format!("Permission denied for tool: {}", call.name)
+                            });
                         results.push(ContentBlock::tool_error(&call.id, &error_msg));
                         self.record_tool_result(&call.id, &error_msg, true);
                         self.emit_event(ThreadEvent::ItemCompleted {
@@ -1563,6 +2316,20 @@ impl Agent {
             if !ctx.full_auto {
                 match requirement {
                     ApprovalRequirement::NeedsApproval { reason } => {
+                        if self
+                            .session
+                            .orchestrator
+                            .auto_approved_class(&call.name, &call.input, &ctx)
+                            .is_some()
+                        {
+                            approved_calls.push((
+                                call.id.clone(),
+                                call.name.clone(),
+                                call.input.clone(),
+                            ));
+                            continue;
+                        }
+
                         if let Some(cached) = self
                             .session
                             .orchestrator
@@ -1584,16 +2351,29 @@ impl Agent {
                             }
                         }
 
-                        if let Some(ref approval_tx) = self.approval_tx {
-                            self.emit_event(ThreadEvent::ItemStarted {
+                        if let Some(approval_tx) = self.approval_tx.clone() {
+                            let explained =
+                                command_explainer::risky_command(&call.name, &call.input, &ctx)
+                                    .filter(|_| self.session.config.explain_commands.enabled);
+                            let reason = match explained {
+                                Some((command, class)) => {
+                                    let explanation =
+                                        self.explain_command(&call.id, command, class).await;
+                                    format!("{}\n{}", reason, explanation.for_approval())
+                                }
+                                None => reason,
+                            };
+
+                            let event = ThreadEvent::ItemStarted {
                                 item: Item::ApprovalRequest {
                                     id: call.id.clone(),
                                     tool_name: call.name.clone(),
                                     input: call.input.clone(),
                                     reason: reason.clone(),
                                 },
-                            })
-                            .await;
+                            };
+                            self.record_event(event.clone());
+                            self.emit_event(event).await;
 
                             let decision = timeout(
                                 APPROVAL_TIMEOUT,
@@ -1617,13 +2397,14 @@ impl Agent {
                                 .store_approval(&call.name, &call.input, &decision)
                                 .await;
 
-                            self.emit_event(ThreadEvent::ItemCompleted {
+                            let event = ThreadEvent::ItemCompleted {
                                 item: Item::ApprovalDecision {
                                     request_id: call.id.clone(),
                                     approved: decision.is_approved(),
                                 },
-                            })
-                            .await;
+                            };
+                            self.record_event(event.clone());
+                            self.emit_event(event).await;
 
                             if decision.is_denied() {
                                 let deny_reason =
@@ -1693,24 +2474,50 @@ impl Agent {
             .await;
         }
 
+        for (_, name, input) in &approved_calls {
+            self.session
+                .orchestrator
+                .snapshot_if_risky(name, input, &ctx.cwd)
+                .await;
+        }
+
         // Phase 3: Execute tools in parallel where possible
         // ToolCallRuntime handles read/write lock semantics:
         // - Parallel-safe tools (Read, Glob, Grep): run concurrently with read lock
         // - Mutating tools (Write, Edit, Bash): run exclusively with write lock
-        let execution_results = self
-            .session
-            .parallel_runtime
-            .execute_batch_with_ids(approved_calls, &ctx)
-            .await;
+        // Interrupting stops waiting on the batch; tools still running
+        // see the same token and stop their processes and subagents
+        let execution_results = tokio::select! {
+            results = self
+                .session
+                .parallel_runtime
+                .execute_batch_timed(approved_calls, &ctx) => results,
+            _ = ctx.cancellation.cancelled() => return Err(AgentLoopError::Cancelled),
+        };
 
         // Phase 4: Process results and emit events (must be sequential)
         let mut todo_updated = false;
-        for (call_id, result) in execution_results {
+        // Images from tool output (e.g. Read on a PNG) follow the tool results
+        let mut attachments = Vec::new();
+        for (call_id, result, elapsed) in execution_results {
             let tool_name = call_id_to_name.get(&call_id).map(|s| s.as_str());
+            let result = match result {
+                Err(e) if tool_name == Some("Edit") && !ctx.full_auto => {
+                    self.resolve_edit_mismatch(&call_id, e, &ctx).await
+                }
+                result => result,
+            };
+            if let (Some(stats), Some(name)) = (self.turn_stats.as_mut(), tool_name) {
+                stats.record_tool(name, elapsed, result.is_ok());
+            }
             match result {
                 Ok(output) => {
                     let content = output.as_text().unwrap_or("").to_string();
                     results.push(ContentBlock::tool_result(&call_id, &content));
+                    attachments.extend(output.content.into_iter().filter_map(|c| match c {
+                        ToolOutputContent::Image { source } => Some(ContentBlock::Image { source }),
+                        ToolOutputContent::Text { .. } => None,
+                    }));
 
                     if !content.is_empty() {
                         self.last_tool_output = Some(content.clone());
@@ -1752,6 +2559,11 @@ impl Agent {
             }
         }
 
+        results.extend(attachments);
+        if let Some(stale_context) = self.take_stale_file_context() {
+            results.push(ContentBlock::text(stale_context));
+        }
+
         // Phase 5: Emit TodoUpdated event for TUI sidebar
         if todo_updated {
             let todos = self.session.todo_store.get(&ctx.session_id).await;
@@ -1760,6 +2572,61 @@ impl Agent {
 
         Ok(results)
     }
+
+    /// Offer the corrected edit from an Edit mismatch report for approval,
+    /// applying it instead of failing the call when the user accepts
+    async fn resolve_edit_mismatch(
+        &self,
+        call_id: &str,
+        error: ToolError,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let (Some(approval_tx), Some(suggested)) = (
+            self.approval_tx.as_ref(),
+            EditTool::suggested_edit(&error.to_string()),
+        ) else {
+            return Err(error);
+        };
+
+        let request_id = format!("{}-correction", call_id);
+        let reason = "old_string didn't match the file; apply the closest match instead?";
+        self.emit_event(ThreadEvent::ItemStarted {
+            item: Item::ApprovalRequest {
+                id: request_id.clone(),
+                tool_name: "Edit".to_string(),
+                input: suggested.clone(),
+                reason: reason.to_string(),
+            },
+        })
+        .await;
+
+        let decision = timeout(
+            APPROVAL_TIMEOUT,
+            approval_tx.request_approval(&request_id, "Edit", suggested.clone(), reason),
+        )
+        .await;
+        let approved = matches!(decision, Ok(Ok(ref decision)) if decision.is_approved());
+        self.emit_event(ThreadEvent::ItemCompleted {
+            item: Item::ApprovalDecision {
+                request_id,
+                approved,
+            },
+        })
+        .await;
+        if !approved {
+            return Err(error);
+        }
+
+        let output = self
+            .session
+            .parallel_runtime
+            .execute("Edit", suggested, ctx)
+            .await?;
+        Ok(ToolOutput::text(format!(
+            "old_string didn't match exactly; the user approved applying the closest match instead.\n{}",
+            output.as_text().unwrap_or("")
+        )))
+    }
 }
 
 #[cfg(test)]
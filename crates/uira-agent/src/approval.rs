@@ -3,7 +3,7 @@
 //! Provides bi-directional approval communication between Agent and TUI.
 
 use tokio::sync::{mpsc, oneshot};
-use uira_core::ReviewDecision;
+use uira_core::{ErrorKind, ReviewDecision, UiraError};
 
 /// A pending approval request that the agent is waiting on
 #[derive(Debug)]
@@ -128,6 +128,27 @@ pub enum ApprovalError {
     Timeout,
 }
 
+impl From<&ApprovalError> for UiraError {
+    fn from(error: &ApprovalError) -> Self {
+        let message = error.to_string();
+        match error {
+            ApprovalError::ChannelClosed | ApprovalError::ResponseDropped => {
+                UiraError::internal("approval.channel_closed", message)
+            }
+            ApprovalError::Denied { .. } => {
+                UiraError::new(ErrorKind::PolicyDenial, "approval.denied", message)
+            }
+            ApprovalError::Timeout => {
+                UiraError::new(ErrorKind::PolicyDenial, "approval.timeout", message)
+                    .with_retryable(true)
+                    .with_remediation(
+                        "Answer the approval prompt sooner, or raise the approval timeout",
+                    )
+            }
+        }
+    }
+}
+
 pub use uira_orchestration::{
     ApprovalCache, ApprovalCacheFile, ApprovalKey, CacheDecision, CachedApproval,
 };
@@ -0,0 +1,464 @@
+//! Benchmark harness for comparing providers and agents on fixed tasks
+//!
+//! A bench directory holds one subdirectory per task:
+//!
+//! ```text
+//! bench/
+//!   fix-off-by-one/
+//!     task.json     # { "prompt": "...", "goals": [...], "max_turns": 20 }
+//!     workspace/    # optional snapshot, copied fresh for every run
+//! ```
+//!
+//! Every task runs once per [`BenchTarget`] in a scratch copy of its
+//! workspace. Afterwards the task's goals are checked in that scratch
+//! directory; a run passes only when the agent finished and all goals pass.
+
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+use uira_core::schema::GoalConfig;
+use uira_core::ExecutionResult;
+use uira_orchestration::features::analytics::CostEstimator;
+use uira_orchestration::hooks::{GoalCheckResult, GoalRunner};
+
+/// File inside each task directory describing the task
+pub const TASK_FILE: &str = "task.json";
+/// Directory inside each task directory holding the workspace snapshot
+pub const WORKSPACE_DIR: &str = "workspace";
+
+#[derive(Debug, Deserialize)]
+struct TaskFile {
+    prompt: String,
+    #[serde(default)]
+    goals: Vec<GoalConfig>,
+    #[serde(default)]
+    max_turns: Option<usize>,
+}
+
+/// A single benchmark fixture
+#[derive(Debug, Clone)]
+pub struct BenchTask {
+    pub name: String,
+    pub prompt: String,
+    pub goals: Vec<GoalConfig>,
+    pub max_turns: Option<usize>,
+    /// Snapshot copied into the scratch directory before each run
+    pub workspace: Option<PathBuf>,
+}
+
+impl BenchTask {
+    /// Load a task from its directory
+    pub fn load(dir: &Path) -> std::io::Result<Self> {
+        let raw = std::fs::read_to_string(dir.join(TASK_FILE))?;
+        let file: TaskFile = serde_json::from_str(&raw).map_err(|e| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{}: {}", dir.join(TASK_FILE).display(), e),
+            )
+        })?;
+        let workspace = dir.join(WORKSPACE_DIR);
+
+        Ok(Self {
+            name: dir
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default(),
+            prompt: file.prompt,
+            goals: file.goals,
+            max_turns: file.max_turns,
+            workspace: workspace.is_dir().then_some(workspace),
+        })
+    }
+}
+
+/// Load every task under `dir`, sorted by name
+///
+/// Subdirectories without a `task.json` are ignored.
+pub fn load_tasks(dir: &Path) -> std::io::Result<Vec<BenchTask>> {
+    let mut tasks = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.join(TASK_FILE).is_file() {
+            tasks.push(BenchTask::load(&path)?);
+        }
+    }
+    tasks.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(tasks)
+}
+
+/// One cell of the provider/agent matrix
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BenchTarget {
+    pub provider: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub model: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+}
+
+impl BenchTarget {
+    /// Parse a `provider[:model]` spec
+    pub fn parse(spec: &str) -> Result<Self, String> {
+        let (provider, model) = match spec.split_once(':') {
+            Some((provider, model)) => (provider.trim(), Some(model.trim())),
+            None => (spec.trim(), None),
+        };
+        if provider.is_empty() || model.is_some_and(str::is_empty) {
+            return Err(format!(
+                "invalid target '{}': expected provider[:model]",
+                spec
+            ));
+        }
+        Ok(Self {
+            provider: provider.to_string(),
+            model: model.map(str::to_string),
+            agent: None,
+        })
+    }
+
+    pub fn with_agent(mut self, agent: impl Into<String>) -> Self {
+        self.agent = Some(agent.into());
+        self
+    }
+
+    /// Short label used in reports, e.g. `anthropic:claude-sonnet-4/executor`
+    pub fn label(&self) -> String {
+        let mut label = self.provider.clone();
+        if let Some(model) = &self.model {
+            label.push(':');
+            label.push_str(model);
+        }
+        if let Some(agent) = &self.agent {
+            label.push('/');
+            label.push_str(agent);
+        }
+        label
+    }
+
+    /// Cross every target with every agent; no agents keeps targets as-is
+    pub fn matrix(targets: &[BenchTarget], agents: &[String]) -> Vec<BenchTarget> {
+        if agents.is_empty() {
+            return targets.to_vec();
+        }
+        targets
+            .iter()
+            .flat_map(|target| {
+                agents
+                    .iter()
+                    .map(move |agent| target.clone().with_agent(agent.clone()))
+            })
+            .collect()
+    }
+}
+
+/// What the caller's runner reports back for one agent run
+#[derive(Debug, Clone)]
+pub struct AgentRun {
+    pub result: ExecutionResult,
+    /// Resolved model name, used for cost estimation
+    pub model: String,
+}
+
+/// Outcome of one task against one target
+#[derive(Debug, Clone, Serialize)]
+pub struct BenchRunResult {
+    pub task: String,
+    pub target: String,
+    pub passed: bool,
+    /// Whether the agent itself reported success
+    pub completed: bool,
+    pub goals: Vec<GoalCheckResult>,
+    pub turns: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    pub duration_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Aggregates for one target across all tasks
+#[derive(Debug, Clone, Serialize)]
+pub struct TargetSummary {
+    pub target: String,
+    pub runs: usize,
+    pub passed: usize,
+    pub pass_rate: f64,
+    pub total_cost_usd: f64,
+    pub avg_duration_ms: u64,
+    pub max_duration_ms: u64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct BenchReport {
+    pub runs: Vec<BenchRunResult>,
+}
+
+impl BenchReport {
+    /// Per-target summaries in the order targets were first run
+    pub fn summaries(&self) -> Vec<TargetSummary> {
+        let mut order = Vec::new();
+        let mut grouped: BTreeMap<&str, Vec<&BenchRunResult>> = BTreeMap::new();
+        for run in &self.runs {
+            grouped
+                .entry(&run.target)
+                .or_insert_with(|| {
+                    order.push(run.target.as_str());
+                    Vec::new()
+                })
+                .push(run);
+        }
+
+        order
+            .into_iter()
+            .map(|target| {
+                let runs = &grouped[target];
+                let passed = runs.iter().filter(|r| r.passed).count();
+                let total_ms: u64 = runs.iter().map(|r| r.duration_ms).sum();
+                TargetSummary {
+                    target: target.to_string(),
+                    runs: runs.len(),
+                    passed,
+                    pass_rate: passed as f64 / runs.len() as f64,
+                    total_cost_usd: runs.iter().map(|r| r.cost_usd).sum(),
+                    avg_duration_ms: total_ms / runs.len() as u64,
+                    max_duration_ms: runs.iter().map(|r| r.duration_ms).max().unwrap_or(0),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Runs tasks across a target matrix
+pub struct BenchHarness {
+    tasks: Vec<BenchTask>,
+    targets: Vec<BenchTarget>,
+    scratch_root: PathBuf,
+    keep_workspaces: bool,
+}
+
+impl BenchHarness {
+    pub fn new(tasks: Vec<BenchTask>, targets: Vec<BenchTarget>) -> Self {
+        Self {
+            tasks,
+            targets,
+            scratch_root: std::env::temp_dir().join("uira-bench"),
+            keep_workspaces: false,
+        }
+    }
+
+    /// Directory scratch workspaces are created under
+    pub fn with_scratch_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.scratch_root = root.into();
+        self
+    }
+
+    /// Leave scratch workspaces on disk for inspection
+    pub fn with_keep_workspaces(mut self, keep: bool) -> Self {
+        self.keep_workspaces = keep;
+        self
+    }
+
+    /// Run every task against every target, one at a time
+    ///
+    /// `run` executes the agent for a target and task inside the given
+    /// scratch directory. Errors are recorded as failed runs rather than
+    /// aborting the benchmark.
+    pub async fn run<F, Fut>(&self, mut run: F) -> std::io::Result<BenchReport>
+    where
+        F: FnMut(BenchTarget, BenchTask, PathBuf) -> Fut,
+        Fut: Future<Output = Result<AgentRun, String>>,
+    {
+        let mut report = BenchReport::default();
+        let stamp = chrono::Utc::now().format("%Y%m%d-%H%M%S");
+
+        for target in &self.targets {
+            for task in &self.tasks {
+                let scratch = self.scratch_root.join(format!(
+                    "{}-{}-{}",
+                    stamp,
+                    sanitize(&target.label()),
+                    task.name
+                ));
+                if scratch.exists() {
+                    std::fs::remove_dir_all(&scratch)?;
+                }
+                std::fs::create_dir_all(&scratch)?;
+                if let Some(workspace) = &task.workspace {
+                    copy_dir(workspace, &scratch)?;
+                }
+
+                tracing::info!(task = %task.name, target = %target.label(), "bench run");
+                let started = Instant::now();
+                let outcome = run(target.clone(), task.clone(), scratch.clone()).await;
+                let duration_ms = started.elapsed().as_millis() as u64;
+
+                let runner = GoalRunner::new(&scratch);
+                let mut goals = Vec::with_capacity(task.goals.len());
+                for goal in &task.goals {
+                    goals.push(runner.check_goal(goal).await);
+                }
+                let goals_passed = goals.iter().all(|g| g.passed);
+
+                let result = match outcome {
+                    Ok(AgentRun { result, model }) => BenchRunResult {
+                        task: task.name.clone(),
+                        target: target.label(),
+                        passed: result.success && goals_passed,
+                        completed: result.success,
+                        goals,
+                        turns: result.turns,
+                        input_tokens: result.usage.input_tokens,
+                        output_tokens: result.usage.output_tokens,
+                        cost_usd: CostEstimator::estimate_cost(
+                            result.usage.input_tokens as usize,
+                            result.usage.output_tokens as usize,
+                            &model,
+                        ),
+                        duration_ms,
+                        error: result.error.map(|e| e.to_string()),
+                    },
+                    Err(error) => BenchRunResult {
+                        task: task.name.clone(),
+                        target: target.label(),
+                        passed: false,
+                        completed: false,
+                        goals,
+                        turns: 0,
+                        input_tokens: 0,
+                        output_tokens: 0,
+                        cost_usd: 0.0,
+                        duration_ms,
+                        error: Some(error),
+                    },
+                };
+                report.runs.push(result);
+
+                if !self.keep_workspaces {
+                    let _ = std::fs::remove_dir_all(&scratch);
+                }
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+fn sanitize(label: &str) -> String {
+    label
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect()
+}
+
+fn copy_dir(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(to)?;
+    for entry in std::fs::read_dir(from)? {
+        let entry = entry?;
+        let target = to.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target)?;
+        } else {
+            std::fs::copy(entry.path(), &target)?;
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uira_core::TokenUsage;
+
+    fn write_task(root: &Path, name: &str, task: serde_json::Value) {
+        let dir = root.join(name);
+        std::fs::create_dir_all(dir.join(WORKSPACE_DIR)).unwrap();
+        std::fs::write(dir.join(TASK_FILE), task.to_string()).unwrap();
+        std::fs::write(dir.join(WORKSPACE_DIR).join("input.txt"), "hello").unwrap();
+    }
+
+    #[test]
+    fn parses_targets_and_builds_matrix() {
+        let target = BenchTarget::parse("anthropic:claude-sonnet-4").unwrap();
+        assert_eq!(target.model.as_deref(), Some("claude-sonnet-4"));
+        assert!(BenchTarget::parse("openai:").is_err());
+
+        let matrix = BenchTarget::matrix(
+            &[target, BenchTarget::parse("ollama").unwrap()],
+            &["executor".to_string(), "architect".to_string()],
+        );
+        let labels: Vec<_> = matrix.iter().map(BenchTarget::label).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "anthropic:claude-sonnet-4/executor",
+                "anthropic:claude-sonnet-4/architect",
+                "ollama/executor",
+                "ollama/architect",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn runs_tasks_in_scratch_workspaces_and_checks_goals() {
+        let fixtures = tempfile::tempdir().unwrap();
+        let scratch = tempfile::tempdir().unwrap();
+        write_task(
+            fixtures.path(),
+            "copy-file",
+            serde_json::json!({
+                "prompt": "Copy input.txt to output.txt",
+                "goals": [{
+                    "name": "output-exists",
+                    "command": "test -f output.txt && echo 100 || echo 0",
+                    "target": 100.0
+                }]
+            }),
+        );
+        std::fs::create_dir(fixtures.path().join("not-a-task")).unwrap();
+
+        let tasks = load_tasks(fixtures.path()).unwrap();
+        assert_eq!(tasks.len(), 1);
+
+        let targets = vec![
+            BenchTarget::parse("good:model").unwrap(),
+            BenchTarget::parse("lazy:model").unwrap(),
+        ];
+        let report = BenchHarness::new(tasks, targets)
+            .with_scratch_root(scratch.path())
+            .run(|target, _task, dir| async move {
+                assert!(dir.join("input.txt").is_file());
+                if target.provider == "good" {
+                    std::fs::copy(dir.join("input.txt"), dir.join("output.txt")).unwrap();
+                }
+                let usage = TokenUsage {
+                    input_tokens: 1_000_000,
+                    output_tokens: 0,
+                    ..Default::default()
+                };
+                Ok(AgentRun {
+                    result: ExecutionResult::success("done", 2, usage),
+                    model: "claude-sonnet-4".to_string(),
+                })
+            })
+            .await
+            .unwrap();
+
+        let summaries = report.summaries();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].target, "good:model");
+        assert_eq!(summaries[0].passed, 1);
+        assert_eq!(summaries[1].passed, 0);
+        assert!(report.runs[1].completed);
+        assert!((summaries[0].total_cost_usd - 3.0).abs() < 1e-9);
+        assert_eq!(std::fs::read_dir(scratch.path()).unwrap().count(), 0);
+    }
+}
@@ -2,13 +2,16 @@
 
 use crate::context::{CompactionConfig, CompactionStrategy};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::path::PathBuf;
 use uira_core::schema::{
-    CompactionSettings, GoalConfig, NamedMcpServerConfig, PermissionActionConfig,
-    PermissionRuleConfig,
+    AutoApproveSettings, CiSettings, CodeHostSettings, CompactionSettings, ExplainCommandsSettings,
+    GoalConfig, NamedMcpServerConfig, OutputContract, PermissionActionConfig, PermissionRuleConfig,
+    PrivacySettings, SnapshotSettings, WorkspaceIndexSettings,
 };
 use uira_core::{SandboxPreference, ToolSpec};
 use uira_memory::MemoryConfig;
+use uira_orchestration::{DelegationContext, WorkspaceRoot};
 use uira_security::{ConfigAction, ConfigRule, SandboxPolicy};
 
 /// Configuration for the agent
@@ -34,6 +37,11 @@ pub struct AgentConfig {
     #[serde(default)]
     pub working_directory: Option<PathBuf>,
 
+    /// Additional roots tools can address as `@<name>/...`, each optionally
+    /// with its own sandbox policy
+    #[serde(default)]
+    pub workspaces: Vec<WorkspaceRoot>,
+
     /// Whether to require approval for write operations
     #[serde(default = "default_true")]
     pub require_approval_for_writes: bool,
@@ -70,10 +78,45 @@ pub struct AgentConfig {
     #[serde(default)]
     pub memory: Option<MemoryConfig>,
 
+    /// PII scrubbing for provider requests and session logs
+    #[serde(default)]
+    pub privacy: PrivacySettings,
+
+    /// No network access: web tools, code host and CI tools are withheld and
+    /// memory embeddings use the local provider
+    #[serde(default)]
+    pub offline: bool,
+
+    /// Background workspace index started with interactive sessions
+    #[serde(default)]
+    pub workspace_index: Option<WorkspaceIndexSettings>,
+
+    /// Working tree snapshots before risky shell commands
+    #[serde(default)]
+    pub snapshots: Option<SnapshotSettings>,
+
+    /// Code host issue and pull request tools
+    #[serde(default)]
+    pub code_host: Option<CodeHostSettings>,
+
+    /// CI log triage tool
+    #[serde(default)]
+    pub ci: Option<CiSettings>,
+
     /// Model to use
     #[serde(default)]
     pub model: Option<String>,
 
+    /// Agent definition the session runs as (`--agent`), recorded in the
+    /// session log
+    #[serde(default)]
+    pub agent: Option<String>,
+
+    /// Experiment variants the session was assigned, by experiment name;
+    /// recorded in the session log
+    #[serde(default)]
+    pub experiments: BTreeMap<String, String>,
+
     /// System prompt
     #[serde(default = "default_system_prompt_option")]
     pub system_prompt: Option<String>,
@@ -82,6 +125,14 @@ pub struct AgentConfig {
     #[serde(default)]
     pub permission_rules: Vec<PermissionRuleConfig>,
 
+    /// Tool classes approved without prompting, per sandbox mode
+    #[serde(default)]
+    pub auto_approve: AutoApproveSettings,
+
+    /// Explanations of risky shell commands shown when asking for approval
+    #[serde(default)]
+    pub explain_commands: ExplainCommandsSettings,
+
     /// Directory for caching approval decisions
     #[serde(default)]
     pub cache_directory: Option<PathBuf>,
@@ -97,6 +148,14 @@ pub struct AgentConfig {
     /// Additional context to inject into the system prompt (e.g., skills content)
     #[serde(default)]
     pub additional_context: Vec<String>,
+
+    /// Format the final message must follow
+    #[serde(default)]
+    pub output_contract: OutputContract,
+
+    /// Agent and tool allowlist when running as a delegated subagent
+    #[serde(skip)]
+    pub delegation: Option<DelegationContext>,
 }
 
 fn default_system_prompt_option() -> Option<String> {
@@ -160,6 +219,7 @@ impl Default for AgentConfig {
             sandbox_policy: SandboxPolicy::default(),
             sandbox_preference: SandboxPreference::default(),
             working_directory: None,
+            workspaces: Vec::new(),
             require_approval_for_writes: false,
             require_approval_for_commands: false,
             ralph_mode: false,
@@ -169,13 +229,25 @@ impl Default for AgentConfig {
             goals: AgentGoalsConfig::default(),
             compaction: CompactionConfig::default(),
             memory: None,
+            privacy: PrivacySettings::default(),
+            offline: false,
+            workspace_index: None,
+            snapshots: None,
+            code_host: None,
+            ci: None,
             model: None,
+            agent: None,
+            experiments: BTreeMap::new(),
             system_prompt: Some(default_system_prompt()),
             permission_rules: Vec::new(),
+            auto_approve: AutoApproveSettings::default(),
+            explain_commands: ExplainCommandsSettings::default(),
             cache_directory: None,
             external_mcp_servers: Vec::new(),
             external_mcp_tool_specs: Vec::new(),
             additional_context: Vec::new(),
+            output_contract: OutputContract::default(),
+            delegation: None,
         }
     }
 }
@@ -195,6 +267,11 @@ impl AgentConfig {
         self
     }
 
+    pub fn with_workspaces(mut self, workspaces: Vec<WorkspaceRoot>) -> Self {
+        self.workspaces = workspaces;
+        self
+    }
+
     pub fn with_model(mut self, model: impl Into<String>) -> Self {
         self.model = Some(model.into());
         self
@@ -243,11 +320,61 @@ impl AgentConfig {
         self
     }
 
+    pub fn with_privacy(mut self, privacy: PrivacySettings) -> Self {
+        self.privacy = privacy;
+        self
+    }
+
+    pub fn with_agent(mut self, agent: impl Into<String>) -> Self {
+        self.agent = Some(agent.into());
+        self
+    }
+
+    pub fn with_offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    pub fn with_workspace_index(mut self, settings: WorkspaceIndexSettings) -> Self {
+        self.workspace_index = Some(settings);
+        self
+    }
+
+    pub fn with_snapshots(mut self, settings: SnapshotSettings) -> Self {
+        self.snapshots = Some(settings);
+        self
+    }
+
+    pub fn with_code_host(mut self, settings: CodeHostSettings) -> Self {
+        self.code_host = Some(settings);
+        self
+    }
+
+    pub fn with_ci(mut self, settings: CiSettings) -> Self {
+        self.ci = Some(settings);
+        self
+    }
+
+    pub fn with_delegation(mut self, delegation: DelegationContext) -> Self {
+        self.delegation = Some(delegation);
+        self
+    }
+
     pub fn with_permission_rules(mut self, rules: Vec<PermissionRuleConfig>) -> Self {
         self.permission_rules = rules;
         self
     }
 
+    pub fn with_auto_approve(mut self, settings: AutoApproveSettings) -> Self {
+        self.auto_approve = settings;
+        self
+    }
+
+    pub fn with_explain_commands(mut self, settings: ExplainCommandsSettings) -> Self {
+        self.explain_commands = settings;
+        self
+    }
+
     pub fn with_external_mcp(
         mut self,
         servers: Vec<NamedMcpServerConfig>,
@@ -263,21 +390,39 @@ impl AgentConfig {
         self
     }
 
+    pub fn with_output_contract(mut self, contract: OutputContract) -> Self {
+        self.output_contract = contract;
+        self
+    }
+
     pub fn get_full_system_prompt(&self) -> Option<String> {
         let base_prompt = self.system_prompt.as_ref()?;
+        let contract = crate::output_contract::instructions(&self.output_contract);
 
-        if self.additional_context.is_empty() {
+        if self.additional_context.is_empty() && self.workspaces.is_empty() && contract.is_none() {
             return Some(base_prompt.clone());
         }
 
         let mut full_prompt = base_prompt.clone();
         full_prompt.push_str("\n\n");
 
+        if !self.workspaces.is_empty() {
+            full_prompt.push_str(&uira_orchestration::tools::workspace::describe_roots(
+                &self.workspaces,
+            ));
+            full_prompt.push_str("\n\n");
+        }
+
         for ctx in &self.additional_context {
             full_prompt.push_str(ctx);
             full_prompt.push_str("\n\n");
         }
 
+        if let Some(contract) = contract {
+            full_prompt.push_str(&contract);
+            full_prompt.push_str("\n\n");
+        }
+
         Some(full_prompt)
     }
 
@@ -293,6 +438,7 @@ impl AgentConfig {
                     PermissionActionConfig::Deny => ConfigAction::Deny,
                     PermissionActionConfig::Ask => ConfigAction::Ask,
                 },
+                priority: r.priority,
                 comment: r.comment.clone(),
             })
             .collect()
@@ -464,4 +610,18 @@ mod tests {
         assert_eq!(config.additional_context.len(), 1);
         assert_eq!(config.additional_context[0], "<skill>Test</skill>");
     }
+
+    #[test]
+    fn test_workspaces_in_system_prompt() {
+        let config = AgentConfig::new().with_workspaces(vec![
+            WorkspaceRoot::new("api", "/work/api"),
+            WorkspaceRoot::new("docs", "/work/docs").with_sandbox_policy(SandboxPolicy::ReadOnly),
+        ]);
+
+        let prompt = config
+            .get_full_system_prompt()
+            .expect("Should have system prompt");
+        assert!(prompt.contains("- @api: /work/api"));
+        assert!(prompt.contains("- @docs: /work/docs (read-only)"));
+    }
 }
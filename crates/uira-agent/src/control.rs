@@ -1,7 +1,9 @@
 //! Agent control and spawn limits
 
 use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
+
+use tokio_util::sync::CancellationToken;
 
 /// Guards for spawn limits and concurrent execution
 pub struct Guards {
@@ -84,9 +86,86 @@ impl Drop for AgentGuard<'_> {
     }
 }
 
+/// Cancellation for an agent and everything it started
+///
+/// The session token lives as long as the agent and is cancelled on
+/// shutdown; background work hangs off it. Each turn runs under a child
+/// token that an interrupt cancels, taking running tools, MCP calls and
+/// delegated subagents with it, and that is replaced once the interrupt has
+/// been handled. A subagent's session token is a child of the delegating
+/// turn's token, so cancelling a parent cancels the whole tree.
+#[derive(Clone)]
+pub struct CancelSignal {
+    session: CancellationToken,
+    turn: Arc<Mutex<CancellationToken>>,
+}
+
+impl CancelSignal {
+    pub fn new() -> Self {
+        Self::with_session(CancellationToken::new())
+    }
+
+    /// Signal cancelled along with `parent`
+    pub fn child_of(parent: &CancellationToken) -> Self {
+        Self::with_session(parent.child_token())
+    }
+
+    fn with_session(session: CancellationToken) -> Self {
+        let turn = session.child_token();
+        Self {
+            session,
+            turn: Arc::new(Mutex::new(turn)),
+        }
+    }
+
+    fn current(&self) -> std::sync::MutexGuard<'_, CancellationToken> {
+        self.turn.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Interrupt the current turn
+    pub fn cancel(&self) {
+        self.current().cancel();
+    }
+
+    /// Cancel the session, including background work; the signal stays
+    /// cancelled
+    pub fn shutdown(&self) {
+        self.session.cancel();
+    }
+
+    /// Start over after an interrupt, unless the session itself was
+    /// cancelled
+    pub fn clear(&self) {
+        let mut turn = self.current();
+        if turn.is_cancelled() && !self.session.is_cancelled() {
+            *turn = self.session.child_token();
+        }
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.current().is_cancelled()
+    }
+
+    /// Token for work belonging to the current turn
+    pub fn turn_token(&self) -> CancellationToken {
+        self.current().clone()
+    }
+
+    /// Token for work that outlives turns, like background tasks
+    pub fn session_token(&self) -> CancellationToken {
+        self.session.clone()
+    }
+}
+
+impl Default for CancelSignal {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// Agent control handle for managing the agent externally
 pub struct AgentControl {
-    cancelled: Arc<AtomicBool>,
+    cancel: CancelSignal,
     paused: Arc<AtomicBool>,
     guards: Arc<Guards>,
 }
@@ -94,20 +173,26 @@ pub struct AgentControl {
 impl AgentControl {
     pub fn new(guards: Arc<Guards>) -> Self {
         Self {
-            cancelled: Arc::new(AtomicBool::new(false)),
+            cancel: CancelSignal::new(),
             paused: Arc::new(AtomicBool::new(false)),
             guards,
         }
     }
 
+    /// Cancel along with `parent`, for a subagent working on its behalf
+    pub fn with_parent(mut self, parent: &CancellationToken) -> Self {
+        self.cancel = CancelSignal::child_of(parent);
+        self
+    }
+
     /// Cancel the agent
     pub fn cancel(&self) {
-        self.cancelled.store(true, Ordering::SeqCst);
+        self.cancel.cancel();
     }
 
     /// Clear the cancelled state
     pub fn clear_cancelled(&self) {
-        self.cancelled.store(false, Ordering::SeqCst);
+        self.cancel.clear();
     }
 
     /// Pause the agent
@@ -122,7 +207,7 @@ impl AgentControl {
 
     /// Check if cancelled
     pub fn is_cancelled(&self) -> bool {
-        self.cancelled.load(Ordering::SeqCst)
+        self.cancel.is_cancelled()
     }
 
     /// Check if paused
@@ -136,8 +221,8 @@ impl AgentControl {
     }
 
     /// Create a clone of the cancel signal for sharing
-    pub fn cancel_signal(&self) -> Arc<AtomicBool> {
-        self.cancelled.clone()
+    pub fn cancel_signal(&self) -> CancelSignal {
+        self.cancel.clone()
     }
 }
 
@@ -181,4 +266,33 @@ mod tests {
         control.resume();
         assert!(!control.is_paused());
     }
+
+    #[test]
+    fn test_cancel_signal_hierarchy() {
+        let parent = CancelSignal::new();
+        let child = AgentControl::default().with_parent(&parent.turn_token());
+        let turn = child.cancel_signal().turn_token();
+        let background = child.cancel_signal().session_token();
+
+        // Interrupting the parent's turn cancels the subagent's work
+        parent.cancel();
+        assert!(child.is_cancelled());
+        assert!(turn.is_cancelled());
+        assert!(background.is_cancelled());
+
+        // An interrupt only ends the turn; the next one starts fresh
+        let signal = CancelSignal::new();
+        let background = signal.session_token();
+        signal.cancel();
+        assert!(signal.is_cancelled());
+        assert!(!background.is_cancelled());
+        signal.clear();
+        assert!(!signal.is_cancelled());
+
+        // Shutdown cancels everything for good
+        signal.shutdown();
+        assert!(background.is_cancelled());
+        signal.clear();
+        assert!(signal.is_cancelled());
+    }
 }
@@ -1,7 +1,7 @@
 //! Agent loop error types
 
 use thiserror::Error;
-use uira_core::AgentError;
+use uira_core::{AgentError, ErrorKind, UiraError};
 
 #[derive(Debug, Error)]
 pub enum AgentLoopError {
@@ -49,3 +49,62 @@ impl AgentLoopError {
         }
     }
 }
+
+impl From<&AgentLoopError> for UiraError {
+    fn from(error: &AgentLoopError) -> Self {
+        let message = error.to_string();
+        match error {
+            AgentLoopError::Agent(e) => e.into(),
+            AgentLoopError::Provider(e) => e.into(),
+            AgentLoopError::Context(e) => e.into(),
+            AgentLoopError::Sandbox(e) => e.into(),
+            AgentLoopError::Approval(e) => e.into(),
+            AgentLoopError::Tool { .. } => {
+                UiraError::new(ErrorKind::ToolError, "tool.execution_failed", message)
+                    .with_retryable(true)
+            }
+            AgentLoopError::Configuration(_) => {
+                UiraError::new(ErrorKind::UserError, "agent.configuration", message)
+                    .with_remediation("Check the agent settings in uira.yml")
+            }
+            AgentLoopError::Io(_) => UiraError::internal("agent.io", message),
+            AgentLoopError::Cancelled => {
+                UiraError::new(ErrorKind::UserError, "agent.cancelled", message)
+            }
+            AgentLoopError::ApprovalTimeout { .. } => {
+                UiraError::new(ErrorKind::PolicyDenial, "approval.timeout", message)
+                    .with_retryable(true)
+                    .with_remediation(
+                        "Answer the approval prompt sooner, or raise the approval timeout",
+                    )
+            }
+            AgentLoopError::ToolForbidden { .. } => {
+                UiraError::new(ErrorKind::PolicyDenial, "tool.forbidden", message)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uira_providers::ProviderError;
+
+    #[test]
+    fn keeps_the_source_classification() {
+        let error = AgentLoopError::Provider(ProviderError::PaymentRequired {
+            message: "quota exhausted".to_string(),
+        });
+        let uira_error = UiraError::from(&error);
+        assert_eq!(uira_error.kind, ErrorKind::Budget);
+        assert_eq!(uira_error.code, "provider.payment_required");
+        // The provider message, not the loop's wrapper prefix
+        assert_eq!(uira_error.message, "payment required: quota exhausted");
+
+        let error = AgentLoopError::ToolForbidden {
+            tool: "bash".to_string(),
+            reason: "read-only agent".to_string(),
+        };
+        assert_eq!(UiraError::from(&error).kind, ErrorKind::PolicyDenial);
+    }
+}
@@ -1,13 +1,16 @@
 //! Recursive agent executor for subagent delegation
 
 use async_trait::async_trait;
+use futures::StreamExt;
 use std::sync::Arc;
 use std::time::Instant;
-use uira_core::{Provider, ThreadEvent};
-use uira_orchestration::AgentExecutor;
+use tokio_util::sync::CancellationToken;
+use uira_core::{Item, Provider, ThreadEvent};
+use uira_orchestration::background_agent::TaskLogger;
+use uira_orchestration::{AgentExecutor, DelegationContext};
 use uira_providers::{ModelClient, ModelClientBuilder, ProviderConfig};
 
-use crate::{Agent, AgentConfig, EventSender};
+use crate::{Agent, AgentConfig, EventSender, EventStream};
 
 const DEFAULT_MAX_DEPTH: usize = 3;
 
@@ -60,17 +63,32 @@ impl RecursiveAgentExecutor {
         }
     }
 
-    fn create_client(&self, model: &str) -> Result<Arc<dyn ModelClient>, String> {
+    /// Provider config for `model`, which may be on another provider than
+    /// the parent agent's
+    fn provider_config_for(&self, model: &str) -> ProviderConfig {
         let (provider, model_name) = parse_model_string(model);
 
         let mut config = self.config.provider_config.clone();
+        if provider != config.provider {
+            // The parent's key and endpoint belong to its own provider; the
+            // client picks up this provider's from the environment or the
+            // credential store instead
+            config.api_key = None;
+            config.base_url = None;
+            config.friendliai = None;
+        }
         config.provider = provider;
-        config.model = model_name.clone();
+        config.model = model_name;
+        config
+    }
+
+    fn create_client(&self, model: &str) -> Result<Arc<dyn ModelClient>, String> {
+        let config = self.provider_config_for(model);
 
         tracing::debug!(
             "Creating subagent client: provider={:?}, model={}, original_provider={:?}",
-            provider,
-            model_name,
+            config.provider,
+            config.model,
             self.config.provider_config.provider
         );
 
@@ -79,17 +97,45 @@ impl RecursiveAgentExecutor {
             .build()
             .map_err(|e| format!("Failed to create model client: {}", e))
     }
-}
 
-#[async_trait]
-impl AgentExecutor for RecursiveAgentExecutor {
-    async fn execute(
+    /// Copy the subagent's events into `log`, forwarding them to the
+    /// configured sender as before
+    fn log_events(&self, log: TaskLogger) -> EventSender {
+        let (sender, mut stream) = EventStream::channel(256);
+        let forward = self.config.event_sender.clone();
+        tokio::spawn(async move {
+            let mut text = String::new();
+            while let Some(event) = stream.next().await {
+                match &event {
+                    ThreadEvent::ContentDelta { delta } => text.push_str(delta),
+                    other => {
+                        flush_text(&log, &mut text);
+                        if let Some(line) = describe_event(other) {
+                            log.event(&line);
+                        }
+                    }
+                }
+                if let Some(ref forward) = forward {
+                    let _ = forward.send(event).await;
+                }
+            }
+            flush_text(&log, &mut text);
+        });
+        sender
+    }
+
+    async fn run(
         &self,
         prompt: &str,
         model: &str,
-        _allowed_tools: Option<Vec<String>>,
+        delegation: DelegationContext,
         max_turns: Option<usize>,
+        cancellation: CancellationToken,
+        log: Option<TaskLogger>,
     ) -> Result<String, String> {
+        if cancellation.is_cancelled() {
+            return Err("Subagent cancelled before it started".to_string());
+        }
         if self.current_depth >= self.config.max_depth {
             return Err(format!(
                 "Maximum delegation depth ({}) exceeded. Current depth: {}",
@@ -98,6 +144,24 @@ impl AgentExecutor for RecursiveAgentExecutor {
         }
 
         let client = self.create_client(model)?;
+        // Built up front so escalating only costs the replayed request
+        let fallback = delegation
+            .escalation_model
+            .as_deref()
+            .map(|model| self.create_client(model))
+            .transpose()?;
+        let consensus = delegation
+            .consensus
+            .as_ref()
+            .map(|panel| {
+                let clients = panel
+                    .models
+                    .iter()
+                    .map(|model| self.create_client(model))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Ok::<_, String>((clients, self.create_client(&panel.judge)?))
+            })
+            .transpose()?;
 
         let mut agent_config = self.config.agent_config.clone();
         agent_config.require_approval_for_writes = false;
@@ -106,11 +170,29 @@ impl AgentExecutor for RecursiveAgentExecutor {
         if let Some(turns) = max_turns {
             agent_config.max_turns = turns;
         }
+        let agent_name = delegation.agent.clone();
+        if let Some(contract) = delegation.output_contract.clone() {
+            agent_config.output_contract = contract;
+        }
+        agent_config.delegation = Some(delegation);
 
         let child_executor = Arc::new(self.child_executor());
-        let mut agent = Agent::new_with_executor(agent_config, client, Some(child_executor));
+        let mut agent = Agent::new_with_executor(agent_config, client, Some(child_executor))
+            .with_parent_cancellation(&cancellation);
+        if let Some(fallback) = fallback {
+            agent = agent.with_speculative_fallback(fallback);
+        }
+        if let Some((panel, judge)) = consensus {
+            // Keep a session log so the panel's disagreements can be reviewed
+            agent = agent
+                .with_consensus(panel, judge)
+                .with_session_recording()
+                .map_err(|e| format!("Failed to start session log: {}", e))?;
+        }
 
-        if let Some(ref sender) = self.config.event_sender {
+        if let Some(log) = log {
+            agent = agent.with_event_sender(self.log_events(log));
+        } else if let Some(ref sender) = self.config.event_sender {
             agent = agent.with_event_sender(sender.clone());
         }
 
@@ -121,7 +203,7 @@ impl AgentExecutor for RecursiveAgentExecutor {
             let _ = sender
                 .send(ThreadEvent::SubagentStarted {
                     task_id: task_id.clone(),
-                    agent_name: model.to_string(),
+                    agent_name,
                     model: model.to_string(),
                     session_id: session_id.clone(),
                 })
@@ -171,6 +253,76 @@ impl AgentExecutor for RecursiveAgentExecutor {
     }
 }
 
+#[async_trait]
+impl AgentExecutor for RecursiveAgentExecutor {
+    async fn execute(
+        &self,
+        prompt: &str,
+        model: &str,
+        delegation: DelegationContext,
+        max_turns: Option<usize>,
+        cancellation: CancellationToken,
+    ) -> Result<String, String> {
+        self.run(prompt, model, delegation, max_turns, cancellation, None)
+            .await
+    }
+
+    async fn execute_logged(
+        &self,
+        prompt: &str,
+        model: &str,
+        delegation: DelegationContext,
+        max_turns: Option<usize>,
+        cancellation: CancellationToken,
+        log: TaskLogger,
+    ) -> Result<String, String> {
+        self.run(
+            prompt,
+            model,
+            delegation,
+            max_turns,
+            cancellation,
+            Some(log),
+        )
+        .await
+    }
+}
+
+fn flush_text(log: &TaskLogger, text: &mut String) {
+    if text.trim().is_empty() {
+        text.clear();
+        return;
+    }
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+    log.output(text);
+    text.clear();
+}
+
+/// One-line log entry for the events worth showing in a task log
+fn describe_event(event: &ThreadEvent) -> Option<String> {
+    match event {
+        ThreadEvent::TurnStarted { turn_number } => Some(format!("turn {turn_number}")),
+        ThreadEvent::ItemStarted {
+            item: Item::ToolCall { name, .. },
+        } => Some(format!("tool: {name}")),
+        ThreadEvent::ItemCompleted {
+            item:
+                Item::ToolResult {
+                    output,
+                    is_error: true,
+                    ..
+                },
+        } => Some(format!(
+            "tool error: {}",
+            output.lines().next().unwrap_or_default()
+        )),
+        ThreadEvent::Error { message, .. } => Some(format!("error: {message}")),
+        _ => None,
+    }
+}
+
 fn parse_model_string(model: &str) -> (Provider, String) {
     if let Some((provider_str, model_name)) = model.split_once('/') {
         let provider = match provider_str.to_lowercase().as_str() {
@@ -180,6 +332,7 @@ fn parse_model_string(model: &str) -> (Provider, String) {
             "ollama" => Provider::Ollama,
             "opencode" => Provider::OpenCode,
             "openrouter" => Provider::OpenRouter,
+            "mock" => Provider::Mock,
             _ => Provider::Custom,
         };
         (provider, model_name.to_string())
@@ -201,6 +354,60 @@ fn parse_model_string(model: &str) -> (Provider, String) {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_describe_event() {
+        let tool = ThreadEvent::ItemStarted {
+            item: Item::ToolCall {
+                id: "1".to_string(),
+                name: "Read".to_string(),
+                input: serde_json::json!({}),
+            },
+        };
+        assert_eq!(describe_event(&tool).as_deref(), Some("tool: Read"));
+
+        let failed = ThreadEvent::ItemCompleted {
+            item: Item::ToolResult {
+                tool_call_id: "1".to_string(),
+                output: "no such file\nmore".to_string(),
+                is_error: true,
+            },
+        };
+        assert_eq!(
+            describe_event(&failed).as_deref(),
+            Some("tool error: no such file")
+        );
+
+        let delta = ThreadEvent::ContentDelta {
+            delta: "hi".to_string(),
+        };
+        assert_eq!(describe_event(&delta), None);
+    }
+
+    #[test]
+    fn test_delegated_model_on_another_provider_uses_its_own_credentials() {
+        let parent = ProviderConfig {
+            provider: Provider::Anthropic,
+            api_key: Some(uira_providers::SecretString::from("sk-ant-parent")),
+            base_url: Some("https://anthropic.proxy.example".to_string()),
+            max_tokens: Some(4096),
+            ..ProviderConfig::default()
+        };
+        let executor =
+            RecursiveAgentExecutor::new(ExecutorConfig::new(parent, AgentConfig::default()));
+
+        let same = executor.provider_config_for("anthropic/claude-3-5-haiku-latest");
+        assert_eq!(same.model, "claude-3-5-haiku-latest");
+        assert!(same.api_key.is_some());
+        assert!(same.base_url.is_some());
+
+        let other = executor.provider_config_for("openrouter/qwen/qwen3-coder");
+        assert_eq!(other.provider, Provider::OpenRouter);
+        assert_eq!(other.model, "qwen/qwen3-coder");
+        assert!(other.api_key.is_none());
+        assert!(other.base_url.is_none());
+        assert_eq!(other.max_tokens, Some(4096));
+    }
+
     #[test]
     fn test_parse_model_string() {
         let (provider, model) = parse_model_string("anthropic/claude-sonnet-4-20250514");
@@ -0,0 +1,168 @@
+//! A/B experiments over prompts and model routing
+//!
+//! Each enabled experiment in `uira.yml` assigns a new session one of its
+//! variants, in proportion to the variant weights. The variant's overrides
+//! are applied to the [`AgentConfig`] and its name is recorded in the session
+//! metadata, so outcomes can be compared per variant afterwards.
+
+use uira_core::schema::{ExperimentSettings, ExperimentVariant};
+
+use crate::AgentConfig;
+
+/// The variant a session runs under for one experiment
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExperimentAssignment {
+    pub experiment: String,
+    pub variant: ExperimentVariant,
+}
+
+/// Assign a variant of every enabled experiment. The same `seed` always
+/// gets the same variants; pass something unique per session.
+pub fn assign(experiments: &[ExperimentSettings], seed: &str) -> Vec<ExperimentAssignment> {
+    experiments
+        .iter()
+        .filter(|experiment| experiment.enabled)
+        .filter_map(|experiment| {
+            let total: u64 = experiment
+                .variants
+                .iter()
+                .map(|variant| u64::from(variant.weight))
+                .sum();
+            if total == 0 {
+                return None;
+            }
+            let mut bucket = fnv1a(&[seed, &experiment.name]) % total;
+            let variant = experiment.variants.iter().find(|variant| {
+                let weight = u64::from(variant.weight);
+                if bucket < weight {
+                    return true;
+                }
+                bucket -= weight;
+                false
+            })?;
+            Some(ExperimentAssignment {
+                experiment: experiment.name.clone(),
+                variant: variant.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Model requested by the assigned variants, if any; the first experiment
+/// listed wins when several set one
+pub fn model_override(assignments: &[ExperimentAssignment]) -> Option<&str> {
+    assignments
+        .iter()
+        .find_map(|assignment| assignment.variant.model.as_deref())
+}
+
+/// Apply the variants' prompt overrides to `config` and tag it with the
+/// assignments
+pub fn apply(mut config: AgentConfig, assignments: &[ExperimentAssignment]) -> AgentConfig {
+    for assignment in assignments {
+        let variant = &assignment.variant;
+        if let Some(prompt) = &variant.system_prompt {
+            config.system_prompt = Some(prompt.clone());
+        }
+        if let Some(append) = &variant.prompt_append {
+            config.additional_context.push(append.clone());
+        }
+        config
+            .experiments
+            .insert(assignment.experiment.clone(), variant.name.clone());
+    }
+    config
+}
+
+/// 64-bit FNV-1a, stable across builds so assignments can be reproduced
+fn fnv1a(parts: &[&str]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn variant(name: &str, weight: u32) -> ExperimentVariant {
+        ExperimentVariant {
+            name: name.to_string(),
+            weight,
+            ..ExperimentVariant::default()
+        }
+    }
+
+    fn experiment(name: &str, variants: Vec<ExperimentVariant>) -> ExperimentSettings {
+        ExperimentSettings {
+            name: name.to_string(),
+            enabled: true,
+            variants,
+        }
+    }
+
+    #[test]
+    fn assignment_is_stable_and_follows_weights() {
+        let experiments = vec![experiment(
+            "prompt",
+            vec![
+                variant("control", 3),
+                variant("off", 0),
+                variant("terse", 1),
+            ],
+        )];
+
+        let first = assign(&experiments, "session-1");
+        assert_eq!(first, assign(&experiments, "session-1"));
+
+        let terse = (0..400)
+            .map(|i| assign(&experiments, &format!("session-{i}")))
+            .filter(|assigned| assigned[0].variant.name == "terse")
+            .count();
+        assert!((60..140).contains(&terse), "terse assigned {terse} times");
+        assert!((0..400).all(|i| assign(&experiments, &i.to_string())[0].variant.name != "off"));
+    }
+
+    #[test]
+    fn skips_disabled_and_empty_experiments() {
+        let mut disabled = experiment("disabled", vec![variant("a", 1)]);
+        disabled.enabled = false;
+        let experiments = vec![
+            disabled,
+            experiment("empty", vec![]),
+            experiment("zero", vec![variant("a", 0)]),
+        ];
+        assert!(assign(&experiments, "seed").is_empty());
+    }
+
+    #[test]
+    fn applies_overrides_and_tags_config() {
+        let assignments = vec![
+            ExperimentAssignment {
+                experiment: "prompt".to_string(),
+                variant: ExperimentVariant {
+                    prompt_append: Some("Be terse.".to_string()),
+                    ..variant("terse", 1)
+                },
+            },
+            ExperimentAssignment {
+                experiment: "routing".to_string(),
+                variant: ExperimentVariant {
+                    model: Some("claude-haiku-4-5".to_string()),
+                    ..variant("haiku", 1)
+                },
+            },
+        ];
+
+        let config = apply(AgentConfig::default(), &assignments);
+        assert_eq!(config.additional_context, vec!["Be terse.".to_string()]);
+        assert_eq!(config.experiments["prompt"], "terse");
+        assert_eq!(config.experiments["routing"], "haiku");
+        assert_eq!(model_override(&assignments), Some("claude-haiku-4-5"));
+    }
+}
@@ -2,7 +2,7 @@
 
 use crate::events::EventSender;
 use futures::future::join_all;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use uira_core::schema::GoalConfig;
 use uira_core::ThreadEvent;
 use uira_orchestration::hooks::{GoalCheckResult, GoalRunner, VerificationResult};
@@ -40,6 +40,13 @@ impl GoalVerifier {
         self
     }
 
+    /// Pass the files changed since the last check, relative to the project
+    /// root, to goal commands so they can check only affected targets
+    pub fn with_changed_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.runner = self.runner.with_changed_files(files);
+        self
+    }
+
     /// Verify all goals
     ///
     /// Emits events during verification:
@@ -148,10 +155,12 @@ mod tests {
             name: name.to_string(),
             workspace: None,
             command: command.to_string(),
+            changed_command: None,
             target,
             timeout_secs: 10,
             enabled: true,
             description: None,
+            shell: None,
         }
     }
 
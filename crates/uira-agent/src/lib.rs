@@ -7,19 +7,24 @@
 //! - Context management
 //! - Event streaming
 //! - Session persistence (JSONL session log)
+//! - PII scrubbing (opt-in privacy mode)
 
 mod agent;
 pub mod approval;
+pub mod bench;
 mod config;
-pub mod context;
 pub mod continuation;
 mod control;
 mod error;
 pub mod event_system;
 pub mod events;
 mod executor;
+pub mod experiments;
 pub mod goals;
+pub mod output_contract;
+pub mod privacy;
 pub mod ralph;
+pub mod retention;
 pub mod session;
 mod session_state;
 pub mod streaming;
@@ -34,21 +39,26 @@ pub use approval::{
     approval_channel, ApprovalCache, ApprovalError, ApprovalKey, ApprovalPending, ApprovalReceiver,
     ApprovalSender, CacheDecision, CachedApproval,
 };
+pub use bench::{load_tasks, BenchHarness, BenchReport, BenchTarget, BenchTask};
 pub use config::AgentConfig;
 pub use context::*;
 pub use continuation::{generate_continuation, is_completion_signal, ContinuationMessage};
-pub use control::AgentControl;
+pub use control::{AgentControl, CancelSignal};
 pub use error::AgentLoopError;
 pub use event_system::{create_event_system, EventSystem};
 pub use events::{EventSender, EventStream};
 pub use executor::{ExecutorConfig, RecursiveAgentExecutor};
 pub use goals::GoalVerifier;
-pub use ralph::{RalphConfig, RalphController, RalphDecision};
-pub use session::{EventWrapper, SessionItem, SessionMessage, SessionMetaLine, SessionRecorder};
+pub use privacy::PiiScrubber;
+pub use ralph::{RalphConfig, RalphController, RalphDecision, RalphExitReason};
+pub use session::{
+    EventWrapper, RecoveryReport, SessionItem, SessionMessage, SessionMetaLine, SessionRecorder,
+};
 pub use session_state::Session;
 pub use streaming::{StreamController, StreamOutput};
 pub use telemetry::*;
 pub use turn::{TurnContext, TurnState};
+pub use uira_agent_core::context;
 
 #[derive(Debug, Clone)]
 pub struct BranchInfo {
@@ -84,6 +94,11 @@ pub enum AgentCommand {
         branch_name: String,
         response_tx: tokio::sync::oneshot::Sender<Result<SwitchBranchResult, String>>,
     },
+    /// Merge another branch's notepad into the current branch's
+    MergeNotes {
+        branch_name: String,
+        response_tx: tokio::sync::oneshot::Sender<Result<String, String>>,
+    },
     ListBranches {
         response_tx: tokio::sync::oneshot::Sender<Result<Vec<BranchInfo>, String>>,
     },
@@ -0,0 +1,256 @@
+//! Output contracts for final messages
+//!
+//! An agent with a non-freeform [`OutputContract`] is told the format up
+//! front, and its final message is checked before the run completes. A
+//! violation is sent back once as a repair prompt; the run fails if the next
+//! final message still breaks the contract.
+
+use serde_json::Value;
+use uira_core::protocol::validate_json;
+use uira_core::schema::OutputContract;
+
+/// System prompt section describing the contract, `None` for freeform
+pub fn instructions(contract: &OutputContract) -> Option<String> {
+    let body = match contract {
+        OutputContract::Freeform => return None,
+        OutputContract::UnifiedDiff => {
+            "Your final message must be a unified diff and nothing else: \
+             `--- a/<path>` and `+++ b/<path>` headers followed by `@@` hunks whose line counts \
+             match their contents. Make your changes with tools as usual, then end with the diff \
+             of everything you changed. Do not add prose before or after it."
+                .to_string()
+        }
+        OutputContract::Json { schema } => {
+            let mut body = "Your final message must be a single JSON value and nothing else. \
+                 Do not add prose before or after it."
+                .to_string();
+            if let Some(schema) = schema {
+                body.push_str(&format!(
+                    " It must match this JSON Schema:\n\n```json\n{}\n```",
+                    serde_json::to_string_pretty(schema).unwrap_or_default()
+                ));
+            }
+            body
+        }
+    };
+    Some(format!("## Output Contract\n\n{}", body))
+}
+
+/// Check a final message, returning it without a surrounding code fence
+pub fn check(contract: &OutputContract, output: &str) -> Result<String, String> {
+    match contract {
+        OutputContract::Freeform => Ok(output.to_string()),
+        OutputContract::UnifiedDiff => {
+            let body = strip_fence(output, &["diff", "patch"]);
+            check_unified_diff(body)?;
+            Ok(format!("{}\n", body))
+        }
+        OutputContract::Json { schema } => {
+            let body = strip_fence(output, &["json"]);
+            let value: Value =
+                serde_json::from_str(body).map_err(|e| format!("not valid JSON: {}", e))?;
+            if let Some(schema) = schema {
+                validate_json(schema, &value).map_err(|violations| {
+                    violations
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                })?;
+            }
+            Ok(body.to_string())
+        }
+    }
+}
+
+/// Follow-up asking the model to restate its final message within the contract
+pub fn repair_prompt(contract: &OutputContract, violation: &str) -> String {
+    format!(
+        "Your final message does not follow the {} output contract: {}. \
+         Reply again with only the corrected final message.",
+        contract.name(),
+        violation
+    )
+}
+
+/// `output` without surrounding whitespace or a code fence tagged with one of
+/// `languages` (or untagged) wrapping all of it
+fn strip_fence<'a>(output: &'a str, languages: &[&str]) -> &'a str {
+    let trimmed = output.trim();
+    let Some(rest) = trimmed.strip_prefix("```") else {
+        return trimmed;
+    };
+    let Some((language, body)) = rest.split_once('\n') else {
+        return trimmed;
+    };
+    let language = language.trim();
+    if !language.is_empty() && !languages.contains(&language) {
+        return trimmed;
+    }
+    match body.trim_end().strip_suffix("```") {
+        Some(body) => body.trim_matches('\n'),
+        None => trimmed,
+    }
+}
+
+/// Lines between a file's `diff --git` and its `---` header
+const EXTENDED_HEADERS: &[&str] = &[
+    "diff --git ",
+    "index ",
+    "new file mode ",
+    "deleted file mode ",
+    "old mode ",
+    "new mode ",
+    "similarity index ",
+    "dissimilarity index ",
+    "rename from ",
+    "rename to ",
+    "copy from ",
+    "copy to ",
+    "Binary files ",
+];
+
+fn check_unified_diff(body: &str) -> Result<(), String> {
+    let mut hunks = 0;
+    let mut old_left = 0usize;
+    let mut new_left = 0usize;
+    let mut hunk_start = 0;
+
+    for (index, line) in body.lines().enumerate() {
+        let number = index + 1;
+        if old_left > 0 || new_left > 0 {
+            match line.chars().next() {
+                Some(' ') | None => {
+                    old_left = old_left.saturating_sub(1);
+                    new_left = new_left.saturating_sub(1);
+                }
+                Some('-') if old_left > 0 => old_left -= 1,
+                Some('+') if new_left > 0 => new_left -= 1,
+                Some('\\') => {}
+                _ => {
+                    return Err(format!(
+                        "hunk at line {} ends before its header's line counts are reached (line {}: {:?})",
+                        hunk_start, number, line
+                    ))
+                }
+            }
+            continue;
+        }
+
+        if line.starts_with("@@ ") {
+            let (old, new) = parse_hunk_header(line)
+                .ok_or_else(|| format!("line {}: malformed hunk header {:?}", number, line))?;
+            old_left = old;
+            new_left = new;
+            hunk_start = number;
+            hunks += 1;
+        } else if !(line.trim().is_empty()
+            || line.starts_with("--- ")
+            || line.starts_with("+++ ")
+            || line.starts_with('\\')
+            || EXTENDED_HEADERS
+                .iter()
+                .any(|header| line.starts_with(header)))
+        {
+            return Err(format!(
+                "line {} is not part of a unified diff: {:?}",
+                number, line
+            ));
+        }
+    }
+
+    if old_left > 0 || new_left > 0 {
+        return Err(format!(
+            "hunk at line {} is missing {} old and {} new lines",
+            hunk_start, old_left, new_left
+        ));
+    }
+    if hunks == 0 {
+        return Err("no `@@` hunks found".to_string());
+    }
+    Ok(())
+}
+
+/// Old and new line counts of a `@@ -a,b +c,d @@` header
+fn parse_hunk_header(line: &str) -> Option<(usize, usize)> {
+    let ranges = line.strip_prefix("@@ ")?.split(" @@").next()?;
+    let (old, new) = ranges.split_once(' ')?;
+    let count = |range: &str| -> Option<usize> {
+        match range.split_once(',') {
+            Some((start, count)) => {
+                start.parse::<usize>().ok()?;
+                count.parse().ok()
+            }
+            None => range.parse::<usize>().ok().map(|_| 1),
+        }
+    };
+    Some((
+        count(old.strip_prefix('-')?)?,
+        count(new.strip_prefix('+')?)?,
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    const DIFF: &str = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1,2 +1,2 @@\n fn main() {\n-    old();\n+    new();\n";
+
+    #[test]
+    fn test_unified_diff_contract() {
+        let contract = OutputContract::UnifiedDiff;
+        assert_eq!(check(&contract, DIFF).unwrap(), DIFF);
+
+        let fenced = format!("```diff\n{}```\n", DIFF);
+        assert_eq!(check(&contract, &fenced).unwrap(), DIFF);
+
+        let with_prose = format!("Here is the change:\n\n{}", DIFF);
+        let err = check(&contract, &with_prose).unwrap_err();
+        assert!(
+            err.contains("line 1 is not part of a unified diff"),
+            "{err}"
+        );
+
+        let miscounted = DIFF.replace("@@ -1,2 +1,2 @@", "@@ -1,3 +1,2 @@");
+        let err = check(&contract, &miscounted).unwrap_err();
+        assert!(err.contains("missing 1 old"), "{err}");
+
+        assert_eq!(
+            check(&contract, "--- a/x\n+++ b/x\n").unwrap_err(),
+            "no `@@` hunks found"
+        );
+    }
+
+    #[test]
+    fn test_json_contract() {
+        let contract = OutputContract::Json {
+            schema: Some(json!({
+                "type": "object",
+                "properties": { "verdict": { "type": "string", "enum": ["approve", "reject"] } },
+                "required": ["verdict"]
+            })),
+        };
+        assert_eq!(
+            check(&contract, "```json\n{\"verdict\": \"approve\"}\n```").unwrap(),
+            "{\"verdict\": \"approve\"}"
+        );
+
+        let err = check(&contract, "Looks good to me!").unwrap_err();
+        assert!(err.starts_with("not valid JSON"), "{err}");
+
+        let err = check(&contract, "{\"verdict\": \"maybe\"}").unwrap_err();
+        assert!(err.starts_with("/verdict: must be one of"), "{err}");
+
+        assert!(check(&OutputContract::Json { schema: None }, "[1, 2]").is_ok());
+    }
+
+    #[test]
+    fn test_freeform_has_no_instructions() {
+        assert_eq!(instructions(&OutputContract::Freeform), None);
+        assert_eq!(check(&OutputContract::Freeform, " any ").unwrap(), " any ");
+        assert!(instructions(&OutputContract::UnifiedDiff)
+            .unwrap()
+            .starts_with("## Output Contract"));
+    }
+}
@@ -0,0 +1,483 @@
+//! Conversation-level PII scrubbing.
+//!
+//! When privacy mode is enabled, every message is passed through a
+//! [`PiiScrubber`] before it is sent to the provider or written to the session
+//! log. Detected values are swapped for stable placeholders such as
+//! `<EMAIL_1>`, and the placeholder mapping is kept in a local JSON file so
+//! model output (and recorded sessions) can be de-anonymized.
+
+use regex::Regex;
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
+use uira_core::schema::PrivacySettings;
+use uira_core::{ContentBlock, Message, MessageContent, ModelResponse, ToolCall, UIRA_DIR};
+
+const EMAIL_PATTERN: &str = r"[A-Za-z0-9._%+-]+@[A-Za-z0-9-]+(?:\.[A-Za-z0-9-]+)*\.[A-Za-z]{2,}";
+const IPV4_PATTERN: &str = r"\b(?:(?:25[0-5]|2[0-4]\d|1?\d?\d)\.){3}(?:25[0-5]|2[0-4]\d|1?\d?\d)\b";
+const IPV6_PATTERN: &str = r"\b(?:[0-9A-Fa-f]{1,4}:){7}[0-9A-Fa-f]{1,4}\b|\b(?:[0-9A-Fa-f]{1,4}:){1,6}:(?:[0-9A-Fa-f]{1,4}(?::[0-9A-Fa-f]{1,4})*)?\b";
+const PLACEHOLDER_PATTERN: &str = r"<([A-Z][A-Z0-9_]*)_(\d+)>";
+
+/// Default mapping file location, relative to the working directory
+pub fn default_mapping_path(cwd: &Path) -> PathBuf {
+    cwd.join(UIRA_DIR).join("privacy").join("mapping.json")
+}
+
+struct Detector {
+    label: String,
+    regex: Regex,
+    /// Extra check for patterns that would otherwise match code (e.g. `add::`)
+    accept: fn(&str) -> bool,
+}
+
+fn accept_any(_: &str) -> bool {
+    true
+}
+
+fn accept_ipv6(candidate: &str) -> bool {
+    candidate.chars().any(|c| c.is_ascii_digit()) && candidate.matches(':').count() >= 2
+}
+
+/// Replaces PII with placeholders and restores it from the persisted mapping
+pub struct PiiScrubber {
+    detectors: Vec<Detector>,
+    placeholder: Regex,
+    /// Placeholder -> original value
+    mapping: BTreeMap<String, String>,
+    /// Original value -> placeholder
+    reverse: HashMap<String, String>,
+    counters: HashMap<String, usize>,
+    mapping_path: Option<PathBuf>,
+}
+
+impl PiiScrubber {
+    /// Build a scrubber from config, loading any existing mapping file.
+    ///
+    /// Relative `mapping_file` paths are resolved against `cwd`. Invalid custom
+    /// patterns are skipped with a warning.
+    pub fn new(settings: &PrivacySettings, cwd: &Path) -> Self {
+        let mapping_path = match settings.mapping_file.as_deref() {
+            Some(path) => cwd.join(path),
+            None => default_mapping_path(cwd),
+        };
+
+        let mut scrubber = Self::with_settings(settings);
+        scrubber.load_mapping(&mapping_path);
+        scrubber.mapping_path = Some(mapping_path);
+        scrubber
+    }
+
+    /// Build a scrubber that keeps its mapping in memory only
+    pub fn in_memory(settings: &PrivacySettings) -> Self {
+        Self::with_settings(settings)
+    }
+
+    fn with_settings(settings: &PrivacySettings) -> Self {
+        let mut detectors = Vec::new();
+
+        if settings.emails {
+            detectors.push(builtin_detector("EMAIL", EMAIL_PATTERN, accept_any));
+        }
+        if settings.ip_addresses {
+            detectors.push(builtin_detector("IP", IPV4_PATTERN, accept_any));
+            detectors.push(builtin_detector("IP", IPV6_PATTERN, accept_ipv6));
+        }
+
+        let mut names: Vec<&str> = settings
+            .names
+            .iter()
+            .map(|name| name.trim())
+            .filter(|name| !name.is_empty())
+            .collect();
+        if !names.is_empty() {
+            // Longest first so "Ada Lovelace" wins over "Ada"
+            names.sort_by_key(|name| std::cmp::Reverse(name.len()));
+            let alternation = names
+                .iter()
+                .map(|name| regex::escape(name))
+                .collect::<Vec<_>>()
+                .join("|");
+            detectors.push(builtin_detector(
+                "NAME",
+                &format!(r"(?i)\b(?:{})\b", alternation),
+                accept_any,
+            ));
+        }
+
+        for pattern in &settings.patterns {
+            let label = normalize_label(&pattern.label);
+            match Regex::new(&pattern.pattern) {
+                Ok(regex) if !label.is_empty() => detectors.push(Detector {
+                    label,
+                    regex,
+                    accept: accept_any,
+                }),
+                Ok(_) => {
+                    tracing::warn!(pattern = %pattern.pattern, "Skipping PII pattern without a label")
+                }
+                Err(e) => {
+                    tracing::warn!(label = %pattern.label, "Skipping invalid PII pattern: {}", e)
+                }
+            }
+        }
+
+        Self {
+            detectors,
+            placeholder: Regex::new(PLACEHOLDER_PATTERN).expect("valid placeholder pattern"),
+            mapping: BTreeMap::new(),
+            reverse: HashMap::new(),
+            counters: HashMap::new(),
+            mapping_path: None,
+        }
+    }
+
+    /// Path of the persisted mapping file, if any
+    pub fn mapping_path(&self) -> Option<&Path> {
+        self.mapping_path.as_deref()
+    }
+
+    /// Placeholder -> original value for everything scrubbed so far
+    pub fn mapping(&self) -> &BTreeMap<String, String> {
+        &self.mapping
+    }
+
+    /// Replace every detected value in `text` with its placeholder
+    pub fn scrub(&mut self, text: &str) -> String {
+        let mut matches: Vec<(usize, usize, usize)> = Vec::new();
+        for (index, detector) in self.detectors.iter().enumerate() {
+            for m in detector.regex.find_iter(text) {
+                if !m.is_empty() && (detector.accept)(m.as_str()) {
+                    matches.push((m.start(), m.end(), index));
+                }
+            }
+        }
+        if matches.is_empty() {
+            return text.to_string();
+        }
+
+        // Earliest match wins; on ties prefer the longer one, then detector order
+        matches.sort_by(|a, b| a.0.cmp(&b.0).then(b.1.cmp(&a.1)).then(a.2.cmp(&b.2)));
+
+        let mut output = String::with_capacity(text.len());
+        let mut cursor = 0;
+        let mut added = false;
+        for (start, end, index) in matches {
+            if start < cursor {
+                continue;
+            }
+            output.push_str(&text[cursor..start]);
+            let (placeholder, is_new) = self.placeholder_for(index, &text[start..end]);
+            added |= is_new;
+            output.push_str(&placeholder);
+            cursor = end;
+        }
+        output.push_str(&text[cursor..]);
+
+        if added {
+            self.save_mapping();
+        }
+        output
+    }
+
+    /// Replace known placeholders in `text` with their original values
+    pub fn restore(&self, text: &str) -> String {
+        if self.mapping.is_empty() {
+            return text.to_string();
+        }
+        self.placeholder
+            .replace_all(text, |caps: &regex::Captures| {
+                self.mapping
+                    .get(&caps[0])
+                    .cloned()
+                    .unwrap_or_else(|| caps[0].to_string())
+            })
+            .into_owned()
+    }
+
+    /// Scrub the text, tool inputs, and tool results of a message.
+    ///
+    /// Thinking blocks come from the model (which only ever saw placeholders)
+    /// and carry provider signatures, so they are left untouched.
+    pub fn scrub_message(&mut self, message: &Message) -> Message {
+        let mut scrubbed = message.clone();
+        scrubbed.content = match &message.content {
+            MessageContent::Text(text) => MessageContent::Text(self.scrub(text)),
+            MessageContent::Blocks(blocks) => {
+                MessageContent::Blocks(blocks.iter().map(|block| self.scrub_block(block)).collect())
+            }
+            MessageContent::ToolCalls(calls) => MessageContent::ToolCalls(
+                calls
+                    .iter()
+                    .map(|call| ToolCall {
+                        input: self.scrub_value(&call.input),
+                        ..call.clone()
+                    })
+                    .collect(),
+            ),
+        };
+        scrubbed
+    }
+
+    pub fn scrub_messages(&mut self, messages: &[Message]) -> Vec<Message> {
+        messages
+            .iter()
+            .map(|message| self.scrub_message(message))
+            .collect()
+    }
+
+    /// Inverse of [`scrub_message`](Self::scrub_message)
+    pub fn restore_message(&self, message: &Message) -> Message {
+        let mut restored = message.clone();
+        restored.content = match &message.content {
+            MessageContent::Text(text) => MessageContent::Text(self.restore(text)),
+            MessageContent::Blocks(blocks) => MessageContent::Blocks(
+                blocks
+                    .iter()
+                    .map(|block| self.restore_block(block))
+                    .collect(),
+            ),
+            MessageContent::ToolCalls(calls) => MessageContent::ToolCalls(
+                calls
+                    .iter()
+                    .map(|call| ToolCall {
+                        input: self.restore_value(&call.input),
+                        ..call.clone()
+                    })
+                    .collect(),
+            ),
+        };
+        restored
+    }
+
+    /// De-anonymize model output so tools and the user see real values
+    pub fn restore_response(&self, mut response: ModelResponse) -> ModelResponse {
+        response.content = response
+            .content
+            .iter()
+            .map(|block| self.restore_block(block))
+            .collect();
+        response
+    }
+
+    pub fn scrub_value(&mut self, value: &Value) -> Value {
+        match value {
+            Value::String(s) => Value::String(self.scrub(s)),
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|item| self.scrub_value(item)).collect())
+            }
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, item)| (key.clone(), self.scrub_value(item)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+
+    pub fn restore_value(&self, value: &Value) -> Value {
+        match value {
+            Value::String(s) => Value::String(self.restore(s)),
+            Value::Array(items) => {
+                Value::Array(items.iter().map(|item| self.restore_value(item)).collect())
+            }
+            Value::Object(map) => Value::Object(
+                map.iter()
+                    .map(|(key, item)| (key.clone(), self.restore_value(item)))
+                    .collect(),
+            ),
+            other => other.clone(),
+        }
+    }
+
+    fn scrub_block(&mut self, block: &ContentBlock) -> ContentBlock {
+        match block {
+            ContentBlock::Text { text } => ContentBlock::Text {
+                text: self.scrub(text),
+            },
+            ContentBlock::ToolUse { id, name, input } => ContentBlock::ToolUse {
+                id: id.clone(),
+                name: name.clone(),
+                input: self.scrub_value(input),
+            },
+            ContentBlock::ToolResult {
+                tool_use_id,
+                content,
+                is_error,
+            } => ContentBlock::ToolResult {
+                tool_use_id: tool_use_id.clone(),
+                content: self.scrub(content),
+                is_error: *is_error,
+            },
+            other => other.clone(),
+        }
+    }
+
+    fn restore_block(&self, block: &ContentBlock) -> ContentBlock {
+        match block {
+            ContentBlock::Text { text } => ContentBlock::Text {
+                text: self.restore(text),
+            },
+            ContentBlock::ToolUse { id, name, input } => ContentBlock::ToolUse {
+                id: id.clone(),
+                name: name.clone(),
+                input: self.restore_value(input),
+            },
+            ContentBlock::ToolResult {
+                tool_use_id,
+                content,
+                is_error,
+            } => ContentBlock::ToolResult {
+                tool_use_id: tool_use_id.clone(),
+                content: self.restore(content),
+                is_error: *is_error,
+            },
+            other => other.clone(),
+        }
+    }
+
+    fn placeholder_for(&mut self, detector: usize, original: &str) -> (String, bool) {
+        if let Some(existing) = self.reverse.get(original) {
+            return (existing.clone(), false);
+        }
+
+        let label = self.detectors[detector].label.clone();
+        let counter = self.counters.entry(label.clone()).or_insert(0);
+        *counter += 1;
+        let placeholder = format!("<{}_{}>", label, counter);
+
+        self.mapping
+            .insert(placeholder.clone(), original.to_string());
+        self.reverse
+            .insert(original.to_string(), placeholder.clone());
+        (placeholder, true)
+    }
+
+    fn load_mapping(&mut self, path: &Path) {
+        let Ok(content) = std::fs::read_to_string(path) else {
+            return;
+        };
+        let mapping: BTreeMap<String, String> = match serde_json::from_str(&content) {
+            Ok(mapping) => mapping,
+            Err(e) => {
+                tracing::warn!(path = %path.display(), "Ignoring unreadable PII mapping: {}", e);
+                return;
+            }
+        };
+
+        for (placeholder, original) in mapping {
+            if let Some(caps) = self.placeholder.captures(&placeholder) {
+                let index: usize = caps[2].parse().unwrap_or(0);
+                let counter = self.counters.entry(caps[1].to_string()).or_insert(0);
+                *counter = (*counter).max(index);
+            }
+            self.reverse.insert(original.clone(), placeholder.clone());
+            self.mapping.insert(placeholder, original);
+        }
+    }
+
+    fn save_mapping(&self) {
+        let Some(path) = self.mapping_path.as_deref() else {
+            return;
+        };
+        let result = (|| -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let json = serde_json::to_string_pretty(&self.mapping)?;
+            std::fs::write(path, json)
+        })();
+        if let Err(e) = result {
+            tracing::warn!(path = %path.display(), "Failed to save PII mapping: {}", e);
+        }
+    }
+}
+
+fn builtin_detector(label: &str, pattern: &str, accept: fn(&str) -> bool) -> Detector {
+    Detector {
+        label: label.to_string(),
+        regex: Regex::new(pattern).expect("valid built-in PII pattern"),
+        accept,
+    }
+}
+
+fn normalize_label(label: &str) -> String {
+    label
+        .trim()
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_uppercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>()
+        .trim_start_matches(|c: char| !c.is_ascii_alphabetic())
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uira_core::schema::PiiPatternConfig;
+
+    fn settings() -> PrivacySettings {
+        PrivacySettings {
+            enabled: true,
+            names: vec!["Ada Lovelace".to_string(), "Ada".to_string()],
+            patterns: vec![PiiPatternConfig {
+                label: "account".to_string(),
+                pattern: r"ACCT-\d{6}".to_string(),
+            }],
+            ..PrivacySettings::default()
+        }
+    }
+
+    #[test]
+    fn scrubs_and_restores_with_stable_placeholders() {
+        let mut scrubber = PiiScrubber::in_memory(&settings());
+        let text = "Mail ada@example.com from 10.0.0.12 about ACCT-123456, cc ada@example.com. \
+                    Ada Lovelace and Ada agreed. Leave std::fmt and add::x alone; ping fe80::1.";
+
+        let scrubbed = scrubber.scrub(text);
+        assert_eq!(
+            scrubbed,
+            "Mail <EMAIL_1> from <IP_1> about <ACCOUNT_1>, cc <EMAIL_1>. \
+             <NAME_1> and <NAME_2> agreed. Leave std::fmt and add::x alone; ping <IP_2>."
+        );
+        assert_eq!(scrubber.restore(&scrubbed), text);
+        assert_eq!(scrubber.restore("<EMAIL_9> stays"), "<EMAIL_9> stays");
+    }
+
+    #[test]
+    fn mapping_file_persists_across_scrubbers() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let mut first = PiiScrubber::new(&settings(), dir.path());
+        let message = Message::tool_result("call_1", "owner: bob@example.com");
+        let scrubbed = first.scrub_message(&message);
+        assert_eq!(scrubbed.content.as_text(), Some("owner: <EMAIL_1>"));
+        assert!(default_mapping_path(dir.path()).exists());
+
+        let mut second = PiiScrubber::new(&settings(), dir.path());
+        assert_eq!(second.scrub("carol@example.com"), "<EMAIL_2>");
+        assert_eq!(second.restore("<EMAIL_1>"), "bob@example.com");
+
+        let response = ModelResponse {
+            id: "msg".to_string(),
+            model: "mock".to_string(),
+            content: vec![ContentBlock::tool_use(
+                "call_2",
+                "bash",
+                serde_json::json!({ "command": "mail <EMAIL_2>" }),
+            )],
+            stop_reason: None,
+            usage: Default::default(),
+        };
+        let restored = second.restore_response(response);
+        assert_eq!(
+            restored.tool_calls()[0].input["command"],
+            "mail carol@example.com"
+        );
+    }
+}
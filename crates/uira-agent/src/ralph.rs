@@ -6,8 +6,13 @@ use crate::config::AgentGoalsConfig;
 use crate::events::EventSender;
 use crate::goals::GoalVerifier;
 use chrono::Utc;
-use std::path::Path;
-use uira_core::{ThreadEvent, TodoStatus};
+use std::collections::hash_map::DefaultHasher;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use uira_core::{ThreadEvent, TodoStatus, TokenUsage};
+use uira_orchestration::features::analytics::CostEstimator;
+use uira_orchestration::features::snapshots::{Snapshot, SnapshotStore};
 use uira_orchestration::hooks::hooks::circuit_breaker::CircuitBreakerConfig;
 use uira_orchestration::hooks::hooks::ralph::{RalphHook, RalphOptions, RalphState};
 use uira_orchestration::hooks::VerificationResult;
@@ -20,6 +25,18 @@ pub struct RalphConfig {
     pub min_confidence: u32,
     pub require_dual_condition: bool,
     pub circuit_breaker: CircuitBreakerConfig,
+    /// Stop once the loop has used this many tokens
+    pub max_tokens: Option<u64>,
+    /// Stop once the loop's estimated cost reaches this many USD
+    pub max_cost_usd: Option<f64>,
+    /// Stop after this many iterations in a row with the same goal scores (0 disables)
+    pub stall_iterations: u32,
+    /// Stop after the working tree flips back to the state before last this
+    /// many times in a row (0 disables)
+    pub oscillation_limit: u32,
+    /// Snapshot the working tree each verified iteration and roll back to the
+    /// best-scoring one when a guardrail stops the loop
+    pub snapshots: bool,
 }
 
 impl Default for RalphConfig {
@@ -30,10 +47,184 @@ impl Default for RalphConfig {
             min_confidence: 50,
             require_dual_condition: true,
             circuit_breaker: CircuitBreakerConfig::default(),
+            max_tokens: None,
+            max_cost_usd: None,
+            stall_iterations: 3,
+            oscillation_limit: 2,
+            snapshots: true,
         }
     }
 }
 
+/// Why a ralph loop stopped before the task completed
+#[derive(Debug, Clone, PartialEq)]
+pub enum RalphExitReason {
+    /// The stagnation circuit breaker tripped
+    CircuitBreaker(String),
+    /// The iteration limit was reached
+    MaxIterations(u32),
+    /// Token usage reached `max_tokens`
+    TokenBudget { used: u64, limit: u64 },
+    /// Estimated cost reached `max_cost_usd`
+    CostBudget { spent: f64, limit: f64 },
+    /// Goal scores stayed the same for this many iterations
+    NoProgress { iterations: u32 },
+    /// Changes kept being undone, returning the tree to an earlier state
+    Oscillation { flips: u32 },
+}
+
+impl fmt::Display for RalphExitReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RalphExitReason::CircuitBreaker(reason) => write!(f, "{}", reason),
+            RalphExitReason::MaxIterations(max) => write!(f, "Max iterations ({}) reached", max),
+            RalphExitReason::TokenBudget { used, limit } => {
+                write!(f, "Token budget exhausted ({} of {} tokens)", used, limit)
+            }
+            RalphExitReason::CostBudget { spent, limit } => write!(
+                f,
+                "Cost budget exhausted ({} of {})",
+                CostEstimator::format_cost(*spent),
+                CostEstimator::format_cost(*limit)
+            ),
+            RalphExitReason::NoProgress { iterations } => {
+                write!(f, "Goal scores unchanged for {} iterations", iterations)
+            }
+            RalphExitReason::Oscillation { flips } => write!(
+                f,
+                "Changes flip-flopped between two states {} times in a row",
+                flips
+            ),
+        }
+    }
+}
+
+/// Per-loop usage and progress tracking behind the ralph guardrails
+#[derive(Debug, Default)]
+struct Guardrails {
+    tokens_used: u64,
+    cost_usd: f64,
+    last_scores: Option<Vec<(String, f64)>>,
+    iterations_at_scores: u32,
+    /// Fingerprints of the last two working tree states, oldest first
+    recent_trees: Vec<u64>,
+    flips: u32,
+}
+
+impl Guardrails {
+    fn record_usage(&mut self, usage: &TokenUsage, model: &str) {
+        self.tokens_used += usage.total();
+        self.cost_usd += CostEstimator::estimate_cost(
+            usage.input_tokens as usize,
+            usage.output_tokens as usize,
+            model,
+        );
+    }
+
+    fn check_budget(&self, config: &RalphConfig) -> Option<RalphExitReason> {
+        if let Some(limit) = config.max_tokens {
+            if self.tokens_used >= limit {
+                return Some(RalphExitReason::TokenBudget {
+                    used: self.tokens_used,
+                    limit,
+                });
+            }
+        }
+        if let Some(limit) = config.max_cost_usd {
+            if self.cost_usd >= limit {
+                return Some(RalphExitReason::CostBudget {
+                    spent: self.cost_usd,
+                    limit,
+                });
+            }
+        }
+        None
+    }
+
+    /// Track goal scores, reporting when they've stopped moving
+    fn record_scores(
+        &mut self,
+        goals: &VerificationResult,
+        config: &RalphConfig,
+    ) -> Option<RalphExitReason> {
+        let scores: Vec<(String, f64)> = goals
+            .results
+            .iter()
+            .map(|r| (r.name.clone(), r.score))
+            .collect();
+
+        if self.last_scores.as_ref() == Some(&scores) {
+            self.iterations_at_scores += 1;
+        } else {
+            self.last_scores = Some(scores);
+            self.iterations_at_scores = 1;
+        }
+
+        if config.stall_iterations > 0 && self.iterations_at_scores >= config.stall_iterations {
+            return Some(RalphExitReason::NoProgress {
+                iterations: self.iterations_at_scores,
+            });
+        }
+        None
+    }
+
+    /// Track working tree states, reporting when each iteration undoes the last
+    fn record_tree(&mut self, fingerprint: u64, config: &RalphConfig) -> Option<RalphExitReason> {
+        match self.recent_trees.as_slice() {
+            [.., last] if *last == fingerprint => return None,
+            [before_last, _] if *before_last == fingerprint => self.flips += 1,
+            _ => self.flips = 0,
+        }
+
+        self.recent_trees.push(fingerprint);
+        if self.recent_trees.len() > 2 {
+            self.recent_trees.remove(0);
+        }
+
+        if config.oscillation_limit > 0 && self.flips >= config.oscillation_limit {
+            return Some(RalphExitReason::Oscillation { flips: self.flips });
+        }
+        None
+    }
+}
+
+/// Fingerprint the uncommitted changes in `directory`, if it's a git repository
+async fn working_tree_fingerprint(directory: &str) -> Option<u64> {
+    let mut hasher = DefaultHasher::new();
+    for args in [
+        &["diff", "HEAD"][..],
+        &["ls-files", "--others", "--exclude-standard"][..],
+    ] {
+        let output = tokio::process::Command::new("git")
+            .args(args)
+            .current_dir(directory)
+            .output()
+            .await
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        output.stdout.hash(&mut hasher);
+    }
+    Some(hasher.finish())
+}
+
+fn open_snapshots(directory: &str, config: &RalphConfig) -> Option<SnapshotStore> {
+    if !config.snapshots {
+        return None;
+    }
+    SnapshotStore::open(Path::new(directory))
+}
+
+/// Mean goal score, used to rank iterations against each other
+fn mean_score(goals: &VerificationResult) -> Option<f64> {
+    if goals.results.is_empty() {
+        return None;
+    }
+    let total: f64 = goals.results.iter().map(|r| r.score).sum();
+    Some(total / goals.results.len() as f64)
+}
+
 /// Ralph mode controller
 ///
 /// Controls ralph (self-referential work loop) mode with event streaming.
@@ -43,6 +234,12 @@ pub struct RalphController {
     directory: String,
     event_tx: Option<EventSender>,
     todo_store: TodoStore,
+    guardrails: Guardrails,
+    snapshots: Option<SnapshotStore>,
+    /// Best-scoring snapshot so far, with its mean goal score
+    last_good: Option<(f64, Snapshot)>,
+    /// Snapshot of the last verified iteration
+    last_snapshot: Option<Snapshot>,
 }
 
 /// Decision from ralph completion check
@@ -51,8 +248,12 @@ pub enum RalphDecision {
     Continue { feedback: String },
     /// Task completed successfully
     Complete,
-    /// Exit due to circuit breaker or max iterations
-    Exit { reason: String },
+    /// Exit because a guardrail stopped the loop; `restored` is the snapshot
+    /// the working tree was rolled back to, if any
+    Exit {
+        reason: RalphExitReason,
+        restored: Option<Snapshot>,
+    },
 }
 
 impl RalphController {
@@ -81,12 +282,17 @@ impl RalphController {
         // Read back state that was written
         let state = RalphHook::read_state(Some(directory))?;
 
+        let snapshots = open_snapshots(directory, &config);
         Some(Self {
             state,
             config,
             directory: directory.to_string(),
             event_tx: None,
             todo_store,
+            guardrails: Guardrails::default(),
+            snapshots,
+            last_good: None,
+            last_snapshot: None,
         })
     }
 
@@ -97,18 +303,23 @@ impl RalphController {
             return None;
         }
 
+        let config = RalphConfig {
+            max_iterations: state.max_iterations,
+            completion_promise: state.completion_promise.clone(),
+            min_confidence: state.min_confidence,
+            require_dual_condition: state.require_dual_condition,
+            ..Default::default()
+        };
         Some(Self {
-            config: RalphConfig {
-                max_iterations: state.max_iterations,
-                completion_promise: state.completion_promise.clone(),
-                min_confidence: state.min_confidence,
-                require_dual_condition: state.require_dual_condition,
-                ..Default::default()
-            },
+            snapshots: open_snapshots(directory, &config),
+            config,
             state,
             directory: directory.to_string(),
             event_tx: None,
             todo_store,
+            guardrails: Guardrails::default(),
+            last_good: None,
+            last_snapshot: None,
         })
     }
 
@@ -118,6 +329,21 @@ impl RalphController {
         self
     }
 
+    /// Count a model turn's token usage against the loop's budgets
+    pub fn record_usage(&mut self, usage: &TokenUsage, model: &str) {
+        self.guardrails.record_usage(usage, model);
+    }
+
+    /// Tokens used by the loop so far
+    pub fn tokens_used(&self) -> u64 {
+        self.guardrails.tokens_used
+    }
+
+    /// Estimated cost of the loop so far in USD
+    pub fn cost_usd(&self) -> f64 {
+        self.guardrails.cost_usd
+    }
+
     /// Check if task should continue or complete
     pub async fn check_completion(
         &mut self,
@@ -126,6 +352,11 @@ impl RalphController {
     ) -> RalphDecision {
         self.emit_iteration_started().await;
 
+        let score = goals_result.and_then(mean_score);
+        if let Some(score) = score {
+            self.snapshot_iteration(score).await;
+        }
+
         // Check circuit breaker
         if self.state.circuit_breaker.is_tripped() {
             let reason = self
@@ -134,17 +365,24 @@ impl RalphController {
                 .trip_reason
                 .clone()
                 .unwrap_or_else(|| "Circuit breaker tripped".into());
-            self.emit_circuit_break(&reason).await;
-            self.clear();
-            return RalphDecision::Exit { reason };
+            return self
+                .exit(RalphExitReason::CircuitBreaker(reason), score)
+                .await;
         }
 
         // Check max iterations
         if self.state.iteration >= self.state.max_iterations {
-            let reason = format!("Max iterations ({}) reached", self.state.max_iterations);
-            self.emit_circuit_break(&reason).await;
-            self.clear();
-            return RalphDecision::Exit { reason };
+            return self
+                .exit(
+                    RalphExitReason::MaxIterations(self.state.max_iterations),
+                    score,
+                )
+                .await;
+        }
+
+        // Check token and cost budgets
+        if let Some(reason) = self.guardrails.check_budget(&self.config) {
+            return self.exit(reason, score).await;
         }
 
         // Check todos
@@ -181,17 +419,115 @@ impl RalphController {
 
         if exit_allowed {
             self.clear();
-            RalphDecision::Complete
-        } else {
-            // Build feedback using now-public function
-            let feedback = RalphHook::build_verification_feedback(
-                &signals,
-                &self.state,
-                &goals_result.cloned(),
-            );
-            self.emit_continuation(&feedback, signals.confidence).await;
-            self.increment_iteration();
-            RalphDecision::Continue { feedback }
+            return RalphDecision::Complete;
+        }
+
+        // Stop loops that have stalled or keep undoing their own changes
+        if let Some(goals) = goals_result {
+            if let Some(reason) = self.guardrails.record_scores(goals, &self.config) {
+                return self.exit(reason, score).await;
+            }
+        }
+        if let Some(fingerprint) = working_tree_fingerprint(&self.directory).await {
+            if let Some(reason) = self.guardrails.record_tree(fingerprint, &self.config) {
+                return self.exit(reason, score).await;
+            }
+        }
+
+        // Build feedback using now-public function
+        let feedback =
+            RalphHook::build_verification_feedback(&signals, &self.state, &goals_result.cloned());
+        self.emit_continuation(&feedback, signals.confidence).await;
+        self.increment_iteration();
+        RalphDecision::Continue { feedback }
+    }
+
+    async fn exit(&self, reason: RalphExitReason, score: Option<f64>) -> RalphDecision {
+        self.emit_circuit_break(&reason.to_string()).await;
+        // Restore before clearing so a snapshotted state file isn't brought back
+        let restored = self.restore_last_good(score).await;
+        self.clear();
+        RalphDecision::Exit { reason, restored }
+    }
+
+    /// Snapshot the tree this iteration produced, remembering it if it's the
+    /// best-scoring state so far
+    async fn snapshot_iteration(&mut self, score: f64) {
+        let Some(store) = self.snapshots.clone() else {
+            return;
+        };
+        let label = format!(
+            "ralph iteration {} (score {:.1})",
+            self.state.iteration, score
+        );
+        let captured = tokio::task::spawn_blocking(move || {
+            // An unchanged tree is already the latest snapshot
+            match store.capture(&label)? {
+                Some(snapshot) => Ok(Some(snapshot)),
+                None => Ok(store.list()?.into_iter().next()),
+            }
+        })
+        .await
+        .unwrap_or_else(|e| Err(std::io::Error::other(e)));
+
+        match captured {
+            Ok(Some(snapshot)) => {
+                if self
+                    .last_good
+                    .as_ref()
+                    .is_none_or(|(best, _)| score > *best)
+                {
+                    self.last_good = Some((score, snapshot.clone()));
+                }
+                self.last_snapshot = Some(snapshot);
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to snapshot ralph iteration"),
+        }
+    }
+
+    /// Files changed since the last verified iteration, or since HEAD before
+    /// the first one
+    async fn changed_files(&self) -> Option<Vec<PathBuf>> {
+        let store = self.snapshots.clone()?;
+        let since = self
+            .last_snapshot
+            .as_ref()
+            .map_or_else(|| "HEAD".to_string(), |s| s.commit.clone());
+        let changed = tokio::task::spawn_blocking(move || store.changed_since(&since))
+            .await
+            .unwrap_or_else(|e| Err(std::io::Error::other(e)));
+        match changed {
+            Ok(files) => Some(files),
+            Err(e) => {
+                tracing::debug!(error = %e, "No change set for goal verification");
+                None
+            }
+        }
+    }
+
+    /// Roll the working tree back to the best-scoring snapshot when the
+    /// current state scores lower
+    async fn restore_last_good(&self, score: Option<f64>) -> Option<Snapshot> {
+        let store = self.snapshots.clone()?;
+        let (best, snapshot) = self.last_good.clone()?;
+        if score.is_some_and(|score| score >= best) {
+            return None;
+        }
+
+        let target = snapshot.clone();
+        let restored = tokio::task::spawn_blocking(move || store.restore(&target))
+            .await
+            .unwrap_or_else(|e| Err(std::io::Error::other(e)));
+        match restored {
+            Ok(_) => {
+                tracing::info!(snapshot = %snapshot.id, score = best, "Restored last good ralph iteration");
+                Some(snapshot)
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to restore last good ralph iteration");
+                None
+            }
         }
     }
 
@@ -283,6 +619,10 @@ impl RalphController {
     ) -> RalphDecision {
         let goals_result = if goals_config.has_goals() && goals_config.auto_verify {
             if let Some(verifier) = self.create_goal_verifier(goals_config) {
+                let verifier = match self.changed_files().await {
+                    Some(files) => verifier.with_changed_files(files),
+                    None => verifier,
+                };
                 Some(verifier.verify_all().await)
             } else {
                 None
@@ -295,3 +635,140 @@ impl RalphController {
             .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uira_orchestration::GoalCheckResult;
+
+    fn goals(scores: &[f64]) -> VerificationResult {
+        VerificationResult {
+            all_passed: false,
+            results: scores
+                .iter()
+                .enumerate()
+                .map(|(i, score)| GoalCheckResult::success(format!("goal-{}", i), *score, 90.0, 10))
+                .collect(),
+            checked_at: Utc::now(),
+            iteration: 0,
+        }
+    }
+
+    #[test]
+    fn budget_stops_at_token_or_cost_ceiling() {
+        let config = RalphConfig {
+            max_tokens: Some(1_000),
+            max_cost_usd: Some(0.01),
+            ..RalphConfig::default()
+        };
+        let mut guardrails = Guardrails::default();
+        let usage = TokenUsage {
+            input_tokens: 400,
+            output_tokens: 100,
+            ..TokenUsage::default()
+        };
+
+        guardrails.record_usage(&usage, "claude-sonnet-4");
+        assert_eq!(guardrails.check_budget(&config), None);
+        guardrails.record_usage(&usage, "claude-sonnet-4");
+        assert_eq!(
+            guardrails.check_budget(&config),
+            Some(RalphExitReason::TokenBudget {
+                used: 1_000,
+                limit: 1_000
+            })
+        );
+
+        let config = RalphConfig {
+            max_cost_usd: Some(0.005),
+            ..RalphConfig::default()
+        };
+        let reason = guardrails.check_budget(&config).unwrap();
+        assert!(reason.to_string().starts_with("Cost budget exhausted"));
+    }
+
+    #[test]
+    fn unchanged_goal_scores_stop_the_loop() {
+        let config = RalphConfig::default();
+        let mut guardrails = Guardrails::default();
+
+        assert_eq!(guardrails.record_scores(&goals(&[40.0]), &config), None);
+        assert_eq!(guardrails.record_scores(&goals(&[55.0]), &config), None);
+        assert_eq!(guardrails.record_scores(&goals(&[55.0]), &config), None);
+        assert_eq!(
+            guardrails.record_scores(&goals(&[55.0]), &config),
+            Some(RalphExitReason::NoProgress { iterations: 3 })
+        );
+    }
+
+    #[test]
+    fn flip_flopping_tree_stops_the_loop() {
+        let config = RalphConfig::default();
+        let mut guardrails = Guardrails::default();
+
+        assert_eq!(guardrails.record_tree(1, &config), None);
+        assert_eq!(guardrails.record_tree(2, &config), None);
+        assert_eq!(guardrails.record_tree(1, &config), None);
+        // A new state resets the count
+        assert_eq!(guardrails.record_tree(3, &config), None);
+        assert_eq!(guardrails.record_tree(1, &config), None);
+        assert_eq!(
+            guardrails.record_tree(3, &config),
+            Some(RalphExitReason::Oscillation { flips: 2 })
+        );
+    }
+
+    #[tokio::test]
+    async fn exit_restores_best_scoring_iteration() {
+        let repo = tempfile::tempdir().unwrap();
+        let dir = repo.path().to_str().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir)
+                .output()
+                .unwrap()
+        };
+        git(&["init", "-q"]);
+        std::fs::write(repo.path().join("lib.rs"), "v0").unwrap();
+
+        let config = RalphConfig {
+            max_iterations: 2,
+            stall_iterations: 0,
+            oscillation_limit: 0,
+            ..RalphConfig::default()
+        };
+        let mut ralph =
+            RalphController::activate("fix it", None, dir, config, TodoStore::new()).unwrap();
+
+        std::fs::write(repo.path().join("lib.rs"), "good").unwrap();
+        let decision = ralph
+            .check_completion("working", Some(&goals(&[80.0])))
+            .await;
+        assert!(matches!(decision, RalphDecision::Continue { .. }));
+
+        std::fs::write(repo.path().join("lib.rs"), "worse").unwrap();
+        std::fs::write(repo.path().join("scratch.rs"), "tmp").unwrap();
+        let decision = ralph
+            .check_completion("working", Some(&goals(&[40.0])))
+            .await;
+        assert!(matches!(decision, RalphDecision::Continue { .. }));
+
+        match ralph
+            .check_completion("working", Some(&goals(&[30.0])))
+            .await
+        {
+            RalphDecision::Exit { reason, restored } => {
+                assert_eq!(reason, RalphExitReason::MaxIterations(2));
+                assert!(restored.unwrap().label.starts_with("ralph iteration 0"));
+            }
+            _ => panic!("expected exit"),
+        }
+        assert_eq!(
+            std::fs::read_to_string(repo.path().join("lib.rs")).unwrap(),
+            "good"
+        );
+        assert!(!repo.path().join("scratch.rs").exists());
+        assert!(RalphHook::read_state(Some(dir)).is_none_or(|state| !state.active));
+    }
+}
@@ -0,0 +1,346 @@
+//! Retention for session logs and background-task artifacts
+//!
+//! Session logs older than `compress_after_days` are gzipped in place; logs
+//! beyond `max_sessions`, older than `max_age_days` or past the
+//! `max_disk_mb` budget are deleted, oldest first. Finished background tasks
+//! older than `max_age_days` lose their record and output log. Logs written
+//! to within the last hour are never touched, since a running session may
+//! still be appending to them.
+
+use std::fs::{self, File};
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use chrono::{DateTime, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use uira_core::schema::RetentionSettings;
+use uira_orchestration::background_agent::{get_background_manager, BackgroundTaskConfig};
+
+use crate::session::{is_compressed, is_session_log, SessionRecorder};
+
+const DAY: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// Logs modified more recently than this are treated as live
+const ACTIVE_WINDOW: Duration = Duration::from_secs(60 * 60);
+
+/// Marks the last automatic run in the sessions directory
+const AUTO_STAMP: &str = ".last-gc";
+
+/// What a collection removed or compressed, or would have with `dry_run`
+#[derive(Debug, Clone, Default)]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub compressed: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+    /// Background tasks whose record and log were removed
+    pub tasks_removed: Vec<String>,
+    /// Session logs plus removed background-task artifacts, before collection
+    pub bytes_before: u64,
+    /// Size afterwards; a dry run cannot know compressed sizes, so it counts
+    /// logs that would be compressed at their current size
+    pub bytes_after: u64,
+}
+
+impl GcReport {
+    pub fn bytes_freed(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.compressed.is_empty() && self.removed.is_empty() && self.tasks_removed.is_empty()
+    }
+}
+
+struct LogFile {
+    path: PathBuf,
+    modified: SystemTime,
+    len: u64,
+}
+
+/// Apply `settings` to the session logs and background tasks
+pub fn collect(
+    settings: &RetentionSettings,
+    background: BackgroundTaskConfig,
+    dry_run: bool,
+) -> std::io::Result<GcReport> {
+    let now = SystemTime::now();
+    let mut report = collect_sessions(&SessionRecorder::sessions_dir()?, settings, now, dry_run)?;
+
+    if settings.max_age_days > 0 {
+        let cutoff = DateTime::<Utc>::from(now - DAY * settings.max_age_days as u32);
+        let pruned = get_background_manager(background).prune_finished(cutoff, dry_run);
+        report.bytes_before += pruned.bytes;
+        report.tasks_removed = pruned.task_ids;
+    }
+    Ok(report)
+}
+
+/// [`collect`] if `settings.auto` is on and it has not run in the last day.
+/// Returns `None` when it was skipped.
+pub fn auto_collect(
+    settings: &RetentionSettings,
+    background: BackgroundTaskConfig,
+) -> std::io::Result<Option<GcReport>> {
+    if !settings.auto {
+        return Ok(None);
+    }
+    let stamp = SessionRecorder::sessions_dir()?.join(AUTO_STAMP);
+    let ran_recently = fs::metadata(&stamp)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|elapsed| elapsed < DAY);
+    if ran_recently {
+        return Ok(None);
+    }
+
+    let report = collect(settings, background, false)?;
+    if let Some(dir) = stamp.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    fs::write(&stamp, Utc::now().to_rfc3339())?;
+    Ok(Some(report))
+}
+
+/// Apply `settings` to the session logs in `dir`, with ages measured from `now`
+pub fn collect_sessions(
+    dir: &Path,
+    settings: &RetentionSettings,
+    now: SystemTime,
+    dry_run: bool,
+) -> std::io::Result<GcReport> {
+    let mut report = GcReport {
+        dry_run,
+        ..GcReport::default()
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Ok(report);
+    };
+
+    let mut logs = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !is_session_log(&path) {
+            continue;
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        logs.push(LogFile {
+            path,
+            modified: metadata.modified()?,
+            len: metadata.len(),
+        });
+    }
+    logs.sort_by_key(|log| std::cmp::Reverse(log.modified));
+    report.bytes_before = logs.iter().map(|log| log.len).sum();
+
+    let age = |log: &LogFile| now.duration_since(log.modified).unwrap_or_default();
+    let max_age = DAY * settings.max_age_days as u32;
+    let compress_after = DAY * settings.compress_after_days as u32;
+
+    let mut kept: Vec<LogFile> = Vec::new();
+    let mut expired = Vec::new();
+    for log in logs {
+        let age = age(&log);
+        let over_count = settings.max_sessions > 0 && kept.len() >= settings.max_sessions;
+        let too_old = settings.max_age_days > 0 && age > max_age;
+        if age >= ACTIVE_WINDOW && (over_count || too_old) {
+            expired.push(log);
+        } else {
+            kept.push(log);
+        }
+    }
+
+    // Compress before enforcing the disk budget so the budget sees the
+    // smaller sizes
+    if settings.compress_after_days > 0 {
+        for log in kept.iter_mut() {
+            if age(log) < compress_after.max(ACTIVE_WINDOW) || is_compressed(&log.path) {
+                continue;
+            }
+            report.compressed.push(log.path.clone());
+            if !dry_run {
+                let compressed = compress(&log.path, log.modified)?;
+                log.len = fs::metadata(&compressed)?.len();
+                log.path = compressed;
+            }
+        }
+    }
+
+    if settings.max_disk_mb > 0 {
+        let budget = settings.max_disk_mb * 1024 * 1024;
+        let mut total: u64 = kept.iter().map(|log| log.len).sum();
+        while total > budget {
+            let Some(index) = kept.iter().rposition(|log| age(log) >= ACTIVE_WINDOW) else {
+                break;
+            };
+            let log = kept.remove(index);
+            total -= log.len;
+            expired.push(log);
+        }
+    }
+
+    for log in expired {
+        if !dry_run {
+            fs::remove_file(&log.path)?;
+        }
+        report.removed.push(log.path);
+    }
+    report.bytes_after = kept.iter().map(|log| log.len).sum();
+
+    tracing::debug!(
+        dir = %dir.display(),
+        compressed = report.compressed.len(),
+        removed = report.removed.len(),
+        dry_run,
+        "Collected session logs"
+    );
+    Ok(report)
+}
+
+/// Gzip `path` to `<path>.gz`, keeping its modification time so it keeps
+/// aging, and remove the original
+fn compress(path: &Path, modified: SystemTime) -> std::io::Result<PathBuf> {
+    let mut target = path.as_os_str().to_owned();
+    target.push(".gz");
+    let target = PathBuf::from(target);
+
+    let mut input = BufReader::new(File::open(path)?);
+    let output = File::create(&target)?;
+    let mut encoder = GzEncoder::new(BufWriter::new(output), Compression::default());
+    std::io::copy(&mut input, &mut encoder)?;
+    let output = encoder.finish()?.into_inner().map_err(|e| e.into_error())?;
+    output.set_modified(modified)?;
+    output.sync_all()?;
+
+    fs::remove_file(path)?;
+    Ok(target)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{SessionItem, SessionMetaLine};
+
+    fn write_log(dir: &Path, name: &str, days_old: u64, now: SystemTime) -> PathBuf {
+        let meta = SessionMetaLine::new(
+            "thread",
+            "model",
+            "anthropic",
+            PathBuf::from("/repo"),
+            "workspace-write",
+        );
+        let line = serde_json::to_string(&SessionItem::SessionMeta(Box::new(meta))).unwrap();
+        let path = dir.join(name);
+        fs::write(&path, format!("{line}\n")).unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(now - DAY * days_old as u32)
+            .unwrap();
+        path
+    }
+
+    fn settings() -> RetentionSettings {
+        RetentionSettings {
+            auto: true,
+            max_sessions: 0,
+            max_age_days: 0,
+            max_disk_mb: 0,
+            compress_after_days: 0,
+        }
+    }
+
+    #[test]
+    fn removes_sessions_beyond_count_and_age() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let now = SystemTime::now();
+        let live = write_log(dir.path(), "session-a.jsonl", 0, now);
+        let recent = write_log(dir.path(), "session-b.jsonl", 2, now);
+        let old = write_log(dir.path(), "session-c.jsonl", 20, now);
+        let ancient = write_log(dir.path(), "session-d.jsonl", 200, now);
+
+        let settings = RetentionSettings {
+            max_sessions: 3,
+            max_age_days: 90,
+            ..settings()
+        };
+        let preview = collect_sessions(dir.path(), &settings, now, true).unwrap();
+        assert_eq!(preview.removed, vec![ancient.clone()]);
+        assert!(ancient.exists());
+
+        let settings = RetentionSettings {
+            max_sessions: 1,
+            ..settings
+        };
+        let report = collect_sessions(dir.path(), &settings, now, false).unwrap();
+        assert_eq!(report.removed.len(), 3);
+        assert!(live.exists());
+        assert!(!recent.exists() && !old.exists() && !ancient.exists());
+        assert!(report.bytes_freed() > 0);
+    }
+
+    #[test]
+    fn compressed_sessions_stay_readable() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let now = SystemTime::now();
+        let recent = write_log(dir.path(), "session-a.jsonl", 1, now);
+        let old = write_log(dir.path(), "session-b.jsonl", 10, now);
+
+        let settings = RetentionSettings {
+            compress_after_days: 7,
+            ..settings()
+        };
+        let report = collect_sessions(dir.path(), &settings, now, false).unwrap();
+        assert_eq!(report.compressed, vec![old.clone()]);
+        assert!(recent.exists() && !old.exists());
+
+        let gz = dir.path().join("session-b.jsonl.gz");
+        assert!(is_session_log(&gz));
+        let meta = SessionRecorder::extract_metadata(&gz).unwrap().unwrap();
+        assert_eq!(meta.provider, "anthropic");
+        assert_eq!(SessionRecorder::load(&gz).unwrap().len(), 1);
+
+        // Already compressed logs are left alone
+        let again = collect_sessions(dir.path(), &settings, now, false).unwrap();
+        assert!(again.is_empty());
+
+        // Resuming restores the plain log
+        let recorder = SessionRecorder::open(gz.clone()).unwrap();
+        assert_eq!(recorder.path(), &old);
+        assert!(old.exists() && !gz.exists());
+    }
+
+    #[test]
+    fn enforces_disk_budget_oldest_first() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let now = SystemTime::now();
+        let big = "x".repeat(700 * 1024);
+        let newer = write_log(dir.path(), "session-a.jsonl", 2, now);
+        let older = write_log(dir.path(), "session-b.jsonl", 3, now);
+        for path in [&newer, &older] {
+            let modified = fs::metadata(path).unwrap().modified().unwrap();
+            let mut content = fs::read_to_string(path).unwrap();
+            content.push_str(&big);
+            fs::write(path, content).unwrap();
+            File::options()
+                .write(true)
+                .open(path)
+                .unwrap()
+                .set_modified(modified)
+                .unwrap();
+        }
+
+        let settings = RetentionSettings {
+            max_disk_mb: 1,
+            ..settings()
+        };
+        let report = collect_sessions(dir.path(), &settings, now, false).unwrap();
+        assert_eq!(report.removed, vec![older]);
+        assert!(newer.exists());
+    }
+}
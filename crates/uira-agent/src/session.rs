@@ -2,20 +2,33 @@
 //!
 //! This module implements the SessionRecorder which persists all session
 //! events to a JSONL file for debugging, replay, and resume capabilities.
+//!
+//! Every line carries a `seq` number that increases by one per line. Writes
+//! are buffered and fsynced in batches, and a `trailer` line marks a log that
+//! was closed cleanly. [`SessionRecorder::recover`] uses both to cut off a
+//! tail torn by a crash or power loss before the log is resumed.
 
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs::{File, OpenOptions};
-use std::io::{BufRead, BufReader, Write};
-use std::path::PathBuf;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use uira_core::{Message, MessageId, SessionId, ThreadEvent, TokenUsage, UIRA_DIR};
 
+/// Lines buffered before the log is flushed and fsynced
+const SYNC_BATCH: usize = 32;
+
+/// Longest a recorded line waits for an fsync once another line is recorded
+const SYNC_INTERVAL: Duration = Duration::from_secs(1);
+
 /// Items that can be recorded to the session log
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum SessionItem {
     /// Session metadata (always first line)
-    SessionMeta(SessionMetaLine),
+    SessionMeta(Box<SessionMetaLine>),
 
     /// A conversation message (user, assistant, tool)
     Message(SessionMessage),
@@ -50,6 +63,17 @@ pub enum SessionItem {
         message_count: usize,
         timestamp: DateTime<Utc>,
     },
+
+    /// Written when the recorder is closed cleanly
+    Trailer { closed_at: DateTime<Utc> },
+
+    /// Written by recovery after a log was left without a trailer; the
+    /// session stopped mid-run and can be resumed from here
+    Recovered {
+        dropped_lines: usize,
+        truncated_bytes: u64,
+        timestamp: DateTime<Utc>,
+    },
 }
 
 /// Wrapper for ThreadEvent to handle serialization properly
@@ -118,6 +142,14 @@ pub struct SessionMetaLine {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub git_branch: Option<String>,
 
+    /// Agent definition the session ran as, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub agent: Option<String>,
+
+    /// Experiment variants the session was assigned, by experiment name
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub experiments: BTreeMap<String, String>,
+
     /// Total turns when metadata was last updated
     #[serde(default)]
     pub turns: usize,
@@ -157,6 +189,8 @@ impl SessionMetaLine {
             sandbox_policy: sandbox_policy.into(),
             git_commit: Self::get_git_commit(),
             git_branch: Self::get_git_branch(),
+            agent: None,
+            experiments: BTreeMap::new(),
             turns: 0,
             total_usage: TokenUsage::default(),
             parent_id: None,
@@ -183,6 +217,8 @@ impl SessionMetaLine {
             sandbox_policy: sandbox_policy.into(),
             git_commit: Self::get_git_commit(),
             git_branch: Self::get_git_branch(),
+            agent: None,
+            experiments: BTreeMap::new(),
             turns: 0,
             total_usage: TokenUsage::default(),
             parent_id: Some(parent_id),
@@ -224,16 +260,41 @@ impl SessionMetaLine {
     }
 }
 
+/// Outcome of [`SessionRecorder::recover`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RecoveryReport {
+    /// The log ended with a trailer, so its writer shut down cleanly
+    pub clean: bool,
+    /// Lines cut from the torn tail
+    pub dropped_lines: usize,
+    /// Bytes cut from the end of the file
+    pub truncated_bytes: u64,
+    /// Sequence number of the last line kept (or appended by recovery)
+    pub last_seq: u64,
+}
+
 /// Recorder for sessions with append-only JSONL persistence
 pub struct SessionRecorder {
-    /// Open file handle for appending
-    file: File,
+    /// Buffered handle for appending
+    writer: BufWriter<File>,
 
     /// Path to the session file
     path: PathBuf,
 
     /// Session metadata (cached)
     meta: SessionMetaLine,
+
+    /// Sequence number of the last line written
+    seq: u64,
+
+    /// Lines written since the last fsync
+    unsynced: usize,
+
+    /// When the log was last fsynced
+    last_sync: Instant,
+
+    /// Set once the trailer is written
+    closed: bool,
 }
 
 impl SessionRecorder {
@@ -247,16 +308,24 @@ impl SessionRecorder {
 
         let file = OpenOptions::new().create(true).append(true).open(&path)?;
 
-        let mut recorder = Self { file, path, meta };
+        let mut recorder = Self::with_file(file, path, meta, 0);
 
         // Write metadata as first line
-        recorder.record(&SessionItem::SessionMeta(recorder.meta.clone()))?;
+        recorder.record(&SessionItem::SessionMeta(Box::new(recorder.meta.clone())))?;
 
         Ok(recorder)
     }
 
-    /// Open an existing session file for resuming
+    /// Open an existing session file for resuming, recovering it first if
+    /// its last writer did not close it cleanly
     pub fn open(path: PathBuf) -> std::io::Result<Self> {
+        let path = if is_compressed(&path) {
+            decompress(&path)?
+        } else {
+            path
+        };
+        let report = Self::recover(&path)?;
+
         // Read metadata from first line
         let meta = Self::extract_metadata(&path)?.ok_or_else(|| {
             std::io::Error::new(
@@ -268,11 +337,23 @@ impl SessionRecorder {
         // Open for appending
         let file = OpenOptions::new().append(true).open(&path)?;
 
-        Ok(Self { file, path, meta })
+        Ok(Self::with_file(file, path, meta, report.last_seq))
+    }
+
+    fn with_file(file: File, path: PathBuf, meta: SessionMetaLine, seq: u64) -> Self {
+        Self {
+            writer: BufWriter::new(file),
+            path,
+            meta,
+            seq,
+            unsynced: 0,
+            last_sync: Instant::now(),
+            closed: false,
+        }
     }
 
     /// Get the sessions directory
-    fn sessions_dir() -> std::io::Result<PathBuf> {
+    pub fn sessions_dir() -> std::io::Result<PathBuf> {
         // Prefer ~/.uira for consistency with other CLI tools, fall back to XDG data dir
         // for environments where HOME is unset (systemd services, containers)
         let base_dir = dirs::home_dir()
@@ -288,15 +369,149 @@ impl SessionRecorder {
         Ok(base_dir.join("sessions"))
     }
 
-    /// Append an item to the session log (immediate flush)
+    /// Append an item to the session log. Lines are buffered and fsynced
+    /// every [`SYNC_BATCH`] lines or [`SYNC_INTERVAL`], and right away for
+    /// items that mark a resume point (metadata, turn ends, forks).
     pub fn record(&mut self, item: &SessionItem) -> std::io::Result<()> {
-        let line = serde_json::to_string(item)
-            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-        writeln!(self.file, "{}", line)?;
-        self.file.flush()?;
+        self.seq += 1;
+        let line = encode_line(self.seq, item)?;
+        self.writer.write_all(line.as_bytes())?;
+        self.unsynced += 1;
+
+        let checkpoint = matches!(
+            item,
+            SessionItem::SessionMeta(_)
+                | SessionItem::TurnContext { .. }
+                | SessionItem::SessionForked { .. }
+                | SessionItem::Trailer { .. }
+        );
+        if checkpoint || self.unsynced >= SYNC_BATCH || self.last_sync.elapsed() >= SYNC_INTERVAL {
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    /// Force everything recorded so far onto disk
+    pub fn sync(&mut self) -> std::io::Result<()> {
+        self.writer.flush()?;
+        self.writer.get_ref().sync_data()?;
+        self.unsynced = 0;
+        self.last_sync = Instant::now();
         Ok(())
     }
 
+    /// Write the trailer and sync. Dropping the recorder does this too.
+    pub fn close(&mut self) -> std::io::Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        self.closed = true;
+        self.record(&SessionItem::Trailer {
+            closed_at: Utc::now(),
+        })
+    }
+
+    /// Truncate a tail torn by a crash or power loss: the first line that is
+    /// incomplete, unparseable or out of sequence and everything after it.
+    /// A log left without a trailer gets a `recovered` line so it reads as a
+    /// resumable, interrupted session. Running it again is a no-op.
+    pub fn recover(path: &Path) -> std::io::Result<RecoveryReport> {
+        let bytes = std::fs::read(path)?;
+        let mut report = RecoveryReport::default();
+        let mut valid_end = 0usize;
+        let mut last_item: Option<SessionItem> = None;
+        let mut missing_newline = false;
+        let mut offset = 0usize;
+
+        while offset < bytes.len() {
+            let (line, next, terminated) = match bytes[offset..].iter().position(|&b| b == b'\n') {
+                Some(pos) => (&bytes[offset..offset + pos], offset + pos + 1, true),
+                None => (&bytes[offset..], bytes.len(), false),
+            };
+
+            if line.iter().all(u8::is_ascii_whitespace) {
+                if terminated {
+                    valid_end = next;
+                }
+                offset = next;
+                continue;
+            }
+
+            let Some((seq, item)) = decode_line(line) else {
+                break;
+            };
+            match seq {
+                Some(seq) if seq <= report.last_seq => break,
+                Some(seq) => report.last_seq = seq,
+                // Sequenced logs never go back to unsequenced lines
+                None if report.last_seq > 0 => break,
+                None => {}
+            }
+            if last_item.is_none() && !matches!(item, SessionItem::SessionMeta(_)) {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    "Missing session metadata in session file",
+                ));
+            }
+
+            last_item = Some(item);
+            valid_end = next;
+            missing_newline = !terminated;
+            offset = next;
+        }
+
+        let Some(last_item) = last_item else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Missing session metadata in session file",
+            ));
+        };
+
+        report.truncated_bytes = (bytes.len() - valid_end) as u64;
+        report.dropped_lines = bytes[valid_end..]
+            .split(|&b| b == b'\n')
+            .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+            .count();
+        report.clean = matches!(last_item, SessionItem::Trailer { .. });
+
+        let already_recovered = matches!(last_item, SessionItem::Recovered { .. });
+        if report.truncated_bytes == 0 && !missing_newline && (report.clean || already_recovered) {
+            return Ok(report);
+        }
+
+        if report.truncated_bytes > 0 {
+            OpenOptions::new()
+                .write(true)
+                .open(path)?
+                .set_len(valid_end as u64)?;
+        }
+        let mut file = OpenOptions::new().append(true).open(path)?;
+        if missing_newline {
+            file.write_all(b"\n")?;
+        }
+        if !report.clean {
+            report.last_seq += 1;
+            let line = encode_line(
+                report.last_seq,
+                &SessionItem::Recovered {
+                    dropped_lines: report.dropped_lines,
+                    truncated_bytes: report.truncated_bytes,
+                    timestamp: Utc::now(),
+                },
+            )?;
+            file.write_all(line.as_bytes())?;
+        }
+        file.sync_all()?;
+
+        tracing::info!(
+            path = %path.display(),
+            dropped_lines = report.dropped_lines,
+            truncated_bytes = report.truncated_bytes,
+            "Recovered session log"
+        );
+        Ok(report)
+    }
+
     /// Record a message
     pub fn record_message(&mut self, message: Message) -> std::io::Result<()> {
         self.record(&SessionItem::Message(SessionMessage::new(message)))
@@ -361,20 +576,29 @@ impl SessionRecorder {
         })
     }
 
-    /// Load all items from a session file
-    pub fn load(path: &PathBuf) -> std::io::Result<Vec<SessionItem>> {
-        let file = File::open(path)?;
-        let reader = BufReader::new(file);
+    /// Load all items from a session file. A torn tail (unreadable lines
+    /// with nothing readable after them) is skipped; corruption in the middle
+    /// of the log is an error.
+    pub fn load(path: &Path) -> std::io::Result<Vec<SessionItem>> {
+        let bytes = read_log(path)?;
         let mut items = Vec::new();
+        let mut torn: Option<std::io::Error> = None;
 
-        for line in reader.lines() {
-            let line = line?;
-            if line.trim().is_empty() {
+        for line in bytes.split(|&b| b == b'\n') {
+            if line.iter().all(u8::is_ascii_whitespace) {
                 continue;
             }
-            let item: SessionItem = serde_json::from_str(&line)
-                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
-            items.push(item);
+            match serde_json::from_slice::<SessionItem>(line) {
+                Ok(item) => {
+                    if let Some(error) = torn.take() {
+                        return Err(error);
+                    }
+                    items.push(item);
+                }
+                Err(e) => {
+                    torn.get_or_insert(std::io::Error::new(std::io::ErrorKind::InvalidData, e));
+                }
+            }
         }
 
         Ok(items)
@@ -383,7 +607,11 @@ impl SessionRecorder {
     /// Extract only the metadata (first line) from a session file
     pub fn extract_metadata(path: &PathBuf) -> std::io::Result<Option<SessionMetaLine>> {
         let file = File::open(path)?;
-        let mut reader = BufReader::new(file);
+        let mut reader: Box<dyn BufRead> = if is_compressed(path) {
+            Box::new(BufReader::new(flate2::read::GzDecoder::new(file)))
+        } else {
+            Box::new(BufReader::new(file))
+        };
         let mut first_line = String::new();
         reader.read_line(&mut first_line)?;
 
@@ -392,7 +620,7 @@ impl SessionRecorder {
         }
 
         match serde_json::from_str::<SessionItem>(&first_line) {
-            Ok(SessionItem::SessionMeta(meta)) => Ok(Some(meta)),
+            Ok(SessionItem::SessionMeta(meta)) => Ok(Some(*meta)),
             Ok(_) => Ok(None),
             Err(_) => Ok(None),
         }
@@ -411,7 +639,7 @@ impl SessionRecorder {
             let entry = entry?;
             let path = entry.path();
 
-            if path.extension().is_some_and(|e| e == "jsonl") {
+            if is_session_log(&path) {
                 if let Ok(Some(meta)) = Self::extract_metadata(&path) {
                     sessions.push((path, meta));
                 }
@@ -442,6 +670,67 @@ impl SessionRecorder {
     }
 }
 
+impl Drop for SessionRecorder {
+    fn drop(&mut self) {
+        if let Err(e) = self.close() {
+            tracing::warn!(path = %self.path.display(), error = %e, "Failed to close session log");
+        }
+    }
+}
+
+/// Session logs are `.jsonl`, or `.jsonl.gz` once compressed by retention
+pub fn is_session_log(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|name| name.to_str())
+        .is_some_and(|name| name.ends_with(".jsonl") || name.ends_with(".jsonl.gz"))
+}
+
+pub fn is_compressed(path: &Path) -> bool {
+    path.extension().is_some_and(|e| e == "gz")
+}
+
+/// Read a session log, decompressing it if needed
+fn read_log(path: &Path) -> std::io::Result<Vec<u8>> {
+    let file = File::open(path)?;
+    let mut bytes = Vec::new();
+    if is_compressed(path) {
+        flate2::read::GzDecoder::new(file).read_to_end(&mut bytes)?;
+    } else {
+        BufReader::new(file).read_to_end(&mut bytes)?;
+    }
+    Ok(bytes)
+}
+
+/// Restore a compressed log to `.jsonl` so it can be appended to again
+fn decompress(path: &Path) -> std::io::Result<PathBuf> {
+    let plain = path.with_extension("");
+    let bytes = read_log(path)?;
+    std::fs::write(&plain, bytes)?;
+    std::fs::remove_file(path)?;
+    Ok(plain)
+}
+
+/// Serialize an item as one log line with its sequence number
+fn encode_line(seq: u64, item: &SessionItem) -> std::io::Result<String> {
+    let mut value = serde_json::to_value(item)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    if let serde_json::Value::Object(map) = &mut value {
+        map.insert("seq".to_string(), seq.into());
+    }
+    let mut line = serde_json::to_string(&value)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+    line.push('\n');
+    Ok(line)
+}
+
+/// Parse one log line into its sequence number (absent in older logs) and item
+fn decode_line(line: &[u8]) -> Option<(Option<u64>, SessionItem)> {
+    let value: serde_json::Value = serde_json::from_slice(line).ok()?;
+    let seq = value.get("seq").and_then(serde_json::Value::as_u64);
+    let item = serde_json::from_value(value).ok()?;
+    Some((seq, item))
+}
+
 /// Extract messages from session items for context reconstruction
 pub fn extract_messages(items: &[SessionItem]) -> Vec<Message> {
     items
@@ -544,13 +833,13 @@ mod tests {
     #[test]
     fn test_extract_messages() {
         let items = vec![
-            SessionItem::SessionMeta(SessionMetaLine::new(
+            SessionItem::SessionMeta(Box::new(SessionMetaLine::new(
                 "thread_1",
                 "model",
                 "provider",
                 PathBuf::from("."),
                 "policy",
-            )),
+            ))),
             SessionItem::Message(SessionMessage::new(Message::user("Hello"))),
             SessionItem::ToolCall {
                 id: "tc_1".to_string(),
@@ -9,13 +9,13 @@ use uira_memory::{
     EmbeddingProvider, MemorySystem, MockEmbeddingProvider, OpenAIEmbeddingProvider,
 };
 use uira_orchestration::{
-    register_builtins_with_todos, AgentExecutor, ApprovalCache, AstToolProvider,
-    DelegationToolProvider, LspToolProvider, McpToolProvider, MemoryForgetTool, MemoryProfileTool,
-    MemorySearchTool, MemoryStoreTool, TodoStore, ToolCallRuntime, ToolContext, ToolOrchestrator,
-    ToolRouter,
+    register_builtins_with_todos, AgentExecutor, ApprovalCache, ApprovalPolicy, AstToolProvider,
+    CiToolProvider, DelegationToolProvider, DocumentExtractTool, FileTracker, GitHubToolProvider,
+    LspToolProvider, McpToolProvider, MemoryForgetTool, MemoryProfileTool, MemorySearchTool,
+    MemoryStoreTool, TodoStore, ToolCallRuntime, ToolContext, ToolOrchestrator, ToolRouter,
 };
 use uira_providers::ModelClient;
-use uira_security::build_evaluator_from_rules;
+use uira_security::EvaluatorBuilder;
 use uira_security::SandboxManager;
 
 use crate::AgentConfig;
@@ -65,6 +65,16 @@ pub struct Session {
 
     /// Total token usage
     pub usage: TokenUsage,
+
+    /// Branch whose notepad this session uses (`None` for the root branch)
+    pub notepad_branch: Option<String>,
+
+    /// Re-inject notepad priority context with the next prompt, set after
+    /// compaction and on forked sessions
+    pub notepad_reinject: bool,
+
+    /// Files the agent has read, for detecting edits made outside the session
+    pub file_tracker: FileTracker,
 }
 
 impl Session {
@@ -95,14 +105,34 @@ impl Session {
             );
         }
         register_builtins_with_todos(&mut tool_router, todo_store.clone());
+        tool_router.set_offline(config.offline);
 
-        let memory_config = config.memory.clone().unwrap_or_default();
+        let mut memory_config = config.memory.clone().unwrap_or_default();
         let mut memory_system: Option<Arc<MemorySystem>> = None;
 
+        if memory_config.enabled && config.offline {
+            match memory_config.with_local_embeddings() {
+                Some(local) => memory_config = local,
+                None => tracing::warn!(
+                    provider = %memory_config.embedding_provider,
+                    "memory embeddings from '{}' are unavailable offline and the local model's {} dimensions do not match the store's {}; using text-only FTS5 search",
+                    memory_config.embedding_provider,
+                    memory_config.local_embedding_dimension,
+                    memory_config.embedding_dimension
+                ),
+            }
+        }
+
         if memory_config.enabled {
             let embedder: Arc<dyn EmbeddingProvider> = {
                 let api_key = std::env::var(&memory_config.embedding_api_key_env).ok();
                 match api_key {
+                    _ if memory_config.uses_local_embeddings() => Arc::new(
+                        OpenAIEmbeddingProvider::new_with_key(String::new(), &memory_config),
+                    ),
+                    _ if config.offline => Arc::new(MockEmbeddingProvider::new(
+                        memory_config.embedding_dimension,
+                    )),
                     Some(key) if !key.is_empty() => {
                         Arc::new(OpenAIEmbeddingProvider::new_with_key(key, &memory_config))
                     }
@@ -131,6 +161,11 @@ impl Session {
                     tool_router.register(MemorySearchTool::new(system.searcher.clone()));
                     tool_router.register(MemoryForgetTool::new(system.store.clone()));
                     tool_router.register(MemoryProfileTool::new(system.profile.clone()));
+                    tool_router.register(DocumentExtractTool::new().with_memory(
+                        system.store.clone(),
+                        system.embedder.clone(),
+                        memory_config.clone(),
+                    ));
                     memory_system = Some(system);
                     tracing::info!("memory system initialized with tools and hooks");
                 }
@@ -143,6 +178,28 @@ impl Session {
         tool_router.register_provider(Arc::new(LspToolProvider::new()));
         tool_router.register_provider(Arc::new(AstToolProvider::new()));
 
+        if config.offline {
+            tracing::info!("offline: code host and CI tools are unavailable");
+        }
+        if let Some(code_host) = config
+            .code_host
+            .as_ref()
+            .filter(|host| host.enabled && !config.offline)
+        {
+            tool_router.register_provider(Arc::new(GitHubToolProvider::new(code_host.clone())));
+        }
+
+        if let Some(ci) = config
+            .ci
+            .as_ref()
+            .filter(|ci| ci.enabled && !config.offline)
+        {
+            tool_router.register_provider(Arc::new(CiToolProvider::new(
+                ci.clone(),
+                config.code_host.clone().unwrap_or_default(),
+            )));
+        }
+
         if !config.external_mcp_servers.is_empty() {
             match McpToolProvider::new(
                 config.external_mcp_servers.clone(),
@@ -165,10 +222,22 @@ impl Session {
         let mut orchestrator =
             ToolOrchestrator::new(tool_router.clone(), config.sandbox_policy.clone())
                 .with_full_auto(full_auto);
+        if let Some(snapshots) = config.snapshots.as_ref().filter(|s| s.enabled) {
+            orchestrator = orchestrator.with_snapshots(snapshots.max_snapshots);
+        }
 
         if !config.permission_rules.is_empty() {
-            let config_rules = config.to_permission_config_rules();
-            match build_evaluator_from_rules(config_rules) {
+            // Relative patterns such as `src/**` are scoped to the working directory
+            let evaluator = EvaluatorBuilder::new()
+                .rules(
+                    config
+                        .to_permission_config_rules()
+                        .into_iter()
+                        .map(Into::into),
+                )
+                .workspace_root(&cwd)
+                .build();
+            match evaluator {
                 Ok(evaluator) => {
                     orchestrator = orchestrator.with_permission_evaluator(evaluator);
                     tracing::debug!(
@@ -182,6 +251,11 @@ impl Session {
             }
         }
 
+        orchestrator = orchestrator.with_approval_policy(ApprovalPolicy::for_sandbox(
+            &config.auto_approve,
+            &config.sandbox_policy,
+        ));
+
         let session_id = SessionId::new();
         let mut approval_cache = ApprovalCache::new(session_id.to_string());
         if let Some(ref cache_dir) = config.cache_directory {
@@ -218,6 +292,9 @@ impl Session {
             cwd,
             turn: 0,
             usage: TokenUsage::default(),
+            notepad_branch: None,
+            notepad_reinject: false,
+            file_tracker: FileTracker::new(),
         }
     }
 
@@ -240,6 +317,10 @@ impl Session {
             env: std::collections::HashMap::new(),
             sandbox_type,
             sandbox_policy: self.config.sandbox_policy.clone(),
+            delegation: self.config.delegation.clone(),
+            workspaces: self.config.workspaces.clone(),
+            file_tracker: self.file_tracker.clone(),
+            ..ToolContext::default()
         }
     }
 
@@ -262,6 +343,7 @@ impl Session {
                     messages_removed = result.messages_removed,
                     "context_compacted"
                 );
+                self.notepad_reinject = true;
             }
         }
     }
@@ -280,7 +362,11 @@ impl Session {
 
     /// Get tool specifications for the model API
     pub fn tool_specs(&self) -> Vec<uira_core::ToolSpec> {
-        self.tool_router.specs()
+        let mut specs = self.tool_router.specs();
+        if let Some(ref delegation) = self.config.delegation {
+            specs.retain(|spec| delegation.permits(&spec.name));
+        }
+        specs
     }
 
     /// Fork this session at the current point
@@ -294,6 +380,7 @@ impl Session {
 
         forked.parent_id = Some(self.id.clone());
         forked.forked_from_message = None;
+        forked.notepad_reinject = true;
 
         for msg in self.context.messages().to_vec() {
             let _ = forked.context.add_message(msg);
@@ -310,6 +397,7 @@ impl Session {
 
         forked.parent_id = Some(self.id.clone());
         forked.forked_from_message = Some(MessageId::new());
+        forked.notepad_reinject = true;
 
         let messages: Vec<_> = self
             .context
@@ -5,11 +5,19 @@
 //! committing lines for display.
 
 use uira_core::{ContentBlock, ContentDelta, ModelResponse, StreamChunk, TokenUsage};
+use uira_providers::PartialJsonParser;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum StreamOutput {
     Text(String),
     Thinking(String),
+    /// Arguments of a tool call still streaming, re-sent each time another
+    /// top-level argument completes
+    ToolInput {
+        id: String,
+        name: String,
+        input: serde_json::Value,
+    },
 }
 
 impl PartialEq<&str> for StreamOutput {
@@ -17,6 +25,7 @@ impl PartialEq<&str> for StreamOutput {
         match self {
             StreamOutput::Text(s) => s == *other,
             StreamOutput::Thinking(s) => s == *other,
+            StreamOutput::ToolInput { .. } => false,
         }
     }
 }
@@ -26,6 +35,7 @@ impl PartialEq<String> for StreamOutput {
         match self {
             StreamOutput::Text(s) => s == other,
             StreamOutput::Thinking(s) => s == other,
+            StreamOutput::ToolInput { .. } => false,
         }
     }
 }
@@ -34,7 +44,8 @@ impl PartialEq<String> for StreamOutput {
 ///
 /// Implements the Codex newline-gated streaming pattern:
 /// - Buffer text until `\n` arrives, then commit the line
-/// - Tool JSON accumulates until block stop
+/// - Tool JSON accumulates until block stop, with a preview of the
+///   arguments emitted as each one completes
 /// - Drain any partial lines on message stop
 pub struct StreamController {
     /// Buffered text not yet committed (no newline yet)
@@ -50,6 +61,12 @@ pub struct StreamController {
     /// Tool JSON being accumulated
     tool_json_buffer: String,
 
+    /// Partial parse of the tool JSON for previews
+    tool_json_parser: PartialJsonParser,
+
+    /// Completed arguments already previewed
+    previewed_fields: usize,
+
     /// Current tool ID being accumulated
     tool_id: Option<String>,
 
@@ -98,6 +115,8 @@ impl StreamController {
             committed_lines: Vec::new(),
             pending_in_block: false,
             tool_json_buffer: String::new(),
+            tool_json_parser: PartialJsonParser::new(),
+            previewed_fields: 0,
             tool_id: None,
             tool_name: None,
             thinking_buffer: String::new(),
@@ -146,6 +165,8 @@ impl StreamController {
                         self.tool_id = Some(id.clone());
                         self.tool_name = Some(name.clone());
                         self.tool_json_buffer.clear();
+                        self.tool_json_parser = PartialJsonParser::new();
+                        self.previewed_fields = 0;
                     }
                     ContentBlock::Thinking { .. } => {
                         self.is_text_block = false;
@@ -163,7 +184,7 @@ impl StreamController {
                     ContentDelta::TextDelta { text } => self.push_text(&text),
                     ContentDelta::InputJsonDelta { partial_json } => {
                         self.tool_json_buffer.push_str(&partial_json);
-                        vec![]
+                        self.push_tool_json(&partial_json)
                     }
                     ContentDelta::ThinkingDelta { thinking } => {
                         self.thinking_buffer.push_str(&thinking);
@@ -242,6 +263,23 @@ impl StreamController {
         new_lines
     }
 
+    fn push_tool_json(&mut self, partial_json: &str) -> Vec<StreamOutput> {
+        self.tool_json_parser.push(partial_json);
+        let completed = self.tool_json_parser.completed_fields();
+        if completed <= self.previewed_fields {
+            return vec![];
+        }
+        let (Some(id), Some(name), Some(input)) = (
+            self.tool_id.clone(),
+            self.tool_name.clone(),
+            self.tool_json_parser.value(),
+        ) else {
+            return vec![];
+        };
+        self.previewed_fields = completed;
+        vec![StreamOutput::ToolInput { id, name, input }]
+    }
+
     fn drain_pending(&mut self) -> Vec<StreamOutput> {
         if self.pending_text.is_empty() {
             vec![]
@@ -511,6 +549,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_tool_input_preview_as_arguments_complete() {
+        let mut controller = StreamController::new();
+
+        controller.push(make_message_start());
+        controller.push(StreamChunk::ContentBlockStart {
+            index: 0,
+            content_block: ContentBlock::ToolUse {
+                id: "tc_1".to_string(),
+                name: "Write".to_string(),
+                input: serde_json::Value::Null,
+            },
+        });
+
+        let mut previews = Vec::new();
+        for fragment in [
+            r#"{"file_pa"#,
+            r#"th": "src/li"#,
+            r#"b.rs", "con"#,
+            r#"tent": "fn main() {}"#,
+            r#""}"#,
+        ] {
+            previews.extend(controller.push(StreamChunk::ContentBlockDelta {
+                index: 0,
+                delta: ContentDelta::InputJsonDelta {
+                    partial_json: fragment.to_string(),
+                },
+            }));
+        }
+
+        assert_eq!(
+            previews,
+            vec![
+                StreamOutput::ToolInput {
+                    id: "tc_1".to_string(),
+                    name: "Write".to_string(),
+                    input: serde_json::json!({ "file_path": "src/lib.rs" }),
+                },
+                StreamOutput::ToolInput {
+                    id: "tc_1".to_string(),
+                    name: "Write".to_string(),
+                    input: serde_json::json!({
+                        "file_path": "src/lib.rs",
+                        "content": "fn main() {}",
+                    }),
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_into_response() {
         let mut controller = StreamController::new();
@@ -1,7 +1,9 @@
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::RwLock;
+use std::time::{Duration, Instant};
+use uira_core::{ThreadEvent, ToolCallStats};
 
 #[derive(Debug, Default, Clone, Serialize, Deserialize)]
 pub struct TokenMetrics {
@@ -104,10 +106,97 @@ impl MetricsCollector {
     }
 }
 
+/// Accumulates timings for a single turn and renders them as a
+/// [`ThreadEvent::TurnSummary`]
+#[derive(Debug)]
+pub struct TurnStats {
+    started: Instant,
+    context_tokens_before: usize,
+    provider_latency: Duration,
+    retries: u32,
+    tools: BTreeMap<String, ToolCallStats>,
+}
+
+impl TurnStats {
+    pub fn start(context_tokens: usize) -> Self {
+        Self {
+            started: Instant::now(),
+            context_tokens_before: context_tokens,
+            provider_latency: Duration::ZERO,
+            retries: 0,
+            tools: BTreeMap::new(),
+        }
+    }
+
+    pub fn record_provider(&mut self, latency: Duration, retries: u32) {
+        self.provider_latency += latency;
+        self.retries += retries;
+    }
+
+    pub fn record_tool(&mut self, name: &str, duration: Duration, success: bool) {
+        let stats = self
+            .tools
+            .entry(name.to_string())
+            .or_insert_with(|| ToolCallStats {
+                name: name.to_string(),
+                ..Default::default()
+            });
+        stats.calls += 1;
+        stats.total_ms += duration.as_millis() as u64;
+        if !success {
+            stats.errors += 1;
+        }
+    }
+
+    pub fn finish(self, turn_number: usize, context_tokens_after: usize) -> ThreadEvent {
+        ThreadEvent::TurnSummary {
+            turn_number,
+            duration_ms: self.started.elapsed().as_millis() as u64,
+            provider_latency_ms: self.provider_latency.as_millis() as u64,
+            retries: self.retries,
+            tools: self.tools.into_values().collect(),
+            context_tokens_before: self.context_tokens_before,
+            context_tokens_after,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_turn_stats_aggregates_per_tool() {
+        let mut stats = TurnStats::start(100);
+        stats.record_provider(Duration::from_millis(250), 1);
+        stats.record_provider(Duration::from_millis(150), 0);
+        stats.record_tool("Read", Duration::from_millis(10), true);
+        stats.record_tool("Bash", Duration::from_millis(30), false);
+        stats.record_tool("Read", Duration::from_millis(5), true);
+
+        let ThreadEvent::TurnSummary {
+            turn_number,
+            provider_latency_ms,
+            retries,
+            tools,
+            context_tokens_before,
+            context_tokens_after,
+            ..
+        } = stats.finish(2, 400)
+        else {
+            panic!("expected turn summary");
+        };
+        assert_eq!(turn_number, 2);
+        assert_eq!(provider_latency_ms, 400);
+        assert_eq!(retries, 1);
+        assert_eq!((context_tokens_before, context_tokens_after), (100, 400));
+        assert_eq!(tools.len(), 2);
+        assert_eq!(tools[0].name, "Bash");
+        assert_eq!(tools[0].errors, 1);
+        assert_eq!(tools[1].name, "Read");
+        assert_eq!((tools[1].calls, tools[1].total_ms), (2, 15));
+    }
+
     #[test]
     fn test_metrics_collector() {
         let collector = MetricsCollector::new();
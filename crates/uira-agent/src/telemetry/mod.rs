@@ -2,6 +2,6 @@ pub mod metrics;
 pub mod spans;
 pub mod subscriber;
 
-pub use metrics::{MetricsCollector, TokenMetrics};
+pub use metrics::{MetricsCollector, TokenMetrics, TurnStats};
 pub use spans::{AgentSpan, SessionSpan, ToolSpan, TurnSpan};
 pub use subscriber::{init_subscriber, init_tui_subscriber, ChannelLayer, TelemetryConfig};
@@ -6,8 +6,9 @@ use futures::StreamExt;
 use mock_client::MockModelClient;
 use std::sync::Arc;
 use uira_agent::{Agent, AgentConfig, AgentLoopError};
-use uira_core::{AgentState, ContentBlock, Message, ThreadEvent};
-use uira_orchestration::AgentExecutor;
+use uira_core::schema::OutputContract;
+use uira_core::{AgentError, AgentState, ContentBlock, Message, ThreadEvent};
+use uira_orchestration::{AgentExecutor, DelegationContext};
 
 struct MockSubagentExecutor;
 
@@ -17,8 +18,9 @@ impl AgentExecutor for MockSubagentExecutor {
         &self,
         _prompt: &str,
         _model: &str,
-        _allowed_tools: Option<Vec<String>>,
+        _delegation: DelegationContext,
         _max_turns: Option<usize>,
+        _cancellation: tokio_util::sync::CancellationToken,
     ) -> Result<String, String> {
         Ok("mock subagent result".to_string())
     }
@@ -422,6 +424,66 @@ async fn test_recorded_messages_context() {
     assert!(recorded[1].len() >= 4);
 }
 
+#[tokio::test]
+async fn test_invalid_tool_input_is_sent_back_for_one_repair() {
+    let client = Arc::new(MockModelClient::new());
+    let path = std::env::temp_dir().join(format!("uira-repair-{}.txt", std::process::id()));
+    client.queue_tool_call("tc_1", "Write", serde_json::json!({"file_path": path}));
+    client.queue_tool_call("tc_2", "Write", serde_json::json!({"path": path}));
+    client.queue_text("Gave up on the file.");
+
+    let mut agent = Agent::new(make_config(), client.clone());
+    let result = agent.run("Write the file").await.unwrap();
+    assert_eq!(result.output, "Gave up on the file.");
+    assert!(!path.exists());
+
+    let recorded = client.recorded_messages();
+    let first = format!("{:?}", recorded[1].last().unwrap());
+    assert!(first.contains("invalid_tool_input"));
+    assert!(first.contains("/content"));
+    assert!(first.contains("Call Write again"));
+
+    // The repaired call failed too, so there is no second repair prompt
+    let second = format!("{:?}", recorded[2].last().unwrap());
+    assert!(second.contains("/path"));
+    assert!(!second.contains("Call Write again"));
+}
+
+#[tokio::test]
+async fn test_output_contract_violation_is_repaired_once() {
+    let diff = "--- a/src/lib.rs\n+++ b/src/lib.rs\n@@ -1 +1 @@\n-old\n+new\n";
+    let client = Arc::new(MockModelClient::new());
+    client.queue_text("I changed `old` to `new`.");
+    client.queue_text(format!("```diff\n{}```", diff));
+
+    let config = make_config().with_output_contract(OutputContract::UnifiedDiff);
+    let mut agent = Agent::new(config, client.clone());
+    let result = agent.run("Rename old to new").await.unwrap();
+    assert!(result.success);
+    assert_eq!(result.output, diff);
+
+    let recorded = client.recorded_messages();
+    let repair = format!("{:?}", recorded[1].last().unwrap());
+    assert!(repair.contains("does not follow the unified-diff output contract"));
+}
+
+#[tokio::test]
+async fn test_output_contract_fails_after_second_violation() {
+    let client = Arc::new(MockModelClient::new());
+    client.queue_text("Looks fine.");
+    client.queue_text("Still looks fine.");
+
+    let config = make_config().with_output_contract(OutputContract::Json { schema: None });
+    let mut agent = Agent::new(config, client.clone());
+    let result = agent.run("Review the change").await.unwrap();
+    assert!(!result.success);
+    assert!(matches!(
+        result.error,
+        Some(AgentError::OutputContractViolated { ref contract, .. }) if contract == "json"
+    ));
+    assert_eq!(client.call_count(), 2);
+}
+
 #[tokio::test]
 async fn test_interactive_mode() {
     let client = Arc::new(MockModelClient::new());
@@ -511,3 +573,354 @@ async fn test_interactive_quit_command() {
     assert!(result.is_ok());
     assert_eq!(client.call_count(), 0); // No model calls made
 }
+
+#[tokio::test]
+async fn test_forked_branch_reinjects_notepad_priority() {
+    use uira_agent::AgentCommand;
+    use uira_orchestration::hooks::NotepadHook;
+
+    let dir = tempfile::tempdir().unwrap();
+    let cwd = dir.path().to_str().unwrap().to_string();
+    NotepadHook::set_priority_context(&cwd, "Deploys go through staging", None);
+
+    let client = Arc::new(MockModelClient::new());
+    client.queue_text("On the branch");
+
+    let config = make_config().with_working_directory(dir.path());
+    let (mut agent, input_tx, _approval_rx, command_tx) =
+        Agent::new(config, client.clone()).with_interactive();
+    let handle = tokio::spawn(async move { agent.run_interactive().await });
+
+    let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+    command_tx
+        .send(AgentCommand::Fork {
+            branch_name: Some("experiment".to_string()),
+            message_count: None,
+            response_tx,
+        })
+        .await
+        .unwrap();
+    response_rx.await.unwrap().unwrap();
+
+    let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+    command_tx
+        .send(AgentCommand::SwitchBranch {
+            branch_name: "experiment".to_string(),
+            response_tx,
+        })
+        .await
+        .unwrap();
+    response_rx.await.unwrap().unwrap();
+
+    // The branch has its own copy of the notes
+    assert!(NotepadHook::read_branch_notepad(&cwd, Some("experiment")).is_some());
+
+    input_tx.send(Message::user("Continue")).await.unwrap();
+    tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+    drop(input_tx);
+    let _ = tokio::time::timeout(tokio::time::Duration::from_millis(200), handle).await;
+
+    let recorded = client.recorded_messages();
+    let prompt = format!("{:?}", recorded[0].last().unwrap().content);
+    assert!(prompt.contains("<notepad-priority>"));
+    assert!(prompt.contains("Deploys go through staging"));
+}
+
+#[tokio::test]
+async fn test_recorded_responses_replay_without_provider() {
+    use uira_providers::{CachingModelClient, ModelClient, ProviderConfig, RecordingMode};
+
+    let dir = tempfile::tempdir().unwrap();
+    let mock = Arc::new(MockModelClient::new());
+    mock.queue_tool_call("tc_1", "bash", serde_json::json!({"command": "echo hello"}));
+    mock.queue_text("The command output was: hello");
+
+    let recorder = Arc::new(CachingModelClient::new(
+        mock.clone(),
+        dir.path(),
+        RecordingMode::Record,
+    ));
+    let recorded = Agent::new(make_config(), recorder)
+        .run("Run echo hello")
+        .await
+        .unwrap();
+
+    let config = ProviderConfig {
+        model: mock.model().to_string(),
+        ..Default::default()
+    };
+    let replayer = Arc::new(CachingModelClient::replay(dir.path(), &config));
+    let replayed = Agent::new(make_config(), replayer)
+        .run("Run echo hello")
+        .await
+        .unwrap();
+
+    assert_eq!(mock.call_count(), 2);
+    assert_eq!(replayed.output, recorded.output);
+    assert_eq!(replayed.turns, 2);
+}
+
+#[tokio::test]
+async fn test_scripted_scenario_with_interrupted_stream() {
+    use uira_providers::MockModelClient as ScriptedClient;
+
+    let client = Arc::new(
+        ScriptedClient::from_yaml(
+            r#"
+name: interrupted
+turns:
+  - tool_calls:
+      - name: bash
+        input: { command: "echo hello" }
+  - text: "This answer never finishes"
+    chunk_delay_ms: 1
+    fail_after_chunks: 3
+"#,
+        )
+        .unwrap(),
+    );
+
+    let mut agent = Agent::new(make_config(), client.clone());
+    let result = agent.run("Run echo hello").await;
+
+    assert!(matches!(result, Err(AgentLoopError::Provider(_))));
+    assert_eq!(client.call_count(), 2);
+    let tool_result = format!("{:?}", client.requests()[1].last().unwrap().content);
+    assert!(tool_result.contains("call_1_0"));
+}
+
+#[tokio::test]
+async fn test_privacy_mode_scrubs_provider_requests() {
+    use uira_core::schema::PrivacySettings;
+    use uira_providers::MockModelClient as ScriptedClient;
+
+    let workdir = tempfile::tempdir().unwrap();
+    let client = Arc::new(
+        ScriptedClient::from_yaml(
+            r#"
+turns:
+  - text: "I will email <EMAIL_1> from <IP_1>."
+"#,
+        )
+        .unwrap(),
+    );
+
+    let config = make_config()
+        .with_working_directory(workdir.path())
+        .with_privacy(PrivacySettings {
+            enabled: true,
+            ..PrivacySettings::default()
+        });
+    let mut agent = Agent::new(config, client.clone());
+    let result = agent
+        .run("Email grace@example.com from 192.168.1.20")
+        .await
+        .unwrap();
+
+    let sent = format!("{:?}", client.requests()[0]);
+    assert!(sent.contains("Email <EMAIL_1> from <IP_1>"));
+    assert!(!sent.contains("grace@example.com"));
+    assert_eq!(
+        result.output,
+        "I will email grace@example.com from 192.168.1.20."
+    );
+    assert!(workdir.path().join(".uira/privacy/mapping.json").exists());
+}
+
+#[tokio::test]
+async fn test_speculative_fallback_replays_on_escalation() {
+    let cheap = Arc::new(MockModelClient::new());
+    cheap.queue_text("I'm not sure how these modules fit together.");
+    let strong = Arc::new(MockModelClient::new());
+    strong.queue_text("The router dispatches to the handlers in src/handlers.");
+
+    let (mut agent, event_stream) = Agent::new(make_config(), cheap.clone())
+        .with_speculative_fallback(strong.clone())
+        .with_event_stream();
+    let events_handle = tokio::spawn(async move {
+        let mut stream = std::pin::pin!(event_stream);
+        let mut switched = false;
+        while let Some(event) = stream.next().await {
+            switched |= matches!(event, ThreadEvent::ModelSwitched { .. });
+        }
+        switched
+    });
+
+    let result = agent.run("Explain the request flow").await.unwrap();
+
+    assert!(result.success);
+    assert_eq!(
+        result.output,
+        "The router dispatches to the handlers in src/handlers."
+    );
+    assert_eq!(cheap.call_count(), 1);
+    assert_eq!(strong.call_count(), 1);
+    // The replay sends the same context the cheap model saw
+    assert_eq!(
+        strong.recorded_messages()[0].len(),
+        cheap.recorded_messages()[0].len()
+    );
+
+    drop(agent);
+    assert!(events_handle.await.unwrap());
+}
+
+#[tokio::test]
+async fn test_speculative_fallback_unused_when_confident() {
+    let cheap = Arc::new(MockModelClient::new());
+    cheap.queue_text("Done.");
+    let strong = Arc::new(MockModelClient::new());
+
+    let mut agent =
+        Agent::new(make_config(), cheap.clone()).with_speculative_fallback(strong.clone());
+    let result = agent.run("Say done").await.unwrap();
+
+    assert_eq!(result.output, "Done.");
+    assert_eq!(strong.call_count(), 0);
+}
+
+#[tokio::test]
+async fn test_consensus_reconciles_final_answer() {
+    let primary = Arc::new(MockModelClient::new());
+    primary.queue_tool_call("tc_1", "bash", serde_json::json!({"command": "echo auth"}));
+    primary.queue_text("Tokens never expire.");
+    let reviewer = Arc::new(MockModelClient::new());
+    reviewer.queue_text("Looks fine.");
+    let judge = Arc::new(MockModelClient::new());
+    judge.queue_text(
+        "Tokens never expire; add a TTL.\nDISAGREEMENTS:\n- second reviewer missed the TTL",
+    );
+
+    let (mut agent, event_stream) = Agent::new(make_config(), primary.clone())
+        .with_consensus(vec![reviewer.clone()], judge.clone())
+        .with_event_stream();
+    let events_handle = tokio::spawn(async move {
+        let mut stream = std::pin::pin!(event_stream);
+        let mut disagreements = Vec::new();
+        while let Some(event) = stream.next().await {
+            if let ThreadEvent::ConsensusReached {
+                models,
+                disagreements: found,
+                ..
+            } = event
+            {
+                assert_eq!(models.len(), 2);
+                disagreements = found;
+            }
+        }
+        disagreements
+    });
+
+    let result = agent.run("Review the auth module").await.unwrap();
+
+    assert_eq!(result.output, "Tokens never expire; add a TTL.");
+    // Only the final answer goes to the panel, not the tool-calling turn
+    assert_eq!(reviewer.call_count(), 1);
+    assert_eq!(judge.call_count(), 1);
+    let judge_request = format!("{:?}", judge.recorded_messages()[0]);
+    assert!(judge_request.contains("Review the auth module"));
+    assert!(judge_request.contains("Looks fine."));
+
+    drop(agent);
+    assert_eq!(
+        events_handle.await.unwrap(),
+        vec!["second reviewer missed the TTL"]
+    );
+}
+
+#[tokio::test]
+async fn test_consensus_keeps_answer_when_panel_fails() {
+    let primary = Arc::new(MockModelClient::new());
+    primary.queue_text("Split the service.");
+    let reviewer = Arc::new(MockModelClient::new());
+    reviewer.queue_error("rate limited");
+    let judge = Arc::new(MockModelClient::new());
+
+    let mut agent = Agent::new(make_config(), primary.clone())
+        .with_consensus(vec![reviewer.clone()], judge.clone());
+    let result = agent.run("Plan the migration").await.unwrap();
+
+    assert_eq!(result.output, "Split the service.");
+    assert_eq!(judge.call_count(), 0);
+}
+
+#[tokio::test]
+async fn test_risky_command_is_explained_before_approval() {
+    let client = Arc::new(MockModelClient::new());
+    client.queue_tool_call(
+        "tc_1",
+        "Bash",
+        serde_json::json!({"command": "git push --force origin main"}),
+    );
+    client.queue_text("Left the branch alone.");
+    let explainer = Arc::new(MockModelClient::new());
+    explainer.queue_text(
+        "SUMMARY: Overwrites origin/main with the local branch.\nEFFECTS:\n- Drops remote commits",
+    );
+
+    let config = AgentConfig {
+        require_approval_for_commands: true,
+        ..AgentConfig::default()
+    };
+    let (agent, event_stream) = Agent::new(config, client.clone())
+        .with_command_explainer(explainer.clone())
+        .with_event_stream();
+    let (mut agent, _input_tx, mut approval_rx, _command_tx) = agent.with_interactive();
+    let approvals = tokio::spawn(async move {
+        let pending = approval_rx.recv().await.unwrap();
+        let reason = pending.reason.clone();
+        let _ = pending
+            .response_tx
+            .send(uira_core::ReviewDecision::Deny { reason: None });
+        reason
+    });
+    let events_handle = tokio::spawn(async move {
+        let mut stream = std::pin::pin!(event_stream);
+        while let Some(event) = stream.next().await {
+            if let ThreadEvent::CommandExplained(explained) = event {
+                return Some((explained.class, explained.effects, explained.model));
+            }
+        }
+        None
+    });
+
+    let result = agent.run("Publish the branch").await.unwrap();
+    assert_eq!(result.output, "Left the branch alone.");
+
+    let reason = approvals.await.unwrap();
+    assert!(reason.contains("Overwrites origin/main with the local branch."));
+    assert!(reason.contains("Effects: Drops remote commits"));
+    assert_eq!(explainer.call_count(), 1);
+
+    let (class, effects, model) = events_handle.await.unwrap().unwrap();
+    assert_eq!(class, "destructive");
+    assert_eq!(effects, vec!["Drops remote commits"]);
+    assert!(model.is_some());
+}
+
+#[tokio::test]
+async fn test_invoked_skill_restrictions_last_for_the_run() {
+    let dir = tempfile::tempdir().unwrap();
+    let commands_dir = dir.path().join(".claude").join("commands");
+    std::fs::create_dir_all(&commands_dir).unwrap();
+    std::fs::write(
+        commands_dir.join("uira:docs.md"),
+        "---\ndescription: Docs\nallowed-tools: [Read]\n---\nUpdate the docs",
+    )
+    .unwrap();
+
+    let client = Arc::new(MockModelClient::new());
+    client.queue_tool_call("tc_1", "Bash", serde_json::json!({"command": "echo hi"}));
+    client.queue_text("Done");
+
+    let config = make_config().with_working_directory(dir.path());
+    let mut agent = Agent::new(config, client.clone());
+    agent.run("/uira:docs").await.unwrap();
+
+    let recorded = client.recorded_messages();
+    let prompt = format!("{:?}", recorded[0].last().unwrap().content);
+    assert!(prompt.contains("Update the docs"));
+    let tool_result = format!("{:?}", recorded[1].last().unwrap().content);
+    assert!(tool_result.contains("allowed-tools of skill 'uira:docs'"));
+    assert!(agent.session().orchestrator.active_skills().is_empty());
+}
@@ -22,7 +22,7 @@ fn make_test_meta() -> SessionMetaLine {
 fn test_session_item_serialization() {
     // Test SessionMeta
     let meta = make_test_meta();
-    let item = SessionItem::SessionMeta(meta.clone());
+    let item = SessionItem::SessionMeta(Box::new(meta.clone()));
     let json = serde_json::to_string(&item).unwrap();
     assert!(json.contains("\"type\":\"session_meta\""));
     assert!(json.contains("test-session-123"));
@@ -70,6 +70,8 @@ fn test_session_save_load() {
         parent_id: None,
         forked_from_message: None,
         fork_count: 0,
+        agent: None,
+        experiments: Default::default(),
     };
 
     let session_path = temp_dir.path().join("test-session.jsonl");
@@ -80,7 +82,7 @@ fn test_session_save_load() {
     writeln!(
         file,
         "{}",
-        serde_json::to_string(&SessionItem::SessionMeta(meta)).unwrap()
+        serde_json::to_string(&SessionItem::SessionMeta(Box::new(meta))).unwrap()
     )
     .unwrap();
     writeln!(
@@ -146,6 +148,8 @@ fn test_extract_metadata() {
         parent_id: None,
         forked_from_message: None,
         fork_count: 0,
+        agent: None,
+        experiments: Default::default(),
     };
 
     // Write metadata
@@ -154,7 +158,7 @@ fn test_extract_metadata() {
     writeln!(
         file,
         "{}",
-        serde_json::to_string(&SessionItem::SessionMeta(meta)).unwrap()
+        serde_json::to_string(&SessionItem::SessionMeta(Box::new(meta))).unwrap()
     )
     .unwrap();
     writeln!(
@@ -181,7 +185,7 @@ fn test_extract_metadata() {
 #[test]
 fn test_extract_messages() {
     let items = vec![
-        SessionItem::SessionMeta(make_test_meta()),
+        SessionItem::SessionMeta(Box::new(make_test_meta())),
         SessionItem::Message(SessionMessage::new(Message::user("Hello"))),
         SessionItem::ToolCall {
             id: "tc_1".to_string(),
@@ -305,3 +309,141 @@ fn test_tool_result_serialization() {
     let json = serde_json::to_string(&error_item).unwrap();
     assert!(json.contains("\"is_error\":true"));
 }
+
+fn sequenced_line(seq: u64, item: &SessionItem) -> String {
+    let mut value = serde_json::to_value(item).unwrap();
+    value["seq"] = seq.into();
+    format!("{}\n", value)
+}
+
+fn write_log(path: &std::path::Path, lines: &[String], tail: &str) {
+    let mut content = lines.concat();
+    content.push_str(tail);
+    std::fs::write(path, content).unwrap();
+}
+
+#[test]
+fn test_recover_truncates_torn_tail_and_marks_session() {
+    let temp_dir = TempDir::new().unwrap();
+    let session_path = temp_dir.path().join("torn.jsonl");
+    write_log(
+        &session_path,
+        &[
+            sequenced_line(1, &SessionItem::SessionMeta(Box::new(make_test_meta()))),
+            sequenced_line(
+                2,
+                &SessionItem::Message(SessionMessage::new(Message::user("Hello"))),
+            ),
+        ],
+        r#"{"seq":3,"type":"message","mess"#,
+    );
+
+    // A torn tail does not stop the log from loading
+    assert_eq!(SessionRecorder::load(&session_path).unwrap().len(), 2);
+
+    let report = SessionRecorder::recover(&session_path).unwrap();
+    assert!(!report.clean);
+    assert_eq!(report.dropped_lines, 1);
+    assert_eq!(report.truncated_bytes, 31);
+    assert_eq!(report.last_seq, 3);
+
+    let items = SessionRecorder::load(&session_path).unwrap();
+    assert_eq!(items.len(), 3);
+    assert!(matches!(
+        items[2],
+        SessionItem::Recovered {
+            dropped_lines: 1,
+            truncated_bytes: 31,
+            ..
+        }
+    ));
+
+    // Recovering again changes nothing
+    let len = std::fs::metadata(&session_path).unwrap().len();
+    let again = SessionRecorder::recover(&session_path).unwrap();
+    assert_eq!(again.dropped_lines, 0);
+    assert_eq!(again.last_seq, 3);
+    assert_eq!(std::fs::metadata(&session_path).unwrap().len(), len);
+}
+
+#[test]
+fn test_recover_drops_lines_out_of_sequence() {
+    let temp_dir = TempDir::new().unwrap();
+    let session_path = temp_dir.path().join("reordered.jsonl");
+    let message = SessionItem::Message(SessionMessage::new(Message::user("Hello")));
+    write_log(
+        &session_path,
+        &[
+            sequenced_line(1, &SessionItem::SessionMeta(Box::new(make_test_meta()))),
+            sequenced_line(2, &message),
+            sequenced_line(2, &message),
+            sequenced_line(3, &message),
+        ],
+        "",
+    );
+
+    let report = SessionRecorder::recover(&session_path).unwrap();
+    assert_eq!(report.dropped_lines, 2);
+    let items = SessionRecorder::load(&session_path).unwrap();
+    assert_eq!(extract_messages(&items).len(), 1);
+}
+
+#[test]
+fn test_load_rejects_corruption_before_the_tail() {
+    let temp_dir = TempDir::new().unwrap();
+    let session_path = temp_dir.path().join("corrupt.jsonl");
+    write_log(
+        &session_path,
+        &[
+            sequenced_line(1, &SessionItem::SessionMeta(Box::new(make_test_meta()))),
+            "{garbage\n".to_string(),
+            sequenced_line(
+                3,
+                &SessionItem::Message(SessionMessage::new(Message::user("Hello"))),
+            ),
+        ],
+        "",
+    );
+
+    assert!(SessionRecorder::load(&session_path).is_err());
+}
+
+#[test]
+fn test_reopened_log_continues_sequence_and_closes_with_trailer() {
+    let temp_dir = TempDir::new().unwrap();
+    let session_path = temp_dir.path().join("reopened.jsonl");
+    write_log(
+        &session_path,
+        &[sequenced_line(
+            1,
+            &SessionItem::SessionMeta(Box::new(make_test_meta())),
+        )],
+        "",
+    );
+
+    {
+        let mut recorder = SessionRecorder::open(session_path.clone()).unwrap();
+        recorder.record_message(Message::user("Hello")).unwrap();
+        recorder.record_turn(1, TokenUsage::default()).unwrap();
+    }
+
+    let content = std::fs::read_to_string(&session_path).unwrap();
+    let seqs: Vec<u64> = content
+        .lines()
+        .map(|line| {
+            serde_json::from_str::<serde_json::Value>(line).unwrap()["seq"]
+                .as_u64()
+                .unwrap()
+        })
+        .collect();
+    // Metadata, the recovery marker for the trailer-less log, message, turn, trailer
+    assert_eq!(seqs, vec![1, 2, 3, 4, 5]);
+
+    let items = SessionRecorder::load(&session_path).unwrap();
+    assert!(matches!(items[1], SessionItem::Recovered { .. }));
+    assert!(matches!(items.last(), Some(SessionItem::Trailer { .. })));
+
+    let report = SessionRecorder::recover(&session_path).unwrap();
+    assert!(report.clean);
+    assert_eq!(report.last_seq, 5);
+}
@@ -9,6 +9,16 @@ pub enum CliMode {
     Rpc,
 }
 
+/// Release stream `self-update` follows
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, ValueEnum)]
+pub enum UpdateChannel {
+    /// Tagged releases only
+    #[default]
+    Stable,
+    /// Prereleases as well, such as `0.2.0-beta.1`
+    Nightly,
+}
+
 /// Uira - Native AI Coding Agent
 #[derive(Parser, Debug)]
 #[command(name = "uira-agent")]
@@ -26,7 +36,7 @@ pub struct Cli {
     #[arg(short, long)]
     pub model: Option<String>,
 
-    /// Provider to use (anthropic, openai, ollama, opencode)
+    /// Provider to use (anthropic, openai, ollama, llamacpp, opencode, mock)
     #[arg(short, long)]
     pub provider: Option<String>,
 
@@ -42,6 +52,11 @@ pub struct Cli {
     #[arg(long)]
     pub full_auto: bool,
 
+    /// Work in a new git worktree and branch, then offer to merge or squash
+    /// the result back when the run ends
+    #[arg(long)]
+    pub worktree: bool,
+
     /// Verbose output - show streaming events (tool calls, thinking, etc.)
     #[arg(short, long)]
     pub verbose: bool,
@@ -59,6 +74,11 @@ pub struct Cli {
     #[arg(long, default_value = "text")]
     pub output: String,
 
+    /// Run without network access: a local provider (ollama or llamacpp),
+    /// no web tools or remote MCP servers, and local memory embeddings
+    #[arg(long, global = true)]
+    pub offline: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -95,12 +115,64 @@ pub enum Commands {
         command: SessionsCommands,
     },
 
+    /// Usage across sessions: tokens and cost per provider and agent, tool
+    /// usage, goal pass rates and turns per task
+    Stats {
+        /// Only sessions started in this window (12h, 30d, 2w), since a date
+        /// (2026-01-31), or all
+        #[arg(long, default_value = "30d")]
+        since: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// A/B experiments over prompts and model routing
+    Experiments {
+        #[command(subcommand)]
+        command: ExperimentsCommands,
+    },
+
+    /// List and restore working tree snapshots
+    Snapshots {
+        #[command(subcommand)]
+        command: SnapshotsCommands,
+    },
+
     /// Authentication commands
     Auth {
         #[command(subcommand)]
         command: AuthCommands,
     },
 
+    /// Models available from authenticated providers
+    Models {
+        #[command(subcommand)]
+        command: ModelsCommands,
+    },
+
+    /// Detect the project's stack and write a starter uira.yml and AGENTS.md
+    Init {
+        /// Accept every proposed section without asking
+        #[arg(long, short = 'y')]
+        yes: bool,
+
+        /// Overwrite an existing config file and AGENTS.md
+        #[arg(long)]
+        force: bool,
+    },
+
+    /// Print the effective configuration with where each value comes from
+    ExplainConfig {
+        /// Only show keys under this prefix (e.g. permissions, agents.explore)
+        key: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
     /// Configuration management
     Config {
         #[command(subcommand)]
@@ -113,12 +185,24 @@ pub enum Commands {
         command: GoalsCommands,
     },
 
+    /// Hook fixtures and replay
+    Hooks {
+        #[command(subcommand)]
+        command: HooksCommands,
+    },
+
     /// Background task management
     Tasks {
         #[command(subcommand)]
         command: TasksCommands,
     },
 
+    /// Planning pipeline artifacts
+    Plan {
+        #[command(subcommand)]
+        command: PlanCommands,
+    },
+
     /// Generate shell completion scripts
     Completion {
         /// Target shell to generate completions for
@@ -132,11 +216,98 @@ pub enum Commands {
         command: GatewayCommands,
     },
 
+    /// Inspect built-in agents and their effective prompts
+    Agents {
+        #[command(subcommand)]
+        command: AgentsCommands,
+    },
+
     /// Manage skills
     Skills {
         #[command(subcommand)]
         command: SkillsCommands,
     },
+
+    /// Scheduled agent jobs (run by `gateway start`)
+    Scheduler {
+        #[command(subcommand)]
+        command: SchedulerCommands,
+    },
+
+    /// Run benchmark task fixtures across a provider/agent matrix
+    Bench {
+        /// Directory of task fixtures (one subdirectory with task.json each)
+        dir: PathBuf,
+
+        /// Target as provider[:model]; repeat to compare (default: current provider)
+        #[arg(long = "target")]
+        targets: Vec<String>,
+
+        /// Agents to cross with every target (comma-separated)
+        #[arg(long, value_delimiter = ',')]
+        agents: Vec<String>,
+
+        /// Keep scratch workspaces after each run
+        #[arg(long)]
+        keep_workspaces: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Open a pull request (GitLab merge request) for the current branch with an
+    /// AI-written description
+    Pr {
+        /// Branch to merge into (default: the remote's default branch)
+        #[arg(long)]
+        base: Option<String>,
+
+        /// Open the pull request as a draft
+        #[arg(long)]
+        draft: bool,
+
+        /// Print the generated title and description without pushing
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Plan an issue with the planning pipeline and work it with autopilot,
+    /// taking goals from its acceptance criteria
+    Issue {
+        /// Issue URL, owner/name#N, or a number in the origin repository
+        issue: String,
+
+        /// Only save the plan and autopilot state; the next interactive
+        /// session picks them up
+        #[arg(long)]
+        no_run: bool,
+    },
+
+    /// Debugging helpers
+    Debug {
+        #[command(subcommand)]
+        command: DebugCommands,
+    },
+
+    /// Replace this binary with the latest signed GitHub release
+    SelfUpdate {
+        /// Release channel to follow
+        #[arg(long, value_enum, default_value_t = UpdateChannel::Stable)]
+        channel: UpdateChannel,
+
+        /// Install this exact version instead of the newest one (e.g. 0.2.0)
+        #[arg(long)]
+        version: Option<String>,
+
+        /// Only report whether an update is available
+        #[arg(long)]
+        check: bool,
+
+        /// Update even when the binary was installed through npm
+        #[arg(long)]
+        force: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -152,7 +323,29 @@ pub enum AuthCommands {
         provider: String,
     },
     /// Show current authentication status
-    Status,
+    Status {
+        /// Also show where credentials are stored (OS keychain or file)
+        #[arg(long = "where")]
+        location: bool,
+    },
+    /// Check credentials with a cheap API call and list available models
+    Test {
+        /// Provider to test (defaults to every configured provider)
+        provider: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ModelsCommands {
+    /// List each provider's models with context window and price
+    List {
+        /// Provider to list (defaults to every configured provider)
+        provider: Option<String>,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -185,10 +378,47 @@ pub enum GoalsCommands {
     Status,
 }
 
+#[derive(Subcommand, Debug)]
+pub enum HooksCommands {
+    /// Replay hook fixtures against the default registry and report any
+    /// decision that changed
+    Test {
+        /// Fixture files or directories of `.json` fixtures
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Rewrite changed fixtures to expect the current output
+        #[arg(long)]
+        update: bool,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Dry-run the messages-transform hooks over a message array and show
+    /// what each hook changed, in the order they ran
+    Transform {
+        /// JSON file holding a message array or an object with `messages`
+        file: PathBuf,
+
+        /// Only run these hooks (repeatable)
+        #[arg(long = "hook")]
+        hooks: Vec<String>,
+
+        /// Output the trace as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum TasksCommands {
     /// List all background tasks
-    List,
+    List {
+        /// Only show failed tasks that haven't been requeued
+        #[arg(long)]
+        failed: bool,
+    },
     /// Get task status
     Status {
         /// Task ID to check
@@ -199,6 +429,55 @@ pub enum TasksCommands {
         /// Task ID to cancel
         task_id: String,
     },
+    /// Show a task's output log
+    Logs {
+        /// Task ID to show logs for
+        task_id: String,
+        /// Keep printing new output until the task finishes
+        #[arg(short, long)]
+        follow: bool,
+    },
+    /// Run a failed task again in the foreground
+    Requeue {
+        /// Task ID of the failed task
+        task_id: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum PlanCommands {
+    /// List persisted planning pipelines
+    List,
+    /// Show a planning pipeline and its stage artifacts
+    Show {
+        /// Plan ID (default: most recently updated plan)
+        plan_id: Option<String>,
+    },
+    /// Continue an interrupted planning pipeline from its last completed stage
+    Resume {
+        /// Plan ID (default: most recent incomplete plan)
+        plan_id: Option<String>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ExperimentsCommands {
+    /// List configured experiments and their variants
+    List,
+    /// Compare goal pass rate, cost and turns across variants
+    Report {
+        /// Only this experiment
+        name: Option<String>,
+
+        /// Only sessions started in this window (12h, 30d, 2w), since a date
+        /// (2026-01-31), or all
+        #[arg(long, default_value = "all")]
+        since: String,
+
+        /// Output as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -217,12 +496,37 @@ pub enum SessionsCommands {
     Info {
         /// Session ID to inspect
         session_id: String,
+        /// Show per-turn telemetry: tool timings, provider latency, retries
+        #[arg(long)]
+        stats: bool,
     },
     /// Delete a session
     Delete {
         /// Session ID to delete
         session_id: String,
     },
+    /// Compress and prune old sessions and background-task artifacts
+    /// according to `retention` in uira.yml
+    Gc {
+        /// Show what would be compressed or removed without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum SnapshotsCommands {
+    /// List snapshots of the current repository, newest first
+    List {
+        /// Show only recent N snapshots
+        #[arg(short, long, default_value = "20")]
+        limit: usize,
+    },
+    /// Restore the working tree to a snapshot
+    Restore {
+        /// Snapshot ID or unique prefix (default: most recent)
+        id: Option<String>,
+    },
 }
 
 #[derive(Subcommand, Debug)]
@@ -241,6 +545,49 @@ pub enum GatewayCommands {
     },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum SchedulerCommands {
+    /// List scheduled jobs and their next run times
+    List,
+    /// Run a job now and print its result without posting it
+    Run {
+        /// Name of the job to run
+        name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum DebugCommands {
+    /// Show the logged provider requests for a session
+    Payloads {
+        /// Session ID or prefix
+        session: String,
+        /// Only the last N requests
+        #[arg(short, long)]
+        last: Option<usize>,
+        /// One line per request instead of full payloads
+        #[arg(long)]
+        summary: bool,
+        /// Payload log file (default: from config)
+        #[arg(long)]
+        path: Option<PathBuf>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum AgentsCommands {
+    /// List built-in agents
+    List,
+    /// Show an agent's model, tools, and prompt overrides
+    Show {
+        /// Name of the agent to show
+        name: String,
+        /// Print only the merged prompt (built-in prompt plus project overrides)
+        #[arg(long)]
+        effective_prompt: bool,
+    },
+}
+
 #[derive(Subcommand, Debug)]
 pub enum SkillsCommands {
     /// List all discovered skills
@@ -250,10 +597,32 @@ pub enum SkillsCommands {
         /// Name of the skill to show
         name: String,
     },
-    /// Install a skill from a local path
+    /// Show skill usage analytics for the current project
+    Stats,
+    /// Install a skill from a local path, git URL, or GitHub owner/repo
     Install {
-        /// Path to the skill directory (must contain SKILL.md)
-        path: String,
+        /// Local directory, git URL, or owner/repo (must contain SKILL.md)
+        source: String,
+        /// Git tag, branch, or commit to pin the install to
+        #[arg(long = "ref")]
+        git_ref: Option<String>,
+        /// Subdirectory within the source that contains SKILL.md
+        #[arg(long)]
+        path: Option<String>,
+        /// Expected sha256 checksum of the skill contents
+        #[arg(long)]
+        checksum: Option<String>,
+        /// Replace an existing install of the same skill
+        #[arg(long)]
+        force: bool,
+    },
+    /// Update installed skills from their recorded sources
+    Update {
+        /// Skill to update (default: all installed skills)
+        name: Option<String>,
+        /// Re-pin the skill to a new git ref
+        #[arg(long = "ref", requires = "name")]
+        git_ref: Option<String>,
     },
 }
 
@@ -272,6 +641,255 @@ mod tests {
     use super::*;
     use clap::Parser;
 
+    #[test]
+    fn parses_plan_resume_command() {
+        let cli = Cli::parse_from(["uira-agent", "plan", "resume", "plan-123"]);
+        match cli.command {
+            Some(Commands::Plan {
+                command: PlanCommands::Resume { plan_id },
+            }) => assert_eq!(plan_id.as_deref(), Some("plan-123")),
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_auth_status_where_flag() {
+        let cli = Cli::parse_from(["uira-agent", "auth", "status", "--where"]);
+        match cli.command {
+            Some(Commands::Auth {
+                command: AuthCommands::Status { location },
+            }) => assert!(location),
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_auth_test_command() {
+        let cli = Cli::parse_from(["uira-agent", "auth", "test", "openai"]);
+        match cli.command {
+            Some(Commands::Auth {
+                command: AuthCommands::Test { provider },
+            }) => assert_eq!(provider.as_deref(), Some("openai")),
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_models_list_command() {
+        let cli = Cli::parse_from(["uira-agent", "models", "list", "anthropic", "--json"]);
+        match cli.command {
+            Some(Commands::Models {
+                command: ModelsCommands::List { provider, json },
+            }) => {
+                assert_eq!(provider.as_deref(), Some("anthropic"));
+                assert!(json);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_snapshots_restore_command() {
+        let cli = Cli::parse_from(["uira-agent", "snapshots", "restore", "20260101-120000"]);
+        match cli.command {
+            Some(Commands::Snapshots {
+                command: SnapshotsCommands::Restore { id },
+            }) => assert_eq!(id.as_deref(), Some("20260101-120000")),
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_pr_command() {
+        let cli = Cli::parse_from(["uira-agent", "pr", "--base", "develop", "--dry-run"]);
+        match cli.command {
+            Some(Commands::Pr {
+                base,
+                draft,
+                dry_run,
+            }) => {
+                assert_eq!(base.as_deref(), Some("develop"));
+                assert!(!draft);
+                assert!(dry_run);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_issue_command() {
+        let cli = Cli::parse_from(["uira-agent", "issue", "junhoyeo/uira#42", "--no-run"]);
+        match cli.command {
+            Some(Commands::Issue { issue, no_run }) => {
+                assert_eq!(issue, "junhoyeo/uira#42");
+                assert!(no_run);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_skills_install_with_ref() {
+        let cli = Cli::parse_from([
+            "uira-agent",
+            "skills",
+            "install",
+            "junhoyeo/uira-skills",
+            "--ref",
+            "v1.0.0",
+            "--path",
+            "skills/release",
+        ]);
+        match cli.command {
+            Some(Commands::Skills {
+                command:
+                    SkillsCommands::Install {
+                        source,
+                        git_ref,
+                        path,
+                        ..
+                    },
+            }) => {
+                assert_eq!(source, "junhoyeo/uira-skills");
+                assert_eq!(git_ref.as_deref(), Some("v1.0.0"));
+                assert_eq!(path.as_deref(), Some("skills/release"));
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_self_update_command() {
+        let cli = Cli::parse_from([
+            "uira-agent",
+            "self-update",
+            "--channel",
+            "nightly",
+            "--check",
+        ]);
+        match cli.command {
+            Some(Commands::SelfUpdate {
+                channel,
+                version,
+                check,
+                force,
+            }) => {
+                assert_eq!(channel, UpdateChannel::Nightly);
+                assert!(version.is_none());
+                assert!(check);
+                assert!(!force);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_init_command() {
+        let cli = Cli::parse_from(["uira-agent", "init", "-y"]);
+        match cli.command {
+            Some(Commands::Init { yes, force }) => {
+                assert!(yes);
+                assert!(!force);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_explain_config_command() {
+        let cli = Cli::parse_from(["uira-agent", "explain-config", "permissions", "--json"]);
+        match cli.command {
+            Some(Commands::ExplainConfig { key, json }) => {
+                assert_eq!(key.as_deref(), Some("permissions"));
+                assert!(json);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_stats_command() {
+        let cli = Cli::parse_from(["uira-agent", "stats", "--since", "7d", "--json"]);
+        match cli.command {
+            Some(Commands::Stats { since, json }) => {
+                assert_eq!(since, "7d");
+                assert!(json);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_experiments_report_command() {
+        let cli = Cli::parse_from(["uira-agent", "experiments", "report", "terse-prompt"]);
+        match cli.command {
+            Some(Commands::Experiments {
+                command: ExperimentsCommands::Report { name, since, json },
+            }) => {
+                assert_eq!(name.as_deref(), Some("terse-prompt"));
+                assert_eq!(since, "all");
+                assert!(!json);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn parses_hooks_test_command() {
+        let cli = Cli::parse_from([
+            "uira-agent",
+            "hooks",
+            "test",
+            "fixtures/keyword",
+            "fixtures/comments",
+            "--update",
+        ]);
+        match cli.command {
+            Some(Commands::Hooks {
+                command:
+                    HooksCommands::Test {
+                        paths,
+                        update,
+                        json,
+                    },
+            }) => {
+                assert_eq!(
+                    paths,
+                    vec![
+                        PathBuf::from("fixtures/keyword"),
+                        PathBuf::from("fixtures/comments")
+                    ]
+                );
+                assert!(update);
+                assert!(!json);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+
+        assert!(Cli::try_parse_from(["uira-agent", "hooks", "test"]).is_err());
+    }
+
+    #[test]
+    fn parses_debug_payloads_command() {
+        let cli = Cli::parse_from(["uira-agent", "debug", "payloads", "abc123", "--last", "2"]);
+        match cli.command {
+            Some(Commands::Debug {
+                command:
+                    DebugCommands::Payloads {
+                        session,
+                        last,
+                        summary,
+                        ..
+                    },
+            }) => {
+                assert_eq!(session, "abc123");
+                assert_eq!(last, Some(2));
+                assert!(!summary);
+            }
+            other => panic!("unexpected command: {:?}", other),
+        }
+    }
+
     #[test]
     fn defaults_to_interactive_mode() {
         let cli = Cli::parse_from(["uira-agent"]);
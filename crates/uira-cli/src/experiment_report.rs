@@ -0,0 +1,226 @@
+//! `uira experiments report`: outcomes per experiment variant
+
+use std::collections::BTreeMap;
+
+use colored::Colorize;
+use serde::Serialize;
+use uira_agent::session::{SessionItem, SessionMetaLine};
+use uira_core::schema::ExperimentSettings;
+use uira_orchestration::features::analytics::CostEstimator;
+
+use crate::stats;
+
+/// Outcome metrics for the sessions assigned one variant
+#[derive(Debug, Clone, Serialize)]
+pub struct VariantOutcome {
+    pub variant: String,
+    /// Configured weight; `None` for variants no longer in the config
+    pub weight: Option<u32>,
+    pub sessions: usize,
+    pub avg_turns: f64,
+    pub avg_cost_usd: f64,
+    pub total_cost_usd: f64,
+    pub goal_checks: usize,
+    /// `None` when no goal was checked in these sessions
+    pub goal_pass_rate: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ExperimentOutcome {
+    pub experiment: String,
+    pub enabled: bool,
+    pub variants: Vec<VariantOutcome>,
+}
+
+/// Compare variants of every configured experiment, plus experiments that
+/// only appear in session logs, optionally narrowed to `name`
+pub fn report(
+    experiments: &[ExperimentSettings],
+    sessions: &[(SessionMetaLine, Vec<SessionItem>)],
+    name: Option<&str>,
+) -> Vec<ExperimentOutcome> {
+    let mut names: Vec<&str> = experiments.iter().map(|e| e.name.as_str()).collect();
+    for (meta, _) in sessions {
+        for experiment in meta.experiments.keys() {
+            if !names.contains(&experiment.as_str()) {
+                names.push(experiment);
+            }
+        }
+    }
+
+    names
+        .into_iter()
+        .filter(|experiment| name.is_none_or(|name| name == *experiment))
+        .map(|experiment| {
+            let settings = experiments.iter().find(|e| e.name == experiment);
+            let mut groups: BTreeMap<&str, Vec<(SessionMetaLine, Vec<SessionItem>)>> =
+                BTreeMap::new();
+            for variant in settings.into_iter().flat_map(|s| &s.variants) {
+                groups.entry(&variant.name).or_default();
+            }
+            for (meta, items) in sessions {
+                if let Some(variant) = meta.experiments.get(experiment) {
+                    groups
+                        .entry(variant)
+                        .or_default()
+                        .push((meta.clone(), items.clone()));
+                }
+            }
+
+            let variants = groups
+                .into_iter()
+                .map(|(variant, group)| {
+                    let summary = stats::aggregate(None, &group);
+                    let per_session = |total: f64| {
+                        if summary.sessions == 0 {
+                            0.0
+                        } else {
+                            total / summary.sessions as f64
+                        }
+                    };
+                    VariantOutcome {
+                        variant: variant.to_string(),
+                        weight: settings
+                            .and_then(|s| s.variants.iter().find(|v| v.name == variant))
+                            .map(|v| v.weight),
+                        sessions: summary.sessions,
+                        avg_turns: summary.avg_turns_per_task,
+                        avg_cost_usd: per_session(summary.cost_usd),
+                        total_cost_usd: summary.cost_usd,
+                        goal_checks: summary.goals.iter().map(|goal| goal.checks).sum(),
+                        goal_pass_rate: summary.goal_pass_rate(),
+                    }
+                })
+                .collect();
+
+            ExperimentOutcome {
+                experiment: experiment.to_string(),
+                enabled: settings.is_some_and(|s| s.enabled),
+                variants,
+            }
+        })
+        .collect()
+}
+
+pub fn display(outcomes: &[ExperimentOutcome]) {
+    if outcomes.is_empty() {
+        println!("No experiments configured or recorded.");
+        return;
+    }
+
+    for outcome in outcomes {
+        let status = if outcome.enabled {
+            "running".green()
+        } else {
+            "stopped".dimmed()
+        };
+        println!("{} ({})", outcome.experiment.cyan().bold(), status);
+        println!(
+            "  {:<20} {:>6} {:>8} {:>10} {:>10} {:>11} {:>8}",
+            "VARIANT", "WEIGHT", "SESSIONS", "AVG TURNS", "AVG COST", "TOTAL COST", "GOALS"
+        );
+        for variant in &outcome.variants {
+            let weight = variant
+                .weight
+                .map_or_else(|| "-".to_string(), |w| w.to_string());
+            let goals = variant
+                .goal_pass_rate
+                .map_or_else(|| "-".to_string(), |rate| format!("{:.0}%", rate * 100.0));
+            println!(
+                "  {:<20} {:>6} {:>8} {:>10.1} {:>10} {:>11} {:>8}",
+                variant.variant,
+                weight,
+                variant.sessions,
+                variant.avg_turns,
+                CostEstimator::format_cost(variant.avg_cost_usd),
+                CostEstimator::format_cost(variant.total_cost_usd),
+                goals
+            );
+        }
+        println!();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use uira_agent::session::EventWrapper;
+    use uira_core::schema::ExperimentVariant;
+    use uira_core::{ThreadEvent, TokenUsage};
+
+    fn session(
+        variant: Option<&str>,
+        turns: usize,
+        passed: bool,
+    ) -> (SessionMetaLine, Vec<SessionItem>) {
+        let mut meta = SessionMetaLine::new(
+            "t",
+            "claude-sonnet-4",
+            "anthropic",
+            PathBuf::from("/"),
+            "read-only",
+        );
+        if let Some(variant) = variant {
+            meta.experiments
+                .insert("prompt".to_string(), variant.to_string());
+        }
+        let mut items: Vec<SessionItem> = (1..=turns)
+            .map(|turn| SessionItem::TurnContext {
+                turn,
+                usage: TokenUsage {
+                    input_tokens: 1000,
+                    output_tokens: 100,
+                    ..TokenUsage::default()
+                },
+            })
+            .collect();
+        items.push(SessionItem::Event {
+            event: EventWrapper::from(ThreadEvent::GoalVerificationResult {
+                goal: "tests".to_string(),
+                score: 0.0,
+                target: 0.0,
+                passed,
+                duration_ms: 0,
+            }),
+        });
+        (meta, items)
+    }
+
+    #[test]
+    fn compares_variants() {
+        let experiments = vec![ExperimentSettings {
+            name: "prompt".to_string(),
+            enabled: true,
+            variants: ["control", "terse", "unused"]
+                .into_iter()
+                .map(|name| ExperimentVariant {
+                    name: name.to_string(),
+                    weight: 1,
+                    ..ExperimentVariant::default()
+                })
+                .collect(),
+        }];
+        let sessions = vec![
+            session(Some("control"), 4, false),
+            session(Some("control"), 2, true),
+            session(Some("terse"), 1, true),
+            session(None, 9, false),
+        ];
+
+        let outcomes = report(&experiments, &sessions, None);
+        assert_eq!(outcomes.len(), 1);
+        let variants = &outcomes[0].variants;
+        assert_eq!(variants.len(), 3);
+        assert_eq!(variants[0].variant, "control");
+        assert_eq!(variants[0].sessions, 2);
+        assert_eq!(variants[0].avg_turns, 3.0);
+        assert_eq!(variants[0].goal_pass_rate, Some(0.5));
+        assert_eq!(variants[1].goal_pass_rate, Some(1.0));
+        assert!(variants[1].avg_cost_usd < variants[0].avg_cost_usd);
+        assert_eq!(variants[2].sessions, 0);
+        assert_eq!(variants[2].goal_pass_rate, None);
+
+        assert!(report(&experiments, &sessions, Some("other")).is_empty());
+    }
+}
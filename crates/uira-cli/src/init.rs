@@ -0,0 +1,604 @@
+//! `uira-agent init`: detect the project's stack and write a starter
+//! `uira.yml` and `AGENTS.md`
+//!
+//! Detection reads manifests (Cargo.toml, package.json, pyproject.toml)
+//! and the git remote without running project code; every proposed section
+//! can be declined before the files are written.
+
+use std::fmt::Write as _;
+use std::io::{BufRead, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+
+use colored::Colorize;
+use uira_core::{ENV_ANTHROPIC_API_KEY, ENV_OPENAI_API_KEY};
+use uira_orchestration::agents::ProviderModels;
+
+const CONFIG_FILE: &str = "uira.yml";
+const AGENTS_FILE: &str = "AGENTS.md";
+
+/// Config files `uira-agent` picks up from the project root
+const EXISTING_CONFIGS: &[&str] = &[
+    "uira.jsonc",
+    "uira.json",
+    "uira.yml",
+    "uira.yaml",
+    ".uira.jsonc",
+    ".uira.json",
+    ".uira.yml",
+    ".uira.yaml",
+];
+
+/// npm's placeholder `test` script
+const NPM_PLACEHOLDER_TEST: &str = "no test specified";
+
+/// A build system found in the project root
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Stack {
+    Cargo { workspace: bool },
+    Node { package_manager: String },
+    Python { runner: Option<String> },
+}
+
+impl Stack {
+    fn label(&self) -> String {
+        match self {
+            Self::Cargo { workspace: true } => "Rust (cargo workspace)".to_string(),
+            Self::Cargo { workspace: false } => "Rust (cargo)".to_string(),
+            Self::Node { package_manager } => format!("JavaScript/TypeScript ({package_manager})"),
+            Self::Python {
+                runner: Some(runner),
+            } => format!("Python ({runner})"),
+            Self::Python { runner: None } => "Python".to_string(),
+        }
+    }
+}
+
+/// A command that checks the project, proposed as a goal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Check {
+    pub name: String,
+    pub command: String,
+    pub description: String,
+}
+
+impl Check {
+    fn new(name: &str, command: impl Into<String>, description: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            command: command.into(),
+            description: description.to_string(),
+        }
+    }
+
+    /// Goals print a score, so a plain pass/fail command scores 100 or 0
+    fn goal_command(&self) -> String {
+        format!("{} >&2 && echo 100 || echo 0", self.command)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct McpServer {
+    pub name: String,
+    pub command: String,
+    pub args: Vec<String>,
+    pub env: Vec<(String, String)>,
+}
+
+/// Everything `init` proposes for a project
+#[derive(Debug, Clone)]
+pub struct Proposal {
+    pub stacks: Vec<Stack>,
+    pub checks: Vec<Check>,
+    pub mcp_servers: Vec<McpServer>,
+    /// Provider prefix for the agent model tiers
+    pub provider: String,
+    pub models: ProviderModels,
+    pub include_permissions: bool,
+    pub include_goals: bool,
+    pub include_mcp: bool,
+    pub include_agents: bool,
+}
+
+impl Proposal {
+    /// Inspect `root` and propose a config for it
+    pub fn detect(root: &Path) -> Self {
+        let mut stacks = Vec::new();
+        let mut checks = Vec::new();
+
+        if let Some(stack) = detect_cargo(root, &mut checks) {
+            stacks.push(stack);
+        }
+        if let Some(stack) = detect_node(root, &mut checks) {
+            stacks.push(stack);
+        }
+        if let Some(stack) = detect_python(root, &mut checks) {
+            stacks.push(stack);
+        }
+
+        let mut mcp_servers = Vec::new();
+        if github_remote(root) {
+            mcp_servers.push(McpServer {
+                name: "github".to_string(),
+                command: "npx".to_string(),
+                args: vec![
+                    "-y".to_string(),
+                    "@modelcontextprotocol/server-github".to_string(),
+                ],
+                env: vec![(
+                    "GITHUB_PERSONAL_ACCESS_TOKEN".to_string(),
+                    "${GITHUB_TOKEN}".to_string(),
+                )],
+            });
+        }
+
+        let (provider, models) = if std::env::var_os(ENV_ANTHROPIC_API_KEY).is_none()
+            && std::env::var_os(ENV_OPENAI_API_KEY).is_some()
+        {
+            ("openai", ProviderModels::openai())
+        } else {
+            ("anthropic", ProviderModels::anthropic())
+        };
+
+        Self {
+            stacks,
+            include_goals: !checks.is_empty(),
+            include_mcp: !mcp_servers.is_empty(),
+            checks,
+            mcp_servers,
+            provider: provider.to_string(),
+            models,
+            include_permissions: true,
+            include_agents: true,
+        }
+    }
+
+    /// Render the accepted sections as `uira.yml`
+    pub fn render_config(&self) -> String {
+        let mut out =
+            String::from("# Generated by `uira-agent init`. See the README for every option.\n");
+
+        if self.include_permissions {
+            out.push_str("\npermissions:\n  rules:\n");
+            // The last matching rule wins, so narrower rules come later
+            push_rule(&mut out, "file:read", "**", "allow", None);
+            push_rule(&mut out, "file:write", "$CWD/**", "allow", None);
+            push_rule(
+                &mut out,
+                "file:read",
+                "**/.env*",
+                "deny",
+                Some("keep secrets out of context"),
+            );
+            push_rule(&mut out, "file:write", "**/.env*", "deny", None);
+            push_rule(&mut out, "shell:execute", "**", "ask", None);
+            for check in &self.checks {
+                push_rule(
+                    &mut out,
+                    "shell:execute",
+                    &format!("{}*", check.command),
+                    "allow",
+                    None,
+                );
+            }
+            push_rule(&mut out, "network:access", "**", "ask", None);
+        }
+
+        if self.include_goals && !self.checks.is_empty() {
+            out.push_str("\ngoals:\n  goals:\n");
+            for check in &self.checks {
+                let _ = writeln!(out, "    - name: {}", yaml_string(&check.name));
+                let _ = writeln!(
+                    out,
+                    "      description: {}",
+                    yaml_string(&check.description)
+                );
+                let _ = writeln!(out, "      command: {}", yaml_string(&check.goal_command()));
+                out.push_str("      target: 100\n      timeout_secs: 600\n");
+            }
+        }
+
+        if self.include_mcp && !self.mcp_servers.is_empty() {
+            out.push_str("\nmcp:\n  servers:\n");
+            for server in &self.mcp_servers {
+                let _ = writeln!(out, "    - name: {}", yaml_string(&server.name));
+                let _ = writeln!(out, "      command: {}", yaml_string(&server.command));
+                let args: Vec<String> = server.args.iter().map(|a| yaml_string(a)).collect();
+                let _ = writeln!(out, "      args: [{}]", args.join(", "));
+                if !server.env.is_empty() {
+                    out.push_str("      env:\n");
+                    for (key, value) in &server.env {
+                        let _ = writeln!(out, "        {}: {}", key, yaml_string(value));
+                    }
+                }
+            }
+        }
+
+        if self.include_agents {
+            out.push_str("\n# Model tiers: quick lookups run cheap, planning and review run strong\nagents:\n");
+            let tiers = [
+                ("explore", &self.models.haiku),
+                ("writer", &self.models.haiku),
+                ("executor", &self.models.sonnet),
+                ("architect", &self.models.opus),
+                ("planner", &self.models.opus),
+                ("critic", &self.models.opus),
+            ];
+            for (agent, model) in tiers {
+                let _ = writeln!(
+                    out,
+                    "  {}:\n    model: {}",
+                    agent,
+                    yaml_string(&format!("{}/{}", self.provider, model))
+                );
+            }
+        }
+
+        out
+    }
+
+    /// Starter `AGENTS.md` listing the stack and the commands found
+    pub fn render_agents_md(&self, project_name: &str) -> String {
+        let mut out = format!("# {project_name}\n\n");
+        out.push_str("Instructions for AI agents working in this repository.\n\n## Stack\n\n");
+        if self.stacks.is_empty() {
+            out.push_str("- TODO: describe the languages and frameworks used\n");
+        }
+        for stack in &self.stacks {
+            let _ = writeln!(out, "- {}", stack.label());
+        }
+
+        out.push_str("\n## Commands\n\n");
+        if self.checks.is_empty() {
+            out.push_str("- TODO: how to build, test and lint\n");
+        }
+        for check in &self.checks {
+            let _ = writeln!(out, "- {}: `{}`", check.description, check.command);
+        }
+        out.push_str("\nRun these before finishing a change.\n");
+
+        out.push_str(
+            "\n## Conventions\n\n- TODO: code style, naming, where tests go\n- TODO: files or directories agents should not touch\n",
+        );
+        out
+    }
+}
+
+fn push_rule(
+    out: &mut String,
+    permission: &str,
+    pattern: &str,
+    action: &str,
+    comment: Option<&str>,
+) {
+    let _ = writeln!(out, "    - permission: {}", yaml_string(permission));
+    let _ = writeln!(out, "      pattern: {}", yaml_string(pattern));
+    let _ = writeln!(out, "      action: {action}");
+    if let Some(comment) = comment {
+        let _ = writeln!(out, "      comment: {}", yaml_string(comment));
+    }
+}
+
+/// JSON strings are valid double-quoted YAML scalars
+fn yaml_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_else(|_| format!("{value:?}"))
+}
+
+fn detect_cargo(root: &Path, checks: &mut Vec<Check>) -> Option<Stack> {
+    let manifest = std::fs::read_to_string(root.join("Cargo.toml")).ok()?;
+    let workspace = manifest
+        .parse::<toml::Table>()
+        .map(|table| table.contains_key("workspace"))
+        .unwrap_or(false);
+    let scope = if workspace { " --workspace" } else { "" };
+
+    checks.push(Check::new(
+        "cargo-test",
+        format!("cargo test{scope}"),
+        "Run the Rust tests",
+    ));
+    checks.push(Check::new(
+        "cargo-clippy",
+        format!("cargo clippy{scope} --all-targets -- -D warnings"),
+        "Lint with clippy",
+    ));
+    checks.push(Check::new(
+        "cargo-fmt",
+        "cargo fmt --all --check",
+        "Check formatting",
+    ));
+    Some(Stack::Cargo { workspace })
+}
+
+fn detect_node(root: &Path, checks: &mut Vec<Check>) -> Option<Stack> {
+    let manifest = std::fs::read_to_string(root.join("package.json")).ok()?;
+    let package: serde_json::Value = serde_json::from_str(&manifest).unwrap_or_default();
+
+    let package_manager = if root.join("bun.lockb").exists() || root.join("bun.lock").exists() {
+        "bun"
+    } else if root.join("pnpm-lock.yaml").exists() {
+        "pnpm"
+    } else if root.join("yarn.lock").exists() {
+        "yarn"
+    } else {
+        "npm"
+    };
+
+    let scripts = package.get("scripts").and_then(|s| s.as_object());
+    for (script, description) in [
+        ("test", "Run the JavaScript tests"),
+        ("lint", "Lint the JavaScript sources"),
+        ("typecheck", "Type-check"),
+    ] {
+        let Some(body) = scripts.and_then(|s| s.get(script)).and_then(|s| s.as_str()) else {
+            continue;
+        };
+        if script == "test" && body.contains(NPM_PLACEHOLDER_TEST) {
+            continue;
+        }
+        checks.push(Check::new(
+            &format!("{package_manager}-{script}"),
+            format!("{package_manager} run {script}"),
+            description,
+        ));
+    }
+
+    Some(Stack::Node {
+        package_manager: package_manager.to_string(),
+    })
+}
+
+fn detect_python(root: &Path, checks: &mut Vec<Check>) -> Option<Stack> {
+    let manifest = std::fs::read_to_string(root.join("pyproject.toml")).ok()?;
+    let table = manifest.parse::<toml::Table>().unwrap_or_default();
+    let tool = table.get("tool").and_then(|t| t.as_table());
+    let has_tool = |name: &str| tool.is_some_and(|t| t.contains_key(name));
+
+    let runner = if root.join("uv.lock").exists() || has_tool("uv") {
+        Some("uv")
+    } else if root.join("poetry.lock").exists() || has_tool("poetry") {
+        Some("poetry")
+    } else {
+        None
+    };
+    let run = |command: &str| match runner {
+        Some(runner) => format!("{runner} run {command}"),
+        None => command.to_string(),
+    };
+
+    if has_tool("pytest") || manifest.contains("pytest") || root.join("tests").is_dir() {
+        checks.push(Check::new("pytest", run("pytest"), "Run the Python tests"));
+    }
+    if has_tool("ruff") || manifest.contains("ruff") {
+        checks.push(Check::new("ruff", run("ruff check ."), "Lint with ruff"));
+    }
+    if has_tool("mypy") {
+        checks.push(Check::new("mypy", run("mypy ."), "Type-check with mypy"));
+    }
+
+    Some(Stack::Python {
+        runner: runner.map(str::to_string),
+    })
+}
+
+fn github_remote(root: &Path) -> bool {
+    std::process::Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(root)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .is_some_and(|output| String::from_utf8_lossy(&output.stdout).contains("github.com"))
+}
+
+fn ask(question: &str, default: bool) -> std::io::Result<bool> {
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    loop {
+        eprint!("{question} {hint} ");
+        std::io::stderr().flush()?;
+        let mut line = String::new();
+        if std::io::stdin().lock().read_line(&mut line)? == 0 {
+            return Ok(default);
+        }
+        match line.trim().to_ascii_lowercase().as_str() {
+            "" => return Ok(default),
+            "y" | "yes" => return Ok(true),
+            "n" | "no" => return Ok(false),
+            other => eprintln!("Unknown choice '{other}'"),
+        }
+    }
+}
+
+pub fn run(yes: bool, force: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let cwd = std::env::current_dir()?;
+    let root = cwd.as_path();
+    if let Some(existing) = EXISTING_CONFIGS
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.exists())
+    {
+        if !force {
+            return Err(format!(
+                "{} already exists; pass --force to overwrite it",
+                existing.display()
+            )
+            .into());
+        }
+    }
+    if !yes && !std::io::stdin().is_terminal() {
+        return Err("stdin is not a terminal; pass --yes to accept the proposed config".into());
+    }
+
+    let mut proposal = Proposal::detect(root);
+
+    if proposal.stacks.is_empty() {
+        println!(
+            "{} no Cargo.toml, package.json or pyproject.toml found",
+            "Detected:".cyan().bold()
+        );
+    } else {
+        let labels: Vec<String> = proposal.stacks.iter().map(Stack::label).collect();
+        println!("{} {}", "Detected:".cyan().bold(), labels.join(", "));
+    }
+
+    if !yes {
+        proposal.include_permissions =
+            ask("Add permission rules (allow project checks, ask before other commands and network access)?", true)?;
+        if !proposal.checks.is_empty() {
+            println!("{}", "Checks found:".cyan().bold());
+            for check in &proposal.checks {
+                println!("  {} {}", check.name.bold(), check.command.dimmed());
+            }
+            proposal.include_goals = ask("Add them as goals?", true)?;
+        }
+        if !proposal.mcp_servers.is_empty() {
+            let names: Vec<&str> = proposal
+                .mcp_servers
+                .iter()
+                .map(|s| s.name.as_str())
+                .collect();
+            proposal.include_mcp = ask(&format!("Add MCP servers ({})?", names.join(", ")), true)?;
+        }
+        proposal.include_agents = ask(
+            &format!(
+                "Set {} model tiers for the built-in agents?",
+                proposal.provider
+            ),
+            true,
+        )?;
+    }
+
+    let config = proposal.render_config();
+    if !yes {
+        println!("\n{}", config.dimmed());
+        if !ask(&format!("Write {CONFIG_FILE}?"), true)? {
+            println!("Nothing written.");
+            return Ok(());
+        }
+    }
+    let config_path = root.join(CONFIG_FILE);
+    std::fs::write(&config_path, config)?;
+    println!("{} {}", "Wrote".green().bold(), config_path.display());
+
+    let agents_path: PathBuf = root.join(AGENTS_FILE);
+    if agents_path.exists() && !force {
+        println!(
+            "{} {} already exists",
+            "Skipped".yellow().bold(),
+            agents_path.display()
+        );
+    } else if yes || ask(&format!("Write a starter {AGENTS_FILE}?"), true)? {
+        let project_name = root
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_else(|| "Project".to_string());
+        std::fs::write(&agents_path, proposal.render_agents_md(&project_name))?;
+        println!("{} {}", "Wrote".green().bold(), agents_path.display());
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn detects_cargo_workspace_and_node_scripts() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"a\"]\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"test": "vitest", "lint": "eslint ."}}"#,
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("pnpm-lock.yaml"), "").unwrap();
+
+        let proposal = Proposal::detect(dir.path());
+        assert_eq!(
+            proposal.stacks,
+            vec![
+                Stack::Cargo { workspace: true },
+                Stack::Node {
+                    package_manager: "pnpm".to_string()
+                }
+            ]
+        );
+        let commands: Vec<&str> = proposal.checks.iter().map(|c| c.command.as_str()).collect();
+        assert!(commands.contains(&"cargo test --workspace"));
+        assert!(commands.contains(&"pnpm run test"));
+        assert!(commands.contains(&"pnpm run lint"));
+    }
+
+    #[test]
+    fn skips_npm_placeholder_test_and_detects_uv_pytest() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("package.json"),
+            r#"{"scripts": {"test": "echo \"Error: no test specified\" && exit 1"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("pyproject.toml"),
+            "[project]\nname = \"x\"\n\n[tool.ruff]\nline-length = 100\n\n[tool.pytest.ini_options]\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("uv.lock"), "").unwrap();
+
+        let proposal = Proposal::detect(dir.path());
+        let commands: Vec<&str> = proposal.checks.iter().map(|c| c.command.as_str()).collect();
+        assert_eq!(commands, vec!["uv run pytest", "uv run ruff check ."]);
+    }
+
+    #[test]
+    fn rendered_config_loads() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        let mut proposal = Proposal::detect(dir.path());
+        proposal.mcp_servers.push(McpServer {
+            name: "github".to_string(),
+            command: "npx".to_string(),
+            args: vec![
+                "-y".to_string(),
+                "@modelcontextprotocol/server-github".to_string(),
+            ],
+            env: vec![("TOKEN".to_string(), "secret".to_string())],
+        });
+        proposal.include_mcp = true;
+
+        let path = dir.path().join(CONFIG_FILE);
+        std::fs::write(&path, proposal.render_config()).unwrap();
+        let config = uira_core::config::load_config_from_file(&path)
+            .unwrap()
+            .config;
+
+        assert_eq!(config.goals.goals.len(), 3);
+        assert_eq!(
+            config.goals.goals[0].command,
+            "cargo test >&2 && echo 100 || echo 0"
+        );
+        assert_eq!(config.goals.goals[0].target, 100.0);
+        assert!(config
+            .permissions
+            .rules
+            .iter()
+            .any(|rule| rule.permission == "shell:execute" && rule.pattern == "cargo test*"));
+        assert_eq!(config.mcp.get("github").unwrap().env["TOKEN"], "secret");
+        let explore = config.agents.agents["explore"].model.as_deref().unwrap();
+        assert!(explore.contains('/'));
+    }
+
+    #[test]
+    fn agents_md_lists_commands() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        let markdown = Proposal::detect(dir.path()).render_agents_md("demo");
+        assert!(markdown.starts_with("# demo\n"));
+        assert!(markdown.contains("- Rust (cargo)\n"));
+        assert!(markdown.contains("`cargo clippy --all-targets -- -D warnings`"));
+    }
+}
@@ -9,45 +9,111 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::mpsc::UnboundedReceiver;
+use uira_agent::experiments;
 use uira_agent::{
     init_subscriber, init_tui_subscriber, Agent, AgentConfig, EventStream, ExecutorConfig,
-    RecursiveAgentExecutor, TelemetryConfig,
+    RecursiveAgentExecutor, SessionRecorder, TelemetryConfig,
 };
 use uira_core::ExecutionResult;
 use uira_core::{
     ENV_ANTHROPIC_API_KEY, ENV_GEMINI_API_KEY, ENV_GOOGLE_API_KEY, ENV_OPENAI_API_KEY,
 };
-use uira_orchestration::{get_agent_definitions, ModelRegistry};
-use uira_providers::{
-    AnthropicClient, GeminiClient, ModelClient, OllamaClient, OpenAIClient, OpenCodeClient,
-    ProviderConfig,
-};
+use uira_orchestration::features::analytics::CostEstimator;
+use uira_orchestration::{get_project_agent_definitions, ModelRegistry};
+use uira_providers::{ModelClient, ModelClientBuilder, ProviderConfig};
 use uira_security::SandboxPolicy;
 
 mod commands;
 mod config;
+mod experiment_report;
+mod init;
+mod offline;
 mod rpc;
+mod self_update;
 mod session;
+mod stats;
+mod worktree;
 
 use commands::{
-    AuthCommands, Cli, CliMode, Commands, ConfigCommands, GatewayCommands, GoalsCommands,
-    SessionsCommands, SkillsCommands, TasksCommands,
+    AgentsCommands, AuthCommands, Cli, CliMode, Commands, ConfigCommands, DebugCommands,
+    ExperimentsCommands, GatewayCommands, GoalsCommands, HooksCommands, ModelsCommands,
+    PlanCommands, SchedulerCommands, SessionsCommands, SkillsCommands, SnapshotsCommands,
+    TasksCommands,
 };
 use config::CliConfig;
 use session::{
-    display_sessions_list, display_sessions_tree, list_sessions, load_session_messages,
-    summarize_session,
+    display_session_stats, display_sessions_list, display_sessions_tree, list_sessions,
+    load_session_messages, session_stats, summarize_session,
 };
 
 // Keep in sync with uira-providers/src/ollama.rs
 const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
 
+/// Default address of llama.cpp's `llama-server`
+const DEFAULT_LLAMACPP_URL: &str = "http://localhost:8080";
+
 #[tokio::main]
 async fn main() {
     let telemetry_config = TelemetryConfig::default();
 
     let cli = Cli::parse();
     let config = CliConfig::load();
+    if let Some(warning) = self_update::current_version_skew() {
+        eprintln!("{}: {}", "Warning".yellow().bold(), warning);
+    }
+    if cfg!(windows) {
+        if let Some(old) = std::env::current_exe()
+            .ok()
+            .and_then(|exe| self_update::stale_binary(&exe))
+        {
+            let _ = std::fs::remove_file(old);
+        }
+    }
+    let payload_log = uira_core::loader::load_config(None)
+        .map(|c| c.providers.effective_payload_log())
+        .unwrap_or_default();
+    uira_providers::payload_log::install(payload_log.clone());
+
+    let worktree_prompt = match &cli.command {
+        Some(Commands::Exec { prompt, .. }) => Some(prompt.clone()),
+        None if cli.mode == CliMode::Interactive => cli.get_prompt(),
+        _ => None,
+    };
+    let worktree = if cli.worktree {
+        let runs_agent = cli.mode == CliMode::Interactive
+            && matches!(cli.command, None | Some(Commands::Exec { .. }));
+        if !runs_agent {
+            eprintln!(
+                "{}: --worktree only applies to interactive and exec runs",
+                "Error".red().bold()
+            );
+            std::process::exit(1);
+        }
+        match enter_worktree() {
+            Ok(worktree) => Some(worktree),
+            Err(e) => {
+                eprintln!("{}: {}", "Error".red().bold(), e);
+                std::process::exit(1);
+            }
+        }
+    } else {
+        None
+    };
+
+    if cli.offline {
+        if let Some(feature) = cli.command.as_ref().and_then(offline::network_feature) {
+            eprintln!(
+                "{}: {}",
+                "Error".red().bold(),
+                offline::unavailable(feature)
+            );
+            std::process::exit(1);
+        }
+    }
+
+    if cli.mode == CliMode::Rpc || matches!(cli.command, None | Some(Commands::Exec { .. })) {
+        spawn_retention_gc();
+    }
 
     let result = if cli.mode == CliMode::Rpc {
         init_subscriber(&telemetry_config);
@@ -70,10 +136,34 @@ async fn main() {
                 init_subscriber(&telemetry_config);
                 run_sessions(command).await
             }
+            Some(Commands::Stats { since, json }) => {
+                init_subscriber(&telemetry_config);
+                run_stats(since, *json)
+            }
+            Some(Commands::Experiments { command }) => {
+                init_subscriber(&telemetry_config);
+                run_experiments(command)
+            }
+            Some(Commands::Snapshots { command }) => {
+                init_subscriber(&telemetry_config);
+                run_snapshots(command)
+            }
             Some(Commands::Auth { command }) => {
                 init_subscriber(&telemetry_config);
                 run_auth(command, &config).await
             }
+            Some(Commands::Models { command }) => {
+                init_subscriber(&telemetry_config);
+                run_models(command).await
+            }
+            Some(Commands::Init { yes, force }) => {
+                init_subscriber(&telemetry_config);
+                init::run(*yes, *force)
+            }
+            Some(Commands::ExplainConfig { key, json }) => {
+                init_subscriber(&telemetry_config);
+                run_explain_config(&cli, &config, key.as_deref(), *json)
+            }
             Some(Commands::Config { command }) => {
                 init_subscriber(&telemetry_config);
                 run_config(command, &config).await
@@ -82,9 +172,17 @@ async fn main() {
                 init_subscriber(&telemetry_config);
                 run_goals(command).await
             }
+            Some(Commands::Hooks { command }) => {
+                init_subscriber(&telemetry_config);
+                run_hooks(command).await
+            }
             Some(Commands::Tasks { command }) => {
                 init_subscriber(&telemetry_config);
-                run_tasks(command).await
+                run_tasks(&cli, &config, command).await
+            }
+            Some(Commands::Plan { command }) => {
+                init_subscriber(&telemetry_config);
+                run_plan(&cli, &config, command).await
             }
             Some(Commands::Completion { shell }) => {
                 init_subscriber(&telemetry_config);
@@ -95,10 +193,53 @@ async fn main() {
                 init_subscriber(&telemetry_config);
                 run_gateway(command).await
             }
+            Some(Commands::Agents { command }) => {
+                init_subscriber(&telemetry_config);
+                run_agents(command)
+            }
             Some(Commands::Skills { command }) => {
                 init_subscriber(&telemetry_config);
                 run_skills(command).await
             }
+            Some(Commands::Scheduler { command }) => {
+                init_subscriber(&telemetry_config);
+                run_scheduler(command).await
+            }
+            Some(Commands::Bench {
+                dir,
+                targets,
+                agents,
+                keep_workspaces,
+                json,
+            }) => {
+                init_subscriber(&telemetry_config);
+                run_bench(&cli, &config, dir, targets, agents, *keep_workspaces, *json).await
+            }
+            Some(Commands::Pr {
+                base,
+                draft,
+                dry_run,
+            }) => {
+                init_subscriber(&telemetry_config);
+                run_pr(&cli, &config, base.as_deref(), *draft, *dry_run).await
+            }
+            Some(Commands::Issue { issue, no_run }) => {
+                init_subscriber(&telemetry_config);
+                run_issue(&cli, &config, issue, *no_run).await
+            }
+            Some(Commands::Debug { command }) => {
+                init_subscriber(&telemetry_config);
+                run_debug(command, payload_log)
+            }
+            Some(Commands::SelfUpdate {
+                channel,
+                version,
+                check,
+                force,
+            }) => {
+                init_subscriber(&telemetry_config);
+                self_update::run(*channel, version.as_deref(), *check, *force).await
+            }
             None => {
                 if let Some(prompt) = cli.get_prompt() {
                     init_subscriber(&telemetry_config);
@@ -111,12 +252,120 @@ async fn main() {
         }
     };
 
+    if let Some(worktree) = worktree {
+        if let Err(e) = finish_worktree(&worktree, worktree_prompt.as_deref()) {
+            eprintln!("{}: {}", "Error".red().bold(), e);
+            eprintln!(
+                "The worktree is still at {} on branch {}",
+                worktree.path.display(),
+                worktree.branch
+            );
+        }
+    }
+
     if let Err(e) = result {
         eprintln!("{}: {}", "Error".red().bold(), e);
         std::process::exit(1);
     }
 }
 
+/// Create a worktree for this run and move the process into it, so the
+/// agent, its tools and the TUI all see the isolated checkout
+fn enter_worktree() -> std::io::Result<worktree::Worktree> {
+    let cwd = std::env::current_dir()?;
+    let worktree = worktree::Worktree::create(&cwd)?;
+    std::env::set_current_dir(worktree.map_cwd(&cwd))?;
+    eprintln!(
+        "{} {} on branch {}",
+        "Worktree:".cyan().bold(),
+        worktree.path.display(),
+        worktree.branch.cyan()
+    );
+    Ok(worktree)
+}
+
+/// Show what the run changed and ask whether to merge, squash, keep or
+/// discard it; without a terminal the worktree is kept
+fn finish_worktree(
+    worktree: &worktree::Worktree,
+    prompt: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use std::io::{BufRead, IsTerminal, Write};
+    use worktree::FinishAction;
+
+    std::env::set_current_dir(&worktree.repo_root)?;
+    let subject = prompt
+        .and_then(|prompt| prompt.lines().next())
+        .map(|line| line.chars().take(72).collect::<String>())
+        .filter(|line| !line.trim().is_empty())
+        .unwrap_or_else(|| format!("Apply changes from {}", worktree.branch));
+    worktree.commit_pending(&subject)?;
+
+    if !worktree.has_changes()? {
+        eprintln!(
+            "{} no changes; removing worktree",
+            "Worktree:".cyan().bold()
+        );
+        worktree.finish(FinishAction::Discard, &subject)?;
+        return Ok(());
+    }
+
+    eprintln!();
+    eprintln!(
+        "{} {} → {}",
+        "Worktree changes:".cyan().bold(),
+        worktree.branch,
+        worktree.original_branch
+    );
+    eprintln!("{}", worktree.diff_stat()?);
+
+    let stdin = std::io::stdin();
+    let action = if stdin.is_terminal() {
+        loop {
+            eprint!("[m]erge, [s]quash, [k]eep, [d]iscard, [v]iew diff (default: keep): ");
+            std::io::stderr().flush()?;
+            let mut line = String::new();
+            if stdin.lock().read_line(&mut line)? == 0 {
+                break FinishAction::Keep;
+            }
+            if matches!(line.trim(), "v" | "view") {
+                eprintln!("{}", worktree.diff()?);
+                continue;
+            }
+            match FinishAction::parse(&line) {
+                Some(action) => break action,
+                None => eprintln!("Unknown choice '{}'", line.trim()),
+            }
+        }
+    } else {
+        FinishAction::Keep
+    };
+
+    worktree.finish(action, &subject)?;
+    match action {
+        FinishAction::Merge => eprintln!(
+            "{} merged {} into {}",
+            "✓".green().bold(),
+            worktree.branch,
+            worktree.original_branch
+        ),
+        FinishAction::Squash => eprintln!(
+            "{} squashed {} into {}",
+            "✓".green().bold(),
+            worktree.branch,
+            worktree.original_branch
+        ),
+        FinishAction::Keep => eprintln!(
+            "Kept {} on branch {}; merge it with `git merge {}`",
+            worktree.path.display(),
+            worktree.branch,
+            worktree.branch
+        ),
+        FinishAction::Discard => eprintln!("Discarded {}", worktree.branch),
+    }
+    Ok(())
+}
+
 async fn run_rpc(cli: &Cli, config: &CliConfig) -> Result<(), Box<dyn std::error::Error>> {
     if cli.command.is_some() || cli.get_prompt().is_some() {
         return Err("RPC mode does not support subcommands or prompt arguments".into());
@@ -124,7 +373,7 @@ async fn run_rpc(cli: &Cli, config: &CliConfig) -> Result<(), Box<dyn std::error
 
     let uira_config = uira_core::loader::load_config(None).ok();
     let agent_model_overrides = build_agent_model_overrides(uira_config.as_ref());
-    let agent_defs = get_agent_definitions(None);
+    let agent_defs = project_agent_definitions(uira_config.as_ref());
     let registry = ModelRegistry::new();
     let (client, _provider_config) = create_client(
         cli,
@@ -136,7 +385,7 @@ async fn run_rpc(cli: &Cli, config: &CliConfig) -> Result<(), Box<dyn std::error
     )?;
 
     let (external_mcp_servers, external_mcp_specs) =
-        prepare_external_mcp(uira_config.as_ref()).await?;
+        prepare_external_mcp(uira_config.as_ref(), cli.offline).await?;
     let agent_config = create_agent_config(
         cli,
         config,
@@ -160,7 +409,7 @@ async fn run_exec(
 
     let uira_config = uira_core::loader::load_config(None).ok();
     let agent_model_overrides = build_agent_model_overrides(uira_config.as_ref());
-    let agent_defs = get_agent_definitions(None);
+    let agent_defs = project_agent_definitions(uira_config.as_ref());
     let registry = ModelRegistry::new();
     let (client, provider_config) = create_client(
         cli,
@@ -171,7 +420,7 @@ async fn run_exec(
         uira_config.as_ref(),
     )?;
     let (external_mcp_servers, external_mcp_specs) =
-        prepare_external_mcp(uira_config.as_ref()).await?;
+        prepare_external_mcp(uira_config.as_ref(), cli.offline).await?;
     let agent_config = create_agent_config(
         cli,
         config,
@@ -181,6 +430,14 @@ async fn run_exec(
         external_mcp_specs,
     );
 
+    let notifier = uira_orchestration::Notifier::new(
+        uira_config
+            .as_ref()
+            .map(|cfg| cfg.notifications.clone())
+            .unwrap_or_default(),
+    );
+    let started_at = std::time::Instant::now();
+
     if !json_output {
         println!("{} {}", "Running:".cyan().bold(), prompt.dimmed());
         println!();
@@ -233,8 +490,16 @@ async fn run_exec(
                             .dimmed()
                         );
                     }
-                    ThreadEvent::Error { message, .. } => {
+                    ThreadEvent::Error {
+                        message, detail, ..
+                    } => {
                         println!("{}: {}", "Error".red().bold(), message);
+                        if let Some(hint) = detail
+                            .as_ref()
+                            .and_then(|detail| detail.remediation.as_ref())
+                        {
+                            println!("{}", format!("Hint: {}", hint).dimmed());
+                        }
                     }
                     ThreadEvent::ThreadCompleted { usage } => {
                         println!(
@@ -256,8 +521,9 @@ async fn run_exec(
             }
         });
 
-        let result = agent.run(prompt).await?;
+        let result = run_exec_prompt(&mut agent, prompt).await?;
         let _ = event_printer.await;
+        notify_exec_finished(&notifier, &result, started_at);
 
         println!();
         println!("{}", "─".repeat(40).dimmed());
@@ -271,7 +537,8 @@ async fn run_exec(
         let executor = Arc::new(RecursiveAgentExecutor::new(executor_config));
         let mut agent = Agent::new_with_executor(agent_config, client, Some(executor))
             .with_session_recording()?;
-        let result = agent.run(prompt).await?;
+        let result = run_exec_prompt(&mut agent, prompt).await?;
+        notify_exec_finished(&notifier, &result, started_at);
 
         if json_output {
             println!("{}", serde_json::to_string_pretty(&result)?);
@@ -405,6 +672,70 @@ async fn run_resume(
     Ok(())
 }
 
+fn run_snapshots(command: &SnapshotsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_orchestration::features::snapshots::SnapshotStore;
+
+    let cwd = std::env::current_dir()?;
+    let store = SnapshotStore::open(&cwd).ok_or("Snapshots need a git repository")?;
+
+    match command {
+        SnapshotsCommands::List { limit } => {
+            let snapshots = store.list()?;
+            if snapshots.is_empty() {
+                println!("{}", "No snapshots yet.".dimmed());
+                return Ok(());
+            }
+
+            println!("{}", "Snapshots:".cyan().bold());
+            println!("{}", "─".repeat(82).dimmed());
+            for snapshot in snapshots.iter().take(*limit) {
+                let created = snapshot
+                    .created_at
+                    .with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M:%S");
+                println!(
+                    "{}  {}  {}",
+                    snapshot.id.yellow(),
+                    created.to_string().dimmed(),
+                    snapshot.label
+                );
+            }
+            println!("{}", "─".repeat(82).dimmed());
+            println!(
+                "{}",
+                "Use 'uira snapshots restore <id>' to roll the working tree back".dimmed()
+            );
+        }
+        SnapshotsCommands::Restore { id } => {
+            let snapshot = match id {
+                Some(id) => store
+                    .find(id)?
+                    .ok_or_else(|| format!("Snapshot not found: {}", id))?,
+                None => store
+                    .list()?
+                    .into_iter()
+                    .next()
+                    .ok_or("No snapshots to restore")?,
+            };
+
+            let backup = store.restore(&snapshot)?;
+            println!(
+                "{} {} ({})",
+                "Restored snapshot".green().bold(),
+                snapshot.id.yellow(),
+                snapshot.label
+            );
+            if let Some(backup) = backup {
+                println!(
+                    "{}",
+                    format!("Previous state saved as {}; restore it to undo", backup.id).dimmed()
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 async fn run_sessions(command: &SessionsCommands) -> Result<(), Box<dyn std::error::Error>> {
     match command {
         SessionsCommands::List { limit, tree } => {
@@ -425,7 +756,7 @@ async fn run_sessions(command: &SessionsCommands) -> Result<(), Box<dyn std::err
                 "Use 'uira sessions list --tree' to show fork relationships".dimmed()
             );
         }
-        SessionsCommands::Info { session_id } => {
+        SessionsCommands::Info { session_id, stats } => {
             println!("{} {}", "Session info:".cyan().bold(), session_id.yellow());
 
             let entries = list_sessions(1000)?;
@@ -446,6 +777,11 @@ async fn run_sessions(command: &SessionsCommands) -> Result<(), Box<dyn std::err
             }
             println!("{}: {}", "Path".cyan(), entry.path.display());
             println!("{}", "─".repeat(50).dimmed());
+
+            if *stats {
+                let items = SessionRecorder::load(&entry.path)?;
+                display_session_stats(&session_stats(&items));
+            }
         }
         SessionsCommands::Delete { session_id } => {
             println!(
@@ -463,6 +799,205 @@ async fn run_sessions(command: &SessionsCommands) -> Result<(), Box<dyn std::err
             std::fs::remove_file(&entry.path)?;
             println!("{} Session deleted", "✓".green().bold());
         }
+        SessionsCommands::Gc { dry_run } => {
+            use uira_orchestration::background_agent::BackgroundTaskConfig;
+
+            let uira_config = uira_core::loader::load_config(None).unwrap_or_default();
+            let background = BackgroundTaskConfig::from_settings(&uira_config.background_tasks);
+            let report =
+                uira_agent::retention::collect(&uira_config.retention, background, *dry_run)?;
+            display_gc_report(&report);
+        }
+    }
+    Ok(())
+}
+
+fn run_stats(since: &str, json_output: bool) -> Result<(), Box<dyn std::error::Error>> {
+    let since = stats::parse_since(since, chrono::Utc::now())?;
+    let report = stats::collect(since)?;
+    if json_output {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        stats::display(&report);
+    }
+    Ok(())
+}
+
+fn run_experiments(command: &ExperimentsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    let uira_config = uira_core::loader::load_config(None).unwrap_or_default();
+    match command {
+        ExperimentsCommands::List => {
+            if uira_config.experiments.is_empty() {
+                println!("No experiments configured. Add them under `experiments:` in uira.yml.");
+                return Ok(());
+            }
+            for experiment in &uira_config.experiments {
+                let status = if experiment.enabled {
+                    "running".green()
+                } else {
+                    "stopped".dimmed()
+                };
+                println!("{} ({})", experiment.name.cyan().bold(), status);
+                let total: u32 = experiment.variants.iter().map(|v| v.weight).sum();
+                for variant in &experiment.variants {
+                    let mut overrides = Vec::new();
+                    if let Some(model) = &variant.model {
+                        overrides.push(format!("model={model}"));
+                    }
+                    if variant.system_prompt.is_some() {
+                        overrides.push("system_prompt".to_string());
+                    }
+                    if variant.prompt_append.is_some() {
+                        overrides.push("prompt_append".to_string());
+                    }
+                    println!(
+                        "  {:<20} {:>4}%  {}",
+                        variant.name,
+                        (variant.weight * 100).checked_div(total).unwrap_or(0),
+                        overrides.join(", ").dimmed()
+                    );
+                }
+            }
+        }
+        ExperimentsCommands::Report { name, since, json } => {
+            let since = stats::parse_since(since, chrono::Utc::now())?;
+            let sessions = stats::load_sessions(since)?;
+            let outcomes =
+                experiment_report::report(&uira_config.experiments, &sessions, name.as_deref());
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&outcomes)?);
+            } else {
+                experiment_report::display(&outcomes);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn display_gc_report(report: &uira_agent::retention::GcReport) {
+    let (compress, remove) = if report.dry_run {
+        ("would compress", "would remove")
+    } else {
+        ("compressed", "removed")
+    };
+    for path in &report.compressed {
+        println!("  {} {}", compress.cyan(), path.display());
+    }
+    for path in &report.removed {
+        println!("  {} {}", remove.red(), path.display());
+    }
+    for task_id in &report.tasks_removed {
+        println!("  {} background task {}", remove.red(), task_id.yellow());
+    }
+    if report.is_empty() {
+        println!("{}", "Nothing to collect.".dimmed());
+        return;
+    }
+
+    let mb = |bytes: u64| bytes as f64 / (1024.0 * 1024.0);
+    println!(
+        "{} {} session(s) {}, {} {}, {} background task(s) {}; {:.1} MB -> {:.1} MB",
+        if report.dry_run {
+            "Dry run:".yellow().bold()
+        } else {
+            "✓".green().bold()
+        },
+        report.compressed.len(),
+        compress,
+        report.removed.len(),
+        remove,
+        report.tasks_removed.len(),
+        remove,
+        mb(report.bytes_before),
+        mb(report.bytes_after)
+    );
+}
+
+/// Compress and prune old sessions in the background, at most once a day
+fn spawn_retention_gc() {
+    use uira_orchestration::background_agent::BackgroundTaskConfig;
+
+    let uira_config = uira_core::loader::load_config(None).unwrap_or_default();
+    let background = BackgroundTaskConfig::from_settings(&uira_config.background_tasks);
+    tokio::task::spawn_blocking(move || {
+        match uira_agent::retention::auto_collect(&uira_config.retention, background) {
+            Ok(Some(report)) if !report.is_empty() => tracing::info!(
+                compressed = report.compressed.len(),
+                removed = report.removed.len(),
+                tasks_removed = report.tasks_removed.len(),
+                bytes_freed = report.bytes_freed(),
+                "Applied session retention"
+            ),
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, "Session retention failed"),
+        }
+    });
+}
+
+fn run_debug(
+    command: &DebugCommands,
+    payload_log: uira_core::schema::PayloadLogSettings,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_providers::{payload_log::read_events, PayloadLogger};
+
+    match command {
+        DebugCommands::Payloads {
+            session,
+            last,
+            summary,
+            path,
+        } => {
+            let log_path = path
+                .clone()
+                .unwrap_or_else(|| PayloadLogger::configured_path(&payload_log));
+            if !log_path.exists() {
+                return Err(format!(
+                    "No payload log at {} (enable providers.payload_log or set UIRA_PAYLOAD_LOG=true)",
+                    log_path.display()
+                )
+                .into());
+            }
+
+            let mut requests: Vec<_> = read_events(&log_path)?
+                .into_iter()
+                .filter(|e| e.stage == "request")
+                .filter(|e| {
+                    e.session_id
+                        .as_deref()
+                        .is_some_and(|id| id == session || id.starts_with(session.as_str()))
+                })
+                .collect();
+            if let Some(last) = last {
+                let skip = requests.len().saturating_sub(*last);
+                requests.drain(..skip);
+            }
+            if requests.is_empty() {
+                return Err(format!(
+                    "No logged requests for session {} in {}",
+                    session,
+                    log_path.display()
+                )
+                .into());
+            }
+
+            for (i, event) in requests.iter().enumerate() {
+                let digest = event.payload_digest.as_deref().unwrap_or("");
+                println!(
+                    "{} {} {}/{} {}",
+                    format!("#{}", i + 1).cyan().bold(),
+                    event.ts.dimmed(),
+                    event.provider.as_deref().unwrap_or("?").yellow(),
+                    event.model_id.as_deref().unwrap_or("?"),
+                    digest.get(..12).unwrap_or(digest).dimmed()
+                );
+                if !*summary {
+                    if let Some(payload) = &event.payload {
+                        println!("{}", serde_json::to_string_pretty(payload)?);
+                    }
+                    println!();
+                }
+            }
+        }
     }
     Ok(())
 }
@@ -594,12 +1129,16 @@ async fn run_auth(
             // Save credentials
             let mut store = CredentialStore::load()?;
 
-            // Convert tokens to StoredCredential via JSON to handle secrecy version mismatch
+            // Convert tokens to StoredCredential via JSON to handle secrecy version mismatch.
+            // Providers report the token lifetime; store when it expires so it
+            // can be refreshed in time.
             let credential_json = serde_json::json!({
                 "type": "oauth",
                 "access_token": tokens.access_token,
                 "refresh_token": tokens.refresh_token,
-                "expires_at": tokens.expires_at,
+                "expires_at": tokens
+                    .expires_at
+                    .map(|expires_in| chrono::Utc::now().timestamp() + expires_in),
             });
             let credential: StoredCredential = serde_json::from_value(credential_json)?;
 
@@ -630,12 +1169,21 @@ async fn run_auth(
                 );
             }
         }
-        AuthCommands::Status => {
+        AuthCommands::Status { location } => {
             println!("{}", "Authentication status:".cyan().bold());
             println!("{}", "─".repeat(50).dimmed());
 
             let store = CredentialStore::load()?;
 
+            if *location {
+                println!(
+                    "{} {} ({})",
+                    "Stored in:".dimmed(),
+                    store.location(),
+                    store.backend_name()
+                );
+            }
+
             if store.is_empty() {
                 println!("{}", "No providers configured".dimmed());
                 println!();
@@ -655,75 +1203,575 @@ async fn run_auth(
 
             println!("{}", "─".repeat(50).dimmed());
         }
-    }
-    Ok(())
-}
-
-async fn run_config(
-    command: &ConfigCommands,
-    config: &CliConfig,
-) -> Result<(), Box<dyn std::error::Error>> {
-    match command {
-        ConfigCommands::Show => {
-            println!("{}", "Current configuration:".cyan().bold());
-            println!("{}", serde_json::to_string_pretty(config)?);
-        }
-        ConfigCommands::Get { key } => {
-            let value = match key.as_str() {
-                "default_provider" => config.default_provider.clone(),
-                "default_model" => config.default_model.clone(),
-                "colors" => Some(config.colors.to_string()),
-                "verbose" => Some(config.verbose.to_string()),
-                _ => None,
+        AuthCommands::Test { provider } => {
+            let providers = match provider {
+                Some(p) => vec![p.to_lowercase()],
+                None => configured_auth_providers(&CredentialStore::load()?),
             };
-            match value {
-                Some(v) => println!("{}", v),
-                _ => println!("{}: {}", "Unknown key".red(), key),
+            if providers.is_empty() {
+                println!("{}", "No providers configured".dimmed());
+                println!("  {} uira auth login <provider>", "→".cyan());
+                return Ok(());
             }
-        }
-        ConfigCommands::Set { key, value } => {
-            let mut new_config = config.clone();
 
-            match key.as_str() {
-                "default_provider" => new_config.default_provider = Some(value.clone()),
-                "default_model" => new_config.default_model = Some(value.clone()),
-                "colors" => new_config.colors = value.parse().unwrap_or(true),
-                "verbose" => new_config.verbose = value.parse().unwrap_or(false),
-                _ => {
-                    println!("{}: {}", "Unknown key".red(), key);
-                    return Ok(());
+            let mut failed = 0;
+            for provider in &providers {
+                if !test_provider_auth(provider).await {
+                    failed += 1;
                 }
             }
-
-            new_config.save()?;
-
-            println!(
-                "{} {} = {}",
-                "Saved:".green().bold(),
-                key.yellow(),
-                value.green()
-            );
-        }
-        ConfigCommands::Reset => {
-            println!("{}", "Resetting configuration to defaults...".cyan().bold());
-            let default_config = CliConfig::default();
-            default_config.save()?;
-            println!("{}", "Configuration reset to defaults.".green());
+            if failed > 0 {
+                return Err(format!("{} of {} providers failed", failed, providers.len()).into());
+            }
         }
     }
     Ok(())
 }
 
-async fn run_goals(command: &GoalsCommands) -> Result<(), Box<dyn std::error::Error>> {
-    use uira_core::loader::load_config;
-    use uira_orchestration::hooks::GoalRunner;
+/// Providers `uira auth test` and `uira models list` cover by default:
+/// those with stored credentials or an API key in the environment
+fn configured_auth_providers(store: &uira_providers::CredentialStore) -> Vec<String> {
+    let sources: &[(&str, &[&str], &[&str])] = &[
+        ("anthropic", &["anthropic"], &[ENV_ANTHROPIC_API_KEY]),
+        ("openai", &["openai"], &[ENV_OPENAI_API_KEY]),
+        (
+            "google",
+            &["google", "gemini"],
+            &[ENV_GEMINI_API_KEY, ENV_GOOGLE_API_KEY],
+        ),
+        ("opencode", &["opencode"], &["OPENCODE_API_KEY"]),
+    ];
+    sources
+        .iter()
+        .filter(|(_, stored, env)| {
+            stored.iter().any(|name| store.get(name).is_some())
+                || env.iter().any(|var| std::env::var(var).is_ok())
+        })
+        .map(|(provider, _, _)| provider.to_string())
+        .collect()
+}
 
-    match command {
-        GoalsCommands::Check => {
-            println!("{}", "Running goal verification...".cyan().bold());
-            println!("{}", "─".repeat(50).dimmed());
+/// Client config for `uira auth test` and `uira models list`; clients fall back to stored
+/// credentials when no API key is set
+fn auth_test_config(provider: &str) -> Option<ProviderConfig> {
+    use uira_core::Provider;
 
-            let config = load_config(None)?;
+    let env_key = |vars: &[&str]| {
+        vars.iter()
+            .find_map(|var| std::env::var(var).ok())
+            .map(SecretString::from)
+    };
+    let (provider, api_key, model, base_url) = match provider {
+        "anthropic" => (
+            Provider::Anthropic,
+            env_key(&[ENV_ANTHROPIC_API_KEY]),
+            uira_core::DEFAULT_ANTHROPIC_MODEL,
+            None,
+        ),
+        "openai" => (
+            Provider::OpenAI,
+            env_key(&[ENV_OPENAI_API_KEY]),
+            uira_core::DEFAULT_OPENAI_MODEL,
+            None,
+        ),
+        "google" | "gemini" => (
+            Provider::Google,
+            env_key(&[ENV_GEMINI_API_KEY, ENV_GOOGLE_API_KEY]),
+            "gemini-1.5-pro",
+            None,
+        ),
+        "opencode" => (
+            Provider::OpenCode,
+            env_key(&["OPENCODE_API_KEY"]),
+            "gpt-5-nano",
+            None,
+        ),
+        "ollama" => (
+            Provider::Ollama,
+            None,
+            "llama3.1",
+            Some(std::env::var("OLLAMA_HOST").unwrap_or_else(|_| DEFAULT_OLLAMA_URL.to_string())),
+        ),
+        _ => return None,
+    };
+    Some(ProviderConfig {
+        provider,
+        api_key,
+        model: model.to_string(),
+        base_url,
+        ..Default::default()
+    })
+}
+
+/// List `provider`'s models as a cheap authenticated call and print the
+/// result; `false` when it failed
+async fn test_provider_auth(provider: &str) -> bool {
+    const MAX_LISTED_MODELS: usize = 15;
+
+    println!("{} {}", "Testing".cyan().bold(), provider.yellow());
+
+    let Some(provider_config) = auth_test_config(provider) else {
+        println!("  {} Unknown provider: {}", "✗".red(), provider);
+        return false;
+    };
+    let client = match ModelClientBuilder::new()
+        .with_config(provider_config)
+        .build()
+    {
+        Ok(client) => client,
+        Err(e) => {
+            println!("  {} {}", "✗".red(), e);
+            return false;
+        }
+    };
+
+    let started = std::time::Instant::now();
+    let list = match client.list_models().await {
+        Ok(list) => list,
+        Err(e) => {
+            println!("  {} {}", "✗".red(), e);
+            return false;
+        }
+    };
+
+    println!(
+        "  {} Authenticated in {}ms, {} models available",
+        "✓".green(),
+        started.elapsed().as_millis(),
+        list.models.len()
+    );
+    for model in list.models.iter().take(MAX_LISTED_MODELS) {
+        match &model.display_name {
+            Some(name) if *name != model.id => {
+                println!("    {} {}", model.id, format!("({})", name).dimmed())
+            }
+            _ => println!("    {}", model.id),
+        }
+    }
+    if list.models.len() > MAX_LISTED_MODELS {
+        println!(
+            "    {}",
+            format!("... and {} more", list.models.len() - MAX_LISTED_MODELS).dimmed()
+        );
+    }
+    if !list.limits.is_empty() {
+        println!("  {}", "Limits:".dimmed());
+        for (name, value) in &list.limits {
+            println!("    {}: {}", name, value);
+        }
+    }
+    true
+}
+
+async fn run_models(command: &ModelsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        ModelsCommands::List { provider, json } => {
+            let providers = match provider {
+                Some(p) => vec![p.to_lowercase()],
+                None => configured_auth_providers(&uira_providers::CredentialStore::load()?),
+            };
+            if providers.is_empty() {
+                println!("{}", "No providers configured".dimmed());
+                println!("  {} uira auth login <provider>", "→".cyan());
+                return Ok(());
+            }
+
+            let listings =
+                futures::future::join_all(providers.iter().map(|p| list_provider_models(p))).await;
+            let registry = ModelRegistry::new();
+
+            if *json {
+                let entries: Vec<serde_json::Value> = providers
+                    .iter()
+                    .zip(&listings)
+                    .map(|(provider, listing)| match listing {
+                        Ok(models) => serde_json::json!({
+                            "provider": provider,
+                            "models": models
+                                .iter()
+                                .map(|id| {
+                                    let metadata = registry.metadata(id);
+                                    serde_json::json!({
+                                        "id": id,
+                                        "context_window": metadata.map(|m| m.context_window),
+                                        "input_price": metadata.and_then(|m| m.input_price),
+                                        "output_price": metadata.and_then(|m| m.output_price),
+                                    })
+                                })
+                                .collect::<Vec<_>>(),
+                        }),
+                        Err(e) => serde_json::json!({ "provider": provider, "error": e }),
+                    })
+                    .collect();
+                println!("{}", serde_json::to_string_pretty(&entries)?);
+                return Ok(());
+            }
+
+            for (provider, listing) in providers.iter().zip(&listings) {
+                println!("{}", provider.to_uppercase().yellow().bold());
+                match listing {
+                    Ok(models) => {
+                        for id in models {
+                            let name = format!("{}/{}", provider, id);
+                            match registry.metadata(id) {
+                                Some(metadata) => {
+                                    let price = match (metadata.input_price, metadata.output_price)
+                                    {
+                                        (Some(input), Some(output)) => {
+                                            format!("${}/${} per 1M", input, output)
+                                        }
+                                        _ => String::new(),
+                                    };
+                                    println!(
+                                        "  {:<48} {:>9}  {}",
+                                        name,
+                                        format!("{}K", metadata.context_window / 1000),
+                                        price.dimmed()
+                                    );
+                                }
+                                None => println!("  {}", name),
+                            }
+                        }
+                    }
+                    Err(e) => println!("  {} {}", "✗".red(), e),
+                }
+            }
+            if listings.iter().all(|listing| listing.is_err()) {
+                return Err("no provider could list its models".into());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Model IDs `provider` reports for the current credentials
+async fn list_provider_models(provider: &str) -> Result<Vec<String>, String> {
+    let provider_config =
+        auth_test_config(provider).ok_or_else(|| format!("Unknown provider: {}", provider))?;
+    let client = ModelClientBuilder::new()
+        .with_config(provider_config)
+        .build()
+        .map_err(|e| e.to_string())?;
+    let list = client.list_models().await.map_err(|e| e.to_string())?;
+    Ok(list.models.into_iter().map(|model| model.id).collect())
+}
+
+fn flag_source(set: bool, flag: &str) -> String {
+    if set {
+        flag.to_string()
+    } else {
+        "built-in default".to_string()
+    }
+}
+
+fn run_explain_config(
+    cli: &Cli,
+    config: &CliConfig,
+    key: Option<&str>,
+    json_output: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_core::config::{explain_config, ValueSource};
+
+    let explanation = explain_config(None)?;
+    let uira_config = uira_core::loader::load_config(None).ok();
+    let agent_model_overrides = build_agent_model_overrides(uira_config.as_ref());
+    let agent_defs = project_agent_definitions(uira_config.as_ref());
+    let registry = ModelRegistry::new();
+    let selection = select_model(cli, config, &agent_defs, &registry, &agent_model_overrides);
+
+    // Settings decided on the command line or in ~/.config/uira/config.toml
+    let mut runtime = vec![
+        (
+            "provider".to_string(),
+            selection.provider.to_string(),
+            selection.provider_source.to_string(),
+        ),
+        (
+            "model".to_string(),
+            selection
+                .model
+                .clone()
+                .unwrap_or_else(|| "(provider default)".to_string()),
+            selection.model_source.clone(),
+        ),
+        (
+            "sandbox".to_string(),
+            cli.sandbox.clone(),
+            flag_source(cli.sandbox != "workspace-write", "--sandbox"),
+        ),
+        (
+            "full_auto".to_string(),
+            cli.full_auto.to_string(),
+            flag_source(cli.full_auto, "--full-auto"),
+        ),
+    ];
+    if let Some(agent) = &cli.agent {
+        runtime.push(("agent".to_string(), agent.clone(), "--agent".to_string()));
+    }
+    if let Some(rules) = &cli.sandbox_rules {
+        runtime.push((
+            "sandbox_rules".to_string(),
+            rules.display().to_string(),
+            "--sandbox-rules".to_string(),
+        ));
+    }
+
+    let values: Vec<_> = match key {
+        Some(prefix) => explanation.filtered(prefix).collect(),
+        None => explanation.values.iter().collect(),
+    };
+
+    if json_output {
+        let runtime: Vec<_> = runtime
+            .iter()
+            .map(|(key, value, source)| serde_json::json!({"key": key, "value": value, "source": source}))
+            .collect();
+        let output = serde_json::json!({
+            "config_file": explanation.path,
+            "shadowed": explanation.shadowed,
+            "runtime": runtime,
+            "values": values,
+        });
+        println!("{}", serde_json::to_string_pretty(&output)?);
+        return Ok(());
+    }
+
+    match &explanation.path {
+        Some(path) => println!("{} {}", "Config file:".cyan().bold(), path.display()),
+        None => println!(
+            "{} none found, using defaults",
+            "Config file:".cyan().bold()
+        ),
+    }
+    for path in &explanation.shadowed {
+        println!(
+            "  {} {} (only the first config file found is loaded)",
+            "ignored".yellow(),
+            path.display()
+        );
+    }
+
+    if key.is_none() {
+        println!();
+        println!("{}", "Runtime".cyan().bold());
+        for (key, value, source) in &runtime {
+            println!("  {} = {}  {}", key, value, source.dimmed());
+        }
+    }
+
+    println!();
+    println!("{}", "Effective configuration".cyan().bold());
+    if values.is_empty() {
+        println!("  {}", "No keys match.".yellow());
+    }
+    for value in &values {
+        let source = match value.source {
+            ValueSource::File => "file".green().to_string(),
+            ValueSource::Env => match &value.raw {
+                Some(raw) => format!("{} {}", "env".magenta(), raw.dimmed()),
+                None => "env".magenta().to_string(),
+            },
+            ValueSource::Default => "default".dimmed().to_string(),
+        };
+        println!("  {} = {}  {}", value.key, value.value, source);
+    }
+    if values
+        .iter()
+        .any(|value| value.key.starts_with("permissions.rules["))
+    {
+        println!();
+        println!(
+            "{}",
+            "Permission rules are checked last to first; the last matching rule wins.".dimmed()
+        );
+    }
+
+    Ok(())
+}
+
+async fn run_config(
+    command: &ConfigCommands,
+    config: &CliConfig,
+) -> Result<(), Box<dyn std::error::Error>> {
+    match command {
+        ConfigCommands::Show => {
+            println!("{}", "Current configuration:".cyan().bold());
+            println!("{}", serde_json::to_string_pretty(config)?);
+        }
+        ConfigCommands::Get { key } => {
+            let value = match key.as_str() {
+                "default_provider" => config.default_provider.clone(),
+                "default_model" => config.default_model.clone(),
+                "colors" => Some(config.colors.to_string()),
+                "verbose" => Some(config.verbose.to_string()),
+                _ => None,
+            };
+            match value {
+                Some(v) => println!("{}", v),
+                _ => println!("{}: {}", "Unknown key".red(), key),
+            }
+        }
+        ConfigCommands::Set { key, value } => {
+            let mut new_config = config.clone();
+
+            match key.as_str() {
+                "default_provider" => new_config.default_provider = Some(value.clone()),
+                "default_model" => new_config.default_model = Some(value.clone()),
+                "colors" => new_config.colors = value.parse().unwrap_or(true),
+                "verbose" => new_config.verbose = value.parse().unwrap_or(false),
+                _ => {
+                    println!("{}: {}", "Unknown key".red(), key);
+                    return Ok(());
+                }
+            }
+
+            new_config.save()?;
+
+            println!(
+                "{} {} = {}",
+                "Saved:".green().bold(),
+                key.yellow(),
+                value.green()
+            );
+        }
+        ConfigCommands::Reset => {
+            println!("{}", "Resetting configuration to defaults...".cyan().bold());
+            let default_config = CliConfig::default();
+            default_config.save()?;
+            println!("{}", "Configuration reset to defaults.".green());
+        }
+    }
+    Ok(())
+}
+
+async fn run_hooks(command: &HooksCommands) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_orchestration::hooks::replay;
+
+    match command {
+        HooksCommands::Test {
+            paths,
+            update,
+            json,
+        } => {
+            let results = replay::replay(paths, *update).await?;
+            let failed = results.iter().filter(|r| !r.passed()).count();
+
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&results)?);
+            } else if results.is_empty() {
+                println!("{}", "No hook fixtures found.".yellow());
+            } else {
+                for result in &results {
+                    let status = if !result.passed() {
+                        "✗".red()
+                    } else if result.updated {
+                        "↻".yellow()
+                    } else {
+                        "✓".green()
+                    };
+                    println!(
+                        "{} {} {}",
+                        status,
+                        result.name.bold(),
+                        result.path.display().to_string().dimmed()
+                    );
+                    for mismatch in &result.mismatches {
+                        println!("    {}", mismatch.red());
+                    }
+                }
+                let updated = results.iter().filter(|r| r.updated).count();
+                println!("{}", "─".repeat(50).dimmed());
+                println!(
+                    "{} passed, {} failed, {} updated",
+                    results.len() - failed,
+                    failed,
+                    updated
+                );
+            }
+
+            if failed > 0 {
+                std::process::exit(1);
+            }
+        }
+        HooksCommands::Transform { file, hooks, json } => {
+            use uira_orchestration::hooks::{
+                default_hooks, transform::messages_in, HookContext, HookInput, HookRegistry,
+                MessagesTransformPipeline,
+            };
+
+            let content = std::fs::read_to_string(file)?;
+            let value: serde_json::Value = serde_json::from_str(&content)?;
+            let messages = messages_in(&value)
+                .ok_or("expected a message array or an object with a `messages` array")?;
+
+            let defaults = default_hooks();
+            let registry = if hooks.is_empty() {
+                defaults
+            } else {
+                let mut registry = HookRegistry::new();
+                for name in hooks {
+                    let hook = defaults
+                        .get(name)
+                        .ok_or_else(|| format!("unknown hook '{}'", name))?;
+                    registry.register(hook);
+                }
+                registry
+            };
+
+            let directory = std::env::current_dir()?.to_string_lossy().to_string();
+            let input = HookInput {
+                directory: Some(directory.clone()),
+                ..HookInput::default()
+            };
+            let context = HookContext::new(None, directory, None);
+            let outcome = MessagesTransformPipeline::new(&registry)
+                .dry_run(true)
+                .run(messages, &input, &context)
+                .await;
+
+            if *json {
+                println!("{}", serde_json::to_string_pretty(&outcome)?);
+            } else if outcome.steps.is_empty() {
+                println!("{}", "No messages-transform hooks registered.".yellow());
+            } else {
+                for step in &outcome.steps {
+                    let status = if step.error.is_some() {
+                        "✗".red()
+                    } else if step.delta.is_empty() {
+                        "·".dimmed()
+                    } else {
+                        "Δ".yellow()
+                    };
+                    println!(
+                        "{} {} {} {}",
+                        status,
+                        step.hook.bold(),
+                        format!("(priority {})", step.priority).dimmed(),
+                        step.delta
+                    );
+                    if let Some(error) = &step.error {
+                        println!("    {}", error.red());
+                    }
+                }
+                if let Some(reason) = &outcome.blocked {
+                    println!("{} {}", "Blocked:".red().bold(), reason);
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+async fn run_goals(command: &GoalsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_core::loader::load_config;
+    use uira_orchestration::hooks::GoalRunner;
+
+    match command {
+        GoalsCommands::Check => {
+            println!("{}", "Running goal verification...".cyan().bold());
+            println!("{}", "─".repeat(50).dimmed());
+
+            let config = load_config(None)?;
             let goals_config = &config.goals;
 
             if goals_config.goals.is_empty() {
@@ -841,14 +1889,52 @@ async fn run_goals(command: &GoalsCommands) -> Result<(), Box<dyn std::error::Er
     Ok(())
 }
 
-async fn run_tasks(command: &TasksCommands) -> Result<(), Box<dyn std::error::Error>> {
-    use uira_orchestration::background_agent::{BackgroundManager, BackgroundTaskConfig};
+async fn run_tasks(
+    cli: &Cli,
+    config: &CliConfig,
+    command: &TasksCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_orchestration::background_agent::{
+        BackgroundManager, BackgroundTaskConfig, BackgroundTaskStatus,
+    };
 
-    let config = BackgroundTaskConfig::default();
-    let manager = BackgroundManager::new(config);
+    let uira_config = uira_core::loader::load_config(None).ok();
+    let task_config = uira_config
+        .as_ref()
+        .map(|cfg| BackgroundTaskConfig::from_settings(&cfg.background_tasks))
+        .unwrap_or_default();
+    let manager = BackgroundManager::new(task_config);
 
     match command {
-        TasksCommands::List => {
+        TasksCommands::List { failed: true } => {
+            println!("{}", "Failed tasks:".cyan().bold());
+            println!("{}", "─".repeat(80).dimmed());
+
+            let tasks = manager.get_failed_tasks();
+            if tasks.is_empty() {
+                println!("{}", "No failed tasks.".dimmed());
+                return Ok(());
+            }
+
+            for task in tasks {
+                println!("{} {}", task.id.yellow(), task.description);
+                println!("  Agent: {}", task.agent.cyan());
+                println!("  Attempts: {}", task.attempts.max(1));
+                if let Some(kind) = task.failure_kind {
+                    println!("  Failure: {}", kind.as_str().red());
+                }
+                if let Some(ref error) = task.error {
+                    println!("  Error: {}", error.lines().next().unwrap_or_default());
+                }
+                if let Some(completed) = task.completed_at {
+                    println!("  Failed at: {}", completed);
+                }
+                println!();
+            }
+
+            println!("Run 'uira tasks requeue <task-id>' to run a task again.");
+        }
+        TasksCommands::List { failed: false } => {
             println!("{}", "Background tasks:".cyan().bold());
             println!("{}", "─".repeat(80).dimmed());
 
@@ -975,6 +2061,324 @@ async fn run_tasks(command: &TasksCommands) -> Result<(), Box<dyn std::error::Er
             println!("{} Task cancelled", "✓".green().bold());
             println!("Status: {}", "cancelled".dimmed());
         }
+        TasksCommands::Logs { task_id, follow } => {
+            use std::io::Write;
+
+            let task = manager
+                .get_task(task_id)
+                .ok_or_else(|| format!("Task not found: {}", task_id))?;
+
+            let (content, mut offset) = manager.read_log(&task.id, 0).map_err(|e| {
+                format!(
+                    "No log for task {} ({}): {}",
+                    task.id,
+                    manager.log_path(&task.id).display(),
+                    e
+                )
+            })?;
+            print!("{}", content);
+            std::io::stdout().flush()?;
+
+            if *follow {
+                loop {
+                    let done = manager
+                        .get_task(&task.id)
+                        .is_none_or(|task| task.status.is_terminal());
+                    let (content, next) = manager.read_log(&task.id, offset)?;
+                    print!("{}", content);
+                    std::io::stdout().flush()?;
+                    offset = next;
+                    if done {
+                        break;
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+                }
+            }
+        }
+        TasksCommands::Requeue { task_id } => {
+            let agent_model_overrides = build_agent_model_overrides(uira_config.as_ref());
+            let agent_defs = project_agent_definitions(uira_config.as_ref());
+            let registry = ModelRegistry::new();
+            let (_client, provider_config) = create_client(
+                cli,
+                config,
+                &agent_defs,
+                &registry,
+                &agent_model_overrides,
+                uira_config.as_ref(),
+            )?;
+            let (external_mcp_servers, external_mcp_specs) =
+                prepare_external_mcp(uira_config.as_ref(), cli.offline).await?;
+            let agent_config = create_agent_config(
+                cli,
+                config,
+                &agent_defs,
+                uira_config.as_ref(),
+                external_mcp_servers,
+                external_mcp_specs,
+            );
+            let model = provider_config.model.clone();
+            let executor =
+                RecursiveAgentExecutor::new(ExecutorConfig::new(provider_config, agent_config));
+
+            let task = manager.requeue(task_id)?;
+            println!(
+                "{} {} as {}",
+                "Requeued".cyan().bold(),
+                task_id.yellow(),
+                task.id.yellow()
+            );
+            println!(
+                "{}",
+                format!(
+                    "Follow progress with 'uira tasks logs {} --follow'",
+                    task.id
+                )
+                .dimmed()
+            );
+
+            let model = task.parent_model.clone().unwrap_or(model);
+            let task = uira_orchestration::run_background_task(
+                &manager,
+                &task.id,
+                &executor,
+                &task.prompt,
+                &model,
+                uira_orchestration::DelegationContext::for_agent(&task.agent, None),
+                None,
+                Default::default(),
+            )
+            .await
+            .ok_or_else(|| format!("Task not found: {}", task_id))?;
+
+            match task.status {
+                BackgroundTaskStatus::Completed => {
+                    println!("{} Task completed", "✓".green().bold());
+                    if let Some(ref result) = task.result {
+                        println!("{}", result);
+                    }
+                }
+                status => {
+                    println!("{}: {}", "Status".cyan(), status.as_str().red());
+                    if let Some(ref error) = task.error {
+                        println!("{}: {}", "Error".red().bold(), error.red());
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+async fn run_plan(
+    cli: &Cli,
+    config: &CliConfig,
+    command: &PlanCommands,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_orchestration::PlanningPipeline;
+
+    let cwd = std::env::current_dir()?;
+
+    match command {
+        PlanCommands::List => {
+            println!("{}", "Planning pipelines:".cyan().bold());
+            println!("{}", "─".repeat(80).dimmed());
+
+            let pipelines = PlanningPipeline::list(&cwd);
+            if pipelines.is_empty() {
+                println!("{}", "No planning pipelines found.".dimmed());
+                return Ok(());
+            }
+
+            for pipeline in pipelines {
+                let status = if pipeline.is_complete() {
+                    "complete".green()
+                } else {
+                    format!("{}/3", pipeline.stages.len()).yellow()
+                };
+                println!(
+                    "{} {} {}",
+                    pipeline.id.yellow(),
+                    status,
+                    pipeline.request.lines().next().unwrap_or_default()
+                );
+            }
+        }
+        PlanCommands::Show { plan_id } => {
+            let pipeline = match plan_id {
+                Some(id) => PlanningPipeline::load(&cwd, id),
+                None => PlanningPipeline::list(&cwd).into_iter().next(),
+            }
+            .ok_or("Planning pipeline not found")?;
+
+            println!("{} {}", "Plan:".cyan().bold(), pipeline.id.yellow().bold());
+            println!("{}", "─".repeat(80).dimmed());
+            println!("{}: {}", "Request".cyan(), pipeline.request);
+            println!("{}: {}", "Created".cyan(), pipeline.created_at);
+            println!("{}: {}", "Updated".cyan(), pipeline.updated_at);
+            println!();
+
+            for output in &pipeline.stages {
+                println!(
+                    "{} {} (v{}, {})",
+                    "✓".green(),
+                    output.stage.artifact_name().bold(),
+                    output.version,
+                    output.stage.agent_type().dimmed()
+                );
+                println!(
+                    "  {}",
+                    pipeline
+                        .artifact_path(&cwd, output)
+                        .display()
+                        .to_string()
+                        .dimmed()
+                );
+            }
+
+            if pipeline.is_complete() {
+                let verdict = match pipeline.is_approved() {
+                    Some(true) => "approved".green(),
+                    _ => "not approved".red(),
+                };
+                println!();
+                println!("{}: {}", "Review".cyan(), verdict);
+            } else {
+                println!(
+                    "{} {} ({})",
+                    "○".dimmed(),
+                    pipeline.current_stage().artifact_name().bold(),
+                    "pending".yellow()
+                );
+                println!();
+                println!("Run 'uira plan resume {}' to continue.", pipeline.id);
+            }
+        }
+        PlanCommands::Resume { plan_id } => {
+            let pipeline = match plan_id {
+                Some(id) => PlanningPipeline::load(&cwd, id),
+                None => PlanningPipeline::latest_incomplete(&cwd),
+            }
+            .ok_or("No resumable planning pipeline found")?;
+
+            if pipeline.is_complete() {
+                return Err(
+                    format!("Planning pipeline {} is already complete", pipeline.id).into(),
+                );
+            }
+
+            let stage = pipeline.current_stage();
+            println!(
+                "{} {} from stage {} ({})",
+                "Resuming".cyan().bold(),
+                pipeline.id.yellow(),
+                stage.artifact_name().bold(),
+                stage.agent_type()
+            );
+
+            let prompt = format!(
+                "Resume the interrupted planning pipeline `{}`.\n\n\
+                 Call the planning_pipeline tool with plan_id \"{}\" to get the next stage prompt, \
+                 delegate it to the named agent, and record each stage's output with stage_output \
+                 until the pipeline is complete.\n\n\
+                 <user-request>\n{}\n</user-request>",
+                pipeline.id, pipeline.id, pipeline.request
+            );
+            run_exec(cli, config, &prompt, false).await?;
+        }
+    }
+
+    Ok(())
+}
+
+fn run_agents(command: &AgentsCommands) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_orchestration::definitions::get_agent_definitions_with_loader;
+    use uira_orchestration::prompts::EMBEDDED_PROMPTS;
+    use uira_orchestration::PromptLoader;
+
+    let uira_config = uira_core::loader::load_config(None).ok();
+    let project_root = std::env::current_dir()?;
+    let loader = PromptLoader::from_embedded_map(EMBEDDED_PROMPTS)
+        .with_project_overrides(uira_config.as_ref(), &project_root);
+    let agent_defs = get_agent_definitions_with_loader(&loader, None, None);
+    let model_overrides = build_agent_model_overrides(uira_config.as_ref());
+
+    let model_label = |agent: &uira_orchestration::AgentConfig| {
+        model_overrides
+            .get(&agent.name)
+            .cloned()
+            .unwrap_or_else(|| {
+                agent
+                    .model
+                    .map(|model| model.as_str().to_string())
+                    .unwrap_or_else(|| "inherit".to_string())
+            })
+    };
+
+    match command {
+        AgentsCommands::List => {
+            println!("{}", "Built-in agents:".cyan().bold());
+            println!("{}", "─".repeat(80).dimmed());
+
+            let mut names: Vec<&String> = agent_defs.keys().collect();
+            names.sort();
+            for name in names {
+                let agent = &agent_defs[name];
+                let marker = if loader.override_sources(name).is_empty() {
+                    " ".normal()
+                } else {
+                    "*".yellow()
+                };
+                println!(
+                    "{} {:<24} {}",
+                    marker,
+                    name.bold(),
+                    model_label(agent).dimmed()
+                );
+            }
+
+            println!("{}", "─".repeat(80).dimmed());
+            println!("{}", "* prompt has project overrides".dimmed());
+        }
+        AgentsCommands::Show {
+            name,
+            effective_prompt,
+        } => {
+            let agent = agent_defs
+                .get(name)
+                .ok_or_else(|| format!("Agent not found: {}", name))?;
+
+            if *effective_prompt {
+                println!("{}", agent.prompt);
+                return Ok(());
+            }
+
+            println!("{} {}", "Agent:".cyan().bold(), agent.name.yellow().bold());
+            println!("{}", "─".repeat(80).dimmed());
+            println!("{} {}", "Description:".bold(), agent.description);
+            println!("{} {}", "Model:".bold(), model_label(agent));
+            println!("{} {}", "Tools:".bold(), agent.tools.join(", "));
+
+            let sources = loader.override_sources(name);
+            if sources.is_empty() {
+                println!("{} none", "Prompt overrides:".bold());
+            } else {
+                println!("{}", "Prompt overrides:".bold());
+                for source in sources {
+                    println!("  {}", source);
+                }
+            }
+            println!();
+            println!(
+                "{}",
+                format!(
+                    "Run `uira agents show {} --effective-prompt` to print the merged prompt.",
+                    name
+                )
+                .dimmed()
+            );
+        }
     }
 
     Ok(())
@@ -1054,92 +2458,136 @@ async fn run_skills(command: &SkillsCommands) -> Result<(), Box<dyn std::error::
             let content = std::fs::read_to_string(&skill.path)?;
             println!("{}", content);
         }
-        SkillsCommands::Install { path } => {
-            use std::path::Path;
-            use uira_gateway::skills::SkillLoader;
+        SkillsCommands::Stats => {
+            use uira_orchestration::hooks::load_skill_usage_state;
 
-            let source_path = Path::new(path);
+            let cwd = std::env::current_dir()?;
+            let state = load_skill_usage_state(&cwd.to_string_lossy());
 
-            if !source_path.exists() {
-                return Err(format!("Path does not exist: {}", path).into());
-            }
-
-            if !source_path.is_dir() {
-                return Err(format!("Path is not a directory: {}", path).into());
-            }
-
-            let skill_md = source_path.join("SKILL.md");
-            if !skill_md.exists() {
-                return Err(format!("SKILL.md not found in: {}", path).into());
-            }
-
-            let loader = SkillLoader::new(&[path])?;
-            if loader.discovered.is_empty() {
-                return Err(format!("No valid skill found in: {}", path).into());
-            }
-
-            let skill_info = &loader.discovered[0];
-            let metadata = &skill_info.metadata;
-
-            let home_dir = dirs::home_dir().ok_or("Could not determine home directory")?;
-            let skills_dir = home_dir.join(".uira/skills");
-            std::fs::create_dir_all(&skills_dir)?;
-
-            let dest_path = skills_dir.join(&metadata.name);
+            println!("{}", "Skill usage:".cyan().bold());
+            println!("{}", "─".repeat(80).dimmed());
 
-            if dest_path.exists() {
-                return Err(format!(
-                    "Skill already exists: {}. Remove it first to reinstall.",
-                    dest_path.display()
-                )
-                .into());
+            if state.skills.is_empty() {
+                println!("{}", "No skill activations recorded yet.".yellow());
+                return Ok(());
             }
 
-            fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
-                std::fs::create_dir_all(dst)?;
-                for entry in std::fs::read_dir(src)? {
-                    let entry = entry?;
-                    let ty = entry.file_type()?;
-                    let src_path = entry.path();
-                    let dst_path = dst.join(entry.file_name());
-
-                    if ty.is_dir() {
-                        copy_dir_recursive(&src_path, &dst_path)?;
-                    } else {
-                        std::fs::copy(&src_path, &dst_path)?;
-                    }
-                }
-                Ok(())
+            let mut records: Vec<_> = state.skills.iter().collect();
+            records.sort_by(|a, b| b.1.activations.cmp(&a.1.activations).then(a.0.cmp(b.0)));
+
+            for (name, record) in records {
+                let rate = record
+                    .completion_rate()
+                    .map(|r| format!("{:.0}%", r * 100.0))
+                    .unwrap_or_else(|| "-".to_string());
+                let avg_tokens = record
+                    .average_tokens()
+                    .map(|t| t.to_string())
+                    .unwrap_or_else(|| "-".to_string());
+                println!(
+                    "{:<24} {:>4} runs  {:>5} completed  {:>8} avg tokens",
+                    name.bold(),
+                    record.activations,
+                    rate,
+                    avg_tokens
+                );
             }
+        }
+        SkillsCommands::Install {
+            source,
+            git_ref,
+            path,
+            checksum,
+            force,
+        } => {
+            use uira_gateway::skills::{InstallOptions, SkillInstaller, SkillSource};
+
+            let source = SkillSource::parse(source)?;
+            let installer = SkillInstaller::user()?;
+            let options = InstallOptions {
+                git_ref: git_ref.clone(),
+                subdir: path.clone(),
+                checksum: checksum.clone(),
+                force: *force,
+            };
 
-            copy_dir_recursive(source_path, &dest_path)?;
+            println!("{} {}", "Installing from".cyan(), source);
+            let installed = installer.install(&source, &options)?;
+            let dest_path = installer.skills_dir().join(&installed.name);
 
             println!(
                 "{} Installed skill: {}",
                 "✓".green().bold(),
-                metadata.name.yellow()
+                installed.name.yellow()
             );
             println!("Location: {}", dest_path.display().to_string().dimmed());
+            if let Some(commit) = &installed.commit {
+                let pin = installed.pinned_ref.as_deref().unwrap_or("default branch");
+                println!("Commit: {} ({})", commit.dimmed(), pin);
+            }
+            println!("Checksum: {}", installed.checksum.dimmed());
             println!();
             println!(
                 "{}",
                 format!(
                     "To activate, add '{}' to the 'skills.active' list in your config.",
-                    metadata.name
+                    installed.name
                 )
                 .dimmed()
             );
         }
+        SkillsCommands::Update { name, git_ref } => {
+            use uira_gateway::skills::{SkillInstaller, UpdateOutcome};
+
+            let installer = SkillInstaller::user()?;
+            let names: Vec<String> = match name {
+                Some(name) => vec![name.clone()],
+                None => installer
+                    .list_installed()
+                    .into_iter()
+                    .map(|s| s.name)
+                    .collect(),
+            };
+
+            if names.is_empty() {
+                println!("{}", "No installed skills to update.".yellow());
+                return Ok(());
+            }
+
+            for name in &names {
+                match installer.update(name, git_ref.as_deref()) {
+                    Ok(UpdateOutcome::Updated { from, to }) => println!(
+                        "{} {} {} → {}",
+                        "✓".green().bold(),
+                        name.yellow(),
+                        short_id(&from).dimmed(),
+                        short_id(&to)
+                    ),
+                    Ok(UpdateOutcome::UpToDate) => {
+                        println!("  {} {}", name, "up to date".dimmed())
+                    }
+                    Ok(UpdateOutcome::Skipped(reason)) => {
+                        println!("  {} {}", name, format!("skipped ({})", reason).dimmed())
+                    }
+                    Err(e) => println!("{} {}: {}", "✗".red().bold(), name.yellow(), e),
+                }
+            }
+        }
     }
 
     Ok(())
 }
 
+fn short_id(id: &str) -> &str {
+    id.get(..12).unwrap_or(id)
+}
+
 async fn run_gateway(command: &GatewayCommands) -> Result<(), Box<dyn std::error::Error>> {
     use std::collections::HashMap;
     use uira_gateway::channel_bridge::ChannelSkillConfig;
     use uira_gateway::{
-        Channel, ChannelBridge, GatewayServer, SkillLoader, SlackChannel, TelegramChannel,
+        telegram, Channel, ChannelBridge, Checkpoint, Dashboard, GatewayServer, Scheduler,
+        SkillLoader, SlackChannel, TelegramChannel,
     };
 
     match command {
@@ -1224,29 +2672,41 @@ async fn run_gateway(command: &GatewayCommands) -> Result<(), Box<dyn std::error
                     skills.dedup();
                 }
 
-                let skill_config = if !channel_active_skills.is_empty() {
-                    let skill_paths = config
-                        .as_ref()
-                        .map(|c| c.skills.paths.clone())
-                        .unwrap_or_default();
-
-                    SkillLoader::new(&skill_paths)
-                        .and_then(|loader| {
-                            ChannelSkillConfig::from_active_skills(
-                                Some(&loader),
-                                channel_active_skills,
-                            )
-                        })
-                        .unwrap_or_else(|e| {
-                            tracing::warn!("Failed to load channel skills: {e}");
-                            ChannelSkillConfig::new()
-                        })
-                } else {
-                    ChannelSkillConfig::new()
-                };
+                let conversation_overrides: Vec<_> = telegram_configs
+                    .iter()
+                    .flat_map(telegram::topic_overrides)
+                    .collect();
+
+                let skill_config =
+                    if !channel_active_skills.is_empty() || !conversation_overrides.is_empty() {
+                        let skill_paths = config
+                            .as_ref()
+                            .map(|c| c.skills.paths.clone())
+                            .unwrap_or_default();
+
+                        SkillLoader::new(&skill_paths)
+                            .and_then(|loader| {
+                                let mut skill_config = ChannelSkillConfig::from_active_skills(
+                                    Some(&loader),
+                                    channel_active_skills,
+                                )?;
+                                skill_config.add_conversation_overrides(
+                                    Some(&loader),
+                                    conversation_overrides,
+                                )?;
+                                Ok(skill_config)
+                            })
+                            .unwrap_or_else(|e| {
+                                tracing::warn!("Failed to load channel skills: {e}");
+                                ChannelSkillConfig::new()
+                            })
+                    } else {
+                        ChannelSkillConfig::new()
+                    };
 
                 Some(
-                    ChannelBridge::with_skill_config(session_manager, skill_config)
+                    ChannelBridge::with_skill_config(session_manager.clone(), skill_config)
+                        .with_limits(channel_settings.limits.clone())
                         .with_outbound_channels(outbound_channels.clone()),
                 )
             } else {
@@ -1279,7 +2739,8 @@ async fn run_gateway(command: &GatewayCommands) -> Result<(), Box<dyn std::error
 
                 for sl_config in slack_configs {
                     let account_id = sl_config.account_id.clone();
-                    let channel = SlackChannel::new(sl_config);
+                    let channel = SlackChannel::new(sl_config)
+                        .with_dashboard(Dashboard::new(session_manager.clone()));
                     match bridge
                         .register_channel(Box::new(channel), account_id.clone())
                         .await
@@ -1299,6 +2760,21 @@ async fn run_gateway(command: &GatewayCommands) -> Result<(), Box<dyn std::error
                 }
             }
 
+            // Pick up sessions a previous run checkpointed on shutdown
+            if let Some(mut checkpoint) = Checkpoint::take() {
+                if let Some(ref bridge) = bridge {
+                    bridge.apply_skill_permissions(&mut checkpoint);
+                }
+                let restored = session_manager.restore_sessions(checkpoint.sessions).await;
+                if let Some(ref bridge) = bridge {
+                    bridge.restore_routes(checkpoint.routes).await;
+                }
+                session_manager.resume_interrupted(&restored).await;
+                if !restored.is_empty() {
+                    println!("Restored {} session(s) from checkpoint", restored.len());
+                }
+            }
+
             if channel_count > 0 {
                 println!(
                     "{}",
@@ -1312,22 +2788,290 @@ async fn run_gateway(command: &GatewayCommands) -> Result<(), Box<dyn std::error
             } else {
                 println!(
                     "{}",
-                    format!("Gateway started on ws://{}:{}", bind_host, bind_port)
-                        .green()
-                        .bold()
+                    format!("Gateway started on ws://{}:{}", bind_host, bind_port)
+                        .green()
+                        .bold()
+                );
+            }
+
+            let scheduler_settings = config
+                .as_ref()
+                .map(|c| c.scheduler.clone())
+                .unwrap_or_default();
+            let mut scheduler = if scheduler_settings.enabled {
+                let mut scheduler = Scheduler::new(session_manager.clone(), &scheduler_settings)?
+                    .with_outbound_channels(outbound_channels.clone());
+                scheduler.start();
+                println!("{} scheduled job(s) active", scheduler.jobs().len());
+                Some(scheduler)
+            } else {
+                None
+            };
+
+            server.serve(&bind_host, bind_port).await?;
+
+            if let Some(ref mut scheduler) = scheduler {
+                scheduler.stop().await;
+                tracing::info!("Scheduler stopped");
+            }
+
+            let sessions = session_manager.checkpoint_sessions().await;
+            let routes = match bridge {
+                Some(ref bridge) => {
+                    let interrupted: Vec<String> = sessions
+                        .iter()
+                        .filter(|session| session.interrupted)
+                        .map(|session| session.id.clone())
+                        .collect();
+                    bridge
+                        .notify_sessions(
+                            &interrupted,
+                            "I'm restarting and will pick this back up shortly.",
+                        )
+                        .await;
+                    bridge.checkpoint_routes().await
+                }
+                None => Vec::new(),
+            };
+            server.shutdown(Checkpoint::new(sessions, routes)).await?;
+
+            if let Some(mut bridge) = bridge {
+                bridge.stop().await;
+                tracing::info!("Channel bridge stopped");
+            }
+
+            Ok(())
+        }
+    }
+}
+
+async fn run_scheduler(command: &SchedulerCommands) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_gateway::{Scheduler, SessionManager};
+
+    let config = uira_core::loader::load_config(None).unwrap_or_default();
+    let session_manager = Arc::new(SessionManager::new_with_settings(
+        config.gateway.max_sessions,
+        config.gateway.clone(),
+    ));
+    let scheduler = Scheduler::new(session_manager, &config.scheduler)?;
+
+    match command {
+        SchedulerCommands::List => {
+            println!("{}", "Scheduled jobs:".cyan().bold());
+            println!("{}", "─".repeat(80).dimmed());
+
+            if !config.scheduler.enabled {
+                println!(
+                    "{}",
+                    "scheduler.enabled is false — `gateway start` will not run these jobs."
+                        .yellow()
                 );
             }
 
-            server.start(&bind_host, bind_port).await?;
+            if scheduler.jobs().is_empty() {
+                println!("{}", "No scheduled jobs.".dimmed());
+                return Ok(());
+            }
 
-            if let Some(mut bridge) = bridge {
-                bridge.stop().await;
-                tracing::info!("Channel bridge stopped");
+            let now = chrono::Local::now();
+            for job in scheduler.jobs() {
+                let job_config = job.config();
+                println!("{} {}", job.name().yellow(), job_config.schedule.cyan());
+                if let Some(next) = job.next_run_after(&now) {
+                    println!("  Next run: {}", next.format("%Y-%m-%d %H:%M %Z"));
+                }
+                if let (Some(channel), Some(recipient)) =
+                    (&job_config.channel, &job_config.recipient)
+                {
+                    println!("  Posts to: {} {}", channel, recipient.dimmed());
+                }
+                println!("  Prompt: {}", job_config.prompt.dimmed());
+                println!();
             }
+        }
+        SchedulerCommands::Run { name } => {
+            println!("{} {}", "Running job:".cyan().bold(), name.yellow());
+            let output = scheduler.run_now(name).await?;
+            println!("{}", output);
+        }
+    }
 
-            Ok(())
+    Ok(())
+}
+
+async fn run_bench(
+    cli: &Cli,
+    config: &CliConfig,
+    dir: &Path,
+    targets: &[String],
+    agents: &[String],
+    keep_workspaces: bool,
+    json_output: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_agent::bench::AgentRun;
+    use uira_agent::{load_tasks, BenchHarness, BenchTarget};
+
+    let tasks = load_tasks(dir)?;
+    if tasks.is_empty() {
+        return Err(format!("No task fixtures found in {}", dir.display()).into());
+    }
+
+    let targets = if targets.is_empty() {
+        vec![BenchTarget {
+            provider: cli
+                .provider
+                .clone()
+                .or_else(|| config.default_provider.clone())
+                .unwrap_or_else(|| "anthropic".to_string()),
+            model: cli.model.clone(),
+            agent: None,
+        }]
+    } else {
+        targets
+            .iter()
+            .map(|spec| BenchTarget::parse(spec))
+            .collect::<Result<Vec<_>, _>>()?
+    };
+    let agents = if agents.is_empty() {
+        cli.agent.iter().cloned().collect()
+    } else {
+        agents.to_vec()
+    };
+    let matrix = BenchTarget::matrix(&targets, &agents);
+
+    if !json_output {
+        println!(
+            "{} {} tasks × {} targets",
+            "Benchmarking:".cyan().bold(),
+            tasks.len(),
+            matrix.len()
+        );
+    }
+
+    let uira_config = uira_core::loader::load_config(None).ok();
+    let agent_model_overrides = build_agent_model_overrides(uira_config.as_ref());
+    let agent_defs = project_agent_definitions(uira_config.as_ref());
+    let registry = ModelRegistry::new();
+
+    let report = BenchHarness::new(tasks, matrix)
+        .with_keep_workspaces(keep_workspaces)
+        .run(|target, task, workdir| {
+            // Each cell runs hermetically: full-auto, no external MCP servers
+            let target_cli = Cli {
+                mode: cli.mode,
+                prompt: Vec::new(),
+                model: target.model.clone(),
+                provider: Some(target.provider.clone()),
+                sandbox: cli.sandbox.clone(),
+                sandbox_rules: cli.sandbox_rules.clone(),
+                full_auto: true,
+                worktree: false,
+                verbose: false,
+                ralph: cli.ralph,
+                agent: target.agent.clone(),
+                output: cli.output.clone(),
+                offline: cli.offline,
+                command: None,
+            };
+            let prepared = create_client(
+                &target_cli,
+                config,
+                &agent_defs,
+                &registry,
+                &agent_model_overrides,
+                uira_config.as_ref(),
+            )
+            .map(|(client, provider_config)| {
+                let mut agent_config = create_agent_config(
+                    &target_cli,
+                    config,
+                    &agent_defs,
+                    uira_config.as_ref(),
+                    Vec::new(),
+                    Vec::new(),
+                )
+                .with_working_directory(workdir);
+                if let Some(max_turns) = task.max_turns {
+                    agent_config = agent_config.with_max_turns(max_turns);
+                }
+                (client, provider_config, agent_config)
+            })
+            .map_err(|e| e.to_string());
+
+            async move {
+                let (client, provider_config, agent_config) = prepared?;
+                let model = provider_config.model.clone();
+                let executor_config = ExecutorConfig::new(provider_config, agent_config.clone());
+                let executor = Arc::new(RecursiveAgentExecutor::new(executor_config));
+                let mut agent = Agent::new_with_executor(agent_config, client, Some(executor))
+                    .with_session_recording()
+                    .map_err(|e| e.to_string())?;
+                let result = agent.run(&task.prompt).await.map_err(|e| e.to_string())?;
+                Ok(AgentRun { result, model })
+            }
+        })
+        .await?;
+
+    if json_output {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&serde_json::json!({
+                "summaries": report.summaries(),
+                "runs": report.runs,
+            }))?
+        );
+        return Ok(());
+    }
+
+    println!();
+    for run in &report.runs {
+        let status = if run.passed {
+            "✓".green()
+        } else {
+            "✗".red()
+        };
+        println!(
+            "{} {} {} ({} turns, {}ms, {})",
+            status,
+            run.task.bold(),
+            run.target.dimmed(),
+            run.turns,
+            run.duration_ms,
+            CostEstimator::format_cost(run.cost_usd)
+        );
+        for goal in run.goals.iter().filter(|g| !g.passed) {
+            println!(
+                "    {} {} {:.1}/{:.1}",
+                "goal".dimmed(),
+                goal.name,
+                goal.score,
+                goal.target
+            );
+        }
+        if let Some(ref error) = run.error {
+            println!("    {} {}", "Error:".red(), error.dimmed());
         }
     }
+
+    println!();
+    println!(
+        "{:<40} {:>7} {:>6} {:>10} {:>9} {:>9}",
+        "TARGET", "PASSED", "RATE", "COST", "AVG MS", "MAX MS"
+    );
+    println!("{}", "─".repeat(86).dimmed());
+    for summary in report.summaries() {
+        println!(
+            "{:<40} {:>7} {:>5.0}% {:>10} {:>9} {:>9}",
+            summary.target,
+            format!("{}/{}", summary.passed, summary.runs),
+            summary.pass_rate * 100.0,
+            CostEstimator::format_cost(summary.total_cost_usd),
+            summary.avg_duration_ms,
+            summary.max_duration_ms
+        );
+    }
+
+    Ok(())
 }
 
 struct TerminalGuard;
@@ -1350,6 +3094,225 @@ impl Drop for TerminalGuard {
     }
 }
 
+async fn run_pr(
+    cli: &Cli,
+    config: &CliConfig,
+    base: Option<&str>,
+    draft: bool,
+    dry_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_github::pr::{description_prompt, parse_description};
+    use uira_github::{connect, origin_url, BranchWork, NewPullRequest, RepoRef};
+
+    let cwd = std::env::current_dir()?;
+    let work = BranchWork::collect(&cwd, base)?;
+    if work.commits.is_empty() {
+        return Err(format!("'{}' has no commits on top of '{}'", work.branch, work.base).into());
+    }
+
+    let uira_config = uira_core::loader::load_config(None).ok();
+    let code_host = uira_config
+        .as_ref()
+        .map(|cfg| cfg.code_host.clone())
+        .unwrap_or_default();
+
+    // Resolve the repository, host and token before spending a model call
+    let target = if dry_run {
+        None
+    } else {
+        let remote_url = origin_url(&cwd)?;
+        let repo = RepoRef::from_remote_url(&remote_url)
+            .ok_or_else(|| format!("'{}' is not a repository remote", remote_url))?;
+        let host = connect(&code_host, Some(&remote_url))?;
+        if let Some(existing) = host.find_pull_request(&repo, &work.branch).await? {
+            println!(
+                "{} {} #{} for {}: {}",
+                "Already open:".yellow().bold(),
+                host.pull_request_noun(),
+                existing.number,
+                work.branch,
+                existing.html_url
+            );
+            return Ok(());
+        }
+        Some((repo, host))
+    };
+
+    let agent_model_overrides = build_agent_model_overrides(uira_config.as_ref());
+    let agent_defs = project_agent_definitions(uira_config.as_ref());
+    let registry = ModelRegistry::new();
+    let (client, _provider_config) = create_client(
+        cli,
+        config,
+        &agent_defs,
+        &registry,
+        &agent_model_overrides,
+        uira_config.as_ref(),
+    )?;
+
+    println!(
+        "{} {} commits from {} into {}",
+        "Describing:".cyan().bold(),
+        work.commits.len(),
+        work.branch,
+        work.base
+    );
+    let response = client
+        .chat(&[uira_core::Message::user(description_prompt(&work))], &[])
+        .await?;
+    let description = parse_description(&response.text(), &work);
+    println!("\n{}\n\n{}\n", description.title.bold(), description.body);
+
+    let Some((repo, host)) = target else {
+        return Ok(());
+    };
+    work.push(&cwd)?;
+    let created = host
+        .create_pull_request(
+            &repo,
+            &NewPullRequest {
+                title: description.title,
+                head: work.branch.clone(),
+                base: work.base.clone(),
+                body: description.body,
+                draft,
+            },
+        )
+        .await?;
+    println!(
+        "{} #{}: {}",
+        format!("Created {}", host.pull_request_noun())
+            .green()
+            .bold(),
+        created.number,
+        created.html_url
+    );
+    Ok(())
+}
+
+async fn run_issue(
+    cli: &Cli,
+    config: &CliConfig,
+    issue: &str,
+    no_run: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    use uira_github::{connect, origin_url, IssueRef, IssueTask, RepoRef};
+    use uira_orchestration::hooks::{AutopilotConfig, AutopilotHook, AutopilotStep};
+    use uira_orchestration::IssueAutopilot;
+
+    let cwd = std::env::current_dir()?;
+    let issue_ref = IssueRef::parse(issue)
+        .ok_or_else(|| format!("'{}' is not an issue URL, owner/name#N or number", issue))?;
+    let remote_url = origin_url(&cwd).ok();
+    let workspace_repo = remote_url.as_deref().and_then(RepoRef::from_remote_url);
+    let repo = issue_ref.resolve_repo(workspace_repo.as_ref())?;
+
+    let uira_config = uira_core::loader::load_config(None).ok();
+    let code_host = uira_config
+        .as_ref()
+        .map(|cfg| cfg.code_host.clone())
+        .unwrap_or_default();
+    let autopilot = uira_config
+        .as_ref()
+        .map(|cfg| cfg.autopilot.clone())
+        .unwrap_or_default();
+
+    let host = connect(
+        &code_host,
+        issue_ref.url.as_deref().or(remote_url.as_deref()),
+    )?;
+    let task = IssueTask::fetch(host.as_ref(), &repo, issue_ref.number).await?;
+    println!(
+        "{} {}: {}",
+        "Issue:".cyan().bold(),
+        task.reference().yellow(),
+        task.title
+    );
+    if task.goals.is_empty() {
+        println!("{}", "No acceptance criteria found".dimmed());
+    }
+    for goal in &task.goals {
+        println!("  {} {}", "○".dimmed(), goal);
+    }
+
+    let run = IssueAutopilot::start(
+        &cwd,
+        &task,
+        None,
+        AutopilotConfig {
+            max_iterations: autopilot.max_iterations,
+            require_approval: autopilot.require_approval,
+        },
+    )?;
+    println!("{} {}", "Plan:".cyan().bold(), run.pipeline.id.yellow());
+    if no_run {
+        println!("Autopilot is ready; start 'uira' to work on the issue.");
+        return Ok(());
+    }
+
+    let agent_model_overrides = build_agent_model_overrides(uira_config.as_ref());
+    let agent_defs = project_agent_definitions(uira_config.as_ref());
+    let registry = ModelRegistry::new();
+    let (client, provider_config) = create_client(
+        cli,
+        config,
+        &agent_defs,
+        &registry,
+        &agent_model_overrides,
+        uira_config.as_ref(),
+    )?;
+    let (external_mcp_servers, external_mcp_specs) =
+        prepare_external_mcp(uira_config.as_ref(), cli.offline).await?;
+    let agent_config = create_agent_config(
+        cli,
+        config,
+        &agent_defs,
+        uira_config.as_ref(),
+        external_mcp_servers,
+        external_mcp_specs,
+    );
+    let executor_config = ExecutorConfig::new(provider_config, agent_config.clone());
+    let executor = Arc::new(RecursiveAgentExecutor::new(executor_config));
+    let mut agent =
+        Agent::new_with_executor(agent_config, client, Some(executor)).with_session_recording()?;
+
+    // Drive autopilot the way interactive sessions do, one phase prompt at a time
+    let directory = cwd.to_string_lossy().to_string();
+    let mut prompt = run.prompt();
+    loop {
+        let result = agent.run(&prompt).await?;
+        print_result(&result);
+        if !result.success {
+            AutopilotHook::fail(&directory, "agent run failed");
+            break;
+        }
+        prompt = match AutopilotHook::process_response(&directory, None, &result.output) {
+            AutopilotStep::Idle => break,
+            AutopilotStep::Continue { state } => AutopilotHook::continuation_prompt(&state),
+            AutopilotStep::Advanced { state, .. } => {
+                println!(
+                    "{} {}",
+                    "Autopilot:".cyan().bold(),
+                    state.phase.as_str().yellow()
+                );
+                if !state.active {
+                    break;
+                }
+                AutopilotHook::get_phase_prompt(&state)
+            }
+            AutopilotStep::AwaitingApproval { state } => {
+                println!(
+                    "{} {} finished; approve the next phase with /autopilot approve in 'uira'.",
+                    "Autopilot:".cyan().bold(),
+                    state.phase.as_str()
+                );
+                break;
+            }
+        };
+    }
+    Ok(())
+}
+
 async fn run_interactive(
     cli: &Cli,
     config: &CliConfig,
@@ -1366,7 +3329,7 @@ async fn run_interactive(
 
     let uira_config = uira_core::loader::load_config(None).ok();
     let agent_model_overrides = build_agent_model_overrides(uira_config.as_ref());
-    let agent_defs = get_agent_definitions(None);
+    let agent_defs = project_agent_definitions(uira_config.as_ref());
     let registry = ModelRegistry::new();
     let (client, provider_config) = create_client(
         cli,
@@ -1377,7 +3340,7 @@ async fn run_interactive(
         uira_config.as_ref(),
     )?;
     let (external_mcp_servers, external_mcp_specs) =
-        prepare_external_mcp(uira_config.as_ref()).await?;
+        prepare_external_mcp(uira_config.as_ref(), cli.offline).await?;
     let agent_config = create_agent_config(
         cli,
         config,
@@ -1416,6 +3379,7 @@ async fn run_interactive(
             uira_tui::KeybindConfig::from_config_with_warnings(&cfg.keybinds);
         app.configure_keybinds(keybinds);
         app.set_show_logo(cfg.show_logo);
+        app.configure_notifications(cfg.notifications.clone());
         for warning in warnings {
             tracing::warn!("{}", warning);
         }
@@ -1457,6 +3421,14 @@ fn build_agent_model_overrides(
     overrides
 }
 
+/// Built-in agent definitions with the project's prompt overrides applied
+fn project_agent_definitions(
+    uira_config: Option<&uira_core::schema::UiraConfig>,
+) -> std::collections::HashMap<String, uira_orchestration::AgentConfig> {
+    let project_root = std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."));
+    get_project_agent_definitions(uira_config, &project_root)
+}
+
 fn build_theme_overrides(
     uira_config: Option<&uira_core::schema::UiraConfig>,
 ) -> uira_tui::ThemeOverrides {
@@ -1472,6 +3444,72 @@ fn build_theme_overrides(
     }
 }
 
+/// Provider and model for a run, each with the setting that chose it
+struct ModelSelection<'a> {
+    provider: &'a str,
+    provider_source: &'static str,
+    /// `None` leaves the choice to the provider's default model
+    model: Option<String>,
+    model_source: String,
+}
+
+fn select_model<'a>(
+    cli: &'a Cli,
+    config: &'a CliConfig,
+    agent_defs: &std::collections::HashMap<String, uira_orchestration::AgentConfig>,
+    registry: &ModelRegistry,
+    agent_model_overrides: &std::collections::HashMap<String, String>,
+) -> ModelSelection<'a> {
+    let (provider, provider_source) = match (&cli.provider, &config.default_provider) {
+        (Some(provider), _) => (provider.as_str(), "--provider"),
+        (None, Some(provider)) => (provider.as_str(), "config.toml default_provider"),
+        (None, None) => ("anthropic", "built-in default"),
+    };
+    // An explicit remote --provider is kept so create_client can refuse it
+    let switched_offline =
+        cli.offline && cli.provider.is_none() && !offline::is_local_provider(provider);
+    let (provider, provider_source) = if switched_offline {
+        (offline::OFFLINE_PROVIDER, "--offline")
+    } else {
+        (provider, provider_source)
+    };
+
+    let model_from_agent = cli.agent.as_ref().and_then(|name| {
+        agent_model_overrides
+            .get(name)
+            .map(|model| (model.clone(), format!("agents.{name}.model")))
+            .or_else(|| {
+                agent_defs.get(name).and_then(|def| {
+                    def.default_model.map(|tier| {
+                        (
+                            registry.resolve(tier, Some(provider)),
+                            format!("{name} agent default tier"),
+                        )
+                    })
+                })
+            })
+    });
+    let (model, model_source) = if let Some(model) = &cli.model {
+        (Some(model.clone()), "--model".to_string())
+    } else if switched_offline {
+        // Configured models belong to the remote provider
+        (None, format!("{provider} default (--offline)"))
+    } else if let Some((model, source)) = model_from_agent {
+        (Some(model), source)
+    } else if let Some(model) = &config.default_model {
+        (Some(model.clone()), "config.toml default_model".to_string())
+    } else {
+        (None, format!("{provider} default"))
+    };
+
+    ModelSelection {
+        provider,
+        provider_source,
+        model,
+        model_source,
+    }
+}
+
 fn create_client(
     cli: &Cli,
     config: &CliConfig,
@@ -1482,23 +3520,23 @@ fn create_client(
 ) -> Result<(Arc<dyn ModelClient>, ProviderConfig), Box<dyn std::error::Error>> {
     use uira_core::Provider;
 
-    let provider = cli
-        .provider
-        .as_deref()
-        .or(config.default_provider.as_deref())
-        .unwrap_or("anthropic");
+    let ModelSelection {
+        provider, model, ..
+    } = select_model(cli, config, agent_defs, registry, agent_model_overrides);
+    let model = match experiments::model_override(experiment_assignments(uira_config)) {
+        Some(variant_model) if cli.model.is_none() && !cli.offline => {
+            Some(variant_model.to_string())
+        }
+        _ => model,
+    };
 
-    let model_from_cli = cli.model.clone();
-    let model_from_agent = cli.agent.as_ref().and_then(|name| {
-        agent_model_overrides.get(name).cloned().or_else(|| {
-            agent_defs.get(name).and_then(|def| {
-                def.default_model
-                    .map(|tier| registry.resolve(tier, Some(provider)))
-            })
-        })
-    });
-    let model_from_config = config.default_model.clone();
-    let model = model_from_cli.or(model_from_agent).or(model_from_config);
+    if cli.offline && !offline::is_local_provider(provider) {
+        return Err(format!(
+            "{}; use --provider ollama or --provider llamacpp",
+            offline::unavailable(&format!("provider '{provider}'"))
+        )
+        .into());
+    }
 
     match provider {
         "anthropic" => {
@@ -1513,8 +3551,10 @@ fn create_client(
                 ..Default::default()
             };
 
-            let client = AnthropicClient::new(provider_config.clone())?;
-            Ok((Arc::new(client), provider_config))
+            let client = ModelClientBuilder::new()
+                .with_config(provider_config.clone())
+                .build()?;
+            Ok((client, provider_config))
         }
         "openai" => {
             let api_key = std::env::var(ENV_OPENAI_API_KEY)
@@ -1528,23 +3568,29 @@ fn create_client(
                 ..Default::default()
             };
 
-            let client = OpenAIClient::new(provider_config.clone())?;
-            Ok((Arc::new(client), provider_config))
+            let client = ModelClientBuilder::new()
+                .with_config(provider_config.clone())
+                .build()?;
+            Ok((client, provider_config))
         }
         "gemini" | "google" => {
+            // Without a key the client falls back to `uira-agent auth login google`
             let api_key = std::env::var(ENV_GEMINI_API_KEY)
                 .or_else(|_| std::env::var(ENV_GOOGLE_API_KEY))
-                .map_err(|_| "GEMINI_API_KEY or GOOGLE_API_KEY not set")?;
+                .ok()
+                .map(SecretString::from);
 
             let provider_config = ProviderConfig {
                 provider: Provider::Google,
-                api_key: Some(SecretString::from(api_key)),
+                api_key,
                 model: model.unwrap_or_else(|| "gemini-1.5-pro".to_string()),
                 ..Default::default()
             };
 
-            let client = GeminiClient::new(provider_config.clone())?;
-            Ok((Arc::new(client), provider_config))
+            let client = ModelClientBuilder::new()
+                .with_config(provider_config.clone())
+                .build()?;
+            Ok((client, provider_config))
         }
         "ollama" => {
             let provider_config = ProviderConfig {
@@ -1557,8 +3603,31 @@ fn create_client(
                 ..Default::default()
             };
 
-            let client = OllamaClient::new(provider_config.clone())?;
-            Ok((Arc::new(client), provider_config))
+            let client = ModelClientBuilder::new()
+                .with_config(provider_config.clone())
+                .build()?;
+            Ok((client, provider_config))
+        }
+        "llamacpp" => {
+            // llama-server speaks the OpenAI chat completions API and only
+            // checks the key when started with --api-key
+            let provider_config = ProviderConfig {
+                provider: Provider::Custom,
+                api_key: Some(SecretString::from(
+                    std::env::var("LLAMACPP_API_KEY").unwrap_or_else(|_| "no-key".to_string()),
+                )),
+                model: model.unwrap_or_else(|| "local".to_string()),
+                base_url: Some(
+                    std::env::var("LLAMACPP_HOST")
+                        .unwrap_or_else(|_| DEFAULT_LLAMACPP_URL.to_string()),
+                ),
+                ..Default::default()
+            };
+
+            let client = ModelClientBuilder::new()
+                .with_config(provider_config.clone())
+                .build()?;
+            Ok((client, provider_config))
         }
         "opencode" => {
             let api_key = std::env::var("OPENCODE_API_KEY")
@@ -1569,8 +3638,22 @@ fn create_client(
             maybe_autostart_opencode_server(opencode_settings);
             let provider_config = build_opencode_provider_config(api_key, model, opencode_settings);
 
-            let client = OpenCodeClient::new(provider_config.clone())?;
-            Ok((Arc::new(client), provider_config))
+            let client = ModelClientBuilder::new()
+                .with_config(provider_config.clone())
+                .build()?;
+            Ok((client, provider_config))
+        }
+        "mock" => {
+            let provider_config = ProviderConfig {
+                provider: Provider::Mock,
+                model: model.unwrap_or_else(|| "mock-model".to_string()),
+                ..Default::default()
+            };
+
+            let client = ModelClientBuilder::new()
+                .with_config(provider_config.clone())
+                .build()?;
+            Ok((client, provider_config))
         }
         _ => Err(format!("Unknown provider: {}", provider).into()),
     }
@@ -1686,12 +3769,17 @@ fn opencode_server_start_args(settings: &uira_core::schema::OpencodeSettings) ->
 #[cfg(test)]
 mod tests {
     use super::{
-        build_opencode_provider_config, opencode_base_url, opencode_server_start_args,
-        wait_for_listener,
+        build_opencode_provider_config, create_client, opencode_base_url,
+        opencode_server_start_args, select_model, wait_for_listener, workspace_roots, Cli,
+        CliConfig, ModelRegistry,
     };
+    use clap::Parser;
+    use std::collections::HashMap;
     use std::net::TcpListener;
+    use std::path::{Path, PathBuf};
     use std::time::Duration;
-    use uira_core::schema::OpencodeSettings;
+    use uira_core::schema::{OpencodeSettings, WorkspaceRootSettings};
+    use uira_security::SandboxPolicy;
 
     #[test]
     fn opencode_provider_config_applies_uira_settings() {
@@ -1743,6 +3831,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn workspace_roots_resolve_against_project_dir() {
+        let settings = vec![
+            WorkspaceRootSettings {
+                name: "api".to_string(),
+                path: "../api".to_string(),
+                sandbox: None,
+            },
+            WorkspaceRootSettings {
+                name: "docs".to_string(),
+                path: "/srv/docs".to_string(),
+                sandbox: Some("read-only".to_string()),
+            },
+        ];
+
+        let roots = workspace_roots(&settings, Path::new("/work/web"));
+        assert_eq!(roots[0].path, PathBuf::from("/work/api"));
+        assert!(roots[0].sandbox_policy.is_none());
+        assert_eq!(roots[1].path, PathBuf::from("/srv/docs"));
+        assert_eq!(roots[1].sandbox_policy, Some(SandboxPolicy::ReadOnly));
+    }
+
     #[test]
     fn wait_for_listener_detects_ready_port() {
         let listener = TcpListener::bind("127.0.0.1:0").unwrap();
@@ -1753,6 +3863,34 @@ mod tests {
         ));
         drop(listener);
     }
+
+    #[test]
+    fn offline_replaces_configured_remote_provider() {
+        let config = CliConfig {
+            default_provider: Some("openai".to_string()),
+            default_model: Some("gpt-4o".to_string()),
+            ..CliConfig::default()
+        };
+        let no_agents = HashMap::new();
+        let registry = ModelRegistry::new();
+        let no_overrides = HashMap::new();
+
+        let cli = Cli::try_parse_from(["uira-agent", "--offline"]).unwrap();
+        let selection = select_model(&cli, &config, &no_agents, &registry, &no_overrides);
+        assert_eq!(selection.provider, "ollama");
+        assert_eq!(selection.provider_source, "--offline");
+        assert_eq!(selection.model, None);
+
+        let cli = Cli::try_parse_from(["uira-agent", "--offline", "-p", "anthropic"]).unwrap();
+        let selection = select_model(&cli, &config, &no_agents, &registry, &no_overrides);
+        assert_eq!(selection.provider, "anthropic");
+        let error = create_client(&cli, &config, &no_agents, &registry, &no_overrides, None)
+            .err()
+            .unwrap();
+        assert!(error
+            .to_string()
+            .starts_with("provider 'anthropic' is unavailable offline"));
+    }
 }
 
 fn create_agent_config(
@@ -1780,7 +3918,7 @@ fn create_agent_config(
         _ => SandboxPolicy::full_access(),
     };
 
-    let mut config = AgentConfig::new().with_working_directory(cwd);
+    let mut config = AgentConfig::new().with_working_directory(cwd.clone());
 
     config.sandbox_policy = sandbox_policy;
 
@@ -1800,21 +3938,82 @@ fn create_agent_config(
         if let Some(agent_def) = agent_defs.get(agent_name) {
             config = config.with_system_prompt(&agent_def.prompt);
         }
+        config = config.with_agent(agent_name);
     }
 
+    config = config.with_offline(cli.offline);
+
     if let Some(uira_cfg) = uira_config {
         config = config.with_compaction_settings(&uira_cfg.compaction);
+        config = config.with_privacy(uira_cfg.privacy.clone());
 
         if !uira_cfg.permissions.rules.is_empty() {
             config = config.with_permission_rules(uira_cfg.permissions.rules.clone());
         }
+        config = config.with_auto_approve(uira_cfg.permissions.auto_approve.clone());
+        config = config.with_explain_commands(uira_cfg.permissions.explain_commands.clone());
+    }
+    config = config.with_workspace_index(
+        uira_config
+            .map(|cfg| cfg.workspace_index.clone())
+            .unwrap_or_default(),
+    );
+    config = config.with_snapshots(
+        uira_config
+            .map(|cfg| cfg.snapshots.clone())
+            .unwrap_or_default(),
+    );
+    if let Some(uira_cfg) = uira_config {
+        config = config.with_workspaces(workspace_roots(&uira_cfg.workspaces, &cwd));
+    }
+    if let Some(code_host) = uira_config.map(|cfg| cfg.code_host.clone()) {
+        config = config.with_code_host(code_host);
+    }
+    if let Some(ci) = uira_config.map(|cfg| cfg.ci.clone()) {
+        config = config.with_ci(ci);
     }
 
     if !external_mcp_servers.is_empty() && !external_mcp_specs.is_empty() {
         config = config.with_external_mcp(external_mcp_servers, external_mcp_specs);
     }
 
-    config
+    let repo_map = uira_config
+        .map(|cfg| cfg.repo_map.clone())
+        .unwrap_or_default();
+    let collector = uira_orchestration::features::context_injector::ContextCollector::new();
+    if uira_orchestration::register_repo_map_context(&collector, "startup", &cwd, &repo_map) {
+        config = config.with_additional_context(vec![collector.consume("startup").merged]);
+    }
+
+    experiments::apply(config, experiment_assignments(uira_config))
+}
+
+/// Experiment variants for sessions started by this process, assigned on
+/// first use
+fn experiment_assignments(
+    uira_config: Option<&uira_core::schema::UiraConfig>,
+) -> &'static [experiments::ExperimentAssignment] {
+    static ASSIGNMENTS: std::sync::OnceLock<Vec<experiments::ExperimentAssignment>> =
+        std::sync::OnceLock::new();
+    ASSIGNMENTS.get_or_init(|| {
+        let Some(uira_config) = uira_config else {
+            return Vec::new();
+        };
+        let seed = format!(
+            "{}-{}",
+            std::process::id(),
+            chrono::Utc::now().timestamp_nanos_opt().unwrap_or_default()
+        );
+        let assignments = experiments::assign(&uira_config.experiments, &seed);
+        for assignment in &assignments {
+            tracing::debug!(
+                experiment = %assignment.experiment,
+                variant = %assignment.variant.name,
+                "Assigned experiment variant"
+            );
+        }
+        assignments
+    })
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -1878,6 +4077,36 @@ fn load_custom_sandbox_policy(
     })
 }
 
+fn workspace_roots(
+    settings: &[uira_core::schema::WorkspaceRootSettings],
+    cwd: &Path,
+) -> Vec<uira_orchestration::WorkspaceRoot> {
+    settings
+        .iter()
+        .map(|entry| {
+            let path = resolve_paths(vec![PathBuf::from(&entry.path)], cwd).remove(0);
+            let root = uira_orchestration::WorkspaceRoot::new(&entry.name, path);
+            let policy = entry.sandbox.as_deref().map(|mode| match mode {
+                "read-only" => SandboxPolicy::read_only(),
+                "workspace-write" => SandboxPolicy::workspace_write(&root.path),
+                "full-access" => SandboxPolicy::full_access(),
+                other => {
+                    tracing::warn!(
+                        "Unknown sandbox '{}' for workspace @{}; using read-only",
+                        other,
+                        entry.name
+                    );
+                    SandboxPolicy::read_only()
+                }
+            });
+            match policy {
+                Some(policy) => root.with_sandbox_policy(policy),
+                None => root,
+            }
+        })
+        .collect()
+}
+
 fn resolve_paths(paths: Vec<PathBuf>, base: &Path) -> Vec<PathBuf> {
     paths
         .into_iter()
@@ -1893,6 +4122,7 @@ fn resolve_paths(paths: Vec<PathBuf>, base: &Path) -> Vec<PathBuf> {
 
 async fn prepare_external_mcp(
     uira_config: Option<&uira_core::schema::UiraConfig>,
+    offline: bool,
 ) -> Result<
     (
         Vec<uira_core::schema::NamedMcpServerConfig>,
@@ -1904,14 +4134,22 @@ async fn prepare_external_mcp(
         return Ok((Vec::new(), Vec::new()));
     };
 
-    if uira_cfg.mcp.servers.is_empty() {
+    let servers = if offline {
+        let (local, skipped) = offline::partition_mcp_servers(&uira_cfg.mcp.servers);
+        for name in skipped {
+            tracing::warn!("{}", offline::unavailable(&format!("MCP server '{name}'")));
+        }
+        local
+    } else {
+        uira_cfg.mcp.servers.clone()
+    };
+
+    if servers.is_empty() {
         return Ok((Vec::new(), Vec::new()));
     }
 
     let cwd = std::env::current_dir()?;
-    let parsed_servers = uira_cfg
-        .mcp
-        .servers
+    let parsed_servers = servers
         .iter()
         .map(|server| {
             uira_mcp_client::McpServerConfig::from_command(
@@ -1938,7 +4176,55 @@ async fn prepare_external_mcp(
         })
         .collect::<Vec<_>>();
 
-    Ok((uira_cfg.mcp.servers.clone(), specs))
+    Ok((servers, specs))
+}
+
+/// Run an `exec` prompt, stopping cleanly on Ctrl+C or SIGTERM: the turn is
+/// cancelled, session state is flushed and background tasks are failed so
+/// they can be requeued.
+async fn run_exec_prompt(
+    agent: &mut Agent,
+    prompt: &str,
+) -> Result<ExecutionResult, Box<dyn std::error::Error>> {
+    let cancel_signal = agent.control().cancel_signal();
+    let result = {
+        let run = agent.run(prompt);
+        tokio::pin!(run);
+        tokio::select! {
+            result = &mut run => result,
+            _ = uira_gateway::shutdown_signal() => {
+                cancel_signal.shutdown();
+                run.await
+            }
+        }
+    };
+
+    if !cancel_signal.session_token().is_cancelled() {
+        return Ok(result?);
+    }
+
+    agent.flush_state().await;
+    uira_orchestration::background_agent::interrupt_background_tasks(
+        "Task interrupted: uira exec was stopped",
+    );
+    eprintln!(
+        "{} resume with {}",
+        "Stopped;".yellow().bold(),
+        format!("uira-agent resume {}", agent.session().id).cyan()
+    );
+    Err("Interrupted by shutdown signal".into())
+}
+
+fn notify_exec_finished(
+    notifier: &uira_orchestration::Notifier,
+    result: &ExecutionResult,
+    started_at: std::time::Instant,
+) {
+    let summary = match &result.error {
+        Some(error) if !result.success => error.to_string(),
+        _ => result.output.clone(),
+    };
+    notifier.run_finished(&summary, started_at.elapsed(), result.success);
 }
 
 fn print_result(result: &ExecutionResult) {
@@ -0,0 +1,86 @@
+//! `--offline`: which features need the network and what stands in for them
+
+use uira_core::schema::NamedMcpServerConfig;
+
+use crate::commands::{AuthCommands, Commands};
+
+/// Providers served from this machine
+pub const LOCAL_PROVIDERS: &[&str] = &["ollama", "llamacpp", "mock"];
+
+/// Provider used offline when the configured one is remote
+pub const OFFLINE_PROVIDER: &str = "ollama";
+
+pub fn is_local_provider(provider: &str) -> bool {
+    LOCAL_PROVIDERS.contains(&provider)
+}
+
+/// Error for a feature that needs the network
+pub fn unavailable(feature: &str) -> String {
+    format!("{feature} is unavailable offline; run without --offline")
+}
+
+/// The feature a subcommand provides when it cannot work without the network
+pub fn network_feature(command: &Commands) -> Option<&'static str> {
+    match command {
+        Commands::SelfUpdate { .. } => Some("self-update"),
+        Commands::Pr { .. } => Some("pr"),
+        Commands::Issue { .. } => Some("issue"),
+        Commands::Models { .. } => Some("models"),
+        Commands::Auth {
+            command: AuthCommands::Login { .. },
+        } => Some("auth login"),
+        Commands::Auth {
+            command: AuthCommands::Test { .. },
+        } => Some("auth test"),
+        _ => None,
+    }
+}
+
+/// Split MCP servers into those that start offline and the names of remote
+/// ones, which are skipped rather than left to time out during discovery
+pub fn partition_mcp_servers(
+    servers: &[NamedMcpServerConfig],
+) -> (Vec<NamedMcpServerConfig>, Vec<String>) {
+    let (remote, local): (Vec<_>, Vec<_>) = servers
+        .iter()
+        .cloned()
+        .partition(|server| server.config.is_remote());
+    (
+        local,
+        remote.into_iter().map(|server| server.name).collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::Cli;
+    use clap::Parser;
+
+    #[test]
+    fn flags_network_only_commands() {
+        let cli = Cli::try_parse_from(["uira-agent", "--offline", "pr", "--dry-run"]).unwrap();
+        assert!(cli.offline);
+        assert_eq!(network_feature(cli.command.as_ref().unwrap()), Some("pr"));
+
+        let cli = Cli::try_parse_from(["uira-agent", "auth", "status", "--offline"]).unwrap();
+        assert!(cli.offline);
+        assert_eq!(network_feature(cli.command.as_ref().unwrap()), None);
+
+        assert!(is_local_provider("llamacpp"));
+        assert!(!is_local_provider("anthropic"));
+    }
+
+    #[test]
+    fn skips_remote_mcp_servers() {
+        let servers: Vec<NamedMcpServerConfig> = serde_json::from_value(serde_json::json!([
+            {"name": "fs", "command": "./bin/fs-mcp"},
+            {"name": "docs", "command": "npx -y @upstash/context7-mcp"},
+        ]))
+        .unwrap();
+        let (local, skipped) = partition_mcp_servers(&servers);
+        assert_eq!(local.len(), 1);
+        assert_eq!(local[0].name, "fs");
+        assert_eq!(skipped, vec!["docs".to_string()]);
+    }
+}
@@ -1,3 +1,11 @@
+//! JSON-RPC stdio server behind `--mode rpc`
+//!
+//! Speaks the versioned editor protocol from `uira_core::protocol::rpc`
+//! (see `v1`) alongside the original `chat` / `tool.*` methods, which keep
+//! their snake_case payloads for existing clients.
+
+mod v1;
+
 use crate::session::list_sessions;
 use futures::StreamExt;
 use serde::de::DeserializeOwned;
@@ -5,23 +13,20 @@ use serde::{Deserialize, Serialize};
 use serde_json::{json, Value};
 use std::collections::HashMap;
 use std::io;
-use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 use tokio::sync::{mpsc, oneshot, Mutex};
-use uira_agent::{Agent, AgentCommand, AgentConfig, ApprovalReceiver, EventStream};
+use uira_agent::{Agent, AgentCommand, AgentConfig, ApprovalReceiver, CancelSignal, EventStream};
+use uira_core::protocol::rpc::{
+    self, error_codes::*, methods, notifications, ApprovalRequiredNotification, EventNotification,
+    PeerInfo, PromptStatus, SendPromptResult, ShutdownNotification, StreamEventsParams,
+    JSONRPC_VERSION,
+};
 use uira_core::{AgentState, Item, Message, ReviewDecision, ThreadEvent};
 use uira_orchestration::ModelRegistry;
 use uira_providers::ModelClient;
 
-const JSONRPC_VERSION: &str = "2.0";
-const PARSE_ERROR: i64 = -32700;
-const INVALID_REQUEST: i64 = -32600;
-const METHOD_NOT_FOUND: i64 = -32601;
-const INVALID_PARAMS: i64 = -32602;
-const SERVER_ERROR: i64 = -32000;
-
 type SharedState = Arc<Mutex<RpcState>>;
 
 #[derive(Debug, Deserialize)]
@@ -135,21 +140,38 @@ struct PendingApprovalEntry {
     response_tx: oneshot::Sender<ReviewDecision>,
 }
 
+/// Which API started the running prompt, and so how its events and final
+/// response are shaped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PromptStyle {
+    /// `chat`: `chat.event` notifications and a `{"type": "done"}` result
+    Legacy,
+    /// `sendPrompt`: `event` / `approvalRequired` notifications and a
+    /// `SendPromptResult`
+    Protocol,
+}
+
 struct RpcState {
     input_tx: mpsc::Sender<Message>,
     command_tx: mpsc::Sender<AgentCommand>,
-    cancel_signal: Arc<AtomicBool>,
+    cancel_signal: CancelSignal,
     active_chat_request: Option<Value>,
+    prompt_style: PromptStyle,
     session_id: Option<String>,
     agent_state: AgentState,
     pending_approvals: HashMap<String, PendingApprovalEntry>,
+    /// Set by `initialize`
+    client: Option<PeerInfo>,
+    /// Set by `streamEvents`
+    event_stream: Option<StreamEventsParams>,
+    event_seq: u64,
 }
 
 impl RpcState {
     fn new(
         input_tx: mpsc::Sender<Message>,
         command_tx: mpsc::Sender<AgentCommand>,
-        cancel_signal: Arc<AtomicBool>,
+        cancel_signal: CancelSignal,
         session_id: String,
     ) -> Self {
         Self {
@@ -157,11 +179,31 @@ impl RpcState {
             command_tx,
             cancel_signal,
             active_chat_request: None,
+            prompt_style: PromptStyle::Legacy,
             session_id: Some(session_id),
             agent_state: AgentState::WaitingForUser,
             pending_approvals: HashMap::new(),
+            client: None,
+            event_stream: None,
+            event_seq: 0,
         }
     }
+
+    /// The `event` notification for `event`, if the client subscribed to it
+    fn event_notification(&mut self, event: &ThreadEvent) -> Option<Value> {
+        let stream = self.event_stream.as_ref()?;
+        let kind = rpc::event_type(event)?;
+        if !stream.wants(&kind) {
+            return None;
+        }
+        self.event_seq += 1;
+        serde_json::to_value(EventNotification {
+            seq: self.event_seq,
+            prompt_id: self.active_chat_request.clone(),
+            event: event.clone(),
+        })
+        .ok()
+    }
 }
 
 #[derive(Debug, Deserialize)]
@@ -210,20 +252,23 @@ pub async fn run_rpc_mode(
     let (agent, event_stream) = agent.with_event_stream();
     let cancel_signal = agent.control().cancel_signal();
     let session_id = agent.session().id.to_string();
+    let session_path = agent
+        .session_path()
+        .map(|path| path.to_string_lossy().to_string());
     let (mut agent, input_tx, approval_rx, command_tx) = agent.with_interactive();
 
     let state = Arc::new(Mutex::new(RpcState::new(
         input_tx,
         command_tx,
-        cancel_signal,
-        session_id,
+        cancel_signal.clone(),
+        session_id.clone(),
     )));
     let writer = RpcWriter::new();
 
     spawn_event_forwarder(event_stream, state.clone(), writer.clone());
     spawn_approval_forwarder(approval_rx, state.clone(), writer.clone());
 
-    tokio::spawn(async move {
+    let agent_handle = tokio::spawn(async move {
         if let Err(error) = agent.run_interactive().await {
             tracing::error!(error = %error, "RPC agent loop terminated");
         }
@@ -231,8 +276,38 @@ pub async fn run_rpc_mode(
 
     let stdin = BufReader::new(tokio::io::stdin());
     let mut lines = stdin.lines();
-
-    while let Some(line) = lines.next_line().await? {
+    let shutdown = uira_gateway::shutdown_signal();
+    tokio::pin!(shutdown);
+
+    loop {
+        let line = tokio::select! {
+            line = lines.next_line() => line?,
+            _ = &mut shutdown => {
+                // The agent loop flushes session state on its way out
+                cancel_signal.shutdown();
+                if tokio::time::timeout(Duration::from_secs(10), agent_handle)
+                    .await
+                    .is_err()
+                {
+                    tracing::warn!("Timed out waiting for the RPC agent loop to stop");
+                }
+                uira_orchestration::background_agent::interrupt_background_tasks(
+                    "Task interrupted: RPC server shutting down",
+                );
+                let params = serde_json::to_value(ShutdownNotification {
+                    session_id,
+                    session_path,
+                })
+                .unwrap_or(Value::Null);
+                writer
+                    .send_notification(notifications::SHUTDOWN, params)
+                    .await?;
+                return Ok(());
+            }
+        };
+        let Some(line) = line else {
+            break;
+        };
         let line = line.trim();
         if line.is_empty() {
             continue;
@@ -278,7 +353,7 @@ fn spawn_approval_forwarder(
             let input = pending.input.clone();
             let reason = pending.reason.clone();
 
-            let maybe_request_id = {
+            let (maybe_request_id, style, initialized) = {
                 let mut guard = state.lock().await;
                 guard.agent_state = AgentState::WaitingForApproval;
                 let request_id = guard.active_chat_request.clone();
@@ -292,10 +367,26 @@ fn spawn_approval_forwarder(
                         response_tx: pending.response_tx,
                     },
                 );
-                request_id
+                (request_id, guard.prompt_style, guard.client.is_some())
             };
 
-            if let Some(request_id) = maybe_request_id {
+            let sent = if style == PromptStyle::Protocol || initialized {
+                let payload = ApprovalRequiredNotification {
+                    approval_id,
+                    prompt_id: maybe_request_id,
+                    tool: tool_name,
+                    args: input,
+                    reason,
+                };
+                match serde_json::to_value(payload) {
+                    Ok(payload) => {
+                        writer
+                            .send_notification(notifications::APPROVAL_REQUIRED, payload)
+                            .await
+                    }
+                    Err(error) => Err(io::Error::new(io::ErrorKind::InvalidData, error)),
+                }
+            } else if let Some(request_id) = maybe_request_id {
                 let payload = json!({
                     "type": "approval_required",
                     "chat_request_id": request_id,
@@ -304,11 +395,14 @@ fn spawn_approval_forwarder(
                     "args": input,
                     "reason": reason,
                 });
+                writer.send_notification("chat.event", payload).await
+            } else {
+                Ok(())
+            };
 
-                if let Err(error) = writer.send_notification("chat.event", payload).await {
-                    tracing::error!(error = %error, "failed to send approval request to RPC client");
-                    break;
-                }
+            if let Err(error) = sent {
+                tracing::error!(error = %error, "failed to send approval request to RPC client");
+                break;
             }
         }
     });
@@ -319,12 +413,19 @@ async fn handle_agent_event(
     state: &SharedState,
     writer: &RpcWriter,
 ) -> io::Result<()> {
+    let event_notification: Option<Value>;
     let mut stream_notification: Option<Value> = None;
     let mut stream_result: Option<(Value, Value)> = None;
-    let mut stream_error: Option<(Option<Value>, String)> = None;
+    let mut stream_error: Option<(Option<Value>, String, Option<Value>)> = None;
 
     {
         let mut guard = state.lock().await;
+        event_notification = guard.event_notification(&event);
+        // Protocol prompts report progress through `event` only
+        let legacy_request = match guard.prompt_style {
+            PromptStyle::Legacy => guard.active_chat_request.clone(),
+            PromptStyle::Protocol => None,
+        };
 
         match event {
             ThreadEvent::ThreadStarted { thread_id } => {
@@ -338,7 +439,7 @@ async fn handle_agent_event(
                 guard.agent_state = AgentState::Thinking;
             }
             ThreadEvent::ContentDelta { delta } => {
-                if let Some(request_id) = guard.active_chat_request.clone() {
+                if let Some(request_id) = legacy_request {
                     stream_notification = Some(json!({
                         "type": "chunk",
                         "chat_request_id": request_id,
@@ -347,7 +448,7 @@ async fn handle_agent_event(
                 }
             }
             ThreadEvent::ThinkingDelta { thinking } => {
-                if let Some(request_id) = guard.active_chat_request.clone() {
+                if let Some(request_id) = legacy_request {
                     stream_notification = Some(json!({
                         "type": "chunk",
                         "chat_request_id": request_id,
@@ -360,7 +461,7 @@ async fn handle_agent_event(
                 item: Item::ToolCall { name, input, .. },
             } => {
                 guard.agent_state = AgentState::ExecutingTool;
-                if let Some(request_id) = guard.active_chat_request.clone() {
+                if let Some(request_id) = legacy_request {
                     stream_notification = Some(json!({
                         "type": "tool_call",
                         "chat_request_id": request_id,
@@ -386,28 +487,40 @@ async fn handle_agent_event(
             ThreadEvent::ThreadCompleted { usage } => {
                 guard.agent_state = AgentState::Complete;
                 if let Some(request_id) = guard.active_chat_request.take() {
-                    stream_result = Some((request_id, json!({ "type": "done", "usage": usage })));
+                    let payload = match guard.prompt_style {
+                        PromptStyle::Legacy => json!({ "type": "done", "usage": usage }),
+                        PromptStyle::Protocol => {
+                            prompt_result(PromptStatus::Completed, Some(usage))
+                        }
+                    };
+                    stream_result = Some((request_id, payload));
                 }
 
                 clear_pending_approvals(&mut guard, "Chat request completed");
-                guard.cancel_signal.store(false, Ordering::SeqCst);
+                guard.cancel_signal.clear();
             }
             ThreadEvent::ThreadCancelled => {
                 guard.agent_state = AgentState::Cancelled;
                 if let Some(request_id) = guard.active_chat_request.take() {
-                    stream_result =
-                        Some((request_id, json!({ "type": "done", "status": "cancelled" })));
+                    let payload = match guard.prompt_style {
+                        PromptStyle::Legacy => json!({ "type": "done", "status": "cancelled" }),
+                        PromptStyle::Protocol => prompt_result(PromptStatus::Cancelled, None),
+                    };
+                    stream_result = Some((request_id, payload));
                 }
 
                 clear_pending_approvals(&mut guard, "Chat request cancelled");
-                guard.cancel_signal.store(false, Ordering::SeqCst);
+                guard.cancel_signal.clear();
             }
-            ThreadEvent::Error { message, .. } => {
+            ThreadEvent::Error {
+                message, detail, ..
+            } => {
                 guard.agent_state = AgentState::Failed;
                 let request_id = guard.active_chat_request.take();
                 clear_pending_approvals(&mut guard, "Chat request failed");
-                guard.cancel_signal.store(false, Ordering::SeqCst);
-                stream_error = Some((request_id, message));
+                guard.cancel_signal.clear();
+                let data = detail.and_then(|detail| serde_json::to_value(detail).ok());
+                stream_error = Some((request_id, message, data));
             }
             ThreadEvent::WaitingForInput { .. } => {
                 if guard.active_chat_request.is_none() {
@@ -418,6 +531,12 @@ async fn handle_agent_event(
         }
     }
 
+    if let Some(payload) = event_notification {
+        writer
+            .send_notification(notifications::EVENT, payload)
+            .await?;
+    }
+
     if let Some(payload) = stream_notification {
         writer.send_notification("chat.event", payload).await?;
     }
@@ -426,15 +545,19 @@ async fn handle_agent_event(
         writer.send_result(request_id, payload).await?;
     }
 
-    if let Some((request_id, message)) = stream_error {
+    if let Some((request_id, message, data)) = stream_error {
         writer
-            .send_error(request_id, SERVER_ERROR, message, None)
+            .send_error(request_id, SERVER_ERROR, message, data)
             .await?;
     }
 
     Ok(())
 }
 
+fn prompt_result(status: PromptStatus, usage: Option<uira_core::TokenUsage>) -> Value {
+    serde_json::to_value(SendPromptResult { status, usage }).unwrap_or_else(|_| json!({}))
+}
+
 fn clear_pending_approvals(state: &mut RpcState, reason: &str) {
     for (_, pending) in state.pending_approvals.drain() {
         let _ = pending.response_tx.send(ReviewDecision::Deny {
@@ -466,9 +589,13 @@ async fn handle_request(
     };
 
     match request.method.as_str() {
+        methods::INITIALIZE => v1::handle_initialize(id, request.params, state, writer).await,
+        methods::SEND_PROMPT => v1::handle_send_prompt(id, request.params, state, writer).await,
+        methods::STREAM_EVENTS => v1::handle_stream_events(id, request.params, state, writer).await,
+        methods::APPROVE => v1::handle_approve(id, request.params, state, writer).await,
+        methods::CANCEL => handle_cancel(id, state, writer).await,
+        methods::STATUS => handle_status(id, state, writer).await,
         "chat" => handle_chat(id, request.params, state, writer).await,
-        "cancel" => handle_cancel(id, state, writer).await,
-        "status" => handle_status(id, state, writer).await,
         "session.create" => handle_session_create(id, request.params, state, writer).await,
         "session.list" => handle_session_list(id, request.params, state, writer).await,
         "model.list" => handle_model_list(id, writer).await,
@@ -502,30 +629,46 @@ async fn handle_chat(
         }
     };
 
+    start_prompt(id, &params.message, PromptStyle::Legacy, state, writer).await
+}
+
+/// Submit a prompt; its response is sent when the thread completes
+async fn start_prompt(
+    id: Value,
+    prompt: &str,
+    style: PromptStyle,
+    state: &SharedState,
+    writer: &RpcWriter,
+) -> io::Result<()> {
     let input_tx = {
         let mut guard = state.lock().await;
         if guard.active_chat_request.is_some() {
             None
         } else {
-            guard.cancel_signal.store(false, Ordering::SeqCst);
+            guard.cancel_signal.clear();
             guard.active_chat_request = Some(id.clone());
+            guard.prompt_style = style;
             guard.agent_state = AgentState::Thinking;
             Some(guard.input_tx.clone())
         }
     };
 
     let Some(input_tx) = input_tx else {
+        let code = match style {
+            PromptStyle::Legacy => SERVER_ERROR,
+            PromptStyle::Protocol => BUSY,
+        };
         return writer
             .send_error(
                 Some(id),
-                SERVER_ERROR,
+                code,
                 "Another chat request is already running",
                 None,
             )
             .await;
     };
 
-    if let Err(error) = input_tx.send(Message::user_prompt(&params.message)).await {
+    if let Err(error) = input_tx.send(Message::user_prompt(prompt)).await {
         let mut guard = state.lock().await;
         guard.active_chat_request = None;
         guard.agent_state = AgentState::Failed;
@@ -547,7 +690,7 @@ async fn handle_cancel(id: Value, state: &SharedState, writer: &RpcWriter) -> io
         let mut guard = state.lock().await;
         let had_active_request = guard.active_chat_request.is_some();
         if had_active_request {
-            guard.cancel_signal.store(true, Ordering::SeqCst);
+            guard.cancel_signal.cancel();
         }
 
         let approvals = guard.pending_approvals.drain().collect::<Vec<_>>();
@@ -741,7 +884,7 @@ async fn handle_session_create(
     {
         let mut guard = state.lock().await;
         guard.session_id = Some(fork_result.session_id.clone());
-        guard.cancel_signal.store(false, Ordering::SeqCst);
+        guard.cancel_signal.clear();
     }
 
     writer
@@ -875,73 +1018,67 @@ async fn handle_tool_decision(
         }
     };
 
-    enum LookupError {
-        InvalidParams(String),
-        Server(String),
+    match resolve_approval(state, params.request_id.as_deref(), approved, params.reason).await {
+        Ok(approval_id) => {
+            writer
+                .send_result(
+                    id,
+                    json!({
+                        "request_id": approval_id,
+                        "decision": if approved { "approved" } else { "rejected" },
+                    }),
+                )
+                .await
+        }
+        Err((code, message)) => writer.send_error(Some(id), code, message, None).await,
     }
+}
 
-    let lookup = {
+/// Deliver a decision to a pending approval; returns the approval id, or an
+/// error code and message
+async fn resolve_approval(
+    state: &SharedState,
+    requested_id: Option<&str>,
+    approved: bool,
+    reason: Option<String>,
+) -> Result<String, (i64, String)> {
+    let (approval_id, pending) = {
         let mut guard = state.lock().await;
 
-        match pick_approval_id(params.request_id.as_deref(), &guard.pending_approvals) {
+        match pick_approval_id(requested_id, &guard.pending_approvals) {
             Ok(approval_id) => match guard.pending_approvals.remove(&approval_id) {
                 Some(pending) => {
                     if guard.pending_approvals.is_empty() && guard.active_chat_request.is_some() {
                         guard.agent_state = AgentState::Thinking;
                     }
 
-                    Ok((approval_id, pending))
+                    (approval_id, pending)
+                }
+                None => {
+                    return Err((
+                        SERVER_ERROR,
+                        "Pending approval disappeared before decision was applied".to_string(),
+                    ))
                 }
-                None => Err(LookupError::Server(
-                    "Pending approval disappeared before decision was applied".to_string(),
-                )),
             },
-            Err(message) => Err(LookupError::InvalidParams(message)),
-        }
-    };
-
-    let (approval_id, pending) = match lookup {
-        Ok(result) => result,
-        Err(LookupError::InvalidParams(message)) => {
-            return writer
-                .send_error(Some(id), INVALID_PARAMS, message, None)
-                .await;
-        }
-        Err(LookupError::Server(message)) => {
-            return writer
-                .send_error(Some(id), SERVER_ERROR, message, None)
-                .await;
+            Err(message) => return Err((INVALID_PARAMS, message)),
         }
     };
 
     let decision = if approved {
         ReviewDecision::Approve
     } else {
-        ReviewDecision::Deny {
-            reason: params.reason,
-        }
+        ReviewDecision::Deny { reason }
     };
 
     if pending.response_tx.send(decision).is_err() {
-        return writer
-            .send_error(
-                Some(id),
-                SERVER_ERROR,
-                "Failed to deliver approval decision",
-                None,
-            )
-            .await;
+        return Err((
+            SERVER_ERROR,
+            "Failed to deliver approval decision".to_string(),
+        ));
     }
 
-    writer
-        .send_result(
-            id,
-            json!({
-                "request_id": approval_id,
-                "decision": if approved { "approved" } else { "rejected" },
-            }),
-        )
-        .await
+    Ok(approval_id)
 }
 
 fn pick_approval_id(
@@ -1027,4 +1164,35 @@ mod tests {
 
         assert_eq!(pick_approval_id(None, &pending).unwrap(), "only");
     }
+
+    #[test]
+    fn event_notifications_follow_stream_subscription() {
+        let (input_tx, _input_rx) = mpsc::channel(1);
+        let (command_tx, _command_rx) = mpsc::channel(1);
+        let mut state = RpcState::new(
+            input_tx,
+            command_tx,
+            CancelSignal::new(),
+            "session".to_string(),
+        );
+        let delta = ThreadEvent::ContentDelta {
+            delta: "hi".to_string(),
+        };
+        assert!(state.event_notification(&delta).is_none());
+
+        state.event_stream = Some(StreamEventsParams {
+            enabled: true,
+            events: vec!["content_delta".to_string()],
+        });
+        state.active_chat_request = Some(json!(4));
+        let first = state.event_notification(&delta).unwrap();
+        assert_eq!(first["seq"], 1);
+        assert_eq!(first["promptId"], 4);
+        assert_eq!(first["event"]["delta"], "hi");
+
+        assert!(state
+            .event_notification(&ThreadEvent::TurnStarted { turn_number: 1 })
+            .is_none());
+        assert_eq!(state.event_notification(&delta).unwrap()["seq"], 2);
+    }
 }
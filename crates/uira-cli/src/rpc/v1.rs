@@ -0,0 +1,158 @@
+//! Handlers for the versioned editor protocol (`initialize`, `sendPrompt`,
+//! `streamEvents`, `approve`); `cancel` and `status` are shared with the
+//! original API
+
+use serde_json::{json, Value};
+use std::io;
+use uira_core::protocol::rpc::{
+    error_codes::*, ApprovalDecision, ApproveParams, ApproveResult, InitializeParams,
+    InitializeResult, PeerInfo, SendPromptParams, ServerCapabilities, StreamEventsParams,
+    StreamEventsResult, RPC_PROTOCOL_VERSION,
+};
+
+use super::{parse_params, resolve_approval, start_prompt, PromptStyle, RpcWriter, SharedState};
+
+pub(super) async fn handle_initialize(
+    id: Value,
+    params: Option<Value>,
+    state: &SharedState,
+    writer: &RpcWriter,
+) -> io::Result<()> {
+    let params = match parse_params::<InitializeParams>(params) {
+        Ok(params) => params,
+        Err(message) => {
+            return writer
+                .send_error(Some(id), INVALID_PARAMS, message, None)
+                .await;
+        }
+    };
+
+    if params.protocol_version != RPC_PROTOCOL_VERSION {
+        return writer
+            .send_error(
+                Some(id),
+                UNSUPPORTED_VERSION,
+                format!(
+                    "Unsupported protocol version {} (server speaks {})",
+                    params.protocol_version, RPC_PROTOCOL_VERSION
+                ),
+                Some(json!({ "supported": [RPC_PROTOCOL_VERSION] })),
+            )
+            .await;
+    }
+
+    let session_id = {
+        let mut guard = state.lock().await;
+        guard.client = Some(params.client_info.unwrap_or_else(|| PeerInfo {
+            name: "unknown".to_string(),
+            version: None,
+        }));
+        guard.session_id.clone()
+    };
+
+    let result = InitializeResult {
+        protocol_version: RPC_PROTOCOL_VERSION,
+        server_info: PeerInfo {
+            name: "uira".to_string(),
+            version: Some(env!("CARGO_PKG_VERSION").to_string()),
+        },
+        session_id,
+        capabilities: ServerCapabilities::default(),
+    };
+    send_typed(id, &result, writer).await
+}
+
+pub(super) async fn handle_send_prompt(
+    id: Value,
+    params: Option<Value>,
+    state: &SharedState,
+    writer: &RpcWriter,
+) -> io::Result<()> {
+    let params = match parse_params::<SendPromptParams>(params) {
+        Ok(params) => params,
+        Err(message) => {
+            return writer
+                .send_error(Some(id), INVALID_PARAMS, message, None)
+                .await;
+        }
+    };
+
+    if params.prompt.trim().is_empty() {
+        return writer
+            .send_error(Some(id), INVALID_PARAMS, "Prompt must not be empty", None)
+            .await;
+    }
+
+    start_prompt(id, &params.prompt, PromptStyle::Protocol, state, writer).await
+}
+
+pub(super) async fn handle_stream_events(
+    id: Value,
+    params: Option<Value>,
+    state: &SharedState,
+    writer: &RpcWriter,
+) -> io::Result<()> {
+    let params = match parse_params::<StreamEventsParams>(params) {
+        Ok(params) => params,
+        Err(message) => {
+            return writer
+                .send_error(Some(id), INVALID_PARAMS, message, None)
+                .await;
+        }
+    };
+
+    let result = StreamEventsResult {
+        enabled: params.enabled,
+        events: params.events.clone(),
+    };
+    {
+        let mut guard = state.lock().await;
+        guard.event_stream = params.enabled.then_some(params);
+    }
+    send_typed(id, &result, writer).await
+}
+
+pub(super) async fn handle_approve(
+    id: Value,
+    params: Option<Value>,
+    state: &SharedState,
+    writer: &RpcWriter,
+) -> io::Result<()> {
+    let params = match parse_params::<ApproveParams>(params) {
+        Ok(params) => params,
+        Err(message) => {
+            return writer
+                .send_error(Some(id), INVALID_PARAMS, message, None)
+                .await;
+        }
+    };
+
+    let approved = params.decision == ApprovalDecision::Approve;
+    match resolve_approval(
+        state,
+        params.approval_id.as_deref(),
+        approved,
+        params.reason,
+    )
+    .await
+    {
+        Ok(approval_id) => {
+            let result = ApproveResult {
+                approval_id,
+                decision: params.decision,
+            };
+            send_typed(id, &result, writer).await
+        }
+        Err((code, message)) => writer.send_error(Some(id), code, message, None).await,
+    }
+}
+
+async fn send_typed<T: serde::Serialize>(
+    id: Value,
+    result: &T,
+    writer: &RpcWriter,
+) -> io::Result<()> {
+    let value = serde_json::to_value(result)
+        .map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    writer.send_result(id, value).await
+}
@@ -0,0 +1,406 @@
+//! `uira-agent self-update`: replace the running binary with a signed release
+//!
+//! Every GitHub release carries `SHA256SUMS.txt` and `SHA256SUMS.txt.sig`,
+//! an ed25519 signature over the checksum file made with the release key.
+//! An update downloads the platform binary, checks the signature against the
+//! public key compiled into this build, checks the binary against its
+//! checksum, and only then renames it over the running executable.
+
+use std::io;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use colored::Colorize;
+use semver::Version;
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use crate::commands::UpdateChannel;
+
+const RELEASES_URL: &str = "https://api.github.com/repos/junhoyeo/uira/releases?per_page=50";
+const CHECKSUMS_ASSET: &str = "SHA256SUMS.txt";
+const SIGNATURE_ASSET: &str = "SHA256SUMS.txt.sig";
+
+/// Base64 ed25519 public key of the release signer, set by the release
+/// workflow. Builds without it refuse to self-update.
+const RELEASE_PUBLIC_KEY: Option<&str> = option_env!("UIRA_RELEASE_PUBLIC_KEY");
+
+const CURRENT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+/// Set by the npm launcher to the version of `@uiradev/uira`, whose napi
+/// addon is built from the same release as the binary it ships
+pub const NPM_VERSION_ENV: &str = "UIRA_NPM_PACKAGE_VERSION";
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct Release {
+    pub tag_name: String,
+    #[serde(default)]
+    pub prerelease: bool,
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub assets: Vec<ReleaseAsset>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct ReleaseAsset {
+    pub name: String,
+    pub browser_download_url: String,
+}
+
+impl Release {
+    pub fn version(&self) -> Option<Version> {
+        Version::parse(self.tag_name.trim_start_matches('v')).ok()
+    }
+
+    fn asset(&self, name: &str) -> Option<&ReleaseAsset> {
+        self.assets.iter().find(|asset| asset.name == name)
+    }
+}
+
+/// Release binary for this platform, named like the release workflow stages
+/// it (`uira-agent-<code-target>[.exe]`)
+pub fn asset_name() -> Option<String> {
+    let target = match (std::env::consts::OS, std::env::consts::ARCH) {
+        ("macos", "aarch64") => "darwin-arm64",
+        ("macos", "x86_64") => "darwin-x64",
+        ("linux", "x86_64") if cfg!(target_env = "musl") => "linux-x64-musl",
+        ("linux", "x86_64") => "linux-x64-gnu",
+        ("linux", "aarch64") if cfg!(target_env = "musl") => "linux-arm64-musl",
+        ("linux", "aarch64") => "linux-arm64-gnu",
+        ("windows", "x86_64") => "win32-x64-msvc",
+        _ => return None,
+    };
+    Some(format!(
+        "uira-agent-{}{}",
+        target,
+        std::env::consts::EXE_SUFFIX
+    ))
+}
+
+/// Newest published release on `channel`. Stable skips prereleases and
+/// versions with a pre-release suffix.
+pub fn select_release(releases: &[Release], channel: UpdateChannel) -> Option<&Release> {
+    releases
+        .iter()
+        .filter(|release| !release.draft)
+        .filter_map(|release| release.version().map(|version| (version, release)))
+        .filter(|(version, release)| {
+            channel == UpdateChannel::Nightly || (!release.prerelease && version.pre.is_empty())
+        })
+        .max_by(|a, b| a.0.cmp(&b.0))
+        .map(|(_, release)| release)
+}
+
+/// Check `signature` (base64) over `checksums` against `public_key` (base64)
+pub fn verify_signature(checksums: &[u8], signature: &str, public_key: &str) -> Result<(), String> {
+    let engine = base64::engine::general_purpose::STANDARD;
+    let public_key = engine
+        .decode(public_key.trim())
+        .map_err(|e| format!("invalid release public key: {e}"))?;
+    let signature = engine
+        .decode(signature.trim())
+        .map_err(|e| format!("invalid checksum signature: {e}"))?;
+    ring::signature::UnparsedPublicKey::new(&ring::signature::ED25519, public_key)
+        .verify(checksums, &signature)
+        .map_err(|_| "checksum signature does not match the release key".to_string())
+}
+
+/// Hex digest listed for `asset` in `sha256sum` output
+pub fn expected_checksum(checksums: &str, asset: &str) -> Option<String> {
+    checksums.lines().find_map(|line| {
+        let (digest, name) = line.trim().split_once(char::is_whitespace)?;
+        // `sha256sum -b` marks binary mode with a leading `*`
+        let name = name.trim_start().trim_start_matches('*');
+        (name == asset).then(|| digest.to_ascii_lowercase())
+    })
+}
+
+pub fn verify_checksum(bytes: &[u8], expected: &str) -> Result<(), String> {
+    let actual = hex::encode(Sha256::digest(bytes));
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!(
+            "checksum mismatch: expected {expected}, downloaded {actual}"
+        ))
+    }
+}
+
+/// Write `bytes` next to `target` and rename it into place, so an
+/// interrupted update never leaves a half-written binary behind. Windows
+/// cannot replace a running executable, so the old one is moved aside
+/// first.
+pub fn replace_binary(target: &Path, bytes: &[u8]) -> io::Result<()> {
+    let file_name = target
+        .file_name()
+        .ok_or_else(|| io::Error::other("executable path has no file name"))?
+        .to_string_lossy()
+        .to_string();
+    let staged = target.with_file_name(format!(".{}.update-{}", file_name, std::process::id()));
+
+    let write = || -> io::Result<()> {
+        std::fs::write(&staged, bytes)?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(target)
+                .map(|metadata| metadata.permissions().mode())
+                .unwrap_or(0o755);
+            std::fs::set_permissions(&staged, std::fs::Permissions::from_mode(mode | 0o111))?;
+        }
+        std::fs::File::open(&staged)?.sync_all()?;
+        if cfg!(windows) {
+            let old = target.with_file_name(format!("{}.old", file_name));
+            let _ = std::fs::remove_file(&old);
+            std::fs::rename(target, &old)?;
+        }
+        std::fs::rename(&staged, target)
+    };
+
+    write().inspect_err(|_| {
+        let _ = std::fs::remove_file(&staged);
+    })
+}
+
+/// Warning for a binary that does not match the npm package it was
+/// launched from, e.g. after `self-update` ran inside `node_modules`
+pub fn version_skew_warning(cli_version: &str, npm_version: Option<&str>) -> Option<String> {
+    let npm_version = npm_version?.trim();
+    if npm_version.is_empty() || npm_version == cli_version {
+        return None;
+    }
+    Some(format!(
+        "uira-agent {cli_version} was launched from @uiradev/uira {npm_version}; the napi addon and CLI differ. Run `npm install -g @uiradev/uira@{cli_version}` to bring them back in line."
+    ))
+}
+
+/// [`version_skew_warning`] for the running process
+pub fn current_version_skew() -> Option<String> {
+    let npm_version = std::env::var(NPM_VERSION_ENV).ok();
+    version_skew_warning(CURRENT_VERSION, npm_version.as_deref())
+}
+
+fn installed_through_npm(exe: &Path) -> bool {
+    exe.components()
+        .any(|component| component.as_os_str() == "node_modules")
+}
+
+pub async fn run(
+    channel: UpdateChannel,
+    version: Option<&str>,
+    check: bool,
+    force: bool,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let exe = std::env::current_exe()?;
+    let exe = exe.canonicalize().unwrap_or(exe);
+    if installed_through_npm(&exe) && !check && !force {
+        return Err(format!(
+            "{} was installed through npm; update it with `npm install -g @uiradev/uira@latest` so the napi addon stays in step, or pass --force",
+            exe.display()
+        )
+        .into());
+    }
+    let asset_name = asset_name().ok_or("no release binaries are published for this platform")?;
+
+    let http = reqwest::Client::new();
+    let mut request = http
+        .get(RELEASES_URL)
+        .header("Accept", "application/vnd.github+json")
+        .header("User-Agent", "uira");
+    if let Ok(token) = std::env::var("GITHUB_TOKEN") {
+        request = request.bearer_auth(token);
+    }
+    let response = request.send().await?.error_for_status()?;
+    let releases: Vec<Release> = response.json().await?;
+
+    let current = Version::parse(CURRENT_VERSION)?;
+    let release = match version {
+        Some(wanted) => {
+            let wanted = Version::parse(wanted.trim_start_matches('v'))?;
+            releases
+                .iter()
+                .find(|release| !release.draft && release.version().as_ref() == Some(&wanted))
+                .ok_or_else(|| format!("release v{wanted} not found"))?
+        }
+        None => select_release(&releases, channel).ok_or_else(|| {
+            let channel = match channel {
+                UpdateChannel::Stable => "stable",
+                UpdateChannel::Nightly => "nightly",
+            };
+            format!("no {channel} release found")
+        })?,
+    };
+    let target_version = release
+        .version()
+        .ok_or_else(|| format!("release tag '{}' is not a version", release.tag_name))?;
+
+    if version.is_none() && target_version <= current {
+        println!("uira-agent {} is up to date", current);
+        return Ok(());
+    }
+    if check {
+        println!(
+            "Update available: {} -> {} ({})",
+            current,
+            target_version.to_string().green(),
+            release.tag_name
+        );
+        return Ok(());
+    }
+
+    let public_key = RELEASE_PUBLIC_KEY.ok_or(
+        "this build has no release signing key, so updates cannot be verified; install a release build",
+    )?;
+    let download = |name: &str| {
+        let asset = release
+            .asset(name)
+            .map(|asset| asset.browser_download_url.clone());
+        let http = http.clone();
+        let name = name.to_string();
+        let tag = release.tag_name.clone();
+        async move {
+            let url = asset.ok_or_else(|| format!("release {tag} has no {name}"))?;
+            let bytes = http
+                .get(url)
+                .header("User-Agent", "uira")
+                .send()
+                .await
+                .and_then(|response| response.error_for_status())
+                .map_err(|e| format!("failed to download {name}: {e}"))?
+                .bytes()
+                .await
+                .map_err(|e| format!("failed to download {name}: {e}"))?;
+            Ok::<_, String>(bytes.to_vec())
+        }
+    };
+
+    println!("Downloading uira-agent {} ...", target_version);
+    let checksums = download(CHECKSUMS_ASSET).await?;
+    let signature = download(SIGNATURE_ASSET).await?;
+    verify_signature(&checksums, &String::from_utf8_lossy(&signature), public_key)?;
+    let expected = expected_checksum(&String::from_utf8_lossy(&checksums), &asset_name)
+        .ok_or_else(|| format!("{CHECKSUMS_ASSET} does not list {asset_name}"))?;
+    let binary = download(&asset_name).await?;
+    verify_checksum(&binary, &expected)?;
+
+    replace_binary(&exe, &binary)?;
+    println!(
+        "{} uira-agent {} -> {}",
+        "Updated".green().bold(),
+        current,
+        target_version
+    );
+    Ok(())
+}
+
+/// Leftover from a Windows update, removable once the new binary runs
+pub fn stale_binary(exe: &Path) -> Option<PathBuf> {
+    let name = exe.file_name()?.to_string_lossy().to_string();
+    let old = exe.with_file_name(format!("{}.old", name));
+    old.exists().then_some(old)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ring::signature::KeyPair;
+
+    fn release(tag: &str, prerelease: bool) -> Release {
+        Release {
+            tag_name: tag.to_string(),
+            prerelease,
+            draft: false,
+            assets: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn channels_pick_the_newest_matching_release() {
+        let releases = vec![
+            release("v0.1.1", false),
+            release("v0.3.0-beta.1", true),
+            release("v0.2.0", false),
+            release("nightly-build", true),
+        ];
+        let stable = select_release(&releases, UpdateChannel::Stable).unwrap();
+        assert_eq!(stable.tag_name, "v0.2.0");
+        let nightly = select_release(&releases, UpdateChannel::Nightly).unwrap();
+        assert_eq!(nightly.tag_name, "v0.3.0-beta.1");
+    }
+
+    #[test]
+    fn finds_checksums_in_sha256sum_output() {
+        let sums = "ABC123  uira-agent-linux-x64-gnu\ndef456 *uira-agent-win32-x64-msvc.exe\n";
+        assert_eq!(
+            expected_checksum(sums, "uira-agent-linux-x64-gnu").as_deref(),
+            Some("abc123")
+        );
+        assert_eq!(
+            expected_checksum(sums, "uira-agent-win32-x64-msvc.exe").as_deref(),
+            Some("def456")
+        );
+        assert!(expected_checksum(sums, "uira-agent-darwin-arm64").is_none());
+
+        let digest = hex::encode(Sha256::digest(b"binary"));
+        assert!(verify_checksum(b"binary", &digest).is_ok());
+        assert!(verify_checksum(b"tampered", &digest).is_err());
+    }
+
+    #[test]
+    fn rejects_checksums_not_signed_by_the_release_key() {
+        let rng = ring::rand::SystemRandom::new();
+        let pkcs8 = ring::signature::Ed25519KeyPair::generate_pkcs8(&rng).unwrap();
+        let key = ring::signature::Ed25519KeyPair::from_pkcs8(pkcs8.as_ref()).unwrap();
+        let engine = base64::engine::general_purpose::STANDARD;
+        let public_key = engine.encode(key.public_key().as_ref());
+
+        let sums = b"abc123  uira-agent-linux-x64-gnu\n";
+        let signature = engine.encode(key.sign(sums).as_ref());
+        assert!(verify_signature(sums, &format!("{signature}\n"), &public_key).is_ok());
+        assert!(verify_signature(
+            b"def456  uira-agent-linux-x64-gnu\n",
+            &signature,
+            &public_key
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn replaces_the_binary_in_place() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let target = dir.path().join("uira-agent");
+        std::fs::write(&target, b"old").unwrap();
+
+        replace_binary(&target, b"new").unwrap();
+        assert_eq!(std::fs::read(&target).unwrap(), b"new");
+        let leftovers: Vec<_> = std::fs::read_dir(dir.path())
+            .unwrap()
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.file_name().to_string_lossy().contains(".update-"))
+            .collect();
+        assert!(leftovers.is_empty());
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&target).unwrap().permissions().mode();
+            assert_ne!(mode & 0o111, 0);
+        }
+    }
+
+    #[test]
+    fn warns_when_cli_and_npm_package_differ() {
+        assert!(version_skew_warning("0.2.0", None).is_none());
+        assert!(version_skew_warning("0.2.0", Some("0.2.0")).is_none());
+        let warning = version_skew_warning("0.2.0", Some("0.1.1")).unwrap();
+        assert!(warning.contains("@uiradev/uira 0.1.1"));
+        assert!(warning.contains("npm install -g @uiradev/uira@0.2.0"));
+
+        assert!(installed_through_npm(Path::new(
+            "/usr/lib/node_modules/@uiradev/uira-linux-x64-gnu/uira-agent"
+        )));
+        assert!(!installed_through_npm(Path::new(
+            "/usr/local/bin/uira-agent"
+        )));
+    }
+}
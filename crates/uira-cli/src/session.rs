@@ -3,7 +3,7 @@ use std::collections::HashMap;
 use std::path::PathBuf;
 use uira_agent::session::{extract_messages, SessionItem};
 use uira_agent::SessionRecorder;
-use uira_core::Message;
+use uira_core::{Message, ThreadEvent, ToolCallStats};
 
 pub struct SessionEntry {
     pub thread_id: String,
@@ -161,6 +161,131 @@ fn print_tree_node(
     }
 }
 
+/// Per-turn telemetry aggregated across a recorded session
+#[derive(Debug, Default)]
+pub struct SessionStats {
+    pub turns: usize,
+    pub total_ms: u64,
+    pub provider_latency_ms: u64,
+    pub max_provider_latency_ms: u64,
+    pub retries: u32,
+    pub context_tokens_start: Option<usize>,
+    pub context_tokens_end: usize,
+    /// Tool totals, sorted by total time descending
+    pub tools: Vec<ToolCallStats>,
+    /// (turn number, duration ms) for the slowest turns
+    pub slowest_turns: Vec<(usize, u64)>,
+}
+
+pub fn session_stats(items: &[SessionItem]) -> SessionStats {
+    let mut stats = SessionStats::default();
+    let mut tools: HashMap<String, ToolCallStats> = HashMap::new();
+    let mut turns = Vec::new();
+
+    for item in items {
+        let SessionItem::Event { event } = item else {
+            continue;
+        };
+        if event.event_type != "turn_summary" {
+            continue;
+        }
+        let Ok(ThreadEvent::TurnSummary {
+            turn_number,
+            duration_ms,
+            provider_latency_ms,
+            retries,
+            tools: turn_tools,
+            context_tokens_before,
+            context_tokens_after,
+        }) = serde_json::from_value(event.data.clone())
+        else {
+            continue;
+        };
+
+        stats.turns += 1;
+        stats.total_ms += duration_ms;
+        stats.provider_latency_ms += provider_latency_ms;
+        stats.max_provider_latency_ms = stats.max_provider_latency_ms.max(provider_latency_ms);
+        stats.retries += retries;
+        stats
+            .context_tokens_start
+            .get_or_insert(context_tokens_before);
+        stats.context_tokens_end = context_tokens_after;
+        turns.push((turn_number, duration_ms));
+
+        for tool in turn_tools {
+            let entry = tools
+                .entry(tool.name.clone())
+                .or_insert_with(|| ToolCallStats {
+                    name: tool.name.clone(),
+                    ..Default::default()
+                });
+            entry.calls += tool.calls;
+            entry.errors += tool.errors;
+            entry.total_ms += tool.total_ms;
+        }
+    }
+
+    stats.tools = tools.into_values().collect();
+    stats
+        .tools
+        .sort_by(|a, b| b.total_ms.cmp(&a.total_ms).then(a.name.cmp(&b.name)));
+    turns.sort_by_key(|(_, ms)| std::cmp::Reverse(*ms));
+    turns.truncate(3);
+    stats.slowest_turns = turns;
+    stats
+}
+
+pub fn display_session_stats(stats: &SessionStats) {
+    if stats.turns == 0 {
+        println!("No turn telemetry recorded for this session.");
+        return;
+    }
+
+    println!("Turns: {} ({} ms total)", stats.turns, stats.total_ms);
+    println!(
+        "Provider latency: {} ms total, {} ms avg, {} ms max",
+        stats.provider_latency_ms,
+        stats.provider_latency_ms / stats.turns as u64,
+        stats.max_provider_latency_ms
+    );
+    println!("Retries: {}", stats.retries);
+    let start = stats.context_tokens_start.unwrap_or_default();
+    println!(
+        "Context: {} -> {} tokens ({:+})",
+        start,
+        stats.context_tokens_end,
+        stats.context_tokens_end as i64 - start as i64
+    );
+
+    if !stats.tools.is_empty() {
+        println!();
+        println!(
+            "{:<24} {:>6} {:>6} {:>10} {:>8}",
+            "TOOL", "CALLS", "ERRORS", "TOTAL MS", "AVG MS"
+        );
+        println!("{}", "-".repeat(58));
+        for tool in &stats.tools {
+            println!(
+                "{:<24} {:>6} {:>6} {:>10} {:>8}",
+                truncate(&tool.name, 24),
+                tool.calls,
+                tool.errors,
+                tool.total_ms,
+                tool.total_ms / tool.calls.max(1) as u64
+            );
+        }
+    }
+
+    if !stats.slowest_turns.is_empty() {
+        println!();
+        println!("Slowest turns:");
+        for (turn, ms) in &stats.slowest_turns {
+            println!("  turn {:<4} {} ms", turn, ms);
+        }
+    }
+}
+
 fn truncate(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
         s.to_string()
@@ -168,3 +293,58 @@ fn truncate(s: &str, max_len: usize) -> String {
         format!("{}...", &s[..max_len.saturating_sub(3)])
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uira_agent::session::EventWrapper;
+
+    fn summary(turn_number: usize, duration_ms: u64, tools: Vec<ToolCallStats>) -> SessionItem {
+        SessionItem::Event {
+            event: EventWrapper::from(ThreadEvent::TurnSummary {
+                turn_number,
+                duration_ms,
+                provider_latency_ms: duration_ms / 2,
+                retries: 1,
+                tools,
+                context_tokens_before: turn_number * 100,
+                context_tokens_after: turn_number * 100 + 100,
+            }),
+        }
+    }
+
+    fn tool(name: &str, calls: u32, errors: u32, total_ms: u64) -> ToolCallStats {
+        ToolCallStats {
+            name: name.to_string(),
+            calls,
+            errors,
+            total_ms,
+        }
+    }
+
+    #[test]
+    fn session_stats_aggregates_turn_summaries() {
+        let items = vec![
+            summary(1, 400, vec![tool("Read", 2, 0, 20)]),
+            SessionItem::Event {
+                event: EventWrapper::from(ThreadEvent::ThreadCancelled),
+            },
+            summary(
+                2,
+                1000,
+                vec![tool("Bash", 1, 1, 500), tool("Read", 1, 0, 5)],
+            ),
+        ];
+
+        let stats = session_stats(&items);
+        assert_eq!(stats.turns, 2);
+        assert_eq!(stats.provider_latency_ms, 700);
+        assert_eq!(stats.max_provider_latency_ms, 500);
+        assert_eq!(stats.retries, 2);
+        assert_eq!(stats.context_tokens_start, Some(100));
+        assert_eq!(stats.context_tokens_end, 300);
+        assert_eq!(stats.tools[0], tool("Bash", 1, 1, 500));
+        assert_eq!(stats.tools[1], tool("Read", 3, 0, 25));
+        assert_eq!(stats.slowest_turns, vec![(2, 1000), (1, 400)]);
+    }
+}
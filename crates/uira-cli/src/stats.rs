@@ -0,0 +1,438 @@
+//! `uira stats`: usage aggregated across session logs
+
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+use colored::Colorize;
+use serde::Serialize;
+use uira_agent::session::{get_total_usage, SessionItem, SessionMetaLine};
+use uira_agent::SessionRecorder;
+use uira_core::ThreadEvent;
+use uira_orchestration::features::analytics::CostEstimator;
+
+/// Sessions started without `--agent` and outside a delegation
+const MAIN_AGENT: &str = "main";
+
+const BAR_WIDTH: usize = 24;
+
+/// Parse `--since`: a span back from now (`12h`, `30d`, `2w`), a date
+/// (`2026-01-31`) or `all`
+pub fn parse_since(value: &str, now: DateTime<Utc>) -> Result<Option<DateTime<Utc>>, String> {
+    let value = value.trim();
+    if value.eq_ignore_ascii_case("all") {
+        return Ok(None);
+    }
+    if let Ok(date) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+        return Ok(date.and_hms_opt(0, 0, 0).map(|start| start.and_utc()));
+    }
+
+    let invalid =
+        || format!("invalid --since '{value}': expected e.g. 12h, 30d, 2w, 2026-01-31 or all");
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let count: i64 = value[..split].parse().map_err(|_| invalid())?;
+    let span = match &value[split..] {
+        "h" => Duration::hours(count),
+        "d" => Duration::days(count),
+        "w" => Duration::weeks(count),
+        _ => return Err(invalid()),
+    };
+    Ok(Some(now - span))
+}
+
+/// Tokens and estimated cost for one provider or agent
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct UsageGroup {
+    pub name: String,
+    pub sessions: usize,
+    pub turns: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ToolUsage {
+    pub name: String,
+    pub calls: usize,
+    pub errors: usize,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct GoalStats {
+    pub name: String,
+    pub checks: usize,
+    pub passed: usize,
+}
+
+impl StatsReport {
+    /// Share of goal checks that passed, across all goals
+    pub fn goal_pass_rate(&self) -> Option<f64> {
+        let checks: usize = self.goals.iter().map(|goal| goal.checks).sum();
+        let passed: usize = self.goals.iter().map(|goal| goal.passed).sum();
+        (checks > 0).then(|| passed as f64 / checks as f64)
+    }
+}
+
+impl GoalStats {
+    pub fn pass_rate(&self) -> f64 {
+        if self.checks == 0 {
+            0.0
+        } else {
+            self.passed as f64 / self.checks as f64
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct StatsReport {
+    pub since: Option<DateTime<Utc>>,
+    pub sessions: usize,
+    pub turns: usize,
+    pub input_tokens: u64,
+    pub output_tokens: u64,
+    pub cost_usd: f64,
+    /// Turns per session, each session being one task
+    pub avg_turns_per_task: f64,
+    pub providers: Vec<UsageGroup>,
+    pub agents: Vec<UsageGroup>,
+    /// Sorted by calls, most used first
+    pub tools: Vec<ToolUsage>,
+    pub goals: Vec<GoalStats>,
+}
+
+/// Aggregate every session log started at or after `since`
+pub fn collect(since: Option<DateTime<Utc>>) -> std::io::Result<StatsReport> {
+    Ok(aggregate(since, &load_sessions(since)?))
+}
+
+/// Every readable session log started at or after `since`
+pub fn load_sessions(
+    since: Option<DateTime<Utc>>,
+) -> std::io::Result<Vec<(SessionMetaLine, Vec<SessionItem>)>> {
+    let mut sessions = Vec::new();
+    for (path, meta) in SessionRecorder::list_sessions()? {
+        if since.is_some_and(|since| meta.timestamp < since) {
+            continue;
+        }
+        match SessionRecorder::load(&path) {
+            Ok(items) => sessions.push((meta, items)),
+            Err(e) => {
+                tracing::warn!(path = %path.display(), error = %e, "Skipping unreadable session")
+            }
+        }
+    }
+    Ok(sessions)
+}
+
+pub fn aggregate(
+    since: Option<DateTime<Utc>>,
+    sessions: &[(SessionMetaLine, Vec<SessionItem>)],
+) -> StatsReport {
+    let mut report = StatsReport {
+        since,
+        sessions: sessions.len(),
+        ..StatsReport::default()
+    };
+    let mut providers: BTreeMap<String, UsageGroup> = BTreeMap::new();
+    let mut agents: BTreeMap<String, UsageGroup> = BTreeMap::new();
+    let mut tools: BTreeMap<String, ToolUsage> = BTreeMap::new();
+    let mut goals: BTreeMap<String, GoalStats> = BTreeMap::new();
+
+    for (meta, items) in sessions {
+        let usage = get_total_usage(items);
+        let turns = items
+            .iter()
+            .filter(|item| matches!(item, SessionItem::TurnContext { .. }))
+            .count();
+        let cost = CostEstimator::estimate_cost(
+            usage.input_tokens as usize,
+            usage.output_tokens as usize,
+            &meta.model,
+        );
+
+        report.turns += turns;
+        report.input_tokens += usage.input_tokens;
+        report.output_tokens += usage.output_tokens;
+        report.cost_usd += cost;
+
+        let agent = meta.agent.as_deref().unwrap_or(MAIN_AGENT);
+        for (groups, name) in [
+            (&mut providers, meta.provider.as_str()),
+            (&mut agents, agent),
+        ] {
+            let group = groups
+                .entry(name.to_string())
+                .or_insert_with(|| UsageGroup {
+                    name: name.to_string(),
+                    ..UsageGroup::default()
+                });
+            group.sessions += 1;
+            group.turns += turns;
+            group.input_tokens += usage.input_tokens;
+            group.output_tokens += usage.output_tokens;
+            group.cost_usd += cost;
+        }
+
+        let mut call_names: HashMap<&str, &str> = HashMap::new();
+        for item in items {
+            match item {
+                SessionItem::ToolCall { id, name, .. } => {
+                    call_names.insert(id, name);
+                    tools
+                        .entry(name.clone())
+                        .or_insert_with(|| ToolUsage {
+                            name: name.clone(),
+                            ..ToolUsage::default()
+                        })
+                        .calls += 1;
+                }
+                SessionItem::ToolResult {
+                    id, is_error: true, ..
+                } => {
+                    if let Some(tool) = call_names
+                        .get(id.as_str())
+                        .and_then(|name| tools.get_mut(*name))
+                    {
+                        tool.errors += 1;
+                    }
+                }
+                SessionItem::Event { event } if event.event_type == "goal_verification_result" => {
+                    let Ok(ThreadEvent::GoalVerificationResult { goal, passed, .. }) =
+                        serde_json::from_value(event.data.clone())
+                    else {
+                        continue;
+                    };
+                    let stats = goals.entry(goal.clone()).or_insert_with(|| GoalStats {
+                        name: goal,
+                        ..GoalStats::default()
+                    });
+                    stats.checks += 1;
+                    stats.passed += usize::from(passed);
+                }
+                _ => {}
+            }
+        }
+    }
+
+    if report.sessions > 0 {
+        report.avg_turns_per_task = report.turns as f64 / report.sessions as f64;
+    }
+    report.providers = sorted_by_cost(providers);
+    report.agents = sorted_by_cost(agents);
+    report.tools = tools.into_values().collect();
+    report
+        .tools
+        .sort_by(|a, b| b.calls.cmp(&a.calls).then(a.name.cmp(&b.name)));
+    report.goals = goals.into_values().collect();
+    report
+}
+
+fn sorted_by_cost(groups: BTreeMap<String, UsageGroup>) -> Vec<UsageGroup> {
+    let mut groups: Vec<_> = groups.into_values().collect();
+    groups.sort_by(|a, b| b.cost_usd.total_cmp(&a.cost_usd).then(a.name.cmp(&b.name)));
+    groups
+}
+
+pub fn display(report: &StatsReport) {
+    let period = match report.since {
+        Some(since) => format!("since {}", since.format("%Y-%m-%d %H:%M")),
+        None => "all time".to_string(),
+    };
+    println!("{} {}", "Usage".cyan().bold(), period.dimmed());
+    println!("{}", "─".repeat(72).dimmed());
+    if report.sessions == 0 {
+        println!("No sessions found.");
+        return;
+    }
+
+    println!(
+        "Sessions: {}   Turns: {} ({:.1} per task)   Tokens: {} in / {} out   Cost: {}",
+        report.sessions,
+        report.turns,
+        report.avg_turns_per_task,
+        report.input_tokens,
+        report.output_tokens,
+        CostEstimator::format_cost(report.cost_usd).green()
+    );
+
+    for (title, groups) in [("PROVIDER", &report.providers), ("AGENT", &report.agents)] {
+        println!();
+        println!(
+            "{:<20} {:>8} {:>6} {:>12} {:>12} {:>10}",
+            title, "SESSIONS", "TURNS", "INPUT", "OUTPUT", "COST"
+        );
+        for group in groups {
+            println!(
+                "{:<20} {:>8} {:>6} {:>12} {:>12} {:>10}",
+                group.name.yellow(),
+                group.sessions,
+                group.turns,
+                group.input_tokens,
+                group.output_tokens,
+                CostEstimator::format_cost(group.cost_usd)
+            );
+        }
+    }
+
+    if !report.tools.is_empty() {
+        println!();
+        println!("{:<24} {:>6} {:>6}", "TOOL", "CALLS", "ERRORS");
+        let max_calls = report.tools[0].calls;
+        for tool in report.tools.iter().take(15) {
+            println!(
+                "{:<24} {:>6} {:>6} {}",
+                tool.name,
+                tool.calls,
+                tool.errors,
+                bar(tool.calls as f64 / max_calls as f64).cyan()
+            );
+        }
+    }
+
+    if !report.goals.is_empty() {
+        println!();
+        println!(
+            "{:<24} {:>6} {:>6} {:>6}",
+            "GOAL", "CHECKS", "PASSED", "RATE"
+        );
+        for goal in &report.goals {
+            let rate = format!("{:.0}%", goal.pass_rate() * 100.0);
+            let rate = if goal.pass_rate() >= 0.8 {
+                rate.green()
+            } else {
+                rate.red()
+            };
+            println!(
+                "{:<24} {:>6} {:>6} {:>6} {}",
+                goal.name,
+                goal.checks,
+                goal.passed,
+                rate,
+                bar(goal.pass_rate()).dimmed()
+            );
+        }
+    }
+}
+
+fn bar(fraction: f64) -> String {
+    let filled = (fraction.clamp(0.0, 1.0) * BAR_WIDTH as f64).round() as usize;
+    "█".repeat(filled.max(1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+    use uira_agent::session::EventWrapper;
+    use uira_core::TokenUsage;
+
+    fn session(
+        provider: &str,
+        agent: Option<&str>,
+        items: Vec<SessionItem>,
+    ) -> (SessionMetaLine, Vec<SessionItem>) {
+        let mut meta = SessionMetaLine::new(
+            "t",
+            "claude-sonnet-4",
+            provider,
+            PathBuf::from("/"),
+            "read-only",
+        );
+        meta.agent = agent.map(str::to_string);
+        (meta, items)
+    }
+
+    fn turn(turn: usize, input_tokens: u64, output_tokens: u64) -> SessionItem {
+        SessionItem::TurnContext {
+            turn,
+            usage: TokenUsage {
+                input_tokens,
+                output_tokens,
+                ..TokenUsage::default()
+            },
+        }
+    }
+
+    fn call(id: &str, name: &str, is_error: bool) -> [SessionItem; 2] {
+        [
+            SessionItem::ToolCall {
+                id: id.to_string(),
+                name: name.to_string(),
+                input: serde_json::Value::Null,
+            },
+            SessionItem::ToolResult {
+                id: id.to_string(),
+                output: String::new(),
+                is_error,
+            },
+        ]
+    }
+
+    fn goal(name: &str, passed: bool) -> SessionItem {
+        SessionItem::Event {
+            event: EventWrapper::from(ThreadEvent::GoalVerificationResult {
+                goal: name.to_string(),
+                score: 0.0,
+                target: 0.0,
+                passed,
+                duration_ms: 0,
+            }),
+        }
+    }
+
+    #[test]
+    fn parses_since() {
+        let now = Utc::now();
+        assert_eq!(
+            parse_since("30d", now).unwrap(),
+            Some(now - Duration::days(30))
+        );
+        assert_eq!(
+            parse_since("2w", now).unwrap(),
+            Some(now - Duration::weeks(2))
+        );
+        assert_eq!(parse_since("all", now).unwrap(), None);
+        assert_eq!(
+            parse_since("2026-01-31", now)
+                .unwrap()
+                .unwrap()
+                .to_rfc3339(),
+            "2026-01-31T00:00:00+00:00"
+        );
+        assert!(parse_since("30x", now).is_err());
+        assert!(parse_since("d", now).is_err());
+    }
+
+    #[test]
+    fn aggregates_usage_tools_and_goals() {
+        let mut first = vec![turn(1, 1000, 100), turn(2, 2000, 200), goal("tests", true)];
+        first.extend(call("a", "read_file", false));
+        first.extend(call("b", "bash", true));
+        let mut second = vec![turn(1, 500, 50), goal("tests", false)];
+        second.extend(call("c", "read_file", false));
+
+        let report = aggregate(
+            None,
+            &[
+                session("anthropic", None, first),
+                session("openai", Some("explore"), second),
+            ],
+        );
+
+        assert_eq!(report.sessions, 2);
+        assert_eq!(report.turns, 3);
+        assert_eq!(report.avg_turns_per_task, 1.5);
+        assert_eq!(report.input_tokens, 3500);
+        assert_eq!(report.providers[0].name, "anthropic");
+        assert_eq!(report.providers[0].output_tokens, 300);
+        let agents: Vec<_> = report.agents.iter().map(|a| a.name.as_str()).collect();
+        assert_eq!(agents, vec![MAIN_AGENT, "explore"]);
+        assert_eq!(report.tools[0].name, "read_file");
+        assert_eq!(report.tools[0].calls, 2);
+        assert_eq!(report.tools[1].errors, 1);
+        assert_eq!(report.goals[0].checks, 2);
+        assert_eq!(report.goals[0].pass_rate(), 0.5);
+    }
+}
@@ -0,0 +1,264 @@
+//! Isolated git worktrees for `--worktree` runs
+//!
+//! The agent works on a fresh branch checked out under the repository's git
+//! directory, so the user's working tree is untouched until they choose to
+//! merge or squash the result back.
+
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Directory under the git common dir holding agent worktrees
+const WORKTREES_DIR: &str = "uira-worktrees";
+
+/// What to do with the worktree branch once the run ends
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FinishAction {
+    /// Merge the branch into the original branch, keeping its commits
+    Merge,
+    /// Squash the branch into a single commit on the original branch
+    Squash,
+    /// Leave the worktree and branch in place for later
+    Keep,
+    /// Remove the worktree and delete the branch
+    Discard,
+}
+
+impl FinishAction {
+    pub fn parse(input: &str) -> Option<Self> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "m" | "merge" => Some(Self::Merge),
+            "s" | "squash" => Some(Self::Squash),
+            "" | "k" | "keep" => Some(Self::Keep),
+            "d" | "discard" => Some(Self::Discard),
+            _ => None,
+        }
+    }
+}
+
+/// A worktree created for one agent run
+#[derive(Debug, Clone)]
+pub struct Worktree {
+    /// Top level of the user's checkout
+    pub repo_root: PathBuf,
+    pub path: PathBuf,
+    pub branch: String,
+    /// Branch checked out in the user's checkout when the run started
+    pub original_branch: String,
+    /// Commit the worktree branch started from
+    pub base_commit: String,
+}
+
+impl Worktree {
+    /// Create a worktree on a new `uira/<timestamp>` branch from the HEAD of
+    /// the checkout containing `cwd`
+    pub fn create(cwd: &Path) -> io::Result<Self> {
+        let repo_root = PathBuf::from(git(cwd, &["rev-parse", "--show-toplevel"])?);
+        let original_branch = git(&repo_root, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+        if original_branch == "HEAD" {
+            return Err(io::Error::other(
+                "HEAD is detached; check out a branch before using --worktree",
+            ));
+        }
+        let base_commit = git(&repo_root, &["rev-parse", "HEAD"])?;
+
+        let common_dir = PathBuf::from(git(
+            &repo_root,
+            &["rev-parse", "--path-format=absolute", "--git-common-dir"],
+        )?);
+        let name = format!(
+            "{}-{}",
+            chrono::Utc::now().format("%Y%m%d-%H%M%S"),
+            std::process::id()
+        );
+        let branch = format!("uira/{}", name);
+        let path = common_dir.join(WORKTREES_DIR).join(&name);
+
+        git(
+            &repo_root,
+            &[
+                "worktree",
+                "add",
+                "-b",
+                &branch,
+                &path.to_string_lossy(),
+                &base_commit,
+            ],
+        )?;
+
+        Ok(Self {
+            repo_root,
+            path,
+            branch,
+            original_branch,
+            base_commit,
+        })
+    }
+
+    /// Where `cwd` lands inside the worktree, keeping the subdirectory the
+    /// user started from
+    pub fn map_cwd(&self, cwd: &Path) -> PathBuf {
+        match cwd.strip_prefix(&self.repo_root) {
+            Ok(relative) => self.path.join(relative),
+            Err(_) => self.path.clone(),
+        }
+    }
+
+    /// Commit whatever the agent left uncommitted; returns whether there was
+    /// anything to commit
+    pub fn commit_pending(&self, message: &str) -> io::Result<bool> {
+        git(&self.path, &["add", "-A"])?;
+        if git(&self.path, &["diff", "--cached", "--quiet"]).is_ok() {
+            return Ok(false);
+        }
+        git(&self.path, &["commit", "--no-verify", "-m", message])?;
+        Ok(true)
+    }
+
+    /// `git diff --stat` of the branch against where it started
+    pub fn diff_stat(&self) -> io::Result<String> {
+        git(&self.path, &["diff", "--stat", &self.range()])
+    }
+
+    pub fn diff(&self) -> io::Result<String> {
+        git(&self.path, &["diff", &self.range()])
+    }
+
+    pub fn has_changes(&self) -> io::Result<bool> {
+        let head = git(&self.path, &["rev-parse", "HEAD"])?;
+        Ok(head != self.base_commit)
+    }
+
+    /// Apply `action`; the worktree is removed unless the action is `Keep`
+    /// or the merge fails
+    pub fn finish(&self, action: FinishAction, squash_message: &str) -> io::Result<()> {
+        match action {
+            FinishAction::Keep => return Ok(()),
+            FinishAction::Discard => {}
+            FinishAction::Merge | FinishAction::Squash => {
+                let checked_out = git(&self.repo_root, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+                if checked_out != self.original_branch {
+                    return Err(io::Error::other(format!(
+                        "'{}' is no longer checked out in {}; merge '{}' manually",
+                        self.original_branch,
+                        self.repo_root.display(),
+                        self.branch
+                    )));
+                }
+                if action == FinishAction::Merge {
+                    git(&self.repo_root, &["merge", "--no-edit", &self.branch])?;
+                } else {
+                    git(&self.repo_root, &["merge", "--squash", &self.branch])?;
+                    git(&self.repo_root, &["commit", "-m", squash_message])?;
+                }
+            }
+        }
+
+        git(
+            &self.repo_root,
+            &[
+                "worktree",
+                "remove",
+                "--force",
+                &self.path.to_string_lossy(),
+            ],
+        )?;
+        git(&self.repo_root, &["branch", "-D", &self.branch])?;
+        Ok(())
+    }
+
+    fn range(&self) -> String {
+        format!("{}..HEAD", self.base_commit)
+    }
+}
+
+fn git(cwd: &Path, args: &[&str]) -> io::Result<String> {
+    let output = Command::new("git").args(args).current_dir(cwd).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q", "-b", "main"]).unwrap();
+        git(root, &["config", "user.email", "dev@example.com"]).unwrap();
+        git(root, &["config", "user.name", "Dev"]).unwrap();
+        std::fs::create_dir(root.join("src")).unwrap();
+        std::fs::write(root.join("src/lib.rs"), "fn a() {}\n").unwrap();
+        git(root, &["add", "-A"]).unwrap();
+        git(root, &["commit", "-q", "-m", "init"]).unwrap();
+        dir
+    }
+
+    #[test]
+    fn parses_finish_actions() {
+        assert_eq!(FinishAction::parse("m"), Some(FinishAction::Merge));
+        assert_eq!(FinishAction::parse("Squash"), Some(FinishAction::Squash));
+        assert_eq!(FinishAction::parse(""), Some(FinishAction::Keep));
+        assert_eq!(FinishAction::parse("d\n"), Some(FinishAction::Discard));
+        assert_eq!(FinishAction::parse("x"), None);
+    }
+
+    #[test]
+    fn squash_back_into_original_branch() {
+        let repo = init_repo();
+        let root = repo.path().canonicalize().unwrap();
+        let worktree = Worktree::create(&root.join("src")).unwrap();
+        assert_eq!(worktree.original_branch, "main");
+        assert!(worktree.branch.starts_with("uira/"));
+        assert_eq!(
+            worktree.map_cwd(&root.join("src")),
+            worktree.path.join("src")
+        );
+
+        std::fs::write(worktree.path.join("src/lib.rs"), "fn b() {}\n").unwrap();
+        // The user's checkout is untouched until the branch is merged
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            "fn a() {}\n"
+        );
+
+        assert!(worktree.commit_pending("agent changes").unwrap());
+        assert!(worktree.has_changes().unwrap());
+        assert!(worktree.diff_stat().unwrap().contains("src/lib.rs"));
+        assert!(worktree.diff().unwrap().contains("+fn b() {}"));
+
+        worktree
+            .finish(FinishAction::Squash, "Apply agent changes")
+            .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join("src/lib.rs")).unwrap(),
+            "fn b() {}\n"
+        );
+        assert_eq!(
+            git(&root, &["log", "-1", "--format=%s"]).unwrap(),
+            "Apply agent changes"
+        );
+        assert!(!worktree.path.exists());
+    }
+
+    #[test]
+    fn discard_removes_worktree_and_branch() {
+        let repo = init_repo();
+        let worktree = Worktree::create(repo.path()).unwrap();
+        assert!(!worktree.commit_pending("nothing").unwrap());
+        assert!(!worktree.has_changes().unwrap());
+
+        worktree.finish(FinishAction::Discard, "").unwrap();
+        assert!(!worktree.path.exists());
+        assert!(git(repo.path(), &["rev-parse", "--verify", &worktree.branch]).is_err());
+    }
+}
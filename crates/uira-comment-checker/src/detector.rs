@@ -57,7 +57,11 @@ impl CommentDetector {
                 let line_number = node.start_position().row + 1;
                 let node_type = node.kind();
 
-                let comment_type = self.determine_comment_type(&text, node_type);
+                let comment_type = if query.capture_names()[capture.index as usize] == "docstring" {
+                    CommentType::Docstring
+                } else {
+                    self.determine_comment_type(&text, node_type)
+                };
                 let is_docstring = comment_type == CommentType::Docstring;
 
                 if is_docstring && !include_docstrings {
@@ -94,7 +98,10 @@ impl CommentDetector {
             return CommentType::Line;
         }
 
-        if stripped.starts_with("/*") || stripped.starts_with("<!--") || stripped.starts_with("--")
+        if stripped.starts_with("/*")
+            || stripped.starts_with("<!--")
+            || stripped.starts_with("--")
+            || stripped.starts_with("=begin")
         {
             return CommentType::Block;
         }
@@ -127,6 +134,60 @@ x = 1  # inline comment
             .any(|c| c.text.contains("This is a comment")));
     }
 
+    #[test]
+    fn test_detect_python_docstrings() {
+        let detector = CommentDetector::new();
+        let code = r#"#!/usr/bin/env python3
+"""Module docstring."""
+
+class Greeter:
+    """Class docstring."""
+
+    def greet(self):
+        '''Method docstring.'''
+        message = """not a docstring"""
+        return message  # trailing
+"#;
+        let comments = detector.detect(code, "test.py", true);
+        let docstrings: Vec<_> = comments
+            .iter()
+            .filter(|c| c.comment_type == CommentType::Docstring)
+            .map(|c| c.line_number)
+            .collect();
+        assert_eq!(docstrings, vec![2, 5, 8]);
+        assert!(comments
+            .iter()
+            .any(|c| c.text == "# trailing" && c.comment_type == CommentType::Line));
+
+        let without = detector.detect(code, "test.py", false);
+        assert!(without.iter().all(|c| !c.is_docstring));
+        assert_eq!(without.len(), 2);
+    }
+
+    #[test]
+    fn test_detect_go_ruby_shell_comments() {
+        let detector = CommentDetector::new();
+
+        let go = detector.detect(
+            "package main\n\n//go:generate stringer\n/* block */\nfunc main() {}\n",
+            "main.go",
+            false,
+        );
+        assert_eq!(go.len(), 2);
+        assert_eq!(go[1].comment_type, CommentType::Block);
+
+        let ruby = detector.detect(
+            "# frozen_string_literal: true\n=begin\nnotes\n=end\nputs 1\n",
+            "app.rb",
+            false,
+        );
+        assert_eq!(ruby.len(), 2);
+        assert_eq!(ruby[1].comment_type, CommentType::Block);
+
+        let shell = detector.detect("#!/bin/sh\n# say hi\necho hi\n", "run.zsh", false);
+        assert!(shell.iter().any(|c| c.text == "# say hi"));
+    }
+
     #[test]
     fn test_detect_rust_comments() {
         let detector = CommentDetector::new();
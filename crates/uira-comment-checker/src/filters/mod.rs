@@ -1,11 +1,13 @@
 mod agent_memo;
 mod bdd;
 mod directive;
+mod pragma;
 mod shebang;
 
 pub use agent_memo::AgentMemoFilter;
 pub use bdd::BddFilter;
 pub use directive::DirectiveFilter;
+pub use pragma::PragmaFilter;
 pub use shebang::ShebangFilter;
 
 use crate::models::CommentInfo;
@@ -24,6 +26,7 @@ impl FilterChain {
             filters: vec![
                 Box::new(BddFilter),
                 Box::new(DirectiveFilter),
+                Box::new(PragmaFilter::new()),
                 Box::new(ShebangFilter),
             ],
         }
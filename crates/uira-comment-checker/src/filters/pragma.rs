@@ -0,0 +1,91 @@
+use super::CommentFilter;
+use crate::languages::LanguageRegistry;
+use crate::models::CommentInfo;
+
+const PYTHON_PRAGMAS: &[&str] = &[
+    "pragma:", "fmt:", "isort:", "nosec", "-*-", "coding:", "coding=", "vim:",
+];
+
+const GO_PRAGMAS: &[&str] = &["go:", "+build", "nolint", "lint:ignore"];
+
+const RUBY_PRAGMAS: &[&str] = &[
+    "frozen_string_literal:",
+    "encoding:",
+    "coding:",
+    "rubocop:",
+    "typed:",
+    "warn_indent:",
+    "shareable_constant_value:",
+    "-*-",
+];
+
+const SHELL_PRAGMAS: &[&str] = &["shellcheck ", "shfmt:"];
+
+/// Skips tool and compiler pragmas that only mean something in one language,
+/// e.g. `//go:generate` in Go or `# frozen_string_literal: true` in Ruby.
+pub struct PragmaFilter {
+    registry: LanguageRegistry,
+}
+
+impl PragmaFilter {
+    pub fn new() -> Self {
+        Self {
+            registry: LanguageRegistry::new(),
+        }
+    }
+}
+
+impl Default for PragmaFilter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl CommentFilter for PragmaFilter {
+    fn should_skip(&self, comment: &CommentInfo) -> bool {
+        let pragmas = match self.registry.get_language_name(&comment.file_path) {
+            Some("python") => PYTHON_PRAGMAS,
+            Some("go") => GO_PRAGMAS,
+            Some("ruby") => RUBY_PRAGMAS,
+            Some("bash") => SHELL_PRAGMAS,
+            _ => return false,
+        };
+
+        let mut normalized = comment.text.trim().to_lowercase();
+        for prefix in &["#", "//", "/*"] {
+            if let Some(rest) = normalized.strip_prefix(prefix) {
+                normalized = rest.trim_start().to_string();
+                break;
+            }
+        }
+
+        pragmas.iter().any(|p| normalized.starts_with(p))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::CommentType;
+
+    fn comment(text: &str, file: &str) -> CommentInfo {
+        CommentInfo::new(text.to_string(), 1, file.to_string(), CommentType::Line)
+    }
+
+    #[test]
+    fn test_language_pragmas() {
+        let filter = PragmaFilter::new();
+
+        assert!(filter.should_skip(&comment("# pragma: no cover", "app.py")));
+        assert!(filter.should_skip(&comment("# -*- coding: utf-8 -*-", "app.py")));
+        assert!(filter.should_skip(&comment("//go:generate stringer -type=Kind", "kind.go")));
+        assert!(filter.should_skip(&comment("//nolint:errcheck", "main.go")));
+        assert!(filter.should_skip(&comment("# frozen_string_literal: true", "app.rb")));
+        assert!(filter.should_skip(&comment("# rubocop:disable Metrics", "Gemfile")));
+        assert!(filter.should_skip(&comment("# shellcheck disable=SC2086", "run.sh")));
+
+        assert!(!filter.should_skip(&comment("# Retry until the server is up", "run.sh")));
+        assert!(!filter.should_skip(&comment("// go: fetch the next page", "main.ts")));
+        assert!(!filter.should_skip(&comment("# pragma: no cover", "app.rb")));
+    }
+}
@@ -13,6 +13,8 @@ impl LanguageRegistry {
 
         // Core languages
         extension_map.insert("py", "python");
+        extension_map.insert("pyi", "python");
+        extension_map.insert("pyw", "python");
         extension_map.insert("js", "javascript");
         extension_map.insert("jsx", "javascript");
         extension_map.insert("ts", "typescript");
@@ -27,8 +29,13 @@ impl LanguageRegistry {
         extension_map.insert("hpp", "cpp");
         extension_map.insert("rs", "rust");
         extension_map.insert("rb", "ruby");
+        extension_map.insert("rake", "ruby");
+        extension_map.insert("gemspec", "ruby");
+        extension_map.insert("gemfile", "ruby");
+        extension_map.insert("rakefile", "ruby");
         extension_map.insert("sh", "bash");
         extension_map.insert("bash", "bash");
+        extension_map.insert("zsh", "bash");
 
         // Additional languages (Phase 1)
         extension_map.insert("cs", "csharp");
@@ -110,11 +117,17 @@ impl Default for LanguageRegistry {
 fn get_query_pattern(lang_name: &str) -> &'static str {
     match lang_name {
         "rust" => "(line_comment) @comment (block_comment) @comment",
-        "python" => "(comment) @comment",
+        // First string statement of a module, class, or function body is its docstring
+        "python" => {
+            r#"(comment) @comment
+            (module . (comment)* . (expression_statement (string) @docstring))
+            (class_definition body: (block . (expression_statement (string) @docstring)))
+            (function_definition body: (block . (expression_statement (string) @docstring)))"#
+        }
         "javascript" | "typescript" | "tsx" => "(comment) @comment",
         "go" => "(comment) @comment",
         "c" | "cpp" => "(comment) @comment",
-        "java" => "(comment) @comment (block_comment) @comment (line_comment) @comment",
+        "java" => "(line_comment) @comment (block_comment) @comment",
         "ruby" => "(comment) @comment",
         "bash" => "(comment) @comment",
         "csharp" => "(comment) @comment",
@@ -146,6 +159,10 @@ mod tests {
         assert_eq!(registry.get_language_name("test.rs"), Some("rust"));
         assert_eq!(registry.get_language_name("test.cs"), Some("csharp"));
         assert_eq!(registry.get_language_name("test.php"), Some("php"));
+        assert_eq!(registry.get_language_name("stubs.pyi"), Some("python"));
+        assert_eq!(registry.get_language_name("Gemfile"), Some("ruby"));
+        assert_eq!(registry.get_language_name("tasks.rake"), Some("ruby"));
+        assert_eq!(registry.get_language_name("init.zsh"), Some("bash"));
         assert_eq!(registry.get_language_name("test.unknown"), None);
     }
 
@@ -167,4 +184,13 @@ mod tests {
         assert!(langs.contains(&"csharp"));
         assert!(langs.contains(&"php"));
     }
+
+    #[test]
+    fn test_comment_queries_compile() {
+        let registry = LanguageRegistry::new();
+        for name in registry.supported_languages() {
+            let language = registry.get_language(name).unwrap();
+            assert!(get_comment_query(name, language).is_some(), "{}", name);
+        }
+    }
 }
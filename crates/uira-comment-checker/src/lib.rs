@@ -6,7 +6,8 @@ mod output;
 
 pub use detector::CommentDetector;
 pub use filters::{
-    AgentMemoFilter, BddFilter, CommentFilter, DirectiveFilter, FilterChain, ShebangFilter,
+    AgentMemoFilter, BddFilter, CommentFilter, DirectiveFilter, FilterChain, PragmaFilter,
+    ShebangFilter,
 };
 pub use languages::LanguageRegistry;
 pub use models::{CommentInfo, CommentType};
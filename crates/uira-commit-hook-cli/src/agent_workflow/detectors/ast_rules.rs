@@ -0,0 +1,165 @@
+use std::path::{Path, PathBuf};
+
+use uira_orchestration::tools::ast_grep::{RuleFinding, RulePack};
+
+use super::{Detector, Issue, RenderBudget, Scope};
+
+/// A rule finding with the file it was found in
+pub struct AstRuleFinding {
+    pub path: PathBuf,
+    pub relative_path: String,
+    pub finding: RuleFinding,
+}
+
+/// Runs the project's ast-grep rules from `.uira/rules`
+pub struct AstRulesDetector {
+    pack: RulePack,
+}
+
+impl AstRulesDetector {
+    pub fn new(working_dir: &Path) -> anyhow::Result<Self> {
+        Ok(Self {
+            pack: RulePack::load(working_dir)?,
+        })
+    }
+
+    pub fn has_rules(&self) -> bool {
+        !self.pack.is_empty()
+    }
+
+    /// Findings on in-scope lines, in path order
+    pub fn scan(&self, scope: &Scope) -> Vec<AstRuleFinding> {
+        let mut results = Vec::new();
+
+        for path in &scope.paths {
+            let relative = path.strip_prefix(&scope.working_dir).unwrap_or(path);
+            if !self.pack.rules.iter().any(|rule| rule.applies_to(relative)) {
+                continue;
+            }
+            let Ok(content) = std::fs::read_to_string(path) else {
+                continue;
+            };
+
+            for finding in self.pack.scan(relative, &content) {
+                if !scope.includes(path, finding.line) {
+                    continue;
+                }
+                results.push(AstRuleFinding {
+                    path: path.clone(),
+                    relative_path: relative.to_string_lossy().into_owned(),
+                    finding,
+                });
+            }
+        }
+
+        results
+    }
+}
+
+impl Detector for AstRulesDetector {
+    fn name(&self) -> &'static str {
+        "ast-rules"
+    }
+
+    fn detect(&self, scope: &Scope) -> anyhow::Result<Vec<Issue>> {
+        Ok(self
+            .scan(scope)
+            .into_iter()
+            .map(|result| {
+                let finding = result.finding;
+                let byte_offset = finding.fix.as_ref().map_or(0, |fix| fix.start);
+                Issue {
+                    id: format!(
+                        "ast-{}-{}-{}",
+                        finding.rule_id, result.relative_path, finding.line
+                    ),
+                    path: result.path,
+                    line: finding.line,
+                    col: finding.column,
+                    byte_offset,
+                    message: format!(
+                        "[{}] {}: {}",
+                        finding.severity, finding.rule_id, finding.message
+                    ),
+                    suggestions: finding.fix.map(|fix| fix.replacement).into_iter().collect(),
+                    context: finding.text.lines().next().map(String::from),
+                }
+            })
+            .collect())
+    }
+
+    fn render_prompt(&self, issues: &[Issue], budget: &RenderBudget) -> String {
+        if issues.is_empty() {
+            return String::from("No rule violations found.");
+        }
+
+        let mut output = format!("Found {} rule violation(s):\n\n", issues.len());
+        for issue in issues.iter().take(budget.max_issues) {
+            output.push_str(&format!(
+                "{}:{}:{} {}\n",
+                issue.path.display(),
+                issue.line,
+                issue.col,
+                issue.message
+            ));
+            if budget.include_context {
+                if let Some(ref ctx) = issue.context {
+                    output.push_str(&format!("    > {}\n", ctx.trim()));
+                }
+            }
+            if let Some(fix) = issue.suggestions.first() {
+                output.push_str(&format!("    suggested fix: {}\n", fix));
+            }
+        }
+
+        if issues.len() > budget.max_issues {
+            output.push_str(&format!(
+                "... and {} more violation(s) not shown.\n",
+                issues.len() - budget.max_issues
+            ));
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_respects_scope() {
+        let dir = tempfile::tempdir().unwrap();
+        let rules_dir = dir.path().join(".uira/rules");
+        std::fs::create_dir_all(&rules_dir).unwrap();
+        std::fs::write(
+            rules_dir.join("rust.yml"),
+            "id: no-dbg\nlanguage: rust\nseverity: error\nmessage: Remove dbg!\nrule:\n  pattern: dbg!($$$)\n",
+        )
+        .unwrap();
+        let file = dir.path().join("lib.rs");
+        std::fs::write(&file, "fn a() {\n    dbg!(1);\n}\n").unwrap();
+
+        let detector = AstRulesDetector::new(dir.path()).unwrap();
+        assert!(detector.has_rules());
+
+        let scope = Scope::from_files(
+            dir.path().to_path_buf(),
+            vec![file.to_string_lossy().into_owned()],
+        );
+        let issues = detector.detect(&scope).unwrap();
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 2);
+        assert_eq!(issues[0].message, "[error] no-dbg: Remove dbg!");
+
+        let prompt = detector.render_prompt(
+            &issues,
+            &RenderBudget {
+                max_issues: 10,
+                include_context: true,
+            },
+        );
+        assert!(prompt.contains("1 rule violation(s)"));
+        assert!(prompt.contains("> dbg!(1)"));
+    }
+}
@@ -0,0 +1,173 @@
+//! Changed line ranges from `git diff --cached`
+//!
+//! Lets `--cached` runs report only issues on lines the commit touches, so
+//! pre-existing problems in large files don't block unrelated commits.
+
+use std::collections::HashMap;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Added or modified lines per staged file, keyed by repo-relative path
+#[derive(Debug, Clone, Default)]
+pub struct ChangedLines {
+    root: PathBuf,
+    files: HashMap<PathBuf, Vec<RangeInclusive<usize>>>,
+}
+
+impl ChangedLines {
+    /// Collect the staged hunks of the repository containing `working_dir`
+    pub fn from_staged(working_dir: &Path) -> anyhow::Result<Self> {
+        let root = Command::new("git")
+            .args(["rev-parse", "--show-toplevel"])
+            .current_dir(working_dir)
+            .output()?;
+        if !root.status.success() {
+            anyhow::bail!("git rev-parse failed");
+        }
+        let root = PathBuf::from(String::from_utf8(root.stdout)?.trim());
+
+        let output = Command::new("git")
+            .args([
+                "diff",
+                "--cached",
+                "--unified=0",
+                "--no-color",
+                "--no-ext-diff",
+                "--src-prefix=a/",
+                "--dst-prefix=b/",
+                "--diff-filter=ACMR",
+            ])
+            .current_dir(&root)
+            .output()?;
+        if !output.status.success() {
+            anyhow::bail!("git diff failed");
+        }
+
+        Ok(Self::parse(root, &String::from_utf8_lossy(&output.stdout)))
+    }
+
+    /// Parse a zero-context unified diff
+    fn parse(root: PathBuf, diff: &str) -> Self {
+        let mut files: HashMap<PathBuf, Vec<RangeInclusive<usize>>> = HashMap::new();
+        let mut current: Option<PathBuf> = None;
+
+        for line in diff.lines() {
+            if let Some(path) = line.strip_prefix("+++ ") {
+                let path = path.trim_matches('"');
+                current = path.strip_prefix("b/").map(PathBuf::from);
+                if let Some(path) = &current {
+                    files.entry(path.clone()).or_default();
+                }
+            } else if let Some(header) = line.strip_prefix("@@ ") {
+                let (Some(path), Some(range)) = (&current, parse_hunk_header(header)) else {
+                    continue;
+                };
+                files.entry(path.clone()).or_default().push(range);
+            }
+        }
+
+        Self { root, files }
+    }
+
+    /// Whether `line` (1-based) of `path` was added or modified
+    ///
+    /// `path` may be absolute or relative to the repository root.
+    pub fn contains(&self, path: &Path, line: usize) -> bool {
+        let path = path.strip_prefix(&self.root).unwrap_or(path);
+        let path = path.strip_prefix("./").unwrap_or(path);
+        self.files
+            .get(path)
+            .is_some_and(|ranges| ranges.iter().any(|r| r.contains(&line)))
+    }
+
+    /// Compact `path: 3-7, 12` listing for prompts
+    pub fn describe(&self) -> String {
+        let mut files: Vec<_> = self
+            .files
+            .iter()
+            .filter(|(_, ranges)| !ranges.is_empty())
+            .collect();
+        files.sort_by(|a, b| a.0.cmp(b.0));
+
+        files
+            .into_iter()
+            .map(|(path, ranges)| {
+                let ranges = ranges
+                    .iter()
+                    .map(|r| {
+                        if r.start() == r.end() {
+                            r.start().to_string()
+                        } else {
+                            format!("{}-{}", r.start(), r.end())
+                        }
+                    })
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                format!("{}: {}", path.display(), ranges)
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+}
+
+/// New-side line range from `-a,b +c,d @@ ...`; `None` for pure deletions
+fn parse_hunk_header(header: &str) -> Option<RangeInclusive<usize>> {
+    let new_side = header.split_whitespace().find(|s| s.starts_with('+'))?;
+    let mut parts = new_side[1..].splitn(2, ',');
+    let start: usize = parts.next()?.parse().ok()?;
+    let count: usize = match parts.next() {
+        Some(count) => count.parse().ok()?,
+        None => 1,
+    };
+    (count > 0).then(|| start..=start + count - 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIFF: &str = "\
+diff --git a/src/lib.rs b/src/lib.rs
+index 1111111..2222222 100644
+--- a/src/lib.rs
++++ b/src/lib.rs
+@@ -3,0 +4,2 @@ fn main() {
++    let a = 1;
++    let b = 2;
+@@ -10 +12 @@
+-old
++new
+@@ -20,3 +23,0 @@
+-gone
+-gone
+-gone
+diff --git a/new.txt b/new.txt
+new file mode 100644
+--- /dev/null
++++ b/new.txt
+@@ -0,0 +1,3 @@
++one
++two
++three
+";
+
+    #[test]
+    fn test_parse_hunks() {
+        let changed = ChangedLines::parse(PathBuf::from("/repo"), DIFF);
+
+        assert!(!changed.contains(Path::new("src/lib.rs"), 3));
+        assert!(changed.contains(Path::new("src/lib.rs"), 4));
+        assert!(changed.contains(Path::new("src/lib.rs"), 5));
+        assert!(changed.contains(Path::new("/repo/src/lib.rs"), 12));
+        assert!(!changed.contains(Path::new("src/lib.rs"), 23));
+        assert!(changed.contains(Path::new("./new.txt"), 3));
+        assert!(!changed.contains(Path::new("other.rs"), 1));
+    }
+
+    #[test]
+    fn test_describe() {
+        let changed = ChangedLines::parse(PathBuf::from("/repo"), DIFF);
+        assert_eq!(changed.describe(), "new.txt: 1-3\nsrc/lib.rs: 4-5, 12");
+    }
+}
@@ -1,9 +1,13 @@
+pub mod ast_rules;
+pub mod hunks;
+pub mod secrets;
 pub mod typos;
 pub mod typos_config;
 
+pub use hunks::ChangedLines;
 pub use typos_config::TyposConfig;
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Budget for rendering prompts (controls token usage)
 pub struct RenderBudget {
@@ -29,15 +33,21 @@ pub struct Issue {
 pub struct Scope {
     pub working_dir: PathBuf,
     pub paths: Vec<PathBuf>,
+    /// Staged hunks; when set, only issues on these lines are reported
+    pub changed_lines: Option<ChangedLines>,
 }
 
 impl Scope {
     pub fn from_files(working_dir: PathBuf, files: Vec<String>) -> Self {
         let paths = files.into_iter().map(PathBuf::from).collect();
-        Self { working_dir, paths }
+        Self {
+            working_dir,
+            paths,
+            changed_lines: None,
+        }
     }
 
-    pub fn from_staged(working_dir: &std::path::Path) -> anyhow::Result<Self> {
+    pub fn from_staged(working_dir: &Path) -> anyhow::Result<Self> {
         let output = std::process::Command::new("git")
             .arg("diff")
             .arg("--cached")
@@ -58,10 +68,11 @@ impl Scope {
         Ok(Self {
             working_dir: working_dir.to_path_buf(),
             paths,
+            changed_lines: Some(ChangedLines::from_staged(working_dir)?),
         })
     }
 
-    pub fn from_repo(working_dir: &std::path::Path) -> anyhow::Result<Self> {
+    pub fn from_repo(working_dir: &Path) -> anyhow::Result<Self> {
         let output = std::process::Command::new("git")
             .arg("ls-files")
             .current_dir(working_dir)
@@ -79,8 +90,21 @@ impl Scope {
         Ok(Self {
             working_dir: working_dir.to_path_buf(),
             paths,
+            changed_lines: None,
         })
     }
+
+    /// Whether the scope is the index rather than the working tree
+    pub fn is_staged(&self) -> bool {
+        self.changed_lines.is_some()
+    }
+
+    /// Whether an issue at `line` of `path` should be reported
+    pub fn includes(&self, path: &Path, line: usize) -> bool {
+        self.changed_lines
+            .as_ref()
+            .is_none_or(|changed| changed.contains(path, line))
+    }
 }
 
 /// Trait for detecting issues in code
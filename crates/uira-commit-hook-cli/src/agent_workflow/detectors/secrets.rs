@@ -0,0 +1,621 @@
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+use super::{Detector, Issue, RenderBudget, Scope};
+use crate::hooks::{blob_contents, blob_sizes};
+
+/// Accepted findings, relative to the working directory.
+pub const BASELINE_FILE: &str = ".uira/secrets-baseline.json";
+
+/// Inline marker that accepts every finding on its line.
+pub const ALLOW_PRAGMA: &str = "uira-allow-secret";
+
+/// Minimum Shannon entropy (bits per char) for values caught by the generic rule.
+const GENERIC_MIN_ENTROPY: f64 = 3.5;
+
+/// Files larger than this are skipped (generated bundles, fixtures).
+const MAX_FILE_SIZE: u64 = 1024 * 1024;
+
+/// Lock files carry high-entropy integrity hashes, never credentials.
+const SKIPPED_FILES: &[&str] = &[
+    "Cargo.lock",
+    "package-lock.json",
+    "yarn.lock",
+    "pnpm-lock.yaml",
+    "bun.lock",
+    "bun.lockb",
+    "poetry.lock",
+    "go.sum",
+];
+
+/// Values the generic rule treats as placeholders rather than credentials.
+const PLACEHOLDER_MARKERS: &[&str] = &[
+    "example",
+    "xxxx",
+    "changeme",
+    "placeholder",
+    "your_",
+    "your-",
+    "dummy",
+    "redacted",
+    "${",
+    "{{",
+    "<",
+];
+
+struct SecretRule {
+    id: &'static str,
+    description: &'static str,
+    /// Capture group 1, when present, is the secret; otherwise the whole match.
+    pattern: Regex,
+    /// Minimum entropy of the secret, for rules that also match ordinary strings.
+    min_entropy: Option<f64>,
+}
+
+impl SecretRule {
+    fn new(id: &'static str, description: &'static str, pattern: &str) -> Self {
+        Self {
+            id,
+            description,
+            pattern: Regex::new(pattern).unwrap(),
+            min_entropy: None,
+        }
+    }
+}
+
+/// Provider-specific rules first so a key is reported under its most precise name.
+static RULES: LazyLock<Vec<SecretRule>> = LazyLock::new(|| {
+    vec![
+        SecretRule::new(
+            "private-key",
+            "Private key",
+            r"-----BEGIN (?:RSA |EC |DSA |OPENSSH |PGP |ENCRYPTED )?PRIVATE KEY(?: BLOCK)?-----",
+        ),
+        SecretRule::new(
+            "aws-access-key-id",
+            "AWS access key ID",
+            r"\b((?:AKIA|ASIA)[0-9A-Z]{16})\b",
+        ),
+        SecretRule::new(
+            "github-token",
+            "GitHub token",
+            r"\b(gh[pousr]_[A-Za-z0-9]{36,}|github_pat_[A-Za-z0-9_]{60,})\b",
+        ),
+        SecretRule::new(
+            "anthropic-api-key",
+            "Anthropic API key",
+            r"\b(sk-ant-[A-Za-z0-9_-]{32,})",
+        ),
+        SecretRule::new(
+            "openai-api-key",
+            "OpenAI API key",
+            r"\b(sk-(?:proj-|svcacct-|admin-)?[A-Za-z0-9_-]{20,}T3BlbkFJ[A-Za-z0-9_-]{20,}|sk-(?:proj|svcacct|admin)-[A-Za-z0-9_-]{40,})",
+        ),
+        SecretRule::new(
+            "slack-token",
+            "Slack token",
+            r"\b(xox[baprs]-[A-Za-z0-9-]{10,})",
+        ),
+        SecretRule::new(
+            "stripe-secret-key",
+            "Stripe secret key",
+            r"\b([sr]k_live_[A-Za-z0-9]{24,})\b",
+        ),
+        SecretRule::new(
+            "google-api-key",
+            "Google API key",
+            r"\b(AIza[0-9A-Za-z_-]{35})",
+        ),
+        SecretRule {
+            min_entropy: Some(GENERIC_MIN_ENTROPY),
+            ..SecretRule::new(
+                "generic-secret",
+                "High-entropy value assigned to a secret-like name",
+                r#"(?i)[\w.-]*(?:secret|token|passwd|password|api[_-]?key|access[_-]?key|auth[_-]?key|private[_-]?key|client[_-]?secret)[\w.-]*["']?\s*(?::=|=>|[:=])\s*["'`]([^"'`\s]{16,})["'`]"#,
+            )
+        },
+    ]
+});
+
+/// A credential found in a file.
+#[derive(Debug, Clone)]
+pub struct SecretFinding {
+    pub fingerprint: String,
+    pub rule: &'static str,
+    pub description: &'static str,
+    pub path: PathBuf,
+    /// Path relative to the working directory, as stored in the baseline
+    pub relative_path: String,
+    pub line: usize,
+    pub col: usize,
+    pub byte_offset: usize,
+    /// Redacted form of the secret, safe to print or send to a model
+    pub masked: String,
+    /// Source line with the secret redacted
+    pub context: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct BaselineEntry {
+    fingerprint: String,
+    path: String,
+    rule: String,
+}
+
+/// Findings accepted as non-secrets (test fixtures, public keys, false positives).
+///
+/// Entries are keyed by a fingerprint of path, rule, and secret value, so they
+/// survive unrelated edits but not a change to the value itself.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SecretsBaseline {
+    #[serde(default)]
+    findings: Vec<BaselineEntry>,
+}
+
+impl SecretsBaseline {
+    /// Load the baseline from `working_dir`, or an empty one if missing or invalid.
+    pub fn load(working_dir: &Path) -> Self {
+        std::fs::read_to_string(working_dir.join(BASELINE_FILE))
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    /// Accept `findings` in addition to the existing entries.
+    pub fn accept(&mut self, findings: &[SecretFinding]) {
+        self.findings.extend(findings.iter().map(|f| BaselineEntry {
+            fingerprint: f.fingerprint.clone(),
+            path: f.relative_path.clone(),
+            rule: f.rule.to_string(),
+        }));
+        self.findings
+            .sort_by(|a, b| (&a.path, &a.fingerprint).cmp(&(&b.path, &b.fingerprint)));
+        self.findings
+            .dedup_by(|a, b| a.fingerprint == b.fingerprint);
+    }
+
+    pub fn save(&self, working_dir: &Path) -> anyhow::Result<PathBuf> {
+        let path = working_dir.join(BASELINE_FILE);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, serde_json::to_string_pretty(self)? + "\n")?;
+        Ok(path)
+    }
+
+    pub fn contains(&self, fingerprint: &str) -> bool {
+        self.findings.iter().any(|e| e.fingerprint == fingerprint)
+    }
+}
+
+/// Regex and entropy based credential scanner.
+pub struct SecretsDetector {
+    baseline: SecretsBaseline,
+}
+
+impl SecretsDetector {
+    pub fn new(working_dir: &Path) -> Self {
+        Self {
+            baseline: SecretsBaseline::load(working_dir),
+        }
+    }
+
+    /// Scan `scope`, including findings already accepted in the baseline.
+    ///
+    /// A staged scope is read from the index, so a credential that was staged
+    /// and then edited out of the working tree is still found.
+    pub fn scan(&self, scope: &Scope) -> anyhow::Result<Vec<SecretFinding>> {
+        let files: Vec<(&PathBuf, String)> = scope
+            .paths
+            .iter()
+            .map(|path| (path, relative_path(path, &scope.working_dir)))
+            .filter(|(_, relative)| !is_skipped(relative))
+            .collect();
+
+        let contents = if scope.is_staged() {
+            staged_contents(&scope.working_dir, &files)?
+        } else {
+            files
+                .iter()
+                .map(|(path, _)| working_tree_content(path))
+                .collect()
+        };
+
+        let mut findings = Vec::new();
+        for ((path, relative), content) in files.iter().zip(contents) {
+            // Non-UTF-8 files are binaries; nothing to scan
+            if let Some(content) = content {
+                findings.extend(scan_content(&content, path, relative));
+            }
+        }
+        Ok(findings)
+    }
+
+    /// Scan `scope`, dropping findings accepted in the baseline.
+    pub fn scan_new(&self, scope: &Scope) -> anyhow::Result<Vec<SecretFinding>> {
+        Ok(self
+            .scan(scope)?
+            .into_iter()
+            .filter(|f| !self.baseline.contains(&f.fingerprint))
+            .collect())
+    }
+}
+
+/// A working-tree file's text, or `None` when it is too large or not UTF-8
+fn working_tree_content(path: &Path) -> Option<String> {
+    let metadata = std::fs::metadata(path).ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_FILE_SIZE {
+        return None;
+    }
+    std::fs::read_to_string(path).ok()
+}
+
+/// The staged text of each file, with the same skips as the working tree
+fn staged_contents(
+    working_dir: &Path,
+    files: &[(&PathBuf, String)],
+) -> anyhow::Result<Vec<Option<String>>> {
+    let paths: Vec<&str> = files
+        .iter()
+        .map(|(_, relative)| relative.as_str())
+        .collect();
+    let sizes = blob_sizes(working_dir, &paths)?;
+    let to_read: Vec<&str> = paths
+        .iter()
+        .zip(&sizes)
+        .filter(|(_, size)| **size <= MAX_FILE_SIZE)
+        .map(|(path, _)| *path)
+        .collect();
+    let mut blobs = blob_contents(working_dir, &to_read)?.into_iter();
+
+    Ok(sizes
+        .iter()
+        .map(|size| {
+            let blob = if *size <= MAX_FILE_SIZE {
+                blobs.next()
+            } else {
+                None
+            };
+            blob.and_then(|blob| String::from_utf8(blob).ok())
+        })
+        .collect())
+}
+
+fn scan_content(content: &str, path: &Path, relative: &str) -> Vec<SecretFinding> {
+    let mut findings = Vec::new();
+    let mut line_start = 0;
+
+    for (index, line) in content.split('\n').enumerate() {
+        let offset = line_start;
+        line_start += line.len() + 1;
+
+        if line.contains(ALLOW_PRAGMA) {
+            continue;
+        }
+
+        let mut claimed: Vec<(usize, usize)> = Vec::new();
+        for rule in RULES.iter() {
+            for captures in rule.pattern.captures_iter(line) {
+                let Some(secret) = captures.get(1).or_else(|| captures.get(0)) else {
+                    continue;
+                };
+                let (start, end) = (secret.start(), secret.end());
+                if claimed.iter().any(|&(s, e)| start < e && s < end) {
+                    continue;
+                }
+
+                let value = secret.as_str();
+                if let Some(min) = rule.min_entropy {
+                    let lower = value.to_lowercase();
+                    if shannon_entropy(value) < min
+                        || looks_like_path(value)
+                        || PLACEHOLDER_MARKERS.iter().any(|m| lower.contains(m))
+                    {
+                        continue;
+                    }
+                }
+                claimed.push((start, end));
+
+                let masked = mask(value);
+                findings.push(SecretFinding {
+                    fingerprint: fingerprint(relative, rule.id, value),
+                    rule: rule.id,
+                    description: rule.description,
+                    path: path.to_path_buf(),
+                    relative_path: relative.to_string(),
+                    line: index + 1,
+                    col: line[..start].chars().count() + 1,
+                    byte_offset: offset + start,
+                    context: format!("{}{}{}", &line[..start], masked, &line[end..]),
+                    masked,
+                });
+            }
+        }
+    }
+
+    findings
+}
+
+fn relative_path(path: &Path, working_dir: &Path) -> String {
+    let relative = path.strip_prefix(working_dir).unwrap_or(path);
+    let relative = relative.strip_prefix(".").unwrap_or(relative);
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+fn is_skipped(relative: &str) -> bool {
+    if relative == BASELINE_FILE {
+        return true;
+    }
+    let file_name = relative.rsplit('/').next().unwrap_or(relative);
+    SKIPPED_FILES.contains(&file_name)
+}
+
+/// `token_file: "~/.config/token"` names where a secret lives, not the secret.
+fn looks_like_path(value: &str) -> bool {
+    ["/", "~/", "./", "../"]
+        .iter()
+        .any(|prefix| value.starts_with(prefix))
+}
+
+/// Shannon entropy in bits per character.
+fn shannon_entropy(value: &str) -> f64 {
+    let mut counts = std::collections::HashMap::new();
+    let mut total = 0usize;
+    for c in value.chars() {
+        *counts.entry(c).or_insert(0usize) += 1;
+        total += 1;
+    }
+    if total == 0 {
+        return 0.0;
+    }
+    counts
+        .values()
+        .map(|&n| {
+            let p = n as f64 / total as f64;
+            -p * p.log2()
+        })
+        .sum()
+}
+
+/// Keep a short prefix so the finding is recognizable without revealing the value.
+fn mask(value: &str) -> String {
+    let prefix: String = value.chars().take(4).collect();
+    format!("{}****", prefix)
+}
+
+/// FNV-1a, stable across builds so baseline entries stay valid after upgrades.
+fn fingerprint(path: &str, rule: &str, secret: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+    for byte in path
+        .bytes()
+        .chain([0])
+        .chain(rule.bytes())
+        .chain([0])
+        .chain(secret.bytes())
+    {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+    format!("secret-{:016x}", hash)
+}
+
+impl Detector for SecretsDetector {
+    fn name(&self) -> &'static str {
+        "secrets"
+    }
+
+    fn detect(&self, scope: &Scope) -> anyhow::Result<Vec<Issue>> {
+        Ok(self
+            .scan_new(scope)?
+            .into_iter()
+            .map(|f| Issue {
+                id: f.fingerprint,
+                path: f.path,
+                line: f.line,
+                col: f.col,
+                byte_offset: f.byte_offset,
+                message: format!("{} ({}): `{}`", f.description, f.rule, f.masked),
+                suggestions: vec![],
+                context: Some(f.context),
+            })
+            .collect())
+    }
+
+    fn render_prompt(&self, issues: &[Issue], budget: &RenderBudget) -> String {
+        if issues.is_empty() {
+            return String::from("No secrets found.");
+        }
+
+        let mut output = String::new();
+        output.push_str(&format!(
+            "Found {} hard-coded secret(s). Values are redacted as `****`.\n\n",
+            issues.len()
+        ));
+
+        let mut by_file: std::collections::BTreeMap<&Path, Vec<&Issue>> =
+            std::collections::BTreeMap::new();
+        for issue in issues.iter().take(budget.max_issues) {
+            by_file.entry(issue.path.as_path()).or_default().push(issue);
+        }
+
+        for (path, file_issues) in &by_file {
+            output.push_str(&format!("## {}\n", path.display()));
+            for issue in file_issues {
+                output.push_str(&format!(
+                    "  L{}:{} {}\n",
+                    issue.line, issue.col, issue.message
+                ));
+                if budget.include_context {
+                    if let Some(ref ctx) = issue.context {
+                        output.push_str(&format!("    > {}\n", ctx.trim()));
+                    }
+                }
+            }
+            output.push('\n');
+        }
+
+        if issues.len() > budget.max_issues {
+            output.push_str(&format!(
+                "... and {} more secret(s) not shown.\n",
+                issues.len() - budget.max_issues
+            ));
+        }
+
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Built at runtime so this file never trips the scanner itself
+    fn aws_key() -> String {
+        ["AKIA", "Z3MBQ7TR4X2LPV5N"].concat()
+    }
+
+    fn scope_with(dir: &Path, files: &[(&str, &str)]) -> Scope {
+        let paths = files
+            .iter()
+            .map(|(name, content)| {
+                let path = dir.join(name);
+                std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+                std::fs::write(&path, content).unwrap();
+                path
+            })
+            .collect();
+        Scope {
+            working_dir: dir.to_path_buf(),
+            paths,
+            changed_lines: None,
+        }
+    }
+
+    #[test]
+    fn test_detects_provider_keys_and_redacts() {
+        let content = format!("const id = \"{}\";\n", aws_key());
+        let findings = scan_content(&content, Path::new("a.ts"), "a.ts");
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "aws-access-key-id");
+        assert_eq!((findings[0].line, findings[0].col), (1, 13));
+        assert_eq!(findings[0].masked, "AKIA****");
+        assert!(!findings[0].context.contains(&aws_key()));
+    }
+
+    #[test]
+    fn test_generic_rule_uses_entropy_and_placeholders() {
+        let strong = ["q8Vf", "2LmZ", "x9Rt", "K4wB", "7nYp"].concat();
+        let content = format!(
+            "api_key = \"{strong}\"\npassword = \"aaaaaaaaaaaaaaaaaaaa\"\ntoken: \"your-token-goes-here-123\"\ntoken_file: \"~/.config/app/token\"\nlabel = \"{strong}\"\n"
+        );
+        let findings = scan_content(&content, Path::new("cfg.py"), "cfg.py");
+
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].rule, "generic-secret");
+        assert_eq!(findings[0].line, 1);
+    }
+
+    #[test]
+    fn test_allow_pragma_and_lock_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = aws_key();
+        let scope = scope_with(
+            dir.path(),
+            &[
+                (
+                    "fixture.ts",
+                    &format!("const k = \"{key}\"; // {ALLOW_PRAGMA}\n"),
+                ),
+                ("Cargo.lock", &format!("checksum = \"{key}\"\n")),
+            ],
+        );
+
+        assert!(SecretsDetector::new(dir.path())
+            .scan(&scope)
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_staged_scope_reads_the_index() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            let status = std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+                .status;
+            assert!(status.success(), "git {args:?}");
+        };
+        git(&["init", "-q"]);
+        let path = dir.path().join("config.rs");
+        std::fs::write(&path, format!("let key = \"{}\";\n", aws_key())).unwrap();
+        git(&["add", "config.rs"]);
+        std::fs::write(&path, "let key = std::env::var(\"KEY\");\n").unwrap();
+
+        let scope = Scope::from_staged(dir.path()).unwrap();
+        let findings = SecretsDetector::new(dir.path()).scan(&scope).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].relative_path, "config.rs");
+    }
+
+    #[test]
+    fn test_baseline_accepts_known_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        let scope = scope_with(
+            dir.path(),
+            &[("src/config.rs", &format!("let key = \"{}\";\n", aws_key()))],
+        );
+
+        let detector = SecretsDetector::new(dir.path());
+        let findings = detector.scan(&scope).unwrap();
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].relative_path, "src/config.rs");
+
+        let mut baseline = SecretsBaseline::default();
+        baseline.accept(&findings);
+        baseline.save(dir.path()).unwrap();
+        let detector = SecretsDetector::new(dir.path());
+        assert!(detector.scan_new(&scope).unwrap().is_empty());
+        assert_eq!(detector.scan(&scope).unwrap().len(), 1);
+
+        // A different value on the same line is a new finding
+        let rotated = ["AKIA", "N5VPL2X4RT7QBM3Z"].concat();
+        std::fs::write(&scope.paths[0], format!("let key = \"{}\";\n", rotated)).unwrap();
+        assert_eq!(detector.detect(&scope).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_shannon_entropy() {
+        assert_eq!(shannon_entropy(""), 0.0);
+        assert_eq!(shannon_entropy("aaaa"), 0.0);
+        assert!((shannon_entropy("abcd") - 2.0).abs() < f64::EPSILON);
+    }
+
+    #[test]
+    fn test_render_prompt_never_contains_secret() {
+        let dir = tempfile::tempdir().unwrap();
+        let scope = scope_with(
+            dir.path(),
+            &[("main.go", &format!("var key = \"{}\"\n", aws_key()))],
+        );
+        let detector = SecretsDetector::new(dir.path());
+        let issues = detector.detect(&scope).unwrap();
+
+        let prompt = detector.render_prompt(
+            &issues,
+            &RenderBudget {
+                max_issues: 10,
+                include_context: true,
+            },
+        );
+        assert!(prompt.contains("1 hard-coded secret(s)"));
+        assert!(prompt.contains("AWS access key ID (aws-access-key-id)"));
+        assert!(!prompt.contains(&aws_key()));
+    }
+}
@@ -156,6 +156,9 @@ impl Detector for TyposDetector {
 
             for typo in typos::check_str(&content, &self.tokenizer, &self.dictionary) {
                 let (line, col) = byte_offset_to_line_col(&content, typo.byte_offset);
+                if !scope.includes(path, line) {
+                    continue;
+                }
                 let context = extract_context_line(&content, typo.byte_offset);
 
                 let suggestions: Vec<String> = match &typo.corrections {
@@ -418,6 +421,7 @@ mod tests {
         let scope = Scope {
             working_dir: dir.path().to_path_buf(),
             paths: vec![file_path],
+            changed_lines: None,
         };
 
         let detector = TyposDetector::new(dir.path());
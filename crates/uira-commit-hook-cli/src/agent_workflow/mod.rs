@@ -19,6 +19,9 @@ pub enum WorkflowTask {
     Typos,
     Diagnostics,
     Comments,
+    Secrets,
+    AstRules,
+    CommitMsg,
 }
 
 impl WorkflowTask {
@@ -27,6 +30,9 @@ impl WorkflowTask {
             Self::Typos => "typos",
             Self::Diagnostics => "diagnostics",
             Self::Comments => "comments",
+            Self::Secrets => "secrets",
+            Self::AstRules => "ast-rules",
+            Self::CommitMsg => "commit-msg",
         }
     }
 
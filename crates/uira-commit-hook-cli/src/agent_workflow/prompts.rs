@@ -102,5 +102,46 @@ Keep:
                 },
             )
         }
+
+        WorkflowTask::Secrets => r#"
+Remove the hard-coded credentials that have been pre-detected for you.
+Secret values are redacted in the findings; never print, copy, or move them elsewhere.
+
+For each finding:
+1. If it's a real credential → use Edit to replace the literal with a lookup from the
+   environment or the project's existing config mechanism (e.g. `process.env.API_KEY`,
+   `std::env::var("API_KEY")`, `os.environ["API_KEY"]`), keeping the variable name descriptive
+2. If it's a test fixture, public value, or false positive → append a `uira-allow-secret`
+   comment on the same line
+
+If a credential was real, mention in your summary that it must be rotated.
+
+When all findings have been handled, output <DONE/>.
+"#
+        .to_string(),
+
+        WorkflowTask::AstRules => r#"
+Resolve the project rule violations that have been pre-detected for you.
+Each rule comes from `.uira/rules` and encodes a team convention.
+
+For each violation:
+1. If a suggested fix is shown → apply it with Edit, adjusting only if it would not compile
+2. Otherwise → use Edit to rewrite the code so the rule's message no longer applies
+3. If the code is a deliberate, justified exception → leave it and say why in your summary
+
+Do NOT edit the rule files themselves.
+
+When all violations have been handled, output <DONE/>.
+"#
+        .to_string(),
+
+        WorkflowTask::CommitMsg => r#"
+Write a commit message for the staged changes that follows the rules you are given.
+
+Do NOT edit any files. Use Read only if the diff alone is unclear.
+Instead of <DONE/>, output the complete message (header, blank line, optional body)
+inside <DONE>...</DONE>. Nothing outside the tags becomes part of the message.
+"#
+        .to_string(),
     }
 }
@@ -6,7 +6,7 @@ use uira_core::Provider;
 use uira_providers::{ModelClientBuilder, ProviderConfig};
 
 use super::{
-    detectors::{Detector, RenderBudget, Scope},
+    detectors::{ChangedLines, Detector, RenderBudget, Scope},
     prompts::build_system_prompt,
     CompletionDetector, GitTracker, WorkflowConfig, WorkflowState, WorkflowTask,
 };
@@ -109,6 +109,7 @@ impl AgentWorkflow {
             thinking_budget: None,
             friendliai: None,
             reasoning_mode: None,
+            reasoning_effort: None,
         };
 
         let client = ModelClientBuilder::new()
@@ -354,6 +355,52 @@ impl AgentWorkflow {
         }
     }
 
+    /// Ask the agent for text wrapped in `<DONE>...</DONE>`, re-prompting with the
+    /// errors from `validate` until it passes or the iteration budget runs out
+    pub async fn generate_text<F>(&mut self, prompt: &str, validate: F) -> Result<Option<String>>
+    where
+        F: Fn(&str) -> Vec<String>,
+    {
+        let mut prompt = prompt.to_string();
+
+        let text = loop {
+            if self.state.iteration >= self.state.max_iterations {
+                break None;
+            }
+
+            let agent = self
+                .agent
+                .as_mut()
+                .expect("text generation requires eager agent creation");
+            let output = match agent.run(&prompt).await {
+                Ok(exec_result) => exec_result.output,
+                Err(AgentLoopError::Cancelled) => break None,
+                Err(e) => {
+                    let _ = WorkflowState::clear(self.task);
+                    anyhow::bail!("{}", e);
+                }
+            };
+            self.state.increment();
+
+            let Some(text) = self.completion_detector.extract_summary(&output) else {
+                prompt = "Output the complete text inside <DONE>...</DONE>.".to_string();
+                continue;
+            };
+
+            let errors = validate(&text);
+            if errors.is_empty() {
+                break Some(text);
+            }
+            prompt = format!(
+                "That is not valid yet:\n- {}\n\nFix it and output the complete text inside <DONE>...</DONE>.",
+                errors.join("\n- ")
+            );
+        };
+
+        let _ = WorkflowState::clear(self.task);
+        Ok(text)
+    }
+
     fn build_initial_prompt(&self) -> String {
         let files_context = if self.config.files.is_empty() {
             if self.config.cached_only {
@@ -365,10 +412,29 @@ impl AgentWorkflow {
             "Process the specified files."
         };
 
+        // Keep --cached runs to the lines this commit touches
+        let hunks_context = if self.config.cached_only {
+            ChangedLines::from_staged(&self.config.working_directory)
+                .map(|changed| changed.describe())
+                .ok()
+                .filter(|described| !described.is_empty())
+                .map(|described| {
+                    format!(
+                        "Only report and fix issues on these staged lines; \
+                        leave pre-existing issues elsewhere alone:\n{}\n\n",
+                        described
+                    )
+                })
+                .unwrap_or_default()
+        } else {
+            String::new()
+        };
+
         format!(
             "Begin the {task} workflow.\n\n\
             {files_context}\n\n\
             Files to process: {files}\n\n\
+            {hunks_context}\
             Remember: Output <DONE/> when all issues are fixed.",
             task = self.task.name(),
             files_context = files_context,
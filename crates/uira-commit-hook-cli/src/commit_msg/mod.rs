@@ -0,0 +1,304 @@
+// Conventional commit message linting for the commit-msg hook.
+// AI generation goes through AgentWorkflow::generate_text.
+
+use std::process::Command;
+use std::sync::LazyLock;
+
+use regex::Regex;
+use uira_core::CommitMsgSettings;
+
+use crate::agent_workflow::{AgentWorkflow, WorkflowConfig, WorkflowTask};
+
+static HEADER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"^(?P<type>[A-Za-z]+)(?:\((?P<scope>[^()]*)\))?(?P<breaking>!)?: (?P<subject>.*)$")
+        .unwrap()
+});
+
+/// Everything below this line is dropped by `git commit --cleanup=scissors`
+const SCISSORS: &str = "# ------------------------ >8 ------------------------";
+
+/// Diffs beyond this many bytes are truncated before being sent to the model
+const MAX_DIFF_BYTES: usize = 12_000;
+
+/// Headers written by git itself or by fixup workflows, exempt from the rules
+const EXEMPT_PREFIXES: &[&str] = &["Merge ", "Revert \"", "fixup! ", "squash! ", "amend! "];
+
+/// Strip comment lines and the scissors section, as `git commit` does
+pub fn clean_message(raw: &str) -> String {
+    let mut lines = Vec::new();
+    for line in raw.lines() {
+        if line == SCISSORS {
+            break;
+        }
+        if !line.starts_with('#') {
+            lines.push(line.trim_end());
+        }
+    }
+    lines.join("\n").trim().to_string()
+}
+
+/// Check a commit message against conventional commit rules
+///
+/// Returns one human-readable error per violated rule; empty means valid.
+pub fn lint_message(raw: &str, rules: &CommitMsgSettings) -> Vec<String> {
+    let message = clean_message(raw);
+    let mut lines = message.lines();
+    let Some(header) = lines.next() else {
+        return vec!["Commit message is empty".to_string()];
+    };
+
+    if EXEMPT_PREFIXES.iter().any(|p| header.starts_with(p)) {
+        return Vec::new();
+    }
+
+    let mut errors = Vec::new();
+
+    let length = header.chars().count();
+    if length > rules.max_header_length {
+        errors.push(format!(
+            "Header is {} characters; the limit is {}",
+            length, rules.max_header_length
+        ));
+    }
+
+    match HEADER.captures(header) {
+        None => errors.push(
+            "Header must look like `type(scope): subject`, e.g. `feat(cli): add commit-msg hook`"
+                .to_string(),
+        ),
+        Some(caps) => {
+            let commit_type = &caps["type"];
+            if !rules.types.iter().any(|t| t == commit_type) {
+                errors.push(format!(
+                    "Type `{}` is not one of: {}",
+                    commit_type,
+                    rules.types.join(", ")
+                ));
+            }
+
+            match caps.name("scope").map(|m| m.as_str()) {
+                Some("") => errors.push("Scope must not be empty".to_string()),
+                Some(scope)
+                    if !rules.scopes.is_empty() && !rules.scopes.iter().any(|s| s == scope) =>
+                {
+                    errors.push(format!(
+                        "Scope `{}` is not one of: {}",
+                        scope,
+                        rules.scopes.join(", ")
+                    ))
+                }
+                None if rules.require_scope => errors.push(format!(
+                    "A scope is required, e.g. `{}(core): ...`",
+                    commit_type
+                )),
+                _ => {}
+            }
+
+            let subject = &caps["subject"];
+            if subject.trim().is_empty() {
+                errors.push("Subject must not be empty".to_string());
+            } else if subject.ends_with('.') {
+                errors.push("Subject must not end with a period".to_string());
+            }
+        }
+    }
+
+    if lines.next().is_some_and(|line| !line.trim().is_empty()) {
+        errors.push("Leave a blank line between the header and the body".to_string());
+    }
+
+    errors
+}
+
+/// Generate a compliant message for the staged changes, keeping the intent of `draft`
+///
+/// Returns `None` when the agent could not produce a valid message within its budget.
+pub async fn generate_message(
+    draft: &str,
+    rules: &CommitMsgSettings,
+) -> anyhow::Result<Option<String>> {
+    let (provider, model) = rules.ai.parse_model();
+    let config = WorkflowConfig {
+        provider,
+        model,
+        max_iterations: 3,
+        cached_only: true,
+        ..Default::default()
+    };
+
+    let prompt = build_prompt(&clean_message(draft), &staged_diff()?, rules);
+    let mut workflow = AgentWorkflow::new(WorkflowTask::CommitMsg, config, None, None).await?;
+    let message = workflow
+        .generate_text(&prompt, |text| lint_message(&unfence(text), rules))
+        .await?;
+
+    Ok(message.map(|text| unfence(&text)))
+}
+
+fn staged_diff() -> anyhow::Result<String> {
+    let stat = Command::new("git")
+        .args(["diff", "--cached", "--stat"])
+        .output()?;
+    let diff = Command::new("git").args(["diff", "--cached"]).output()?;
+
+    let stat = String::from_utf8_lossy(&stat.stdout).trim().to_string();
+    if stat.is_empty() {
+        return Ok("(no staged changes)".to_string());
+    }
+
+    let mut diff = String::from_utf8_lossy(&diff.stdout).to_string();
+    if diff.len() > MAX_DIFF_BYTES {
+        let mut cut = MAX_DIFF_BYTES;
+        while !diff.is_char_boundary(cut) {
+            cut -= 1;
+        }
+        diff.truncate(cut);
+        diff.push_str("\n... (diff truncated)");
+    }
+
+    Ok(format!("{}\n\n```diff\n{}\n```", stat, diff))
+}
+
+fn build_prompt(draft: &str, changes: &str, rules: &CommitMsgSettings) -> String {
+    let scope_rule = match (rules.require_scope, rules.scopes.is_empty()) {
+        (true, true) => "required".to_string(),
+        (true, false) => format!("required, one of: {}", rules.scopes.join(", ")),
+        (false, true) => "optional".to_string(),
+        (false, false) => format!("optional, one of: {}", rules.scopes.join(", ")),
+    };
+    let draft_note = if draft.is_empty() {
+        String::new()
+    } else {
+        format!(
+            "\nThe author's draft (keep its intent):\n\n{}\n",
+            draft
+                .lines()
+                .map(|l| format!("> {}", l))
+                .collect::<Vec<_>>()
+                .join("\n")
+        )
+    };
+
+    format!(
+        r#"Write a Conventional Commits message for the staged changes.
+
+Rules:
+- Header: `type(scope): subject`, at most {max} characters
+- Types: {types}
+- Scope: {scope_rule}
+- Subject in the imperative mood, no trailing period
+- Optional body after one blank line explaining why, wrapped at 72 columns
+{draft_note}
+Staged changes:
+
+{changes}
+
+Output only the message inside <DONE>...</DONE>."#,
+        max = rules.max_header_length,
+        types = rules.types.join(", "),
+    )
+}
+
+/// Drop a surrounding code fence if the model wrapped the message in one
+fn unfence(text: &str) -> String {
+    let trimmed = text.trim();
+    trimmed
+        .strip_prefix("```")
+        .and_then(|rest| rest.strip_suffix("```"))
+        .map(|inner| {
+            inner
+                .split_once('\n')
+                .map_or(inner, |(_, body)| body)
+                .trim()
+        })
+        .unwrap_or(trimmed)
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lint(message: &str) -> Vec<String> {
+        lint_message(message, &CommitMsgSettings::default())
+    }
+
+    #[test]
+    fn test_valid_messages() {
+        assert!(lint("feat(cli): add commit-msg hook").is_empty());
+        assert!(lint("fix!: drop legacy flag\n\nBREAKING CHANGE: removed").is_empty());
+        assert!(lint("docs: fix typo\n# Please enter the commit message\n").is_empty());
+        assert!(lint("Merge branch 'main' into feature").is_empty());
+        assert!(lint("fixup! feat: add thing").is_empty());
+    }
+
+    #[test]
+    fn test_invalid_messages() {
+        assert_eq!(lint("# only comments\n"), vec!["Commit message is empty"]);
+        assert_eq!(
+            lint("update stuff"),
+            vec!["Header must look like `type(scope): subject`, e.g. `feat(cli): add commit-msg hook`"]
+        );
+        assert_eq!(
+            lint("feature: add thing"),
+            vec!["Type `feature` is not one of: feat, fix, docs, style, refactor, perf, test, build, ci, chore, revert"]
+        );
+        assert_eq!(
+            lint("feat(): add thing."),
+            vec![
+                "Scope must not be empty",
+                "Subject must not end with a period"
+            ]
+        );
+        assert_eq!(
+            lint("feat: add thing\nbody without gap"),
+            vec!["Leave a blank line between the header and the body"]
+        );
+        assert_eq!(
+            lint(&format!("feat: {}", "x".repeat(80))),
+            vec!["Header is 86 characters; the limit is 72"]
+        );
+    }
+
+    #[test]
+    fn test_scope_rules() {
+        let rules = CommitMsgSettings {
+            scopes: vec!["core".to_string(), "cli".to_string()],
+            require_scope: true,
+            ..Default::default()
+        };
+        assert!(lint_message("feat(core): add thing", &rules).is_empty());
+        assert_eq!(
+            lint_message("feat(tui): add thing", &rules),
+            vec!["Scope `tui` is not one of: core, cli"]
+        );
+        assert_eq!(
+            lint_message("feat: add thing", &rules),
+            vec!["A scope is required, e.g. `feat(core): ...`"]
+        );
+    }
+
+    #[test]
+    fn test_clean_message_stops_at_scissors() {
+        let raw = format!(
+            "feat: add thing\n\nBody\n# comment\n{}\ndiff --git a b\n",
+            SCISSORS
+        );
+        assert_eq!(clean_message(&raw), "feat: add thing\n\nBody");
+    }
+
+    #[test]
+    fn test_unfence() {
+        assert_eq!(unfence("```text\nfeat: add thing\n```"), "feat: add thing");
+        assert_eq!(unfence("  feat: add thing \n"), "feat: add thing");
+    }
+
+    #[test]
+    fn test_build_prompt_includes_rules_and_draft() {
+        let prompt = build_prompt("wip", "1 file changed", &CommitMsgSettings::default());
+        assert!(prompt.contains("at most 72 characters"));
+        assert!(prompt.contains("Scope: optional"));
+        assert!(prompt.contains("> wip"));
+        assert!(prompt.contains("1 file changed"));
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
+use uira_core::ShellKind;
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
 pub struct Config {
@@ -50,6 +51,45 @@ pub struct HookConfig {
 
     #[serde(default)]
     pub commands: Vec<Command>,
+
+    /// Built-in checks on staged files; pre-commit runs them with defaults when unset
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub checks: Option<FastChecks>,
+}
+
+/// Built-in pre-commit checks that need no external tools
+#[derive(Debug, Deserialize, Serialize, Clone)]
+#[serde(default)]
+pub struct FastChecks {
+    pub enabled: bool,
+
+    /// Reject staged files larger than this many kilobytes (0 disables)
+    pub max_file_size_kb: u64,
+
+    /// Reject `<<<<<<<` / `>>>>>>>` merge conflict markers
+    pub conflict_markers: bool,
+
+    /// Reject text files that are not valid UTF-8
+    pub non_utf8: bool,
+
+    /// Warn when a lockfile changes by more than this many lines (0 disables)
+    pub lockfile_churn_lines: usize,
+
+    /// Globs for files skipped by every check
+    pub exclude: Vec<String>,
+}
+
+impl Default for FastChecks {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_file_size_kb: 500,
+            conflict_markers: true,
+            non_utf8: true,
+            lockfile_churn_lines: 500,
+            exclude: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -68,6 +108,11 @@ pub struct Command {
     /// Behavior when command fails: "stop" (default), "warn", or "continue"
     #[serde(default = "default_on_fail")]
     pub on_fail: OnFail,
+
+    /// Shell that runs `run` (`sh`, `bash`, `cmd`, `powershell`, `pwsh`);
+    /// defaults to `UIRA_SHELL`, then `sh` on unix and `cmd` on Windows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<ShellKind>,
 }
 
 fn default_on_fail() -> OnFail {
@@ -86,13 +131,25 @@ impl Config {
 
         let pre_commit = HookConfig {
             parallel: true,
-            commands: vec![Command {
-                name: Some("lint".to_string()),
-                run: "uira-commit-hook-cli lint {staged_files}".to_string(),
-                glob: Some("**/*.{js,ts,jsx,tsx}".to_string()),
-                stage_fixed: false,
-                on_fail: default_on_fail(),
-            }],
+            commands: vec![
+                Command {
+                    name: Some("lint".to_string()),
+                    run: "uira-commit-hook-cli lint {staged_files}".to_string(),
+                    glob: Some("**/*.{js,ts,jsx,tsx}".to_string()),
+                    stage_fixed: false,
+                    on_fail: default_on_fail(),
+                    shell: None,
+                },
+                Command {
+                    name: Some("secrets".to_string()),
+                    run: "uira-commit-hook-cli secrets --cached".to_string(),
+                    glob: None,
+                    stage_fixed: false,
+                    on_fail: default_on_fail(),
+                    shell: None,
+                },
+            ],
+            checks: None,
         };
 
         let post_commit = HookConfig {
@@ -103,7 +160,9 @@ impl Config {
                 glob: None,
                 stage_fixed: false,
                 on_fail: default_on_fail(),
+                shell: None,
             }],
+            checks: None,
         };
 
         hooks.insert("pre-commit".to_string(), pre_commit);
@@ -134,7 +193,7 @@ mod tests {
 
         let pre_commit = &config.hooks["pre-commit"];
         assert!(pre_commit.parallel);
-        assert_eq!(pre_commit.commands.len(), 1);
+        assert_eq!(pre_commit.commands.len(), 2);
 
         let post_commit = &config.hooks["post-commit"];
         assert!(!post_commit.parallel);
@@ -0,0 +1,326 @@
+//! Built-in fast checks on staged files
+//!
+//! Catches common mistakes (oversized files, leftover conflict markers, non-UTF-8 text,
+//! noisy lockfile updates) before any configured command runs.
+
+use crate::config::FastChecks;
+use anyhow::{Context, Result};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::collections::HashSet;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Bytes scanned for NUL when deciding whether a blob is binary (same heuristic as git)
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+/// Lockfiles and the manifest each one is generated from
+const LOCKFILES: &[(&str, &str)] = &[
+    ("Cargo.lock", "Cargo.toml"),
+    ("package-lock.json", "package.json"),
+    ("npm-shrinkwrap.json", "package.json"),
+    ("yarn.lock", "package.json"),
+    ("pnpm-lock.yaml", "package.json"),
+    ("bun.lock", "package.json"),
+    ("bun.lockb", "package.json"),
+    ("poetry.lock", "pyproject.toml"),
+    ("uv.lock", "pyproject.toml"),
+    ("Pipfile.lock", "Pipfile"),
+    ("Gemfile.lock", "Gemfile"),
+    ("go.sum", "go.mod"),
+    ("composer.lock", "composer.json"),
+];
+
+#[derive(Debug, Default)]
+pub struct CheckReport {
+    /// Problems that block the commit
+    pub errors: Vec<String>,
+    /// Problems worth a look that don't block the commit
+    pub warnings: Vec<String>,
+}
+
+/// A staged file with its line churn (`None` for binary files)
+struct StagedFile {
+    path: String,
+    churn: Option<usize>,
+}
+
+/// Run every enabled check against the staged files
+pub fn run(checks: &FastChecks) -> Result<CheckReport> {
+    let exclude = build_exclude(&checks.exclude)?;
+    let staged: Vec<StagedFile> = staged_files()?
+        .into_iter()
+        .filter(|file| !exclude.is_match(&file.path))
+        .collect();
+
+    let mut report = CheckReport::default();
+    if staged.is_empty() {
+        return Ok(report);
+    }
+
+    let paths: Vec<&str> = staged.iter().map(|f| f.path.as_str()).collect();
+    report.warnings = lockfile_warnings(&staged, checks.lockfile_churn_lines);
+
+    let limit = checks.max_file_size_kb * 1024;
+    let mut to_read = Vec::new();
+    for (path, size) in paths.iter().zip(blob_sizes(Path::new("."), &paths)?) {
+        if limit > 0 && size > limit && lockfile_manifest(path).is_none() {
+            report.errors.push(format!(
+                "{}: {} KB exceeds the {} KB limit (use Git LFS or raise max_file_size_kb)",
+                path,
+                size.div_ceil(1024),
+                checks.max_file_size_kb
+            ));
+        } else {
+            to_read.push(*path);
+        }
+    }
+
+    if checks.conflict_markers || checks.non_utf8 {
+        for (path, content) in to_read.iter().zip(blob_contents(Path::new("."), &to_read)?) {
+            report.errors.extend(inspect_blob(path, &content, checks));
+        }
+    }
+
+    Ok(report)
+}
+
+/// Conflict-marker and encoding problems in one file's staged content
+fn inspect_blob(path: &str, content: &[u8], checks: &FastChecks) -> Vec<String> {
+    let mut errors = Vec::new();
+    if is_binary(content) {
+        return errors;
+    }
+
+    if checks.conflict_markers {
+        let marker_line = content.split(|&b| b == b'\n').position(is_conflict_marker);
+        if let Some(index) = marker_line {
+            errors.push(format!("{}:{}: merge conflict marker", path, index + 1));
+        }
+    }
+
+    if checks.non_utf8 {
+        if let Err(e) = std::str::from_utf8(content) {
+            let line = content[..e.valid_up_to()]
+                .iter()
+                .filter(|&&b| b == b'\n')
+                .count();
+            errors.push(format!("{}:{}: invalid UTF-8", path, line + 1));
+        }
+    }
+
+    errors
+}
+
+fn is_binary(content: &[u8]) -> bool {
+    content[..content.len().min(BINARY_SNIFF_BYTES)].contains(&0)
+}
+
+fn is_conflict_marker(line: &[u8]) -> bool {
+    let line = line.strip_suffix(b"\r").unwrap_or(line);
+    [b"<<<<<<<", b">>>>>>>", b"|||||||"].iter().any(|marker| {
+        line.strip_prefix(*marker)
+            .is_some_and(|rest| rest.is_empty() || rest[0] == b' ')
+    })
+}
+
+fn lockfile_manifest(path: &str) -> Option<&'static str> {
+    let name = Path::new(path).file_name()?.to_str()?;
+    LOCKFILES
+        .iter()
+        .find(|(lockfile, _)| *lockfile == name)
+        .map(|(_, manifest)| *manifest)
+}
+
+fn lockfile_warnings(staged: &[StagedFile], churn_limit: usize) -> Vec<String> {
+    let staged_names: HashSet<&str> = staged
+        .iter()
+        .filter_map(|f| Path::new(&f.path).file_name()?.to_str())
+        .collect();
+
+    let mut warnings = Vec::new();
+    for file in staged {
+        let Some(manifest) = lockfile_manifest(&file.path) else {
+            continue;
+        };
+        if !staged_names.contains(manifest) {
+            warnings.push(format!(
+                "{} changed without a staged {}; is the dependency update intentional?",
+                file.path, manifest
+            ));
+        }
+        if let Some(churn) = file.churn.filter(|&c| churn_limit > 0 && c > churn_limit) {
+            warnings.push(format!(
+                "{} changes {} lines (limit {})",
+                file.path, churn, churn_limit
+            ));
+        }
+    }
+    warnings
+}
+
+fn build_exclude(patterns: &[String]) -> Result<GlobSet> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern).with_context(|| format!("Invalid glob: {}", pattern))?);
+    }
+    Ok(builder.build()?)
+}
+
+fn staged_files() -> Result<Vec<StagedFile>> {
+    let output = Command::new("git")
+        .args([
+            "diff",
+            "--cached",
+            "--numstat",
+            "--no-renames",
+            "-z",
+            "--diff-filter=ACM",
+        ])
+        .output()
+        .context("Failed to get staged files")?;
+
+    Ok(parse_numstat(&String::from_utf8_lossy(&output.stdout)))
+}
+
+/// Parse `git diff --numstat -z` output (`added\tdeleted\tpath\0` per file)
+fn parse_numstat(output: &str) -> Vec<StagedFile> {
+    output
+        .split('\0')
+        .filter_map(|entry| {
+            let mut fields = entry.splitn(3, '\t');
+            let added = fields.next()?.parse::<usize>().ok();
+            let deleted = fields.next()?.parse::<usize>().ok();
+            let path = fields.next()?.to_string();
+            Some(StagedFile {
+                path,
+                churn: added.zip(deleted).map(|(a, d)| a + d),
+            })
+        })
+        .collect()
+}
+
+/// Sizes of the staged blobs in the repository at `dir`, in the same order as `paths`
+pub(crate) fn blob_sizes(dir: &Path, paths: &[&str]) -> Result<Vec<u64>> {
+    let output = cat_file(dir, "--batch-check=%(objectsize)", paths)?;
+    Ok(String::from_utf8_lossy(&output)
+        .lines()
+        .map(|line| line.trim().parse().unwrap_or(0))
+        .collect())
+}
+
+/// Contents of the staged blobs in the repository at `dir`, in the same order as `paths`
+pub(crate) fn blob_contents(dir: &Path, paths: &[&str]) -> Result<Vec<Vec<u8>>> {
+    let output = cat_file(dir, "--batch=%(objectsize)", paths)?;
+
+    let mut contents = Vec::with_capacity(paths.len());
+    let mut rest = output.as_slice();
+    while let Some(newline) = rest.iter().position(|&b| b == b'\n') {
+        let header = String::from_utf8_lossy(&rest[..newline]);
+        let Ok(size) = header.trim().parse::<usize>() else {
+            // "<object> missing"
+            contents.push(Vec::new());
+            rest = &rest[newline + 1..];
+            continue;
+        };
+        let body = &rest[newline + 1..];
+        let size = size.min(body.len());
+        contents.push(body[..size].to_vec());
+        rest = body.get(size + 1..).unwrap_or_default();
+    }
+    Ok(contents)
+}
+
+fn cat_file(dir: &Path, mode: &str, paths: &[&str]) -> Result<Vec<u8>> {
+    let mut child = Command::new("git")
+        .args(["cat-file", mode])
+        .current_dir(dir)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .context("Failed to run git cat-file")?;
+
+    let input: String = paths.iter().map(|path| format!(":{}\n", path)).collect();
+    let mut stdin = child
+        .stdin
+        .take()
+        .context("Failed to open git cat-file stdin")?;
+    // Write from another thread so a full stdout pipe can't deadlock us
+    let writer = std::thread::spawn(move || stdin.write_all(input.as_bytes()));
+    let output = child.wait_with_output()?;
+    writer
+        .join()
+        .map_err(|_| anyhow::anyhow!("git cat-file writer panicked"))??;
+
+    Ok(output.stdout)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_conflict_markers() {
+        let checks = FastChecks::default();
+        let content = b"fn a() {}\n<<<<<<< HEAD\nx\n=======\ny\n>>>>>>> branch\n";
+        assert_eq!(
+            inspect_blob("src/a.rs", content, &checks),
+            vec!["src/a.rs:2: merge conflict marker"]
+        );
+        assert!(inspect_blob("README.md", b"Title\n=======\n<<<<<<<<\n", &checks).is_empty());
+    }
+
+    #[test]
+    fn test_non_utf8_and_binary() {
+        let checks = FastChecks::default();
+        assert_eq!(
+            inspect_blob("a.txt", b"ok\ncaf\xe9\n", &checks),
+            vec!["a.txt:2: invalid UTF-8"]
+        );
+        assert!(inspect_blob("a.png", b"\x89PNG\0\xff<<<<<<<", &checks).is_empty());
+
+        let off = FastChecks {
+            non_utf8: false,
+            ..FastChecks::default()
+        };
+        assert!(inspect_blob("a.txt", b"caf\xe9", &off).is_empty());
+    }
+
+    #[test]
+    fn test_parse_numstat() {
+        let files = parse_numstat("3\t1\tsrc/a.rs\0-\t-\tlogo.png\0");
+        assert_eq!(files.len(), 2);
+        assert_eq!(
+            (files[0].path.as_str(), files[0].churn),
+            ("src/a.rs", Some(4))
+        );
+        assert_eq!((files[1].path.as_str(), files[1].churn), ("logo.png", None));
+    }
+
+    #[test]
+    fn test_lockfile_warnings() {
+        let staged = vec![
+            StagedFile {
+                path: "Cargo.lock".to_string(),
+                churn: Some(900),
+            },
+            StagedFile {
+                path: "web/package-lock.json".to_string(),
+                churn: Some(10),
+            },
+            StagedFile {
+                path: "web/package.json".to_string(),
+                churn: Some(1),
+            },
+        ];
+        assert_eq!(
+            lockfile_warnings(&staged, 500),
+            vec![
+                "Cargo.lock changed without a staged Cargo.toml; is the dependency update intentional?",
+                "Cargo.lock changes 900 lines (limit 500)",
+            ]
+        );
+        assert_eq!(lockfile_warnings(&staged, 0).len(), 1);
+    }
+}
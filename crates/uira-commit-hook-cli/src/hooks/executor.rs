@@ -1,17 +1,28 @@
-use crate::config::{Command, HookConfig};
-use crate::hooks::OnFail;
+use crate::config::{Command, FastChecks, HookConfig};
+use crate::hooks::{checks, OnFail};
 use anyhow::{Context, Result};
 use colored::Colorize;
 use rayon::prelude::*;
 use std::process::{Command as ProcessCommand, Stdio};
+use uira_core::{shell_command, ShellKind};
 
 pub struct HookExecutor {
     hook_name: String,
+    /// Arguments git passed to the hook (e.g. the message file for commit-msg)
+    args: Vec<String>,
 }
 
 impl HookExecutor {
     pub fn new(hook_name: String) -> Self {
-        Self { hook_name }
+        Self {
+            hook_name,
+            args: Vec::new(),
+        }
+    }
+
+    pub fn with_args(mut self, args: Vec<String>) -> Self {
+        self.args = args;
+        self
     }
 
     pub fn execute(&self, hook_config: &HookConfig) -> Result<()> {
@@ -21,6 +32,15 @@ impl HookExecutor {
             self.hook_name.bright_cyan()
         );
 
+        let checks = match &hook_config.checks {
+            Some(checks) => Some(checks.clone()),
+            None if self.hook_name == "pre-commit" => Some(FastChecks::default()),
+            None => None,
+        };
+        if let Some(checks) = checks.filter(|c| c.enabled) {
+            self.run_checks(&checks)?;
+        }
+
         if hook_config.parallel {
             self.execute_parallel(&hook_config.commands)
         } else {
@@ -48,27 +68,37 @@ impl HookExecutor {
         Ok(())
     }
 
+    fn run_checks(&self, checks: &FastChecks) -> Result<()> {
+        println!("  {} {}", "→".bright_blue(), "checks".bright_white());
+        let report = checks::run(checks)?;
+
+        for warning in &report.warnings {
+            println!("    {} {}", "⚠".bright_yellow(), warning);
+        }
+        for error in &report.errors {
+            println!("    {} {}", "✗".bright_red(), error);
+        }
+
+        if !report.errors.is_empty() {
+            anyhow::bail!("Built-in checks found {} problem(s)", report.errors.len());
+        }
+
+        println!("  {} {}", "✓".bright_green(), "checks".bright_white());
+        Ok(())
+    }
+
     fn run_command(&self, cmd: &Command) -> Result<()> {
         let name = cmd.name.as_deref().unwrap_or("unnamed");
         println!("  {} {}", "→".bright_blue(), name.bright_white());
 
-        let shell_cmd = self.expand_variables(&cmd.run);
+        let shell = cmd.shell.unwrap_or_else(ShellKind::from_env);
+        let shell_cmd = self.expand_variables(&cmd.run, shell);
 
-        let output = if cfg!(target_os = "windows") {
-            ProcessCommand::new("cmd")
-                .args(["/C", &shell_cmd])
-                .stdout(Stdio::inherit())
-                .stderr(Stdio::inherit())
-                .output()
-        } else {
-            ProcessCommand::new("sh")
-                .arg("-c")
-                .arg(&shell_cmd)
-                .stdout(Stdio::inherit())
-                .stderr(Stdio::inherit())
-                .output()
-        }
-        .with_context(|| format!("Failed to execute command: {}", cmd.run))?;
+        let output = shell_command(&shell_cmd, Some(shell))
+            .stdout(Stdio::inherit())
+            .stderr(Stdio::inherit())
+            .output()
+            .with_context(|| format!("Failed to execute command: {}", cmd.run))?;
 
         if !output.status.success() {
             let exit_code = output.status.code().unwrap_or(-1);
@@ -101,7 +131,7 @@ impl HookExecutor {
         Ok(())
     }
 
-    fn expand_variables(&self, command: &str) -> String {
+    fn expand_variables(&self, command: &str, shell: ShellKind) -> String {
         let mut expanded = command.to_string();
 
         if expanded.contains("{staged_files}") {
@@ -114,6 +144,19 @@ impl HookExecutor {
             expanded = expanded.replace("{all_files}", &all_files);
         }
 
+        // Positional hook arguments; unset ones expand to nothing
+        for index in (1..=9).rev() {
+            let placeholder = format!("{{{}}}", index);
+            if expanded.contains(&placeholder) {
+                let value = self
+                    .args
+                    .get(index - 1)
+                    .map(|arg| shell.quote(arg))
+                    .unwrap_or_default();
+                expanded = expanded.replace(&placeholder, &value);
+            }
+        }
+
         expanded
     }
 
@@ -145,11 +188,26 @@ mod tests {
         let executor = HookExecutor::new("test".to_string());
 
         let cmd = "echo {staged_files}";
-        let expanded = executor.expand_variables(cmd);
+        let expanded = executor.expand_variables(cmd, ShellKind::Sh);
 
         assert!(!expanded.contains("{staged_files}"));
     }
 
+    #[test]
+    fn test_positional_arg_expansion() {
+        let executor = HookExecutor::new("commit-msg".to_string())
+            .with_args(vec![".git/COMMIT_EDITMSG".to_string(), "it's".to_string()]);
+
+        assert_eq!(
+            executor.expand_variables("check {1} {2} {3}", ShellKind::Sh),
+            "check '.git/COMMIT_EDITMSG' 'it'\\''s' "
+        );
+        assert_eq!(
+            executor.expand_variables("check {1}", ShellKind::Cmd),
+            "check \".git/COMMIT_EDITMSG\""
+        );
+    }
+
     #[test]
     fn test_executor_creation() {
         let executor = HookExecutor::new("pre-commit".to_string());
@@ -1,5 +1,7 @@
+mod checks;
 mod executor;
 
+pub(crate) use checks::{blob_contents, blob_sizes};
 pub use executor::HookExecutor;
 
 use serde::{Deserialize, Serialize};
@@ -1,5 +1,6 @@
 mod agent_workflow;
 mod comments;
+mod commit_msg;
 mod config;
 mod diagnostics;
 mod hooks;
@@ -7,7 +8,12 @@ mod linter;
 mod runtime;
 mod typos;
 
-use agent_workflow::detectors::{typos::TyposDetector, Scope};
+use agent_workflow::detectors::{
+    ast_rules::AstRulesDetector,
+    secrets::{SecretsBaseline, SecretsDetector, ALLOW_PRAGMA},
+    typos::TyposDetector,
+    ChangedLines, Scope,
+};
 use agent_workflow::{AgentWorkflow, TaskOptions, WorkflowConfig, WorkflowResult, WorkflowTask};
 use clap::{Parser, Subcommand};
 use colored::Colorize;
@@ -43,7 +49,12 @@ enum Commands {
     /// Install git hooks to .git/hooks/
     Install,
     /// Run a specific git hook
-    Run { hook: String },
+    Run {
+        hook: String,
+        /// Arguments git passed to the hook, available to commands as {1}, {2}, ...
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        args: Vec<String>,
+    },
     /// Lint JS/TS files with native oxc
     Lint {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
@@ -70,10 +81,92 @@ enum Commands {
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         files: Vec<String>,
     },
+    /// Check a commit message against conventional commit rules from uira.yml
+    CommitMsg {
+        /// Commit message file (defaults to .git/COMMIT_EDITMSG)
+        file: Option<String>,
+        #[arg(
+            long,
+            help = "Rewrite an invalid or empty message from the staged diff with AI"
+        )]
+        ai: bool,
+    },
+    /// Scan for hard-coded secrets (API keys, tokens, private keys)
+    Secrets {
+        #[arg(
+            long,
+            help = "Use AI to remove detected secrets (stages by default, use --no-add to skip)"
+        )]
+        ai: bool,
+        #[arg(long, help = "Check only staged/cached files")]
+        cached: bool,
+        #[arg(
+            long,
+            help = "Commit after fixing with AI-generated message",
+            requires = "ai",
+            conflicts_with = "no_add"
+        )]
+        commit: bool,
+        #[arg(long, help = "Fix only, do not stage modified files")]
+        no_add: bool,
+        #[arg(
+            long,
+            help = "Accept all current findings in .uira/secrets-baseline.json",
+            conflicts_with = "ai"
+        )]
+        update_baseline: bool,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        files: Vec<String>,
+    },
+    /// Run the project's ast-grep rules from .uira/rules
+    AstLint {
+        #[arg(
+            long,
+            help = "Use AI to resolve rule violations (stages by default, use --no-add to skip)"
+        )]
+        ai: bool,
+        #[arg(long, help = "Check only staged/cached files")]
+        cached: bool,
+        #[arg(
+            long,
+            help = "Commit after fixing with AI-generated message",
+            requires = "ai",
+            conflicts_with = "no_add"
+        )]
+        commit: bool,
+        #[arg(long, help = "Fix only, do not stage modified files")]
+        no_add: bool,
+        #[arg(
+            long,
+            help = "Apply the rules' fix templates in place before reporting",
+            conflicts_with = "ai"
+        )]
+        fix: bool,
+        #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
+        files: Vec<String>,
+    },
     /// Format code (Rust via rustfmt/cargo fmt, JS/TS via oxfmt)
+    ///
+    /// With --native, JS/TS is instead reprinted one top-level statement at a
+    /// time by the built-in OXC code generator. It is not Prettier-compatible:
+    /// there is no line width, and statements containing comments are left
+    /// untouched.
     Format {
         #[arg(long, help = "Check formatting without applying changes")]
         check: bool,
+        #[arg(
+            long,
+            help = "Format JS/TS with the built-in OXC formatter instead of oxfmt"
+        )]
+        native: bool,
+        #[arg(
+            long,
+            value_name = "PATH",
+            help = "Format JS/TS read from stdin as PATH and write the result to stdout",
+            conflicts_with = "files",
+            requires = "native"
+        )]
+        stdin_filepath: Option<String>,
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         files: Vec<String>,
     },
@@ -121,6 +214,12 @@ enum Commands {
             help = "Severity filter: error, warning, all (default from config or 'error')"
         )]
         severity: Option<String>,
+        #[arg(
+            long,
+            help = "Sort JS/TS imports in place before reporting",
+            conflicts_with = "ai"
+        )]
+        fix: bool,
         #[arg(trailing_var_arg = true, allow_hyphen_values = true)]
         files: Vec<String>,
     },
@@ -212,7 +311,7 @@ fn main() {
     let result = match cli.command {
         Commands::Init { config } => init_command(&config),
         Commands::Install => install_command(),
-        Commands::Run { hook } => run_command(&hook),
+        Commands::Run { hook, args } => run_command(&hook, &args),
         Commands::Lint { files } => lint_command(&files),
         Commands::Typos {
             ai,
@@ -221,7 +320,32 @@ fn main() {
             no_add,
             files,
         } => typos_command(ai, cached, commit, no_add, &files),
-        Commands::Format { check, files } => format_command(check, &files),
+        Commands::CommitMsg { file, ai } => commit_msg_command(file.as_deref(), ai),
+        Commands::Secrets {
+            ai,
+            cached,
+            commit,
+            no_add,
+            update_baseline,
+            files,
+        } => secrets_command(ai, cached, commit, no_add, update_baseline, &files),
+        Commands::AstLint {
+            ai,
+            cached,
+            commit,
+            no_add,
+            fix,
+            files,
+        } => ast_lint_command(ai, cached, commit, no_add, fix, &files),
+        Commands::Format {
+            check,
+            native,
+            stdin_filepath,
+            files,
+        } => match stdin_filepath {
+            Some(path) => format_stdin_command(check, &path),
+            None => format_command(check, native, &files),
+        },
         Commands::Agent { action } => agent_command(action),
         Commands::Session { action } => session_command(action),
         Commands::Skill { action } => skill_command(action),
@@ -232,8 +356,9 @@ fn main() {
             commit,
             no_add,
             severity,
+            fix,
             files,
-        } => diagnostics_command(ai, cached, commit, no_add, severity.as_deref(), &files),
+        } => diagnostics_command(ai, cached, commit, no_add, severity.as_deref(), fix, &files),
         Commands::Comments {
             ai,
             cached,
@@ -356,30 +481,38 @@ fn find_git_dir() -> anyhow::Result<std::path::PathBuf> {
     }
 }
 
+/// Git runs hooks through `sh` on every platform (Git for Windows bundles
+/// one), so the script stays POSIX and only the binary name differs
 fn generate_hook_script(hook_name: &str) -> String {
-    format!(
+    let mut script = String::from(
         r#"#!/bin/sh
 # uira-commit-hook-cli managed hook - do not edit
 # This hook was generated by uira-commit-hook-cli. To modify, edit uira.yml
 
 REPO_ROOT="$(git rev-parse --show-toplevel 2>/dev/null || pwd)"
 cd "$REPO_ROOT" || exit 1
-
-if [ -x "./target/release/uira-commit-hook-cli" ]; then
-    exec ./target/release/uira-commit-hook-cli run {}
-fi
-
-if [ -x "./target/debug/uira-commit-hook-cli" ]; then
-    exec ./target/debug/uira-commit-hook-cli run {}
+"#,
+    );
+
+    for profile in ["release", "debug"] {
+        for binary in ["uira-commit-hook-cli", "uira-commit-hook-cli.exe"] {
+            script.push_str(&format!(
+                r#"
+if [ -x "./target/{profile}/{binary}" ]; then
+    exec ./target/{profile}/{binary} run {hook_name} "$@"
 fi
+"#
+            ));
+        }
+    }
 
-exec cargo run -q -p uira-commit-hook-cli -- run {}
-"#,
-        hook_name, hook_name, hook_name
-    )
+    script.push_str(&format!(
+        "\nexec cargo run -q -p uira-commit-hook-cli -- run {hook_name} \"$@\"\n"
+    ));
+    script
 }
 
-fn run_command(hook_name: &str) -> anyhow::Result<()> {
+fn run_command(hook_name: &str, args: &[String]) -> anyhow::Result<()> {
     let config_path = "uira.yml";
 
     if !Path::new(config_path).exists() {
@@ -396,7 +529,7 @@ fn run_command(hook_name: &str) -> anyhow::Result<()> {
         .get(hook_name)
         .ok_or_else(|| anyhow::anyhow!("Hook '{}' not found in config", hook_name))?;
 
-    let executor = HookExecutor::new(hook_name.to_string());
+    let executor = HookExecutor::new(hook_name.to_string()).with_args(args.to_vec());
     executor.execute(hook_config)?;
 
     println!("\n✅ Hook '{}' completed successfully", hook_name);
@@ -504,6 +637,7 @@ fn typos_command(
     } else {
         println!("🔍 Checking for typos...\n");
         let mut cmd = std::process::Command::new("typos");
+        let mut changed_lines = None;
 
         if !files.is_empty() {
             cmd.args(files);
@@ -522,22 +656,370 @@ fn typos_command(
                 return Ok(());
             }
             cmd.args(&staged_files);
+            changed_lines = Some(ChangedLines::from_staged(&std::env::current_dir()?)?);
         } else {
             cmd.arg(".");
         }
 
-        let status = cmd.status().map_err(|_| {
+        let not_installed = |_| {
             anyhow::anyhow!("Failed to run typos. Is it installed? Run: cargo install typos-cli")
-        })?;
-        if !status.success() {
-            process::exit(1);
+        };
+        if let Some(changed) = changed_lines {
+            let output = cmd
+                .args(["--format", "brief"])
+                .output()
+                .map_err(not_installed)?;
+            let reported: Vec<String> = String::from_utf8_lossy(&output.stdout)
+                .lines()
+                .filter(|line| in_changed_lines(&changed, line).unwrap_or(true))
+                .map(String::from)
+                .collect();
+            if !reported.is_empty() {
+                for line in &reported {
+                    println!("{}", line);
+                }
+                process::exit(1);
+            }
+        } else {
+            let status = cmd.status().map_err(not_installed)?;
+            if !status.success() {
+                process::exit(1);
+            }
         }
         println!("✓ No typos found");
         Ok(())
     }
 }
 
-fn format_command(check: bool, files: &[String]) -> anyhow::Result<()> {
+fn commit_msg_command(file: Option<&str>, ai: bool) -> anyhow::Result<()> {
+    let path = match file {
+        Some(file) => file.to_string(),
+        None => {
+            let output = std::process::Command::new("git")
+                .args(["rev-parse", "--git-path", "COMMIT_EDITMSG"])
+                .output()
+                .map_err(|_| anyhow::anyhow!("Failed to locate the git directory"))?;
+            String::from_utf8_lossy(&output.stdout).trim().to_string()
+        }
+    };
+    let raw = fs::read_to_string(&path)
+        .map_err(|e| anyhow::anyhow!("Failed to read commit message {}: {}", path, e))?;
+    let rules = uira_core::load_config(None)
+        .map(|config| config.commit_msg)
+        .unwrap_or_default();
+
+    let errors = commit_msg::lint_message(&raw, &rules);
+    if errors.is_empty() {
+        println!("✓ Commit message follows conventional commits");
+        return Ok(());
+    }
+
+    if !ai {
+        eprintln!("❌ Commit message does not follow conventional commits:\n");
+        for error in &errors {
+            eprintln!("  {} {}", "✗".red(), error);
+        }
+        eprintln!("\n   Example: feat(cli): add commit-msg hook");
+        eprintln!("   Run with --ai to generate a compliant message from the staged diff");
+        process::exit(1);
+    }
+
+    println!("🤖 Writing a conventional commit message...\n");
+    match block_on(commit_msg::generate_message(&raw, &rules))? {
+        Some(message) => {
+            fs::write(&path, format!("{}\n", message))?;
+            println!("✓ Commit message rewritten:\n");
+            for line in message.lines() {
+                println!("   {}", line);
+            }
+            Ok(())
+        }
+        None => {
+            eprintln!("❌ Could not generate a compliant commit message:\n");
+            for error in &errors {
+                eprintln!("  {} {}", "✗".red(), error);
+            }
+            process::exit(1);
+        }
+    }
+}
+
+fn secrets_command(
+    ai: bool,
+    cached: bool,
+    commit: bool,
+    no_add: bool,
+    update_baseline: bool,
+    files: &[String],
+) -> anyhow::Result<()> {
+    let working_dir = std::env::current_dir()?;
+    let scope = if !files.is_empty() {
+        Scope::from_files(working_dir.clone(), files.to_vec())
+    } else if cached {
+        Scope::from_staged(&working_dir)?
+    } else {
+        Scope::from_repo(&working_dir)?
+    };
+    let detector = SecretsDetector::new(&working_dir);
+
+    if update_baseline {
+        let findings = detector.scan_new(&scope)?;
+        let mut baseline = SecretsBaseline::load(&working_dir);
+        baseline.accept(&findings);
+        let path = baseline.save(&working_dir)?;
+        println!(
+            "✓ Accepted {} new finding(s) in {}",
+            findings.len(),
+            path.display()
+        );
+        return Ok(());
+    }
+
+    if !ai {
+        println!("🔐 Scanning for secrets...\n");
+        let findings = detector.scan_new(&scope)?;
+        if findings.is_empty() {
+            println!("✓ No secrets found");
+            return Ok(());
+        }
+
+        for finding in &findings {
+            println!(
+                "  {} {}:{}:{} {} ({}) `{}`",
+                "✗".red(),
+                finding.relative_path,
+                finding.line,
+                finding.col,
+                finding.description,
+                finding.rule,
+                finding.masked
+            );
+        }
+        eprintln!("\n❌ {} secret(s) found", findings.len());
+        eprintln!("   Move them to environment variables, mark false positives with a");
+        eprintln!(
+            "   `{}` comment, or accept them with 'uira-commit-hook-cli secrets --update-baseline'",
+            ALLOW_PRAGMA
+        );
+        process::exit(1);
+    }
+
+    println!("🔐 Starting AI-assisted secrets workflow...\n");
+
+    let config = WorkflowConfig {
+        auto_stage: !no_add,
+        auto_commit: commit,
+        cached_only: cached,
+        files: files.to_vec(),
+        ..Default::default()
+    };
+
+    block_on(async {
+        let mut workflow = AgentWorkflow::new(
+            WorkflowTask::Secrets,
+            config,
+            Some(Box::new(detector)),
+            Some(scope),
+        )
+        .await?;
+        match workflow.run().await? {
+            WorkflowResult::Complete {
+                iterations,
+                files_modified,
+                summary,
+            } => {
+                println!("\n✅ Secrets workflow complete!");
+                println!("   Iterations: {}", iterations);
+                println!("   Files modified: {}", files_modified.len());
+                if let Some(s) = summary {
+                    println!("   Summary: {}", s);
+                }
+                Ok(())
+            }
+            WorkflowResult::MaxIterationsReached {
+                iterations,
+                files_modified,
+            } => {
+                println!("\n⚠️  Max iterations ({}) reached", iterations);
+                println!("   Files modified: {}", files_modified.len());
+                std::process::exit(1);
+            }
+            WorkflowResult::VerificationFailed {
+                remaining_issues,
+                details,
+            } => {
+                println!(
+                    "\n❌ Verification failed: {} secrets remain",
+                    remaining_issues
+                );
+                println!("   Details: {}", details);
+                std::process::exit(1);
+            }
+            WorkflowResult::Cancelled => {
+                println!("\n⚠️  Workflow cancelled");
+                std::process::exit(1);
+            }
+            WorkflowResult::Failed { error } => {
+                eprintln!("\n❌ Workflow failed: {}", error);
+                std::process::exit(1);
+            }
+        }
+    })
+}
+
+fn ast_lint_command(
+    ai: bool,
+    cached: bool,
+    commit: bool,
+    no_add: bool,
+    fix: bool,
+    files: &[String],
+) -> anyhow::Result<()> {
+    use uira_orchestration::tools::ast_grep::{apply_fixes, RuleSeverity};
+
+    let working_dir = std::env::current_dir()?;
+    let scope = if !files.is_empty() {
+        Scope::from_files(working_dir.clone(), files.to_vec())
+    } else if cached {
+        Scope::from_staged(&working_dir)?
+    } else {
+        Scope::from_repo(&working_dir)?
+    };
+    let detector = AstRulesDetector::new(&working_dir)?;
+
+    if !detector.has_rules() {
+        println!("✓ No rules in .uira/rules");
+        return Ok(());
+    }
+
+    if !ai {
+        println!("🌳 Running ast-grep rules...\n");
+
+        if fix {
+            let mut by_file: std::collections::BTreeMap<_, Vec<_>> =
+                std::collections::BTreeMap::new();
+            for result in detector.scan(&scope) {
+                by_file.entry(result.path).or_default().push(result.finding);
+            }
+            for (path, findings) in by_file {
+                let content = fs::read_to_string(&path)?;
+                let (fixed, applied) = apply_fixes(&content, &findings);
+                if applied > 0 {
+                    fs::write(&path, fixed)?;
+                    println!(
+                        "{} Fixed {} violation(s) in {}",
+                        "✓".green(),
+                        applied,
+                        path.display()
+                    );
+                }
+            }
+        }
+
+        let findings = detector.scan(&scope);
+        if findings.is_empty() {
+            println!("✓ No rule violations found");
+            return Ok(());
+        }
+
+        let mut errors = 0;
+        for result in &findings {
+            let finding = &result.finding;
+            let marker = if finding.severity == RuleSeverity::Error {
+                errors += 1;
+                "✗".red()
+            } else {
+                "⚠".yellow()
+            };
+            println!(
+                "  {} {}:{}:{} {} ({})",
+                marker,
+                result.relative_path,
+                finding.line,
+                finding.column,
+                finding.message,
+                finding.rule_id
+            );
+            if let Some(note) = &finding.note {
+                println!("      {}", note.trim());
+            }
+        }
+
+        if errors > 0 {
+            eprintln!("\n❌ {} rule violation(s) with severity error", errors);
+            if findings.iter().any(|r| r.finding.fix.is_some()) {
+                eprintln!("   Some rules have fixes: 'uira-commit-hook-cli ast-lint --fix'");
+            }
+            process::exit(1);
+        }
+        println!("\n⚠ {} rule violation(s), none blocking", findings.len());
+        return Ok(());
+    }
+
+    println!("🌳 Starting AI-assisted ast-grep rules workflow...\n");
+
+    let config = WorkflowConfig {
+        auto_stage: !no_add,
+        auto_commit: commit,
+        cached_only: cached,
+        files: files.to_vec(),
+        ..Default::default()
+    };
+
+    block_on(async {
+        let mut workflow = AgentWorkflow::new(
+            WorkflowTask::AstRules,
+            config,
+            Some(Box::new(detector)),
+            Some(scope),
+        )
+        .await?;
+        match workflow.run().await? {
+            WorkflowResult::Complete {
+                iterations,
+                files_modified,
+                summary,
+            } => {
+                println!("\n✅ ast-grep rules workflow complete!");
+                println!("   Iterations: {}", iterations);
+                println!("   Files modified: {}", files_modified.len());
+                if let Some(s) = summary {
+                    println!("   Summary: {}", s);
+                }
+                Ok(())
+            }
+            WorkflowResult::MaxIterationsReached {
+                iterations,
+                files_modified,
+            } => {
+                println!("\n⚠️  Max iterations ({}) reached", iterations);
+                println!("   Files modified: {}", files_modified.len());
+                std::process::exit(1);
+            }
+            WorkflowResult::VerificationFailed {
+                remaining_issues,
+                details,
+            } => {
+                println!(
+                    "\n❌ Verification failed: {} violations remain",
+                    remaining_issues
+                );
+                println!("   Details: {}", details);
+                std::process::exit(1);
+            }
+            WorkflowResult::Cancelled => {
+                println!("\n⚠️  Workflow cancelled");
+                std::process::exit(1);
+            }
+            WorkflowResult::Failed { error } => {
+                eprintln!("\n❌ Workflow failed: {}", error);
+                std::process::exit(1);
+            }
+        }
+    })
+}
+
+fn format_command(check: bool, native: bool, files: &[String]) -> anyhow::Result<()> {
     if check {
         println!("🔍 Checking formatting...\n");
     } else {
@@ -549,7 +1031,8 @@ fn format_command(check: bool, files: &[String]) -> anyhow::Result<()> {
     let (rust_files, non_rust): (Vec<&String>, Vec<&String>) =
         files.iter().partition(|p| p.ends_with(".rs"));
 
-    if files.is_empty() || !rust_files.is_empty() {
+    // `cargo fmt` needs a manifest; JS/TS-only projects skip the Rust pass
+    if (files.is_empty() && Path::new("Cargo.toml").is_file()) || !rust_files.is_empty() {
         let mut cmd = if rust_files.is_empty() {
             let mut cmd = std::process::Command::new("cargo");
             cmd.arg("fmt");
@@ -579,52 +1062,153 @@ fn format_command(check: bool, files: &[String]) -> anyhow::Result<()> {
         }
     }
 
-    // Filter non_rust to only include JS/TS files or directories for oxfmt
-    let js_ts_extensions = [".js", ".jsx", ".ts", ".tsx", ".mjs", ".cjs", ".mts", ".cts"];
+    if native {
+        ran_any |= format_js_ts_native(check, files, &non_rust)?;
+    } else {
+        ran_any |= format_js_ts_oxfmt(check, files, &non_rust)?;
+    }
+
+    if !ran_any {
+        println!("ℹ️  No files to format");
+        return Ok(());
+    }
+
+    if check {
+        println!("✓ All files are properly formatted");
+    } else {
+        println!("✓ Formatting complete");
+    }
+
+    Ok(())
+}
+
+/// Run oxfmt on the JS/TS files and directories in `non_rust`, or on the
+/// whole project when no files were given; returns whether it ran
+fn format_js_ts_oxfmt(check: bool, files: &[String], non_rust: &[&String]) -> anyhow::Result<bool> {
     let js_ts_files: Vec<&String> = non_rust
-        .into_iter()
-        .filter(|p| {
-            // Allow directories (oxfmt can handle them)
-            std::path::Path::new(p).is_dir() || js_ts_extensions.iter().any(|ext| p.ends_with(ext))
-        })
+        .iter()
+        .copied()
+        .filter(|p| Path::new(p).is_dir() || uira_oxc::Formatter::supports(Path::new(p)))
         .collect();
+    if !files.is_empty() && js_ts_files.is_empty() {
+        return Ok(false);
+    }
+
+    let mut cmd = std::process::Command::new("oxfmt");
+    if check {
+        cmd.arg("--check");
+    }
+    if files.is_empty() {
+        cmd.arg(".");
+    } else {
+        cmd.args(js_ts_files);
+    }
+
+    let status = cmd.status().map_err(|_| {
+        anyhow::anyhow!(
+            "Failed to run oxfmt. Install: npm add -D oxfmt (or pnpm/yarn/bun), or pass --native"
+        )
+    })?;
 
-    if files.is_empty() || !js_ts_files.is_empty() {
-        let mut cmd = std::process::Command::new("oxfmt");
+    if !status.success() {
         if check {
-            cmd.arg("--check");
+            eprintln!("\n❌ JS/TS files are not formatted correctly");
+            eprintln!("   Run 'uira-commit-hook-cli format' to fix formatting");
         }
+        process::exit(1);
+    }
+    Ok(true)
+}
 
-        if files.is_empty() {
-            cmd.arg(".");
-        } else {
-            cmd.args(js_ts_files);
+/// Format JS/TS with the built-in OXC formatter; returns whether any file was
+/// found
+fn format_js_ts_native(
+    check: bool,
+    files: &[String],
+    non_rust: &[&String],
+) -> anyhow::Result<bool> {
+    let mut js_ts_files = Vec::new();
+    if files.is_empty() {
+        js_ts_files = collect_files_from_cwd()?;
+    } else {
+        for path in non_rust {
+            if Path::new(path).is_dir() {
+                collect_files_recursive(Path::new(path), &mut js_ts_files, &is_js_ts_file)?;
+            } else if uira_oxc::Formatter::supports(Path::new(path)) {
+                js_ts_files.push((*path).clone());
+            }
         }
+    }
+    if js_ts_files.is_empty() {
+        return Ok(false);
+    }
 
-        let status = cmd.status().map_err(|_| {
-            anyhow::anyhow!("Failed to run oxfmt. Install: npm add -D oxfmt (or pnpm/yarn/bun)")
-        })?;
+    let formatter = uira_oxc::Formatter::new(uira_oxc::FormatOptions::discover(Path::new(".")));
+    let mut unformatted = 0;
+    let mut failed = 0;
 
-        ran_any = true;
+    for file in &js_ts_files {
+        let path = Path::new(file);
+        let result = if check {
+            formatter.check_file(path).map(|diff| {
+                if let Some(diff) = &diff {
+                    print!("{}", diff);
+                }
+                diff.is_some()
+            })
+        } else {
+            formatter.format_file(path)
+        };
 
-        if !status.success() {
-            if check {
-                eprintln!("\n❌ JS/TS files are not formatted correctly");
-                eprintln!("   Run 'uira-commit-hook-cli format' to fix formatting");
+        match result {
+            Ok(true) => {
+                unformatted += 1;
+                if !check {
+                    println!("  {} {}", "✓".green(), file);
+                }
+            }
+            Ok(false) => {}
+            Err(e) => {
+                failed += 1;
+                eprintln!("  {} {}", "✗".red(), e);
             }
-            process::exit(1);
         }
     }
 
-    if !ran_any {
-        println!("ℹ️  No files to format");
-        return Ok(());
+    if check && unformatted > 0 {
+        eprintln!(
+            "\n❌ {} JS/TS file(s) are not formatted correctly",
+            unformatted
+        );
+        eprintln!("   Run 'uira-commit-hook-cli format --native' to fix formatting");
+        process::exit(1);
     }
+    if failed > 0 {
+        eprintln!("\n❌ Failed to format {} JS/TS file(s)", failed);
+        process::exit(1);
+    }
+    Ok(true)
+}
 
+/// Format stdin for editor integration; output goes to stdout with no banners
+fn format_stdin_command(check: bool, filepath: &str) -> anyhow::Result<()> {
+    let mut source = String::new();
+    std::io::Read::read_to_string(&mut std::io::stdin(), &mut source)?;
+
+    let formatter = uira_oxc::Formatter::new(uira_oxc::FormatOptions::discover(Path::new(".")));
     if check {
-        println!("✓ All files are properly formatted");
+        if let Some(diff) = formatter
+            .check_source(filepath, &source)
+            .map_err(|e| anyhow::anyhow!(e))?
+        {
+            print!("{}", diff);
+            process::exit(1);
+        }
     } else {
-        println!("✓ Formatting complete");
+        let formatted = formatter
+            .format_source(filepath, &source)
+            .map_err(|e| anyhow::anyhow!(e))?;
+        print!("{}", formatted);
     }
 
     Ok(())
@@ -632,18 +1216,41 @@ fn format_command(check: bool, files: &[String]) -> anyhow::Result<()> {
 
 fn collect_files_from_cwd() -> anyhow::Result<Vec<String>> {
     let mut files = Vec::new();
-    collect_files_recursive(Path::new("."), &mut files)?;
+    collect_files_recursive(Path::new("."), &mut files, &is_js_ts_file)?;
     Ok(files)
 }
 
-fn collect_files_recursive(dir: &Path, files: &mut Vec<String>) -> anyhow::Result<()> {
+/// Every file the comment checker has a grammar for, not just JS/TS
+fn collect_comment_files_from_cwd() -> anyhow::Result<Vec<String>> {
+    let registry = uira_comment_checker::LanguageRegistry::new();
+    let mut files = Vec::new();
+    collect_files_recursive(Path::new("."), &mut files, &|path| {
+        registry.is_supported(&path.to_string_lossy())
+    })?;
+    Ok(files)
+}
+
+fn is_js_ts_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "mts" | "cts")
+    )
+}
+
+fn collect_files_recursive(
+    dir: &Path,
+    files: &mut Vec<String>,
+    accept: &dyn Fn(&Path) -> bool,
+) -> anyhow::Result<()> {
     if !dir.is_dir() {
         return Ok(());
     }
 
     let dir_name = dir.file_name().and_then(|n| n.to_str()).unwrap_or("");
-    if dir_name == "node_modules" || dir_name == ".git" || dir_name == "dist" || dir_name == "build"
-    {
+    if matches!(
+        dir_name,
+        "node_modules" | ".git" | "dist" | "build" | "target" | "vendor" | ".venv" | "__pycache__"
+    ) {
         return Ok(());
     }
 
@@ -652,14 +1259,9 @@ fn collect_files_recursive(dir: &Path, files: &mut Vec<String>) -> anyhow::Resul
         let path = entry.path();
 
         if path.is_dir() {
-            collect_files_recursive(&path, files)?;
-        } else if let Some(ext) = path.extension().and_then(|e| e.to_str()) {
-            if matches!(
-                ext,
-                "js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "mts" | "cts"
-            ) {
-                files.push(path.to_string_lossy().to_string());
-            }
+            collect_files_recursive(&path, files, accept)?;
+        } else if accept(&path) {
+            files.push(path.to_string_lossy().to_string());
         }
     }
 
@@ -954,21 +1556,37 @@ fn goals_command(action: GoalsCommands) -> anyhow::Result<()> {
     rt.block_on(async { goals_command_async(action).await })
 }
 
+/// Whether a `path:line:...` report line falls in the staged hunks
+///
+/// Returns `None` when the line carries no location.
+fn in_changed_lines(changed: &ChangedLines, report: &str) -> Option<bool> {
+    let mut parts = report.splitn(3, ':');
+    let path = parts.next()?;
+    let line = parts.next()?.trim().parse::<usize>().ok()?;
+    Some(changed.contains(Path::new(path), line))
+}
+
 fn diagnostics_command(
     ai: bool,
     cached: bool,
     commit: bool,
     no_add: bool,
     severity: Option<&str>,
+    fix: bool,
     files: &[String],
 ) -> anyhow::Result<()> {
     use anyhow::Context;
     use colored::Colorize;
     use std::process::Command;
-    use uira_oxc::{LintRule, Linter, Severity};
+    use uira_oxc::{LintConfig, LintRule, Linter, ModuleLinter, ModuleRule, Severity};
 
     println!("🔍 Running diagnostics...\n");
 
+    let changed_lines = if cached && !ai {
+        Some(ChangedLines::from_staged(&std::env::current_dir()?)?)
+    } else {
+        None
+    };
     let files_to_check = if cached {
         let output = Command::new("git")
             .args(["diff", "--cached", "--name-only", "--diff-filter=ACMR"])
@@ -1064,9 +1682,28 @@ fn diagnostics_command(
     let mut warning_count = 0;
 
     if !js_ts_files.is_empty() {
-        let linter = Linter::new(LintRule::recommended());
+        let lint_config = match uira_core::load_config(None) {
+            Ok(config) => LintConfig::from_levels(config.diagnostics.lint.rules)
+                .map_err(|e| anyhow::anyhow!("diagnostics.lint.rules: {}", e))?,
+            Err(_) => LintConfig::default(),
+        };
+        let linter = Linter::new(LintRule::recommended()).with_config(lint_config.clone());
+        let module_linter =
+            ModuleLinter::new(ModuleRule::all(), std::env::current_dir()?).with_config(lint_config);
         let js_files_owned: Vec<String> = js_ts_files.into_iter().cloned().collect();
-        let diagnostics = linter.lint_files(&js_files_owned);
+
+        if fix {
+            for file in &js_files_owned {
+                match module_linter.fix_file(file) {
+                    Ok(true) => println!("{} Sorted imports in {}", "✓".green(), file),
+                    Ok(false) => {}
+                    Err(e) => eprintln!("{} {}: {}", "⚠".yellow(), file, e),
+                }
+            }
+        }
+
+        let mut diagnostics = linter.lint_files(&js_files_owned);
+        diagnostics.extend(module_linter.lint_files(&js_files_owned));
 
         let filtered: Vec<_> = diagnostics
             .iter()
@@ -1075,6 +1712,11 @@ fn diagnostics_command(
                 "warning" => matches!(d.severity, Severity::Error | Severity::Warning),
                 _ => true,
             })
+            .filter(|d| {
+                changed_lines.as_ref().is_none_or(|changed| {
+                    changed.contains(std::path::Path::new(&d.file), d.line as usize)
+                })
+            })
             .collect();
 
         for d in &filtered {
@@ -1116,6 +1758,13 @@ fn diagnostics_command(
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             for line in stderr.lines() {
+                // With --cached, skip diagnostics outside the staged hunks and the
+                // unlocated "could not compile" summaries that would still count them
+                if let Some(changed) = &changed_lines {
+                    if !in_changed_lines(changed, line).unwrap_or(false) {
+                        continue;
+                    }
+                }
                 if line.contains("error")
                     && (severity == "error" || severity == "warning" || severity == "all")
                 {
@@ -1161,6 +1810,11 @@ fn comments_command(
 
     println!("💬 Checking comments...\n");
 
+    let changed_lines = if cached && !ai {
+        Some(ChangedLines::from_staged(&std::env::current_dir()?)?)
+    } else {
+        None
+    };
     let files_to_check = if cached {
         let output = Command::new("git")
             .args(["diff", "--cached", "--name-only", "--diff-filter=ACMR"])
@@ -1171,7 +1825,7 @@ fn comments_command(
             .map(String::from)
             .collect::<Vec<_>>()
     } else if files.is_empty() {
-        collect_files_from_cwd()?
+        collect_comment_files_from_cwd()?
     } else {
         files.to_vec()
     };
@@ -1245,6 +1899,11 @@ fn comments_command(
             if filter_chain.should_skip(&comment) {
                 continue;
             }
+            if let Some(changed) = &changed_lines {
+                if !changed.contains(Path::new(file), comment.line_number) {
+                    continue;
+                }
+            }
             comment_count += 1;
 
             println!(
@@ -0,0 +1,273 @@
+//! Effective configuration with the source of every value
+//!
+//! Compares the loaded [`UiraConfig`] against the raw file it came from, so
+//! each setting can be traced to the file, an environment variable expanded
+//! in it, or the built-in default.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::config::load_config_from_file;
+use crate::config::loader::{find_all_config_files, find_config_file, parse_raw_content};
+use crate::config::schema::UiraConfig;
+
+/// Keys accepted under another name when deserializing
+const KEY_ALIASES: &[(&str, &str)] = &[("code_host", "github")];
+
+/// Words in a key name whose values are never printed
+const SECRET_KEY_WORDS: &[&str] = &["token", "secret", "password"];
+
+const REDACTED: &str = "<redacted>";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ValueSource {
+    /// Set in the config file
+    File,
+    /// Set in the config file through `$VAR` / `${VAR}` expansion
+    Env,
+    /// Not set; the built-in default applies
+    Default,
+}
+
+/// One leaf setting of the effective configuration
+#[derive(Debug, Clone, Serialize)]
+pub struct ExplainedValue {
+    /// Dotted path such as `permissions.rules[0].pattern`
+    pub key: String,
+    pub value: Value,
+    pub source: ValueSource,
+    /// The value as written in the file, for env-expanded values
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ConfigExplanation {
+    /// File the configuration was loaded from, if any
+    pub path: Option<PathBuf>,
+    /// Other config files that exist but were not loaded, because only the
+    /// first one found is used
+    pub shadowed: Vec<PathBuf>,
+    pub values: Vec<ExplainedValue>,
+}
+
+impl ConfigExplanation {
+    /// Values whose key equals `prefix` or lies under it
+    pub fn filtered<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = &'a ExplainedValue> {
+        self.values.iter().filter(move |value| {
+            value.key == prefix
+                || value
+                    .key
+                    .strip_prefix(prefix)
+                    .is_some_and(|rest| rest.starts_with('.') || rest.starts_with('['))
+        })
+    }
+}
+
+/// Resolve the configuration the same way [`crate::load_config`] does and
+/// annotate every value with where it came from
+pub fn explain_config(config_path: Option<&Path>) -> Result<ConfigExplanation> {
+    let path = config_path.map(Path::to_path_buf).or_else(find_config_file);
+
+    let (config, raw) = match &path {
+        Some(path) => {
+            let resolved = load_config_from_file(path)?;
+            let content = std::fs::read_to_string(path)?;
+            let raw = parse_raw_content(&content, resolved.format)?;
+            (resolved.config, raw)
+        }
+        None => (UiraConfig::default(), Value::Null),
+    };
+
+    let shadowed = find_all_config_files()
+        .into_iter()
+        .filter(|candidate| Some(candidate) != path.as_ref())
+        .collect();
+
+    Ok(ConfigExplanation {
+        path,
+        shadowed,
+        values: explain_values(&config, &raw),
+    })
+}
+
+/// Annotate `config` against `raw`, the file content parsed without the schema
+pub fn explain_values(config: &UiraConfig, raw: &Value) -> Vec<ExplainedValue> {
+    let effective = serde_json::to_value(config).unwrap_or(Value::Null);
+    let mut values = Vec::new();
+    walk(&effective, Some(raw), String::new(), false, &mut values);
+    values
+}
+
+fn walk(
+    effective: &Value,
+    raw: Option<&Value>,
+    key: String,
+    named_entry: bool,
+    out: &mut Vec<ExplainedValue>,
+) {
+    match effective {
+        Value::Object(map) if !map.is_empty() => {
+            for (field, child) in map {
+                let raw_child = raw.and_then(|raw| {
+                    raw.get(field).or_else(|| {
+                        KEY_ALIASES
+                            .iter()
+                            .find(|(name, _)| name == field)
+                            .and_then(|(_, alias)| raw.get(alias))
+                    })
+                });
+                // Entries written as a `name: {...}` map carry their name in the key
+                let raw_child = match raw_child {
+                    None if named_entry && field == "name" => Some(child),
+                    other => other,
+                };
+                walk(child, raw_child, join_key(&key, field), false, out);
+            }
+        }
+        Value::Array(items) if !items.is_empty() => {
+            for (index, child) in items.iter().enumerate() {
+                let name = child.get("name").and_then(Value::as_str);
+                let (raw_child, named_entry) = match (raw, name) {
+                    (Some(Value::Array(raw_items)), Some(name)) => (
+                        raw_items
+                            .iter()
+                            .find(|item| item.get("name").and_then(Value::as_str) == Some(name)),
+                        false,
+                    ),
+                    (Some(Value::Array(raw_items)), None) => (raw_items.get(index), false),
+                    (Some(Value::Object(raw_map)), Some(name)) => (raw_map.get(name), true),
+                    _ => (None, false),
+                };
+                walk(
+                    child,
+                    raw_child,
+                    format!("{key}[{index}]"),
+                    named_entry,
+                    out,
+                );
+            }
+        }
+        _ => out.push(leaf(effective, raw, key)),
+    }
+}
+
+fn leaf(effective: &Value, raw: Option<&Value>, key: String) -> ExplainedValue {
+    let (source, raw_text) = match raw {
+        Some(Value::String(text))
+            if text.contains('$') && Some(text.as_str()) != effective.as_str() =>
+        {
+            (ValueSource::Env, Some(text.clone()))
+        }
+        Some(Value::Null) | None => (ValueSource::Default, None),
+        Some(_) => (ValueSource::File, None),
+    };
+
+    let value = if is_secret(&key) && !effective.is_null() {
+        Value::String(REDACTED.to_string())
+    } else {
+        effective.clone()
+    };
+    ExplainedValue {
+        key,
+        value,
+        source,
+        raw: raw_text,
+    }
+}
+
+fn join_key(parent: &str, field: &str) -> String {
+    if parent.is_empty() {
+        field.to_string()
+    } else {
+        format!("{parent}.{field}")
+    }
+}
+
+/// Secret-looking keys and every environment variable passed to MCP servers
+fn is_secret(key: &str) -> bool {
+    let lower = key.to_ascii_lowercase();
+    let last = lower.rsplit('.').next().unwrap_or(&lower);
+    last.split('_').any(|word| SECRET_KEY_WORDS.contains(&word))
+        || last.contains("api_key")
+        || lower.contains("].env.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn explain_yaml(yaml: &str) -> Vec<ExplainedValue> {
+        let raw: Value = serde_yaml_ng::from_str(yaml).unwrap();
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        explain_values(&config, &raw)
+    }
+
+    fn find<'a>(values: &'a [ExplainedValue], key: &str) -> &'a ExplainedValue {
+        values
+            .iter()
+            .find(|value| value.key == key)
+            .unwrap_or_else(|| panic!("missing key {key}"))
+    }
+
+    #[test]
+    fn annotates_file_and_default_values() {
+        let values = explain_yaml(
+            "theme: dracula\npermissions:\n  rules:\n    - permission: \"file:write\"\n      pattern: \"src/**\"\n      action: deny\n",
+        );
+
+        assert_eq!(find(&values, "theme").source, ValueSource::File);
+        assert_eq!(find(&values, "show_logo").source, ValueSource::Default);
+        let pattern = find(&values, "permissions.rules[0].pattern");
+        assert_eq!(pattern.source, ValueSource::File);
+        assert_eq!(pattern.value, "src/**");
+        assert_eq!(
+            find(&values, "permissions.rules[0].comment").source,
+            ValueSource::Default
+        );
+    }
+
+    #[test]
+    fn follows_mcp_servers_written_as_a_map_and_redacts_env() {
+        let values = explain_yaml(
+            "mcp:\n  servers:\n    zeta:\n      command: z\n    alpha:\n      command: a\n      env:\n        TOKEN: abc\n",
+        );
+
+        let first = find(&values, "mcp.servers[0].command");
+        assert_eq!(first.value, "a");
+        assert_eq!(first.source, ValueSource::File);
+        assert_eq!(
+            find(&values, "mcp.servers[0].name").source,
+            ValueSource::File
+        );
+        assert_eq!(find(&values, "mcp.servers[0].env.TOKEN").value, REDACTED);
+        assert_eq!(find(&values, "mcp.servers[1].command").value, "z");
+    }
+
+    #[test]
+    fn marks_env_expanded_values() {
+        unsafe {
+            std::env::set_var("UIRA_EXPLAIN_TEST_MODEL", "openai/gpt-4o");
+        }
+        let yaml = "agents:\n  explore:\n    model: ${UIRA_EXPLAIN_TEST_MODEL}\ntypos:\n  ai:\n    model: ${UIRA_EXPLAIN_TEST_MODEL}\n";
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("uira.yml");
+        std::fs::write(&path, yaml).unwrap();
+
+        let explanation = explain_config(Some(&path)).unwrap();
+        assert_eq!(explanation.path.as_deref(), Some(path.as_path()));
+        let typos = explanation.filtered("typos.ai.model").next().unwrap();
+        assert_eq!(typos.value, "openai/gpt-4o");
+        assert_eq!(typos.source, ValueSource::Env);
+        assert_eq!(typos.raw.as_deref(), Some("${UIRA_EXPLAIN_TEST_MODEL}"));
+        // Agent models are not expanded by the loader
+        let explore = explanation.filtered("agents.explore.model").next().unwrap();
+        assert_eq!(explore.source, ValueSource::File);
+        assert_eq!(explanation.filtered("agents.explore").count(), 6);
+        assert_eq!(explanation.filtered("agents.exp").count(), 0);
+    }
+}
@@ -60,6 +60,15 @@ pub fn load_config_from_file(path: &Path) -> Result<ResolvedConfig> {
     })
 }
 
+/// Parse a config file without the schema, keeping only what is written
+pub(crate) fn parse_raw_content(content: &str, format: ConfigFormat) -> Result<serde_json::Value> {
+    match format {
+        ConfigFormat::Jsonc => json5::from_str(content).context("Failed to parse JSONC"),
+        ConfigFormat::Json => serde_json::from_str(content).context("Failed to parse JSON"),
+        ConfigFormat::Yaml => serde_yaml_ng::from_str(content).context("Failed to parse YAML"),
+    }
+}
+
 fn parse_config_content(content: &str, format: ConfigFormat) -> Result<UiraConfig> {
     match format {
         ConfigFormat::Jsonc => json5::from_str(content).context("Failed to parse JSONC"),
@@ -79,7 +88,7 @@ const CONFIG_CANDIDATES: &[&str] = &[
     ".uira.yaml",
 ];
 
-fn find_config_file() -> Option<PathBuf> {
+pub(crate) fn find_config_file() -> Option<PathBuf> {
     for candidate in CONFIG_CANDIDATES {
         let path = PathBuf::from(candidate);
         if path.exists() {
@@ -134,6 +143,7 @@ fn expand_env_vars(config: UiraConfig) -> UiraConfig {
         typos: expand_typos_settings(config.typos),
         diagnostics: expand_diagnostics_settings(config.diagnostics),
         comments: expand_comments_settings(config.comments),
+        commit_msg: expand_commit_msg_settings(config.commit_msg),
         opencode: expand_opencode_settings(config.opencode),
         mcp: expand_mcp_settings(config.mcp),
         agents: config.agents,
@@ -145,7 +155,23 @@ fn expand_env_vars(config: UiraConfig) -> UiraConfig {
         skills: config.skills,
         gateway: config.gateway,
         channels: config.channels,
+        scheduler: config.scheduler,
+        background_tasks: config.background_tasks,
+        retention: config.retention,
+        autopilot: config.autopilot,
+        notifications: config.notifications,
+        workspaces: expand_workspace_roots(config.workspaces),
+        workspace_index: config.workspace_index,
+        snapshots: config.snapshots,
+        repo_map: config.repo_map,
+        routing: config.routing,
+        consensus: config.consensus,
+        delegation_cache: config.delegation_cache,
+        experiments: config.experiments,
+        code_host: config.code_host,
+        ci: config.ci,
         providers: expand_providers_settings(config.providers),
+        privacy: expand_privacy_settings(config.privacy),
         keybinds: config.keybinds,
         sidebar: config.sidebar,
         show_logo: config.show_logo,
@@ -153,6 +179,18 @@ fn expand_env_vars(config: UiraConfig) -> UiraConfig {
     }
 }
 
+fn expand_workspace_roots(
+    roots: Vec<crate::config::schema::WorkspaceRootSettings>,
+) -> Vec<crate::config::schema::WorkspaceRootSettings> {
+    roots
+        .into_iter()
+        .map(|mut root| {
+            root.path = expand_env_string(&root.path);
+            root
+        })
+        .collect()
+}
+
 fn expand_theme_color_overrides(
     mut overrides: crate::config::schema::ThemeColorOverrides,
 ) -> crate::config::schema::ThemeColorOverrides {
@@ -166,6 +204,13 @@ fn expand_theme_color_overrides(
     overrides
 }
 
+fn expand_privacy_settings(
+    mut privacy: crate::config::schema::PrivacySettings,
+) -> crate::config::schema::PrivacySettings {
+    privacy.mapping_file = privacy.mapping_file.as_ref().map(|v| expand_env_string(v));
+    privacy
+}
+
 fn expand_compaction_settings(
     mut compaction: crate::config::schema::CompactionSettings,
 ) -> crate::config::schema::CompactionSettings {
@@ -203,11 +248,21 @@ fn expand_comments_settings(
     comments
 }
 
+fn expand_commit_msg_settings(
+    mut commit_msg: crate::config::schema::CommitMsgSettings,
+) -> crate::config::schema::CommitMsgSettings {
+    commit_msg.ai.model = expand_env_string(&commit_msg.ai.model);
+    commit_msg
+}
+
 fn expand_goals_settings(
     mut goals: crate::config::schema::GoalsConfig,
 ) -> crate::config::schema::GoalsConfig {
     for goal in goals.goals.iter_mut() {
         goal.command = expand_env_string(&goal.command);
+        if let Some(command) = &goal.changed_command {
+            goal.changed_command = Some(expand_env_string(command));
+        }
         if let Some(ws) = &goal.workspace {
             goal.workspace = Some(expand_env_string(ws));
         }
@@ -232,7 +287,8 @@ fn expand_mcp_settings(
     }
     mcp
 }
-fn expand_env_string(s: &str) -> String {
+/// Expand `${VAR}` and `$VAR` references, leaving unset variables as written
+pub fn expand_env_string(s: &str) -> String {
     let mut result = String::new();
     let mut chars = s.chars().peekable();
 
@@ -283,6 +339,9 @@ fn expand_env_string(s: &str) -> String {
 fn expand_providers_settings(
     mut settings: crate::schema::ProvidersSettings,
 ) -> crate::schema::ProvidersSettings {
+    if let Some(ref path) = settings.payload_log.path {
+        settings.payload_log.path = Some(expand_env_string(path));
+    }
     if let Some(ref path) = settings.anthropic.payload_log.path {
         settings.anthropic.payload_log.path = Some(expand_env_string(path));
     }
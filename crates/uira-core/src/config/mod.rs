@@ -1,14 +1,18 @@
+pub mod explain;
 pub mod loader;
 pub mod schema;
 
+pub use explain::{explain_config, ConfigExplanation, ExplainedValue, ValueSource};
+
 pub use loader::{
-    find_all_config_files, load_config, load_config_from_file, resolve_config, ConfigFormat,
-    ResolvedConfig,
+    expand_env_string, find_all_config_files, load_config, load_config_from_file, resolve_config,
+    ConfigFormat, ResolvedConfig,
 };
 pub use schema::{
     AgentConfig, AgentSettings, AiHookCommand, AiHooksConfig, AnthropicProviderSettings,
-    CommentsAiSettings, CommentsSettings, DiagnosticsAiSettings, DiagnosticsSettings,
-    FriendliAIProviderSettings, HookCommand, HookConfig, HooksConfig, KeybindsConfig,
-    McpServerConfig, McpSettings, NamedMcpServerConfig, PayloadLogSettings, ProvidersSettings,
-    SidebarConfig, ThemeColorOverrides, TyposAiSettings, TyposSettings, UiraConfig,
+    CommentsAiSettings, CommentsSettings, CommitMsgAiSettings, CommitMsgSettings,
+    DiagnosticsAiSettings, DiagnosticsSettings, FriendliAIProviderSettings, HookCommand,
+    HookConfig, HooksConfig, KeybindsConfig, LintSettings, McpServerConfig, McpSettings,
+    NamedMcpServerConfig, PayloadLogSettings, ProvidersSettings, SidebarConfig,
+    ThemeColorOverrides, TyposAiSettings, TyposSettings, UiraConfig,
 };
@@ -38,6 +38,10 @@ pub struct UiraConfig {
     #[serde(default)]
     pub comments: CommentsSettings,
 
+    /// Commit message rules (conventional commits) for the commit-msg hook
+    #[serde(default)]
+    pub commit_msg: CommitMsgSettings,
+
     /// OpenCode server settings
     #[serde(default)]
     pub opencode: OpencodeSettings,
@@ -82,10 +86,74 @@ pub struct UiraConfig {
     #[serde(default)]
     pub channels: ChannelSettings,
 
+    /// Recurring agent jobs run by the gateway
+    #[serde(default)]
+    pub scheduler: SchedulerSettings,
+
+    /// Background agent task settings
+    #[serde(default)]
+    pub background_tasks: BackgroundTaskSettings,
+
+    /// Pruning and compression of old session logs and background-task artifacts
+    #[serde(default)]
+    pub retention: RetentionSettings,
+
+    /// Autopilot mode settings
+    #[serde(default)]
+    pub autopilot: AutopilotSettings,
+
+    /// Desktop notifications for long-running work
+    #[serde(default)]
+    pub notifications: NotificationSettings,
+
+    /// Additional workspace roots the agent can address as `@<name>/...`
+    #[serde(default)]
+    pub workspaces: Vec<WorkspaceRootSettings>,
+
+    /// Background workspace indexer for interactive sessions
+    #[serde(default)]
+    pub workspace_index: WorkspaceIndexSettings,
+
+    /// Working tree snapshots before risky commands and between ralph iterations
+    #[serde(default)]
+    pub snapshots: SnapshotSettings,
+
+    /// Ranked repository summary added to the system prompt at session start
+    #[serde(default)]
+    pub repo_map: RepoMapSettings,
+
+    /// Model tier selection for delegated tasks without a configured model
+    #[serde(default)]
+    pub routing: RoutingSettings,
+
+    /// Default panel for agents and skills using the `consensus` strategy
+    #[serde(default)]
+    pub consensus: ConsensusSettings,
+
+    /// Reuse of explore/librarian results for repeated delegations
+    #[serde(default)]
+    pub delegation_cache: DelegationCacheSettings,
+
+    /// A/B experiments assigning sessions to prompt or model variants
+    #[serde(default)]
+    pub experiments: Vec<ExperimentSettings>,
+
+    /// GitHub, GitLab or Gitea issue and pull request tools for the agent
+    #[serde(default, alias = "github")]
+    pub code_host: CodeHostSettings,
+
+    /// CI log triage tool for the agent
+    #[serde(default)]
+    pub ci: CiSettings,
+
     /// Provider-specific settings
     #[serde(default)]
     pub providers: ProvidersSettings,
 
+    /// PII scrubbing for provider requests and session logs
+    #[serde(default)]
+    pub privacy: PrivacySettings,
+
     /// TUI keybindings configuration
     #[serde(default)]
     pub keybinds: KeybindsConfig,
@@ -108,6 +176,7 @@ impl Default for UiraConfig {
             typos: TyposSettings::default(),
             diagnostics: DiagnosticsSettings::default(),
             comments: CommentsSettings::default(),
+            commit_msg: CommitMsgSettings::default(),
             opencode: OpencodeSettings::default(),
             mcp: McpSettings::default(),
             agents: AgentSettings::default(),
@@ -119,7 +188,23 @@ impl Default for UiraConfig {
             skills: SkillsSettings::default(),
             gateway: GatewaySettings::default(),
             channels: ChannelSettings::default(),
+            scheduler: SchedulerSettings::default(),
+            background_tasks: BackgroundTaskSettings::default(),
+            retention: RetentionSettings::default(),
+            autopilot: AutopilotSettings::default(),
+            notifications: NotificationSettings::default(),
+            workspaces: Vec::new(),
+            workspace_index: WorkspaceIndexSettings::default(),
+            snapshots: SnapshotSettings::default(),
+            repo_map: RepoMapSettings::default(),
+            routing: RoutingSettings::default(),
+            consensus: ConsensusSettings::default(),
+            delegation_cache: DelegationCacheSettings::default(),
+            experiments: Vec::new(),
+            code_host: CodeHostSettings::default(),
+            ci: CiSettings::default(),
             providers: ProvidersSettings::default(),
+            privacy: PrivacySettings::default(),
             keybinds: KeybindsConfig::default(),
             sidebar: SidebarConfig::default(),
             show_logo: true,
@@ -311,6 +396,26 @@ fn default_typos_model() -> String {
 pub struct DiagnosticsSettings {
     #[serde(default)]
     pub ai: DiagnosticsAiSettings,
+
+    /// Native JS/TS linter settings
+    #[serde(default)]
+    pub lint: LintSettings,
+}
+
+/// Native JS/TS linter settings
+///
+/// ```yaml
+/// diagnostics:
+///   lint:
+///     rules:
+///       no-console: error
+///       no-var: off
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LintSettings {
+    /// Rule name -> level (`off`, `info`, `warning`/`warn`, `error`)
+    #[serde(default)]
+    pub rules: HashMap<String, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -424,6 +529,98 @@ fn default_comments_docstrings() -> bool {
     false
 }
 
+// ============================================================================
+// Commit Message Configuration
+// ============================================================================
+
+/// Conventional commit rules enforced by `uira-commit-hook-cli commit-msg`
+///
+/// ```yaml
+/// commit_msg:
+///   types: [feat, fix, docs, chore]
+///   scopes: [core, cli]
+///   require_scope: true
+///   max_header_length: 72
+///   ai:
+///     model: "anthropic/claude-sonnet-4-20250514"
+/// ```
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitMsgSettings {
+    /// Allowed commit types
+    #[serde(default = "default_commit_types")]
+    pub types: Vec<String>,
+
+    /// Allowed scopes (empty allows any scope)
+    #[serde(default)]
+    pub scopes: Vec<String>,
+
+    /// Reject headers without a `(scope)`
+    #[serde(default)]
+    pub require_scope: bool,
+
+    /// Maximum length of the first line
+    #[serde(default = "default_commit_max_header_length")]
+    pub max_header_length: usize,
+
+    /// AI settings for generating compliant messages
+    #[serde(default)]
+    pub ai: CommitMsgAiSettings,
+}
+
+impl Default for CommitMsgSettings {
+    fn default() -> Self {
+        Self {
+            types: default_commit_types(),
+            scopes: Vec::new(),
+            require_scope: false,
+            max_header_length: default_commit_max_header_length(),
+            ai: CommitMsgAiSettings::default(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CommitMsgAiSettings {
+    #[serde(default = "default_commit_msg_model")]
+    pub model: String,
+}
+
+impl Default for CommitMsgAiSettings {
+    fn default() -> Self {
+        Self {
+            model: default_commit_msg_model(),
+        }
+    }
+}
+
+impl CommitMsgAiSettings {
+    pub fn parse_model(&self) -> (String, String) {
+        if let Some((provider, model)) = self.model.split_once('/') {
+            (provider.to_string(), model.to_string())
+        } else {
+            ("anthropic".to_string(), self.model.clone())
+        }
+    }
+}
+
+fn default_commit_types() -> Vec<String> {
+    [
+        "feat", "fix", "docs", "style", "refactor", "perf", "test", "build", "ci", "chore",
+        "revert",
+    ]
+    .iter()
+    .map(|t| t.to_string())
+    .collect()
+}
+
+fn default_commit_max_header_length() -> usize {
+    72
+}
+
+fn default_commit_msg_model() -> String {
+    format!("anthropic/{}", crate::DEFAULT_ANTHROPIC_MODEL)
+}
+
 /// MCP (Model Context Protocol) settings
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct McpSettings {
@@ -458,6 +655,37 @@ pub struct McpServerConfig {
     /// Environment variables
     #[serde(default)]
     pub env: HashMap<String, String>,
+
+    /// Whether the server needs the network; guessed from the command when
+    /// unset (see [`McpServerConfig::is_remote`])
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remote: Option<bool>,
+}
+
+/// Launchers that fetch the server package from a registry on every start
+const MCP_PACKAGE_RUNNERS: &[&str] = &["npx", "bunx", "uvx", "pipx", "dlx"];
+
+impl McpServerConfig {
+    /// Whether the server cannot work offline: `remote` when set, otherwise
+    /// a URL argument (`mcp-remote https://...`) or a package runner such as
+    /// `npx -y` that downloads the server before starting it
+    pub fn is_remote(&self) -> bool {
+        if let Some(remote) = self.remote {
+            return remote;
+        }
+        let words: Vec<&str> = self
+            .command
+            .split_whitespace()
+            .chain(self.args.iter().map(String::as_str))
+            .collect();
+        words
+            .iter()
+            .any(|word| word.starts_with("http://") || word.starts_with("https://"))
+            || words.iter().any(|word| {
+                let program = word.rsplit(['/', '\\']).next().unwrap_or(word);
+                MCP_PACKAGE_RUNNERS.contains(&program)
+            })
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -513,9 +741,64 @@ pub struct AgentConfig {
     /// Agent model override (uses ai.model if not specified)
     pub model: Option<String>,
 
+    /// Overrides for the agent's built-in prompt
+    #[serde(default)]
+    pub prompt: Option<PromptOverrideConfig>,
+
     /// Agent-specific settings
     #[serde(default)]
     pub settings: HashMap<String, serde_json::Value>,
+
+    /// How the agent produces its answers; falls back to the delegated
+    /// skill's strategy, then `single`
+    #[serde(default)]
+    pub strategy: Option<ExecutionStrategy>,
+
+    /// Panel override for the `consensus` strategy
+    #[serde(default)]
+    pub consensus: Option<ConsensusSettings>,
+
+    /// Format the agent's final message must follow when it is delegated to;
+    /// a `delegate_task` call can override it
+    #[serde(default)]
+    pub output_contract: Option<OutputContract>,
+}
+
+/// Changes applied on top of an embedded agent prompt
+///
+/// ```yaml
+/// agents:
+///   executor:
+///     prompt:
+///       sections:
+///         "Must Do":
+///           append: "- Run `cargo fmt` before finishing"
+///       append: "Our team prefers small, reviewable commits."
+/// ```
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PromptOverrideConfig {
+    /// Replaces the whole prompt
+    #[serde(default)]
+    pub replace: Option<String>,
+
+    /// Appended to the end of the prompt
+    #[serde(default)]
+    pub append: Option<String>,
+
+    /// Per-section changes keyed by markdown heading text
+    #[serde(default)]
+    pub sections: HashMap<String, PromptSectionOverride>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PromptSectionOverride {
+    /// Replaces the section body (the heading is kept)
+    #[serde(default)]
+    pub replace: Option<String>,
+
+    /// Appended to the section body
+    #[serde(default)]
+    pub append: Option<String>,
 }
 
 /// Git hooks configuration
@@ -629,6 +912,11 @@ pub struct AiHookCommand {
 ///   - name: test-coverage
 ///     command: bun run coverage --json | jq '.total'
 ///     target: 80
+///
+///   - name: unit-tests
+///     command: ./scripts/test-score.sh
+///     changed_command: ./scripts/test-score.sh {files}
+///     target: 100
 /// ```
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GoalConfig {
@@ -648,6 +936,13 @@ pub struct GoalConfig {
     /// - Use stderr for logging/debug output
     pub command: String,
 
+    /// Command to run instead of `command` when the files changed since the
+    /// last check are known, e.g. in ralph loops. `{files}` expands to the
+    /// shell-quoted changed paths, relative to the goal's working directory.
+    /// The paths are also passed newline-separated in `UIRA_CHANGED_FILES`.
+    #[serde(default)]
+    pub changed_command: Option<String>,
+
     /// Target score threshold (0-100) to consider the goal passed
     pub target: f64,
 
@@ -662,6 +957,11 @@ pub struct GoalConfig {
     /// Optional description of what this goal measures
     #[serde(default)]
     pub description: Option<String>,
+
+    /// Shell that runs `command` (`sh`, `bash`, `cmd`, `powershell`, `pwsh`);
+    /// defaults to `UIRA_SHELL`, then `sh` on unix and `cmd` on Windows
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub shell: Option<crate::shell::ShellKind>,
 }
 
 fn default_goal_timeout() -> u64 {
@@ -771,25 +1071,90 @@ fn default_compaction_strategy() -> String {
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProvidersSettings {
+    /// Request payload logging shared by all providers
+    #[serde(default)]
+    pub payload_log: PayloadLogSettings,
     #[serde(default)]
     pub anthropic: AnthropicProviderSettings,
     #[serde(default)]
     pub friendliai: FriendliAIProviderSettings,
 }
 
+impl ProvidersSettings {
+    /// Payload log settings with the legacy `anthropic.payload_log` section
+    /// folded in: when only that one is enabled, it logs Anthropic requests
+    pub fn effective_payload_log(&self) -> PayloadLogSettings {
+        let legacy = &self.anthropic.payload_log;
+        if self.payload_log.enabled || !legacy.enabled {
+            return self.payload_log.clone();
+        }
+        PayloadLogSettings {
+            providers: vec!["anthropic".to_string()],
+            ..legacy.clone()
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct AnthropicProviderSettings {
     #[serde(default)]
     pub payload_log: PayloadLogSettings,
 }
 
-#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct PayloadLogSettings {
     #[serde(default)]
     pub enabled: bool,
 
+    /// Log file (default: ~/.local/share/uira/logs/payloads.jsonl)
     #[serde(default)]
     pub path: Option<String>,
+
+    /// Providers to log, e.g. `["anthropic", "openai"]`; empty logs all
+    #[serde(default)]
+    pub providers: Vec<String>,
+
+    /// Longer strings (file contents, images) are truncated (default: 4096)
+    #[serde(default = "default_payload_log_max_string_bytes")]
+    pub max_string_bytes: usize,
+
+    /// Rotate the log once it grows past this size (default: 10 MiB)
+    #[serde(default = "default_payload_log_max_file_bytes")]
+    pub max_file_bytes: u64,
+
+    /// Rotated files to keep (default: 5)
+    #[serde(default = "default_payload_log_max_files")]
+    pub max_files: usize,
+
+    /// Gzip rotated files (default: true)
+    #[serde(default = "default_true")]
+    pub compress: bool,
+}
+
+impl Default for PayloadLogSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            path: None,
+            providers: Vec::new(),
+            max_string_bytes: default_payload_log_max_string_bytes(),
+            max_file_bytes: default_payload_log_max_file_bytes(),
+            max_files: default_payload_log_max_files(),
+            compress: true,
+        }
+    }
+}
+
+fn default_payload_log_max_string_bytes() -> usize {
+    4096
+}
+
+fn default_payload_log_max_file_bytes() -> u64 {
+    10 * 1024 * 1024
+}
+
+fn default_payload_log_max_files() -> usize {
+    5
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -811,6 +1176,58 @@ pub struct FriendliAIProviderSettings {
     pub custom_endpoint: Option<String>,
 }
 
+// ============================================================================
+// Privacy Configuration
+// ============================================================================
+
+/// Opt-in PII scrubbing applied before messages reach remote providers and
+/// before they are written to session logs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PrivacySettings {
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Replace email addresses
+    #[serde(default = "default_true")]
+    pub emails: bool,
+
+    /// Replace IPv4 and IPv6 addresses
+    #[serde(default = "default_true")]
+    pub ip_addresses: bool,
+
+    /// Literal names (people, customers, hosts) to replace, matched case-insensitively
+    #[serde(default)]
+    pub names: Vec<String>,
+
+    /// Custom regex patterns, each replaced with its own placeholder label
+    #[serde(default)]
+    pub patterns: Vec<PiiPatternConfig>,
+
+    /// Local file holding placeholder mappings (default: .uira/privacy/mapping.json)
+    #[serde(default)]
+    pub mapping_file: Option<String>,
+}
+
+impl Default for PrivacySettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            emails: true,
+            ip_addresses: true,
+            names: Vec::new(),
+            patterns: Vec::new(),
+            mapping_file: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PiiPatternConfig {
+    /// Placeholder label, e.g. "ACCOUNT" produces `<ACCOUNT_1>`
+    pub label: String,
+    pub pattern: String,
+}
+
 // ============================================================================
 // Permissions Configuration
 // ============================================================================
@@ -819,6 +1236,79 @@ pub struct FriendliAIProviderSettings {
 pub struct PermissionsSettings {
     #[serde(default)]
     pub rules: Vec<PermissionRuleConfig>,
+
+    /// Tool classes approved without prompting, per sandbox mode
+    #[serde(default)]
+    pub auto_approve: AutoApproveSettings,
+
+    /// Plain-English explanations of risky shell commands in approval prompts
+    #[serde(default)]
+    pub explain_commands: ExplainCommandsSettings,
+}
+
+/// Explain network and destructive shell commands before asking to run
+/// them. Without a model the explanation is generated locally.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExplainCommandsSettings {
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Model asked for the explanation, as "provider/model"; a LOW-tier
+    /// model such as "anthropic/claude-haiku-4-5" is plenty
+    #[serde(default)]
+    pub model: Option<String>,
+}
+
+impl Default for ExplainCommandsSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            model: None,
+        }
+    }
+}
+
+/// Classes of tool call, from least to most dangerous
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ToolClassConfig {
+    ReadOnly,
+    WorkspaceWrite,
+    Network,
+    /// Never auto-approved, even when listed
+    Destructive,
+}
+
+/// Tool classes auto-approved under each sandbox mode. Calls that are
+/// denied by a permission rule or forbidden outright are unaffected.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AutoApproveSettings {
+    #[serde(default = "default_auto_approve_read_only")]
+    pub read_only: Vec<ToolClassConfig>,
+
+    #[serde(default = "default_auto_approve_workspace_write")]
+    pub workspace_write: Vec<ToolClassConfig>,
+
+    #[serde(default = "default_auto_approve_read_only")]
+    pub full_access: Vec<ToolClassConfig>,
+}
+
+impl Default for AutoApproveSettings {
+    fn default() -> Self {
+        Self {
+            read_only: default_auto_approve_read_only(),
+            workspace_write: default_auto_approve_workspace_write(),
+            full_access: default_auto_approve_read_only(),
+        }
+    }
+}
+
+fn default_auto_approve_read_only() -> Vec<ToolClassConfig> {
+    vec![ToolClassConfig::ReadOnly]
+}
+
+fn default_auto_approve_workspace_write() -> Vec<ToolClassConfig> {
+    vec![ToolClassConfig::ReadOnly, ToolClassConfig::WorkspaceWrite]
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -832,6 +1322,11 @@ pub struct PermissionRuleConfig {
 
     pub action: PermissionActionConfig,
 
+    /// Higher-priority rules win over lower ones that also match; among
+    /// equal priorities the last matching rule wins
+    #[serde(default)]
+    pub priority: i32,
+
     #[serde(default)]
     pub comment: Option<String>,
 }
@@ -934,6 +1429,14 @@ pub struct GatewaySettings {
     /// Working directory for gateway-spawned sessions
     #[serde(default)]
     pub working_directory: Option<String>,
+
+    /// Lifetime of a session share link when the request doesn't set one
+    #[serde(default = "default_share_ttl")]
+    pub share_ttl_secs: u64,
+
+    /// Longest lifetime a share link may be minted with
+    #[serde(default = "default_max_share_ttl")]
+    pub max_share_ttl_secs: u64,
 }
 
 impl Default for GatewaySettings {
@@ -949,6 +1452,8 @@ impl Default for GatewaySettings {
             auth_token: None,
             idle_timeout_secs: default_idle_timeout(),
             working_directory: None,
+            share_ttl_secs: default_share_ttl(),
+            max_share_ttl_secs: default_max_share_ttl(),
         }
     }
 }
@@ -981,39 +1486,900 @@ fn default_idle_timeout() -> Option<u64> {
     Some(1800)
 }
 
+fn default_share_ttl() -> u64 {
+    3600
+}
+
+fn default_max_share_ttl() -> u64 {
+    86400
+}
+
 // ============================================================================
-// Channel Configuration
+// Scheduler Configuration
 // ============================================================================
 
-/// Channel settings for multi-channel messaging
+/// Scheduler settings for recurring agent jobs
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
-pub struct ChannelSettings {
+pub struct SchedulerSettings {
+    /// Whether `gateway start` runs the scheduled jobs
     #[serde(default)]
-    pub telegram: Option<TelegramChannelConfig>,
+    pub enabled: bool,
 
+    /// Jobs to run on a schedule
     #[serde(default)]
-    pub telegram_accounts: Vec<TelegramChannelConfig>,
+    pub jobs: Vec<ScheduledJobConfig>,
+}
+
+/// A prompt sent to a fresh agent session on a cron schedule
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledJobConfig {
+    /// Unique name of the job
+    pub name: String,
 
+    /// Cron expression in local time (e.g., "0 3 * * *" or "@daily")
+    pub schedule: String,
+
+    /// Prompt sent to the agent
+    pub prompt: String,
+
+    /// Orchestrator personality (defaults to `gateway.default_agent`)
     #[serde(default)]
-    pub slack: Option<SlackChannelConfig>,
+    pub agent: Option<String>,
 
+    /// Model override (defaults to `gateway.model`)
     #[serde(default)]
-    pub slack_accounts: Vec<SlackChannelConfig>,
+    pub model: Option<String>,
 
+    /// Working directory for the job's session
     #[serde(default)]
-    pub discord: Option<DiscordChannelConfig>,
+    pub working_directory: Option<String>,
 
+    /// Channel type to post the result to (e.g., "slack", "telegram")
     #[serde(default)]
-    pub discord_accounts: Vec<DiscordChannelConfig>,
-}
+    pub channel: Option<String>,
 
-fn default_account_id() -> String {
-    "default".to_string()
-}
+    /// Channel-specific recipient, such as a Slack channel ID
+    #[serde(default)]
+    pub recipient: Option<String>,
 
-fn default_stream_mode() -> String {
-    "partial".to_string()
-}
+    /// Maximum run time in seconds (default: 3600)
+    #[serde(default = "default_job_timeout")]
+    pub timeout_secs: u64,
+
+    /// Whether the job is scheduled (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+fn default_job_timeout() -> u64 {
+    3600
+}
+
+// ============================================================================
+// Background Task Configuration
+// ============================================================================
+
+/// Settings for background agent tasks
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BackgroundTaskSettings {
+    /// Retry policy for failed tasks
+    #[serde(default)]
+    pub retry: TaskRetrySettings,
+}
+
+/// How failed background tasks are retried before they are dead-lettered
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TaskRetrySettings {
+    /// Total attempts including the first (default: 3, 1 disables retries)
+    #[serde(default = "default_retry_max_attempts")]
+    pub max_attempts: u32,
+
+    /// Delay before the first retry in milliseconds (default: 2000)
+    #[serde(default = "default_retry_backoff_ms")]
+    pub backoff_ms: u64,
+
+    /// Factor the delay grows by after each retry (default: 2.0)
+    #[serde(default = "default_retry_backoff_multiplier")]
+    pub backoff_multiplier: f64,
+
+    /// Upper bound on the delay in milliseconds (default: 60000)
+    #[serde(default = "default_retry_max_backoff_ms")]
+    pub max_backoff_ms: u64,
+
+    /// Failure kinds worth retrying (default: rate_limit, overloaded, timeout, network)
+    #[serde(default = "default_retry_on")]
+    pub retry_on: Vec<TaskFailureKind>,
+}
+
+impl Default for TaskRetrySettings {
+    fn default() -> Self {
+        Self {
+            max_attempts: default_retry_max_attempts(),
+            backoff_ms: default_retry_backoff_ms(),
+            backoff_multiplier: default_retry_backoff_multiplier(),
+            max_backoff_ms: default_retry_max_backoff_ms(),
+            retry_on: default_retry_on(),
+        }
+    }
+}
+
+/// Why a background task attempt failed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskFailureKind {
+    RateLimit,
+    Overloaded,
+    Timeout,
+    Network,
+    Panic,
+    Other,
+}
+
+impl TaskFailureKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            TaskFailureKind::RateLimit => "rate_limit",
+            TaskFailureKind::Overloaded => "overloaded",
+            TaskFailureKind::Timeout => "timeout",
+            TaskFailureKind::Network => "network",
+            TaskFailureKind::Panic => "panic",
+            TaskFailureKind::Other => "other",
+        }
+    }
+}
+
+fn default_retry_max_attempts() -> u32 {
+    3
+}
+
+fn default_retry_backoff_ms() -> u64 {
+    2000
+}
+
+fn default_retry_backoff_multiplier() -> f64 {
+    2.0
+}
+
+fn default_retry_max_backoff_ms() -> u64 {
+    60_000
+}
+
+fn default_retry_on() -> Vec<TaskFailureKind> {
+    vec![
+        TaskFailureKind::RateLimit,
+        TaskFailureKind::Overloaded,
+        TaskFailureKind::Timeout,
+        TaskFailureKind::Network,
+    ]
+}
+
+// ============================================================================
+// Autopilot Configuration
+// ============================================================================
+
+/// Settings for autopilot runs (plan, execute, verify)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AutopilotSettings {
+    /// Iteration limit across all phases (default: 10)
+    #[serde(default = "default_autopilot_max_iterations")]
+    pub max_iterations: u32,
+
+    /// Wait for the user to approve each new phase (default: false)
+    #[serde(default)]
+    pub require_approval: bool,
+}
+
+impl Default for AutopilotSettings {
+    fn default() -> Self {
+        Self {
+            max_iterations: default_autopilot_max_iterations(),
+            require_approval: false,
+        }
+    }
+}
+
+fn default_autopilot_max_iterations() -> u32 {
+    10
+}
+
+// ============================================================================
+// Notification Configuration
+// ============================================================================
+
+/// Native OS notifications, so long runs can finish while the terminal is
+/// in the background
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct NotificationSettings {
+    /// Send desktop notifications at all (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Notify when a run finishes after at least this many seconds (default: 30)
+    #[serde(default = "default_notification_min_duration_secs")]
+    pub min_duration_secs: u64,
+
+    /// Notify when a background task completes or fails (default: true)
+    #[serde(default = "default_true")]
+    pub background_tasks: bool,
+
+    /// Custom notifier command; `{title}` and `{body}` are substituted in each
+    /// argument (default: osascript on macOS, notify-send on Linux)
+    #[serde(default)]
+    pub command: Vec<String>,
+}
+
+impl Default for NotificationSettings {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            min_duration_secs: default_notification_min_duration_secs(),
+            background_tasks: true,
+            command: Vec::new(),
+        }
+    }
+}
+
+fn default_notification_min_duration_secs() -> u64 {
+    30
+}
+
+// ============================================================================
+// Retention Configuration
+// ============================================================================
+
+/// Limits on stored session logs (`~/.uira/sessions`) and finished
+/// background-task records and logs
+///
+/// ```yaml
+/// retention:
+///   max_sessions: 200
+///   max_age_days: 30
+///   max_disk_mb: 512
+///   compress_after_days: 3
+/// ```
+///
+/// A limit of 0 disables it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RetentionSettings {
+    /// Prune at startup, at most once a day (default: true); `uira sessions gc`
+    /// runs it on demand
+    #[serde(default = "default_true")]
+    pub auto: bool,
+
+    /// Session logs kept, newest first (default: 500)
+    #[serde(default = "default_retention_max_sessions")]
+    pub max_sessions: usize,
+
+    /// Delete session logs and finished background tasks older than this
+    /// (default: 90)
+    #[serde(default = "default_retention_max_age_days")]
+    pub max_age_days: u64,
+
+    /// Delete the oldest session logs while the total exceeds this (default: 1024)
+    #[serde(default = "default_retention_max_disk_mb")]
+    pub max_disk_mb: u64,
+
+    /// Gzip session logs older than this; they stay listable and resumable
+    /// (default: 7)
+    #[serde(default = "default_retention_compress_after_days")]
+    pub compress_after_days: u64,
+}
+
+impl Default for RetentionSettings {
+    fn default() -> Self {
+        Self {
+            auto: true,
+            max_sessions: default_retention_max_sessions(),
+            max_age_days: default_retention_max_age_days(),
+            max_disk_mb: default_retention_max_disk_mb(),
+            compress_after_days: default_retention_compress_after_days(),
+        }
+    }
+}
+
+fn default_retention_max_sessions() -> usize {
+    500
+}
+
+fn default_retention_max_age_days() -> u64 {
+    90
+}
+
+fn default_retention_max_disk_mb() -> u64 {
+    1024
+}
+
+fn default_retention_compress_after_days() -> u64 {
+    7
+}
+
+// ============================================================================
+// Workspace Roots Configuration
+// ============================================================================
+
+/// An extra root for sessions spanning several packages or repositories
+///
+/// ```yaml
+/// workspaces:
+///   - name: api
+///     path: ../backend
+///   - name: design
+///     path: ../design-system
+///     sandbox: read-only
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceRootSettings {
+    /// Name used in `@<name>/` paths
+    pub name: String,
+
+    /// Root directory, relative to the project directory unless absolute
+    pub path: String,
+
+    /// `read-only`, `workspace-write` or `full-access`; inherits the session
+    /// sandbox when unset
+    #[serde(default)]
+    pub sandbox: Option<String>,
+}
+
+// ============================================================================
+// Snapshot Configuration
+// ============================================================================
+
+/// Settings for git-backed working tree snapshots
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotSettings {
+    /// Snapshot before shell commands that need approval and after each
+    /// verified ralph iteration (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Snapshots kept per repository before the oldest are pruned (default: 50)
+    #[serde(default = "default_max_snapshots")]
+    pub max_snapshots: usize,
+}
+
+impl Default for SnapshotSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_snapshots: default_max_snapshots(),
+        }
+    }
+}
+
+fn default_max_snapshots() -> usize {
+    50
+}
+
+// ============================================================================
+// Workspace Index Configuration
+// ============================================================================
+
+/// Settings for the workspace indexer that serves file, symbol and semantic
+/// queries to explore agents
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WorkspaceIndexSettings {
+    /// Build and watch the index during interactive sessions (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Seconds between change scans (default: 5)
+    #[serde(default = "default_index_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+
+    /// Stop indexing after this many files (default: 50000)
+    #[serde(default = "default_index_max_files")]
+    pub max_files: usize,
+
+    /// Skip symbol extraction for files larger than this (default: 512 KiB)
+    #[serde(default = "default_index_max_file_bytes")]
+    pub max_file_bytes: u64,
+
+    /// Ask running language servers for symbols instead of the built-in
+    /// extractor (default: false)
+    #[serde(default)]
+    pub lsp_symbols: bool,
+
+    /// Embed files with the memory system's embedding provider for semantic
+    /// queries; needs `memory.enabled` (default: false)
+    #[serde(default)]
+    pub embeddings: bool,
+}
+
+impl Default for WorkspaceIndexSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            poll_interval_secs: default_index_poll_interval_secs(),
+            max_files: default_index_max_files(),
+            max_file_bytes: default_index_max_file_bytes(),
+            lsp_symbols: false,
+            embeddings: false,
+        }
+    }
+}
+
+fn default_index_poll_interval_secs() -> u64 {
+    5
+}
+
+fn default_index_max_files() -> usize {
+    50_000
+}
+
+fn default_index_max_file_bytes() -> u64 {
+    512 * 1024
+}
+
+/// Settings for the repository map injected into the first prompt
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RepoMapSettings {
+    /// Add the map at session start (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// Approximate size budget in tokens (default: 1500)
+    #[serde(default = "default_repo_map_max_tokens")]
+    pub max_tokens: usize,
+}
+
+impl Default for RepoMapSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            max_tokens: default_repo_map_max_tokens(),
+        }
+    }
+}
+
+fn default_repo_map_max_tokens() -> usize {
+    1500
+}
+
+// ============================================================================
+// Routing Configuration
+// ============================================================================
+
+/// How a delegated task picks its model tier
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum RoutingStrategy {
+    /// Use the agent's configured model or the default model
+    #[default]
+    Fixed,
+    /// Pick the tier from the task's complexity
+    Complexity,
+    /// Like `complexity`, moving up a tier when routing confidence is low
+    Escalate,
+    /// Start low-confidence tasks on the cheaper tier and switch to the next
+    /// tier, replaying the context, if the first response looks inadequate
+    Speculative,
+}
+
+/// Settings for routing delegated tasks to model tiers
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoutingSettings {
+    #[serde(default)]
+    pub strategy: RoutingStrategy,
+
+    /// Routing confidence (0.5–0.9) below which `escalate` and `speculative`
+    /// kick in (default: 0.7)
+    #[serde(default = "default_routing_confidence_threshold")]
+    pub confidence_threshold: f64,
+
+    /// Models a delegating call may pick with `provider`/`model`, as
+    /// `provider/model`, `provider/*` or `*` (default: any)
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_models: Vec<String>,
+
+    /// Highest output price, in USD per 1M tokens, of a model a delegating
+    /// call may pick; models without a known price are allowed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_output_price: Option<f64>,
+}
+
+impl Default for RoutingSettings {
+    fn default() -> Self {
+        Self {
+            strategy: RoutingStrategy::default(),
+            confidence_threshold: default_routing_confidence_threshold(),
+            allowed_models: Vec::new(),
+            max_output_price: None,
+        }
+    }
+}
+
+fn default_routing_confidence_threshold() -> f64 {
+    0.7
+}
+
+// ============================================================================
+// Consensus Configuration
+// ============================================================================
+
+/// How an agent produces its answers
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum ExecutionStrategy {
+    /// One model answers
+    #[default]
+    Single,
+    /// Plans and reviews are also answered by a panel of models in parallel
+    /// and reconciled by a judge, with disagreements recorded in the session
+    /// log
+    Consensus,
+}
+
+/// Models consulted by the `consensus` strategy
+///
+/// ```yaml
+/// consensus:
+///   models: [openai/gpt-4.1, gemini/gemini-2.5-pro]
+///   judge_model: anthropic/claude-opus-4-1
+/// agents:
+///   architect:
+///     strategy: consensus
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ConsensusSettings {
+    /// Models answering alongside the agent's own model; at most two are
+    /// used, so a decision draws on two or three models
+    #[serde(default)]
+    pub models: Vec<String>,
+
+    /// Model reconciling the answers (default: the agent's own model)
+    #[serde(default)]
+    pub judge_model: Option<String>,
+}
+
+// ============================================================================
+// Output Contract Configuration
+// ============================================================================
+
+/// Format an agent's final message must follow, so orchestrators can consume
+/// it without parsing prose. A final message that breaks the contract is
+/// sent back to the model once with the violation; a second violation fails
+/// the run.
+///
+/// ```yaml
+/// agents:
+///   executor:
+///     output_contract:
+///       format: unified-diff
+///   reviewer:
+///     output_contract:
+///       format: json
+///       schema:
+///         type: object
+///         properties:
+///           verdict: { type: string, enum: [approve, reject] }
+///         required: [verdict]
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "format", rename_all = "kebab-case")]
+pub enum OutputContract {
+    /// Any text
+    #[default]
+    Freeform,
+    /// A unified diff and nothing else
+    UnifiedDiff,
+    /// A single JSON value, checked against `schema` when one is given
+    Json {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        schema: Option<serde_json::Value>,
+    },
+}
+
+impl OutputContract {
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Freeform => "freeform",
+            Self::UnifiedDiff => "unified-diff",
+            Self::Json { .. } => "json",
+        }
+    }
+}
+
+// ============================================================================
+// Delegation Cache Configuration
+// ============================================================================
+
+/// Reuse of delegated results for repeated tasks. A foreground
+/// `delegate_task` call to one of `agents` returns the earlier result when
+/// the same agent got the same prompt (ignoring case and whitespace) within
+/// `ttl_secs` and the working tree hasn't changed since.
+///
+/// ```yaml
+/// delegation_cache:
+///   ttl_secs: 900
+///   agents: [explore, librarian]
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DelegationCacheSettings {
+    /// Whether delegated results are cached (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    /// How long a result stays reusable, in seconds (default: 600)
+    #[serde(default = "default_delegation_cache_ttl_secs")]
+    pub ttl_secs: u64,
+
+    /// Agents whose results are cached; read-only agents only, since a
+    /// cached result skips the agent's side effects (default: explore, librarian)
+    #[serde(default = "default_delegation_cache_agents")]
+    pub agents: Vec<String>,
+}
+
+impl Default for DelegationCacheSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            ttl_secs: default_delegation_cache_ttl_secs(),
+            agents: default_delegation_cache_agents(),
+        }
+    }
+}
+
+fn default_delegation_cache_ttl_secs() -> u64 {
+    600
+}
+
+fn default_delegation_cache_agents() -> Vec<String> {
+    vec!["explore".to_string(), "librarian".to_string()]
+}
+
+// ============================================================================
+// Experiments Configuration
+// ============================================================================
+
+/// An A/B experiment: each session is assigned one variant at random, in
+/// proportion to the weights, and its log is tagged with the variant so
+/// `uira experiments report` can compare outcomes
+///
+/// ```yaml
+/// experiments:
+///   - name: terse-prompt
+///     variants:
+///       - name: control
+///       - name: terse
+///         prompt_append: "Answer in as few words as possible."
+///   - name: cheap-model
+///     variants:
+///       - name: control
+///         weight: 3
+///       - name: haiku
+///         model: claude-haiku-4-5
+/// ```
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentSettings {
+    pub name: String,
+
+    /// Assign new sessions to this experiment (default: true)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub variants: Vec<ExperimentVariant>,
+}
+
+/// One arm of an experiment; a variant without overrides is the control
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ExperimentVariant {
+    pub name: String,
+
+    /// Relative share of sessions (default: 1, 0 disables the variant)
+    #[serde(default = "default_experiment_weight")]
+    pub weight: u32,
+
+    /// Model used with the session's provider unless `--model` is given
+    #[serde(default)]
+    pub model: Option<String>,
+
+    /// Replaces the system prompt
+    #[serde(default)]
+    pub system_prompt: Option<String>,
+
+    /// Appended to the system prompt
+    #[serde(default)]
+    pub prompt_append: Option<String>,
+}
+
+fn default_experiment_weight() -> u32 {
+    1
+}
+
+// ============================================================================
+// Code Host Configuration
+// ============================================================================
+
+/// Service hosting the repository's issues and pull requests
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CodeHostKind {
+    GitHub,
+    GitLab,
+    /// Gitea and Forgejo, including Codeberg
+    Gitea,
+}
+
+/// Code host integration
+///
+/// The host and its API are detected from the `origin` remote. Tokens come
+/// from `GH_TOKEN` / `GITHUB_TOKEN` or `gh` for GitHub, `GITLAB_TOKEN` /
+/// `GL_TOKEN` or `glab` for GitLab, and `GITEA_TOKEN` for Gitea. `uira pr`
+/// works without `enabled`, which only controls the agent tools.
+///
+/// ```yaml
+/// code_host:
+///   enabled: true
+///   kind: gitlab
+///   api_url: https://git.example.com/api/v4
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CodeHostSettings {
+    /// Give the agent issue and pull request tools (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// Host type, for self-hosted instances on unrecognizable domains
+    /// (default: detected from the remote URL)
+    #[serde(default)]
+    pub kind: Option<CodeHostKind>,
+
+    /// REST API root (default: derived from the remote host)
+    #[serde(default)]
+    pub api_url: Option<String>,
+}
+
+// ============================================================================
+// CI Configuration
+// ============================================================================
+
+/// CI service the `ci_fetch_logs` tool reads failing jobs from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CiProviderKind {
+    GithubActions,
+    Buildkite,
+}
+
+/// CI log triage
+///
+/// GitHub Actions reads the `origin` repository with the `code_host` token
+/// and API root. Buildkite needs the pipeline and a `BUILDKITE_API_TOKEN`.
+///
+/// ```yaml
+/// ci:
+///   enabled: true
+///   buildkite:
+///     organization: acme
+///     pipeline: web
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct CiSettings {
+    /// Give the agent the `ci_fetch_logs` tool (default: false)
+    #[serde(default)]
+    pub enabled: bool,
+
+    /// CI service (default: Buildkite when `buildkite` is set, else GitHub
+    /// Actions)
+    #[serde(default)]
+    pub provider: Option<CiProviderKind>,
+
+    #[serde(default)]
+    pub buildkite: Option<BuildkiteSettings>,
+}
+
+impl CiSettings {
+    pub fn effective_provider(&self) -> CiProviderKind {
+        self.provider.unwrap_or(if self.buildkite.is_some() {
+            CiProviderKind::Buildkite
+        } else {
+            CiProviderKind::GithubActions
+        })
+    }
+}
+
+/// Buildkite pipeline whose builds are read
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BuildkiteSettings {
+    /// Organization slug
+    pub organization: String,
+
+    /// Pipeline slug
+    pub pipeline: String,
+
+    /// REST API root (default: https://api.buildkite.com/v2)
+    #[serde(default)]
+    pub api_url: Option<String>,
+}
+
+// ============================================================================
+// Channel Configuration
+// ============================================================================
+
+/// Channel settings for multi-channel messaging
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ChannelSettings {
+    #[serde(default)]
+    pub telegram: Option<TelegramChannelConfig>,
+
+    #[serde(default)]
+    pub telegram_accounts: Vec<TelegramChannelConfig>,
+
+    #[serde(default)]
+    pub slack: Option<SlackChannelConfig>,
+
+    #[serde(default)]
+    pub slack_accounts: Vec<SlackChannelConfig>,
+
+    #[serde(default)]
+    pub discord: Option<DiscordChannelConfig>,
+
+    #[serde(default)]
+    pub discord_accounts: Vec<DiscordChannelConfig>,
+
+    /// Abuse protection applied to messages from every channel
+    #[serde(default)]
+    pub limits: ChannelLimitsConfig,
+}
+
+fn default_rate_limit_messages() -> usize {
+    10
+}
+
+fn default_rate_limit_window_secs() -> u64 {
+    60
+}
+
+fn default_max_prompt_chars() -> usize {
+    8000
+}
+
+/// Per-user limits the gateway enforces before a channel message reaches a session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChannelLimitsConfig {
+    /// Messages one user may send per window before being throttled. Default: 10
+    #[serde(default = "default_rate_limit_messages")]
+    pub messages_per_window: usize,
+
+    /// Length of the rate limit window in seconds. Default: 60
+    #[serde(default = "default_rate_limit_window_secs")]
+    pub window_secs: u64,
+
+    /// User IDs allowed to talk to the bot, as `<id>` or `<channel>:<id>`
+    /// (e.g. `slack:U0123`). Empty allows everyone.
+    #[serde(default)]
+    pub allowed_users: Vec<String>,
+
+    /// Longest message accepted, in characters; 0 disables the cap. Default: 8000
+    #[serde(default = "default_max_prompt_chars")]
+    pub max_prompt_chars: usize,
+
+    /// Tell users when their message was throttled or too long. Default: true
+    #[serde(default = "default_true")]
+    pub notify: bool,
+}
+
+impl Default for ChannelLimitsConfig {
+    fn default() -> Self {
+        Self {
+            messages_per_window: default_rate_limit_messages(),
+            window_secs: default_rate_limit_window_secs(),
+            allowed_users: Vec::new(),
+            max_prompt_chars: default_max_prompt_chars(),
+            notify: true,
+        }
+    }
+}
+
+fn default_account_id() -> String {
+    "default".to_string()
+}
+
+fn default_stream_mode() -> String {
+    "partial".to_string()
+}
 
 fn default_stream_throttle_ms() -> u64 {
     300
@@ -1041,6 +2407,33 @@ pub struct TelegramChannelConfig {
     /// Default: 300
     #[serde(default = "default_stream_throttle_ms")]
     pub stream_throttle_ms: u64,
+
+    /// Give each forum topic and each reply thread in a group its own session,
+    /// instead of one session per sender. Default: true
+    #[serde(default = "default_true")]
+    pub thread_sessions: bool,
+
+    /// Per-topic agent and skill overrides
+    #[serde(default)]
+    pub topics: Vec<TelegramTopicConfig>,
+}
+
+/// Overrides for the session of one forum topic
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TelegramTopicConfig {
+    /// Chat ID of the forum supergroup, e.g. "-1001234567890"
+    pub chat_id: String,
+
+    /// Topic ID (the `message_thread_id` of messages in the topic)
+    pub thread_id: i32,
+
+    /// Agent personality for the topic's session, overriding `gateway.default_agent`
+    #[serde(default)]
+    pub agent: Option<String>,
+
+    /// Skills for the topic's session, replacing the account's `active_skills`
+    #[serde(default)]
+    pub skills: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1424,6 +2817,7 @@ target: 100
         assert_eq!(goal.timeout_secs, 60);
         assert!(goal.enabled);
         assert!(goal.workspace.is_none());
+        assert!(goal.changed_command.is_none());
         assert!(goal.description.is_none());
     }
 
@@ -1489,6 +2883,29 @@ ai:
         assert!(settings.ai.include_docstrings);
     }
 
+    #[test]
+    fn test_commit_msg_settings() {
+        let settings = CommitMsgSettings::default();
+        assert!(settings.types.contains(&"feat".to_string()));
+        assert!(settings.scopes.is_empty());
+        assert_eq!(settings.max_header_length, 72);
+
+        let yaml = r#"
+types: [feat, fix]
+require_scope: true
+ai:
+  model: openai/gpt-4o
+"#;
+        let settings: CommitMsgSettings = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(settings.types, vec!["feat", "fix"]);
+        assert!(settings.require_scope);
+        assert_eq!(settings.max_header_length, 72);
+        assert_eq!(
+            settings.ai.parse_model(),
+            ("openai".to_string(), "gpt-4o".to_string())
+        );
+    }
+
     #[test]
     fn test_full_config_with_diagnostics_comments() {
         let yaml = r#"
@@ -1533,13 +2950,16 @@ rules:
   - permission: "shell:execute"
     pattern: "**"
     action: ask
+    priority: 5
 "#;
         let settings: PermissionsSettings = serde_yaml_ng::from_str(yaml).unwrap();
         assert_eq!(settings.rules.len(), 3);
         assert_eq!(settings.rules[0].permission, "file:read");
         assert_eq!(settings.rules[0].action, PermissionActionConfig::Allow);
+        assert_eq!(settings.rules[0].priority, 0);
         assert_eq!(settings.rules[1].name, Some("allow-src-writes".to_string()));
         assert_eq!(settings.rules[2].action, PermissionActionConfig::Ask);
+        assert_eq!(settings.rules[2].priority, 5);
     }
 
     #[test]
@@ -1605,6 +3025,31 @@ mcp:
         );
     }
 
+    #[test]
+    fn test_mcp_server_is_remote() {
+        let yaml = r#"
+mcp:
+   servers:
+     docs:
+       command: npx -y @upstash/context7-mcp
+     linear:
+       command: mcp-remote
+       args: ["https://mcp.linear.app/sse"]
+     local:
+       command: ./target/release/my-mcp
+     pinned:
+       command: npx
+       args: ["-y", "server-with-bundled-deps"]
+       remote: false
+"#;
+
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.mcp.get("docs").unwrap().is_remote());
+        assert!(config.mcp.get("linear").unwrap().is_remote());
+        assert!(!config.mcp.get("local").unwrap().is_remote());
+        assert!(!config.mcp.get("pinned").unwrap().is_remote());
+    }
+
     #[test]
     fn test_tui_theme_defaults() {
         let config = UiraConfig::default();
@@ -1770,6 +3215,372 @@ gateway:
         assert_eq!(config.gateway.max_sessions, 20);
     }
 
+    #[test]
+    fn test_full_config_with_scheduler() {
+        let yaml = r#"
+scheduler:
+  enabled: true
+  jobs:
+    - name: triage
+      schedule: "0 9 * * 1-5"
+      prompt: Triage new GitHub issues
+      channel: slack
+      recipient: C12345
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.scheduler.enabled);
+        let job = &config.scheduler.jobs[0];
+        assert_eq!(job.name, "triage");
+        assert_eq!(job.schedule, "0 9 * * 1-5");
+        assert_eq!(job.channel.as_deref(), Some("slack"));
+        assert_eq!(job.timeout_secs, 3600);
+        assert!(job.enabled);
+        assert!(job.agent.is_none());
+    }
+
+    #[test]
+    fn test_background_task_retry_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert_eq!(config.background_tasks.retry, TaskRetrySettings::default());
+        assert_eq!(config.background_tasks.retry.max_attempts, 3);
+
+        let yaml = r#"
+background_tasks:
+  retry:
+    max_attempts: 5
+    retry_on: [rate_limit, other]
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        let retry = config.background_tasks.retry;
+        assert_eq!(retry.max_attempts, 5);
+        assert_eq!(retry.backoff_ms, 2000);
+        assert_eq!(
+            retry.retry_on,
+            vec![TaskFailureKind::RateLimit, TaskFailureKind::Other]
+        );
+    }
+
+    #[test]
+    fn test_autopilot_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert_eq!(config.autopilot, AutopilotSettings::default());
+        assert!(!config.autopilot.require_approval);
+
+        let yaml = r#"
+autopilot:
+  require_approval: true
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.autopilot.require_approval);
+        assert_eq!(config.autopilot.max_iterations, 10);
+    }
+
+    #[test]
+    fn test_notification_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert_eq!(config.notifications, NotificationSettings::default());
+        assert!(!config.notifications.enabled);
+
+        let yaml = r#"
+notifications:
+  enabled: true
+  min_duration_secs: 5
+  command: ["terminal-notifier", "-title", "{title}", "-message", "{body}"]
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.notifications.enabled);
+        assert!(config.notifications.background_tasks);
+        assert_eq!(config.notifications.min_duration_secs, 5);
+        assert_eq!(config.notifications.command.len(), 5);
+    }
+
+    #[test]
+    fn test_experiment_settings() {
+        let yaml = r#"
+experiments:
+  - name: terse-prompt
+    variants:
+      - name: control
+      - name: terse
+        weight: 2
+        prompt_append: Be terse.
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        let experiment = &config.experiments[0];
+        assert!(experiment.enabled);
+        assert_eq!(experiment.variants[0].weight, 1);
+        assert_eq!(experiment.variants[0].prompt_append, None);
+        assert_eq!(experiment.variants[1].weight, 2);
+        assert_eq!(
+            experiment.variants[1].prompt_append.as_deref(),
+            Some("Be terse.")
+        );
+    }
+
+    #[test]
+    fn test_retention_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert_eq!(config.retention, RetentionSettings::default());
+        assert!(config.retention.auto);
+        assert_eq!(config.retention.max_sessions, 500);
+
+        let yaml = r#"
+retention:
+  auto: false
+  max_age_days: 30
+  compress_after_days: 0
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(!config.retention.auto);
+        assert_eq!(config.retention.max_age_days, 30);
+        assert_eq!(config.retention.compress_after_days, 0);
+        assert_eq!(config.retention.max_disk_mb, 1024);
+    }
+
+    #[test]
+    fn test_auto_approve_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert_eq!(
+            config.permissions.auto_approve,
+            AutoApproveSettings::default()
+        );
+        assert_eq!(
+            config.permissions.auto_approve.workspace_write,
+            vec![ToolClassConfig::ReadOnly, ToolClassConfig::WorkspaceWrite]
+        );
+
+        let yaml = r#"
+permissions:
+  auto_approve:
+    full_access: [read-only, workspace-write, network]
+    read_only: []
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        let auto_approve = &config.permissions.auto_approve;
+        assert!(auto_approve.read_only.is_empty());
+        assert_eq!(auto_approve.full_access.len(), 3);
+        assert_eq!(auto_approve.full_access[2], ToolClassConfig::Network);
+        assert_eq!(auto_approve.workspace_write.len(), 2);
+    }
+
+    #[test]
+    fn test_explain_commands_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert!(config.permissions.explain_commands.enabled);
+        assert_eq!(config.permissions.explain_commands.model, None);
+
+        let yaml = r#"
+permissions:
+  explain_commands:
+    model: anthropic/claude-haiku-4-5
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        let explain = &config.permissions.explain_commands;
+        assert!(explain.enabled);
+        assert_eq!(explain.model.as_deref(), Some("anthropic/claude-haiku-4-5"));
+    }
+
+    #[test]
+    fn test_workspace_roots() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert!(config.workspaces.is_empty());
+
+        let yaml = r#"
+workspaces:
+  - name: api
+    path: ../backend
+  - name: design
+    path: /src/design-system
+    sandbox: read-only
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(config.workspaces.len(), 2);
+        assert_eq!(config.workspaces[0].name, "api");
+        assert!(config.workspaces[0].sandbox.is_none());
+        assert_eq!(config.workspaces[1].sandbox.as_deref(), Some("read-only"));
+    }
+
+    #[test]
+    fn test_snapshot_settings() {
+        let config = UiraConfig::default();
+        assert!(config.snapshots.enabled);
+        assert_eq!(config.snapshots.max_snapshots, 50);
+
+        let yaml = r#"
+snapshots:
+  enabled: false
+  max_snapshots: 10
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(!config.snapshots.enabled);
+        assert_eq!(config.snapshots.max_snapshots, 10);
+    }
+
+    #[test]
+    fn test_workspace_index_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert_eq!(config.workspace_index, WorkspaceIndexSettings::default());
+        assert!(config.workspace_index.enabled);
+
+        let yaml = r#"
+workspace_index:
+  poll_interval_secs: 30
+  embeddings: true
+"#;
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(config.workspace_index.poll_interval_secs, 30);
+        assert!(config.workspace_index.embeddings);
+        assert_eq!(config.workspace_index.max_files, 50_000);
+    }
+
+    #[test]
+    fn test_repo_map_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert!(config.repo_map.enabled);
+        assert_eq!(config.repo_map.max_tokens, 1500);
+
+        let config: UiraConfig = serde_yaml_ng::from_str("repo_map:\n  enabled: false\n").unwrap();
+        assert!(!config.repo_map.enabled);
+        assert_eq!(config.repo_map.max_tokens, 1500);
+    }
+
+    #[test]
+    fn test_routing_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert_eq!(config.routing.strategy, RoutingStrategy::Fixed);
+        assert!((config.routing.confidence_threshold - 0.7).abs() < f64::EPSILON);
+
+        let yaml = "routing:\n  strategy: speculative\n  confidence_threshold: 0.8\n";
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(config.routing.strategy, RoutingStrategy::Speculative);
+        assert!((config.routing.confidence_threshold - 0.8).abs() < f64::EPSILON);
+        assert!(config.routing.allowed_models.is_empty());
+
+        let yaml =
+            "routing:\n  allowed_models: [anthropic/*, openrouter/*]\n  max_output_price: 5\n";
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(
+            config.routing.allowed_models,
+            vec!["anthropic/*", "openrouter/*"]
+        );
+        assert_eq!(config.routing.max_output_price, Some(5.0));
+    }
+
+    #[test]
+    fn test_consensus_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert!(config.consensus.models.is_empty());
+        assert!(config.consensus.judge_model.is_none());
+
+        let yaml = "consensus:\n  models: [openai/gpt-4.1]\n  judge_model: anthropic/claude-opus-4-1\nagents:\n  architect:\n    strategy: consensus\n    consensus:\n      models: [gemini/gemini-2.5-pro]\n";
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(config.consensus.models, vec!["openai/gpt-4.1"]);
+        let architect = &config.agents.agents["architect"];
+        assert_eq!(architect.strategy, Some(ExecutionStrategy::Consensus));
+        assert_eq!(
+            architect.consensus.as_ref().unwrap().models,
+            vec!["gemini/gemini-2.5-pro"]
+        );
+    }
+
+    #[test]
+    fn test_delegation_cache_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert_eq!(config.delegation_cache, DelegationCacheSettings::default());
+        assert!(config.delegation_cache.enabled);
+        assert_eq!(config.delegation_cache.agents, ["explore", "librarian"]);
+
+        let yaml = "delegation_cache:\n  ttl_secs: 60\n  agents: [explore]\n";
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.delegation_cache.enabled);
+        assert_eq!(config.delegation_cache.ttl_secs, 60);
+        assert_eq!(config.delegation_cache.agents, ["explore"]);
+    }
+
+    #[test]
+    fn test_output_contract_settings() {
+        let yaml = "agents:\n  executor:\n    output_contract:\n      format: unified-diff\n  reviewer:\n    output_contract:\n      format: json\n      schema:\n        type: object\n        required: [verdict]\n  explore: {}\n";
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        let agents = &config.agents.agents;
+        assert_eq!(
+            agents["executor"].output_contract,
+            Some(OutputContract::UnifiedDiff)
+        );
+        assert_eq!(
+            agents["reviewer"].output_contract,
+            Some(OutputContract::Json {
+                schema: Some(serde_json::json!({ "type": "object", "required": ["verdict"] })),
+            })
+        );
+        assert_eq!(agents["explore"].output_contract, None);
+    }
+
+    #[test]
+    fn test_code_host_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert_eq!(config.code_host, CodeHostSettings::default());
+
+        let yaml = "code_host:\n  enabled: true\n  kind: gitlab\n  api_url: https://git.example.com/api/v4\n";
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.code_host.enabled);
+        assert_eq!(config.code_host.kind, Some(CodeHostKind::GitLab));
+        assert_eq!(
+            config.code_host.api_url.as_deref(),
+            Some("https://git.example.com/api/v4")
+        );
+
+        let config: UiraConfig = serde_yaml_ng::from_str("github:\n  enabled: true\n").unwrap();
+        assert!(config.code_host.enabled);
+        assert_eq!(config.code_host.kind, None);
+    }
+
+    #[test]
+    fn test_ci_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        assert_eq!(config.ci, CiSettings::default());
+        assert_eq!(
+            config.ci.effective_provider(),
+            CiProviderKind::GithubActions
+        );
+
+        let yaml =
+            "ci:\n  enabled: true\n  buildkite:\n    organization: acme\n    pipeline: web\n";
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert!(config.ci.enabled);
+        assert_eq!(config.ci.effective_provider(), CiProviderKind::Buildkite);
+        assert_eq!(config.ci.buildkite.unwrap().pipeline, "web");
+
+        let yaml = "ci:\n  provider: github_actions\n  buildkite:\n    organization: acme\n    pipeline: web\n";
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        assert_eq!(
+            config.ci.effective_provider(),
+            CiProviderKind::GithubActions
+        );
+    }
+
+    #[test]
+    fn test_payload_log_settings() {
+        let config: UiraConfig = serde_yaml_ng::from_str("{}").unwrap();
+        let log = config.providers.effective_payload_log();
+        assert!(!log.enabled);
+        assert_eq!(log.max_string_bytes, 4096);
+        assert_eq!(log.max_files, 5);
+        assert!(log.compress);
+
+        let yaml = "providers:\n  anthropic:\n    payload_log:\n      enabled: true\n      path: /tmp/a.jsonl\n";
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        let log = config.providers.effective_payload_log();
+        assert!(log.enabled);
+        assert_eq!(log.providers, vec!["anthropic"]);
+        assert_eq!(log.path.as_deref(), Some("/tmp/a.jsonl"));
+
+        let yaml = "providers:\n  payload_log:\n    enabled: true\n    providers: [openai]\n    compress: false\n";
+        let config: UiraConfig = serde_yaml_ng::from_str(yaml).unwrap();
+        let log = config.providers.effective_payload_log();
+        assert_eq!(log.providers, vec!["openai"]);
+        assert!(!log.compress);
+    }
+
     #[test]
     fn test_full_config_with_channels() {
         let yaml = r#"
@@ -99,10 +99,12 @@ impl From<ThreadEvent> for Event {
             ThreadEvent::Error {
                 message,
                 recoverable,
+                detail,
             } => Event::Error {
                 session_id: String::new(),
                 message,
                 recoverable,
+                detail,
             },
             ThreadEvent::ThreadCompleted { usage: _ } => Event::SessionEnded {
                 session_id: String::new(),
@@ -255,10 +257,12 @@ impl From<Event> for Option<ThreadEvent> {
             Event::Error {
                 message,
                 recoverable,
+                detail,
                 ..
             } => Some(ThreadEvent::Error {
                 message,
                 recoverable,
+                detail,
             }),
             Event::SessionEnded { reason, .. } => match reason {
                 SessionEndReason::Completed => Some(ThreadEvent::ThreadCompleted {
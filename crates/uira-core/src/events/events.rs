@@ -1,4 +1,4 @@
-use crate::TokenUsage;
+use crate::{TokenUsage, UiraError};
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -239,6 +239,8 @@ pub enum Event {
         session_id: String,
         message: String,
         recoverable: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        detail: Option<UiraError>,
     },
 
     // ============================================================================
@@ -1,6 +1,10 @@
+#[cfg(feature = "native")]
 pub mod config;
+#[cfg(feature = "native")]
 pub mod events;
 pub mod protocol;
+#[cfg(feature = "native")]
+pub mod shell;
 
 pub const UIRA_DIR: &str = ".uira";
 pub const DEFAULT_ANTHROPIC_MODEL: &str = "claude-sonnet-4-20250514";
@@ -11,16 +15,26 @@ pub const ENV_GEMINI_API_KEY: &str = "GEMINI_API_KEY";
 pub const ENV_GOOGLE_API_KEY: &str = "GOOGLE_API_KEY";
 pub const ENV_FRIENDLI_TOKEN: &str = "FRIENDLI_TOKEN";
 
+#[cfg(feature = "native")]
 pub use config::*;
+#[cfg(feature = "native")]
 pub use events::*;
+#[cfg(feature = "native")]
+pub use shell::{shell_command, ShellKind, SHELL_ENV};
 
 // Selective re-exports from protocol to avoid collisions with:
 // - FileChangeType (exists in events::*)
 // - HookCommand (exists in config::*)
 // Consumers needing protocol-specific versions can use uira_core::protocol::*
 
+// From protocol/error.rs
+pub use protocol::{ErrorKind, UiraError};
+
 // From protocol/events.rs (excluding FileChangeType)
-pub use protocol::{AgentError, AgentState, ExecutionResult, Item, Progress, ThreadEvent};
+pub use protocol::{
+    AgentError, AgentState, CommandExplanationEvent, ExecutionResult, Item, Progress, ThreadEvent,
+    ToolCallStats,
+};
 
 // From protocol/messages.rs
 pub use protocol::{
@@ -36,13 +50,16 @@ pub use protocol::{
 
 // From protocol/types.rs
 pub use protocol::{
-    MessageId, ModelTier, Provider, SessionId, StopReason, ThreadId, TodoItem, TodoPriority,
-    TodoStatus, TokenUsage, WorkspaceConfig, TODO_CONTINUATION_PROMPT,
+    actionable_todos, todo_tree, validate_todos, MessageId, ModelTier, Provider, SessionId,
+    StopReason, ThreadId, TodoEstimate, TodoItem, TodoPriority, TodoStatus, TokenUsage,
+    WorkspaceConfig, TODO_CONTINUATION_PROMPT,
 };
 
 // From protocol/primitives (excluding HookCommand, HookMatcher, OnFail which collide or depend on HookCommand)
+#[cfg(feature = "native")]
+pub use protocol::{atomic_write, atomic_write_secure};
 pub use protocol::{
-    atomic_write, atomic_write_secure, HookContext, HookEvent, HookEventParseError, HookOutput,
-    HookResult, PermissionDecision, PermissionMode, PostToolUseInput, PreCompactInput,
-    PreToolUseInput, SessionInfo, StopInput, ToolResponse, UserPromptSubmitInput,
+    HookContext, HookEvent, HookEventParseError, HookOutput, HookResult, PermissionDecision,
+    PermissionMode, PostToolUseInput, PreCompactInput, PreToolUseInput, SessionInfo, StopInput,
+    ToolResponse, UserPromptSubmitInput,
 };
@@ -0,0 +1,152 @@
+//! Shared error taxonomy
+//!
+//! Every crate keeps its own error enum; [`UiraError`] is the common shape
+//! they convert into at the edges (agent events, RPC, the gateway, the C
+//! ABI) so hosts can branch on `kind`/`code` and show `remediation` instead
+//! of pattern-matching on messages.
+
+use serde::{Deserialize, Serialize};
+
+use super::AgentError;
+
+/// Broad class of a failure, deciding who can fix it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// Bad input or configuration; the user can fix it
+    UserError,
+    /// The model provider failed or rejected the request
+    ProviderError,
+    /// A tool failed while running
+    ToolError,
+    /// A sandbox, permission or approval rule blocked the action
+    PolicyDenial,
+    /// A limit ran out: context window, turns, quota, sessions
+    Budget,
+    /// A bug or unexpected state inside uira
+    Internal,
+}
+
+impl ErrorKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::UserError => "user_error",
+            Self::ProviderError => "provider_error",
+            Self::ToolError => "tool_error",
+            Self::PolicyDenial => "policy_denial",
+            Self::Budget => "budget",
+            Self::Internal => "internal",
+        }
+    }
+}
+
+impl std::fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// A classified error as hosts see it
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error, Serialize, Deserialize)]
+#[error("{message}")]
+pub struct UiraError {
+    pub kind: ErrorKind,
+    /// Stable dotted identifier such as `provider.rate_limited`; new codes
+    /// may be added, existing ones are never renamed
+    pub code: String,
+    pub message: String,
+    /// Trying the same thing again may succeed
+    #[serde(default)]
+    pub retryable: bool,
+    /// What the user can do about it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<String>,
+}
+
+impl UiraError {
+    pub fn new(kind: ErrorKind, code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            kind,
+            code: code.into(),
+            message: message.into(),
+            retryable: false,
+            remediation: None,
+        }
+    }
+
+    pub fn internal(code: impl Into<String>, message: impl Into<String>) -> Self {
+        Self::new(ErrorKind::Internal, code, message)
+    }
+
+    pub fn with_retryable(mut self, retryable: bool) -> Self {
+        self.retryable = retryable;
+        self
+    }
+
+    pub fn with_remediation(mut self, remediation: impl Into<String>) -> Self {
+        self.remediation = Some(remediation.into());
+        self
+    }
+
+    /// Message followed by the remediation hint, for plain-text surfaces
+    pub fn display_with_hint(&self) -> String {
+        match &self.remediation {
+            Some(hint) => format!("{}\nHint: {}", self.message, hint),
+            None => self.message.clone(),
+        }
+    }
+}
+
+impl From<&AgentError> for UiraError {
+    fn from(error: &AgentError) -> Self {
+        let message = error.to_string();
+        match error {
+            AgentError::ToolError { .. } => {
+                Self::new(ErrorKind::ToolError, "agent.tool_error", message).with_retryable(true)
+            }
+            AgentError::Cancelled => Self::new(ErrorKind::UserError, "agent.cancelled", message),
+            AgentError::MaxTurnsExceeded { .. } => {
+                Self::new(ErrorKind::Budget, "agent.max_turns_exceeded", message).with_remediation(
+                    "Raise `max_turns` in the agent config or split the task into smaller requests",
+                )
+            }
+            AgentError::OutputContractViolated { .. } => {
+                Self::new(ErrorKind::ProviderError, "agent.output_contract", message).with_remediation(
+                    "Loosen the agent's `output_contract` or make the task's expected output explicit in the prompt",
+                )
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn serializes_kind_in_snake_case_and_omits_empty_hint() {
+        let error = UiraError::new(ErrorKind::PolicyDenial, "tool.sandbox_denied", "denied");
+        let value = serde_json::to_value(&error).unwrap();
+        assert_eq!(value["kind"], "policy_denial");
+        assert_eq!(value["code"], "tool.sandbox_denied");
+        assert_eq!(value["retryable"], false);
+        assert!(value.get("remediation").is_none());
+
+        let parsed: UiraError = serde_json::from_value(
+            serde_json::json!({"kind": "budget", "code": "x", "message": "m"}),
+        )
+        .unwrap();
+        assert_eq!(parsed.kind, ErrorKind::Budget);
+        assert!(!parsed.retryable);
+    }
+
+    #[test]
+    fn classifies_agent_errors() {
+        let error = UiraError::from(&AgentError::MaxTurnsExceeded { turns: 50 });
+        assert_eq!(error.kind, ErrorKind::Budget);
+        assert_eq!(error.code, "agent.max_turns_exceeded");
+        assert!(error
+            .display_with_hint()
+            .contains("\nHint: Raise `max_turns`"));
+    }
+}
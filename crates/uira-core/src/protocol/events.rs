@@ -1,5 +1,6 @@
 //! Event types for streaming and JSONL output
 
+use super::UiraError;
 use crate::TokenUsage;
 use serde::{Deserialize, Serialize};
 use std::path::PathBuf;
@@ -21,6 +22,19 @@ pub enum ThreadEvent {
         usage: TokenUsage,
     },
 
+    /// Timing breakdown for a finished turn, including its tool calls
+    TurnSummary {
+        turn_number: usize,
+        duration_ms: u64,
+        /// Time spent waiting on the model provider
+        provider_latency_ms: u64,
+        /// Provider request retries during the turn
+        retries: u32,
+        tools: Vec<ToolCallStats>,
+        context_tokens_before: usize,
+        context_tokens_after: usize,
+    },
+
     /// An item has started processing
     ItemStarted { item: Item },
 
@@ -33,11 +47,25 @@ pub enum ThreadEvent {
     /// Thinking/reasoning content is being streamed
     ThinkingDelta { thinking: String },
 
+    /// Arguments of a tool call parsed so far while they stream, sent each
+    /// time another argument completes and before `ItemStarted`
+    ToolInputPreview {
+        tool_call_id: String,
+        tool_name: String,
+        input: serde_json::Value,
+    },
+
     /// Waiting for user input
     WaitingForInput { prompt: String },
 
     /// Error occurred
-    Error { message: String, recoverable: bool },
+    Error {
+        message: String,
+        recoverable: bool,
+        /// Classification and remediation hint, when the source error has one
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        detail: Option<UiraError>,
+    },
 
     /// Thread has completed
     ThreadCompleted { usage: TokenUsage },
@@ -79,6 +107,17 @@ pub enum ThreadEvent {
     /// Ralph circuit breaker tripped
     RalphCircuitBreak { reason: String, iteration: u32 },
 
+    // Autopilot Events
+    /// Autopilot moved between phases, or finished one and waits for approval
+    AutopilotPhaseChanged {
+        from: String,
+        to: String,
+        iteration: u32,
+        max_iterations: u32,
+        /// `to` has not started yet and needs user approval
+        awaiting_approval: bool,
+    },
+
     // Background Task Events
     /// Background task has been spawned
     BackgroundTaskSpawned {
@@ -118,6 +157,14 @@ pub enum ThreadEvent {
     /// Model was switched at runtime
     ModelSwitched { model: String, provider: String },
 
+    /// A consensus panel answered and the judge reconciled the answers
+    ConsensusReached {
+        models: Vec<String>,
+        judge: String,
+        /// Points the answers disagreed on, as reported by the judge
+        disagreements: Vec<String>,
+    },
+
     // Permission/Approval/Compaction Events
     /// Permission was evaluated for a tool
     PermissionEvaluated {
@@ -132,6 +179,8 @@ pub enum ThreadEvent {
         pattern: String,
         decision: String,
     },
+    /// A risky shell command was explained before asking to run it
+    CommandExplained(Box<CommandExplanationEvent>),
     /// Context compaction started
     CompactionStarted {
         strategy: String,
@@ -148,6 +197,29 @@ pub enum ThreadEvent {
     TodoUpdated { todos: Vec<crate::TodoItem> },
 }
 
+/// Payload of [`ThreadEvent::CommandExplained`], boxed to keep the event small
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CommandExplanationEvent {
+    pub tool_call_id: String,
+    pub command: String,
+    /// Tool class the command was classified as
+    pub class: String,
+    pub summary: String,
+    /// Predicted effects of running the command
+    pub effects: Vec<String>,
+    /// Model that wrote the explanation; `None` when generated locally
+    pub model: Option<String>,
+}
+
+/// Aggregated calls to one tool within a turn
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolCallStats {
+    pub name: String,
+    pub calls: u32,
+    pub errors: u32,
+    pub total_ms: u64,
+}
+
 /// Item types that can be processed
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -265,6 +337,9 @@ pub enum AgentError {
 
     #[error("max turns exceeded: {turns}")]
     MaxTurnsExceeded { turns: usize },
+
+    #[error("final message violates the {contract} output contract: {violation}")]
+    OutputContractViolated { contract: String, violation: String },
 }
 
 impl AgentError {
@@ -329,6 +404,30 @@ mod tests {
         assert!(json.contains("\"type\":\"thread_started\""));
     }
 
+    #[test]
+    fn test_command_explained_keeps_flat_wire_format() {
+        let event = ThreadEvent::CommandExplained(Box::new(CommandExplanationEvent {
+            tool_call_id: "call_1".to_string(),
+            command: "git push --force".to_string(),
+            class: "git_write".to_string(),
+            summary: "Overwrites the remote branch".to_string(),
+            effects: vec!["Drops remote commits".to_string()],
+            model: None,
+        }));
+        let json = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["type"], "command_explained");
+        assert_eq!(json["command"], "git push --force");
+        let parsed: ThreadEvent = serde_json::from_value(json).unwrap();
+        assert!(matches!(parsed, ThreadEvent::CommandExplained(e) if e.class == "git_write"));
+    }
+
+    #[test]
+    fn test_thread_event_stays_small() {
+        // Large payloads are boxed so `Result<_, TrySendError<ThreadEvent>>`
+        // stays cheap to return
+        assert!(std::mem::size_of::<ThreadEvent>() <= 128);
+    }
+
     #[test]
     fn test_item_serialization() {
         let item = Item::AgentMessage {
@@ -421,6 +520,36 @@ mod tests {
         let json = serde_json::to_string(&event).unwrap();
         assert!(json.contains("\"type\":\"ralph_circuit_break\""));
 
+        // Turn summary
+        let event = ThreadEvent::TurnSummary {
+            turn_number: 3,
+            duration_ms: 1200,
+            provider_latency_ms: 800,
+            retries: 1,
+            tools: vec![ToolCallStats {
+                name: "Read".to_string(),
+                calls: 2,
+                errors: 0,
+                total_ms: 40,
+            }],
+            context_tokens_before: 1000,
+            context_tokens_after: 1500,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"type\":\"turn_summary\""));
+        assert!(json.contains("\"provider_latency_ms\":800"));
+
+        // Autopilot events
+        let event = ThreadEvent::AutopilotPhaseChanged {
+            from: "planning".to_string(),
+            to: "executing".to_string(),
+            iteration: 2,
+            max_iterations: 10,
+            awaiting_approval: true,
+        };
+        let json = serde_json::to_string(&event).unwrap();
+        assert!(json.contains("\"type\":\"autopilot_phase_changed\""));
+
         // Background task events
         let event = ThreadEvent::BackgroundTaskSpawned {
             task_id: "bg_123".to_string(),
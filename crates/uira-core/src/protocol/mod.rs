@@ -4,16 +4,22 @@
 //! - Message types for model communication
 //! - Event types for streaming and JSONL output
 //! - Tool call/response types
-//! - Common error types
+//! - Common error types and the shared error taxonomy
+//! - The JSON-RPC editor integration protocol
 
+mod error;
 mod events;
 mod messages;
 mod primitives;
+pub mod rpc;
 mod tools;
 mod types;
+mod validation;
 
+pub use error::*;
 pub use events::*;
 pub use messages::*;
 pub use primitives::*;
 pub use tools::*;
 pub use types::*;
+pub use validation::*;
@@ -1,8 +1,10 @@
+#[cfg(feature = "native")]
 mod atomic_write;
 mod event;
 mod result;
 mod types;
 
+#[cfg(feature = "native")]
 pub use atomic_write::{atomic_write, atomic_write_secure};
 pub use event::*;
 pub use result::*;
@@ -0,0 +1,293 @@
+//! Editor integration protocol spoken by `uira-agent --mode rpc`
+//!
+//! Newline-delimited JSON-RPC 2.0 over stdio. Clients call `initialize`
+//! first to agree on [`RPC_PROTOCOL_VERSION`], then drive the agent with
+//! `sendPrompt`, `approve` and `cancel`, and opt into the typed event stream
+//! with `streamEvents`. Field names are camelCase on the wire.
+
+use crate::{ThreadEvent, TokenUsage};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// Protocol revision; bumped on any incompatible change to methods or types
+pub const RPC_PROTOCOL_VERSION: u32 = 1;
+
+/// JSON-RPC version string required on every message
+pub const JSONRPC_VERSION: &str = "2.0";
+
+/// Request method names
+pub mod methods {
+    pub const INITIALIZE: &str = "initialize";
+    pub const SEND_PROMPT: &str = "sendPrompt";
+    pub const STREAM_EVENTS: &str = "streamEvents";
+    pub const APPROVE: &str = "approve";
+    pub const CANCEL: &str = "cancel";
+    pub const STATUS: &str = "status";
+
+    /// Every method a protocol client may call
+    pub const ALL: &[&str] = &[
+        INITIALIZE,
+        SEND_PROMPT,
+        STREAM_EVENTS,
+        APPROVE,
+        CANCEL,
+        STATUS,
+    ];
+}
+
+/// Server-to-client notification names
+pub mod notifications {
+    /// Carries an [`EventNotification`](super::EventNotification)
+    pub const EVENT: &str = "event";
+    /// Carries an [`ApprovalRequiredNotification`](super::ApprovalRequiredNotification)
+    pub const APPROVAL_REQUIRED: &str = "approvalRequired";
+    /// Carries a [`ShutdownNotification`](super::ShutdownNotification)
+    pub const SHUTDOWN: &str = "shutdown";
+
+    pub const ALL: &[&str] = &[EVENT, APPROVAL_REQUIRED, SHUTDOWN];
+}
+
+/// Error codes; the JSON-RPC reserved range plus uira-specific codes
+pub mod error_codes {
+    pub const PARSE_ERROR: i64 = -32700;
+    pub const INVALID_REQUEST: i64 = -32600;
+    pub const METHOD_NOT_FOUND: i64 = -32601;
+    pub const INVALID_PARAMS: i64 = -32602;
+    pub const SERVER_ERROR: i64 = -32000;
+    /// The client asked for a protocol version this server does not speak
+    pub const UNSUPPORTED_VERSION: i64 = -32001;
+    /// A prompt is already running
+    pub const BUSY: i64 = -32002;
+}
+
+/// Name and version of a client or server
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerInfo {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeParams {
+    pub protocol_version: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub client_info: Option<PeerInfo>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct InitializeResult {
+    pub protocol_version: u32,
+    pub server_info: PeerInfo,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    pub capabilities: ServerCapabilities,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServerCapabilities {
+    pub methods: Vec<String>,
+    pub notifications: Vec<String>,
+}
+
+impl Default for ServerCapabilities {
+    fn default() -> Self {
+        Self {
+            methods: methods::ALL.iter().map(|m| m.to_string()).collect(),
+            notifications: notifications::ALL.iter().map(|n| n.to_string()).collect(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendPromptParams {
+    pub prompt: String,
+}
+
+/// How a prompt ended; the response to `sendPrompt` arrives when it does
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PromptStatus {
+    Completed,
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SendPromptResult {
+    pub status: PromptStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage: Option<TokenUsage>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamEventsParams {
+    /// Turn the stream on or off (default: on)
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+    /// Only forward these event types (the `type` tag of [`ThreadEvent`]);
+    /// empty forwards everything
+    #[serde(default)]
+    pub events: Vec<String>,
+}
+
+impl Default for StreamEventsParams {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            events: Vec::new(),
+        }
+    }
+}
+
+impl StreamEventsParams {
+    /// Whether an event with this `type` tag passes the filter
+    pub fn wants(&self, event_type: &str) -> bool {
+        self.enabled && (self.events.is_empty() || self.events.iter().any(|e| e == event_type))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StreamEventsResult {
+    pub enabled: bool,
+    pub events: Vec<String>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalDecision {
+    Approve,
+    Deny,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApproveParams {
+    /// Which approval to answer; may be omitted when exactly one is pending
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub approval_id: Option<String>,
+    pub decision: ApprovalDecision,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApproveResult {
+    pub approval_id: String,
+    pub decision: ApprovalDecision,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CancelResult {
+    pub cancelled: bool,
+}
+
+/// Params of the `event` notification
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct EventNotification {
+    /// Increases by one per forwarded event, so clients can detect gaps
+    pub seq: u64,
+    /// Request id of the `sendPrompt` the event belongs to, if any
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_id: Option<Value>,
+    pub event: ThreadEvent,
+}
+
+/// Params of the `approvalRequired` notification
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ApprovalRequiredNotification {
+    pub approval_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub prompt_id: Option<Value>,
+    pub tool: String,
+    pub args: Value,
+    pub reason: String,
+}
+
+/// Params of the `shutdown` notification, sent once before the server exits
+/// on a termination signal
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ShutdownNotification {
+    pub session_id: String,
+    /// Session log to pass to `uira resume` to pick the session back up
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_path: Option<String>,
+}
+
+/// The `type` tag an event carries on the wire
+pub fn event_type(event: &ThreadEvent) -> Option<String> {
+    serde_json::to_value(event)
+        .ok()?
+        .get("type")?
+        .as_str()
+        .map(str::to_string)
+}
+
+fn default_true() -> bool {
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn uses_camel_case_on_the_wire() {
+        let params: ApproveParams =
+            serde_json::from_value(json!({"approvalId": "a1", "decision": "deny"})).unwrap();
+        assert_eq!(params.approval_id.as_deref(), Some("a1"));
+        assert_eq!(params.decision, ApprovalDecision::Deny);
+
+        let result = InitializeResult {
+            protocol_version: RPC_PROTOCOL_VERSION,
+            server_info: PeerInfo {
+                name: "uira".to_string(),
+                version: None,
+            },
+            session_id: Some("s1".to_string()),
+            capabilities: ServerCapabilities::default(),
+        };
+        let value = serde_json::to_value(&result).unwrap();
+        assert_eq!(value["protocolVersion"], RPC_PROTOCOL_VERSION);
+        assert_eq!(value["sessionId"], "s1");
+        assert_eq!(value["capabilities"]["methods"][1], "sendPrompt");
+    }
+
+    #[test]
+    fn filters_stream_by_event_type() {
+        let event = ThreadEvent::ContentDelta {
+            delta: "hi".to_string(),
+        };
+        let kind = event_type(&event).unwrap();
+        assert_eq!(kind, "content_delta");
+
+        assert!(StreamEventsParams::default().wants(&kind));
+        let filtered = StreamEventsParams {
+            enabled: true,
+            events: vec!["thread_completed".to_string()],
+        };
+        assert!(!filtered.wants(&kind));
+        assert!(filtered.wants("thread_completed"));
+
+        let notification = EventNotification {
+            seq: 3,
+            prompt_id: Some(json!(7)),
+            event,
+        };
+        let value = serde_json::to_value(&notification).unwrap();
+        assert_eq!(value["promptId"], 7);
+        assert_eq!(value["event"]["type"], "content_delta");
+    }
+}
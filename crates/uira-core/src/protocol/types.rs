@@ -1,6 +1,7 @@
 //! Common types used across the Uira protocol
 
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 /// Provider identifier for model routing
@@ -15,6 +16,8 @@ pub enum Provider {
     OpenCode,
     FriendliAI,
     OpenRouter,
+    /// Scripted responses for tests (see `UIRA_MOCK_SCENARIO`)
+    Mock,
     Custom,
 }
 
@@ -28,6 +31,7 @@ impl std::fmt::Display for Provider {
             Self::OpenCode => write!(f, "opencode"),
             Self::FriendliAI => write!(f, "friendliai"),
             Self::OpenRouter => write!(f, "openrouter"),
+            Self::Mock => write!(f, "mock"),
             Self::Custom => write!(f, "custom"),
         }
     }
@@ -203,9 +207,10 @@ impl Default for WorkspaceConfig {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 pub enum TodoStatus {
+    #[default]
     Pending,
     InProgress,
     Completed,
@@ -223,10 +228,13 @@ impl std::fmt::Display for TodoStatus {
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[derive(
+    Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize,
+)]
 #[serde(rename_all = "snake_case")]
 pub enum TodoPriority {
     High,
+    #[default]
     Medium,
     Low,
 }
@@ -241,12 +249,174 @@ impl std::fmt::Display for TodoPriority {
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+/// Rough size of a todo, used to plan multi-day work
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TodoEstimate {
+    Small,
+    Medium,
+    Large,
+}
+
+impl std::fmt::Display for TodoEstimate {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Small => write!(f, "S"),
+            Self::Medium => write!(f, "M"),
+            Self::Large => write!(f, "L"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct TodoItem {
     pub id: String,
     pub content: String,
     pub status: TodoStatus,
     pub priority: TodoPriority,
+    /// Parent todo for subtasks
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub parent_id: Option<String>,
+    /// Todos that must be finished before this one can start
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub depends_on: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub estimate: Option<TodoEstimate>,
+}
+
+impl TodoItem {
+    pub fn is_incomplete(&self) -> bool {
+        matches!(self.status, TodoStatus::Pending | TodoStatus::InProgress)
+    }
+
+    /// True while a dependency or a subtask of this todo is still incomplete
+    pub fn is_blocked(&self, todos: &[TodoItem]) -> bool {
+        todos.iter().any(|other| {
+            other.is_incomplete()
+                && (self.depends_on.contains(&other.id)
+                    || other.parent_id.as_deref() == Some(self.id.as_str()))
+        })
+    }
+}
+
+/// Incomplete, unblocked todos in the order they should be worked on:
+/// in-progress first, then by priority, then by list order
+pub fn actionable_todos(todos: &[TodoItem]) -> Vec<&TodoItem> {
+    let mut actionable: Vec<&TodoItem> = todos
+        .iter()
+        .filter(|t| t.is_incomplete() && !t.is_blocked(todos))
+        .collect();
+    actionable.sort_by_key(|t| (t.status != TodoStatus::InProgress, t.priority));
+    actionable
+}
+
+/// Todos in depth-first tree order, paired with their nesting depth.
+/// Todos whose parent is missing are shown at the top level.
+pub fn todo_tree(todos: &[TodoItem]) -> Vec<(usize, &TodoItem)> {
+    fn visit<'a>(
+        todos: &'a [TodoItem],
+        item: &'a TodoItem,
+        depth: usize,
+        seen: &mut HashSet<&'a str>,
+        out: &mut Vec<(usize, &'a TodoItem)>,
+    ) {
+        if !seen.insert(item.id.as_str()) {
+            return;
+        }
+        out.push((depth, item));
+        for child in todos
+            .iter()
+            .filter(|t| t.parent_id.as_deref() == Some(item.id.as_str()))
+        {
+            visit(todos, child, depth + 1, seen, out);
+        }
+    }
+
+    let ids: HashSet<&str> = todos.iter().map(|t| t.id.as_str()).collect();
+    let mut seen = HashSet::new();
+    let mut out = Vec::with_capacity(todos.len());
+    for root in todos.iter().filter(|t| {
+        t.parent_id
+            .as_deref()
+            .is_none_or(|parent| !ids.contains(parent))
+    }) {
+        visit(todos, root, 0, &mut seen, &mut out);
+    }
+    // Anything left is part of a parent cycle; keep it visible
+    for item in todos {
+        if !seen.contains(item.id.as_str()) {
+            visit(todos, item, 0, &mut seen, &mut out);
+        }
+    }
+    out
+}
+
+/// Check that parents and dependencies refer to known todos and form no cycles
+pub fn validate_todos(todos: &[TodoItem]) -> Result<(), String> {
+    let by_id: HashMap<&str, &TodoItem> = todos.iter().map(|t| (t.id.as_str(), t)).collect();
+    if by_id.len() != todos.len() {
+        return Err("todo ids must be unique".to_string());
+    }
+
+    for todo in todos {
+        let references = todo.parent_id.iter().chain(todo.depends_on.iter());
+        for id in references {
+            if id == &todo.id {
+                return Err(format!("todo '{}' cannot reference itself", todo.id));
+            }
+            if !by_id.contains_key(id.as_str()) {
+                return Err(format!(
+                    "todo '{}' references unknown todo '{}'",
+                    todo.id, id
+                ));
+            }
+        }
+    }
+
+    // A todo waits on its dependencies and on its subtasks, so both edges
+    // count when looking for cycles
+    fn waits_on<'a>(todos: &'a [TodoItem], todo: &'a TodoItem) -> Vec<&'a str> {
+        todo.depends_on
+            .iter()
+            .map(String::as_str)
+            .chain(
+                todos
+                    .iter()
+                    .filter(|t| t.parent_id.as_deref() == Some(todo.id.as_str()))
+                    .map(|t| t.id.as_str()),
+            )
+            .collect()
+    }
+
+    fn has_cycle<'a>(
+        todos: &'a [TodoItem],
+        by_id: &HashMap<&'a str, &'a TodoItem>,
+        id: &'a str,
+        visiting: &mut HashSet<&'a str>,
+        done: &mut HashSet<&'a str>,
+    ) -> bool {
+        if done.contains(id) {
+            return false;
+        }
+        if !visiting.insert(id) {
+            return true;
+        }
+        let cycle = waits_on(todos, by_id[id])
+            .into_iter()
+            .any(|next| has_cycle(todos, by_id, next, visiting, done));
+        visiting.remove(id);
+        done.insert(id);
+        cycle
+    }
+
+    let mut visiting = HashSet::new();
+    let mut done = HashSet::new();
+    for todo in todos {
+        if has_cycle(todos, &by_id, &todo.id, &mut visiting, &mut done) {
+            return Err(format!("todo '{}' is part of a dependency cycle", todo.id));
+        }
+    }
+    Ok(())
 }
 
 /// Prompt injected when todo continuation is triggered
@@ -291,6 +461,85 @@ mod tests {
         assert!(!id1.as_str().is_empty());
     }
 
+    fn todo(id: &str, status: TodoStatus) -> TodoItem {
+        TodoItem {
+            id: id.to_string(),
+            content: format!("Task {}", id),
+            status,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_todo_blocking_and_actionable_order() {
+        let mut parent = todo("1", TodoStatus::Pending);
+        parent.priority = TodoPriority::High;
+        let mut child = todo("2", TodoStatus::Pending);
+        child.parent_id = Some("1".to_string());
+        let mut dependent = todo("3", TodoStatus::Pending);
+        dependent.depends_on = vec!["2".to_string()];
+        dependent.priority = TodoPriority::High;
+        let mut low = todo("4", TodoStatus::InProgress);
+        low.priority = TodoPriority::Low;
+        let todos = vec![parent, child, dependent, low];
+
+        assert!(todos[0].is_blocked(&todos));
+        assert!(todos[2].is_blocked(&todos));
+        let ids: Vec<&str> = actionable_todos(&todos)
+            .iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        assert_eq!(ids, vec!["4", "2"]);
+
+        let depths: Vec<(usize, &str)> = todo_tree(&todos)
+            .into_iter()
+            .map(|(depth, t)| (depth, t.id.as_str()))
+            .collect();
+        assert_eq!(depths, vec![(0, "1"), (1, "2"), (0, "3"), (0, "4")]);
+    }
+
+    #[test]
+    fn test_validate_todos_rejects_bad_references() {
+        let mut a = todo("a", TodoStatus::Pending);
+        let mut b = todo("b", TodoStatus::Pending);
+        assert!(validate_todos(&[a.clone(), b.clone()]).is_ok());
+
+        a.depends_on = vec!["missing".to_string()];
+        assert!(validate_todos(&[a.clone(), b.clone()])
+            .unwrap_err()
+            .contains("unknown"));
+
+        a.depends_on = vec!["b".to_string()];
+        b.depends_on = vec!["a".to_string()];
+        assert!(validate_todos(&[a.clone(), b.clone()])
+            .unwrap_err()
+            .contains("cycle"));
+
+        // A parent depending on its own subtask is fine; a subtask depending
+        // on its parent can never start
+        b.depends_on.clear();
+        b.parent_id = Some("a".to_string());
+        assert!(validate_todos(&[a.clone(), b.clone()]).is_ok());
+        a.depends_on.clear();
+        b.depends_on = vec!["a".to_string()];
+        assert!(validate_todos(&[a, b]).is_err());
+    }
+
+    #[test]
+    fn test_todo_item_deserializes_without_hierarchy_fields() {
+        let item: TodoItem = serde_json::from_str(
+            r#"{"id":"1","content":"x","status":"pending","priority":"high"}"#,
+        )
+        .unwrap();
+        assert_eq!(item.parent_id, None);
+        assert!(item.depends_on.is_empty());
+        assert_eq!(item.estimate, None);
+        assert_eq!(
+            serde_json::to_string(&item).unwrap(),
+            r#"{"id":"1","content":"x","status":"pending","priority":"high"}"#
+        );
+    }
+
     #[test]
     fn test_provider_display() {
         assert_eq!(Provider::Anthropic.to_string(), "anthropic");
@@ -0,0 +1,305 @@
+//! Validation of tool inputs against their JSON Schema
+//!
+//! Covers the keywords tool schemas actually use: `type`, `enum`,
+//! `required`, `properties`, `additionalProperties`, `items`, `anyOf`/`oneOf`
+//! and the numeric, length and item-count bounds. Other keywords are ignored,
+//! so an unusual schema never rejects input it would accept.
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::JsonSchema;
+
+/// One way an input does not match its schema
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SchemaViolation {
+    /// JSON pointer to the offending value; empty for the input itself
+    pub path: String,
+    pub message: String,
+}
+
+impl std::fmt::Display for SchemaViolation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.path.is_empty() {
+            f.write_str(&self.message)
+        } else {
+            write!(f, "{}: {}", self.path, self.message)
+        }
+    }
+}
+
+impl JsonSchema {
+    /// Check `input` against this schema, listing every violation found
+    pub fn validate(&self, input: &Value) -> Result<(), Vec<SchemaViolation>> {
+        validate_json(&serde_json::to_value(self).unwrap_or(Value::Null), input)
+    }
+}
+
+/// Check `input` against a raw JSON Schema value, listing every violation found
+pub fn validate_json(schema: &Value, input: &Value) -> Result<(), Vec<SchemaViolation>> {
+    let mut violations = Vec::new();
+    validate_value(schema, input, "", &mut violations);
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+fn validate_value(schema: &Value, value: &Value, path: &str, out: &mut Vec<SchemaViolation>) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    let mut violation = |message: String| {
+        out.push(SchemaViolation {
+            path: path.to_string(),
+            message,
+        })
+    };
+
+    if let Some(expected) = schema.get("type") {
+        let types: Vec<&str> = match expected {
+            Value::String(ty) => vec![ty.as_str()],
+            Value::Array(types) => types.iter().filter_map(Value::as_str).collect(),
+            _ => Vec::new(),
+        };
+        if !types.is_empty() && !types.iter().any(|ty| has_type(value, ty)) {
+            violation(format!(
+                "expected {}, got {}",
+                types.join(" or "),
+                type_name(value)
+            ));
+            return;
+        }
+    }
+
+    if let Some(allowed) = schema.get("enum").and_then(Value::as_array) {
+        if !allowed.contains(value) {
+            let allowed: Vec<String> = allowed.iter().map(Value::to_string).collect();
+            violation(format!("must be one of {}", allowed.join(", ")));
+        }
+    }
+
+    for keyword in ["anyOf", "oneOf"] {
+        if let Some(branches) = schema.get(keyword).and_then(Value::as_array) {
+            let matches = branches.iter().any(|branch| {
+                let mut branch_violations = Vec::new();
+                validate_value(branch, value, path, &mut branch_violations);
+                branch_violations.is_empty()
+            });
+            if !matches {
+                violation("does not match any of the allowed forms".to_string());
+            }
+        }
+    }
+
+    match value {
+        Value::Object(fields) => validate_object(schema, fields, path, out),
+        Value::Array(items) => {
+            if let Some(min) = schema.get("minItems").and_then(Value::as_u64) {
+                if (items.len() as u64) < min {
+                    out.push(SchemaViolation {
+                        path: path.to_string(),
+                        message: format!("must have at least {} items", min),
+                    });
+                }
+            }
+            if let Some(item_schema) = schema.get("items") {
+                for (index, item) in items.iter().enumerate() {
+                    validate_value(item_schema, item, &format!("{}/{}", path, index), out);
+                }
+            }
+        }
+        Value::String(text) => {
+            let length = text.chars().count() as u64;
+            if let Some(min) = schema.get("minLength").and_then(Value::as_u64) {
+                if length < min {
+                    out.push(SchemaViolation {
+                        path: path.to_string(),
+                        message: format!("must be at least {} characters", min),
+                    });
+                }
+            }
+            if let Some(max) = schema.get("maxLength").and_then(Value::as_u64) {
+                if length > max {
+                    out.push(SchemaViolation {
+                        path: path.to_string(),
+                        message: format!("must be at most {} characters", max),
+                    });
+                }
+            }
+        }
+        Value::Number(number) => {
+            let number = number.as_f64().unwrap_or_default();
+            if let Some(min) = schema.get("minimum").and_then(Value::as_f64) {
+                if number < min {
+                    out.push(SchemaViolation {
+                        path: path.to_string(),
+                        message: format!("must be at least {}", min),
+                    });
+                }
+            }
+            if let Some(max) = schema.get("maximum").and_then(Value::as_f64) {
+                if number > max {
+                    out.push(SchemaViolation {
+                        path: path.to_string(),
+                        message: format!("must be at most {}", max),
+                    });
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn validate_object(
+    schema: &serde_json::Map<String, Value>,
+    fields: &serde_json::Map<String, Value>,
+    path: &str,
+    out: &mut Vec<SchemaViolation>,
+) {
+    let properties = schema.get("properties").and_then(Value::as_object);
+
+    if let Some(required) = schema.get("required").and_then(Value::as_array) {
+        for name in required.iter().filter_map(Value::as_str) {
+            if !fields.contains_key(name) {
+                out.push(SchemaViolation {
+                    path: format!("{}/{}", path, name),
+                    message: "is required".to_string(),
+                });
+            }
+        }
+    }
+
+    for (name, field) in fields {
+        let field_path = format!("{}/{}", path, name);
+        match properties.and_then(|properties| properties.get(name)) {
+            Some(field_schema) => validate_value(field_schema, field, &field_path, out),
+            None => match schema.get("additionalProperties") {
+                Some(Value::Bool(false)) => out.push(SchemaViolation {
+                    path: field_path,
+                    message: "is not a known argument".to_string(),
+                }),
+                Some(extra @ Value::Object(_)) => validate_value(extra, field, &field_path, out),
+                _ => {}
+            },
+        }
+    }
+}
+
+fn has_type(value: &Value, ty: &str) -> bool {
+    match ty {
+        "object" => value.is_object(),
+        "array" => value.is_array(),
+        "string" => value.is_string(),
+        "boolean" => value.is_boolean(),
+        "null" => value.is_null(),
+        "number" => value.is_number(),
+        "integer" => {
+            value.is_i64()
+                || value.is_u64()
+                || value.as_f64().is_some_and(|number| number.fract() == 0.0)
+        }
+        _ => true,
+    }
+}
+
+fn type_name(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "null",
+        Value::Bool(_) => "boolean",
+        Value::Number(_) => "number",
+        Value::String(_) => "string",
+        Value::Array(_) => "array",
+        Value::Object(_) => "object",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn edit_schema() -> JsonSchema {
+        JsonSchema::object()
+            .property("file_path", JsonSchema::string())
+            .property("old_string", JsonSchema::string())
+            .property("new_string", JsonSchema::string())
+            .property("replace_all", JsonSchema::boolean())
+            .required(&["file_path", "old_string", "new_string"])
+    }
+
+    #[test]
+    fn accepts_matching_input() {
+        let input = json!({
+            "file_path": "src/main.rs",
+            "old_string": "a",
+            "new_string": "b",
+        });
+        assert_eq!(edit_schema().validate(&input), Ok(()));
+    }
+
+    #[test]
+    fn reports_every_violation() {
+        let input = json!({
+            "file_path": "src/main.rs",
+            "new_string": 3,
+            "replace_all": "yes",
+            "path": "src/lib.rs",
+        });
+        let violations = edit_schema().validate(&input).unwrap_err();
+        let rendered: Vec<String> = violations.iter().map(ToString::to_string).collect();
+        assert_eq!(
+            rendered,
+            vec![
+                "/old_string: is required",
+                "/new_string: expected string, got number",
+                "/path: is not a known argument",
+                "/replace_all: expected boolean, got string",
+            ]
+        );
+
+        let violations = edit_schema().validate(&json!("src/main.rs")).unwrap_err();
+        assert_eq!(violations[0].to_string(), "expected object, got string");
+    }
+
+    #[test]
+    fn checks_nested_keywords() {
+        let schema = JsonSchema::object()
+            .with_properties(json!({
+                "edits": {
+                    "type": "array",
+                    "minItems": 1,
+                    "items": {
+                        "type": "object",
+                        "properties": { "line": { "type": "integer", "minimum": 1 } },
+                        "required": ["line"]
+                    }
+                },
+                "mode": { "type": "string", "enum": ["fast", "full"] },
+                "limit": { "anyOf": [{ "type": "integer" }, { "type": "null" }] }
+            }))
+            .required(&["edits"]);
+
+        assert_eq!(
+            schema.validate(&json!({ "edits": [{ "line": 2 }], "limit": null })),
+            Ok(())
+        );
+
+        let violations = schema
+            .validate(&json!({
+                "edits": [{ "line": 0 }, {}],
+                "mode": "slow",
+                "limit": "ten",
+            }))
+            .unwrap_err();
+        let paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        assert_eq!(
+            paths,
+            vec!["/edits/0/line", "/edits/1/line", "/limit", "/mode"]
+        );
+
+        let violations = schema.validate(&json!({ "edits": [] })).unwrap_err();
+        assert_eq!(violations[0].message, "must have at least 1 items");
+    }
+}
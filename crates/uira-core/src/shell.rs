@@ -0,0 +1,179 @@
+//! Shell selection for user-supplied command strings
+//!
+//! Goals, git hooks and verification commands are written as one-line shell
+//! scripts. On unix they run under `sh -c`; Windows has no `sh`, so they run
+//! under `cmd /C` unless `UIRA_SHELL` (or a per-command `shell`) picks
+//! PowerShell or a POSIX shell from Git for Windows / MSYS2.
+
+use std::process::Command;
+
+use serde::{Deserialize, Serialize};
+
+/// Overrides the shell used for command strings (`sh`, `bash`, `cmd`,
+/// `powershell`, `pwsh`)
+pub const SHELL_ENV: &str = "UIRA_SHELL";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShellKind {
+    Sh,
+    Bash,
+    Cmd,
+    /// Windows PowerShell 5.x
+    #[serde(rename = "powershell")]
+    PowerShell,
+    /// PowerShell 7+
+    Pwsh,
+}
+
+impl ShellKind {
+    pub fn parse(name: &str) -> Option<Self> {
+        let name = name.trim().to_ascii_lowercase();
+        let name = name.strip_suffix(".exe").unwrap_or(&name);
+        match name {
+            "sh" => Some(Self::Sh),
+            "bash" => Some(Self::Bash),
+            "cmd" => Some(Self::Cmd),
+            "powershell" => Some(Self::PowerShell),
+            "pwsh" => Some(Self::Pwsh),
+            _ => None,
+        }
+    }
+
+    /// `UIRA_SHELL` if it names a known shell, otherwise `sh` on unix and
+    /// `cmd` on Windows
+    pub fn from_env() -> Self {
+        Self::from_env_or(Self::Sh)
+    }
+
+    /// `UIRA_SHELL` if set; otherwise `unix_default` on unix and `cmd` on
+    /// Windows
+    pub fn from_env_or(unix_default: Self) -> Self {
+        if let Some(shell) = std::env::var(SHELL_ENV)
+            .ok()
+            .and_then(|name| Self::parse(&name))
+        {
+            return shell;
+        }
+        if cfg!(windows) {
+            Self::Cmd
+        } else {
+            unix_default
+        }
+    }
+
+    pub fn program(&self) -> &'static str {
+        match self {
+            Self::Sh => "sh",
+            Self::Bash => "bash",
+            Self::Cmd => "cmd",
+            Self::PowerShell => "powershell",
+            Self::Pwsh => "pwsh",
+        }
+    }
+
+    /// Arguments that make the shell run `script` and exit
+    pub fn args(&self, script: &str) -> Vec<String> {
+        match self {
+            Self::Sh | Self::Bash => vec!["-c".to_string(), script.to_string()],
+            Self::Cmd => vec![
+                "/D".to_string(),
+                "/S".to_string(),
+                "/C".to_string(),
+                script.to_string(),
+            ],
+            Self::PowerShell | Self::Pwsh => vec![
+                "-NoProfile".to_string(),
+                "-NonInteractive".to_string(),
+                "-Command".to_string(),
+                script.to_string(),
+            ],
+        }
+    }
+
+    /// Quote `arg` as a single word for this shell
+    pub fn quote(&self, arg: &str) -> String {
+        match self {
+            Self::Sh | Self::Bash => format!("'{}'", arg.replace('\'', "'\\''")),
+            Self::Cmd => format!("\"{}\"", arg.replace('"', "\"\"")),
+            Self::PowerShell | Self::Pwsh => format!("'{}'", arg.replace('\'', "''")),
+        }
+    }
+
+    /// A command running `script` under this shell
+    pub fn command(&self, script: &str) -> Command {
+        let mut command = Command::new(self.program());
+        #[cfg(windows)]
+        if *self == Self::Cmd {
+            // cmd does its own parsing; the MSVCRT quoting std applies to
+            // ordinary arguments would leave stray backslashes in the script
+            use std::os::windows::process::CommandExt;
+            command.args(["/D", "/S", "/C"]);
+            command.raw_arg(format!("\"{script}\""));
+            return command;
+        }
+        command.args(self.args(script));
+        command
+    }
+}
+
+impl std::fmt::Display for ShellKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.program())
+    }
+}
+
+/// A command running `script` under `shell`, or `UIRA_SHELL` / the platform
+/// default when `None`
+pub fn shell_command(script: &str, shell: Option<ShellKind>) -> Command {
+    shell.unwrap_or_else(ShellKind::from_env).command(script)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_shell_names() {
+        assert_eq!(
+            ShellKind::parse("PowerShell.exe"),
+            Some(ShellKind::PowerShell)
+        );
+        assert_eq!(ShellKind::parse("pwsh"), Some(ShellKind::Pwsh));
+        assert_eq!(ShellKind::parse(" cmd "), Some(ShellKind::Cmd));
+        assert_eq!(ShellKind::parse("fish"), None);
+        assert_eq!(
+            serde_json::from_str::<ShellKind>("\"powershell\"").unwrap(),
+            ShellKind::PowerShell
+        );
+    }
+
+    #[test]
+    fn builds_shell_specific_arguments() {
+        assert_eq!(ShellKind::Sh.args("echo 1"), vec!["-c", "echo 1"]);
+        assert_eq!(
+            ShellKind::Pwsh.args("Get-Date"),
+            vec!["-NoProfile", "-NonInteractive", "-Command", "Get-Date"]
+        );
+        assert_eq!(ShellKind::Cmd.args("dir")[2..], ["/C", "dir"]);
+    }
+
+    #[test]
+    fn quotes_per_shell() {
+        assert_eq!(ShellKind::Sh.quote("it's"), "'it'\\''s'");
+        assert_eq!(
+            ShellKind::Cmd.quote("my \"file\".txt"),
+            "\"my \"\"file\"\".txt\""
+        );
+        assert_eq!(ShellKind::Pwsh.quote("it's"), "'it''s'");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn runs_scripts_under_sh() {
+        let output = shell_command("echo $((40 + 2))", Some(ShellKind::Sh))
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "42");
+    }
+}
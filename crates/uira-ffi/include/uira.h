@@ -0,0 +1,104 @@
+/*
+ * uira.h - C ABI for embedding the Uira agent runtime
+ *
+ * Link against libuira (cdylib or staticlib from the uira-ffi crate).
+ * Every function is synchronous and safe to call from any thread. Strings
+ * passed in are NUL-terminated UTF-8; strings returned by the library must
+ * be released with uira_string_free().
+ *
+ * Typical host loop:
+ *
+ *   UiraSession *s = uira_session_create("{\"model\": \"claude-sonnet-4-20250514\"}");
+ *   uira_session_send(s, "Explain src/main.rs");
+ *   for (;;) {
+ *     char *event = uira_session_poll_event(s, 100);
+ *     if (event == NULL) { if (uira_last_error()) break; else continue; }
+ *     // dispatch on the "type" field; answer "approval_required" events
+ *     // with uira_session_approve(s, id, "{\"decision\": \"approve\"}")
+ *     uira_string_free(event);
+ *   }
+ *   uira_session_destroy(s);
+ */
+
+#ifndef UIRA_H
+#define UIRA_H
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* Bumped on breaking changes to this header */
+#define UIRA_ABI_VERSION 1
+
+typedef enum UiraStatus {
+  UIRA_STATUS_OK = 0,
+  /* A pointer was null or a string was not valid UTF-8 / JSON */
+  UIRA_STATUS_INVALID_ARGUMENT = 1,
+  /* The agent loop has stopped and no longer accepts input */
+  UIRA_STATUS_CLOSED = 2,
+  /* No pending approval with the given id */
+  UIRA_STATUS_NOT_FOUND = 3,
+  /* A Rust panic was caught at the boundary */
+  UIRA_STATUS_PANIC = 4,
+} UiraStatus;
+
+typedef struct UiraSession UiraSession;
+
+/* ABI version of the loaded library; compare against UIRA_ABI_VERSION */
+uint32_t uira_abi_version(void);
+
+/* Last error recorded on the calling thread, or NULL. Valid until the next
+ * uira_* call on the same thread; do not free. */
+const char *uira_last_error(void);
+
+/* Stable code of the last error, or NULL: "ffi.invalid_argument",
+ * "ffi.closed", "ffi.not_found", "ffi.panic", or a shared taxonomy code such
+ * as "provider.authentication_failed" when session creation fails. Same
+ * lifetime as uira_last_error(). */
+const char *uira_last_error_code(void);
+
+/* Create a session from a JSON config:
+ *   model              required model id
+ *   provider           "anthropic" (default), "openai", "google", "ollama", ...
+ *   working_directory  workspace root (default: process cwd)
+ *   base_url           provider endpoint override
+ *   full_auto          skip approval prompts (default: false)
+ *   record             record a resumable JSONL transcript (default: true)
+ * Returns NULL on failure. */
+UiraSession *uira_session_create(const char *config_json);
+
+/* Session id of the recorded transcript; free with uira_string_free() */
+char *uira_session_id(const UiraSession *session);
+
+/* Queue a user prompt; the agent works on it in the background */
+UiraStatus uira_session_send(const UiraSession *session, const char *prompt);
+
+/* Next event as a JSON object tagged by "type", waiting up to timeout_ms
+ * (0 returns immediately, negative waits forever). Approval requests arrive
+ * as {"type": "approval_required", "approval_id", "tool", "args", "reason"}.
+ * Returns NULL on timeout, or when the loop has stopped (uira_last_error()
+ * is then set). Free the result with uira_string_free(). */
+char *uira_session_poll_event(const UiraSession *session, int64_t timeout_ms);
+
+/* Answer a pending approval with {"decision": "approve"},
+ * {"decision": "approve_all"} or {"decision": "deny", "reason": "..."} */
+UiraStatus uira_session_approve(const UiraSession *session,
+                                const char *approval_id,
+                                const char *decision_json);
+
+/* Interrupt the running turn and deny its pending approvals */
+UiraStatus uira_session_cancel(const UiraSession *session);
+
+/* Stop the agent loop and free the session; NULL is ignored */
+void uira_session_destroy(UiraSession *session);
+
+/* Free a string returned by this library; NULL is ignored */
+void uira_string_free(char *text);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* UIRA_H */
@@ -0,0 +1,89 @@
+use std::cell::RefCell;
+use std::ffi::{c_char, CString};
+
+use uira_core::UiraError;
+
+/// Status codes returned by the `uira_session_*` calls. Values are part of
+/// the ABI; new codes are only ever appended.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UiraStatus {
+    Ok = 0,
+    /// A pointer was null or a string was not valid UTF-8 / JSON
+    InvalidArgument = 1,
+    /// The agent loop has stopped and no longer accepts input
+    Closed = 2,
+    /// No pending approval with the given id
+    NotFound = 3,
+    /// A Rust panic was caught at the boundary
+    Panic = 4,
+}
+
+impl UiraStatus {
+    /// Error code recorded alongside a failure with this status
+    pub(crate) fn code(self) -> &'static str {
+        match self {
+            UiraStatus::Ok => "ffi.ok",
+            UiraStatus::InvalidArgument => "ffi.invalid_argument",
+            UiraStatus::Closed => "ffi.closed",
+            UiraStatus::NotFound => "ffi.not_found",
+            UiraStatus::Panic => "ffi.panic",
+        }
+    }
+}
+
+struct LastError {
+    message: CString,
+    code: CString,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<LastError>> = const { RefCell::new(None) };
+}
+
+/// Record `message` as this thread's last error and return `status`
+pub(crate) fn fail(status: UiraStatus, message: impl Into<String>) -> UiraStatus {
+    set_last_error(status.code(), message);
+    status
+}
+
+pub(crate) fn set_last_error(code: &str, message: impl Into<String>) {
+    let to_c = |text: String| CString::new(text.replace('\0', " ")).unwrap_or_default();
+    let error = LastError {
+        message: to_c(message.into()),
+        code: to_c(code.to_string()),
+    };
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = Some(error));
+}
+
+/// Record a classified error; the remediation hint is appended to the
+/// message since C hosts only get the one string
+pub(crate) fn set_uira_error(context: &str, error: &UiraError) {
+    set_last_error(
+        &error.code,
+        format!("{context}: {}", error.display_with_hint()),
+    );
+}
+
+pub(crate) fn clear_last_error() {
+    LAST_ERROR.with(|slot| *slot.borrow_mut() = None);
+}
+
+/// Pointer to this thread's last error, or null. Valid until the next
+/// `uira_*` call on the same thread.
+pub(crate) fn last_error_ptr() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |error| error.message.as_ptr())
+    })
+}
+
+/// Pointer to the code of this thread's last error, or null
+pub(crate) fn last_error_code_ptr() -> *const c_char {
+    LAST_ERROR.with(|slot| {
+        slot.borrow()
+            .as_ref()
+            .map_or(std::ptr::null(), |error| error.code.as_ptr())
+    })
+}
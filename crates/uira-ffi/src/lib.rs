@@ -0,0 +1,416 @@
+//! C ABI for embedding Uira
+//!
+//! A `UiraSession*` owns one interactive agent loop on a shared runtime.
+//! Hosts push prompts with [`uira_session_send`], drain agent events and
+//! approval requests as JSON with [`uira_session_poll_event`], answer
+//! approvals with [`uira_session_approve`], and free everything with
+//! [`uira_session_destroy`]. Every call is synchronous and thread-safe, so
+//! editors without an async runtime (JetBrains, Emacs dynamic modules, Vim)
+//! can drive the agent from their own event loop. `include/uira.h` is the
+//! stable header; [`UIRA_ABI_VERSION`] is bumped on breaking changes.
+
+mod error;
+mod session;
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::time::Duration;
+
+use uira_core::ReviewDecision;
+
+pub use error::UiraStatus;
+use error::{
+    clear_last_error, fail, last_error_code_ptr, last_error_ptr, set_last_error, set_uira_error,
+};
+use session::{Session, SessionConfig};
+
+/// Version of the C ABI described by `include/uira.h`
+pub const UIRA_ABI_VERSION: u32 = 1;
+
+/// Opaque session handle
+pub struct UiraSession {
+    inner: Session,
+}
+
+fn guard<T>(on_panic: T, f: impl FnOnce() -> T) -> T {
+    clear_last_error();
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|_| {
+        set_last_error(UiraStatus::Panic.code(), "panic inside uira");
+        on_panic
+    })
+}
+
+/// Borrow a C string argument, recording an error on null or bad UTF-8
+unsafe fn str_arg<'a>(ptr: *const c_char, name: &str) -> Result<&'a str, UiraStatus> {
+    if ptr.is_null() {
+        return Err(fail(
+            UiraStatus::InvalidArgument,
+            format!("{name} must not be null"),
+        ));
+    }
+    CStr::from_ptr(ptr).to_str().map_err(|_| {
+        fail(
+            UiraStatus::InvalidArgument,
+            format!("{name} is not valid UTF-8"),
+        )
+    })
+}
+
+unsafe fn session_arg<'a>(session: *const UiraSession) -> Result<&'a Session, UiraStatus> {
+    session
+        .as_ref()
+        .map(|session| &session.inner)
+        .ok_or_else(|| fail(UiraStatus::InvalidArgument, "session must not be null"))
+}
+
+fn into_c_string(text: String) -> *mut c_char {
+    CString::new(text.replace('\0', " "))
+        .map(CString::into_raw)
+        .unwrap_or(std::ptr::null_mut())
+}
+
+/// ABI version of the loaded library; compare against `UIRA_ABI_VERSION`
+#[no_mangle]
+pub extern "C" fn uira_abi_version() -> u32 {
+    UIRA_ABI_VERSION
+}
+
+/// Last error recorded on the calling thread, or null
+#[no_mangle]
+pub extern "C" fn uira_last_error() -> *const c_char {
+    last_error_ptr()
+}
+
+/// Stable code of the last error, such as `provider.authentication_failed`
+/// or `ffi.invalid_argument`, or null
+#[no_mangle]
+pub extern "C" fn uira_last_error_code() -> *const c_char {
+    last_error_code_ptr()
+}
+
+/// Create a session from a JSON config such as
+/// `{"model": "claude-sonnet-4-20250514", "provider": "anthropic"}`.
+/// Returns null on failure; see [`uira_last_error`].
+///
+/// # Safety
+///
+/// `config_json` must be null or a valid NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn uira_session_create(config_json: *const c_char) -> *mut UiraSession {
+    guard(std::ptr::null_mut(), || {
+        let config = match str_arg(config_json, "config_json") {
+            Ok(config) => config,
+            Err(_) => return std::ptr::null_mut(),
+        };
+        let config: SessionConfig = match serde_json::from_str(config) {
+            Ok(config) => config,
+            Err(e) => {
+                fail(
+                    UiraStatus::InvalidArgument,
+                    format!("Invalid session config: {e}"),
+                );
+                return std::ptr::null_mut();
+            }
+        };
+        match Session::create(config) {
+            Ok(inner) => Box::into_raw(Box::new(UiraSession { inner })),
+            Err(e) => {
+                set_uira_error("Failed to create session", &e);
+                std::ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Session id used for the recorded transcript. The caller frees the
+/// result with [`uira_string_free`].
+///
+/// # Safety
+///
+/// `session` must be null or a pointer returned by [`uira_session_create`]
+/// that has not been destroyed.
+#[no_mangle]
+pub unsafe extern "C" fn uira_session_id(session: *const UiraSession) -> *mut c_char {
+    guard(std::ptr::null_mut(), || match session_arg(session) {
+        Ok(session) => into_c_string(session.id().to_string()),
+        Err(_) => std::ptr::null_mut(),
+    })
+}
+
+/// Queue a user prompt. The agent works on it in the background; watch
+/// progress with [`uira_session_poll_event`].
+///
+/// # Safety
+///
+/// `session` must be a live session handle and `prompt` a valid
+/// NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn uira_session_send(
+    session: *const UiraSession,
+    prompt: *const c_char,
+) -> UiraStatus {
+    guard(UiraStatus::Panic, || {
+        let session = match session_arg(session) {
+            Ok(session) => session,
+            Err(status) => return status,
+        };
+        let prompt = match str_arg(prompt, "prompt") {
+            Ok(prompt) => prompt,
+            Err(status) => return status,
+        };
+        match session.send(prompt) {
+            Ok(()) => UiraStatus::Ok,
+            Err(e) => fail(UiraStatus::Closed, e),
+        }
+    })
+}
+
+/// Next agent event as a JSON object tagged by `"type"`, waiting up to
+/// `timeout_ms` (0 returns immediately, negative waits forever).
+/// Approval requests arrive as `{"type": "approval_required", ...}`.
+/// Returns null when nothing arrived in time, or when the loop has stopped
+/// (then [`uira_last_error`] is set). Free the result with
+/// [`uira_string_free`].
+///
+/// # Safety
+///
+/// `session` must be a live session handle.
+#[no_mangle]
+pub unsafe extern "C" fn uira_session_poll_event(
+    session: *const UiraSession,
+    timeout_ms: i64,
+) -> *mut c_char {
+    guard(std::ptr::null_mut(), || {
+        let session = match session_arg(session) {
+            Ok(session) => session,
+            Err(_) => return std::ptr::null_mut(),
+        };
+        let timeout = u64::try_from(timeout_ms).ok().map(Duration::from_millis);
+        match session.poll(timeout) {
+            Ok(Some(event)) => into_c_string(event.to_string()),
+            Ok(None) => std::ptr::null_mut(),
+            Err(()) => {
+                fail(UiraStatus::Closed, "agent loop has stopped");
+                std::ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Answer the approval `approval_id` with a decision such as
+/// `{"decision": "approve"}` or `{"decision": "deny", "reason": "..."}`.
+///
+/// # Safety
+///
+/// `session` must be a live session handle; `approval_id` and
+/// `decision_json` must be valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn uira_session_approve(
+    session: *const UiraSession,
+    approval_id: *const c_char,
+    decision_json: *const c_char,
+) -> UiraStatus {
+    guard(UiraStatus::Panic, || {
+        let session = match session_arg(session) {
+            Ok(session) => session,
+            Err(status) => return status,
+        };
+        let (approval_id, decision) = match (
+            str_arg(approval_id, "approval_id"),
+            str_arg(decision_json, "decision_json"),
+        ) {
+            (Ok(approval_id), Ok(decision)) => (approval_id, decision),
+            (Err(status), _) | (_, Err(status)) => return status,
+        };
+        let decision: ReviewDecision = match serde_json::from_str(decision) {
+            Ok(decision) => decision,
+            Err(e) => {
+                return fail(
+                    UiraStatus::InvalidArgument,
+                    format!("Invalid decision: {e}"),
+                )
+            }
+        };
+        if session.approve(approval_id, decision) {
+            UiraStatus::Ok
+        } else {
+            fail(
+                UiraStatus::NotFound,
+                format!("No pending approval '{approval_id}'"),
+            )
+        }
+    })
+}
+
+/// Interrupt the running turn and deny its pending approvals
+///
+/// # Safety
+///
+/// `session` must be a live session handle.
+#[no_mangle]
+pub unsafe extern "C" fn uira_session_cancel(session: *const UiraSession) -> UiraStatus {
+    guard(UiraStatus::Panic, || match session_arg(session) {
+        Ok(session) => {
+            session.cancel();
+            UiraStatus::Ok
+        }
+        Err(status) => status,
+    })
+}
+
+/// Stop the agent loop and free the session. Null is ignored.
+///
+/// # Safety
+///
+/// `session` must be null or a live session handle; it must not be used
+/// again, and no other thread may be using it.
+#[no_mangle]
+pub unsafe extern "C" fn uira_session_destroy(session: *mut UiraSession) {
+    guard((), || {
+        if session.is_null() {
+            return;
+        }
+        let session = Box::from_raw(session);
+        session.inner.cancel();
+        drop(session);
+    })
+}
+
+/// Free a string returned by this library. Null is ignored.
+///
+/// # Safety
+///
+/// `text` must be null or a string returned by a `uira_*` call that has not
+/// been freed yet.
+#[no_mangle]
+pub unsafe extern "C" fn uira_string_free(text: *mut c_char) {
+    if !text.is_null() {
+        drop(CString::from_raw(text));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn last_error() -> String {
+        let ptr = uira_last_error();
+        assert!(!ptr.is_null());
+        unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn last_error_code() -> String {
+        let ptr = uira_last_error_code();
+        assert!(!ptr.is_null());
+        unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    fn take_string(ptr: *mut c_char) -> String {
+        assert!(!ptr.is_null());
+        let text = unsafe { CStr::from_ptr(ptr) }
+            .to_string_lossy()
+            .into_owned();
+        unsafe { uira_string_free(ptr) };
+        text
+    }
+
+    #[test]
+    fn test_header_declares_every_export() {
+        let header = include_str!("../include/uira.h");
+        let source = include_str!("lib.rs");
+        for line in source.lines() {
+            let Some(rest) = line.split("extern \"C\" fn ").nth(1) else {
+                continue;
+            };
+            let name = rest.split('(').next().unwrap();
+            assert!(
+                header.contains(&format!("{name}(")),
+                "uira.h is missing {name}"
+            );
+        }
+        assert!(header.contains(&format!("#define UIRA_ABI_VERSION {UIRA_ABI_VERSION}")));
+    }
+
+    #[test]
+    fn test_create_rejects_bad_config() {
+        assert!(unsafe { uira_session_create(std::ptr::null()) }.is_null());
+        assert_eq!(last_error(), "config_json must not be null");
+
+        let config = CString::new(r#"{"provider": "anthropic"}"#).unwrap();
+        assert!(unsafe { uira_session_create(config.as_ptr()) }.is_null());
+        assert!(last_error().contains("missing field `model`"));
+
+        let config = CString::new(r#"{"model": "m", "provider": "nope"}"#).unwrap();
+        assert!(unsafe { uira_session_create(config.as_ptr()) }.is_null());
+        assert!(last_error().starts_with("Invalid session config"));
+        assert_eq!(last_error_code(), "ffi.invalid_argument");
+
+        let config = CString::new(r#"{"model": " "}"#).unwrap();
+        assert!(unsafe { uira_session_create(config.as_ptr()) }.is_null());
+        assert_eq!(
+            last_error(),
+            "Failed to create session: model must not be empty"
+        );
+        assert_eq!(last_error_code(), "ffi.invalid_argument");
+    }
+
+    #[test]
+    fn test_null_session_is_invalid_argument() {
+        let prompt = CString::new("hi").unwrap();
+        assert_eq!(
+            unsafe { uira_session_send(std::ptr::null(), prompt.as_ptr()) },
+            UiraStatus::InvalidArgument
+        );
+        assert_eq!(last_error(), "session must not be null");
+        assert!(unsafe { uira_session_poll_event(std::ptr::null(), 0) }.is_null());
+        unsafe { uira_session_destroy(std::ptr::null_mut()) };
+        unsafe { uira_string_free(std::ptr::null_mut()) };
+    }
+
+    #[test]
+    fn test_mock_session_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let scenario = dir.path().join("scenario.yaml");
+        std::fs::write(&scenario, "turns:\n  - text: \"hello from mock\"\n").unwrap();
+        std::env::set_var("UIRA_MOCK_SCENARIO", &scenario);
+
+        let config = serde_json::json!({
+            "model": "mock-model",
+            "provider": "mock",
+            "working_directory": dir.path(),
+            "record": false,
+        });
+        let config = CString::new(config.to_string()).unwrap();
+        let session = unsafe { uira_session_create(config.as_ptr()) };
+        assert!(!session.is_null(), "{}", last_error());
+        assert!(!take_string(unsafe { uira_session_id(session) }).is_empty());
+
+        let unknown = CString::new("missing").unwrap();
+        let decision = CString::new(r#"{"decision": "approve"}"#).unwrap();
+        assert_eq!(
+            unsafe { uira_session_approve(session, unknown.as_ptr(), decision.as_ptr()) },
+            UiraStatus::NotFound
+        );
+
+        let prompt = CString::new("say hello").unwrap();
+        assert_eq!(
+            unsafe { uira_session_send(session, prompt.as_ptr()) },
+            UiraStatus::Ok
+        );
+
+        let mut types = Vec::new();
+        while !types.iter().any(|t| t == "thread_completed") {
+            let event = unsafe { uira_session_poll_event(session, 5_000) };
+            let event: serde_json::Value = serde_json::from_str(&take_string(event)).unwrap();
+            types.push(event["type"].as_str().unwrap().to_string());
+        }
+        assert!(types.iter().any(|t| t == "content_delta"));
+
+        assert_eq!(unsafe { uira_session_cancel(session) }, UiraStatus::Ok);
+        unsafe { uira_session_destroy(session) };
+    }
+}
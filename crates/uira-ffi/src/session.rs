@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::Duration;
+
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::runtime::Runtime;
+use tokio::sync::{mpsc, oneshot};
+use uira_agent::{Agent, AgentConfig, ApprovalReceiver, CancelSignal, EventStream, EventSystem};
+use uira_core::{ErrorKind, Message, Provider, ReviewDecision, UiraError};
+use uira_providers::{ModelClientBuilder, ProviderConfig};
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("uira-ffi")
+            .build()
+            .expect("failed to start the uira tokio runtime")
+    })
+}
+
+/// JSON accepted by `uira_session_create`
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(crate) struct SessionConfig {
+    pub model: String,
+    #[serde(default)]
+    pub provider: Provider,
+    #[serde(default)]
+    pub working_directory: Option<PathBuf>,
+    #[serde(default)]
+    pub base_url: Option<String>,
+    /// Skip approval prompts entirely
+    #[serde(default)]
+    pub full_auto: bool,
+    /// Record the session to JSONL so `uira-agent` can resume it
+    #[serde(default = "default_record")]
+    pub record: bool,
+}
+
+fn default_record() -> bool {
+    true
+}
+
+/// Agent runtime owned by one `UiraSession*` handle
+///
+/// Agent events and approval requests are merged into a single queue of
+/// JSON objects that the host drains with `uira_session_poll_event`.
+pub(crate) struct Session {
+    id: String,
+    input_tx: mpsc::Sender<Message>,
+    events_rx: Mutex<mpsc::UnboundedReceiver<Value>>,
+    pending_approvals: Arc<Mutex<HashMap<String, oneshot::Sender<ReviewDecision>>>>,
+    cancel_signal: CancelSignal,
+    _event_system: EventSystem,
+}
+
+impl Session {
+    pub(crate) fn create(config: SessionConfig) -> Result<Self, UiraError> {
+        if config.model.trim().is_empty() {
+            return Err(UiraError::new(
+                ErrorKind::UserError,
+                "ffi.invalid_argument",
+                "model must not be empty",
+            ));
+        }
+        let _guard = runtime().enter();
+
+        let provider_config = ProviderConfig {
+            provider: config.provider,
+            model: config.model.clone(),
+            base_url: config.base_url.clone(),
+            ..Default::default()
+        };
+        let client = ModelClientBuilder::new()
+            .with_config(provider_config)
+            .build()
+            .map_err(|e| UiraError::from(&e))?;
+
+        let mut agent_config = AgentConfig::new().with_model(&config.model);
+        agent_config.require_approval_for_writes = !config.full_auto;
+        agent_config.require_approval_for_commands = !config.full_auto;
+        if let Some(path) = &config.working_directory {
+            agent_config = agent_config.with_working_directory(path);
+        }
+        let working_directory = config
+            .working_directory
+            .clone()
+            .or_else(|| std::env::current_dir().ok())
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
+
+        let mut agent = Agent::new(agent_config, client);
+        if config.record {
+            agent = agent
+                .with_session_recording()
+                .map_err(|e| UiraError::from(&e))?;
+        }
+        let mut event_system = uira_agent::create_event_system(
+            working_directory,
+            agent.session().memory_system.clone(),
+        );
+        event_system.start();
+
+        let (agent, event_stream) = agent.with_event_system(&event_system).with_event_stream();
+        let cancel_signal = agent.control().cancel_signal();
+        let id = agent.session().id.to_string();
+        let (mut agent, input_tx, approval_rx, _command_tx) = agent.with_interactive();
+
+        let (events_tx, events_rx) = mpsc::unbounded_channel();
+        let pending_approvals = Arc::new(Mutex::new(HashMap::new()));
+        spawn_event_forwarder(event_stream, events_tx.clone());
+        spawn_approval_forwarder(approval_rx, events_tx, pending_approvals.clone());
+        runtime().spawn(async move {
+            if let Err(error) = agent.run_interactive().await {
+                tracing::error!(error = %error, "embedded agent loop terminated");
+            }
+        });
+
+        Ok(Self {
+            id,
+            input_tx,
+            events_rx: Mutex::new(events_rx),
+            pending_approvals,
+            cancel_signal,
+            _event_system: event_system,
+        })
+    }
+
+    pub(crate) fn id(&self) -> &str {
+        &self.id
+    }
+
+    pub(crate) fn send(&self, prompt: &str) -> Result<(), String> {
+        self.input_tx
+            .blocking_send(Message::user_prompt(prompt))
+            .map_err(|_| "agent loop has stopped".to_string())
+    }
+
+    /// Next queued event, waiting up to `timeout` (`None` waits forever).
+    /// `Err` means the agent loop is gone and the queue is drained.
+    pub(crate) fn poll(&self, timeout: Option<Duration>) -> Result<Option<Value>, ()> {
+        let mut events_rx = self.events_rx.lock().unwrap_or_else(|e| e.into_inner());
+        match events_rx.try_recv() {
+            Ok(event) => return Ok(Some(event)),
+            Err(mpsc::error::TryRecvError::Disconnected) => return Err(()),
+            Err(mpsc::error::TryRecvError::Empty) if timeout == Some(Duration::ZERO) => {
+                return Ok(None)
+            }
+            Err(mpsc::error::TryRecvError::Empty) => {}
+        }
+
+        runtime().block_on(async {
+            match timeout {
+                Some(timeout) => match tokio::time::timeout(timeout, events_rx.recv()).await {
+                    Ok(Some(event)) => Ok(Some(event)),
+                    Ok(None) => Err(()),
+                    Err(_) => Ok(None),
+                },
+                None => events_rx.recv().await.map(Some).ok_or(()),
+            }
+        })
+    }
+
+    /// Answer a pending approval; `false` if no approval has that id
+    pub(crate) fn approve(&self, approval_id: &str, decision: ReviewDecision) -> bool {
+        let pending = self
+            .pending_approvals
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(approval_id);
+        match pending {
+            Some(response_tx) => {
+                let _ = response_tx.send(decision);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Stop the running turn and deny every approval it is waiting on
+    pub(crate) fn cancel(&self) {
+        self.cancel_signal.cancel();
+        let pending: Vec<_> = self
+            .pending_approvals
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .drain()
+            .collect();
+        for (_, response_tx) in pending {
+            let _ = response_tx.send(ReviewDecision::Deny {
+                reason: Some("Cancelled by host".to_string()),
+            });
+        }
+    }
+}
+
+fn spawn_event_forwarder(event_stream: EventStream, events_tx: mpsc::UnboundedSender<Value>) {
+    runtime().spawn(async move {
+        let mut stream = event_stream;
+        while let Some(event) = stream.next().await {
+            match serde_json::to_value(&event) {
+                Ok(event) => {
+                    if events_tx.send(event).is_err() {
+                        break;
+                    }
+                }
+                Err(error) => {
+                    tracing::warn!(%error, "Failed to serialize agent event; skipping");
+                }
+            }
+        }
+    });
+}
+
+fn spawn_approval_forwarder(
+    mut approval_rx: ApprovalReceiver,
+    events_tx: mpsc::UnboundedSender<Value>,
+    pending_approvals: Arc<Mutex<HashMap<String, oneshot::Sender<ReviewDecision>>>>,
+) {
+    runtime().spawn(async move {
+        while let Some(pending) = approval_rx.recv().await {
+            let event = json!({
+                "type": "approval_required",
+                "approval_id": pending.id,
+                "tool": pending.tool_name,
+                "args": pending.input,
+                "reason": pending.reason,
+            });
+            pending_approvals
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .insert(pending.id, pending.response_tx);
+            if events_tx.send(event).is_err() {
+                break;
+            }
+        }
+    });
+}
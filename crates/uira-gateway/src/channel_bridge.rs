@@ -7,7 +7,9 @@ use tokio::sync::{mpsc, Mutex, RwLock};
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
 use uira_agent::EventStream;
+use uira_core::schema::ChannelLimitsConfig;
 use uira_core::ThreadEvent;
+use uira_orchestration::features::builtin_skills::SkillPermissions;
 
 use crate::channels::types::floor_char_boundary;
 use crate::channels::{Channel, ChannelCapabilities, ChannelMessage, ChannelResponse, ChannelType};
@@ -15,6 +17,7 @@ use crate::channels::{Channel, ChannelCapabilities, ChannelMessage, ChannelRespo
 use crate::config::SessionConfig;
 use crate::error::GatewayError;
 use crate::session_manager::SessionManager;
+use crate::shutdown::{Checkpoint, CheckpointRoute};
 use crate::skills::{get_context_injection, SkillError, SkillLoader};
 
 // Type aliases for complex types
@@ -179,6 +182,8 @@ struct RateLimiter {
     max_messages: usize,
     window: Duration,
     timestamps: HashMap<(String, String, String), Vec<Instant>>,
+    /// When each throttled sender was last told so, to reply once per window
+    notices: HashMap<(String, String, String), Instant>,
 }
 
 impl RateLimiter {
@@ -187,6 +192,7 @@ impl RateLimiter {
             max_messages,
             window,
             timestamps: HashMap::new(),
+            notices: HashMap::new(),
         }
     }
 
@@ -204,26 +210,84 @@ impl RateLimiter {
         true
     }
 
+    /// Time until a throttled sender may send again
+    fn retry_after(&self, key: &(String, String, String)) -> Duration {
+        let now = Instant::now();
+        self.timestamps
+            .get(key)
+            .and_then(|entries| entries.first())
+            .map(|&oldest| self.window.saturating_sub(now.duration_since(oldest)))
+            .unwrap_or_default()
+    }
+
+    /// Whether a throttled sender should be told, at most once per window
+    fn take_notice(&mut self, key: &(String, String, String)) -> bool {
+        let now = Instant::now();
+        match self.notices.get(key) {
+            Some(&last) if now.duration_since(last) < self.window => false,
+            _ => {
+                self.notices.insert(key.clone(), now);
+                true
+            }
+        }
+    }
+
     fn cleanup_stale(&mut self) {
         let now = Instant::now();
         self.timestamps.retain(|_, entries| {
             entries.retain(|&ts| now.duration_since(ts) < self.window);
             !entries.is_empty()
         });
+        self.notices
+            .retain(|_, &mut last| now.duration_since(last) < self.window);
     }
 }
 
+/// Whether `sender` on `channel_type` passes the `allowed_users` list
+fn is_sender_allowed(allowed_users: &[String], channel_type: &str, sender: &str) -> bool {
+    allowed_users.is_empty()
+        || allowed_users.iter().any(|allowed| {
+            allowed == sender
+                || allowed
+                    .split_once(':')
+                    .is_some_and(|(channel, id)| channel == channel_type && id == sender)
+        })
+}
+
 /// Per-channel skill configuration with pre-resolved context injection strings.
 #[derive(Debug, Clone, Default)]
 pub struct ChannelSkillConfig {
     /// Map from channel type string (e.g., "telegram", "slack") to pre-resolved skill context
     configs: HashMap<String, ResolvedChannelSkills>,
+    /// Overrides keyed by `(channel_type, account_id, conversation_key)`
+    conversations: HashMap<(String, String, String), ResolvedConversation>,
+}
+
+/// Agent and skill overrides for one conversation on a channel account, such
+/// as a Telegram forum topic. Matched against the `conversation_key` metadata
+/// of inbound messages.
+#[derive(Debug, Clone, Default)]
+pub struct ConversationOverride {
+    pub channel_type: String,
+    pub account_id: String,
+    pub conversation_key: String,
+    /// Agent personality, overriding `gateway.default_agent`
+    pub agent: Option<String>,
+    /// Skills replacing the channel's active skills
+    pub skills: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+struct ResolvedConversation {
+    agent: Option<String>,
+    skills: Option<ResolvedChannelSkills>,
 }
 
 #[derive(Debug, Clone)]
 struct ResolvedChannelSkills {
     skill_names: Vec<String>,
     context_injection: String,
+    skill_permissions: Vec<(String, SkillPermissions)>,
 }
 
 impl ChannelSkillConfig {
@@ -242,19 +306,66 @@ impl ChannelSkillConfig {
         let mut config = Self::new();
 
         for (channel_type, skill_names) in channel_active_skills {
-            let context_injection = if let Some(loader) = skill_loader {
-                let loaded = loader.load_active_skills(&skill_names)?;
-                get_context_injection(&loaded)
-            } else {
-                String::new()
-            };
-
-            config.add_channel_skills(&channel_type, skill_names, context_injection);
+            let resolved = Self::resolve_skills(skill_loader, skill_names)?;
+            config.configs.insert(channel_type, resolved);
         }
 
         Ok(config)
     }
 
+    /// Add per-conversation overrides, resolving their skills like
+    /// [`Self::from_active_skills`].
+    pub fn add_conversation_overrides(
+        &mut self,
+        skill_loader: Option<&SkillLoader>,
+        overrides: Vec<ConversationOverride>,
+    ) -> Result<(), SkillError> {
+        for entry in overrides {
+            let skills = entry
+                .skills
+                .map(|names| Self::resolve_skills(skill_loader, names))
+                .transpose()?;
+            self.conversations.insert(
+                (entry.channel_type, entry.account_id, entry.conversation_key),
+                ResolvedConversation {
+                    agent: entry.agent,
+                    skills,
+                },
+            );
+        }
+        Ok(())
+    }
+
+    fn resolve_skills(
+        skill_loader: Option<&SkillLoader>,
+        skill_names: Vec<String>,
+    ) -> Result<ResolvedChannelSkills, SkillError> {
+        let Some(loader) = skill_loader else {
+            return Ok(ResolvedChannelSkills {
+                skill_names,
+                context_injection: String::new(),
+                skill_permissions: Vec::new(),
+            });
+        };
+
+        let loaded = loader.load_active_skills(&skill_names)?;
+        let skill_permissions = loaded
+            .iter()
+            .filter(|skill| !skill.metadata.permissions.is_empty())
+            .map(|skill| {
+                (
+                    skill.metadata.name.clone(),
+                    skill.metadata.permissions.clone(),
+                )
+            })
+            .collect();
+        Ok(ResolvedChannelSkills {
+            skill_names,
+            context_injection: get_context_injection(&loaded)?,
+            skill_permissions,
+        })
+    }
+
     /// Register skills for a channel type. Takes the skill names and the pre-resolved
     /// context injection string (from `get_context_injection()`).
     pub fn add_channel_skills(
@@ -268,6 +379,7 @@ impl ChannelSkillConfig {
             ResolvedChannelSkills {
                 skill_names,
                 context_injection,
+                skill_permissions: Vec::new(),
             },
         );
     }
@@ -275,18 +387,38 @@ impl ChannelSkillConfig {
     /// Get the SessionConfig for a given channel type, with skills pre-populated.
     fn session_config_for_channel(&self, channel_type: &str) -> SessionConfig {
         match self.configs.get(channel_type) {
-            Some(resolved) => SessionConfig {
-                skills: resolved.skill_names.clone(),
-                skill_context: if resolved.context_injection.is_empty() {
-                    None
-                } else {
-                    Some(resolved.context_injection.clone())
-                },
-                ..Default::default()
-            },
+            Some(resolved) => resolved.session_config(),
             None => SessionConfig::default(),
         }
     }
+
+    /// Get the SessionConfig for a conversation, applying its overrides on top
+    /// of the channel's skills.
+    fn session_config_for_conversation(&self, key: &(String, String, String)) -> SessionConfig {
+        let mut config = self.session_config_for_channel(&key.0);
+        if let Some(conversation) = self.conversations.get(key) {
+            if let Some(skills) = &conversation.skills {
+                config = skills.session_config();
+            }
+            config.agent = conversation.agent.clone();
+        }
+        config
+    }
+}
+
+impl ResolvedChannelSkills {
+    fn session_config(&self) -> SessionConfig {
+        SessionConfig {
+            skills: self.skill_names.clone(),
+            skill_context: if self.context_injection.is_empty() {
+                None
+            } else {
+                Some(self.context_injection.clone())
+            },
+            skill_permissions: self.skill_permissions.clone(),
+            ..Default::default()
+        }
+    }
 }
 
 /// Routes messages between Channel implementations and the SessionManager.
@@ -294,6 +426,9 @@ impl ChannelSkillConfig {
 /// Maintains sender affinity via `(channel_type, account_id, sender_id) -> session_id` so that
 /// each unique sender on each account is associated with a persistent session, and separately
 /// routes responses via `session_id -> (channel_type, account_id, channel_id)`.
+/// Messages carrying a `conversation_key` in their metadata (e.g. a Telegram forum topic)
+/// are keyed on it instead of the sender, giving each conversation its own session.
+/// A `new_session` flag replaces the conversation's session with a fresh one.
 /// Multiple accounts of the same channel type (e.g., two Telegram bots) are supported
 /// by keying channels on `(channel_type, account_id)`.
 pub struct ChannelBridge {
@@ -305,13 +440,14 @@ pub struct ChannelBridge {
     channel_handles: Vec<JoinHandle<()>>,
     response_handles: Arc<RwLock<Vec<JoinHandle<()>>>>,
     skill_config: Arc<ChannelSkillConfig>,
+    limits: ChannelLimitsConfig,
 }
 
 impl ChannelBridge {
     const MAX_PENDING_TEXT_BYTES: usize = 64 * 1024;
     const DEFAULT_STREAM_THROTTLE_MS: u64 = 300;
-    const RATE_LIMIT_MESSAGES: usize = 10;
-    const RATE_LIMIT_WINDOW_SECS: u64 = 60;
+    const RESTARTING_NOTICE: &str =
+        "I'm restarting right now. Please send your message again in a minute.";
 
     /// Create a new ChannelBridge backed by the given SessionManager.
     pub fn new(session_manager: Arc<SessionManager>) -> Self {
@@ -331,9 +467,16 @@ impl ChannelBridge {
             channel_handles: Vec::new(),
             response_handles: Arc::new(RwLock::new(Vec::new())),
             skill_config: Arc::new(skill_config),
+            limits: ChannelLimitsConfig::default(),
         }
     }
 
+    /// Rate limits, allow-list and prompt cap applied to inbound messages
+    pub fn with_limits(mut self, limits: ChannelLimitsConfig) -> Self {
+        self.limits = limits;
+        self
+    }
+
     pub fn with_outbound_channels(mut self, outbound_channels: OutboundChannelMap) -> Self {
         self.outbound_channels = Some(outbound_channels);
         self
@@ -536,7 +679,9 @@ impl ChannelBridge {
                         )
                         .await;
                     }
-                    ThreadEvent::Error { message, .. } => {
+                    ThreadEvent::Error {
+                        message, detail, ..
+                    } => {
                         ChannelBridge::flush_pending_response(
                             &session_id,
                             &mut pending_text,
@@ -545,6 +690,9 @@ impl ChannelBridge {
                         )
                         .await;
 
+                        let message = detail
+                            .map(|detail| detail.display_with_hint())
+                            .unwrap_or(message);
                         ChannelBridge::deliver_to_channel(
                             &session_id,
                             message,
@@ -708,6 +856,39 @@ impl ChannelBridge {
         }
     }
 
+    /// Answer a sender on their channel without involving a session
+    async fn reply_directly(
+        channels: &ChannelMap,
+        key: &(String, String, String),
+        recipient: &str,
+        content: String,
+    ) {
+        let channel = {
+            let channels_guard = channels.read().await;
+            channels_guard.get(&(key.0.clone(), key.1.clone())).cloned()
+        };
+        let Some(channel) = channel else {
+            return;
+        };
+
+        let response = ChannelResponse {
+            content,
+            recipient: recipient.to_string(),
+        };
+        let send_result = {
+            let guard = channel.lock().await;
+            guard.send_message(response).await
+        };
+        if let Err(e) = send_result {
+            warn!(
+                channel_type = %key.0,
+                account_id = %key.1,
+                error = %e,
+                "Failed to send notice to sender"
+            );
+        }
+    }
+
     /// Register a channel with an account identifier and start listening for inbound messages.
     pub async fn register_channel(
         &mut self,
@@ -762,30 +943,101 @@ impl ChannelBridge {
         let channels = self.channels.clone();
         let response_handles = self.response_handles.clone();
         let skill_config = self.skill_config.clone();
+        let limits = self.limits.clone();
 
         let handle = tokio::spawn(async move {
             let mut rx = rx;
             let mut rate_limiter = RateLimiter::new(
-                ChannelBridge::RATE_LIMIT_MESSAGES,
-                Duration::from_secs(ChannelBridge::RATE_LIMIT_WINDOW_SECS),
+                limits.messages_per_window,
+                Duration::from_secs(limits.window_secs),
             );
             let mut cleanup_counter = 0u32;
 
             while let Some(msg) = rx.recv().await {
                 let channel_type_str = msg.channel_type.to_string();
-                let key = (
+                let rate_key = (
                     channel_type_str.clone(),
                     account_id.clone(),
                     msg.sender.clone(),
                 );
+                let key = (
+                    channel_type_str.clone(),
+                    account_id.clone(),
+                    msg.metadata
+                        .get("conversation_key")
+                        .cloned()
+                        .unwrap_or_else(|| msg.sender.clone()),
+                );
 
-                if !rate_limiter.check_and_record(&key) {
+                if !is_sender_allowed(&limits.allowed_users, &channel_type_str, &msg.sender) {
+                    debug!(
+                        channel_type = %rate_key.0,
+                        account_id = %rate_key.1,
+                        sender = %rate_key.2,
+                        "Ignoring message from sender not in channels.limits.allowed_users"
+                    );
+                    continue;
+                }
+
+                if !rate_limiter.check_and_record(&rate_key) {
                     warn!(
-                        channel_type = %key.0,
-                        account_id = %key.1,
-                        sender = %key.2,
+                        channel_type = %rate_key.0,
+                        account_id = %rate_key.1,
+                        sender = %rate_key.2,
                         "Rate limited: dropping inbound message from sender"
                     );
+                    if limits.notify && rate_limiter.take_notice(&rate_key) {
+                        let wait_secs = rate_limiter.retry_after(&rate_key).as_secs().max(1);
+                        let notice = format!(
+                            "You're sending messages faster than I can take them. Please wait {} seconds and try again.",
+                            wait_secs
+                        );
+                        ChannelBridge::reply_directly(
+                            &channels,
+                            &rate_key,
+                            &msg.channel_id,
+                            notice,
+                        )
+                        .await;
+                    }
+                    continue;
+                }
+
+                let prompt_chars = msg.content.chars().count();
+                if limits.max_prompt_chars > 0 && prompt_chars > limits.max_prompt_chars {
+                    warn!(
+                        channel_type = %rate_key.0,
+                        account_id = %rate_key.1,
+                        sender = %rate_key.2,
+                        prompt_chars,
+                        "Dropping inbound message over channels.limits.max_prompt_chars"
+                    );
+                    if limits.notify {
+                        let notice = format!(
+                            "That message is {} characters long; the limit is {}. Please shorten it and send it again.",
+                            prompt_chars, limits.max_prompt_chars
+                        );
+                        ChannelBridge::reply_directly(
+                            &channels,
+                            &rate_key,
+                            &msg.channel_id,
+                            notice,
+                        )
+                        .await;
+                    }
+                    continue;
+                }
+
+                if session_manager.is_shutting_down() {
+                    if limits.notify {
+                        ChannelBridge::reply_directly(
+                            &channels,
+                            &rate_key,
+                            &msg.channel_id,
+                            ChannelBridge::RESTARTING_NOTICE.to_string(),
+                        )
+                        .await;
+                    }
                     continue;
                 }
 
@@ -794,19 +1046,33 @@ impl ChannelBridge {
                     rate_limiter.cleanup_stale();
                 }
 
+                // e.g. Slack's `/uira new <prompt>`: replace the conversation's session
+                if msg.metadata.get("new_session").map(String::as_str) == Some("true") {
+                    let previous = sender_sessions.write().await.remove(&key);
+                    if let Some(previous) = previous {
+                        session_routes.write().await.remove(&previous);
+                        if let Err(e) = session_manager.destroy_session(&previous).await {
+                            warn!(
+                                session_id = %previous,
+                                error = %e,
+                                "Failed to destroy replaced session"
+                            );
+                        }
+                    }
+                }
+
                 let (session_id, is_new_session) = {
                     let mut write_guard = sender_sessions.write().await;
                     if let Some(existing_id) = write_guard.get(&key) {
                         (existing_id.clone(), false)
                     } else {
-                        let session_config =
-                            skill_config.session_config_for_channel(&channel_type_str);
+                        let session_config = skill_config.session_config_for_conversation(&key);
                         match session_manager.create_session(session_config).await {
                             Ok(id) => {
                                 info!(
                                     channel_type = %key.0,
                                     account_id = %key.1,
-                                    sender = %key.2,
+                                    conversation = %key.2,
                                     session_id = %id,
                                     "Created new session for sender"
                                 );
@@ -817,7 +1083,7 @@ impl ChannelBridge {
                                 error!(
                                     channel_type = %key.0,
                                     account_id = %key.1,
-                                    sender = %key.2,
+                                    conversation = %key.2,
                                     error = %e,
                                     "Failed to create session for sender"
                                 );
@@ -888,7 +1154,7 @@ impl ChannelBridge {
                             session_id = %session_id,
                             channel_type = %key.0,
                             account_id = %key.1,
-                            sender = %key.2,
+                            conversation = %key.2,
                             error = %e,
                             "Evicted stale session mapping after routing failure"
                         );
@@ -914,17 +1180,130 @@ impl ChannelBridge {
         channel_type: &str,
         account_id: &str,
         sender: &str,
+    ) -> Option<String> {
+        self.get_session_for_conversation(channel_type, account_id, sender)
+            .await
+    }
+
+    /// Session of a conversation, by its `conversation_key` (or sender, for
+    /// messages without one).
+    pub async fn get_session_for_conversation(
+        &self,
+        channel_type: &str,
+        account_id: &str,
+        conversation_key: &str,
     ) -> Option<String> {
         let guard = self.sender_sessions.read().await;
         guard
             .get(&(
                 channel_type.to_string(),
                 account_id.to_string(),
-                sender.to_string(),
+                conversation_key.to_string(),
             ))
             .cloned()
     }
 
+    /// Conversation-to-session routes, for the shutdown checkpoint.
+    pub async fn checkpoint_routes(&self) -> Vec<CheckpointRoute> {
+        let sender_sessions = self.sender_sessions.read().await;
+        let session_routes = self.session_routes.read().await;
+        let mut routes: Vec<CheckpointRoute> = sender_sessions
+            .iter()
+            .filter_map(
+                |((channel_type, account_id, conversation_key), session_id)| {
+                    let (_, _, channel_id) = session_routes.get(session_id)?;
+                    Some(CheckpointRoute {
+                        channel_type: channel_type.clone(),
+                        account_id: account_id.clone(),
+                        conversation_key: conversation_key.clone(),
+                        channel_id: channel_id.clone(),
+                        session_id: session_id.clone(),
+                    })
+                },
+            )
+            .collect();
+        routes.sort_by(|a, b| a.session_id.cmp(&b.session_id));
+        routes
+    }
+
+    /// Skill permissions are not serialized, so resolve them again for
+    /// checkpointed sessions that belong to a channel conversation.
+    pub fn apply_skill_permissions(&self, checkpoint: &mut Checkpoint) {
+        for session in &mut checkpoint.sessions {
+            let Some(route) = checkpoint
+                .routes
+                .iter()
+                .find(|route| route.session_id == session.id)
+            else {
+                continue;
+            };
+            let key = (
+                route.channel_type.clone(),
+                route.account_id.clone(),
+                route.conversation_key.clone(),
+            );
+            session.config.skill_permissions = self
+                .skill_config
+                .session_config_for_conversation(&key)
+                .skill_permissions;
+        }
+    }
+
+    /// Reattach conversations to their restored sessions so replies keep
+    /// flowing to the same chat. Routes to sessions that no longer exist are
+    /// dropped.
+    pub async fn restore_routes(&self, routes: Vec<CheckpointRoute>) -> usize {
+        let mut restored = 0usize;
+        for route in routes {
+            if !self.session_manager.has_session(&route.session_id).await {
+                continue;
+            }
+            let Some(event_stream) = self
+                .session_manager
+                .take_event_stream(&route.session_id)
+                .await
+            else {
+                continue;
+            };
+
+            self.sender_sessions.write().await.insert(
+                (
+                    route.channel_type.clone(),
+                    route.account_id.clone(),
+                    route.conversation_key,
+                ),
+                route.session_id.clone(),
+            );
+            self.session_routes.write().await.insert(
+                route.session_id.clone(),
+                (route.channel_type, route.account_id, route.channel_id),
+            );
+
+            let delivery_handle = ChannelBridge::spawn_response_delivery_task(
+                route.session_id,
+                event_stream,
+                self.channels.clone(),
+                self.session_routes.clone(),
+            );
+            self.response_handles.write().await.push(delivery_handle);
+            restored += 1;
+        }
+        restored
+    }
+
+    /// Post a message to the conversations routed to the given sessions.
+    pub async fn notify_sessions(&self, session_ids: &[String], message: &str) {
+        for session_id in session_ids {
+            ChannelBridge::deliver_to_channel(
+                session_id,
+                message.to_string(),
+                &self.channels,
+                &self.session_routes,
+            )
+            .await;
+        }
+    }
+
     /// Stop all channel listener tasks and clear state.
     pub async fn stop(&mut self) {
         for handle in self.channel_handles.drain(..) {
@@ -1183,6 +1562,93 @@ mod tests {
         assert!(limiter.check_and_record(&key2));
     }
 
+    #[test]
+    fn test_rate_limiter_notices_once_per_window() {
+        let mut limiter = RateLimiter::new(1, Duration::from_secs(60));
+        let key = ("slack".to_string(), "default".to_string(), "U1".to_string());
+
+        assert!(limiter.check_and_record(&key));
+        assert!(!limiter.check_and_record(&key));
+        assert!(limiter.take_notice(&key));
+        assert!(!limiter.take_notice(&key));
+
+        let wait = limiter.retry_after(&key);
+        assert!(wait > Duration::from_secs(55) && wait <= Duration::from_secs(60));
+    }
+
+    #[test]
+    fn test_is_sender_allowed() {
+        assert!(is_sender_allowed(&[], "slack", "U1"));
+
+        let allowed = vec!["U1".to_string(), "telegram:42".to_string()];
+        assert!(is_sender_allowed(&allowed, "slack", "U1"));
+        assert!(is_sender_allowed(&allowed, "telegram", "42"));
+        assert!(!is_sender_allowed(&allowed, "slack", "42"));
+        assert!(!is_sender_allowed(&allowed, "slack", "U2"));
+    }
+
+    #[tokio::test]
+    async fn test_limits_reply_to_throttled_and_oversized_messages() {
+        let sm = test_session_manager(100);
+        let mut bridge = ChannelBridge::new(sm.clone()).with_limits(ChannelLimitsConfig {
+            messages_per_window: 2,
+            max_prompt_chars: 10,
+            allowed_users: vec!["alice".to_string()],
+            ..ChannelLimitsConfig::default()
+        });
+
+        let channel = MockChannel::new(ChannelType::Slack);
+        let tx = channel.sender();
+        let sent = channel.sent_messages_shared();
+        bridge
+            .register_channel(Box::new(channel), "default".to_string())
+            .await
+            .unwrap();
+
+        tx.send(make_channel_message("mallory", "hi", ChannelType::Slack))
+            .await
+            .unwrap();
+        tx.send(make_channel_message(
+            "alice",
+            "this prompt is too long",
+            ChannelType::Slack,
+        ))
+        .await
+        .unwrap();
+        for _ in 0..3 {
+            tx.send(make_channel_message("alice", "hi", ChannelType::Slack))
+                .await
+                .unwrap();
+        }
+
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let notices = loop {
+            let notices: Vec<ChannelResponse> = sent
+                .lock()
+                .unwrap()
+                .iter()
+                .filter(|m| m.content.contains("the limit is 10") || m.content.contains("faster"))
+                .cloned()
+                .collect();
+            if notices.len() >= 2 || Instant::now() >= deadline {
+                break notices;
+            }
+            sleep(Duration::from_millis(10)).await;
+        };
+        assert_eq!(notices.len(), 2, "expected one notice of each kind");
+        assert!(notices[0].content.contains("the limit is 10"));
+        assert!(notices[1].content.contains("faster than I can take them"));
+        assert_eq!(notices[1].recipient, "test-channel");
+        // The allowed, short message got through; mallory was ignored
+        assert_eq!(sm.session_count().await, 1);
+        assert!(bridge
+            .get_session_for_sender("slack", "default", "mallory")
+            .await
+            .is_none());
+
+        bridge.stop().await;
+    }
+
     #[test]
     fn test_rate_limiter_cleanup_stale() {
         let mut limiter = RateLimiter::new(2, Duration::from_millis(1));
@@ -1327,6 +1793,104 @@ mod tests {
         bridge.stop().await;
     }
 
+    #[tokio::test]
+    async fn test_conversations_get_their_own_sessions_and_overrides() {
+        let sm = test_session_manager(100);
+        let mut skill_config = ChannelSkillConfig::new();
+        skill_config.add_channel_skills("telegram", vec!["github".to_string()], String::new());
+        skill_config
+            .add_conversation_overrides(
+                None,
+                vec![ConversationOverride {
+                    channel_type: "telegram".to_string(),
+                    account_id: "default".to_string(),
+                    conversation_key: "-100#topic:7".to_string(),
+                    agent: Some("autonomous".to_string()),
+                    skills: Some(vec!["triage".to_string()]),
+                }],
+            )
+            .unwrap();
+        let mut bridge = ChannelBridge::with_skill_config(sm.clone(), skill_config);
+
+        let channel = MockChannel::new(ChannelType::Telegram);
+        let tx = channel.sender();
+        bridge
+            .register_channel(Box::new(channel), "default".to_string())
+            .await
+            .unwrap();
+
+        for topic in ["-100#topic:7", "-100#topic:8"] {
+            let mut msg = make_channel_message("alice", "hello", ChannelType::Telegram);
+            msg.channel_id = topic.to_string();
+            msg.metadata
+                .insert("conversation_key".to_string(), topic.to_string());
+            tx.send(msg).await.unwrap();
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+
+        assert_eq!(sm.session_count().await, 2);
+        assert!(bridge
+            .get_session_for_sender("telegram", "default", "alice")
+            .await
+            .is_none());
+
+        let overridden = bridge
+            .get_session_for_conversation("telegram", "default", "-100#topic:7")
+            .await
+            .unwrap();
+        let config = sm.get_session_config(&overridden).await.unwrap();
+        assert_eq!(config.agent.as_deref(), Some("autonomous"));
+        assert_eq!(config.skills, vec!["triage".to_string()]);
+
+        let plain = bridge
+            .get_session_for_conversation("telegram", "default", "-100#topic:8")
+            .await
+            .unwrap();
+        let config = sm.get_session_config(&plain).await.unwrap();
+        assert!(config.agent.is_none());
+        assert_eq!(config.skills, vec!["github".to_string()]);
+
+        bridge.stop().await;
+    }
+
+    #[tokio::test]
+    async fn test_new_session_flag_replaces_session() {
+        let sm = test_session_manager(100);
+        let mut bridge = ChannelBridge::new(sm.clone());
+
+        let channel = MockChannel::new(ChannelType::Slack);
+        let tx = channel.sender();
+        bridge
+            .register_channel(Box::new(channel), "default".to_string())
+            .await
+            .unwrap();
+
+        tx.send(make_channel_message("alice", "hello", ChannelType::Slack))
+            .await
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+        let first = bridge
+            .get_session_for_sender("slack", "default", "alice")
+            .await
+            .unwrap();
+
+        let mut msg = make_channel_message("alice", "start over", ChannelType::Slack);
+        msg.metadata
+            .insert("new_session".to_string(), "true".to_string());
+        tx.send(msg).await.unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(200)).await;
+
+        let second = bridge
+            .get_session_for_sender("slack", "default", "alice")
+            .await
+            .unwrap();
+        assert_ne!(first, second);
+        assert!(!sm.has_session(&first).await);
+        assert_eq!(sm.session_count().await, 1);
+
+        bridge.stop().await;
+    }
+
     #[tokio::test]
     async fn test_different_channels_get_different_skills() {
         let sm = test_session_manager(100);
@@ -1400,6 +1964,32 @@ mod tests {
         bridge.stop().await;
     }
 
+    #[test]
+    fn test_channel_skills_carry_permissions() {
+        let dir = tempfile::tempdir().unwrap();
+        let skill_dir = dir.path().join("docs-writer");
+        std::fs::create_dir_all(&skill_dir).unwrap();
+        std::fs::write(
+            skill_dir.join("SKILL.md"),
+            "---\nname: docs-writer\ndescription: Writes docs\nallowed-tools: [Read, Write]\n---\nWrite docs.\n",
+        )
+        .unwrap();
+
+        let loader = SkillLoader::new(&[dir.path().to_str().unwrap()]).unwrap();
+        let skill_config = ChannelSkillConfig::from_active_skills(
+            Some(&loader),
+            HashMap::from([("slack".to_string(), vec!["docs-writer".to_string()])]),
+        )
+        .unwrap();
+
+        let config = skill_config.session_config_for_channel("slack");
+        assert_eq!(config.skill_permissions.len(), 1);
+        let (name, permissions) = &config.skill_permissions[0];
+        assert_eq!(name, "docs-writer");
+        assert!(!permissions.allows_tool("Bash"));
+        assert!(permissions.allows_tool("write"));
+    }
+
     #[tokio::test]
     async fn test_channel_without_skills_gets_default_config() {
         let sm = test_session_manager(100);
@@ -1512,6 +2102,71 @@ mod tests {
         bridge.stop().await;
     }
 
+    #[tokio::test]
+    async fn test_restored_routes_deliver_and_draining_replies_with_notice() {
+        let sm = test_session_manager_with_mock_client(MockModelClient::new("hello from mock"));
+        let mut bridge = ChannelBridge::new(sm.clone());
+        let channel = MockChannel::new(ChannelType::Telegram);
+        let tx = channel.sender();
+        let sent_messages = channel.sent_messages_shared();
+        bridge
+            .register_channel(Box::new(channel), "default".to_string())
+            .await
+            .unwrap();
+
+        tx.send(make_channel_message(
+            "user1",
+            "hello",
+            ChannelType::Telegram,
+        ))
+        .await
+        .unwrap();
+        wait_for_sent_message_count(&sent_messages, 1).await;
+
+        let routes = bridge.checkpoint_routes().await;
+        assert_eq!(routes.len(), 1);
+        assert_eq!(routes[0].conversation_key, "user1");
+        assert_eq!(routes[0].channel_id, "test-channel");
+        let session_id = routes[0].session_id.clone();
+
+        // A fresh bridge picks the conversation back up
+        let mut restored = ChannelBridge::new(sm.clone());
+        let channel = MockChannel::new(ChannelType::Telegram);
+        let tx = channel.sender();
+        let sent_messages = channel.sent_messages_shared();
+        restored
+            .register_channel(Box::new(channel), "default".to_string())
+            .await
+            .unwrap();
+        assert_eq!(restored.restore_routes(routes).await, 1);
+        assert_eq!(
+            restored
+                .get_session_for_sender("telegram", "default", "user1")
+                .await,
+            Some(session_id)
+        );
+
+        tx.send(make_channel_message(
+            "user1",
+            "again",
+            ChannelType::Telegram,
+        ))
+        .await
+        .unwrap();
+        let sent = wait_for_sent_message_count(&sent_messages, 1).await;
+        assert_eq!(sent[0].content.trim_end(), "hello from mock");
+
+        sm.begin_shutdown();
+        tx.send(make_channel_message("user1", "late", ChannelType::Telegram))
+            .await
+            .unwrap();
+        let sent = wait_for_sent_message_count(&sent_messages, 2).await;
+        assert_eq!(sent[1].content, ChannelBridge::RESTARTING_NOTICE);
+
+        restored.stop().await;
+        bridge.stop().await;
+    }
+
     #[tokio::test]
     async fn test_routes_agent_error_back_to_originating_channel() {
         let sm = test_session_manager_with_mock_client(MockModelClient::new("unused").with_error(
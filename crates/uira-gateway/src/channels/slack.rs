@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::fmt::Write as _;
 
 use async_trait::async_trait;
 use futures_util::{SinkExt, StreamExt};
@@ -12,17 +13,48 @@ use super::error::ChannelError;
 use super::types::{
     floor_char_boundary, ChannelCapabilities, ChannelMessage, ChannelResponse, ChannelType,
 };
+use crate::dashboard::{format_age, Dashboard, DashboardSummary};
 
 const SLACK_MAX_MESSAGE_LENGTH: usize = 4000;
 const CONNECTIONS_OPEN_URL: &str = "https://slack.com/api/apps.connections.open";
 const CHAT_POST_MESSAGE_URL: &str = "https://slack.com/api/chat.postMessage";
+const VIEWS_PUBLISH_URL: &str = "https://slack.com/api/views.publish";
 
+/// Entries listed per section of a slash-command reply or the App Home view
+const MAX_LISTED_ITEMS: usize = 10;
+
+const SLASH_COMMAND_USAGE: &str = "*Usage:*\n• `/uira status`: sessions, background tasks and goals\n• `/uira tasks`: background tasks\n• `/uira new <prompt>`: start a fresh session with a prompt";
+
+/// Slack channel over Socket Mode.
+///
+/// Besides channel messages, it answers the `/uira` slash command and, with a
+/// [`Dashboard`], publishes an App Home view summarizing the gateway.
 pub struct SlackChannel {
     config: SlackChannelConfig,
     message_tx: Option<mpsc::Sender<ChannelMessage>>,
     message_rx: Option<mpsc::Receiver<ChannelMessage>>,
     ws_handle: Option<JoinHandle<()>>,
     http_client: reqwest::Client,
+    dashboard: Option<Dashboard>,
+}
+
+/// Everything the Socket Mode loop needs to handle an envelope
+#[derive(Clone)]
+struct SocketModeContext {
+    tx: mpsc::Sender<ChannelMessage>,
+    allowed_channels: Vec<String>,
+    http_client: reqwest::Client,
+    bot_token: String,
+    dashboard: Option<Dashboard>,
+}
+
+/// A parsed `/uira` slash command
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum SlashCommand {
+    Status,
+    Tasks,
+    New(String),
+    Help,
 }
 
 impl SlackChannel {
@@ -34,8 +66,15 @@ impl SlackChannel {
             message_rx: Some(rx),
             ws_handle: None,
             http_client: reqwest::Client::new(),
+            dashboard: None,
         }
     }
+
+    /// Serve `/uira status`, `/uira tasks` and the App Home view from `dashboard`
+    pub fn with_dashboard(mut self, dashboard: Dashboard) -> Self {
+        self.dashboard = Some(dashboard);
+        self
+    }
 }
 
 #[async_trait]
@@ -60,7 +99,13 @@ impl Channel for SlackChannel {
             .message_tx
             .clone()
             .ok_or_else(|| ChannelError::Other("Message sender already taken".into()))?;
-        let allowed_channels = self.config.allowed_channels.clone();
+        let context = SocketModeContext {
+            tx: tx.clone(),
+            allowed_channels: self.config.allowed_channels.clone(),
+            http_client: self.http_client.clone(),
+            bot_token: self.config.bot_token.clone(),
+            dashboard: self.dashboard.clone(),
+        };
         let http_client = self.http_client.clone();
         let app_token = self.config.app_token.clone();
 
@@ -71,7 +116,7 @@ impl Channel for SlackChannel {
             let mut current_url = ws_url;
 
             loop {
-                match run_socket_mode_loop(&current_url, tx.clone(), &allowed_channels).await {
+                match run_socket_mode_loop(&current_url, &context).await {
                     Ok(()) => {
                         info!("Socket Mode loop ended; reconnecting");
                     }
@@ -196,8 +241,7 @@ async fn request_socket_mode_url(
 
 async fn run_socket_mode_loop(
     ws_url: &str,
-    tx: mpsc::Sender<ChannelMessage>,
-    allowed_channels: &[String],
+    context: &SocketModeContext,
 ) -> Result<(), ChannelError> {
     let (ws_stream, _) = tokio_tungstenite::connect_async(ws_url)
         .await
@@ -242,8 +286,21 @@ async fn run_socket_mode_loop(
             }
         };
 
+        // Slash commands are answered in the ack itself
+        let (reply, forwarded) = if envelope["type"].as_str() == Some("slash_commands") {
+            handle_slash_command(&envelope["payload"], context).await
+        } else {
+            (
+                None,
+                parse_socket_mode_event(&envelope, &context.allowed_channels),
+            )
+        };
+
         if let Some(envelope_id) = envelope["envelope_id"].as_str() {
-            let ack = build_envelope_ack(envelope_id);
+            let ack = match reply {
+                Some(text) => build_envelope_ack_with_reply(envelope_id, &text),
+                None => build_envelope_ack(envelope_id),
+            };
             if let Err(e) = ws_sink
                 .send(tokio_tungstenite::tungstenite::Message::Text(ack.into()))
                 .await
@@ -252,12 +309,21 @@ async fn run_socket_mode_loop(
             }
         }
 
-        if let Some(channel_msg) = parse_socket_mode_event(&envelope, allowed_channels) {
+        if let Some(user) = parse_app_home_opened(&envelope) {
+            if let Some(dashboard) = &context.dashboard {
+                let view = home_view(&dashboard.summary().await);
+                if let Err(e) = publish_home_view(context, user, view).await {
+                    warn!("Failed to publish App Home view: {e}");
+                }
+            }
+        }
+
+        if let Some(channel_msg) = forwarded {
             debug!(
                 "Received message from {}: {}",
                 channel_msg.sender, channel_msg.content
             );
-            if tx.send(channel_msg).await.is_err() {
+            if context.tx.send(channel_msg).await.is_err() {
                 info!("Message receiver dropped, stopping Socket Mode loop");
                 break;
             }
@@ -271,6 +337,252 @@ fn build_envelope_ack(envelope_id: &str) -> String {
     serde_json::json!({ "envelope_id": envelope_id }).to_string()
 }
 
+/// Ack carrying an ephemeral reply to a slash command
+fn build_envelope_ack_with_reply(envelope_id: &str, text: &str) -> String {
+    serde_json::json!({
+        "envelope_id": envelope_id,
+        "payload": { "response_type": "ephemeral", "text": text },
+    })
+    .to_string()
+}
+
+fn parse_slash_command(text: &str) -> SlashCommand {
+    let text = text.trim();
+    let (name, rest) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    match name.to_ascii_lowercase().as_str() {
+        "status" => SlashCommand::Status,
+        "tasks" => SlashCommand::Tasks,
+        "new" if !rest.trim().is_empty() => SlashCommand::New(rest.trim().to_string()),
+        _ => SlashCommand::Help,
+    }
+}
+
+/// Reply to a slash command, and the message to forward when it starts a session
+async fn handle_slash_command(
+    payload: &serde_json::Value,
+    context: &SocketModeContext,
+) -> (Option<String>, Option<ChannelMessage>) {
+    let channel_id = payload["channel_id"].as_str().unwrap_or_default();
+    if !is_channel_allowed(channel_id, &context.allowed_channels) {
+        return (
+            Some("Uira isn't enabled in this channel.".to_string()),
+            None,
+        );
+    }
+
+    let command = parse_slash_command(payload["text"].as_str().unwrap_or_default());
+    match (command, &context.dashboard) {
+        (SlashCommand::Status, Some(dashboard)) => {
+            (Some(status_text(&dashboard.summary().await)), None)
+        }
+        (SlashCommand::Tasks, Some(dashboard)) => {
+            (Some(tasks_text(&dashboard.summary().await)), None)
+        }
+        (SlashCommand::Status | SlashCommand::Tasks, None) => (
+            Some("Status isn't available from this gateway.".to_string()),
+            None,
+        ),
+        (SlashCommand::New(prompt), _) => {
+            let reply = format!("Starting a new session: _{}_", prompt);
+            (Some(reply), slash_command_message(payload, prompt))
+        }
+        (SlashCommand::Help, _) => (Some(SLASH_COMMAND_USAGE.to_string()), None),
+    }
+}
+
+/// The prompt of `/uira new`, marked so the bridge starts a fresh session
+fn slash_command_message(payload: &serde_json::Value, prompt: String) -> Option<ChannelMessage> {
+    let channel_id = payload["channel_id"].as_str()?;
+    let sender = payload["user_id"].as_str()?.to_string();
+
+    let mut metadata = HashMap::new();
+    metadata.insert("interaction_type".to_string(), "command".to_string());
+    metadata.insert(
+        "command_name".to_string(),
+        payload["command"]
+            .as_str()
+            .unwrap_or("/uira")
+            .trim_start_matches('/')
+            .to_string(),
+    );
+    metadata.insert("new_session".to_string(), "true".to_string());
+    if let Some(team) = payload["team_id"].as_str() {
+        metadata.insert("team".to_string(), team.to_string());
+    }
+
+    Some(ChannelMessage {
+        sender,
+        content: prompt,
+        channel_type: ChannelType::Slack,
+        channel_id: channel_id.to_string(),
+        timestamp: chrono::Utc::now(),
+        metadata,
+    })
+}
+
+/// User whose App Home tab was just opened
+fn parse_app_home_opened(envelope: &serde_json::Value) -> Option<&str> {
+    let event = &envelope["payload"]["event"];
+    if envelope["type"].as_str()? != "events_api"
+        || event["type"].as_str()? != "app_home_opened"
+        || event["tab"].as_str() != Some("home")
+    {
+        return None;
+    }
+    event["user"].as_str()
+}
+
+async fn publish_home_view(
+    context: &SocketModeContext,
+    user: &str,
+    view: serde_json::Value,
+) -> Result<(), ChannelError> {
+    let body: serde_json::Value = context
+        .http_client
+        .post(VIEWS_PUBLISH_URL)
+        .bearer_auth(&context.bot_token)
+        .json(&serde_json::json!({ "user_id": user, "view": view }))
+        .send()
+        .await
+        .map_err(|e| ChannelError::SendFailed(e.to_string()))?
+        .json()
+        .await
+        .map_err(|e| ChannelError::SendFailed(e.to_string()))?;
+
+    if body.get("ok") != Some(&serde_json::Value::Bool(true)) {
+        let err = body["error"].as_str().unwrap_or("unknown error");
+        return Err(ChannelError::SendFailed(err.to_string()));
+    }
+    Ok(())
+}
+
+fn sessions_section(summary: &DashboardSummary) -> String {
+    if summary.sessions.is_empty() {
+        return "No sessions.".to_string();
+    }
+    let mut out = String::new();
+    for session in summary.sessions.iter().take(MAX_LISTED_ITEMS) {
+        let _ = writeln!(
+            out,
+            "• `{}` {} · {} · last message {}",
+            session.id,
+            session.status,
+            session.config.agent.as_deref().unwrap_or("default agent"),
+            format_age(session.last_message_at, summary.generated_at)
+        );
+    }
+    if summary.sessions.len() > MAX_LISTED_ITEMS {
+        let _ = writeln!(
+            out,
+            "…and {} more",
+            summary.sessions.len() - MAX_LISTED_ITEMS
+        );
+    }
+    out.trim_end().to_string()
+}
+
+fn tasks_section(summary: &DashboardSummary) -> String {
+    if summary.tasks.is_empty() {
+        return "No background tasks.".to_string();
+    }
+    let mut out = String::new();
+    for task in summary.tasks.iter().take(MAX_LISTED_ITEMS) {
+        let _ = writeln!(
+            out,
+            "• `{}` {} · {} ({}) · started {}",
+            task.id,
+            task.status.as_str(),
+            task.description,
+            task.agent,
+            format_age(task.started_at, summary.generated_at)
+        );
+    }
+    if summary.tasks.len() > MAX_LISTED_ITEMS {
+        let _ = writeln!(out, "…and {} more", summary.tasks.len() - MAX_LISTED_ITEMS);
+    }
+    out.trim_end().to_string()
+}
+
+fn goals_section(summary: &DashboardSummary) -> String {
+    if summary.goals.is_empty() {
+        return "No goals checked yet.".to_string();
+    }
+    let mut out = String::new();
+    for goal in summary.goals.iter().take(MAX_LISTED_ITEMS) {
+        let _ = writeln!(
+            out,
+            "• {} *{}* {:.1}/{:.1} · checked {}",
+            if goal.passed {
+                ":white_check_mark:"
+            } else {
+                ":x:"
+            },
+            goal.goal,
+            goal.score,
+            goal.target,
+            format_age(goal.checked_at, summary.generated_at)
+        );
+    }
+    out.trim_end().to_string()
+}
+
+fn overview_line(summary: &DashboardSummary) -> String {
+    format!(
+        "{} sessions ({} active) · {} background tasks running or queued · {}/{} goals passing",
+        summary.sessions.len(),
+        summary.active_sessions(),
+        summary.unfinished_tasks(),
+        summary.passing_goals(),
+        summary.goals.len()
+    )
+}
+
+/// `/uira status` reply
+fn status_text(summary: &DashboardSummary) -> String {
+    format!(
+        "{}\n\n*Sessions*\n{}\n\n*Goals*\n{}",
+        overview_line(summary),
+        sessions_section(summary),
+        goals_section(summary)
+    )
+}
+
+/// `/uira tasks` reply
+fn tasks_text(summary: &DashboardSummary) -> String {
+    format!("*Background tasks*\n{}", tasks_section(summary))
+}
+
+/// App Home view in Block Kit
+fn home_view(summary: &DashboardSummary) -> serde_json::Value {
+    let section = |text: String| {
+        serde_json::json!({
+            "type": "section",
+            "text": { "type": "mrkdwn", "text": text },
+        })
+    };
+    serde_json::json!({
+        "type": "home",
+        "blocks": [
+            { "type": "header", "text": { "type": "plain_text", "text": "Uira" } },
+            section(overview_line(summary)),
+            { "type": "divider" },
+            section(format!("*Sessions*\n{}", sessions_section(summary))),
+            section(format!("*Background tasks*\n{}", tasks_section(summary))),
+            section(format!("*Goals*\n{}", goals_section(summary))),
+            {
+                "type": "context",
+                "elements": [{
+                    "type": "mrkdwn",
+                    "text": format!(
+                        "Updated {} · `/uira status`, `/uira tasks`, `/uira new <prompt>`",
+                        summary.generated_at.format("%Y-%m-%d %H:%M UTC")
+                    ),
+                }],
+            },
+        ],
+    })
+}
+
 fn parse_socket_mode_event(
     envelope: &serde_json::Value,
     allowed_channels: &[String],
@@ -603,6 +915,121 @@ mod tests {
         assert_eq!(msg.metadata.get("team").unwrap(), "T12345");
     }
 
+    #[test]
+    fn test_parse_slash_command() {
+        assert_eq!(parse_slash_command("status"), SlashCommand::Status);
+        assert_eq!(parse_slash_command("  TASKS "), SlashCommand::Tasks);
+        assert_eq!(
+            parse_slash_command("new fix the flaky test\nin ci"),
+            SlashCommand::New("fix the flaky test\nin ci".to_string())
+        );
+        assert_eq!(parse_slash_command("new"), SlashCommand::Help);
+        assert_eq!(parse_slash_command(""), SlashCommand::Help);
+        assert_eq!(parse_slash_command("deploy"), SlashCommand::Help);
+    }
+
+    #[tokio::test]
+    async fn test_slash_command_new_forwards_prompt() {
+        let (tx, _rx) = mpsc::channel(1);
+        let context = SocketModeContext {
+            tx,
+            allowed_channels: vec!["C12345".to_string()],
+            http_client: reqwest::Client::new(),
+            bot_token: "xoxb-test".to_string(),
+            dashboard: None,
+        };
+        let payload = serde_json::json!({
+            "command": "/uira",
+            "text": "new triage open issues",
+            "user_id": "U12345",
+            "channel_id": "C12345",
+            "team_id": "T12345"
+        });
+
+        let (reply, forwarded) = handle_slash_command(&payload, &context).await;
+        assert!(reply.unwrap().contains("triage open issues"));
+        let msg = forwarded.unwrap();
+        assert_eq!(msg.content, "triage open issues");
+        assert_eq!(msg.sender, "U12345");
+        assert_eq!(msg.channel_id, "C12345");
+        assert_eq!(msg.metadata["new_session"], "true");
+        assert_eq!(msg.metadata["command_name"], "uira");
+
+        let (reply, forwarded) = handle_slash_command(
+            &serde_json::json!({"text": "status", "channel_id": "C12345"}),
+            &context,
+        )
+        .await;
+        assert_eq!(reply.unwrap(), "Status isn't available from this gateway.");
+        assert!(forwarded.is_none());
+
+        let (reply, _) = handle_slash_command(
+            &serde_json::json!({"text": "status", "channel_id": "C99999"}),
+            &context,
+        )
+        .await;
+        assert_eq!(reply.unwrap(), "Uira isn't enabled in this channel.");
+    }
+
+    #[test]
+    fn test_slash_command_ack_carries_reply() {
+        let ack: serde_json::Value =
+            serde_json::from_str(&build_envelope_ack_with_reply("env-1", "hi")).unwrap();
+        assert_eq!(ack["envelope_id"], "env-1");
+        assert_eq!(ack["payload"]["text"], "hi");
+        assert_eq!(ack["payload"]["response_type"], "ephemeral");
+    }
+
+    #[test]
+    fn test_parse_app_home_opened() {
+        let envelope = serde_json::json!({
+            "envelope_id": "abc123",
+            "type": "events_api",
+            "payload": {"event": {"type": "app_home_opened", "user": "U12345", "tab": "home"}}
+        });
+        assert_eq!(parse_app_home_opened(&envelope), Some("U12345"));
+        assert!(parse_socket_mode_event(&envelope, &[]).is_none());
+
+        let messages_tab = serde_json::json!({
+            "type": "events_api",
+            "payload": {"event": {"type": "app_home_opened", "user": "U12345", "tab": "messages"}}
+        });
+        assert!(parse_app_home_opened(&messages_tab).is_none());
+    }
+
+    #[test]
+    fn test_dashboard_rendering() {
+        use crate::session_manager::GoalStatus;
+
+        let summary = DashboardSummary::new(
+            Vec::new(),
+            Vec::new(),
+            vec![GoalStatus {
+                goal: "coverage".to_string(),
+                session_id: "gw_ses_1".to_string(),
+                score: 72.5,
+                target: 80.0,
+                passed: false,
+                checked_at: chrono::Utc::now(),
+            }],
+        );
+
+        let status = status_text(&summary);
+        assert!(status.starts_with("0 sessions (0 active)"));
+        assert!(status.contains("0/1 goals passing"));
+        assert!(status.contains("No sessions."));
+        assert!(status.contains(":x: *coverage* 72.5/80.0"));
+        assert!(tasks_text(&summary).contains("No background tasks."));
+
+        let view = home_view(&summary);
+        assert_eq!(view["type"], "home");
+        assert_eq!(view["blocks"][0]["type"], "header");
+        assert!(view["blocks"][5]["text"]["text"]
+            .as_str()
+            .unwrap()
+            .contains("coverage"));
+    }
+
     #[test]
     fn test_slack_channel_type() {
         let config = SlackChannelConfig {
@@ -4,14 +4,14 @@
 //! allowing Uira to receive and send messages via the Telegram Bot API.
 
 use std::collections::HashMap;
-
-#[cfg(test)]
+use std::fmt;
+use std::str::FromStr;
 use std::sync::{Arc, Mutex};
 
 use async_trait::async_trait;
 use chrono::Utc;
 use teloxide::prelude::*;
-use teloxide::types::Me;
+use teloxide::types::{Me, MessageId, ReplyParameters, ThreadId};
 use tokio::sync::mpsc;
 use tokio::task::JoinHandle;
 use tracing::{debug, error, info, warn};
@@ -22,21 +22,30 @@ use super::error::ChannelError;
 use super::types::{
     floor_char_boundary, ChannelCapabilities, ChannelMessage, ChannelResponse, ChannelType,
 };
+use crate::channel_bridge::ConversationOverride;
 
 /// Maximum message length for Telegram messages (in characters).
 const TELEGRAM_MAX_MESSAGE_LENGTH: usize = 4096;
 
+/// Reply-to-root mappings kept before the index is reset.
+const MAX_TRACKED_REPLIES: usize = 10_000;
+
 /// Telegram channel that communicates via the Telegram Bot API.
 ///
 /// Uses teloxide for polling-based update handling. Inbound text messages
 /// are converted to [`ChannelMessage`] and forwarded through an mpsc channel.
 /// Outbound messages are sent via the Telegram Bot API, automatically chunked
 /// if they exceed Telegram's 4096-character limit.
+///
+/// With `thread_sessions` enabled, messages in a forum topic or in a group
+/// reply thread carry a `conversation_key` so the bridge gives each thread its
+/// own session, and responses are posted back into the same topic or thread.
 pub struct TelegramChannel {
     config: TelegramChannelConfig,
     message_tx: Option<mpsc::Sender<ChannelMessage>>,
     message_rx: Option<mpsc::Receiver<ChannelMessage>>,
     bot_handle: Option<JoinHandle<()>>,
+    reply_threads: Arc<Mutex<ReplyThreads>>,
     #[cfg(test)]
     sent_messages: Arc<Mutex<Vec<ChannelResponse>>>,
 }
@@ -50,10 +59,184 @@ impl TelegramChannel {
             message_tx: Some(tx),
             message_rx: Some(rx),
             bot_handle: None,
+            reply_threads: Arc::new(Mutex::new(ReplyThreads::default())),
             #[cfg(test)]
             sent_messages: Arc::new(Mutex::new(Vec::new())),
         }
     }
+
+    /// Send one chunk to `target`, posting it into the target's topic or
+    /// reply thread.
+    async fn send_chunk(
+        &self,
+        bot: &Bot,
+        target: TelegramTarget,
+        text: &str,
+    ) -> Result<Message, ChannelError> {
+        let mut request = bot.send_message(ChatId(target.chat_id), text);
+        match target.thread {
+            Some(TelegramThread::Topic(thread_id)) => {
+                request = request.message_thread_id(ThreadId(MessageId(thread_id)));
+            }
+            Some(TelegramThread::Reply(root)) => {
+                request = request.reply_parameters(
+                    ReplyParameters::new(MessageId(root)).allow_sending_without_reply(),
+                );
+            }
+            None => {}
+        }
+
+        let sent = request
+            .await
+            .map_err(|e| ChannelError::SendFailed(format!("Telegram send error: {e}")))?;
+
+        // Replies to the bot's messages continue the thread they were posted in
+        if let Some(TelegramThread::Reply(root)) = target.thread {
+            lock_threads(&self.reply_threads).record(target.chat_id, sent.id.0, root);
+        }
+        Ok(sent)
+    }
+}
+
+/// A Telegram chat, optionally narrowed to a forum topic or a reply thread.
+///
+/// Used as the channel ID of inbound messages and parsed back from response
+/// recipients. Formats as `<chat_id>`, `<chat_id>#topic:<id>` or
+/// `<chat_id>#reply:<root message id>`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TelegramTarget {
+    pub chat_id: i64,
+    pub thread: Option<TelegramThread>,
+}
+
+/// A conversation within a Telegram chat
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TelegramThread {
+    /// A forum topic, by its `message_thread_id`
+    Topic(i32),
+    /// A reply thread in a group, by the ID of the message that started it
+    Reply(i32),
+}
+
+impl fmt::Display for TelegramTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.thread {
+            None => write!(f, "{}", self.chat_id),
+            Some(TelegramThread::Topic(id)) => write!(f, "{}#topic:{}", self.chat_id, id),
+            Some(TelegramThread::Reply(id)) => write!(f, "{}#reply:{}", self.chat_id, id),
+        }
+    }
+}
+
+impl FromStr for TelegramTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (chat, thread) = match s.split_once('#') {
+            Some((chat, thread)) => (chat, Some(thread)),
+            None => (s, None),
+        };
+        let chat_id = chat
+            .parse()
+            .map_err(|e| format!("Invalid chat_id '{chat}': {e}"))?;
+        let thread = match thread {
+            None => None,
+            Some(thread) => {
+                let (kind, id) = thread
+                    .split_once(':')
+                    .ok_or_else(|| format!("Invalid thread '{thread}'"))?;
+                let id = id
+                    .parse()
+                    .map_err(|e| format!("Invalid thread id '{id}': {e}"))?;
+                match kind {
+                    "topic" => Some(TelegramThread::Topic(id)),
+                    "reply" => Some(TelegramThread::Reply(id)),
+                    _ => return Err(format!("Unknown thread kind '{kind}'")),
+                }
+            }
+        };
+        Ok(Self { chat_id, thread })
+    }
+}
+
+/// Maps messages in group reply chains to the message that started the chain.
+///
+/// Telegram only reports the message a reply points at, so every message seen
+/// or sent in a thread is recorded to resolve deeper replies to the same root.
+#[derive(Debug, Default)]
+pub struct ReplyThreads {
+    roots: HashMap<(i64, i32), i32>,
+}
+
+impl ReplyThreads {
+    /// Root of the thread containing `message_id`; a message outside any
+    /// known thread starts its own.
+    pub fn root_of(&self, chat_id: i64, message_id: i32) -> i32 {
+        self.roots
+            .get(&(chat_id, message_id))
+            .copied()
+            .unwrap_or(message_id)
+    }
+
+    pub fn record(&mut self, chat_id: i64, message_id: i32, root: i32) {
+        if self.roots.len() >= MAX_TRACKED_REPLIES {
+            self.roots.clear();
+        }
+        self.roots.insert((chat_id, message_id), root);
+    }
+}
+
+fn lock_threads(threads: &Mutex<ReplyThreads>) -> std::sync::MutexGuard<'_, ReplyThreads> {
+    threads.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Conversation overrides for the configured forum topics of a Telegram account.
+///
+/// Topics with an unparseable chat ID are skipped with a warning.
+pub fn topic_overrides(config: &TelegramChannelConfig) -> Vec<ConversationOverride> {
+    config
+        .topics
+        .iter()
+        .filter_map(|topic| {
+            let chat_id = match topic.chat_id.parse() {
+                Ok(chat_id) => chat_id,
+                Err(e) => {
+                    warn!(chat_id = %topic.chat_id, "Ignoring Telegram topic override: {e}");
+                    return None;
+                }
+            };
+            let target = TelegramTarget {
+                chat_id,
+                thread: Some(TelegramThread::Topic(topic.thread_id)),
+            };
+            Some(ConversationOverride {
+                channel_type: ChannelType::Telegram.to_string(),
+                account_id: config.account_id.clone(),
+                conversation_key: target.to_string(),
+                agent: topic.agent.clone(),
+                skills: topic.skills.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Work out which topic or reply thread a message belongs to, recording it
+/// so replies to it land in the same thread.
+///
+/// Private chats and messages outside any topic or reply chain have no thread.
+pub fn message_thread(msg: &Message, reply_threads: &mut ReplyThreads) -> Option<TelegramThread> {
+    if msg.is_topic_message {
+        return msg
+            .thread_id
+            .map(|thread_id| TelegramThread::Topic(thread_id.0 .0));
+    }
+    if !(msg.chat.is_group() || msg.chat.is_supergroup()) {
+        return None;
+    }
+    let replied = msg.reply_to_message()?;
+    let root = reply_threads.root_of(msg.chat.id.0, replied.id.0);
+    reply_threads.record(msg.chat.id.0, msg.id.0, root);
+    Some(TelegramThread::Reply(root))
 }
 
 /// Check whether a user is allowed based on the allowed_users list.
@@ -114,10 +297,13 @@ pub fn chunk_message(text: &str, max_len: usize) -> Vec<String> {
 
 /// Convert a teloxide [`Message`] into a [`ChannelMessage`].
 ///
-/// Returns `None` if the message has no text content or no sender.
+/// Messages in a `thread` are addressed to that thread and carry it as their
+/// `conversation_key`. Returns `None` if the message has no text content or
+/// no sender.
 pub fn telegram_message_to_channel_message(
     msg: &Message,
     bot_username: &str,
+    thread: Option<TelegramThread>,
 ) -> Option<ChannelMessage> {
     let text = msg.text()?;
     let from = msg.from.as_ref()?;
@@ -134,11 +320,23 @@ pub fn telegram_message_to_channel_message(
         metadata.insert("username".to_string(), uname.clone());
     }
 
+    let target = TelegramTarget {
+        chat_id: msg.chat.id.0,
+        thread,
+    };
+    if let Some(thread) = thread {
+        let thread_id = match thread {
+            TelegramThread::Topic(id) | TelegramThread::Reply(id) => id,
+        };
+        metadata.insert("thread_id".to_string(), thread_id.to_string());
+        metadata.insert("conversation_key".to_string(), target.to_string());
+    }
+
     Some(ChannelMessage {
         sender,
         content: text.to_string(),
         channel_type: ChannelType::Telegram,
-        channel_id: msg.chat.id.to_string(),
+        channel_id: target.to_string(),
         timestamp: Utc::now(),
         metadata,
     })
@@ -184,12 +382,15 @@ impl Channel for TelegramChannel {
             .clone()
             .ok_or_else(|| ChannelError::ChannelClosed)?;
         let allowed_users = self.config.allowed_users.clone();
+        let thread_sessions = self.config.thread_sessions;
+        let reply_threads = self.reply_threads.clone();
 
         let handle = tokio::spawn(async move {
             let handler = Update::filter_message().endpoint(move |_bot: Bot, msg: Message| {
                 let tx = message_tx.clone();
                 let allowed = allowed_users.clone();
                 let bot_uname = bot_username.clone();
+                let reply_threads = reply_threads.clone();
 
                 async move {
                     if let Some(from) = &msg.from {
@@ -205,7 +406,14 @@ impl Channel for TelegramChannel {
                         return Ok(());
                     }
 
-                    if let Some(channel_msg) = telegram_message_to_channel_message(&msg, &bot_uname)
+                    let thread = if thread_sessions {
+                        message_thread(&msg, &mut lock_threads(&reply_threads))
+                    } else {
+                        None
+                    };
+
+                    if let Some(channel_msg) =
+                        telegram_message_to_channel_message(&msg, &bot_uname, thread)
                     {
                         if let Err(e) = tx.send(channel_msg).await {
                             error!("Failed to forward Telegram message: {}", e);
@@ -237,16 +445,15 @@ impl Channel for TelegramChannel {
     async fn send_message(&self, response: ChannelResponse) -> Result<(), ChannelError> {
         let bot = Bot::new(&self.config.bot_token);
 
-        let chat_id: i64 = response.recipient.parse().map_err(|e| {
-            ChannelError::SendFailed(format!("Invalid chat_id '{}': {e}", response.recipient))
-        })?;
+        let target: TelegramTarget = response
+            .recipient
+            .parse()
+            .map_err(ChannelError::SendFailed)?;
 
         let chunks = chunk_message(&response.content, TELEGRAM_MAX_MESSAGE_LENGTH);
 
         for chunk in &chunks {
-            bot.send_message(ChatId(chat_id), chunk)
-                .await
-                .map_err(|e| ChannelError::SendFailed(format!("Telegram send error: {e}")))?;
+            self.send_chunk(&bot, target, chunk).await?;
         }
 
         #[cfg(test)]
@@ -261,19 +468,17 @@ impl Channel for TelegramChannel {
     ) -> Result<Option<String>, ChannelError> {
         let bot = Bot::new(&self.config.bot_token);
 
-        let chat_id: i64 = response.recipient.parse().map_err(|e| {
-            ChannelError::SendFailed(format!("Invalid chat_id '{}': {e}", response.recipient))
-        })?;
+        let target: TelegramTarget = response
+            .recipient
+            .parse()
+            .map_err(ChannelError::SendFailed)?;
 
         let chunks = chunk_message(&response.content, TELEGRAM_MAX_MESSAGE_LENGTH);
         let first_chunk = chunks
             .first()
             .ok_or_else(|| ChannelError::SendFailed("Cannot send empty message".to_string()))?;
 
-        let sent = bot
-            .send_message(ChatId(chat_id), first_chunk)
-            .await
-            .map_err(|e| ChannelError::SendFailed(format!("Telegram send error: {e}")))?;
+        let sent = self.send_chunk(&bot, target, first_chunk).await?;
 
         #[cfg(test)]
         self.sent_messages.lock().unwrap().push(response);
@@ -289,19 +494,13 @@ impl Channel for TelegramChannel {
     ) -> Result<(), ChannelError> {
         let bot = Bot::new(&self.config.bot_token);
 
-        let chat_id: i64 = recipient
-            .parse()
-            .map_err(|e| ChannelError::SendFailed(format!("Invalid chat_id '{recipient}': {e}")))?;
+        let target: TelegramTarget = recipient.parse().map_err(ChannelError::SendFailed)?;
         let message_id: i32 = message_id.parse().map_err(|e| {
             ChannelError::SendFailed(format!("Invalid message_id '{message_id}': {e}"))
         })?;
 
         let result = bot
-            .edit_message_text(
-                ChatId(chat_id),
-                teloxide::types::MessageId(message_id),
-                new_content,
-            )
+            .edit_message_text(ChatId(target.chat_id), MessageId(message_id), new_content)
             .await;
 
         match result {
@@ -335,7 +534,123 @@ mod tests {
             active_skills: Vec::new(),
             stream_mode: "partial".to_string(),
             stream_throttle_ms: 300,
+            thread_sessions: true,
+            topics: Vec::new(),
+        }
+    }
+
+    fn group_message(message_id: i32, extra: serde_json::Value) -> Message {
+        let mut json = serde_json::json!({
+            "message_id": message_id,
+            "date": 1_700_000_000,
+            "chat": {"id": -1001234, "type": "supergroup", "title": "Team", "is_forum": true},
+            "from": {"id": 42, "is_bot": false, "first_name": "Ada", "username": "ada"},
+            "text": "hello"
+        });
+        json.as_object_mut()
+            .unwrap()
+            .extend(extra.as_object().unwrap().clone());
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[test]
+    fn test_topic_overrides() {
+        let mut config = test_config();
+        config.topics = vec![
+            uira_core::schema::TelegramTopicConfig {
+                chat_id: "-1001234".to_string(),
+                thread_id: 7,
+                agent: Some("orchestrator".to_string()),
+                skills: None,
+            },
+            uira_core::schema::TelegramTopicConfig {
+                chat_id: "not-a-chat".to_string(),
+                thread_id: 8,
+                agent: None,
+                skills: None,
+            },
+        ];
+
+        let overrides = topic_overrides(&config);
+        assert_eq!(overrides.len(), 1);
+        assert_eq!(overrides[0].conversation_key, "-1001234#topic:7");
+        assert_eq!(overrides[0].account_id, "default");
+        assert_eq!(overrides[0].agent.as_deref(), Some("orchestrator"));
+    }
+
+    #[test]
+    fn test_telegram_target_round_trip() {
+        for raw in ["12345", "-1001234#topic:7", "-1001234#reply:99"] {
+            let target: TelegramTarget = raw.parse().unwrap();
+            assert_eq!(target.to_string(), raw);
         }
+        assert_eq!(
+            "-1001234#topic:7".parse::<TelegramTarget>().unwrap(),
+            TelegramTarget {
+                chat_id: -1001234,
+                thread: Some(TelegramThread::Topic(7)),
+            }
+        );
+        assert!("abc".parse::<TelegramTarget>().is_err());
+        assert!("1#thread:2".parse::<TelegramTarget>().is_err());
+    }
+
+    #[test]
+    fn test_forum_topic_messages_get_topic_conversation() {
+        let mut threads = ReplyThreads::default();
+        let msg = group_message(
+            10,
+            serde_json::json!({"message_thread_id": 7, "is_topic_message": true}),
+        );
+
+        let thread = message_thread(&msg, &mut threads);
+        assert_eq!(thread, Some(TelegramThread::Topic(7)));
+
+        let channel_msg = telegram_message_to_channel_message(&msg, "uira_bot", thread).unwrap();
+        assert_eq!(channel_msg.channel_id, "-1001234#topic:7");
+        assert_eq!(
+            channel_msg
+                .metadata
+                .get("conversation_key")
+                .map(String::as_str),
+            Some("-1001234#topic:7")
+        );
+        assert_eq!(channel_msg.metadata["chat_id"], "-1001234");
+    }
+
+    #[test]
+    fn test_reply_chains_resolve_to_their_root() {
+        let mut threads = ReplyThreads::default();
+        let root = group_message(1, serde_json::json!({}));
+        assert_eq!(message_thread(&root, &mut threads), None);
+
+        let reply = group_message(
+            2,
+            serde_json::json!({"reply_to_message": group_message(1, serde_json::json!({}))}),
+        );
+        assert_eq!(
+            message_thread(&reply, &mut threads),
+            Some(TelegramThread::Reply(1))
+        );
+
+        // A bot response posted into the thread, then a reply to it
+        threads.record(-1001234, 3, 1);
+        let nested = group_message(
+            4,
+            serde_json::json!({"reply_to_message": group_message(3, serde_json::json!({}))}),
+        );
+        assert_eq!(
+            message_thread(&nested, &mut threads),
+            Some(TelegramThread::Reply(1))
+        );
+
+        let channel_msg = telegram_message_to_channel_message(
+            &nested,
+            "uira_bot",
+            Some(TelegramThread::Reply(1)),
+        )
+        .unwrap();
+        assert_eq!(channel_msg.channel_id, "-1001234#reply:1");
     }
 
     #[test]
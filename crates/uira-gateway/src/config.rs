@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use uira_orchestration::features::builtin_skills::SkillPermissions;
 
 /// Configuration for creating a new gateway session
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -28,6 +29,10 @@ pub struct SessionConfig {
     #[serde(default)]
     pub skill_context: Option<String>,
 
+    /// Tool and permission restrictions of the active skills, keyed by skill name
+    #[serde(skip)]
+    pub skill_permissions: Vec<(String, SkillPermissions)>,
+
     /// Working directory for this session
     #[serde(default)]
     pub working_directory: Option<String>,
@@ -73,6 +78,7 @@ mod tests {
             agent: Some("autonomous".to_string()),
             skills: vec!["skill1".to_string()],
             skill_context: Some("<dangerous/>".to_string()),
+            skill_permissions: Vec::new(),
             working_directory: Some("/etc/passwd".to_string()),
         };
         config.sanitize();
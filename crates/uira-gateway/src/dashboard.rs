@@ -0,0 +1,178 @@
+//! Gateway status summary for chat dashboards
+//!
+//! Collects active sessions, background tasks and the latest goal results
+//! into a [`DashboardSummary`], which channels render as slash-command
+//! replies or home views.
+
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
+use uira_orchestration::background_agent::{
+    get_background_manager, BackgroundTask, BackgroundTaskConfig,
+};
+
+use crate::session_manager::{GoalStatus, SessionInfo, SessionManager, SessionStatus};
+
+/// Source of dashboard summaries, backed by the gateway's sessions
+#[derive(Clone)]
+pub struct Dashboard {
+    session_manager: Arc<SessionManager>,
+}
+
+impl Dashboard {
+    pub fn new(session_manager: Arc<SessionManager>) -> Self {
+        Self { session_manager }
+    }
+
+    /// Snapshot of the gateway right now
+    pub async fn summary(&self) -> DashboardSummary {
+        let sessions = self.session_manager.list_sessions().await;
+        let tasks = get_background_manager(BackgroundTaskConfig::default()).get_all_tasks();
+        DashboardSummary::new(sessions, tasks, self.session_manager.goal_statuses())
+    }
+}
+
+/// Sessions, background tasks and goal results at one point in time
+#[derive(Debug, Clone)]
+pub struct DashboardSummary {
+    /// Gateway sessions, oldest first
+    pub sessions: Vec<SessionInfo>,
+    /// Background tasks, unfinished first, then most recently started
+    pub tasks: Vec<BackgroundTask>,
+    /// Latest result of each goal, by name
+    pub goals: Vec<GoalStatus>,
+    pub generated_at: DateTime<Utc>,
+}
+
+impl DashboardSummary {
+    pub fn new(
+        sessions: Vec<SessionInfo>,
+        mut tasks: Vec<BackgroundTask>,
+        goals: Vec<GoalStatus>,
+    ) -> Self {
+        tasks.sort_by(|a, b| {
+            a.status
+                .is_terminal()
+                .cmp(&b.status.is_terminal())
+                .then(b.started_at.cmp(&a.started_at))
+        });
+        Self {
+            sessions,
+            tasks,
+            goals,
+            generated_at: Utc::now(),
+        }
+    }
+
+    /// Sessions currently processing a message
+    pub fn active_sessions(&self) -> usize {
+        self.sessions
+            .iter()
+            .filter(|session| session.status == SessionStatus::Active)
+            .count()
+    }
+
+    /// Background tasks that are queued or running
+    pub fn unfinished_tasks(&self) -> usize {
+        self.tasks
+            .iter()
+            .filter(|task| !task.status.is_terminal())
+            .count()
+    }
+
+    pub fn passing_goals(&self) -> usize {
+        self.goals.iter().filter(|goal| goal.passed).count()
+    }
+}
+
+/// Coarse age of a timestamp, e.g. `5m ago`
+pub fn format_age(at: DateTime<Utc>, now: DateTime<Utc>) -> String {
+    let secs = (now - at).num_seconds().max(0);
+    match secs {
+        0..=59 => format!("{}s ago", secs),
+        60..=3599 => format!("{}m ago", secs / 60),
+        3600..=86_399 => format!("{}h ago", secs / 3600),
+        _ => format!("{}d ago", secs / 86_400),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use uira_core::schema::GatewaySettings;
+    use uira_orchestration::background_agent::BackgroundTaskStatus;
+
+    use crate::config::SessionConfig;
+
+    fn task(id: &str, status: BackgroundTaskStatus, started_mins_ago: i64) -> BackgroundTask {
+        serde_json::from_value(serde_json::json!({
+            "id": id,
+            "session_id": format!("ses_{id}"),
+            "parent_session_id": "parent",
+            "description": format!("task {id}"),
+            "prompt": "do it",
+            "agent": "explore",
+            "status": status,
+            "started_at": Utc::now() - Duration::minutes(started_mins_ago),
+        }))
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_summary_counts_sessions_tasks_and_goals() {
+        let manager = Arc::new(SessionManager::new_with_settings(
+            10,
+            GatewaySettings {
+                provider: "ollama".to_string(),
+                model: "llama3.1".to_string(),
+                ..GatewaySettings::default()
+            },
+        ));
+        manager
+            .create_session(SessionConfig::default())
+            .await
+            .unwrap();
+        manager.record_goal_status(GoalStatus {
+            goal: "coverage".to_string(),
+            session_id: "gw_ses_1".to_string(),
+            score: 85.0,
+            target: 80.0,
+            passed: true,
+            checked_at: Utc::now(),
+        });
+
+        let summary = Dashboard::new(manager.clone()).summary().await;
+        assert_eq!(summary.sessions.len(), 1);
+        assert_eq!(summary.active_sessions(), 1);
+        assert_eq!(summary.passing_goals(), 1);
+        assert_eq!(summary.goals[0].goal, "coverage");
+
+        manager.shutdown().await.unwrap();
+    }
+
+    #[test]
+    fn test_unfinished_tasks_sort_first() {
+        let summary = DashboardSummary::new(
+            Vec::new(),
+            vec![
+                task("done", BackgroundTaskStatus::Completed, 1),
+                task("old", BackgroundTaskStatus::Running, 30),
+                task("new", BackgroundTaskStatus::Queued, 2),
+            ],
+            Vec::new(),
+        );
+        let ids: Vec<&str> = summary.tasks.iter().map(|t| t.id.as_str()).collect();
+        assert_eq!(ids, vec!["new", "old", "done"]);
+        assert_eq!(summary.unfinished_tasks(), 2);
+    }
+
+    #[test]
+    fn test_format_age() {
+        let now = Utc::now();
+        assert_eq!(format_age(now - Duration::seconds(5), now), "5s ago");
+        assert_eq!(format_age(now - Duration::minutes(5), now), "5m ago");
+        assert_eq!(format_age(now - Duration::hours(3), now), "3h ago");
+        assert_eq!(format_age(now - Duration::days(2), now), "2d ago");
+    }
+}
@@ -1,4 +1,5 @@
 use thiserror::Error;
+use uira_core::{ErrorKind, UiraError};
 
 #[derive(Debug, Error)]
 pub enum GatewayError {
@@ -17,6 +18,56 @@ pub enum GatewayError {
     #[error("Failed to send message: {0}")]
     SendFailed(String),
 
+    #[error("Gateway is shutting down")]
+    ShuttingDown,
+
     #[error("Gateway server error: {0}")]
     ServerError(String),
+
+    #[error("Invalid scheduler config: {0}")]
+    InvalidSchedule(String),
+
+    #[error("Scheduled job failed: {0}")]
+    JobFailed(String),
+}
+
+impl GatewayError {
+    /// Stable code for the shared error taxonomy
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::SessionNotFound(_) => "gateway.session_not_found",
+            Self::MaxSessionsReached(_) => "gateway.max_sessions_reached",
+            Self::SessionCreationFailed(_) => "gateway.session_creation_failed",
+            Self::SessionShutdownFailed(_) => "gateway.session_shutdown_failed",
+            Self::SendFailed(_) => "gateway.send_failed",
+            Self::ShuttingDown => "gateway.shutting_down",
+            Self::ServerError(_) => "gateway.server_error",
+            Self::InvalidSchedule(_) => "gateway.invalid_schedule",
+            Self::JobFailed(_) => "gateway.job_failed",
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::SessionNotFound(_) | Self::InvalidSchedule(_) => ErrorKind::UserError,
+            Self::MaxSessionsReached(_) => ErrorKind::Budget,
+            _ => ErrorKind::Internal,
+        }
+    }
+}
+
+impl From<&GatewayError> for UiraError {
+    fn from(error: &GatewayError) -> Self {
+        let uira_error = UiraError::new(error.kind(), error.code(), error.to_string());
+        match error {
+            GatewayError::MaxSessionsReached(_) => uira_error.with_remediation(
+                "Destroy idle sessions or raise `gateway.max_sessions` in uira.yml",
+            ),
+            GatewayError::ShuttingDown => uira_error
+                .with_retryable(true)
+                .with_remediation("Retry once the gateway has restarted"),
+            GatewayError::SendFailed(_) => uira_error.with_retryable(true),
+            _ => uira_error,
+        }
+    }
 }
@@ -3,18 +3,26 @@
 pub mod channel_bridge;
 pub mod channels;
 pub mod config;
+pub mod dashboard;
 pub mod error;
 pub mod protocol;
+pub mod scheduler;
 pub mod server;
 pub mod session_manager;
+pub mod share;
+pub mod shutdown;
 pub mod skills;
 pub mod testing;
 
 pub use channel_bridge::ChannelBridge;
 pub use channels::*;
 pub use config::SessionConfig;
+pub use dashboard::{Dashboard, DashboardSummary};
 pub use error::GatewayError;
 pub use protocol::{GatewayMessage, GatewayResponse};
+pub use scheduler::{ScheduledJob, Scheduler};
 pub use server::GatewayServer;
-pub use session_manager::{SessionInfo, SessionManager, SessionStatus};
+pub use session_manager::{GoalStatus, SessionInfo, SessionManager, SessionStatus};
+pub use share::{ShareLink, ShareRegistry};
+pub use shutdown::{shutdown_signal, Checkpoint, CheckpointRoute, CheckpointSession};
 pub use skills::*;
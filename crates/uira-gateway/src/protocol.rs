@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
+use uira_core::UiraError;
 
 use crate::config::SessionConfig;
+use crate::error::GatewayError;
 
 /// Inbound messages from WebSocket clients
 #[derive(Debug, Deserialize)]
@@ -26,6 +28,31 @@ pub enum GatewayMessage {
         recipient: String,
         text: String,
     },
+    /// Read a background task's log from byte `offset`; poll with the returned
+    /// offset to follow it
+    TaskLogs {
+        task_id: String,
+        #[serde(default)]
+        offset: u64,
+    },
+    /// Plan an issue and work it with autopilot in a new session
+    StartIssueTask {
+        /// Issue URL, owner/name#N, or a number in the workspace repository
+        issue: String,
+        #[serde(default)]
+        config: SessionConfig,
+    },
+    /// Mint an expiring read-only link to a session's live events
+    CreateShareLink {
+        session_id: String,
+        /// Defaults to `gateway.share_ttl_secs`, capped at
+        /// `gateway.max_share_ttl_secs`
+        #[serde(default)]
+        ttl_secs: Option<u64>,
+    },
+    RevokeShareLink {
+        token: String,
+    },
 }
 
 /// Outbound messages to WebSocket clients
@@ -58,11 +85,55 @@ pub enum GatewayResponse {
         channel_type: String,
         recipient: String,
     },
+    TaskLogs {
+        task_id: String,
+        status: String,
+        content: String,
+        offset: u64,
+    },
+    IssueTaskStarted {
+        session_id: String,
+        issue: String,
+        title: String,
+        plan_id: String,
+        goals: Vec<String>,
+    },
+    ShareLinkCreated {
+        session_id: String,
+        token: String,
+        /// Server-relative path: SSE at `path`, WebSocket at `path/ws`
+        path: String,
+        expires_at: String,
+    },
+    ShareLinkRevoked {
+        token: String,
+    },
     Error {
         message: String,
+        /// Classification and remediation hint, when the failure has one
+        #[serde(skip_serializing_if = "Option::is_none")]
+        detail: Option<UiraError>,
     },
 }
 
+impl GatewayResponse {
+    pub fn error(message: impl Into<String>) -> Self {
+        Self::Error {
+            message: message.into(),
+            detail: None,
+        }
+    }
+}
+
+impl From<&GatewayError> for GatewayResponse {
+    fn from(error: &GatewayError) -> Self {
+        Self::Error {
+            message: error.to_string(),
+            detail: Some(error.into()),
+        }
+    }
+}
+
 #[derive(Debug, Serialize)]
 pub struct SessionInfoResponse {
     pub id: String,
@@ -141,6 +212,61 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_deserialize_task_logs() {
+        let json = r#"{"type": "task_logs", "task_id": "bg_1"}"#;
+        let msg: GatewayMessage = serde_json::from_str(json).unwrap();
+        match msg {
+            GatewayMessage::TaskLogs { task_id, offset } => {
+                assert_eq!(task_id, "bg_1");
+                assert_eq!(offset, 0);
+            }
+            _ => panic!("Expected TaskLogs"),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_start_issue_task() {
+        let json = r#"{"type": "start_issue_task", "issue": "junhoyeo/uira#42"}"#;
+        let msg: GatewayMessage = serde_json::from_str(json).unwrap();
+        match msg {
+            GatewayMessage::StartIssueTask { issue, config } => {
+                assert_eq!(issue, "junhoyeo/uira#42");
+                assert!(config.model.is_none());
+            }
+            _ => panic!("Expected StartIssueTask"),
+        }
+    }
+
+    #[test]
+    fn test_deserialize_create_share_link() {
+        let json = r#"{"type": "create_share_link", "session_id": "gw_ses_1"}"#;
+        let msg: GatewayMessage = serde_json::from_str(json).unwrap();
+        match msg {
+            GatewayMessage::CreateShareLink {
+                session_id,
+                ttl_secs,
+            } => {
+                assert_eq!(session_id, "gw_ses_1");
+                assert!(ttl_secs.is_none());
+            }
+            _ => panic!("Expected CreateShareLink"),
+        }
+    }
+
+    #[test]
+    fn test_serialize_share_link_created() {
+        let resp = GatewayResponse::ShareLinkCreated {
+            session_id: "gw_ses_1".to_string(),
+            token: "abc".to_string(),
+            path: "/share/abc".to_string(),
+            expires_at: "2025-01-01T00:00:00Z".to_string(),
+        };
+        let json = serde_json::to_string(&resp).unwrap();
+        assert!(json.contains("\"type\":\"share_link_created\""));
+        assert!(json.contains("\"path\":\"/share/abc\""));
+    }
+
     #[test]
     fn test_serialize_session_created() {
         let resp = GatewayResponse::SessionCreated {
@@ -153,12 +279,25 @@ mod tests {
 
     #[test]
     fn test_serialize_error() {
-        let resp = GatewayResponse::Error {
-            message: "bad request".to_string(),
-        };
+        let resp = GatewayResponse::error("bad request");
         let json = serde_json::to_string(&resp).unwrap();
         assert!(json.contains("\"type\":\"error\""));
         assert!(json.contains("\"message\":\"bad request\""));
+        assert!(!json.contains("detail"));
+    }
+
+    #[test]
+    fn test_serialize_gateway_error_detail() {
+        let resp = GatewayResponse::from(&GatewayError::MaxSessionsReached(4));
+        let json = serde_json::to_value(&resp).unwrap();
+        assert_eq!(json["type"], "error");
+        assert_eq!(json["message"], "Maximum sessions reached (limit: 4)");
+        assert_eq!(json["detail"]["kind"], "budget");
+        assert_eq!(json["detail"]["code"], "gateway.max_sessions_reached");
+        assert!(json["detail"]["remediation"]
+            .as_str()
+            .unwrap()
+            .contains("max_sessions"));
     }
 
     #[test]
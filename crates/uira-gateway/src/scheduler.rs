@@ -0,0 +1,373 @@
+//! Cron-scheduled agent jobs whose results are posted to channels.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Local};
+use croner::Cron;
+use futures_util::StreamExt;
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
+use uira_core::schema::{ScheduledJobConfig, SchedulerSettings};
+use uira_core::ThreadEvent;
+
+use crate::channels::{Channel, ChannelResponse};
+use crate::config::SessionConfig;
+use crate::error::GatewayError;
+use crate::session_manager::SessionManager;
+
+type OutboundChannelMap = Arc<RwLock<HashMap<String, Arc<dyn Channel>>>>;
+
+/// A job from `scheduler.jobs` with its parsed cron expression
+#[derive(Debug, Clone)]
+pub struct ScheduledJob {
+    config: ScheduledJobConfig,
+    cron: Cron,
+}
+
+impl ScheduledJob {
+    pub fn new(config: ScheduledJobConfig) -> Result<Self, GatewayError> {
+        let cron = Cron::new(&config.schedule).parse().map_err(|e| {
+            GatewayError::InvalidSchedule(format!(
+                "job '{}' has invalid schedule '{}': {}",
+                config.name, config.schedule, e
+            ))
+        })?;
+
+        if config.channel.is_some() && config.recipient.is_none() {
+            return Err(GatewayError::InvalidSchedule(format!(
+                "job '{}' sets a channel but no recipient",
+                config.name
+            )));
+        }
+
+        Ok(Self { config, cron })
+    }
+
+    pub fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    pub fn config(&self) -> &ScheduledJobConfig {
+        &self.config
+    }
+
+    /// The first time the job is due strictly after `after`
+    pub fn next_run_after(&self, after: &DateTime<Local>) -> Option<DateTime<Local>> {
+        self.cron.find_next_occurrence(after, false).ok()
+    }
+}
+
+/// Runs scheduled jobs in fresh gateway sessions.
+///
+/// Each job gets its own session for the duration of a run. The agent's
+/// response is posted to the job's channel, if it has one.
+pub struct Scheduler {
+    jobs: Vec<ScheduledJob>,
+    session_manager: Arc<SessionManager>,
+    outbound_channels: Option<OutboundChannelMap>,
+    handles: Vec<JoinHandle<()>>,
+}
+
+impl Scheduler {
+    /// Build a scheduler from the enabled jobs in `settings`
+    pub fn new(
+        session_manager: Arc<SessionManager>,
+        settings: &SchedulerSettings,
+    ) -> Result<Self, GatewayError> {
+        let mut names = HashSet::new();
+        let mut jobs = Vec::new();
+        for config in settings.jobs.iter().filter(|job| job.enabled) {
+            if !names.insert(config.name.clone()) {
+                return Err(GatewayError::InvalidSchedule(format!(
+                    "duplicate job name '{}'",
+                    config.name
+                )));
+            }
+            jobs.push(ScheduledJob::new(config.clone())?);
+        }
+
+        Ok(Self {
+            jobs,
+            session_manager,
+            outbound_channels: None,
+            handles: Vec::new(),
+        })
+    }
+
+    pub fn with_outbound_channels(mut self, outbound_channels: OutboundChannelMap) -> Self {
+        self.outbound_channels = Some(outbound_channels);
+        self
+    }
+
+    pub fn jobs(&self) -> &[ScheduledJob] {
+        &self.jobs
+    }
+
+    /// Spawn a timer for every job
+    pub fn start(&mut self) {
+        for job in self.jobs.clone() {
+            let session_manager = self.session_manager.clone();
+            let outbound_channels = self.outbound_channels.clone();
+
+            self.handles.push(tokio::spawn(async move {
+                loop {
+                    let now = Local::now();
+                    let Some(next) = job.next_run_after(&now) else {
+                        warn!(job = %job.name(), "Scheduled job has no upcoming runs");
+                        break;
+                    };
+                    info!(job = %job.name(), next_run = %next, "Scheduled job waiting");
+                    tokio::time::sleep((next - now).to_std().unwrap_or_default()).await;
+
+                    // Runs are sequential, so a slow run skips the ticks it overlaps
+                    let _ =
+                        run_and_deliver(&session_manager, outbound_channels.as_ref(), &job).await;
+                }
+            }));
+        }
+    }
+
+    /// Run the job named `name` now, posting its result like a scheduled run
+    pub async fn run_now(&self, name: &str) -> Result<String, GatewayError> {
+        let job = self
+            .jobs
+            .iter()
+            .find(|job| job.name() == name)
+            .ok_or_else(|| GatewayError::InvalidSchedule(format!("unknown job '{}'", name)))?;
+        run_and_deliver(&self.session_manager, self.outbound_channels.as_ref(), job).await
+    }
+
+    pub async fn stop(&mut self) {
+        for handle in self.handles.drain(..) {
+            handle.abort();
+            let _ = handle.await;
+        }
+    }
+}
+
+async fn run_and_deliver(
+    session_manager: &SessionManager,
+    outbound_channels: Option<&OutboundChannelMap>,
+    job: &ScheduledJob,
+) -> Result<String, GatewayError> {
+    info!(job = %job.name(), "Running scheduled job");
+    let result = run_job(session_manager, job.config()).await;
+
+    let message = match &result {
+        Ok(output) => {
+            info!(job = %job.name(), "Scheduled job completed");
+            format!("Scheduled job '{}':\n\n{}", job.name(), output)
+        }
+        Err(e) => {
+            error!(job = %job.name(), error = %e, "Scheduled job failed");
+            format!("Scheduled job '{}' failed: {}", job.name(), e)
+        }
+    };
+
+    if let Err(e) = deliver(outbound_channels, job.config(), message).await {
+        error!(job = %job.name(), error = %e, "Failed to post scheduled job result");
+    }
+
+    result
+}
+
+async fn run_job(
+    session_manager: &SessionManager,
+    job: &ScheduledJobConfig,
+) -> Result<String, GatewayError> {
+    let session_id = session_manager
+        .create_session(SessionConfig {
+            model: job.model.clone(),
+            agent: job.agent.clone(),
+            working_directory: job.working_directory.clone(),
+            ..SessionConfig::default()
+        })
+        .await?;
+
+    let result = collect_response(session_manager, &session_id, job).await;
+    if let Err(e) = session_manager.destroy_session(&session_id).await {
+        warn!(job = %job.name, error = %e, "Failed to destroy scheduled job session");
+    }
+    result
+}
+
+/// Send the job's prompt and gather the agent's text until the run completes
+async fn collect_response(
+    session_manager: &SessionManager,
+    session_id: &str,
+    job: &ScheduledJobConfig,
+) -> Result<String, GatewayError> {
+    let mut events = session_manager
+        .take_event_stream(session_id)
+        .await
+        .ok_or_else(|| GatewayError::SessionNotFound(session_id.to_string()))?;
+    session_manager
+        .send_message(session_id, job.prompt.clone())
+        .await?;
+
+    let collect = async {
+        let mut output = String::new();
+        while let Some(event) = events.next().await {
+            match event {
+                ThreadEvent::ContentDelta { delta } => output.push_str(&delta),
+                ThreadEvent::ThreadCompleted { .. } => return Ok(output.trim().to_string()),
+                ThreadEvent::ThreadCancelled => {
+                    return Err(GatewayError::JobFailed("run was cancelled".to_string()));
+                }
+                ThreadEvent::Error {
+                    message,
+                    recoverable: false,
+                    ..
+                } => return Err(GatewayError::JobFailed(message)),
+                _ => {}
+            }
+        }
+        Err(GatewayError::JobFailed(
+            "session ended before the run completed".to_string(),
+        ))
+    };
+
+    tokio::time::timeout(Duration::from_secs(job.timeout_secs), collect)
+        .await
+        .map_err(|_| GatewayError::JobFailed(format!("timed out after {}s", job.timeout_secs)))?
+}
+
+async fn deliver(
+    outbound_channels: Option<&OutboundChannelMap>,
+    job: &ScheduledJobConfig,
+    content: String,
+) -> Result<(), GatewayError> {
+    let (Some(channel_type), Some(recipient)) = (&job.channel, &job.recipient) else {
+        return Ok(());
+    };
+
+    // Without channels (e.g. a one-off local run) results are only returned
+    let Some(channels) = outbound_channels else {
+        return Ok(());
+    };
+    let channel = channels
+        .read()
+        .await
+        .get(channel_type)
+        .cloned()
+        .ok_or_else(|| GatewayError::SendFailed(format!("Channel '{}' not found", channel_type)))?;
+
+    channel
+        .send_message(ChannelResponse {
+            content,
+            recipient: recipient.clone(),
+        })
+        .await
+        .map_err(|e| GatewayError::SendFailed(e.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::channels::ChannelType;
+    use crate::testing::{MockChannel, MockModelClient};
+    use chrono::TimeZone;
+    use uira_core::schema::GatewaySettings;
+
+    fn job(name: &str, schedule: &str) -> ScheduledJobConfig {
+        ScheduledJobConfig {
+            name: name.to_string(),
+            schedule: schedule.to_string(),
+            prompt: "Triage new issues".to_string(),
+            agent: None,
+            model: None,
+            working_directory: None,
+            channel: Some("slack".to_string()),
+            recipient: Some("C123".to_string()),
+            timeout_secs: 30,
+            enabled: true,
+        }
+    }
+
+    fn test_session_manager(mock_client: MockModelClient) -> Arc<SessionManager> {
+        Arc::new(SessionManager::new_with_test_client(
+            10,
+            GatewaySettings {
+                provider: "ollama".to_string(),
+                model: "llama3.1".to_string(),
+                ..GatewaySettings::default()
+            },
+            Arc::new(mock_client),
+        ))
+    }
+
+    #[test]
+    fn test_next_run_after() {
+        let job = ScheduledJob::new(job("nightly", "0 3 * * *")).unwrap();
+        let now = Local.with_ymd_and_hms(2026, 1, 5, 12, 0, 0).unwrap();
+        let next = job.next_run_after(&now).unwrap();
+        assert_eq!(next, Local.with_ymd_and_hms(2026, 1, 6, 3, 0, 0).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_jobs_are_rejected() {
+        let manager = test_session_manager(MockModelClient::new("ok"));
+
+        let bad_schedule = SchedulerSettings {
+            enabled: true,
+            jobs: vec![job("nightly", "not a cron")],
+        };
+        let err = Scheduler::new(manager.clone(), &bad_schedule)
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("invalid schedule 'not a cron'"));
+
+        let duplicate = SchedulerSettings {
+            enabled: true,
+            jobs: vec![job("nightly", "@daily"), job("nightly", "@hourly")],
+        };
+        let err = Scheduler::new(manager.clone(), &duplicate).err().unwrap();
+        assert!(err.to_string().contains("duplicate job name 'nightly'"));
+
+        let mut disabled = job("nightly", "not a cron");
+        disabled.enabled = false;
+        let settings = SchedulerSettings {
+            enabled: true,
+            jobs: vec![disabled],
+        };
+        assert!(Scheduler::new(manager, &settings)
+            .unwrap()
+            .jobs()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_run_now_posts_result_to_channel() {
+        let manager = test_session_manager(MockModelClient::new("3 issues triaged"));
+        let channel = MockChannel::new(ChannelType::Slack);
+        let sent = channel.sent_messages_shared();
+        let outbound: OutboundChannelMap = Arc::new(RwLock::new(HashMap::new()));
+        outbound
+            .write()
+            .await
+            .insert("slack".to_string(), Arc::new(channel));
+
+        let settings = SchedulerSettings {
+            enabled: true,
+            jobs: vec![job("triage", "@daily")],
+        };
+        let scheduler = Scheduler::new(manager.clone(), &settings)
+            .unwrap()
+            .with_outbound_channels(outbound);
+
+        let output = scheduler.run_now("triage").await.unwrap();
+        assert_eq!(output, "3 issues triaged");
+
+        let sent = sent.lock().unwrap().clone();
+        assert_eq!(sent.len(), 1);
+        assert_eq!(sent[0].recipient, "C123");
+        assert_eq!(
+            sent[0].content,
+            "Scheduled job 'triage':\n\n3 issues triaged"
+        );
+        assert_eq!(manager.session_count().await, 0);
+    }
+}
@@ -1,15 +1,18 @@
 use std::collections::HashMap;
+use std::convert::Infallible;
+use std::path::PathBuf;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::Instant;
 
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
-use axum::extract::State;
-use axum::http::HeaderMap;
-use axum::response::IntoResponse;
+use axum::extract::{Path, State};
+use axum::http::{HeaderMap, StatusCode};
+use axum::response::sse::{Event, KeepAlive, Sse};
+use axum::response::{IntoResponse, Response};
 use axum::{Json, Router};
 use futures_util::stream::SplitSink;
-use futures_util::{SinkExt, StreamExt};
+use futures_util::{SinkExt, Stream, StreamExt};
 use serde::Serialize;
 use tokio::net::TcpListener;
 use tokio::sync::broadcast;
@@ -20,18 +23,24 @@ use tracing::Instrument;
 use uira_core::schema::GatewaySettings;
 
 use crate::channels::{Channel, ChannelResponse};
+use crate::config::SessionConfig;
 use crate::error::GatewayError;
 use crate::protocol::{GatewayMessage, GatewayResponse, SessionInfoResponse};
 use crate::session_manager::SessionManager;
+use crate::share::{shared_events, ShareLink, ShareRegistry};
+use crate::shutdown::{shutdown_signal, Checkpoint};
 
 /// Maximum size (in bytes) for a single WS frame payload.
 const MAX_WS_FRAME_SIZE: usize = 128 * 1024; // 128 KB
 const MAX_MESSAGE_CONTENT_SIZE: usize = 64 * 1024;
+/// Maximum bytes of task log returned per `task_logs` request
+const MAX_TASK_LOG_CHUNK: usize = 64 * 1024;
 
 struct AppState {
     session_manager: Arc<SessionManager>,
     channels: Arc<RwLock<HashMap<String, Arc<dyn Channel>>>>,
     auth_token: Option<String>,
+    shares: ShareRegistry,
     start_time: Instant,
     next_conn_id: AtomicU64,
 }
@@ -49,6 +58,7 @@ pub struct GatewayServer {
     session_manager: Arc<SessionManager>,
     channels: Arc<RwLock<HashMap<String, Arc<dyn Channel>>>>,
     auth_token: Option<String>,
+    shares: ShareRegistry,
 }
 
 impl GatewayServer {
@@ -57,6 +67,7 @@ impl GatewayServer {
             session_manager: Arc::new(SessionManager::new(max_sessions)),
             channels: Arc::new(RwLock::new(HashMap::new())),
             auth_token: None,
+            shares: ShareRegistry::default(),
         }
     }
 
@@ -68,6 +79,7 @@ impl GatewayServer {
         Self {
             session_manager,
             channels: Arc::new(RwLock::new(HashMap::new())),
+            shares: ShareRegistry::from_settings(&settings),
             auth_token: settings.auth_token,
         }
     }
@@ -98,16 +110,27 @@ impl GatewayServer {
             session_manager: self.session_manager.clone(),
             channels: self.channels.clone(),
             auth_token: self.auth_token.clone(),
+            shares: self.shares.clone(),
             start_time: Instant::now(),
             next_conn_id: AtomicU64::new(1),
         });
         Router::new()
             .route("/ws", axum::routing::any(ws_handler))
             .route("/health", axum::routing::get(health_handler))
+            .route("/share/{token}", axum::routing::get(share_sse_handler))
+            .route("/share/{token}/ws", axum::routing::any(share_ws_handler))
             .with_state(state)
     }
 
     pub async fn start(&self, host: &str, port: u16) -> Result<(), GatewayError> {
+        self.serve(host, port).await?;
+        let sessions = self.session_manager.checkpoint_sessions().await;
+        self.shutdown(Checkpoint::new(sessions, Vec::new())).await
+    }
+
+    /// Serve until Ctrl+C or SIGTERM, then stop taking new sessions and
+    /// messages. Running sessions are left for [`Self::shutdown`].
+    pub async fn serve(&self, host: &str, port: u16) -> Result<(), GatewayError> {
         let app = self.router();
         let addr = format!("{}:{}", host, port);
         let listener = TcpListener::bind(&addr)
@@ -119,14 +142,42 @@ impl GatewayServer {
         let session_manager = self.session_manager.clone();
 
         axum::serve(listener, app)
-            .with_graceful_shutdown(async {
-                tokio::signal::ctrl_c().await.ok();
+            .with_graceful_shutdown(async move {
+                shutdown_signal().await;
                 tracing::info!("Shutdown signal received, stopping gateway...");
+                session_manager.begin_shutdown();
             })
             .await
-            .map_err(|e| GatewayError::ServerError(e.to_string()))?;
+            .map_err(|e| GatewayError::ServerError(e.to_string()))
+    }
+
+    /// Save `checkpoint` for the next start, fail background tasks so they
+    /// can be requeued, and stop every session (which flushes its state).
+    pub async fn shutdown(&self, checkpoint: Checkpoint) -> Result<(), GatewayError> {
+        self.session_manager.begin_shutdown();
+
+        if !checkpoint.is_empty() {
+            match checkpoint.save() {
+                Ok(path) => tracing::info!(
+                    sessions = checkpoint.sessions.len(),
+                    path = %path.display(),
+                    "Saved gateway checkpoint"
+                ),
+                Err(error) => tracing::error!(%error, "Failed to save gateway checkpoint"),
+            }
+        }
+
+        let interrupted = uira_orchestration::background_agent::interrupt_background_tasks(
+            "Task interrupted: gateway shutting down",
+        );
+        if !interrupted.is_empty() {
+            tracing::info!(
+                tasks = interrupted.len(),
+                "Interrupted background tasks; requeue them with `uira-agent tasks requeue`"
+            );
+        }
 
-        session_manager.shutdown().await?;
+        self.session_manager.shutdown().await?;
         tracing::info!("Gateway shutdown complete");
 
         Ok(())
@@ -170,6 +221,7 @@ async fn ws_handler(
             socket,
             state.session_manager.clone(),
             state.channels.clone(),
+            state.shares.clone(),
         )
         .instrument(span)
     })
@@ -195,6 +247,7 @@ async fn handle_socket(
     socket: WebSocket,
     session_manager: Arc<SessionManager>,
     channels: Arc<RwLock<HashMap<String, Arc<dyn Channel>>>>,
+    shares: ShareRegistry,
 ) {
     tracing::debug!("WebSocket connection established");
 
@@ -217,12 +270,10 @@ async fn handle_socket(
                     .get(&session_id)
                     .is_some_and(|t| !t.is_finished())
                 {
-                    let err = GatewayResponse::Error {
-                        message: format!(
-                            "Already subscribed to events for session '{}'",
-                            session_id
-                        ),
-                    };
+                    let err = GatewayResponse::error(format!(
+                        "Already subscribed to events for session '{}'",
+                        session_id
+                    ));
                     if tx.send(serialize_response(&err)).await.is_err() {
                         break;
                     }
@@ -255,26 +306,7 @@ async fn handle_socket(
                                     Err(broadcast::error::RecvError::Closed) => break,
                                 };
 
-                                let response = GatewayResponse::AgentEvent {
-                                    session_id: stream_session_id.clone(),
-                                    event: event_json,
-                                };
-
-                                let serialized = serialize_response(&response);
-                                let to_send = if serialized.len() > MAX_WS_FRAME_SIZE {
-                                    let truncated = GatewayResponse::AgentEvent {
-                                        session_id: stream_session_id.clone(),
-                                        event: serde_json::json!({
-                                            "type": "truncated",
-                                            "original_size": serialized.len(),
-                                            "message": "Event payload exceeded maximum frame size"
-                                        }),
-                                    };
-                                    serialize_response(&truncated)
-                                } else {
-                                    serialized
-                                };
-
+                                let to_send = agent_event_frame(&stream_session_id, event_json);
                                 if tx_clone.send(to_send).await.is_err() {
                                     return;
                                 }
@@ -288,9 +320,8 @@ async fn handle_socket(
                         event_tasks.insert(session_id, task);
                     }
                     None => {
-                        let err = GatewayResponse::Error {
-                            message: format!("Session '{}' not found", session_id),
-                        };
+                        let err =
+                            GatewayResponse::error(format!("Session '{}' not found", session_id));
                         if tx.send(serialize_response(&err)).await.is_err() {
                             break;
                         }
@@ -298,15 +329,14 @@ async fn handle_socket(
                 }
             }
             Ok(gateway_msg) => {
-                let response = handle_message(gateway_msg, &session_manager, &channels).await;
+                let response =
+                    handle_message(gateway_msg, &session_manager, &channels, &shares).await;
                 if tx.send(serialize_response(&response)).await.is_err() {
                     break;
                 }
             }
             Err(e) => {
-                let err = GatewayResponse::Error {
-                    message: format!("Invalid JSON: {}", e),
-                };
+                let err = GatewayResponse::error(format!("Invalid JSON: {}", e));
                 if tx.send(serialize_response(&err)).await.is_err() {
                     break;
                 }
@@ -342,10 +372,133 @@ fn serialize_response(response: &GatewayResponse) -> String {
     }
 }
 
+/// Serialized `agent_event`, replaced by a `truncated` marker when it would
+/// exceed the frame size limit
+fn agent_event_frame(session_id: &str, event: serde_json::Value) -> String {
+    let response = GatewayResponse::AgentEvent {
+        session_id: session_id.to_string(),
+        event,
+    };
+
+    let serialized = serialize_response(&response);
+    if serialized.len() > MAX_WS_FRAME_SIZE {
+        let truncated = GatewayResponse::AgentEvent {
+            session_id: session_id.to_string(),
+            event: serde_json::json!({
+                "type": "truncated",
+                "original_size": serialized.len(),
+                "message": "Event payload exceeded maximum frame size"
+            }),
+        };
+        serialize_response(&truncated)
+    } else {
+        serialized
+    }
+}
+
+/// Resolve a share token to its link and the session's redacted events,
+/// which end when the link is revoked. Share routes skip bearer auth: the
+/// token is the credential, so unknown, expired and orphaned tokens all
+/// look the same.
+async fn open_share(
+    state: &AppState,
+    token: &str,
+) -> Option<(
+    ShareLink,
+    impl Stream<Item = serde_json::Value> + Send + 'static,
+)> {
+    let (link, revoked) = state.shares.open(token)?;
+    let event_rx = state
+        .session_manager
+        .subscribe_events(&link.session_id)
+        .await?;
+    let events = shared_events(event_rx, link.deadline(), revoked);
+    Some((link, events))
+}
+
+/// Read-only live view over Server-Sent Events
+async fn share_sse_handler(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+) -> Response {
+    let Some((link, events)) = open_share(&state, &token).await else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let session_id = link.session_id.clone();
+    let events = events.map(move |event| {
+        Ok::<_, Infallible>(Event::default().data(agent_event_frame(&session_id, event)))
+    });
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Read-only live view over WebSocket
+async fn share_ws_handler(
+    State(state): State<Arc<AppState>>,
+    Path(token): Path<String>,
+    ws: WebSocketUpgrade,
+) -> Response {
+    let Some((link, events)) = open_share(&state, &token).await else {
+        return StatusCode::NOT_FOUND.into_response();
+    };
+
+    let span = tracing::info_span!("share_conn", session_id = %link.session_id);
+    ws.on_upgrade(move |socket| handle_share_socket(socket, link, events).instrument(span))
+        .into_response()
+}
+
+async fn handle_share_socket(
+    socket: WebSocket,
+    link: ShareLink,
+    events: impl Stream<Item = serde_json::Value> + Send + 'static,
+) {
+    let (mut ws_sender, mut ws_receiver) = socket.split();
+    let session_id = link.session_id.clone();
+    let mut events = Box::pin(events);
+
+    let subscribed = GatewayResponse::EventsSubscribed {
+        session_id: session_id.clone(),
+    };
+    if ws_sender
+        .send(Message::text(serialize_response(&subscribed)))
+        .await
+        .is_err()
+    {
+        return;
+    }
+
+    loop {
+        let outbound = tokio::select! {
+            event = events.next() => match event {
+                Some(event) => agent_event_frame(&session_id, event),
+                None => {
+                    let ended = GatewayResponse::EventStreamEnded {
+                        session_id: session_id.clone(),
+                    };
+                    let _ = ws_sender.send(Message::text(serialize_response(&ended))).await;
+                    break;
+                }
+            },
+            inbound = ws_receiver.next() => match inbound {
+                Some(Ok(Message::Text(_))) => serialize_response(&GatewayResponse::error("Shared sessions are read-only")),
+                Some(Ok(Message::Close(_))) | Some(Err(_)) | None => break,
+                Some(Ok(_)) => continue,
+            },
+        };
+
+        if ws_sender.send(Message::text(outbound)).await.is_err() {
+            break;
+        }
+    }
+}
+
 async fn handle_message(
     msg: GatewayMessage,
     manager: &SessionManager,
     channels: &Arc<RwLock<HashMap<String, Arc<dyn Channel>>>>,
+    shares: &ShareRegistry,
 ) -> GatewayResponse {
     match msg {
         GatewayMessage::CreateSession { config } => {
@@ -353,9 +506,7 @@ async fn handle_message(
             config.sanitize();
             match manager.create_session(config).await {
                 Ok(id) => GatewayResponse::SessionCreated { session_id: id },
-                Err(e) => GatewayResponse::Error {
-                    message: e.to_string(),
-                },
+                Err(e) => GatewayResponse::from(&e),
             }
         }
         GatewayMessage::ListSessions => {
@@ -377,30 +528,25 @@ async fn handle_message(
             content,
         } => {
             if content.len() > MAX_MESSAGE_CONTENT_SIZE {
-                return GatewayResponse::Error {
-                    message: "Message content exceeds maximum size (64KB)".to_string(),
-                };
+                return GatewayResponse::error("Message content exceeds maximum size (64KB)");
             }
 
             match manager.send_message(&session_id, content).await {
                 Ok(()) => GatewayResponse::MessageSent { session_id },
-                Err(e) => GatewayResponse::Error {
-                    message: e.to_string(),
-                },
+                Err(e) => GatewayResponse::from(&e),
             }
         }
-        GatewayMessage::SubscribeEvents { session_id } => GatewayResponse::Error {
-            message: format!(
-                "subscribe_events must be handled by the WebSocket event stream loop: {}",
-                session_id
-            ),
-        },
+        GatewayMessage::SubscribeEvents { session_id } => GatewayResponse::error(format!(
+            "subscribe_events must be handled by the WebSocket event stream loop: {}",
+            session_id
+        )),
         GatewayMessage::DestroySession { session_id } => {
             match manager.destroy_session(&session_id).await {
-                Ok(()) => GatewayResponse::SessionDestroyed { session_id },
-                Err(e) => GatewayResponse::Error {
-                    message: e.to_string(),
-                },
+                Ok(()) => {
+                    shares.revoke_session(&session_id);
+                    GatewayResponse::SessionDestroyed { session_id }
+                }
+                Err(e) => GatewayResponse::from(&e),
             }
         }
         GatewayMessage::SendOutbound {
@@ -409,9 +555,7 @@ async fn handle_message(
             text,
         } => {
             if text.len() > MAX_MESSAGE_CONTENT_SIZE {
-                return GatewayResponse::Error {
-                    message: "Outbound text exceeds maximum size (64KB)".to_string(),
-                };
+                return GatewayResponse::error("Outbound text exceeds maximum size (64KB)");
             }
 
             let channel = {
@@ -430,19 +574,148 @@ async fn handle_message(
                             channel_type,
                             recipient,
                         },
-                        Err(e) => GatewayResponse::Error {
-                            message: format!("Failed to send message: {}", e),
-                        },
+                        Err(e) => GatewayResponse::error(format!("Failed to send message: {}", e)),
                     }
                 }
-                None => GatewayResponse::Error {
-                    message: format!("Channel '{}' not found", channel_type),
-                },
+                None => GatewayResponse::error(format!("Channel '{}' not found", channel_type)),
+            }
+        }
+        GatewayMessage::TaskLogs { task_id, offset } => read_task_logs(task_id, offset),
+        GatewayMessage::StartIssueTask { issue, config } => {
+            start_issue_task(&issue, config, manager)
+                .await
+                .unwrap_or_else(GatewayResponse::error)
+        }
+        GatewayMessage::CreateShareLink {
+            session_id,
+            ttl_secs,
+        } => {
+            if !manager.has_session(&session_id).await {
+                return GatewayResponse::error(format!("Session '{}' not found", session_id));
+            }
+            let link = shares.mint(&session_id, ttl_secs.map(std::time::Duration::from_secs));
+            GatewayResponse::ShareLinkCreated {
+                path: link.path(),
+                session_id: link.session_id,
+                token: link.token,
+                expires_at: link.expires_at.to_rfc3339(),
+            }
+        }
+        GatewayMessage::RevokeShareLink { token } => {
+            if shares.revoke(&token) {
+                GatewayResponse::ShareLinkRevoked { token }
+            } else {
+                GatewayResponse::error("Share link not found or already expired")
             }
         }
     }
 }
 
+/// Fetch an issue, save its planning pipeline and autopilot state in the
+/// gateway's working directory, and start a session on the first phase
+async fn start_issue_task(
+    issue: &str,
+    mut config: SessionConfig,
+    manager: &SessionManager,
+) -> Result<GatewayResponse, String> {
+    use uira_github::{connect, origin_url, IssueRef, IssueTask, RepoRef};
+    use uira_orchestration::hooks::{AutopilotConfig, AutopilotHook};
+    use uira_orchestration::IssueAutopilot;
+
+    let issue_ref = IssueRef::parse(issue)
+        .ok_or_else(|| format!("'{}' is not an issue URL, owner/name#N or number", issue))?;
+    let directory = match manager.default_working_directory() {
+        Some(dir) => PathBuf::from(dir),
+        None => std::env::current_dir().map_err(|e| e.to_string())?,
+    };
+
+    let uira_config = uira_core::loader::load_config(None).ok();
+    let code_host = uira_config
+        .as_ref()
+        .map(|cfg| cfg.code_host.clone())
+        .unwrap_or_default();
+    let autopilot = uira_config
+        .as_ref()
+        .map(|cfg| cfg.autopilot.clone())
+        .unwrap_or_default();
+
+    let remote_url = origin_url(&directory).ok();
+    let workspace_repo = remote_url.as_deref().and_then(RepoRef::from_remote_url);
+    let repo = issue_ref
+        .resolve_repo(workspace_repo.as_ref())
+        .map_err(|e| e.to_string())?;
+    let host = connect(
+        &code_host,
+        issue_ref.url.as_deref().or(remote_url.as_deref()),
+    )
+    .map_err(|e| e.to_string())?;
+    let task = IssueTask::fetch(host.as_ref(), &repo, issue_ref.number)
+        .await
+        .map_err(|e| e.to_string())?;
+
+    let run = IssueAutopilot::start(
+        &directory,
+        &task,
+        None,
+        AutopilotConfig {
+            max_iterations: autopilot.max_iterations,
+            require_approval: autopilot.require_approval,
+        },
+    )?;
+
+    let dir = directory.to_string_lossy().to_string();
+    config.sanitize();
+    config.working_directory = Some(dir.clone());
+    let started = match manager.create_session(config).await {
+        Ok(session_id) => manager
+            .send_message(&session_id, run.prompt())
+            .await
+            .map(|()| session_id),
+        Err(e) => Err(e),
+    };
+    let session_id = started.map_err(|e| {
+        AutopilotHook::cancel(&dir, Some("gateway session failed to start"));
+        e.to_string()
+    })?;
+
+    Ok(GatewayResponse::IssueTaskStarted {
+        session_id,
+        issue: task.reference(),
+        title: task.title,
+        plan_id: run.pipeline.id,
+        goals: task.goals,
+    })
+}
+
+fn read_task_logs(task_id: String, offset: u64) -> GatewayResponse {
+    use uira_orchestration::background_agent::{get_background_manager, BackgroundTaskConfig};
+
+    let tasks = get_background_manager(BackgroundTaskConfig::default());
+    let Some(task) = tasks.get_task(&task_id) else {
+        return GatewayResponse::error(format!("Task not found: {}", task_id));
+    };
+
+    match tasks.read_log(&task_id, offset) {
+        Ok((mut content, mut next)) => {
+            if content.len() > MAX_TASK_LOG_CHUNK {
+                let mut end = MAX_TASK_LOG_CHUNK;
+                while !content.is_char_boundary(end) {
+                    end -= 1;
+                }
+                content.truncate(end);
+                next = offset + end as u64;
+            }
+            GatewayResponse::TaskLogs {
+                task_id,
+                status: task.status.as_str().to_string(),
+                content,
+                offset: next,
+            }
+        }
+        Err(e) => GatewayResponse::error(format!("Failed to read log for task {}: {}", task_id, e)),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1018,6 +1291,39 @@ mod tests {
         assert_eq!(sent[0].recipient, "user123");
     }
 
+    #[tokio::test]
+    async fn test_start_issue_task_rejects_invalid_reference() {
+        let url = start_test_server().await;
+        let mut ws = connect(&url).await;
+
+        let resp = send_and_recv(
+            &mut ws,
+            r#"{"type": "start_issue_task", "issue": "not an issue"}"#,
+        )
+        .await;
+
+        assert_eq!(resp["type"], "error");
+        assert!(resp["message"]
+            .as_str()
+            .unwrap()
+            .contains("is not an issue URL"));
+    }
+
+    #[tokio::test]
+    async fn test_task_logs_unknown_task() {
+        let url = start_test_server().await;
+        let mut ws = connect(&url).await;
+
+        let resp =
+            send_and_recv(&mut ws, r#"{"type": "task_logs", "task_id": "bg_missing"}"#).await;
+
+        assert_eq!(resp["type"], "error");
+        assert_eq!(
+            resp["message"].as_str().unwrap(),
+            "Task not found: bg_missing"
+        );
+    }
+
     #[tokio::test]
     async fn test_send_outbound_unknown_channel_returns_error() {
         let (url, _mock_channel) = start_test_server_with_mock_channel().await;
@@ -1120,4 +1426,158 @@ mod tests {
         let resp = send_and_recv(&mut ws, r#"{"type": "list_sessions"}"#).await;
         assert_eq!(resp["sessions"].as_array().unwrap().len(), 0);
     }
+
+    // -- Share link tests ----------------------------------------------------
+
+    async fn create_share_link(
+        ws: &mut tokio_tungstenite::WebSocketStream<
+            tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>,
+        >,
+    ) -> (String, serde_json::Value) {
+        let create_resp = send_and_recv(ws, r#"{"type": "create_session"}"#).await;
+        let session_id = create_resp["session_id"].as_str().unwrap().to_string();
+        let msg = format!(
+            r#"{{"type": "create_share_link", "session_id": "{}"}}"#,
+            session_id
+        );
+        (session_id, send_and_recv(ws, &msg).await)
+    }
+
+    #[tokio::test]
+    async fn test_share_link_unknown_session() {
+        let url = start_test_server().await;
+        let mut ws = connect(&url).await;
+
+        let resp = send_and_recv(
+            &mut ws,
+            r#"{"type": "create_share_link", "session_id": "nope"}"#,
+        )
+        .await;
+        assert_eq!(resp["type"], "error");
+    }
+
+    #[tokio::test]
+    async fn test_share_link_streams_read_only_without_auth() {
+        unsafe {
+            std::env::set_var("ANTHROPIC_API_KEY", "test-key");
+        }
+        let addr = start_auth_server(Some("owner-secret".to_string())).await;
+        let mut owner = connect_with_auth(&addr, Some("owner-secret"))
+            .await
+            .unwrap();
+
+        let (session_id, share) = create_share_link(&mut owner).await;
+        assert_eq!(share["type"], "share_link_created");
+        assert_eq!(share["session_id"], session_id.as_str());
+        let path = share["path"].as_str().unwrap();
+        assert_eq!(path, format!("/share/{}", share["token"].as_str().unwrap()));
+
+        let (mut viewer, _) = tokio_tungstenite::connect_async(format!("ws://{}{}/ws", addr, path))
+            .await
+            .expect("share links should not need the bearer token");
+        let subscribed = viewer.next().await.unwrap().unwrap().into_text().unwrap();
+        let subscribed: serde_json::Value = serde_json::from_str(&subscribed).unwrap();
+        assert_eq!(subscribed["type"], "events_subscribed");
+
+        let resp = send_and_recv(
+            &mut viewer,
+            &format!(
+                r#"{{"type": "send_message", "session_id": "{}", "content": "hi"}}"#,
+                session_id
+            ),
+        )
+        .await;
+        assert_eq!(resp["type"], "error");
+        assert_eq!(resp["message"], "Shared sessions are read-only");
+
+        let send_msg = format!(
+            r#"{{"type": "send_message", "session_id": "{}", "content": "hello"}}"#,
+            session_id
+        );
+        owner
+            .send(tungstenite::Message::Text(send_msg.into()))
+            .await
+            .unwrap();
+        let event = recv_until_type(&mut viewer, "agent_event").await;
+        assert_eq!(event["session_id"], session_id.as_str());
+    }
+
+    #[tokio::test]
+    async fn test_share_link_revoke_and_unknown_token() {
+        let url = start_test_server().await;
+        let http_url = url.replace("ws://", "http://");
+        let mut ws = connect(&url).await;
+
+        let (_, share) = create_share_link(&mut ws).await;
+        let token = share["token"].as_str().unwrap().to_string();
+
+        let client = reqwest::Client::new();
+        let resp = client
+            .get(format!("{}/share/{}", http_url, token))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), 200);
+        assert_eq!(
+            resp.headers()["content-type"].to_str().unwrap(),
+            "text/event-stream"
+        );
+        drop(resp);
+
+        let revoke = format!(r#"{{"type": "revoke_share_link", "token": "{}"}}"#, token);
+        let resp = send_and_recv(&mut ws, &revoke).await;
+        assert_eq!(resp["type"], "share_link_revoked");
+        let resp = send_and_recv(&mut ws, &revoke).await;
+        assert_eq!(resp["type"], "error");
+
+        for path in [format!("/share/{}", token), "/share/unknown".to_string()] {
+            let resp = client
+                .get(format!("{}{}", http_url, path))
+                .send()
+                .await
+                .unwrap();
+            assert_eq!(resp.status(), 404);
+        }
+        let url = format!("{}/share/{}/ws", url, token);
+        assert!(tokio_tungstenite::connect_async(url).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_revoking_a_share_link_disconnects_live_viewers() {
+        let url = start_test_server().await;
+        let mut owner = connect(&url).await;
+
+        let (_, share) = create_share_link(&mut owner).await;
+        let token = share["token"].as_str().unwrap();
+        let (mut viewer, _) =
+            tokio_tungstenite::connect_async(format!("{}/share/{}/ws", url, token))
+                .await
+                .unwrap();
+        recv_until_type(&mut viewer, "events_subscribed").await;
+
+        let revoke = format!(r#"{{"type": "revoke_share_link", "token": "{}"}}"#, token);
+        let resp = send_and_recv(&mut owner, &revoke).await;
+        assert_eq!(resp["type"], "share_link_revoked");
+        recv_until_type(&mut viewer, "event_stream_ended").await;
+    }
+
+    #[tokio::test]
+    async fn test_destroy_session_revokes_share_links() {
+        let url = start_test_server().await;
+        let mut ws = connect(&url).await;
+
+        let (session_id, share) = create_share_link(&mut ws).await;
+        let destroy = format!(
+            r#"{{"type": "destroy_session", "session_id": "{}"}}"#,
+            session_id
+        );
+        send_and_recv(&mut ws, &destroy).await;
+
+        let revoke = format!(
+            r#"{{"type": "revoke_share_link", "token": "{}"}}"#,
+            share["token"].as_str().unwrap()
+        );
+        let resp = send_and_recv(&mut ws, &revoke).await;
+        assert_eq!(resp["type"], "error");
+    }
 }
@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::atomic::AtomicBool;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
@@ -9,13 +10,14 @@ use tokio::sync::{broadcast, mpsc, RwLock};
 use tokio::task::JoinHandle;
 use tokio::time::{timeout, Duration};
 
-use uira_agent::{Agent, AgentConfig, EventStream};
-use uira_core::schema::GatewaySettings;
+use uira_agent::{Agent, AgentConfig, CancelSignal, EventStream};
+use uira_core::schema::{GatewaySettings, RepoMapSettings};
 use uira_core::{Message, Provider, ThreadEvent};
 use uira_providers::{ModelClient, ModelClientBuilder, ProviderConfig};
 
 use crate::config::SessionConfig;
 use crate::error::GatewayError;
+use crate::shutdown::{CheckpointSession, RESUME_PROMPT};
 
 /// Status of a managed session
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -45,13 +47,29 @@ pub struct SessionInfo {
     pub config: SessionConfig,
 }
 
+/// Latest verification result of a goal, from whichever session checked it last
+#[derive(Debug, Clone, PartialEq)]
+pub struct GoalStatus {
+    pub goal: String,
+    pub session_id: String,
+    pub score: f64,
+    pub target: f64,
+    pub passed: bool,
+    pub checked_at: DateTime<Utc>,
+}
+
+type GoalStatusMap = Arc<std::sync::Mutex<HashMap<String, GoalStatus>>>;
+
 struct ManagedSession {
     info: SessionInfo,
     agent_input_tx: mpsc::Sender<Message>,
     event_broadcast_tx: broadcast::Sender<serde_json::Value>,
     _relay_handle: JoinHandle<()>,
     agent_handle: JoinHandle<()>,
-    agent_control: Arc<AtomicBool>,
+    agent_control: CancelSignal,
+    rollout: Option<PathBuf>,
+    /// Set while the agent is working on a message
+    busy: Arc<AtomicBool>,
 }
 
 /// Manages multiple concurrent agent sessions for the gateway.
@@ -64,6 +82,8 @@ pub struct SessionManager {
     reaper_started: Arc<AtomicBool>,
     reaper_interval: Duration,
     reaper_handle: Arc<std::sync::Mutex<Option<JoinHandle<()>>>>,
+    goal_statuses: GoalStatusMap,
+    draining: Arc<AtomicBool>,
     test_model_client: Option<Arc<dyn ModelClient>>,
 }
 
@@ -81,6 +101,8 @@ impl SessionManager {
             reaper_started: Arc::new(AtomicBool::new(false)),
             reaper_interval: Duration::from_secs(60),
             reaper_handle: Arc::new(std::sync::Mutex::new(None)),
+            goal_statuses: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            draining: Arc::new(AtomicBool::new(false)),
             test_model_client: None,
         }
     }
@@ -98,6 +120,8 @@ impl SessionManager {
             reaper_started: Arc::new(AtomicBool::new(false)),
             reaper_interval: Duration::from_secs(60),
             reaper_handle: Arc::new(std::sync::Mutex::new(None)),
+            goal_statuses: Arc::new(std::sync::Mutex::new(HashMap::new())),
+            draining: Arc::new(AtomicBool::new(false)),
             test_model_client: Some(test_model_client),
         }
     }
@@ -108,16 +132,150 @@ impl SessionManager {
         *self.settings.write().unwrap_or_else(|e| e.into_inner()) = settings;
     }
 
+    /// Working directory new sessions default to
+    pub fn default_working_directory(&self) -> Option<String> {
+        self.settings
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .working_directory
+            .clone()
+    }
+
     /// Create a new session. Returns the session ID.
     pub async fn create_session(&self, config: SessionConfig) -> Result<String, GatewayError> {
-        if let Some(test_model_client) = &self.test_model_client {
-            return self
-                .create_session_with_client(config, test_model_client.clone())
-                .await;
+        let client = self.model_client_for(&config)?;
+        self.create_session_with_client(config, client).await
+    }
+
+    /// Stop accepting new sessions and messages ahead of a shutdown.
+    /// Sessions already running keep going until [`Self::shutdown`].
+    pub fn begin_shutdown(&self) {
+        self.draining.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_shutting_down(&self) -> bool {
+        self.draining.load(Ordering::SeqCst)
+    }
+
+    /// Snapshot of every live session, for the shutdown checkpoint.
+    pub async fn checkpoint_sessions(&self) -> Vec<CheckpointSession> {
+        let sessions = self.sessions.read().await;
+        let mut entries: Vec<(DateTime<Utc>, CheckpointSession)> = sessions
+            .iter()
+            .map(|(id, session)| {
+                (
+                    session.info.created_at,
+                    CheckpointSession {
+                        id: id.clone(),
+                        config: session.info.config.clone(),
+                        rollout: session.rollout.clone(),
+                        interrupted: session.busy.load(Ordering::SeqCst),
+                    },
+                )
+            })
+            .collect();
+        entries.sort_by_key(|(created_at, _)| *created_at);
+        entries.into_iter().map(|(_, entry)| entry).collect()
+    }
+
+    /// Bring back checkpointed sessions, returning the ones that were
+    /// restored. Sessions that fail to come back are logged and skipped.
+    pub async fn restore_sessions(
+        &self,
+        entries: Vec<CheckpointSession>,
+    ) -> Vec<CheckpointSession> {
+        let mut restored = Vec::new();
+        for entry in entries {
+            match self.restore_session(entry.clone()).await {
+                Ok(_) => restored.push(entry),
+                Err(error) => {
+                    tracing::warn!(
+                        session_id = %entry.id,
+                        %error,
+                        "Failed to restore checkpointed gateway session"
+                    );
+                }
+            }
         }
+        restored
+    }
 
-        let client = self.build_model_client(&config)?;
-        self.create_session_with_client(config, client).await
+    /// Bring back a checkpointed session under its old ID, replaying its
+    /// rollout.
+    pub async fn restore_session(&self, entry: CheckpointSession) -> Result<String, GatewayError> {
+        let client = self.model_client_for(&entry.config)?;
+        self.restore_session_with_client(entry, client).await
+    }
+
+    /// Ask restored sessions that were cut off mid-turn to carry on. Call
+    /// this once their event subscribers are attached so no output is lost.
+    pub async fn resume_interrupted(&self, entries: &[CheckpointSession]) {
+        for entry in entries.iter().filter(|entry| entry.interrupted) {
+            if let Err(error) = self
+                .send_message(&entry.id, RESUME_PROMPT.to_string())
+                .await
+            {
+                tracing::warn!(
+                    session_id = %entry.id,
+                    %error,
+                    "Failed to resume interrupted gateway session"
+                );
+            }
+        }
+    }
+
+    async fn restore_session_with_client(
+        &self,
+        entry: CheckpointSession,
+        client: Arc<dyn ModelClient>,
+    ) -> Result<String, GatewayError> {
+        if self.is_shutting_down() {
+            return Err(GatewayError::ShuttingDown);
+        }
+        self.start_reaper();
+
+        {
+            let sessions = self.sessions.read().await;
+            if sessions.len() >= self.max_sessions {
+                return Err(GatewayError::MaxSessionsReached(self.max_sessions));
+            }
+            if sessions.contains_key(&entry.id) {
+                return Err(GatewayError::SessionCreationFailed(format!(
+                    "Session '{}' already exists",
+                    entry.id
+                )));
+            }
+        }
+        // Keep newly created sessions from reusing a restored ID
+        if let Some(number) = entry
+            .id
+            .strip_prefix("gw_ses_")
+            .and_then(|n| n.parse::<u64>().ok())
+        {
+            self.next_id.fetch_max(number + 1, Ordering::Relaxed);
+        }
+
+        let agent_config = self.build_agent_config(&entry.config)?;
+        let agent = match entry.rollout.as_ref().filter(|path| path.exists()) {
+            Some(path) => Agent::resume_from_session(agent_config, client, path.clone())
+                .map_err(|e| GatewayError::SessionCreationFailed(e.to_string()))?,
+            None => Agent::new(agent_config, client)
+                .with_session_recording()
+                .map_err(|e| GatewayError::SessionCreationFailed(e.to_string()))?,
+        };
+        activate_skills(&agent, &entry.config)?;
+
+        self.spawn_session(entry.id, entry.config, agent).await
+    }
+
+    fn model_client_for(
+        &self,
+        config: &SessionConfig,
+    ) -> Result<Arc<dyn ModelClient>, GatewayError> {
+        match &self.test_model_client {
+            Some(test_model_client) => Ok(test_model_client.clone()),
+            None => self.build_model_client(config),
+        }
     }
 
     async fn create_session_with_client(
@@ -125,6 +283,9 @@ impl SessionManager {
         config: SessionConfig,
         client: Arc<dyn ModelClient>,
     ) -> Result<String, GatewayError> {
+        if self.is_shutting_down() {
+            return Err(GatewayError::ShuttingDown);
+        }
         self.start_reaper();
 
         // Phase 1: Check capacity and reserve ID under read lock
@@ -139,15 +300,59 @@ impl SessionManager {
         // Phase 2: Build agent OUTSIDE the lock
         let agent_config = self.build_agent_config(&config)?;
         let agent = Agent::new(agent_config, client);
+        activate_skills(&agent, &config)?;
         let agent = agent
             .with_session_recording()
             .map_err(|e| GatewayError::SessionCreationFailed(e.to_string()))?;
+
+        self.spawn_session(id, config, agent).await
+    }
+
+    /// Start the agent's interactive loop and event relay, then register it.
+    async fn spawn_session(
+        &self,
+        id: String,
+        config: SessionConfig,
+        agent: Agent,
+    ) -> Result<String, GatewayError> {
+        let rollout = agent.session_path().cloned();
+        let busy = Arc::new(AtomicBool::new(false));
         let (agent, event_stream) = agent.with_event_stream();
         let (event_broadcast_tx, _) = broadcast::channel::<serde_json::Value>(256);
         let relay_broadcast_tx = event_broadcast_tx.clone();
+        let relay_goal_statuses = self.goal_statuses.clone();
+        let relay_session_id = id.clone();
+        let relay_busy = busy.clone();
         let relay_handle = tokio::spawn(async move {
             let mut event_stream = event_stream;
             while let Some(event) = event_stream.next().await {
+                match &event {
+                    ThreadEvent::TurnStarted { .. } => relay_busy.store(true, Ordering::SeqCst),
+                    ThreadEvent::WaitingForInput { .. } => {
+                        relay_busy.store(false, Ordering::SeqCst)
+                    }
+                    _ => {}
+                }
+                if let ThreadEvent::GoalVerificationResult {
+                    goal,
+                    score,
+                    target,
+                    passed,
+                    ..
+                } = &event
+                {
+                    record_goal_status(
+                        &relay_goal_statuses,
+                        GoalStatus {
+                            goal: goal.clone(),
+                            session_id: relay_session_id.clone(),
+                            score: *score,
+                            target: *target,
+                            passed: *passed,
+                            checked_at: Utc::now(),
+                        },
+                    );
+                }
                 match serde_json::to_value(&event) {
                     Ok(event_json) => {
                         let _ = relay_broadcast_tx.send(event_json);
@@ -178,7 +383,7 @@ impl SessionManager {
         // Phase 3: Insert under write lock (fast — just a HashMap insert)
         let mut sessions = self.sessions.write().await;
         // Re-check capacity (another session may have been created between phase 1 and 3)
-        if sessions.len() >= self.max_sessions {
+        if sessions.len() >= self.max_sessions || sessions.contains_key(&id) {
             // Clean up the agent we just created
             relay_handle.abort();
             agent_handle.abort();
@@ -193,6 +398,8 @@ impl SessionManager {
                 _relay_handle: relay_handle,
                 agent_handle,
                 agent_control,
+                rollout,
+                busy,
             },
         );
 
@@ -289,9 +496,10 @@ impl SessionManager {
             agent_handle,
             agent_control,
             info: _,
+            ..
         } = session;
 
-        agent_control.store(true, Ordering::SeqCst);
+        agent_control.shutdown();
         drop(agent_input_tx);
         _relay_handle.abort();
 
@@ -326,12 +534,33 @@ impl SessionManager {
         infos
     }
 
+    /// Latest result of every goal verified in any session, by goal name.
+    pub fn goal_statuses(&self) -> Vec<GoalStatus> {
+        let mut statuses: Vec<GoalStatus> = self
+            .goal_statuses
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .values()
+            .cloned()
+            .collect();
+        statuses.sort_by(|a, b| a.goal.cmp(&b.goal));
+        statuses
+    }
+
+    #[cfg(test)]
+    pub(crate) fn record_goal_status(&self, status: GoalStatus) {
+        record_goal_status(&self.goal_statuses, status);
+    }
+
     /// Send a message to a specific session.
     pub async fn send_message(
         &self,
         session_id: &str,
         message: String,
     ) -> Result<(), GatewayError> {
+        if self.is_shutting_down() {
+            return Err(GatewayError::ShuttingDown);
+        }
         let sender = {
             let mut sessions = self.sessions.write().await;
             let session = sessions
@@ -345,6 +574,7 @@ impl SessionManager {
             }
             session.info.status = SessionStatus::Active;
             session.info.last_message_at = Utc::now();
+            session.busy.store(true, Ordering::SeqCst);
             session.agent_input_tx.clone()
         };
 
@@ -385,9 +615,10 @@ impl SessionManager {
                 agent_handle,
                 agent_control,
                 info: _,
+                ..
             } = session;
 
-            agent_control.store(true, Ordering::SeqCst);
+            agent_control.shutdown();
             drop(agent_input_tx);
             _relay_handle.abort();
 
@@ -543,15 +774,23 @@ impl SessionManager {
         let personality = uira_orchestration::OrchestratorPersonality::parse(agent_name)
             .unwrap_or(uira_orchestration::OrchestratorPersonality::Balanced);
 
-        // Build additional context: orchestrator prompt + environment context + optional skill context.
+        // Build additional context: orchestrator prompt + repo map + environment context + optional skill context.
         let context_collector =
             uira_orchestration::features::context_injector::ContextCollector::new();
+        if let Some(path) = working_directory {
+            uira_orchestration::features::context_injector::register_repo_map_context(
+                &context_collector,
+                "gateway-session-startup",
+                std::path::Path::new(path),
+                &RepoMapSettings::default(),
+            );
+        }
         uira_orchestration::features::context_injector::register_environment_context(
             &context_collector,
             "gateway-session-startup",
         );
-        let env_context = context_collector.consume("gateway-session-startup").merged;
-        let mut additional_context = vec![personality.system_prompt().to_string(), env_context];
+        let startup_context = context_collector.consume("gateway-session-startup").merged;
+        let mut additional_context = vec![personality.system_prompt().to_string(), startup_context];
         if let Some(skill_context) = &config.skill_context {
             additional_context.push(skill_context.clone());
         }
@@ -569,6 +808,7 @@ fn parse_provider(provider_name: &str) -> Result<Provider, GatewayError> {
         "ollama" => Ok(Provider::Ollama),
         "opencode" => Ok(Provider::OpenCode),
         "openrouter" => Ok(Provider::OpenRouter),
+        "mock" => Ok(Provider::Mock),
         "custom" => Ok(Provider::Custom),
         _ => Err(GatewayError::SessionCreationFailed(format!(
             "Unknown provider: {}",
@@ -577,6 +817,29 @@ fn parse_provider(provider_name: &str) -> Result<Provider, GatewayError> {
     }
 }
 
+fn activate_skills(agent: &Agent, config: &SessionConfig) -> Result<(), GatewayError> {
+    for (skill, permissions) in &config.skill_permissions {
+        agent
+            .session()
+            .orchestrator
+            .activate_skill(skill, permissions.clone())
+            .map_err(|e| {
+                GatewayError::SessionCreationFailed(format!(
+                    "Invalid permissions for skill '{}': {}",
+                    skill, e
+                ))
+            })?;
+    }
+    Ok(())
+}
+
+fn record_goal_status(statuses: &GoalStatusMap, status: GoalStatus) {
+    statuses
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .insert(status.goal.clone(), status);
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -842,6 +1105,90 @@ mod tests {
         assert!(err.contains("shutting down"));
     }
 
+    #[tokio::test]
+    async fn test_draining_refuses_new_sessions_and_messages() {
+        let manager = SessionManager::new_with_settings(10, test_settings());
+        let client: Arc<dyn ModelClient> = Arc::new(MockModelClient::new("ok"));
+        let id = manager
+            .create_session_with_client(SessionConfig::default(), client.clone())
+            .await
+            .unwrap();
+
+        manager.begin_shutdown();
+        assert!(manager.is_shutting_down());
+
+        let result = manager.send_message(&id, "hello".to_string()).await;
+        assert!(matches!(result, Err(GatewayError::ShuttingDown)));
+        let result = manager
+            .create_session_with_client(SessionConfig::default(), client)
+            .await;
+        assert!(matches!(result, Err(GatewayError::ShuttingDown)));
+        assert!(manager.has_session(&id).await);
+    }
+
+    #[tokio::test]
+    async fn test_restore_session_keeps_id_and_rollout() {
+        let manager = SessionManager::new_with_settings(10, test_settings());
+        let client: Arc<dyn ModelClient> = Arc::new(MockModelClient::new("ok"));
+        let id = manager
+            .create_session_with_client(
+                SessionConfig {
+                    model: Some("llama3.2".to_string()),
+                    ..SessionConfig::default()
+                },
+                client.clone(),
+            )
+            .await
+            .unwrap();
+
+        let checkpoint = manager.checkpoint_sessions().await;
+        assert_eq!(checkpoint.len(), 1);
+        assert_eq!(checkpoint[0].id, id);
+        assert!(!checkpoint[0].interrupted);
+        let rollout = checkpoint[0].rollout.clone().unwrap();
+        manager.shutdown().await.unwrap();
+
+        let restarted = SessionManager::new_with_settings(10, test_settings());
+        let mut entry = checkpoint[0].clone();
+        entry.id = "gw_ses_41".to_string();
+        let restored_id = restarted
+            .restore_session_with_client(entry, client.clone())
+            .await
+            .unwrap();
+        assert_eq!(restored_id, "gw_ses_41");
+        assert_eq!(
+            restarted
+                .get_session_config(&restored_id)
+                .await
+                .unwrap()
+                .model
+                .as_deref(),
+            Some("llama3.2")
+        );
+        assert_eq!(
+            restarted.checkpoint_sessions().await[0].rollout,
+            Some(rollout.clone())
+        );
+
+        // New sessions never collide with a restored ID
+        let next_id = restarted
+            .create_session_with_client(SessionConfig::default(), client)
+            .await
+            .unwrap();
+        assert_eq!(next_id, "gw_ses_42");
+
+        let rollouts: Vec<_> = restarted
+            .checkpoint_sessions()
+            .await
+            .into_iter()
+            .filter_map(|session| session.rollout)
+            .collect();
+        restarted.shutdown().await.unwrap();
+        for path in rollouts {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+
     #[tokio::test]
     async fn test_list_sessions() {
         let manager = SessionManager::new_with_settings(10, test_settings());
@@ -0,0 +1,272 @@
+//! Expiring read-only share links for live sessions
+//!
+//! A share token grants a visitor the event stream of one session and
+//! nothing else: no messages, no approvals, no other sessions. Events are
+//! passed through [`redact_secrets`] before they leave the gateway. Tokens
+//! live in memory only, so restarting the gateway revokes every link, and
+//! revoking a link disconnects viewers that are already watching.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Duration, Utc};
+use futures_util::Stream;
+use tokio::sync::{broadcast, watch};
+use uira_core::schema::GatewaySettings;
+use uira_providers::redact_secrets;
+
+/// A minted share link
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareLink {
+    pub token: String,
+    pub session_id: String,
+    pub expires_at: DateTime<Utc>,
+}
+
+impl ShareLink {
+    /// Server-relative path visitors open; append `/ws` for a WebSocket
+    pub fn path(&self) -> String {
+        format!("/share/{}", self.token)
+    }
+
+    pub fn is_expired(&self) -> bool {
+        Utc::now() >= self.expires_at
+    }
+
+    /// Tokio deadline matching `expires_at`
+    pub fn deadline(&self) -> tokio::time::Instant {
+        let remaining = (self.expires_at - Utc::now()).to_std().unwrap_or_default();
+        tokio::time::Instant::now() + remaining
+    }
+}
+
+/// A live link and the channel its viewers watch for revocation.
+/// Dropping the entry drops `revoked`, which ends every viewer's stream.
+#[derive(Debug)]
+struct ShareEntry {
+    link: ShareLink,
+    revoked: watch::Sender<()>,
+}
+
+/// In-memory share tokens and the lifetime policy for new ones
+#[derive(Debug, Clone)]
+pub struct ShareRegistry {
+    links: Arc<RwLock<HashMap<String, ShareEntry>>>,
+    default_ttl: std::time::Duration,
+    max_ttl: std::time::Duration,
+}
+
+impl Default for ShareRegistry {
+    fn default() -> Self {
+        Self::from_settings(&GatewaySettings::default())
+    }
+}
+
+impl ShareRegistry {
+    pub fn from_settings(settings: &GatewaySettings) -> Self {
+        Self {
+            links: Arc::default(),
+            default_ttl: std::time::Duration::from_secs(settings.share_ttl_secs),
+            max_ttl: std::time::Duration::from_secs(settings.max_share_ttl_secs),
+        }
+    }
+
+    /// Mint a token for `session_id` that expires after `ttl` (or the
+    /// default lifetime), capped at the maximum lifetime
+    pub fn mint(&self, session_id: &str, ttl: Option<std::time::Duration>) -> ShareLink {
+        let ttl = ttl.unwrap_or(self.default_ttl).min(self.max_ttl);
+        let ttl = Duration::from_std(ttl).unwrap_or(Duration::MAX);
+        let link = ShareLink {
+            token: format!(
+                "{}{}",
+                uuid::Uuid::new_v4().simple(),
+                uuid::Uuid::new_v4().simple()
+            ),
+            session_id: session_id.to_string(),
+            expires_at: Utc::now()
+                .checked_add_signed(ttl)
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        };
+
+        let mut links = self.links.write().unwrap_or_else(|e| e.into_inner());
+        links.retain(|_, entry| !entry.link.is_expired());
+        let (revoked, _) = watch::channel(());
+        links.insert(
+            link.token.clone(),
+            ShareEntry {
+                link: link.clone(),
+                revoked,
+            },
+        );
+        link
+    }
+
+    /// The live link for `token`; expired tokens are dropped
+    pub fn resolve(&self, token: &str) -> Option<ShareLink> {
+        self.open(token).map(|(link, _)| link)
+    }
+
+    /// The live link for `token` and a receiver that closes when the link
+    /// is revoked; pass it to [`shared_events`]
+    pub fn open(&self, token: &str) -> Option<(ShareLink, watch::Receiver<()>)> {
+        let (link, revoked) = {
+            let links = self.links.read().unwrap_or_else(|e| e.into_inner());
+            let entry = links.get(token)?;
+            (entry.link.clone(), entry.revoked.subscribe())
+        };
+        if link.is_expired() {
+            self.revoke(token);
+            return None;
+        }
+        Some((link, revoked))
+    }
+
+    /// Revoke one token; `false` if it was unknown or already expired
+    pub fn revoke(&self, token: &str) -> bool {
+        self.links
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(token)
+            .is_some_and(|entry| !entry.link.is_expired())
+    }
+
+    /// Revoke every token for a session, e.g. when it is destroyed
+    pub fn revoke_session(&self, session_id: &str) {
+        self.links
+            .write()
+            .unwrap_or_else(|e| e.into_inner())
+            .retain(|_, entry| entry.link.session_id != session_id);
+    }
+}
+
+/// Redacted events from `event_rx` until the session ends, `deadline`
+/// passes, or the link behind `revoked` is revoked. Lagged events are
+/// skipped, as for regular subscribers.
+pub fn shared_events(
+    event_rx: broadcast::Receiver<serde_json::Value>,
+    deadline: tokio::time::Instant,
+    revoked: watch::Receiver<()>,
+) -> impl Stream<Item = serde_json::Value> + Send + 'static {
+    let state = (event_rx, revoked);
+    futures_util::stream::unfold(state, move |(mut event_rx, mut revoked)| async move {
+        loop {
+            let received = tokio::select! {
+                received = event_rx.recv() => received,
+                _ = tokio::time::sleep_until(deadline) => return None,
+                // Nothing is ever sent; this resolves when the entry is dropped.
+                _ = revoked.changed() => return None,
+            };
+            match received {
+                Ok(event) => return Some((redact_secrets(&event), (event_rx, revoked))),
+                Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                    tracing::warn!(
+                        skipped,
+                        "Share link viewer lagged behind; skipping missed events"
+                    );
+                }
+                Err(broadcast::error::RecvError::Closed) => return None,
+            }
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures_util::StreamExt;
+
+    #[test]
+    fn test_mint_and_resolve() {
+        let shares = ShareRegistry::default();
+        let link = shares.mint("gw_ses_1", None);
+
+        assert_eq!(link.token.len(), 64);
+        assert_eq!(link.path(), format!("/share/{}", link.token));
+        assert_eq!(shares.resolve(&link.token), Some(link.clone()));
+        assert!(shares.resolve("unknown").is_none());
+
+        let other = shares.mint("gw_ses_1", None);
+        assert_ne!(other.token, link.token);
+        let remaining = link.expires_at - Utc::now();
+        assert!(remaining > Duration::minutes(59) && remaining <= Duration::hours(1));
+    }
+
+    #[test]
+    fn test_expired_links_do_not_resolve() {
+        let shares = ShareRegistry::default();
+        let link = shares.mint("gw_ses_1", Some(std::time::Duration::ZERO));
+        assert!(shares.resolve(&link.token).is_none());
+        assert!(!shares.revoke(&link.token));
+    }
+
+    #[test]
+    fn test_ttl_is_capped() {
+        let shares = ShareRegistry::from_settings(&GatewaySettings {
+            max_share_ttl_secs: 60,
+            ..GatewaySettings::default()
+        });
+        let link = shares.mint("gw_ses_1", Some(std::time::Duration::from_secs(86400 * 30)));
+        assert!(link.expires_at - Utc::now() <= Duration::seconds(60));
+    }
+
+    #[test]
+    fn test_revoke() {
+        let shares = ShareRegistry::default();
+        let first = shares.mint("gw_ses_1", None);
+        let second = shares.mint("gw_ses_1", None);
+        let other = shares.mint("gw_ses_2", None);
+
+        assert!(shares.revoke(&first.token));
+        assert!(!shares.revoke(&first.token));
+        assert!(shares.resolve(&first.token).is_none());
+
+        shares.revoke_session("gw_ses_1");
+        assert!(shares.resolve(&second.token).is_none());
+        assert!(shares.resolve(&other.token).is_some());
+    }
+
+    #[tokio::test]
+    async fn test_shared_events_redacts_and_stops_at_deadline() {
+        let (tx, rx) = broadcast::channel(8);
+        let deadline = tokio::time::Instant::now() + std::time::Duration::from_millis(200);
+        let (_revoked_tx, revoked) = watch::channel(());
+        let mut events = Box::pin(shared_events(rx, deadline, revoked));
+
+        tx.send(serde_json::json!({
+            "type": "tool_result",
+            "output": "export OPENAI_API_KEY=sk-proj-0123456789abcdefghij",
+            "api_key": "plain",
+        }))
+        .unwrap();
+        let event = events.next().await.unwrap();
+        assert_eq!(event["output"], "export OPENAI_API_KEY=[REDACTED]");
+        assert_eq!(event["api_key"], "[REDACTED]");
+
+        assert!(events.next().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_revoke_ends_live_streams() {
+        let shares = ShareRegistry::default();
+        let (tx, _) = broadcast::channel(8);
+        let far = tokio::time::Instant::now() + std::time::Duration::from_secs(3600);
+
+        let link = shares.mint("gw_ses_1", None);
+        let (_, revoked) = shares.open(&link.token).unwrap();
+        let mut events = Box::pin(shared_events(tx.subscribe(), far, revoked));
+        tx.send(serde_json::json!({"type": "turn_started"}))
+            .unwrap();
+        assert!(events.next().await.is_some());
+
+        assert!(shares.revoke(&link.token));
+        let ended = tokio::time::timeout(std::time::Duration::from_secs(5), events.next());
+        assert!(ended.await.unwrap().is_none());
+
+        let link = shares.mint("gw_ses_1", None);
+        let (_, revoked) = shares.open(&link.token).unwrap();
+        let mut events = Box::pin(shared_events(tx.subscribe(), far, revoked));
+        shares.revoke_session("gw_ses_1");
+        let ended = tokio::time::timeout(std::time::Duration::from_secs(5), events.next());
+        assert!(ended.await.unwrap().is_none());
+    }
+}
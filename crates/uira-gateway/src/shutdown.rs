@@ -0,0 +1,203 @@
+//! Graceful shutdown: termination signals and the resumable checkpoint the
+//! gateway writes before exiting.
+
+use std::path::{Path, PathBuf};
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::SessionConfig;
+use crate::error::GatewayError;
+
+const CHECKPOINT_VERSION: u32 = 1;
+
+/// Sent to a restored session that was mid-turn when the gateway went down
+pub const RESUME_PROMPT: &str = "The gateway restarted while you were working on the last request. Pick up where you left off and finish it.";
+
+/// Resolves on Ctrl+C or, on unix, SIGTERM (what container runtimes send on
+/// redeploy).
+pub async fn shutdown_signal() {
+    let ctrl_c = async {
+        tokio::signal::ctrl_c().await.ok();
+    };
+
+    #[cfg(unix)]
+    let terminate = async {
+        match tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate()) {
+            Ok(mut signal) => {
+                signal.recv().await;
+            }
+            Err(error) => {
+                tracing::warn!(%error, "Failed to install SIGTERM handler");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    #[cfg(not(unix))]
+    let terminate = std::future::pending::<()>();
+
+    tokio::select! {
+        _ = ctrl_c => {}
+        _ = terminate => {}
+    }
+}
+
+/// A session that was live when the gateway shut down
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CheckpointSession {
+    pub id: String,
+    pub config: SessionConfig,
+    /// Rollout log the session was recording to
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rollout: Option<PathBuf>,
+    /// The session was mid-turn and should be told to carry on once restored
+    #[serde(default)]
+    pub interrupted: bool,
+}
+
+/// A channel conversation routed to a checkpointed session
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CheckpointRoute {
+    pub channel_type: String,
+    pub account_id: String,
+    pub conversation_key: String,
+    pub channel_id: String,
+    pub session_id: String,
+}
+
+/// Everything needed to pick sessions back up after a restart
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Checkpoint {
+    pub version: u32,
+    pub created_at: DateTime<Utc>,
+    #[serde(default)]
+    pub sessions: Vec<CheckpointSession>,
+    #[serde(default)]
+    pub routes: Vec<CheckpointRoute>,
+}
+
+impl Checkpoint {
+    pub fn new(sessions: Vec<CheckpointSession>, routes: Vec<CheckpointRoute>) -> Self {
+        Self {
+            version: CHECKPOINT_VERSION,
+            created_at: Utc::now(),
+            sessions,
+            routes,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.sessions.is_empty()
+    }
+
+    /// `~/.uira/gateway/checkpoint.json`
+    pub fn default_path() -> Option<PathBuf> {
+        dirs::home_dir().map(|home| {
+            home.join(uira_core::UIRA_DIR)
+                .join("gateway")
+                .join("checkpoint.json")
+        })
+    }
+
+    pub fn save(&self) -> Result<PathBuf, GatewayError> {
+        let path = Self::default_path().ok_or_else(|| {
+            GatewayError::ServerError("No home directory for gateway checkpoint".to_string())
+        })?;
+        self.save_to(&path)?;
+        Ok(path)
+    }
+
+    pub fn save_to(&self, path: &Path) -> Result<(), GatewayError> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| GatewayError::ServerError(e.to_string()))?;
+        }
+        let json = serde_json::to_string_pretty(self)
+            .map_err(|e| GatewayError::ServerError(e.to_string()))?;
+        // Write then rename so a kill mid-write never leaves a torn checkpoint
+        let tmp = path.with_extension("json.tmp");
+        std::fs::write(&tmp, json).map_err(|e| GatewayError::ServerError(e.to_string()))?;
+        std::fs::rename(&tmp, path).map_err(|e| GatewayError::ServerError(e.to_string()))
+    }
+
+    /// Load and remove the checkpoint so it is only restored once
+    pub fn take() -> Option<Self> {
+        Self::take_from(&Self::default_path()?)
+    }
+
+    pub fn take_from(path: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(path).ok()?;
+        let _ = std::fs::remove_file(path);
+        match serde_json::from_str::<Self>(&content) {
+            Ok(checkpoint) if checkpoint.version == CHECKPOINT_VERSION => Some(checkpoint),
+            Ok(checkpoint) => {
+                tracing::warn!(
+                    version = checkpoint.version,
+                    "Ignoring gateway checkpoint with unknown version"
+                );
+                None
+            }
+            Err(error) => {
+                tracing::warn!(%error, "Ignoring unreadable gateway checkpoint");
+                None
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checkpoint_roundtrips_once() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("gateway").join("checkpoint.json");
+        let checkpoint = Checkpoint::new(
+            vec![CheckpointSession {
+                id: "gw_ses_3".to_string(),
+                config: SessionConfig {
+                    model: Some("llama3.1".to_string()),
+                    ..SessionConfig::default()
+                },
+                rollout: Some(PathBuf::from("/tmp/rollout.jsonl")),
+                interrupted: true,
+            }],
+            vec![CheckpointRoute {
+                channel_type: "slack".to_string(),
+                account_id: "default".to_string(),
+                conversation_key: "C1:alice".to_string(),
+                channel_id: "C1".to_string(),
+                session_id: "gw_ses_3".to_string(),
+            }],
+        );
+
+        checkpoint.save_to(&path).unwrap();
+        let restored = Checkpoint::take_from(&path).unwrap();
+        assert_eq!(restored.created_at, checkpoint.created_at);
+        assert_eq!(restored.sessions.len(), 1);
+        assert_eq!(restored.sessions[0].id, "gw_ses_3");
+        assert_eq!(
+            restored.sessions[0].config.model.as_deref(),
+            Some("llama3.1")
+        );
+        assert_eq!(
+            restored.sessions[0].rollout,
+            Some(PathBuf::from("/tmp/rollout.jsonl"))
+        );
+        assert!(restored.sessions[0].interrupted);
+        assert_eq!(restored.routes, checkpoint.routes);
+        assert!(!path.exists());
+        assert!(Checkpoint::take_from(&path).is_none());
+    }
+
+    #[test]
+    fn unreadable_checkpoint_is_discarded() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("checkpoint.json");
+        std::fs::write(&path, "{not json").unwrap();
+
+        assert!(Checkpoint::take_from(&path).is_none());
+        assert!(!path.exists());
+    }
+}
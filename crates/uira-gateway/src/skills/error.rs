@@ -25,4 +25,16 @@ pub enum SkillError {
 
     #[error("Skill not found: {0}")]
     NotFound(String),
+
+    #[error("Failed to install skill: {0}")]
+    InstallError(String),
+
+    #[error("Checksum mismatch: expected {expected}, got {actual}")]
+    ChecksumMismatch { expected: String, actual: String },
+
+    #[error("Invalid arguments for skill '{skill}': {source}")]
+    InvalidArguments {
+        skill: String,
+        source: uira_orchestration::features::builtin_skills::SkillArgumentError,
+    },
 }
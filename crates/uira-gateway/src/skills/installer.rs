@@ -0,0 +1,733 @@
+use std::path::{Component, Path, PathBuf};
+use std::process::Command;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use super::error::SkillError;
+use super::parser::{parse_skill, SkillMetadata};
+
+/// File written into each installed skill directory recording where it came from.
+pub const INSTALL_MANIFEST_FILE: &str = ".uira-skill.json";
+
+/// Where a skill package is installed from.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+pub enum SkillSource {
+    /// A directory on the local filesystem
+    Local { path: PathBuf },
+    /// A git repository (full URL or GitHub `owner/repo` shorthand)
+    Git { url: String },
+}
+
+impl SkillSource {
+    /// Parse an install argument: an existing local path, a git URL, or `owner/repo`.
+    pub fn parse(input: &str) -> Result<Self, SkillError> {
+        let local = super::discovery::expand_tilde(input);
+        if local.exists() {
+            return Ok(Self::Local { path: local });
+        }
+
+        let is_url = input.starts_with("https://")
+            || input.starts_with("http://")
+            || input.starts_with("ssh://")
+            || input.starts_with("git@")
+            || input.starts_with("file://");
+        if is_url {
+            return Ok(Self::Git {
+                url: input.to_string(),
+            });
+        }
+
+        let parts: Vec<&str> = input.split('/').collect();
+        let valid_segment =
+            |s: &str| !s.is_empty() && s.chars().all(|c| c.is_alphanumeric() || "-_.".contains(c));
+        if parts.len() == 2 && parts.iter().all(|p| valid_segment(p)) {
+            return Ok(Self::Git {
+                url: format!("https://github.com/{}/{}.git", parts[0], parts[1]),
+            });
+        }
+
+        Err(SkillError::InstallError(format!(
+            "Unrecognized skill source: {} (expected a local path, git URL, or owner/repo)",
+            input
+        )))
+    }
+}
+
+impl std::fmt::Display for SkillSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Local { path } => write!(f, "{}", path.display()),
+            Self::Git { url } => write!(f, "{}", url),
+        }
+    }
+}
+
+/// Options controlling a skill install.
+#[derive(Debug, Clone, Default)]
+pub struct InstallOptions {
+    /// Git ref (tag, branch, or commit) to pin the install to
+    pub git_ref: Option<String>,
+    /// Subdirectory within the source containing SKILL.md
+    pub subdir: Option<String>,
+    /// Expected sha256 checksum of the skill contents
+    pub checksum: Option<String>,
+    /// Replace an existing install of the same skill
+    pub force: bool,
+}
+
+/// Provenance recorded for an installed skill, used by `uira skills update`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InstalledSkill {
+    pub name: String,
+    pub source: SkillSource,
+    /// Ref the install is pinned to; unpinned installs track the default branch
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pinned_ref: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub subdir: Option<String>,
+    /// Commit that was checked out (git sources only)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub commit: Option<String>,
+    pub checksum: String,
+    pub installed_at: DateTime<Utc>,
+}
+
+impl InstalledSkill {
+    /// Read the install manifest from an installed skill directory.
+    pub fn read(skill_dir: &Path) -> Option<Self> {
+        let content = std::fs::read_to_string(skill_dir.join(INSTALL_MANIFEST_FILE)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+}
+
+/// Outcome of updating a single installed skill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    /// Contents changed and were reinstalled
+    Updated { from: String, to: String },
+    /// Already at the latest contents
+    UpToDate,
+    /// Pinned to a ref or installed from a local path
+    Skipped(String),
+}
+
+/// Installs skill packages into a skills directory (normally `~/.uira/skills`).
+pub struct SkillInstaller {
+    skills_dir: PathBuf,
+}
+
+impl SkillInstaller {
+    pub fn new(skills_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            skills_dir: skills_dir.into(),
+        }
+    }
+
+    /// Installer targeting `~/.uira/skills`.
+    pub fn user() -> Result<Self, SkillError> {
+        let home = dirs::home_dir().ok_or_else(|| {
+            SkillError::InstallError("Could not determine home directory".to_string())
+        })?;
+        Ok(Self::new(home.join(".uira").join("skills")))
+    }
+
+    pub fn skills_dir(&self) -> &Path {
+        &self.skills_dir
+    }
+
+    /// Fetch, verify, and install a skill package.
+    pub fn install(
+        &self,
+        source: &SkillSource,
+        options: &InstallOptions,
+    ) -> Result<InstalledSkill, SkillError> {
+        let fetched = fetch(source, options)?;
+        self.place(&fetched, source, options)
+    }
+
+    /// Copy a fetched skill into the skills directory.
+    ///
+    /// The copy is staged next to its destination and renamed into place, so a
+    /// failed reinstall leaves the previous install untouched.
+    fn place(
+        &self,
+        fetched: &FetchedSkill,
+        source: &SkillSource,
+        options: &InstallOptions,
+    ) -> Result<InstalledSkill, SkillError> {
+        let io_err = |path: &Path| {
+            let path = path.to_path_buf();
+            move |e| SkillError::IoError { path, source: e }
+        };
+
+        let dest = self.skills_dir.join(&fetched.metadata.name);
+        if dest.exists() && !options.force {
+            return Err(SkillError::InstallError(format!(
+                "Skill already exists: {}. Use --force to reinstall.",
+                dest.display()
+            )));
+        }
+
+        std::fs::create_dir_all(&self.skills_dir).map_err(io_err(&self.skills_dir))?;
+        let staging = tempfile::Builder::new()
+            .prefix(".uira-install-")
+            .tempdir_in(&self.skills_dir)
+            .map_err(io_err(&self.skills_dir))?;
+        let staged = staging.path().join(&fetched.metadata.name);
+        copy_skill_dir(&fetched.root, &staged)?;
+
+        let installed = InstalledSkill {
+            name: fetched.metadata.name.clone(),
+            source: source.clone(),
+            pinned_ref: options.git_ref.clone(),
+            subdir: options.subdir.clone(),
+            commit: fetched.commit.clone(),
+            checksum: fetched.checksum.clone(),
+            installed_at: Utc::now(),
+        };
+        let manifest = serde_json::to_string_pretty(&installed)
+            .map_err(|e| SkillError::InstallError(e.to_string()))?;
+        let manifest_path = staged.join(INSTALL_MANIFEST_FILE);
+        std::fs::write(&manifest_path, manifest).map_err(io_err(&manifest_path))?;
+
+        // Move the previous install aside; it is removed with the staging dir.
+        let previous = staging.path().join(".previous");
+        let replaced = dest.exists();
+        if replaced {
+            std::fs::rename(&dest, &previous).map_err(io_err(&dest))?;
+        }
+        if let Err(e) = std::fs::rename(&staged, &dest) {
+            if replaced {
+                let _ = std::fs::rename(&previous, &dest);
+            }
+            return Err(io_err(&dest)(e));
+        }
+
+        Ok(installed)
+    }
+
+    /// List skills installed with a provenance manifest, sorted by name.
+    pub fn list_installed(&self) -> Vec<InstalledSkill> {
+        let Ok(entries) = std::fs::read_dir(&self.skills_dir) else {
+            return Vec::new();
+        };
+
+        let mut installed: Vec<InstalledSkill> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| InstalledSkill::read(&e.path()))
+            .collect();
+        installed.sort_by(|a, b| a.name.cmp(&b.name));
+        installed
+    }
+
+    /// Re-fetch an installed skill from its recorded source.
+    ///
+    /// Skills pinned to a ref are left alone unless `new_ref` re-pins them.
+    pub fn update(&self, name: &str, new_ref: Option<&str>) -> Result<UpdateOutcome, SkillError> {
+        validate_skill_name(name)?;
+        let installed = InstalledSkill::read(&self.skills_dir.join(name))
+            .ok_or_else(|| SkillError::NotFound(name.to_string()))?;
+
+        if matches!(installed.source, SkillSource::Local { .. }) {
+            return Ok(UpdateOutcome::Skipped(
+                "installed from a local path".to_string(),
+            ));
+        }
+
+        let git_ref = match (new_ref, &installed.pinned_ref) {
+            (Some(r), _) => Some(r.to_string()),
+            (None, Some(pinned)) => {
+                return Ok(UpdateOutcome::Skipped(format!("pinned to {}", pinned)));
+            }
+            (None, None) => None,
+        };
+
+        let options = InstallOptions {
+            git_ref,
+            subdir: installed.subdir.clone(),
+            checksum: None,
+            force: true,
+        };
+
+        // Compare before placing so an unchanged skill is not rewritten.
+        let fetched = fetch(&installed.source, &options)?;
+        if fetched.checksum == installed.checksum && options.git_ref == installed.pinned_ref {
+            return Ok(UpdateOutcome::UpToDate);
+        }
+
+        let updated = self.place(&fetched, &installed.source, &options)?;
+        Ok(UpdateOutcome::Updated {
+            from: installed.commit.unwrap_or(installed.checksum),
+            to: updated.commit.unwrap_or(updated.checksum),
+        })
+    }
+}
+
+/// A skill package fetched and verified, but not yet installed.
+struct FetchedSkill {
+    /// Keeps a git checkout alive until the skill is copied out of it
+    _scratch: tempfile::TempDir,
+    root: PathBuf,
+    metadata: SkillMetadata,
+    checksum: String,
+    commit: Option<String>,
+}
+
+fn fetch(source: &SkillSource, options: &InstallOptions) -> Result<FetchedSkill, SkillError> {
+    let scratch = tempfile::tempdir().map_err(|e| SkillError::IoError {
+        path: std::env::temp_dir(),
+        source: e,
+    })?;
+
+    let (root, commit) = match source {
+        SkillSource::Local { path } => (path.clone(), None),
+        SkillSource::Git { url } => {
+            let checkout = scratch.path().join("checkout");
+            let commit = git_checkout(url, options.git_ref.as_deref(), &checkout)?;
+            (checkout, Some(commit))
+        }
+    };
+
+    let skill_root = match &options.subdir {
+        Some(subdir) => resolve_subdir(&root, subdir)?,
+        None => root,
+    };
+    let metadata = verify_skill_dir(&skill_root)?;
+
+    let checksum = compute_checksum(&skill_root)?;
+    if let Some(expected) = &options.checksum {
+        if !expected.eq_ignore_ascii_case(&checksum) {
+            return Err(SkillError::ChecksumMismatch {
+                expected: expected.clone(),
+                actual: checksum,
+            });
+        }
+    }
+
+    Ok(FetchedSkill {
+        _scratch: scratch,
+        root: skill_root,
+        metadata,
+        checksum,
+        commit,
+    })
+}
+
+/// Join `subdir` onto `root`, refusing anything that could leave the source tree.
+fn resolve_subdir(root: &Path, subdir: &str) -> Result<PathBuf, SkillError> {
+    let relative = Path::new(subdir);
+    if !relative
+        .components()
+        .all(|c| matches!(c, Component::Normal(_)))
+    {
+        return Err(SkillError::InstallError(format!(
+            "Invalid subdirectory '{}': use a relative path without '..'",
+            subdir
+        )));
+    }
+
+    let mut dir = root.to_path_buf();
+    for component in relative.components() {
+        dir.push(component);
+        if is_symlink(&dir) {
+            return Err(SkillError::InstallError(format!(
+                "Invalid subdirectory '{}': {} is a symlink",
+                subdir,
+                dir.display()
+            )));
+        }
+    }
+    Ok(dir)
+}
+
+fn is_symlink(path: &Path) -> bool {
+    std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_symlink())
+}
+
+fn symlink_error(path: &Path) -> SkillError {
+    SkillError::InstallError(format!(
+        "Skill packages may not contain symlinks: {}",
+        path.display()
+    ))
+}
+
+/// Check that a directory contains a SKILL.md with valid metadata.
+pub fn verify_skill_dir(dir: &Path) -> Result<SkillMetadata, SkillError> {
+    let skill_md = dir.join("SKILL.md");
+    if is_symlink(&skill_md) {
+        return Err(symlink_error(&skill_md));
+    }
+    if !skill_md.is_file() {
+        return Err(SkillError::InstallError(format!(
+            "SKILL.md not found in: {}",
+            dir.display()
+        )));
+    }
+
+    let content = std::fs::read_to_string(&skill_md).map_err(|e| SkillError::IoError {
+        path: skill_md.clone(),
+        source: e,
+    })?;
+    let (metadata, _body) = parse_skill(&content)?;
+
+    validate_skill_name(&metadata.name)?;
+    if metadata.description.trim().is_empty() {
+        return Err(SkillError::InstallError(format!(
+            "Skill '{}' is missing a description",
+            metadata.name
+        )));
+    }
+
+    Ok(metadata)
+}
+
+/// Check that `name` is usable as a directory name under the skills directory.
+fn validate_skill_name(name: &str) -> Result<(), SkillError> {
+    let valid = !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(SkillError::InstallError(format!(
+            "Invalid skill name '{}': use letters, digits, '-' or '_'",
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Compute a sha256 checksum over a skill directory's files.
+///
+/// Files are hashed in sorted relative-path order; `.git` and the install
+/// manifest are excluded so the value is stable across installs.
+pub fn compute_checksum(dir: &Path) -> Result<String, SkillError> {
+    let mut files = Vec::new();
+    collect_files(dir, dir, &mut files)?;
+    files.sort();
+
+    let mut hasher = Sha256::new();
+    for relative in files {
+        let path = dir.join(&relative);
+        let content = std::fs::read(&path).map_err(|e| SkillError::IoError {
+            path: path.clone(),
+            source: e,
+        })?;
+        hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+        hasher.update([0]);
+        hasher.update(&content);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
+}
+
+fn is_excluded(name: &std::ffi::OsStr) -> bool {
+    name == ".git" || name == INSTALL_MANIFEST_FILE
+}
+
+fn collect_files(root: &Path, dir: &Path, out: &mut Vec<PathBuf>) -> Result<(), SkillError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| SkillError::IoError {
+        path: dir.to_path_buf(),
+        source: e,
+    })?;
+
+    for entry in entries.flatten() {
+        if is_excluded(&entry.file_name()) {
+            continue;
+        }
+        let path = entry.path();
+        let file_type = entry.file_type().map_err(|e| SkillError::IoError {
+            path: path.clone(),
+            source: e,
+        })?;
+        if file_type.is_symlink() {
+            return Err(symlink_error(&path));
+        }
+        if file_type.is_dir() {
+            collect_files(root, &path, out)?;
+        } else if let Ok(relative) = path.strip_prefix(root) {
+            out.push(relative.to_path_buf());
+        }
+    }
+    Ok(())
+}
+
+fn copy_skill_dir(src: &Path, dst: &Path) -> Result<(), SkillError> {
+    let io_err = |path: &Path| {
+        let path = path.to_path_buf();
+        move |e| SkillError::IoError { path, source: e }
+    };
+
+    std::fs::create_dir_all(dst).map_err(io_err(dst))?;
+    for entry in std::fs::read_dir(src).map_err(io_err(src))?.flatten() {
+        if is_excluded(&entry.file_name()) {
+            continue;
+        }
+        let src_path = entry.path();
+        let dst_path = dst.join(entry.file_name());
+        let file_type = entry.file_type().map_err(io_err(&src_path))?;
+        if file_type.is_symlink() {
+            return Err(symlink_error(&src_path));
+        }
+        if file_type.is_dir() {
+            copy_skill_dir(&src_path, &dst_path)?;
+        } else {
+            std::fs::copy(&src_path, &dst_path).map_err(io_err(&src_path))?;
+        }
+    }
+    Ok(())
+}
+
+fn run_git(args: &[&str], cwd: Option<&Path>) -> Result<String, SkillError> {
+    let mut cmd = Command::new("git");
+    cmd.args(args);
+    if let Some(dir) = cwd {
+        cmd.current_dir(dir);
+    }
+
+    let output = cmd
+        .output()
+        .map_err(|e| SkillError::InstallError(format!("Failed to run git: {}", e)))?;
+    if !output.status.success() {
+        return Err(SkillError::InstallError(format!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Clone `url` into `dest`, check out `git_ref` if given, and return the resolved commit.
+fn git_checkout(url: &str, git_ref: Option<&str>, dest: &Path) -> Result<String, SkillError> {
+    // A ref starting with '-' would be parsed as a git option
+    if let Some(r) = git_ref.filter(|r| r.is_empty() || r.starts_with('-')) {
+        return Err(SkillError::InstallError(format!(
+            "Invalid git ref: '{}'",
+            r
+        )));
+    }
+
+    let dest_str = dest.to_string_lossy();
+    run_git(&["clone", "--quiet", "--", url, &dest_str], None)?;
+    if let Some(r) = git_ref {
+        run_git(&["checkout", "--quiet", r, "--"], Some(dest))?;
+    }
+    run_git(&["rev-parse", "HEAD"], Some(dest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_skill(dir: &Path, name: &str, body: &str) {
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(
+            dir.join("SKILL.md"),
+            format!("---\nname: {name}\ndescription: {name} skill\n---\n{body}\n"),
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn test_parse_source() {
+        assert_eq!(
+            SkillSource::parse("junhoyeo/uira-skills").unwrap(),
+            SkillSource::Git {
+                url: "https://github.com/junhoyeo/uira-skills.git".to_string()
+            }
+        );
+        assert_eq!(
+            SkillSource::parse("git@github.com:a/b.git").unwrap(),
+            SkillSource::Git {
+                url: "git@github.com:a/b.git".to_string()
+            }
+        );
+        assert!(SkillSource::parse("not a source").is_err());
+    }
+
+    #[test]
+    fn test_install_local_with_checksum() {
+        let src = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        write_skill(src.path(), "docs-writer", "Write docs.");
+
+        let checksum = compute_checksum(src.path()).unwrap();
+        let installer = SkillInstaller::new(dest.path());
+        let source = SkillSource::parse(src.path().to_str().unwrap()).unwrap();
+
+        let bad = InstallOptions {
+            checksum: Some("deadbeef".to_string()),
+            ..Default::default()
+        };
+        assert!(matches!(
+            installer.install(&source, &bad),
+            Err(SkillError::ChecksumMismatch { .. })
+        ));
+
+        let good = InstallOptions {
+            checksum: Some(checksum.clone()),
+            ..Default::default()
+        };
+        let installed = installer.install(&source, &good).unwrap();
+        assert_eq!(installed.name, "docs-writer");
+        assert!(dest.path().join("docs-writer/SKILL.md").exists());
+
+        let listed = installer.list_installed();
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].checksum, checksum);
+
+        assert!(installer.install(&source, &good).is_err());
+        assert_eq!(
+            installer.update("docs-writer", None).unwrap(),
+            UpdateOutcome::Skipped("installed from a local path".to_string())
+        );
+    }
+
+    #[test]
+    fn test_verify_rejects_invalid_name() {
+        let src = tempfile::tempdir().unwrap();
+        write_skill(src.path(), "../escape", "Body");
+        assert!(verify_skill_dir(src.path()).is_err());
+    }
+
+    #[test]
+    fn test_update_rejects_invalid_name() {
+        let parent = tempfile::tempdir().unwrap();
+        let src = parent.path().join("src");
+        write_skill(&src, "outside", "Body");
+        SkillInstaller::new(parent.path())
+            .install(
+                &SkillSource::parse(src.to_str().unwrap()).unwrap(),
+                &InstallOptions::default(),
+            )
+            .unwrap();
+
+        let installer = SkillInstaller::new(parent.path().join("skills"));
+        assert!(matches!(
+            installer.update("../outside", None),
+            Err(SkillError::InstallError(_))
+        ));
+    }
+
+    #[test]
+    fn test_git_ref_is_checked_before_cloning() {
+        let dest = tempfile::tempdir().unwrap();
+        let checkout = dest.path().join("checkout");
+        let err = git_checkout("file:///nonexistent", Some("--upload-pack=x"), &checkout)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid git ref"), "{}", err);
+        assert!(!checkout.exists());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_install_rejects_paths_outside_the_package() {
+        let outside = tempfile::tempdir().unwrap();
+        let src = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        std::fs::write(outside.path().join("secret.txt"), "secret").unwrap();
+        write_skill(&src.path().join("skill"), "linker", "v1");
+
+        let installer = SkillInstaller::new(dest.path());
+        let source = SkillSource::parse(src.path().to_str().unwrap()).unwrap();
+        let with_subdir = |subdir: &str| InstallOptions {
+            subdir: Some(subdir.to_string()),
+            force: true,
+            ..Default::default()
+        };
+
+        installer.install(&source, &with_subdir("skill")).unwrap();
+        assert!(installer
+            .install(&source, &with_subdir("../skill"))
+            .is_err());
+        assert!(installer
+            .install(&source, &with_subdir(outside.path().to_str().unwrap()))
+            .is_err());
+
+        std::os::unix::fs::symlink(outside.path(), src.path().join("escape")).unwrap();
+        assert!(installer.install(&source, &with_subdir("escape")).is_err());
+
+        std::os::unix::fs::symlink(
+            outside.path().join("secret.txt"),
+            src.path().join("skill/notes.txt"),
+        )
+        .unwrap();
+        write_skill(&src.path().join("skill"), "linker", "v2");
+        assert!(installer.install(&source, &with_subdir("skill")).is_err());
+
+        // The failed reinstall leaves the previous install in place.
+        let content = std::fs::read_to_string(dest.path().join("linker/SKILL.md")).unwrap();
+        assert!(content.contains("v1"));
+        assert!(!dest.path().join("linker/notes.txt").exists());
+        assert_eq!(installer.list_installed().len(), 1);
+    }
+
+    #[test]
+    fn test_install_and_update_from_git() {
+        if run_git(&["--version"], None).is_err() {
+            return;
+        }
+
+        let repo = tempfile::tempdir().unwrap();
+        let dest = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            run_git(
+                &[
+                    &["-c", "user.name=test", "-c", "user.email=test@example.com"],
+                    args,
+                ]
+                .concat(),
+                Some(repo.path()),
+            )
+            .unwrap()
+        };
+
+        git(&["init", "--quiet"]);
+        write_skill(repo.path(), "release-notes", "v1");
+        git(&["add", "."]);
+        git(&["commit", "--quiet", "-m", "v1"]);
+        git(&["tag", "v1"]);
+
+        let url = format!("file://{}", repo.path().display());
+        let source = SkillSource::parse(&url).unwrap();
+        let installer = SkillInstaller::new(dest.path());
+
+        let option_ref = InstallOptions {
+            git_ref: Some("--upload-pack=touch pwned".to_string()),
+            ..Default::default()
+        };
+        assert!(installer.install(&source, &option_ref).is_err());
+
+        installer
+            .install(&source, &InstallOptions::default())
+            .unwrap();
+        assert_eq!(
+            installer.update("release-notes", None).unwrap(),
+            UpdateOutcome::UpToDate
+        );
+
+        write_skill(repo.path(), "release-notes", "v2");
+        git(&["commit", "--quiet", "-am", "v2"]);
+        assert!(matches!(
+            installer.update("release-notes", None).unwrap(),
+            UpdateOutcome::Updated { .. }
+        ));
+        let content = std::fs::read_to_string(dest.path().join("release-notes/SKILL.md")).unwrap();
+        assert!(content.contains("v2"));
+
+        let pinned = InstallOptions {
+            git_ref: Some("v1".to_string()),
+            force: true,
+            ..Default::default()
+        };
+        installer.install(&source, &pinned).unwrap();
+        assert_eq!(
+            installer.update("release-notes", None).unwrap(),
+            UpdateOutcome::Skipped("pinned to v1".to_string())
+        );
+    }
+}
@@ -63,27 +63,28 @@ impl SkillLoader {
 
 /// Format loaded skills as XML-tagged blocks for context injection.
 ///
+/// Gateway skills are activated by name, so each body is rendered with no
+/// invocation arguments: declared defaults fill their placeholders, and a
+/// required argument without one fails with [`SkillError::InvalidArguments`].
+///
 /// Each skill becomes:
 /// ```text
 /// <skill name="my-skill">
 /// markdown content
 /// </skill>
 /// ```
-pub fn get_context_injection(skills: &[Skill]) -> String {
-    if skills.is_empty() {
-        return String::new();
-    }
-
-    skills
+pub fn get_context_injection(skills: &[Skill]) -> Result<String, SkillError> {
+    let blocks = skills
         .iter()
         .map(|skill| {
-            format!(
+            Ok(format!(
                 "<skill name=\"{}\">\n{}\n</skill>",
-                skill.metadata.name, skill.content
-            )
+                skill.metadata.name,
+                skill.render("")?
+            ))
         })
-        .collect::<Vec<_>>()
-        .join("\n\n")
+        .collect::<Result<Vec<_>, SkillError>>()?;
+    Ok(blocks.join("\n\n"))
 }
 
 #[cfg(test)]
@@ -107,6 +108,8 @@ mod tests {
                     name: "alpha".to_string(),
                     description: "Alpha skill".to_string(),
                     metadata: None,
+                    arguments: Vec::new(),
+                    permissions: Default::default(),
                 },
                 content: "# Alpha\n\nAlpha content.".to_string(),
                 source_path: PathBuf::from("/fake/alpha/SKILL.md"),
@@ -119,13 +122,15 @@ mod tests {
                         emoji: Some("🔥".to_string()),
                         requirements: None,
                     }),
+                    arguments: Vec::new(),
+                    permissions: Default::default(),
                 },
                 content: "# Beta\n\nBeta content.".to_string(),
                 source_path: PathBuf::from("/fake/beta/SKILL.md"),
             },
         ];
 
-        let result = get_context_injection(&skills);
+        let result = get_context_injection(&skills).unwrap();
         assert!(result.contains("<skill name=\"alpha\">"));
         assert!(result.contains("# Alpha\n\nAlpha content."));
         assert!(result.contains("</skill>"));
@@ -136,9 +141,27 @@ mod tests {
         assert_eq!(skill_tags.len(), 2);
     }
 
+    #[test]
+    fn test_context_injection_renders_arguments() {
+        let content = "---\nname: release\ndescription: Release\narguments:\n  - name: branch\n    default: main\n---\nRelease from {{branch}}.";
+        let (metadata, body) = parse_skill(content).unwrap();
+        let mut skill = Skill {
+            metadata,
+            content: body,
+            source_path: PathBuf::from("/fake/release/SKILL.md"),
+        };
+        let result = get_context_injection(std::slice::from_ref(&skill)).unwrap();
+        assert!(result.contains("Release from main."));
+
+        skill.metadata.arguments[0].default = None;
+        skill.metadata.arguments[0].required = true;
+        let err = get_context_injection(&[skill]).unwrap_err();
+        assert!(matches!(err, SkillError::InvalidArguments { .. }));
+    }
+
     #[test]
     fn test_context_injection_empty() {
-        let result = get_context_injection(&[]);
+        let result = get_context_injection(&[]).unwrap();
         assert!(result.is_empty());
     }
 
@@ -2,10 +2,15 @@
 
 mod discovery;
 mod error;
+mod installer;
 mod loader;
 mod parser;
 
 pub use discovery::{discover_skills, SkillInfo};
 pub use error::SkillError;
+pub use installer::{
+    compute_checksum, verify_skill_dir, InstallOptions, InstalledSkill, SkillInstaller,
+    SkillSource, UpdateOutcome, INSTALL_MANIFEST_FILE,
+};
 pub use loader::{get_context_injection, SkillLoader};
 pub use parser::{Skill, SkillMeta, SkillMetadata};
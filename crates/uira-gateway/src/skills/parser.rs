@@ -1,6 +1,9 @@
 use std::path::PathBuf;
 
 use serde::Deserialize;
+use uira_orchestration::features::builtin_skills::{
+    render_skill_template, SkillArgument, SkillPermissions,
+};
 
 use super::error::SkillError;
 
@@ -18,6 +21,12 @@ pub struct SkillMetadata {
     pub description: String,
     #[serde(default)]
     pub metadata: Option<SkillMeta>,
+    /// Declared arguments substituted into `{{name}}` placeholders on invocation
+    #[serde(default)]
+    pub arguments: Vec<SkillArgument>,
+    /// Tool and permission restrictions applied while the skill is active
+    #[serde(flatten)]
+    pub permissions: SkillPermissions,
 }
 
 /// A fully loaded skill with metadata, markdown content, and source path.
@@ -28,6 +37,21 @@ pub struct Skill {
     pub source_path: PathBuf,
 }
 
+impl Skill {
+    /// Render the skill body with invocation arguments substituted.
+    ///
+    /// Fails with [`SkillError::InvalidArguments`] when required arguments are
+    /// missing or a value does not match its declared type.
+    pub fn render(&self, raw_args: &str) -> Result<String, SkillError> {
+        render_skill_template(&self.content, &self.metadata.arguments, raw_args).map_err(|e| {
+            SkillError::InvalidArguments {
+                skill: self.metadata.name.clone(),
+                source: e,
+            }
+        })
+    }
+}
+
 /// Parse a SKILL.md file's content into metadata and markdown body.
 ///
 /// The expected format is:
@@ -132,6 +156,40 @@ Body content here.
         assert_eq!(body, "Body content here.");
     }
 
+    #[test]
+    fn test_parse_and_render_arguments() {
+        let content = r#"---
+name: release-notes
+description: Draft release notes
+arguments:
+  - name: version
+    type: string
+    required: true
+  - name: draft
+    type: boolean
+    default: true
+---
+Write notes for {{version}} (draft: {{draft}}).
+"#;
+        let (meta, body) = parse_skill(content).unwrap();
+        assert_eq!(meta.arguments.len(), 2);
+        assert!(meta.arguments[0].required);
+
+        let skill = Skill {
+            metadata: meta,
+            content: body,
+            source_path: PathBuf::from("/fake/release-notes/SKILL.md"),
+        };
+        assert_eq!(
+            skill.render("v2.0").unwrap(),
+            "Write notes for v2.0 (draft: true)."
+        );
+
+        let err = skill.render("").unwrap_err().to_string();
+        assert!(err.contains("release-notes"));
+        assert!(err.contains("missing required argument(s): version"));
+    }
+
     #[test]
     fn test_parse_missing_frontmatter() {
         let content = "# Just markdown, no frontmatter\n\nSome text.";
@@ -0,0 +1,107 @@
+//! Token discovery for each code host
+
+use std::process::Command;
+
+use uira_core::schema::CodeHostKind;
+
+use crate::error::{GitHubError, Result};
+
+/// Environment variables checked for a `kind` token, in order
+pub fn token_env_vars(kind: CodeHostKind) -> &'static [&'static str] {
+    match kind {
+        CodeHostKind::GitHub => &["GH_TOKEN", "GITHUB_TOKEN"],
+        CodeHostKind::GitLab => &["GITLAB_TOKEN", "GL_TOKEN"],
+        CodeHostKind::Gitea => &["GITEA_TOKEN"],
+    }
+}
+
+/// Token for `host` from the environment, else from the host's CLI
+/// (`gh auth token` or `glab config get token`)
+pub fn resolve_token(kind: CodeHostKind, host: &str) -> Result<String> {
+    token_from(
+        kind,
+        |name| std::env::var(name).ok(),
+        || cli_token(kind, host),
+    )
+}
+
+fn token_from(
+    kind: CodeHostKind,
+    env: impl Fn(&str) -> Option<String>,
+    cli: impl FnOnce() -> Option<String>,
+) -> Result<String> {
+    token_env_vars(kind)
+        .iter()
+        .filter_map(|name| env(name))
+        .map(|token| token.trim().to_string())
+        .find(|token| !token.is_empty())
+        .or_else(cli)
+        .ok_or(match kind {
+            CodeHostKind::GitHub => GitHubError::MissingToken {
+                host: "GitHub",
+                hint: "set GH_TOKEN or GITHUB_TOKEN, or run `gh auth login`",
+            },
+            CodeHostKind::GitLab => GitHubError::MissingToken {
+                host: "GitLab",
+                hint: "set GITLAB_TOKEN or GL_TOKEN, or run `glab auth login`",
+            },
+            CodeHostKind::Gitea => GitHubError::MissingToken {
+                host: "Gitea",
+                hint: "set GITEA_TOKEN",
+            },
+        })
+}
+
+fn cli_token(kind: CodeHostKind, host: &str) -> Option<String> {
+    let (program, args): (_, &[&str]) = match kind {
+        CodeHostKind::GitHub => ("gh", &["auth", "token", "--hostname", host]),
+        CodeHostKind::GitLab => ("glab", &["config", "get", "token", "--host", host]),
+        CodeHostKind::Gitea => return None,
+    };
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        tracing::debug!(
+            "`{}` token lookup failed: {}",
+            program,
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+        return None;
+    }
+    let token = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!token.is_empty()).then_some(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn env_token_wins_over_cli() {
+        let token = token_from(
+            CodeHostKind::GitHub,
+            |name| (name == "GITHUB_TOKEN").then(|| " ghp_env \n".to_string()),
+            || Some("gho_cli".to_string()),
+        )
+        .unwrap();
+        assert_eq!(token, "ghp_env");
+
+        let token = token_from(
+            CodeHostKind::GitHub,
+            |name| (name == "GH_TOKEN").then(String::new),
+            || Some("gho_cli".to_string()),
+        )
+        .unwrap();
+        assert_eq!(token, "gho_cli");
+
+        let token = token_from(
+            CodeHostKind::GitLab,
+            |name| (name == "GITHUB_TOKEN").then(|| "ghp_env".to_string()),
+            || Some("glpat_cli".to_string()),
+        )
+        .unwrap();
+        assert_eq!(token, "glpat_cli");
+
+        let err = token_from(CodeHostKind::Gitea, |_| None, || None).unwrap_err();
+        assert_eq!(err.to_string(), "no Gitea token found; set GITEA_TOKEN");
+    }
+}
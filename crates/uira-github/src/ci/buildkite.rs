@@ -0,0 +1,241 @@
+//! Buildkite builds and job logs
+
+use async_trait::async_trait;
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use uira_core::schema::BuildkiteSettings;
+
+use super::{encode_query, CiProvider, FailedBuild, FailedJob};
+use crate::client::send;
+use crate::error::Result;
+
+pub const DEFAULT_BUILDKITE_API_URL: &str = "https://api.buildkite.com/v2";
+
+#[derive(Clone)]
+pub struct BuildkiteClient {
+    http: reqwest::Client,
+    api_url: String,
+    token: String,
+    organization: String,
+    pipeline: String,
+}
+
+impl std::fmt::Debug for BuildkiteClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("BuildkiteClient")
+            .field("api_url", &self.api_url)
+            .field("organization", &self.organization)
+            .field("pipeline", &self.pipeline)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct Build {
+    number: u64,
+    state: String,
+    #[serde(default)]
+    message: Option<String>,
+    #[serde(default)]
+    branch: Option<String>,
+    #[serde(default)]
+    commit: Option<String>,
+    web_url: String,
+    #[serde(default)]
+    jobs: Vec<Job>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Job {
+    id: String,
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    state: Option<String>,
+    #[serde(default)]
+    web_url: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobLog {
+    #[serde(default)]
+    content: String,
+}
+
+impl BuildkiteClient {
+    pub fn new(token: impl Into<String>, settings: &BuildkiteSettings) -> Self {
+        let api_url = settings
+            .api_url
+            .as_deref()
+            .unwrap_or(DEFAULT_BUILDKITE_API_URL);
+        Self {
+            http: reqwest::Client::new(),
+            api_url: api_url.trim_end_matches('/').to_string(),
+            token: token.into(),
+            organization: settings.organization.clone(),
+            pipeline: settings.pipeline.clone(),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        send("Buildkite", self.request(Method::GET, path)).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.api_url, path))
+            .bearer_auth(&self.token)
+            .header("User-Agent", "uira")
+    }
+
+    fn builds_path(&self) -> String {
+        format!(
+            "/organizations/{}/pipelines/{}/builds",
+            self.organization, self.pipeline
+        )
+    }
+}
+
+#[async_trait]
+impl CiProvider for BuildkiteClient {
+    fn name(&self) -> &'static str {
+        "Buildkite"
+    }
+
+    async fn latest_failure(&self, branch: Option<&str>) -> Result<Option<FailedBuild>> {
+        let mut path = format!("{}?state=finished&per_page=1", self.builds_path());
+        if let Some(branch) = branch {
+            path.push_str(&format!("&branch={}", encode_query(branch)));
+        }
+        let builds: Vec<Build> = self.get(&path).await?;
+        let Some(build) = builds.into_iter().next() else {
+            return Ok(None);
+        };
+        if build.state != "failed" {
+            return Ok(None);
+        }
+
+        let builds_path = self.builds_path();
+        let jobs = build
+            .jobs
+            .into_iter()
+            .filter(|job| {
+                job.kind == "script"
+                    && matches!(
+                        job.state.as_deref(),
+                        Some("failed" | "timed_out" | "broken")
+                    )
+            })
+            .map(|job| FailedJob {
+                name: job.name.unwrap_or_else(|| job.id.clone()),
+                url: job.web_url.unwrap_or_else(|| build.web_url.clone()),
+                step: None,
+                log_path: format!("{}/{}/jobs/{}/log", builds_path, build.number, job.id),
+            })
+            .collect();
+
+        Ok(Some(FailedBuild {
+            title: build
+                .message
+                .as_deref()
+                .and_then(|message| message.lines().next())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("build #{}", build.number)),
+            branch: build.branch,
+            commit: build.commit,
+            url: build.web_url,
+            jobs,
+        }))
+    }
+
+    async fn job_log(&self, job: &FailedJob) -> Result<String> {
+        let log: JobLog = self.get(&job.log_path).await?;
+        Ok(log.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn client(server: &MockServer) -> BuildkiteClient {
+        BuildkiteClient::new(
+            "bk_test",
+            &BuildkiteSettings {
+                organization: "acme".to_string(),
+                pipeline: "web".to_string(),
+                api_url: Some(server.uri()),
+            },
+        )
+    }
+
+    #[tokio::test]
+    async fn finds_failed_script_jobs_and_logs() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/organizations/acme/pipelines/web/builds"))
+            .and(query_param("branch", "main"))
+            .and(header("authorization", "Bearer bk_test"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "number": 42,
+                "state": "failed",
+                "message": "Fix flaky test\n\nDetails",
+                "branch": "main",
+                "commit": "abc123",
+                "web_url": "https://buildkite.com/acme/web/builds/42",
+                "jobs": [
+                    {"id": "j1", "type": "script", "name": "lint", "state": "passed"},
+                    {"id": "j2", "type": "script", "name": "test", "state": "failed",
+                     "web_url": "https://buildkite.com/acme/web/builds/42#j2"},
+                    {"id": "w1", "type": "waiter"}
+                ]
+            }])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(
+                "/organizations/acme/pipelines/web/builds/42/jobs/j2/log",
+            ))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!({"content": "FAIL src/app.test.ts\n"})),
+            )
+            .mount(&server)
+            .await;
+
+        let ci = client(&server);
+        let build = ci.latest_failure(Some("main")).await.unwrap().unwrap();
+        assert_eq!(build.title, "Fix flaky test");
+        assert_eq!(build.jobs.len(), 1);
+        assert_eq!(build.jobs[0].name, "test");
+
+        let log = ci.job_log(&build.jobs[0]).await.unwrap();
+        assert_eq!(log, "FAIL src/app.test.ts\n");
+    }
+
+    #[tokio::test]
+    async fn passed_build_has_no_failure() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/organizations/acme/pipelines/web/builds"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "number": 43,
+                "state": "passed",
+                "web_url": "https://buildkite.com/acme/web/builds/43"
+            }])))
+            .mount(&server)
+            .await;
+
+        assert!(client(&server)
+            .latest_failure(None)
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -0,0 +1,243 @@
+//! GitHub Actions workflow runs and job logs
+
+use async_trait::async_trait;
+use serde::Deserialize;
+
+use super::{encode_query, CiProvider, FailedBuild, FailedJob};
+use crate::client::{GitHubClient, PAGE_SIZE};
+use crate::error::Result;
+use crate::repo::RepoRef;
+
+/// Finished runs inspected for the newest commit's workflows
+const RECENT_RUNS: u32 = 20;
+
+#[derive(Debug)]
+pub struct GitHubActionsClient {
+    client: GitHubClient,
+    repo: RepoRef,
+}
+
+#[derive(Debug, Deserialize)]
+struct RunList {
+    workflow_runs: Vec<Run>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Run {
+    id: u64,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    display_title: Option<String>,
+    #[serde(default)]
+    head_branch: Option<String>,
+    head_sha: String,
+    #[serde(default)]
+    conclusion: Option<String>,
+    html_url: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobList {
+    jobs: Vec<Job>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Job {
+    id: u64,
+    name: String,
+    #[serde(default)]
+    conclusion: Option<String>,
+    html_url: String,
+    #[serde(default)]
+    steps: Vec<Step>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Step {
+    name: String,
+    #[serde(default)]
+    conclusion: Option<String>,
+}
+
+fn failed(conclusion: Option<&str>) -> bool {
+    matches!(
+        conclusion,
+        Some("failure" | "timed_out" | "startup_failure")
+    )
+}
+
+impl GitHubActionsClient {
+    pub fn new(token: impl Into<String>, api_url: impl Into<String>, repo: RepoRef) -> Self {
+        Self {
+            client: GitHubClient::new(token, api_url),
+            repo,
+        }
+    }
+}
+
+#[async_trait]
+impl CiProvider for GitHubActionsClient {
+    fn name(&self) -> &'static str {
+        "GitHub Actions"
+    }
+
+    async fn latest_failure(&self, branch: Option<&str>) -> Result<Option<FailedBuild>> {
+        let mut path = format!(
+            "/repos/{}/actions/runs?status=completed&per_page={}",
+            self.repo, RECENT_RUNS
+        );
+        if let Some(branch) = branch {
+            path.push_str(&format!("&branch={}", encode_query(branch)));
+        }
+        let runs: RunList = self.client.get(&path).await?;
+
+        // Every workflow of the newest finished commit counts, so a passing
+        // lint run doesn't hide a failing test run
+        let Some(newest) = runs.workflow_runs.first() else {
+            return Ok(None);
+        };
+        let sha = newest.head_sha.clone();
+        let failed_runs: Vec<&Run> = runs
+            .workflow_runs
+            .iter()
+            .filter(|run| run.head_sha == sha && failed(run.conclusion.as_deref()))
+            .collect();
+        let Some(first) = failed_runs.first() else {
+            return Ok(None);
+        };
+
+        let mut jobs = Vec::new();
+        for run in &failed_runs {
+            let list: JobList = self
+                .client
+                .get(&format!(
+                    "/repos/{}/actions/runs/{}/jobs?filter=latest&per_page={}",
+                    self.repo, run.id, PAGE_SIZE
+                ))
+                .await?;
+            jobs.extend(
+                list.jobs
+                    .into_iter()
+                    .filter(|job| failed(job.conclusion.as_deref()))
+                    .map(|job| FailedJob {
+                        name: match &run.name {
+                            Some(workflow) => format!("{} / {}", workflow, job.name),
+                            None => job.name,
+                        },
+                        url: job.html_url,
+                        step: job
+                            .steps
+                            .into_iter()
+                            .find(|step| failed(step.conclusion.as_deref()))
+                            .map(|step| step.name),
+                        log_path: format!("/repos/{}/actions/jobs/{}/logs", self.repo, job.id),
+                    }),
+            );
+        }
+
+        Ok(Some(FailedBuild {
+            title: first
+                .display_title
+                .clone()
+                .or_else(|| first.name.clone())
+                .unwrap_or_else(|| format!("run {}", first.id)),
+            branch: first.head_branch.clone(),
+            commit: Some(sha),
+            url: first.html_url.clone(),
+            jobs,
+        }))
+    }
+
+    async fn job_log(&self, job: &FailedJob) -> Result<String> {
+        // Answers with a redirect to the raw log, which reqwest follows
+        self.client.get_text(&job.log_path).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use wiremock::matchers::{method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn run(id: u64, name: &str, sha: &str, conclusion: &str) -> serde_json::Value {
+        json!({
+            "id": id,
+            "name": name,
+            "display_title": "Add CI triage",
+            "head_branch": "feature",
+            "head_sha": sha,
+            "conclusion": conclusion,
+            "html_url": format!("https://github.com/team/app/actions/runs/{}", id)
+        })
+    }
+
+    #[tokio::test]
+    async fn finds_failed_jobs_of_newest_commit() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/team/app/actions/runs"))
+            .and(query_param("branch", "feature"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "workflow_runs": [
+                    run(3, "Lint", "bbb", "success"),
+                    run(2, "Test", "bbb", "failure"),
+                    run(1, "Test", "aaa", "failure")
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/team/app/actions/runs/2/jobs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "jobs": [
+                    {"id": 20, "name": "linux", "conclusion": "success", "html_url": "https://github.com/team/app/actions/runs/2/job/20"},
+                    {"id": 21, "name": "macos", "conclusion": "failure", "html_url": "https://github.com/team/app/actions/runs/2/job/21",
+                     "steps": [{"name": "Checkout", "conclusion": "success"}, {"name": "cargo test", "conclusion": "failure"}]}
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/team/app/actions/jobs/21/logs"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("error: boom\n"))
+            .mount(&server)
+            .await;
+
+        let ci = GitHubActionsClient::new(
+            "ghp_test",
+            server.uri(),
+            RepoRef::parse("team/app").unwrap(),
+        );
+        let build = ci.latest_failure(Some("feature")).await.unwrap().unwrap();
+        assert_eq!(build.commit.as_deref(), Some("bbb"));
+        assert_eq!(build.title, "Add CI triage");
+        assert_eq!(build.jobs.len(), 1);
+        assert_eq!(build.jobs[0].name, "Test / macos");
+        assert_eq!(build.jobs[0].step.as_deref(), Some("cargo test"));
+
+        let log = ci.job_log(&build.jobs[0]).await.unwrap();
+        assert_eq!(log, "error: boom\n");
+    }
+
+    #[tokio::test]
+    async fn passing_newest_commit_has_no_failure() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/team/app/actions/runs"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "workflow_runs": [run(5, "Test", "ccc", "success"), run(4, "Test", "bbb", "failure")]
+            })))
+            .mount(&server)
+            .await;
+
+        let ci = GitHubActionsClient::new(
+            "ghp_test",
+            server.uri(),
+            RepoRef::parse("team/app").unwrap(),
+        );
+        assert!(ci.latest_failure(None).await.unwrap().is_none());
+    }
+}
@@ -0,0 +1,160 @@
+//! CI services whose failing jobs the agent can triage
+//!
+//! A [`CiProvider`] finds the failed jobs of a branch's newest finished
+//! build and downloads their logs; [`extract_signature`] boils a log down to
+//! the error worth fixing.
+
+mod buildkite;
+mod github_actions;
+mod signature;
+
+pub use buildkite::{BuildkiteClient, DEFAULT_BUILDKITE_API_URL};
+pub use github_actions::GitHubActionsClient;
+pub use signature::{extract_signature, FailureSignature};
+
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uira_core::schema::{CiProviderKind, CiSettings, CodeHostKind, CodeHostSettings};
+
+use crate::auth::resolve_token;
+use crate::error::{GitHubError, Result};
+use crate::host::default_api_url;
+use crate::repo::{remote_host, RepoRef};
+
+/// Environment variables checked for a Buildkite API token, in order
+const BUILDKITE_TOKEN_VARS: [&str; 2] = ["BUILDKITE_API_TOKEN", "BUILDKITE_TOKEN"];
+
+/// A finished build with failed jobs
+#[derive(Debug, Clone)]
+pub struct FailedBuild {
+    pub title: String,
+    pub branch: Option<String>,
+    pub commit: Option<String>,
+    pub url: String,
+    pub jobs: Vec<FailedJob>,
+}
+
+#[derive(Debug, Clone)]
+pub struct FailedJob {
+    pub name: String,
+    pub url: String,
+    /// Step that failed, when the service reports steps
+    pub step: Option<String>,
+    /// API path of the job's log
+    pub(crate) log_path: String,
+}
+
+#[async_trait]
+pub trait CiProvider: Send + Sync + fmt::Debug {
+    /// Service name for display
+    fn name(&self) -> &'static str;
+
+    /// Failed jobs of the newest finished build on `branch` (any branch when
+    /// `None`), or `None` when that build passed
+    async fn latest_failure(&self, branch: Option<&str>) -> Result<Option<FailedBuild>>;
+
+    /// Full log of a failed job
+    async fn job_log(&self, job: &FailedJob) -> Result<String>;
+}
+
+/// Client for the CI service in `settings`, reading GitHub Actions from the
+/// repository of `remote_url` with the code host's token
+pub fn connect_ci(
+    settings: &CiSettings,
+    code_host: &CodeHostSettings,
+    remote_url: Option<&str>,
+) -> Result<Arc<dyn CiProvider>> {
+    match settings.effective_provider() {
+        CiProviderKind::GithubActions => {
+            let remote_url = remote_url.ok_or_else(|| {
+                GitHubError::Repo("no origin remote to read GitHub Actions runs from".to_string())
+            })?;
+            let (Some(repo), Some(host)) = (
+                RepoRef::from_remote_url(remote_url),
+                remote_host(remote_url),
+            ) else {
+                return Err(GitHubError::Repo(format!(
+                    "'{}' is not a repository remote",
+                    remote_url
+                )));
+            };
+            let api_url = code_host
+                .api_url
+                .clone()
+                .unwrap_or_else(|| default_api_url(CodeHostKind::GitHub, &host));
+            let token = resolve_token(CodeHostKind::GitHub, &host)?;
+            Ok(Arc::new(GitHubActionsClient::new(token, api_url, repo)))
+        }
+        CiProviderKind::Buildkite => {
+            let buildkite = settings.buildkite.as_ref().ok_or_else(|| {
+                GitHubError::InvalidInput(
+                    "set ci.buildkite.organization and ci.buildkite.pipeline".to_string(),
+                )
+            })?;
+            let token = BUILDKITE_TOKEN_VARS
+                .iter()
+                .filter_map(|name| std::env::var(name).ok())
+                .map(|token| token.trim().to_string())
+                .find(|token| !token.is_empty())
+                .ok_or(GitHubError::MissingToken {
+                    host: "Buildkite",
+                    hint: "set BUILDKITE_API_TOKEN",
+                })?;
+            Ok(Arc::new(BuildkiteClient::new(token, buildkite)))
+        }
+    }
+}
+
+/// Percent-encode a query parameter value
+fn encode_query(value: &str) -> String {
+    value
+        .bytes()
+        .map(|b| match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' | b'/' => {
+                (b as char).to_string()
+            }
+            b => format!("%{:02X}", b),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uira_core::schema::BuildkiteSettings;
+
+    #[test]
+    fn buildkite_requires_pipeline_settings() {
+        let settings = CiSettings {
+            provider: Some(CiProviderKind::Buildkite),
+            ..CiSettings::default()
+        };
+        let err = connect_ci(&settings, &CodeHostSettings::default(), None).unwrap_err();
+        assert!(matches!(err, GitHubError::InvalidInput(_)));
+
+        let settings = CiSettings {
+            buildkite: Some(BuildkiteSettings {
+                organization: "acme".to_string(),
+                pipeline: "web".to_string(),
+                api_url: None,
+            }),
+            ..CiSettings::default()
+        };
+        assert_eq!(settings.effective_provider(), CiProviderKind::Buildkite);
+    }
+
+    #[test]
+    fn encodes_branch_names() {
+        assert_eq!(encode_query("feat/ci-logs"), "feat/ci-logs");
+        assert_eq!(encode_query("fix#1 & more"), "fix%231%20%26%20more");
+    }
+
+    #[test]
+    fn github_actions_requires_remote() {
+        let err =
+            connect_ci(&CiSettings::default(), &CodeHostSettings::default(), None).unwrap_err();
+        assert!(matches!(err, GitHubError::Repo(_)));
+    }
+}
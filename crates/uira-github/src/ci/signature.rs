@@ -0,0 +1,223 @@
+//! Failure signatures: the error line, source locations and surrounding log
+//! lines that explain why a CI job failed
+
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+/// Error lines whose surroundings are kept in the excerpt
+const MAX_ERROR_SITES: usize = 5;
+/// Lines kept before and after each error line
+const CONTEXT_BEFORE: usize = 3;
+const CONTEXT_AFTER: usize = 10;
+const MAX_EXCERPT_LINES: usize = 80;
+/// Lines kept from the end of a log without recognizable errors
+const TAIL_LINES: usize = 40;
+const MAX_LOCATIONS: usize = 10;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FailureSignature {
+    /// The most specific error line
+    pub summary: String,
+    /// `path:line` locations mentioned near the errors
+    pub locations: Vec<String>,
+    /// Log lines around the errors, or the end of the log
+    pub excerpt: String,
+}
+
+fn noise_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        // ANSI escapes, Buildkite timestamps, GitHub Actions line timestamps
+        Regex::new(r"\x1b\[[0-9;?]*[A-Za-z]|_bk;t=\d+\x07|^\d{4}-\d\d-\d\dT[\d:.]+Z ?")
+            .expect("valid regex")
+    })
+}
+
+fn error_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(
+            r"(?x)
+            ^\#\#\[error\]
+            | ^(?i:error)(\[\w+\])?:
+            | ^(?i:fatal):
+            | ^FAIL(ED)?\b
+            | panicked\ at
+            | \btest\ result:\ FAILED
+            | ^npm\ ERR!
+            | ^Traceback\ \(most\ recent\ call\ last\)
+            | ^\w+(Error|Exception):
+            | ^E\s{2,}
+            | \b\d+\ (failed|failing)\b
+            ",
+        )
+        .expect("valid regex")
+    })
+}
+
+fn location_regex() -> &'static Regex {
+    static RE: OnceLock<Regex> = OnceLock::new();
+    RE.get_or_init(|| {
+        Regex::new(r"(?:^|[\s(\[])((?:[\w.-]+/)*[\w-]+\.[A-Za-z]\w*):(\d+)").expect("valid regex")
+    })
+}
+
+/// Lines that only report that a step failed, not why
+fn is_generic(line: &str) -> bool {
+    let line = line.to_ascii_lowercase();
+    line.contains("process completed with exit code")
+        || line.contains("exited with status")
+        || line.contains("exit status")
+        || line.starts_with("error: could not compile")
+        || line.starts_with("error: aborting due to")
+        || line.starts_with("error: test failed")
+        || line.starts_with("error: process didn't exit successfully")
+}
+
+/// Signature of a job log
+pub fn extract_signature(log: &str) -> FailureSignature {
+    let lines: Vec<String> = log
+        .lines()
+        .map(|line| {
+            // Keep the final state of lines redrawn with carriage returns
+            let line = line.rsplit('\r').next().unwrap_or_default();
+            noise_regex().replace_all(line, "").trim_end().to_string()
+        })
+        .collect();
+
+    let errors: Vec<usize> = lines
+        .iter()
+        .enumerate()
+        .filter(|(_, line)| error_regex().is_match(line.trim_start()))
+        .map(|(index, _)| index)
+        .collect();
+
+    let summary = errors
+        .iter()
+        .map(|&index| lines[index].trim())
+        .find(|line| !is_generic(line))
+        .or_else(|| errors.first().map(|&index| lines[index].trim()))
+        .or_else(|| {
+            lines
+                .iter()
+                .rev()
+                .map(|line| line.trim())
+                .find(|l| !l.is_empty())
+        })
+        .unwrap_or("(empty log)")
+        .trim_start_matches("##[error]")
+        .to_string();
+
+    let specific: Vec<usize> = errors
+        .iter()
+        .copied()
+        .filter(|&index| !is_generic(lines[index].trim()))
+        .collect();
+    let sites = if specific.is_empty() {
+        &errors
+    } else {
+        &specific
+    };
+    let excerpt_lines = if sites.is_empty() {
+        let start = lines.len().saturating_sub(TAIL_LINES);
+        lines[start..].to_vec()
+    } else {
+        excerpt(&lines, sites)
+    };
+
+    let mut locations = Vec::new();
+    for line in &excerpt_lines {
+        for capture in location_regex().captures_iter(line) {
+            let location = format!("{}:{}", &capture[1], &capture[2]);
+            let in_url = line.contains(&format!("//{}", &capture[1]))
+                || line.contains(&format!("://{}", location));
+            if !in_url && !locations.contains(&location) && locations.len() < MAX_LOCATIONS {
+                locations.push(location);
+            }
+        }
+    }
+
+    FailureSignature {
+        summary,
+        locations,
+        excerpt: excerpt_lines.join("\n"),
+    }
+}
+
+/// Lines around the first error sites, with `…` between separate windows
+fn excerpt(lines: &[String], sites: &[usize]) -> Vec<String> {
+    let mut windows: Vec<(usize, usize)> = Vec::new();
+    for &site in sites.iter().take(MAX_ERROR_SITES) {
+        let start = site.saturating_sub(CONTEXT_BEFORE);
+        let end = (site + CONTEXT_AFTER + 1).min(lines.len());
+        match windows.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = (*last_end).max(end),
+            _ => windows.push((start, end)),
+        }
+    }
+
+    let mut out = Vec::new();
+    for (start, end) in windows {
+        if !out.is_empty() {
+            out.push("…".to_string());
+        }
+        out.extend(lines[start..end].iter().cloned());
+        if out.len() >= MAX_EXCERPT_LINES {
+            out.truncate(MAX_EXCERPT_LINES);
+            break;
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_rust_compiler_errors() {
+        let log = "\
+2026-01-05T10:00:00.0000000Z    Compiling app v0.1.0
+2026-01-05T10:00:01.0000000Z \x1b[31merror[E0308]\x1b[0m: mismatched types
+2026-01-05T10:00:01.0000000Z   --> src/main.rs:10:5
+2026-01-05T10:00:01.0000000Z    |
+2026-01-05T10:00:01.0000000Z error: could not compile `app` (bin \"app\") due to 1 previous error
+2026-01-05T10:00:02.0000000Z ##[error]Process completed with exit code 101.";
+        let signature = extract_signature(log);
+        assert_eq!(signature.summary, "error[E0308]: mismatched types");
+        assert_eq!(signature.locations, vec!["src/main.rs:10"]);
+        assert!(signature.excerpt.starts_with("   Compiling app"));
+        assert!(!signature.excerpt.contains("2026-01-05"));
+    }
+
+    #[test]
+    fn finds_test_panics() {
+        let log = "running 2 tests\n\
+                   test parses ... ok\n\
+                   thread 'tests::parses_empty' panicked at crates/app/src/lib.rs:42:9:\n\
+                   assertion failed: result.is_ok()\n\
+                   test result: FAILED. 1 passed; 1 failed";
+        let signature = extract_signature(log);
+        assert!(signature.summary.contains("panicked at"));
+        assert_eq!(signature.locations, vec!["crates/app/src/lib.rs:42"]);
+    }
+
+    #[test]
+    fn falls_back_to_generic_lines_and_tail() {
+        let signature = extract_signature("step 1\n##[error]Process completed with exit code 1.");
+        assert_eq!(signature.summary, "Process completed with exit code 1.");
+
+        let signature = extract_signature("building\nsegmentation fault\n");
+        assert_eq!(signature.summary, "segmentation fault");
+        assert_eq!(signature.excerpt, "building\nsegmentation fault");
+    }
+
+    #[test]
+    fn skips_urls_when_collecting_locations() {
+        let log =
+            "error: fetch failed for https://registry.example.com:443/pkg\n  at src/fetch.ts:12:3";
+        let signature = extract_signature(log);
+        assert_eq!(signature.locations, vec!["src/fetch.ts:12"]);
+    }
+}
@@ -0,0 +1,267 @@
+//! GitHub REST client
+
+use async_trait::async_trait;
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use uira_core::schema::CodeHostKind;
+
+use crate::error::{GitHubError, Result};
+use crate::host::CodeHost;
+use crate::repo::RepoRef;
+use crate::types::{
+    Issue, IssueComment, NewPullRequest, NewReview, PullRequest, Review, ReviewComment,
+};
+
+pub const DEFAULT_API_URL: &str = "https://api.github.com";
+
+const API_VERSION: &str = "2022-11-28";
+/// Comment listings read a single page of this size
+pub(crate) const PAGE_SIZE: u32 = 100;
+
+#[derive(Clone)]
+pub struct GitHubClient {
+    http: reqwest::Client,
+    api_url: String,
+    token: String,
+}
+
+impl std::fmt::Debug for GitHubClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitHubClient")
+            .field("api_url", &self.api_url)
+            .finish_non_exhaustive()
+    }
+}
+
+impl GitHubClient {
+    pub fn new(token: impl Into<String>, api_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+        }
+    }
+
+    pub(crate) async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        send("GitHub", self.request(Method::GET, path)).await
+    }
+
+    pub(crate) async fn get_text(&self, path: &str) -> Result<String> {
+        send_text("GitHub", self.request(Method::GET, path)).await
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B) -> Result<T> {
+        send("GitHub", self.request(Method::POST, path).json(body)).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.api_url, path))
+            .bearer_auth(&self.token)
+            .header("Accept", "application/vnd.github+json")
+            .header("X-GitHub-Api-Version", API_VERSION)
+            .header("User-Agent", "uira")
+    }
+}
+
+#[async_trait]
+impl CodeHost for GitHubClient {
+    fn kind(&self) -> CodeHostKind {
+        CodeHostKind::GitHub
+    }
+
+    async fn issue(&self, repo: &RepoRef, number: u64) -> Result<Issue> {
+        self.get(&format!("/repos/{}/issues/{}", repo, number))
+            .await
+    }
+
+    async fn issue_comments(&self, repo: &RepoRef, number: u64) -> Result<Vec<IssueComment>> {
+        self.get(&format!(
+            "/repos/{}/issues/{}/comments?per_page={}",
+            repo, number, PAGE_SIZE
+        ))
+        .await
+    }
+
+    async fn pull_request_comments(
+        &self,
+        repo: &RepoRef,
+        number: u64,
+    ) -> Result<Vec<IssueComment>> {
+        // Pull request conversations are issue comments on GitHub
+        self.issue_comments(repo, number).await
+    }
+
+    async fn review_comments(&self, repo: &RepoRef, number: u64) -> Result<Vec<ReviewComment>> {
+        self.get(&format!(
+            "/repos/{}/pulls/{}/comments?per_page={}",
+            repo, number, PAGE_SIZE
+        ))
+        .await
+    }
+
+    async fn find_pull_request(&self, repo: &RepoRef, branch: &str) -> Result<Option<PullRequest>> {
+        let open: Vec<PullRequest> = self
+            .get(&format!(
+                "/repos/{}/pulls?state=open&head={}:{}",
+                repo, repo.owner, branch
+            ))
+            .await?;
+        Ok(open.into_iter().next())
+    }
+
+    async fn create_pull_request(
+        &self,
+        repo: &RepoRef,
+        pull_request: &NewPullRequest,
+    ) -> Result<PullRequest> {
+        self.post(&format!("/repos/{}/pulls", repo), pull_request)
+            .await
+    }
+
+    async fn create_review(
+        &self,
+        repo: &RepoRef,
+        number: u64,
+        review: &NewReview,
+    ) -> Result<Review> {
+        self.post(&format!("/repos/{}/pulls/{}/reviews", repo, number), review)
+            .await
+    }
+}
+
+/// Send a request, turning error statuses into [`GitHubError::Api`] with
+/// the message from the response body
+pub(crate) async fn send<T: DeserializeOwned>(
+    host: &'static str,
+    request: RequestBuilder,
+) -> Result<T> {
+    let response = request.send().await?;
+    if response.status().is_success() {
+        return Ok(response.json().await?);
+    }
+    Err(api_error(host, response).await)
+}
+
+/// Like [`send`], for endpoints answering with plain text
+pub(crate) async fn send_text(host: &'static str, request: RequestBuilder) -> Result<String> {
+    let response = request.send().await?;
+    if response.status().is_success() {
+        return Ok(response.text().await?);
+    }
+    Err(api_error(host, response).await)
+}
+
+async fn api_error(host: &'static str, response: reqwest::Response) -> GitHubError {
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    let message = serde_json::from_str::<serde_json::Value>(&text)
+        .ok()
+        .and_then(|body| {
+            body["message"]
+                .as_str()
+                .or_else(|| body["error"].as_str())
+                .map(str::to_string)
+        })
+        .unwrap_or(text);
+    GitHubError::Api {
+        host,
+        status: status.as_u16(),
+        message,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{NewReviewComment, ReviewEvent};
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn repo() -> RepoRef {
+        RepoRef::parse("junhoyeo/uira").unwrap()
+    }
+
+    #[tokio::test]
+    async fn fetches_issues_with_auth_headers() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/junhoyeo/uira/issues/7"))
+            .and(header("authorization", "Bearer ghp_test"))
+            .and(header("x-github-api-version", API_VERSION))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "number": 7,
+                "title": "Crash on resume",
+                "body": "Steps to reproduce",
+                "state": "open",
+                "user": {"login": "octocat"},
+                "labels": [{"name": "bug"}],
+                "html_url": "https://github.com/junhoyeo/uira/issues/7",
+                "comments": 2
+            })))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new("ghp_test", server.uri());
+        let issue = client.issue(&repo(), 7).await.unwrap();
+        assert_eq!(issue.title, "Crash on resume");
+        assert_eq!(issue.labels[0].name, "bug");
+        assert!(issue.pull_request.is_none());
+    }
+
+    #[tokio::test]
+    async fn posts_reviews_and_surfaces_api_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/junhoyeo/uira/pulls/3/reviews"))
+            .and(body_partial_json(json!({
+                "event": "REQUEST_CHANGES",
+                "comments": [{"path": "src/lib.rs", "line": 4, "body": "Unwrap panics here"}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 11,
+                "state": "CHANGES_REQUESTED",
+                "html_url": "https://github.com/junhoyeo/uira/pull/3#pullrequestreview-11"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/junhoyeo/uira/pulls"))
+            .and(query_param("head", "junhoyeo:feature"))
+            .respond_with(
+                ResponseTemplate::new(403).set_body_json(json!({"message": "Bad credentials"})),
+            )
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new("ghp_test", server.uri());
+        let review = client
+            .create_review(
+                &repo(),
+                3,
+                &NewReview {
+                    body: None,
+                    event: ReviewEvent::RequestChanges,
+                    comments: vec![NewReviewComment {
+                        path: "src/lib.rs".to_string(),
+                        line: 4,
+                        body: "Unwrap panics here".to_string(),
+                    }],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(review.id, 11);
+
+        let err = client
+            .find_pull_request(&repo(), "feature")
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            GitHubError::Api { status: 403, ref message, .. } if message == "Bad credentials"
+        ));
+    }
+}
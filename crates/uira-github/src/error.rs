@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum GitHubError {
+    #[error("no {host} token found; {hint}")]
+    MissingToken {
+        host: &'static str,
+        hint: &'static str,
+    },
+
+    #[error("could not determine the repository: {0}")]
+    Repo(String),
+
+    #[error("{host} API returned {status}: {message}")]
+    Api {
+        host: &'static str,
+        status: u16,
+        message: String,
+    },
+
+    #[error("code host request failed: {0}")]
+    Http(#[from] reqwest::Error),
+
+    #[error("`git {command}` failed: {message}")]
+    Git { command: String, message: String },
+
+    #[error("invalid input: {0}")]
+    InvalidInput(String),
+}
+
+pub type Result<T> = std::result::Result<T, GitHubError>;
@@ -0,0 +1,327 @@
+//! Gitea and Forgejo REST client
+
+use async_trait::async_trait;
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use uira_core::schema::CodeHostKind;
+
+use crate::client::{send, PAGE_SIZE};
+use crate::error::Result;
+use crate::host::CodeHost;
+use crate::repo::RepoRef;
+use crate::types::{
+    Issue, IssueComment, NewPullRequest, NewReview, PullRequest, Review, ReviewComment,
+    ReviewEvent, User,
+};
+
+/// Open pull requests searched for a branch's existing pull request
+const OPEN_PULLS_LIMIT: u32 = 50;
+
+#[derive(Clone)]
+pub struct GiteaClient {
+    http: reqwest::Client,
+    api_url: String,
+    token: String,
+}
+
+impl std::fmt::Debug for GiteaClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GiteaClient")
+            .field("api_url", &self.api_url)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GtReview {
+    id: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GtReviewComment {
+    id: u64,
+    user: User,
+    body: String,
+    path: String,
+    /// Line in the new file, 0 for comments on removed lines
+    #[serde(default)]
+    position: u64,
+    created_at: String,
+    html_url: String,
+}
+
+#[derive(Debug, Serialize)]
+struct GtNewPullRequest<'a> {
+    title: String,
+    head: &'a str,
+    base: &'a str,
+    body: &'a str,
+}
+
+#[derive(Debug, Serialize)]
+struct GtNewReviewComment<'a> {
+    path: &'a str,
+    body: &'a str,
+    new_position: u64,
+}
+
+#[derive(Debug, Serialize)]
+struct GtNewReview<'a> {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    body: Option<&'a str>,
+    event: &'static str,
+    comments: Vec<GtNewReviewComment<'a>>,
+}
+
+impl GiteaClient {
+    pub fn new(token: impl Into<String>, api_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+        }
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        send("Gitea", self.request(Method::GET, path)).await
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B) -> Result<T> {
+        send("Gitea", self.request(Method::POST, path).json(body)).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.api_url, path))
+            .header("Authorization", format!("token {}", self.token))
+            .header("User-Agent", "uira")
+    }
+}
+
+#[async_trait]
+impl CodeHost for GiteaClient {
+    fn kind(&self) -> CodeHostKind {
+        CodeHostKind::Gitea
+    }
+
+    async fn issue(&self, repo: &RepoRef, number: u64) -> Result<Issue> {
+        self.get(&format!("/repos/{}/issues/{}", repo, number))
+            .await
+    }
+
+    async fn issue_comments(&self, repo: &RepoRef, number: u64) -> Result<Vec<IssueComment>> {
+        self.get(&format!(
+            "/repos/{}/issues/{}/comments?limit={}",
+            repo, number, PAGE_SIZE
+        ))
+        .await
+    }
+
+    async fn pull_request_comments(
+        &self,
+        repo: &RepoRef,
+        number: u64,
+    ) -> Result<Vec<IssueComment>> {
+        self.issue_comments(repo, number).await
+    }
+
+    async fn review_comments(&self, repo: &RepoRef, number: u64) -> Result<Vec<ReviewComment>> {
+        let reviews: Vec<GtReview> = self
+            .get(&format!("/repos/{}/pulls/{}/reviews", repo, number))
+            .await?;
+        let mut comments = Vec::new();
+        for review in reviews {
+            let batch: Vec<GtReviewComment> = self
+                .get(&format!(
+                    "/repos/{}/pulls/{}/reviews/{}/comments",
+                    repo, number, review.id
+                ))
+                .await?;
+            comments.extend(batch.into_iter().map(|comment| ReviewComment {
+                id: comment.id,
+                user: comment.user,
+                body: comment.body,
+                path: comment.path,
+                line: (comment.position > 0).then_some(comment.position),
+                in_reply_to_id: None,
+                created_at: comment.created_at,
+                html_url: comment.html_url,
+            }));
+        }
+        Ok(comments)
+    }
+
+    async fn find_pull_request(&self, repo: &RepoRef, branch: &str) -> Result<Option<PullRequest>> {
+        let open: Vec<PullRequest> = self
+            .get(&format!(
+                "/repos/{}/pulls?state=open&limit={}",
+                repo, OPEN_PULLS_LIMIT
+            ))
+            .await?;
+        Ok(open.into_iter().find(|pull| pull.head.name == branch))
+    }
+
+    async fn create_pull_request(
+        &self,
+        repo: &RepoRef,
+        pull_request: &NewPullRequest,
+    ) -> Result<PullRequest> {
+        // Gitea marks drafts by title prefix
+        let title = if pull_request.draft {
+            format!("WIP: {}", pull_request.title)
+        } else {
+            pull_request.title.clone()
+        };
+        let mut created: PullRequest = self
+            .post(
+                &format!("/repos/{}/pulls", repo),
+                &GtNewPullRequest {
+                    title,
+                    head: &pull_request.head,
+                    base: &pull_request.base,
+                    body: &pull_request.body,
+                },
+            )
+            .await?;
+        created.draft = pull_request.draft;
+        Ok(created)
+    }
+
+    async fn create_review(
+        &self,
+        repo: &RepoRef,
+        number: u64,
+        review: &NewReview,
+    ) -> Result<Review> {
+        let event = match review.event {
+            ReviewEvent::Comment => "COMMENT",
+            ReviewEvent::Approve => "APPROVED",
+            ReviewEvent::RequestChanges => "REQUEST_CHANGES",
+        };
+        self.post(
+            &format!("/repos/{}/pulls/{}/reviews", repo, number),
+            &GtNewReview {
+                body: review.body.as_deref(),
+                event,
+                comments: review
+                    .comments
+                    .iter()
+                    .map(|comment| GtNewReviewComment {
+                        path: &comment.path,
+                        body: &comment.body,
+                        new_position: comment.line,
+                    })
+                    .collect(),
+            },
+        )
+        .await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::NewReviewComment;
+    use serde_json::json;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn repo() -> RepoRef {
+        RepoRef::parse("team/app").unwrap()
+    }
+
+    fn pull(number: u64, branch: &str) -> serde_json::Value {
+        json!({
+            "number": number,
+            "title": "Change",
+            "body": "",
+            "state": "open",
+            "html_url": format!("https://codeberg.org/team/app/pulls/{}", number),
+            "head": {"ref": branch},
+            "base": {"ref": "main"}
+        })
+    }
+
+    #[tokio::test]
+    async fn finds_pull_requests_by_head_branch() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/team/app/pulls"))
+            .and(header("authorization", "token gitea_test"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(json!([pull(1, "other"), pull(2, "feature")])),
+            )
+            .mount(&server)
+            .await;
+
+        let client = GiteaClient::new("gitea_test", server.uri());
+        let found = client.find_pull_request(&repo(), "feature").await.unwrap();
+        assert_eq!(found.unwrap().number, 2);
+        assert!(client
+            .find_pull_request(&repo(), "missing")
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn posts_reviews_with_gitea_events() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/repos/team/app/pulls/2/reviews"))
+            .and(body_partial_json(json!({
+                "event": "REQUEST_CHANGES",
+                "comments": [{"path": "src/main.rs", "body": "Handle the error", "new_position": 3}]
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "id": 8,
+                "state": "REQUEST_CHANGES",
+                "html_url": "https://codeberg.org/team/app/pulls/2#issuecomment-8"
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/team/app/pulls/2/reviews"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{"id": 8}])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/team/app/pulls/2/reviews/8/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "id": 21,
+                "user": {"login": "lead"},
+                "body": "Handle the error",
+                "path": "src/main.rs",
+                "position": 3,
+                "created_at": "2026-01-02T00:00:00Z",
+                "html_url": "https://codeberg.org/team/app/pulls/2/files#issuecomment-21"
+            }])))
+            .mount(&server)
+            .await;
+
+        let client = GiteaClient::new("gitea_test", server.uri());
+        let review = client
+            .create_review(
+                &repo(),
+                2,
+                &NewReview {
+                    body: None,
+                    event: ReviewEvent::RequestChanges,
+                    comments: vec![NewReviewComment {
+                        path: "src/main.rs".to_string(),
+                        line: 3,
+                        body: "Handle the error".to_string(),
+                    }],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(review.id, 8);
+
+        let comments = client.review_comments(&repo(), 2).await.unwrap();
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].line, Some(3));
+    }
+}
@@ -0,0 +1,558 @@
+//! GitLab REST client, exposing merge requests as pull requests
+
+use async_trait::async_trait;
+use reqwest::{Method, RequestBuilder};
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uira_core::schema::CodeHostKind;
+
+use crate::client::{send, PAGE_SIZE};
+use crate::error::{GitHubError, Result};
+use crate::host::CodeHost;
+use crate::repo::RepoRef;
+use crate::types::{
+    BranchRef, Issue, IssueComment, Label, NewPullRequest, NewReview, PullRequest, Review,
+    ReviewComment, ReviewEvent, User,
+};
+
+#[derive(Clone)]
+pub struct GitLabClient {
+    http: reqwest::Client,
+    api_url: String,
+    token: String,
+}
+
+impl std::fmt::Debug for GitLabClient {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("GitLabClient")
+            .field("api_url", &self.api_url)
+            .finish_non_exhaustive()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct GlUser {
+    username: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlIssue {
+    iid: u64,
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    state: String,
+    author: GlUser,
+    #[serde(default)]
+    labels: Vec<String>,
+    web_url: String,
+    #[serde(default)]
+    user_notes_count: u64,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlDiffRefs {
+    base_sha: String,
+    start_sha: String,
+    head_sha: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlMergeRequest {
+    #[serde(flatten)]
+    issue: GlIssue,
+    #[serde(default)]
+    draft: bool,
+    source_branch: String,
+    target_branch: String,
+    #[serde(default)]
+    diff_refs: Option<GlDiffRefs>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlPosition {
+    new_path: Option<String>,
+    old_path: Option<String>,
+    new_line: Option<u64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct GlNote {
+    id: u64,
+    body: String,
+    author: GlUser,
+    created_at: String,
+    #[serde(default)]
+    system: bool,
+    #[serde(default)]
+    position: Option<GlPosition>,
+}
+
+#[derive(Debug, Serialize)]
+struct GlNewMergeRequest<'a> {
+    source_branch: &'a str,
+    target_branch: &'a str,
+    title: String,
+    description: &'a str,
+}
+
+/// GitLab reports `opened` where GitHub and Gitea say `open`
+fn state(state: String) -> String {
+    if state == "opened" {
+        "open".to_string()
+    } else {
+        state
+    }
+}
+
+impl From<GlIssue> for Issue {
+    fn from(issue: GlIssue) -> Self {
+        Self {
+            number: issue.iid,
+            title: issue.title,
+            body: issue.description,
+            state: state(issue.state),
+            user: User {
+                login: issue.author.username,
+            },
+            labels: issue
+                .labels
+                .into_iter()
+                .map(|name| Label { name })
+                .collect(),
+            html_url: issue.web_url,
+            comments: issue.user_notes_count,
+            pull_request: None,
+        }
+    }
+}
+
+impl From<GlMergeRequest> for PullRequest {
+    fn from(mr: GlMergeRequest) -> Self {
+        Self {
+            number: mr.issue.iid,
+            title: mr.issue.title,
+            body: mr.issue.description,
+            state: state(mr.issue.state),
+            html_url: mr.issue.web_url,
+            draft: mr.draft,
+            head: BranchRef {
+                name: mr.source_branch,
+            },
+            base: BranchRef {
+                name: mr.target_branch,
+            },
+        }
+    }
+}
+
+impl GitLabClient {
+    pub fn new(token: impl Into<String>, api_url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            api_url: api_url.into().trim_end_matches('/').to_string(),
+            token: token.into(),
+        }
+    }
+
+    /// `/projects/:id` path, with the project's full path as its id
+    fn project(repo: &RepoRef) -> String {
+        format!("/projects/{}", repo.to_string().replace('/', "%2F"))
+    }
+
+    async fn merge_request(&self, repo: &RepoRef, number: u64) -> Result<GlMergeRequest> {
+        self.get(&format!(
+            "{}/merge_requests/{}",
+            Self::project(repo),
+            number
+        ))
+        .await
+    }
+
+    /// User notes in creation order, linked from `web_url`
+    async fn notes(&self, path: &str, web_url: &str) -> Result<Vec<(GlNote, String)>> {
+        let notes: Vec<GlNote> = self
+            .get(&format!(
+                "{}/notes?sort=asc&order_by=created_at&per_page={}",
+                path, PAGE_SIZE
+            ))
+            .await?;
+        Ok(notes
+            .into_iter()
+            .filter(|note| !note.system)
+            .map(|note| {
+                let url = format!("{}#note_{}", web_url, note.id);
+                (note, url)
+            })
+            .collect())
+    }
+
+    async fn get<T: DeserializeOwned>(&self, path: &str) -> Result<T> {
+        send("GitLab", self.request(Method::GET, path)).await
+    }
+
+    async fn post<B: Serialize, T: DeserializeOwned>(&self, path: &str, body: &B) -> Result<T> {
+        send("GitLab", self.request(Method::POST, path).json(body)).await
+    }
+
+    fn request(&self, method: Method, path: &str) -> RequestBuilder {
+        self.http
+            .request(method, format!("{}{}", self.api_url, path))
+            .bearer_auth(&self.token)
+            .header("User-Agent", "uira")
+    }
+}
+
+fn comment((note, html_url): (GlNote, String)) -> IssueComment {
+    IssueComment {
+        id: note.id,
+        user: User {
+            login: note.author.username,
+        },
+        body: Some(note.body),
+        created_at: note.created_at,
+        html_url,
+    }
+}
+
+#[async_trait]
+impl CodeHost for GitLabClient {
+    fn kind(&self) -> CodeHostKind {
+        CodeHostKind::GitLab
+    }
+
+    async fn issue(&self, repo: &RepoRef, number: u64) -> Result<Issue> {
+        let path = format!("{}/issues/{}", Self::project(repo), number);
+        match self.get::<GlIssue>(&path).await {
+            Ok(issue) => Ok(issue.into()),
+            Err(GitHubError::Api { status: 404, .. }) => {
+                let mr = self.merge_request(repo, number).await?;
+                let mut issue = Issue::from(mr.issue);
+                issue.pull_request = Some(json!({ "draft": mr.draft }));
+                Ok(issue)
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    async fn issue_comments(&self, repo: &RepoRef, number: u64) -> Result<Vec<IssueComment>> {
+        let path = format!("{}/issues/{}", Self::project(repo), number);
+        let issue: GlIssue = self.get(&path).await?;
+        let notes = self.notes(&path, &issue.web_url).await?;
+        Ok(notes.into_iter().map(comment).collect())
+    }
+
+    async fn pull_request_comments(
+        &self,
+        repo: &RepoRef,
+        number: u64,
+    ) -> Result<Vec<IssueComment>> {
+        let path = format!("{}/merge_requests/{}", Self::project(repo), number);
+        let mr = self.merge_request(repo, number).await?;
+        let notes = self.notes(&path, &mr.issue.web_url).await?;
+        Ok(notes
+            .into_iter()
+            .filter(|(note, _)| note.position.is_none())
+            .map(comment)
+            .collect())
+    }
+
+    async fn review_comments(&self, repo: &RepoRef, number: u64) -> Result<Vec<ReviewComment>> {
+        let path = format!("{}/merge_requests/{}", Self::project(repo), number);
+        let mr = self.merge_request(repo, number).await?;
+        let notes = self.notes(&path, &mr.issue.web_url).await?;
+        Ok(notes
+            .into_iter()
+            .filter_map(|(note, html_url)| {
+                let position = note.position?;
+                Some(ReviewComment {
+                    id: note.id,
+                    user: User {
+                        login: note.author.username,
+                    },
+                    body: note.body,
+                    path: position.new_path.or(position.old_path).unwrap_or_default(),
+                    line: position.new_line,
+                    in_reply_to_id: None,
+                    created_at: note.created_at,
+                    html_url,
+                })
+            })
+            .collect())
+    }
+
+    async fn find_pull_request(&self, repo: &RepoRef, branch: &str) -> Result<Option<PullRequest>> {
+        let open: Vec<GlMergeRequest> = self
+            .get(&format!(
+                "{}/merge_requests?state=opened&source_branch={}",
+                Self::project(repo),
+                branch
+            ))
+            .await?;
+        Ok(open.into_iter().next().map(PullRequest::from))
+    }
+
+    async fn create_pull_request(
+        &self,
+        repo: &RepoRef,
+        pull_request: &NewPullRequest,
+    ) -> Result<PullRequest> {
+        let title = if pull_request.draft {
+            format!("Draft: {}", pull_request.title)
+        } else {
+            pull_request.title.clone()
+        };
+        let mr: GlMergeRequest = self
+            .post(
+                &format!("{}/merge_requests", Self::project(repo)),
+                &GlNewMergeRequest {
+                    source_branch: &pull_request.head,
+                    target_branch: &pull_request.base,
+                    title,
+                    description: &pull_request.body,
+                },
+            )
+            .await?;
+        Ok(mr.into())
+    }
+
+    /// GitLab has no review object: line comments become diff discussions,
+    /// the body a note, and approval a merge request approval
+    async fn create_review(
+        &self,
+        repo: &RepoRef,
+        number: u64,
+        review: &NewReview,
+    ) -> Result<Review> {
+        if review.event == ReviewEvent::RequestChanges {
+            return Err(GitHubError::InvalidInput(
+                "GitLab merge requests cannot request changes; comment instead".to_string(),
+            ));
+        }
+        let path = format!("{}/merge_requests/{}", Self::project(repo), number);
+        let mr = self.merge_request(repo, number).await?;
+
+        if !review.comments.is_empty() {
+            let refs = mr.diff_refs.as_ref().ok_or_else(|| {
+                GitHubError::InvalidInput(format!("!{} has no diff to comment on", number))
+            })?;
+            for comment in &review.comments {
+                let _: serde_json::Value = self
+                    .post(
+                        &format!("{}/discussions", path),
+                        &json!({
+                            "body": comment.body,
+                            "position": {
+                                "position_type": "text",
+                                "base_sha": refs.base_sha,
+                                "start_sha": refs.start_sha,
+                                "head_sha": refs.head_sha,
+                                "old_path": comment.path,
+                                "new_path": comment.path,
+                                "new_line": comment.line,
+                            },
+                        }),
+                    )
+                    .await?;
+            }
+        }
+
+        let mut id = 0;
+        let mut html_url = mr.issue.web_url.clone();
+        if let Some(body) = &review.body {
+            let note: GlNote = self
+                .post(&format!("{}/notes", path), &json!({ "body": body }))
+                .await?;
+            id = note.id;
+            html_url = format!("{}#note_{}", mr.issue.web_url, note.id);
+        }
+
+        let state = if review.event == ReviewEvent::Approve {
+            let _: serde_json::Value = self.post(&format!("{}/approve", path), &json!({})).await?;
+            "APPROVED"
+        } else {
+            "COMMENTED"
+        };
+        Ok(Review {
+            id,
+            state: state.to_string(),
+            html_url,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::NewReviewComment;
+    use wiremock::matchers::{body_partial_json, header, method, path, query_param};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const PROJECT: &str = "/projects/group%2Fsub%2Fapp";
+
+    fn repo() -> RepoRef {
+        RepoRef::parse("group/sub/app").unwrap()
+    }
+
+    fn merge_request() -> serde_json::Value {
+        json!({
+            "iid": 4,
+            "title": "Add caching",
+            "description": "Caches lookups",
+            "state": "opened",
+            "author": {"username": "dev"},
+            "labels": ["perf"],
+            "web_url": "https://gitlab.com/group/sub/app/-/merge_requests/4",
+            "user_notes_count": 2,
+            "draft": false,
+            "source_branch": "cache",
+            "target_branch": "main",
+            "diff_refs": {"base_sha": "b", "start_sha": "s", "head_sha": "h"}
+        })
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_merge_requests_and_splits_notes() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/issues/4", PROJECT)))
+            .respond_with(
+                ResponseTemplate::new(404).set_body_json(json!({"message": "404 Not found"})),
+            )
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/merge_requests/4", PROJECT)))
+            .and(header("authorization", "Bearer glpat"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(merge_request()))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/merge_requests/4/notes", PROJECT)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([
+                {"id": 1, "body": "added 1 commit", "author": {"username": "dev"},
+                 "created_at": "2026-01-01T00:00:00Z", "system": true},
+                {"id": 2, "body": "Looks good", "author": {"username": "lead"},
+                 "created_at": "2026-01-02T00:00:00Z", "system": false},
+                {"id": 3, "body": "Off by one", "author": {"username": "lead"},
+                 "created_at": "2026-01-02T00:00:00Z", "system": false,
+                 "position": {"new_path": "src/cache.rs", "old_path": "src/cache.rs", "new_line": 9}}
+            ])))
+            .mount(&server)
+            .await;
+
+        let client = GitLabClient::new("glpat", server.uri());
+        let issue = client.issue(&repo(), 4).await.unwrap();
+        assert!(issue.pull_request.is_some());
+        assert_eq!(issue.state, "open");
+        assert_eq!(issue.labels[0].name, "perf");
+
+        let conversation = client.pull_request_comments(&repo(), 4).await.unwrap();
+        assert_eq!(conversation.len(), 1);
+        assert_eq!(conversation[0].user.login, "lead");
+        assert!(conversation[0]
+            .html_url
+            .ends_with("/merge_requests/4#note_2"));
+
+        let review = client.review_comments(&repo(), 4).await.unwrap();
+        assert_eq!(review.len(), 1);
+        assert_eq!(review[0].path, "src/cache.rs");
+        assert_eq!(review[0].line, Some(9));
+    }
+
+    #[tokio::test]
+    async fn creates_merge_requests_and_reviews() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/merge_requests", PROJECT)))
+            .and(query_param("source_branch", "cache"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([])))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/merge_requests", PROJECT)))
+            .and(body_partial_json(json!({
+                "source_branch": "cache",
+                "target_branch": "main",
+                "title": "Draft: Add caching"
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(merge_request()))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path(format!("{}/merge_requests/4", PROJECT)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(merge_request()))
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/merge_requests/4/discussions", PROJECT)))
+            .and(body_partial_json(json!({
+                "position": {"head_sha": "h", "new_path": "src/cache.rs", "new_line": 9}
+            })))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({"id": "d1"})))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path(format!("{}/merge_requests/4/approve", PROJECT)))
+            .respond_with(ResponseTemplate::new(201).set_body_json(json!({})))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = GitLabClient::new("glpat", server.uri());
+        assert!(client
+            .find_pull_request(&repo(), "cache")
+            .await
+            .unwrap()
+            .is_none());
+        let created = client
+            .create_pull_request(
+                &repo(),
+                &NewPullRequest {
+                    title: "Add caching".to_string(),
+                    head: "cache".to_string(),
+                    base: "main".to_string(),
+                    body: "Caches lookups".to_string(),
+                    draft: true,
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(created.number, 4);
+        assert_eq!(created.head.name, "cache");
+
+        let review = client
+            .create_review(
+                &repo(),
+                4,
+                &NewReview {
+                    body: None,
+                    event: ReviewEvent::Approve,
+                    comments: vec![NewReviewComment {
+                        path: "src/cache.rs".to_string(),
+                        line: 9,
+                        body: "Off by one".to_string(),
+                    }],
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(review.state, "APPROVED");
+
+        let err = client
+            .create_review(
+                &repo(),
+                4,
+                &NewReview {
+                    body: Some("Please fix".to_string()),
+                    event: ReviewEvent::RequestChanges,
+                    comments: Vec::new(),
+                },
+            )
+            .await
+            .unwrap_err();
+        assert!(matches!(err, GitHubError::InvalidInput(_)));
+    }
+}
@@ -0,0 +1,158 @@
+//! The code host abstraction and its selection from a remote URL
+
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use uira_core::schema::{CodeHostKind, CodeHostSettings};
+
+use crate::auth::resolve_token;
+use crate::client::GitHubClient;
+use crate::error::{GitHubError, Result};
+use crate::gitea::GiteaClient;
+use crate::gitlab::GitLabClient;
+use crate::repo::{remote_host, RepoRef};
+use crate::types::{
+    Issue, IssueComment, NewPullRequest, NewReview, PullRequest, Review, ReviewComment,
+};
+
+/// Issue and pull request operations shared by GitHub, GitLab and Gitea.
+/// GitLab merge requests are exposed as pull requests.
+#[async_trait]
+pub trait CodeHost: Send + Sync + fmt::Debug {
+    fn kind(&self) -> CodeHostKind;
+
+    /// What the host calls a pull request
+    fn pull_request_noun(&self) -> &'static str {
+        match self.kind() {
+            CodeHostKind::GitLab => "merge request",
+            CodeHostKind::GitHub | CodeHostKind::Gitea => "pull request",
+        }
+    }
+
+    /// An issue, or a pull request when no issue has that number
+    async fn issue(&self, repo: &RepoRef, number: u64) -> Result<Issue>;
+
+    /// Comments on an issue
+    async fn issue_comments(&self, repo: &RepoRef, number: u64) -> Result<Vec<IssueComment>>;
+
+    /// Comments on the conversation of a pull request
+    async fn pull_request_comments(&self, repo: &RepoRef, number: u64)
+        -> Result<Vec<IssueComment>>;
+
+    /// Comments on lines of a pull request diff
+    async fn review_comments(&self, repo: &RepoRef, number: u64) -> Result<Vec<ReviewComment>>;
+
+    /// Open pull request whose head is `branch` in `repo` itself
+    async fn find_pull_request(&self, repo: &RepoRef, branch: &str) -> Result<Option<PullRequest>>;
+
+    async fn create_pull_request(
+        &self,
+        repo: &RepoRef,
+        pull_request: &NewPullRequest,
+    ) -> Result<PullRequest>;
+
+    async fn create_review(
+        &self,
+        repo: &RepoRef,
+        number: u64,
+        review: &NewReview,
+    ) -> Result<Review>;
+}
+
+/// Host kind a domain most likely runs; unknown domains are assumed to be
+/// GitHub Enterprise Server unless configured otherwise
+pub fn detect_kind(host: &str) -> CodeHostKind {
+    if host.contains("gitlab") {
+        CodeHostKind::GitLab
+    } else if host.contains("gitea") || host.contains("forgejo") || host == "codeberg.org" {
+        CodeHostKind::Gitea
+    } else {
+        CodeHostKind::GitHub
+    }
+}
+
+/// REST API root of a `kind` host
+pub fn default_api_url(kind: CodeHostKind, host: &str) -> String {
+    match kind {
+        CodeHostKind::GitHub if host == "github.com" => "https://api.github.com".to_string(),
+        CodeHostKind::GitHub => format!("https://{}/api/v3", host),
+        CodeHostKind::GitLab => format!("https://{}/api/v4", host),
+        CodeHostKind::Gitea => format!("https://{}/api/v1", host),
+    }
+}
+
+/// Client for the host of `remote_url` (usually `origin`), with the kind
+/// and API root taken from `settings` when set
+pub fn connect(settings: &CodeHostSettings, remote_url: Option<&str>) -> Result<Arc<dyn CodeHost>> {
+    let host = remote_url
+        .and_then(remote_host)
+        .or_else(|| settings.api_url.as_deref().and_then(api_host));
+    let Some(host) = host else {
+        return Err(GitHubError::Repo(
+            "no remote to detect the code host from; set code_host.api_url".to_string(),
+        ));
+    };
+    let kind = settings.kind.unwrap_or_else(|| detect_kind(&host));
+    let api_url = settings
+        .api_url
+        .clone()
+        .unwrap_or_else(|| default_api_url(kind, &host));
+    let token = resolve_token(kind, &host)?;
+
+    Ok(match kind {
+        CodeHostKind::GitHub => Arc::new(GitHubClient::new(token, api_url)),
+        CodeHostKind::GitLab => Arc::new(GitLabClient::new(token, api_url)),
+        CodeHostKind::Gitea => Arc::new(GiteaClient::new(token, api_url)),
+    })
+}
+
+/// Host an API root is served from, mapping `api.github.com` to the
+/// `github.com` host its tokens belong to
+fn api_host(api_url: &str) -> Option<String> {
+    let host = api_url
+        .split_once("://")
+        .map_or(api_url, |(_, rest)| rest)
+        .split(['/', ':'])
+        .next()
+        .filter(|host| !host.is_empty())?;
+    Some(match host {
+        "api.github.com" => "github.com".to_string(),
+        host => host.to_ascii_lowercase(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_kind_and_api_from_host() {
+        assert_eq!(detect_kind("github.com"), CodeHostKind::GitHub);
+        assert_eq!(detect_kind("gitlab.example.com"), CodeHostKind::GitLab);
+        assert_eq!(detect_kind("codeberg.org"), CodeHostKind::Gitea);
+        assert_eq!(detect_kind("git.example.com"), CodeHostKind::GitHub);
+
+        assert_eq!(
+            default_api_url(CodeHostKind::GitHub, "github.com"),
+            "https://api.github.com"
+        );
+        assert_eq!(
+            default_api_url(CodeHostKind::GitLab, "gitlab.com"),
+            "https://gitlab.com/api/v4"
+        );
+        assert_eq!(
+            default_api_url(CodeHostKind::Gitea, "codeberg.org"),
+            "https://codeberg.org/api/v1"
+        );
+
+        assert_eq!(
+            api_host("https://api.github.com").as_deref(),
+            Some("github.com")
+        );
+        assert_eq!(
+            api_host("https://ghe.example.com/api/v3").as_deref(),
+            Some("ghe.example.com")
+        );
+    }
+}
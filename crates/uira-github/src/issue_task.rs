@@ -0,0 +1,330 @@
+//! Issues turned into agent tasks, with goals taken from their acceptance
+//! criteria
+
+use std::fmt::Write;
+
+use crate::error::{GitHubError, Result};
+use crate::host::CodeHost;
+use crate::repo::RepoRef;
+use crate::types::IssueComment;
+
+/// An issue named by URL, `owner/name#N`, `#N` or `N`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IssueRef {
+    /// Repository, when the reference names one
+    pub repo: Option<RepoRef>,
+    pub number: u64,
+    /// Web URL the reference was given as, to pick the host from
+    pub url: Option<String>,
+}
+
+impl IssueRef {
+    pub fn parse(value: &str) -> Option<Self> {
+        let value = value.trim();
+        if value.contains("://") {
+            return Self::parse_url(value);
+        }
+        let (repo, number) = match value.rsplit_once('#') {
+            Some(("", number)) => (None, number),
+            Some((repo, number)) => (Some(RepoRef::parse(repo)?), number),
+            None => (None, value),
+        };
+        Some(Self {
+            repo,
+            number: number.parse().ok()?,
+            url: None,
+        })
+    }
+
+    /// `https://host/owner/name/issues/N`, or `.../-/issues/N` on GitLab
+    fn parse_url(url: &str) -> Option<Self> {
+        let (_, rest) = url.split_once("://")?;
+        let (_, path) = rest.split_once('/')?;
+        let path = path.split(['?', '#']).next()?.trim_end_matches('/');
+        let (repo, number) = path.rsplit_once("/issues/")?;
+        let repo = repo.strip_suffix("/-").unwrap_or(repo);
+        Some(Self {
+            repo: Some(RepoRef::parse(repo)?),
+            number: number.parse().ok()?,
+            url: Some(url.to_string()),
+        })
+    }
+
+    /// Repository of the reference, falling back to `workspace`
+    pub fn resolve_repo(&self, workspace: Option<&RepoRef>) -> Result<RepoRef> {
+        self.repo
+            .clone()
+            .or_else(|| workspace.cloned())
+            .ok_or_else(|| {
+                GitHubError::InvalidInput(format!(
+                    "#{} names no repository and the workspace has no origin remote",
+                    self.number
+                ))
+            })
+    }
+}
+
+/// An issue with its conversation, ready to plan and work on
+#[derive(Debug, Clone)]
+pub struct IssueTask {
+    pub repo: RepoRef,
+    pub number: u64,
+    pub title: String,
+    pub url: String,
+    pub body: String,
+    pub comments: Vec<IssueComment>,
+    /// Acceptance criteria, used as the goals the work is verified against
+    pub goals: Vec<String>,
+}
+
+impl IssueTask {
+    pub async fn fetch(client: &dyn CodeHost, repo: &RepoRef, number: u64) -> Result<Self> {
+        let issue = client.issue(repo, number).await?;
+        if issue.pull_request.is_some() {
+            return Err(GitHubError::InvalidInput(format!(
+                "{}#{} is a {}, not an issue",
+                repo,
+                number,
+                client.pull_request_noun()
+            )));
+        }
+        let comments = if issue.comments > 0 {
+            client.issue_comments(repo, number).await?
+        } else {
+            Vec::new()
+        };
+        let body = issue.body.unwrap_or_default().trim().to_string();
+        Ok(Self {
+            repo: repo.clone(),
+            number,
+            goals: acceptance_criteria(&body),
+            title: issue.title,
+            url: issue.html_url,
+            body,
+            comments,
+        })
+    }
+
+    /// `owner/name#N`
+    pub fn reference(&self) -> String {
+        format!("{}#{}", self.repo, self.number)
+    }
+
+    /// The request handed to the planning pipeline
+    pub fn request(&self) -> String {
+        let mut out = format!(
+            "Resolve issue {}: {}\n{}\n\n",
+            self.reference(),
+            self.title,
+            self.url
+        );
+        out.push_str(if self.body.is_empty() {
+            "(no description)"
+        } else {
+            &self.body
+        });
+        out.push('\n');
+
+        let comments: Vec<&IssueComment> = self
+            .comments
+            .iter()
+            .filter(|comment| {
+                comment
+                    .body
+                    .as_deref()
+                    .is_some_and(|body| !body.trim().is_empty())
+            })
+            .collect();
+        if !comments.is_empty() {
+            let _ = writeln!(out, "\n## Comments");
+            for comment in comments {
+                let _ = writeln!(
+                    out,
+                    "\n@{}:\n{}",
+                    comment.user.login,
+                    comment.body.as_deref().unwrap_or_default().trim()
+                );
+            }
+        }
+
+        if !self.goals.is_empty() {
+            let _ = writeln!(out, "\n## Acceptance criteria");
+            for goal in &self.goals {
+                let _ = writeln!(out, "- {}", goal);
+            }
+        }
+        out
+    }
+}
+
+/// List items under an "Acceptance criteria" heading, or every task list
+/// item when the description has no such section
+pub fn acceptance_criteria(body: &str) -> Vec<String> {
+    let mut section = Vec::new();
+    let mut tasks = Vec::new();
+    let mut in_section = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim();
+        if is_heading(trimmed) {
+            in_section = trimmed.to_ascii_lowercase().contains("acceptance criteria");
+            continue;
+        }
+        let Some((item, is_task)) = list_item(trimmed) else {
+            continue;
+        };
+        if in_section {
+            section.push(item.to_string());
+        } else if is_task {
+            tasks.push(item.to_string());
+        }
+    }
+
+    if section.is_empty() {
+        tasks
+    } else {
+        section
+    }
+}
+
+/// Markdown heading, a line that is entirely bold, or a line ending in `:`
+/// that is not a list item
+fn is_heading(line: &str) -> bool {
+    line.starts_with('#')
+        || (line.len() > 4 && line.starts_with("**") && line.trim_end_matches(':').ends_with("**"))
+        || (line.ends_with(':') && list_item(line).is_none())
+}
+
+/// Text of a bullet, numbered or task list item, and whether it is a task
+fn list_item(line: &str) -> Option<(&str, bool)> {
+    let rest = line
+        .strip_prefix("- ")
+        .or_else(|| line.strip_prefix("* "))
+        .or_else(|| line.strip_prefix("+ "))
+        .or_else(|| {
+            let digits = line.find(|c: char| !c.is_ascii_digit())?;
+            (digits > 0)
+                .then(|| line[digits..].strip_prefix(". "))
+                .flatten()
+        })?
+        .trim();
+    let (text, is_task) = match rest
+        .strip_prefix("[ ]")
+        .or_else(|| rest.strip_prefix("[x]"))
+        .or_else(|| rest.strip_prefix("[X]"))
+    {
+        Some(text) => (text.trim(), true),
+        None => (rest, false),
+    };
+    (!text.is_empty()).then_some((text, is_task))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::GitHubClient;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn fetches_issue_with_comments_and_goals() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/team/app/issues/4"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!({
+                "number": 4,
+                "title": "Export reports",
+                "body": "## Acceptance criteria\n- [ ] CSV download works",
+                "state": "open",
+                "user": {"login": "pm"},
+                "html_url": "https://github.com/team/app/issues/4",
+                "comments": 1
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/team/app/issues/4/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "id": 1,
+                "user": {"login": "lead"},
+                "body": "Use the existing table component",
+                "created_at": "2026-01-01T00:00:00Z",
+                "html_url": "https://github.com/team/app/issues/4#issuecomment-1"
+            }])))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new("ghp_test", server.uri());
+        let repo = RepoRef::parse("team/app").unwrap();
+        let task = IssueTask::fetch(&client, &repo, 4).await.unwrap();
+        assert_eq!(task.goals, vec!["CSV download works"]);
+        assert_eq!(task.reference(), "team/app#4");
+
+        let request = task.request();
+        assert!(request.starts_with("Resolve issue team/app#4: Export reports"));
+        assert!(request.contains("@lead:\nUse the existing table component"));
+        assert!(request.contains("## Acceptance criteria\n- CSV download works"));
+    }
+
+    #[test]
+    fn parses_issue_references() {
+        let github = IssueRef::parse("https://github.com/junhoyeo/uira/issues/42").unwrap();
+        assert_eq!(github.repo, RepoRef::parse("junhoyeo/uira"));
+        assert_eq!(github.number, 42);
+        assert!(github.url.is_some());
+
+        let gitlab = IssueRef::parse("https://gitlab.com/group/sub/app/-/issues/7#note_1").unwrap();
+        assert_eq!(gitlab.repo, RepoRef::parse("group/sub/app"));
+        assert_eq!(gitlab.number, 7);
+
+        let short = IssueRef::parse("junhoyeo/uira#5").unwrap();
+        assert_eq!(short.repo, RepoRef::parse("junhoyeo/uira"));
+        assert_eq!(IssueRef::parse("#5").unwrap().repo, None);
+        assert_eq!(IssueRef::parse("5").unwrap().number, 5);
+
+        assert!(IssueRef::parse("https://github.com/junhoyeo/uira/pull/3").is_none());
+        assert!(IssueRef::parse("uira#five").is_none());
+    }
+
+    #[test]
+    fn resolves_repository_from_workspace() {
+        let workspace = RepoRef::parse("team/app").unwrap();
+        let issue = IssueRef::parse("9").unwrap();
+        assert_eq!(issue.resolve_repo(Some(&workspace)).unwrap(), workspace);
+        assert!(issue.resolve_repo(None).is_err());
+    }
+
+    #[test]
+    fn extracts_acceptance_criteria_section() {
+        let body = "Users cannot export reports.\n\n\
+                    - [ ] unrelated chore\n\n\
+                    ## Acceptance Criteria\n\
+                    - [ ] Export button downloads a CSV\n\
+                    - [x] Columns match the table\n\
+                    1. Empty reports show a notice\n\n\
+                    ## Notes\n\
+                    - Not a goal";
+        assert_eq!(
+            acceptance_criteria(body),
+            vec![
+                "Export button downloads a CSV",
+                "Columns match the table",
+                "Empty reports show a notice",
+            ]
+        );
+
+        let bold = "**Acceptance criteria:**\n* Works offline";
+        assert_eq!(acceptance_criteria(bold), vec!["Works offline"]);
+    }
+
+    #[test]
+    fn falls_back_to_task_list_items() {
+        let body = "Steps:\n- [ ] Add the flag\n- plain bullet\n- [x] Document it";
+        assert_eq!(
+            acceptance_criteria(body),
+            vec!["Add the flag", "Document it"]
+        );
+        assert!(acceptance_criteria("Just prose.").is_empty());
+    }
+}
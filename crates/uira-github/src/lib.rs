@@ -0,0 +1,37 @@
+//! Code host integration for Uira
+//!
+//! Small REST clients for the issue and pull request endpoints the agent
+//! needs on GitHub, GitLab and Gitea, behind the [`CodeHost`] trait and
+//! picked from the repository's remote by [`connect`]. Tokens are shared with
+//! the hosts' CLIs (`gh`, `glab`). [`tools`] exposes the agent-facing
+//! operations, [`pr`] gathers a branch's work for `uira pr` and
+//! [`issue_task`] turns an issue into a task for `uira issue`. [`ci`] reads
+//! failing GitHub Actions and Buildkite jobs for CI triage.
+
+pub mod auth;
+pub mod ci;
+pub mod client;
+pub mod error;
+pub mod gitea;
+pub mod gitlab;
+pub mod host;
+pub mod issue_task;
+pub mod pr;
+pub mod repo;
+pub mod tools;
+pub mod types;
+
+pub use auth::resolve_token;
+pub use ci::{connect_ci, CiProvider};
+pub use client::{GitHubClient, DEFAULT_API_URL};
+pub use error::{GitHubError, Result};
+pub use gitea::GiteaClient;
+pub use gitlab::GitLabClient;
+pub use host::{connect, detect_kind, CodeHost};
+pub use issue_task::{IssueRef, IssueTask};
+pub use pr::{BranchWork, PrDescription};
+pub use repo::{current_branch, origin_url, RepoRef};
+pub use types::{
+    Issue, IssueComment, NewPullRequest, NewReview, NewReviewComment, PullRequest, Review,
+    ReviewComment, ReviewEvent,
+};
@@ -0,0 +1,240 @@
+//! Branch summaries and AI-written descriptions for `uira pr`
+
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{GitHubError, Result};
+
+/// Diff characters included in the description prompt
+const MAX_DIFF_CHARS: usize = 40_000;
+
+/// What the current branch adds on top of its base
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchWork {
+    pub branch: String,
+    pub base: String,
+    /// `git log --oneline` entries, newest first
+    pub commits: Vec<String>,
+    pub diff_stat: String,
+    /// Diff against the merge base, truncated to keep the prompt bounded
+    pub diff: String,
+}
+
+impl BranchWork {
+    /// Collect the work on the checked-out branch at `cwd` relative to
+    /// `base`, or to the remote's default branch when `base` is `None`
+    pub fn collect(cwd: &Path, base: Option<&str>) -> Result<Self> {
+        let branch = git(cwd, &["rev-parse", "--abbrev-ref", "HEAD"])?;
+        if branch == "HEAD" {
+            return Err(GitHubError::InvalidInput(
+                "HEAD is detached; check out a branch first".to_string(),
+            ));
+        }
+        let base = match base {
+            Some(base) => base.to_string(),
+            None => default_base(cwd),
+        };
+        if branch == base {
+            return Err(GitHubError::InvalidInput(format!(
+                "'{}' is the base branch; create a feature branch first",
+                branch
+            )));
+        }
+
+        // Compare against the remote base when it exists, so unpushed local
+        // base commits don't leak into the PR
+        let remote_base = format!("origin/{}", base);
+        let base_rev = if git(cwd, &["rev-parse", "--verify", "--quiet", &remote_base]).is_ok() {
+            remote_base
+        } else {
+            base.clone()
+        };
+        let range = format!("{}...HEAD", base_rev);
+        let commits = git(cwd, &["log", "--oneline", "--no-decorate", &range])?
+            .lines()
+            .map(str::to_string)
+            .collect();
+        let diff_stat = git(cwd, &["diff", "--stat", &range])?;
+        let mut diff = git(cwd, &["diff", &range])?;
+        if diff.len() > MAX_DIFF_CHARS {
+            let mut end = MAX_DIFF_CHARS;
+            while !diff.is_char_boundary(end) {
+                end -= 1;
+            }
+            diff.truncate(end);
+            diff.push_str("\n… diff truncated");
+        }
+
+        Ok(Self {
+            branch,
+            base,
+            commits,
+            diff_stat,
+            diff,
+        })
+    }
+
+    /// Push the branch to `origin` and set it as upstream
+    pub fn push(&self, cwd: &Path) -> Result<()> {
+        git(cwd, &["push", "--set-upstream", "origin", &self.branch]).map(|_| ())
+    }
+}
+
+/// Branch `origin/HEAD` points at, falling back to `main`
+fn default_base(cwd: &Path) -> String {
+    git(
+        cwd,
+        &["symbolic-ref", "--short", "refs/remotes/origin/HEAD"],
+    )
+    .ok()
+    .and_then(|head| head.strip_prefix("origin/").map(str::to_string))
+    .unwrap_or_else(|| "main".to_string())
+}
+
+fn git(cwd: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(cwd)
+        .output()
+        .map_err(|e| GitHubError::Git {
+            command: args.join(" "),
+            message: e.to_string(),
+        })?;
+    if !output.status.success() {
+        return Err(GitHubError::Git {
+            command: args.join(" "),
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Title and body for a new pull request
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PrDescription {
+    pub title: String,
+    pub body: String,
+}
+
+/// Prompt asking a model to describe `work` as a pull request
+pub fn description_prompt(work: &BranchWork) -> String {
+    format!(
+        "Write a GitHub pull request description for the branch `{branch}`, which targets \
+         `{base}`.\n\n\
+         Start with the title on the first line, under 72 characters, with no prefix or \
+         quotes. Leave a blank line, then write the body in Markdown: open with one or two \
+         plain sentences on what the change does and why, then list the notable changes. \
+         Describe only what the commits and diff show, and do not invent test results.\n\n\
+         <commits>\n{commits}\n</commits>\n\n\
+         <diffstat>\n{stat}\n</diffstat>\n\n\
+         <diff>\n{diff}\n</diff>",
+        branch = work.branch,
+        base = work.base,
+        commits = work.commits.join("\n"),
+        stat = work.diff_stat,
+        diff = work.diff,
+    )
+}
+
+/// Split the model's reply into title and body, falling back to the latest
+/// commit subject when the reply has no usable title
+pub fn parse_description(text: &str, work: &BranchWork) -> PrDescription {
+    let text = text.trim();
+    let (first, rest) = text.split_once('\n').unwrap_or((text, ""));
+    let title = first
+        .trim()
+        .trim_start_matches('#')
+        .trim()
+        .trim_start_matches("Title:")
+        .trim()
+        .trim_matches(|c| c == '"' || c == '`')
+        .to_string();
+    if title.is_empty() {
+        let subject = work
+            .commits
+            .first()
+            .and_then(|commit| commit.split_once(' '))
+            .map(|(_, subject)| subject.to_string())
+            .unwrap_or_else(|| work.branch.clone());
+        return PrDescription {
+            title: subject,
+            body: text.to_string(),
+        };
+    }
+    PrDescription {
+        title,
+        body: rest.trim().to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn work() -> BranchWork {
+        BranchWork {
+            branch: "fix-resume".to_string(),
+            base: "main".to_string(),
+            commits: vec!["abc1234 Fix crash when resuming a forked session".to_string()],
+            diff_stat: " src/session.rs | 4 ++--".to_string(),
+            diff: "-old\n+new".to_string(),
+        }
+    }
+
+    #[test]
+    fn prompt_includes_branch_work() {
+        let prompt = description_prompt(&work());
+        assert!(prompt.contains("`fix-resume`, which targets `main`"));
+        assert!(prompt.contains("<commits>\nabc1234 Fix crash"));
+        assert!(prompt.contains("<diff>\n-old\n+new\n</diff>"));
+    }
+
+    #[test]
+    fn parses_title_and_body() {
+        let parsed = parse_description(
+            "# Title: \"Fix crash when resuming forked sessions\"\n\nResuming a fork no longer panics.\n\n- Guard the parent lookup\n",
+            &work(),
+        );
+        assert_eq!(parsed.title, "Fix crash when resuming forked sessions");
+        assert_eq!(
+            parsed.body,
+            "Resuming a fork no longer panics.\n\n- Guard the parent lookup"
+        );
+
+        let fallback = parse_description("#\nBody only", &work());
+        assert_eq!(fallback.title, "Fix crash when resuming a forked session");
+    }
+
+    #[test]
+    fn collects_branch_work_from_git() {
+        let dir = tempfile::tempdir().unwrap();
+        let cwd = dir.path();
+        let run = |args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(cwd)
+                .output()
+                .unwrap();
+            assert!(status.status.success(), "git {:?}", args);
+        };
+        run(&["init", "-q", "-b", "main"]);
+        run(&["config", "user.email", "dev@example.com"]);
+        run(&["config", "user.name", "Dev"]);
+        std::fs::write(cwd.join("a.txt"), "one\n").unwrap();
+        run(&["add", "."]);
+        run(&["commit", "-qm", "Initial"]);
+        run(&["checkout", "-qb", "feature"]);
+        std::fs::write(cwd.join("a.txt"), "two\n").unwrap();
+        run(&["commit", "-qam", "Change a"]);
+
+        let work = BranchWork::collect(cwd, Some("main")).unwrap();
+        assert_eq!(work.branch, "feature");
+        assert_eq!(work.commits.len(), 1);
+        assert!(work.commits[0].ends_with("Change a"));
+        assert!(work.diff_stat.contains("a.txt"));
+        assert!(work.diff.contains("+two"));
+
+        run(&["checkout", "-q", "main"]);
+        assert!(BranchWork::collect(cwd, Some("main")).is_err());
+    }
+}
@@ -0,0 +1,151 @@
+//! Repository identification from `owner/name` strings and git remotes
+
+use std::fmt;
+use std::path::Path;
+use std::process::Command;
+
+use crate::error::{GitHubError, Result};
+
+/// A repository on a code host
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoRef {
+    /// User or organization; a `group/subgroup` path on GitLab
+    pub owner: String,
+    pub name: String,
+}
+
+impl RepoRef {
+    /// Parse `owner/name`, or `group/subgroup/name` on GitLab
+    pub fn parse(value: &str) -> Option<Self> {
+        let (owner, name) = value.trim().rsplit_once('/')?;
+        Self::from_parts(owner, name)
+    }
+
+    /// Parse an HTTPS, SSH or scp-style remote URL
+    pub fn from_remote_url(url: &str) -> Option<Self> {
+        let (_, path) = split_remote(url)?;
+        Self::parse(path.trim_end_matches('/'))
+    }
+
+    /// Repository of the `origin` remote of the checkout at `cwd`
+    pub fn from_git(cwd: &Path) -> Result<Self> {
+        let url = origin_url(cwd)?;
+        Self::from_remote_url(&url)
+            .ok_or_else(|| GitHubError::Repo(format!("'{}' is not a repository remote", url)))
+    }
+
+    fn from_parts(owner: &str, name: &str) -> Option<Self> {
+        let name = name.trim_end_matches(".git");
+        let valid = |part: &str| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        };
+        (owner.split('/').all(valid) && valid(name)).then(|| Self {
+            owner: owner.to_string(),
+            name: name.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for RepoRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}", self.owner, self.name)
+    }
+}
+
+/// URL of the `origin` remote of the checkout at `cwd`
+pub fn origin_url(cwd: &Path) -> Result<String> {
+    let output = Command::new("git")
+        .args(["remote", "get-url", "origin"])
+        .current_dir(cwd)
+        .output()
+        .map_err(|e| GitHubError::Repo(e.to_string()))?;
+    if !output.status.success() {
+        return Err(GitHubError::Repo(
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Branch checked out at `cwd`, `None` when HEAD is detached
+pub fn current_branch(cwd: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .current_dir(cwd)
+        .output()
+        .ok()?;
+    let branch = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (output.status.success() && branch != "HEAD").then_some(branch)
+}
+
+/// Host name of a remote URL, without user or port
+pub fn remote_host(url: &str) -> Option<String> {
+    let (authority, _) = split_remote(url)?;
+    let host = authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host);
+    let host = host.split(':').next().unwrap_or_default();
+    (!host.is_empty()).then(|| host.to_ascii_lowercase())
+}
+
+/// Split a remote URL into its authority and repository path
+fn split_remote(url: &str) -> Option<(&str, &str)> {
+    let url = url.trim();
+    if let Some((_, rest)) = url.split_once("://") {
+        // https://host/owner/name, ssh://git@host:22/owner/name
+        rest.split_once('/')
+    } else {
+        // git@host:owner/name
+        url.split_once(':')
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_remote_urls() {
+        let expected = RepoRef::parse("junhoyeo/uira").unwrap();
+        for url in [
+            "https://github.com/junhoyeo/uira.git",
+            "https://github.com/junhoyeo/uira/",
+            "git@github.com:junhoyeo/uira.git",
+            "ssh://git@github.com/junhoyeo/uira.git",
+        ] {
+            assert_eq!(
+                RepoRef::from_remote_url(url),
+                Some(expected.clone()),
+                "{url}"
+            );
+        }
+        assert_eq!(expected.to_string(), "junhoyeo/uira");
+
+        let nested = RepoRef::from_remote_url("git@gitlab.com:group/sub/project.git").unwrap();
+        assert_eq!(nested.owner, "group/sub");
+        assert_eq!(nested.to_string(), "group/sub/project");
+
+        assert!(RepoRef::parse("uira").is_none());
+        assert!(RepoRef::parse("junhoyeo//uira").is_none());
+        assert!(RepoRef::from_remote_url("/srv/git/uira.git").is_none());
+    }
+
+    #[test]
+    fn extracts_remote_hosts() {
+        assert_eq!(
+            remote_host("ssh://git@Git.Example.com:2222/team/app.git").as_deref(),
+            Some("git.example.com")
+        );
+        assert_eq!(
+            remote_host("git@codeberg.org:team/app.git").as_deref(),
+            Some("codeberg.org")
+        );
+        assert_eq!(
+            remote_host("https://gitlab.com/group/sub/project").as_deref(),
+            Some("gitlab.com")
+        );
+    }
+}
@@ -0,0 +1,456 @@
+//! Agent-facing code host operations
+//!
+//! Each function takes the tool's JSON input and returns the text shown to the
+//! model. `repo` is the repository to use when the input has no `repo` field.
+
+use std::fmt::Write as _;
+
+use serde_json::Value;
+
+use crate::ci::{extract_signature, CiProvider};
+use crate::error::{GitHubError, Result};
+use crate::host::CodeHost;
+use crate::repo::RepoRef;
+use crate::types::{NewPullRequest, NewReview, NewReviewComment, ReviewEvent};
+
+/// Repository named by the input's `repo` field, else `default`
+pub fn input_repo(input: &Value, default: Option<&RepoRef>) -> Result<RepoRef> {
+    match input.get("repo").and_then(Value::as_str) {
+        Some(repo) => RepoRef::parse(repo).ok_or_else(|| {
+            GitHubError::InvalidInput(format!("'{}' is not an owner/name repository", repo))
+        }),
+        None => default.cloned().ok_or_else(|| {
+            GitHubError::Repo("pass 'repo' as owner/name outside a GitHub checkout".to_string())
+        }),
+    }
+}
+
+fn required_str<'a>(input: &'a Value, field: &str) -> Result<&'a str> {
+    input
+        .get(field)
+        .and_then(Value::as_str)
+        .filter(|value| !value.trim().is_empty())
+        .ok_or_else(|| GitHubError::InvalidInput(format!("missing '{}'", field)))
+}
+
+fn number(input: &Value) -> Result<u64> {
+    input
+        .get("number")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| GitHubError::InvalidInput("missing 'number'".to_string()))
+}
+
+/// `github_fetch_issue`: an issue or pull request with its conversation
+pub async fn fetch_issue(client: &dyn CodeHost, repo: &RepoRef, input: &Value) -> Result<String> {
+    let number = number(input)?;
+    let issue = client.issue(repo, number).await?;
+    let is_pull_request = issue.pull_request.is_some();
+    let kind = if is_pull_request {
+        capitalize(client.pull_request_noun())
+    } else {
+        "Issue".to_string()
+    };
+
+    let mut out = format!(
+        "{} {}#{}: {}\nState: {} | Author: @{}",
+        kind, repo, issue.number, issue.title, issue.state, issue.user.login
+    );
+    if !issue.labels.is_empty() {
+        let labels: Vec<&str> = issue.labels.iter().map(|l| l.name.as_str()).collect();
+        let _ = write!(out, " | Labels: {}", labels.join(", "));
+    }
+    let _ = writeln!(out, "\n{}\n", issue.html_url);
+    out.push_str(issue.body.as_deref().unwrap_or("(no description)").trim());
+    out.push('\n');
+
+    let include_comments = input
+        .get("includeComments")
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+    if include_comments && issue.comments > 0 {
+        let comments = if is_pull_request {
+            client.pull_request_comments(repo, number).await?
+        } else {
+            client.issue_comments(repo, number).await?
+        };
+        let _ = writeln!(out, "\n## Comments ({})", comments.len());
+        for comment in comments {
+            let _ = writeln!(
+                out,
+                "\n@{} ({}):\n{}",
+                comment.user.login,
+                comment.created_at,
+                comment.body.as_deref().unwrap_or_default().trim()
+            );
+        }
+    }
+    Ok(out)
+}
+
+/// `github_list_pr_comments`: conversation and inline review comments
+pub async fn list_pr_comments(
+    client: &dyn CodeHost,
+    repo: &RepoRef,
+    input: &Value,
+) -> Result<String> {
+    let number = number(input)?;
+    let conversation = client.pull_request_comments(repo, number).await?;
+    let review = client.review_comments(repo, number).await?;
+    if conversation.is_empty() && review.is_empty() {
+        return Ok(format!("No comments on {}#{}.", repo, number));
+    }
+
+    let mut out = format!("Comments on {}#{}\n", repo, number);
+    if !conversation.is_empty() {
+        let _ = writeln!(out, "\n## Conversation ({})", conversation.len());
+        for comment in conversation {
+            let _ = writeln!(
+                out,
+                "\n@{} ({}):\n{}",
+                comment.user.login,
+                comment.created_at,
+                comment.body.as_deref().unwrap_or_default().trim()
+            );
+        }
+    }
+    if !review.is_empty() {
+        let _ = writeln!(out, "\n## Review comments ({})", review.len());
+        for comment in review {
+            let location = match comment.line {
+                Some(line) => format!("{}:{}", comment.path, line),
+                None => format!("{} (outdated)", comment.path),
+            };
+            let reply = if comment.in_reply_to_id.is_some() {
+                " reply"
+            } else {
+                ""
+            };
+            let _ = writeln!(
+                out,
+                "\n[{}] @{}{} ({}):\n{}",
+                location,
+                comment.user.login,
+                reply,
+                comment.created_at,
+                comment.body.trim()
+            );
+        }
+    }
+    Ok(out)
+}
+
+/// `github_create_pr`: open a pull request from an already pushed branch
+pub async fn create_pr(client: &dyn CodeHost, repo: &RepoRef, input: &Value) -> Result<String> {
+    let pull_request = NewPullRequest {
+        title: required_str(input, "title")?.to_string(),
+        head: required_str(input, "head")?.to_string(),
+        base: required_str(input, "base")?.to_string(),
+        body: input
+            .get("body")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_string(),
+        draft: input.get("draft").and_then(Value::as_bool).unwrap_or(false),
+    };
+    let created = client.create_pull_request(repo, &pull_request).await?;
+    Ok(format!(
+        "Created {}{} {}#{}: {}\n{}",
+        if created.draft { "draft " } else { "" },
+        client.pull_request_noun(),
+        repo,
+        created.number,
+        created.title,
+        created.html_url
+    ))
+}
+
+/// `github_post_review`: review a pull request with optional line comments
+pub async fn post_review(client: &dyn CodeHost, repo: &RepoRef, input: &Value) -> Result<String> {
+    let number = number(input)?;
+    let event = match input.get("event").and_then(Value::as_str) {
+        Some(event) => serde_json::from_value(Value::String(event.to_uppercase()))
+            .map_err(|_| GitHubError::InvalidInput(format!("unknown review event '{}'", event)))?,
+        None => ReviewEvent::default(),
+    };
+    let comments: Vec<NewReviewComment> = match input.get("comments") {
+        Some(comments) => serde_json::from_value(comments.clone())
+            .map_err(|e| GitHubError::InvalidInput(format!("invalid 'comments': {}", e)))?,
+        None => Vec::new(),
+    };
+    let body = input
+        .get("body")
+        .and_then(Value::as_str)
+        .filter(|body| !body.trim().is_empty())
+        .map(str::to_string);
+    if body.is_none() && comments.is_empty() && event != ReviewEvent::Approve {
+        return Err(GitHubError::InvalidInput(
+            "a review needs a 'body' or 'comments'".to_string(),
+        ));
+    }
+
+    let count = comments.len();
+    let review = client
+        .create_review(
+            repo,
+            number,
+            &NewReview {
+                body,
+                event,
+                comments,
+            },
+        )
+        .await?;
+    Ok(format!(
+        "Posted review on {}#{} ({}, {} line comment{})\n{}",
+        repo,
+        number,
+        review.state,
+        count,
+        if count == 1 { "" } else { "s" },
+        review.html_url
+    ))
+}
+
+/// Failed jobs whose logs are downloaded per call
+const MAX_CI_JOBS: usize = 3;
+
+/// `ci_fetch_logs`: failure signatures of the failed jobs in the newest
+/// finished build of `branch` (the input's `branch` wins), with a hand-off to
+/// the build-fixer agent
+pub async fn ci_fetch_logs(
+    ci: &dyn CiProvider,
+    branch: Option<&str>,
+    input: &Value,
+) -> Result<String> {
+    let branch = input.get("branch").and_then(Value::as_str).or(branch);
+    let Some(build) = ci.latest_failure(branch).await? else {
+        return Ok(format!(
+            "The newest finished {} build{} passed; there is nothing to fix.",
+            ci.name(),
+            branch.map(|b| format!(" on '{}'", b)).unwrap_or_default()
+        ));
+    };
+
+    let filter = input
+        .get("job")
+        .and_then(Value::as_str)
+        .map(str::to_lowercase);
+    let jobs: Vec<_> = build
+        .jobs
+        .iter()
+        .filter(|job| {
+            filter
+                .as_deref()
+                .is_none_or(|filter| job.name.to_lowercase().contains(filter))
+        })
+        .collect();
+
+    let mut out = format!("{} build failed: {}\n", ci.name(), build.title);
+    if let Some(branch) = &build.branch {
+        let _ = write!(out, "Branch: {}", branch);
+    }
+    if let Some(commit) = &build.commit {
+        let short: String = commit.chars().take(12).collect();
+        let _ = write!(out, " | Commit: {}", short);
+    }
+    let _ = writeln!(out, "\n{}", build.url);
+
+    if jobs.is_empty() {
+        let names: Vec<&str> = build.jobs.iter().map(|job| job.name.as_str()).collect();
+        let _ = writeln!(
+            out,
+            "\nNo failed job matches; failed jobs: {}",
+            if names.is_empty() {
+                "(none reported)".to_string()
+            } else {
+                names.join(", ")
+            }
+        );
+        return Ok(out);
+    }
+
+    for job in jobs.iter().take(MAX_CI_JOBS) {
+        let _ = write!(out, "\n## Job: {}", job.name);
+        if let Some(step) = &job.step {
+            let _ = write!(out, " (step: {})", step);
+        }
+        let _ = writeln!(out, "\n{}", job.url);
+        let log = ci.job_log(job).await?;
+        let signature = extract_signature(&log);
+        let _ = writeln!(out, "Failure: {}", signature.summary);
+        if !signature.locations.is_empty() {
+            let _ = writeln!(out, "Locations: {}", signature.locations.join(", "));
+        }
+        let _ = writeln!(out, "\n```\n{}\n```", signature.excerpt);
+    }
+    if jobs.len() > MAX_CI_JOBS {
+        let _ = writeln!(
+            out,
+            "\n{} more failed jobs; pass 'job' to read one of them.",
+            jobs.len() - MAX_CI_JOBS
+        );
+    }
+
+    out.push_str(
+        "\nTo fix this, call delegate_task with agent \"build-fixer\" and a prompt containing \
+         each job's failure, locations and log excerpt above, then rerun the failing command \
+         locally to confirm.",
+    );
+    Ok(out)
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::GitHubClient;
+    use serde_json::json;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    fn repo() -> RepoRef {
+        RepoRef::parse("junhoyeo/uira").unwrap()
+    }
+
+    #[test]
+    fn input_repo_prefers_explicit_repo() {
+        let default = repo();
+        let other = input_repo(&json!({"repo": "octo/hello"}), Some(&default)).unwrap();
+        assert_eq!(other.to_string(), "octo/hello");
+        assert_eq!(input_repo(&json!({}), Some(&default)).unwrap(), default);
+        assert!(input_repo(&json!({}), None).is_err());
+        assert!(input_repo(&json!({"repo": "nope"}), None).is_err());
+    }
+
+    #[tokio::test]
+    async fn lists_conversation_and_review_comments() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/repos/junhoyeo/uira/issues/3/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "id": 1,
+                "user": {"login": "octocat"},
+                "body": "Can you add a test?",
+                "created_at": "2026-01-02T00:00:00Z",
+                "html_url": "https://github.com/junhoyeo/uira/pull/3#issuecomment-1"
+            }])))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/repos/junhoyeo/uira/pulls/3/comments"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(json!([{
+                "id": 2,
+                "user": {"login": "hubot"},
+                "body": "This unwrap can panic",
+                "path": "src/lib.rs",
+                "line": 12,
+                "created_at": "2026-01-02T00:00:00Z",
+                "html_url": "https://github.com/junhoyeo/uira/pull/3#discussion_r2"
+            }])))
+            .mount(&server)
+            .await;
+
+        let client = GitHubClient::new("token", server.uri());
+        let out = list_pr_comments(&client, &repo(), &json!({"number": 3}))
+            .await
+            .unwrap();
+        assert!(out.contains("## Conversation (1)"));
+        assert!(out.contains("@octocat"));
+        assert!(out.contains("[src/lib.rs:12] @hubot"));
+        assert!(out.contains("This unwrap can panic"));
+    }
+
+    #[derive(Debug)]
+    struct FakeCi(Option<crate::ci::FailedBuild>);
+
+    #[async_trait::async_trait]
+    impl CiProvider for FakeCi {
+        fn name(&self) -> &'static str {
+            "Fake CI"
+        }
+
+        async fn latest_failure(
+            &self,
+            _branch: Option<&str>,
+        ) -> Result<Option<crate::ci::FailedBuild>> {
+            Ok(self.0.clone())
+        }
+
+        async fn job_log(&self, job: &crate::ci::FailedJob) -> Result<String> {
+            Ok(format!(
+                "setup\nerror: {} broke\n  --> src/lib.rs:7:1",
+                job.name
+            ))
+        }
+    }
+
+    #[tokio::test]
+    async fn ci_logs_report_signatures_and_hand_off() {
+        let job = |name: &str| crate::ci::FailedJob {
+            name: name.to_string(),
+            url: format!("https://ci.example.com/{}", name),
+            step: Some("cargo test".to_string()),
+            log_path: String::new(),
+        };
+        let ci = FakeCi(Some(crate::ci::FailedBuild {
+            title: "Add parser".to_string(),
+            branch: Some("feature".to_string()),
+            commit: Some("0123456789abcdef".to_string()),
+            url: "https://ci.example.com/builds/1".to_string(),
+            jobs: vec![job("test-linux"), job("lint")],
+        }));
+
+        let out = ci_fetch_logs(&ci, Some("feature"), &json!({}))
+            .await
+            .unwrap();
+        assert!(out.starts_with("Fake CI build failed: Add parser"));
+        assert!(out.contains("Commit: 0123456789ab\n"));
+        assert!(out.contains("## Job: test-linux (step: cargo test)"));
+        assert!(out.contains("Failure: error: test-linux broke"));
+        assert!(out.contains("Locations: src/lib.rs:7"));
+        assert!(out.contains("agent \"build-fixer\""));
+
+        let out = ci_fetch_logs(&ci, None, &json!({"job": "LINT"}))
+            .await
+            .unwrap();
+        assert!(out.contains("## Job: lint"));
+        assert!(!out.contains("## Job: test-linux"));
+
+        let out = ci_fetch_logs(&ci, None, &json!({"job": "deploy"}))
+            .await
+            .unwrap();
+        assert!(out.contains("failed jobs: test-linux, lint"));
+
+        let passing = FakeCi(None);
+        let out = ci_fetch_logs(&passing, Some("main"), &json!({}))
+            .await
+            .unwrap();
+        assert!(out.contains("on 'main' passed"));
+    }
+
+    #[tokio::test]
+    async fn validates_review_input() {
+        let client = GitHubClient::new("token", "http://127.0.0.1:9");
+        let err = post_review(&client, &repo(), &json!({"number": 3}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("'body' or 'comments'"));
+
+        let err = post_review(&client, &repo(), &json!({"number": 3, "event": "merge"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("unknown review event"));
+
+        let err = create_pr(&client, &repo(), &json!({"title": "Fix", "head": "fix"}))
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("missing 'base'"));
+    }
+}
@@ -0,0 +1,120 @@
+//! REST API payloads, limited to the fields Uira reads or sends
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct User {
+    pub login: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Label {
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Issue {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub state: String,
+    pub user: User,
+    #[serde(default)]
+    pub labels: Vec<Label>,
+    pub html_url: String,
+    #[serde(default)]
+    pub comments: u64,
+    /// Present when the issue is a pull request
+    #[serde(default)]
+    pub pull_request: Option<serde_json::Value>,
+}
+
+/// A comment on the conversation tab of an issue or pull request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IssueComment {
+    pub id: u64,
+    pub user: User,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub created_at: String,
+    pub html_url: String,
+}
+
+/// A comment on a line of a pull request diff
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ReviewComment {
+    pub id: u64,
+    pub user: User,
+    pub body: String,
+    pub path: String,
+    #[serde(default)]
+    pub line: Option<u64>,
+    #[serde(default)]
+    pub in_reply_to_id: Option<u64>,
+    pub created_at: String,
+    pub html_url: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BranchRef {
+    #[serde(rename = "ref")]
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PullRequest {
+    pub number: u64,
+    pub title: String,
+    #[serde(default)]
+    pub body: Option<String>,
+    pub state: String,
+    pub html_url: String,
+    #[serde(default)]
+    pub draft: bool,
+    pub head: BranchRef,
+    pub base: BranchRef,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewPullRequest {
+    pub title: String,
+    /// Branch with the changes, `owner:branch` for forks
+    pub head: String,
+    pub base: String,
+    pub body: String,
+    pub draft: bool,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum ReviewEvent {
+    #[default]
+    Comment,
+    Approve,
+    RequestChanges,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NewReviewComment {
+    pub path: String,
+    /// Line in the new version of the file
+    pub line: u64,
+    pub body: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct NewReview {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub body: Option<String>,
+    pub event: ReviewEvent,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub comments: Vec<NewReviewComment>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Review {
+    pub id: u64,
+    pub state: String,
+    pub html_url: String,
+}
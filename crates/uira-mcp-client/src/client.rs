@@ -9,6 +9,7 @@ use thiserror::Error;
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio::process::{Child, ChildStdin, ChildStdout, Command};
 use tokio::sync::{oneshot, Mutex};
+use tokio_util::sync::CancellationToken;
 
 const DEFAULT_RPC_TIMEOUT: Duration = Duration::from_secs(20);
 const MAX_MESSAGE_SIZE: usize = 8 * 1024 * 1024;
@@ -42,6 +43,9 @@ pub enum McpClientError {
 
     #[error("unknown MCP server: {server}")]
     UnknownServer { server: String },
+
+    #[error("MCP call to '{server}' was cancelled")]
+    Cancelled { server: String },
 }
 
 #[derive(Clone)]
@@ -77,6 +81,7 @@ impl McpRuntimeManager {
         tool_name: &str,
         arguments: Value,
         cwd: &Path,
+        cancel: &CancellationToken,
     ) -> Result<Value, McpClientError> {
         let runtime = self
             .servers
@@ -86,15 +91,30 @@ impl McpRuntimeManager {
             })?
             .clone();
 
-        let mut runtime = runtime.lock().await;
+        let mut runtime = tokio::select! {
+            runtime = runtime.lock() => runtime,
+            _ = cancel.cancelled() => {
+                return Err(McpClientError::Cancelled {
+                    server: server_name.to_string(),
+                });
+            }
+        };
         runtime.default_cwd = cwd.to_path_buf();
         runtime.ensure_connected(self.rpc_timeout).await?;
 
-        match call_tool_once(&mut runtime, tool_name, arguments.clone(), self.rpc_timeout).await {
+        match call_tool_once(
+            &mut runtime,
+            tool_name,
+            arguments.clone(),
+            self.rpc_timeout,
+            cancel,
+        )
+        .await
+        {
             Ok(v) => Ok(v),
             Err(McpClientError::TransportClosed { .. }) => {
                 runtime.restart(self.rpc_timeout).await?;
-                call_tool_once(&mut runtime, tool_name, arguments, self.rpc_timeout).await
+                call_tool_once(&mut runtime, tool_name, arguments, self.rpc_timeout, cancel).await
             }
             Err(err) => Err(err),
         }
@@ -106,11 +126,12 @@ async fn call_tool_once(
     tool_name: &str,
     arguments: Value,
     timeout: Duration,
+    cancel: &CancellationToken,
 ) -> Result<Value, McpClientError> {
     let params = json!({"name": tool_name, "arguments": arguments});
     let response = runtime
         .connection_mut()?
-        .request("tools/call", Some(params), timeout)
+        .request_cancellable("tools/call", Some(params), timeout, cancel)
         .await?;
     Ok(parse_tools_call_result(response))
 }
@@ -220,7 +241,8 @@ impl McpConnection {
             .current_dir(cwd)
             .stdin(std::process::Stdio::piped())
             .stdout(std::process::Stdio::piped())
-            .stderr(std::process::Stdio::piped());
+            .stderr(std::process::Stdio::piped())
+            .kill_on_drop(true);
 
         for (key, value) in &config.env {
             command.env(key, value);
@@ -323,6 +345,19 @@ impl McpConnection {
         method: &str,
         params: Option<Value>,
         timeout: Duration,
+    ) -> Result<Value, McpClientError> {
+        self.request_cancellable(method, params, timeout, &CancellationToken::new())
+            .await
+    }
+
+    /// Send a request, telling the server to stop working on it if `cancel`
+    /// fires before the response arrives
+    async fn request_cancellable(
+        &mut self,
+        method: &str,
+        params: Option<Value>,
+        timeout: Duration,
+        cancel: &CancellationToken,
     ) -> Result<Value, McpClientError> {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let request = json!({
@@ -357,7 +392,23 @@ impl McpConnection {
             });
         }
 
-        match tokio::time::timeout(timeout, rx).await {
+        let response = tokio::select! {
+            response = tokio::time::timeout(timeout, rx) => response,
+            _ = cancel.cancelled() => {
+                let _ = self.pending.lock().await.remove(&id);
+                let _ = self
+                    .notify(
+                        "notifications/cancelled",
+                        Some(json!({"requestId": id, "reason": "cancelled by client"})),
+                    )
+                    .await;
+                return Err(McpClientError::Cancelled {
+                    server: self.server_name.clone(),
+                });
+            }
+        };
+
+        match response {
             Ok(Ok(result)) => result,
             Ok(Err(_)) => Err(McpClientError::TransportClosed {
                 server: self.server_name.clone(),
@@ -632,6 +683,53 @@ mod tests {
         assert!(buffer.is_empty());
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn cancelled_call_notifies_server() {
+        let workspace = tempdir().unwrap();
+        let log = workspace.path().join("cancelled.log");
+        // Answers initialize, never answers tools/call, and records cancellations
+        let script = format!(
+            r#"while IFS= read -r line; do
+  case "$line" in
+    *'"initialize"'*) echo '{{"jsonrpc":"2.0","id":1,"result":{{"protocolVersion":"2024-11-05"}}}}' ;;
+    *notifications/cancelled*) echo "$line" > '{}' ;;
+  esac
+done"#,
+            log.display()
+        );
+        let server = McpServerConfig::from_command(
+            "hang",
+            "sh",
+            vec!["-c".to_string(), script],
+            HashMap::new(),
+        )
+        .unwrap();
+        let manager = McpRuntimeManager::new(vec![server], workspace.path().to_path_buf());
+
+        let cancel = CancellationToken::new();
+        let trigger = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(200)).await;
+            trigger.cancel();
+        });
+        let result = manager
+            .call_tool("hang", "slow", json!({}), workspace.path(), &cancel)
+            .await;
+        assert!(matches!(result, Err(McpClientError::Cancelled { .. })));
+
+        let mut notified = String::new();
+        for _ in 0..50 {
+            notified = std::fs::read_to_string(&log).unwrap_or_default();
+            if !notified.is_empty() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let notification: Value = serde_json::from_str(notified.trim()).unwrap();
+        assert_eq!(notification["params"]["requestId"], 2);
+    }
+
     #[tokio::test]
     #[ignore = "requires npm/npx and network access"]
     async fn discover_tools_from_real_filesystem_server() {
@@ -106,6 +106,19 @@ impl McpServer {
                     "required": ["filePath"]
                 }
             }),
+            json!({
+                "name": "lsp_workspace_diagnostics",
+                "description": "Summarize diagnostics by severity across open files, or files matching a glob.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "glob": {"type": "string", "description": "Glob relative to the workspace root, e.g. src/**/*.rs"},
+                        "severity": {"type": "string", "enum": ["error", "warning", "information", "hint", "all"], "default": "all"},
+                        "limit": {"type": "integer", "minimum": 1, "default": 50},
+                        "timeoutSecs": {"type": "integer", "minimum": 1, "default": 5}
+                    }
+                }
+            }),
             json!({
                 "name": "lsp_hover",
                 "description": "Get type information and documentation for a symbol at a position.",
@@ -133,6 +146,64 @@ impl McpServer {
                     "required": ["filePath", "line", "character", "newName"]
                 }
             }),
+            json!({
+                "name": "lsp_code_actions",
+                "description": "List code actions (quick fixes, refactors) for a position or range, or apply one by index.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "filePath": {"type": "string"},
+                        "line": {"type": "integer", "minimum": 1},
+                        "character": {"type": "integer", "minimum": 0},
+                        "endLine": {"type": "integer", "minimum": 1},
+                        "endCharacter": {"type": "integer", "minimum": 0},
+                        "apply": {"type": "integer", "minimum": 0, "description": "Index of the listed action to apply"}
+                    },
+                    "required": ["filePath", "line", "character"]
+                }
+            }),
+            json!({
+                "name": "lsp_format_range",
+                "description": "Format a line range, or the whole file when no range is given, and write the result.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "filePath": {"type": "string"},
+                        "startLine": {"type": "integer", "minimum": 1},
+                        "endLine": {"type": "integer", "minimum": 1},
+                        "tabSize": {"type": "integer", "minimum": 1, "default": 4},
+                        "insertSpaces": {"type": "boolean", "default": true}
+                    },
+                    "required": ["filePath"]
+                }
+            }),
+            json!({
+                "name": "lsp_call_hierarchy",
+                "description": "List the incoming and/or outgoing calls of the function at a position.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "filePath": {"type": "string"},
+                        "line": {"type": "integer", "minimum": 1},
+                        "character": {"type": "integer", "minimum": 0},
+                        "direction": {"type": "string", "enum": ["incoming", "outgoing", "both"], "default": "both"}
+                    },
+                    "required": ["filePath", "line", "character"]
+                }
+            }),
+            json!({
+                "name": "lsp_implementations",
+                "description": "Find implementations of a trait, interface or abstract method.",
+                "inputSchema": {
+                    "type": "object",
+                    "properties": {
+                        "filePath": {"type": "string"},
+                        "line": {"type": "integer", "minimum": 1},
+                        "character": {"type": "integer", "minimum": 0}
+                    },
+                    "required": ["filePath", "line", "character"]
+                }
+            }),
             // AST-grep Tools
             json!({
                 "name": "ast_search",
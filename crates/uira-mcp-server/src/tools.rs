@@ -9,7 +9,7 @@ use std::process::Stdio;
 use std::sync::Arc;
 use tokio::process::Command;
 use uira_orchestration::{LspClient, LspClientImpl, ToolContent, ToolOutput};
-use uira_oxc::{LintRule, Linter, Severity};
+use uira_oxc::{LintConfig, LintRule, Linter, ModuleLinter, ModuleRule, Severity};
 use walkdir::WalkDir;
 
 use uira_core::load_config;
@@ -21,8 +21,12 @@ use uira_orchestration::background_agent::{
 use crate::anthropic_client;
 use crate::router::{route_model, ModelPath};
 
-static BACKGROUND_MANAGER: Lazy<Arc<BackgroundManager>> =
-    Lazy::new(|| get_background_manager(BackgroundTaskConfig::default()));
+static BACKGROUND_MANAGER: Lazy<Arc<BackgroundManager>> = Lazy::new(|| {
+    let config = load_config(None)
+        .map(|config| BackgroundTaskConfig::from_settings(&config.background_tasks))
+        .unwrap_or_default();
+    get_background_manager(config)
+});
 
 fn get_extensions_for_lang(lang: &str) -> &'static [&'static str] {
     match lang {
@@ -144,8 +148,13 @@ impl ToolExecutor {
             "lsp_find_references" => self.lsp_find_references(args).await,
             "lsp_symbols" => self.lsp_symbols(args).await,
             "lsp_diagnostics" => self.lsp_diagnostics(args).await,
+            "lsp_workspace_diagnostics" => self.lsp_workspace_diagnostics(args).await,
             "lsp_hover" => self.lsp_hover(args).await,
             "lsp_rename" => self.lsp_rename(args).await,
+            "lsp_code_actions" => self.lsp_code_actions(args).await,
+            "lsp_format_range" => self.lsp_format_range(args).await,
+            "lsp_call_hierarchy" => self.lsp_call_hierarchy(args).await,
+            "lsp_implementations" => self.lsp_implementations(args).await,
 
             // AST-grep Tools - native integration via ast-grep-language
             "ast_search" => self.ast_search(args).await,
@@ -212,6 +221,14 @@ impl ToolExecutor {
         }
     }
 
+    async fn lsp_workspace_diagnostics(&self, args: Value) -> Result<String, String> {
+        self.lsp_client
+            .workspace_diagnostics(args)
+            .await
+            .map(extract_text)
+            .map_err(|e| e.to_string())
+    }
+
     async fn lsp_hover(&self, args: Value) -> Result<String, String> {
         self.lsp_client
             .hover(args)
@@ -228,6 +245,38 @@ impl ToolExecutor {
             .map_err(|e| e.to_string())
     }
 
+    async fn lsp_code_actions(&self, args: Value) -> Result<String, String> {
+        self.lsp_client
+            .code_actions(args)
+            .await
+            .map(extract_text)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn lsp_format_range(&self, args: Value) -> Result<String, String> {
+        self.lsp_client
+            .format_range(args)
+            .await
+            .map(extract_text)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn lsp_call_hierarchy(&self, args: Value) -> Result<String, String> {
+        self.lsp_client
+            .call_hierarchy(args)
+            .await
+            .map(extract_text)
+            .map_err(|e| e.to_string())
+    }
+
+    async fn lsp_implementations(&self, args: Value) -> Result<String, String> {
+        self.lsp_client
+            .implementations(args)
+            .await
+            .map(extract_text)
+            .map_err(|e| e.to_string())
+    }
+
     async fn ast_search(&self, args: Value) -> Result<String, String> {
         let pattern = args["pattern"].as_str().ok_or("Missing pattern")?;
         let lang_str = args["lang"].as_str().ok_or("Missing lang")?;
@@ -380,8 +429,15 @@ impl ToolExecutor {
     }
 
     fn run_oxc_lint(&self, file_path: &str, severity_filter: &str) -> Result<String, String> {
-        let linter = Linter::new(LintRule::recommended());
-        let diagnostics = linter.lint_file(file_path)?;
+        let lint_config = uira_core::load_config(None)
+            .ok()
+            .and_then(|config| LintConfig::from_levels(config.diagnostics.lint.rules).ok())
+            .unwrap_or_default();
+        let linter = Linter::new(LintRule::recommended()).with_config(lint_config.clone());
+        let mut diagnostics = linter.lint_file(file_path)?;
+        let module_linter =
+            ModuleLinter::new(ModuleRule::all(), &self.root_path).with_config(lint_config);
+        diagnostics.extend(module_linter.lint_files(&[file_path.to_string()]));
 
         if diagnostics.is_empty() {
             return Ok("No diagnostics found".to_string());
@@ -529,23 +585,43 @@ impl ToolExecutor {
             let allowed_tools_owned = allowed_tools.clone();
 
             let handle = tokio::spawn(async move {
-                let result = match route_model(&model_owned) {
-                    ModelPath::Anthropic => {
-                        anthropic_client::query(&prompt_owned, &model_owned, allowed_tools_owned)
+                loop {
+                    let result = match route_model(&model_owned) {
+                        ModelPath::Anthropic => {
+                            anthropic_client::query(
+                                &prompt_owned,
+                                &model_owned,
+                                allowed_tools_owned.clone(),
+                            )
                             .await
-                    }
-                    ModelPath::DirectProvider => {
-                        Err("OpenCode proxy support has been removed. Use native Anthropic or OpenAI providers.".to_string())
-                    }
-                };
+                        }
+                        ModelPath::DirectProvider => {
+                            Err("OpenCode proxy support has been removed. Use native Anthropic or OpenAI providers.".to_string())
+                        }
+                    };
 
-                match result {
-                    Ok(output) => {
-                        BACKGROUND_MANAGER.complete_task(&task_id, output);
-                    }
-                    Err(e) => {
-                        BACKGROUND_MANAGER.fail_task(&task_id, e);
+                    match result {
+                        Ok(output) => {
+                            BACKGROUND_MANAGER
+                                .task_logger(&task_id)
+                                .output(&format!("{output}\n"));
+                            BACKGROUND_MANAGER.complete_task(&task_id, output);
+                        }
+                        Err(e) => {
+                            if let Some(delay) = BACKGROUND_MANAGER.retry_delay(&task_id, &e) {
+                                tokio::time::sleep(delay).await;
+                                if BACKGROUND_MANAGER
+                                    .get_task(&task_id)
+                                    .is_some_and(|task| !task.status.is_terminal())
+                                {
+                                    continue;
+                                }
+                            } else {
+                                BACKGROUND_MANAGER.fail_task(&task_id, e);
+                            }
+                        }
                     }
+                    break;
                 }
             });
 
@@ -1,5 +1,9 @@
 use serde::{Deserialize, Serialize};
 
+/// Embedding provider that runs on this machine (Ollama's OpenAI-compatible
+/// `/v1/embeddings` endpoint)
+pub const LOCAL_EMBEDDING_PROVIDER: &str = "ollama";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryConfig {
     #[serde(default = "default_enabled")]
@@ -23,6 +27,16 @@ pub struct MemoryConfig {
     #[serde(default = "default_embedding_api_base")]
     pub embedding_api_base: String,
 
+    /// Model used instead of `embedding_model` when running offline
+    #[serde(default = "default_local_embedding_model")]
+    pub local_embedding_model: String,
+
+    #[serde(default = "default_local_embedding_dimension")]
+    pub local_embedding_dimension: usize,
+
+    #[serde(default = "default_local_embedding_api_base")]
+    pub local_embedding_api_base: String,
+
     #[serde(default = "default_auto_recall")]
     pub auto_recall: bool,
 
@@ -85,6 +99,9 @@ impl Default for MemoryConfig {
             embedding_api_key_env: default_embedding_api_key_env(),
             embedding_api_base: default_embedding_api_base(),
             embedding_provider: default_embedding_provider(),
+            local_embedding_model: default_local_embedding_model(),
+            local_embedding_dimension: default_local_embedding_dimension(),
+            local_embedding_api_base: default_local_embedding_api_base(),
             auto_recall: default_auto_recall(),
             auto_capture: default_auto_capture(),
             max_recall_results: default_max_recall_results(),
@@ -106,6 +123,32 @@ impl Default for MemoryConfig {
     }
 }
 
+impl MemoryConfig {
+    /// Whether embeddings are computed without leaving the machine
+    pub fn uses_local_embeddings(&self) -> bool {
+        self.embedding_provider == LOCAL_EMBEDDING_PROVIDER
+    }
+
+    /// This config with embeddings from the local provider, for offline runs
+    ///
+    /// `None` when the local model's vectors do not fit the store: its vector
+    /// table is created with `embedding_dimension` and cannot mix sizes.
+    pub fn with_local_embeddings(&self) -> Option<Self> {
+        if self.uses_local_embeddings() {
+            return Some(self.clone());
+        }
+        if self.local_embedding_dimension != self.embedding_dimension {
+            return None;
+        }
+        Some(Self {
+            embedding_provider: LOCAL_EMBEDDING_PROVIDER.to_string(),
+            embedding_model: self.local_embedding_model.clone(),
+            embedding_api_base: self.local_embedding_api_base.clone(),
+            ..self.clone()
+        })
+    }
+}
+
 fn default_enabled() -> bool {
     false
 }
@@ -137,6 +180,19 @@ fn default_embedding_provider() -> String {
     "openai".to_string()
 }
 
+fn default_local_embedding_model() -> String {
+    "nomic-embed-text".to_string()
+}
+
+fn default_local_embedding_dimension() -> usize {
+    768
+}
+
+fn default_local_embedding_api_base() -> String {
+    let host = std::env::var("OLLAMA_HOST").unwrap_or_else(|_| "http://localhost:11434".into());
+    format!("{}/v1", host.trim_end_matches('/'))
+}
+
 fn default_auto_recall() -> bool {
     true
 }
@@ -235,6 +291,24 @@ mod tests {
         assert_eq!(config.embedding_provider, "openai");
     }
 
+    #[test]
+    fn switches_to_local_embeddings_when_dimensions_fit() {
+        let config = MemoryConfig::default();
+        assert!(!config.uses_local_embeddings());
+        assert!(config.with_local_embeddings().is_none());
+
+        let config = MemoryConfig {
+            embedding_dimension: 768,
+            local_embedding_api_base: "http://127.0.0.1:11434/v1".to_string(),
+            ..Default::default()
+        };
+        let local = config.with_local_embeddings().unwrap();
+        assert!(local.uses_local_embeddings());
+        assert_eq!(local.embedding_model, "nomic-embed-text");
+        assert_eq!(local.embedding_api_base, "http://127.0.0.1:11434/v1");
+        assert_eq!(local.storage_path, config.storage_path);
+    }
+
     #[test]
     fn deserialize_with_defaults() {
         let yaml = r#"
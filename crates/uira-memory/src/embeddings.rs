@@ -83,11 +83,15 @@ impl EmbeddingProvider for OpenAIEmbeddingProvider {
             input: texts.to_vec(),
         };
 
-        let response = self
+        let mut builder = self
             .client
             .post(format!("{}/embeddings", self.api_base))
-            .header("Authorization", format!("Bearer {}", self.api_key))
-            .header("Content-Type", "application/json")
+            .header("Content-Type", "application/json");
+        // Local OpenAI-compatible servers such as Ollama take no key
+        if !self.api_key.is_empty() {
+            builder = builder.header("Authorization", format!("Bearer {}", self.api_key));
+        }
+        let response = builder
             .json(&request)
             .send()
             .await
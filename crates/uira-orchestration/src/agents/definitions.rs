@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::Path;
 
 use super::types::{AgentConfig, AgentOverrides, AgentPromptMetadata, ModelType};
 use crate::config::apply_overrides;
@@ -29,6 +30,17 @@ pub fn get_agent_definitions_with_config(
     get_agent_definitions_with_loader(&loader, overrides, model_config)
 }
 
+/// Returns agent definitions with project prompt overrides applied: uira.yml
+/// `agents.<name>.prompt` and `.uira/prompts/<name>.md` under `project_root`.
+pub fn get_project_agent_definitions(
+    config: Option<&uira_core::UiraConfig>,
+    project_root: &Path,
+) -> HashMap<String, AgentConfig> {
+    let loader = PromptLoader::from_embedded_map(crate::prompts::EMBEDDED_PROMPTS)
+        .with_project_overrides(config, project_root);
+    get_agent_definitions_with_loader(&loader, None, None)
+}
+
 pub fn get_agent_definitions_with_loader(
     prompt_loader: &PromptLoader,
     overrides: Option<&AgentOverrides>,
@@ -257,10 +269,13 @@ fn insert(
                     "TodoWrite".to_string(),
                     "WebSearch".to_string(),
                     "WebFetch".to_string(),
+                    "docs_lookup".to_string(),
+                    "workspace_index".to_string(),
                     "delegate_task".to_string(),
                     "background_output".to_string(),
                     "background_cancel".to_string(),
                     "planning_pipeline".to_string(),
+                    "agent_lookup".to_string(),
                     "python_repl".to_string(),
                 ];
                 let mut tmp = AgentConfig {
@@ -11,16 +11,17 @@ pub mod tool_restrictions;
 pub mod types;
 
 pub use self::definitions::{
-    get_agent_definitions, get_agent_definitions_with_config, AgentModelConfig,
+    get_agent_definitions, get_agent_definitions_with_config, get_project_agent_definitions,
+    AgentModelConfig,
 };
-pub use self::models::{ModelRegistry, ProviderModels};
+pub use self::models::{ModelMetadata, ModelRegistry, ProviderModels};
 pub use self::orchestrator_prompts::OrchestratorPersonality;
 pub use self::planning_pipeline::{PlanningPipeline, PlanningStage};
-pub use self::prompt_loader::{PromptLoader, PromptSource};
+pub use self::prompt_loader::{project_prompts_dir, PromptFileMode, PromptLoader, PromptSource};
 pub use self::prompts::{get_embedded_prompt, EMBEDDED_PROMPTS};
 pub use self::registry::{AgentFactory, AgentRegistry};
 pub use self::tier_builder::{ModelTier, TierBuilder};
-pub use self::tool_restrictions::{ToolRestrictions, ToolRestrictionsRegistry};
+pub use self::tool_restrictions::{DelegationContext, ToolRestrictions, ToolRestrictionsRegistry};
 pub use self::types::{
     AgentCategory, AgentConfig, AgentCost, AgentOverrideConfig, AgentOverrides,
     AgentPromptMetadata, DelegationTrigger, ModelType, RoutingTier,
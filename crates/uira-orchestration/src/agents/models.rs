@@ -1,6 +1,7 @@
 //! Provider-specific model mapping
 //!
-//! Maps abstract model tiers (Opus/Sonnet/Haiku) to actual model IDs per provider.
+//! Maps abstract model tiers (Opus/Sonnet/Haiku) to actual model IDs per provider,
+//! and model IDs to their context window and list price.
 
 use super::types::ModelType;
 use std::collections::HashMap;
@@ -55,10 +56,63 @@ impl ProviderModels {
     }
 }
 
+/// Context window and list price of a model
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ModelMetadata {
+    pub context_window: usize,
+    /// USD per 1M input tokens
+    pub input_price: Option<f64>,
+    /// USD per 1M output tokens
+    pub output_price: Option<f64>,
+}
+
+impl ModelMetadata {
+    pub const fn new(context_window: usize, input_price: f64, output_price: f64) -> Self {
+        Self {
+            context_window,
+            input_price: Some(input_price),
+            output_price: Some(output_price),
+        }
+    }
+}
+
+/// Known model families by ID prefix; the longest matching prefix wins
+const MODEL_METADATA: &[(&str, ModelMetadata)] = &[
+    ("claude-opus-4", ModelMetadata::new(200_000, 15.0, 75.0)),
+    ("claude-sonnet-4", ModelMetadata::new(200_000, 3.0, 15.0)),
+    ("claude-3-7-sonnet", ModelMetadata::new(200_000, 3.0, 15.0)),
+    ("claude-3-5-sonnet", ModelMetadata::new(200_000, 3.0, 15.0)),
+    ("claude-3-5-haiku", ModelMetadata::new(200_000, 0.8, 4.0)),
+    ("claude-3-haiku", ModelMetadata::new(200_000, 0.25, 1.25)),
+    ("claude-3-opus", ModelMetadata::new(200_000, 15.0, 75.0)),
+    ("gpt-5", ModelMetadata::new(400_000, 1.25, 10.0)),
+    ("gpt-5-mini", ModelMetadata::new(400_000, 0.25, 2.0)),
+    ("gpt-5-nano", ModelMetadata::new(400_000, 0.05, 0.4)),
+    ("gpt-4.1", ModelMetadata::new(1_047_576, 2.0, 8.0)),
+    ("gpt-4.1-mini", ModelMetadata::new(1_047_576, 0.4, 1.6)),
+    ("gpt-4.1-nano", ModelMetadata::new(1_047_576, 0.1, 0.4)),
+    ("gpt-4o", ModelMetadata::new(128_000, 2.5, 10.0)),
+    ("gpt-4o-mini", ModelMetadata::new(128_000, 0.15, 0.6)),
+    ("o1", ModelMetadata::new(200_000, 15.0, 60.0)),
+    ("o1-mini", ModelMetadata::new(128_000, 1.1, 4.4)),
+    ("o3", ModelMetadata::new(200_000, 2.0, 8.0)),
+    ("o3-mini", ModelMetadata::new(200_000, 1.1, 4.4)),
+    ("o4-mini", ModelMetadata::new(200_000, 1.1, 4.4)),
+    ("gemini-2.5-pro", ModelMetadata::new(1_048_576, 1.25, 10.0)),
+    ("gemini-2.5-flash", ModelMetadata::new(1_048_576, 0.3, 2.5)),
+    ("gemini-2.0-flash", ModelMetadata::new(1_048_576, 0.1, 0.4)),
+    ("gemini-1.5-pro", ModelMetadata::new(2_097_152, 1.25, 5.0)),
+    (
+        "gemini-1.5-flash",
+        ModelMetadata::new(1_048_576, 0.075, 0.3),
+    ),
+];
+
 #[derive(Debug, Clone)]
 pub struct ModelRegistry {
     providers: HashMap<String, ProviderModels>,
     default_provider: String,
+    metadata: Vec<(String, ModelMetadata)>,
 }
 
 impl Default for ModelRegistry {
@@ -77,6 +131,10 @@ impl ModelRegistry {
         Self {
             providers,
             default_provider: "anthropic".to_string(),
+            metadata: MODEL_METADATA
+                .iter()
+                .map(|(prefix, metadata)| (prefix.to_string(), *metadata))
+                .collect(),
         }
     }
 
@@ -117,6 +175,26 @@ impl ModelRegistry {
     pub fn get_provider(&self, name: &str) -> Option<&ProviderModels> {
         self.providers.get(name)
     }
+
+    /// Register metadata for model IDs starting with `prefix`
+    pub fn with_metadata(mut self, prefix: impl Into<String>, metadata: ModelMetadata) -> Self {
+        self.metadata.push((prefix.into(), metadata));
+        self
+    }
+
+    /// Metadata for `model`, with or without a `provider/` prefix
+    pub fn metadata(&self, model: &str) -> Option<ModelMetadata> {
+        let id = model
+            .rsplit('/')
+            .next()
+            .unwrap_or(model)
+            .to_ascii_lowercase();
+        self.metadata
+            .iter()
+            .filter(|(prefix, _)| id.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map(|(_, metadata)| *metadata)
+    }
 }
 
 #[cfg(test)]
@@ -151,6 +229,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_metadata_uses_longest_prefix() {
+        let registry = ModelRegistry::new();
+        let mini = registry.metadata("openai/gpt-4o-mini-2024-07-18").unwrap();
+        assert_eq!(mini.context_window, 128_000);
+        assert_eq!(mini.input_price, Some(0.15));
+        assert_eq!(
+            registry
+                .metadata("claude-sonnet-4-20250514")
+                .unwrap()
+                .output_price,
+            Some(15.0)
+        );
+        assert!(registry.metadata("llama3.1").is_none());
+
+        let registry = registry.with_metadata(
+            "llama3",
+            ModelMetadata {
+                context_window: 8192,
+                input_price: None,
+                output_price: None,
+            },
+        );
+        assert_eq!(registry.metadata("llama3.1").unwrap().context_window, 8192);
+    }
+
     #[test]
     fn test_model_override() {
         let registry = ModelRegistry::new();
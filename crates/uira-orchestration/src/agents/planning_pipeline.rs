@@ -2,9 +2,25 @@
 //!
 //! Orchestrates the 3-stage planning flow: Analyst -> Planner -> Critic.
 //! Each stage generates a prompt that incorporates the output from the previous stage.
+//!
+//! Stage outputs are persisted as versioned artifacts under `.uira/plans/<plan_id>/`
+//! so an interrupted run can resume from the last completed stage.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uira_core::UIRA_DIR;
+
+/// Directory (relative to the project root) holding pipeline artifacts
+pub const PLAN_ARTIFACTS_DIR: &str = "plans";
+
+/// Manifest file written alongside each pipeline's stage artifacts
+pub const PIPELINE_MANIFEST_FILE: &str = "pipeline.json";
 
 /// Stages in the planning pipeline
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum PlanningStage {
     /// Pre-planning analysis: intent classification, ambiguity detection, risk assessment
     Analysis,
@@ -41,30 +57,75 @@ impl PlanningStage {
             Self::Review => super::prompts::CRITIC_PROMPT,
         }
     }
+
+    /// Get the artifact name used when persisting this stage's output
+    pub fn artifact_name(&self) -> &'static str {
+        match self {
+            Self::Analysis => "requirements",
+            Self::Planning => "plan",
+            Self::Review => "review",
+        }
+    }
+
+    fn index(&self) -> usize {
+        match self {
+            Self::Analysis => 0,
+            Self::Planning => 1,
+            Self::Review => 2,
+        }
+    }
 }
 
 /// Represents the output from a pipeline stage
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StageOutput {
     pub stage: PlanningStage,
     pub content: String,
+    /// Artifact version (incremented each time the stage is re-run)
+    #[serde(default = "default_version")]
+    pub version: u32,
+    pub completed_at: DateTime<Utc>,
+}
+
+fn default_version() -> u32 {
+    1
 }
 
 /// Planning pipeline that coordinates analyst -> planner -> critic flow
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PlanningPipeline {
+    /// Unique pipeline identifier (also the artifact directory name)
+    pub id: String,
     /// The original user request
     pub request: String,
     /// Accumulated stage outputs
     pub stages: Vec<StageOutput>,
+    /// Earlier stage outputs discarded by [`PlanningPipeline::rewind_to`]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub superseded: Vec<StageOutput>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
 }
 
 impl PlanningPipeline {
     /// Create a new pipeline for a user request
     pub fn new(request: impl Into<String>) -> Self {
+        let now = Utc::now();
         Self {
+            id: format!("plan-{}", now.format("%Y%m%d-%H%M%S-%3f")),
             request: request.into(),
             stages: Vec::new(),
+            superseded: Vec::new(),
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Create a new pipeline with an explicit identifier
+    pub fn with_id(id: impl Into<String>, request: impl Into<String>) -> Self {
+        Self {
+            id: id.into(),
+            ..Self::new(request)
         }
     }
 
@@ -139,7 +200,106 @@ impl PlanningPipeline {
     /// Record the output from the current stage and advance
     pub fn record_output(&mut self, content: String) {
         let stage = self.current_stage();
-        self.stages.push(StageOutput { stage, content });
+        let version = self.superseded.iter().filter(|s| s.stage == stage).count() as u32 + 1;
+        self.stages.push(StageOutput {
+            stage,
+            content,
+            version,
+            completed_at: Utc::now(),
+        });
+        self.updated_at = Utc::now();
+    }
+
+    /// Discard the output of `stage` and every later stage so they can be re-run
+    ///
+    /// Re-running a stage produces a new artifact version; earlier versions stay on disk.
+    pub fn rewind_to(&mut self, stage: PlanningStage) {
+        let keep = stage.index().min(self.stages.len());
+        self.superseded.extend(self.stages.drain(keep..));
+        self.updated_at = Utc::now();
+    }
+
+    /// Directory holding this pipeline's artifacts
+    pub fn artifact_dir(&self, directory: impl AsRef<Path>) -> PathBuf {
+        plans_dir(directory).join(&self.id)
+    }
+
+    /// Path of the artifact file for a stage output
+    pub fn artifact_path(&self, directory: impl AsRef<Path>, output: &StageOutput) -> PathBuf {
+        self.artifact_dir(directory).join(format!(
+            "{:02}-{}.v{}.md",
+            output.stage.index() + 1,
+            output.stage.artifact_name(),
+            output.version
+        ))
+    }
+
+    /// Persist the manifest and every completed stage's artifact
+    ///
+    /// Existing artifact files are never overwritten, so earlier versions are preserved.
+    pub fn save(&self, directory: impl AsRef<Path>) -> std::io::Result<PathBuf> {
+        if !Self::is_valid_id(&self.id) {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidInput,
+                format!("invalid planning pipeline id '{}'", self.id),
+            ));
+        }
+        let directory = directory.as_ref();
+        let dir = self.artifact_dir(directory);
+        fs::create_dir_all(&dir)?;
+
+        for output in &self.stages {
+            let path = self.artifact_path(directory, output);
+            if !path.exists() {
+                fs::write(&path, &output.content)?;
+            }
+        }
+
+        let manifest = serde_json::to_string_pretty(self)
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+        fs::write(dir.join(PIPELINE_MANIFEST_FILE), manifest)?;
+        Ok(dir)
+    }
+
+    /// Whether `id` can name a pipeline directory: non-empty and only ASCII
+    /// alphanumerics, hyphens and underscores, so it can't leave the plans
+    /// directory
+    pub fn is_valid_id(id: &str) -> bool {
+        !id.is_empty()
+            && id
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    }
+
+    /// Load a persisted pipeline by identifier
+    pub fn load(directory: impl AsRef<Path>, id: &str) -> Option<Self> {
+        if !Self::is_valid_id(id) {
+            return None;
+        }
+        let manifest = plans_dir(directory).join(id).join(PIPELINE_MANIFEST_FILE);
+        let content = fs::read_to_string(manifest).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// List persisted pipelines, most recently updated first
+    pub fn list(directory: impl AsRef<Path>) -> Vec<Self> {
+        let Ok(entries) = fs::read_dir(plans_dir(directory.as_ref())) else {
+            return Vec::new();
+        };
+
+        let mut pipelines: Vec<Self> = entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().is_dir())
+            .filter_map(|e| Self::load(directory.as_ref(), &e.file_name().to_string_lossy()))
+            .collect();
+
+        pipelines.sort_by_key(|p| std::cmp::Reverse(p.updated_at));
+        pipelines
+    }
+
+    /// Load the most recently updated pipeline that has not finished all stages
+    pub fn latest_incomplete(directory: impl AsRef<Path>) -> Option<Self> {
+        Self::list(directory).into_iter().find(|p| !p.is_complete())
     }
 
     /// Check if the critic approved the plan (looks for [OKAY] in last stage output)
@@ -159,6 +319,11 @@ impl PlanningPipeline {
     }
 }
 
+/// Directory holding all persisted planning pipelines
+pub fn plans_dir(directory: impl AsRef<Path>) -> PathBuf {
+    directory.as_ref().join(UIRA_DIR).join(PLAN_ARTIFACTS_DIR)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -257,4 +422,60 @@ mod tests {
         pipeline.record_output("The actual plan content".to_string());
         assert_eq!(pipeline.get_plan(), Some("The actual plan content"));
     }
+
+    #[test]
+    fn test_save_and_load_artifacts() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut pipeline = PlanningPipeline::with_id("plan-test", "Add auth");
+        pipeline.record_output("Requirements".to_string());
+        let artifact_dir = pipeline.save(dir.path()).unwrap();
+
+        assert!(artifact_dir.join("01-requirements.v1.md").exists());
+        assert!(artifact_dir.join(PIPELINE_MANIFEST_FILE).exists());
+
+        let loaded = PlanningPipeline::load(dir.path(), "plan-test").unwrap();
+        assert_eq!(loaded.request, "Add auth");
+        assert_eq!(loaded.current_stage(), PlanningStage::Planning);
+
+        let latest = PlanningPipeline::latest_incomplete(dir.path()).unwrap();
+        assert_eq!(latest.id, "plan-test");
+    }
+
+    #[test]
+    fn test_ids_cannot_leave_plans_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(PlanningPipeline::is_valid_id("plan-1700000000000-3"));
+        assert!(PlanningPipeline::is_valid_id(
+            &PlanningPipeline::new("x").id
+        ));
+
+        for id in ["", "..", "../../x", "a/b", "a\\b", "plan.v1"] {
+            assert!(!PlanningPipeline::is_valid_id(id), "{id}");
+            assert!(PlanningPipeline::with_id(id, "x").save(dir.path()).is_err());
+            assert!(PlanningPipeline::load(dir.path(), id).is_none());
+        }
+        assert!(!dir.path().join("x").exists());
+    }
+
+    #[test]
+    fn test_rewind_creates_new_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut pipeline = PlanningPipeline::with_id("plan-rewind", "Refactor");
+        pipeline.record_output("Analysis".to_string());
+        pipeline.record_output("Plan v1".to_string());
+        pipeline.record_output("[REJECT] Missing tests".to_string());
+        pipeline.save(dir.path()).unwrap();
+
+        pipeline.rewind_to(PlanningStage::Planning);
+        assert_eq!(pipeline.current_stage(), PlanningStage::Planning);
+        pipeline.record_output("Plan v2".to_string());
+        assert_eq!(pipeline.stages[1].version, 2);
+        let artifact_dir = pipeline.save(dir.path()).unwrap();
+
+        let v1 = fs::read_to_string(artifact_dir.join("02-plan.v1.md")).unwrap();
+        let v2 = fs::read_to_string(artifact_dir.join("02-plan.v2.md")).unwrap();
+        assert_eq!(v1, "Plan v1");
+        assert_eq!(v2, "Plan v2");
+        assert_eq!(pipeline.get_plan(), Some("Plan v2"));
+    }
 }
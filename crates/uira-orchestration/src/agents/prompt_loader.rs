@@ -1,5 +1,8 @@
+use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use uira_core::schema::PromptOverrideConfig;
+use uira_core::UIRA_DIR;
 
 /// How prompts should be resolved.
 #[derive(Debug, Clone)]
@@ -20,36 +23,133 @@ pub enum PromptSource {
 }
 
 /// Loads agent prompts and strips YAML frontmatter (if present).
+///
+/// Project overrides are layered on top of the base prompt in order:
+/// `agents.<name>.prompt` from uira.yml, then `<override_dir>/<name>.md`.
 #[derive(Debug, Clone)]
 pub struct PromptLoader {
     source: PromptSource,
+    overrides: HashMap<String, PromptOverrideConfig>,
+    override_dir: Option<PathBuf>,
+}
+
+/// How a `.uira/prompts/<agent>.md` file is merged into the base prompt,
+/// selected with a `mode:` frontmatter key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PromptFileMode {
+    /// `## Heading` sections replace same-named sections (unknown ones are
+    /// added); text before the first heading is appended
+    Sections,
+    /// The file is appended to the prompt
+    Append,
+    /// The file replaces the prompt
+    Replace,
+}
+
+/// Project prompt overrides directory (`.uira/prompts`) under `project_root`
+pub fn project_prompts_dir(project_root: &Path) -> PathBuf {
+    project_root.join(UIRA_DIR).join("prompts")
 }
 
 impl PromptLoader {
     pub fn from_fs(root_dir: impl Into<PathBuf>) -> Self {
-        Self {
-            source: PromptSource::FileSystem {
-                root_dir: root_dir.into(),
-            },
-        }
+        Self::from_source(PromptSource::FileSystem {
+            root_dir: root_dir.into(),
+        })
     }
 
     pub fn from_embedded(name: impl Into<String>, content: &'static str) -> Self {
-        Self {
-            source: PromptSource::Embedded {
-                name: name.into(),
-                content,
-            },
-        }
+        Self::from_source(PromptSource::Embedded {
+            name: name.into(),
+            content,
+        })
     }
 
     pub fn from_embedded_map(prompts: &'static [(&'static str, &'static str)]) -> Self {
+        Self::from_source(PromptSource::EmbeddedMap { prompts })
+    }
+
+    fn from_source(source: PromptSource) -> Self {
         Self {
-            source: PromptSource::EmbeddedMap { prompts },
+            source,
+            overrides: HashMap::new(),
+            override_dir: None,
         }
     }
 
+    /// Apply per-agent prompt overrides from config
+    pub fn with_overrides(mut self, overrides: HashMap<String, PromptOverrideConfig>) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Merge `<dir>/<agent>.md` override files when present
+    pub fn with_override_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.override_dir = Some(dir.into());
+        self
+    }
+
+    /// Apply the uira.yml `agents.<name>.prompt` overrides and the
+    /// `.uira/prompts` directory of `project_root`
+    pub fn with_project_overrides(
+        self,
+        config: Option<&uira_core::UiraConfig>,
+        project_root: &Path,
+    ) -> Self {
+        let overrides = config
+            .map(|config| {
+                config
+                    .agents
+                    .agents
+                    .iter()
+                    .filter_map(|(name, agent)| Some((name.clone(), agent.prompt.clone()?)))
+                    .collect()
+            })
+            .unwrap_or_default();
+        self.with_overrides(overrides)
+            .with_override_dir(project_prompts_dir(project_root))
+    }
+
+    /// Load the prompt for `agent_name` with all overrides applied
     pub fn load(&self, agent_name: &str) -> String {
+        let mut prompt = self.load_base(agent_name);
+
+        if let Some(config) = self.overrides.get(agent_name) {
+            prompt = apply_override_config(&prompt, config);
+        }
+        if let Some((mode, content)) = self.override_file(agent_name) {
+            prompt = apply_override_file(&prompt, mode, &content);
+        }
+
+        prompt
+    }
+
+    /// Describe which overrides apply to `agent_name`, in merge order
+    pub fn override_sources(&self, agent_name: &str) -> Vec<String> {
+        let mut sources = Vec::new();
+        if self.overrides.contains_key(agent_name) {
+            sources.push(format!("config: agents.{agent_name}.prompt"));
+        }
+        if let Some(dir) = &self.override_dir {
+            let path = dir.join(format!("{agent_name}.md"));
+            if let Some((mode, _)) = self.override_file(agent_name) {
+                sources.push(format!("file: {} ({})", path.display(), mode.as_str()));
+            }
+        }
+        sources
+    }
+
+    fn override_file(&self, agent_name: &str) -> Option<(PromptFileMode, String)> {
+        let path = self.override_dir.as_ref()?.join(format!("{agent_name}.md"));
+        let content = fs::read_to_string(path).ok()?;
+        let mode = frontmatter_value(&content, "mode")
+            .and_then(|mode| PromptFileMode::parse(&mode))
+            .unwrap_or(PromptFileMode::Sections);
+        Some((mode, strip_yaml_frontmatter(&content)))
+    }
+
+    /// Load the prompt for `agent_name` without project overrides
+    pub fn load_base(&self, agent_name: &str) -> String {
         match &self.source {
             PromptSource::FileSystem { root_dir } => {
                 let path = root_dir.join(format!("{agent_name}.md"));
@@ -77,6 +177,176 @@ impl PromptLoader {
     }
 }
 
+impl PromptFileMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "sections" => Some(Self::Sections),
+            "append" => Some(Self::Append),
+            "replace" => Some(Self::Replace),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Sections => "sections",
+            Self::Append => "append",
+            Self::Replace => "replace",
+        }
+    }
+}
+
+fn apply_override_config(prompt: &str, config: &PromptOverrideConfig) -> String {
+    let mut prompt = match &config.replace {
+        Some(replacement) => replacement.trim().to_string(),
+        None => prompt.to_string(),
+    };
+
+    // Sort so the result does not depend on map iteration order
+    let mut sections: Vec<_> = config.sections.iter().collect();
+    sections.sort_by(|a, b| a.0.cmp(b.0));
+    for (heading, section) in sections {
+        if let Some(replacement) = &section.replace {
+            prompt = replace_section(&prompt, heading, replacement);
+        }
+        if let Some(addition) = &section.append {
+            prompt = append_to_section(&prompt, heading, addition);
+        }
+    }
+
+    match &config.append {
+        Some(addition) => append_block(&prompt, addition),
+        None => prompt,
+    }
+}
+
+fn apply_override_file(prompt: &str, mode: PromptFileMode, content: &str) -> String {
+    match mode {
+        PromptFileMode::Replace => content.trim().to_string(),
+        PromptFileMode::Append => append_block(prompt, content),
+        PromptFileMode::Sections => {
+            let (preamble, sections) = split_sections(content);
+            let mut prompt = prompt.to_string();
+            for (heading, body) in sections {
+                prompt = replace_section(&prompt, &heading, &body);
+            }
+            append_block(&prompt, &preamble)
+        }
+    }
+}
+
+fn append_block(prompt: &str, addition: &str) -> String {
+    let addition = addition.trim();
+    if addition.is_empty() {
+        return prompt.to_string();
+    }
+    if prompt.is_empty() {
+        return addition.to_string();
+    }
+    format!("{}\n\n{}", prompt.trim_end(), addition)
+}
+
+/// Markdown heading level and text, e.g. `## Must Do` -> (2, "Must Do")
+fn parse_heading(line: &str) -> Option<(usize, &str)> {
+    let level = line.chars().take_while(|c| *c == '#').count();
+    if level == 0 || level > 6 {
+        return None;
+    }
+    let text = line[level..].strip_prefix(' ')?;
+    Some((level, text.trim()))
+}
+
+/// Line range of the section body under `heading`: from the line after the
+/// heading to the next heading of the same or a higher level
+fn find_section(lines: &[&str], heading: &str) -> Option<(usize, usize)> {
+    let (start, level) = lines.iter().enumerate().find_map(|(i, line)| {
+        let (level, text) = parse_heading(line)?;
+        text.eq_ignore_ascii_case(heading.trim())
+            .then_some((i + 1, level))
+    })?;
+    let end = lines[start..]
+        .iter()
+        .position(|line| parse_heading(line).is_some_and(|(other, _)| other <= level))
+        .map_or(lines.len(), |offset| start + offset);
+    Some((start, end))
+}
+
+fn rebuild(lines: &[&str], start: usize, end: usize, body: &str) -> String {
+    let mut out: Vec<&str> = lines[..start].to_vec();
+    out.push("");
+    out.extend(body.trim().lines());
+    if end < lines.len() {
+        out.push("");
+        out.extend(&lines[end..]);
+    }
+    out.join("\n").trim().to_string()
+}
+
+fn replace_section(prompt: &str, heading: &str, body: &str) -> String {
+    let lines: Vec<&str> = prompt.lines().collect();
+    match find_section(&lines, heading) {
+        Some((start, end)) => rebuild(&lines, start, end, body),
+        None => append_block(prompt, &format!("## {}\n\n{}", heading.trim(), body.trim())),
+    }
+}
+
+fn append_to_section(prompt: &str, heading: &str, addition: &str) -> String {
+    let lines: Vec<&str> = prompt.lines().collect();
+    match find_section(&lines, heading) {
+        Some((start, end)) => {
+            let existing = lines[start..end].join("\n");
+            rebuild(&lines, start, end, &append_block(existing.trim(), addition))
+        }
+        None => append_block(
+            prompt,
+            &format!("## {}\n\n{}", heading.trim(), addition.trim()),
+        ),
+    }
+}
+
+/// Split an override file into its preamble and `## Heading` sections
+fn split_sections(content: &str) -> (String, Vec<(String, String)>) {
+    let mut preamble = Vec::new();
+    let mut sections: Vec<(String, Vec<&str>)> = Vec::new();
+
+    for line in content.lines() {
+        match parse_heading(line) {
+            Some((2, text)) => sections.push((text.to_string(), Vec::new())),
+            _ => match sections.last_mut() {
+                Some((_, body)) => body.push(line),
+                None => preamble.push(line),
+            },
+        }
+    }
+
+    (
+        preamble.join("\n").trim().to_string(),
+        sections
+            .into_iter()
+            .map(|(heading, body)| (heading, body.join("\n").trim().to_string()))
+            .collect(),
+    )
+}
+
+/// Read a top-level `key: value` from YAML frontmatter, if present
+fn frontmatter_value(content: &str, key: &str) -> Option<String> {
+    let mut lines = content.trim_start().lines();
+    if lines.next()?.trim() != "---" {
+        return None;
+    }
+    for line in lines {
+        if line.trim() == "---" {
+            return None;
+        }
+        if let Some((k, v)) = line.split_once(':') {
+            if k.trim() == key {
+                return Some(v.trim().trim_matches(['"', '\'']).to_string());
+            }
+        }
+    }
+    None
+}
+
 /// Convenience macro for embedding prompts with `include_str!()`.
 ///
 /// Example:
@@ -164,6 +434,68 @@ mod tests {
         assert_eq!(strip_yaml_frontmatter(md), "---\nname: test\nHello");
     }
 
+    #[test]
+    fn config_overrides_replace_and_append_sections() {
+        use uira_core::schema::PromptSectionOverride;
+
+        let base = "# Agent\n\nIntro\n\n## Must Do\n\n- Read first\n\n### Detail\n\nx\n\n## Must Not Do\n\n- Break things";
+        let config = PromptOverrideConfig {
+            replace: None,
+            append: Some("Team note".to_string()),
+            sections: HashMap::from([
+                (
+                    "must do".to_string(),
+                    PromptSectionOverride {
+                        replace: None,
+                        append: Some("- Run fmt".to_string()),
+                    },
+                ),
+                (
+                    "Must Not Do".to_string(),
+                    PromptSectionOverride {
+                        replace: Some("- Push to main".to_string()),
+                        append: None,
+                    },
+                ),
+                (
+                    "Style".to_string(),
+                    PromptSectionOverride {
+                        replace: Some("Be terse".to_string()),
+                        append: None,
+                    },
+                ),
+            ]),
+        };
+
+        assert_eq!(
+            apply_override_config(base, &config),
+            "# Agent\n\nIntro\n\n## Must Do\n\n- Read first\n\n### Detail\n\nx\n\n- Run fmt\n\n\
+             ## Must Not Do\n\n- Push to main\n\n## Style\n\nBe terse\n\nTeam note"
+        );
+    }
+
+    #[test]
+    fn override_files_merge_by_mode() {
+        static PROMPTS: &[(&str, &str)] =
+            &[("a", "# A\n\n## Rules\n\nold"), ("b", "# B"), ("c", "# C")];
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(tmp.path().join("a.md"), "Extra context\n\n## Rules\n\nnew").unwrap();
+        fs::write(
+            tmp.path().join("b.md"),
+            "---\nmode: replace\n---\n# Custom B",
+        )
+        .unwrap();
+        fs::write(tmp.path().join("c.md"), "---\nmode: append\n---\nMore").unwrap();
+
+        let loader = PromptLoader::from_embedded_map(PROMPTS).with_override_dir(tmp.path());
+        assert_eq!(loader.load("a"), "# A\n\n## Rules\n\nnew\n\nExtra context");
+        assert_eq!(loader.load("b"), "# Custom B");
+        assert_eq!(loader.load("c"), "# C\n\nMore");
+        assert_eq!(loader.load_base("b"), "# B");
+        assert_eq!(loader.override_sources("b").len(), 1);
+        assert!(loader.override_sources("missing").is_empty());
+    }
+
     #[test]
     fn fs_loader_falls_back_when_missing() {
         let tmp = tempfile::tempdir().unwrap();
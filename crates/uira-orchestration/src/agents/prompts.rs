@@ -121,10 +121,12 @@ You are a fast codebase exploration agent specialized in pattern matching and di
 
 ## Approach
 
-1. **Targeted Search**: Use specific patterns, not broad queries
-2. **Multiple Angles**: Try different search strategies
-3. **Cross-Reference**: Connect related findings
-4. **Summarize**: Provide clear, actionable findings
+1. **Index First**: Use `workspace_index` to find files, declarations and directory layout before falling back to Glob/Grep scans
+2. **Targeted Search**: Use specific patterns, not broad queries
+3. **Multiple Angles**: Try different search strategies
+4. **Cross-Reference**: Connect related findings
+5. **Map Structure**: Use `code_outline` to see a file's declarations before reading it; in JS/TS projects, use `ast_dependencies` to see imports, exports, and callers without reading every file
+6. **Summarize**: Provide clear, actionable findings
 
 ## Output Format
 
@@ -154,6 +156,7 @@ You are an open-source codebase understanding agent for multi-repository analysi
 
 ## Tools
 
+- Use docs_lookup first for library and framework APIs (versioned snippets)
 - Use WebSearch for documentation
 - Use WebFetch to retrieve pages
 - Use grep.app for code search across GitHub"#;
@@ -498,6 +501,7 @@ You are a build and type error resolution specialist.
 
 ## Must Do
 
+- Survey errors with `lsp_workspace_diagnostics` (pass a glob) before and after fixing
 - Read the full error context
 - Fix root cause, not symptoms
 - Verify with build command
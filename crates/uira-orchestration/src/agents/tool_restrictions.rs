@@ -1,6 +1,9 @@
 use std::collections::{HashMap, HashSet};
 
 use super::types::AgentConfig;
+use crate::features::consensus::ConsensusPanel;
+use crate::tools::ToolError;
+use uira_core::schema::OutputContract;
 
 /// Tool restrictions expressed as an allowlist.
 #[derive(Debug, Clone, Default, PartialEq)]
@@ -16,20 +19,100 @@ impl ToolRestrictions {
     {
         let allowed = tools
             .into_iter()
-            .map(|t| t.into())
-            .map(|t| t.to_lowercase())
+            .map(|t| canonical_tool_name(&t.into()))
             .collect();
         Self { allowed }
     }
 
     pub fn allows(&self, tool: &str) -> bool {
-        self.allowed.contains(&tool.to_lowercase())
+        self.allowed.contains(&canonical_tool_name(tool))
     }
 
     pub fn apply_to_config(&self, config: &mut AgentConfig) {
-        config
-            .tools
-            .retain(|t| self.allowed.contains(&t.to_lowercase()));
+        config.tools.retain(|t| self.allows(t));
+    }
+}
+
+/// Lowercased tool name, with the web tools' runtime names folded onto the
+/// names used in the allowlists
+fn canonical_tool_name(tool: &str) -> String {
+    let tool = tool.to_lowercase();
+    match tool.as_str() {
+        "web_search" => "websearch".to_string(),
+        "fetch_url" => "webfetch".to_string(),
+        _ => tool,
+    }
+}
+
+/// The agent a delegated session runs as, and the tools it may call
+#[derive(Debug, Clone, PartialEq)]
+pub struct DelegationContext {
+    pub agent: String,
+    /// `None` when neither the agent nor the delegating call restricts tools
+    pub restrictions: Option<ToolRestrictions>,
+    /// Higher-tier model to switch to when speculative routing escalates
+    pub escalation_model: Option<String>,
+    /// Panel that also answers plans and reviews under the `consensus`
+    /// strategy
+    pub consensus: Option<ConsensusPanel>,
+    /// Format the subagent's final message must follow
+    pub output_contract: Option<OutputContract>,
+}
+
+impl DelegationContext {
+    /// Context for `agent` using its default allowlist, narrowed to
+    /// `allowed_tools` when the delegating call passes one
+    pub fn for_agent(agent: impl Into<String>, allowed_tools: Option<Vec<String>>) -> Self {
+        let agent = agent.into();
+        let registry = ToolRestrictionsRegistry::with_default_allowlists();
+        let restrictions = match (registry.get(&agent), allowed_tools) {
+            (Some(defaults), Some(tools)) => Some(ToolRestrictions::from_allowlist(
+                tools.into_iter().filter(|t| defaults.allows(t)),
+            )),
+            (Some(defaults), None) => Some(defaults.clone()),
+            (None, Some(tools)) => Some(ToolRestrictions::from_allowlist(tools)),
+            (None, None) => None,
+        };
+        Self {
+            agent,
+            restrictions,
+            escalation_model: None,
+            consensus: None,
+            output_contract: None,
+        }
+    }
+
+    pub fn with_escalation_model(mut self, model: Option<String>) -> Self {
+        self.escalation_model = model;
+        self
+    }
+
+    pub fn with_consensus(mut self, panel: Option<ConsensusPanel>) -> Self {
+        self.consensus = panel;
+        self
+    }
+
+    pub fn with_output_contract(mut self, contract: Option<OutputContract>) -> Self {
+        self.output_contract = contract;
+        self
+    }
+
+    pub fn permits(&self, tool: &str) -> bool {
+        self.restrictions
+            .as_ref()
+            .is_none_or(|restrictions| restrictions.allows(tool))
+    }
+
+    /// Fail with [`ToolError::NotPermitted`] unless `tool` is allowed
+    pub fn check(&self, tool: &str) -> Result<(), ToolError> {
+        if self.permits(tool) {
+            Ok(())
+        } else {
+            Err(ToolError::NotPermitted {
+                tool: tool.to_string(),
+                agent: self.agent.clone(),
+            })
+        }
     }
 }
 
@@ -68,9 +151,27 @@ impl ToolRestrictionsRegistry {
         );
         reg.register(
             "librarian",
-            allow(&["Read", "Glob", "Grep", "WebSearch", "WebFetch", "Bash"]),
+            allow(&[
+                "Read",
+                "Glob",
+                "Grep",
+                "WebSearch",
+                "WebFetch",
+                "docs_lookup",
+                "Bash",
+            ]),
+        );
+        reg.register(
+            "explore",
+            allow(&[
+                "Read",
+                "Glob",
+                "Grep",
+                "ast_dependencies",
+                "code_outline",
+                "workspace_index",
+            ]),
         );
-        reg.register("explore", allow(&["Read", "Glob", "Grep"]));
         reg.register(
             "executor",
             allow(&["Read", "Glob", "Grep", "Edit", "Write", "Bash", "TodoWrite"]),
@@ -157,6 +258,7 @@ impl ToolRestrictionsRegistry {
                 "background_output",
                 "background_cancel",
                 "planning_pipeline",
+                "agent_lookup",
             ]),
         );
         reg.register(
@@ -173,6 +275,7 @@ impl ToolRestrictionsRegistry {
                 "background_output",
                 "background_cancel",
                 "planning_pipeline",
+                "agent_lookup",
             ]),
         );
 
@@ -245,4 +348,29 @@ mod tests {
         assert!(reg.get("nonexistent-agent").is_none());
         assert!(reg.get("architect-ultra").is_none());
     }
+
+    #[test]
+    fn delegation_context_narrows_agent_allowlist() {
+        let architect = DelegationContext::for_agent("architect", None);
+        assert!(architect.permits("web_search"));
+        assert!(architect.permits("fetch_url"));
+        assert!(!architect.permits("Edit"));
+
+        let narrowed = DelegationContext::for_agent(
+            "architect",
+            Some(vec!["Read".to_string(), "Edit".to_string()]),
+        );
+        assert!(narrowed.permits("Read"));
+        assert!(!narrowed.permits("Edit"));
+        assert!(!narrowed.permits("Grep"));
+        assert_eq!(
+            narrowed.check("Grep"),
+            Err(ToolError::NotPermitted {
+                tool: "Grep".to_string(),
+                agent: "architect".to_string(),
+            })
+        );
+
+        assert!(DelegationContext::for_agent("custom", None).permits("Edit"));
+    }
 }
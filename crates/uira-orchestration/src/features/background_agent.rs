@@ -1,12 +1,14 @@
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
-use std::sync::atomic::AtomicBool;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Condvar, Mutex};
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, SecondsFormat, Utc};
 use serde::{Deserialize, Serialize};
+use tokio_util::sync::CancellationToken;
+use uira_core::schema::{BackgroundTaskSettings, TaskFailureKind, TaskRetrySettings};
 use uira_core::UIRA_DIR;
 
 /// Type alias for stale session callback to reduce type complexity.
@@ -25,7 +27,18 @@ pub enum BackgroundTaskStatus {
 }
 
 impl BackgroundTaskStatus {
-    fn is_terminal(self) -> bool {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            BackgroundTaskStatus::Queued => "queued",
+            BackgroundTaskStatus::Pending => "pending",
+            BackgroundTaskStatus::Running => "running",
+            BackgroundTaskStatus::Completed => "completed",
+            BackgroundTaskStatus::Error => "error",
+            BackgroundTaskStatus::Cancelled => "cancelled",
+        }
+    }
+
+    pub fn is_terminal(self) -> bool {
         matches!(
             self,
             BackgroundTaskStatus::Completed
@@ -71,8 +84,23 @@ pub struct BackgroundTask {
     pub concurrency_key: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub parent_model: Option<String>,
+    /// PID of the process executing the task, used to recover it after a crash
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub owner_pid: Option<u32>,
+    /// Attempts started so far, including retries
+    #[serde(default)]
+    pub attempts: u32,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub failure_kind: Option<TaskFailureKind>,
+    /// The failed task this one was requeued from
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requeued_from: Option<String>,
+    /// The task that replaced this one after it failed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub requeued_as: Option<String>,
+    /// Cancels the subagent running this task
     #[serde(skip)]
-    pub cancel_signal: Option<Arc<AtomicBool>>,
+    pub cancel_signal: Option<CancellationToken>,
 }
 
 impl PartialEq for BackgroundTask {
@@ -92,6 +120,11 @@ impl PartialEq for BackgroundTask {
             && self.progress == other.progress
             && self.concurrency_key == other.concurrency_key
             && self.parent_model == other.parent_model
+            && self.owner_pid == other.owner_pid
+            && self.attempts == other.attempts
+            && self.failure_kind == other.failure_kind
+            && self.requeued_from == other.requeued_from
+            && self.requeued_as == other.requeued_as
         // Explicitly skip cancel_signal in comparison
     }
 }
@@ -136,6 +169,21 @@ pub struct BackgroundTaskConfig {
     pub stale_threshold_ms: Option<u64>,
     pub on_stale_session: Option<StaleSessionCallback>,
     pub storage_dir: Option<PathBuf>,
+    /// Where per-task output logs go; defaults to `logs/` under a custom
+    /// `storage_dir`, or `~/.claude/.uira/background`
+    pub log_dir: Option<PathBuf>,
+    /// How failed tasks are retried before they are dead-lettered
+    pub retry: TaskRetrySettings,
+}
+
+impl BackgroundTaskConfig {
+    /// Config with the `background_tasks` settings from `uira.yml` applied
+    pub fn from_settings(settings: &BackgroundTaskSettings) -> Self {
+        Self {
+            retry: settings.retry.clone(),
+            ..Self::default()
+        }
+    }
 }
 
 impl std::fmt::Debug for BackgroundTaskConfig {
@@ -153,6 +201,8 @@ impl std::fmt::Debug for BackgroundTaskConfig {
                 &self.on_stale_session.as_ref().map(|_| "<callback>"),
             )
             .field("storage_dir", &self.storage_dir)
+            .field("log_dir", &self.log_dir)
+            .field("retry", &self.retry)
             .finish()
     }
 }
@@ -267,6 +317,85 @@ impl ConcurrencyManager {
     }
 }
 
+/// Whether `pid` names a live process
+///
+/// PIDs can be reused, so a recycled PID keeps a lost task attached until the
+/// new process exits.
+fn is_process_alive(pid: u32) -> bool {
+    #[cfg(target_os = "linux")]
+    {
+        Path::new("/proc").join(pid.to_string()).exists()
+    }
+    #[cfg(all(unix, not(target_os = "linux")))]
+    {
+        std::process::Command::new("kill")
+            .args(["-0", &pid.to_string()])
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::null())
+            .status()
+            .is_ok_and(|status| status.success())
+    }
+    #[cfg(windows)]
+    {
+        std::process::Command::new("tasklist")
+            .args(["/FI", &format!("PID eq {pid}"), "/NH"])
+            .output()
+            .is_ok_and(|output| String::from_utf8_lossy(&output.stdout).contains(&pid.to_string()))
+    }
+    #[cfg(not(any(unix, windows)))]
+    {
+        let _ = pid;
+        true
+    }
+}
+
+/// Why a non-terminal task whose owner is gone can no longer finish
+fn lost_task_error(task: &BackgroundTask) -> String {
+    let owner = task.owner_pid.map_or_else(
+        || "an earlier session".to_string(),
+        |pid| format!("process {pid}"),
+    );
+    if task.status == BackgroundTaskStatus::Running {
+        format!("Task lost: {owner} exited before the task finished")
+    } else {
+        format!("Task lost: {owner} exited before the task started")
+    }
+}
+
+/// Classify a task error by the kind of failure it describes
+pub fn classify_failure(error: &str) -> TaskFailureKind {
+    let error = error.to_lowercase();
+    let mentions = |needles: &[&str]| needles.iter().any(|needle| error.contains(needle));
+
+    if mentions(&["429", "rate limit", "rate_limit", "too many requests"]) {
+        TaskFailureKind::RateLimit
+    } else if mentions(&["529", "503", "overloaded", "service unavailable"]) {
+        TaskFailureKind::Overloaded
+    } else if mentions(&["timed out", "timeout", "deadline exceeded"]) {
+        TaskFailureKind::Timeout
+    } else if mentions(&[
+        "connection",
+        "network",
+        "dns",
+        "broken pipe",
+        "error sending request",
+    ]) {
+        TaskFailureKind::Network
+    } else if mentions(&["panicked"]) {
+        TaskFailureKind::Panic
+    } else {
+        TaskFailureKind::Other
+    }
+}
+
+/// Delay before the retry that follows attempt `attempt` (1-based)
+fn backoff_delay(policy: &TaskRetrySettings, attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(i32::MAX as u32) as i32;
+    let delay = policy.backoff_ms as f64 * policy.backoff_multiplier.powi(exponent);
+    let delay = delay.clamp(0.0, policy.max_backoff_ms as f64);
+    Duration::from_millis(delay as u64)
+}
+
 fn default_storage_dir() -> PathBuf {
     let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
     PathBuf::from(home)
@@ -275,6 +404,46 @@ fn default_storage_dir() -> PathBuf {
         .join("background-tasks")
 }
 
+fn default_log_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home)
+        .join(".claude")
+        .join(UIRA_DIR)
+        .join("background")
+}
+
+/// Appends a background task's activity to its log file
+#[derive(Debug, Clone)]
+pub struct TaskLogger {
+    path: PathBuf,
+}
+
+impl TaskLogger {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Append a timestamped status line
+    pub fn event(&self, message: &str) {
+        let timestamp = Utc::now().to_rfc3339_opts(SecondsFormat::Secs, true);
+        self.output(&format!("[{timestamp}] {message}\n"));
+    }
+
+    /// Append raw output produced by the task
+    pub fn output(&self, text: &str) {
+        if let Some(parent) = self.path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        if let Ok(mut file) = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+        {
+            let _ = file.write_all(text.as_bytes());
+        }
+    }
+}
+
 #[derive(Debug)]
 pub struct BackgroundManager {
     tasks: Mutex<HashMap<String, BackgroundTask>>,
@@ -282,6 +451,15 @@ pub struct BackgroundManager {
     concurrency: ConcurrencyManager,
     config: BackgroundTaskConfig,
     storage_dir: PathBuf,
+    log_dir: PathBuf,
+}
+
+/// Tasks removed by [`BackgroundManager::prune_finished`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PrunedTasks {
+    pub task_ids: Vec<String>,
+    /// Size of the task records and logs removed
+    pub bytes: u64,
 }
 
 impl BackgroundManager {
@@ -290,12 +468,19 @@ impl BackgroundManager {
             .storage_dir
             .clone()
             .unwrap_or_else(default_storage_dir);
+        let log_dir = config.log_dir.clone().unwrap_or_else(|| {
+            config
+                .storage_dir
+                .as_ref()
+                .map_or_else(default_log_dir, |dir| dir.join("logs"))
+        });
         let manager = Self {
             tasks: Mutex::new(HashMap::new()),
             notifications: Mutex::new(HashMap::new()),
             concurrency: ConcurrencyManager::new(config.clone()),
             config,
             storage_dir,
+            log_dir,
         };
 
         manager.ensure_storage_dir();
@@ -339,35 +524,174 @@ impl BackgroundManager {
         self.storage_dir.join(format!("{task_id}.json"))
     }
 
+    pub fn log_path(&self, task_id: &str) -> PathBuf {
+        self.log_dir.join(format!("{task_id}.log"))
+    }
+
+    pub fn task_logger(&self, task_id: &str) -> TaskLogger {
+        TaskLogger {
+            path: self.log_path(task_id),
+        }
+    }
+
+    /// Read a task's log from byte `offset`, returning the text and the offset
+    /// to continue from
+    pub fn read_log(&self, task_id: &str, offset: u64) -> std::io::Result<(String, u64)> {
+        let mut file = fs::File::open(self.log_path(task_id))?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut bytes = Vec::new();
+        file.read_to_end(&mut bytes)?;
+
+        // Leave a multi-byte character cut off mid-write for the next read
+        let len = match std::str::from_utf8(&bytes) {
+            Ok(_) => bytes.len(),
+            Err(e) if e.error_len().is_none() => e.valid_up_to(),
+            Err(_) => bytes.len(),
+        };
+        let text = String::from_utf8_lossy(&bytes[..len]).into_owned();
+        Ok((text, offset + len as u64))
+    }
+
     fn persist_task(&self, task: &BackgroundTask) {
         let path = self.task_path(&task.id);
         let Ok(payload) = serde_json::to_string_pretty(task) else {
             return;
         };
-        let _ = fs::write(path, payload);
+        // Write-then-rename so a crash mid-write never leaves a truncated task file
+        let tmp = path.with_extension("json.tmp");
+        if fs::write(&tmp, payload).is_ok() {
+            let _ = fs::rename(tmp, path);
+        }
     }
 
+    fn read_task_file(path: &Path) -> Option<BackgroundTask> {
+        let content = fs::read_to_string(path).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    /// Whether `task` is unfinished and owned by another process
+    fn is_foreign(task: &BackgroundTask) -> bool {
+        !task.status.is_terminal() && task.owner_pid.is_some_and(|pid| pid != std::process::id())
+    }
+
+    /// Whether another live process is executing `task`
+    fn is_attached(task: &BackgroundTask) -> bool {
+        Self::is_foreign(task) && task.owner_pid.is_some_and(is_process_alive)
+    }
+
+    /// Load persisted tasks, re-attaching to tasks still owned by a live process
+    /// and failing the ones whose process is gone
     fn load_persisted_tasks(&self) {
         let Ok(entries) = fs::read_dir(&self.storage_dir) else {
             return;
         };
 
-        let mut tasks = self.tasks.lock().expect("lock");
-        for entry in entries.flatten() {
-            let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) != Some("json") {
-                continue;
+        let mut lost = Vec::new();
+        {
+            let mut tasks = self.tasks.lock().expect("lock");
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if path.extension().and_then(|s| s.to_str()) != Some("json") {
+                    continue;
+                }
+                let Some(mut task) = Self::read_task_file(&path) else {
+                    continue;
+                };
+                if !task.status.is_terminal() && !Self::is_attached(&task) {
+                    Self::mark_lost(&mut task);
+                    lost.push(task.clone());
+                }
+                tasks.insert(task.id.clone(), task);
+            }
+        }
+
+        for task in lost {
+            self.persist_task(&task);
+            self.log_lost(&task);
+            self.notify(task);
+        }
+    }
+
+    fn mark_lost(task: &mut BackgroundTask) {
+        task.error = Some(lost_task_error(task));
+        task.status = BackgroundTaskStatus::Error;
+        task.completed_at = Some(Utc::now());
+        // The slot belonged to the dead process's concurrency manager
+        task.concurrency_key = None;
+    }
+
+    fn log_lost(&self, task: &BackgroundTask) {
+        if let Some(ref error) = task.error {
+            self.task_logger(&task.id).event(error);
+        }
+    }
+
+    /// Pick up progress written by the processes running attached tasks
+    fn refresh_attached_tasks(&self) {
+        let attached: Vec<BackgroundTask> = self
+            .tasks
+            .lock()
+            .expect("lock")
+            .values()
+            .filter(|task| Self::is_foreign(task))
+            .cloned()
+            .collect();
+        if attached.is_empty() {
+            return;
+        }
+
+        let mut finished = Vec::new();
+        let mut lost = Vec::new();
+        for task in attached {
+            let mut latest =
+                Self::read_task_file(&self.task_path(&task.id)).unwrap_or_else(|| task.clone());
+            if latest.status.is_terminal() {
+                finished.push(latest);
+            } else if Self::is_attached(&latest) {
+                self.tasks
+                    .lock()
+                    .expect("lock")
+                    .insert(latest.id.clone(), latest);
+            } else {
+                Self::mark_lost(&mut latest);
+                lost.push(latest);
             }
-            let Ok(content) = fs::read_to_string(&path) else {
-                continue;
-            };
-            let Ok(task) = serde_json::from_str::<BackgroundTask>(&content) else {
-                continue;
-            };
-            tasks.insert(task.id.clone(), task);
+        }
+
+        for task in lost {
+            self.persist_task(&task);
+            self.log_lost(&task);
+            finished.push(task);
+        }
+        for task in finished {
+            self.tasks
+                .lock()
+                .expect("lock")
+                .insert(task.id.clone(), task.clone());
+            self.notify(task);
         }
     }
 
+    fn notify(&self, task: BackgroundTask) {
+        self.notifications
+            .lock()
+            .expect("lock")
+            .entry(task.parent_session_id.clone())
+            .or_default()
+            .push(task);
+    }
+
+    /// Drain tasks recovered or finished elsewhere that `parent_session_id`
+    /// hasn't been told about
+    pub fn take_notifications(&self, parent_session_id: &str) -> Vec<BackgroundTask> {
+        self.refresh_attached_tasks();
+        self.notifications
+            .lock()
+            .expect("lock")
+            .remove(parent_session_id)
+            .unwrap_or_default()
+    }
+
     pub fn launch(&self, input: LaunchInput) -> Result<BackgroundTask, String> {
         let (running_count, queued_count) = {
             let tasks = self.tasks.lock().expect("lock");
@@ -425,6 +749,11 @@ impl BackgroundManager {
             }),
             concurrency_key: Some(concurrency_key.clone()),
             parent_model: input.model,
+            owner_pid: Some(std::process::id()),
+            attempts: 1,
+            failure_kind: None,
+            requeued_from: None,
+            requeued_as: None,
             cancel_signal: None,
         };
 
@@ -435,6 +764,11 @@ impl BackgroundManager {
                 .insert(task.id.clone(), task.clone());
         }
         self.persist_task(&task);
+        let logger = self.task_logger(&task.id);
+        logger.event(&format!(
+            "queued: {} (agent: {})",
+            task.description, task.agent
+        ));
 
         self.concurrency.acquire(&concurrency_key);
 
@@ -450,6 +784,7 @@ impl BackgroundManager {
         tasks.insert(updated.id.clone(), updated.clone());
         drop(tasks);
         self.persist_task(&updated);
+        logger.event("running");
 
         Ok(updated)
     }
@@ -460,7 +795,22 @@ impl BackgroundManager {
         };
         for entry in entries.flatten() {
             let path = entry.path();
-            if path.extension().and_then(|s| s.to_str()) == Some("json") {
+            if matches!(
+                path.extension().and_then(|s| s.to_str()),
+                Some("json" | "tmp")
+            ) {
+                let _ = fs::remove_file(path);
+            }
+        }
+    }
+
+    fn clear_logs(&self) {
+        let Ok(entries) = fs::read_dir(&self.log_dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().and_then(|s| s.to_str()) == Some("log") {
                 let _ = fs::remove_file(path);
             }
         }
@@ -471,17 +821,54 @@ impl BackgroundManager {
         self.tasks.lock().expect("lock").clear();
         self.notifications.lock().expect("lock").clear();
         self.clear_storage();
+        self.clear_logs();
+    }
+
+    /// Remove the record and log of every task that finished before `before`.
+    /// Unfinished tasks are kept however old they are. With `dry_run` nothing
+    /// is deleted; the result lists what would be.
+    pub fn prune_finished(&self, before: DateTime<Utc>, dry_run: bool) -> PrunedTasks {
+        self.refresh_attached_tasks();
+        let mut pruned = PrunedTasks::default();
+        let mut tasks = self.tasks.lock().expect("lock");
+        let expired: Vec<String> = tasks
+            .values()
+            .filter(|task| {
+                task.status.is_terminal() && task.completed_at.is_some_and(|at| at < before)
+            })
+            .map(|task| task.id.clone())
+            .collect();
+
+        for task_id in expired {
+            for path in [self.task_path(&task_id), self.log_path(&task_id)] {
+                let Ok(metadata) = fs::metadata(&path) else {
+                    continue;
+                };
+                if dry_run || fs::remove_file(&path).is_ok() {
+                    pruned.bytes += metadata.len();
+                }
+            }
+            if !dry_run {
+                tasks.remove(&task_id);
+            }
+            pruned.task_ids.push(task_id);
+        }
+        pruned.task_ids.sort();
+        pruned
     }
 
     pub fn get_task(&self, task_id: &str) -> Option<BackgroundTask> {
+        self.refresh_attached_tasks();
         self.tasks.lock().expect("lock").get(task_id).cloned()
     }
 
     pub fn get_all_tasks(&self) -> Vec<BackgroundTask> {
+        self.refresh_attached_tasks();
         self.tasks.lock().expect("lock").values().cloned().collect()
     }
 
     pub fn get_tasks_for_session(&self, parent_session_id: &str) -> Vec<BackgroundTask> {
+        self.refresh_attached_tasks();
         self.tasks
             .lock()
             .expect("lock")
@@ -510,6 +897,8 @@ impl BackgroundManager {
             let updated = task.clone();
             drop(tasks);
             self.persist_task(&updated);
+            self.task_logger(task_id)
+                .event(&format!("status: {}", status.as_str()));
             Some(updated)
         } else {
             None
@@ -529,6 +918,7 @@ impl BackgroundManager {
             let updated = task.clone();
             drop(tasks);
             self.persist_task(&updated);
+            self.task_logger(task_id).event("completed");
             Some(updated)
         } else {
             None
@@ -540,6 +930,7 @@ impl BackgroundManager {
         if let Some(task) = tasks.get_mut(task_id) {
             task.status = BackgroundTaskStatus::Error;
             task.completed_at = Some(Utc::now());
+            task.failure_kind = Some(classify_failure(&error));
             task.error = Some(error);
             // Use take() to atomically clear the key, preventing double-release
             if let Some(key) = task.concurrency_key.take() {
@@ -548,12 +939,150 @@ impl BackgroundManager {
             let updated = task.clone();
             drop(tasks);
             self.persist_task(&updated);
+            self.task_logger(task_id).event(&format!(
+                "failed: {}",
+                updated.error.as_deref().unwrap_or_default()
+            ));
             Some(updated)
         } else {
             None
         }
     }
 
+    /// Record a failed attempt and decide whether to run the task again
+    ///
+    /// Returns how long to wait before the next attempt, or `None` when the
+    /// task should be failed: the error isn't retryable, attempts are used up,
+    /// or the task already finished (e.g. it was cancelled).
+    pub fn retry_delay(&self, task_id: &str, error: &str) -> Option<Duration> {
+        let policy = &self.config.retry;
+        let kind = classify_failure(error);
+
+        let mut tasks = self.tasks.lock().expect("lock");
+        let task = tasks.get_mut(task_id)?;
+        let attempt = task.attempts.max(1);
+        if task.status.is_terminal()
+            || attempt >= policy.max_attempts
+            || !policy.retry_on.contains(&kind)
+        {
+            return None;
+        }
+
+        let delay = backoff_delay(policy, attempt);
+        task.attempts = attempt + 1;
+        task.failure_kind = Some(kind);
+        let updated = task.clone();
+        drop(tasks);
+        self.persist_task(&updated);
+        self.task_logger(task_id).event(&format!(
+            "attempt {}/{} failed ({}): {}; retrying in {:.1}s",
+            attempt,
+            policy.max_attempts,
+            kind.as_str(),
+            error,
+            delay.as_secs_f64()
+        ));
+        Some(delay)
+    }
+
+    /// Failed tasks that haven't been requeued yet, most recent first
+    pub fn get_failed_tasks(&self) -> Vec<BackgroundTask> {
+        let mut failed: Vec<BackgroundTask> = self
+            .get_all_tasks()
+            .into_iter()
+            .filter(|task| task.status == BackgroundTaskStatus::Error && task.requeued_as.is_none())
+            .collect();
+        failed.sort_by_key(|task| std::cmp::Reverse(task.completed_at));
+        failed
+    }
+
+    /// Launch a fresh copy of a failed task, returning the new task
+    ///
+    /// The new task is owned by the calling process, which is responsible for
+    /// running it.
+    pub fn requeue(&self, task_id: &str) -> Result<BackgroundTask, String> {
+        let original = self
+            .get_task(task_id)
+            .ok_or_else(|| format!("Task not found: {task_id}"))?;
+        if original.status != BackgroundTaskStatus::Error {
+            return Err(format!(
+                "Task {task_id} is {}; only failed tasks can be requeued",
+                original.status.as_str()
+            ));
+        }
+        if let Some(ref new_id) = original.requeued_as {
+            return Err(format!("Task {task_id} was already requeued as {new_id}"));
+        }
+
+        let mut task = self.launch(LaunchInput {
+            description: original.description.clone(),
+            prompt: original.prompt.clone(),
+            agent: original.agent.clone(),
+            parent_session_id: original.parent_session_id.clone(),
+            model: original.parent_model.clone(),
+        })?;
+        task.requeued_from = Some(task_id.to_string());
+
+        let original = {
+            let mut tasks = self.tasks.lock().expect("lock");
+            tasks.insert(task.id.clone(), task.clone());
+            tasks.get_mut(task_id).map(|original| {
+                original.requeued_as = Some(task.id.clone());
+                original.clone()
+            })
+        };
+        self.persist_task(&task);
+        if let Some(original) = original {
+            self.persist_task(&original);
+        }
+        self.task_logger(task_id)
+            .event(&format!("requeued as {}", task.id));
+        self.task_logger(&task.id)
+            .event(&format!("requeued from {task_id}"));
+        Ok(task)
+    }
+
+    /// Fail every unfinished task this process is running with `reason`,
+    /// leaving them to be requeued by a later process
+    pub fn interrupt_owned_tasks(&self, reason: &str) -> Vec<BackgroundTask> {
+        let interrupted: Vec<BackgroundTask> = {
+            let mut tasks = self.tasks.lock().expect("lock");
+            tasks
+                .values_mut()
+                .filter(|task| !task.status.is_terminal() && !Self::is_foreign(task))
+                .map(|task| {
+                    task.status = BackgroundTaskStatus::Error;
+                    task.completed_at = Some(Utc::now());
+                    task.failure_kind = Some(classify_failure(reason));
+                    task.error = Some(reason.to_string());
+                    if let Some(key) = task.concurrency_key.take() {
+                        self.concurrency.release(&key);
+                    }
+                    if let Some(signal) = task.cancel_signal.take() {
+                        signal.cancel();
+                    }
+                    task.clone()
+                })
+                .collect()
+        };
+
+        for task in &interrupted {
+            self.persist_task(task);
+            self.task_logger(&task.id).event(reason);
+        }
+        interrupted
+    }
+
+    /// Cancel `token` along with the task. A task that already finished
+    /// cancels it right away.
+    pub fn attach_cancellation(&self, task_id: &str, token: CancellationToken) {
+        let mut tasks = self.tasks.lock().expect("lock");
+        match tasks.get_mut(task_id) {
+            Some(task) if !task.status.is_terminal() => task.cancel_signal = Some(token),
+            _ => token.cancel(),
+        }
+    }
+
     pub fn cancel_task(&self, task_id: &str) -> Option<BackgroundTask> {
         let mut tasks = self.tasks.lock().expect("lock");
         if let Some(task) = tasks.get_mut(task_id) {
@@ -565,7 +1094,7 @@ impl BackgroundManager {
 
             // Signal cancellation to the running task
             if let Some(ref signal) = task.cancel_signal {
-                signal.store(true, std::sync::atomic::Ordering::SeqCst);
+                signal.cancel();
             }
 
             // Use take() to atomically clear the key, preventing double-release
@@ -575,6 +1104,7 @@ impl BackgroundManager {
             let updated = task.clone();
             drop(tasks);
             self.persist_task(&updated);
+            self.task_logger(task_id).event("cancelled");
             Some(updated)
         } else {
             None
@@ -594,6 +1124,22 @@ pub fn get_background_manager(config: BackgroundTaskConfig) -> Arc<BackgroundMan
     manager
 }
 
+/// Drain pending notifications from the shared manager, if one has been created
+pub fn take_background_notifications(parent_session_id: &str) -> Vec<BackgroundTask> {
+    let manager = BACKGROUND_MANAGER_INSTANCE.lock().expect("lock").clone();
+    manager
+        .map(|manager| manager.take_notifications(parent_session_id))
+        .unwrap_or_default()
+}
+
+/// Interrupt the shared manager's running tasks, if one has been created
+pub fn interrupt_background_tasks(reason: &str) -> Vec<BackgroundTask> {
+    let manager = BACKGROUND_MANAGER_INSTANCE.lock().expect("lock").clone();
+    manager
+        .map(|manager| manager.interrupt_owned_tasks(reason))
+        .unwrap_or_default()
+}
+
 pub fn reset_background_manager() {
     let mut instance = BACKGROUND_MANAGER_INSTANCE.lock().expect("lock");
     if let Some(manager) = instance.take() {
@@ -659,6 +1205,318 @@ mod tests {
         assert!(manager2.get_task(&task.id).is_some());
     }
 
+    fn launch_task(manager: &BackgroundManager, description: &str) -> BackgroundTask {
+        manager
+            .launch(LaunchInput {
+                description: description.to_string(),
+                prompt: "prompt".to_string(),
+                agent: "agent".to_string(),
+                parent_session_id: "parent".to_string(),
+                model: None,
+            })
+            .unwrap()
+    }
+
+    fn rewrite_task(dir: &Path, task_id: &str, update: impl FnOnce(&mut BackgroundTask)) {
+        let path = dir.join(format!("{task_id}.json"));
+        let mut task: BackgroundTask =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        update(&mut task);
+        fs::write(path, serde_json::to_string(&task).unwrap()).unwrap();
+    }
+
+    #[test]
+    fn prune_finished_removes_old_records_and_logs() {
+        let dir = TempDir::new().unwrap();
+        let manager = BackgroundManager::new(BackgroundTaskConfig {
+            default_concurrency: Some(0),
+            storage_dir: Some(dir.path().to_path_buf()),
+            ..BackgroundTaskConfig::default()
+        });
+        let done = launch_task(&manager, "done");
+        let running = launch_task(&manager, "running");
+        manager.task_logger(&done.id).output("finished");
+        manager.complete_task(&done.id, "ok".to_string());
+
+        let cutoff = Utc::now() + chrono::Duration::seconds(1);
+        let preview = manager.prune_finished(cutoff, true);
+        assert_eq!(preview.task_ids, vec![done.id.clone()]);
+        assert!(preview.bytes > 0);
+        assert!(manager.get_task(&done.id).is_some());
+
+        let pruned = manager.prune_finished(cutoff, false);
+        assert_eq!(pruned, preview);
+        assert!(manager.get_task(&done.id).is_none());
+        assert!(!dir.path().join(format!("{}.json", done.id)).exists());
+        assert!(!manager.log_path(&done.id).exists());
+        assert!(manager.get_task(&running.id).is_some());
+    }
+
+    #[test]
+    fn interrupted_tasks_can_be_requeued() {
+        let dir = TempDir::new().unwrap();
+        let config = BackgroundTaskConfig {
+            storage_dir: Some(dir.path().to_path_buf()),
+            ..BackgroundTaskConfig::default()
+        };
+        let manager = BackgroundManager::new(config.clone());
+        let running = launch_task(&manager, "running");
+        let done = launch_task(&manager, "done");
+        manager.complete_task(&done.id, "ok".to_string());
+        let token = CancellationToken::new();
+        manager.attach_cancellation(&running.id, token.clone());
+
+        let interrupted = manager.interrupt_owned_tasks("Task interrupted: shutting down");
+        assert_eq!(interrupted.len(), 1);
+        assert_eq!(interrupted[0].id, running.id);
+        assert!(token.is_cancelled());
+        assert_eq!(
+            manager.get_task(&done.id).unwrap().status,
+            BackgroundTaskStatus::Completed
+        );
+
+        // Persisted as failed, so the next process can run it again
+        let restarted = BackgroundManager::new(config);
+        let task = restarted.get_task(&running.id).unwrap();
+        assert_eq!(task.status, BackgroundTaskStatus::Error);
+        assert_eq!(
+            task.error.as_deref(),
+            Some("Task interrupted: shutting down")
+        );
+        assert!(restarted.requeue(&running.id).is_ok());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn recovery_fails_tasks_whose_process_exited() {
+        let dir = TempDir::new().unwrap();
+        let config = BackgroundTaskConfig {
+            default_concurrency: Some(0),
+            storage_dir: Some(dir.path().to_path_buf()),
+            ..BackgroundTaskConfig::default()
+        };
+        let manager = BackgroundManager::new(config.clone());
+        let running = launch_task(&manager, "running");
+        let queued = launch_task(&manager, "queued");
+
+        let mut exited = std::process::Command::new("true").spawn().unwrap();
+        let dead_pid = exited.id();
+        exited.wait().unwrap();
+        rewrite_task(dir.path(), &running.id, |task| {
+            task.owner_pid = Some(dead_pid)
+        });
+        rewrite_task(dir.path(), &queued.id, |task| {
+            task.status = BackgroundTaskStatus::Queued;
+            task.owner_pid = None;
+        });
+
+        let recovered = BackgroundManager::new(config.clone());
+        let running = recovered.get_task(&running.id).unwrap();
+        assert_eq!(running.status, BackgroundTaskStatus::Error);
+        assert_eq!(
+            running.error.as_deref(),
+            Some(format!("Task lost: process {dead_pid} exited before the task finished").as_str())
+        );
+        let queued = recovered.get_task(&queued.id).unwrap();
+        assert_eq!(
+            queued.error.as_deref(),
+            Some("Task lost: an earlier session exited before the task started")
+        );
+
+        assert_eq!(recovered.take_notifications("parent").len(), 2);
+        assert!(recovered.take_notifications("parent").is_empty());
+
+        // The failure was persisted, so the next load doesn't notify again
+        let reloaded = BackgroundManager::new(config);
+        assert!(reloaded.take_notifications("parent").is_empty());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn recovery_reattaches_to_live_owner() {
+        let dir = TempDir::new().unwrap();
+        let config = BackgroundTaskConfig {
+            default_concurrency: Some(0),
+            storage_dir: Some(dir.path().to_path_buf()),
+            ..BackgroundTaskConfig::default()
+        };
+        let manager = BackgroundManager::new(config.clone());
+        let first = launch_task(&manager, "first");
+        let second = launch_task(&manager, "second");
+
+        let mut owner = std::process::Command::new("sleep")
+            .arg("30")
+            .spawn()
+            .unwrap();
+        for task in [&first, &second] {
+            rewrite_task(dir.path(), &task.id, |task| {
+                task.owner_pid = Some(owner.id())
+            });
+        }
+
+        let attached = BackgroundManager::new(config);
+        assert_eq!(
+            attached.get_task(&first.id).unwrap().status,
+            BackgroundTaskStatus::Running
+        );
+        assert!(attached.take_notifications("parent").is_empty());
+
+        // The owner finishes a task and writes it to disk
+        rewrite_task(dir.path(), &first.id, |task| {
+            task.status = BackgroundTaskStatus::Completed;
+            task.result = Some("done".to_string());
+        });
+        let first = attached.get_task(&first.id).unwrap();
+        assert_eq!(first.status, BackgroundTaskStatus::Completed);
+        assert_eq!(first.result.as_deref(), Some("done"));
+
+        // Then it crashes before finishing the other
+        owner.kill().unwrap();
+        owner.wait().unwrap();
+        assert_eq!(
+            attached.get_task(&second.id).unwrap().status,
+            BackgroundTaskStatus::Error
+        );
+
+        let notified: Vec<_> = attached
+            .take_notifications("parent")
+            .into_iter()
+            .map(|task| task.description)
+            .collect();
+        assert_eq!(notified, vec!["first", "second"]);
+    }
+
+    #[test]
+    fn task_log_records_lifecycle_and_output() {
+        let dir = TempDir::new().unwrap();
+        let manager = BackgroundManager::new(BackgroundTaskConfig {
+            default_concurrency: Some(0),
+            storage_dir: Some(dir.path().to_path_buf()),
+            ..BackgroundTaskConfig::default()
+        });
+
+        let task = launch_task(&manager, "index repo");
+        assert_eq!(
+            manager.log_path(&task.id),
+            dir.path().join("logs").join(format!("{}.log", task.id))
+        );
+
+        let (content, offset) = manager.read_log(&task.id, 0).unwrap();
+        assert!(content.contains("] queued: index repo (agent: agent)\n"));
+        assert!(content.ends_with("] running\n"));
+
+        manager.task_logger(&task.id).output("found 3 files\n");
+        manager.complete_task(&task.id, "done".to_string());
+
+        let (tail, next) = manager.read_log(&task.id, offset).unwrap();
+        assert!(tail.starts_with("found 3 files\n"));
+        assert!(tail.ends_with("] completed\n"));
+        assert_eq!(manager.read_log(&task.id, next).unwrap().0, "");
+
+        manager.cleanup();
+        assert!(!manager.log_path(&task.id).exists());
+    }
+
+    #[test]
+    fn classify_failure_recognizes_transient_errors() {
+        assert_eq!(
+            classify_failure("API error 429: Too Many Requests"),
+            TaskFailureKind::RateLimit
+        );
+        assert_eq!(
+            classify_failure("Anthropic API is overloaded"),
+            TaskFailureKind::Overloaded
+        );
+        assert_eq!(
+            classify_failure("request timed out after 60s"),
+            TaskFailureKind::Timeout
+        );
+        assert_eq!(
+            classify_failure("error sending request: Connection reset by peer"),
+            TaskFailureKind::Network
+        );
+        assert_eq!(
+            classify_failure("Agent not found: writer"),
+            TaskFailureKind::Other
+        );
+    }
+
+    #[test]
+    fn retry_delay_backs_off_until_attempts_run_out() {
+        let dir = TempDir::new().unwrap();
+        let config = BackgroundTaskConfig {
+            default_concurrency: Some(0),
+            storage_dir: Some(dir.path().to_path_buf()),
+            retry: TaskRetrySettings {
+                max_attempts: 3,
+                backoff_ms: 100,
+                backoff_multiplier: 3.0,
+                max_backoff_ms: 250,
+                ..TaskRetrySettings::default()
+            },
+            ..BackgroundTaskConfig::default()
+        };
+        let manager = BackgroundManager::new(config);
+        let task = launch_task(&manager, "flaky");
+        assert_eq!(task.attempts, 1);
+
+        assert_eq!(manager.retry_delay(&task.id, "not retryable"), None);
+        assert_eq!(
+            manager.retry_delay(&task.id, "429 rate limited"),
+            Some(Duration::from_millis(100))
+        );
+        assert_eq!(
+            manager.retry_delay(&task.id, "429 rate limited"),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(manager.retry_delay(&task.id, "429 rate limited"), None);
+
+        let task = manager.get_task(&task.id).unwrap();
+        assert_eq!(task.attempts, 3);
+        assert_eq!(task.status, BackgroundTaskStatus::Running);
+        let (log, _) = manager.read_log(&task.id, 0).unwrap();
+        assert!(log.contains("attempt 1/3 failed (rate_limit): 429 rate limited; retrying in 0.1s"));
+    }
+
+    #[test]
+    fn failed_tasks_can_be_requeued_once() {
+        let dir = TempDir::new().unwrap();
+        let config = BackgroundTaskConfig {
+            default_concurrency: Some(0),
+            storage_dir: Some(dir.path().to_path_buf()),
+            ..BackgroundTaskConfig::default()
+        };
+        let manager = BackgroundManager::new(config.clone());
+        let failed = launch_task(&manager, "flaky");
+        let done = launch_task(&manager, "fine");
+        manager.fail_task(&failed.id, "request timed out".to_string());
+        manager.complete_task(&done.id, "ok".to_string());
+
+        let dead_letters = manager.get_failed_tasks();
+        assert_eq!(dead_letters.len(), 1);
+        assert_eq!(dead_letters[0].failure_kind, Some(TaskFailureKind::Timeout));
+        assert!(manager
+            .requeue(&done.id)
+            .unwrap_err()
+            .contains("is completed"));
+
+        let requeued = manager.requeue(&failed.id).unwrap();
+        assert_eq!(requeued.status, BackgroundTaskStatus::Running);
+        assert_eq!(requeued.prompt, failed.prompt);
+        assert_eq!(requeued.requeued_from.as_deref(), Some(failed.id.as_str()));
+        assert!(manager.get_failed_tasks().is_empty());
+        assert!(manager
+            .requeue(&failed.id)
+            .unwrap_err()
+            .contains("already requeued"));
+
+        let reloaded = BackgroundManager::new(config);
+        assert_eq!(
+            reloaded.get_task(&failed.id).unwrap().requeued_as,
+            Some(requeued.id)
+        );
+    }
+
     #[test]
     fn cleanup_clears_storage() {
         let dir = TempDir::new().unwrap();
@@ -0,0 +1,349 @@
+//! Declared skill arguments and `{{arg}}` template substitution
+//!
+//! Skills may declare arguments in their frontmatter:
+//!
+//! ```yaml
+//! arguments:
+//!   - name: version
+//!     type: string
+//!     required: true
+//!   - name: draft
+//!     type: boolean
+//!     default: false
+//! ```
+//!
+//! Invocation arguments are given as `name=value` pairs or positionally in
+//! declaration order, then substituted into `{{version}}`-style placeholders.
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use thiserror::Error;
+
+/// Value type of a declared skill argument
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SkillArgumentType {
+    #[default]
+    String,
+    Number,
+    Boolean,
+}
+
+impl std::fmt::Display for SkillArgumentType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::String => write!(f, "string"),
+            Self::Number => write!(f, "number"),
+            Self::Boolean => write!(f, "boolean"),
+        }
+    }
+}
+
+/// An argument declared in a skill's frontmatter
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillArgument {
+    pub name: String,
+    #[serde(rename = "type", default)]
+    pub arg_type: SkillArgumentType,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl SkillArgument {
+    fn default_string(&self) -> Option<String> {
+        match self.default.as_ref()? {
+            serde_json::Value::Null => None,
+            serde_json::Value::String(s) => Some(s.clone()),
+            other => Some(other.to_string()),
+        }
+    }
+
+    fn validate(&self, value: &str) -> Result<(), SkillArgumentError> {
+        let valid = match self.arg_type {
+            SkillArgumentType::String => true,
+            SkillArgumentType::Number => value.parse::<f64>().is_ok(),
+            SkillArgumentType::Boolean => matches!(value, "true" | "false"),
+        };
+
+        if valid {
+            Ok(())
+        } else {
+            Err(SkillArgumentError::InvalidType {
+                name: self.name.clone(),
+                expected: self.arg_type,
+                value: value.to_string(),
+            })
+        }
+    }
+}
+
+/// Errors raised while resolving skill invocation arguments
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum SkillArgumentError {
+    #[error("missing required argument(s): {}", .0.join(", "))]
+    Missing(Vec<String>),
+
+    #[error("argument '{name}' expects a {expected}, got '{value}'")]
+    InvalidType {
+        name: String,
+        expected: SkillArgumentType,
+        value: String,
+    },
+}
+
+/// Format a usage line such as `<version> [draft=false]` for declared arguments
+pub fn format_argument_usage(declared: &[SkillArgument]) -> String {
+    declared
+        .iter()
+        .map(|arg| {
+            if arg.required {
+                format!("<{}>", arg.name)
+            } else if let Some(default) = arg.default_string() {
+                format!("[{}={}]", arg.name, default)
+            } else {
+                format!("[{}]", arg.name)
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Split a raw argument string into tokens, honoring single and double quotes
+fn tokenize(raw: &str) -> Vec<String> {
+    let mut tokens = Vec::new();
+    let mut current = String::new();
+    let mut quote: Option<char> = None;
+    let mut in_token = false;
+
+    for c in raw.chars() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => current.push(c),
+            None if c == '"' || c == '\'' => {
+                quote = Some(c);
+                in_token = true;
+            }
+            None if c.is_whitespace() => {
+                if in_token {
+                    tokens.push(std::mem::take(&mut current));
+                    in_token = false;
+                }
+            }
+            None => {
+                current.push(c);
+                in_token = true;
+            }
+        }
+    }
+
+    if in_token {
+        tokens.push(current);
+    }
+    tokens
+}
+
+/// Parse a raw invocation string against the declared arguments
+///
+/// `name=value` tokens bind by name when `name` is a declared argument; other
+/// tokens, including ones like `KEY=value` or `a==b`, fill unbound arguments in
+/// declaration order, with any overflow joined into the last positional argument.
+pub fn parse_invocation_args(
+    raw: &str,
+    declared: &[SkillArgument],
+) -> Result<HashMap<String, String>, SkillArgumentError> {
+    let mut values = HashMap::new();
+    let mut positional = Vec::new();
+
+    for token in tokenize(raw) {
+        match token.split_once('=') {
+            Some((key, value)) if declared.iter().any(|a| a.name == key) => {
+                values.insert(key.to_string(), value.to_string());
+            }
+            _ => positional.push(token),
+        }
+    }
+
+    let unbound: Vec<&SkillArgument> = declared
+        .iter()
+        .filter(|a| !values.contains_key(&a.name))
+        .collect();
+
+    let mut positional = positional.into_iter();
+    for (i, arg) in unbound.iter().enumerate() {
+        let value = if i + 1 == unbound.len() {
+            let rest: Vec<String> = positional.by_ref().collect();
+            (!rest.is_empty()).then(|| rest.join(" "))
+        } else {
+            positional.next()
+        };
+        match value {
+            Some(v) => {
+                values.insert(arg.name.clone(), v);
+            }
+            None => break,
+        }
+    }
+
+    Ok(values)
+}
+
+/// Apply defaults, then check required arguments and value types
+pub fn resolve_arguments(
+    declared: &[SkillArgument],
+    mut values: HashMap<String, String>,
+) -> Result<HashMap<String, String>, SkillArgumentError> {
+    let mut missing = Vec::new();
+
+    for arg in declared {
+        if !values.contains_key(&arg.name) {
+            if let Some(default) = arg.default_string() {
+                values.insert(arg.name.clone(), default);
+            } else if arg.required {
+                missing.push(arg.name.clone());
+            }
+        }
+    }
+
+    if !missing.is_empty() {
+        return Err(SkillArgumentError::Missing(missing));
+    }
+
+    for arg in declared {
+        if let Some(value) = values.get(&arg.name) {
+            arg.validate(value)?;
+        }
+    }
+
+    Ok(values)
+}
+
+/// Replace `{{name}}` (and `{{ name }}`) placeholders with resolved values
+///
+/// Placeholders without a value are left untouched. The template is scanned
+/// once, so placeholders inside substituted values are never expanded.
+pub fn render_template(template: &str, values: &HashMap<String, String>) -> String {
+    let mut rendered = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let (before, tail) = rest.split_at(start);
+        rendered.push_str(before);
+        let Some(end) = tail.find("}}") else {
+            rest = tail;
+            break;
+        };
+        let inner = &tail[2..end];
+        let name = inner
+            .strip_prefix(' ')
+            .and_then(|name| name.strip_suffix(' '));
+        let Some(value) = values.get(name.unwrap_or(inner)) else {
+            // Not a known placeholder; keep one brace and scan on from the next
+            rendered.push('{');
+            rest = &tail[1..];
+            continue;
+        };
+        rendered.push_str(value);
+        rest = &tail[end + 2..];
+    }
+    rendered.push_str(rest);
+    rendered
+}
+
+/// Parse, resolve, and substitute invocation arguments into a skill template
+pub fn render_skill_template(
+    template: &str,
+    declared: &[SkillArgument],
+    raw_args: &str,
+) -> Result<String, SkillArgumentError> {
+    let values = parse_invocation_args(raw_args, declared)?;
+    let values = resolve_arguments(declared, values)?;
+    Ok(render_template(template, &values))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> Vec<SkillArgument> {
+        serde_yaml_ng::from_str(
+            r#"
+- name: version
+  required: true
+- name: count
+  type: number
+  default: 3
+- name: notes
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_parse_positional_and_named() {
+        let values = parse_invocation_args("v1.2 count=5 first release", &args()).unwrap();
+        assert_eq!(values.get("version").unwrap(), "v1.2");
+        assert_eq!(values.get("count").unwrap(), "5");
+        assert_eq!(values.get("notes").unwrap(), "first release");
+    }
+
+    #[test]
+    fn test_quoted_tokens() {
+        let values = parse_invocation_args("version=\"v 2\" notes='a b'", &args()).unwrap();
+        assert_eq!(values.get("version").unwrap(), "v 2");
+        assert_eq!(values.get("notes").unwrap(), "a b");
+    }
+
+    #[test]
+    fn test_defaults_and_missing() {
+        let err = render_skill_template("{{version}}", &args(), "").unwrap_err();
+        assert_eq!(
+            err,
+            SkillArgumentError::Missing(vec!["version".to_string()])
+        );
+
+        let rendered =
+            render_skill_template("Release {{version}} x{{ count }}", &args(), "v1").unwrap();
+        assert_eq!(rendered, "Release v1 x3");
+    }
+
+    #[test]
+    fn test_substituted_values_are_not_rescanned() {
+        let rendered = render_skill_template(
+            "{{notes}} / {{version}} / {{missing}} {{{version}}}",
+            &args(),
+            "v1 notes={{version}}",
+        )
+        .unwrap();
+        assert_eq!(rendered, "{{version}} / v1 / {{missing}} {v1}");
+    }
+
+    #[test]
+    fn test_type_validation() {
+        let err = render_skill_template("", &args(), "v1 count=many").unwrap_err();
+        assert!(matches!(err, SkillArgumentError::InvalidType { .. }));
+        assert!(err.to_string().contains("expects a number"));
+    }
+
+    #[test]
+    fn test_undeclared_names_are_positional() {
+        let values =
+            parse_invocation_args("v1 colour=red count=2 RUST_LOG=debug", &args()).unwrap();
+        assert_eq!(values.get("version").unwrap(), "v1");
+        assert_eq!(values.get("count").unwrap(), "2");
+        assert_eq!(values.get("notes").unwrap(), "colour=red RUST_LOG=debug");
+
+        let values = parse_invocation_args("a==b", &args()).unwrap();
+        assert_eq!(values.get("version").unwrap(), "a==b");
+    }
+
+    #[test]
+    fn test_format_usage() {
+        assert_eq!(
+            format_argument_usage(&args()),
+            "<version> [count=3] [notes]"
+        );
+    }
+}
@@ -1,5 +1,12 @@
+pub mod arguments;
+pub mod permissions;
 pub mod types;
 
+pub use arguments::{
+    format_argument_usage, parse_invocation_args, render_skill_template, render_template,
+    resolve_arguments, SkillArgument, SkillArgumentError, SkillArgumentType,
+};
+pub use permissions::{SkillPermissionRule, SkillPermissions, SkillScope};
 pub use types::*;
 
 use std::collections::HashMap;
@@ -60,6 +67,9 @@ fn load_skill_from_file(skill_path: &Path, skill_name: &str) -> Option<BuiltinSk
         subtask: None,
         argument_hint: data.get("argument-hint").cloned(),
         mcp_config: None,
+        strategy: data
+            .get("strategy")
+            .and_then(|strategy| serde_json::from_value(strategy.as_str().into()).ok()),
     })
 }
 
@@ -157,6 +167,7 @@ pub fn clear_skills_cache() {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use uira_core::schema::ExecutionStrategy;
 
     #[test]
     fn test_parse_frontmatter() {
@@ -174,6 +185,25 @@ This is the template content"#;
         assert_eq!(body.trim(), "This is the template content");
     }
 
+    #[test]
+    fn test_load_skill_strategy() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("SKILL.md");
+        fs::write(
+            &path,
+            "---\ndescription: Security review\nstrategy: consensus\n---\nReview it",
+        )
+        .unwrap();
+        let skill = load_skill_from_file(&path, "security-review").unwrap();
+        assert_eq!(skill.strategy, Some(ExecutionStrategy::Consensus));
+
+        fs::write(&path, "---\nstrategy: unanimous\n---\nReview it").unwrap();
+        assert!(load_skill_from_file(&path, "security-review")
+            .unwrap()
+            .strategy
+            .is_none());
+    }
+
     #[test]
     fn test_parse_frontmatter_no_frontmatter() {
         let content = "Just plain content";
@@ -0,0 +1,193 @@
+//! Per-skill tool and permission restrictions
+//!
+//! Skills may restrict what the session can do while they are active:
+//!
+//! ```yaml
+//! allowed-tools: [Read, Glob, Grep, Write]
+//! permissions:
+//!   - permission: "file:write"
+//!     pattern: "docs/**"
+//!     action: allow
+//!   - permission: "file:write"
+//!     pattern: "**"
+//!     action: deny
+//! ```
+//!
+//! `allowed-tools` denies every tool not listed. `permissions` are regular
+//! permission rules layered on top, with the last matching rule winning.
+//! Skills can only narrow what the session allows: an `allow` carves an
+//! exception out of the skill's own denials and never overrides a session
+//! rule that asks or denies.
+
+use serde::{Deserialize, Serialize};
+use uira_security::{Action, Permission, PermissionEvaluator, PermissionRule};
+
+/// Tool and permission restrictions declared in a skill's frontmatter
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct SkillPermissions {
+    /// Tools the session may use while the skill is active (`None` = unrestricted)
+    #[serde(
+        default,
+        alias = "allowed_tools",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub allowed_tools: Option<Vec<String>>,
+    /// Permission rule overrides applied while the skill is active
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub permissions: Vec<SkillPermissionRule>,
+}
+
+/// A permission override rule, in the same shape as `permissions.rules` in uira.yml
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillPermissionRule {
+    pub permission: String,
+    #[serde(default = "default_pattern")]
+    pub pattern: String,
+    pub action: Action,
+}
+
+fn default_pattern() -> String {
+    "**".to_string()
+}
+
+impl SkillPermissions {
+    /// Whether the skill declares any restriction at all
+    pub fn is_empty(&self) -> bool {
+        self.allowed_tools.is_none() && self.permissions.is_empty()
+    }
+
+    /// Whether `tool_name` is permitted by `allowed-tools`
+    ///
+    /// Entries match case-insensitively and may end in `*` to match a prefix
+    /// (e.g. `mcp__github__*`).
+    pub fn allows_tool(&self, tool_name: &str) -> bool {
+        let Some(allowed) = &self.allowed_tools else {
+            return true;
+        };
+        let tool = tool_name.to_lowercase();
+        allowed.iter().any(|entry| {
+            let entry = entry.to_lowercase();
+            match entry.strip_suffix('*') {
+                Some(prefix) => tool.starts_with(prefix),
+                None => tool == entry,
+            }
+        })
+    }
+
+    /// Convert the declared overrides into permission rules named after the skill
+    pub fn to_rules(&self, skill_name: &str) -> Vec<PermissionRule> {
+        self.permissions
+            .iter()
+            .map(|rule| {
+                PermissionRule::new(&rule.permission, &rule.pattern, rule.action)
+                    .with_name(format!("skill:{}", skill_name))
+            })
+            .collect()
+    }
+}
+
+/// Restrictions of one active skill, compiled for evaluation
+#[derive(Debug)]
+pub struct SkillScope {
+    skill: String,
+    permissions: SkillPermissions,
+    evaluator: PermissionEvaluator,
+}
+
+impl SkillScope {
+    pub fn new(
+        skill: impl Into<String>,
+        permissions: SkillPermissions,
+    ) -> Result<Self, uira_security::PatternError> {
+        let skill = skill.into();
+        let evaluator = PermissionEvaluator::with_rules(permissions.to_rules(&skill))?;
+        Ok(Self {
+            skill,
+            permissions,
+            evaluator,
+        })
+    }
+
+    pub fn skill(&self) -> &str {
+        &self.skill
+    }
+
+    /// Evaluate a tool call against this skill's restrictions
+    ///
+    /// Returns `None` when the skill has no opinion about the call.
+    pub fn evaluate(&self, tool_name: &str, input: &serde_json::Value) -> Option<Action> {
+        if !self.permissions.allows_tool(tool_name) {
+            return Some(Action::Deny);
+        }
+        let result = self.evaluator.evaluate_tool(tool_name, input);
+        result.matched_rule.map(|_| result.action)
+    }
+
+    /// Human-readable reason for a denied tool call
+    pub fn denial_reason(&self, tool_name: &str) -> String {
+        if self.permissions.allows_tool(tool_name) {
+            format!(
+                "{} is restricted by skill '{}'",
+                Permission::from_tool_name(tool_name),
+                self.skill
+            )
+        } else {
+            format!(
+                "tool '{}' is not in the allowed-tools of skill '{}'",
+                tool_name, self.skill
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn docs_writer() -> SkillScope {
+        let permissions: SkillPermissions = serde_yaml_ng::from_str(
+            r#"
+allowed-tools: [Read, Grep, Write, "mcp__docs__*"]
+permissions:
+  - permission: "file:write"
+    action: deny
+  - permission: "file:write"
+    pattern: "docs/**"
+    action: allow
+"#,
+        )
+        .unwrap();
+        SkillScope::new("docs-writer", permissions).unwrap()
+    }
+
+    #[test]
+    fn test_allowed_tools() {
+        let scope = docs_writer();
+        assert_eq!(scope.evaluate("bash", &json!({})), Some(Action::Deny));
+        assert_eq!(scope.evaluate("read", &json!({"path": "src/a.rs"})), None);
+        assert_eq!(scope.evaluate("mcp__docs__search", &json!({})), None);
+        assert!(scope.denial_reason("bash").contains("allowed-tools"));
+    }
+
+    #[test]
+    fn test_permission_overrides() {
+        let scope = docs_writer();
+        assert_eq!(
+            scope.evaluate("write", &json!({"file_path": "docs/guide.md"})),
+            Some(Action::Allow)
+        );
+        assert_eq!(
+            scope.evaluate("write", &json!({"file_path": "src/main.rs"})),
+            Some(Action::Deny)
+        );
+    }
+
+    #[test]
+    fn test_unrestricted_by_default() {
+        let scope = SkillScope::new("plain", SkillPermissions::default()).unwrap();
+        assert!(scope.permissions.is_empty());
+        assert_eq!(scope.evaluate("bash", &json!({"command": "ls"})), None);
+    }
+}
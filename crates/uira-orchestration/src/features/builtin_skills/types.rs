@@ -1,5 +1,6 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
+use uira_core::schema::ExecutionStrategy;
 
 /// Configuration for MCP server integration with a skill
 pub type SkillMcpConfig = HashMap<String, McpServerConfig>;
@@ -49,6 +50,9 @@ pub struct BuiltinSkill {
     /// MCP server configuration (optional)
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mcp_config: Option<SkillMcpConfig>,
+    /// Execution strategy for agents delegated with this skill (optional)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub strategy: Option<ExecutionStrategy>,
 }
 
 /// Skill registry for runtime access
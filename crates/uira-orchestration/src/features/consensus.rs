@@ -0,0 +1,242 @@
+//! Multi-model consensus for plans and reviews
+//!
+//! Under the `consensus` execution strategy an agent's final answer, the plan
+//! or review it hands back, is also requested from a small panel of other
+//! models. [`judge_prompt`] asks a judge model to reconcile the answers and
+//! [`parse_verdict`] splits its reply into the reconciled answer and the points
+//! the panel disagreed on.
+
+use std::fmt::Write as _;
+
+use uira_core::schema::{ExecutionStrategy, UiraConfig};
+use uira_core::{ContentBlock, Message, MessageContent, Role};
+
+/// Panel models consulted besides the agent's own model
+pub const MAX_PANEL_MODELS: usize = 2;
+
+const DISAGREEMENTS_HEADER: &str = "DISAGREEMENTS:";
+
+/// Models consulted for one consensus run
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConsensusPanel {
+    /// Models answering alongside the agent's own model
+    pub models: Vec<String>,
+    pub judge: String,
+}
+
+/// Consensus panel for `agent` running on `primary`, or `None` when the agent
+/// uses the `single` strategy or no other model is configured
+///
+/// The agent's configured strategy wins over `skill_strategy`, the strategy
+/// declared by the skill it was delegated with.
+pub fn panel_for(
+    config: &UiraConfig,
+    agent: &str,
+    skill_strategy: Option<ExecutionStrategy>,
+    primary: &str,
+) -> Option<ConsensusPanel> {
+    let agent_config = config.agents.agents.get(agent);
+    let strategy = agent_config
+        .and_then(|agent_config| agent_config.strategy)
+        .or(skill_strategy)
+        .unwrap_or_default();
+    if strategy != ExecutionStrategy::Consensus {
+        return None;
+    }
+
+    let settings = agent_config
+        .and_then(|agent_config| agent_config.consensus.as_ref())
+        .unwrap_or(&config.consensus);
+    let mut models: Vec<String> = Vec::new();
+    for model in &settings.models {
+        if model != primary && !models.contains(model) {
+            models.push(model.clone());
+        }
+    }
+    models.truncate(MAX_PANEL_MODELS);
+    if models.is_empty() {
+        tracing::warn!(
+            "Agent '{}' uses the consensus strategy but no panel models besides {} are configured",
+            agent,
+            primary
+        );
+        return None;
+    }
+
+    Some(ConsensusPanel {
+        models,
+        judge: settings
+            .judge_model
+            .clone()
+            .unwrap_or_else(|| primary.to_string()),
+    })
+}
+
+/// One model's answer to the task
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    pub model: String,
+    pub answer: String,
+}
+
+/// The judge's reconciled answer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    pub answer: String,
+    pub disagreements: Vec<String>,
+}
+
+/// Text of the first user message, the task the panel is answering
+pub fn task_text(messages: &[Message]) -> String {
+    messages
+        .iter()
+        .filter(|message| message.role == Role::User)
+        .map(|message| match &message.content {
+            MessageContent::Text(text) => text.clone(),
+            MessageContent::Blocks(blocks) => blocks
+                .iter()
+                .filter_map(|block| match block {
+                    ContentBlock::Text { text } => Some(text.as_str()),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+                .join("\n"),
+            MessageContent::ToolCalls(_) => String::new(),
+        })
+        .find(|text| !text.trim().is_empty())
+        .unwrap_or_default()
+}
+
+/// Prompt asking the judge to reconcile `candidates` into one answer
+pub fn judge_prompt(task: &str, candidates: &[Candidate]) -> String {
+    let mut prompt = String::from(
+        "You are judging a panel of models that answered the same request independently. \
+         Reconcile their answers into the single best answer: keep what they agree on, \
+         settle conflicts on the merits, and correct mistakes any of them made.\n\n",
+    );
+    let _ = writeln!(prompt, "<request>\n{}\n</request>\n", task.trim());
+    for candidate in candidates {
+        let _ = writeln!(
+            prompt,
+            "<answer model=\"{}\">\n{}\n</answer>\n",
+            candidate.model,
+            candidate.answer.trim()
+        );
+    }
+    let _ = write!(
+        prompt,
+        "Reply with the reconciled answer only, written as if it were your own. Then, on a \
+         line of its own, write `{}` followed by one `- ` bullet per point where the answers \
+         materially conflicted, naming the models involved and how you resolved it, or \
+         `- none`.",
+        DISAGREEMENTS_HEADER
+    );
+    prompt
+}
+
+/// Split the judge's reply into the reconciled answer and its disagreements
+pub fn parse_verdict(text: &str) -> Verdict {
+    let Some(index) = text.rfind(DISAGREEMENTS_HEADER) else {
+        return Verdict {
+            answer: text.trim().to_string(),
+            disagreements: Vec::new(),
+        };
+    };
+    let disagreements = text[index + DISAGREEMENTS_HEADER.len()..]
+        .lines()
+        .map(|line| {
+            line.trim()
+                .trim_start_matches(['-', '*'])
+                .trim()
+                .to_string()
+        })
+        .filter(|line| !line.is_empty() && !line.eq_ignore_ascii_case("none"))
+        .collect();
+    Verdict {
+        answer: text[..index].trim().to_string(),
+        disagreements,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panel_follows_agent_then_skill_strategy() {
+        let mut config: UiraConfig = serde_yaml_ng::from_str(
+            "consensus:\n  models: [sonnet, openai/gpt-4.1, gemini/gemini-2.5-pro, openai/o3]\nagents:\n  architect:\n    strategy: consensus\n  explore:\n    strategy: single\n",
+        )
+        .unwrap();
+
+        let panel = panel_for(&config, "architect", None, "sonnet").unwrap();
+        assert_eq!(
+            panel.models,
+            vec!["openai/gpt-4.1", "gemini/gemini-2.5-pro"]
+        );
+        assert_eq!(panel.judge, "sonnet");
+
+        assert!(panel_for(
+            &config,
+            "explore",
+            Some(ExecutionStrategy::Consensus),
+            "sonnet"
+        )
+        .is_none());
+        assert!(panel_for(&config, "executor", None, "sonnet").is_none());
+        assert!(panel_for(
+            &config,
+            "executor",
+            Some(ExecutionStrategy::Consensus),
+            "sonnet"
+        )
+        .is_some());
+
+        config.consensus.models = vec!["sonnet".to_string()];
+        assert!(panel_for(&config, "architect", None, "sonnet").is_none());
+    }
+
+    #[test]
+    fn judge_prompt_and_verdict_round_trip() {
+        let prompt = judge_prompt(
+            "Review the auth module",
+            &[
+                Candidate {
+                    model: "sonnet".to_string(),
+                    answer: "Tokens never expire.".to_string(),
+                },
+                Candidate {
+                    model: "gpt-4.1".to_string(),
+                    answer: "Looks fine.".to_string(),
+                },
+            ],
+        );
+        assert!(prompt.contains("<request>\nReview the auth module\n</request>"));
+        assert!(prompt.contains("<answer model=\"gpt-4.1\">\nLooks fine.\n</answer>"));
+        assert!(prompt.contains(DISAGREEMENTS_HEADER));
+
+        let verdict = parse_verdict(
+            "Tokens never expire; add a TTL.\n\nDISAGREEMENTS:\n- gpt-4.1 missed the missing TTL; kept sonnet's finding\n",
+        );
+        assert_eq!(verdict.answer, "Tokens never expire; add a TTL.");
+        assert_eq!(
+            verdict.disagreements,
+            vec!["gpt-4.1 missed the missing TTL; kept sonnet's finding"]
+        );
+
+        let agreed = parse_verdict("All good.\nDISAGREEMENTS:\n- none");
+        assert_eq!(agreed.answer, "All good.");
+        assert!(agreed.disagreements.is_empty());
+        assert_eq!(parse_verdict(" Plain answer ").answer, "Plain answer");
+    }
+
+    #[test]
+    fn task_text_uses_first_user_text() {
+        let messages = vec![
+            Message::system("You are an architect"),
+            Message::with_blocks(Role::User, vec![ContentBlock::text("Design the cache")]),
+            Message::user("follow-up"),
+        ];
+        assert_eq!(task_text(&messages), "Design the cache");
+    }
+}
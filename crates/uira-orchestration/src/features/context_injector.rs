@@ -24,6 +24,8 @@ pub enum ContextSourceType {
     Learner,
     #[serde(rename = "environment")]
     Environment,
+    #[serde(rename = "repo-map")]
+    RepoMap,
     #[serde(rename = "custom")]
     Custom,
 }
@@ -39,6 +41,7 @@ impl ContextSourceType {
             ContextSourceType::SessionContext => "session-context",
             ContextSourceType::Learner => "learner",
             ContextSourceType::Environment => "environment",
+            ContextSourceType::RepoMap => "repo-map",
             ContextSourceType::Custom => "custom",
         }
     }
@@ -376,6 +379,39 @@ pub fn register_environment_context(collector: &ContextCollector, session_id: &s
     );
 }
 
+/// Register a [`RepoMap`](super::repo_map::RepoMap) of `root` for a session.
+///
+/// Like [`register_environment_context`], this is meant for session start so
+/// the first turn already knows the repository layout. Returns `false` when
+/// the map is disabled or the workspace has no files.
+pub fn register_repo_map_context(
+    collector: &ContextCollector,
+    session_id: &str,
+    root: &std::path::Path,
+    settings: &uira_core::schema::RepoMapSettings,
+) -> bool {
+    if !settings.enabled {
+        return false;
+    }
+    let Some(map) = super::repo_map::RepoMap::new(root)
+        .max_tokens(settings.max_tokens)
+        .build()
+    else {
+        return false;
+    };
+    collector.register(
+        session_id,
+        RegisterContextOptions {
+            id: "repo-map".to_string(),
+            source: ContextSourceType::RepoMap,
+            content: map,
+            priority: Some(ContextPriority::Normal),
+            metadata: None,
+        },
+    );
+    true
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -494,6 +530,38 @@ mod tests {
         assert!(pending.entries[0].content.contains("<uira-env>"));
     }
 
+    #[test]
+    fn test_register_repo_map_context() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("lib.rs"), "pub fn entry() {}\n").unwrap();
+
+        let collector = ContextCollector::new();
+        let settings = uira_core::schema::RepoMapSettings::default();
+        assert!(register_repo_map_context(
+            &collector,
+            "s1",
+            dir.path(),
+            &settings
+        ));
+        register_environment_context(&collector, "s1");
+
+        let pending = collector.get_pending("s1");
+        assert_eq!(pending.entries[0].source, ContextSourceType::RepoMap);
+        assert!(pending.merged.starts_with("<repo-map>"));
+        assert!(pending.merged.contains("- lib.rs: entry"));
+
+        let disabled = uira_core::schema::RepoMapSettings {
+            enabled: false,
+            ..settings
+        };
+        assert!(!register_repo_map_context(
+            &collector,
+            "s2",
+            dir.path(),
+            &disabled
+        ));
+    }
+
     #[test]
     fn test_environment_context_injected_into_message() {
         let collector = ContextCollector::new();
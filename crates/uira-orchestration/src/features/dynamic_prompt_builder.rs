@@ -5,9 +5,25 @@
 //! key triggers, and tool selection guide auto-update.
 //!
 //! Inspired by oh-my-opencode's `dynamic-agent-prompt-builder.ts`.
+//!
+//! [`build_budgeted_orchestrator_prompt`] trims the prompt to a token budget,
+//! keeping the agents and skills most relevant to the current task and
+//! pointing the model at the `agent_lookup` tool for everything else.
 
 use crate::agents::types::{AgentCategory, AgentCost, AgentPromptMetadata};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+
+/// Tool that searches the full agent/skill registry (see `DelegationToolProvider`)
+pub const REGISTRY_LOOKUP_TOOL: &str = "agent_lookup";
+
+/// Default token budget for [`build_budgeted_orchestrator_prompt`]
+pub const DEFAULT_ORCHESTRATOR_PROMPT_BUDGET: usize = 4_000;
+
+const STOP_WORDS: &[&str] = &[
+    "the", "and", "for", "with", "that", "this", "from", "into", "use", "when", "you", "your",
+    "are", "can", "all", "any", "our", "not", "but", "how", "what", "should", "need", "please",
+    "make", "some", "then", "than", "them", "they", "have", "has", "was", "were", "will",
+];
 
 /// Available agent info for prompt generation
 #[derive(Debug, Clone)]
@@ -75,6 +91,193 @@ pub fn build_dynamic_orchestrator_prompt(
     sections.join("\n\n")
 }
 
+/// Build the orchestrator prompt from the agents and skills most relevant to
+/// `task` that fit in `max_tokens` (estimated at ~4 chars per token).
+///
+/// Returns the full prompt unchanged when it already fits. Otherwise agents
+/// and skills are ranked by keyword overlap with the task (ties keep input
+/// order) and added greedily; omitted entries are listed by name in a footer
+/// that points at the [`REGISTRY_LOOKUP_TOOL`].
+pub fn build_budgeted_orchestrator_prompt(
+    agents: &[AvailableAgent],
+    skills: &[AvailableSkill],
+    categories: &[AvailableDelegationCategory],
+    task: &str,
+    max_tokens: usize,
+) -> String {
+    let full = build_dynamic_orchestrator_prompt(agents, skills, categories);
+    if estimate_tokens(&full) <= max_tokens {
+        return full;
+    }
+
+    enum Candidate {
+        Agent(usize),
+        Skill(usize),
+    }
+
+    let mut candidates: Vec<(usize, Candidate)> = rank_agents(agents, task)
+        .into_iter()
+        .map(|(index, score)| (score, Candidate::Agent(index)))
+        .chain(
+            rank_skills(skills, task)
+                .into_iter()
+                .map(|(index, score)| (score, Candidate::Skill(index))),
+        )
+        .collect();
+    // Stable sort keeps agents ahead of skills on equal scores
+    candidates.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+
+    let mut agent_included = vec![false; agents.len()];
+    let mut skill_included = vec![false; skills.len()];
+    let mut prompt = render_trimmed(agents, skills, categories, &agent_included, &skill_included);
+
+    for (_, candidate) in candidates {
+        let flag = match candidate {
+            Candidate::Agent(index) => &mut agent_included[index],
+            Candidate::Skill(index) => &mut skill_included[index],
+        };
+        *flag = true;
+
+        let attempt = render_trimmed(agents, skills, categories, &agent_included, &skill_included);
+        if estimate_tokens(&attempt) <= max_tokens {
+            prompt = attempt;
+        } else {
+            // Undo and keep trying: a smaller entry may still fit
+            match candidate {
+                Candidate::Agent(index) => agent_included[index] = false,
+                Candidate::Skill(index) => skill_included[index] = false,
+            }
+        }
+    }
+
+    prompt
+}
+
+fn render_trimmed(
+    agents: &[AvailableAgent],
+    skills: &[AvailableSkill],
+    categories: &[AvailableDelegationCategory],
+    agent_included: &[bool],
+    skill_included: &[bool],
+) -> String {
+    let (kept_agents, omitted_agents): (Vec<_>, Vec<_>) = agents
+        .iter()
+        .zip(agent_included)
+        .partition(|(_, included)| **included);
+    let (kept_skills, omitted_skills): (Vec<_>, Vec<_>) = skills
+        .iter()
+        .zip(skill_included)
+        .partition(|(_, included)| **included);
+
+    let kept_agents: Vec<AvailableAgent> =
+        kept_agents.into_iter().map(|(a, _)| a.clone()).collect();
+    let kept_skills: Vec<AvailableSkill> =
+        kept_skills.into_iter().map(|(s, _)| s.clone()).collect();
+    let prompt = build_dynamic_orchestrator_prompt(&kept_agents, &kept_skills, categories);
+
+    if omitted_agents.is_empty() && omitted_skills.is_empty() {
+        return prompt;
+    }
+
+    let mut footer = vec![
+        "## More Agents & Skills\n".to_string(),
+        format!(
+            "Only the entries most relevant to this task are listed above. Call `{}` with a short query to see details for any of these before delegating to them:",
+            REGISTRY_LOOKUP_TOOL
+        ),
+    ];
+    if !omitted_agents.is_empty() {
+        let names: Vec<&str> = omitted_agents
+            .iter()
+            .map(|(a, _)| a.name.as_str())
+            .collect();
+        footer.push(format!("- Agents: {}", names.join(", ")));
+    }
+    if !omitted_skills.is_empty() {
+        let names: Vec<&str> = omitted_skills
+            .iter()
+            .map(|(s, _)| s.name.as_str())
+            .collect();
+        footer.push(format!("- Skills: {}", names.join(", ")));
+    }
+
+    format!("{}\n\n{}", prompt, footer.join("\n"))
+}
+
+/// Rank agents by keyword relevance to `query`, most relevant first.
+///
+/// Returns `(index, score)` pairs for every agent; matches on the agent name
+/// weigh most, then triggers and use-when hints, then descriptions.
+pub fn rank_agents(agents: &[AvailableAgent], query: &str) -> Vec<(usize, usize)> {
+    let query = keywords(query);
+    let mut ranked: Vec<(usize, usize)> = agents
+        .iter()
+        .enumerate()
+        .map(|(index, agent)| {
+            let meta = &agent.metadata;
+            let hints = meta
+                .triggers
+                .iter()
+                .flat_map(|t| [t.domain.as_str(), t.trigger.as_str()])
+                .chain(meta.use_when.iter().map(String::as_str))
+                .collect::<Vec<_>>()
+                .join(" ");
+            let description = format!(
+                "{} {}",
+                agent.description,
+                meta.prompt_description.as_deref().unwrap_or_default()
+            );
+            let score = 3 * overlap(&query, &agent.name)
+                + 2 * overlap(&query, &hints)
+                + overlap(&query, &description);
+            (index, score)
+        })
+        .collect();
+    ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    ranked
+}
+
+/// Rank skills by keyword relevance to `query`, most relevant first
+pub fn rank_skills(skills: &[AvailableSkill], query: &str) -> Vec<(usize, usize)> {
+    let query = keywords(query);
+    let mut ranked: Vec<(usize, usize)> = skills
+        .iter()
+        .enumerate()
+        .map(|(index, skill)| {
+            let score = 3 * overlap(&query, &skill.name) + overlap(&query, &skill.description);
+            (index, score)
+        })
+        .collect();
+    ranked.sort_by_key(|(_, score)| std::cmp::Reverse(*score));
+    ranked
+}
+
+fn estimate_tokens(text: &str) -> usize {
+    text.len().div_ceil(4)
+}
+
+fn keywords(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| word.len() >= 3)
+        .map(str::to_lowercase)
+        .filter(|word| !STOP_WORDS.contains(&word.as_str()))
+        .collect()
+}
+
+/// Number of query keywords found in `text`, treating shared prefixes of at
+/// least four characters as matches ("test" ~ "testing")
+fn overlap(query: &HashSet<String>, text: &str) -> usize {
+    let words = keywords(text);
+    query
+        .iter()
+        .filter(|q| {
+            words.iter().any(|w| {
+                w == *q || (q.len().min(w.len()) >= 4 && (w.starts_with(*q) || q.starts_with(w)))
+            })
+        })
+        .count()
+}
+
 /// Build the key triggers section from agent metadata.
 fn build_key_triggers_section(agents: &[AvailableAgent]) -> String {
     let mut lines = vec!["## Key Triggers\n".to_string()];
@@ -463,8 +666,12 @@ pub fn builtin_agent_metadata() -> HashMap<String, AgentPromptMetadata> {
 /// Convenience: build the full dynamic orchestrator prompt using all builtin agents.
 /// This is the main entry point for callers who just want the complete prompt section.
 pub fn build_default_orchestrator_prompt() -> String {
-    let metadata = builtin_agent_metadata();
-    let agents: Vec<AvailableAgent> = metadata
+    build_dynamic_orchestrator_prompt(&builtin_available_agents(), &[], &[])
+}
+
+/// All builtin agents with metadata, sorted by name
+pub fn builtin_available_agents() -> Vec<AvailableAgent> {
+    let mut agents: Vec<AvailableAgent> = builtin_agent_metadata()
         .into_iter()
         .map(|(name, meta)| AvailableAgent {
             description: meta.prompt_description.clone().unwrap_or_default(),
@@ -472,7 +679,8 @@ pub fn build_default_orchestrator_prompt() -> String {
             metadata: meta,
         })
         .collect();
-    build_dynamic_orchestrator_prompt(&agents, &[], &[])
+    agents.sort_by(|a, b| a.name.cmp(&b.name));
+    agents
 }
 
 #[cfg(test)]
@@ -540,6 +748,47 @@ mod tests {
         assert!(prompt.contains("frontend-ui-ux"));
     }
 
+    #[test]
+    fn test_budgeted_prompt_keeps_relevant_agents() {
+        let agents = builtin_available_agents();
+        let full = build_dynamic_orchestrator_prompt(&agents, &[], &[]);
+        assert_eq!(
+            build_budgeted_orchestrator_prompt(&agents, &[], &[], "anything", usize::MAX),
+            full
+        );
+
+        let budget = estimate_tokens(&full) * 3 / 4;
+        let prompt = build_budgeted_orchestrator_prompt(
+            &agents,
+            &[],
+            &[],
+            "Build a responsive frontend UI component",
+            budget,
+        );
+        assert!(estimate_tokens(&prompt) <= budget);
+        assert!(prompt.contains("| designer |"));
+        assert!(prompt.contains(REGISTRY_LOOKUP_TOOL));
+        assert!(prompt.contains("Anti-Patterns"));
+        assert!(prompt.len() < full.len());
+    }
+
+    #[test]
+    fn test_rank_skills_prefers_name_matches() {
+        let skills = vec![
+            AvailableSkill {
+                name: "git-master".to_string(),
+                description: "Commit and rebase workflows".to_string(),
+            },
+            AvailableSkill {
+                name: "frontend-ui-ux".to_string(),
+                description: "Frontend UI/UX expertise".to_string(),
+            },
+        ];
+        let ranked = rank_skills(&skills, "polish the frontend styling");
+        assert_eq!(ranked[0], (1, 4));
+        assert_eq!(ranked[1].1, 0);
+    }
+
     #[test]
     fn test_builtin_metadata_covers_key_agents() {
         let meta = builtin_agent_metadata();
@@ -0,0 +1,107 @@
+//! Autopilot runs started from code host issues
+//!
+//! The issue becomes the request of a new planning pipeline, and autopilot
+//! starts with a planning phase that drives that pipeline and with the
+//! issue's acceptance criteria as its goals.
+
+use std::path::Path;
+
+use uira_github::IssueTask;
+
+use crate::agents::PlanningPipeline;
+use crate::hooks::hooks::autopilot::{AutopilotConfig, AutopilotHook, AutopilotState};
+
+/// A started issue run
+#[derive(Debug, Clone)]
+pub struct IssueAutopilot {
+    pub pipeline: PlanningPipeline,
+    pub state: AutopilotState,
+}
+
+impl IssueAutopilot {
+    /// Save a planning pipeline for `task` and start autopilot on it in
+    /// `directory`
+    pub fn start(
+        directory: &Path,
+        task: &IssueTask,
+        session_id: Option<String>,
+        config: AutopilotConfig,
+    ) -> Result<Self, String> {
+        let dir = directory.to_string_lossy();
+        if AutopilotHook::is_active(&dir) {
+            return Err(format!("autopilot is already running in {}", dir));
+        }
+
+        let pipeline = PlanningPipeline::new(task.request());
+        pipeline
+            .save(directory)
+            .map_err(|e| format!("failed to save planning pipeline: {}", e))?;
+
+        let state = AutopilotHook::start_with_goals(
+            &dir,
+            &autopilot_task(task, &pipeline),
+            task.goals.clone(),
+            session_id,
+            Some(config),
+        )?;
+        Ok(Self { pipeline, state })
+    }
+
+    /// Prompt that starts the run
+    pub fn prompt(&self) -> String {
+        AutopilotHook::get_phase_prompt(&self.state)
+    }
+}
+
+fn autopilot_task(task: &IssueTask, pipeline: &PlanningPipeline) -> String {
+    format!(
+        "Resolve issue {}: {}\n\n\
+         Plan with the planning pipeline `{}`: call the planning_pipeline tool with plan_id \"{}\" \
+         to get the next stage prompt, delegate it to the named agent, and record each stage's \
+         output with stage_output until the pipeline is complete and the plan is approved.\n\n\
+         <user-request>\n{}\n</user-request>",
+        task.reference(),
+        task.title,
+        pipeline.id,
+        pipeline.id,
+        pipeline.request
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::hooks::autopilot::AutopilotPhase;
+    use uira_github::RepoRef;
+
+    fn task() -> IssueTask {
+        IssueTask {
+            repo: RepoRef::parse("team/app").unwrap(),
+            number: 4,
+            title: "Export reports".to_string(),
+            url: "https://github.com/team/app/issues/4".to_string(),
+            body: "## Acceptance criteria\n- CSV download works".to_string(),
+            comments: Vec::new(),
+            goals: vec!["CSV download works".to_string()],
+        }
+    }
+
+    #[test]
+    fn starts_pipeline_and_autopilot_for_issue() {
+        let dir = tempfile::tempdir().unwrap();
+        let run =
+            IssueAutopilot::start(dir.path(), &task(), None, AutopilotConfig::default()).unwrap();
+
+        let saved = PlanningPipeline::load(dir.path(), &run.pipeline.id).unwrap();
+        assert!(saved.request.starts_with("Resolve issue team/app#4"));
+
+        assert_eq!(run.state.phase, AutopilotPhase::Planning);
+        assert_eq!(run.state.goals, vec!["CSV download works"]);
+        let prompt = run.prompt();
+        assert!(prompt.contains(&format!("plan_id \"{}\"", run.pipeline.id)));
+
+        let err = IssueAutopilot::start(dir.path(), &task(), None, AutopilotConfig::default())
+            .unwrap_err();
+        assert!(err.contains("already running"));
+    }
+}
@@ -7,26 +7,47 @@
 //! - Advanced execution patterns
 //! - Analytics and metrics collection
 //! - Keyword detection for mode activation
+//! - Desktop notifications for long-running work
+//! - Incremental workspace index for fast code lookups
+//! - Repository map for first-turn grounding
+//! - Multi-model consensus for plans and reviews
+//! - Autopilot runs started from code host issues
+//! - Working tree snapshots to roll back failed iterations
 
 pub mod analytics;
 pub mod background_agent;
 pub mod builtin_skills;
+pub mod consensus;
 pub mod context_injector;
 pub mod delegation_categories;
 pub mod dynamic_prompt_builder;
+pub mod issue_autopilot;
 pub mod keywords;
 pub mod model_routing;
 pub mod notepad_wisdom;
+pub mod notifications;
 pub mod rate_limit_wait;
+pub mod repo_map;
+pub mod snapshots;
 pub mod state_manager;
 pub mod task_decomposer;
 pub mod uira_state;
 pub mod verification;
+pub mod workspace_index;
 
-pub use context_injector::{build_environment_context, register_environment_context};
+pub use context_injector::{
+    build_environment_context, register_environment_context, register_repo_map_context,
+};
 pub use dynamic_prompt_builder::{
-    build_default_orchestrator_prompt, build_dynamic_orchestrator_prompt, builtin_agent_metadata,
+    build_budgeted_orchestrator_prompt, build_default_orchestrator_prompt,
+    build_dynamic_orchestrator_prompt, builtin_agent_metadata, builtin_available_agents,
     AvailableAgent, AvailableDelegationCategory, AvailableSkill,
+    DEFAULT_ORCHESTRATOR_PROMPT_BUDGET,
 };
+pub use issue_autopilot::IssueAutopilot;
 pub use keywords::{KeywordDetector, KeywordPattern};
+pub use notifications::Notifier;
+pub use repo_map::RepoMap;
+pub use snapshots::{Snapshot, SnapshotStore};
 pub use state_manager::{SessionState, StateManager};
+pub use workspace_index::WorkspaceIndex;
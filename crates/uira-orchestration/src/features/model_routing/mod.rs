@@ -3,6 +3,7 @@ pub mod router;
 pub mod rules;
 pub mod scorer;
 pub mod signals;
+pub mod speculative;
 pub mod types;
 
 pub use prompts::gpt;
@@ -13,8 +14,8 @@ pub use prompts::{
 };
 pub use router::{
     analyze_task_complexity, can_escalate, escalate_model, explain_routing, get_model_for_task,
-    get_routing_recommendation, is_fixed_tier_agent, quick_tier_for_agent, route_task,
-    route_with_escalation,
+    get_routing_recommendation, is_fixed_tier_agent, plan_route, quick_tier_for_agent,
+    resolve_model_override, route_task, route_with_escalation,
 };
 
 pub use rules::{
@@ -28,4 +29,5 @@ pub use signals::{
     extract_all_signals, extract_context_signals, extract_lexical_signals,
     extract_structural_signals,
 };
+pub use speculative::escalation_reason;
 pub use types::*;
@@ -5,8 +5,10 @@ use crate::model_routing::scorer::{
 use crate::model_routing::signals::extract_all_signals;
 use crate::model_routing::types::{
     model_type_to_tier, tier_to_model_type, ModelTier, RoutingConfigOverrides, RoutingContext,
-    RoutingDecision,
+    RoutingDecision, RoutingPlan, RoutingStrategy,
 };
+use crate::ModelRegistry;
+use uira_core::schema::RoutingSettings;
 
 pub fn route_task(context: RoutingContext, config: RoutingConfigOverrides) -> RoutingDecision {
     let merged = config.merge_with_default();
@@ -150,6 +152,132 @@ pub fn route_with_escalation(
     decision
 }
 
+/// Route under the configured [`RoutingStrategy`]
+///
+/// `Speculative` keeps the routed tier when confidence is below the
+/// escalation threshold and returns the next tier up as the fallback, so the
+/// caller can prepare it while the cheaper model takes the first turn.
+pub fn plan_route(context: RoutingContext, config: RoutingConfigOverrides) -> RoutingPlan {
+    let merged = config.clone().merge_with_default();
+    match merged.strategy {
+        RoutingStrategy::Fixed | RoutingStrategy::Complexity => RoutingPlan {
+            decision: route_task(context, config),
+            fallback: None,
+        },
+        RoutingStrategy::Escalate => RoutingPlan {
+            decision: route_with_escalation(
+                context,
+                RoutingConfigOverrides {
+                    escalation_enabled: Some(true),
+                    ..config
+                },
+            ),
+            fallback: None,
+        },
+        RoutingStrategy::Speculative => {
+            let mut decision = route_task(context, config);
+            if decision.confidence >= merged.escalation_threshold || !can_escalate(decision.tier) {
+                return RoutingPlan {
+                    decision,
+                    fallback: None,
+                };
+            }
+            let tier = escalate_model(decision.tier);
+            decision.reasons.push(format!(
+                "Speculative: starting on {} with {} ready (confidence {:.2} < threshold {:.2})",
+                decision.tier.as_str(),
+                tier.as_str(),
+                decision.confidence,
+                merged.escalation_threshold
+            ));
+            let fallback = RoutingDecision {
+                model: merged.tier_models.for_tier(tier).to_string(),
+                model_type: tier_to_model_type(tier),
+                tier,
+                confidence: decision.confidence,
+                reasons: vec![format!(
+                    "Escalated from {} after an inadequate first response",
+                    decision.tier.as_str()
+                )],
+                adapted_prompt: None,
+                escalated: true,
+                original_tier: Some(decision.tier),
+            };
+            RoutingPlan {
+                decision,
+                fallback: Some(fallback),
+            }
+        }
+    }
+}
+
+/// Model a delegating call asked for with `provider` and `model`, checked
+/// against the `routing` section's allow-list and price cap
+pub fn resolve_model_override(
+    settings: &RoutingSettings,
+    provider: Option<&str>,
+    model: Option<&str>,
+) -> Result<Option<String>, String> {
+    let model = match (provider, model) {
+        (None, None) => return Ok(None),
+        (Some(provider), None) => {
+            return Err(format!("provider '{}' needs a model as well", provider))
+        }
+        (None, Some(model)) => model.to_string(),
+        // Model IDs may contain slashes themselves (e.g. on OpenRouter)
+        (Some(provider), Some(model)) => {
+            let provider = provider.to_ascii_lowercase();
+            if model.starts_with(&format!("{}/", provider)) {
+                model.to_string()
+            } else {
+                format!("{}/{}", provider, model)
+            }
+        }
+    };
+
+    if !settings.allowed_models.is_empty()
+        && !settings
+            .allowed_models
+            .iter()
+            .any(|pattern| model_matches(pattern, &model))
+    {
+        return Err(format!(
+            "model '{}' is not in routing.allowed_models ({})",
+            model,
+            settings.allowed_models.join(", ")
+        ));
+    }
+
+    if let Some(max_price) = settings.max_output_price {
+        let price = ModelRegistry::new()
+            .metadata(&model)
+            .and_then(|metadata| metadata.output_price);
+        if let Some(price) = price.filter(|price| *price > max_price) {
+            return Err(format!(
+                "model '{}' costs ${}/1M output tokens, over routing.max_output_price (${})",
+                model, price, max_price
+            ));
+        }
+    }
+
+    Ok(Some(model))
+}
+
+/// `provider/model`, `provider/*` or `*` against a model, with or without
+/// its provider
+fn model_matches(pattern: &str, model: &str) -> bool {
+    if pattern == "*" || pattern.eq_ignore_ascii_case(model) {
+        return true;
+    }
+    match (pattern.strip_suffix("/*"), model.split_once('/')) {
+        (Some(provider), Some((model_provider, _))) => {
+            provider.eq_ignore_ascii_case(model_provider)
+        }
+        (None, Some((_, id))) => pattern.eq_ignore_ascii_case(id),
+        _ => false,
+    }
+}
+
 pub fn explain_routing(context: RoutingContext, config: RoutingConfigOverrides) -> String {
     let decision = route_task(context.clone(), config);
     let signals = extract_all_signals(&context.task_prompt, &context);
@@ -318,6 +446,33 @@ pub fn analyze_task_complexity(
 mod tests {
     use super::*;
 
+    #[test]
+    fn model_override_follows_routing_policy() {
+        let mut settings = RoutingSettings::default();
+        assert_eq!(resolve_model_override(&settings, None, None), Ok(None));
+        assert_eq!(
+            resolve_model_override(&settings, Some("OpenRouter"), Some("qwen/qwen3-coder")),
+            Ok(Some("openrouter/qwen/qwen3-coder".to_string()))
+        );
+        assert!(resolve_model_override(&settings, Some("openai"), None).is_err());
+
+        settings.allowed_models = vec!["anthropic/*".to_string(), "gpt-4o-mini".to_string()];
+        assert_eq!(
+            resolve_model_override(&settings, Some("openai"), Some("gpt-4o-mini")),
+            Ok(Some("openai/gpt-4o-mini".to_string()))
+        );
+        assert!(resolve_model_override(&settings, None, Some("anthropic/claude-opus-4-1")).is_ok());
+        assert!(resolve_model_override(&settings, Some("openai"), Some("o3")).is_err());
+
+        settings.max_output_price = Some(20.0);
+        let err =
+            resolve_model_override(&settings, None, Some("anthropic/claude-opus-4-1")).unwrap_err();
+        assert!(err.contains("max_output_price"), "{}", err);
+        assert!(
+            resolve_model_override(&settings, None, Some("anthropic/claude-sonnet-4-5")).is_ok()
+        );
+    }
+
     #[test]
     fn routes_simple_search_to_low() {
         let decision = route_task(
@@ -333,6 +488,41 @@ mod tests {
         assert_eq!(decision.model_type, crate::sdk::ModelType::Haiku);
     }
 
+    #[test]
+    fn speculative_plan_prepares_next_tier_when_unsure() {
+        let context = RoutingContext {
+            task_prompt: "find where auth is implemented".to_string(),
+            agent_type: Some("explore".to_string()),
+            ..RoutingContext::default()
+        };
+        let speculative = |threshold| RoutingConfigOverrides {
+            strategy: Some(RoutingStrategy::Speculative),
+            escalation_threshold: Some(threshold),
+            ..RoutingConfigOverrides::default()
+        };
+
+        let plan = plan_route(context.clone(), speculative(1.0));
+        assert_eq!(plan.decision.tier, ModelTier::Low);
+        assert!(!plan.decision.escalated);
+        let fallback = plan.fallback.unwrap();
+        assert_eq!(fallback.tier, ModelTier::Medium);
+        assert_eq!(fallback.original_tier, Some(ModelTier::Low));
+
+        let confident = plan_route(context.clone(), speculative(0.0));
+        assert!(confident.fallback.is_none());
+
+        let escalate = plan_route(
+            context,
+            RoutingConfigOverrides {
+                strategy: Some(RoutingStrategy::Escalate),
+                escalation_threshold: Some(1.0),
+                ..RoutingConfigOverrides::default()
+            },
+        );
+        assert_eq!(escalate.decision.tier, ModelTier::Medium);
+        assert!(escalate.fallback.is_none());
+    }
+
     #[test]
     fn orchestrator_is_fixed_opus() {
         let (model, tier, _reason) = get_model_for_task(
@@ -0,0 +1,119 @@
+//! Escalation heuristics for speculative routing
+//!
+//! A speculatively routed task takes its first turn on the cheaper tier.
+//! [`escalation_reason`] inspects that first response and says whether the
+//! task should move to the prepared higher tier instead.
+
+use uira_core::{ModelResponse, StopReason};
+
+/// Phrases that show the model is out of its depth
+const UNCERTAINTY_PHRASES: &[&str] = &[
+    "i'm not sure",
+    "i am not sure",
+    "i'm not certain",
+    "i am not certain",
+    "i'm unable to",
+    "i am unable to",
+    "i cannot determine",
+    "i can't determine",
+    "i don't have enough",
+    "i do not have enough",
+    "beyond my capabilities",
+    "too complex for me",
+];
+
+/// Why the first response of a speculative run should be retried on the
+/// higher tier, or `None` when it looks adequate
+///
+/// Triggers on empty or truncated responses, provider-reported errors, calls
+/// to tools that are not in `tool_names`, and text that admits uncertainty
+/// without making any tool call.
+pub fn escalation_reason(response: &ModelResponse, tool_names: &[&str]) -> Option<String> {
+    let text = response.text();
+    let tool_calls = response.tool_calls();
+
+    if text.trim().is_empty() && tool_calls.is_empty() {
+        return Some("empty response".to_string());
+    }
+    match response.stop_reason {
+        Some(StopReason::MaxTokens) => return Some("response hit the token limit".to_string()),
+        Some(StopReason::Error) => return Some("response ended with an error".to_string()),
+        _ => {}
+    }
+    if let Some(call) = tool_calls
+        .iter()
+        .find(|call| !tool_names.contains(&call.name.as_str()))
+    {
+        return Some(format!("called unknown tool '{}'", call.name));
+    }
+    if tool_calls.is_empty() {
+        let lower = text.to_lowercase();
+        if let Some(phrase) = UNCERTAINTY_PHRASES.iter().find(|p| lower.contains(*p)) {
+            return Some(format!("response expressed uncertainty (\"{}\")", phrase));
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uira_core::{ContentBlock, TokenUsage};
+
+    fn response(content: Vec<ContentBlock>, stop_reason: StopReason) -> ModelResponse {
+        ModelResponse {
+            id: "msg".to_string(),
+            model: "haiku".to_string(),
+            content,
+            stop_reason: Some(stop_reason),
+            usage: TokenUsage::default(),
+        }
+    }
+
+    fn text(text: &str) -> ContentBlock {
+        ContentBlock::Text {
+            text: text.to_string(),
+        }
+    }
+
+    #[test]
+    fn escalates_inadequate_first_responses() {
+        let tools = ["read_file", "grep"];
+
+        let ok = response(
+            vec![text("The handler lives in src/auth.rs.")],
+            StopReason::EndTurn,
+        );
+        assert_eq!(escalation_reason(&ok, &tools), None);
+
+        let empty = response(vec![], StopReason::EndTurn);
+        assert_eq!(
+            escalation_reason(&empty, &tools).as_deref(),
+            Some("empty response")
+        );
+
+        let truncated = response(vec![text("Partial")], StopReason::MaxTokens);
+        assert!(escalation_reason(&truncated, &tools).is_some());
+
+        let unsure = response(
+            vec![text("I'm not sure how these modules interact.")],
+            StopReason::EndTurn,
+        );
+        assert!(escalation_reason(&unsure, &tools)
+            .unwrap()
+            .contains("uncertainty"));
+
+        let bad_tool = response(
+            vec![ContentBlock::ToolUse {
+                id: "1".to_string(),
+                name: "search_everything".to_string(),
+                input: serde_json::json!({}),
+            }],
+            StopReason::ToolUse,
+        );
+        assert_eq!(
+            escalation_reason(&bad_tool, &tools).as_deref(),
+            Some("called unknown tool 'search_everything'")
+        );
+    }
+}
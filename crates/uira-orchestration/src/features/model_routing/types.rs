@@ -2,6 +2,9 @@ use std::collections::HashMap;
 
 use crate::sdk::ModelType;
 use serde::{Deserialize, Serialize};
+use uira_core::schema::RoutingSettings;
+
+pub use uira_core::schema::RoutingStrategy;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Hash)]
 #[serde(rename_all = "UPPERCASE")]
@@ -107,6 +110,14 @@ pub struct RoutingDecision {
     pub original_tier: Option<ModelTier>,
 }
 
+/// Routing outcome under a [`RoutingStrategy`]: the decision to start with
+/// and, for speculative routing, the tier to escalate to
+#[derive(Debug, Clone, PartialEq)]
+pub struct RoutingPlan {
+    pub decision: RoutingDecision,
+    pub fallback: Option<RoutingDecision>,
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct RoutingContext {
     pub task_prompt: String,
@@ -146,6 +157,7 @@ impl TierModels {
 #[derive(Debug, Clone)]
 pub struct RoutingConfig {
     pub enabled: bool,
+    pub strategy: RoutingStrategy,
     pub default_tier: ModelTier,
     pub escalation_enabled: bool,
     pub max_escalations: u32,
@@ -169,6 +181,7 @@ impl Default for RoutingConfig {
 
         Self {
             enabled: true,
+            strategy: RoutingStrategy::default(),
             default_tier: ModelTier::Medium,
             escalation_enabled: false,
             max_escalations: 0,
@@ -206,6 +219,7 @@ impl Default for RoutingConfig {
 #[derive(Debug, Clone, Default)]
 pub struct RoutingConfigOverrides {
     pub enabled: Option<bool>,
+    pub strategy: Option<RoutingStrategy>,
     pub default_tier: Option<ModelTier>,
     pub escalation_enabled: Option<bool>,
     pub max_escalations: Option<u32>,
@@ -217,12 +231,24 @@ pub struct RoutingConfigOverrides {
 }
 
 impl RoutingConfigOverrides {
+    /// Overrides for the `routing` config section
+    pub fn from_settings(settings: &RoutingSettings) -> Self {
+        Self {
+            strategy: Some(settings.strategy),
+            escalation_threshold: Some(settings.confidence_threshold),
+            ..Self::default()
+        }
+    }
+
     pub fn merge_with_default(self) -> RoutingConfig {
         let mut cfg = RoutingConfig::default();
 
         if let Some(v) = self.enabled {
             cfg.enabled = v;
         }
+        if let Some(v) = self.strategy {
+            cfg.strategy = v;
+        }
         if let Some(v) = self.default_tier {
             cfg.default_tier = v;
         }
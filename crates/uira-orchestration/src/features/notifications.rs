@@ -0,0 +1,185 @@
+//! Native desktop notifications for long-running work
+//!
+//! Notifications go through the platform's own notifier (`osascript` on
+//! macOS, `notify-send` on Linux) or a user-configured command, so no
+//! notification daemon bindings are needed. Sending never blocks and never
+//! fails the caller; a missing notifier is only logged.
+
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+use uira_core::schema::NotificationSettings;
+
+const APP_NAME: &str = "uira";
+
+/// Sends desktop notifications according to the `notifications:` settings
+#[derive(Debug, Clone, Default)]
+pub struct Notifier {
+    settings: NotificationSettings,
+}
+
+impl Notifier {
+    pub fn new(settings: NotificationSettings) -> Self {
+        Self { settings }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.settings.enabled
+    }
+
+    /// Notify that a run finished, if it ran for at least `min_duration_secs`
+    pub fn run_finished(&self, summary: &str, elapsed: Duration, success: bool) -> bool {
+        if elapsed.as_secs() < self.settings.min_duration_secs {
+            return false;
+        }
+        let title = if success {
+            "Uira finished"
+        } else {
+            "Uira stopped"
+        };
+        let body = format!("{} ({})", summary, format_elapsed(elapsed));
+        self.send(title, &body)
+    }
+
+    /// Notify that a background task completed or failed
+    pub fn background_task_finished(&self, description: &str, success: bool) -> bool {
+        if !self.settings.background_tasks {
+            return false;
+        }
+        let title = if success {
+            "Background task completed"
+        } else {
+            "Background task failed"
+        };
+        self.send(title, description)
+    }
+
+    /// Send a notification now; returns whether a notifier was launched
+    pub fn send(&self, title: &str, body: &str) -> bool {
+        if !self.settings.enabled {
+            return false;
+        }
+        let Some((program, args)) = notifier_command(&self.settings.command, title, body) else {
+            tracing::debug!("No desktop notifier available on this platform");
+            return false;
+        };
+
+        match Command::new(&program)
+            .args(&args)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        {
+            Ok(mut child) => {
+                // Reap the notifier without holding up the caller
+                std::thread::spawn(move || {
+                    let _ = child.wait();
+                });
+                true
+            }
+            Err(e) => {
+                tracing::debug!("Failed to launch notifier {}: {}", program, e);
+                false
+            }
+        }
+    }
+}
+
+/// Program and arguments for a notification, from `custom` when configured
+fn notifier_command(custom: &[String], title: &str, body: &str) -> Option<(String, Vec<String>)> {
+    let body = truncate(body, 240);
+    if let Some((program, args)) = custom.split_first() {
+        let args = args
+            .iter()
+            .map(|arg| arg.replace("{title}", title).replace("{body}", &body))
+            .collect();
+        return Some((program.clone(), args));
+    }
+
+    if cfg!(target_os = "macos") {
+        let script = format!(
+            "display notification {} with title {}",
+            applescript_string(&body),
+            applescript_string(title)
+        );
+        Some(("osascript".to_string(), vec!["-e".to_string(), script]))
+    } else if cfg!(target_os = "linux") {
+        Some((
+            "notify-send".to_string(),
+            vec![format!("--app-name={APP_NAME}"), title.to_string(), body],
+        ))
+    } else {
+        None
+    }
+}
+
+fn applescript_string(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+fn truncate(s: &str, max_chars: usize) -> String {
+    let line = s.lines().next().unwrap_or_default().trim();
+    if line.chars().count() <= max_chars {
+        return line.to_string();
+    }
+    let mut out: String = line.chars().take(max_chars).collect();
+    out.push('…');
+    out
+}
+
+fn format_elapsed(elapsed: Duration) -> String {
+    let secs = elapsed.as_secs();
+    if secs >= 60 {
+        format!("{}m {}s", secs / 60, secs % 60)
+    } else {
+        format!("{secs}s")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_custom_and_platform_commands() {
+        let custom = vec![
+            "terminal-notifier".to_string(),
+            "-title".to_string(),
+            "{title}".to_string(),
+            "-message".to_string(),
+            "{body}".to_string(),
+        ];
+        let (program, args) = notifier_command(&custom, "Done", "all tests pass\nmore").unwrap();
+        assert_eq!(program, "terminal-notifier");
+        assert_eq!(args, vec!["-title", "Done", "-message", "all tests pass"]);
+
+        if cfg!(target_os = "macos") {
+            let (program, args) = notifier_command(&[], "Say \"hi\"", "body").unwrap();
+            assert_eq!(program, "osascript");
+            assert!(args[1].ends_with("with title \"Say \\\"hi\\\"\""));
+        } else if cfg!(target_os = "linux") {
+            let (program, args) = notifier_command(&[], "Done", "body").unwrap();
+            assert_eq!(program, "notify-send");
+            assert_eq!(args[1..], ["Done", "body"]);
+        }
+    }
+
+    #[test]
+    fn respects_enabled_and_thresholds() {
+        let disabled = Notifier::default();
+        assert!(!disabled.send("title", "body"));
+
+        let notifier = Notifier::new(NotificationSettings {
+            enabled: true,
+            min_duration_secs: 30,
+            background_tasks: false,
+            // `true` exists everywhere the tests run and ignores its arguments
+            command: vec!["true".to_string()],
+        });
+        assert!(!notifier.run_finished("quick", Duration::from_secs(5), true));
+        assert!(notifier.run_finished("slow", Duration::from_secs(90), true));
+        assert!(!notifier.background_task_finished("task", true));
+        assert_eq!(format_elapsed(Duration::from_secs(90)), "1m 30s");
+    }
+}
@@ -0,0 +1,263 @@
+//! Repository map for first-turn grounding
+//!
+//! [`RepoMap`] condenses the workspace index into a small ranked summary:
+//! top-level layout, languages, and the files that declare the most public
+//! API. It is registered as startup context so the model knows where things
+//! live before it makes its first tool call.
+
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use uira_core::schema::WorkspaceIndexSettings;
+
+use crate::features::workspace_index::{self, IndexedFile, WorkspaceIndex};
+
+/// Rough characters-per-token ratio used to enforce the budget
+const CHARS_PER_TOKEN: usize = 4;
+const DEFAULT_MAX_TOKENS: usize = 1500;
+const DEFAULT_SYMBOLS_PER_FILE: usize = 8;
+const MAX_TOP_LEVEL_ENTRIES: usize = 20;
+
+/// Manifests and entry points that anchor a codebase
+const KEY_FILES: &[&str] = &[
+    "README.md",
+    "Cargo.toml",
+    "package.json",
+    "go.mod",
+    "pyproject.toml",
+    "setup.py",
+    "lib.rs",
+    "main.rs",
+    "main.go",
+    "main.py",
+    "index.ts",
+    "index.js",
+];
+
+/// Builds a compact ranked summary of a repository
+#[derive(Debug, Clone)]
+pub struct RepoMap {
+    root: PathBuf,
+    max_tokens: usize,
+    symbols_per_file: usize,
+}
+
+impl RepoMap {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self {
+            root: root.into(),
+            max_tokens: DEFAULT_MAX_TOKENS,
+            symbols_per_file: DEFAULT_SYMBOLS_PER_FILE,
+        }
+    }
+
+    pub fn max_tokens(mut self, max_tokens: usize) -> Self {
+        self.max_tokens = max_tokens;
+        self
+    }
+
+    pub fn symbols_per_file(mut self, count: usize) -> Self {
+        self.symbols_per_file = count;
+        self
+    }
+
+    /// Build from the shared workspace index, falling back to a one-off scan
+    /// when no index snapshot exists yet
+    pub fn build(&self) -> Option<String> {
+        let index = WorkspaceIndex::shared(&self.root);
+        let files = if index.warm_start() {
+            index.files()
+        } else {
+            workspace_index::scan_files(&self.root, &WorkspaceIndexSettings::default())
+        };
+        self.render(&files)
+    }
+
+    /// Render the map for `files`; `None` for an empty workspace
+    pub fn render(&self, files: &[IndexedFile]) -> Option<String> {
+        if files.is_empty() {
+            return None;
+        }
+        let budget = self.max_tokens * CHARS_PER_TOKEN;
+        let mut out = String::from("<repo-map>\n");
+
+        out.push_str(&format!("Top-level: {}\n", top_level(files)));
+        let languages = languages(files);
+        if !languages.is_empty() {
+            out.push_str(&format!("Languages: {}\n", languages));
+        }
+
+        let mut ranked: Vec<(f64, &IndexedFile)> = files
+            .iter()
+            .map(|file| (score(file), file))
+            .filter(|(score, _)| *score > 0.0)
+            .collect();
+        ranked.sort_by(|a, b| b.0.total_cmp(&a.0).then(a.1.path.cmp(&b.1.path)));
+
+        if !ranked.is_empty() {
+            out.push_str("Key files:\n");
+        }
+        let closing = "</repo-map>";
+        for (_, file) in ranked {
+            let line = self.file_line(file);
+            if out.len() + line.len() + closing.len() > budget {
+                break;
+            }
+            out.push_str(&line);
+        }
+        out.push_str(closing);
+        Some(out)
+    }
+
+    fn file_line(&self, file: &IndexedFile) -> String {
+        let exported: Vec<&str> = file
+            .symbols
+            .iter()
+            .filter(|s| s.exported)
+            .map(|s| s.name.as_str())
+            .collect();
+        if exported.is_empty() {
+            return format!("- {}\n", file.path);
+        }
+        let shown = exported.len().min(self.symbols_per_file);
+        let mut names = exported[..shown].join(", ");
+        if exported.len() > shown {
+            names.push_str(&format!(" (+{} more)", exported.len() - shown));
+        }
+        format!("- {}: {}\n", file.path, names)
+    }
+}
+
+/// Top-level directories with file counts, largest first, then root files
+fn top_level(files: &[IndexedFile]) -> String {
+    let mut dirs: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut root_files = Vec::new();
+    for file in files {
+        match file.path.split_once('/') {
+            Some((dir, _)) => *dirs.entry(dir).or_insert(0) += 1,
+            None => root_files.push(file.path.as_str()),
+        }
+    }
+    let mut dirs: Vec<_> = dirs.into_iter().collect();
+    dirs.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+
+    let mut entries: Vec<String> = dirs
+        .into_iter()
+        .map(|(dir, count)| format!("{}/ ({})", dir, count))
+        .chain(root_files.into_iter().map(str::to_string))
+        .collect();
+    if entries.len() > MAX_TOP_LEVEL_ENTRIES {
+        let hidden = entries.len() - MAX_TOP_LEVEL_ENTRIES;
+        entries.truncate(MAX_TOP_LEVEL_ENTRIES);
+        entries.push(format!("… {} more", hidden));
+    }
+    entries.join(", ")
+}
+
+fn languages(files: &[IndexedFile]) -> String {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for language in files.iter().filter_map(|f| f.language.as_deref()) {
+        *counts.entry(language).or_insert(0) += 1;
+    }
+    let mut counts: Vec<_> = counts.into_iter().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    counts
+        .into_iter()
+        .map(|(language, count)| format!("{} ({})", language, count))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Files declaring more public API rank higher; entry points and manifests
+/// get a bonus, tests and deep paths a penalty
+fn score(file: &IndexedFile) -> f64 {
+    let name = file.path.rsplit('/').next().unwrap_or(&file.path);
+    let exported = file.symbols.iter().filter(|s| s.exported).count().min(15);
+    let mut score = exported as f64 * 2.0;
+    if KEY_FILES.contains(&name) {
+        score += 20.0;
+    } else if name == "mod.rs" {
+        score += 6.0;
+    }
+    if is_test_path(&file.path) {
+        score *= 0.2;
+    }
+    let depth = file.path.matches('/').count();
+    score / (1.0 + depth as f64 * 0.25)
+}
+
+fn is_test_path(path: &str) -> bool {
+    let lower = path.to_lowercase();
+    lower
+        .split('/')
+        .any(|part| matches!(part, "test" | "tests" | "__tests__" | "spec" | "fixtures"))
+        || lower.contains("_test.")
+        || lower.contains(".test.")
+        || lower.contains(".spec.")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::features::workspace_index::IndexedSymbol;
+
+    fn file(path: &str, symbols: &[(&str, bool)]) -> IndexedFile {
+        IndexedFile {
+            path: path.to_string(),
+            size: 1,
+            modified_ms: 0,
+            language: Some("rust".to_string()),
+            symbols: symbols
+                .iter()
+                .enumerate()
+                .map(|(i, (name, exported))| IndexedSymbol {
+                    name: name.to_string(),
+                    kind: "function".to_string(),
+                    line: i as u32 + 1,
+                    exported: *exported,
+                })
+                .collect(),
+            embedding: None,
+        }
+    }
+
+    #[test]
+    fn ranks_public_api_and_respects_budget() {
+        let files = vec![
+            file("Cargo.toml", &[]),
+            file(
+                "crates/core/src/lib.rs",
+                &[("Config", true), ("load", true)],
+            ),
+            file("crates/core/src/util.rs", &[("helper", false)]),
+            file("crates/core/tests/it.rs", &[("a", true), ("b", true)]),
+            file("crates/cli/src/main.rs", &[("run", true)]),
+        ];
+
+        let map = RepoMap::new("/repo").render(&files).unwrap();
+        assert!(map.starts_with("<repo-map>\nTop-level: crates/ (4), Cargo.toml\n"));
+        assert!(map.contains("Languages: rust (5)"));
+        assert!(map.contains("- crates/core/src/lib.rs: Config, load\n"));
+        assert!(!map.contains("util.rs"));
+        let lib = map.find("core/src/lib.rs").unwrap();
+        let tests = map.find("tests/it.rs").unwrap();
+        assert!(lib < tests);
+
+        let tiny = RepoMap::new("/repo").max_tokens(25).render(&files).unwrap();
+        assert!(tiny.len() <= 25 * CHARS_PER_TOKEN);
+        assert!(!tiny.contains("main.rs"));
+        assert!(tiny.ends_with("</repo-map>"));
+
+        assert!(RepoMap::new("/repo").render(&[]).is_none());
+    }
+
+    #[test]
+    fn truncates_symbol_lists() {
+        let names: Vec<(&str, bool)> = ["a", "b", "c", "d"].iter().map(|n| (*n, true)).collect();
+        let map = RepoMap::new("/repo")
+            .symbols_per_file(2)
+            .render(&[file("src/lib.rs", &names)])
+            .unwrap();
+        assert!(map.contains("- src/lib.rs: a, b (+2 more)\n"));
+    }
+}
@@ -0,0 +1,364 @@
+//! Working tree snapshots stored as git refs
+//!
+//! A snapshot records every tracked and untracked (non-ignored) file in the
+//! repository as a commit under `refs/uira/snapshots/`. Capturing goes through
+//! a scratch index, so the user's index, HEAD and stash are never touched.
+//! Restoring rewrites the working tree to match the snapshot, after first
+//! snapshotting the current state so the restore can itself be undone.
+
+use chrono::{DateTime, TimeZone, Utc};
+use serde::Serialize;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Ref namespace holding snapshots
+pub const SNAPSHOT_REF_PREFIX: &str = "refs/uira/snapshots/";
+
+/// Snapshots kept per repository before the oldest are pruned
+pub const DEFAULT_MAX_SNAPSHOTS: usize = 50;
+
+/// Identity used for snapshot commits, so capturing works without a
+/// configured git user
+const SNAPSHOT_AUTHOR: (&str, &str) = ("uira", "uira@localhost");
+
+/// A captured working tree state
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Snapshot {
+    pub id: String,
+    pub commit: String,
+    pub label: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Snapshot store for the repository containing a directory
+#[derive(Debug, Clone)]
+pub struct SnapshotStore {
+    root: PathBuf,
+    max_snapshots: usize,
+}
+
+impl SnapshotStore {
+    /// Open the store for the repository containing `dir`, `None` outside git
+    pub fn open(dir: &Path) -> Option<Self> {
+        let root = git(dir, &["rev-parse", "--show-toplevel"]).ok()?;
+        Some(Self {
+            root: PathBuf::from(root),
+            max_snapshots: DEFAULT_MAX_SNAPSHOTS,
+        })
+    }
+
+    pub fn with_max_snapshots(mut self, max_snapshots: usize) -> Self {
+        self.max_snapshots = max_snapshots.max(1);
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Snapshot the working tree; returns `None` when it matches the latest
+    /// snapshot already
+    pub fn capture(&self, label: &str) -> io::Result<Option<Snapshot>> {
+        let tree = self.write_tree()?;
+        if let Some(latest) = self.list()?.into_iter().next() {
+            if self.tree_of(&latest.commit)? == tree {
+                return Ok(None);
+            }
+        }
+
+        let mut args = vec!["commit-tree", tree.as_str(), "-m", label];
+        let head = git(&self.root, &["rev-parse", "--verify", "--quiet", "HEAD"]).ok();
+        if let Some(head) = head.as_deref() {
+            args.extend(["-p", head]);
+        }
+        let commit = git_with_env(&self.root, &args, &author_env())?;
+
+        let created_at = Utc::now();
+        let id = created_at.format("%Y%m%d-%H%M%S%.3f").to_string();
+        let reference = format!("{}{}", SNAPSHOT_REF_PREFIX, id);
+        git(&self.root, &["update-ref", &reference, &commit])?;
+        self.prune()?;
+
+        Ok(Some(Snapshot {
+            id,
+            commit,
+            label: label.to_string(),
+            created_at,
+        }))
+    }
+
+    /// Snapshots, newest first
+    pub fn list(&self) -> io::Result<Vec<Snapshot>> {
+        let output = git(
+            &self.root,
+            &[
+                "for-each-ref",
+                "--sort=-refname",
+                "--format=%(refname:lstrip=3)%00%(objectname)%00%(committerdate:unix)%00%(subject)",
+                SNAPSHOT_REF_PREFIX,
+            ],
+        )?;
+        Ok(output
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(4, '\0');
+                let id = parts.next()?.to_string();
+                let commit = parts.next()?.to_string();
+                let created_at = Utc.timestamp_opt(parts.next()?.parse().ok()?, 0).single()?;
+                let label = parts.next().unwrap_or_default().to_string();
+                Some(Snapshot {
+                    id,
+                    commit,
+                    label,
+                    created_at,
+                })
+            })
+            .collect())
+    }
+
+    /// Find a snapshot by id or unique id prefix
+    pub fn find(&self, id: &str) -> io::Result<Option<Snapshot>> {
+        let matches: Vec<Snapshot> = self
+            .list()?
+            .into_iter()
+            .filter(|snapshot| snapshot.id.starts_with(id))
+            .collect();
+        match matches.len() {
+            0 => Ok(None),
+            1 => Ok(matches.into_iter().next()),
+            n => Err(io::Error::other(format!(
+                "'{}' matches {} snapshots; use a longer id",
+                id, n
+            ))),
+        }
+    }
+
+    /// Files added or modified in the working tree since `commit`, relative
+    /// to the repository root
+    pub fn changed_since(&self, commit: &str) -> io::Result<Vec<PathBuf>> {
+        let current = self.write_tree()?;
+        let changed = git(
+            &self.root,
+            &[
+                "diff-tree",
+                "-r",
+                "-z",
+                "--name-only",
+                "--diff-filter=d",
+                commit,
+                &current,
+            ],
+        )?;
+        Ok(changed
+            .split('\0')
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from)
+            .collect())
+    }
+
+    /// Rewrite the working tree to match `snapshot`, removing files created
+    /// since; returns the snapshot of the state it replaced, if that state
+    /// wasn't already snapshotted
+    pub fn restore(&self, snapshot: &Snapshot) -> io::Result<Option<Snapshot>> {
+        let backup = self.capture(&format!("before restoring {}", snapshot.id))?;
+        let current = self.write_tree()?;
+        let target = self.tree_of(&snapshot.commit)?;
+
+        let added = git(
+            &self.root,
+            &[
+                "diff-tree",
+                "-r",
+                "-z",
+                "--name-only",
+                "--diff-filter=A",
+                &target,
+                &current,
+            ],
+        )?;
+        for path in added.split('\0').filter(|path| !path.is_empty()) {
+            let path = self.root.join(path);
+            if path.is_file() || path.is_symlink() {
+                std::fs::remove_file(path)?;
+            }
+        }
+
+        git(
+            &self.root,
+            &[
+                "restore",
+                "--source",
+                &snapshot.commit,
+                "--worktree",
+                "--",
+                ".",
+            ],
+        )?;
+        Ok(backup)
+    }
+
+    /// Write the working tree to a tree object through a scratch index
+    /// seeded from the real one, so unchanged files aren't rehashed
+    fn write_tree(&self) -> io::Result<String> {
+        let real_index = PathBuf::from(git(
+            &self.root,
+            &["rev-parse", "--path-format=absolute", "--git-path", "index"],
+        )?);
+        let scratch =
+            real_index.with_file_name(format!("uira-snapshot-index-{}", std::process::id()));
+        if real_index.exists() {
+            std::fs::copy(&real_index, &scratch)?;
+        }
+
+        let index_env = [("GIT_INDEX_FILE", scratch.to_string_lossy().to_string())];
+        let result = git_with_env(&self.root, &["add", "-A", "--", "."], &index_env)
+            .and_then(|_| git_with_env(&self.root, &["write-tree"], &index_env));
+        let _ = std::fs::remove_file(&scratch);
+        result
+    }
+
+    fn tree_of(&self, commit: &str) -> io::Result<String> {
+        git(&self.root, &["rev-parse", &format!("{}^{{tree}}", commit)])
+    }
+
+    fn prune(&self) -> io::Result<()> {
+        for stale in self.list()?.into_iter().skip(self.max_snapshots) {
+            let reference = format!("{}{}", SNAPSHOT_REF_PREFIX, stale.id);
+            git(&self.root, &["update-ref", "-d", &reference])?;
+        }
+        Ok(())
+    }
+}
+
+fn author_env() -> Vec<(&'static str, String)> {
+    let (name, email) = SNAPSHOT_AUTHOR;
+    vec![
+        ("GIT_AUTHOR_NAME", name.to_string()),
+        ("GIT_AUTHOR_EMAIL", email.to_string()),
+        ("GIT_COMMITTER_NAME", name.to_string()),
+        ("GIT_COMMITTER_EMAIL", email.to_string()),
+    ]
+}
+
+fn git(cwd: &Path, args: &[&str]) -> io::Result<String> {
+    git_with_env(cwd, args, &[])
+}
+
+fn git_with_env(cwd: &Path, args: &[&str], env: &[(&str, String)]) -> io::Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .envs(env.iter().map(|(key, value)| (*key, value.as_str())))
+        .current_dir(cwd)
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "git {}: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .trim_end()
+        .to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn init_repo() -> tempfile::TempDir {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "-q"]).unwrap();
+        std::fs::write(root.join(".gitignore"), "target/\n").unwrap();
+        std::fs::write(root.join("lib.rs"), "fn a() {}\n").unwrap();
+        git(root, &["add", "-A"]).unwrap();
+        git_with_env(root, &["commit", "-q", "-m", "init"], &author_env()).unwrap();
+        dir
+    }
+
+    #[test]
+    fn test_capture_skips_unchanged_tree() {
+        let repo = init_repo();
+        let store = SnapshotStore::open(repo.path()).unwrap();
+
+        let first = store.capture("start").unwrap().unwrap();
+        assert!(store.capture("again").unwrap().is_none());
+
+        std::fs::write(repo.path().join("new.rs"), "fn b() {}\n").unwrap();
+        let second = store.capture("after edit").unwrap().unwrap();
+        assert_eq!(
+            store.changed_since(&first.commit).unwrap(),
+            vec![PathBuf::from("new.rs")]
+        );
+
+        let listed = store.list().unwrap();
+        assert_eq!(listed.len(), 2);
+        assert_eq!(listed[0].id, second.id);
+        assert_eq!(listed[0].label, "after edit");
+        assert_eq!(listed[1].commit, first.commit);
+        // The user's index is untouched
+        assert_eq!(
+            git(repo.path(), &["status", "--porcelain"]).unwrap(),
+            "?? new.rs"
+        );
+    }
+
+    #[test]
+    fn test_restore_reverts_edits_and_new_files() {
+        let repo = init_repo();
+        let root = repo.path();
+        let store = SnapshotStore::open(root).unwrap();
+        std::fs::write(root.join("wip.rs"), "fn wip() {}\n").unwrap();
+        let good = store.capture("good").unwrap().unwrap();
+
+        std::fs::write(root.join("lib.rs"), "broken").unwrap();
+        std::fs::write(root.join("extra.rs"), "fn extra() {}\n").unwrap();
+        std::fs::remove_file(root.join("wip.rs")).unwrap();
+        std::fs::create_dir(root.join("target")).unwrap();
+        std::fs::write(root.join("target/out"), "build").unwrap();
+
+        let backup = store.restore(&good).unwrap().unwrap();
+        assert_eq!(backup.label, format!("before restoring {}", good.id));
+        assert_eq!(
+            std::fs::read_to_string(root.join("lib.rs")).unwrap(),
+            "fn a() {}\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(root.join("wip.rs")).unwrap(),
+            "fn wip() {}\n"
+        );
+        assert!(!root.join("extra.rs").exists());
+        // Ignored files are left alone
+        assert!(root.join("target/out").exists());
+
+        let found = store.find(&backup.id).unwrap().unwrap();
+        store.restore(&found).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(root.join("lib.rs")).unwrap(),
+            "broken"
+        );
+        assert!(root.join("extra.rs").exists());
+    }
+
+    #[test]
+    fn test_prunes_oldest_snapshots() {
+        let repo = init_repo();
+        let store = SnapshotStore::open(repo.path())
+            .unwrap()
+            .with_max_snapshots(2);
+        for i in 0..3 {
+            std::fs::write(repo.path().join("lib.rs"), format!("fn v{}() {{}}\n", i)).unwrap();
+            store.capture(&format!("v{}", i)).unwrap().unwrap();
+        }
+        let labels: Vec<String> = store.list().unwrap().into_iter().map(|s| s.label).collect();
+        assert_eq!(labels, vec!["v2", "v1"]);
+    }
+
+    #[test]
+    fn test_open_outside_git() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(SnapshotStore::open(dir.path()).is_none());
+    }
+}
@@ -3,7 +3,6 @@ pub mod types;
 pub use types::*;
 
 use std::collections::HashMap;
-use std::process::Command;
 use std::time::{Duration, SystemTime};
 
 /// Standard verification checks used across workflows
@@ -193,15 +192,7 @@ async fn run_single_check(
 /// until the command completes. For actual timeout support, use an async runtime
 /// with `tokio::time::timeout` wrapping the command execution.
 fn run_command_without_timeout(cmd: &str, cwd: Option<&str>) -> Result<(String, String), String> {
-    let mut command = if cfg!(target_os = "windows") {
-        let mut c = Command::new("cmd");
-        c.args(["/C", cmd]);
-        c
-    } else {
-        let mut c = Command::new("sh");
-        c.args(["-c", cmd]);
-        c
-    };
+    let mut command = uira_core::shell_command(cmd, None);
 
     if let Some(dir) = cwd {
         command.current_dir(dir);
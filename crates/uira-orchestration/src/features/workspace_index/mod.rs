@@ -0,0 +1,835 @@
+//! Workspace indexer
+//!
+//! Keeps an incrementally updated index of the project's file tree, the
+//! symbols declared in each file and (optionally) one embedding per file, so
+//! explore agents can answer "where is X" without cold grep/glob scans.
+//!
+//! One index exists per workspace root ([`WorkspaceIndex::shared`]).
+//! Interactive sessions start a polling watcher on it; every scan only
+//! re-reads files whose size or modification time changed. The index is
+//! persisted to `.uira/index/workspace.json` so restarts begin warm.
+
+pub mod symbols;
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+use lazy_static::lazy_static;
+use parking_lot::{Mutex, RwLock};
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use uira_core::schema::WorkspaceIndexSettings;
+use uira_core::UIRA_DIR;
+use uira_memory::EmbeddingProvider;
+
+use crate::tools::lsp::{LspClient, LspClientImpl};
+use crate::tools::ToolContent;
+pub use symbols::IndexedSymbol;
+
+const SNAPSHOT_VERSION: u32 = 2;
+const EMBED_BATCH: usize = 64;
+const EMBED_PREVIEW_CHARS: usize = 1500;
+
+lazy_static! {
+    static ref INDEXES: Mutex<HashMap<PathBuf, Arc<WorkspaceIndex>>> = Mutex::new(HashMap::new());
+}
+
+/// One file in the index
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct IndexedFile {
+    /// Path relative to the workspace root, `/`-separated
+    pub path: String,
+    pub size: u64,
+    /// Modification time in milliseconds since the Unix epoch
+    pub modified_ms: u64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub symbols: Vec<IndexedSymbol>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub embedding: Option<Vec<f32>>,
+}
+
+#[derive(Debug, Default)]
+struct IndexState {
+    files: BTreeMap<String, IndexedFile>,
+    truncated: bool,
+    last_refresh: Option<Instant>,
+    loaded_snapshot: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct Snapshot {
+    version: u32,
+    #[serde(default)]
+    truncated: bool,
+    files: Vec<IndexedFile>,
+}
+
+/// What one incremental scan changed
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct RefreshStats {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub unchanged: usize,
+    pub duration_ms: u64,
+}
+
+impl RefreshStats {
+    pub fn changed(&self) -> bool {
+        self.added + self.updated + self.removed > 0
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct IndexStats {
+    pub root: String,
+    pub files: usize,
+    pub symbols: usize,
+    pub embedded: usize,
+    pub languages: BTreeMap<String, usize>,
+    /// The file limit was hit and some files are missing
+    pub truncated: bool,
+    pub watching: bool,
+    pub last_refresh_secs_ago: Option<u64>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FileMatch {
+    pub path: String,
+    pub score: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SymbolMatch {
+    pub name: String,
+    pub kind: String,
+    pub path: String,
+    pub line: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct SemanticMatch {
+    pub path: String,
+    pub score: f32,
+}
+
+/// Incrementally maintained index of one workspace
+pub struct WorkspaceIndex {
+    root: PathBuf,
+    state: RwLock<IndexState>,
+    settings: RwLock<WorkspaceIndexSettings>,
+    embedder: RwLock<Option<Arc<dyn EmbeddingProvider>>>,
+    refresh_lock: tokio::sync::Mutex<()>,
+    watching: AtomicBool,
+    lsp: LspClientImpl,
+}
+
+impl WorkspaceIndex {
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        Self {
+            lsp: LspClientImpl::new(root.clone()),
+            root,
+            state: RwLock::new(IndexState::default()),
+            settings: RwLock::new(WorkspaceIndexSettings::default()),
+            embedder: RwLock::new(None),
+            refresh_lock: tokio::sync::Mutex::new(()),
+            watching: AtomicBool::new(false),
+        }
+    }
+
+    /// The process-wide index for `root`, shared by every session and tool
+    pub fn shared(root: &Path) -> Arc<Self> {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+        INDEXES
+            .lock()
+            .entry(root.clone())
+            .or_insert_with(|| Arc::new(Self::new(root)))
+            .clone()
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn configure(
+        &self,
+        settings: WorkspaceIndexSettings,
+        embedder: Option<Arc<dyn EmbeddingProvider>>,
+    ) {
+        *self.settings.write() = settings;
+        if embedder.is_some() {
+            *self.embedder.write() = embedder;
+        }
+    }
+
+    pub fn is_watching(&self) -> bool {
+        self.watching.load(Ordering::SeqCst)
+    }
+
+    /// Start the polling watcher; a no-op when one is already running or
+    /// there is no Tokio runtime
+    pub fn start_watching(self: &Arc<Self>) {
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        if self.watching.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let index = Arc::clone(self);
+        handle.spawn(async move {
+            tracing::debug!(root = %index.root.display(), "workspace index watcher started");
+            while index.is_watching() {
+                let stats = index.refresh().await;
+                if stats.changed() {
+                    tracing::debug!(?stats, "workspace index updated");
+                }
+                let interval = index.settings.read().poll_interval_secs.max(1);
+                tokio::time::sleep(Duration::from_secs(interval)).await;
+            }
+        });
+    }
+
+    pub fn stop_watching(&self) {
+        self.watching.store(false, Ordering::SeqCst);
+    }
+
+    /// Refresh unless a scan finished within the poll interval, so queries
+    /// without a running watcher still see current files
+    pub async fn ensure_fresh(&self) {
+        let interval = Duration::from_secs(self.settings.read().poll_interval_secs.max(1));
+        let fresh = self
+            .state
+            .read()
+            .last_refresh
+            .is_some_and(|at| at.elapsed() < interval);
+        if !fresh {
+            self.refresh().await;
+        }
+    }
+
+    /// Scan the workspace and re-index files that changed since last scan
+    pub async fn refresh(&self) -> RefreshStats {
+        let _guard = self.refresh_lock.lock().await;
+        let started = Instant::now();
+        let settings = self.settings.read().clone();
+
+        if !self.state.read().loaded_snapshot {
+            let snapshot_path = self.snapshot_path();
+            let loaded = tokio::task::spawn_blocking(move || load_snapshot(&snapshot_path))
+                .await
+                .ok()
+                .flatten();
+            self.apply_snapshot(loaded);
+        }
+
+        let root = self.root.clone();
+        let max_files = settings.max_files;
+        let (entries, truncated) = tokio::task::spawn_blocking(move || scan(&root, max_files))
+            .await
+            .unwrap_or_default();
+
+        let mut stats = RefreshStats::default();
+        let (changed, removed): (Vec<ScanEntry>, Vec<String>) = {
+            let state = self.state.read();
+            let seen: HashSet<&str> = entries.iter().map(|e| e.path.as_str()).collect();
+            let removed = state
+                .files
+                .keys()
+                .filter(|path| !seen.contains(path.as_str()))
+                .cloned()
+                .collect();
+            let changed = entries
+                .into_iter()
+                .filter(|entry| match state.files.get(&entry.path) {
+                    Some(file) => {
+                        let same = file.size == entry.size && file.modified_ms == entry.modified_ms;
+                        if same {
+                            stats.unchanged += 1;
+                        } else {
+                            stats.updated += 1;
+                        }
+                        !same
+                    }
+                    None => {
+                        stats.added += 1;
+                        true
+                    }
+                })
+                .collect();
+            (changed, removed)
+        };
+        stats.removed = removed.len();
+
+        let root = self.root.clone();
+        let max_bytes = settings.max_file_bytes;
+        let mut indexed = tokio::task::spawn_blocking(move || {
+            changed
+                .into_iter()
+                .map(|entry| index_file(&root, entry, max_bytes))
+                .collect::<Vec<_>>()
+        })
+        .await
+        .unwrap_or_default();
+
+        if settings.lsp_symbols {
+            self.apply_lsp_symbols(&mut indexed).await;
+        }
+        let embedder = self.embedder.read().clone();
+        if let (true, Some(embedder)) = (settings.embeddings, embedder) {
+            embed_files(embedder.as_ref(), &mut indexed).await;
+        }
+
+        {
+            let mut state = self.state.write();
+            for path in &removed {
+                state.files.remove(path);
+            }
+            for (file, _) in indexed {
+                state.files.insert(file.path.clone(), file);
+            }
+            state.truncated = truncated;
+            state.last_refresh = Some(Instant::now());
+        }
+
+        if stats.changed() {
+            self.save_snapshot().await;
+        }
+        stats.duration_ms = started.elapsed().as_millis() as u64;
+        stats
+    }
+
+    /// Load the on-disk snapshot without scanning, for callers that need
+    /// files right away; returns whether the index has any files
+    pub fn warm_start(&self) -> bool {
+        if !self.state.read().loaded_snapshot {
+            self.apply_snapshot(load_snapshot(&self.snapshot_path()));
+        }
+        !self.state.read().files.is_empty()
+    }
+
+    fn apply_snapshot(&self, snapshot: Option<Snapshot>) {
+        let mut state = self.state.write();
+        if state.loaded_snapshot {
+            return;
+        }
+        state.loaded_snapshot = true;
+        if let Some(snapshot) = snapshot {
+            state.truncated = snapshot.truncated;
+            state.files = snapshot
+                .files
+                .into_iter()
+                .map(|file| (file.path.clone(), file))
+                .collect();
+        }
+    }
+
+    /// Every indexed file, without embeddings
+    pub fn files(&self) -> Vec<IndexedFile> {
+        self.state
+            .read()
+            .files
+            .values()
+            .map(|file| IndexedFile {
+                embedding: None,
+                ..file.clone()
+            })
+            .collect()
+    }
+
+    async fn apply_lsp_symbols(&self, files: &mut [(IndexedFile, Option<String>)]) {
+        let mut unavailable: HashSet<String> = HashSet::new();
+        for (file, content) in files.iter_mut() {
+            let Some(language) = file.language.clone() else {
+                continue;
+            };
+            if content.is_none() || unavailable.contains(&language) {
+                continue;
+            }
+            let path = self.root.join(&file.path);
+            let response = self
+                .lsp
+                .symbols(json!({ "filePath": path.to_string_lossy(), "scope": "document" }))
+                .await;
+            match response {
+                Ok(output) => {
+                    let parsed = output
+                        .content
+                        .first()
+                        .and_then(|ToolContent::Text { text }| serde_json::from_str(text).ok())
+                        .map(|value| symbols::from_lsp_response(&value))
+                        .unwrap_or_default();
+                    if !parsed.is_empty() {
+                        file.symbols = parsed;
+                    }
+                }
+                Err(e) => {
+                    // Keep the built-in symbols and stop asking this server
+                    tracing::debug!(%language, error = %e, "LSP symbols unavailable");
+                    unavailable.insert(language);
+                }
+            }
+        }
+    }
+
+    fn snapshot_path(&self) -> PathBuf {
+        self.root
+            .join(UIRA_DIR)
+            .join("index")
+            .join("workspace.json")
+    }
+
+    async fn save_snapshot(&self) {
+        let snapshot = {
+            let state = self.state.read();
+            Snapshot {
+                version: SNAPSHOT_VERSION,
+                truncated: state.truncated,
+                files: state.files.values().cloned().collect(),
+            }
+        };
+        let path = self.snapshot_path();
+        let written = tokio::task::spawn_blocking(move || -> std::io::Result<()> {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let json = serde_json::to_vec(&snapshot).map_err(std::io::Error::other)?;
+            uira_core::atomic_write(&path, &json, None)
+        })
+        .await;
+        if let Ok(Err(e)) = written {
+            tracing::debug!(error = %e, "failed to save workspace index snapshot");
+        }
+    }
+
+    /// Files whose path matches `query`, best first. `*` and `?` make the
+    /// query a glob; otherwise file name matches outrank directory matches.
+    pub fn find_files(&self, query: &str, limit: usize) -> Vec<FileMatch> {
+        let state = self.state.read();
+        let query = query.trim();
+        if query.contains('*') || query.contains('?') {
+            let Ok(pattern) = glob::Pattern::new(query) else {
+                return Vec::new();
+            };
+            return state
+                .files
+                .keys()
+                .filter(|path| pattern.matches(path))
+                .take(limit)
+                .map(|path| FileMatch {
+                    path: path.clone(),
+                    score: 100,
+                })
+                .collect();
+        }
+
+        let needle = query.to_lowercase();
+        let mut matches: Vec<FileMatch> = state
+            .files
+            .keys()
+            .filter_map(|path| {
+                let score = path_score(path, &needle)?;
+                Some(FileMatch {
+                    path: path.clone(),
+                    score,
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| {
+            b.score
+                .cmp(&a.score)
+                .then(a.path.len().cmp(&b.path.len()))
+                .then(a.path.cmp(&b.path))
+        });
+        matches.truncate(limit);
+        matches
+    }
+
+    /// Declarations named like `query` (exact, then prefix, then substring)
+    pub fn find_symbols(&self, query: &str, kind: Option<&str>, limit: usize) -> Vec<SymbolMatch> {
+        let state = self.state.read();
+        let needle = query.trim().to_lowercase();
+        let mut scored: Vec<(u32, SymbolMatch)> = Vec::new();
+        for file in state.files.values() {
+            for symbol in &file.symbols {
+                if kind.is_some_and(|k| !symbol.kind.eq_ignore_ascii_case(k)) {
+                    continue;
+                }
+                let name = symbol.name.to_lowercase();
+                let score = if symbol.name == query.trim() {
+                    100
+                } else if name == needle {
+                    90
+                } else if name.starts_with(&needle) {
+                    70
+                } else if name.contains(&needle) {
+                    50
+                } else {
+                    continue;
+                };
+                scored.push((
+                    score,
+                    SymbolMatch {
+                        name: symbol.name.clone(),
+                        kind: symbol.kind.clone(),
+                        path: file.path.clone(),
+                        line: symbol.line,
+                    },
+                ));
+            }
+        }
+        scored.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then(a.1.name.len().cmp(&b.1.name.len()))
+                .then(a.1.path.cmp(&b.1.path))
+        });
+        scored.into_iter().take(limit).map(|(_, m)| m).collect()
+    }
+
+    /// Files most similar to `query` by embedding
+    pub async fn semantic_search(
+        &self,
+        query: &str,
+        limit: usize,
+    ) -> Result<Vec<SemanticMatch>, String> {
+        let embedder = self.embedder.read().clone().ok_or_else(|| {
+            "semantic search needs workspace_index.embeddings and an enabled memory system"
+                .to_string()
+        })?;
+        let vectors = embedder
+            .embed(&[query.to_string()])
+            .await
+            .map_err(|e| format!("failed to embed query: {e}"))?;
+        let Some(query_vector) = vectors.into_iter().next() else {
+            return Ok(Vec::new());
+        };
+
+        let state = self.state.read();
+        let mut matches: Vec<SemanticMatch> = state
+            .files
+            .values()
+            .filter_map(|file| {
+                let embedding = file.embedding.as_ref()?;
+                Some(SemanticMatch {
+                    path: file.path.clone(),
+                    score: cosine_similarity(&query_vector, embedding),
+                })
+            })
+            .collect();
+        matches.sort_by(|a, b| b.score.total_cmp(&a.score));
+        matches.truncate(limit);
+        Ok(matches)
+    }
+
+    /// Entries under `prefix`, cut to `depth` path components below it;
+    /// directories end with `/`
+    pub fn tree(&self, prefix: &str, depth: usize, limit: usize) -> Vec<String> {
+        let prefix = prefix.trim_matches('/');
+        let state = self.state.read();
+        let mut entries: Vec<String> = Vec::new();
+        let mut seen = HashSet::new();
+        for path in state.files.keys() {
+            let rest = if prefix.is_empty() {
+                path.as_str()
+            } else if let Some(rest) = path.strip_prefix(prefix) {
+                match rest.strip_prefix('/') {
+                    Some(rest) => rest,
+                    None => continue,
+                }
+            } else {
+                continue;
+            };
+            let parts: Vec<&str> = rest.split('/').collect();
+            let entry = if parts.len() > depth.max(1) {
+                format!("{}/", parts[..depth.max(1)].join("/"))
+            } else {
+                rest.to_string()
+            };
+            if seen.insert(entry.clone()) {
+                entries.push(entry);
+                if entries.len() >= limit {
+                    break;
+                }
+            }
+        }
+        entries
+    }
+
+    pub fn stats(&self) -> IndexStats {
+        let state = self.state.read();
+        let mut languages = BTreeMap::new();
+        let mut symbols = 0;
+        let mut embedded = 0;
+        for file in state.files.values() {
+            if let Some(language) = &file.language {
+                *languages.entry(language.clone()).or_insert(0) += 1;
+            }
+            symbols += file.symbols.len();
+            embedded += usize::from(file.embedding.is_some());
+        }
+        IndexStats {
+            root: self.root.display().to_string(),
+            files: state.files.len(),
+            symbols,
+            embedded,
+            languages,
+            truncated: state.truncated,
+            watching: self.is_watching(),
+            last_refresh_secs_ago: state.last_refresh.map(|at| at.elapsed().as_secs()),
+        }
+    }
+}
+
+#[derive(Debug)]
+struct ScanEntry {
+    path: String,
+    size: u64,
+    modified_ms: u64,
+}
+
+/// Every non-ignored file under `root`, capped at `max_files`
+fn scan(root: &Path, max_files: usize) -> (Vec<ScanEntry>, bool) {
+    let walker = ignore::WalkBuilder::new(root)
+        .hidden(false)
+        .require_git(false)
+        .filter_entry(|entry| {
+            let name = entry.file_name();
+            name != ".git" && name != UIRA_DIR
+        })
+        .build();
+
+    let mut entries = Vec::new();
+    for entry in walker.flatten() {
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        if entries.len() >= max_files {
+            return (entries, true);
+        }
+        let Ok(metadata) = entry.metadata() else {
+            continue;
+        };
+        let Ok(relative) = entry.path().strip_prefix(root) else {
+            continue;
+        };
+        let modified_ms = metadata
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as u64)
+            .unwrap_or_default();
+        entries.push(ScanEntry {
+            path: relative.to_string_lossy().replace('\\', "/"),
+            size: metadata.len(),
+            modified_ms,
+        });
+    }
+    (entries, false)
+}
+
+/// One-off synchronous scan with the built-in symbol extractor
+pub(crate) fn scan_files(root: &Path, settings: &WorkspaceIndexSettings) -> Vec<IndexedFile> {
+    let (entries, _) = scan(root, settings.max_files);
+    entries
+        .into_iter()
+        .map(|entry| index_file(root, entry, settings.max_file_bytes).0)
+        .collect()
+}
+
+/// Index one file; also returns its text for LSP and embedding passes
+fn index_file(root: &Path, entry: ScanEntry, max_bytes: u64) -> (IndexedFile, Option<String>) {
+    let language = Path::new(&entry.path)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(symbols::language_for)
+        .map(str::to_string);
+    let content = (entry.size <= max_bytes)
+        .then(|| std::fs::read_to_string(root.join(&entry.path)).ok())
+        .flatten();
+    let symbols = match (&language, &content) {
+        (Some(language), Some(content)) => symbols::extract(language, content),
+        _ => Vec::new(),
+    };
+    (
+        IndexedFile {
+            path: entry.path,
+            size: entry.size,
+            modified_ms: entry.modified_ms,
+            language,
+            symbols,
+            embedding: None,
+        },
+        content,
+    )
+}
+
+async fn embed_files(
+    embedder: &dyn EmbeddingProvider,
+    files: &mut [(IndexedFile, Option<String>)],
+) {
+    let targets: Vec<usize> = files
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, content))| content.as_ref().is_some_and(|c| !c.trim().is_empty()))
+        .map(|(i, _)| i)
+        .collect();
+
+    for batch in targets.chunks(EMBED_BATCH) {
+        let texts: Vec<String> = batch
+            .iter()
+            .map(|&i| {
+                let (file, content) = &files[i];
+                embedding_text(file, content.as_deref().unwrap_or_default())
+            })
+            .collect();
+        match embedder.embed(&texts).await {
+            Ok(vectors) => {
+                for (&i, vector) in batch.iter().zip(vectors) {
+                    files[i].0.embedding = Some(vector);
+                }
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "workspace index embedding failed");
+                return;
+            }
+        }
+    }
+}
+
+/// Path, declared names and the start of the file
+fn embedding_text(file: &IndexedFile, content: &str) -> String {
+    let names: Vec<&str> = file.symbols.iter().map(|s| s.name.as_str()).collect();
+    let preview: String = content.chars().take(EMBED_PREVIEW_CHARS).collect();
+    format!("{}\n{}\n{}", file.path, names.join(" "), preview)
+}
+
+fn load_snapshot(path: &Path) -> Option<Snapshot> {
+    let bytes = std::fs::read(path).ok()?;
+    let snapshot: Snapshot = serde_json::from_slice(&bytes).ok()?;
+    (snapshot.version == SNAPSHOT_VERSION).then_some(snapshot)
+}
+
+/// Match quality of `path` against a lowercase needle, if it matches at all
+fn path_score(path: &str, needle: &str) -> Option<u32> {
+    if needle.is_empty() {
+        return Some(1);
+    }
+    let lower = path.to_lowercase();
+    let name = lower.rsplit('/').next().unwrap_or(&lower);
+    let stem = name.split('.').next().unwrap_or(name);
+    if name == needle || stem == needle {
+        Some(100)
+    } else if name.starts_with(needle) {
+        Some(80)
+    } else if name.contains(needle) {
+        Some(60)
+    } else if lower.contains(needle) {
+        Some(40)
+    } else if is_subsequence(needle, name) {
+        Some(20)
+    } else {
+        None
+    }
+}
+
+fn is_subsequence(needle: &str, haystack: &str) -> bool {
+    let mut chars = haystack.chars();
+    needle.chars().all(|c| chars.any(|h| h == c))
+}
+
+fn cosine_similarity(a: &[f32], b: &[f32]) -> f32 {
+    if a.len() != b.len() || a.is_empty() {
+        return 0.0;
+    }
+    let dot: f32 = a.iter().zip(b).map(|(x, y)| x * y).sum();
+    let norm_a: f32 = a.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let norm_b: f32 = b.iter().map(|x| x * x).sum::<f32>().sqrt();
+    if norm_a == 0.0 || norm_b == 0.0 {
+        return 0.0;
+    }
+    dot / (norm_a * norm_b)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uira_memory::MockEmbeddingProvider;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, content).unwrap();
+    }
+
+    #[tokio::test]
+    async fn indexes_incrementally_and_answers_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "src/lib.rs",
+            "pub struct WorkspaceIndex;\npub fn refresh() {}\n",
+        );
+        write(root, "src/main.rs", "fn main() {}\n");
+        write(root, "web/app.ts", "export class App {}\n");
+        write(root, "target/debug/out.rs", "fn ignored() {}\n");
+        write(root, ".gitignore", "target/\n");
+
+        let index = WorkspaceIndex::new(root);
+        let stats = index.refresh().await;
+        assert_eq!(stats.added, 4);
+        assert_eq!(index.stats().files, 4);
+
+        assert_eq!(index.find_files("lib", 5)[0].path, "src/lib.rs");
+        assert_eq!(index.find_files("src/*.rs", 5).len(), 2);
+        let symbols = index.find_symbols("workspaceindex", None, 5);
+        assert_eq!(symbols[0].path, "src/lib.rs");
+        assert_eq!(symbols[0].line, 1);
+        assert_eq!(index.find_symbols("App", Some("class"), 5).len(), 1);
+        assert_eq!(index.tree("", 1, 10), vec![".gitignore", "src/", "web/"]);
+
+        // Unchanged files are not re-read; edits and deletions are picked up
+        let stats = index.refresh().await;
+        assert!(!stats.changed());
+        assert_eq!(stats.unchanged, 4);
+
+        std::thread::sleep(Duration::from_millis(20));
+        write(root, "src/main.rs", "fn main() {}\nfn helper_fn() {}\n");
+        std::fs::remove_file(root.join("web/app.ts")).unwrap();
+        let stats = index.refresh().await;
+        assert_eq!((stats.updated, stats.removed), (1, 1));
+        assert_eq!(index.find_symbols("helper_fn", None, 5).len(), 1);
+        assert!(index.find_symbols("App", None, 5).is_empty());
+
+        // A new index over the same root starts from the snapshot
+        let warm = WorkspaceIndex::new(root);
+        let stats = warm.refresh().await;
+        assert_eq!(stats.added, 0);
+        assert_eq!(stats.unchanged, 3);
+    }
+
+    #[tokio::test]
+    async fn semantic_search_uses_configured_embedder() {
+        let dir = tempfile::tempdir().unwrap();
+        write(dir.path(), "notes.md", "indexing notes");
+        let index = WorkspaceIndex::new(dir.path());
+        assert!(index.semantic_search("notes", 5).await.is_err());
+
+        index.configure(
+            WorkspaceIndexSettings {
+                embeddings: true,
+                ..Default::default()
+            },
+            Some(Arc::new(MockEmbeddingProvider::new(8))),
+        );
+        index.refresh().await;
+        assert_eq!(index.stats().embedded, 1);
+        let matches = index.semantic_search("notes", 5).await.unwrap();
+        assert_eq!(matches[0].path, "notes.md");
+    }
+}
@@ -0,0 +1,261 @@
+//! Symbol extraction for the workspace index
+//!
+//! The built-in extractor matches top-level declarations with one regex per
+//! language, which is fast enough to run over a whole monorepo. Language
+//! server results, when enabled, are converted with [`from_lsp_response`].
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+/// A named declaration in an indexed file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexedSymbol {
+    pub name: String,
+    /// Lowercase kind such as `function`, `struct`, `class` or `trait`
+    pub kind: String,
+    /// 1-based line of the declaration
+    pub line: u32,
+    /// Visible outside its module (`pub`, `export`, capitalized Go names)
+    #[serde(default)]
+    pub exported: bool,
+}
+
+lazy_static! {
+    static ref RUST: Regex = Regex::new(
+        r"^\s*(?:pub(?:\([^)]*\))?\s+)?(?:async\s+|const\s+|unsafe\s+|extern\s+\S+\s+)*(fn|struct|enum|trait|type|mod|const|static|macro_rules!)\s+([A-Za-z_][A-Za-z0-9_]*)"
+    )
+    .unwrap();
+    static ref RUST_IMPL: Regex =
+        Regex::new(r"^\s*impl(?:<[^>]*>)?\s+(?:[A-Za-z_][A-Za-z0-9_:<>, ]*\s+for\s+)?([A-Za-z_][A-Za-z0-9_]*)")
+            .unwrap();
+    static ref TYPESCRIPT: Regex = Regex::new(
+        r"^\s*(?:export\s+)?(?:default\s+)?(?:declare\s+)?(?:abstract\s+)?(?:async\s+)?(function\*?|class|interface|type|enum|const|let)\s+([A-Za-z_$][A-Za-z0-9_$]*)"
+    )
+    .unwrap();
+    static ref PYTHON: Regex =
+        Regex::new(r"^(?:async\s+)?(def|class)\s+([A-Za-z_][A-Za-z0-9_]*)").unwrap();
+    static ref PYTHON_METHOD: Regex =
+        Regex::new(r"^\s+(?:async\s+)?def\s+([A-Za-z_][A-Za-z0-9_]*)").unwrap();
+    static ref GO: Regex = Regex::new(
+        r"^(?:func\s+(?:\([^)]*\)\s*)?([A-Za-z_][A-Za-z0-9_]*)|type\s+([A-Za-z_][A-Za-z0-9_]*)\s+(struct|interface)?)"
+    )
+    .unwrap();
+}
+
+/// Language name for a file extension, matching the LSP client's names
+pub fn language_for(extension: &str) -> Option<&'static str> {
+    match extension {
+        "rs" => Some("rust"),
+        "ts" | "tsx" | "js" | "jsx" | "mjs" | "cjs" => Some("typescript"),
+        "py" => Some("python"),
+        "go" => Some("go"),
+        "java" => Some("java"),
+        _ => None,
+    }
+}
+
+/// Declarations found in `content` by the built-in extractor
+pub fn extract(language: &str, content: &str) -> Vec<IndexedSymbol> {
+    let mut symbols = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line_no = index as u32 + 1;
+        let found = match language {
+            "rust" => RUST
+                .captures(line)
+                .map(|c| (rust_kind(&c[1]), c[2].to_string()))
+                .or_else(|| {
+                    RUST_IMPL
+                        .captures(line)
+                        .map(|c| ("impl".to_string(), c[1].to_string()))
+                }),
+            "typescript" => TYPESCRIPT.captures(line).and_then(|c| {
+                let kind = match &c[1] {
+                    "function" | "function*" => "function",
+                    "const" | "let" if !line.trim_start().starts_with("export") => return None,
+                    "const" | "let" => "variable",
+                    other => other,
+                };
+                Some((kind.to_string(), c[2].to_string()))
+            }),
+            "python" => PYTHON
+                .captures(line)
+                .map(|c| {
+                    let kind = if &c[1] == "class" {
+                        "class"
+                    } else {
+                        "function"
+                    };
+                    (kind.to_string(), c[2].to_string())
+                })
+                .or_else(|| {
+                    PYTHON_METHOD
+                        .captures(line)
+                        .map(|c| ("method".to_string(), c[1].to_string()))
+                }),
+            "go" => GO.captures(line).and_then(|c| {
+                if let Some(name) = c.get(1) {
+                    return Some(("function".to_string(), name.as_str().to_string()));
+                }
+                let name = c.get(2)?.as_str().to_string();
+                let kind = c.get(3).map_or("type", |k| k.as_str());
+                Some((kind.to_string(), name))
+            }),
+            _ => None,
+        };
+        if let Some((kind, name)) = found {
+            symbols.push(IndexedSymbol {
+                exported: is_exported(language, line, &kind, &name),
+                name,
+                kind,
+                line: line_no,
+            });
+        }
+    }
+    symbols
+}
+
+fn is_exported(language: &str, line: &str, kind: &str, name: &str) -> bool {
+    let line = line.trim_start();
+    match language {
+        "rust" => kind != "impl" && line.starts_with("pub "),
+        "typescript" => line.starts_with("export"),
+        "python" => kind != "method" && !name.starts_with('_'),
+        "go" => name.starts_with(|c: char| c.is_ascii_uppercase()),
+        _ => false,
+    }
+}
+
+fn rust_kind(keyword: &str) -> String {
+    match keyword {
+        "fn" => "function",
+        "mod" => "module",
+        "macro_rules!" => "macro",
+        "static" => "const",
+        other => other,
+    }
+    .to_string()
+}
+
+/// Symbols from a `textDocument/documentSymbol` result, which is either a
+/// `DocumentSymbol` tree or a flat `SymbolInformation` list
+pub fn from_lsp_response(result: &Value) -> Vec<IndexedSymbol> {
+    let mut symbols = Vec::new();
+    if let Some(items) = result.as_array() {
+        for item in items {
+            collect_lsp_symbol(item, true, &mut symbols);
+        }
+    }
+    symbols
+}
+
+/// LSP results carry no visibility, so top-level symbols count as exported
+fn collect_lsp_symbol(item: &Value, top_level: bool, out: &mut Vec<IndexedSymbol>) {
+    let Some(name) = item.get("name").and_then(Value::as_str) else {
+        return;
+    };
+    let line = item
+        .pointer("/selectionRange/start/line")
+        .or_else(|| item.pointer("/range/start/line"))
+        .or_else(|| item.pointer("/location/range/start/line"))
+        .and_then(Value::as_u64)
+        .unwrap_or(0) as u32
+        + 1;
+    let kind = item
+        .get("kind")
+        .and_then(Value::as_u64)
+        .map(lsp_kind_name)
+        .unwrap_or("symbol");
+    out.push(IndexedSymbol {
+        name: name.to_string(),
+        kind: kind.to_string(),
+        line,
+        exported: top_level,
+    });
+    if let Some(children) = item.get("children").and_then(Value::as_array) {
+        for child in children {
+            collect_lsp_symbol(child, false, out);
+        }
+    }
+}
+
+/// Names for the LSP `SymbolKind` numbers
+fn lsp_kind_name(kind: u64) -> &'static str {
+    match kind {
+        2 => "module",
+        3 => "namespace",
+        5 => "class",
+        6 => "method",
+        8 => "field",
+        9 => "constructor",
+        10 => "enum",
+        11 => "interface",
+        12 => "function",
+        13 => "variable",
+        14 => "const",
+        22 => "enum_member",
+        23 => "struct",
+        26 => "type_parameter",
+        _ => "symbol",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn extracts_declarations_per_language() {
+        let rust = "pub struct Index;\nimpl Drop for Index {}\npub(crate) async fn build() {}\n";
+        let names: Vec<_> = extract("rust", rust)
+            .into_iter()
+            .map(|s| (s.kind, s.name, s.line, s.exported))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                ("struct".to_string(), "Index".to_string(), 1, true),
+                ("impl".to_string(), "Index".to_string(), 2, false),
+                ("function".to_string(), "build".to_string(), 3, false),
+            ]
+        );
+
+        let ts = "export default class App {}\nconst local = 1;\nexport const api = {};\n";
+        let ts_names: Vec<_> = extract("typescript", ts)
+            .into_iter()
+            .map(|s| s.name)
+            .collect();
+        assert_eq!(ts_names, vec!["App", "api"]);
+
+        let py = "class Store:\n    def get(self):\n        pass\ndef main():\n";
+        let py_kinds: Vec<_> = extract("python", py).into_iter().map(|s| s.kind).collect();
+        assert_eq!(py_kinds, vec!["class", "method", "function"]);
+
+        let go = "func (s *Server) Start() {}\ntype Config struct {\n";
+        let go_names: Vec<_> = extract("go", go).into_iter().map(|s| s.name).collect();
+        assert_eq!(go_names, vec!["Start", "Config"]);
+    }
+
+    #[test]
+    fn converts_lsp_document_symbols() {
+        let response = json!([{
+            "name": "Server",
+            "kind": 23,
+            "range": {"start": {"line": 4, "character": 0}},
+            "selectionRange": {"start": {"line": 4, "character": 11}},
+            "children": [{
+                "name": "start",
+                "kind": 6,
+                "range": {"start": {"line": 9, "character": 4}}
+            }]
+        }]);
+        let symbols = from_lsp_response(&response);
+        assert_eq!(symbols.len(), 2);
+        assert_eq!(symbols[0].kind, "struct");
+        assert_eq!(symbols[0].line, 5);
+        assert_eq!(symbols[1].name, "start");
+        assert_eq!(symbols[1].line, 10);
+    }
+}
@@ -43,6 +43,7 @@ impl HookEventAdapter {
             Event::SessionStarted { .. } => Some(HookEvent::SessionStart),
             Event::SessionEnded { .. } => Some(HookEvent::Stop),
             Event::SessionIdle { .. } => Some(HookEvent::SessionIdle),
+            Event::TurnCompleted { .. } => Some(HookEvent::AssistantTurnComplete),
             Event::UserInputRequested { .. } => None,
             Event::UserPromptSubmitted { .. } => Some(HookEvent::UserPromptSubmit),
             Event::ToolExecutionStarted { .. } => Some(HookEvent::PreToolUse),
@@ -194,6 +195,30 @@ impl HookEventAdapter {
                 }
             }
 
+            Event::TurnCompleted {
+                session_id,
+                turn_number,
+                usage,
+            } => {
+                let mut extra = HashMap::new();
+                extra.insert("turn_number".to_string(), serde_json::json!(turn_number));
+                extra.insert("usage".to_string(), serde_json::json!(usage));
+                HookInput {
+                    session_id: Some(session_id.clone()),
+                    prompt: None,
+                    message: None,
+                    parts: None,
+                    tool_name: None,
+                    tool_input: None,
+                    tool_output: None,
+                    directory: None,
+                    stop_reason: None,
+                    user_requested: None,
+                    transcript_path: None,
+                    extra,
+                }
+            }
+
             Event::MessagesTransform { session_id } => HookInput {
                 session_id: Some(session_id.clone()),
                 prompt: None,
@@ -249,6 +274,7 @@ impl EventHandler for HookEventAdapter {
     fn filter(&self) -> SubscriptionFilter {
         SubscriptionFilter::new().categories([
             EventCategory::Session,
+            EventCategory::Turn,
             EventCategory::Tool,
             EventCategory::Content,
         ])
@@ -373,6 +399,27 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_turn_completed_carries_usage() {
+        let event = Event::TurnCompleted {
+            session_id: "ses_turn".to_string(),
+            turn_number: 3,
+            usage: uira_core::TokenUsage {
+                input_tokens: 120,
+                output_tokens: 30,
+                ..Default::default()
+            },
+        };
+
+        assert_eq!(
+            HookEventAdapter::event_to_hook_event(&event),
+            Some(HookEvent::AssistantTurnComplete)
+        );
+        let input = HookEventAdapter::event_to_hook_input(&event);
+        assert_eq!(input.extra["usage"]["input_tokens"], 120);
+        assert_eq!(input.extra["turn_number"], 3);
+    }
+
     #[test]
     fn test_session_ended_maps_last_response_to_prompt() {
         let event = Event::SessionEnded {
@@ -6,6 +6,7 @@ use thiserror::Error;
 use tokio::process::Command;
 use tokio::time::timeout;
 use uira_core::schema::GoalConfig;
+use uira_core::{shell_command, ShellKind};
 
 #[derive(Error, Debug)]
 pub enum GoalError {
@@ -30,6 +31,10 @@ pub enum GoalError {
 
 pub type GoalResult<T> = Result<T, GoalError>;
 
+/// Environment variable listing changed files, newline-separated, for goal
+/// commands run with a known change set
+pub const CHANGED_FILES_ENV: &str = "UIRA_CHANGED_FILES";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GoalCheckResult {
     pub name: String,
@@ -94,12 +99,51 @@ impl VerificationResult {
 
 pub struct GoalRunner {
     project_root: PathBuf,
+    /// Files changed since the last check, relative to the project root
+    changed_files: Option<Vec<PathBuf>>,
 }
 
 impl GoalRunner {
     pub fn new(project_root: impl AsRef<Path>) -> Self {
         Self {
             project_root: project_root.as_ref().to_path_buf(),
+            changed_files: None,
+        }
+    }
+
+    /// Scope goals with a `changed_command` to these files, given relative
+    /// to the project root
+    pub fn with_changed_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.changed_files = Some(files);
+        self
+    }
+
+    /// Changed files under `working_dir`, relative to it
+    fn changed_files_in(&self, working_dir: &Path) -> Option<Vec<String>> {
+        let files = self.changed_files.as_ref()?;
+        let prefix = working_dir
+            .strip_prefix(&self.project_root)
+            .unwrap_or(Path::new(""));
+        Some(
+            files
+                .iter()
+                .filter_map(|file| file.strip_prefix(prefix).ok())
+                .map(|file| file.to_string_lossy().into_owned())
+                .collect(),
+        )
+    }
+
+    /// The command to run for `goal`: its `changed_command` when there are
+    /// changed files for it, its full `command` otherwise
+    fn goal_command(goal: &GoalConfig, changed: Option<&[String]>) -> String {
+        match (&goal.changed_command, changed) {
+            (Some(template), Some(files)) if !files.is_empty() => {
+                let shell = goal.shell.unwrap_or_else(ShellKind::from_env);
+                let quoted: Vec<String> =
+                    files.iter().map(|file| shell_quote(file, shell)).collect();
+                template.replace("{files}", &quoted.join(" "))
+            }
+            _ => goal.command.clone(),
         }
     }
 
@@ -126,14 +170,16 @@ impl GoalRunner {
         };
 
         let timeout_duration = Duration::from_secs(goal.timeout_secs);
+        let changed = self.changed_files_in(&working_dir);
+        let command = Self::goal_command(goal, changed.as_deref());
 
         let output = timeout(timeout_duration, async {
-            Command::new("sh")
-                .arg("-c")
-                .arg(&goal.command)
-                .current_dir(&working_dir)
-                .output()
-                .await
+            let mut cmd = Command::from(shell_command(&command, goal.shell));
+            cmd.current_dir(&working_dir);
+            if let Some(files) = &changed {
+                cmd.env(CHANGED_FILES_ENV, files.join("\n"));
+            }
+            cmd.output().await
         })
         .await
         .map_err(|_| GoalError::Timeout(goal.timeout_secs))?
@@ -227,6 +273,18 @@ impl GoalRunner {
     }
 }
 
+fn shell_quote(arg: &str, shell: ShellKind) -> String {
+    if !arg.is_empty()
+        && arg
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || "-_./".contains(c))
+    {
+        arg.to_string()
+    } else {
+        shell.quote(arg)
+    }
+}
+
 pub struct VerifyOptions {
     pub check_interval_secs: u64,
     pub max_iterations: u32,
@@ -255,10 +313,12 @@ mod tests {
             name: name.to_string(),
             workspace: None,
             command: command.to_string(),
+            changed_command: None,
             target,
             timeout_secs: 10,
             enabled: true,
             description: None,
+            shell: None,
         }
     }
 
@@ -339,6 +399,47 @@ mod tests {
         assert_eq!(result.results.iter().filter(|r| r.passed).count(), 2);
     }
 
+    #[tokio::test]
+    async fn test_changed_command_gets_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir(dir.path().join("web")).unwrap();
+        let runner = GoalRunner::new(dir.path()).with_changed_files(vec![
+            PathBuf::from("web/app.ts"),
+            PathBuf::from("web/my file.ts"),
+            PathBuf::from("api/main.rs"),
+        ]);
+
+        // Only the two files under the workspace are passed, one per argument
+        let mut goal = make_goal("scoped", "echo 10", 80.0);
+        goal.workspace = Some("web".to_string());
+        goal.changed_command =
+            Some("for f in {files}; do echo \"$f\"; done | wc -l | sed 's/$/0/'".to_string());
+        assert_eq!(runner.check_goal(&goal).await.score, 20.0);
+
+        let mut goal = make_goal(
+            "env",
+            r#"[ "$UIRA_CHANGED_FILES" = "$(printf 'web/app.ts\nweb/my file.ts\napi/main.rs')" ] && echo 100"#,
+            80.0,
+        );
+        goal.changed_command = None;
+        assert!(runner.check_goal(&goal).await.passed);
+
+        // Without changed files under the goal, the full command runs
+        let mut goal = make_goal("unchanged", "echo 95", 80.0);
+        goal.workspace = Some("docs".to_string());
+        goal.changed_command = Some("echo 5".to_string());
+        std::fs::create_dir(dir.path().join("docs")).unwrap();
+        assert_eq!(runner.check_goal(&goal).await.score, 95.0);
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        assert_eq!(shell_quote("src/lib.rs", ShellKind::Sh), "src/lib.rs");
+        assert_eq!(shell_quote("my file.ts", ShellKind::Sh), "'my file.ts'");
+        assert_eq!(shell_quote("it's", ShellKind::Sh), "'it'\\''s'");
+        assert_eq!(shell_quote("my file.ts", ShellKind::Cmd), "\"my file.ts\"");
+    }
+
     #[test]
     fn test_parse_score_valid() {
         let runner = GoalRunner::new(".");
@@ -10,9 +10,14 @@ use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::RwLock;
+use uira_core::UIRA_DIR;
 
 use super::super::hook::{Hook, HookContext, HookResult};
 use super::super::types::{HookEvent, HookInput, HookOutput, MessagePart};
+use crate::features::builtin_skills::{
+    format_argument_usage, render_skill_template, SkillArgument, SkillArgumentError,
+    SkillPermissions, SkillScope,
+};
 
 // --- constants.ts ---
 
@@ -116,6 +121,12 @@ pub struct CommandMetadata {
     pub model: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub agent: Option<String>,
+    /// Declared arguments substituted into `{{name}}` placeholders
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub arguments: Vec<SkillArgument>,
+    /// Tool and permission restrictions applied while the command runs
+    #[serde(default, skip_serializing_if = "SkillPermissions::is_empty")]
+    pub permissions: SkillPermissions,
 }
 
 /// Discovered command information
@@ -248,6 +259,35 @@ fn parse_frontmatter(content: &str) -> (HashMap<String, String>, String) {
     (data, body.to_string())
 }
 
+/// Parse the `arguments:` list from a command's YAML frontmatter.
+///
+/// The line-based parser above cannot represent nested lists, so the block is
+/// re-read as YAML. Malformed declarations are ignored.
+fn parse_declared_arguments(content: &str) -> Vec<SkillArgument> {
+    #[derive(Deserialize)]
+    struct ArgumentsFrontmatter {
+        #[serde(default)]
+        arguments: Vec<SkillArgument>,
+    }
+
+    parse_yaml_frontmatter::<ArgumentsFrontmatter>(content)
+        .map(|f| f.arguments)
+        .unwrap_or_default()
+}
+
+/// Parse the `allowed-tools` and `permissions` restrictions from a command's
+/// YAML frontmatter. Malformed restrictions are ignored, leaving the
+/// session's own permissions in force.
+fn parse_declared_permissions(content: &str) -> SkillPermissions {
+    parse_yaml_frontmatter(content).unwrap_or_default()
+}
+
+fn parse_yaml_frontmatter<T: serde::de::DeserializeOwned>(content: &str) -> Option<T> {
+    let re = Regex::new(r"(?s)^---\r?\n(.*?)\r?\n---").unwrap();
+    let yaml = re.captures(content)?.get(1)?;
+    serde_yaml_ng::from_str(yaml.as_str()).ok()
+}
+
 fn discover_commands_from_dir(commands_dir: &Path, scope: CommandScope) -> Vec<CommandInfo> {
     let Ok(entries) = fs::read_dir(commands_dir) else {
         return Vec::new();
@@ -280,6 +320,8 @@ fn discover_commands_from_dir(commands_dir: &Path, scope: CommandScope) -> Vec<C
             argument_hint: data.get("argument-hint").cloned(),
             model: data.get("model").cloned(),
             agent: data.get("agent").cloned(),
+            arguments: parse_declared_arguments(&content),
+            permissions: parse_declared_permissions(&content),
         };
 
         commands.push(CommandInfo {
@@ -294,26 +336,13 @@ fn discover_commands_from_dir(commands_dir: &Path, scope: CommandScope) -> Vec<C
     commands
 }
 
-/// Discover all available commands from multiple sources
-pub fn discover_all_commands(working_directory: &Path) -> Vec<CommandInfo> {
-    let Some(claude_dir) = claude_config_dir() else {
+/// Discover skills from a directory of `<name>/SKILL.md` packages
+fn discover_skills_from_dir(skills_dir: &Path) -> Vec<CommandInfo> {
+    let Ok(skill_entries) = fs::read_dir(skills_dir) else {
         return Vec::new();
     };
 
-    let user_commands_dir = claude_dir.join("commands");
-    let project_commands_dir = working_directory.join(".claude").join("commands");
-    let skills_dir = claude_dir.join("skills");
-
-    let user_commands = discover_commands_from_dir(&user_commands_dir, CommandScope::User);
-    let project_commands = discover_commands_from_dir(&project_commands_dir, CommandScope::Project);
-
-    // Discover skills (each skill directory may have a SKILL.md)
     let mut skill_commands = Vec::new();
-    let Ok(skill_entries) = fs::read_dir(skills_dir) else {
-        // Priority: project > user > skills
-        return project_commands.into_iter().chain(user_commands).collect();
-    };
-
     for entry in skill_entries.flatten() {
         let dir_path = entry.path();
         if !dir_path.is_dir() {
@@ -344,6 +373,8 @@ pub fn discover_all_commands(working_directory: &Path) -> Vec<CommandInfo> {
             argument_hint: data.get("argument-hint").cloned(),
             model: data.get("model").cloned(),
             agent: data.get("agent").cloned(),
+            arguments: parse_declared_arguments(&content),
+            permissions: parse_declared_permissions(&content),
         };
 
         skill_commands.push(CommandInfo {
@@ -355,6 +386,38 @@ pub fn discover_all_commands(working_directory: &Path) -> Vec<CommandInfo> {
         });
     }
 
+    skill_commands
+}
+
+/// Discover all available commands from multiple sources
+pub fn discover_all_commands(working_directory: &Path) -> Vec<CommandInfo> {
+    let Some(claude_dir) = claude_config_dir() else {
+        return Vec::new();
+    };
+
+    let user_commands_dir = claude_dir.join("commands");
+    let project_commands_dir = working_directory.join(".claude").join("commands");
+
+    let user_commands = discover_commands_from_dir(&user_commands_dir, CommandScope::User);
+    let project_commands = discover_commands_from_dir(&project_commands_dir, CommandScope::Project);
+
+    // Skills installed with `uira skills install` take precedence over ~/.claude/skills
+    let mut skill_commands = Vec::new();
+    let skill_dirs = dirs::home_dir()
+        .map(|home| home.join(UIRA_DIR).join("skills"))
+        .into_iter()
+        .chain(std::iter::once(claude_dir.join("skills")));
+    for skills_dir in skill_dirs {
+        for skill in discover_skills_from_dir(&skills_dir) {
+            if !skill_commands
+                .iter()
+                .any(|s: &CommandInfo| s.name.eq_ignore_ascii_case(&skill.name))
+            {
+                skill_commands.push(skill);
+            }
+        }
+    }
+
     // Priority: project > user > skills
     project_commands
         .into_iter()
@@ -364,10 +427,22 @@ pub fn discover_all_commands(working_directory: &Path) -> Vec<CommandInfo> {
 }
 
 /// Find a specific command by name
+///
+/// Skills are also matched without the `uira:` namespace, so `/uira:release-notes`
+/// resolves a skill named `release-notes`.
 pub fn find_command(command_name: &str, working_directory: &Path) -> Option<CommandInfo> {
-    discover_all_commands(working_directory)
-        .into_iter()
+    let commands = discover_all_commands(working_directory);
+    if let Some(cmd) = commands
+        .iter()
         .find(|cmd| cmd.name.eq_ignore_ascii_case(command_name))
+    {
+        return Some(cmd.clone());
+    }
+
+    let name = command_name.strip_prefix("uira:")?;
+    commands
+        .into_iter()
+        .find(|cmd| cmd.scope == CommandScope::Skill && cmd.name.eq_ignore_ascii_case(name))
 }
 
 fn resolve_arguments(content: &str, args: &str) -> String {
@@ -381,7 +456,7 @@ fn resolve_arguments(content: &str, args: &str) -> String {
     )
 }
 
-fn format_command_template(cmd: &CommandInfo, args: &str) -> String {
+fn format_command_template(cmd: &CommandInfo, args: &str) -> Result<String, SkillArgumentError> {
     let mut sections = Vec::<String>::new();
 
     sections.push(format!("<command-name>/{}</command-name>\n", cmd.name));
@@ -410,16 +485,22 @@ fn format_command_template(cmd: &CommandInfo, args: &str) -> String {
     sections.push("---\n".to_string());
 
     let content = cmd.content.as_deref().unwrap_or_default();
-    let resolved = resolve_arguments(content, args);
+    let declared = &cmd.metadata.arguments;
+    let content = if declared.is_empty() {
+        content.to_string()
+    } else {
+        render_skill_template(content, declared, args)?
+    };
+    let resolved = resolve_arguments(&content, args);
     sections.push(resolved.trim().to_string());
 
-    if !args.is_empty() && !content.contains("$ARGUMENTS") {
+    if !args.is_empty() && declared.is_empty() && !content.contains("$ARGUMENTS") {
         sections.push("\n\n---\n".to_string());
         sections.push("## User Request\n".to_string());
         sections.push(args.to_string());
     }
 
-    sections.join("\n")
+    Ok(sections.join("\n"))
 }
 
 /// Execute a slash command and return replacement text
@@ -427,23 +508,107 @@ pub fn execute_slash_command(
     parsed: &ParsedSlashCommand,
     working_directory: &Path,
 ) -> ExecuteResult {
-    let Some(command) = find_command(&parsed.command, working_directory) else {
-        return ExecuteResult {
+    match find_command(&parsed.command, working_directory) {
+        Some(command) => execute_command(&command, parsed),
+        None => ExecuteResult {
             success: false,
             replacement_text: None,
             error: Some(format!(
                 "Command \"/{}\" not found. Available commands are in ~/.claude/commands/ or .claude/commands/",
                 parsed.command
             )),
+        },
+    }
+}
+
+fn execute_command(command: &CommandInfo, parsed: &ParsedSlashCommand) -> ExecuteResult {
+    if let Err(err) = SkillScope::new(&command.name, command.metadata.permissions.clone()) {
+        return ExecuteResult {
+            success: false,
+            replacement_text: None,
+            error: Some(format!(
+                "Invalid permissions for \"/{}\": {}",
+                command.name, err
+            )),
         };
-    };
+    }
 
-    let template = format_command_template(&command, &parsed.args);
-    ExecuteResult {
-        success: true,
-        replacement_text: Some(template),
-        error: None,
+    match format_command_template(command, &parsed.args) {
+        Ok(template) => ExecuteResult {
+            success: true,
+            replacement_text: Some(template),
+            error: None,
+        },
+        Err(err) => ExecuteResult {
+            success: false,
+            replacement_text: None,
+            error: Some(format!(
+                "Invalid arguments for \"/{}\": {}\nUsage: /{} {}",
+                command.name,
+                err,
+                command.name,
+                format_argument_usage(&command.metadata.arguments)
+            )),
+        },
+    }
+}
+
+/// The tagged message that replaces a slash command in the prompt
+fn injected_message(result: ExecuteResult, parsed: &ParsedSlashCommand) -> String {
+    if let (true, Some(text)) = (result.success, result.replacement_text) {
+        return format!(
+            "{}\n{}\n{}",
+            AUTO_SLASH_COMMAND_TAG_OPEN, text, AUTO_SLASH_COMMAND_TAG_CLOSE
+        );
+    }
+    format!(
+        "{}\n[AUTO-SLASH-COMMAND ERROR]\n{}\n\nOriginal input: {}\n{}",
+        AUTO_SLASH_COMMAND_TAG_OPEN,
+        result.error.unwrap_or_else(|| "Unknown error".to_string()),
+        parsed.raw,
+        AUTO_SLASH_COMMAND_TAG_CLOSE
+    )
+}
+
+/// A prompt's slash command, expanded for the agent
+#[derive(Debug, Clone)]
+pub struct SlashCommandExpansion {
+    /// Name of the command or skill that ran
+    pub command: String,
+    /// The tagged template, or the tagged error when the command failed
+    pub prompt: String,
+    /// Restrictions to apply while the command runs, if it declares any
+    pub permissions: Option<SkillPermissions>,
+}
+
+/// Expand a prompt that invokes a `/uira:*` command or skill. `None` when the
+/// prompt is not a slash command or was already expanded.
+pub fn expand_slash_command(
+    prompt: &str,
+    working_directory: &Path,
+) -> Option<SlashCommandExpansion> {
+    if prompt.contains(AUTO_SLASH_COMMAND_TAG_OPEN) || prompt.contains(AUTO_SLASH_COMMAND_TAG_CLOSE)
+    {
+        return None;
     }
+    let parsed = detect_slash_command(prompt)?;
+    let Some(command) = find_command(&parsed.command, working_directory) else {
+        let result = execute_slash_command(&parsed, working_directory);
+        return Some(SlashCommandExpansion {
+            command: parsed.command.clone(),
+            prompt: injected_message(result, &parsed),
+            permissions: None,
+        });
+    };
+
+    let result = execute_command(&command, &parsed);
+    let permissions = Some(command.metadata.permissions)
+        .filter(|permissions| result.success && !permissions.is_empty());
+    Some(SlashCommandExpansion {
+        command: command.name,
+        prompt: injected_message(result, &parsed),
+        permissions,
+    })
 }
 
 /// List all available commands
@@ -551,23 +716,9 @@ impl Hook for AutoSlashCommandHook {
 
         let working_dir = Path::new(&context.directory);
         let result = execute_slash_command(&parsed, working_dir);
-
-        if result.success {
-            if let Some(text) = result.replacement_text {
-                let tagged = format!(
-                    "{}\n{}\n{}",
-                    AUTO_SLASH_COMMAND_TAG_OPEN, text, AUTO_SLASH_COMMAND_TAG_CLOSE
-                );
-                return Ok(HookOutput::continue_with_message(tagged));
-            }
-        }
-
-        let err = result.error.unwrap_or_else(|| "Unknown error".to_string());
-        let error_message = format!(
-            "{}\n[AUTO-SLASH-COMMAND ERROR]\n{}\n\nOriginal input: {}\n{}",
-            AUTO_SLASH_COMMAND_TAG_OPEN, err, parsed.raw, AUTO_SLASH_COMMAND_TAG_CLOSE
-        );
-        Ok(HookOutput::continue_with_message(error_message))
+        Ok(HookOutput::continue_with_message(injected_message(
+            result, &parsed,
+        )))
     }
 
     fn priority(&self) -> i32 {
@@ -595,29 +746,10 @@ pub fn process_slash_command(prompt: &str, working_directory: &Path) -> AutoSlas
     };
 
     let result = execute_slash_command(&parsed, working_directory);
-    if result.success {
-        if let Some(text) = result.replacement_text {
-            return AutoSlashCommandResult {
-                detected: true,
-                parsed_command: Some(parsed),
-                injected_message: Some(format!(
-                    "{}\n{}\n{}",
-                    AUTO_SLASH_COMMAND_TAG_OPEN, text, AUTO_SLASH_COMMAND_TAG_CLOSE
-                )),
-            };
-        }
-    }
-
     AutoSlashCommandResult {
         detected: true,
-        parsed_command: Some(parsed.clone()),
-        injected_message: Some(format!(
-            "{}\n[AUTO-SLASH-COMMAND ERROR]\n{}\n\nOriginal input: {}\n{}",
-            AUTO_SLASH_COMMAND_TAG_OPEN,
-            result.error.unwrap_or_else(|| "Unknown error".to_string()),
-            parsed.raw,
-            AUTO_SLASH_COMMAND_TAG_CLOSE
-        )),
+        injected_message: Some(injected_message(result, &parsed)),
+        parsed_command: Some(parsed),
     }
 }
 
@@ -692,6 +824,68 @@ mod tests {
         assert!(text.contains("<command-name>/uira:test</command-name>"));
     }
 
+    #[test]
+    fn test_execute_slash_command_with_declared_arguments() {
+        let wd = tempdir().unwrap();
+        let commands_dir = wd.path().join(".claude").join("commands");
+        fs::create_dir_all(&commands_dir).unwrap();
+        fs::write(
+            commands_dir.join("uira:release.md"),
+            "---\ndescription: Release\narguments:\n  - name: version\n    required: true\n  - name: channel\n    default: stable\n---\nShip {{version}} to {{channel}}",
+        )
+        .unwrap();
+
+        let mut parsed = ParsedSlashCommand {
+            command: "uira:release".to_string(),
+            args: "v1.4".to_string(),
+            raw: "/uira:release v1.4".to_string(),
+        };
+        let result = execute_slash_command(&parsed, wd.path());
+        assert!(result.success);
+        assert!(result
+            .replacement_text
+            .unwrap()
+            .contains("Ship v1.4 to stable"));
+
+        parsed.args = String::new();
+        let result = execute_slash_command(&parsed, wd.path());
+        assert!(!result.success);
+        let error = result.error.unwrap();
+        assert!(error.contains("missing required argument(s): version"));
+        assert!(error.contains("Usage: /uira:release <version> [channel=stable]"));
+    }
+
+    #[test]
+    fn test_expand_slash_command_carries_declared_permissions() {
+        let wd = tempdir().unwrap();
+        let commands_dir = wd.path().join(".claude").join("commands");
+        fs::create_dir_all(&commands_dir).unwrap();
+        fs::write(
+            commands_dir.join("uira:docs.md"),
+            "---\ndescription: Docs\nallowed-tools: [Read, Write]\n---\nUpdate the docs",
+        )
+        .unwrap();
+        fs::write(
+            commands_dir.join("uira:broken.md"),
+            "---\npermissions:\n  - permission: \"file:write\"\n    pattern: \"[\"\n    action: deny\n---\nBody",
+        )
+        .unwrap();
+
+        let expansion = expand_slash_command("/uira:docs", wd.path()).unwrap();
+        assert_eq!(expansion.command, "uira:docs");
+        assert!(expansion.prompt.contains("Update the docs"));
+        let permissions = expansion.permissions.unwrap();
+        assert!(permissions.allows_tool("Write"));
+        assert!(!permissions.allows_tool("Bash"));
+
+        let broken = expand_slash_command("/uira:broken", wd.path()).unwrap();
+        assert!(broken.prompt.contains("Invalid permissions"));
+        assert!(broken.permissions.is_none());
+
+        assert!(expand_slash_command("no command here", wd.path()).is_none());
+        assert!(expand_slash_command(&expansion.prompt, wd.path()).is_none());
+    }
+
     #[tokio::test]
     async fn test_hook_integration_injects_tagged_message() {
         let wd = tempdir().unwrap();
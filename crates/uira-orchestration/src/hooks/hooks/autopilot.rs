@@ -28,10 +28,42 @@ pub enum AutopilotPhase {
     Cancelled,
 }
 
+impl AutopilotPhase {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Idle => "idle",
+            Self::Planning => "planning",
+            Self::Executing => "executing",
+            Self::Verifying => "verifying",
+            Self::Complete => "complete",
+            Self::Failed => "failed",
+            Self::Cancelled => "cancelled",
+        }
+    }
+
+    /// The phase a completion signal advances to
+    pub fn next(&self) -> Option<AutopilotPhase> {
+        match self {
+            Self::Idle => Some(Self::Planning),
+            Self::Planning => Some(Self::Executing),
+            Self::Executing => Some(Self::Verifying),
+            Self::Verifying => Some(Self::Complete),
+            _ => None,
+        }
+    }
+
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, Self::Complete | Self::Failed | Self::Cancelled)
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct AutopilotConfig {
     #[serde(default = "default_max_iterations")]
     pub max_iterations: u32,
+    /// Pause for user approval before starting each new phase
+    #[serde(default)]
+    pub require_approval: bool,
 }
 
 fn default_max_iterations() -> u32 {
@@ -42,6 +74,7 @@ impl Default for AutopilotConfig {
     fn default() -> Self {
         Self {
             max_iterations: default_max_iterations(),
+            require_approval: false,
         }
     }
 }
@@ -54,6 +87,10 @@ pub struct AutopilotState {
     pub max_iterations: u32,
     pub original_task: String,
 
+    /// Acceptance criteria the work is verified against
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub goals: Vec<String>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub plan_path: Option<String>,
 
@@ -67,6 +104,13 @@ pub struct AutopilotState {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     pub last_error: Option<String>,
+
+    #[serde(default)]
+    pub require_approval: bool,
+
+    /// Phase waiting for user approval before it starts
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub pending_phase: Option<AutopilotPhase>,
 }
 
 impl AutopilotState {
@@ -78,12 +122,15 @@ impl AutopilotState {
             iteration: 1,
             max_iterations: config.max_iterations,
             original_task: task,
+            goals: Vec::new(),
             plan_path: None,
             session_id,
             started_at: now,
             updated_at: now,
             completed_at: None,
             last_error: None,
+            require_approval: config.require_approval,
+            pending_phase: None,
         }
     }
 }
@@ -162,6 +209,22 @@ pub fn detect_any_signal(text: &str) -> Option<AutopilotSignal> {
     signals.into_iter().find(|&s| detect_signal(text, s))
 }
 
+/// What processing an assistant response did to the autopilot state
+#[derive(Debug, Clone)]
+pub enum AutopilotStep {
+    /// Autopilot is not driving this session (inactive, paused, or ralph is running)
+    Idle,
+    /// No phase change; keep working on `state.phase`
+    Continue { state: AutopilotState },
+    /// The phase changed from `from` to `state.phase`
+    Advanced {
+        from: AutopilotPhase,
+        state: AutopilotState,
+    },
+    /// `state.phase` finished and `state.pending_phase` waits for approval
+    AwaitingApproval { state: AutopilotState },
+}
+
 pub struct AutopilotHook {
     _default_config: AutopilotConfig,
 }
@@ -246,9 +309,21 @@ impl AutopilotHook {
         task: &str,
         session_id: Option<String>,
         config: Option<AutopilotConfig>,
+    ) -> Result<AutopilotState, String> {
+        Self::start_with_goals(directory, task, Vec::new(), session_id, config)
+    }
+
+    /// Start autopilot on a task whose work is verified against `goals`
+    pub fn start_with_goals(
+        directory: &str,
+        task: &str,
+        goals: Vec<String>,
+        session_id: Option<String>,
+        config: Option<AutopilotConfig>,
     ) -> Result<AutopilotState, String> {
         let merged_config = config.unwrap_or_default();
-        let state = AutopilotState::new(task.to_string(), session_id, merged_config);
+        let mut state = AutopilotState::new(task.to_string(), session_id, merged_config);
+        state.goals = goals;
         if !Self::write_state(directory, &state) {
             return Err(format!(
                 "Failed to persist autopilot state to {}",
@@ -312,6 +387,104 @@ impl AutopilotHook {
         Self::write_state(directory, &state).then_some(state)
     }
 
+    /// Start the phase waiting for approval
+    pub fn approve(directory: &str) -> Result<AutopilotState, String> {
+        let mut state = Self::read_state(directory).ok_or_else(|| "no state".to_string())?;
+        let next = state
+            .pending_phase
+            .take()
+            .ok_or_else(|| "no phase is waiting for approval".to_string())?;
+        if !Self::write_state(directory, &state) {
+            return Err("failed to persist state".to_string());
+        }
+        Self::transition(directory, next)
+    }
+
+    /// Advance the state machine from an assistant response.
+    ///
+    /// Looks for phase signals in `response`, moving to the next phase (or
+    /// pausing for approval when `require_approval` is set), and counts an
+    /// iteration when the current phase carries on.
+    pub fn process_response(
+        directory: &str,
+        session_id: Option<&str>,
+        response: &str,
+    ) -> AutopilotStep {
+        let mut state = match Self::read_state(directory) {
+            Some(s) if s.active && s.pending_phase.is_none() => s,
+            _ => return AutopilotStep::Idle,
+        };
+
+        // If ralph is active, let it handle continuation
+        if Self::is_ralph_active(directory) {
+            return AutopilotStep::Idle;
+        }
+
+        // Check session binding
+        if let (Some(bound_sid), Some(sid)) = (&state.session_id, session_id) {
+            if bound_sid != sid {
+                return AutopilotStep::Idle;
+            }
+        }
+
+        let from = state.phase;
+
+        // Cancellation via explicit signal in the assistant output.
+        if detect_signal(response, AutopilotSignal::AutopilotCancelled) {
+            return match Self::cancel(directory, Some("cancelled by signal")) {
+                Some(state) => AutopilotStep::Advanced { from, state },
+                None => AutopilotStep::Idle,
+            };
+        }
+
+        // Phase advancement based on signal in the assistant output.
+        if let Some(expected) = expected_signal_for_phase(from) {
+            if detect_signal(response, expected)
+                || (from == AutopilotPhase::Verifying
+                    && detect_signal(response, AutopilotSignal::VerifyingComplete))
+            {
+                let next = from.next().unwrap_or(from);
+
+                if state.require_approval && !next.is_terminal() {
+                    state.pending_phase = Some(next);
+                    state.updated_at = Utc::now();
+                    if !Self::write_state(directory, &state) {
+                        return AutopilotStep::Idle;
+                    }
+                    return AutopilotStep::AwaitingApproval { state };
+                }
+
+                let _ = Self::transition(directory, next);
+                state = match Self::read_state(directory) {
+                    Some(s) => s,
+                    None => return AutopilotStep::Idle,
+                };
+
+                if state.phase == AutopilotPhase::Complete {
+                    return AutopilotStep::Advanced { from, state };
+                }
+            }
+        }
+
+        // Safety limit.
+        if state.iteration >= state.max_iterations {
+            return match Self::fail(
+                directory,
+                format!("max iterations ({}) reached", state.max_iterations),
+            ) {
+                Some(state) => AutopilotStep::Advanced { from, state },
+                None => AutopilotStep::Idle,
+            };
+        }
+
+        // Continue current phase.
+        match Self::increment_iteration(directory) {
+            Some(state) if state.phase != from => AutopilotStep::Advanced { from, state },
+            Some(state) => AutopilotStep::Continue { state },
+            None => AutopilotStep::Idle,
+        }
+    }
+
     fn increment_iteration(directory: &str) -> Option<AutopilotState> {
         let mut state = Self::read_state(directory)?;
         if !state.active {
@@ -322,23 +495,33 @@ impl AutopilotHook {
         Self::write_state(directory, &state).then_some(state)
     }
 
-    fn get_phase_prompt(state: &AutopilotState) -> String {
+    pub fn get_phase_prompt(state: &AutopilotState) -> String {
         match state.phase {
             AutopilotPhase::Planning => format!(
                 "## AUTOPILOT PHASE: PLANNING\n\nOriginal task:\n{}\n\nWhen the plan is finished, output: PLANNING_COMPLETE\n",
                 state.original_task
             ),
-            AutopilotPhase::Executing => {
-                "## AUTOPILOT PHASE: EXECUTING\n\nExecute the plan and implement the task.\n\nWhen implementation is finished, output: EXECUTION_COMPLETE\n".to_string()
-            }
-            AutopilotPhase::Verifying => {
-                "## AUTOPILOT PHASE: VERIFYING\n\nRun verification (tests/build/lint as applicable).\n\nWhen fully verified, output: AUTOPILOT_COMPLETE\n".to_string()
-            }
+            AutopilotPhase::Executing => format!(
+                "## AUTOPILOT PHASE: EXECUTING\n\nExecute the plan and implement the task.\n{}\nWhen implementation is finished, output: EXECUTION_COMPLETE\n",
+                Self::goals_section(state, "The work must meet these goals:")
+            ),
+            AutopilotPhase::Verifying => format!(
+                "## AUTOPILOT PHASE: VERIFYING\n\nRun verification (tests/build/lint as applicable).\n{}\nWhen fully verified, output: AUTOPILOT_COMPLETE\n",
+                Self::goals_section(state, "Confirm each goal is met:")
+            ),
             _ => String::new(),
         }
     }
 
-    fn continuation_prompt(state: &AutopilotState) -> String {
+    fn goals_section(state: &AutopilotState, heading: &str) -> String {
+        if state.goals.is_empty() {
+            return String::new();
+        }
+        let goals: Vec<String> = state.goals.iter().map(|g| format!("- {}", g)).collect();
+        format!("\n{}\n{}\n", heading, goals.join("\n"))
+    }
+
+    pub fn continuation_prompt(state: &AutopilotState) -> String {
         format!(
             r#"<autopilot-continuation>
 
@@ -383,84 +566,42 @@ impl Hook for AutopilotHook {
         input: &HookInput,
         context: &HookContext,
     ) -> HookResult {
-        let mut state = match Self::read_state(&context.directory) {
-            Some(s) => s,
-            None => return Ok(HookOutput::pass()),
-        };
-
-        if !state.active {
-            return Ok(HookOutput::pass());
-        }
-
-        // If ralph is active, let it handle continuation
-        if Self::is_ralph_active(&context.directory) {
-            return Ok(HookOutput::pass());
-        }
-
-        // Check session binding
-        if let (Some(bound_sid), Some(sid)) = (&state.session_id, &input.session_id) {
-            if bound_sid != sid {
-                return Ok(HookOutput::pass());
-            }
-        }
-
-        // Cancellation via explicit signal in the assistant output.
         let prompt_text = input.get_prompt_text();
-        if detect_signal(&prompt_text, AutopilotSignal::AutopilotCancelled) {
-            Self::cancel(&context.directory, Some("cancelled by signal"));
-            return Ok(HookOutput::continue_with_message(
-                "[AUTOPILOT CANCELLED] Session cancelled; progress preserved in .uira/autopilot-state.json",
-            ));
-        }
-
-        // Phase advancement based on signal in the assistant output.
-        if let Some(expected) = expected_signal_for_phase(state.phase) {
-            if detect_signal(&prompt_text, expected)
-                || (state.phase == AutopilotPhase::Verifying
-                    && detect_signal(&prompt_text, AutopilotSignal::VerifyingComplete))
-            {
-                let next = match state.phase {
-                    AutopilotPhase::Planning => AutopilotPhase::Executing,
-                    AutopilotPhase::Executing => AutopilotPhase::Verifying,
-                    AutopilotPhase::Verifying => AutopilotPhase::Complete,
-                    _ => state.phase,
-                };
-
-                let _ = Self::transition(&context.directory, next);
-                state = match Self::read_state(&context.directory) {
-                    Some(s) => s,
-                    None => return Ok(HookOutput::pass()),
-                };
-
-                if state.phase == AutopilotPhase::Complete {
-                    return Ok(HookOutput::continue_with_message(
-                        "[AUTOPILOT COMPLETE] All phases finished successfully.",
-                    ));
-                }
+        let step = Self::process_response(
+            &context.directory,
+            input.session_id.as_deref(),
+            &prompt_text,
+        );
+
+        match step {
+            AutopilotStep::Idle => Ok(HookOutput::pass()),
+            AutopilotStep::AwaitingApproval { state } => {
+                Ok(HookOutput::continue_with_message(format!(
+                    "[AUTOPILOT PAUSED] {} finished. Approve to start {}.",
+                    state.phase.as_str(),
+                    state.pending_phase.map(|p| p.as_str()).unwrap_or("the next phase")
+                )))
             }
+            AutopilotStep::Advanced { state, .. } if state.phase == AutopilotPhase::Complete => {
+                Ok(HookOutput::continue_with_message(
+                    "[AUTOPILOT COMPLETE] All phases finished successfully.",
+                ))
+            }
+            AutopilotStep::Advanced { state, .. } if state.phase == AutopilotPhase::Cancelled => {
+                Ok(HookOutput::continue_with_message(
+                    "[AUTOPILOT CANCELLED] Session cancelled; progress preserved in .uira/autopilot-state.json",
+                ))
+            }
+            AutopilotStep::Advanced { state, .. } if state.phase == AutopilotPhase::Failed => {
+                Ok(HookOutput::continue_with_message(format!(
+                    "[AUTOPILOT STOPPED] Max iterations ({}) reached. State preserved in .uira/autopilot-state.json",
+                    state.max_iterations
+                )))
+            }
+            AutopilotStep::Advanced { state, .. } | AutopilotStep::Continue { state } => Ok(
+                HookOutput::block_with_reason(Self::continuation_prompt(&state)),
+            ),
         }
-
-        // Safety limit.
-        if state.iteration >= state.max_iterations {
-            Self::fail(
-                &context.directory,
-                format!("max iterations ({}) reached", state.max_iterations),
-            );
-            return Ok(HookOutput::continue_with_message(format!(
-                "[AUTOPILOT STOPPED] Max iterations ({}) reached. State preserved in .uira/autopilot-state.json",
-                state.max_iterations
-            )));
-        }
-
-        // Continue current phase.
-        let new_state = match Self::increment_iteration(&context.directory) {
-            Some(s) => s,
-            None => return Ok(HookOutput::pass()),
-        };
-
-        Ok(HookOutput::block_with_reason(Self::continuation_prompt(
-            &new_state,
-        )))
     }
 
     fn priority(&self) -> i32 {
@@ -549,4 +690,82 @@ mod tests {
         assert!(!state.active);
         assert_eq!(state.last_error.as_deref(), Some("user request"));
     }
+
+    #[test]
+    fn test_process_response_advances_phases() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+
+        AutopilotHook::start(root, "task", None, None).unwrap();
+
+        let step = AutopilotHook::process_response(root, None, "still planning");
+        let AutopilotStep::Continue { state } = step else {
+            panic!("expected continue, got {:?}", step);
+        };
+        assert_eq!(state.phase, AutopilotPhase::Planning);
+        assert_eq!(state.iteration, 2);
+
+        let step = AutopilotHook::process_response(root, None, "PLANNING_COMPLETE");
+        let AutopilotStep::Advanced { from, state } = step else {
+            panic!("expected advance, got {:?}", step);
+        };
+        assert_eq!(from, AutopilotPhase::Planning);
+        assert_eq!(state.phase, AutopilotPhase::Executing);
+    }
+
+    #[test]
+    fn test_require_approval_pauses_between_phases() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+
+        let config = AutopilotConfig {
+            require_approval: true,
+            ..AutopilotConfig::default()
+        };
+        AutopilotHook::start(root, "task", None, Some(config)).unwrap();
+
+        let step = AutopilotHook::process_response(root, None, "PLANNING_COMPLETE");
+        let AutopilotStep::AwaitingApproval { state } = step else {
+            panic!("expected approval pause, got {:?}", step);
+        };
+        assert_eq!(state.phase, AutopilotPhase::Planning);
+        assert_eq!(state.pending_phase, Some(AutopilotPhase::Executing));
+
+        // Nothing moves while the phase waits for approval
+        assert!(matches!(
+            AutopilotHook::process_response(root, None, "EXECUTION_COMPLETE"),
+            AutopilotStep::Idle
+        ));
+
+        let state = AutopilotHook::approve(root).unwrap();
+        assert_eq!(state.phase, AutopilotPhase::Executing);
+        assert!(state.pending_phase.is_none());
+        assert!(AutopilotHook::approve(root).is_err());
+
+        // Finishing verification completes without another pause
+        AutopilotHook::transition(root, AutopilotPhase::Verifying).unwrap();
+        let step = AutopilotHook::process_response(root, None, "AUTOPILOT_COMPLETE");
+        let AutopilotStep::Advanced { state, .. } = step else {
+            panic!("expected completion, got {:?}", step);
+        };
+        assert_eq!(state.phase, AutopilotPhase::Complete);
+    }
+
+    #[test]
+    fn test_goals_persist_and_reach_phase_prompts() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_str().unwrap();
+
+        let goals = vec!["CSV export works".to_string()];
+        AutopilotHook::start_with_goals(root, "task", goals.clone(), None, None).unwrap();
+        let state = AutopilotHook::read_state(root).unwrap();
+        assert_eq!(state.goals, goals);
+        assert!(!AutopilotHook::get_phase_prompt(&state).contains("CSV export works"));
+
+        let state = AutopilotHook::transition(root, AutopilotPhase::Executing).unwrap();
+        assert!(AutopilotHook::get_phase_prompt(&state).contains("- CSV export works"));
+        let state = AutopilotHook::transition(root, AutopilotPhase::Verifying).unwrap();
+        let prompt = AutopilotHook::get_phase_prompt(&state);
+        assert!(prompt.contains("Confirm each goal is met:\n- CSV export works"));
+    }
 }
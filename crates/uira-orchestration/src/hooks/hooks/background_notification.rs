@@ -13,6 +13,8 @@ use std::path::PathBuf;
 use std::sync::RwLock;
 use uira_core::UIRA_DIR;
 
+use crate::features::background_agent::{self, take_background_notifications};
+
 use super::super::hook::{Hook, HookContext, HookResult};
 use super::super::types::{HookEvent, HookInput, HookOutput};
 
@@ -97,6 +99,48 @@ pub struct BackgroundTask {
     pub parent_model: Option<String>,
 }
 
+impl From<background_agent::BackgroundTaskStatus> for BackgroundTaskStatus {
+    fn from(status: background_agent::BackgroundTaskStatus) -> Self {
+        use background_agent::BackgroundTaskStatus as S;
+        match status {
+            S::Queued => Self::Queued,
+            S::Pending => Self::Pending,
+            S::Running => Self::Running,
+            S::Completed => Self::Completed,
+            S::Error => Self::Error,
+            S::Cancelled => Self::Cancelled,
+        }
+    }
+}
+
+impl From<background_agent::BackgroundTask> for BackgroundTask {
+    fn from(task: background_agent::BackgroundTask) -> Self {
+        Self {
+            id: task.id,
+            session_id: task.session_id,
+            parent_session_id: task.parent_session_id,
+            description: task.description,
+            prompt: task.prompt,
+            agent: task.agent,
+            status: task.status.into(),
+            queued_at: task.queued_at,
+            started_at: task.started_at,
+            completed_at: task.completed_at,
+            result: task.result,
+            error: task.error,
+            progress: task.progress.map(|p| TaskProgress {
+                tool_calls: u32::try_from(p.tool_calls).unwrap_or(u32::MAX),
+                last_tool: p.last_tool,
+                last_update: p.last_update,
+                last_message: p.last_message,
+                last_message_at: p.last_message_at,
+            }),
+            concurrency_key: task.concurrency_key,
+            parent_model: task.parent_model,
+        }
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NotificationCheckResult {
     pub has_notifications: bool,
@@ -333,6 +377,16 @@ pub fn check_background_notifications(
     session_id: &str,
     config: Option<&BackgroundNotificationHookConfig>,
 ) -> NotificationCheckResult {
+    // Tasks lost in a crash or finished by another process
+    let recovered = take_background_notifications(session_id);
+    if !recovered.is_empty() {
+        if let Ok(mut mgr) = MANAGER.write() {
+            for task in recovered {
+                mgr.mark_for_notification(task.into());
+            }
+        }
+    }
+
     let tasks = MANAGER
         .read()
         .ok()
@@ -20,12 +20,14 @@ pub mod preemptive_compaction;
 pub mod ralph;
 pub mod recovery;
 pub mod rules_injector;
+pub mod skill_usage;
 pub mod think_mode;
 pub mod thinking_block_validator;
 pub mod todo_continuation;
 pub mod uira_orchestrator;
 pub mod ultrapilot;
 pub mod ultraqa;
+pub mod ultraqa_adapters;
 pub mod ultrawork;
 
 pub use agent_usage_reminder::{
@@ -33,14 +35,14 @@ pub use agent_usage_reminder::{
     AgentUsageReminderHook, AgentUsageState, AGENT_TOOLS, REMINDER_MESSAGE, TARGET_TOOLS,
 };
 pub use auto_slash_command::{
-    AutoSlashCommandHook, AutoSlashCommandHookInput, AutoSlashCommandHookOutput,
-    AutoSlashCommandResult, CommandInfo, CommandMetadata, CommandScope, ExecuteResult,
-    ParsedSlashCommand,
+    expand_slash_command, AutoSlashCommandHook, AutoSlashCommandHookInput,
+    AutoSlashCommandHookOutput, AutoSlashCommandResult, CommandInfo, CommandMetadata, CommandScope,
+    ExecuteResult, ParsedSlashCommand, SlashCommandExpansion,
 };
 pub use autopilot::{
     detect_any_signal, detect_signal, expected_signal_for_phase, validate_state,
     validate_transition, AutopilotConfig, AutopilotHook, AutopilotPhase, AutopilotSignal,
-    AutopilotState, AUTOPILOT_STATE_FILE,
+    AutopilotState, AutopilotStep, AUTOPILOT_STATE_FILE,
 };
 pub use background_notification::{
     background_tasks_dir, check_background_notifications, handle_background_event_public,
@@ -127,7 +129,12 @@ pub use recovery::{
     TRUNCATION_APPLIED_MESSAGE,
 };
 pub use rules_injector::{RuleFileCandidate, RuleMetadata, RuleToInject, RulesInjectorHook};
-pub use think_mode::{ThinkModeHook, ThinkModeState, ThinkingConfig, THINKING_CONFIGS};
+pub use skill_usage::{
+    load_skill_usage_state, save_skill_usage_state, suggest_skill, ActiveSkillUsage,
+    SkillSuggestion, SkillUsageHook, SkillUsageRecord, SkillUsageState,
+    HOOK_NAME as SKILL_USAGE_HOOK_NAME, SKILL_USAGE_STATE_FILE,
+};
+pub use think_mode::{ThinkLevel, ThinkModeHook, ThinkModeState, ThinkingConfig, THINKING_CONFIGS};
 pub use thinking_block_validator::{
     get_validation_stats, is_extended_thinking_model, prepend_thinking_block, validate_message,
     validate_messages, MessageInfo as ThinkingMessageInfo, MessagePart as ThinkingMessagePart,
@@ -138,8 +145,13 @@ pub use thinking_block_validator::{
 pub use todo_continuation::{IncompleteTodosResult, StopContext, TodoContinuationHook};
 pub use uira_orchestrator::{UiraOrchestratorHook, HOOK_NAME as UIRA_ORCHESTRATOR_HOOK_NAME};
 pub use ultrapilot::{
-    FileOwnership, IntegrationResult, UltrapilotConfig, UltrapilotHook, UltrapilotState,
-    WorkerState, WorkerStatus,
+    FileOwnership, IntegrationResult, OwnershipCheck, UltrapilotConfig, UltrapilotHook,
+    UltrapilotState, WorkerState, WorkerStatus,
 };
 pub use ultraqa::{UltraQAExitReason, UltraQAGoalType, UltraQAHook, UltraQAResult, UltraQAState};
+pub use ultraqa_adapters::{
+    run_verification, CargoTestAdapter, CustomCommandAdapter, JestAdapter, LighthouseAdapter,
+    PlaywrightAdapter, PytestAdapter, QaFailure, QaReport, VerificationAdapter,
+    VerificationAdapterRegistry,
+};
 pub use ultrawork::{UltraworkHook, UltraworkState};
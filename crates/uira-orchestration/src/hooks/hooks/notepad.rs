@@ -5,6 +5,10 @@
 //! 1. Priority Context - Always loaded, critical discoveries (max 500 chars)
 //! 2. Working Memory - Session notes, auto-pruned after 7 days
 //! 3. MANUAL - User content, never auto-pruned
+//!
+//! Session branches get their own notepad under `.uira/notepads/`. Forking
+//! copies the parent's notepad and merging reconciles two notepads entry by
+//! entry; the root branch keeps using `.uira/notepad.md`.
 
 use async_trait::async_trait;
 use chrono::{DateTime, Duration, Utc};
@@ -18,6 +22,7 @@ use super::super::hook::{Hook, HookContext, HookResult};
 use super::super::types::{HookEvent, HookInput, HookOutput};
 
 pub const NOTEPAD_FILENAME: &str = "notepad.md";
+pub const BRANCH_NOTEPADS_DIR: &str = "notepads";
 pub const PRIORITY_HEADER: &str = "## Priority Context";
 pub const WORKING_MEMORY_HEADER: &str = "## Working Memory";
 pub const MANUAL_HEADER: &str = "## MANUAL";
@@ -91,7 +96,11 @@ impl NotepadHook {
             return true;
         }
 
-        let content = format!(
+        fs::write(&notepad_path, Self::empty_notepad()).is_ok()
+    }
+
+    fn empty_notepad() -> String {
+        format!(
             r#"# Notepad
 <!-- Auto-managed by Uira. Manual edits preserved in MANUAL section. -->
 
@@ -105,9 +114,123 @@ impl NotepadHook {
 <!-- User content. Never auto-pruned. -->
 
 "#
-        );
+        )
+    }
+
+    /// Notepad for a session branch; `None` is the root branch
+    pub fn get_branch_notepad_path(directory: &str, branch: Option<&str>) -> PathBuf {
+        match branch {
+            Some(branch) => Path::new(directory)
+                .join(UIRA_DIR)
+                .join(BRANCH_NOTEPADS_DIR)
+                .join(format!("{}.md", branch)),
+            None => Self::get_notepad_path(directory),
+        }
+    }
+
+    pub fn read_branch_notepad(directory: &str, branch: Option<&str>) -> Option<String> {
+        fs::read_to_string(Self::get_branch_notepad_path(directory, branch)).ok()
+    }
+
+    /// Give a new branch a copy of its parent's notepad
+    pub fn fork_branch(directory: &str, parent: Option<&str>, branch: &str) -> bool {
+        let Some(content) = Self::read_branch_notepad(directory, parent) else {
+            return true;
+        };
+
+        let path = Self::get_branch_notepad_path(directory, Some(branch));
+        if let Some(parent_dir) = path.parent() {
+            if fs::create_dir_all(parent_dir).is_err() {
+                return false;
+            }
+        }
+        fs::write(path, content).is_ok()
+    }
+
+    /// Fold the notes of `source` into `target`.
+    ///
+    /// Priority lines and timestamped entries missing from `target` are
+    /// appended; entries present in both are kept once.
+    pub fn merge_branch(directory: &str, source: Option<&str>, target: Option<&str>) -> bool {
+        let Some(source_content) = Self::read_branch_notepad(directory, source) else {
+            return true;
+        };
+
+        let target_path = Self::get_branch_notepad_path(directory, target);
+        let mut merged =
+            Self::read_branch_notepad(directory, target).unwrap_or_else(Self::empty_notepad);
+
+        let source_priority =
+            Self::extract_section(&source_content, PRIORITY_HEADER).unwrap_or_default();
+        let mut priority = Self::extract_section(&merged, PRIORITY_HEADER).unwrap_or_default();
+        for line in source_priority.lines().filter(|l| !l.trim().is_empty()) {
+            if !priority
+                .lines()
+                .any(|existing| existing.trim() == line.trim())
+            {
+                if !priority.is_empty() {
+                    priority.push('\n');
+                }
+                priority.push_str(line);
+            }
+        }
+        merged = Self::replace_section(&merged, PRIORITY_HEADER, &priority);
+
+        for header in [WORKING_MEMORY_HEADER, MANUAL_HEADER] {
+            let mut entries =
+                Self::split_entries(&Self::extract_section(&merged, header).unwrap_or_default());
+            for entry in Self::split_entries(
+                &Self::extract_section(&source_content, header).unwrap_or_default(),
+            ) {
+                if !entries.contains(&entry) {
+                    entries.push(entry);
+                }
+            }
+            // Timestamps sort chronologically; untimestamped text stays first
+            entries.sort_by(|a, b| a.0.cmp(&b.0));
+            let section = entries
+                .iter()
+                .map(|(ts, content)| match ts {
+                    Some(ts) => format!("### {}\n{}", ts, content),
+                    None => content.clone(),
+                })
+                .collect::<Vec<_>>()
+                .join("\n\n");
+            merged = Self::replace_section(&merged, header, &section);
+        }
 
-        fs::write(&notepad_path, content).is_ok()
+        if let Some(parent_dir) = target_path.parent() {
+            if fs::create_dir_all(parent_dir).is_err() {
+                return false;
+            }
+        }
+        fs::write(target_path, merged).is_ok()
+    }
+
+    /// Split a section into `### YYYY-MM-DD HH:MM` entries
+    fn split_entries(section: &str) -> Vec<(Option<String>, String)> {
+        let mut entries = Vec::new();
+        let mut current: Option<(Option<String>, String)> = None;
+
+        for line in section.lines() {
+            if let Some(ts) = line.strip_prefix("### ") {
+                entries.extend(current.take());
+                current = Some((Some(ts.trim().to_string()), String::new()));
+                continue;
+            }
+            let entry = current.get_or_insert_with(|| (None, String::new()));
+            if !entry.1.is_empty() {
+                entry.1.push('\n');
+            }
+            entry.1.push_str(line);
+        }
+        entries.extend(current);
+
+        entries
+            .into_iter()
+            .map(|(ts, content)| (ts, content.trim().to_string()))
+            .filter(|(ts, content)| ts.is_some() || !content.is_empty())
+            .collect()
     }
 
     pub fn read_notepad(directory: &str) -> Option<String> {
@@ -427,6 +550,24 @@ impl NotepadHook {
 
 {}
 
+</notepad-priority>
+"#,
+            priority_context
+        ))
+    }
+
+    /// Priority context of a branch's notepad, formatted for re-injection
+    pub fn format_branch_notepad_context(directory: &str, branch: Option<&str>) -> Option<String> {
+        let content = Self::read_branch_notepad(directory, branch)?;
+        let priority_context = Self::extract_section(&content, PRIORITY_HEADER)?;
+
+        Some(format!(
+            r#"<notepad-priority>
+
+## Priority Context
+
+{}
+
 </notepad-priority>
 "#,
             priority_context
@@ -578,6 +719,64 @@ mod tests {
         assert!(formatted.contains("</notepad-priority>"));
     }
 
+    #[test]
+    fn test_fork_branch_copies_notepad() {
+        let dir = tempdir().unwrap();
+        let dir_path = dir.path().to_str().unwrap();
+
+        // Nothing to copy yet
+        assert!(NotepadHook::fork_branch(dir_path, None, "feature"));
+        assert!(NotepadHook::read_branch_notepad(dir_path, Some("feature")).is_none());
+
+        NotepadHook::set_priority_context(dir_path, "Use the v2 API", None);
+        assert!(NotepadHook::fork_branch(dir_path, None, "feature"));
+
+        let context =
+            NotepadHook::format_branch_notepad_context(dir_path, Some("feature")).unwrap();
+        assert!(context.contains("Use the v2 API"));
+
+        // Forks of forks copy from their parent branch
+        assert!(NotepadHook::fork_branch(
+            dir_path,
+            Some("feature"),
+            "feature-2"
+        ));
+        assert!(NotepadHook::read_branch_notepad(dir_path, Some("feature-2")).is_some());
+    }
+
+    #[test]
+    fn test_merge_branch_reconciles_entries() {
+        let dir = tempdir().unwrap();
+        let dir_path = dir.path().to_str().unwrap();
+
+        let root = format!(
+            "# Notepad\n\n{}\nUse the v2 API\n\n{}\n### 2026-01-01 10:00\nShared note\n\n{}\n",
+            PRIORITY_HEADER, WORKING_MEMORY_HEADER, MANUAL_HEADER
+        );
+        NotepadHook::init_notepad(dir_path);
+        fs::write(NotepadHook::get_notepad_path(dir_path), &root).unwrap();
+        NotepadHook::fork_branch(dir_path, None, "feature");
+
+        let branch = format!(
+            "# Notepad\n\n{}\nUse the v2 API\nTests need --release\n\n{}\n### 2026-01-01 10:00\nShared note\n\n### 2026-01-01 09:00\nBranch note\n\n{}\n",
+            PRIORITY_HEADER, WORKING_MEMORY_HEADER, MANUAL_HEADER
+        );
+        fs::write(
+            NotepadHook::get_branch_notepad_path(dir_path, Some("feature")),
+            branch,
+        )
+        .unwrap();
+
+        assert!(NotepadHook::merge_branch(dir_path, Some("feature"), None));
+
+        let priority = NotepadHook::get_priority_context(dir_path).unwrap();
+        assert_eq!(priority, "Use the v2 API\nTests need --release");
+
+        let memory = NotepadHook::get_working_memory(dir_path).unwrap();
+        assert_eq!(memory.matches("Shared note").count(), 1);
+        assert!(memory.find("Branch note").unwrap() < memory.find("Shared note").unwrap());
+    }
+
     #[test]
     fn test_extract_section() {
         let content = r#"# Notepad
@@ -457,15 +457,7 @@ fn run_shell_command(command: &str, cwd: Option<&str>) -> Result<ToolRunResult,
 }
 
 fn run_shell_raw(command: &str, cwd: Option<&str>) -> Result<(), String> {
-    let mut cmd = if cfg!(windows) {
-        let mut c = Command::new("cmd");
-        c.arg("/C").arg(command);
-        c
-    } else {
-        let mut c = Command::new("sh");
-        c.arg("-c").arg(command);
-        c
-    };
+    let mut cmd = uira_core::shell_command(command, None);
 
     if let Some(dir) = cwd {
         cmd.current_dir(dir);
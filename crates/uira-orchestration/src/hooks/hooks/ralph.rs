@@ -66,6 +66,7 @@ use std::path::{Path, PathBuf};
 use uira_core::UIRA_DIR;
 
 use super::super::hook::{Hook, HookContext, HookResult};
+use super::super::transcript::TranscriptReader;
 use super::super::types::{HookEvent, HookInput, HookOutput};
 use super::circuit_breaker::{CircuitBreakerConfig, CircuitBreakerState};
 use super::todo_continuation::TodoContinuationHook;
@@ -577,6 +578,24 @@ impl RalphHook {
         let _ = Self::write_progress(directory, &progress);
     }
 
+    /// Add the files the transcript's `Edit`/`Write` calls touched to the
+    /// progress file
+    pub fn record_transcript_files(directory: &str, transcript: &TranscriptReader) {
+        let files: Vec<&str> = transcript
+            .tool_calls()
+            .filter(|call| matches!(call.name.as_str(), "Edit" | "MultiEdit" | "Write"))
+            .filter_map(|call| call.input.get("file_path").and_then(|p| p.as_str()))
+            .collect();
+        if files.is_empty() {
+            return;
+        }
+        let mut progress = Self::read_progress(directory).unwrap_or_default();
+        for file in files {
+            progress.add_file(file);
+        }
+        let _ = Self::write_progress(directory, &progress);
+    }
+
     /// Clear progress file
     pub fn clear_progress(directory: &str) {
         let progress_file = Self::get_progress_file_path(directory);
@@ -1035,18 +1054,34 @@ impl Hook for RalphHook {
             )));
         }
 
+        let transcript = input.transcript();
+        if let Some(transcript) = &transcript {
+            Self::record_transcript_files(&context.directory, transcript);
+        }
+
         // Check for completion intent via transcript
-        if let Some(last_response) = input.get_last_assistant_response() {
+        if let Some(last_response) = transcript
+            .as_ref()
+            .and_then(|transcript| transcript.last_assistant_message())
+        {
             let has_promise =
-                Self::detect_completion_promise(&last_response, &state.completion_promise);
-            let has_exit_signal = Self::detect_exit_signal(&last_response);
+                Self::detect_completion_promise(last_response, &state.completion_promise);
+            let has_exit_signal = Self::detect_exit_signal(last_response);
 
             if has_promise || has_exit_signal {
-                let todo_result = TodoContinuationHook::check_incomplete_todos(
+                let mut todo_result = TodoContinuationHook::check_incomplete_todos(
                     input.session_id.as_deref(),
                     &context.directory,
                     None,
                 );
+                if todo_result.total == 0 {
+                    if let Some(from_transcript) = transcript
+                        .as_ref()
+                        .and_then(TodoContinuationHook::check_transcript_todos)
+                    {
+                        todo_result = from_transcript;
+                    }
+                }
 
                 let goals_result = Self::check_goals_from_config(&context.directory).await;
 
@@ -1057,7 +1092,7 @@ impl Hook for RalphHook {
                 };
 
                 let signals = Self::detect_completion_signals_with_goals(
-                    &last_response,
+                    last_response,
                     &state.completion_promise,
                     todo_counts,
                     goals_result.as_ref(),
@@ -1275,6 +1310,22 @@ EXIT_SIGNAL: true
         assert_eq!(progress.files_modified.len(), 2);
     }
 
+    #[test]
+    fn test_record_transcript_files() {
+        let temp = tempfile::tempdir().unwrap();
+        let directory = temp.path().to_string_lossy().to_string();
+        let transcript = TranscriptReader::parse(concat!(
+            r#"{"type":"assistant","message":{"content":["#,
+            r#"{"type":"tool_use","name":"Read","input":{"file_path":"src/a.rs"}},"#,
+            r#"{"type":"tool_use","name":"Edit","input":{"file_path":"src/b.rs"}},"#,
+            r#"{"type":"tool_use","name":"Write","input":{"file_path":"src/b.rs"}}]}}"#
+        ));
+
+        RalphHook::record_transcript_files(&directory, &transcript);
+        let progress = RalphHook::read_progress(&directory).unwrap();
+        assert_eq!(progress.files_modified, vec!["src/b.rs".to_string()]);
+    }
+
     #[test]
     fn test_ralph_progress_summary() {
         let mut progress = RalphProgress::default();
@@ -0,0 +1,532 @@
+//! Skill Usage Hook
+//!
+//! Records which skills are activated through slash commands, whether the
+//! session they ran in completed, and how many tokens they consumed. On plain
+//! prompts it suggests an installed skill whose name or description matches.
+
+use async_trait::async_trait;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uira_core::UIRA_DIR;
+
+use super::super::hook::{Hook, HookContext, HookResult};
+use super::super::types::{HookEvent, HookInput, HookOutput};
+use super::auto_slash_command::{
+    detect_slash_command, discover_all_commands, CommandInfo, CommandScope,
+    AUTO_SLASH_COMMAND_TAG_OPEN,
+};
+
+pub const HOOK_NAME: &str = "skill-usage";
+
+/// State file under `.uira/state/`
+pub const SKILL_USAGE_STATE_FILE: &str = "skill-usage.json";
+
+/// Minimum keyword score before a skill is suggested
+const MIN_SUGGESTION_SCORE: u32 = 2;
+
+/// Sessions tracked per map. `Stop` clears a session's entries, but sessions
+/// that never stop (crashes, killed gateways) would otherwise pile up.
+const MAX_TRACKED_SESSIONS: usize = 64;
+
+const STOPWORDS: &[&str] = &[
+    "the", "and", "for", "with", "this", "that", "from", "into", "your", "you", "are", "was",
+    "can", "please", "use", "using", "when", "what", "how", "will", "should", "could", "would",
+    "about", "skill", "help", "make", "want", "need",
+];
+
+/// Aggregated usage of a single skill
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillUsageRecord {
+    pub activations: u32,
+    pub completions: u32,
+    pub failures: u32,
+    pub total_tokens: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_used: Option<DateTime<Utc>>,
+}
+
+impl SkillUsageRecord {
+    /// Fraction of finished activations that completed successfully
+    pub fn completion_rate(&self) -> Option<f64> {
+        let finished = self.completions + self.failures;
+        (finished > 0).then(|| self.completions as f64 / finished as f64)
+    }
+
+    /// Average tokens spent per activation
+    pub fn average_tokens(&self) -> Option<u64> {
+        (self.activations > 0).then(|| self.total_tokens / self.activations as u64)
+    }
+}
+
+/// A skill currently running in a session
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ActiveSkillUsage {
+    pub skill: String,
+    pub started_at: DateTime<Utc>,
+    pub tokens: u64,
+}
+
+/// Persisted skill usage analytics for a project
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkillUsageState {
+    #[serde(default)]
+    pub skills: HashMap<String, SkillUsageRecord>,
+    /// Active skill per session id
+    #[serde(default)]
+    pub active: HashMap<String, ActiveSkillUsage>,
+    /// Skills already suggested per session id, so each is offered once
+    #[serde(default)]
+    pub suggested: HashMap<String, Vec<String>>,
+}
+
+impl SkillUsageState {
+    /// Record a skill activation, finishing any skill still active in the session
+    pub fn activate(&mut self, session_id: &str, skill: &str) {
+        self.finish(session_id, false);
+        let now = Utc::now();
+        let record = self.skills.entry(skill.to_string()).or_default();
+        record.activations += 1;
+        record.last_used = Some(now);
+        self.active.insert(
+            session_id.to_string(),
+            ActiveSkillUsage {
+                skill: skill.to_string(),
+                started_at: now,
+                tokens: 0,
+            },
+        );
+        self.prune();
+    }
+
+    /// Attribute token usage to the session's active skill
+    pub fn add_tokens(&mut self, session_id: &str, tokens: u64) {
+        let Some(active) = self.active.get_mut(session_id) else {
+            return;
+        };
+        active.tokens += tokens;
+        if let Some(record) = self.skills.get_mut(&active.skill) {
+            record.total_tokens += tokens;
+        }
+    }
+
+    /// Finish the session's active skill, counting it as completed or failed
+    pub fn finish(&mut self, session_id: &str, completed: bool) -> Option<ActiveSkillUsage> {
+        let active = self.active.remove(session_id)?;
+        if let Some(record) = self.skills.get_mut(&active.skill) {
+            if completed {
+                record.completions += 1;
+            } else {
+                record.failures += 1;
+            }
+        }
+        Some(active)
+    }
+
+    fn mark_suggested(&mut self, session_id: &str, skill: &str) -> bool {
+        let suggested = self.suggested.entry(session_id.to_string()).or_default();
+        if suggested.iter().any(|s| s == skill) {
+            return false;
+        }
+        suggested.push(skill.to_string());
+        self.prune();
+        true
+    }
+
+    /// Keep at most [`MAX_TRACKED_SESSIONS`] sessions per map. The oldest
+    /// active skills are counted as failed; suggestion lists of sessions with
+    /// no active skill are dropped first.
+    fn prune(&mut self) {
+        while self.active.len() > MAX_TRACKED_SESSIONS {
+            let Some(oldest) = self
+                .active
+                .iter()
+                .min_by_key(|(_, active)| active.started_at)
+                .map(|(session_id, _)| session_id.clone())
+            else {
+                break;
+            };
+            self.finish(&oldest, false);
+        }
+
+        let excess = self.suggested.len().saturating_sub(MAX_TRACKED_SESSIONS);
+        if excess > 0 {
+            let mut sessions: Vec<String> = self.suggested.keys().cloned().collect();
+            sessions.sort_by_key(|session_id| self.active.contains_key(session_id));
+            for session_id in sessions.into_iter().take(excess) {
+                self.suggested.remove(&session_id);
+            }
+        }
+    }
+}
+
+fn state_path(directory: &str) -> PathBuf {
+    Path::new(directory)
+        .join(UIRA_DIR)
+        .join("state")
+        .join(SKILL_USAGE_STATE_FILE)
+}
+
+pub fn load_skill_usage_state(directory: &str) -> SkillUsageState {
+    fs::read_to_string(state_path(directory))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+pub fn save_skill_usage_state(directory: &str, state: &SkillUsageState) -> bool {
+    let path = state_path(directory);
+    if let Some(parent) = path.parent() {
+        if fs::create_dir_all(parent).is_err() {
+            return false;
+        }
+    }
+    match serde_json::to_string_pretty(state) {
+        Ok(content) => fs::write(path, content).is_ok(),
+        Err(_) => false,
+    }
+}
+
+/// A skill suggested for a prompt
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkillSuggestion {
+    pub skill: String,
+    pub score: u32,
+    /// Prompt keywords that matched the skill
+    pub matched: Vec<String>,
+}
+
+fn keywords(text: &str) -> HashSet<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .map(|w| w.to_lowercase())
+        .filter(|w| w.len() >= 3 && !STOPWORDS.contains(&w.as_str()))
+        .map(|w| stem(&w).to_string())
+        .collect()
+}
+
+/// Strip common English suffixes so "releases" matches "release"
+fn stem(word: &str) -> &str {
+    for suffix in ["ing", "ed", "s"] {
+        if let Some(stripped) = word.strip_suffix(suffix) {
+            if stripped.len() >= 3 {
+                return stripped;
+            }
+        }
+    }
+    word
+}
+
+/// Rank skills by keyword overlap with the prompt
+///
+/// Matches in the skill name count double. Ties are broken by past
+/// completion rate so skills that tend to finish are preferred.
+pub fn suggest_skill(
+    prompt: &str,
+    skills: &[CommandInfo],
+    usage: &SkillUsageState,
+) -> Option<SkillSuggestion> {
+    let prompt_words = keywords(prompt);
+    if prompt_words.is_empty() {
+        return None;
+    }
+
+    skills
+        .iter()
+        .filter_map(|skill| {
+            let name_words = keywords(&skill.name);
+            let description_words = keywords(&skill.metadata.description);
+
+            let mut matched: Vec<String> = prompt_words
+                .iter()
+                .filter(|w| name_words.contains(*w) || description_words.contains(*w))
+                .cloned()
+                .collect();
+            matched.sort();
+
+            let score: u32 = matched
+                .iter()
+                .map(|w| if name_words.contains(w) { 2 } else { 1 })
+                .sum();
+            (score >= MIN_SUGGESTION_SCORE).then(|| SkillSuggestion {
+                skill: skill.name.clone(),
+                score,
+                matched,
+            })
+        })
+        .max_by(|a, b| {
+            let rate = |s: &SkillSuggestion| {
+                usage
+                    .skills
+                    .get(&s.skill)
+                    .and_then(|r| r.completion_rate())
+                    .unwrap_or(0.0)
+            };
+            a.score
+                .cmp(&b.score)
+                .then(rate(a).total_cmp(&rate(b)))
+                .then(b.skill.cmp(&a.skill))
+        })
+}
+
+fn format_suggestion(suggestion: &SkillSuggestion, usage: &SkillUsageState) -> String {
+    let mut message = format!(
+        "[SKILL SUGGESTION] This looks like {} work — try /uira:{}",
+        suggestion.matched.join("/"),
+        suggestion.skill
+    );
+    if let Some(record) = usage.skills.get(&suggestion.skill) {
+        if let Some(rate) = record.completion_rate() {
+            message.push_str(&format!(
+                " (used {} times, {:.0}% completed)",
+                record.activations,
+                rate * 100.0
+            ));
+        }
+    }
+    message
+}
+
+/// Resolve a slash command name (`uira:name` or `name`) to an installed skill
+fn resolve_skill<'a>(command: &str, skills: &'a [CommandInfo]) -> Option<&'a CommandInfo> {
+    let name = command.strip_prefix("uira:").unwrap_or(command);
+    skills.iter().find(|s| s.name.eq_ignore_ascii_case(name))
+}
+
+fn turn_tokens(input: &HookInput) -> u64 {
+    let Some(usage) = input.extra.get("usage") else {
+        return 0;
+    };
+    ["input_tokens", "output_tokens"]
+        .iter()
+        .filter_map(|key| usage.get(key).and_then(|v| v.as_u64()))
+        .sum()
+}
+
+pub struct SkillUsageHook;
+
+impl SkillUsageHook {
+    pub fn new() -> Self {
+        Self
+    }
+
+    fn installed_skills(directory: &str) -> Vec<CommandInfo> {
+        discover_all_commands(Path::new(directory))
+            .into_iter()
+            .filter(|c| c.scope == CommandScope::Skill)
+            .collect()
+    }
+
+    fn on_prompt(input: &HookInput, context: &HookContext, session_id: &str) -> HookOutput {
+        let prompt = input.get_prompt_text();
+        if prompt.trim().is_empty() || prompt.contains(AUTO_SLASH_COMMAND_TAG_OPEN) {
+            return HookOutput::pass();
+        }
+
+        let skills = Self::installed_skills(&context.directory);
+        if skills.is_empty() {
+            return HookOutput::pass();
+        }
+        let mut state = load_skill_usage_state(&context.directory);
+
+        if let Some(parsed) = detect_slash_command(&prompt) {
+            if let Some(skill) = resolve_skill(&parsed.command, &skills) {
+                state.activate(session_id, &skill.name);
+                save_skill_usage_state(&context.directory, &state);
+            }
+            return HookOutput::pass();
+        }
+
+        let Some(suggestion) = suggest_skill(&prompt, &skills, &state) else {
+            return HookOutput::pass();
+        };
+        if !state.mark_suggested(session_id, &suggestion.skill) {
+            return HookOutput::pass();
+        }
+        save_skill_usage_state(&context.directory, &state);
+        HookOutput::continue_with_message(format_suggestion(&suggestion, &state))
+    }
+}
+
+impl Default for SkillUsageHook {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Hook for SkillUsageHook {
+    fn name(&self) -> &str {
+        HOOK_NAME
+    }
+
+    fn events(&self) -> &[HookEvent] {
+        &[
+            HookEvent::UserPromptSubmit,
+            HookEvent::AssistantTurnComplete,
+            HookEvent::Stop,
+        ]
+    }
+
+    async fn execute(
+        &self,
+        event: HookEvent,
+        input: &HookInput,
+        context: &HookContext,
+    ) -> HookResult {
+        let Some(session_id) = input
+            .session_id
+            .as_deref()
+            .or(context.session_id.as_deref())
+        else {
+            return Ok(HookOutput::pass());
+        };
+
+        match event {
+            HookEvent::UserPromptSubmit => Ok(Self::on_prompt(input, context, session_id)),
+            HookEvent::AssistantTurnComplete => {
+                let tokens = turn_tokens(input);
+                if tokens > 0 {
+                    let mut state = load_skill_usage_state(&context.directory);
+                    if state.active.contains_key(session_id) {
+                        state.add_tokens(session_id, tokens);
+                        save_skill_usage_state(&context.directory, &state);
+                    }
+                }
+                Ok(HookOutput::pass())
+            }
+            HookEvent::Stop => {
+                let mut state = load_skill_usage_state(&context.directory);
+                let completed = input.stop_reason.as_deref() == Some("completed");
+                let finished = state.finish(session_id, completed).is_some();
+                let suggested = state.suggested.remove(session_id).is_some();
+                if finished || suggested {
+                    save_skill_usage_state(&context.directory, &state);
+                }
+                Ok(HookOutput::pass())
+            }
+            _ => Ok(HookOutput::pass()),
+        }
+    }
+
+    fn priority(&self) -> i32 {
+        -10
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::hooks::auto_slash_command::CommandMetadata;
+
+    fn skill(name: &str, description: &str) -> CommandInfo {
+        CommandInfo {
+            name: name.to_string(),
+            path: None,
+            metadata: CommandMetadata {
+                name: name.to_string(),
+                description: description.to_string(),
+                ..Default::default()
+            },
+            content: None,
+            scope: CommandScope::Skill,
+        }
+    }
+
+    #[test]
+    fn test_suggests_matching_skill() {
+        let skills = vec![
+            skill("release-notes", "Draft release notes from merged PRs"),
+            skill("docs-writer", "Write and update documentation"),
+        ];
+        let usage = SkillUsageState::default();
+
+        let suggestion =
+            suggest_skill("Let's cut the v2.1 release today", &skills, &usage).unwrap();
+        assert_eq!(suggestion.skill, "release-notes");
+        assert_eq!(suggestion.matched, vec!["release"]);
+        assert!(format_suggestion(&suggestion, &usage).contains("try /uira:release-notes"));
+
+        assert!(suggest_skill("fix the flaky login test", &skills, &usage).is_none());
+    }
+
+    #[test]
+    fn test_tracks_activation_tokens_and_completion() {
+        let mut state = SkillUsageState::default();
+        state.activate("s1", "release-notes");
+        state.add_tokens("s1", 1200);
+        state.finish("s1", true);
+
+        state.activate("s2", "release-notes");
+        state.add_tokens("s2", 800);
+        state.activate("s2", "docs-writer");
+
+        let record = &state.skills["release-notes"];
+        assert_eq!(record.activations, 2);
+        assert_eq!(record.completions, 1);
+        assert_eq!(record.failures, 1);
+        assert_eq!(record.total_tokens, 2000);
+        assert_eq!(record.completion_rate(), Some(0.5));
+        assert_eq!(record.average_tokens(), Some(1000));
+        assert_eq!(state.active["s2"].skill, "docs-writer");
+    }
+
+    #[test]
+    fn test_caps_tracked_sessions() {
+        let mut state = SkillUsageState::default();
+        for i in 0..MAX_TRACKED_SESSIONS + 10 {
+            let session_id = format!("s{i}");
+            state.mark_suggested(&session_id, "docs-writer");
+            state.activate(&session_id, "release-notes");
+        }
+
+        assert_eq!(state.active.len(), MAX_TRACKED_SESSIONS);
+        assert_eq!(state.suggested.len(), MAX_TRACKED_SESSIONS);
+        assert_eq!(state.skills["release-notes"].failures, 10);
+    }
+
+    #[tokio::test]
+    async fn test_hook_records_usage_across_events() {
+        let dir = tempfile::tempdir().unwrap();
+        let directory = dir.path().to_string_lossy().to_string();
+        let hook = SkillUsageHook::new();
+        let context = HookContext::new(Some("s1".to_string()), directory.clone(), None);
+
+        let mut state = SkillUsageState::default();
+        state.activate("s1", "release-notes");
+        save_skill_usage_state(&directory, &state);
+
+        let mut extra = HashMap::new();
+        extra.insert(
+            "usage".to_string(),
+            serde_json::json!({"input_tokens": 300, "output_tokens": 200}),
+        );
+        let turn = HookInput {
+            session_id: Some("s1".to_string()),
+            extra,
+            ..Default::default()
+        };
+        hook.execute(HookEvent::AssistantTurnComplete, &turn, &context)
+            .await
+            .unwrap();
+
+        let stop = HookInput {
+            session_id: Some("s1".to_string()),
+            stop_reason: Some("completed".to_string()),
+            ..Default::default()
+        };
+        hook.execute(HookEvent::Stop, &stop, &context)
+            .await
+            .unwrap();
+
+        let state = load_skill_usage_state(&directory);
+        let record = &state.skills["release-notes"];
+        assert_eq!(record.total_tokens, 500);
+        assert_eq!(record.completions, 1);
+        assert!(state.active.is_empty());
+    }
+}
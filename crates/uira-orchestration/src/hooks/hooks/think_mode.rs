@@ -144,6 +144,16 @@ pub struct ThinkModeState {
     pub model_id: Option<String>,
 }
 
+/// How much reasoning a prompt asked for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ThinkLevel {
+    /// "think" and its multilingual equivalents
+    Think,
+    /// "ultrathink": the provider's maximum reasoning settings
+    Ultrathink,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelRef {
     pub provider_id: String,
@@ -173,6 +183,73 @@ pub enum ThinkingConfig {
     },
 }
 
+impl ThinkingConfig {
+    /// Scale a maximum config down to the given level. Ultrathink keeps the
+    /// maximum; think halves token budgets and asks for medium effort.
+    pub fn for_level(&self, level: ThinkLevel) -> ThinkingConfig {
+        let mut config = self.clone();
+        if level == ThinkLevel::Ultrathink {
+            return config;
+        }
+        match &mut config {
+            ThinkingConfig::Anthropic { thinking, .. } => {
+                thinking.budget_tokens /= 2;
+            }
+            ThinkingConfig::AmazonBedrock {
+                reasoning_config, ..
+            } => {
+                reasoning_config.budget_tokens /= 2;
+            }
+            ThinkingConfig::Google { provider_options } => {
+                provider_options.google.thinking_config.thinking_level = "MEDIUM".to_string();
+            }
+            ThinkingConfig::OpenAI { reasoning_effort } => {
+                *reasoning_effort = "medium".to_string();
+            }
+        }
+        config
+    }
+
+    /// Thinking token budget, with Gemini thinking levels mapped to a budget
+    pub fn budget_tokens(&self) -> Option<u32> {
+        match self {
+            ThinkingConfig::Anthropic { thinking, .. } => Some(thinking.budget_tokens),
+            ThinkingConfig::AmazonBedrock {
+                reasoning_config, ..
+            } => Some(reasoning_config.budget_tokens),
+            ThinkingConfig::Google { provider_options } => {
+                match provider_options
+                    .google
+                    .thinking_config
+                    .thinking_level
+                    .as_str()
+                {
+                    "HIGH" => Some(32768),
+                    "MEDIUM" => Some(16384),
+                    _ => Some(8192),
+                }
+            }
+            ThinkingConfig::OpenAI { .. } => None,
+        }
+    }
+
+    /// Output token limit the budget needs, where the provider has one
+    pub fn max_tokens(&self) -> Option<u32> {
+        match self {
+            ThinkingConfig::Anthropic { max_tokens, .. }
+            | ThinkingConfig::AmazonBedrock { max_tokens, .. } => Some(*max_tokens),
+            _ => None,
+        }
+    }
+
+    pub fn reasoning_effort(&self) -> Option<&str> {
+        match self {
+            ThinkingConfig::OpenAI { reasoning_effort } => Some(reasoning_effort),
+            _ => None,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct AnthropicThinking {
     #[serde(rename = "type")]
@@ -323,6 +400,16 @@ impl ThinkModeHook {
         ULTRATHINK_PATTERN.is_match(&text_without_code)
     }
 
+    pub fn detect_think_level(text: &str) -> Option<ThinkLevel> {
+        if Self::detect_ultrathink_keyword(text) {
+            Some(ThinkLevel::Ultrathink)
+        } else if Self::detect_think_keyword(text) {
+            Some(ThinkLevel::Think)
+        } else {
+            None
+        }
+    }
+
     pub fn extract_prompt_text(parts: &[MessagePart]) -> String {
         parts
             .iter()
@@ -369,6 +456,9 @@ impl ThinkModeHook {
     }
 
     fn resolve_provider(provider_id: &str, model_id: &str) -> String {
+        if provider_id == "gemini" {
+            return "google".to_string();
+        }
         if provider_id == "github-copilot" {
             let model_lower = model_id.to_lowercase();
             if model_lower.contains("claude") {
@@ -415,6 +505,15 @@ impl ThinkModeHook {
         }
     }
 
+    /// Provider-native thinking settings for a think level
+    pub fn get_thinking_config_for_level(
+        provider_id: &str,
+        model_id: &str,
+        level: ThinkLevel,
+    ) -> Option<ThinkingConfig> {
+        Self::get_thinking_config(provider_id, model_id).map(|config| config.for_level(level))
+    }
+
     pub fn get_claude_thinking_config(budget_tokens: Option<u32>) -> ThinkingConfig {
         ThinkingConfig::Anthropic {
             thinking: AnthropicThinking {
@@ -651,6 +750,53 @@ mod tests {
         assert!(config.is_none());
     }
 
+    #[test]
+    fn test_detect_think_level() {
+        assert_eq!(
+            ThinkModeHook::detect_think_level("ultrathink this"),
+            Some(ThinkLevel::Ultrathink)
+        );
+        assert_eq!(
+            ThinkModeHook::detect_think_level("think it through"),
+            Some(ThinkLevel::Think)
+        );
+        assert_eq!(ThinkModeHook::detect_think_level("just do it"), None);
+    }
+
+    #[test]
+    fn test_thinking_config_for_level() {
+        let ultra = ThinkModeHook::get_thinking_config_for_level(
+            "anthropic",
+            "claude-sonnet-4-5",
+            ThinkLevel::Ultrathink,
+        )
+        .unwrap();
+        assert_eq!(ultra.budget_tokens(), Some(64000));
+        assert_eq!(ultra.max_tokens(), Some(128000));
+
+        let think = ThinkModeHook::get_thinking_config_for_level(
+            "anthropic",
+            "claude-sonnet-4-5",
+            ThinkLevel::Think,
+        )
+        .unwrap();
+        assert_eq!(think.budget_tokens(), Some(32000));
+
+        let openai =
+            ThinkModeHook::get_thinking_config_for_level("openai", "gpt-5", ThinkLevel::Think)
+                .unwrap();
+        assert_eq!(openai.reasoning_effort(), Some("medium"));
+        assert_eq!(openai.budget_tokens(), None);
+
+        let gemini = ThinkModeHook::get_thinking_config_for_level(
+            "gemini",
+            "gemini-2.5-pro",
+            ThinkLevel::Ultrathink,
+        )
+        .unwrap();
+        assert_eq!(gemini.budget_tokens(), Some(32768));
+    }
+
     #[test]
     fn test_resolve_github_copilot() {
         assert_eq!(
@@ -5,8 +5,9 @@ use std::fs;
 use std::path::{Path, PathBuf};
 
 use super::super::hook::{Hook, HookContext, HookResult};
+use super::super::transcript::TranscriptReader;
 use super::super::types::{HookEvent, HookInput, HookOutput};
-use uira_core::{TodoItem, TodoPriority, TodoStatus, UIRA_DIR};
+use uira_core::{actionable_todos, TodoItem, TodoPriority, TodoStatus, UIRA_DIR};
 
 /// Lenient deserialization struct for backward-compatible parsing of todo files
 /// written by Claude Code (`~/.claude/todos/`) which use optional fields.
@@ -45,6 +46,7 @@ impl RawTodo {
                 Some("low") => TodoPriority::Low,
                 _ => TodoPriority::Medium,
             },
+            ..Default::default()
         }
     }
 }
@@ -52,6 +54,7 @@ impl RawTodo {
 #[derive(Debug, Clone)]
 pub struct IncompleteTodosResult {
     pub count: usize,
+    /// Incomplete todos, unblocked ones first in the order they should be worked on
     pub todos: Vec<TodoItem>,
     pub total: usize,
 }
@@ -186,7 +189,6 @@ impl TodoContinuationHook {
         let paths = Self::get_todo_file_paths(session_id, directory);
         let mut seen_contents: HashSet<String> = HashSet::new();
         let mut all_todos: Vec<TodoItem> = Vec::new();
-        let mut incomplete_todos: Vec<TodoItem> = Vec::new();
 
         for path in paths {
             if !path.exists() {
@@ -201,14 +203,44 @@ impl TodoContinuationHook {
                     continue;
                 }
                 seen_contents.insert(key);
-
-                if matches!(todo.status, TodoStatus::Pending | TodoStatus::InProgress) {
-                    incomplete_todos.push(todo.clone());
-                }
                 all_todos.push(todo);
             }
         }
 
+        Self::summarize(all_todos)
+    }
+
+    /// Incomplete todos from the transcript's most recent `TodoWrite` call,
+    /// for sessions whose todo list was never written to disk
+    pub fn check_transcript_todos(transcript: &TranscriptReader) -> Option<IncompleteTodosResult> {
+        let todos = transcript.last_tool_input("TodoWrite")?.get("todos")?;
+        let raw: Vec<RawTodo> = serde_json::from_value(todos.clone()).ok()?;
+        Some(Self::summarize(
+            raw.into_iter()
+                .enumerate()
+                .map(|(i, t)| t.into_item(i))
+                .collect(),
+        ))
+    }
+
+    fn summarize(all_todos: Vec<TodoItem>) -> IncompleteTodosResult {
+        let mut incomplete_todos: Vec<TodoItem> = all_todos
+            .iter()
+            .filter(|t| matches!(t.status, TodoStatus::Pending | TodoStatus::InProgress))
+            .cloned()
+            .collect();
+
+        let actionable: Vec<&str> = actionable_todos(&all_todos)
+            .into_iter()
+            .map(|t| t.id.as_str())
+            .collect();
+        incomplete_todos.sort_by_key(|t| {
+            actionable
+                .iter()
+                .position(|id| *id == t.id)
+                .unwrap_or(actionable.len())
+        });
+
         IncompleteTodosResult {
             count: incomplete_todos.len(),
             todos: incomplete_todos,
@@ -244,19 +276,32 @@ impl Hook for TodoContinuationHook {
             user_requested: input.user_requested,
         };
 
-        let result = Self::check_incomplete_todos(
+        let mut result = Self::check_incomplete_todos(
             input.session_id.as_deref(),
             &context.directory,
             Some(&stop_context),
         );
+        if result.total == 0 && !stop_context.is_user_abort() {
+            if let Some(from_transcript) = input
+                .transcript()
+                .and_then(|transcript| Self::check_transcript_todos(&transcript))
+            {
+                result = from_transcript;
+            }
+        }
 
         if result.count == 0 {
             return Ok(HookOutput::pass());
         }
 
+        let next = result
+            .todos
+            .first()
+            .map(|todo| format!(" Next: {}.", todo.content))
+            .unwrap_or_default();
         Ok(HookOutput::continue_with_message(format!(
-            "[TODO CONTINUATION] {} pending task(s) remain. Continue execution until all todos are complete.",
-            result.count
+            "[TODO CONTINUATION] {} pending task(s) remain.{} Continue execution until all todos are complete.",
+            result.count, next
         )))
     }
 
@@ -277,6 +322,7 @@ mod tests {
             content: content.to_string(),
             status,
             priority: TodoPriority::Medium,
+            ..Default::default()
         }
     }
 
@@ -352,6 +398,51 @@ mod tests {
             .all(|p| p.to_string_lossy().contains("sid-123")));
     }
 
+    #[test]
+    fn test_incomplete_todos_list_unblocked_first() {
+        let temp = tempdir().unwrap();
+        let todo_dir = temp.path().join(".uira");
+        std::fs::create_dir_all(&todo_dir).unwrap();
+
+        let mut deploy = make_item("1", "Deploy", TodoStatus::Pending);
+        deploy.depends_on = vec!["2".to_string()];
+        let build = make_item("2", "Build", TodoStatus::Pending);
+        std::fs::write(
+            todo_dir.join("todos.json"),
+            serde_json::to_string(&vec![deploy, build]).unwrap(),
+        )
+        .unwrap();
+
+        let result = TodoContinuationHook::check_incomplete_todos(
+            None,
+            &temp.path().to_string_lossy(),
+            None,
+        );
+        assert_eq!(result.count, 2);
+        assert_eq!(result.todos[0].content, "Build");
+        assert_eq!(result.todos[1].content, "Deploy");
+    }
+
+    #[test]
+    fn test_transcript_todos_use_latest_todo_write() {
+        let transcript = TranscriptReader::parse(concat!(
+            r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"TodoWrite","input":{"todos":["#,
+            r#"{"content":"Build","status":"pending"}]}}]}}"#,
+            "\n",
+            r#"{"type":"assistant","message":{"content":[{"type":"tool_use","name":"TodoWrite","input":{"todos":["#,
+            r#"{"content":"Build","status":"completed"},{"content":"Deploy","status":"in_progress"}]}}]}}"#
+        ));
+
+        let result = TodoContinuationHook::check_transcript_todos(&transcript).unwrap();
+        assert_eq!(result.total, 2);
+        assert_eq!(result.count, 1);
+        assert_eq!(result.todos[0].content, "Deploy");
+
+        assert!(
+            TodoContinuationHook::check_transcript_todos(&TranscriptReader::default()).is_none()
+        );
+    }
+
     #[tokio::test]
     async fn test_hook_emits_message_for_incomplete_todos() {
         let temp = tempdir().unwrap();
@@ -15,6 +15,8 @@ use uira_core::UIRA_DIR;
 
 use super::super::hook::{Hook, HookContext, HookResult};
 use super::super::types::{HookEvent, HookInput, HookOutput};
+use super::orchestrator_constants::is_write_edit_tool;
+use crate::tools::workspace;
 
 /// Configuration options for ultrapilot behavior
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -138,6 +140,17 @@ pub struct FileOwnership {
     pub conflicts: Vec<String>,
 }
 
+/// Outcome of checking whether a worker may modify a file
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OwnershipCheck {
+    /// The worker owns the file, or nobody owns it yet
+    Allowed,
+    /// The file is shared and only the coordinator may modify it
+    SharedFile,
+    /// The file is owned by a different worker
+    OwnedByOther { owner: String },
+}
+
 /// Complete ultrapilot state
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UltrapilotState {
@@ -680,6 +693,139 @@ impl UltrapilotHook {
         Self::write_state(directory, &state)
     }
 
+    /// Normalize a tool-supplied path to the project-relative form used in ownership maps.
+    /// `.` and `..` are folded lexically, and absolute paths may go through the
+    /// project root or its symlink-resolved form.
+    fn normalize_path(directory: &str, file_path: &str) -> String {
+        let root = workspace::normalize_path(Path::new(directory));
+        let path = workspace::normalize_path(&root.join(file_path.replace('\\', "/")));
+        let canonical_root = fs::canonicalize(directory).ok();
+        let relative = path
+            .strip_prefix(&root)
+            .ok()
+            .or_else(|| {
+                let canonical_root = canonical_root.as_deref()?;
+                path.strip_prefix(canonical_root).ok()
+            })
+            .unwrap_or(&path);
+        relative.to_string_lossy().replace('\\', "/")
+    }
+
+    /// Find the worker bound to a session (matched by worker ID or delegated task ID)
+    pub fn find_worker_for_session<'a>(
+        state: &'a UltrapilotState,
+        session_id: &str,
+    ) -> Option<&'a WorkerState> {
+        state
+            .workers
+            .iter()
+            .find(|w| w.id == session_id || w.task_id.as_deref() == Some(session_id))
+    }
+
+    /// Check whether a worker may modify a file according to the ownership map
+    pub fn check_file_access(
+        state: &UltrapilotState,
+        worker_id: &str,
+        file_path: &str,
+    ) -> OwnershipCheck {
+        let file_path = file_path.to_string();
+
+        if state.ownership.coordinator.contains(&file_path) {
+            return OwnershipCheck::SharedFile;
+        }
+
+        for (id, files) in &state.ownership.workers {
+            if id != worker_id && files.contains(&file_path) {
+                return OwnershipCheck::OwnedByOther { owner: id.clone() };
+            }
+        }
+
+        OwnershipCheck::Allowed
+    }
+
+    /// Build the deny message returned to a worker that touched a file it does not own
+    fn ownership_violation_message(
+        worker: &WorkerState,
+        file_path: &str,
+        check: &OwnershipCheck,
+    ) -> String {
+        let owner = match check {
+            OwnershipCheck::SharedFile => "the ultrapilot coordinator (shared file)".to_string(),
+            OwnershipCheck::OwnedByOther { owner } => format!("worker {}", owner),
+            OwnershipCheck::Allowed => return String::new(),
+        };
+
+        let owned = if worker.owned_files.is_empty() {
+            "(none assigned)".to_string()
+        } else {
+            worker.owned_files.join(", ")
+        };
+
+        format!(
+            "[ULTRAPILOT FILE OWNERSHIP VIOLATION]\n\n\
+             Worker {} attempted to modify `{}`, which is owned by {}.\n\n\
+             Your owned files: {}\n\n\
+             Do NOT modify this file. Instead:\n\
+             - Keep your changes within the files you own\n\
+             - Describe the change this file needs in your final report so the coordinator can apply it during integration",
+            worker.id, file_path, owner, owned
+        )
+    }
+
+    /// Enforce file ownership for Write/Edit calls made by ultrapilot workers
+    fn process_pre_tool(input: &HookInput, context: &HookContext) -> HookOutput {
+        let Some(tool_name) = input.tool_name.as_deref() else {
+            return HookOutput::pass();
+        };
+        if !is_write_edit_tool(tool_name) {
+            return HookOutput::pass();
+        }
+
+        let Some(file_path) = input.tool_input.as_ref().and_then(|tool_input| {
+            tool_input
+                .get("file_path")
+                .or_else(|| tool_input.get("filePath"))
+                .or_else(|| tool_input.get("path"))
+                .and_then(|v| v.as_str())
+        }) else {
+            return HookOutput::pass();
+        };
+
+        let Some(session_id) = input
+            .session_id
+            .as_deref()
+            .or(context.session_id.as_deref())
+        else {
+            return HookOutput::pass();
+        };
+
+        let directory = &context.directory;
+        let Some(state) = Self::read_state(directory).filter(|s| s.active) else {
+            return HookOutput::pass();
+        };
+
+        // Only workers are restricted; the coordinator session manages shared files
+        let Some(worker) = Self::find_worker_for_session(&state, session_id) else {
+            return HookOutput::pass();
+        };
+
+        let file_path = Self::normalize_path(directory, file_path);
+        let check = Self::check_file_access(&state, &worker.id, &file_path);
+
+        match check {
+            OwnershipCheck::Allowed => HookOutput::pass(),
+            OwnershipCheck::OwnedByOther { .. } => {
+                Self::record_conflict(directory, &file_path);
+                HookOutput::block_with_reason(Self::ownership_violation_message(
+                    worker, &file_path, &check,
+                ))
+            }
+            OwnershipCheck::SharedFile => HookOutput::block_with_reason(
+                Self::ownership_violation_message(worker, &file_path, &check),
+            ),
+        }
+    }
+
     /// Handle shared files that multiple workers might need to access
     pub fn handle_shared_files(directory: &str, files: Vec<String>) -> bool {
         let mut state = match Self::read_state(directory) {
@@ -711,17 +857,24 @@ impl Hook for UltrapilotHook {
     }
 
     fn events(&self) -> &[HookEvent] {
-        &[HookEvent::UserPromptSubmit, HookEvent::Stop]
+        &[
+            HookEvent::UserPromptSubmit,
+            HookEvent::Stop,
+            HookEvent::PreToolUse,
+        ]
     }
 
     async fn execute(
         &self,
-        _event: HookEvent,
-        _input: &HookInput,
-        _context: &HookContext,
+        event: HookEvent,
+        input: &HookInput,
+        context: &HookContext,
     ) -> HookResult {
-        // Process ultrapilot commands
-        Ok(HookOutput::pass())
+        match event {
+            HookEvent::PreToolUse => Ok(Self::process_pre_tool(input, context)),
+            // Process ultrapilot commands
+            _ => Ok(HookOutput::pass()),
+        }
     }
 }
 
@@ -870,4 +1023,177 @@ mod tests {
         assert!(summary.contains("worker-2"));
         assert!(summary.contains("conflict.rs"));
     }
+
+    fn worker(id: &str, task_id: Option<&str>, owned: &[&str]) -> WorkerState {
+        WorkerState {
+            id: id.to_string(),
+            index: 0,
+            task: format!("{} task", id),
+            owned_files: owned.iter().map(|f| f.to_string()).collect(),
+            status: WorkerStatus::Running,
+            task_id: task_id.map(|t| t.to_string()),
+            started_at: None,
+            completed_at: None,
+            error: None,
+            files_created: Vec::new(),
+            files_modified: Vec::new(),
+        }
+    }
+
+    fn write_input(session_id: &str, file_path: &str) -> HookInput {
+        HookInput {
+            session_id: Some(session_id.to_string()),
+            prompt: None,
+            message: None,
+            parts: None,
+            tool_name: Some("Write".to_string()),
+            tool_input: Some(serde_json::json!({ "file_path": file_path, "content": "x" })),
+            tool_output: None,
+            directory: None,
+            stop_reason: None,
+            user_requested: None,
+            transcript_path: None,
+            extra: HashMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_check_file_access() {
+        let dir = tempfile::tempdir().unwrap();
+        let directory = dir.path().to_str().unwrap();
+        UltrapilotHook::init(directory, "task", Vec::new(), None, None);
+        UltrapilotHook::add_worker(directory, worker("worker-1", None, &["src/a.rs"]));
+        UltrapilotHook::add_worker(directory, worker("worker-2", None, &["src/b.rs"]));
+        let state = UltrapilotHook::read_state(directory).unwrap();
+
+        assert_eq!(
+            UltrapilotHook::check_file_access(&state, "worker-1", "src/a.rs"),
+            OwnershipCheck::Allowed
+        );
+        assert_eq!(
+            UltrapilotHook::check_file_access(&state, "worker-1", "src/new.rs"),
+            OwnershipCheck::Allowed
+        );
+        assert_eq!(
+            UltrapilotHook::check_file_access(&state, "worker-1", "src/b.rs"),
+            OwnershipCheck::OwnedByOther {
+                owner: "worker-2".to_string()
+            }
+        );
+        assert_eq!(
+            UltrapilotHook::check_file_access(&state, "worker-1", "Cargo.toml"),
+            OwnershipCheck::SharedFile
+        );
+    }
+
+    #[tokio::test]
+    async fn test_pre_tool_use_denies_foreign_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let directory = dir.path().to_str().unwrap();
+        UltrapilotHook::init(
+            directory,
+            "task",
+            Vec::new(),
+            Some("coordinator".to_string()),
+            None,
+        );
+        UltrapilotHook::add_worker(directory, worker("worker-1", Some("task-1"), &["src/a.rs"]));
+        UltrapilotHook::add_worker(directory, worker("worker-2", None, &["src/b.rs"]));
+
+        let hook = UltrapilotHook::new();
+        let context = HookContext::new(None, directory.to_string(), None);
+
+        let absolute = dir.path().join("src/b.rs");
+        let output = hook
+            .execute(
+                HookEvent::PreToolUse,
+                &write_input("task-1", absolute.to_str().unwrap()),
+                &context,
+            )
+            .await
+            .unwrap();
+        assert!(!output.should_continue);
+        let reason = output.reason.unwrap();
+        assert!(reason.contains("worker-2"));
+        assert!(reason.contains("src/a.rs"));
+
+        let state = UltrapilotHook::read_state(directory).unwrap();
+        assert_eq!(state.ownership.conflicts, vec!["src/b.rs".to_string()]);
+
+        let output = hook
+            .execute(
+                HookEvent::PreToolUse,
+                &write_input("task-1", "src/a.rs"),
+                &context,
+            )
+            .await
+            .unwrap();
+        assert!(output.should_continue);
+
+        let output = hook
+            .execute(
+                HookEvent::PreToolUse,
+                &write_input("task-1", "src/../src/./b.rs"),
+                &context,
+            )
+            .await
+            .unwrap();
+        assert!(!output.should_continue);
+
+        let output = hook
+            .execute(
+                HookEvent::PreToolUse,
+                &write_input("coordinator", "src/b.rs"),
+                &context,
+            )
+            .await
+            .unwrap();
+        assert!(output.should_continue);
+    }
+
+    #[tokio::test]
+    async fn test_pre_tool_use_denies_shared_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let directory = dir.path().to_str().unwrap();
+        UltrapilotHook::init(directory, "task", Vec::new(), None, None);
+        UltrapilotHook::add_worker(directory, worker("worker-1", None, &[]));
+
+        let hook = UltrapilotHook::new();
+        let context = HookContext::new(None, directory.to_string(), None);
+        let output = hook
+            .execute(
+                HookEvent::PreToolUse,
+                &write_input("worker-1", "./package.json"),
+                &context,
+            )
+            .await
+            .unwrap();
+        assert!(!output.should_continue);
+        assert!(output.reason.unwrap().contains("coordinator"));
+    }
+
+    #[test]
+    fn test_normalize_path_folds_dot_segments() {
+        let dir = tempfile::tempdir().unwrap();
+        let directory = dir.path().to_str().unwrap();
+        for path in [
+            "src/b.rs",
+            "./src/./b.rs",
+            "src/../src/b.rs",
+            "lib/../src/b.rs",
+        ] {
+            assert_eq!(UltrapilotHook::normalize_path(directory, path), "src/b.rs");
+        }
+        let absolute = dir.path().join("other/../src/b.rs");
+        assert_eq!(
+            UltrapilotHook::normalize_path(directory, absolute.to_str().unwrap()),
+            "src/b.rs"
+        );
+
+        let canonical = fs::canonicalize(dir.path()).unwrap().join("src/b.rs");
+        assert_eq!(
+            UltrapilotHook::normalize_path(directory, canonical.to_str().unwrap()),
+            "src/b.rs"
+        );
+    }
 }
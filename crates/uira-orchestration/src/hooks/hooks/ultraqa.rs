@@ -9,11 +9,16 @@ use regex::Regex;
 use serde::{Deserialize, Serialize};
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use uira_core::UIRA_DIR;
 
 use super::super::hook::{Hook, HookContext, HookResult};
 use super::super::types::{HookEvent, HookInput, HookOutput};
 use super::ralph::RalphHook;
+use super::ultraqa_adapters::{
+    run_verification, CustomCommandAdapter, QaReport, VerificationAdapter,
+    VerificationAdapterRegistry,
+};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -26,6 +31,16 @@ pub enum UltraQAGoalType {
 }
 
 impl UltraQAGoalType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Tests => "tests",
+            Self::Build => "build",
+            Self::Lint => "lint",
+            Self::Typecheck => "typecheck",
+            Self::Custom => "custom",
+        }
+    }
+
     pub fn get_command(&self) -> &'static str {
         match self {
             Self::Tests => "npm test",
@@ -43,6 +58,9 @@ pub struct UltraQAState {
     pub goal_type: UltraQAGoalType,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub goal_pattern: Option<String>,
+    /// Verification adapter to run, by registry name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub adapter: Option<String>,
     pub cycle: u32,
     pub max_cycles: u32,
     pub failures: Vec<String>,
@@ -55,6 +73,8 @@ pub struct UltraQAState {
 pub struct UltraQAOptions {
     pub max_cycles: Option<u32>,
     pub custom_pattern: Option<String>,
+    /// Verification adapter to run instead of the goal type's default
+    pub adapter: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -76,6 +96,7 @@ pub struct UltraQAResult {
 
 const DEFAULT_MAX_CYCLES: u32 = 5;
 const SAME_FAILURE_THRESHOLD: usize = 3;
+const VERIFY_TIMEOUT_SECS: u64 = 600;
 
 pub struct UltraQAHook;
 
@@ -154,6 +175,7 @@ impl UltraQAHook {
             active: true,
             goal_type,
             goal_pattern: opts.custom_pattern,
+            adapter: opts.adapter,
             cycle: 1,
             max_cycles: opts.max_cycles.unwrap_or(DEFAULT_MAX_CYCLES),
             failures: Vec::new(),
@@ -224,6 +246,79 @@ impl UltraQAHook {
         Some((state, None))
     }
 
+    /// The adapter that verifies the QA goal
+    ///
+    /// An explicitly chosen adapter wins. Otherwise test goals use the adapter
+    /// detected from the project, custom goals run their pattern as a command,
+    /// and everything else falls back to the goal type's command.
+    pub fn resolve_adapter(
+        state: &UltraQAState,
+        directory: &str,
+        registry: &VerificationAdapterRegistry,
+    ) -> Result<Arc<dyn VerificationAdapter>, String> {
+        if let Some(ref name) = state.adapter {
+            return registry.get(name).ok_or_else(|| {
+                format!(
+                    "Unknown verification adapter '{}' (available: {})",
+                    name,
+                    registry.names().join(", ")
+                )
+            });
+        }
+
+        match (state.goal_type, &state.goal_pattern) {
+            (UltraQAGoalType::Tests, _) => {
+                if let Some(adapter) = registry.detect(Path::new(directory)) {
+                    return Ok(adapter);
+                }
+            }
+            (UltraQAGoalType::Custom, Some(command)) => {
+                return Ok(Arc::new(CustomCommandAdapter::new("custom", command)));
+            }
+            _ => {}
+        }
+
+        Ok(Arc::new(CustomCommandAdapter::new(
+            state.goal_type.as_str(),
+            state.goal_type.get_command(),
+        )))
+    }
+
+    /// Run the active QA loop's verification
+    pub async fn verify(
+        directory: &str,
+        registry: &VerificationAdapterRegistry,
+    ) -> Result<QaReport, String> {
+        let state = Self::read_state(directory)
+            .filter(|state| state.active)
+            .ok_or_else(|| "UltraQA is not active".to_string())?;
+        let adapter = Self::resolve_adapter(&state, directory, registry)?;
+        run_verification(adapter.as_ref(), Path::new(directory), VERIFY_TIMEOUT_SECS).await
+    }
+
+    /// Record a failed verification run, keyed by its failing cases so
+    /// repeated failures are detected
+    pub fn record_report(
+        directory: &str,
+        report: &QaReport,
+    ) -> Option<(UltraQAState, Option<String>)> {
+        Self::record_failure(directory, &report.summary())
+    }
+
+    /// Prompt asking the agent to fix the failures in `report`
+    pub fn build_fix_prompt(state: &UltraQAState, report: &QaReport) -> String {
+        format!(
+            "{}\n\n{}\n\nFix these failures, then verify with `{}`.",
+            Self::format_progress_message(
+                state.cycle,
+                state.max_cycles,
+                &format!("{} failed", report.adapter)
+            ),
+            report.format_failures(),
+            report.command
+        )
+    }
+
     pub fn complete(directory: &str) -> Option<UltraQAResult> {
         let state = Self::read_state(directory)?;
 
@@ -308,6 +403,7 @@ impl Hook for UltraQAHook {
 
 #[cfg(test)]
 mod tests {
+    use super::super::ultraqa_adapters::QaFailure;
     use super::*;
 
     #[test]
@@ -326,6 +422,75 @@ mod tests {
         assert!(!normalized.contains(":10:20"));
     }
 
+    #[test]
+    fn test_resolve_adapter() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let directory = dir.path().to_str().unwrap();
+        let registry = VerificationAdapterRegistry::default();
+        let mut state = UltraQAState {
+            active: true,
+            goal_type: UltraQAGoalType::Tests,
+            goal_pattern: None,
+            adapter: None,
+            cycle: 1,
+            max_cycles: 5,
+            failures: Vec::new(),
+            started_at: Utc::now(),
+            session_id: None,
+        };
+
+        let adapter = UltraQAHook::resolve_adapter(&state, directory, &registry).unwrap();
+        assert_eq!(adapter.command(), "npm test");
+
+        fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        let adapter = UltraQAHook::resolve_adapter(&state, directory, &registry).unwrap();
+        assert_eq!(adapter.name(), "cargo-test");
+
+        state.adapter = Some("pytest".to_string());
+        let adapter = UltraQAHook::resolve_adapter(&state, directory, &registry).unwrap();
+        assert_eq!(adapter.name(), "pytest");
+
+        state.adapter = Some("nope".to_string());
+        assert!(UltraQAHook::resolve_adapter(&state, directory, &registry)
+            .err()
+            .unwrap()
+            .contains("Unknown verification adapter 'nope'"));
+    }
+
+    #[test]
+    fn test_build_fix_prompt() {
+        let state = UltraQAState {
+            active: true,
+            goal_type: UltraQAGoalType::Tests,
+            goal_pattern: None,
+            adapter: None,
+            cycle: 2,
+            max_cycles: 5,
+            failures: Vec::new(),
+            started_at: Utc::now(),
+            session_id: None,
+        };
+        let report = QaReport {
+            adapter: "cargo-test".to_string(),
+            command: "cargo test --no-fail-fast 2>&1".to_string(),
+            passed: false,
+            exit_code: Some(101),
+            failures: vec![QaFailure {
+                name: "parser::tests::parses_nested".to_string(),
+                location: Some("src/parser.rs:88:9".to_string()),
+                message: Some("assertion failed".to_string()),
+            }],
+            output: String::new(),
+        };
+
+        assert_eq!(
+            UltraQAHook::build_fix_prompt(&state, &report),
+            "[ULTRAQA Cycle 2/5] cargo-test failed\n\n\
+             Failing:\n- parser::tests::parses_nested (src/parser.rs:88:9)\n  assertion failed\n\n\
+             Fix these failures, then verify with `cargo test --no-fail-fast 2>&1`."
+        );
+    }
+
     #[test]
     fn test_format_progress_message() {
         let msg = UltraQAHook::format_progress_message(2, 5, "Running tests");
@@ -0,0 +1,620 @@
+//! UltraQA verification adapters
+//!
+//! An adapter knows how to run one kind of check (a test runner, an audit,
+//! a custom command) and how to pull the failing cases out of its output, so
+//! a QA cycle can name them in the fix prompt instead of pasting raw logs.
+
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::process::Command;
+use tokio::time::timeout;
+
+/// How much of a failed run's output to keep for the fix prompt
+const OUTPUT_TAIL_CHARS: usize = 4000;
+
+lazy_static! {
+    static ref CARGO_FAILED_PATTERN: Regex = Regex::new(r"^test (\S+) \.\.\. FAILED$").unwrap();
+    static ref CARGO_SECTION_PATTERN: Regex = Regex::new(r"^---- (\S+) stdout ----$").unwrap();
+    static ref CARGO_PANIC_PATTERN: Regex =
+        Regex::new(r"panicked at ([^\s:]+:\d+:\d+):?$").unwrap();
+    static ref PYTEST_FAILED_PATTERN: Regex =
+        Regex::new(r"^(?:FAILED|ERROR) (\S+?)(?: - (.+))?$").unwrap();
+    static ref JEST_FILE_PATTERN: Regex = Regex::new(r"^FAIL (\S+)").unwrap();
+    static ref JEST_TEST_PATTERN: Regex = Regex::new(r"^● (.+)$").unwrap();
+    static ref PLAYWRIGHT_HEADER_PATTERN: Regex =
+        Regex::new(r"^\d+\) (?:\[[^\]]+\] › )?(\S+:\d+:\d+) › (.+?)(?:\s+─+)?$").unwrap();
+}
+
+/// One failing case extracted from a verification run
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct QaFailure {
+    /// Test name, audit category, or matched failure line
+    pub name: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub location: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+impl QaFailure {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            location: None,
+            message: None,
+        }
+    }
+}
+
+/// Outcome of running a verification adapter
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QaReport {
+    pub adapter: String,
+    pub command: String,
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exit_code: Option<i32>,
+    pub failures: Vec<QaFailure>,
+    /// Tail of the combined stdout and stderr
+    pub output: String,
+}
+
+impl QaReport {
+    /// One-line description, stable across runs with the same failures
+    pub fn summary(&self) -> String {
+        if self.passed {
+            return format!("{}: passed", self.adapter);
+        }
+        if self.failures.is_empty() {
+            return match self.exit_code {
+                Some(code) => format!("{}: exited with code {}", self.adapter, code),
+                None => format!("{}: failed", self.adapter),
+            };
+        }
+        let names: Vec<&str> = self.failures.iter().map(|f| f.name.as_str()).collect();
+        format!(
+            "{}: {} failing: {}",
+            self.adapter,
+            self.failures.len(),
+            names.join(", ")
+        )
+    }
+
+    /// Failure details for a fix prompt
+    pub fn format_failures(&self) -> String {
+        if self.failures.is_empty() {
+            return format!("Output of `{}`:\n{}", self.command, self.output.trim_end());
+        }
+
+        let mut text = String::from("Failing:\n");
+        for failure in &self.failures {
+            text.push_str(&format!("- {}", failure.name));
+            if let Some(ref location) = failure.location {
+                text.push_str(&format!(" ({})", location));
+            }
+            text.push('\n');
+            if let Some(ref message) = failure.message {
+                text.push_str(&format!("  {}\n", message));
+            }
+        }
+        text.trim_end().to_string()
+    }
+}
+
+/// A kind of verification an UltraQA loop can run
+pub trait VerificationAdapter: Send + Sync {
+    /// Registry name, e.g. `cargo-test`
+    fn name(&self) -> &str;
+
+    /// Shell command that runs the check
+    fn command(&self) -> String;
+
+    /// Failing cases found in the command's combined output
+    fn extract_failures(&self, output: &str) -> Vec<QaFailure>;
+}
+
+/// Rust tests via `cargo test`
+pub struct CargoTestAdapter;
+
+impl VerificationAdapter for CargoTestAdapter {
+    fn name(&self) -> &str {
+        "cargo-test"
+    }
+
+    fn command(&self) -> String {
+        "cargo test --no-fail-fast 2>&1".to_string()
+    }
+
+    fn extract_failures(&self, output: &str) -> Vec<QaFailure> {
+        let mut failures: Vec<QaFailure> = output
+            .lines()
+            .filter_map(|line| CARGO_FAILED_PATTERN.captures(line.trim()))
+            .map(|caps| QaFailure::new(&caps[1]))
+            .collect();
+        failures.dedup_by(|a, b| a.name == b.name);
+
+        // Panic location and message from each failing test's captured stdout
+        let mut current: Option<usize> = None;
+        let mut lines = output.lines().peekable();
+        while let Some(line) = lines.next() {
+            if let Some(caps) = CARGO_SECTION_PATTERN.captures(line.trim()) {
+                current = failures.iter().position(|f| f.name == caps[1]);
+            } else if let (Some(idx), Some(caps)) =
+                (current, CARGO_PANIC_PATTERN.captures(line.trim()))
+            {
+                failures[idx].location = Some(caps[1].to_string());
+                failures[idx].message = lines
+                    .peek()
+                    .map(|next| next.trim().to_string())
+                    .filter(|next| !next.is_empty());
+                current = None;
+            }
+        }
+        failures
+    }
+}
+
+/// Python tests via `pytest`
+pub struct PytestAdapter;
+
+impl VerificationAdapter for PytestAdapter {
+    fn name(&self) -> &str {
+        "pytest"
+    }
+
+    fn command(&self) -> String {
+        "pytest -rf --tb=short 2>&1".to_string()
+    }
+
+    fn extract_failures(&self, output: &str) -> Vec<QaFailure> {
+        output
+            .lines()
+            .filter_map(|line| PYTEST_FAILED_PATTERN.captures(line.trim()))
+            .map(|caps| QaFailure {
+                name: caps[1].to_string(),
+                location: caps[1].split("::").next().map(str::to_string),
+                message: caps.get(2).map(|m| m.as_str().to_string()),
+            })
+            .collect()
+    }
+}
+
+/// JavaScript tests via `jest`
+pub struct JestAdapter;
+
+impl VerificationAdapter for JestAdapter {
+    fn name(&self) -> &str {
+        "jest"
+    }
+
+    fn command(&self) -> String {
+        "npx jest --ci 2>&1".to_string()
+    }
+
+    fn extract_failures(&self, output: &str) -> Vec<QaFailure> {
+        let mut failures = Vec::new();
+        let mut current_file = None;
+        let mut lines = output.lines().peekable();
+        while let Some(line) = lines.next() {
+            let line = line.trim();
+            if let Some(caps) = JEST_FILE_PATTERN.captures(line) {
+                current_file = Some(caps[1].to_string());
+            } else if let Some(caps) = JEST_TEST_PATTERN.captures(line) {
+                while lines.peek().is_some_and(|next| next.trim().is_empty()) {
+                    lines.next();
+                }
+                failures.push(QaFailure {
+                    name: caps[1].to_string(),
+                    location: current_file.clone(),
+                    message: lines.peek().map(|next| next.trim().to_string()),
+                });
+            }
+        }
+        failures
+    }
+}
+
+/// Browser tests via `playwright test`
+pub struct PlaywrightAdapter;
+
+impl VerificationAdapter for PlaywrightAdapter {
+    fn name(&self) -> &str {
+        "playwright"
+    }
+
+    fn command(&self) -> String {
+        "npx playwright test --reporter=list 2>&1".to_string()
+    }
+
+    fn extract_failures(&self, output: &str) -> Vec<QaFailure> {
+        let mut failures: Vec<QaFailure> = Vec::new();
+        let mut lines = output.lines().peekable();
+        while let Some(line) = lines.next() {
+            let Some(caps) = PLAYWRIGHT_HEADER_PATTERN.captures(line.trim()) else {
+                continue;
+            };
+            // The list reporter prints each failure twice: once inline, once in the summary
+            if failures.iter().any(|f| f.name == caps[2]) {
+                continue;
+            }
+            while lines.peek().is_some_and(|next| next.trim().is_empty()) {
+                lines.next();
+            }
+            failures.push(QaFailure {
+                name: caps[2].to_string(),
+                location: Some(caps[1].to_string()),
+                message: lines.peek().map(|next| next.trim().to_string()),
+            });
+        }
+        failures
+    }
+}
+
+/// Lighthouse audit of `$LIGHTHOUSE_URL` (default `http://localhost:3000`),
+/// failing any category that scores below `min_score`
+pub struct LighthouseAdapter {
+    pub min_score: f64,
+}
+
+impl Default for LighthouseAdapter {
+    fn default() -> Self {
+        Self { min_score: 0.9 }
+    }
+}
+
+impl VerificationAdapter for LighthouseAdapter {
+    fn name(&self) -> &str {
+        "lighthouse"
+    }
+
+    fn command(&self) -> String {
+        "npx lighthouse \"${LIGHTHOUSE_URL:-http://localhost:3000}\" --output=json --output-path=stdout --quiet --chrome-flags=\"--headless\"".to_string()
+    }
+
+    fn extract_failures(&self, output: &str) -> Vec<QaFailure> {
+        // The report is the JSON object in stdout; anything else is chatter
+        let Some(start) = output.find('{') else {
+            return Vec::new();
+        };
+        let Ok(report) = serde_json::from_str::<serde_json::Value>(&output[start..]) else {
+            return Vec::new();
+        };
+        let Some(categories) = report["categories"].as_object() else {
+            return Vec::new();
+        };
+
+        let mut failures: Vec<QaFailure> = categories
+            .iter()
+            .filter_map(|(id, category)| {
+                let score = category["score"].as_f64()?;
+                (score < self.min_score).then(|| QaFailure {
+                    name: id.clone(),
+                    location: None,
+                    message: Some(format!(
+                        "{} score {:.0} is below {:.0}",
+                        category["title"].as_str().unwrap_or(id),
+                        score * 100.0,
+                        self.min_score * 100.0
+                    )),
+                })
+            })
+            .collect();
+        failures.sort_by(|a, b| a.name.cmp(&b.name));
+        failures
+    }
+}
+
+/// Any shell command, optionally with a pattern that picks out failing
+/// cases (its first capture group, or the whole match, names the failure)
+pub struct CustomCommandAdapter {
+    name: String,
+    command: String,
+    failure_pattern: Option<Regex>,
+}
+
+impl CustomCommandAdapter {
+    pub fn new(name: impl Into<String>, command: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            command: command.into(),
+            failure_pattern: None,
+        }
+    }
+
+    pub fn with_failure_pattern(mut self, pattern: &str) -> Result<Self, regex::Error> {
+        self.failure_pattern = Some(Regex::new(pattern)?);
+        Ok(self)
+    }
+}
+
+impl VerificationAdapter for CustomCommandAdapter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn command(&self) -> String {
+        self.command.clone()
+    }
+
+    fn extract_failures(&self, output: &str) -> Vec<QaFailure> {
+        let Some(ref pattern) = self.failure_pattern else {
+            return Vec::new();
+        };
+        output
+            .lines()
+            .filter_map(|line| pattern.captures(line))
+            .map(|caps| {
+                let name = caps
+                    .get(1)
+                    .or_else(|| caps.get(0))
+                    .map_or("", |m| m.as_str());
+                QaFailure::new(name.trim())
+            })
+            .collect()
+    }
+}
+
+/// Verification adapters by name
+#[derive(Clone)]
+pub struct VerificationAdapterRegistry {
+    adapters: HashMap<String, Arc<dyn VerificationAdapter>>,
+}
+
+impl VerificationAdapterRegistry {
+    /// An empty registry
+    pub fn new() -> Self {
+        Self {
+            adapters: HashMap::new(),
+        }
+    }
+
+    /// A registry with the built-in adapters
+    pub fn with_builtins() -> Self {
+        let mut registry = Self::new();
+        registry.register(Arc::new(CargoTestAdapter));
+        registry.register(Arc::new(PytestAdapter));
+        registry.register(Arc::new(JestAdapter));
+        registry.register(Arc::new(PlaywrightAdapter));
+        registry.register(Arc::new(LighthouseAdapter::default()));
+        registry
+    }
+
+    /// Add an adapter, replacing any with the same name
+    pub fn register(&mut self, adapter: Arc<dyn VerificationAdapter>) {
+        self.adapters.insert(adapter.name().to_string(), adapter);
+    }
+
+    pub fn get(&self, name: &str) -> Option<Arc<dyn VerificationAdapter>> {
+        self.adapters.get(name).cloned()
+    }
+
+    pub fn names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.adapters.keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    /// Pick a test adapter from the project files in `directory`
+    pub fn detect(&self, directory: &Path) -> Option<Arc<dyn VerificationAdapter>> {
+        let has = |file: &str| directory.join(file).exists();
+
+        let name = if has("Cargo.toml") {
+            "cargo-test"
+        } else if ["pytest.ini", "conftest.py", "pyproject.toml", "setup.cfg"]
+            .iter()
+            .any(|file| has(file))
+        {
+            "pytest"
+        } else if ["playwright.config.ts", "playwright.config.js"]
+            .iter()
+            .any(|file| has(file))
+        {
+            "playwright"
+        } else if fs::read_to_string(directory.join("package.json"))
+            .is_ok_and(|package| package.contains("\"jest\""))
+        {
+            "jest"
+        } else {
+            return None;
+        };
+        self.get(name)
+    }
+}
+
+impl Default for VerificationAdapterRegistry {
+    fn default() -> Self {
+        Self::with_builtins()
+    }
+}
+
+/// Run `adapter` in `directory` and extract its failures
+pub async fn run_verification(
+    adapter: &dyn VerificationAdapter,
+    directory: &Path,
+    timeout_secs: u64,
+) -> Result<QaReport, String> {
+    let command = adapter.command();
+    let output = timeout(Duration::from_secs(timeout_secs), async {
+        Command::from(uira_core::shell_command(&command, None))
+            .current_dir(directory)
+            .output()
+            .await
+    })
+    .await
+    .map_err(|_| format!("`{}` timed out after {} seconds", command, timeout_secs))?
+    .map_err(|e| format!("Failed to run `{}`: {}", command, e))?;
+
+    let mut text = String::from_utf8_lossy(&output.stdout).into_owned();
+    text.push_str(&String::from_utf8_lossy(&output.stderr));
+
+    let failures = adapter.extract_failures(&text);
+    Ok(QaReport {
+        adapter: adapter.name().to_string(),
+        command,
+        passed: output.status.success() && failures.is_empty(),
+        exit_code: output.status.code(),
+        failures,
+        output: tail_chars(&text, OUTPUT_TAIL_CHARS),
+    })
+}
+
+fn tail_chars(text: &str, max: usize) -> String {
+    let count = text.chars().count();
+    if count <= max {
+        return text.to_string();
+    }
+    text.chars().skip(count - max).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cargo_failures() {
+        let output = "\
+running 3 tests
+test parser::tests::parses_empty ... ok
+test parser::tests::parses_nested ... FAILED
+test lexer::tests::handles_unicode ... FAILED
+
+failures:
+
+---- parser::tests::parses_nested stdout ----
+
+thread 'parser::tests::parses_nested' panicked at src/parser.rs:88:9:
+assertion `left == right` failed
+
+---- lexer::tests::handles_unicode stdout ----
+thread 'lexer::tests::handles_unicode' panicked at src/lexer.rs:12:5:
+index out of bounds
+";
+        let failures = CargoTestAdapter.extract_failures(output);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].name, "parser::tests::parses_nested");
+        assert_eq!(failures[0].location.as_deref(), Some("src/parser.rs:88:9"));
+        assert_eq!(
+            failures[0].message.as_deref(),
+            Some("assertion `left == right` failed")
+        );
+        assert_eq!(failures[1].message.as_deref(), Some("index out of bounds"));
+    }
+
+    #[test]
+    fn test_pytest_failures() {
+        let output = "\
+=========================== short test summary info ============================
+FAILED tests/test_api.py::test_login - AssertionError: expected 200, got 401
+FAILED tests/test_api.py::TestUsers::test_delete
+========================= 2 failed, 10 passed in 1.2s ==========================
+";
+        let failures = PytestAdapter.extract_failures(output);
+        assert_eq!(failures.len(), 2);
+        assert_eq!(failures[0].name, "tests/test_api.py::test_login");
+        assert_eq!(failures[0].location.as_deref(), Some("tests/test_api.py"));
+        assert_eq!(
+            failures[0].message.as_deref(),
+            Some("AssertionError: expected 200, got 401")
+        );
+        assert_eq!(failures[1].message, None);
+    }
+
+    #[test]
+    fn test_jest_failures() {
+        let output = "\
+FAIL src/cart.test.ts
+  ● Cart › applies discount
+
+    expect(received).toBe(expected) // Object.is equality
+
+PASS src/user.test.ts
+";
+        let failures = JestAdapter.extract_failures(output);
+        assert_eq!(
+            failures,
+            vec![QaFailure {
+                name: "Cart › applies discount".to_string(),
+                location: Some("src/cart.test.ts".to_string()),
+                message: Some("expect(received).toBe(expected) // Object.is equality".to_string()),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_playwright_failures() {
+        let output = "\
+  ✘  1 [chromium] › tests/home.spec.ts:3:5 › has title (1.2s)
+
+  1) [chromium] › tests/home.spec.ts:3:5 › has title ───────────────────────────
+
+    Error: Timed out 5000ms waiting for expect(locator).toHaveTitle(expected)
+
+  1 failed
+";
+        let failures = PlaywrightAdapter.extract_failures(output);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].name, "has title");
+        assert_eq!(
+            failures[0].location.as_deref(),
+            Some("tests/home.spec.ts:3:5")
+        );
+        assert!(failures[0]
+            .message
+            .as_deref()
+            .unwrap()
+            .starts_with("Error: Timed out"));
+    }
+
+    #[test]
+    fn test_lighthouse_failures() {
+        let output = r#"{"categories": {
+            "performance": {"title": "Performance", "score": 0.72},
+            "accessibility": {"title": "Accessibility", "score": 0.98}
+        }}"#;
+        let failures = LighthouseAdapter::default().extract_failures(output);
+        assert_eq!(failures.len(), 1);
+        assert_eq!(failures[0].name, "performance");
+        assert_eq!(
+            failures[0].message.as_deref(),
+            Some("Performance score 72 is below 90")
+        );
+    }
+
+    #[test]
+    fn test_registry_detects_project_adapter() {
+        let registry = VerificationAdapterRegistry::default();
+        let dir = TempDir::new().unwrap();
+        assert!(registry.detect(dir.path()).is_none());
+
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"devDependencies": {"jest": "^29"}}"#,
+        )
+        .unwrap();
+        assert_eq!(registry.detect(dir.path()).unwrap().name(), "jest");
+
+        fs::write(dir.path().join("Cargo.toml"), "[package]").unwrap();
+        assert_eq!(registry.detect(dir.path()).unwrap().name(), "cargo-test");
+    }
+
+    #[tokio::test]
+    async fn test_run_custom_command() {
+        let dir = TempDir::new().unwrap();
+        let adapter = CustomCommandAdapter::new("e2e", "echo 'FAIL checkout flow'; exit 1")
+            .with_failure_pattern(r"^FAIL (.+)$")
+            .unwrap();
+
+        let report = run_verification(&adapter, dir.path(), 10).await.unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.exit_code, Some(1));
+        assert_eq!(report.summary(), "e2e: 1 failing: checkout flow");
+        assert_eq!(report.format_failures(), "Failing:\n- checkout flow");
+    }
+}
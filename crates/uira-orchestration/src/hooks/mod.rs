@@ -4,6 +4,9 @@ pub mod hook;
 #[allow(clippy::module_inception)]
 pub mod hooks;
 pub mod registry;
+pub mod replay;
+pub mod transcript;
+pub mod transform;
 pub mod types;
 
 pub use adapter::{create_hook_event_adapter, HookEventAdapter};
@@ -16,7 +19,7 @@ pub use hooks::{
     },
     autopilot::{
         AutopilotConfig, AutopilotHook, AutopilotPhase, AutopilotSignal, AutopilotState,
-        AUTOPILOT_STATE_FILE,
+        AutopilotStep, AUTOPILOT_STATE_FILE,
     },
     background_notification::{
         background_tasks_dir, check_background_notifications, handle_background_event_public,
@@ -38,14 +41,25 @@ pub use hooks::{
         PersistentModeResult,
     },
     ralph::{CompletionSignal, CompletionSignals, RalphHook, RalphOptions, RalphState, SignalType},
-    think_mode::{ThinkModeHook, ThinkModeState, ThinkingConfig, THINKING_CONFIGS},
+    skill_usage::{
+        load_skill_usage_state, SkillUsageHook, SkillUsageRecord, SkillUsageState,
+        SKILL_USAGE_STATE_FILE,
+    },
+    think_mode::{ThinkLevel, ThinkModeHook, ThinkModeState, ThinkingConfig, THINKING_CONFIGS},
     todo_continuation::{IncompleteTodosResult, StopContext, TodoContinuationHook},
     ultrapilot::{
-        FileOwnership, IntegrationResult, UltrapilotConfig, UltrapilotHook, UltrapilotState,
-        WorkerState, WorkerStatus,
+        FileOwnership, IntegrationResult, OwnershipCheck, UltrapilotConfig, UltrapilotHook,
+        UltrapilotState, WorkerState, WorkerStatus,
     },
     ultraqa::{UltraQAExitReason, UltraQAGoalType, UltraQAHook, UltraQAResult, UltraQAState},
+    ultraqa_adapters::{
+        run_verification, QaFailure, QaReport, VerificationAdapter, VerificationAdapterRegistry,
+    },
     ultrawork::{UltraworkHook, UltraworkState},
 };
-pub use registry::{default_hooks, HookRegistry};
+pub use registry::{default_hooks, default_hooks_in, HookRegistry};
+pub use transcript::{
+    TranscriptReader, TranscriptRole, TranscriptTokens, TranscriptToolCall, TranscriptTurn,
+};
+pub use transform::{MessagesDelta, MessagesTransformPipeline, TransformOutcome, TransformStep};
 pub use types::{HookEvent, HookInput, HookOutput, HookType};
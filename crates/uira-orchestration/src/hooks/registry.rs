@@ -1,4 +1,5 @@
 use std::collections::HashMap;
+use std::path::PathBuf;
 use std::sync::Arc;
 
 use super::hook::{Hook, HookContext, HookResult};
@@ -61,12 +62,22 @@ impl HookRegistry {
     /// Hooks are executed in priority order (highest first).
     /// If any hook returns continue=false, execution stops and that output is returned.
     /// Otherwise, messages from all hooks are combined.
+    ///
+    /// `messages-transform` inputs carrying messages go through the
+    /// [`MessagesTransformPipeline`](super::transform::MessagesTransformPipeline)
+    /// instead, which chains each hook's rewrite into the next.
     pub async fn execute_hooks(
         &self,
         event: HookEvent,
         input: &HookInput,
         context: &HookContext,
     ) -> HookResult {
+        if event == HookEvent::MessagesTransform {
+            if let Some(output) = super::transform::execute(self, input, context).await {
+                return Ok(output);
+            }
+        }
+
         let hooks = self.get_hooks_for_event(event);
 
         if hooks.is_empty() {
@@ -118,18 +129,27 @@ impl Default for HookRegistry {
     }
 }
 
+/// Hooks run from the event bus.
+///
+/// The autopilot hook is left out: the agent loop drives autopilot itself so
+/// its continuation prompts and phase events reach the session.
 pub fn default_hooks() -> HookRegistry {
+    default_hooks_in(std::env::current_dir().unwrap_or_default())
+}
+
+/// [`default_hooks`] with the directory-scoped injectors rooted at `directory`
+/// instead of the current directory
+pub fn default_hooks_in(directory: PathBuf) -> HookRegistry {
     let mut registry = HookRegistry::new();
 
     registry.register(Arc::new(AgentUsageReminderHook));
     registry.register(Arc::new(AutoSlashCommandHook));
-    registry.register(Arc::new(AutopilotHook::new()));
     registry.register(Arc::new(BackgroundNotificationHook::new()));
     registry.register(Arc::new(DirectoryReadmeInjectorHook::new(
-        std::env::current_dir().unwrap_or_default(),
+        directory.clone(),
     )));
     registry.register(Arc::new(DirectoryAgentsInjectorHook::new(
-        std::env::current_dir().unwrap_or_default(),
+        directory.clone(),
     )));
     registry.register(Arc::new(EmptyMessageSanitizerHook::new()));
     registry.register(Arc::new(KeywordDetectorHook::new()));
@@ -141,9 +161,8 @@ pub fn default_hooks() -> HookRegistry {
     registry.register(Arc::new(PreemptiveCompactionHook::new(None)));
     registry.register(Arc::new(RalphHook::new()));
     registry.register(Arc::new(RecoveryHook::new()));
-    registry.register(Arc::new(RulesInjectorHook::new(
-        std::env::current_dir().unwrap_or_default(),
-    )));
+    registry.register(Arc::new(RulesInjectorHook::new(directory)));
+    registry.register(Arc::new(SkillUsageHook::new()));
     registry.register(Arc::new(ThinkModeHook::new()));
     registry.register(Arc::new(ThinkingBlockValidatorHook));
     registry.register(Arc::new(TodoContinuationHook::new()));
@@ -0,0 +1,369 @@
+//! Replay fixtures for hook decisions
+//!
+//! A fixture is a JSON file capturing one hook invocation: the event, the
+//! [`HookInput`] and context it ran with, and the [`HookOutput`] the default
+//! registry is expected to return. Replaying a directory of fixtures catches
+//! refactors that silently change what a hook decides.
+//!
+//! Each fixture runs against a fresh [`default_hooks_in`] registry rooted at
+//! a temporary working directory, seeded with the fixture's `files`.
+//! `{{directory}}` in the input, context data and expected output stands for
+//! that directory. Hooks that read state outside it, such as learned skills
+//! under the home directory, should be left out by listing the hooks under
+//! test in `hooks`.
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context as _};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::Value;
+use walkdir::WalkDir;
+
+use super::hook::HookContext;
+use super::registry::{default_hooks_in, HookRegistry};
+use super::types::{HookEvent, HookInput, HookOutput};
+
+/// Stands for the fixture's working directory
+pub const DIRECTORY_PLACEHOLDER: &str = "{{directory}}";
+
+/// One recorded hook invocation and the decision expected for it
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HookFixture {
+    /// Defaults to the file name
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub event: HookEvent,
+    /// Hooks from the default registry to run; all of them when empty
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub hooks: Vec<String>,
+    #[serde(default)]
+    pub input: HookInput,
+    #[serde(default)]
+    pub context: FixtureContext,
+    /// Files created in the working directory, by relative path
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub files: BTreeMap<String, String>,
+    pub expected: FixtureExpectation,
+}
+
+/// The parts of [`HookContext`] a fixture can set
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct FixtureContext {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub data: HashMap<String, Value>,
+}
+
+/// Expected [`HookOutput`]; unset fields must be absent from the output
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FixtureExpectation {
+    #[serde(rename = "continue", default = "default_continue")]
+    pub should_continue: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    /// Substrings the message must contain, checked instead of `message`
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub message_contains: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub modified_input: Option<Value>,
+}
+
+fn default_continue() -> bool {
+    true
+}
+
+impl From<HookOutput> for FixtureExpectation {
+    fn from(output: HookOutput) -> Self {
+        Self {
+            should_continue: output.should_continue,
+            message: output.message,
+            message_contains: Vec::new(),
+            reason: output.reason,
+            modified_input: output.modified_input,
+        }
+    }
+}
+
+impl FixtureExpectation {
+    /// Differences between this expectation and `actual`, one per field
+    pub fn mismatches(&self, actual: &HookOutput) -> Vec<String> {
+        let mut mismatches = Vec::new();
+        if self.should_continue != actual.should_continue {
+            mismatches.push(format!(
+                "continue: expected {}, got {}",
+                self.should_continue, actual.should_continue
+            ));
+        }
+        if self.message_contains.is_empty() {
+            if self.message != actual.message {
+                mismatches.push(format!(
+                    "message: expected {:?}, got {:?}",
+                    self.message, actual.message
+                ));
+            }
+        } else {
+            let message = actual.message.as_deref().unwrap_or_default();
+            for needle in &self.message_contains {
+                if !message.contains(needle.as_str()) {
+                    mismatches.push(format!("message: missing {needle:?}"));
+                }
+            }
+        }
+        if self.reason != actual.reason {
+            mismatches.push(format!(
+                "reason: expected {:?}, got {:?}",
+                self.reason, actual.reason
+            ));
+        }
+        if self.modified_input != actual.modified_input {
+            mismatches.push(format!(
+                "modified_input: expected {}, got {}",
+                display_value(self.modified_input.as_ref()),
+                display_value(actual.modified_input.as_ref())
+            ));
+        }
+        mismatches
+    }
+}
+
+fn display_value(value: Option<&Value>) -> String {
+    value.map_or_else(|| "none".to_string(), Value::to_string)
+}
+
+/// Outcome of replaying one fixture file
+#[derive(Debug, Clone, Serialize)]
+pub struct FixtureResult {
+    pub path: PathBuf,
+    pub name: String,
+    /// `None` when the fixture could not be loaded or run
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub actual: Option<HookOutput>,
+    pub mismatches: Vec<String>,
+    /// Whether `--update` rewrote the expectation
+    pub updated: bool,
+}
+
+impl FixtureResult {
+    pub fn passed(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+}
+
+/// Fixture files under `path`, sorted; `path` itself if it is a file
+pub fn find_fixtures(path: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+    if !path.is_dir() {
+        bail!("no fixtures at {}", path.display());
+    }
+    let mut fixtures: Vec<PathBuf> = WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(|entry| entry.into_path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    fixtures.sort();
+    Ok(fixtures)
+}
+
+pub fn load_fixture(path: &Path) -> anyhow::Result<HookFixture> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    serde_json::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+}
+
+/// Run `fixture` against the default registry, with the working directory
+/// written back as [`DIRECTORY_PLACEHOLDER`] in the output
+pub async fn run_fixture(fixture: &HookFixture) -> anyhow::Result<HookOutput> {
+    let workdir = tempfile::TempDir::new()?;
+    let directory = workdir.path().to_string_lossy().to_string();
+    for (relative, content) in &fixture.files {
+        let path = workdir.path().join(relative);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, content)?;
+    }
+
+    let defaults = default_hooks_in(workdir.path().to_path_buf());
+    let registry = if fixture.hooks.is_empty() {
+        defaults
+    } else {
+        let mut registry = HookRegistry::new();
+        for name in &fixture.hooks {
+            let Some(hook) = defaults.get(name) else {
+                bail!("unknown hook '{name}'");
+            };
+            registry.register(hook);
+        }
+        registry
+    };
+
+    let input: HookInput = substitute(&fixture.input, DIRECTORY_PLACEHOLDER, &directory)?;
+    let mut context = HookContext::new(fixture.context.session_id.clone(), directory.clone(), None);
+    context.data = substitute(&fixture.context.data, DIRECTORY_PLACEHOLDER, &directory)?;
+
+    let output = registry
+        .execute_hooks(fixture.event, &input, &context)
+        .await?;
+    substitute(&output, &directory, DIRECTORY_PLACEHOLDER)
+}
+
+/// Replay every fixture under `paths`. With `update`, fixtures whose output
+/// changed are rewritten to expect the new output instead of failing.
+pub async fn replay(paths: &[PathBuf], update: bool) -> anyhow::Result<Vec<FixtureResult>> {
+    let mut results = Vec::new();
+    for root in paths {
+        for path in find_fixtures(root)? {
+            results.push(replay_file(&path, update).await);
+        }
+    }
+    Ok(results)
+}
+
+async fn replay_file(path: &Path, update: bool) -> FixtureResult {
+    let file_name = path
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .unwrap_or_default();
+    let mut result = FixtureResult {
+        path: path.to_path_buf(),
+        name: file_name,
+        actual: None,
+        mismatches: Vec::new(),
+        updated: false,
+    };
+
+    let mut fixture = match load_fixture(path) {
+        Ok(fixture) => fixture,
+        Err(e) => {
+            result.mismatches.push(format!("{e:#}"));
+            return result;
+        }
+    };
+    if let Some(name) = &fixture.name {
+        result.name = name.clone();
+    }
+    let actual = match run_fixture(&fixture).await {
+        Ok(actual) => actual,
+        Err(e) => {
+            result.mismatches.push(format!("{e:#}"));
+            return result;
+        }
+    };
+
+    let mismatches = fixture.expected.mismatches(&actual);
+    if update && !mismatches.is_empty() {
+        fixture.expected = actual.clone().into();
+        match write_fixture(path, &fixture) {
+            Ok(()) => result.updated = true,
+            Err(e) => result.mismatches.push(format!("{e:#}")),
+        }
+    } else {
+        result.mismatches = mismatches;
+    }
+    result.actual = Some(actual);
+    result
+}
+
+fn write_fixture(path: &Path, fixture: &HookFixture) -> anyhow::Result<()> {
+    let mut content = serde_json::to_string_pretty(fixture)?;
+    content.push('\n');
+    fs::write(path, content).with_context(|| format!("writing {}", path.display()))
+}
+
+/// Round-trip `value` through JSON, replacing `from` with `to` in every string
+fn substitute<T: Serialize + DeserializeOwned>(
+    value: &T,
+    from: &str,
+    to: &str,
+) -> anyhow::Result<T> {
+    fn walk(value: &mut Value, from: &str, to: &str) {
+        match value {
+            Value::String(s) if s.contains(from) => *s = s.replace(from, to),
+            Value::Array(items) => items.iter_mut().for_each(|item| walk(item, from, to)),
+            Value::Object(map) => map.values_mut().for_each(|item| walk(item, from, to)),
+            _ => {}
+        }
+    }
+
+    let mut json = serde_json::to_value(value)?;
+    walk(&mut json, from, to);
+    Ok(serde_json::from_value(json)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fixture(json: Value) -> HookFixture {
+        serde_json::from_value(json).unwrap()
+    }
+
+    #[tokio::test]
+    async fn replays_keyword_detection() {
+        let fixture = fixture(serde_json::json!({
+            "event": "user-prompt-submit",
+            "hooks": ["keyword-detector"],
+            "input": {"prompt": "ultrawork: ship the release"},
+            "expected": {"message_contains": ["[ULTRAWORK MODE ACTIVATED]"]}
+        }));
+        let actual = run_fixture(&fixture).await.unwrap();
+        assert!(fixture.expected.mismatches(&actual).is_empty());
+
+        let stale = FixtureExpectation {
+            message: Some("[RALPH MODE ACTIVATED]".to_string()),
+            message_contains: Vec::new(),
+            ..FixtureExpectation::from(HookOutput::pass())
+        };
+        assert_eq!(stale.mismatches(&actual).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn rejects_unknown_hooks() {
+        let fixture = fixture(serde_json::json!({
+            "event": "stop",
+            "hooks": ["no-such-hook"],
+            "expected": {}
+        }));
+        assert!(run_fixture(&fixture).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn update_rewrites_expectation_with_placeholder() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("comment.json");
+        let fixture = fixture(serde_json::json!({
+            "event": "post-tool-use",
+            "hooks": ["comment-checker"],
+            "input": {
+                "tool_name": "Write",
+                "tool_input": {
+                    "file_path": "{{directory}}/main.py",
+                    "content": "# add two numbers\ndef add(a, b):\n    return a + b\n"
+                }
+            },
+            "expected": {}
+        }));
+        write_fixture(&path, &fixture).unwrap();
+
+        let results = replay(&[dir.path().to_path_buf()], false).await.unwrap();
+        assert!(!results[0].passed());
+
+        let results = replay(std::slice::from_ref(&path), true).await.unwrap();
+        assert!(results[0].updated && results[0].passed());
+        let results = replay(std::slice::from_ref(&path), false).await.unwrap();
+        assert!(results[0].passed());
+
+        let updated = load_fixture(&path).unwrap();
+        let message = updated.expected.message.unwrap();
+        assert!(!message.contains(&dir.path().to_string_lossy().to_string()));
+    }
+}
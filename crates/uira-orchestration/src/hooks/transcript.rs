@@ -0,0 +1,270 @@
+//! Reading the transcript a hook was given
+//!
+//! Stop hooks receive `transcript_path`, a JSONL file with one entry per
+//! message. User and assistant entries look like
+//! `{"type": "assistant", "message": {"content": [...], "usage": {...}}}`;
+//! subagent messages arrive wrapped as
+//! `{"type": "progress", "data": {"message": {"type": "assistant", ...}}}`.
+//! [`TranscriptReader`] parses both shapes into [`TranscriptTurn`]s and skips
+//! every other entry and any line that is not valid JSON, so hooks can ask
+//! for the last assistant message, the tools called or the tokens spent
+//! without re-implementing the parsing.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+
+/// Who sent a transcript message
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptRole {
+    User,
+    Assistant,
+}
+
+/// A tool call an assistant message made
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptToolCall {
+    pub id: Option<String>,
+    pub name: String,
+    pub input: Value,
+}
+
+/// Token usage reported for assistant messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct TranscriptTokens {
+    pub input: u64,
+    pub output: u64,
+    pub cache_read: u64,
+    pub cache_creation: u64,
+}
+
+impl TranscriptTokens {
+    pub fn total(&self) -> u64 {
+        self.input + self.output + self.cache_read + self.cache_creation
+    }
+
+    fn from_usage(usage: &Value) -> Self {
+        let count = |key: &str| usage.get(key).and_then(Value::as_u64).unwrap_or(0);
+        Self {
+            input: count("input_tokens"),
+            output: count("output_tokens"),
+            cache_read: count("cache_read_input_tokens"),
+            cache_creation: count("cache_creation_input_tokens"),
+        }
+    }
+
+    fn add(&mut self, other: &Self) {
+        self.input += other.input;
+        self.output += other.output;
+        self.cache_read += other.cache_read;
+        self.cache_creation += other.cache_creation;
+    }
+}
+
+/// One user or assistant message
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TranscriptTurn {
+    pub role: TranscriptRole,
+    /// Text blocks joined by newlines
+    pub text: String,
+    pub tool_calls: Vec<TranscriptToolCall>,
+    pub usage: Option<TranscriptTokens>,
+}
+
+/// Parsed transcript JSONL
+#[derive(Debug, Clone, Default)]
+pub struct TranscriptReader {
+    turns: Vec<TranscriptTurn>,
+}
+
+impl TranscriptReader {
+    /// Read and parse the transcript at `path`
+    pub fn open(path: impl AsRef<Path>) -> std::io::Result<Self> {
+        Ok(Self::parse(&std::fs::read_to_string(path)?))
+    }
+
+    /// Parse transcript JSONL, skipping entries that are not messages
+    pub fn parse(content: &str) -> Self {
+        let turns = content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .filter_map(|line| serde_json::from_str::<Value>(line).ok())
+            .filter_map(|entry| parse_turn(&entry))
+            .collect();
+        Self { turns }
+    }
+
+    /// Messages in transcript order
+    pub fn turns(&self) -> impl DoubleEndedIterator<Item = &TranscriptTurn> {
+        self.turns.iter()
+    }
+
+    /// Text of the last assistant message that had any
+    pub fn last_assistant_message(&self) -> Option<&str> {
+        self.turns
+            .iter()
+            .rev()
+            .filter(|turn| turn.role == TranscriptRole::Assistant)
+            .map(|turn| turn.text.as_str())
+            .find(|text| !text.is_empty())
+    }
+
+    /// Every tool call in transcript order
+    pub fn tool_calls(&self) -> impl DoubleEndedIterator<Item = &TranscriptToolCall> {
+        self.turns.iter().flat_map(|turn| turn.tool_calls.iter())
+    }
+
+    /// Input of the most recent call to `tool`
+    pub fn last_tool_input(&self, tool: &str) -> Option<&Value> {
+        self.tool_calls()
+            .rev()
+            .find(|call| call.name == tool)
+            .map(|call| &call.input)
+    }
+
+    /// Number of calls per tool name
+    pub fn tool_call_summary(&self) -> BTreeMap<&str, usize> {
+        let mut summary = BTreeMap::new();
+        for call in self.tool_calls() {
+            *summary.entry(call.name.as_str()).or_insert(0) += 1;
+        }
+        summary
+    }
+
+    /// Usage summed over every assistant message that reported it
+    pub fn token_totals(&self) -> TranscriptTokens {
+        let mut totals = TranscriptTokens::default();
+        for usage in self.turns.iter().filter_map(|turn| turn.usage.as_ref()) {
+            totals.add(usage);
+        }
+        totals
+    }
+}
+
+fn parse_turn(entry: &Value) -> Option<TranscriptTurn> {
+    let entry = match entry.get("type").and_then(Value::as_str)? {
+        "progress" => entry.get("data")?.get("message")?,
+        _ => entry,
+    };
+    let role = match entry.get("type").and_then(Value::as_str)? {
+        "user" => TranscriptRole::User,
+        "assistant" => TranscriptRole::Assistant,
+        _ => return None,
+    };
+    let message = entry.get("message")?;
+
+    let mut texts = Vec::new();
+    let mut tool_calls = Vec::new();
+    match message.get("content") {
+        Some(Value::String(text)) => texts.push(text.as_str()),
+        Some(Value::Array(blocks)) => {
+            for block in blocks {
+                match block.get("type").and_then(Value::as_str) {
+                    Some("text") => texts.extend(block.get("text").and_then(Value::as_str)),
+                    Some("tool_use") => {
+                        let Some(name) = block.get("name").and_then(Value::as_str) else {
+                            continue;
+                        };
+                        tool_calls.push(TranscriptToolCall {
+                            id: block.get("id").and_then(Value::as_str).map(String::from),
+                            name: name.to_string(),
+                            input: block.get("input").cloned().unwrap_or(Value::Null),
+                        });
+                    }
+                    _ => {}
+                }
+            }
+        }
+        _ => {}
+    }
+
+    Some(TranscriptTurn {
+        role,
+        text: texts.join("\n"),
+        tool_calls,
+        usage: message.get("usage").map(TranscriptTokens::from_usage),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    fn transcript() -> String {
+        [
+            json!({ "type": "summary", "summary": "Fix the parser" }),
+            json!({ "type": "user", "message": { "role": "user", "content": "Fix the parser" } }),
+            json!({ "type": "assistant", "message": {
+                "content": [
+                    { "type": "text", "text": "Reading it first." },
+                    { "type": "tool_use", "id": "t1", "name": "Read", "input": { "file_path": "src/parser.rs" } }
+                ],
+                "usage": { "input_tokens": 100, "output_tokens": 20, "cache_read_input_tokens": 50 }
+            }}),
+            json!({ "type": "user", "message": { "content": [
+                { "type": "tool_result", "tool_use_id": "t1", "content": "fn parse() {}" }
+            ]}}),
+            json!({ "type": "progress", "data": { "message": { "type": "assistant", "message": {
+                "content": [{ "type": "tool_use", "id": "t2", "name": "Edit", "input": { "file_path": "src/parser.rs" } }],
+                "usage": { "input_tokens": 30, "output_tokens": 10 }
+            }}}}),
+            json!({ "type": "assistant", "message": {
+                "content": [
+                    { "type": "tool_use", "id": "t3", "name": "Edit", "input": { "file_path": "src/lib.rs" } },
+                    { "type": "text", "text": "Done." }
+                ]
+            }}),
+        ]
+        .iter()
+        .map(Value::to_string)
+        .chain(["not json".to_string(), String::new()])
+        .collect::<Vec<_>>()
+        .join("\n")
+    }
+
+    #[test]
+    fn test_turns_skip_non_message_entries() {
+        let reader = TranscriptReader::parse(&transcript());
+        let roles: Vec<_> = reader.turns().map(|turn| turn.role).collect();
+        assert_eq!(
+            roles,
+            [
+                TranscriptRole::User,
+                TranscriptRole::Assistant,
+                TranscriptRole::User,
+                TranscriptRole::Assistant,
+                TranscriptRole::Assistant,
+            ]
+        );
+        assert_eq!(reader.turns().next().unwrap().text, "Fix the parser");
+    }
+
+    #[test]
+    fn test_last_assistant_message() {
+        let reader = TranscriptReader::parse(&transcript());
+        assert_eq!(reader.last_assistant_message(), Some("Done."));
+        assert_eq!(TranscriptReader::parse("").last_assistant_message(), None);
+    }
+
+    #[test]
+    fn test_tool_calls_and_tokens() {
+        let reader = TranscriptReader::parse(&transcript());
+        let summary = reader.tool_call_summary();
+        assert_eq!(summary.get("Read"), Some(&1));
+        assert_eq!(summary.get("Edit"), Some(&2));
+        assert_eq!(
+            reader.last_tool_input("Edit"),
+            Some(&json!({ "file_path": "src/lib.rs" }))
+        );
+
+        let tokens = reader.token_totals();
+        assert_eq!(tokens.input, 130);
+        assert_eq!(tokens.output, 30);
+        assert_eq!(tokens.cache_read, 50);
+        assert_eq!(tokens.total(), 210);
+    }
+}
@@ -0,0 +1,433 @@
+//! The `messages-transform` pipeline
+//!
+//! Hooks registered for [`HookEvent::MessagesTransform`] rewrite the message
+//! array about to be sent to the model: injecting context, stripping noise or
+//! reordering. Unlike other events, their outputs are chained: each hook gets
+//! the array as the previous hook left it, in `tool_input`, and returns its
+//! rewrite in `modified_input`, either as an array or as an object with a
+//! `messages` array. A hook that leaves `modified_input` unset passes the
+//! array through unchanged.
+//!
+//! Hooks run by descending [`Hook::priority`], ties broken by name, so the
+//! order never depends on registration order. A hook that fails is skipped;
+//! one that returns `continue: false` ends the pipeline with its reason.
+//!
+//! A dry run executes every hook and records what each changed, but hands
+//! back the original messages.
+
+use std::sync::Arc;
+
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use super::hook::{Hook, HookContext};
+use super::registry::HookRegistry;
+use super::types::{HookEvent, HookInput, HookOutput};
+
+/// How one hook changed the message array, by message id
+///
+/// Messages are identified by `info.id` or `id`, falling back to their
+/// position as `#<index>`.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MessagesDelta {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub added: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub removed: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub modified: Vec<String>,
+    /// Messages present before and after changed their relative order
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub reordered: bool,
+}
+
+impl MessagesDelta {
+    /// Compare two message arrays
+    pub fn between(before: &[Value], after: &[Value]) -> Self {
+        let before_ids = message_ids(before);
+        let after_ids = message_ids(after);
+
+        let added = after_ids
+            .iter()
+            .filter(|id| !before_ids.contains(id))
+            .cloned()
+            .collect();
+        let removed = before_ids
+            .iter()
+            .filter(|id| !after_ids.contains(id))
+            .cloned()
+            .collect();
+        let modified = after_ids
+            .iter()
+            .zip(after)
+            .filter_map(|(id, message)| {
+                let index = before_ids.iter().position(|before_id| before_id == id)?;
+                (before[index] != *message).then(|| id.clone())
+            })
+            .collect();
+
+        let kept_before: Vec<&String> = before_ids
+            .iter()
+            .filter(|id| after_ids.contains(id))
+            .collect();
+        let kept_after: Vec<&String> = after_ids
+            .iter()
+            .filter(|id| before_ids.contains(id))
+            .collect();
+
+        Self {
+            added,
+            removed,
+            modified,
+            reordered: kept_before != kept_after,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.removed.is_empty()
+            && self.modified.is_empty()
+            && !self.reordered
+    }
+}
+
+impl std::fmt::Display for MessagesDelta {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.is_empty() {
+            return f.write_str("no change");
+        }
+        let mut parts = Vec::new();
+        for (label, ids) in [
+            ("added", &self.added),
+            ("removed", &self.removed),
+            ("modified", &self.modified),
+        ] {
+            if !ids.is_empty() {
+                parts.push(format!("{} {}", label, ids.join(", ")));
+            }
+        }
+        if self.reordered {
+            parts.push("reordered".to_string());
+        }
+        f.write_str(&parts.join("; "))
+    }
+}
+
+/// What one hook did during a pipeline run
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformStep {
+    pub hook: String,
+    pub priority: i32,
+    pub delta: MessagesDelta,
+    /// Set when the hook failed or returned something other than messages;
+    /// the array is passed on unchanged
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Result of running the pipeline over a message array
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TransformOutcome {
+    /// The transformed messages, or the original ones on a dry run
+    pub messages: Vec<Value>,
+    pub steps: Vec<TransformStep>,
+    /// Reason given by the hook that ended the pipeline
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blocked: Option<String>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub dry_run: bool,
+}
+
+impl TransformOutcome {
+    /// Whether any hook changed the messages
+    pub fn changed(&self) -> bool {
+        self.steps.iter().any(|step| !step.delta.is_empty())
+    }
+}
+
+/// Runs the `messages-transform` hooks of a registry in order
+pub struct MessagesTransformPipeline<'a> {
+    registry: &'a HookRegistry,
+    dry_run: bool,
+}
+
+impl<'a> MessagesTransformPipeline<'a> {
+    pub fn new(registry: &'a HookRegistry) -> Self {
+        Self {
+            registry,
+            dry_run: false,
+        }
+    }
+
+    /// Record each hook's changes without applying them
+    pub fn dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// The hooks in the order they run
+    pub fn hooks(&self) -> Vec<Arc<dyn Hook>> {
+        let mut hooks = self
+            .registry
+            .get_hooks_for_event(HookEvent::MessagesTransform);
+        hooks.sort_by(|a, b| {
+            b.priority()
+                .cmp(&a.priority())
+                .then_with(|| a.name().cmp(b.name()))
+        });
+        hooks
+    }
+
+    /// Pass `messages` through every hook; `input` supplies the session and
+    /// any other fields the hooks read
+    pub async fn run(
+        &self,
+        messages: Vec<Value>,
+        input: &HookInput,
+        context: &HookContext,
+    ) -> TransformOutcome {
+        let mut current = messages.clone();
+        let mut steps = Vec::new();
+        let mut blocked = None;
+
+        for hook in self.hooks() {
+            let mut hook_input = input.clone();
+            hook_input.tool_input = Some(Value::Array(current.clone()));
+
+            let mut step = TransformStep {
+                hook: hook.name().to_string(),
+                priority: hook.priority(),
+                delta: MessagesDelta::default(),
+                error: None,
+            };
+            match hook
+                .execute(HookEvent::MessagesTransform, &hook_input, context)
+                .await
+            {
+                Ok(output) => {
+                    match output.modified_input.as_ref().map(messages_in) {
+                        Some(Some(rewritten)) => {
+                            step.delta = MessagesDelta::between(&current, &rewritten);
+                            current = rewritten;
+                        }
+                        Some(None) => {
+                            step.error =
+                                Some("modified_input does not contain a message array".to_string())
+                        }
+                        None => {}
+                    }
+                    if !output.should_continue {
+                        blocked = Some(output.reason.unwrap_or_default());
+                        steps.push(step);
+                        break;
+                    }
+                }
+                Err(e) => step.error = Some(e.to_string()),
+            }
+            steps.push(step);
+        }
+
+        TransformOutcome {
+            messages: if self.dry_run { messages } else { current },
+            steps,
+            blocked,
+            dry_run: self.dry_run,
+        }
+    }
+}
+
+/// The message array in a hook input or output: the value itself when it is
+/// an array, otherwise its `messages` field
+pub fn messages_in(value: &Value) -> Option<Vec<Value>> {
+    match value {
+        Value::Array(messages) => Some(messages.clone()),
+        Value::Object(fields) => fields.get("messages")?.as_array().cloned(),
+        _ => None,
+    }
+}
+
+/// Run the pipeline for [`HookRegistry::execute_hooks`], taking the messages
+/// from `tool_input` or `extra["messages"]` and returning the rewrite in the
+/// same shape. `None` when the input carries no messages.
+pub(crate) async fn execute(
+    registry: &HookRegistry,
+    input: &HookInput,
+    context: &HookContext,
+) -> Option<HookOutput> {
+    let original = input
+        .tool_input
+        .as_ref()
+        .or_else(|| input.extra.get("messages"))?;
+    let messages = messages_in(original)?;
+
+    let outcome = MessagesTransformPipeline::new(registry)
+        .run(messages, input, context)
+        .await;
+    if let Some(reason) = outcome.blocked {
+        return Some(HookOutput::block_with_reason(reason));
+    }
+    if !outcome.changed() {
+        return Some(HookOutput::pass());
+    }
+
+    let rewritten = Value::Array(outcome.messages);
+    let modified_input = match original {
+        Value::Object(fields) => {
+            let mut fields = fields.clone();
+            fields.insert("messages".to_string(), rewritten);
+            Value::Object(fields)
+        }
+        _ => rewritten,
+    };
+    Some(HookOutput {
+        modified_input: Some(modified_input),
+        ..HookOutput::pass()
+    })
+}
+
+fn message_ids(messages: &[Value]) -> Vec<String> {
+    messages
+        .iter()
+        .enumerate()
+        .map(|(index, message)| {
+            message
+                .pointer("/info/id")
+                .or_else(|| message.get("id"))
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("#{}", index))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::hooks::hook::HookResult;
+    use async_trait::async_trait;
+    use serde_json::json;
+
+    /// Appends a message with its own name as id
+    struct Appender {
+        name: &'static str,
+        priority: i32,
+    }
+
+    #[async_trait]
+    impl Hook for Appender {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn events(&self) -> &[HookEvent] {
+            &[HookEvent::MessagesTransform]
+        }
+
+        fn priority(&self) -> i32 {
+            self.priority
+        }
+
+        async fn execute(
+            &self,
+            _event: HookEvent,
+            input: &HookInput,
+            _context: &HookContext,
+        ) -> HookResult {
+            let mut messages = messages_in(input.tool_input.as_ref().unwrap()).unwrap();
+            messages.push(json!({ "id": self.name }));
+            Ok(HookOutput {
+                modified_input: Some(json!({ "messages": messages })),
+                ..HookOutput::pass()
+            })
+        }
+    }
+
+    fn registry(hooks: &[(&'static str, i32)]) -> HookRegistry {
+        let mut registry = HookRegistry::new();
+        for &(name, priority) in hooks {
+            registry.register(Arc::new(Appender { name, priority }));
+        }
+        registry
+    }
+
+    fn context() -> HookContext {
+        HookContext::new(None, ".".to_string(), None)
+    }
+
+    fn ids(messages: &[Value]) -> Vec<&str> {
+        messages.iter().map(|m| m["id"].as_str().unwrap()).collect()
+    }
+
+    #[tokio::test]
+    async fn chains_hooks_by_priority_then_name() {
+        let registry = registry(&[("zeta", 0), ("alpha", 0), ("first", 10)]);
+        let outcome = MessagesTransformPipeline::new(&registry)
+            .run(
+                vec![json!({ "id": "u1" })],
+                &HookInput::default(),
+                &context(),
+            )
+            .await;
+
+        assert_eq!(ids(&outcome.messages), vec!["u1", "first", "alpha", "zeta"]);
+        let order: Vec<&str> = outcome.steps.iter().map(|s| s.hook.as_str()).collect();
+        assert_eq!(order, vec!["first", "alpha", "zeta"]);
+        assert_eq!(outcome.steps[1].delta.added, vec!["alpha"]);
+        assert!(outcome.blocked.is_none());
+    }
+
+    #[tokio::test]
+    async fn dry_run_traces_without_applying() {
+        let registry = registry(&[("inject", 0)]);
+        let outcome = MessagesTransformPipeline::new(&registry)
+            .dry_run(true)
+            .run(
+                vec![json!({ "id": "u1" })],
+                &HookInput::default(),
+                &context(),
+            )
+            .await;
+
+        assert_eq!(ids(&outcome.messages), vec!["u1"]);
+        assert!(outcome.changed());
+        assert_eq!(outcome.steps[0].delta.to_string(), "added inject");
+    }
+
+    #[test]
+    fn delta_reports_removed_modified_and_reordered() {
+        let before = vec![
+            json!({ "info": { "id": "a" }, "parts": [] }),
+            json!({ "info": { "id": "b" }, "parts": [] }),
+            json!({ "info": { "id": "c" }, "parts": [] }),
+        ];
+        let after = vec![
+            json!({ "info": { "id": "c" }, "parts": [] }),
+            json!({ "info": { "id": "a" }, "parts": [{ "type": "text" }] }),
+        ];
+        let delta = MessagesDelta::between(&before, &after);
+        assert_eq!(delta.removed, vec!["b"]);
+        assert_eq!(delta.modified, vec!["a"]);
+        assert!(delta.reordered);
+        assert!(delta.added.is_empty());
+    }
+
+    #[tokio::test]
+    async fn execute_hooks_returns_rewrite_in_input_shape() {
+        let registry = registry(&[("inject", 0)]);
+        let input = HookInput {
+            tool_input: Some(json!({ "messages": [{ "id": "u1" }], "model": "m" })),
+            ..HookInput::default()
+        };
+        let output = registry
+            .execute_hooks(HookEvent::MessagesTransform, &input, &context())
+            .await
+            .unwrap();
+
+        let modified = output.modified_input.unwrap();
+        assert_eq!(modified["model"], "m");
+        assert_eq!(
+            ids(modified["messages"].as_array().unwrap()),
+            vec!["u1", "inject"]
+        );
+    }
+}
@@ -1,6 +1,8 @@
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 
+use super::transcript::TranscriptReader;
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum HookEvent {
@@ -99,7 +101,7 @@ pub struct Message {
     pub content: Option<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct HookInput {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub session_id: Option<String>,
@@ -171,58 +173,16 @@ impl HookInput {
         })
     }
 
+    /// Parsed transcript, `None` when there is no readable transcript file
+    pub fn transcript(&self) -> Option<TranscriptReader> {
+        TranscriptReader::open(self.transcript_path.as_ref()?).ok()
+    }
+
     /// Get the last assistant text response from transcript JSONL
-    ///
-    /// Parses the transcript file (if available) and extracts the last
-    /// assistant message's text content.
     pub fn get_last_assistant_response(&self) -> Option<String> {
-        let transcript_path = self.transcript_path.as_ref()?;
-        let path = std::path::Path::new(transcript_path);
-        if !path.exists() {
-            return None;
-        }
-
-        let content = std::fs::read_to_string(path).ok()?;
-
-        // Parse JSONL from end to find last assistant message
-        for line in content.lines().rev() {
-            if line.trim().is_empty() {
-                continue;
-            }
-            if let Ok(entry) = serde_json::from_str::<serde_json::Value>(line) {
-                if entry.get("type").and_then(|v| v.as_str()) == Some("progress") {
-                    if let Some(data) = entry.get("data") {
-                        if let Some(msg) = data.get("message") {
-                            if msg.get("type").and_then(|v| v.as_str()) == Some("assistant") {
-                                if let Some(content) = msg
-                                    .get("message")
-                                    .and_then(|m| m.get("content"))
-                                    .and_then(|c| c.as_array())
-                                {
-                                    let texts: Vec<&str> = content
-                                        .iter()
-                                        .filter_map(|item| {
-                                            if item.get("type").and_then(|t| t.as_str())
-                                                == Some("text")
-                                            {
-                                                item.get("text").and_then(|t| t.as_str())
-                                            } else {
-                                                None
-                                            }
-                                        })
-                                        .collect();
-
-                                    if !texts.is_empty() {
-                                        return Some(texts.join("\n"));
-                                    }
-                                }
-                            }
-                        }
-                    }
-                }
-            }
-        }
-        None
+        self.transcript()?
+            .last_assistant_message()
+            .map(String::from)
     }
 }
 
@@ -9,19 +9,24 @@ pub use agents::{
     tool_restrictions, types,
 };
 pub use agents::{
-    get_agent_definitions, get_agent_definitions_with_config, AgentCategory, AgentConfig,
-    AgentCost, AgentFactory, AgentOverrideConfig, AgentOverrides, AgentPromptMetadata,
-    AgentRegistry, DelegationTrigger, ModelRegistry, ModelTier, ModelType, OrchestratorPersonality,
-    PlanningPipeline, PlanningStage, PromptLoader, PromptSource, RoutingTier, TierBuilder,
-    ToolRestrictions, ToolRestrictionsRegistry,
+    get_agent_definitions, get_agent_definitions_with_config, get_project_agent_definitions,
+    AgentCategory, AgentConfig, AgentCost, AgentFactory, AgentOverrideConfig, AgentOverrides,
+    AgentPromptMetadata, AgentRegistry, DelegationContext, DelegationTrigger, ModelMetadata,
+    ModelRegistry, ModelTier, ModelType, OrchestratorPersonality, PlanningPipeline, PlanningStage,
+    PromptLoader, PromptSource, RoutingTier, TierBuilder, ToolRestrictions,
+    ToolRestrictionsRegistry,
 };
 pub use features::{background_agent, dynamic_prompt_builder, model_routing, uira_state};
 pub use features::{
-    build_default_orchestrator_prompt, build_dynamic_orchestrator_prompt,
-    build_environment_context, builtin_agent_metadata, register_environment_context,
-    AvailableAgent, AvailableDelegationCategory, AvailableSkill,
+    build_budgeted_orchestrator_prompt, build_default_orchestrator_prompt,
+    build_dynamic_orchestrator_prompt, build_environment_context, builtin_agent_metadata,
+    register_environment_context, register_repo_map_context, AvailableAgent,
+    AvailableDelegationCategory, AvailableSkill, DEFAULT_ORCHESTRATOR_PROMPT_BUDGET,
+};
+pub use features::{
+    IssueAutopilot, KeywordDetector, KeywordPattern, Notifier, RepoMap, StateManager,
+    WorkspaceIndex,
 };
-pub use features::{KeywordDetector, KeywordPattern, StateManager};
 pub use hooks::{
     create_hook_event_adapter, default_hooks, GoalCheckResult, GoalRunner, Hook, HookEventAdapter,
     HookRegistry, MemoryCaptureAdapter, MemoryRecallAdapter, VerificationResult,
@@ -35,12 +40,14 @@ pub use sdk::{
 };
 pub use tools::{
     builtin_tools, create_builtin_router, register_builtins, register_builtins_with_todos,
-    register_builtins_without_todos, AgentExecutor, ApprovalCache, ApprovalCacheFile, ApprovalKey,
-    AstToolProvider, BashTool, BoxedTool, CacheDecision, CachedApproval, CommentChecker,
-    DelegationToolProvider, EditTool, FetchUrlTool, FunctionTool, GlobTool, GrepTool, LspClient,
-    LspClientImpl, LspServerConfig, LspToolProvider, McpToolProvider, MemoryForgetTool,
-    MemoryProfileTool, MemorySearchTool, MemoryStoreTool, PendingApproval, ReadTool, RunOptions,
-    TodoReadTool, TodoSessionInfo, TodoStore, TodoWriteTool, Tool, ToolCallRuntime, ToolContent,
-    ToolContext, ToolDefinition, ToolError, ToolFuture, ToolHandler, ToolInput, ToolOrchestrator,
-    ToolOutput, ToolProvider, ToolRegistry, ToolRouter, WebSearchTool, WriteTool,
+    register_builtins_without_todos, run_background_task, AgentExecutor, ApprovalCache,
+    ApprovalCacheFile, ApprovalKey, ApprovalPolicy, AstToolProvider, BashTool, BoxedTool,
+    CacheDecision, CachedApproval, CiToolProvider, CommentChecker, DelegationToolProvider,
+    DocumentExtractTool, EditTool, FetchUrlTool, FileTracker, FunctionTool, GitHubToolProvider,
+    GlobTool, GrepTool, LspClient, LspClientImpl, LspServerConfig, LspToolProvider,
+    McpToolProvider, MemoryForgetTool, MemoryProfileTool, MemorySearchTool, MemoryStoreTool,
+    PendingApproval, ReadTool, RunOptions, TodoReadTool, TodoSessionInfo, TodoStore, TodoWriteTool,
+    Tool, ToolCallRuntime, ToolClass, ToolContent, ToolContext, ToolDefinition, ToolError,
+    ToolFuture, ToolHandler, ToolInput, ToolOrchestrator, ToolOutput, ToolProvider, ToolRegistry,
+    ToolRouter, WebSearchTool, WorkspaceRoot, WriteTool,
 };
@@ -109,6 +109,14 @@ pub struct ExaConfig {
 pub struct Context7Config {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub enabled: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub api_key: Option<String>,
+    /// Override for the Context7 API root (default `https://context7.com/api/v1`)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+    /// Cache directory for docs_lookup, relative to the project root
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub cache_dir: Option<String>,
 }
 
 /// Permission settings
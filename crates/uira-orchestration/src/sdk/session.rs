@@ -302,9 +302,11 @@ continue working until everything is done."#
                 });
             }
 
-            if uira.mcp.contains_key("context7") {
+            if let Some(context7) = uira.mcp.get("context7") {
                 config.context7 = Some(Context7Config {
                     enabled: Some(true),
+                    api_key: context7.env.get("CONTEXT7_API_KEY").cloned(),
+                    ..Default::default()
                 });
             }
 
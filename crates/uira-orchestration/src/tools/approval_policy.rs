@@ -0,0 +1,717 @@
+//! Rule-based auto-approval for tool calls
+//!
+//! Sits above the [`ApprovalCache`](super::ApprovalCache): a call that needs
+//! approval is classified as read-only, workspace-write, network or
+//! destructive, and runs without prompting when the policy for the session's
+//! sandbox mode lists its class. Destructive calls, which include commands
+//! the classifier doesn't recognize, always go to the user.
+
+use std::fmt;
+
+use uira_core::schema::{AutoApproveSettings, ToolClassConfig};
+use uira_security::{is_dangerous_command, shell_write_targets, SandboxPolicy};
+
+use crate::tools::workspace::{normalize_path, root_for};
+use crate::tools::ToolContext;
+
+/// What a tool call can affect, from least to most dangerous
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ToolClass {
+    ReadOnly,
+    WorkspaceWrite,
+    Network,
+    Destructive,
+}
+
+impl ToolClass {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read-only",
+            Self::WorkspaceWrite => "workspace-write",
+            Self::Network => "network",
+            Self::Destructive => "destructive",
+        }
+    }
+}
+
+impl fmt::Display for ToolClass {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl From<ToolClassConfig> for ToolClass {
+    fn from(class: ToolClassConfig) -> Self {
+        match class {
+            ToolClassConfig::ReadOnly => Self::ReadOnly,
+            ToolClassConfig::WorkspaceWrite => Self::WorkspaceWrite,
+            ToolClassConfig::Network => Self::Network,
+            ToolClassConfig::Destructive => Self::Destructive,
+        }
+    }
+}
+
+/// Tools that only read
+const READ_ONLY_TOOLS: &[&str] = &["Read", "Glob", "Grep", "TodoRead", "ast_search"];
+
+/// Tools that modify the file named in their input, or files across the
+/// workspace when they name none
+const WRITE_TOOLS: &[&str] = &[
+    "Write",
+    "Edit",
+    "MultiEdit",
+    "NotebookEdit",
+    "ast_replace",
+    "ast_lint",
+    "lsp_rename",
+    "lsp_code_actions",
+    "lsp_format_range",
+];
+
+/// Provider-backed tools that publish to a remote service
+const NETWORK_TOOLS: &[&str] = &["github_create_pr", "github_post_review"];
+
+const READ_ONLY_COMMANDS: &[&str] = &[
+    "ls", "pwd", "cat", "head", "tail", "wc", "grep", "rg", "find", "echo", "which", "file",
+    "stat", "du", "df", "tree", "sort", "uniq", "diff", "jq", "printenv", "date", "uname",
+    "whoami",
+];
+
+/// Commands that only change files under the working directory. Anything
+/// not listed here or in the other lists is treated as destructive. That
+/// includes interpreters running inline code like `python -c`, build runners
+/// like `make` that run whatever the build files say, tools that load JS
+/// config like `eslint`, and `sed`, whose `e` command runs a shell.
+const WRITE_COMMANDS: &[&str] = &[
+    "mkdir", "touch", "cp", "mv", "ln", "patch", "tar", "zip", "unzip", "gzip", "gunzip",
+    "rustfmt", "gofmt", "tsc", "git",
+];
+
+/// `tar` options that run a program
+const TAR_EXEC_OPTIONS: &[&str] = &[
+    "-I",
+    "-F",
+    "--use-compress-program",
+    "--to-command",
+    "--checkpoint-action",
+    "--info-script",
+    "--new-volume-script",
+];
+
+/// Commands that run the command given in their arguments, with the options
+/// of each that take a value
+const WRAPPERS: &[(&str, &[&str])] = &[
+    ("env", &["-u", "--unset", "-C", "--chdir"]),
+    ("nice", &["-n", "--adjustment"]),
+    ("nohup", &[]),
+    ("time", &["-f", "--format", "-o", "--output"]),
+    ("timeout", &["-s", "--signal", "-k", "--kill-after"]),
+    ("command", &[]),
+    (
+        "xargs",
+        &[
+            "-a",
+            "-d",
+            "-E",
+            "-I",
+            "-L",
+            "-n",
+            "-P",
+            "-s",
+            "--arg-file",
+            "--delimiter",
+            "--max-args",
+            "--max-lines",
+            "--max-procs",
+            "--max-chars",
+        ],
+    ),
+];
+
+/// Git subcommands that change the repository or working tree without
+/// running anything but git's own hooks. Others, like `submodule foreach`,
+/// `bisect run` or aliases, can run any program.
+const WRITE_GIT: &[&str] = &[
+    "add",
+    "commit",
+    "checkout",
+    "switch",
+    "branch",
+    "merge",
+    "stash",
+    "tag",
+    "reset",
+    "mv",
+    "rm",
+    "cherry-pick",
+    "revert",
+    "init",
+    "apply",
+    "am",
+    "notes",
+    "format-patch",
+];
+
+const READ_ONLY_GIT: &[&str] = &[
+    "status",
+    "log",
+    "diff",
+    "show",
+    "blame",
+    "rev-parse",
+    "ls-files",
+    "describe",
+    "shortlog",
+    "grep",
+];
+
+const NETWORK_COMMANDS: &[&str] = &[
+    "curl", "wget", "ssh", "scp", "sftp", "rsync", "ftp", "nc", "netcat", "telnet", "ping", "gh",
+];
+
+const NETWORK_GIT: &[&str] = &["push", "pull", "fetch", "clone", "ls-remote"];
+
+/// Package managers, whose install and publish subcommands reach registries
+const PACKAGE_MANAGERS: &[&str] = &[
+    "npm", "pnpm", "yarn", "bun", "pip", "pip3", "cargo", "gem", "go",
+];
+
+/// Package manager subcommands that run no project or package code. The
+/// rest, like `run`, `exec`, `x`, `dlx`, `test` or `build`, can run anything.
+const SAFE_PACKAGE_COMMANDS: &[&str] = &[
+    "list",
+    "ls",
+    "tree",
+    "why",
+    "freeze",
+    "show",
+    "version",
+    "--version",
+    "-V",
+    "help",
+    "--help",
+    "fmt",
+    "metadata",
+];
+
+const NETWORK_PACKAGE_COMMANDS: &[&str] = &[
+    "install", "i", "add", "publish", "update", "upgrade", "get", "pull", "push", "login",
+];
+
+const DESTRUCTIVE_COMMANDS: &[&str] = &[
+    "rm", "rmdir", "shred", "dd", "mkfs", "chmod", "chown", "kill", "killall", "pkill", "truncate",
+    "sudo", "su", "doas", "reboot", "shutdown", "eval", "exec",
+];
+
+/// Classify a tool call that needs approval. Unknown tools are treated as
+/// destructive so they are never approved silently.
+pub fn classify(
+    tool_name: &str,
+    input: &serde_json::Value,
+    requires_network: bool,
+    ctx: &ToolContext,
+) -> ToolClass {
+    if requires_network || NETWORK_TOOLS.contains(&tool_name) {
+        return ToolClass::Network;
+    }
+    if tool_name == "Bash" {
+        let command = input
+            .get("command")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        return classify_command(command, ctx);
+    }
+    if READ_ONLY_TOOLS.contains(&tool_name) {
+        return ToolClass::ReadOnly;
+    }
+    if WRITE_TOOLS.contains(&tool_name) {
+        let path = ["file_path", "filePath", "path"]
+            .iter()
+            .find_map(|field| input.get(*field).and_then(|v| v.as_str()));
+        return match path {
+            Some(path) if !in_workspace(path, ctx) => ToolClass::Destructive,
+            _ => ToolClass::WorkspaceWrite,
+        };
+    }
+    ToolClass::Destructive
+}
+
+/// Whether `tool_name` can change files on disk: Bash and the write tools
+pub fn writes_files(tool_name: &str) -> bool {
+    tool_name == "Bash" || WRITE_TOOLS.contains(&tool_name)
+}
+
+/// Whether `raw` resolves inside the working directory or a workspace root
+fn in_workspace(raw: &str, ctx: &ToolContext) -> bool {
+    let path = ctx.resolve_path(raw);
+    path.starts_with(normalize_path(&ctx.cwd)) || root_for(&path, &ctx.workspaces).is_some()
+}
+
+/// Whether a file a shell command writes stays in the workspace. Home and
+/// variable paths are never expanded, so they count as outside.
+fn shell_target_in_workspace(target: &str, ctx: &ToolContext) -> bool {
+    !target.starts_with('~') && !target.contains('$') && in_workspace(target, ctx)
+}
+
+/// Classify a shell command by its most dangerous segment. Writing any
+/// file outside the workspace is destructive, as for the Write tool.
+pub fn classify_command(command: &str, ctx: &ToolContext) -> ToolClass {
+    // Command and process substitutions can run anything, so there is
+    // nothing to classify
+    if ["$(", "`", "<(", ">("]
+        .iter()
+        .any(|substitution| command.contains(substitution))
+    {
+        return ToolClass::Destructive;
+    }
+    if shell_write_targets(command)
+        .iter()
+        .any(|target| !shell_target_in_workspace(target, ctx))
+    {
+        return ToolClass::Destructive;
+    }
+
+    command
+        .split(['\n', ';', '|', '&'])
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .map(|segment| classify_segment(segment, ctx))
+        .max()
+        .unwrap_or(ToolClass::ReadOnly)
+}
+
+fn classify_segment(segment: &str, ctx: &ToolContext) -> ToolClass {
+    let words: Vec<&str> = segment.split_whitespace().collect();
+    let Some(words) = unwrap_command(&words) else {
+        return ToolClass::Destructive;
+    };
+    let Some(&program) = words.first() else {
+        return ToolClass::ReadOnly;
+    };
+    let program = program.rsplit('/').next().unwrap_or(program);
+    if WRAPPERS.iter().any(|(wrapper, _)| *wrapper == program) {
+        return ToolClass::ReadOnly;
+    }
+    let args = &words[1..];
+    let owned: Vec<String> = std::iter::once(program)
+        .chain(args.iter().copied())
+        .map(String::from)
+        .collect();
+
+    if is_dangerous_command(&owned) || DESTRUCTIVE_COMMANDS.contains(&program) {
+        return ToolClass::Destructive;
+    }
+
+    if program == "git" {
+        // Config can point hooksPath or sshCommand at any program, and
+        // another repository can have hooks of its own
+        let Some(subcommand) = git_subcommand(args, ctx) else {
+            return ToolClass::Destructive;
+        };
+        let force = args
+            .iter()
+            .any(|arg| matches!(*arg, "-f" | "--force" | "--hard" | "-D"));
+        if force || matches!(subcommand, "clean" | "rebase" | "restore" | "config") {
+            return ToolClass::Destructive;
+        }
+        if NETWORK_GIT.contains(&subcommand) {
+            return ToolClass::Network;
+        }
+        if READ_ONLY_GIT.contains(&subcommand) {
+            return ToolClass::ReadOnly;
+        }
+        if WRITE_GIT.contains(&subcommand) {
+            return ToolClass::WorkspaceWrite;
+        }
+        return ToolClass::Destructive;
+    }
+
+    let subcommand = args.first().copied().unwrap_or_default();
+    if NETWORK_COMMANDS.contains(&program) {
+        return ToolClass::Network;
+    }
+    if PACKAGE_MANAGERS.contains(&program) {
+        if NETWORK_PACKAGE_COMMANDS.contains(&subcommand) {
+            return ToolClass::Network;
+        }
+        if SAFE_PACKAGE_COMMANDS.contains(&subcommand) {
+            return ToolClass::WorkspaceWrite;
+        }
+        return ToolClass::Destructive;
+    }
+
+    let tar_runs_program = program == "tar"
+        && args.iter().any(|arg| {
+            TAR_EXEC_OPTIONS.iter().any(|option| {
+                arg.strip_prefix(option)
+                    .is_some_and(|rest| rest.is_empty() || rest.starts_with('='))
+            })
+        });
+    if tar_runs_program {
+        return ToolClass::Destructive;
+    }
+
+    let writes_file = segment.contains('>') || !shell_write_targets(segment).is_empty();
+    let find_writes = program == "find"
+        && args
+            .iter()
+            .any(|arg| matches!(*arg, "-delete" | "-exec" | "-execdir"));
+    if find_writes {
+        return ToolClass::Destructive;
+    }
+    if READ_ONLY_COMMANDS.contains(&program) && !writes_file {
+        return ToolClass::ReadOnly;
+    }
+    if READ_ONLY_COMMANDS.contains(&program) || WRITE_COMMANDS.contains(&program) {
+        return ToolClass::WorkspaceWrite;
+    }
+    ToolClass::Destructive
+}
+
+/// The git subcommand after the global options, or `None` when those
+/// options set config (`-c`, `--config-env`) or point git at a repository
+/// outside the workspace (`-C`, `--git-dir`, `--work-tree`)
+fn git_subcommand<'w>(mut args: &[&'w str], ctx: &ToolContext) -> Option<&'w str> {
+    while let Some(&option) = args.first() {
+        if !option.starts_with('-') {
+            break;
+        }
+        if option == "-c" || option.starts_with("--config-env") {
+            return None;
+        }
+        let (name, inline) = match option.split_once('=') {
+            Some((name, value)) => (name, Some(value)),
+            None => (option, None),
+        };
+        let takes_value = matches!(name, "-C" | "--git-dir" | "--work-tree" | "--namespace");
+        if takes_value && name != "--namespace" {
+            let value = inline.or_else(|| args.get(1).copied()).unwrap_or_default();
+            if !shell_target_in_workspace(value, ctx) {
+                return None;
+            }
+        }
+        let skip = if takes_value && inline.is_none() {
+            2
+        } else {
+            1
+        };
+        args = &args[skip.min(args.len())..];
+    }
+    Some(args.first().copied().unwrap_or_default())
+}
+
+/// Strip leading variable assignments and wrapper commands like `env`,
+/// `timeout` or `xargs`, leaving the command that actually runs. A wrapper
+/// with no command left runs its default (`env` prints the environment,
+/// `xargs` runs `echo`), so it is kept. `None` when the command is hidden in
+/// an option, as with `env -S`.
+fn unwrap_command<'a, 'w>(mut words: &'a [&'w str]) -> Option<&'a [&'w str]> {
+    loop {
+        while words
+            .first()
+            .is_some_and(|word| word.contains('=') && !word.starts_with('-'))
+        {
+            words = &words[1..];
+        }
+        let Some(&first) = words.first() else {
+            return Some(words);
+        };
+        let name = first.rsplit('/').next().unwrap_or(first);
+        let Some((_, value_options)) = WRAPPERS.iter().find(|(wrapper, _)| *wrapper == name) else {
+            return Some(words);
+        };
+
+        let mut rest = &words[1..];
+        while let Some(&option) = rest.first() {
+            if !option.starts_with('-') {
+                break;
+            }
+            // `command -v` and `-V` only look the program up
+            if name == "command" && matches!(option, "-v" | "-V") {
+                return Some(&[]);
+            }
+            if name == "env" && (option.starts_with("-S") || option.starts_with("--split")) {
+                return None;
+            }
+            let skip = if value_options.contains(&option) {
+                2
+            } else {
+                1
+            };
+            rest = &rest[skip.min(rest.len())..];
+            if option == "--" {
+                break;
+            }
+        }
+        if name == "timeout" && !rest.is_empty() {
+            rest = &rest[1..];
+        }
+        if rest.iter().all(|word| word.contains('=')) {
+            return Some(words);
+        }
+        words = rest;
+    }
+}
+
+/// Classes approved without prompting
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApprovalPolicy {
+    auto_approve: Vec<ToolClass>,
+}
+
+impl ApprovalPolicy {
+    /// Auto-approve `classes`; destructive is dropped if listed
+    pub fn new(classes: impl IntoIterator<Item = ToolClass>) -> Self {
+        let mut auto_approve: Vec<ToolClass> = classes
+            .into_iter()
+            .filter(|class| *class != ToolClass::Destructive)
+            .collect();
+        auto_approve.sort();
+        auto_approve.dedup();
+        Self { auto_approve }
+    }
+
+    /// The classes `settings` lists for `sandbox`'s mode. Custom policies use
+    /// the read-only list, since they can restrict more than workspace-write.
+    pub fn for_sandbox(settings: &AutoApproveSettings, sandbox: &SandboxPolicy) -> Self {
+        let classes = match sandbox {
+            SandboxPolicy::WorkspaceWrite { .. } => &settings.workspace_write,
+            SandboxPolicy::FullAccess => &settings.full_access,
+            SandboxPolicy::ReadOnly | SandboxPolicy::Custom { .. } => &settings.read_only,
+        };
+        Self::new(classes.iter().copied().map(ToolClass::from))
+    }
+
+    pub fn auto_approves(&self, class: ToolClass) -> bool {
+        self.auto_approve.contains(&class)
+    }
+
+    pub fn classes(&self) -> &[ToolClass] {
+        &self.auto_approve
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::path::PathBuf;
+
+    fn ctx() -> ToolContext {
+        ToolContext {
+            cwd: PathBuf::from("/work/app"),
+            ..ToolContext::default()
+        }
+    }
+
+    #[test]
+    fn classifies_shell_commands() {
+        let ctx = ctx();
+        assert_eq!(
+            classify_command("git log --oneline | head", &ctx),
+            ToolClass::ReadOnly
+        );
+        assert_eq!(
+            classify_command("cargo fmt --all", &ctx),
+            ToolClass::WorkspaceWrite
+        );
+        assert_eq!(
+            classify_command("grep foo src > hits.txt", &ctx),
+            ToolClass::WorkspaceWrite
+        );
+        assert_eq!(
+            classify_command("npm install left-pad", &ctx),
+            ToolClass::Network
+        );
+        assert_eq!(
+            classify_command("git fetch && git status", &ctx),
+            ToolClass::Network
+        );
+        assert_eq!(
+            classify_command("git push --force", &ctx),
+            ToolClass::Destructive
+        );
+        assert_eq!(
+            classify_command("ls; rm -r build", &ctx),
+            ToolClass::Destructive
+        );
+        assert_eq!(
+            classify_command("find . -name '*.o' -delete", &ctx),
+            ToolClass::Destructive
+        );
+        assert_eq!(
+            classify_command("echo $(cat secret)", &ctx),
+            ToolClass::Destructive
+        );
+        assert_eq!(
+            classify_command("RUST_LOG=debug cat log.txt", &ctx),
+            ToolClass::ReadOnly
+        );
+    }
+
+    #[test]
+    fn classifies_the_command_wrappers_run() {
+        let ctx = ctx();
+        assert_eq!(classify_command("env", &ctx), ToolClass::ReadOnly);
+        assert_eq!(
+            classify_command("env rm -rf ~", &ctx),
+            ToolClass::Destructive
+        );
+        assert_eq!(
+            classify_command("env -u HOME FOO=1 cat a.txt", &ctx),
+            ToolClass::ReadOnly
+        );
+        assert_eq!(
+            classify_command("env -S 'rm -rf build'", &ctx),
+            ToolClass::Destructive
+        );
+        assert_eq!(
+            classify_command("/bin/rm a.txt", &ctx),
+            ToolClass::Destructive
+        );
+        assert_eq!(
+            classify_command("timeout -s KILL 5 /usr/bin/rm a.txt", &ctx),
+            ToolClass::Destructive
+        );
+        assert_eq!(
+            classify_command("find . -name '*.rs' | xargs -n 1 grep foo", &ctx),
+            ToolClass::ReadOnly
+        );
+        assert_eq!(
+            classify_command("git ls-files | xargs rm", &ctx),
+            ToolClass::Destructive
+        );
+        assert_eq!(
+            classify_command("nice -n 10 nohup cargo fmt", &ctx),
+            ToolClass::WorkspaceWrite
+        );
+        assert_eq!(classify_command("command -v rm", &ctx), ToolClass::ReadOnly);
+        assert_eq!(
+            classify_command("command rm a.txt", &ctx),
+            ToolClass::Destructive
+        );
+        assert_eq!(classify_command("sudo ls", &ctx), ToolClass::Destructive);
+    }
+
+    #[test]
+    fn unknown_programs_and_inline_code_are_destructive() {
+        let ctx = ctx();
+        for command in [
+            "python -c 'import shutil'",
+            "node -e 'require(\"fs\")'",
+            "perl -e 'unlink glob \"*\"'",
+            "sh -c 'rm -rf build'",
+            "./deploy.sh",
+            "cargo build --release",
+            "cargo run -- --help",
+            "go run ./cmd/tool",
+            "npm exec left-pad",
+            "npx left-pad",
+            "yarn dlx create-app",
+            "make",
+            "cmake --build build",
+            "ninja -C build",
+            "sed -n '1e id' a.txt",
+            "eslint --fix src",
+            "docker run -v /:/host alpine",
+            "git submodule foreach 'rm -rf .'",
+            "git bisect run ./test.sh",
+            "tar --to-command=sh -xf a.tar",
+            "cat <(rm -rf ~)",
+            "diff a.txt >(tee ~/.bashrc)",
+            "some-unknown-tool --flag",
+        ] {
+            assert_eq!(
+                classify_command(command, &ctx),
+                ToolClass::Destructive,
+                "{command}"
+            );
+        }
+    }
+
+    #[test]
+    fn shell_writes_outside_the_workspace_are_destructive() {
+        let ctx = ctx();
+        for command in [
+            "echo x >> ~/.bashrc",
+            "cp a.txt /etc/x",
+            "mv x ~/..",
+            "sed -i 's/a/b/' /home/me/notes.txt",
+            "sort -o ~/.profile a.txt",
+            "cargo build > ../log.txt",
+            "git config core.hooksPath /tmp/hooks",
+            "git -c core.sshCommand=evil status",
+            "git -C /tmp/other commit -m x",
+            "git --git-dir=/tmp/other/.git status",
+            "tar -xf a.tar -C /tmp",
+            "unzip a.zip -d ~/bin",
+        ] {
+            assert_eq!(
+                classify_command(command, &ctx),
+                ToolClass::Destructive,
+                "{command}"
+            );
+        }
+        assert_eq!(
+            classify_command("sort -o sorted.txt a.txt", &ctx),
+            ToolClass::WorkspaceWrite
+        );
+        assert_eq!(
+            classify_command("cp a.txt /work/app/b.txt", &ctx),
+            ToolClass::WorkspaceWrite
+        );
+        assert_eq!(
+            classify_command("git -C sub status", &ctx),
+            ToolClass::ReadOnly
+        );
+    }
+
+    #[test]
+    fn classifies_tools_by_target() {
+        let ctx = ctx();
+        let write = |path: &str| classify("Write", &json!({ "file_path": path }), false, &ctx);
+        assert_eq!(write("src/main.rs"), ToolClass::WorkspaceWrite);
+        assert_eq!(write("/work/app/src/main.rs"), ToolClass::WorkspaceWrite);
+        assert_eq!(write("../other/main.rs"), ToolClass::Destructive);
+        assert_eq!(write("/home/me/.bashrc"), ToolClass::Destructive);
+
+        assert_eq!(
+            classify("lsp_rename", &json!({ "newName": "x" }), false, &ctx),
+            ToolClass::WorkspaceWrite
+        );
+        assert_eq!(
+            classify("github_create_pr", &json!({}), false, &ctx),
+            ToolClass::Network
+        );
+        assert_eq!(
+            classify("fetch_url", &json!({}), true, &ctx),
+            ToolClass::Network
+        );
+        assert_eq!(
+            classify("mcp__db__drop", &json!({}), false, &ctx),
+            ToolClass::Destructive
+        );
+    }
+
+    #[test]
+    fn policy_follows_sandbox_mode_and_never_approves_destructive() {
+        let settings = AutoApproveSettings {
+            full_access: vec![ToolClassConfig::Network, ToolClassConfig::Destructive],
+            ..AutoApproveSettings::default()
+        };
+
+        let workspace = ApprovalPolicy::for_sandbox(
+            &settings,
+            &SandboxPolicy::workspace_write(PathBuf::from("/work/app")),
+        );
+        assert!(workspace.auto_approves(ToolClass::WorkspaceWrite));
+        assert!(!workspace.auto_approves(ToolClass::Network));
+
+        let read_only = ApprovalPolicy::for_sandbox(&settings, &SandboxPolicy::ReadOnly);
+        assert_eq!(read_only.classes(), &[ToolClass::ReadOnly]);
+
+        let full = ApprovalPolicy::for_sandbox(&settings, &SandboxPolicy::FullAccess);
+        assert!(full.auto_approves(ToolClass::Network));
+        assert!(!full.auto_approves(ToolClass::Destructive));
+    }
+}
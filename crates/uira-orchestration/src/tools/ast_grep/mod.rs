@@ -1 +1,7 @@
-mod tools;
+//! ast-grep infrastructure shared by the AST tools and the commit hooks
+
+pub mod outline;
+pub mod rules;
+
+pub use outline::{find_symbol, outline, outline_language, OutlineItem, SymbolMatch};
+pub use rules::{apply_fixes, AstRule, RuleError, RuleFinding, RuleFix, RulePack, RuleSeverity};
@@ -0,0 +1,424 @@
+//! Declaration outlines from tree-sitter syntax trees
+//!
+//! Walks the parse tree ast-grep already builds and keeps the nodes that
+//! declare something (functions, types, classes, impls, …) with their line
+//! ranges. No language server is involved, so it works on any file the
+//! grammars can parse, including ones with syntax errors.
+
+use std::fmt::Write as _;
+use std::path::Path;
+
+use ast_grep_core::tree_sitter::StrDoc;
+use ast_grep_core::{Language, Node};
+use ast_grep_language::{LanguageExt, SupportLang};
+use serde::Serialize;
+
+type SyntaxNode<'r> = Node<'r, StrDoc<SupportLang>>;
+
+/// One declaration and the declarations nested inside it
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct OutlineItem {
+    pub kind: String,
+    pub name: String,
+    /// 1-based, inclusive
+    pub start_line: usize,
+    pub end_line: usize,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<OutlineItem>,
+}
+
+/// Languages with outline support
+pub const OUTLINE_LANGUAGES: &[&str] = &["rust", "typescript", "tsx", "javascript", "python", "go"];
+
+/// Outline language for a file, from its extension
+pub fn outline_language(path: &Path) -> Option<SupportLang> {
+    SupportLang::from_path(path).filter(is_outline_language)
+}
+
+fn is_outline_language(lang: &SupportLang) -> bool {
+    matches!(
+        lang,
+        SupportLang::Rust
+            | SupportLang::TypeScript
+            | SupportLang::Tsx
+            | SupportLang::JavaScript
+            | SupportLang::Python
+            | SupportLang::Go
+    )
+}
+
+/// Parse `source` and return its top-level declarations; `None` when the
+/// language has no outline support
+pub fn outline(lang: SupportLang, source: &str) -> Option<Vec<OutlineItem>> {
+    if !is_outline_language(&lang) {
+        return None;
+    }
+    let grep = lang.ast_grep(source);
+    let mut items = Vec::new();
+    collect(lang, &grep.root(), false, &mut items);
+    Some(items)
+}
+
+/// Render items as an indented list with line ranges
+pub fn render(items: &[OutlineItem]) -> String {
+    fn write_items(out: &mut String, items: &[OutlineItem], depth: usize) {
+        for item in items {
+            let _ = writeln!(
+                out,
+                "{}{} {} [{}-{}]",
+                "  ".repeat(depth),
+                item.kind,
+                item.name,
+                item.start_line,
+                item.end_line
+            );
+            write_items(out, &item.children, depth + 1);
+        }
+    }
+    let mut out = String::new();
+    write_items(&mut out, items, 0);
+    out
+}
+
+/// A declaration matched by [`find_symbol`], with the outline path leading
+/// to it (`impl Index > method default`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SymbolMatch<'a> {
+    pub item: &'a OutlineItem,
+    pub path: String,
+}
+
+/// Declarations named by `query`: a bare name (`default`) or a path through
+/// enclosing types with `::` or `.` (`Index::default`, `Store.get`)
+pub fn find_symbol<'a>(items: &'a [OutlineItem], query: &str) -> Vec<SymbolMatch<'a>> {
+    let segments: Vec<&str> = query
+        .split("::")
+        .flat_map(|part| part.split('.'))
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    let mut matches = Vec::new();
+    if !segments.is_empty() {
+        search(
+            items,
+            &segments,
+            &mut Vec::new(),
+            &mut Vec::new(),
+            &mut matches,
+        );
+    }
+    matches
+}
+
+fn search<'a>(
+    items: &'a [OutlineItem],
+    segments: &[&str],
+    keys: &mut Vec<String>,
+    labels: &mut Vec<String>,
+    out: &mut Vec<SymbolMatch<'a>>,
+) {
+    for item in items {
+        let own_keys = match_keys(item);
+        let depth = keys.len();
+        keys.extend(own_keys);
+        labels.push(format!("{} {}", item.kind, item.name));
+        if keys.len() >= segments.len() && keys[keys.len() - segments.len()..] == *segments {
+            out.push(SymbolMatch {
+                item,
+                path: labels.join(" > "),
+            });
+        }
+        search(&item.children, segments, keys, labels, out);
+        keys.truncate(depth);
+        labels.pop();
+    }
+}
+
+/// Names a query can use for `item`: impls answer to their target type and
+/// Go methods to their receiver followed by the method name
+fn match_keys(item: &OutlineItem) -> Vec<String> {
+    let strip_generics = |name: &str| name.split('<').next().unwrap_or(name).trim().to_string();
+    match item.kind.as_str() {
+        "impl" => {
+            let target = item.name.rsplit(" for ").next().unwrap_or(&item.name);
+            vec![strip_generics(target)]
+        }
+        "method" if item.name.starts_with('(') => match item.name.split_once(") ") {
+            Some((receiver, name)) => vec![receiver[1..].to_string(), name.to_string()],
+            None => vec![item.name.clone()],
+        },
+        _ => vec![item.name.clone()],
+    }
+}
+
+/// What a declaration node contributes: its kind, name and the node whose
+/// children may hold nested declarations
+struct Declaration<'r> {
+    kind: &'static str,
+    name: String,
+    body: Option<SyntaxNode<'r>>,
+}
+
+fn collect(lang: SupportLang, node: &SyntaxNode<'_>, in_type: bool, out: &mut Vec<OutlineItem>) {
+    for child in node.children().filter(|c| c.is_named()) {
+        if let Some(decl) = classify(lang, &child, in_type) {
+            let mut children = Vec::new();
+            if let Some(body) = &decl.body {
+                let nested_in_type = matches!(
+                    decl.kind,
+                    "impl" | "trait" | "class" | "interface" | "struct"
+                );
+                collect(lang, body, nested_in_type, &mut children);
+            }
+            out.push(OutlineItem {
+                kind: decl.kind.to_string(),
+                name: decl.name,
+                start_line: child.start_pos().line() + 1,
+                end_line: child.end_pos().line() + 1,
+                children,
+            });
+        } else if is_wrapper(lang, &child) {
+            collect(lang, &child, in_type, out);
+        }
+    }
+}
+
+/// Nodes that only wrap declarations (`export …`, decorators, `type (…)`)
+fn is_wrapper(lang: SupportLang, node: &SyntaxNode<'_>) -> bool {
+    let kind = node.kind();
+    match lang {
+        SupportLang::TypeScript | SupportLang::Tsx | SupportLang::JavaScript => {
+            kind == "export_statement"
+        }
+        SupportLang::Python => kind == "decorated_definition",
+        SupportLang::Go => kind == "type_declaration",
+        _ => false,
+    }
+}
+
+fn classify<'r>(
+    lang: SupportLang,
+    node: &SyntaxNode<'r>,
+    in_type: bool,
+) -> Option<Declaration<'r>> {
+    match lang {
+        SupportLang::Rust => classify_rust(node, in_type),
+        SupportLang::TypeScript | SupportLang::Tsx | SupportLang::JavaScript => {
+            classify_typescript(node)
+        }
+        SupportLang::Python => classify_python(node, in_type),
+        SupportLang::Go => classify_go(node),
+        _ => None,
+    }
+}
+
+fn field_text(node: &SyntaxNode<'_>, field: &str) -> Option<String> {
+    node.field(field).map(|n| n.text().to_string())
+}
+
+fn named<'r>(node: &SyntaxNode<'r>, kind: &'static str) -> Option<Declaration<'r>> {
+    Some(Declaration {
+        kind,
+        name: field_text(node, "name")?,
+        body: node.field("body"),
+    })
+}
+
+fn classify_rust<'r>(node: &SyntaxNode<'r>, in_type: bool) -> Option<Declaration<'r>> {
+    match node.kind().as_ref() {
+        "function_item" | "function_signature_item" => {
+            named(node, if in_type { "method" } else { "fn" })
+        }
+        "struct_item" => named(node, "struct"),
+        "enum_item" => named(node, "enum"),
+        "union_item" => named(node, "union"),
+        "trait_item" => named(node, "trait"),
+        "mod_item" => named(node, "mod"),
+        "type_item" => named(node, "type"),
+        "const_item" => named(node, "const"),
+        "static_item" => named(node, "static"),
+        "macro_definition" => named(node, "macro"),
+        "impl_item" => {
+            let target = field_text(node, "type")?;
+            let name = match field_text(node, "trait") {
+                Some(trait_name) => format!("{} for {}", trait_name, target),
+                None => target,
+            };
+            Some(Declaration {
+                kind: "impl",
+                name,
+                body: node.field("body"),
+            })
+        }
+        _ => None,
+    }
+}
+
+fn classify_typescript<'r>(node: &SyntaxNode<'r>) -> Option<Declaration<'r>> {
+    match node.kind().as_ref() {
+        "function_declaration" | "generator_function_declaration" | "function_signature" => {
+            named(node, "function")
+        }
+        "class_declaration" | "abstract_class_declaration" | "class" => named(node, "class"),
+        "interface_declaration" => named(node, "interface"),
+        "type_alias_declaration" => Some(Declaration {
+            kind: "type",
+            name: field_text(node, "name")?,
+            body: None,
+        }),
+        "enum_declaration" => Some(Declaration {
+            kind: "enum",
+            name: field_text(node, "name")?,
+            body: None,
+        }),
+        "internal_module" | "module" => named(node, "namespace"),
+        "method_definition" | "method_signature" | "abstract_method_signature" => {
+            named(node, "method")
+        }
+        "lexical_declaration" | "variable_declaration" => {
+            // `const handler = () => …` reads as a function in an outline
+            let declarator = node
+                .children()
+                .find(|c| c.kind() == "variable_declarator")?;
+            let value_kind = declarator.field("value")?.kind().to_string();
+            if !matches!(
+                value_kind.as_str(),
+                "arrow_function" | "function_expression" | "function"
+            ) {
+                return None;
+            }
+            Some(Declaration {
+                kind: "function",
+                name: field_text(&declarator, "name")?,
+                body: None,
+            })
+        }
+        _ => None,
+    }
+}
+
+fn classify_python<'r>(node: &SyntaxNode<'r>, in_type: bool) -> Option<Declaration<'r>> {
+    match node.kind().as_ref() {
+        "function_definition" => named(node, if in_type { "method" } else { "def" }),
+        "class_definition" => named(node, "class"),
+        _ => None,
+    }
+}
+
+fn classify_go<'r>(node: &SyntaxNode<'r>) -> Option<Declaration<'r>> {
+    match node.kind().as_ref() {
+        "function_declaration" => Some(Declaration {
+            kind: "func",
+            name: field_text(node, "name")?,
+            body: None,
+        }),
+        "method_declaration" => {
+            let name = field_text(node, "name")?;
+            let receiver = node
+                .field("receiver")
+                .and_then(|r| {
+                    r.dfs()
+                        .find(|n| n.kind() == "type_identifier")
+                        .map(|n| n.text().to_string())
+                })
+                .map(|r| format!("({}) ", r))
+                .unwrap_or_default();
+            Some(Declaration {
+                kind: "method",
+                name: format!("{}{}", receiver, name),
+                body: None,
+            })
+        }
+        "type_spec" => {
+            let kind = match node.field("type").map(|t| t.kind().to_string()).as_deref() {
+                Some("struct_type") => "struct",
+                Some("interface_type") => "interface",
+                _ => "type",
+            };
+            Some(Declaration {
+                kind,
+                name: field_text(node, "name")?,
+                body: None,
+            })
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(items: &[OutlineItem]) -> Vec<String> {
+        items
+            .iter()
+            .map(|i| format!("{} {}", i.kind, i.name))
+            .collect()
+    }
+
+    #[test]
+    fn outlines_rust_with_nested_methods() {
+        let source = "pub struct Index {\n    files: usize,\n}\n\nimpl Default for Index {\n    fn default() -> Self {\n        Self { files: 0 }\n    }\n}\n\nfn main() {}\n";
+        let items = outline(SupportLang::Rust, source).unwrap();
+        assert_eq!(
+            names(&items),
+            vec!["struct Index", "impl Default for Index", "fn main"]
+        );
+        assert_eq!((items[1].start_line, items[1].end_line), (5, 9));
+        assert_eq!(names(&items[1].children), vec!["method default"]);
+        assert_eq!(
+            render(&items[1..2]),
+            "impl Default for Index [5-9]\n  method default [6-8]\n"
+        );
+    }
+
+    #[test]
+    fn outlines_typescript_python_and_go() {
+        let ts = "export class Store {\n  get(id: string) {}\n}\nexport const load = async () => {};\nconst value = 1;\ninterface Options {}\n";
+        let items = outline(SupportLang::TypeScript, ts).unwrap();
+        assert_eq!(
+            names(&items),
+            vec!["class Store", "function load", "interface Options"]
+        );
+        assert_eq!(names(&items[0].children), vec!["method get"]);
+
+        let py = "@dataclass\nclass Point:\n    def norm(self):\n        pass\n\ndef main():\n    pass\n";
+        let items = outline(SupportLang::Python, py).unwrap();
+        assert_eq!(names(&items), vec!["class Point", "def main"]);
+        assert_eq!(names(&items[0].children), vec!["method norm"]);
+
+        let go = "package main\n\ntype Server struct{}\n\nfunc (s *Server) Start() {}\n\nfunc main() {}\n";
+        let items = outline(SupportLang::Go, go).unwrap();
+        assert_eq!(
+            names(&items),
+            vec!["struct Server", "method (Server) Start", "func main"]
+        );
+
+        assert!(outline(SupportLang::Json, "{}").is_none());
+    }
+
+    #[test]
+    fn finds_symbols_by_name_and_path() {
+        let source = "struct Index;\n\nimpl Index {\n    fn new() -> Self {\n        Index\n    }\n}\n\nimpl<T> Default for Wrapper<T> {\n    fn default() -> Self {\n        todo!()\n    }\n}\n\nfn new() {}\n";
+        let items = outline(SupportLang::Rust, source).unwrap();
+
+        let all_new = find_symbol(&items, "new");
+        assert_eq!(all_new.len(), 2);
+        assert_eq!(all_new[0].path, "impl Index > method new");
+        assert_eq!(all_new[1].path, "fn new");
+
+        let qualified = find_symbol(&items, "Index::new");
+        assert_eq!(qualified.len(), 1);
+        assert_eq!(
+            (qualified[0].item.start_line, qualified[0].item.end_line),
+            (4, 6)
+        );
+        assert_eq!(find_symbol(&items, "Wrapper.default").len(), 1);
+        assert!(find_symbol(&items, "Index::default").is_empty());
+
+        let go = "package main\n\nfunc (s *Server) Start() {}\n";
+        let items = outline(SupportLang::Go, go).unwrap();
+        assert_eq!(find_symbol(&items, "Server.Start").len(), 1);
+        assert_eq!(find_symbol(&items, "Start").len(), 1);
+    }
+}
@@ -0,0 +1,610 @@
+//! Project rule packs in ast-grep's YAML rule format
+//!
+//! Rules live in `.uira/rules/*.yml` (one or more `---`-separated documents per
+//! file). The supported subset covers most hand-written rules: `pattern`, `kind`
+//! and `regex` atoms, `all`/`any`/`not` composites, `inside`/`has` relations with
+//! `stopBy: neighbor | end`, per-metavariable `constraints`, and string `fix`
+//! templates. Unsupported rule keys are rejected instead of silently ignored.
+
+use ast_grep_core::matcher::{KindMatcher, Pattern, RegexMatcher};
+use ast_grep_core::meta_var::MetaVarEnv;
+use ast_grep_core::{Doc, Language, Matcher, Node, NodeMatch};
+use ast_grep_language::{LanguageExt, SupportLang};
+use serde::Deserialize;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashSet};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// Rule directory, relative to the project root
+pub const RULES_DIR: &str = ".uira/rules";
+
+#[derive(Debug, thiserror::Error)]
+pub enum RuleError {
+    #[error("failed to read {path}: {source}")]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("{path}: {message}")]
+    Invalid { path: PathBuf, message: String },
+    #[error("unknown rule id: {0}")]
+    UnknownRule(String),
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleSeverity {
+    Off,
+    Hint,
+    Info,
+    #[default]
+    Warning,
+    Error,
+}
+
+impl fmt::Display for RuleSeverity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Off => "off",
+            Self::Hint => "hint",
+            Self::Info => "info",
+            Self::Warning => "warning",
+            Self::Error => "error",
+        })
+    }
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct RuleFile {
+    id: String,
+    language: String,
+    #[serde(default)]
+    severity: RuleSeverity,
+    #[serde(default)]
+    message: String,
+    note: Option<String>,
+    #[serde(default)]
+    files: Vec<String>,
+    #[serde(default)]
+    ignores: Vec<String>,
+    rule: RuleObject,
+    #[serde(default)]
+    constraints: BTreeMap<String, RuleObject>,
+    fix: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct RuleObject {
+    pattern: Option<String>,
+    kind: Option<String>,
+    regex: Option<String>,
+    all: Option<Vec<RuleObject>>,
+    any: Option<Vec<RuleObject>>,
+    not: Option<Box<RuleObject>>,
+    inside: Option<Box<RuleObject>>,
+    has: Option<Box<RuleObject>>,
+    stop_by: Option<StopBy>,
+}
+
+/// How far `inside`/`has` search: direct parent/children or all ancestors/descendants
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum StopBy {
+    #[default]
+    Neighbor,
+    End,
+}
+
+enum RuleMatcher {
+    Pattern(Pattern),
+    Kind(KindMatcher),
+    Regex(RegexMatcher),
+    All(Vec<RuleMatcher>),
+    Any(Vec<RuleMatcher>),
+    Not(Box<RuleMatcher>),
+    Inside(Box<RuleMatcher>, StopBy),
+    Has(Box<RuleMatcher>, StopBy),
+}
+
+impl RuleMatcher {
+    fn compile(rule: RuleObject, lang: SupportLang) -> Result<Self, String> {
+        let mut parts = Vec::new();
+        if let Some(pattern) = rule.pattern {
+            let compiled = Pattern::try_new(&pattern, lang)
+                .map_err(|e| format!("invalid pattern `{}`: {}", pattern, e))?;
+            parts.push(Self::Pattern(compiled));
+        }
+        if let Some(kind) = rule.kind {
+            let compiled = KindMatcher::try_new(&kind, lang)
+                .map_err(|e| format!("invalid kind `{}`: {}", kind, e))?;
+            parts.push(Self::Kind(compiled));
+        }
+        if let Some(regex) = rule.regex {
+            let compiled = RegexMatcher::try_new(&regex)
+                .map_err(|e| format!("invalid regex `{}`: {}", regex, e))?;
+            parts.push(Self::Regex(compiled));
+        }
+        if let Some(rules) = rule.all {
+            parts.push(Self::All(Self::compile_all(rules, lang)?));
+        }
+        if let Some(rules) = rule.any {
+            parts.push(Self::Any(Self::compile_all(rules, lang)?));
+        }
+        if let Some(rule) = rule.not {
+            parts.push(Self::Not(Box::new(Self::compile(*rule, lang)?)));
+        }
+        if let Some(rule) = rule.inside {
+            let stop_by = rule.stop_by.unwrap_or_default();
+            parts.push(Self::Inside(Box::new(Self::compile(*rule, lang)?), stop_by));
+        }
+        if let Some(rule) = rule.has {
+            let stop_by = rule.stop_by.unwrap_or_default();
+            parts.push(Self::Has(Box::new(Self::compile(*rule, lang)?), stop_by));
+        }
+
+        match parts.len() {
+            0 => Err("rule needs one of pattern, kind, regex, all, any, not, inside, has".into()),
+            1 => Ok(parts.remove(0)),
+            _ => Ok(Self::All(parts)),
+        }
+    }
+
+    fn compile_all(rules: Vec<RuleObject>, lang: SupportLang) -> Result<Vec<Self>, String> {
+        rules
+            .into_iter()
+            .map(|rule| Self::compile(rule, lang))
+            .collect()
+    }
+
+    /// Whether the rule can match on its own; a bare `not` would match every node
+    fn is_positive(&self) -> bool {
+        match self {
+            Self::Not(_) => false,
+            Self::All(rules) => rules.iter().any(Self::is_positive),
+            Self::Any(rules) => rules.iter().all(Self::is_positive),
+            _ => true,
+        }
+    }
+
+    /// Match the first candidate, keeping its metavariables only on success
+    fn match_any<'tree, D: Doc>(
+        &self,
+        candidates: Vec<Node<'tree, D>>,
+        env: &mut Cow<MetaVarEnv<'tree, D>>,
+    ) -> bool {
+        candidates.into_iter().any(|candidate| {
+            let mut scoped = env.clone();
+            let matched = self.match_node_with_env(candidate, &mut scoped).is_some();
+            if matched {
+                *env = scoped;
+            }
+            matched
+        })
+    }
+}
+
+impl Matcher for RuleMatcher {
+    fn match_node_with_env<'tree, D: Doc>(
+        &self,
+        node: Node<'tree, D>,
+        env: &mut Cow<MetaVarEnv<'tree, D>>,
+    ) -> Option<Node<'tree, D>> {
+        match self {
+            Self::Pattern(pattern) => pattern.match_node_with_env(node, env),
+            Self::Kind(kind) => kind.match_node_with_env(node, env),
+            Self::Regex(regex) => regex.match_node_with_env(node, env),
+            Self::All(rules) => {
+                let mut scoped = env.clone();
+                for rule in rules {
+                    rule.match_node_with_env(node.clone(), &mut scoped)?;
+                }
+                *env = scoped;
+                Some(node)
+            }
+            Self::Any(rules) => rules
+                .iter()
+                .any(|rule| rule.match_any(vec![node.clone()], env))
+                .then_some(node),
+            Self::Not(rule) => rule
+                .match_node_with_env(node.clone(), &mut env.clone())
+                .is_none()
+                .then_some(node),
+            Self::Inside(rule, stop_by) => {
+                let candidates = match stop_by {
+                    StopBy::Neighbor => node.parent().into_iter().collect(),
+                    StopBy::End => node.ancestors().collect(),
+                };
+                rule.match_any(candidates, env).then_some(node)
+            }
+            Self::Has(rule, stop_by) => {
+                let candidates = match stop_by {
+                    StopBy::Neighbor => node.children().collect(),
+                    StopBy::End => node.dfs().skip(1).collect(),
+                };
+                rule.match_any(candidates, env).then_some(node)
+            }
+        }
+    }
+}
+
+/// A compiled rule from a rule pack
+pub struct AstRule {
+    pub id: String,
+    pub language: SupportLang,
+    pub severity: RuleSeverity,
+    pub message: String,
+    pub note: Option<String>,
+    pub fix: Option<String>,
+    /// File the rule was loaded from
+    pub source: PathBuf,
+    matcher: RuleMatcher,
+    constraints: Vec<(String, RuleMatcher)>,
+    files: Vec<glob::Pattern>,
+    ignores: Vec<glob::Pattern>,
+}
+
+/// A rewrite produced by a rule's `fix` template, as a byte range of the source
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleFix {
+    pub start: usize,
+    pub end: usize,
+    pub replacement: String,
+}
+
+/// One rule match
+#[derive(Debug, Clone)]
+pub struct RuleFinding {
+    pub rule_id: String,
+    pub severity: RuleSeverity,
+    pub message: String,
+    pub note: Option<String>,
+    /// 1-based line and column of the match start
+    pub line: usize,
+    pub column: usize,
+    pub end_line: usize,
+    pub text: String,
+    pub fix: Option<RuleFix>,
+}
+
+impl AstRule {
+    fn from_file(file: RuleFile, source: &Path) -> Result<Self, RuleError> {
+        let invalid = |message: String| RuleError::Invalid {
+            path: source.to_path_buf(),
+            message: format!("rule `{}`: {}", file.id, message),
+        };
+
+        let language: SupportLang = file
+            .language
+            .parse()
+            .map_err(|_| invalid(format!("unsupported language `{}`", file.language)))?;
+        let matcher = RuleMatcher::compile(file.rule, language).map_err(&invalid)?;
+        if !matcher.is_positive() {
+            return Err(invalid(
+                "rule needs a positive matcher besides `not`".to_string(),
+            ));
+        }
+        let constraints = file
+            .constraints
+            .into_iter()
+            .map(|(var, rule)| {
+                let var = var.trim_start_matches('$').to_string();
+                RuleMatcher::compile(rule, language).map(|rule| (var, rule))
+            })
+            .collect::<Result<_, _>>()
+            .map_err(&invalid)?;
+        let compile_globs = |globs: Vec<String>| {
+            globs
+                .iter()
+                .map(|g| glob::Pattern::new(g).map_err(|e| format!("invalid glob `{}`: {}", g, e)))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        Ok(Self {
+            files: compile_globs(file.files).map_err(&invalid)?,
+            ignores: compile_globs(file.ignores).map_err(&invalid)?,
+            id: file.id,
+            language,
+            severity: file.severity,
+            message: file.message,
+            note: file.note,
+            fix: file.fix,
+            source: source.to_path_buf(),
+            matcher,
+            constraints,
+        })
+    }
+
+    /// Whether the rule runs on `path` (relative to the project root)
+    pub fn applies_to(&self, path: &Path) -> bool {
+        SupportLang::from_path(path) == Some(self.language)
+            && (self.files.is_empty() || self.files.iter().any(|g| g.matches_path(path)))
+            && !self.ignores.iter().any(|g| g.matches_path(path))
+    }
+
+    /// Every match of the rule in `source`
+    pub fn scan(&self, source: &str) -> Vec<RuleFinding> {
+        let grep = self.language.ast_grep(source);
+        grep.root()
+            .find_all(&self.matcher)
+            .filter(|m| self.satisfies_constraints(m))
+            .map(|m| {
+                let node = m.get_node();
+                let start = node.start_pos();
+                let fix = self.fix.as_deref().map(|template| {
+                    let edit = m.replace_by(template);
+                    RuleFix {
+                        start: edit.position,
+                        end: edit.position + edit.deleted_length,
+                        replacement: String::from_utf8_lossy(&edit.inserted_text).into_owned(),
+                    }
+                });
+                RuleFinding {
+                    rule_id: self.id.clone(),
+                    severity: self.severity,
+                    message: self.message.clone(),
+                    note: self.note.clone(),
+                    line: start.line() + 1,
+                    column: start.column(node) + 1,
+                    end_line: node.end_pos().line() + 1,
+                    text: node.text().to_string(),
+                    fix,
+                }
+            })
+            .collect()
+    }
+
+    fn satisfies_constraints<D: Doc>(&self, m: &NodeMatch<'_, D>) -> bool {
+        self.constraints.iter().all(|(var, rule)| {
+            m.get_env().get_match(var).is_none_or(|node| {
+                rule.match_node_with_env(node.clone(), &mut Cow::Owned(MetaVarEnv::new()))
+                    .is_some()
+            })
+        })
+    }
+}
+
+/// All rules of a project
+#[derive(Default)]
+pub struct RulePack {
+    pub rules: Vec<AstRule>,
+}
+
+impl RulePack {
+    /// Load every `*.yml`/`*.yaml` file under `<root>/.uira/rules`
+    ///
+    /// A missing directory yields an empty pack; rules with `severity: off` are dropped.
+    pub fn load(root: &Path) -> Result<Self, RuleError> {
+        let dir = root.join(RULES_DIR);
+        let mut pack = Self::default();
+        if !dir.is_dir() {
+            return Ok(pack);
+        }
+
+        let entries = WalkDir::new(&dir)
+            .sort_by_file_name()
+            .into_iter()
+            .flatten()
+            .filter(|e| e.file_type().is_file())
+            .filter(|e| {
+                e.path()
+                    .extension()
+                    .is_some_and(|ext| ext == "yml" || ext == "yaml")
+            });
+        for entry in entries {
+            let path = entry.path();
+            let source = std::fs::read_to_string(path).map_err(|source| RuleError::Io {
+                path: path.to_path_buf(),
+                source,
+            })?;
+            pack.rules.extend(Self::parse(&source, path)?);
+        }
+
+        let mut ids = HashSet::new();
+        for rule in &pack.rules {
+            if !ids.insert(rule.id.as_str()) {
+                return Err(RuleError::Invalid {
+                    path: rule.source.clone(),
+                    message: format!("duplicate rule id `{}`", rule.id),
+                });
+            }
+        }
+        Ok(pack)
+    }
+
+    /// Parse the rule documents in `source`, read from `path`
+    pub fn parse(source: &str, path: &Path) -> Result<Vec<AstRule>, RuleError> {
+        let mut rules = Vec::new();
+        for document in serde_yaml_ng::Deserializer::from_str(source) {
+            let file = RuleFile::deserialize(document).map_err(|e| RuleError::Invalid {
+                path: path.to_path_buf(),
+                message: e.to_string(),
+            })?;
+            if file.severity != RuleSeverity::Off {
+                rules.push(AstRule::from_file(file, path)?);
+            }
+        }
+        Ok(rules)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Keep only the rules in `ids`
+    pub fn select(&mut self, ids: &[String]) -> Result<(), RuleError> {
+        if let Some(unknown) = ids
+            .iter()
+            .find(|id| !self.rules.iter().any(|r| &r.id == *id))
+        {
+            return Err(RuleError::UnknownRule(unknown.clone()));
+        }
+        self.rules.retain(|rule| ids.contains(&rule.id));
+        Ok(())
+    }
+
+    /// Languages targeted by at least one rule
+    pub fn languages(&self) -> Vec<SupportLang> {
+        let mut languages = Vec::new();
+        for rule in &self.rules {
+            if !languages.contains(&rule.language) {
+                languages.push(rule.language);
+            }
+        }
+        languages
+    }
+
+    /// Findings of every applicable rule on `source`, ordered by position
+    ///
+    /// `path` is relative to the project root.
+    pub fn scan(&self, path: &Path, source: &str) -> Vec<RuleFinding> {
+        let mut findings: Vec<_> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.applies_to(path))
+            .flat_map(|rule| rule.scan(source))
+            .collect();
+        findings.sort_by_key(|f| (f.line, f.column));
+        findings
+    }
+}
+
+/// Apply the fixes of `findings` to `source`, returning the new text and fix count
+///
+/// Fixes that overlap an earlier (in source order) fix are skipped.
+pub fn apply_fixes(source: &str, findings: &[RuleFinding]) -> (String, usize) {
+    let mut fixes: Vec<&RuleFix> = findings.iter().filter_map(|f| f.fix.as_ref()).collect();
+    fixes.sort_by_key(|fix| (fix.start, fix.end));
+
+    let mut result = String::with_capacity(source.len());
+    let mut cursor = 0;
+    let mut applied = 0;
+    for fix in fixes {
+        if fix.start < cursor || fix.end > source.len() {
+            continue;
+        }
+        result.push_str(&source[cursor..fix.start]);
+        result.push_str(&fix.replacement);
+        cursor = fix.end;
+        applied += 1;
+    }
+    result.push_str(&source[cursor..]);
+    (result, applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RULES: &str = r#"
+id: no-unwrap
+language: rust
+severity: error
+message: Avoid unwrap outside tests
+rule:
+  pattern: $X.unwrap()
+  not:
+    inside:
+      kind: mod_item
+      has:
+        kind: identifier
+        regex: ^tests$
+      stopBy: end
+---
+id: prefer-is-empty
+language: rust
+message: Use is_empty()
+rule:
+  pattern: $V.len() == 0
+constraints:
+  V:
+    regex: ^items
+fix: $V.is_empty()
+---
+id: disabled
+language: rust
+severity: off
+rule:
+  pattern: foo()
+"#;
+
+    fn pack() -> RulePack {
+        RulePack {
+            rules: RulePack::parse(RULES, Path::new("rules.yml")).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_parse_rule_pack() {
+        let pack = pack();
+        assert_eq!(pack.rules.len(), 2);
+        assert_eq!(pack.rules[0].severity, RuleSeverity::Error);
+        assert_eq!(pack.rules[1].severity, RuleSeverity::Warning);
+        assert_eq!(pack.languages(), vec![SupportLang::Rust]);
+        assert!(pack.rules[0].applies_to(Path::new("src/lib.rs")));
+        assert!(!pack.rules[0].applies_to(Path::new("src/lib.ts")));
+    }
+
+    #[test]
+    fn test_scan_with_relations_and_constraints() {
+        let source = "\
+fn run() {
+    let a = items.len() == 0;
+    let b = other.len() == 0;
+    config().unwrap();
+}
+mod tests {
+    fn check() {
+        config().unwrap();
+    }
+}
+";
+        let findings = pack().scan(Path::new("src/lib.rs"), source);
+        let found: Vec<_> = findings
+            .iter()
+            .map(|f| (f.rule_id.as_str(), f.line))
+            .collect();
+        assert_eq!(found, vec![("prefer-is-empty", 2), ("no-unwrap", 4)]);
+
+        let (fixed, applied) = apply_fixes(source, &findings);
+        assert_eq!(applied, 1);
+        assert!(fixed.contains("let a = items.is_empty();"));
+        assert!(fixed.contains("let b = other.len() == 0;"));
+    }
+
+    #[test]
+    fn test_invalid_rules() {
+        let parse = |yaml: &str| RulePack::parse(yaml, Path::new("bad.yml"));
+
+        let unknown_key =
+            "id: a\nlanguage: rust\nrule:\n  pattern: foo()\n  follows:\n    kind: x\n";
+        assert!(parse(unknown_key).is_err());
+        let only_not = "id: a\nlanguage: rust\nrule:\n  not:\n    pattern: foo()\n";
+        assert!(parse(only_not).is_err());
+        let bad_language = "id: a\nlanguage: cobol\nrule:\n  pattern: foo()\n";
+        assert!(parse(bad_language).is_err());
+    }
+
+    #[test]
+    fn test_load_and_select() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(RulePack::load(dir.path()).unwrap().is_empty());
+
+        let rules_dir = dir.path().join(RULES_DIR);
+        std::fs::create_dir_all(&rules_dir).unwrap();
+        std::fs::write(rules_dir.join("rust.yml"), RULES).unwrap();
+        std::fs::write(rules_dir.join("notes.txt"), "not a rule").unwrap();
+
+        let mut pack = RulePack::load(dir.path()).unwrap();
+        assert_eq!(pack.rules.len(), 2);
+        pack.select(&["no-unwrap".to_string()]).unwrap();
+        assert_eq!(pack.rules.len(), 1);
+        assert!(pack.select(&["missing".to_string()]).is_err());
+
+        std::fs::write(rules_dir.join("copy.yaml"), RULES).unwrap();
+        assert!(RulePack::load(dir.path()).is_err());
+    }
+}
@@ -1,2 +0,0 @@
-// AST-grep tool definitions are provided by uira-mcp-server.
-// This module is reserved for future AST-grep client infrastructure.
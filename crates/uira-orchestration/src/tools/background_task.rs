@@ -1,6 +1,7 @@
 use crate::features::background_agent::{
     get_background_manager, BackgroundTask, BackgroundTaskConfig, BackgroundTaskStatus, LaunchInput,
 };
+use crate::features::model_routing::resolve_model_override;
 use crate::tools::types::{ToolDefinition, ToolError, ToolInput, ToolOutput};
 use serde_json::{json, Value};
 use std::sync::Arc;
@@ -71,7 +72,15 @@ async fn handle_launch(
     let agent = get_optional_string(input, "agent").unwrap_or_else(|| "executor".to_string());
     let parent_session_id =
         get_optional_string(input, "parent_session_id").unwrap_or_else(|| "unknown".to_string());
-    let model = get_optional_string(input, "model");
+    let routing = uira_core::load_config(None)
+        .map(|config| config.routing)
+        .unwrap_or_default();
+    let model = resolve_model_override(
+        &routing,
+        get_optional_string(input, "provider").as_deref(),
+        get_optional_string(input, "model").as_deref(),
+    )
+    .map_err(|message| ToolError::InvalidInput { message })?;
 
     let launch_input = LaunchInput {
         description,
@@ -300,9 +309,13 @@ pub fn tool_definition() -> ToolDefinition {
                     "type": "string",
                     "description": "Parent session ID for tracking (optional for launch)"
                 },
+                "provider": {
+                    "type": "string",
+                    "description": "Provider for model, e.g. 'openrouter' (optional for launch)"
+                },
                 "model": {
                     "type": "string",
-                    "description": "Model to use for the task (optional for launch)"
+                    "description": "Model to use for the task, allowed by the routing policy (optional for launch)"
                 },
                 "taskId": {
                     "type": "string",
@@ -386,6 +399,25 @@ mod tests {
         assert!(response["task"]["task_id"].is_string());
     }
 
+    #[tokio::test]
+    async fn test_launch_with_provider_requires_model() {
+        let (manager, _temp_dir) = create_test_manager();
+
+        let input = json!({
+            "action": "launch",
+            "description": "Test task",
+            "prompt": "Do something",
+            "provider": "openrouter"
+        });
+
+        match handle_background_task_with_manager(input, &manager).await {
+            Err(ToolError::InvalidInput { message }) => {
+                assert!(message.contains("needs a model"));
+            }
+            other => panic!("Expected InvalidInput error, got {:?}", other.is_ok()),
+        }
+    }
+
     #[tokio::test]
     async fn test_list_action() {
         let (manager, _temp_dir) = create_test_manager();
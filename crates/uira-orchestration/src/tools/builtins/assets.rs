@@ -0,0 +1,256 @@
+//! Binary file handling for the file tools
+//!
+//! Identifies common binary formats from their magic bytes so Read can
+//! describe a file (type, size, image dimensions, optional hexdump) instead
+//! of dumping garbage into the context. PDFs go through `pdftotext` when it's
+//! installed; images small enough for the model are attached as images.
+
+use std::fmt::Write as _;
+use std::path::Path;
+use std::time::Duration;
+
+/// Bytes sniffed to identify a file
+pub const SNIFF_BYTES: usize = 8192;
+
+/// Largest image attached to a Read result, matching provider limits
+pub const MAX_IMAGE_BYTES: u64 = 5 * 1024 * 1024;
+
+/// Default number of bytes shown by a hexdump
+pub const DEFAULT_HEXDUMP_BYTES: usize = 512;
+
+/// Upper bound on one hexdump, so a large `limit` can't flood the context
+pub const MAX_HEXDUMP_BYTES: usize = 4096;
+
+const PDF_EXTRACT_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// A recognized file format
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FileType {
+    pub mime: &'static str,
+    pub description: &'static str,
+}
+
+impl FileType {
+    const fn new(mime: &'static str, description: &'static str) -> Self {
+        Self { mime, description }
+    }
+
+    /// Unrecognized binary data
+    pub const UNKNOWN: FileType = FileType::new("application/octet-stream", "binary data");
+
+    /// A text file, for hexdumps of files that aren't binary
+    pub const TEXT: FileType = FileType::new("text/plain", "text");
+
+    /// Image formats models accept as image input
+    pub fn is_model_image(&self) -> bool {
+        matches!(
+            self.mime,
+            "image/png" | "image/jpeg" | "image/gif" | "image/webp"
+        )
+    }
+
+    pub fn is_pdf(&self) -> bool {
+        self.mime == "application/pdf"
+    }
+}
+
+/// Identify a binary format from the first bytes of a file; `None` for
+/// anything not in the table, including text
+pub fn sniff(header: &[u8]) -> Option<FileType> {
+    const SIGNATURES: &[(&[u8], FileType)] = &[
+        (
+            b"\x89PNG\r\n\x1a\n",
+            FileType::new("image/png", "PNG image"),
+        ),
+        (b"\xff\xd8\xff", FileType::new("image/jpeg", "JPEG image")),
+        (b"GIF87a", FileType::new("image/gif", "GIF image")),
+        (b"GIF89a", FileType::new("image/gif", "GIF image")),
+        (b"%PDF-", FileType::new("application/pdf", "PDF document")),
+        (
+            b"PK\x03\x04",
+            FileType::new("application/zip", "ZIP archive"),
+        ),
+        (
+            b"\x1f\x8b",
+            FileType::new("application/gzip", "gzip archive"),
+        ),
+        (
+            b"\x7fELF",
+            FileType::new("application/x-elf", "ELF executable"),
+        ),
+        (
+            b"\xcf\xfa\xed\xfe",
+            FileType::new("application/x-mach-binary", "Mach-O executable"),
+        ),
+        (
+            b"\0asm",
+            FileType::new("application/wasm", "WebAssembly module"),
+        ),
+        (
+            b"SQLite format 3\0",
+            FileType::new("application/vnd.sqlite3", "SQLite database"),
+        ),
+        (
+            b"MZ",
+            FileType::new(
+                "application/vnd.microsoft.portable-executable",
+                "Windows executable",
+            ),
+        ),
+    ];
+
+    if header.len() >= 12 && &header[..4] == b"RIFF" && &header[8..12] == b"WEBP" {
+        return Some(FileType::new("image/webp", "WebP image"));
+    }
+    SIGNATURES
+        .iter()
+        .find(|(magic, _)| header.starts_with(magic))
+        .map(|(_, file_type)| *file_type)
+}
+
+/// Width and height of a PNG, GIF or JPEG image
+pub fn image_dimensions(bytes: &[u8]) -> Option<(u32, u32)> {
+    let be16 = |at: usize| Some(u16::from_be_bytes([*bytes.get(at)?, *bytes.get(at + 1)?]) as u32);
+    let le16 = |at: usize| Some(u16::from_le_bytes([*bytes.get(at)?, *bytes.get(at + 1)?]) as u32);
+
+    if bytes.starts_with(b"\x89PNG") {
+        let width = u32::from_be_bytes(bytes.get(16..20)?.try_into().ok()?);
+        let height = u32::from_be_bytes(bytes.get(20..24)?.try_into().ok()?);
+        return Some((width, height));
+    }
+    if bytes.starts_with(b"GIF8") {
+        return Some((le16(6)?, le16(8)?));
+    }
+    if bytes.starts_with(b"\xff\xd8") {
+        // Walk the segments up to the start-of-frame marker
+        let mut at = 2;
+        while at + 9 < bytes.len() {
+            if bytes[at] != 0xff {
+                return None;
+            }
+            let marker = bytes[at + 1];
+            let is_frame = matches!(marker, 0xc0..=0xcf) && !matches!(marker, 0xc4 | 0xc8 | 0xcc);
+            if is_frame {
+                return Some((be16(at + 7)?, be16(at + 5)?));
+            }
+            at += 2 + be16(at + 2)? as usize;
+        }
+    }
+    None
+}
+
+/// Classic offset / hex / ASCII dump, 16 bytes per line, with offsets
+/// counted from `start`
+pub fn hexdump(bytes: &[u8], start: usize) -> String {
+    let mut out = String::new();
+    for (index, chunk) in bytes.chunks(16).enumerate() {
+        let hex: Vec<String> = chunk.iter().map(|b| format!("{:02x}", b)).collect();
+        let ascii: String = chunk
+            .iter()
+            .map(|&b| {
+                if b.is_ascii_graphic() || b == b' ' {
+                    b as char
+                } else {
+                    '.'
+                }
+            })
+            .collect();
+        let _ = writeln!(
+            out,
+            "{:08x}  {:<47}  |{}|",
+            start + index * 16,
+            hex.join(" "),
+            ascii
+        );
+    }
+    out.trim_end().to_string()
+}
+
+/// Human-readable size, e.g. `1.5 MB (1572864 bytes)`
+pub fn format_size(bytes: u64) -> String {
+    const UNITS: &[&str] = &["KB", "MB", "GB"];
+    if bytes < 1024 {
+        return format!("{} bytes", bytes);
+    }
+    let mut value = bytes as f64;
+    let mut unit = "";
+    for next in UNITS {
+        if value < 1024.0 {
+            break;
+        }
+        value /= 1024.0;
+        unit = next;
+    }
+    format!("{:.1} {} ({} bytes)", value, unit, bytes)
+}
+
+/// Text of a PDF via poppler's `pdftotext`; `Err` explains why nothing was
+/// extracted
+pub async fn pdf_text(path: &Path) -> Result<String, String> {
+    let output = tokio::time::timeout(
+        PDF_EXTRACT_TIMEOUT,
+        tokio::process::Command::new("pdftotext")
+            .args(["-layout", "-enc", "UTF-8"])
+            .arg(path)
+            .arg("-")
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await
+    .map_err(|_| "pdftotext timed out".to_string())?
+    .map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            "install poppler's pdftotext to extract PDF text".to_string()
+        } else {
+            format!("failed to run pdftotext: {}", e)
+        }
+    })?;
+
+    if !output.status.success() {
+        return Err(format!(
+            "pdftotext failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        ));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PNG_1X2: &[u8] = &[
+        0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', 0, 0, 0, 13, b'I', b'H', b'D', b'R', 0,
+        0, 0, 1, 0, 0, 0, 2, 8, 6, 0, 0, 0,
+    ];
+
+    #[test]
+    fn sniffs_formats_and_dimensions() {
+        let png = sniff(PNG_1X2).unwrap();
+        assert_eq!(png.mime, "image/png");
+        assert!(png.is_model_image());
+        assert_eq!(image_dimensions(PNG_1X2), Some((1, 2)));
+
+        assert_eq!(
+            image_dimensions(b"GIF89a\x40\x01\xf0\x00"),
+            Some((320, 240))
+        );
+        let jpeg = b"\xff\xd8\xff\xe0\x00\x04\x00\x00\xff\xc0\x00\x11\x08\x00\x30\x00\x40\x03";
+        assert_eq!(image_dimensions(jpeg), Some((64, 48)));
+
+        assert!(sniff(b"%PDF-1.7\n").unwrap().is_pdf());
+        assert_eq!(sniff(b"RIFF\0\0\0\0WEBPVP8 ").unwrap().mime, "image/webp");
+        assert!(sniff(b"fn main() {}").is_none());
+    }
+
+    #[test]
+    fn formats_hexdump_and_sizes() {
+        assert_eq!(
+            hexdump(b"\x7fELF\x02\x01 hello", 32),
+            "00000020  7f 45 4c 46 02 01 20 68 65 6c 6c 6f              |.ELF.. hello|"
+        );
+        assert_eq!(format_size(512), "512 bytes");
+        assert_eq!(format_size(1536), "1.5 KB (1536 bytes)");
+        assert_eq!(format_size(3 * 1024 * 1024), "3.0 MB (3145728 bytes)");
+    }
+}
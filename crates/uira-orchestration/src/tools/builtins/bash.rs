@@ -4,12 +4,16 @@ use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
 use std::process::Stdio;
 use std::time::Duration;
-use tokio::time::timeout;
 use uira_core::{ApprovalRequirement, JsonSchema, SandboxPreference, ToolOutput};
 use uira_security::{SandboxManager, SandboxPolicy, SandboxType};
 
 use crate::tools::{Tool, ToolContext, ToolError};
 
+/// `bash` on unix; `UIRA_SHELL` or `cmd` on Windows
+fn shell_command(command: &str) -> std::process::Command {
+    uira_core::ShellKind::from_env_or(uira_core::ShellKind::Bash).command(command)
+}
+
 const MAX_OUTPUT_BYTES: usize = 5 * 1024 * 1024;
 
 fn truncate_output(s: &str) -> String {
@@ -189,23 +193,34 @@ impl Tool for BashTool {
         let timeout_duration = Duration::from_millis(input.timeout_ms.unwrap_or(120_000));
         let working_dir = input
             .working_directory
-            .map(std::path::PathBuf::from)
+            .map(|path| ctx.resolve_path(&path))
             .unwrap_or_else(|| ctx.cwd.clone());
-        let bash_output = match ctx.sandbox_type {
+        // A root with its own policy sandboxes commands run inside it even
+        // when the session itself is unrestricted
+        let sandbox_policy = ctx.sandbox_policy_for(&working_dir);
+        let sandbox_type =
+            if sandbox_policy != &ctx.sandbox_policy && sandbox_policy.is_restrictive() {
+                SandboxType::Native
+            } else {
+                ctx.sandbox_type
+            };
+        let bash_output = match sandbox_type {
             SandboxType::Native => {
                 self.execute_sandboxed(
                     &command,
                     &working_dir,
                     timeout_duration,
-                    &ctx.sandbox_policy,
+                    sandbox_policy,
+                    ctx,
                 )
                 .await
             }
             SandboxType::None | SandboxType::Container => {
-                self.execute_direct(&command, &working_dir, timeout_duration)
+                self.execute_direct(&command, &working_dir, timeout_duration, ctx)
                     .await
             }
-        }?;
+        };
+        let bash_output = bash_output?;
 
         Ok(ToolOutput::text(Self::format_output(
             &command,
@@ -238,38 +253,12 @@ impl BashTool {
         command: &str,
         working_dir: &std::path::Path,
         timeout_duration: Duration,
+        ctx: &ToolContext,
     ) -> Result<BashOutput, ToolError> {
-        let mut cmd = tokio::process::Command::new("bash");
-        cmd.arg("-c")
-            .arg(command)
-            .current_dir(working_dir)
-            .kill_on_drop(true)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+        let mut cmd = tokio::process::Command::from(shell_command(command));
+        cmd.current_dir(working_dir);
 
-        let child = cmd.spawn().map_err(|e| ToolError::ExecutionFailed {
-            message: format!("Failed to start command: {}", e),
-        })?;
-        let result = timeout(timeout_duration, child.wait_with_output()).await;
-
-        match result {
-            Ok(Ok(output)) => {
-                let stdout = truncate_output(&String::from_utf8_lossy(&output.stdout));
-                let stderr = truncate_output(&String::from_utf8_lossy(&output.stderr));
-                let exit_code = output.status.code().unwrap_or(-1);
-                Ok(BashOutput {
-                    stdout,
-                    stderr,
-                    exit_code,
-                })
-            }
-            Ok(Err(e)) => Err(ToolError::ExecutionFailed {
-                message: format!("Failed to execute command: {}", e),
-            }),
-            Err(_) => Err(ToolError::ExecutionFailed {
-                message: format!("Command timed out after {}ms", timeout_duration.as_millis()),
-            }),
-        }
+        Self::run_command(cmd, timeout_duration, ctx, "Command").await
     }
 
     async fn execute_sandboxed(
@@ -278,15 +267,12 @@ impl BashTool {
         working_dir: &std::path::Path,
         timeout_duration: Duration,
         sandbox_policy: &SandboxPolicy,
+        ctx: &ToolContext,
     ) -> Result<BashOutput, ToolError> {
         let sandbox_manager = SandboxManager::new(sandbox_policy.clone());
 
-        let mut cmd = std::process::Command::new("bash");
-        cmd.arg("-c")
-            .arg(command)
-            .current_dir(working_dir)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped());
+        let mut cmd = shell_command(command);
+        cmd.current_dir(working_dir);
 
         if let Err(e) = sandbox_manager.wrap_command(&mut cmd, SandboxType::Native) {
             return Err(ToolError::ExecutionFailed {
@@ -294,14 +280,57 @@ impl BashTool {
             });
         }
 
-        let result = tokio::time::timeout(
+        Self::run_command(
+            tokio::process::Command::from(cmd),
             timeout_duration,
-            tokio::task::spawn_blocking(move || cmd.output()),
+            ctx,
+            "Sandboxed command",
         )
-        .await;
+        .await
+    }
+
+    /// Run `cmd` in its own process group until it exits, times out, or the
+    /// turn is cancelled. Anything it left running is killed in the latter
+    /// two cases.
+    async fn run_command(
+        mut cmd: tokio::process::Command,
+        timeout_duration: Duration,
+        ctx: &ToolContext,
+        label: &str,
+    ) -> Result<BashOutput, ToolError> {
+        cmd.stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        #[cfg(unix)]
+        cmd.process_group(0);
+
+        let child = cmd.spawn().map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to start {}: {}", label.to_lowercase(), e),
+        })?;
+        let group = ProcessGroupGuard(child.id());
+
+        let output = tokio::select! {
+            output = child.wait_with_output() => output,
+            _ = ctx.cancellation.cancelled() => {
+                return Err(ToolError::ExecutionFailed {
+                    message: format!("{} cancelled", label),
+                });
+            }
+            _ = tokio::time::sleep(timeout_duration) => {
+                return Err(ToolError::ExecutionFailed {
+                    message: format!(
+                        "{} timed out after {}ms",
+                        label,
+                        timeout_duration.as_millis()
+                    ),
+                });
+            }
+        };
+        group.disarm();
 
-        match result {
-            Ok(Ok(Ok(output))) => {
+        match output {
+            Ok(output) => {
                 let stdout = truncate_output(&String::from_utf8_lossy(&output.stdout));
                 let stderr = truncate_output(&String::from_utf8_lossy(&output.stderr));
                 let exit_code = output.status.code().unwrap_or(-1);
@@ -311,22 +340,34 @@ impl BashTool {
                     exit_code,
                 })
             }
-            Ok(Ok(Err(e))) => Err(ToolError::ExecutionFailed {
-                message: format!("Failed to execute sandboxed command: {}", e),
-            }),
-            Ok(Err(e)) => Err(ToolError::ExecutionFailed {
-                message: format!("Sandbox task panicked: {}", e),
-            }),
-            Err(_) => Err(ToolError::ExecutionFailed {
-                message: format!(
-                    "Sandboxed command timed out after {}ms",
-                    timeout_duration.as_millis()
-                ),
+            Err(e) => Err(ToolError::ExecutionFailed {
+                message: format!("Failed to execute {}: {}", label.to_lowercase(), e),
             }),
         }
     }
 }
 
+/// Kills a command's process group when dropped, so processes it spawned
+/// don't outlive a cancelled or timed out command
+struct ProcessGroupGuard(Option<u32>);
+
+impl ProcessGroupGuard {
+    fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for ProcessGroupGuard {
+    fn drop(&mut self) {
+        #[cfg(unix)]
+        if let Some(pid) = self.0 {
+            use nix::sys::signal::{killpg, Signal};
+            use nix::unistd::Pid;
+            let _ = killpg(Pid::from_raw(pid as i32), Signal::SIGKILL);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -347,6 +388,58 @@ mod tests {
         assert_eq!(lines[1], "hello");
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_cancel_kills_spawned_processes() {
+        let dir = tempfile::tempdir().unwrap();
+        let pid_file = dir.path().join("pid");
+        let ctx = std::sync::Arc::new(ToolContext {
+            cwd: dir.path().to_path_buf(),
+            ..Default::default()
+        });
+        let cancel = ctx.cancellation.clone();
+
+        let command = format!("sleep 30 & echo $! > {}; wait", pid_file.display());
+        let run = tokio::spawn({
+            let ctx = ctx.clone();
+            async move {
+                BashTool::new()
+                    .execute(json!({"command": command}), &ctx)
+                    .await
+            }
+        });
+
+        let mut pid = None;
+        for _ in 0..100 {
+            pid = std::fs::read_to_string(&pid_file)
+                .ok()
+                .and_then(|s| s.trim().parse::<i32>().ok());
+            if pid.is_some() {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        let pid = nix::unistd::Pid::from_raw(pid.expect("background pid"));
+
+        cancel.cancel();
+        let result = run.await.unwrap();
+        assert!(matches!(
+            result,
+            Err(ToolError::ExecutionFailed { message }) if message == "Command cancelled"
+        ));
+
+        // Killed, and reaped once its parent shell is gone
+        let mut alive = true;
+        for _ in 0..100 {
+            alive = nix::sys::signal::kill(pid, None).is_ok();
+            if !alive {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert!(!alive, "background process outlived cancellation");
+    }
+
     #[test]
     fn test_dangerous_command_detection() {
         assert!(BashTool::is_dangerous_command("rm -rf /"));
@@ -0,0 +1,166 @@
+//! Clipboard tool for handing results back to the user
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use uira_core::{ApprovalRequirement, JsonSchema, SandboxPreference, ToolOutput};
+
+use crate::tools::{Tool, ToolContext, ToolError};
+
+/// Input for clipboard tool
+#[derive(Debug, Deserialize)]
+struct ClipboardInput {
+    #[serde(default)]
+    text: Option<String>,
+    #[serde(default)]
+    diff: bool,
+    #[serde(default)]
+    path: Option<String>,
+}
+
+/// Copies text, or the working tree diff, to the system clipboard
+pub struct ClipboardTool;
+
+impl ClipboardTool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for ClipboardTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for ClipboardTool {
+    fn name(&self) -> &str {
+        "clipboard"
+    }
+
+    fn description(&self) -> &str {
+        "Copy text to the user's system clipboard, e.g. a final answer, command or snippet. Set diff=true to copy the uncommitted git diff instead (optionally limited to a path)."
+    }
+
+    fn schema(&self) -> JsonSchema {
+        JsonSchema::object()
+            .property("text", JsonSchema::string().description("The text to copy"))
+            .property(
+                "diff",
+                JsonSchema::boolean().description("Copy the uncommitted git diff instead of text"),
+            )
+            .property(
+                "path",
+                JsonSchema::string().description("Limit the diff to this path"),
+            )
+    }
+
+    fn approval_requirement(&self, _input: &serde_json::Value) -> ApprovalRequirement {
+        // Only replaces clipboard contents; nothing on disk changes
+        ApprovalRequirement::Skip {
+            bypass_sandbox: false,
+        }
+    }
+
+    fn sandbox_preference(&self) -> SandboxPreference {
+        // The clipboard lives in the user's display session
+        SandboxPreference::Forbid
+    }
+
+    async fn execute(
+        &self,
+        input: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let input: ClipboardInput =
+            serde_json::from_value(input).map_err(|e| ToolError::InvalidInput {
+                message: e.to_string(),
+            })?;
+
+        let (content, what) = match (input.text, input.diff) {
+            (Some(_), true) => {
+                return Err(ToolError::InvalidInput {
+                    message: "Provide either text or diff=true, not both".to_string(),
+                })
+            }
+            (Some(text), false) => (text, "text"),
+            (None, true) => (git_diff(ctx, input.path.as_deref()).await?, "diff"),
+            (None, false) => {
+                return Err(ToolError::InvalidInput {
+                    message: "Provide text to copy or set diff=true".to_string(),
+                })
+            }
+        };
+        if content.is_empty() {
+            return Err(ToolError::InvalidInput {
+                message: format!("Nothing to copy: the {what} is empty"),
+            });
+        }
+
+        let chars = content.chars().count();
+        let lines = content.lines().count();
+        tokio::task::spawn_blocking(move || {
+            arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(content))
+        })
+        .await
+        .map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Clipboard task failed: {}", e),
+        })?
+        .map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Clipboard unavailable: {}", e),
+        })?;
+
+        Ok(ToolOutput::text(format!(
+            "Copied {} to clipboard ({} lines, {} characters)",
+            what, lines, chars
+        )))
+    }
+}
+
+/// Staged and unstaged changes against HEAD
+async fn git_diff(ctx: &ToolContext, path: Option<&str>) -> Result<String, ToolError> {
+    let mut command = tokio::process::Command::new("git");
+    command.arg("diff").arg("HEAD").current_dir(&ctx.cwd);
+    if let Some(path) = path {
+        command.arg("--").arg(path);
+    }
+    let output = command
+        .output()
+        .await
+        .map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to run git diff: {}", e),
+        })?;
+    if !output.status.success() {
+        return Err(ToolError::ExecutionFailed {
+            message: format!(
+                "git diff failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_clipboard_requires_exactly_one_source() {
+        let tool = ClipboardTool::new();
+        let ctx = ToolContext::default();
+
+        let err = tool.execute(json!({}), &ctx).await.unwrap_err();
+        assert!(matches!(err, ToolError::InvalidInput { .. }));
+
+        let err = tool
+            .execute(json!({"text": "hi", "diff": true}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::InvalidInput { .. }));
+
+        let err = tool.execute(json!({"text": ""}), &ctx).await.unwrap_err();
+        assert!(err.to_string().contains("Nothing to copy"));
+    }
+}
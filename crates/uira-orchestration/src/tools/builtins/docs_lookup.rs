@@ -0,0 +1,576 @@
+//! Versioned library documentation lookup backed by Context7
+//!
+//! Resolves a package name to a Context7 library ID, fetches documentation
+//! for the requested version and topic, and returns only the snippets most
+//! relevant to the topic. Responses are cached on disk so repeated lookups
+//! (and lookups while offline) are served locally.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+use uira_core::{ApprovalRequirement, JsonSchema, SandboxPreference, ToolOutput};
+
+use super::fetch_cache::now_secs;
+use crate::sdk::Context7Config;
+use crate::tools::{Tool, ToolContext, ToolError};
+
+const DEFAULT_BASE_URL: &str = "https://context7.com/api/v1";
+const DEFAULT_TOKENS: usize = 4000;
+const MAX_TOKENS: usize = 20000;
+const REQUEST_TIMEOUT_SECS: u64 = 20;
+const CACHE_TTL_SECS: u64 = 7 * 24 * 3600;
+const SNIPPET_SEPARATOR: &str = "----------------------------------------";
+
+pub struct DocsLookupTool {
+    config: Context7Config,
+}
+
+impl DocsLookupTool {
+    pub fn new() -> Self {
+        Self::with_config(Context7Config::default())
+    }
+
+    pub fn with_config(config: Context7Config) -> Self {
+        Self { config }
+    }
+
+    /// This tool with `tools.docs_lookup` from the project's uira.yml
+    /// layered over the configured defaults
+    fn for_project(&self, cwd: &Path) -> Self {
+        let project = std::fs::read_to_string(cwd.join("uira.yml"))
+            .ok()
+            .and_then(|content| serde_yaml_ng::from_str::<UiraConfigFile>(&content).ok())
+            .and_then(|file| file.tools)
+            .and_then(|tools| tools.docs_lookup)
+            .unwrap_or_default();
+        Self::with_config(Context7Config {
+            enabled: project.enabled.or(self.config.enabled),
+            api_key: project
+                .api_key
+                .map(|key| uira_core::expand_env_string(&key))
+                .or_else(|| self.config.api_key.clone()),
+            base_url: project.base_url.or_else(|| self.config.base_url.clone()),
+            cache_dir: project.cache_dir.or_else(|| self.config.cache_dir.clone()),
+        })
+    }
+
+    fn base_url(&self) -> String {
+        self.config
+            .base_url
+            .clone()
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string())
+            .trim_end_matches('/')
+            .to_string()
+    }
+
+    fn api_key(&self) -> Option<String> {
+        self.config
+            .api_key
+            .clone()
+            .or_else(|| std::env::var("CONTEXT7_API_KEY").ok())
+            .filter(|key| !key.is_empty())
+    }
+
+    fn cache(&self, cwd: &Path) -> DocsCache {
+        DocsCache {
+            dir: self
+                .config
+                .cache_dir
+                .as_ref()
+                .map(|dir| cwd.join(dir))
+                .unwrap_or_else(|| cwd.join(".uira").join("cache").join("docs")),
+        }
+    }
+
+    fn client(&self) -> Result<reqwest::Client, ToolError> {
+        reqwest::Client::builder()
+            .timeout(Duration::from_secs(REQUEST_TIMEOUT_SECS))
+            .user_agent(super::web_search::USER_AGENT)
+            .build()
+            .map_err(|e| ToolError::ExecutionFailed {
+                message: format!("Failed to initialize HTTP client: {e}"),
+            })
+    }
+
+    fn request(&self, client: &reqwest::Client, url: &str) -> reqwest::RequestBuilder {
+        let request = client.get(url).header("X-Context7-Source", "uira");
+        match self.api_key() {
+            Some(key) => request.bearer_auth(key),
+            None => request,
+        }
+    }
+
+    /// Resolve `library` to a Context7 ID such as `/tokio-rs/tokio`
+    async fn resolve(&self, library: &str, cache: &DocsCache) -> Result<Library, ToolError> {
+        if library.starts_with('/') {
+            return Ok(Library {
+                id: library.trim_end_matches('/').to_string(),
+                title: library.to_string(),
+                description: None,
+                versions: Vec::new(),
+            });
+        }
+
+        let key = format!("resolve:{}", library.to_lowercase());
+        if let Some(entry) = cache.get(&key).filter(|e| e.is_fresh()) {
+            if let Ok(library) = serde_json::from_str(&entry.content) {
+                return Ok(library);
+            }
+        }
+
+        let url = format!("{}/search", self.base_url());
+        let fetched =
+            async {
+                let response = self
+                    .request(&self.client()?, &url)
+                    .query(&[("query", library)])
+                    .send()
+                    .await
+                    .map_err(request_error)?;
+                let body: SearchResponse = check_status(response)?.json().await.map_err(|e| {
+                    ToolError::ExecutionFailed {
+                        message: format!("Failed to parse Context7 search response: {e}"),
+                    }
+                })?;
+                best_match(library, body.results).ok_or_else(|| ToolError::ExecutionFailed {
+                    message: format!("No documentation found for library '{library}'"),
+                })
+            }
+            .await;
+
+        match fetched {
+            Ok(found) => {
+                if let Ok(content) = serde_json::to_string(&found) {
+                    cache.put(&key, &content);
+                }
+                Ok(found)
+            }
+            // Offline or rate-limited: a stale resolution still beats failing
+            Err(err) => cache
+                .get(&key)
+                .and_then(|entry| serde_json::from_str(&entry.content).ok())
+                .ok_or(err),
+        }
+    }
+
+    /// Documentation text for `library_id`, from cache when fresh
+    async fn docs(
+        &self,
+        library_id: &str,
+        version: Option<&str>,
+        topic: Option<&str>,
+        tokens: usize,
+        cache: &DocsCache,
+    ) -> Result<(String, bool), ToolError> {
+        let path = match version {
+            Some(version) => format!("{library_id}/{version}"),
+            None => library_id.to_string(),
+        };
+        let key = format!("docs:{path}:{}:{tokens}", topic.unwrap_or_default());
+        if let Some(entry) = cache.get(&key).filter(|e| e.is_fresh()) {
+            return Ok((entry.content, true));
+        }
+
+        let url = format!("{}{}", self.base_url(), path);
+        let mut query = vec![("type", "txt".to_string()), ("tokens", tokens.to_string())];
+        if let Some(topic) = topic {
+            query.push(("topic", topic.to_string()));
+        }
+        let fetched = async {
+            let response = self
+                .request(&self.client()?, &url)
+                .query(&query)
+                .send()
+                .await
+                .map_err(request_error)?;
+            check_status(response)?
+                .text()
+                .await
+                .map_err(|e| ToolError::ExecutionFailed {
+                    message: format!("Failed to read Context7 documentation: {e}"),
+                })
+        }
+        .await;
+
+        match fetched {
+            Ok(text) => {
+                cache.put(&key, &text);
+                Ok((text, false))
+            }
+            Err(err) => cache
+                .get(&key)
+                .map(|entry| (entry.content, true))
+                .ok_or(err),
+        }
+    }
+}
+
+impl Default for DocsLookupTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct UiraConfigFile {
+    #[serde(default)]
+    tools: Option<ToolsConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolsConfig {
+    #[serde(default)]
+    docs_lookup: Option<Context7Config>,
+}
+
+#[derive(Debug, Deserialize)]
+struct DocsLookupInput {
+    library: String,
+    #[serde(default)]
+    topic: Option<String>,
+    #[serde(default)]
+    version: Option<String>,
+    #[serde(default)]
+    tokens: Option<usize>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    results: Vec<Library>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct Library {
+    id: String,
+    #[serde(default)]
+    title: String,
+    #[serde(default)]
+    description: Option<String>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[async_trait]
+impl Tool for DocsLookupTool {
+    fn name(&self) -> &str {
+        "docs_lookup"
+    }
+
+    fn description(&self) -> &str {
+        "Look up versioned documentation for a library or package (via Context7) and return the snippets most relevant to a topic. Pass a package name like 'tokio' or a Context7 ID like '/tokio-rs/tokio'."
+    }
+
+    fn schema(&self) -> JsonSchema {
+        JsonSchema::object()
+            .property(
+                "library",
+                JsonSchema::string().description("Package name or Context7 library ID"),
+            )
+            .property(
+                "topic",
+                JsonSchema::string()
+                    .description("What to focus on, e.g. 'select macro' or 'middleware'"),
+            )
+            .property(
+                "version",
+                JsonSchema::string()
+                    .description("Library version, e.g. 'v1.38.0' (default: latest)"),
+            )
+            .property(
+                "tokens",
+                JsonSchema::number()
+                    .description("Approximate token budget (default 4000, max 20000)"),
+            )
+            .required(&["library"])
+    }
+
+    fn requires_network(&self) -> bool {
+        true
+    }
+
+    fn approval_requirement(&self, input: &serde_json::Value) -> ApprovalRequirement {
+        let library = input
+            .get("library")
+            .and_then(|v| v.as_str())
+            .unwrap_or("<unknown>");
+        ApprovalRequirement::NeedsApproval {
+            reason: format!("Look up documentation for: {library}"),
+        }
+    }
+
+    fn sandbox_preference(&self) -> SandboxPreference {
+        SandboxPreference::Forbid
+    }
+
+    async fn execute(
+        &self,
+        input: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let tool = self.for_project(&ctx.cwd);
+        if tool.config.enabled == Some(false) {
+            return Err(ToolError::ExecutionFailed {
+                message: "docs_lookup is disabled by the context7 configuration".to_string(),
+            });
+        }
+
+        let input: DocsLookupInput =
+            serde_json::from_value(input).map_err(|e| ToolError::InvalidInput {
+                message: e.to_string(),
+            })?;
+        let library = input.library.trim();
+        if library.is_empty() {
+            return Err(ToolError::InvalidInput {
+                message: "library must not be empty".to_string(),
+            });
+        }
+        let topic = input
+            .topic
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty());
+        let tokens = input
+            .tokens
+            .unwrap_or(DEFAULT_TOKENS)
+            .clamp(500, MAX_TOKENS);
+
+        let cache = tool.cache(&ctx.cwd);
+        let resolved = tool.resolve(library, &cache).await?;
+        let version = input
+            .version
+            .as_deref()
+            .map(|v| match_version(v, &resolved.versions));
+        let (text, cached) = tool
+            .docs(&resolved.id, version.as_deref(), topic, tokens, &cache)
+            .await?;
+        let snippets = focus_snippets(&text, topic, tokens);
+
+        Ok(ToolOutput::json(json!({
+            "library": resolved.id,
+            "title": resolved.title,
+            "version": version,
+            "availableVersions": resolved.versions,
+            "topic": topic,
+            "cached": cached,
+            "snippets": snippets,
+        })))
+    }
+}
+
+fn request_error(e: reqwest::Error) -> ToolError {
+    ToolError::ExecutionFailed {
+        message: if e.is_timeout() {
+            "Context7 request timed out".to_string()
+        } else {
+            format!("Context7 request failed: {e}")
+        },
+    }
+}
+
+fn check_status(response: reqwest::Response) -> Result<reqwest::Response, ToolError> {
+    let status = response.status();
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(ToolError::ExecutionFailed {
+            message: "Context7 rate-limited this request (HTTP 429); set CONTEXT7_API_KEY for higher limits".to_string(),
+        });
+    }
+    if !status.is_success() {
+        return Err(ToolError::ExecutionFailed {
+            message: format!("Context7 returned HTTP {status}"),
+        });
+    }
+    Ok(response)
+}
+
+/// Exact ID or title match first, then Context7's own ranking
+fn best_match(query: &str, results: Vec<Library>) -> Option<Library> {
+    let query = query.to_lowercase();
+    let exact = results.iter().position(|lib| {
+        lib.title.to_lowercase() == query
+            || lib
+                .id
+                .rsplit('/')
+                .next()
+                .is_some_and(|name| name.to_lowercase() == query)
+    });
+    let index = exact.unwrap_or(0);
+    results.into_iter().nth(index)
+}
+
+/// The listed version matching `requested`, ignoring a leading `v`
+fn match_version(requested: &str, available: &[String]) -> String {
+    let bare = |v: &str| v.trim().trim_start_matches('v').to_string();
+    available
+        .iter()
+        .find(|v| bare(v) == bare(requested))
+        .cloned()
+        .unwrap_or_else(|| requested.trim().to_string())
+}
+
+/// Snippets ranked by overlap with `topic`, within roughly `tokens` tokens
+fn focus_snippets(text: &str, topic: Option<&str>, tokens: usize) -> Vec<String> {
+    let snippets: Vec<&str> = text
+        .split(SNIPPET_SEPARATOR)
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .collect();
+
+    let terms: Vec<String> = topic
+        .map(|t| {
+            t.split(|c: char| !c.is_alphanumeric())
+                .filter(|w| w.len() > 2)
+                .map(str::to_lowercase)
+                .collect()
+        })
+        .unwrap_or_default();
+
+    let mut ranked: Vec<(usize, usize, &str)> = snippets
+        .iter()
+        .enumerate()
+        .map(|(index, snippet)| {
+            let lower = snippet.to_lowercase();
+            let score = terms.iter().filter(|t| lower.contains(t.as_str())).count();
+            (score, index, *snippet)
+        })
+        .collect();
+    // Highest score first; ties keep Context7's order
+    ranked.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+
+    let budget = tokens * 4;
+    let mut used = 0;
+    let mut out = Vec::new();
+    for (_, _, snippet) in ranked {
+        if used + snippet.len() > budget && !out.is_empty() {
+            break;
+        }
+        used += snippet.len();
+        out.push(snippet.to_string());
+    }
+    out
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CacheEntry {
+    key: String,
+    content: String,
+    fetched_at: u64,
+}
+
+impl CacheEntry {
+    fn is_fresh(&self) -> bool {
+        now_secs().saturating_sub(self.fetched_at) < CACHE_TTL_SECS
+    }
+}
+
+struct DocsCache {
+    dir: PathBuf,
+}
+
+impl DocsCache {
+    fn path(&self, key: &str) -> PathBuf {
+        let digest = Sha256::digest(key.as_bytes());
+        self.dir.join(format!("{}.json", hex::encode(digest)))
+    }
+
+    fn get(&self, key: &str) -> Option<CacheEntry> {
+        let content = std::fs::read_to_string(self.path(key)).ok()?;
+        serde_json::from_str::<CacheEntry>(&content)
+            .ok()
+            .filter(|entry| entry.key == key)
+    }
+
+    fn put(&self, key: &str, content: &str) {
+        let entry = CacheEntry {
+            key: key.to_string(),
+            content: content.to_string(),
+            fetched_at: now_secs(),
+        };
+        let written = std::fs::create_dir_all(&self.dir).and_then(|_| {
+            let json = serde_json::to_string(&entry).map_err(std::io::Error::other)?;
+            std::fs::write(self.path(key), json)
+        });
+        if let Err(e) = written {
+            tracing::debug!("Failed to write docs cache entry: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn focuses_snippets_on_topic_within_budget() {
+        let text = format!(
+            "TITLE: Spawning tasks\nCODE: tokio::spawn(async {{}})\n{SNIPPET_SEPARATOR}\n\
+             TITLE: Select macro\nCODE: tokio::select! {{ ... }}\n{SNIPPET_SEPARATOR}\n\
+             TITLE: Timers\nCODE: tokio::time::sleep"
+        );
+        let snippets = focus_snippets(&text, Some("select macro"), 4000);
+        assert_eq!(snippets.len(), 3);
+        assert!(snippets[0].starts_with("TITLE: Select macro"));
+        assert!(snippets[1].starts_with("TITLE: Spawning tasks"));
+
+        // A tiny budget still returns the best snippet
+        assert_eq!(focus_snippets(&text, Some("timers"), 1).len(), 1);
+    }
+
+    #[test]
+    fn resolves_versions_and_best_match() {
+        let versions = vec!["v1.38.0".to_string(), "v1.40.0".to_string()];
+        assert_eq!(match_version("1.38.0", &versions), "v1.38.0");
+        assert_eq!(match_version("2.0", &versions), "2.0");
+
+        let results = vec![
+            Library {
+                id: "/someone/tokio-utils".to_string(),
+                title: "tokio-utils".to_string(),
+                description: None,
+                versions: Vec::new(),
+            },
+            Library {
+                id: "/tokio-rs/tokio".to_string(),
+                title: "Tokio".to_string(),
+                description: None,
+                versions: versions.clone(),
+            },
+        ];
+        assert_eq!(best_match("tokio", results).unwrap().id, "/tokio-rs/tokio");
+    }
+
+    #[tokio::test]
+    async fn serves_cached_docs_without_network() {
+        let dir = tempfile::tempdir().unwrap();
+        let tool = DocsLookupTool::with_config(Context7Config {
+            // Unroutable, so any network access would fail the lookup
+            base_url: Some("http://127.0.0.1:9".to_string()),
+            ..Default::default()
+        });
+        let cache = tool.cache(dir.path());
+        let library = Library {
+            id: "/tokio-rs/tokio".to_string(),
+            title: "Tokio".to_string(),
+            description: None,
+            versions: Vec::new(),
+        };
+        cache.put("resolve:tokio", &serde_json::to_string(&library).unwrap());
+        cache.put("docs:/tokio-rs/tokio:select:4000", "TITLE: Select macro");
+
+        let ctx = ToolContext {
+            cwd: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let output = tool
+            .execute(json!({"library": "tokio", "topic": "select"}), &ctx)
+            .await
+            .unwrap();
+        let value: serde_json::Value = serde_json::from_str(output.as_text().unwrap()).unwrap();
+        assert_eq!(value["library"], "/tokio-rs/tokio");
+        assert_eq!(value["cached"], true);
+        assert_eq!(value["snippets"][0], "TITLE: Select macro");
+    }
+}
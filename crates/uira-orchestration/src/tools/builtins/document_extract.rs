@@ -0,0 +1,934 @@
+//! Markdown extraction from PDF, DOCX and EPUB documents
+//!
+//! Converts a document into anchored sections (PDF pages, DOCX pages or
+//! headings, EPUB chapters) and serves the markdown in chunks, so a long spec
+//! can be read piece by piece or stored in long-term memory with each chunk
+//! pointing back at where it came from.
+
+use std::collections::HashMap;
+use std::io::Read as _;
+use std::path::Path;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use serde::Deserialize;
+use uira_core::{ApprovalRequirement, JsonSchema, ToolOutput};
+use uira_memory::types::{MemoryEntry, MemorySource};
+use uira_memory::{EmbeddingProvider, MemoryConfig, MemoryStore, TextChunker};
+
+use super::{assets, readability};
+use crate::tools::{Tool, ToolContext, ToolError};
+
+const DEFAULT_CHUNK_CHARS: usize = 12_000;
+const MIN_CHUNK_CHARS: usize = 1_000;
+
+/// Largest document accepted, and largest single archive member read from a
+/// DOCX or EPUB, so a zip bomb can't exhaust memory
+const MAX_DOCUMENT_BYTES: u64 = 50 * 1024 * 1024;
+
+/// A part of a document that chunks point back to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentSection {
+    /// Markdown anchor id, e.g. `page-3` or `chapter-2`
+    pub anchor: String,
+    /// Human-readable location, e.g. `page 3` or `chapter 2: Design`
+    pub label: String,
+    pub markdown: String,
+}
+
+/// A document converted to markdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Document {
+    pub format: &'static str,
+    pub sections: Vec<DocumentSection>,
+}
+
+/// One chunk of a document's markdown
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DocumentChunk {
+    pub markdown: String,
+    /// Labels of the first and last sections the chunk covers
+    pub first: String,
+    pub last: String,
+}
+
+/// Read `path` and convert it to markdown sections
+pub async fn extract_document(path: &Path) -> Result<Document, String> {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or_default()
+        .to_ascii_lowercase();
+    let size = tokio::fs::metadata(path)
+        .await
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?
+        .len();
+    if size > MAX_DOCUMENT_BYTES {
+        return Err(format!(
+            "{} is {}; documents over {} MB aren't extracted",
+            path.display(),
+            assets::format_size(size),
+            MAX_DOCUMENT_BYTES / (1024 * 1024)
+        ));
+    }
+
+    match extension.as_str() {
+        "pdf" => Ok(pdf_document(&assets::pdf_text(path).await?)),
+        "docx" | "epub" => {
+            let bytes = tokio::fs::read(path)
+                .await
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+            tokio::task::spawn_blocking(move || {
+                if extension == "docx" {
+                    docx_document(&bytes)
+                } else {
+                    epub_document(&bytes)
+                }
+            })
+            .await
+            .map_err(|e| format!("Extraction failed: {}", e))?
+        }
+        _ => Err(format!(
+            "Unsupported document type '{}'; expected .pdf, .docx or .epub",
+            path.display()
+        )),
+    }
+}
+
+/// Sections for `pdftotext` output, which separates pages with form feeds
+fn pdf_document(text: &str) -> Document {
+    let mut pages: Vec<&str> = text.split('\u{c}').collect();
+    if pages.len() > 1 && pages.last().is_some_and(|page| page.trim().is_empty()) {
+        pages.pop();
+    }
+    let sections = pages
+        .iter()
+        .enumerate()
+        .map(|(index, page)| DocumentSection {
+            anchor: format!("page-{}", index + 1),
+            label: format!("page {}", index + 1),
+            markdown: collapse_blank_lines(page),
+        })
+        .filter(|section| !section.markdown.is_empty())
+        .collect();
+    Document {
+        format: "PDF",
+        sections,
+    }
+}
+
+/// A DOCX paragraph or table rendered as markdown
+struct Block {
+    page: usize,
+    heading: Option<String>,
+    markdown: String,
+}
+
+fn docx_document(bytes: &[u8]) -> Result<Document, String> {
+    let mut archive = open_zip(bytes)?;
+    let xml = zip_text(&mut archive, "word/document.xml")?;
+    let (blocks, paginated) = docx_blocks(&xml)?;
+
+    let mut sections: Vec<DocumentSection> = Vec::new();
+    for block in blocks {
+        // Word records rendered page breaks when it saves; without them,
+        // headings are the only landmarks
+        let starts_section = match sections.last() {
+            None => true,
+            Some(last) if paginated => last.anchor != format!("page-{}", block.page),
+            Some(_) => block.heading.is_some(),
+        };
+        if starts_section {
+            let (anchor, label) = if paginated {
+                (
+                    format!("page-{}", block.page),
+                    format!("page {}", block.page),
+                )
+            } else {
+                let number = sections.len() + 1;
+                let label = match &block.heading {
+                    Some(heading) => format!("section {}: {}", number, heading),
+                    None => format!("section {}", number),
+                };
+                (format!("section-{}", number), label)
+            };
+            sections.push(DocumentSection {
+                anchor,
+                label,
+                markdown: String::new(),
+            });
+        }
+        let section = sections.last_mut().expect("section pushed above");
+        if !section.markdown.is_empty() {
+            section.markdown.push_str("\n\n");
+        }
+        section.markdown.push_str(&block.markdown);
+    }
+    Ok(Document {
+        format: "DOCX",
+        sections,
+    })
+}
+
+/// Paragraphs and tables of `word/document.xml`, and whether the document
+/// carries page break markers
+fn docx_blocks(xml: &str) -> Result<(Vec<Block>, bool), String> {
+    let mut reader = Reader::from_str(xml);
+    let mut blocks = Vec::new();
+    let mut page = 1;
+    let mut paginated = false;
+
+    let mut paragraph = String::new();
+    let mut style: Option<String> = None;
+    let mut list_item = false;
+    let mut in_text = false;
+    let mut table_depth = 0usize;
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut cell = String::new();
+
+    loop {
+        let event = reader
+            .read_event()
+            .map_err(|e| format!("Malformed document.xml: {}", e))?;
+        match event {
+            Event::Start(e) if e.local_name().as_ref() == b"p" => {
+                paragraph.clear();
+                style = None;
+                list_item = false;
+            }
+            Event::Start(e) => match e.local_name().as_ref() {
+                b"t" => in_text = true,
+                b"tbl" => {
+                    table_depth += 1;
+                    if table_depth == 1 {
+                        rows.clear();
+                    }
+                }
+                b"tr" if table_depth == 1 => rows.push(Vec::new()),
+                b"tc" if table_depth == 1 => cell.clear(),
+                b"pStyle" => style = attribute(&e, b"val"),
+                b"numPr" => list_item = true,
+                _ => {}
+            },
+            Event::Empty(e) => match e.local_name().as_ref() {
+                b"pStyle" => style = attribute(&e, b"val"),
+                b"numPr" => list_item = true,
+                b"tab" => paragraph.push('\t'),
+                b"br" => {
+                    if attribute(&e, b"type").as_deref() == Some("page") {
+                        page += 1;
+                        paginated = true;
+                    } else {
+                        paragraph.push(' ');
+                    }
+                }
+                b"lastRenderedPageBreak" => {
+                    // Marks where a page began; the first one is page 1
+                    if !paragraph.is_empty() || !blocks.is_empty() {
+                        page += 1;
+                    }
+                    paginated = true;
+                }
+                _ => {}
+            },
+            Event::Text(text) if in_text => {
+                paragraph.push_str(&text.decode().map_err(|e| e.to_string())?);
+            }
+            Event::GeneralRef(reference) if in_text => {
+                if let Ok(Some(c)) = reference.resolve_char_ref() {
+                    paragraph.push(c);
+                } else if let Ok(name) = reference.decode() {
+                    if let Some(value) = quick_xml::escape::resolve_predefined_entity(&name) {
+                        paragraph.push_str(value);
+                    }
+                }
+            }
+            Event::End(e) => match e.local_name().as_ref() {
+                b"t" => in_text = false,
+                b"p" => {
+                    let text = paragraph.trim().to_string();
+                    if text.is_empty() {
+                        continue;
+                    }
+                    if table_depth > 0 {
+                        if !cell.is_empty() {
+                            cell.push(' ');
+                        }
+                        cell.push_str(&text);
+                        continue;
+                    }
+                    let level = style.as_deref().and_then(heading_level);
+                    let markdown = match level {
+                        Some(level) => format!("{} {}", "#".repeat(level), text),
+                        None if list_item => format!("- {}", text),
+                        None => text.clone(),
+                    };
+                    blocks.push(Block {
+                        page,
+                        heading: level.map(|_| text),
+                        markdown,
+                    });
+                }
+                b"tc" if table_depth == 1 => {
+                    if let Some(row) = rows.last_mut() {
+                        row.push(cell.replace('|', "\\|"));
+                    }
+                }
+                b"tbl" => {
+                    table_depth = table_depth.saturating_sub(1);
+                    if table_depth == 0 && !rows.is_empty() {
+                        blocks.push(Block {
+                            page,
+                            heading: None,
+                            markdown: markdown_table(&rows),
+                        });
+                    }
+                }
+                _ => {}
+            },
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok((blocks, paginated))
+}
+
+/// Markdown heading level for a Word paragraph style (`Title`, `Heading2`)
+fn heading_level(style: &str) -> Option<usize> {
+    let lower = style.to_ascii_lowercase();
+    if lower == "title" {
+        return Some(1);
+    }
+    let level: usize = lower
+        .strip_prefix("heading")?
+        .trim()
+        .parse()
+        .ok()
+        .filter(|level| (1..=5).contains(level))?;
+    Some(level + 1)
+}
+
+fn markdown_table(rows: &[Vec<String>]) -> String {
+    let columns = rows.iter().map(Vec::len).max().unwrap_or(0).max(1);
+    let render = |row: &Vec<String>| {
+        let mut cells: Vec<&str> = row.iter().map(String::as_str).collect();
+        cells.resize(columns, "");
+        format!("| {} |", cells.join(" | "))
+    };
+    let mut lines = vec![render(&rows[0]), format!("|{}", " --- |".repeat(columns))];
+    lines.extend(rows[1..].iter().map(render));
+    lines.join("\n")
+}
+
+fn epub_document(bytes: &[u8]) -> Result<Document, String> {
+    let mut archive = open_zip(bytes)?;
+    let container = zip_text(&mut archive, "META-INF/container.xml")?;
+    let opf_path = xml_elements(&container, b"rootfile")?
+        .into_iter()
+        .find_map(|attrs| attrs.get("full-path").cloned())
+        .ok_or("EPUB container.xml names no package file")?;
+    let opf = zip_text(&mut archive, &opf_path)?;
+    let base = match opf_path.rfind('/') {
+        Some(slash) => &opf_path[..=slash],
+        None => "",
+    };
+
+    let manifest: HashMap<String, String> = xml_elements(&opf, b"item")?
+        .into_iter()
+        .filter_map(|attrs| Some((attrs.get("id")?.clone(), attrs.get("href")?.clone())))
+        .collect();
+    let spine: Vec<String> = xml_elements(&opf, b"itemref")?
+        .into_iter()
+        .filter_map(|attrs| attrs.get("idref").cloned())
+        .collect();
+
+    let mut sections = Vec::new();
+    for idref in spine {
+        let Some(href) = manifest.get(&idref) else {
+            continue;
+        };
+        let member = resolve_href(base, href);
+        let Ok(html) = zip_text(&mut archive, &member) else {
+            continue;
+        };
+        let page = readability::extract(&html, None);
+        let markdown = collapse_blank_lines(&page.markdown);
+        if markdown.is_empty() {
+            continue;
+        }
+        let number = sections.len() + 1;
+        let label = match page.title.filter(|title| !title.trim().is_empty()) {
+            Some(title) => format!("chapter {}: {}", number, title.trim()),
+            None => format!("chapter {}", number),
+        };
+        sections.push(DocumentSection {
+            anchor: format!("chapter-{}", number),
+            label,
+            markdown,
+        });
+    }
+    Ok(Document {
+        format: "EPUB",
+        sections,
+    })
+}
+
+/// Attributes of every `name` element in `xml`, keyed by local name
+fn xml_elements(xml: &str, name: &[u8]) -> Result<Vec<HashMap<String, String>>, String> {
+    let mut reader = Reader::from_str(xml);
+    let mut found = Vec::new();
+    loop {
+        match reader.read_event().map_err(|e| e.to_string())? {
+            Event::Start(e) | Event::Empty(e) if e.local_name().as_ref() == name => {
+                let attrs = e
+                    .attributes()
+                    .flatten()
+                    .filter_map(|attr| {
+                        let key =
+                            String::from_utf8_lossy(attr.key.local_name().as_ref()).into_owned();
+                        Some((key, attr.unescape_value().ok()?.into_owned()))
+                    })
+                    .collect();
+                found.push(attrs);
+            }
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+    Ok(found)
+}
+
+fn attribute(element: &BytesStart<'_>, local_name: &[u8]) -> Option<String> {
+    element
+        .attributes()
+        .flatten()
+        .find(|attr| attr.key.local_name().as_ref() == local_name)
+        .and_then(|attr| attr.unescape_value().ok().map(|v| v.into_owned()))
+}
+
+/// Archive path for an OPF `href`, relative to the package file's directory
+fn resolve_href(base: &str, href: &str) -> String {
+    let href = href.split('#').next().unwrap_or(href);
+    let mut parts: Vec<String> = base
+        .split('/')
+        .filter(|p| !p.is_empty())
+        .map(String::from)
+        .collect();
+    for part in href.split('/') {
+        match part {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            part => parts.push(percent_decode(part)),
+        }
+    }
+    parts.join("/")
+}
+
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            if let Some(byte) = input
+                .get(i + 1..i + 3)
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+            {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+fn open_zip(bytes: &[u8]) -> Result<zip::ZipArchive<std::io::Cursor<&[u8]>>, String> {
+    zip::ZipArchive::new(std::io::Cursor::new(bytes))
+        .map_err(|e| format!("Not a valid archive: {}", e))
+}
+
+fn zip_text(
+    archive: &mut zip::ZipArchive<std::io::Cursor<&[u8]>>,
+    name: &str,
+) -> Result<String, String> {
+    let file = archive
+        .by_name(name)
+        .map_err(|_| format!("Archive has no {}", name))?;
+    let mut text = String::new();
+    file.take(MAX_DOCUMENT_BYTES)
+        .read_to_string(&mut text)
+        .map_err(|e| format!("Failed to read {}: {}", name, e))?;
+    Ok(text)
+}
+
+/// Trim trailing whitespace and collapse runs of blank lines
+fn collapse_blank_lines(text: &str) -> String {
+    let mut out = String::new();
+    let mut blank_run = 0;
+    for line in text.lines().map(str::trim_end) {
+        if line.is_empty() {
+            blank_run += 1;
+            continue;
+        }
+        if !out.is_empty() {
+            out.push_str(if blank_run > 0 { "\n\n" } else { "\n" });
+        }
+        blank_run = 0;
+        out.push_str(line);
+    }
+    out
+}
+
+/// Split sections into chunks of at most about `max_chars`, each section
+/// opened by an `<a id>` anchor; oversized sections are split between
+/// paragraphs and marked as continued
+pub fn chunk_document(document: &Document, max_chars: usize) -> Vec<DocumentChunk> {
+    let mut chunks: Vec<DocumentChunk> = Vec::new();
+    let mut current: Option<DocumentChunk> = None;
+
+    let mut push_piece = |piece: String, label: &str, current: &mut Option<DocumentChunk>| {
+        if let Some(chunk) = current.as_mut() {
+            if chunk.markdown.len() + piece.len() + 2 <= max_chars {
+                chunk.markdown.push_str("\n\n");
+                chunk.markdown.push_str(&piece);
+                chunk.last = label.to_string();
+                return;
+            }
+        }
+        if let Some(full) = current.take() {
+            chunks.push(full);
+        }
+        *current = Some(DocumentChunk {
+            markdown: piece,
+            first: label.to_string(),
+            last: label.to_string(),
+        });
+    };
+
+    for section in &document.sections {
+        let mut parts = split_paragraphs(&section.markdown, max_chars).into_iter();
+        let first = parts.next().unwrap_or_default();
+        push_piece(
+            format!("<a id=\"{}\"></a>\n\n{}", section.anchor, first),
+            &section.label,
+            &mut current,
+        );
+        for part in parts {
+            push_piece(
+                format!("<!-- {}, continued -->\n\n{}", section.label, part),
+                &section.label,
+                &mut current,
+            );
+        }
+    }
+    chunks.extend(current);
+    chunks
+}
+
+/// Group paragraphs into parts of at most `max_chars`, hard-splitting any
+/// single paragraph longer than that
+fn split_paragraphs(text: &str, max_chars: usize) -> Vec<String> {
+    let mut parts: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for paragraph in text.split("\n\n") {
+        let mut paragraph = paragraph;
+        while paragraph.len() > max_chars {
+            let mut cut = max_chars;
+            while !paragraph.is_char_boundary(cut) {
+                cut -= 1;
+            }
+            if !current.is_empty() {
+                parts.push(std::mem::take(&mut current));
+            }
+            parts.push(paragraph[..cut].to_string());
+            paragraph = &paragraph[cut..];
+        }
+        if !current.is_empty() && current.len() + paragraph.len() + 2 > max_chars {
+            parts.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str("\n\n");
+        }
+        current.push_str(paragraph);
+    }
+    if !current.is_empty() || parts.is_empty() {
+        parts.push(current);
+    }
+    parts
+}
+
+/// Input for document_extract tool
+#[derive(Debug, Deserialize)]
+struct DocumentExtractInput {
+    path: String,
+    #[serde(default)]
+    chunk: Option<usize>,
+    #[serde(default)]
+    max_chars: Option<usize>,
+    #[serde(default)]
+    store_in_memory: bool,
+    #[serde(default)]
+    container_tag: Option<String>,
+}
+
+/// Memory store that extracted documents can be saved to
+struct DocumentMemory {
+    store: Arc<MemoryStore>,
+    embedder: Arc<dyn EmbeddingProvider>,
+    config: MemoryConfig,
+}
+
+/// Converts PDF, DOCX and EPUB files into chunked markdown
+pub struct DocumentExtractTool {
+    memory: Option<DocumentMemory>,
+}
+
+impl DocumentExtractTool {
+    pub fn new() -> Self {
+        Self { memory: None }
+    }
+
+    /// Allow `store_in_memory` to save documents to long-term memory
+    pub fn with_memory(
+        mut self,
+        store: Arc<MemoryStore>,
+        embedder: Arc<dyn EmbeddingProvider>,
+        config: MemoryConfig,
+    ) -> Self {
+        self.memory = Some(DocumentMemory {
+            store,
+            embedder,
+            config,
+        });
+        self
+    }
+
+    /// Store every section, split with the memory chunker, as entries tagged
+    /// with the document and the section they came from
+    async fn store_in_memory(
+        &self,
+        path: &Path,
+        document: &Document,
+        container_tag: Option<&str>,
+    ) -> Result<usize, ToolError> {
+        let memory = self.memory.as_ref().ok_or_else(|| ToolError::ExecutionFailed {
+            message: "Long-term memory isn't enabled; set memory.enabled in uira.yml to store documents".to_string(),
+        })?;
+        let failed = |e: anyhow::Error| ToolError::ExecutionFailed {
+            message: format!("Failed to store document in memory: {}", e),
+        };
+        let name = path
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_else(|| path.display().to_string());
+        let container_tag = container_tag.unwrap_or(&memory.config.container_tag);
+        let chunker = TextChunker::new(memory.config.chunk_size, memory.config.chunk_overlap);
+
+        let mut entries = Vec::new();
+        for section in &document.sections {
+            for piece in chunker.chunk(&section.markdown) {
+                let entry = MemoryEntry::new(
+                    format!("[{}, {}]\n{}", name, section.label, piece),
+                    MemorySource::Manual,
+                    container_tag,
+                )
+                .with_metadata("document", serde_json::json!(path.display().to_string()))
+                .with_metadata("anchor", serde_json::json!(section.anchor));
+                entries.push(entry);
+            }
+        }
+
+        let texts: Vec<String> = entries.iter().map(|entry| entry.content.clone()).collect();
+        let embeddings = memory.embedder.embed(&texts).await.map_err(failed)?;
+        for (entry, embedding) in entries.iter().zip(embeddings.iter()) {
+            memory.store.insert(entry, embedding).map_err(failed)?;
+        }
+        Ok(entries.len())
+    }
+}
+
+impl Default for DocumentExtractTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for DocumentExtractTool {
+    fn name(&self) -> &str {
+        "document_extract"
+    }
+
+    fn description(&self) -> &str {
+        "Convert a PDF, DOCX or EPUB document (design docs, specs) into markdown with page or chapter anchors, returned in chunks. Use chunk to page through long documents, and store_in_memory to save the whole document to long-term memory for later memory_search."
+    }
+
+    fn schema(&self) -> JsonSchema {
+        JsonSchema::object()
+            .property(
+                "path",
+                JsonSchema::string().description("Path to a .pdf, .docx or .epub file"),
+            )
+            .property(
+                "chunk",
+                JsonSchema::number().description("1-based chunk to return (default: 1)"),
+            )
+            .property(
+                "max_chars",
+                JsonSchema::number().description("Approximate size of each chunk in characters (default: 12000)"),
+            )
+            .property(
+                "store_in_memory",
+                JsonSchema::boolean().description("Also save the whole document to long-term memory, tagged with its page or chapter (requires memory to be enabled)"),
+            )
+            .property(
+                "container_tag",
+                JsonSchema::string().description("Memory container tag for store_in_memory (default: the configured tag)"),
+            )
+            .required(&["path"])
+    }
+
+    fn approval_requirement(&self, _input: &serde_json::Value) -> ApprovalRequirement {
+        // Reads local files; memory writes stay in the user's own store
+        ApprovalRequirement::Skip {
+            bypass_sandbox: false,
+        }
+    }
+
+    fn supports_parallel(&self) -> bool {
+        true
+    }
+
+    async fn execute(
+        &self,
+        input: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let input: DocumentExtractInput =
+            serde_json::from_value(input).map_err(|e| ToolError::InvalidInput {
+                message: e.to_string(),
+            })?;
+        let path = ctx.resolve_path(&input.path);
+        if !path.is_file() {
+            return Err(ToolError::ExecutionFailed {
+                message: format!("File not found: {}", input.path),
+            });
+        }
+
+        let document = extract_document(&path)
+            .await
+            .map_err(|message| ToolError::ExecutionFailed { message })?;
+        if document.sections.is_empty() {
+            return Err(ToolError::ExecutionFailed {
+                message: format!(
+                    "No text found in {}; it may be scanned images without a text layer",
+                    input.path
+                ),
+            });
+        }
+
+        let max_chars = input
+            .max_chars
+            .unwrap_or(DEFAULT_CHUNK_CHARS)
+            .max(MIN_CHUNK_CHARS);
+        let chunks = chunk_document(&document, max_chars);
+        let index = input.chunk.unwrap_or(1).max(1);
+        let Some(chunk) = chunks.get(index - 1) else {
+            return Err(ToolError::InvalidInput {
+                message: format!(
+                    "chunk {} is out of range; {} has {} chunks",
+                    index,
+                    input.path,
+                    chunks.len()
+                ),
+            });
+        };
+
+        let unit = match document.format {
+            "PDF" => "pages",
+            "EPUB" => "chapters",
+            _ if document.sections[0].anchor.starts_with("page-") => "pages",
+            _ => "sections",
+        };
+        let range = if chunk.first == chunk.last {
+            chunk.first.clone()
+        } else {
+            format!("{} – {}", chunk.first, chunk.last)
+        };
+        let mut output = format!(
+            "# {}\n{}, {} {}; chunk {} of {} ({})\n\n{}",
+            input.path,
+            document.format,
+            document.sections.len(),
+            unit,
+            index,
+            chunks.len(),
+            range,
+            chunk.markdown
+        );
+        if index < chunks.len() {
+            output.push_str(&format!(
+                "\n\n[Chunk {} of {}; call document_extract with chunk={} for the next part]",
+                index,
+                chunks.len(),
+                index + 1
+            ));
+        }
+        if input.store_in_memory {
+            let stored = self
+                .store_in_memory(&path, &document, input.container_tag.as_deref())
+                .await?;
+            output.push_str(&format!(
+                "\n\n[Stored {} memory entries from {}]",
+                stored, input.path
+            ));
+        }
+        Ok(ToolOutput::text(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use std::io::Write as _;
+    use uira_memory::MockEmbeddingProvider;
+
+    fn zip_bytes(files: &[(&str, &str)]) -> Vec<u8> {
+        let mut writer = zip::ZipWriter::new(std::io::Cursor::new(Vec::new()));
+        for (name, content) in files {
+            writer
+                .start_file(*name, zip::write::SimpleFileOptions::default())
+                .unwrap();
+            writer.write_all(content.as_bytes()).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+
+    const DOCX_BODY: &str = r#"<?xml version="1.0"?>
+<w:document xmlns:w="http://schemas.openxmlformats.org/wordprocessingml/2006/main"><w:body>
+<w:p><w:pPr><w:pStyle w:val="Title"/></w:pPr><w:r><w:t>Sync Spec</w:t></w:r></w:p>
+<w:p><w:r><w:t xml:space="preserve">Clients &amp; servers </w:t></w:r><w:r><w:t>agree on a clock.</w:t></w:r></w:p>
+<w:p><w:pPr><w:numPr><w:ilvl w:val="0"/></w:numPr></w:pPr><w:r><w:t>Retry on conflict</w:t></w:r></w:p>
+<w:tbl><w:tr><w:tc><w:p><w:r><w:t>Field</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>Type</w:t></w:r></w:p></w:tc></w:tr>
+<w:tr><w:tc><w:p><w:r><w:t>version</w:t></w:r></w:p></w:tc><w:tc><w:p><w:r><w:t>u64</w:t></w:r></w:p></w:tc></w:tr></w:tbl>
+<w:p><w:r><w:br w:type="page"/></w:r></w:p>
+<w:p><w:pPr><w:pStyle w:val="Heading1"/></w:pPr><w:r><w:t>Conflicts</w:t></w:r></w:p>
+</w:body></w:document>"#;
+
+    #[test]
+    fn converts_docx_to_paged_markdown() {
+        let bytes = zip_bytes(&[("word/document.xml", DOCX_BODY)]);
+        let document = docx_document(&bytes).unwrap();
+        assert_eq!(document.sections.len(), 2);
+        assert_eq!(document.sections[0].anchor, "page-1");
+        assert_eq!(
+            document.sections[0].markdown,
+            "# Sync Spec\n\nClients & servers agree on a clock.\n\n- Retry on conflict\n\n| Field | Type |\n| --- | --- |\n| version | u64 |"
+        );
+        assert_eq!(document.sections[1].label, "page 2");
+        assert_eq!(document.sections[1].markdown, "## Conflicts");
+    }
+
+    #[test]
+    fn converts_epub_chapters_in_spine_order() {
+        let bytes = zip_bytes(&[
+            (
+                "META-INF/container.xml",
+                r#"<container><rootfiles><rootfile full-path="OEBPS/content.opf"/></rootfiles></container>"#,
+            ),
+            (
+                "OEBPS/content.opf",
+                r#"<package><manifest><item id="a" href="text/intro.xhtml"/><item id="b" href="text/design%20notes.xhtml"/></manifest><spine><itemref idref="b"/><itemref idref="a"/></spine></package>"#,
+            ),
+            (
+                "OEBPS/text/intro.xhtml",
+                "<html><head><title>Intro</title></head><body><p>Welcome.</p></body></html>",
+            ),
+            (
+                "OEBPS/text/design notes.xhtml",
+                "<html><body><h1>Design</h1><p>Use a queue.</p></body></html>",
+            ),
+        ]);
+        let document = epub_document(&bytes).unwrap();
+        let labels: Vec<&str> = document.sections.iter().map(|s| s.label.as_str()).collect();
+        assert_eq!(labels, vec!["chapter 1", "chapter 2: Intro"]);
+        assert!(document.sections[0].markdown.contains("Use a queue."));
+    }
+
+    #[test]
+    fn chunks_keep_section_anchors() {
+        let document = pdf_document("Page one text\u{c}Second page\n\n\nmore\u{c}");
+        assert_eq!(document.sections.len(), 2);
+        assert_eq!(document.sections[1].markdown, "Second page\n\nmore");
+
+        let chunks = chunk_document(&document, 10_000);
+        assert_eq!(chunks.len(), 1);
+        assert!(chunks[0]
+            .markdown
+            .starts_with("<a id=\"page-1\"></a>\n\nPage one text"));
+        assert_eq!(
+            (chunks[0].first.as_str(), chunks[0].last.as_str()),
+            ("page 1", "page 2")
+        );
+
+        let long = Document {
+            format: "PDF",
+            sections: vec![DocumentSection {
+                anchor: "page-1".to_string(),
+                label: "page 1".to_string(),
+                markdown: ["a".repeat(60), "b".repeat(60), "c".repeat(60)].join("\n\n"),
+            }],
+        };
+        let chunks = chunk_document(&long, 100);
+        assert_eq!(chunks.len(), 3);
+        assert!(chunks[1]
+            .markdown
+            .starts_with("<!-- page 1, continued -->\n\nbbb"));
+    }
+
+    #[tokio::test]
+    async fn tool_pages_through_chunks_and_stores_in_memory() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("spec.docx");
+        std::fs::write(&path, zip_bytes(&[("word/document.xml", DOCX_BODY)])).unwrap();
+
+        let store = Arc::new(MemoryStore::new_in_memory(8).unwrap());
+        let config = MemoryConfig {
+            embedding_dimension: 8,
+            ..Default::default()
+        };
+        let tool = DocumentExtractTool::new().with_memory(
+            store.clone(),
+            Arc::new(MockEmbeddingProvider::new(8)),
+            config,
+        );
+        let ctx = ToolContext {
+            cwd: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        let result = tool
+            .execute(json!({"path": "spec.docx", "store_in_memory": true}), &ctx)
+            .await
+            .unwrap();
+        let text = result.as_text().unwrap();
+        assert!(text.starts_with("# spec.docx\nDOCX, 2 pages; chunk 1 of 1 (page 1 – page 2)"));
+        assert!(text.contains("<a id=\"page-2\"></a>\n\n## Conflicts"));
+        assert!(text.contains("[Stored 2 memory entries from spec.docx]"));
+        assert_eq!(store.count().unwrap(), 2);
+
+        let err = tool
+            .execute(json!({"path": "spec.docx", "chunk": 3}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("has 1 chunks"));
+    }
+}
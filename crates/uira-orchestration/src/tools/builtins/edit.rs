@@ -1,16 +1,24 @@
 use async_trait::async_trait;
 use serde::Deserialize;
 use similar::TextDiff;
-use std::path::Path;
 use tokio::fs;
 use uira_core::{ApprovalRequirement, JsonSchema, SandboxPreference, ToolOutput};
 
 use crate::tools::{Tool, ToolContext, ToolError};
 
-use super::fuzzy::{find_similar_strings, FindOptions};
+use super::fuzzy::{find_closest_hunk, find_similar_strings, ClosestHunk, FindOptions};
 use super::hashline;
 use crate::tools::output::{StandardOutput, ToolOutputFormat, SECTION_DIFF};
 
+/// Minimum similarity for a run of lines to be shown as the closest match
+const MIN_HUNK_SIMILARITY: f64 = 0.5;
+
+/// Minimum similarity for a closest match to be offered as a corrected edit
+const MIN_SUGGEST_SIMILARITY: f64 = 0.8;
+
+/// Heading of the mismatch report line carrying the corrected edit as JSON
+const SUGGESTED_EDIT_HEADER: &str = "🛠 SUGGESTED EDIT:";
+
 #[derive(Debug, Deserialize)]
 struct LegacyEditInput {
     file_path: String,
@@ -220,7 +228,8 @@ impl EditTool {
         result
     }
 
-    fn format_error_with_suggestions(old_string: &str, content: &str) -> String {
+    fn format_error_with_suggestions(input: &LegacyEditInput, content: &str) -> String {
+        let old_string = &input.old_string;
         let similar = find_similar_strings(content, old_string, &FindOptions::default());
         let escaped = Self::escape_for_display(old_string);
 
@@ -243,6 +252,36 @@ impl EditTool {
             }
         }
 
+        if let Some(hunk) = find_closest_hunk(content, old_string, MIN_HUNK_SIMILARITY) {
+            msg.push_str(&format!(
+                "\n📄 CLOSEST MATCH (lines {}-{}, {:.0}%{}):\n",
+                hunk.start_line,
+                hunk.end_line,
+                hunk.similarity * 100.0,
+                if hunk.whitespace_only {
+                    ", only whitespace differs"
+                } else {
+                    ""
+                }
+            ));
+            for (i, line) in hunk.lines.iter().enumerate() {
+                msg.push_str(&format!(
+                    "  {:>5} | {}\n",
+                    hunk.start_line + i,
+                    Self::escape_for_display(line)
+                ));
+            }
+
+            if let Some(suggested) = Self::suggest_edit(input, content, &hunk) {
+                msg.push_str(&format!("\n{}\n  {}\n", SUGGESTED_EDIT_HEADER, suggested));
+                if !hunk.whitespace_only {
+                    msg.push_str(
+                        "  (old_string is copied from the file; check new_string still applies)\n",
+                    );
+                }
+            }
+        }
+
         msg.push_str("\n📋 RECOVERY STRATEGIES:\n");
         msg.push_str("  1. Use Read tool to view the file around the target area\n");
         msg.push_str("  2. Copy the exact text from the file content\n");
@@ -252,6 +291,71 @@ impl EditTool {
         msg
     }
 
+    /// A corrected edit targeting `hunk`, when it's close enough and unique
+    fn suggest_edit(
+        input: &LegacyEditInput,
+        content: &str,
+        hunk: &ClosestHunk,
+    ) -> Option<serde_json::Value> {
+        if !hunk.whitespace_only && hunk.similarity < MIN_SUGGEST_SIMILARITY {
+            return None;
+        }
+
+        let newline = if content.contains("\r\n") {
+            "\r\n"
+        } else {
+            "\n"
+        };
+        let mut old_string = hunk.lines.join(newline);
+        if input.old_string.ends_with('\n') {
+            old_string.push_str(newline);
+        }
+        if content.matches(&old_string).count() != 1 {
+            return None;
+        }
+
+        let new_string = if hunk.whitespace_only {
+            Self::reindent(&input.old_string, &hunk.lines, &input.new_string)
+        } else {
+            input.new_string.clone()
+        };
+        Some(serde_json::json!({
+            "file_path": input.file_path,
+            "old_string": old_string,
+            "new_string": new_string,
+        }))
+    }
+
+    /// Shift `new_string` by the indentation difference between the first
+    /// non-blank lines of `old_string` and the file
+    fn reindent(old_string: &str, file_lines: &[String], new_string: &str) -> String {
+        let indent = |line: &str| line[..line.len() - line.trim_start().len()].to_string();
+        let Some((i, old_line)) = old_string
+            .lines()
+            .enumerate()
+            .find(|(_, line)| !line.trim().is_empty())
+        else {
+            return new_string.to_string();
+        };
+        let (from, to) = (indent(old_line), indent(&file_lines[i]));
+
+        new_string
+            .split('\n')
+            .map(|line| match line.strip_prefix(from.as_str()) {
+                Some(rest) if !line.trim().is_empty() => format!("{}{}", to, rest),
+                _ => line.to_string(),
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    /// The corrected edit from a mismatch report, ready to run as Edit input
+    pub fn suggested_edit(error: &str) -> Option<serde_json::Value> {
+        let (_, rest) = error.split_once(SUGGESTED_EDIT_HEADER)?;
+        let json = rest.trim_start_matches('\n').lines().next()?.trim();
+        serde_json::from_str(json).ok()
+    }
+
     fn apply_legacy_edit(content: &str, input: &LegacyEditInput) -> Result<String, ToolError> {
         if input.old_string == input.new_string {
             return Err(ToolError::ExecutionFailed {
@@ -260,7 +364,7 @@ impl EditTool {
         }
 
         if !content.contains(&input.old_string) {
-            let msg = Self::format_error_with_suggestions(&input.old_string, content);
+            let msg = Self::format_error_with_suggestions(input, content);
             return Err(ToolError::ExecutionFailed { message: msg });
         }
         if !input.replace_all {
@@ -380,7 +484,7 @@ impl Tool for EditTool {
     async fn execute(
         &self,
         input: serde_json::Value,
-        _ctx: &ToolContext,
+        ctx: &ToolContext,
     ) -> Result<ToolOutput, ToolError> {
         let input: EditInput =
             serde_json::from_value(input).map_err(|e| ToolError::InvalidInput {
@@ -392,7 +496,10 @@ impl Tool for EditTool {
             EditInput::Legacy(i) => &i.file_path,
         };
 
-        let path = Path::new(file_path);
+        let resolved = ctx.resolve_path(file_path);
+        let path = resolved.as_path();
+        ctx.check_writable(path)?;
+        ctx.file_tracker.check_fresh(path)?;
 
         if !path.exists() {
             if let EditInput::Legacy(legacy) = &input {
@@ -410,6 +517,7 @@ impl Tool for EditTool {
                             message: format!("Failed to write file: {}", e),
                         }
                     })?;
+                    ctx.file_tracker.record(path, legacy.new_string.as_bytes());
 
                     let diff = TextDiff::from_lines("", &legacy.new_string);
                     let unified = diff
@@ -471,6 +579,7 @@ impl Tool for EditTool {
             .map_err(|e| ToolError::ExecutionFailed {
                 message: format!("Failed to write file: {}", e),
             })?;
+        ctx.file_tracker.record(path, new_content.as_bytes());
 
         let diff = TextDiff::from_lines(&content, &new_content);
         let unified = diff
@@ -488,6 +597,7 @@ mod tests {
     use super::*;
     use serde_json::json;
     use std::io::Write;
+    use std::path::Path;
     use tempfile::NamedTempFile;
 
     fn read_text(path: &Path) -> String {
@@ -845,6 +955,72 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_edit_legacy_mismatch_suggests_reindented_edit() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(
+            file,
+            "fn main() {{\n    let x = 1;\n    if x > 0 {{\n        run();\n    }}\n}}\n"
+        )
+        .unwrap();
+
+        let tool = EditTool::new();
+        let ctx = ToolContext::default();
+        let err_msg = tool
+            .execute(
+                json!({
+                    "file_path": file.path().to_string_lossy(),
+                    "old_string": "if x > 0 {\n    run();\n}",
+                    "new_string": "if x > 1 {\n    run();\n}"
+                }),
+                &ctx,
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            err_msg.contains("CLOSEST MATCH (lines 3-5, 100%, only whitespace differs)"),
+            "got: {err_msg}"
+        );
+        let suggested = EditTool::suggested_edit(&err_msg).unwrap();
+        assert_eq!(
+            suggested["new_string"],
+            "    if x > 1 {\n        run();\n    }"
+        );
+
+        tool.execute(suggested, &ctx).await.unwrap();
+        let content = std::fs::read_to_string(file.path()).unwrap();
+        assert!(content.contains("    if x > 1 {\n        run();\n    }"));
+    }
+
+    #[tokio::test]
+    async fn test_edit_legacy_mismatch_without_close_hunk_has_no_suggestion() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "let total = a + b;\nprintln!(total);\n").unwrap();
+
+        let tool = EditTool::new();
+        let ctx = ToolContext::default();
+        let err_msg = tool
+            .execute(
+                json!({
+                    "file_path": file.path().to_string_lossy(),
+                    "old_string": "let total = x * y * z;\nprint(total)",
+                    "new_string": "let total = 0;"
+                }),
+                &ctx,
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+
+        assert!(
+            err_msg.contains("CLOSEST MATCH (lines 1-2"),
+            "got: {err_msg}"
+        );
+        assert!(EditTool::suggested_edit(&err_msg).is_none());
+    }
+
     #[tokio::test]
     async fn test_edit_legacy_not_found_no_similar_strings() {
         let mut file = NamedTempFile::new().unwrap();
@@ -1124,4 +1300,36 @@ mod tests {
         assert_eq!(EditTool::escape_for_display("line1\nline2"), "line1\nline2");
         assert_eq!(EditTool::escape_for_display("no specials"), "no specials");
     }
+
+    #[tokio::test]
+    async fn test_edit_refuses_file_changed_since_read() {
+        let mut file = NamedTempFile::new().unwrap();
+        write!(file, "let a = 1;").unwrap();
+        let path = file.path().to_string_lossy().to_string();
+
+        let ctx = ToolContext::default();
+        let read = crate::tools::builtins::ReadTool::new();
+        read.execute(json!({ "file_path": path }), &ctx)
+            .await
+            .unwrap();
+
+        // The user saves a different version in their editor
+        std::fs::write(file.path(), "let a = 1;\nlet b = 2;").unwrap();
+
+        let tool = EditTool::new();
+        let edit = json!({
+            "file_path": path,
+            "old_string": "let a = 1;",
+            "new_string": "let a = 10;"
+        });
+        let err = tool.execute(edit.clone(), &ctx).await.unwrap_err();
+        assert!(err.to_string().contains("changed on disk"));
+        assert_eq!(read_text(file.path()), "let a = 1;\nlet b = 2;");
+
+        read.execute(json!({ "file_path": path }), &ctx)
+            .await
+            .unwrap();
+        tool.execute(edit, &ctx).await.unwrap();
+        assert_eq!(read_text(file.path()), "let a = 10;\nlet b = 2;");
+    }
 }
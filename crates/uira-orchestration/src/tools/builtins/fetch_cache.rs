@@ -0,0 +1,106 @@
+//! On-disk cache for fetch_url, revalidated with ETag / Last-Modified
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A processed page as returned to the model
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct CachedPage {
+    pub url: String,
+    pub format: String,
+    pub content_type: String,
+    pub title: Option<String>,
+    pub content: String,
+    #[serde(default)]
+    pub etag: Option<String>,
+    #[serde(default)]
+    pub last_modified: Option<String>,
+    /// Unix seconds of the last successful fetch or revalidation
+    pub fetched_at: u64,
+}
+
+impl CachedPage {
+    pub fn is_fresh(&self, ttl_secs: u64) -> bool {
+        now_secs().saturating_sub(self.fetched_at) < ttl_secs
+    }
+}
+
+pub(crate) fn now_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Directory of cached pages, one JSON file per URL and output format
+pub(crate) struct FetchCache {
+    dir: PathBuf,
+}
+
+impl FetchCache {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self { dir: dir.into() }
+    }
+
+    /// Default location under the project's `.uira` directory
+    pub fn default_dir(cwd: &Path) -> PathBuf {
+        cwd.join(".uira").join("cache").join("fetch")
+    }
+
+    fn path(&self, url: &str, format: &str) -> PathBuf {
+        let digest = Sha256::digest(format!("{format}:{url}").as_bytes());
+        self.dir.join(format!("{}.json", hex::encode(digest)))
+    }
+
+    pub fn get(&self, url: &str, format: &str) -> Option<CachedPage> {
+        let content = std::fs::read_to_string(self.path(url, format)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    pub fn put(&self, page: &CachedPage) -> std::io::Result<()> {
+        std::fs::create_dir_all(&self.dir)?;
+        let json = serde_json::to_string(page).map_err(std::io::Error::other)?;
+        std::fs::write(self.path(&page.url, &page.format), json)
+    }
+
+    pub fn remove(&self, url: &str, format: &str) {
+        let _ = std::fs::remove_file(self.path(url, format));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips_pages_per_format() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = FetchCache::new(dir.path());
+        let page = CachedPage {
+            url: "https://example.com/".to_string(),
+            format: "markdown".to_string(),
+            content_type: "text/html".to_string(),
+            title: Some("Example".to_string()),
+            content: "# Example".to_string(),
+            etag: Some("\"abc\"".to_string()),
+            last_modified: None,
+            fetched_at: now_secs(),
+        };
+        cache.put(&page).unwrap();
+
+        assert_eq!(cache.get(&page.url, "markdown"), Some(page.clone()));
+        assert_eq!(cache.get(&page.url, "text"), None);
+        assert!(page.is_fresh(60));
+
+        let stale = CachedPage {
+            fetched_at: page.fetched_at - 120,
+            ..page.clone()
+        };
+        assert!(!stale.is_fresh(60));
+
+        cache.remove(&page.url, "markdown");
+        assert_eq!(cache.get(&page.url, "markdown"), None);
+    }
+}
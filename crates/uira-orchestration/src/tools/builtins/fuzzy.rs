@@ -242,6 +242,71 @@ pub fn find_similar_strings(
         .collect()
 }
 
+/// A run of lines closely matching a multi-line search string.
+#[derive(Debug, Clone)]
+pub struct ClosestHunk {
+    /// 1-based line number of the first line
+    pub start_line: usize,
+    /// 1-based line number of the last line
+    pub end_line: usize,
+    /// The lines as they appear in the content
+    pub lines: Vec<String>,
+    /// Mean per-line similarity, ignoring surrounding whitespace (0.0-1.0)
+    pub similarity: f64,
+    /// Whether the lines differ from the search only in surrounding whitespace
+    pub whitespace_only: bool,
+}
+
+/// Find the run of lines that best matches `search`, line for line.
+///
+/// Only windows whose first or last line resembles the search's first or last
+/// line are scored in full, which keeps large files cheap.
+///
+/// # Returns
+/// The best window with similarity of at least `min_similarity`, if any
+pub fn find_closest_hunk(content: &str, search: &str, min_similarity: f64) -> Option<ClosestHunk> {
+    let lines: Vec<&str> = content.lines().collect();
+    let search_lines: Vec<&str> = search.lines().map(str::trim).collect();
+    let n = search_lines.len();
+    if n == 0 || lines.len() < n {
+        return None;
+    }
+
+    let (first, last) = (search_lines[0], search_lines[n - 1]);
+    let mut best: Option<(usize, f64)> = None;
+    for start in 0..=(lines.len() - n) {
+        let anchored = similarity_ratio(lines[start].trim(), first) >= 0.5
+            || similarity_ratio(lines[start + n - 1].trim(), last) >= 0.5;
+        if !anchored {
+            continue;
+        }
+
+        let total: f64 = search_lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| similarity_ratio(lines[start + i].trim(), line))
+            .sum();
+        let similarity = total / n as f64;
+        if similarity >= min_similarity && best.is_none_or(|(_, b)| similarity > b) {
+            best = Some((start, similarity));
+        }
+    }
+
+    best.map(|(start, similarity)| {
+        let window = &lines[start..start + n];
+        ClosestHunk {
+            start_line: start + 1,
+            end_line: start + n,
+            lines: window.iter().map(|s| s.to_string()).collect(),
+            similarity,
+            whitespace_only: window
+                .iter()
+                .zip(&search_lines)
+                .all(|(line, search)| line.trim() == *search),
+        }
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -388,4 +453,28 @@ mod tests {
         assert_eq!(results[0].context_before.len(), 0);
         assert_eq!(results[0].context_after.len(), 0);
     }
+
+    #[test]
+    fn test_find_closest_hunk_reindented() {
+        let content = "fn main() {\n    let x = 1;\n    let y = 2;\n}\n";
+        let hunk = find_closest_hunk(content, "let x = 1;\nlet y = 2;", 0.5).unwrap();
+
+        assert_eq!((hunk.start_line, hunk.end_line), (2, 3));
+        assert_eq!(hunk.lines, vec!["    let x = 1;", "    let y = 2;"]);
+        assert_eq!(hunk.similarity, 1.0);
+        assert!(hunk.whitespace_only);
+    }
+
+    #[test]
+    fn test_find_closest_hunk_best_window() {
+        let content = "let a = 1;\nlet b = 2;\nlet c = 3;\nlet total = a + b;\n";
+        let hunk = find_closest_hunk(content, "let c = 3;\nlet totl = a + b;", 0.5).unwrap();
+
+        assert_eq!(hunk.start_line, 3);
+        assert!(hunk.similarity > 0.9 && hunk.similarity < 1.0);
+        assert!(!hunk.whitespace_only);
+
+        assert!(find_closest_hunk(content, "zzzz\nqqqq", 0.5).is_none());
+        assert!(find_closest_hunk("one line", "a\nb", 0.0).is_none());
+    }
 }
@@ -81,7 +81,7 @@ impl Tool for GlobTool {
 
         let base_path = input
             .path
-            .map(std::path::PathBuf::from)
+            .map(|path| ctx.resolve_path(&path))
             .unwrap_or_else(|| ctx.cwd.clone());
 
         // Build full pattern
@@ -8,9 +8,12 @@ use tokio::fs;
 use uira_core::{ApprovalRequirement, JsonSchema, SandboxPreference, ToolOutput};
 use walkdir::WalkDir;
 
-use super::hashline;
+use super::{assets, guards, hashline};
 use crate::tools::{Tool, ToolContext, ToolError};
 
+/// Files larger than this are skipped rather than searched line by line
+const MAX_GREP_FILE_BYTES: u64 = guards::DEFAULT_MAX_FILE_SIZE;
+
 /// Input for grep tool
 #[derive(Debug, Deserialize)]
 #[allow(dead_code)]
@@ -205,7 +208,7 @@ impl Tool for GrepTool {
 
         let base_path = input
             .path
-            .map(std::path::PathBuf::from)
+            .map(|path| ctx.resolve_path(&path))
             .unwrap_or_else(|| ctx.cwd.clone());
 
         let output_mode = input.output_mode.as_deref().unwrap_or("files_with_matches");
@@ -213,6 +216,8 @@ impl Tool for GrepTool {
 
         let mut matches: Vec<GrepMatch> = Vec::new();
         let mut files_with_matches: Vec<String> = Vec::new();
+        let mut skipped_binary = 0usize;
+        let mut skipped_large = 0usize;
 
         for entry in WalkDir::new(&base_path)
             .into_iter()
@@ -228,10 +233,30 @@ impl Tool for GrepTool {
                 continue;
             }
 
+            if entry
+                .metadata()
+                .is_ok_and(|m| m.len() > MAX_GREP_FILE_BYTES)
+            {
+                skipped_large += 1;
+                continue;
+            }
+
             // Read file content
-            let content = match fs::read_to_string(path).await {
-                Ok(c) => c,
-                Err(_) => continue, // Skip binary/unreadable files
+            let bytes = match fs::read(path).await {
+                Ok(bytes) => bytes,
+                Err(_) => continue, // Skip unreadable files
+            };
+            let header = &bytes[..bytes.len().min(assets::SNIFF_BYTES)];
+            if assets::sniff(header).is_some() || guards::looks_binary(header) {
+                skipped_binary += 1;
+                continue;
+            }
+            let content = match String::from_utf8(bytes) {
+                Ok(content) => content,
+                Err(_) => {
+                    skipped_binary += 1;
+                    continue;
+                }
             };
 
             let mut file_has_match = false;
@@ -303,6 +328,28 @@ impl Tool for GrepTool {
             }
         };
 
+        let mut skipped = Vec::new();
+        if skipped_binary > 0 {
+            skipped.push(format!(
+                "{} binary {}",
+                skipped_binary,
+                if skipped_binary == 1 { "file" } else { "files" }
+            ));
+        }
+        if skipped_large > 0 {
+            skipped.push(format!(
+                "{} {} over {} MB",
+                skipped_large,
+                if skipped_large == 1 { "file" } else { "files" },
+                MAX_GREP_FILE_BYTES / (1024 * 1024)
+            ));
+        }
+        let output = if skipped.is_empty() {
+            output
+        } else {
+            format!("{}\n(skipped {})", output, skipped.join(" and "))
+        };
+
         Ok(ToolOutput::text(output))
     }
 }
@@ -371,4 +418,31 @@ mod tests {
         assert!(text.contains("test.rs"));
         assert!(!text.contains("test.py"));
     }
+
+    #[tokio::test]
+    async fn test_grep_skips_binary_files() {
+        let dir = tempdir().unwrap();
+        std::fs::write(dir.path().join("notes.txt"), "needle in text").unwrap();
+        std::fs::write(dir.path().join("blob.bin"), b"needle\0\x01\x02binary").unwrap();
+        std::fs::write(dir.path().join("logo.png"), b"\x89PNG\r\n\x1a\nneedle").unwrap();
+
+        let tool = GrepTool::new();
+        let ctx = ToolContext::default();
+        let result = tool
+            .execute(
+                json!({
+                    "pattern": "needle",
+                    "path": dir.path().to_string_lossy()
+                }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+
+        let text = result.as_text().unwrap();
+        assert!(text.contains("Found 1 files"));
+        assert!(text.contains("notes.txt"));
+        assert!(!text.contains("blob.bin"));
+        assert!(text.ends_with("(skipped 2 binary files)"));
+    }
 }
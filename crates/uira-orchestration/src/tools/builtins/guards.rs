@@ -45,11 +45,19 @@ pub fn is_binary_file(path: &Path) -> io::Result<bool> {
         return Ok(false);
     }
 
-    let content = &buffer[..bytes_read];
+    Ok(looks_binary(&buffer[..bytes_read]))
+}
+
+/// Binary check on the first bytes of a file, the same heuristic as
+/// [`is_binary_file`]
+pub fn looks_binary(content: &[u8]) -> bool {
+    if content.is_empty() {
+        return false;
+    }
 
     // Check for null bytes (strong indicator of binary)
     if content.contains(&0) {
-        return Ok(true);
+        return true;
     }
 
     // Count non-printable characters (excluding common whitespace)
@@ -62,8 +70,8 @@ pub fn is_binary_file(path: &Path) -> io::Result<bool> {
         .count();
 
     // If more than 30% of bytes are non-printable, consider it binary
-    let non_printable_ratio = non_printable_count as f64 / bytes_read as f64;
-    Ok(non_printable_ratio > 0.3)
+    let non_printable_ratio = non_printable_count as f64 / content.len() as f64;
+    non_printable_ratio > 0.3
 }
 
 /// Checks if a path matches common ignore patterns.
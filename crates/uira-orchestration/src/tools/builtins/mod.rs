@@ -3,8 +3,13 @@
 //! These are the core tools that the agent uses to interact with the filesystem
 //! and execute commands.
 
+pub(crate) mod assets;
 mod bash;
+mod clipboard;
+mod docs_lookup;
+mod document_extract;
 mod edit;
+mod fetch_cache;
 pub(crate) mod fuzzy;
 mod glob;
 mod grep;
@@ -12,11 +17,18 @@ pub(crate) mod guards;
 pub(crate) mod hashline;
 pub mod memory;
 mod read;
+mod readability;
+mod robots;
+mod search_backends;
 pub mod todo;
 mod web_search;
+mod workspace_index;
 mod write;
 
 pub use bash::BashTool;
+pub use clipboard::ClipboardTool;
+pub use docs_lookup::DocsLookupTool;
+pub use document_extract::DocumentExtractTool;
 pub use edit::EditTool;
 pub use glob::GlobTool;
 pub use grep::GrepTool;
@@ -24,6 +36,7 @@ pub use memory::{MemoryForgetTool, MemoryProfileTool, MemorySearchTool, MemorySt
 pub use read::ReadTool;
 pub use todo::{TodoReadTool, TodoSessionInfo, TodoStore, TodoWriteTool};
 pub use web_search::{CodeSearchTool, FetchUrlTool, GrepAppTool, WebSearchTool};
+pub use workspace_index::WorkspaceIndexTool;
 pub use write::WriteTool;
 
 use crate::tools::{BoxedTool, ToolRouter};
@@ -40,6 +53,10 @@ pub fn register_builtins(router: &mut ToolRouter) {
     router.register(FetchUrlTool::new());
     router.register(CodeSearchTool::new());
     router.register(GrepAppTool::new());
+    router.register(DocsLookupTool::new());
+    router.register(ClipboardTool::new());
+    router.register(WorkspaceIndexTool::new());
+    router.register(DocumentExtractTool::new());
 }
 
 pub fn register_builtins_with_todos(router: &mut ToolRouter, store: TodoStore) {
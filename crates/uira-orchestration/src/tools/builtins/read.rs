@@ -1,19 +1,28 @@
 //! Read tool for reading file contents
 
 use async_trait::async_trait;
+use base64::Engine;
 use serde::Deserialize;
 use std::path::Path;
 use tokio::fs;
-use uira_core::{ApprovalRequirement, JsonSchema, SandboxPreference, ToolOutput};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncSeekExt, BufReader};
+use uira_core::{
+    ApprovalRequirement, ImageSource, JsonSchema, SandboxPreference, ToolOutput, ToolOutputContent,
+};
 
+use crate::tools::ast_grep::{find_symbol, outline, outline_language};
 use crate::tools::{Tool, ToolContext, ToolError};
 
+use super::assets::{self, FileType};
 use super::guards;
 use super::hashline;
 use crate::tools::output::{StandardOutput, ToolOutputFormat, SECTION_CONTENT, SECTION_METADATA};
 
 const MAX_READ_FILE_BYTES: u64 = 10 * 1024 * 1024;
 const MAX_LINE_DISPLAY_CHARS: usize = 2000;
+const DEFAULT_READ_LIMIT: usize = 2000;
+/// Top-level declarations listed when a `symbol` isn't found
+const MAX_LISTED_SYMBOLS: usize = 40;
 
 /// Input for read tool
 #[derive(Debug, Deserialize)]
@@ -29,6 +38,10 @@ struct ReadInput {
     before: Option<usize>,
     #[serde(default, rename = "after")]
     after_lines: Option<usize>,
+    #[serde(default)]
+    symbol: Option<String>,
+    #[serde(default)]
+    hexdump: bool,
 }
 
 /// Read tool for reading file contents
@@ -61,6 +74,270 @@ impl ReadTool {
             .collect::<Vec<_>>()
             .join("\n")
     }
+
+    /// 0-based start, line count and the end of the requested region for an
+    /// offset/limit or around_line read of a file with `total_lines` lines
+    fn line_window(input: &ReadInput, total_lines: usize) -> (usize, usize, usize) {
+        if let Some(center) = input.around_line {
+            let before_count = input.before.unwrap_or(5);
+            let after_count = input.after_lines.unwrap_or(10);
+            let center_0based = center.saturating_sub(1); // Convert 1-based to 0-based
+            let start = center_0based.saturating_sub(before_count);
+            let count = before_count + 1 + after_count;
+            (start, count, start + count)
+        } else {
+            (
+                input.offset.unwrap_or(0),
+                input.limit.unwrap_or(DEFAULT_READ_LIMIT),
+                total_lines,
+            )
+        }
+    }
+
+    /// Describe a binary file instead of returning its bytes: type and size,
+    /// plus a hexdump on request, extracted text for PDFs and the image
+    /// itself for formats models accept
+    async fn read_binary(
+        path: &Path,
+        input: &ReadInput,
+        size: u64,
+        header: &[u8],
+        file_type: FileType,
+    ) -> Result<ToolOutput, ToolError> {
+        let io_error = |e: std::io::Error| ToolError::ExecutionFailed {
+            message: format!("Failed to read file: {}", e),
+        };
+        let type_str = format!("{} ({})", file_type.mime, file_type.description);
+        let size_str = assets::format_size(size);
+        let dimensions =
+            assets::image_dimensions(header).map(|(width, height)| format!("{}x{}", width, height));
+        let mut metadata_pairs = vec![("type", type_str.as_str()), ("size", size_str.as_str())];
+        if let Some(dimensions) = dimensions.as_deref() {
+            metadata_pairs.push(("dimensions", dimensions));
+        }
+
+        let attach_image =
+            !input.hexdump && file_type.is_model_image() && size <= assets::MAX_IMAGE_BYTES;
+        let mut hint = None;
+        let body = if input.hexdump {
+            // offset/limit count bytes here
+            let start = input.offset.unwrap_or(0);
+            let count = input
+                .limit
+                .unwrap_or(assets::DEFAULT_HEXDUMP_BYTES)
+                .min(assets::MAX_HEXDUMP_BYTES);
+            let mut file = fs::File::open(path).await.map_err(io_error)?;
+            file.seek(std::io::SeekFrom::Start(start as u64))
+                .await
+                .map_err(io_error)?;
+            let mut bytes = Vec::with_capacity(count);
+            file.take(count as u64)
+                .read_to_end(&mut bytes)
+                .await
+                .map_err(io_error)?;
+            let end = start + bytes.len();
+            if (end as u64) < size {
+                hint = Some(format!(
+                    "[{} more bytes; Read with hexdump=true offset={} to continue]",
+                    size - end as u64,
+                    end
+                ));
+            }
+            assets::hexdump(&bytes, start)
+        } else if file_type.is_pdf() {
+            match assets::pdf_text(path).await {
+                Ok(text) => {
+                    let lines: Vec<&str> = text.lines().collect();
+                    let (offset, limit, wanted_end) = Self::line_window(input, lines.len());
+                    let selected: Vec<&str> =
+                        lines.iter().skip(offset).take(limit).copied().collect();
+                    let end = offset + selected.len();
+                    if end < wanted_end.min(lines.len()) {
+                        hint = Some(format!(
+                            "[{} more lines of extracted text; Read with offset={} to continue]",
+                            lines.len() - end,
+                            end
+                        ));
+                    }
+                    selected.join("\n")
+                }
+                Err(reason) => format!("No text extracted: {}.", reason),
+            }
+        } else if attach_image {
+            "Image attached below.".to_string()
+        } else {
+            "Binary file; contents not shown. Read with hexdump=true to see the raw bytes."
+                .to_string()
+        };
+
+        let metadata = StandardOutput::format_metadata(&metadata_pairs);
+        let mut output = format!(
+            "{}\n{}\n{}",
+            input.file_path,
+            StandardOutput::format_section(SECTION_METADATA, &metadata),
+            StandardOutput::format_section(SECTION_CONTENT, &body)
+        );
+        if let Some(hint) = hint {
+            output.push_str(&format!("\n\n{}", hint));
+        }
+
+        let mut result = ToolOutput::text(output);
+        if attach_image {
+            let bytes = fs::read(path).await.map_err(io_error)?;
+            result.content.push(ToolOutputContent::Image {
+                source: ImageSource::Base64 {
+                    media_type: file_type.mime.to_string(),
+                    data: base64::engine::general_purpose::STANDARD.encode(bytes),
+                },
+            });
+        }
+        Ok(result)
+    }
+
+    /// Read a window of a text file too large to load whole, streaming lines
+    /// up to the window instead of reading the entire file
+    async fn read_large(
+        path: &Path,
+        input: &ReadInput,
+        size: u64,
+    ) -> Result<ToolOutput, ToolError> {
+        let size_str = assets::format_size(size);
+        if input.symbol.is_some() {
+            return Err(ToolError::ExecutionFailed {
+                message: format!(
+                    "{} is {}, too large for symbol lookup; find the declaration with Grep and read around_line instead",
+                    input.file_path, size_str
+                ),
+            });
+        }
+        let io_error = |e: std::io::Error| ToolError::ExecutionFailed {
+            message: format!("Failed to read file: {}", e),
+        };
+
+        let (offset, limit, wanted_end) = Self::line_window(input, usize::MAX);
+        let file = fs::File::open(path).await.map_err(io_error)?;
+        let mut reader = BufReader::new(file);
+        let mut buf = Vec::new();
+        let mut selected = Vec::new();
+        let mut index = 0;
+        let mut more = false;
+        loop {
+            buf.clear();
+            if reader.read_until(b'\n', &mut buf).await.map_err(io_error)? == 0 {
+                break;
+            }
+            if index >= offset {
+                if selected.len() == limit {
+                    more = true;
+                    break;
+                }
+                let line = buf.strip_suffix(b"\n").unwrap_or(&buf);
+                let line = line.strip_suffix(b"\r").unwrap_or(line);
+                selected.push(String::from_utf8_lossy(line).into_owned());
+            }
+            index += 1;
+        }
+
+        let selected: Vec<&str> = selected.iter().map(String::as_str).collect();
+        let start_line = if selected.is_empty() { 0 } else { offset + 1 };
+        let end_line = offset + selected.len();
+        let range_str = format!("L{}-L{}", start_line, end_line);
+        let metadata = StandardOutput::format_metadata(&[
+            ("size", size_str.as_str()),
+            ("range", range_str.as_str()),
+        ]);
+        let mut output = format!(
+            "{}\n{}\n{}",
+            input.file_path,
+            StandardOutput::format_section(SECTION_METADATA, &metadata),
+            StandardOutput::format_section(
+                SECTION_CONTENT,
+                &Self::format_output(&selected, offset)
+            )
+        );
+        if more && end_line < wanted_end {
+            output.push_str(&format!(
+                "\n\n[File is {}; only a window is shown. Read with offset={} limit={} to continue]",
+                size_str, end_line, DEFAULT_READ_LIMIT
+            ));
+        }
+        Ok(ToolOutput::text(output))
+    }
+
+    /// 0-based start and line count of the declaration named `symbol`,
+    /// including the doc comments and attributes directly above it
+    fn symbol_range(
+        path: &Path,
+        content: &str,
+        lines: &[&str],
+        symbol: &str,
+    ) -> Result<(usize, usize), ToolError> {
+        let unsupported = || ToolError::ExecutionFailed {
+            message: format!(
+                "Symbol lookup isn't supported for {}; use offset/limit instead",
+                path.display()
+            ),
+        };
+        let lang = outline_language(path).ok_or_else(unsupported)?;
+        let items = outline(lang, content).ok_or_else(unsupported)?;
+
+        let matches = find_symbol(&items, symbol);
+        let found = match matches.as_slice() {
+            [found] => found,
+            [] => {
+                let mut declarations: Vec<String> = items
+                    .iter()
+                    .take(MAX_LISTED_SYMBOLS)
+                    .map(|item| {
+                        format!(
+                            "  {} {} [{}-{}]",
+                            item.kind, item.name, item.start_line, item.end_line
+                        )
+                    })
+                    .collect();
+                if items.len() > MAX_LISTED_SYMBOLS {
+                    declarations.push(format!("  … {} more", items.len() - MAX_LISTED_SYMBOLS));
+                }
+                return Err(ToolError::ExecutionFailed {
+                    message: format!(
+                        "Symbol '{}' not found in {}. Top-level declarations:\n{}",
+                        symbol,
+                        path.display(),
+                        declarations.join("\n")
+                    ),
+                });
+            }
+            _ => {
+                let candidates: Vec<String> = matches
+                    .iter()
+                    .map(|m| format!("  {} [{}-{}]", m.path, m.item.start_line, m.item.end_line))
+                    .collect();
+                return Err(ToolError::ExecutionFailed {
+                    message: format!(
+                        "Symbol '{}' matches {} declarations; qualify it with its enclosing type (e.g. Type::name) or read a line range:\n{}",
+                        symbol,
+                        matches.len(),
+                        candidates.join("\n")
+                    ),
+                });
+            }
+        };
+
+        let mut start = found.item.start_line - 1;
+        while start > 0 && is_leading_annotation(lines[start - 1]) {
+            start -= 1;
+        }
+        Ok((start, found.item.end_line - start))
+    }
+}
+
+/// Doc comments, attributes and decorators that belong to the declaration
+/// below them
+fn is_leading_annotation(line: &str) -> bool {
+    let line = line.trim_start();
+    ["//", "#", "@", "/*", "*"]
+        .iter()
+        .any(|prefix| line.starts_with(prefix))
 }
 
 impl Default for ReadTool {
@@ -76,7 +353,7 @@ impl Tool for ReadTool {
     }
 
     fn description(&self) -> &str {
-        "Read file contents with LINE#ID hashline tags and file hash metadata."
+        "Read file contents with LINE#ID hashline tags and file hash metadata. Use symbol to read a single declaration (function, type, method) instead of the whole file. Binary files return their type and size instead of contents; images are attached and PDF text is extracted."
     }
 
     fn schema(&self) -> JsonSchema {
@@ -105,6 +382,14 @@ impl Tool for ReadTool {
                 "after",
                 JsonSchema::number().description("Number of lines to include after around_line (default: 10)"),
             )
+            .property(
+                "symbol",
+                JsonSchema::string().description("Read only this declaration, e.g. 'parse', 'Parser::parse' or 'Store.get'. Resolved from the syntax tree for Rust, TypeScript/JavaScript, Python and Go. Overrides offset and around_line; limit still caps the lines returned."),
+            )
+            .property(
+                "hexdump",
+                JsonSchema::boolean().description("Return a hex/ASCII dump of the raw bytes instead of text. offset and limit then count bytes (default: first 512 bytes, at most 4096)."),
+            )
             .required(&["file_path"])
     }
 
@@ -133,7 +418,8 @@ impl Tool for ReadTool {
                 message: e.to_string(),
             })?;
 
-        let path = Path::new(&input.file_path);
+        let resolved = ctx.resolve_path(&input.file_path);
+        let path = resolved.as_path();
 
         if !path.exists() {
             return Err(ToolError::ExecutionFailed {
@@ -153,11 +439,36 @@ impl Tool for ReadTool {
             });
         }
 
-        guards::check_file_guards(path, MAX_READ_FILE_BYTES).map_err(|e| {
-            ToolError::ExecutionFailed {
-                message: e.to_string(),
-            }
-        })?;
+        let size = fs::metadata(path)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed {
+                message: format!("Failed to read file: {}", e),
+            })?
+            .len();
+        let mut header = Vec::with_capacity(assets::SNIFF_BYTES);
+        fs::File::open(path)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed {
+                message: format!("Failed to read file: {}", e),
+            })?
+            .take(assets::SNIFF_BYTES as u64)
+            .read_to_end(&mut header)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed {
+                message: format!("Failed to read file: {}", e),
+            })?;
+
+        let file_type = assets::sniff(&header)
+            .or_else(|| guards::looks_binary(&header).then_some(FileType::UNKNOWN));
+        if let Some(file_type) = file_type {
+            return Self::read_binary(path, &input, size, &header, file_type).await;
+        }
+        if input.hexdump {
+            return Self::read_binary(path, &input, size, &header, FileType::TEXT).await;
+        }
+        if size > MAX_READ_FILE_BYTES {
+            return Self::read_large(path, &input, size).await;
+        }
 
         let content = fs::read_to_string(path).await.map_err(|e| {
             if e.kind() == std::io::ErrorKind::InvalidData {
@@ -174,16 +485,17 @@ impl Tool for ReadTool {
             }
         })?;
 
+        ctx.file_tracker.record(path, content.as_bytes());
+
         let lines: Vec<&str> = content.lines().collect();
-        let (offset, limit) = if let Some(center) = input.around_line {
-            let before_count = input.before.unwrap_or(5);
-            let after_count = input.after_lines.unwrap_or(10);
-            let center_0based = center.saturating_sub(1); // Convert 1-based to 0-based
-            let start = center_0based.saturating_sub(before_count);
-            let count = before_count + 1 + after_count;
-            (start, count)
+        // `wanted_end` is where the requested region ends, so a limit that
+        // cuts it short gets a continuation hint instead of silently truncating
+        let (offset, limit, wanted_end) = if let Some(symbol) = input.symbol.as_deref() {
+            let (start, count) = Self::symbol_range(path, &content, &lines, symbol)?;
+            let limit = input.limit.map_or(count, |limit| limit.min(count));
+            (start, limit, start + count)
         } else {
-            (input.offset.unwrap_or(0), input.limit.unwrap_or(2000))
+            Self::line_window(&input, lines.len())
         };
         let selected_lines: Vec<&str> = lines.iter().skip(offset).take(limit).copied().collect();
         let formatted = Self::format_output(&selected_lines, offset);
@@ -197,16 +509,38 @@ impl Tool for ReadTool {
 
         // Prepend file path so TUI render_read can extract it
         let range_str = format!("L{}-L{}", start_line, end_line);
-        let metadata = StandardOutput::format_metadata(&[
+        let total_lines = lines.len().to_string();
+        let mut metadata_pairs = vec![
             ("file_hash", file_hash.as_str()),
             ("range", range_str.as_str()),
-        ]);
+            ("total_lines", total_lines.as_str()),
+        ];
+        if let Some(symbol) = input.symbol.as_deref() {
+            metadata_pairs.push(("symbol", symbol));
+        }
+        let metadata = StandardOutput::format_metadata(&metadata_pairs);
         let metadata_section = StandardOutput::format_section(SECTION_METADATA, &metadata);
         let content_section = StandardOutput::format_section(SECTION_CONTENT, &formatted);
-        let output = format!(
+        let mut output = format!(
             "{}\n{}\n{}",
             input.file_path, metadata_section, content_section
         );
+        let wanted_end = wanted_end.min(lines.len());
+        if !selected_lines.is_empty() && end_line < wanted_end {
+            output.push_str(&format!(
+                "\n\n[{} more lines after L{}; Read with offset={} limit={} to continue]",
+                wanted_end - end_line,
+                end_line,
+                end_line,
+                (wanted_end - end_line).min(DEFAULT_READ_LIMIT)
+            ));
+        } else if selected_lines.is_empty() && offset >= lines.len() && !lines.is_empty() {
+            output.push_str(&format!(
+                "\n\n[offset {} is past the end of the file ({} lines)]",
+                offset,
+                lines.len()
+            ));
+        }
         Ok(ToolOutput::text(output))
     }
 }
@@ -465,4 +799,194 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("ignored path"));
     }
+
+    #[tokio::test]
+    async fn test_read_symbol() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(
+            &path,
+            "use std::fmt;\n\npub struct Parser;\n\nimpl Parser {\n    /// Parse the input\n    #[inline]\n    pub fn parse(&self) -> u32 {\n        42\n    }\n}\n\nfn parse() {}\n",
+        )
+        .unwrap();
+
+        let tool = ReadTool::new();
+        let ctx = ToolContext::default();
+        let result = tool
+            .execute(
+                json!({"file_path": path.to_string_lossy(), "symbol": "Parser::parse"}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        let text = result.as_text().unwrap();
+        // Doc comments and attributes above the declaration are included
+        assert!(text.contains("range: L6-L10"));
+        assert!(text.contains("total_lines: 13"));
+        assert!(text.contains("/// Parse the input"));
+        assert!(text.contains("42"));
+        assert!(!text.contains("pub struct Parser"));
+        assert!(!text.contains("more lines after"));
+
+        let ambiguous = tool
+            .execute(
+                json!({"file_path": path.to_string_lossy(), "symbol": "parse"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(ambiguous.contains("matches 2 declarations"));
+        assert!(ambiguous.contains("impl Parser > method parse [8-10]"));
+
+        let missing = tool
+            .execute(
+                json!({"file_path": path.to_string_lossy(), "symbol": "render"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err()
+            .to_string();
+        assert!(missing.contains("struct Parser [3-3]"));
+    }
+
+    #[tokio::test]
+    async fn test_read_reports_remaining_lines() {
+        let mut file = NamedTempFile::new().unwrap();
+        for i in 1..=10 {
+            writeln!(file, "line {}", i).unwrap();
+        }
+
+        let tool = ReadTool::new();
+        let ctx = ToolContext::default();
+        let result = tool
+            .execute(
+                json!({"file_path": file.path().to_string_lossy(), "limit": 4}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        let text = result.as_text().unwrap();
+        assert!(text.contains("[6 more lines after L4; Read with offset=4 limit=6 to continue]"));
+
+        let result = tool
+            .execute(
+                json!({"file_path": file.path().to_string_lossy(), "offset": 6}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert!(!result.as_text().unwrap().contains("more lines after"));
+    }
+
+    #[tokio::test]
+    async fn test_read_binary_returns_metadata() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tool");
+        let mut bytes = b"\x7fELF\x02\x01\x01".to_vec();
+        bytes.extend(std::iter::repeat_n(0u8, 1000));
+        std::fs::write(&path, &bytes).unwrap();
+
+        let tool = ReadTool::new();
+        let ctx = ToolContext::default();
+        let result = tool
+            .execute(json!({"file_path": path.to_string_lossy()}), &ctx)
+            .await
+            .unwrap();
+        let text = result.as_text().unwrap();
+        assert!(text.contains("type: application/x-elf (ELF executable)"));
+        assert!(text.contains("size: 1007 bytes"));
+        assert!(text.contains("hexdump=true"));
+        assert!(!text.contains("ELF\u{2}"));
+
+        let result = tool
+            .execute(
+                json!({"file_path": path.to_string_lossy(), "hexdump": true, "limit": 16}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        let text = result.as_text().unwrap();
+        assert!(text.contains("00000000  7f 45 4c 46 02 01 01 00"));
+        assert!(text.contains("|.ELF............|"));
+        assert!(text.contains("[991 more bytes; Read with hexdump=true offset=16 to continue]"));
+    }
+
+    #[tokio::test]
+    async fn test_read_attaches_images() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("icon.png");
+        let png: &[u8] = &[
+            0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n', 0, 0, 0, 13, b'I', b'H', b'D', b'R',
+            0, 0, 0, 16, 0, 0, 0, 8, 8, 6, 0, 0, 0,
+        ];
+        std::fs::write(&path, png).unwrap();
+
+        let tool = ReadTool::new();
+        let ctx = ToolContext::default();
+        let result = tool
+            .execute(json!({"file_path": path.to_string_lossy()}), &ctx)
+            .await
+            .unwrap();
+        let text = result.as_text().unwrap();
+        assert!(text.contains("type: image/png (PNG image)"));
+        assert!(text.contains("dimensions: 16x8"));
+        assert!(matches!(
+            &result.content[1],
+            ToolOutputContent::Image {
+                source: ImageSource::Base64 { media_type, .. }
+            } if media_type == "image/png"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_read_pdf_is_not_dumped_as_text() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("spec.pdf");
+        std::fs::write(&path, "%PDF-1.4\n%\u{e2}\u{e3}\nnot really a pdf\n").unwrap();
+
+        let tool = ReadTool::new();
+        let ctx = ToolContext::default();
+        let result = tool
+            .execute(json!({"file_path": path.to_string_lossy()}), &ctx)
+            .await
+            .unwrap();
+        let text = result.as_text().unwrap();
+        assert!(text.contains("type: application/pdf (PDF document)"));
+        // Without pdftotext, or with a corrupt PDF, the reason is reported
+        assert!(text.contains("No text extracted:"));
+        assert!(!text.contains("not really a pdf"));
+    }
+
+    #[tokio::test]
+    async fn test_read_large_file_streams_a_window() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("huge.log");
+        let line = format!("{}\n", "x".repeat(99));
+        let lines = (MAX_READ_FILE_BYTES / 100) as usize + 10;
+        std::fs::write(&path, line.repeat(lines)).unwrap();
+
+        let tool = ReadTool::new();
+        let ctx = ToolContext::default();
+        let result = tool
+            .execute(
+                json!({"file_path": path.to_string_lossy(), "offset": 5, "limit": 3}),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        let text = result.as_text().unwrap();
+        assert!(text.contains("range: L6-L8"));
+        assert!(text.contains("size: 10.0 MB"));
+        assert!(text.contains("Read with offset=8 limit=2000 to continue"));
+
+        let err = tool
+            .execute(
+                json!({"file_path": path.to_string_lossy(), "symbol": "main"}),
+                &ctx,
+            )
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("too large for symbol lookup"));
+    }
 }
@@ -0,0 +1,473 @@
+//! Readability pipeline for fetched pages
+//!
+//! Narrows a page to its main content, drops navigation, ads and other
+//! boilerplate, and renders what is left as compact markdown.
+
+use once_cell::sync::Lazy;
+use regex::Regex;
+
+static TAG_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?s)<!--.*?-->|<!\[CDATA\[.*?\]\]>|<!(?:[^>]*)>|<(/?)([a-zA-Z][a-zA-Z0-9-]*)((?:[^>\x22']|\x22[^\x22]*\x22|'[^']*')*)>")
+        .expect("valid regex")
+});
+static ATTR_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?s)([a-zA-Z_:][-a-zA-Z0-9_:.]*)(?:\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s"'>]+)))?"#)
+        .expect("valid regex")
+});
+static NOISE_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r"(?i)(?:^|[\s_-])(?:nav|navbar|menu|sidebar|footer|masthead|cookies?|consent|banner|ads?|advert|advertisement|promo|sponsored|social|share|sharing|related|comments?|popup|modal|newsletter|subscribe|breadcrumbs?|skip-link)(?:$|[\s_-])")
+        .expect("valid regex")
+});
+static META_ROBOTS_RE: Lazy<Regex> = Lazy::new(|| {
+    Regex::new(r#"(?is)<meta\s[^>]*name\s*=\s*["']?robots["']?[^>]*>"#).expect("valid regex")
+});
+
+/// Elements dropped with everything inside them
+const NOISE_TAGS: &[&str] = &[
+    "head", "title", "script", "style", "noscript", "svg", "iframe", "nav", "header", "footer",
+    "aside", "form", "template", "button", "select", "canvas", "object", "dialog",
+];
+
+const VOID_TAGS: &[&str] = &[
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta", "source", "track",
+    "wbr",
+];
+
+/// Elements never dropped by the class/id heuristics
+const STRUCTURAL_TAGS: &[&str] = &["html", "body", "main", "article"];
+
+/// A page rendered for the model
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ReadablePage {
+    pub title: Option<String>,
+    pub markdown: String,
+}
+
+/// Render `html` as markdown, resolving relative links against `base_url`
+pub(crate) fn extract(html: &str, base_url: Option<&reqwest::Url>) -> ReadablePage {
+    let title = super::web_search::extract_title(html);
+    let tokens = tokenize(html);
+    let tokens = main_content(&tokens).unwrap_or(&tokens);
+    let markdown = render(tokens, base_url);
+    ReadablePage { title, markdown }
+}
+
+/// Directives from `<meta name="robots">`, lowercased
+pub(crate) fn meta_robots(html: &str) -> Vec<String> {
+    META_ROBOTS_RE
+        .find_iter(html)
+        .filter_map(|tag| attribute(tag.as_str(), "content"))
+        .flat_map(|content| {
+            content
+                .split(',')
+                .map(|d| d.trim().to_lowercase())
+                .collect::<Vec<_>>()
+        })
+        .filter(|d| !d.is_empty())
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token<'a> {
+    Text(&'a str),
+    Open { name: String, attrs: &'a str },
+    Close { name: String },
+}
+
+fn tokenize(html: &str) -> Vec<Token<'_>> {
+    let mut tokens = Vec::new();
+    let mut last = 0;
+    for caps in TAG_RE.captures_iter(html) {
+        let whole = caps.get(0).expect("match");
+        if whole.start() > last {
+            tokens.push(Token::Text(&html[last..whole.start()]));
+        }
+        last = whole.end();
+        let Some(name) = caps.get(2) else {
+            // Comments, doctype and CDATA
+            continue;
+        };
+        let name = name.as_str().to_lowercase();
+        if caps.get(1).is_some_and(|m| !m.as_str().is_empty()) {
+            tokens.push(Token::Close { name });
+        } else {
+            tokens.push(Token::Open {
+                name,
+                attrs: caps.get(3).map(|m| m.as_str()).unwrap_or_default(),
+            });
+        }
+    }
+    if last < html.len() {
+        tokens.push(Token::Text(&html[last..]));
+    }
+    tokens
+}
+
+fn attribute(attrs: &str, key: &str) -> Option<String> {
+    ATTR_RE.captures_iter(attrs).find_map(|caps| {
+        if !caps[1].eq_ignore_ascii_case(key) {
+            return None;
+        }
+        Some(
+            caps.get(2)
+                .or_else(|| caps.get(3))
+                .or_else(|| caps.get(4))
+                .map(|m| m.as_str().to_string())
+                .unwrap_or_default(),
+        )
+    })
+}
+
+fn is_noise(name: &str, attrs: &str) -> bool {
+    if NOISE_TAGS.contains(&name) {
+        return true;
+    }
+    if STRUCTURAL_TAGS.contains(&name) {
+        return false;
+    }
+    if attribute(attrs, "hidden").is_some()
+        || attribute(attrs, "aria-hidden").is_some_and(|v| v == "true")
+    {
+        return true;
+    }
+    if attribute(attrs, "role").is_some_and(|role| {
+        matches!(
+            role.as_str(),
+            "navigation" | "banner" | "complementary" | "contentinfo" | "search" | "dialog"
+        )
+    }) {
+        return true;
+    }
+    ["class", "id"]
+        .iter()
+        .filter_map(|key| attribute(attrs, key))
+        .any(|value| NOISE_RE.is_match(&value))
+}
+
+/// Index just past the element opened at `start`
+fn element_end(tokens: &[Token<'_>], start: usize) -> usize {
+    let Token::Open { name, .. } = &tokens[start] else {
+        return start + 1;
+    };
+    if VOID_TAGS.contains(&name.as_str()) {
+        return start + 1;
+    }
+    let mut depth = 0usize;
+    for (offset, token) in tokens[start..].iter().enumerate() {
+        match token {
+            Token::Open { name: n, .. } if n == name => depth += 1,
+            Token::Close { name: n } if n == name => {
+                depth -= 1;
+                if depth == 0 {
+                    return start + offset + 1;
+                }
+            }
+            _ => {}
+        }
+    }
+    tokens.len()
+}
+
+/// Contents of the first `<main>`, else the longest `<article>`
+fn main_content<'t, 'a>(tokens: &'t [Token<'a>]) -> Option<&'t [Token<'a>]> {
+    let inner = |start: usize| {
+        let end = element_end(tokens, start);
+        &tokens[start + 1..end.saturating_sub(1).max(start + 1)]
+    };
+    let text_len = |slice: &[Token<'_>]| {
+        slice
+            .iter()
+            .map(|t| match t {
+                Token::Text(text) => text.trim().len(),
+                _ => 0,
+            })
+            .sum::<usize>()
+    };
+
+    let opens = |tag: &str| {
+        tokens
+            .iter()
+            .enumerate()
+            .filter(|(_, t)| matches!(t, Token::Open { name, .. } if name == tag))
+            .map(|(i, _)| i)
+            .collect::<Vec<_>>()
+    };
+
+    if let Some(&start) = opens("main").first() {
+        let content = inner(start);
+        if text_len(content) > 0 {
+            return Some(content);
+        }
+    }
+    opens("article")
+        .into_iter()
+        .map(inner)
+        .max_by_key(|content| text_len(content))
+        .filter(|content| text_len(content) > 0)
+}
+
+struct Renderer<'u> {
+    out: String,
+    base_url: Option<&'u reqwest::Url>,
+    links: Vec<Option<String>>,
+    lists: Vec<Option<usize>>,
+    pre_depth: usize,
+}
+
+impl Renderer<'_> {
+    fn block(&mut self) {
+        let trimmed = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(trimmed);
+        if !self.out.is_empty() && !self.out.ends_with("\n\n") {
+            self.out.push_str(if self.out.ends_with('\n') {
+                "\n"
+            } else {
+                "\n\n"
+            });
+        }
+    }
+
+    fn line(&mut self) {
+        let trimmed = self.out.trim_end_matches([' ', '\t']).len();
+        self.out.truncate(trimmed);
+        if !self.out.is_empty() && !self.out.ends_with('\n') {
+            self.out.push('\n');
+        }
+    }
+
+    fn text(&mut self, raw: &str) {
+        let decoded = super::web_search::decode_html_entities(raw);
+        if self.pre_depth > 0 {
+            self.out.push_str(&decoded);
+            return;
+        }
+        let collapsed = decoded.split_whitespace().collect::<Vec<_>>().join(" ");
+        if collapsed.is_empty() {
+            if raw.chars().next().is_some_and(char::is_whitespace) {
+                self.space();
+            }
+            return;
+        }
+        if decoded.starts_with(char::is_whitespace) {
+            self.space();
+        }
+        self.out.push_str(&collapsed);
+        if decoded.ends_with(char::is_whitespace) {
+            self.space();
+        }
+    }
+
+    fn space(&mut self) {
+        if !self.out.is_empty() && !self.out.ends_with([' ', '\n', '[', '(']) {
+            self.out.push(' ');
+        }
+    }
+
+    fn open(&mut self, name: &str, attrs: &str) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" => {
+                self.block();
+                let level = name[1..].parse::<usize>().unwrap_or(1);
+                self.out.push_str(&"#".repeat(level));
+                self.out.push(' ');
+            }
+            "p" | "div" | "section" | "article" | "main" | "table" | "figure" | "dl" => {
+                self.block()
+            }
+            "br" | "tr" | "dt" | "dd" => self.line(),
+            "hr" => {
+                self.block();
+                self.out.push_str("---");
+                self.block();
+            }
+            "ul" => {
+                self.line();
+                self.lists.push(None);
+            }
+            "ol" => {
+                self.line();
+                self.lists.push(Some(0));
+            }
+            "li" => {
+                self.line();
+                let indent = "  ".repeat(self.lists.len().saturating_sub(1));
+                let marker = match self.lists.last_mut() {
+                    Some(Some(n)) => {
+                        *n += 1;
+                        format!("{n}. ")
+                    }
+                    _ => "- ".to_string(),
+                };
+                self.out.push_str(&indent);
+                self.out.push_str(&marker);
+            }
+            "blockquote" => {
+                self.block();
+                self.out.push_str("> ");
+            }
+            "pre" => {
+                self.block();
+                self.out.push_str("```\n");
+                self.pre_depth += 1;
+            }
+            "code" if self.pre_depth == 0 => self.out.push('`'),
+            "strong" | "b" => self.out.push_str("**"),
+            "em" | "i" => self.out.push('*'),
+            "td" | "th" => self.out.push_str("| "),
+            "a" => {
+                let href = attribute(attrs, "href").and_then(|href| self.resolve(&href));
+                if href.is_some() {
+                    self.space();
+                    self.out.push('[');
+                }
+                self.links.push(href);
+            }
+            "img" => {
+                if let Some(alt) = attribute(attrs, "alt").filter(|alt| !alt.trim().is_empty()) {
+                    self.space();
+                    self.out.push_str(&format!("[image: {}]", alt.trim()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn close(&mut self, name: &str) {
+        match name {
+            "h1" | "h2" | "h3" | "h4" | "h5" | "h6" | "p" | "div" | "section" | "article"
+            | "main" | "table" | "figure" | "dl" | "blockquote" => self.block(),
+            "ul" | "ol" => {
+                self.lists.pop();
+                self.line();
+            }
+            "pre" => {
+                self.pre_depth = self.pre_depth.saturating_sub(1);
+                self.line();
+                self.out.push_str("```");
+                self.block();
+            }
+            "code" if self.pre_depth == 0 => self.out.push('`'),
+            "strong" | "b" => self.out.push_str("**"),
+            "em" | "i" => self.out.push('*'),
+            "td" | "th" => self.out.push(' '),
+            "a" => {
+                if let Some(Some(href)) = self.links.pop() {
+                    let trimmed = self.out.trim_end().len();
+                    self.out.truncate(trimmed);
+                    if self.out.ends_with('[') {
+                        // Link without text: drop it
+                        self.out.pop();
+                    } else {
+                        self.out.push_str(&format!("]({href})"));
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn resolve(&self, href: &str) -> Option<String> {
+        let href = href.trim();
+        if href.is_empty() || href.starts_with('#') || href.starts_with("javascript:") {
+            return None;
+        }
+        match self.base_url {
+            Some(base) => base.join(href).ok().map(|url| url.to_string()),
+            None => Some(href.to_string()),
+        }
+    }
+}
+
+fn render(tokens: &[Token<'_>], base_url: Option<&reqwest::Url>) -> String {
+    let mut renderer = Renderer {
+        out: String::new(),
+        base_url,
+        links: Vec::new(),
+        lists: Vec::new(),
+        pre_depth: 0,
+    };
+
+    let mut index = 0;
+    while index < tokens.len() {
+        match &tokens[index] {
+            Token::Text(text) => renderer.text(text),
+            Token::Open { name, attrs } => {
+                if is_noise(name, attrs) {
+                    index = element_end(tokens, index);
+                    continue;
+                }
+                renderer.open(name, attrs);
+            }
+            Token::Close { name } => renderer.close(name),
+        }
+        index += 1;
+    }
+
+    let mut markdown = String::new();
+    let mut blank_run = 0;
+    for line in renderer.out.lines() {
+        let line = line.trim_end();
+        if line.trim().is_empty() {
+            blank_run += 1;
+            if blank_run > 1 {
+                continue;
+            }
+        } else {
+            blank_run = 0;
+        }
+        markdown.push_str(line);
+        markdown.push('\n');
+    }
+    markdown.trim().to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extracts_main_content_as_markdown() {
+        let html = r#"<!doctype html>
+<html><head><title>Guide &amp; Docs</title><style>body { color: red }</style></head>
+<body>
+  <nav><a href="/">Home</a> <a href="/blog">Blog</a></nav>
+  <div class="ad-banner">Buy now!</div>
+  <article>
+    <h1>Getting   started</h1>
+    <p>Install with <code>cargo add uira</code> and read the <a href="/docs/api">API docs</a>.</p>
+    <div class="share-buttons">Share on X</div>
+    <ul><li>Fast</li><li><strong>Safe</strong></li></ul>
+    <pre><code>fn main() {
+    println!("hi");
+}</code></pre>
+  </article>
+  <footer>© 2025</footer>
+  <script>track()</script>
+</body></html>"#;
+        let base = reqwest::Url::parse("https://example.com/guide/").unwrap();
+        let page = extract(html, Some(&base));
+
+        assert_eq!(page.title.as_deref(), Some("Guide & Docs"));
+        assert_eq!(
+            page.markdown,
+            "# Getting started\n\n\
+             Install with `cargo add uira` and read the [API docs](https://example.com/docs/api).\n\n\
+             - Fast\n\
+             - **Safe**\n\n\
+             ```\nfn main() {\n    println!(\"hi\");\n}\n```"
+        );
+    }
+
+    #[test]
+    fn falls_back_to_body_without_main_or_article() {
+        let html = "<body><div id=\"sidebar\">Links</div><p>Hello <em>world</em></p><ol><li>one</li><li>two</li></ol></body>";
+        let page = extract(html, None);
+        assert_eq!(page.markdown, "Hello *world*\n\n1. one\n2. two");
+    }
+
+    #[test]
+    fn reads_meta_robots_directives() {
+        let html = r#"<head><meta name="robots" content="noarchive, NoIndex"><meta name="viewport" content="x"></head>"#;
+        assert_eq!(meta_robots(html), vec!["noarchive", "noindex"]);
+    }
+}
@@ -0,0 +1,153 @@
+//! robots.txt parsing for fetch_url
+
+use regex::Regex;
+
+/// Product token matched against `User-agent` lines
+pub(crate) const ROBOTS_USER_AGENT: &str = "uira";
+
+/// Allow/Disallow rules that apply to uira
+#[derive(Debug, Clone, Default)]
+pub(crate) struct RobotsRules {
+    rules: Vec<Rule>,
+}
+
+#[derive(Debug, Clone)]
+struct Rule {
+    allow: bool,
+    pattern: Regex,
+    specificity: usize,
+}
+
+impl RobotsRules {
+    /// Rules from the group naming `user_agent`, else the `*` group
+    pub fn parse(robots_txt: &str, user_agent: &str) -> Self {
+        let user_agent = user_agent.to_lowercase();
+        let mut named = Vec::new();
+        let mut wildcard = Vec::new();
+
+        let mut agents: Vec<String> = Vec::new();
+        let mut in_rules = false;
+        for line in robots_txt.lines() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            let Some((key, value)) = line.split_once(':') else {
+                continue;
+            };
+            let key = key.trim().to_lowercase();
+            let value = value.trim();
+
+            match key.as_str() {
+                "user-agent" => {
+                    // A user-agent line after rules starts a new group
+                    if in_rules {
+                        agents.clear();
+                        in_rules = false;
+                    }
+                    agents.push(value.to_lowercase());
+                }
+                "allow" | "disallow" => {
+                    in_rules = true;
+                    // An empty Disallow allows everything
+                    if value.is_empty() {
+                        continue;
+                    }
+                    let Some(rule) = Rule::new(key == "allow", value) else {
+                        continue;
+                    };
+                    if agents
+                        .iter()
+                        .any(|a| !a.is_empty() && a != "*" && user_agent.contains(a.as_str()))
+                    {
+                        named.push(rule);
+                    } else if agents.iter().any(|a| a == "*") {
+                        wildcard.push(rule);
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        let named_group = robots_txt.lines().any(|line| {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            line.split_once(':').is_some_and(|(key, value)| {
+                key.trim().eq_ignore_ascii_case("user-agent") && {
+                    let value = value.trim().to_lowercase();
+                    !value.is_empty() && value != "*" && user_agent.contains(value.as_str())
+                }
+            })
+        });
+
+        Self {
+            rules: if named_group { named } else { wildcard },
+        }
+    }
+
+    /// Whether `path` (path plus query) may be fetched; the most specific
+    /// matching rule wins and Allow wins ties
+    pub fn allows(&self, path: &str) -> bool {
+        self.rules
+            .iter()
+            .filter(|rule| rule.pattern.is_match(path))
+            .max_by_key(|rule| (rule.specificity, rule.allow))
+            .is_none_or(|rule| rule.allow)
+    }
+}
+
+impl Rule {
+    fn new(allow: bool, path: &str) -> Option<Self> {
+        let (path, anchored) = match path.strip_suffix('$') {
+            Some(path) => (path, true),
+            None => (path, false),
+        };
+        let mut pattern = String::from("^");
+        pattern.push_str(
+            &path
+                .split('*')
+                .map(regex::escape)
+                .collect::<Vec<_>>()
+                .join(".*"),
+        );
+        if anchored {
+            pattern.push('$');
+        }
+        Some(Self {
+            allow,
+            pattern: Regex::new(&pattern).ok()?,
+            specificity: path.len(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn prefers_named_group_and_most_specific_rule() {
+        let robots = "\
+User-agent: *
+Disallow: /
+
+User-agent: Googlebot
+User-agent: uira
+Disallow: /private/
+Allow: /private/public-*.html$
+Disallow: /*.pdf$
+";
+        let rules = RobotsRules::parse(robots, ROBOTS_USER_AGENT);
+        assert!(rules.allows("/docs/intro"));
+        assert!(!rules.allows("/private/notes"));
+        assert!(rules.allows("/private/public-page.html"));
+        assert!(!rules.allows("/private/public-page.html?x=1"));
+        assert!(!rules.allows("/files/report.pdf"));
+    }
+
+    #[test]
+    fn falls_back_to_wildcard_group() {
+        let robots =
+            "User-agent: other\nDisallow: /\n\nUser-agent: *\nDisallow: /admin\nDisallow:\n";
+        let rules = RobotsRules::parse(robots, ROBOTS_USER_AGENT);
+        assert!(rules.allows("/"));
+        assert!(!rules.allows("/admin/users"));
+        assert!(RobotsRules::parse("", ROBOTS_USER_AGENT).allows("/anything"));
+    }
+}
@@ -0,0 +1,585 @@
+//! Pluggable web search backends
+//!
+//! Each backend turns a query into structured [`SearchResult`]s. Results from
+//! every backend go through [`finalize_results`], which applies domain
+//! filters, drops duplicate URLs and numbers the survivors for citation.
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use std::collections::HashSet;
+use std::time::Duration;
+
+use crate::tools::ToolError;
+
+const BACKEND_TIMEOUT_SECS: u64 = 20;
+const BRAVE_SEARCH_URL: &str = "https://api.search.brave.com/res/v1/web/search";
+const TAVILY_SEARCH_URL: &str = "https://api.tavily.com/search";
+const EXA_SEARCH_URL: &str = "https://api.exa.ai/search";
+const SNIPPET_MAX_CHARS: usize = 500;
+
+/// Providers accepted by `tools.web_search.provider`
+pub(crate) const SUPPORTED_PROVIDERS: &[&str] =
+    &["exa", "duckduckgo", "brave", "tavily", "searxng"];
+
+/// One search hit, with the metadata the model needs to cite it
+#[derive(Debug, Serialize, Clone, PartialEq)]
+pub(crate) struct SearchResult {
+    /// 1-based number to cite this result by, assigned by [`finalize_results`]
+    pub citation: usize,
+    pub title: String,
+    pub url: String,
+    pub snippet: String,
+    pub domain: String,
+    /// Backend that produced the result
+    pub source: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub published: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub score: Option<f64>,
+}
+
+impl SearchResult {
+    pub fn new(
+        title: impl Into<String>,
+        url: impl Into<String>,
+        snippet: impl Into<String>,
+        source: &str,
+    ) -> Self {
+        let url = url.into();
+        Self {
+            citation: 0,
+            title: title.into(),
+            domain: domain_of(&url).unwrap_or_default(),
+            url,
+            snippet: truncate(snippet.into()),
+            source: source.to_string(),
+            published: None,
+            score: None,
+        }
+    }
+
+    fn with_published(mut self, published: Option<&str>) -> Self {
+        self.published = published.filter(|p| !p.is_empty()).map(String::from);
+        self
+    }
+
+    fn with_score(mut self, score: Option<f64>) -> Self {
+        self.score = score;
+        self
+    }
+}
+
+/// Credentials and endpoint for one backend under `tools.web_search`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub(crate) struct BackendConfig {
+    #[serde(default)]
+    pub api_key: Option<String>,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+impl BackendConfig {
+    /// Configured API key, falling back to `env_var`
+    fn api_key(&self, env_var: &str) -> Option<String> {
+        self.api_key
+            .as_deref()
+            .map(uira_core::expand_env_string)
+            .filter(|key| !key.is_empty() && !key.starts_with('$'))
+            .or_else(|| std::env::var(env_var).ok().filter(|key| !key.is_empty()))
+    }
+}
+
+/// Domains a search is restricted to or must skip
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct DomainFilter {
+    pub include: Vec<String>,
+    pub exclude: Vec<String>,
+}
+
+impl DomainFilter {
+    pub fn new<I, E>(include: I, exclude: E) -> Self
+    where
+        I: IntoIterator<Item = String>,
+        E: IntoIterator<Item = String>,
+    {
+        let normalize = |domains: Vec<String>| {
+            let mut seen = HashSet::new();
+            domains
+                .into_iter()
+                .map(|d| {
+                    d.trim()
+                        .trim_start_matches("*.")
+                        .trim_start_matches('.')
+                        .to_lowercase()
+                })
+                .filter(|d| !d.is_empty() && seen.insert(d.clone()))
+                .collect()
+        };
+        Self {
+            include: normalize(include.into_iter().collect()),
+            exclude: normalize(exclude.into_iter().collect()),
+        }
+    }
+
+    pub fn allows(&self, domain: &str) -> bool {
+        let matches =
+            |pattern: &String| domain == pattern || domain.ends_with(&format!(".{pattern}"));
+        if self.exclude.iter().any(matches) {
+            return false;
+        }
+        self.include.is_empty() || self.include.iter().any(matches)
+    }
+
+    /// Stable representation for cache keys
+    pub fn cache_key(&self) -> String {
+        format!("+{}-{}", self.include.join(","), self.exclude.join(","))
+    }
+}
+
+pub(crate) struct SearchRequest<'a> {
+    pub query: &'a str,
+    pub limit: usize,
+    pub domains: &'a DomainFilter,
+}
+
+#[async_trait]
+pub(crate) trait SearchBackend: Send + Sync {
+    fn name(&self) -> &'static str;
+
+    async fn search(&self, request: &SearchRequest<'_>) -> Result<Vec<SearchResult>, ToolError>;
+}
+
+/// Build the API-backed search backend for `provider`
+///
+/// Returns `Ok(None)` for providers served elsewhere: DuckDuckGo, and Exa
+/// without an API key (which uses the hosted MCP endpoint).
+pub(crate) fn backend_for(
+    provider: &str,
+    exa: &BackendConfig,
+    brave: &BackendConfig,
+    tavily: &BackendConfig,
+    searxng: &BackendConfig,
+) -> Result<Option<Box<dyn SearchBackend>>, ToolError> {
+    match provider {
+        "exa" => Ok(exa
+            .api_key("EXA_API_KEY")
+            .map(|api_key| Box::new(ExaBackend { api_key }) as Box<dyn SearchBackend>)),
+        "brave" => {
+            let api_key = brave
+                .api_key("BRAVE_API_KEY")
+                .ok_or_else(|| missing_key("brave", "BRAVE_API_KEY"))?;
+            Ok(Some(Box::new(BraveBackend { api_key })))
+        }
+        "tavily" => {
+            let api_key = tavily
+                .api_key("TAVILY_API_KEY")
+                .ok_or_else(|| missing_key("tavily", "TAVILY_API_KEY"))?;
+            Ok(Some(Box::new(TavilyBackend { api_key })))
+        }
+        "searxng" => {
+            let base_url = searxng
+                .url
+                .as_deref()
+                .map(uira_core::expand_env_string)
+                .or_else(|| std::env::var("SEARXNG_URL").ok())
+                .filter(|url| !url.is_empty())
+                .ok_or_else(|| ToolError::ExecutionFailed {
+                    message: "web_search provider 'searxng' needs an instance URL: set tools.web_search.searxng.url in uira.yml or SEARXNG_URL".to_string(),
+                })?;
+            Ok(Some(Box::new(SearxngBackend { base_url })))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn missing_key(name: &str, env_var: &str) -> ToolError {
+    ToolError::ExecutionFailed {
+        message: format!(
+            "web_search provider '{name}' needs an API key: set tools.web_search.{name}.api_key in uira.yml or {env_var}"
+        ),
+    }
+}
+
+/// Filter, deduplicate and number `results`, keeping at most `limit`
+pub(crate) fn finalize_results(
+    results: Vec<SearchResult>,
+    domains: &DomainFilter,
+    limit: usize,
+) -> Vec<SearchResult> {
+    let mut seen = HashSet::new();
+    results
+        .into_iter()
+        .filter(|result| !result.url.is_empty() && domains.allows(&result.domain))
+        .filter(|result| seen.insert(dedup_key(&result.url)))
+        .take(limit)
+        .enumerate()
+        .map(|(index, mut result)| {
+            result.citation = index + 1;
+            result
+        })
+        .collect()
+}
+
+/// URL identity used for deduplication: scheme, `www.`, fragment, trailing
+/// slash and tracking parameters are ignored
+fn dedup_key(url: &str) -> String {
+    let Ok(parsed) = reqwest::Url::parse(url) else {
+        return url.trim_end_matches('/').to_lowercase();
+    };
+    let host = parsed
+        .host_str()
+        .unwrap_or_default()
+        .trim_start_matches("www.")
+        .to_lowercase();
+    let query: Vec<String> = parsed
+        .query_pairs()
+        .filter(|(k, _)| !k.starts_with("utm_") && k != "ref")
+        .map(|(k, v)| format!("{k}={v}"))
+        .collect();
+    let mut key = format!("{host}{}", parsed.path().trim_end_matches('/'));
+    if !query.is_empty() {
+        key.push('?');
+        key.push_str(&query.join("&"));
+    }
+    key
+}
+
+fn domain_of(url: &str) -> Option<String> {
+    let parsed = reqwest::Url::parse(url).ok()?;
+    let host = parsed.host_str()?.to_lowercase();
+    Some(host.trim_start_matches("www.").to_string())
+}
+
+fn truncate(snippet: String) -> String {
+    let snippet = snippet.split_whitespace().collect::<Vec<_>>().join(" ");
+    match snippet.char_indices().nth(SNIPPET_MAX_CHARS) {
+        Some((index, _)) => format!("{}…", &snippet[..index]),
+        None => snippet,
+    }
+}
+
+fn http_client() -> Result<reqwest::Client, ToolError> {
+    reqwest::Client::builder()
+        .timeout(Duration::from_secs(BACKEND_TIMEOUT_SECS))
+        .user_agent(super::web_search::USER_AGENT)
+        .build()
+        .map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to initialize HTTP client: {e}"),
+        })
+}
+
+async fn read_json(
+    response: Result<reqwest::Response, reqwest::Error>,
+    provider: &str,
+) -> Result<Value, ToolError> {
+    let response = response.map_err(|e| ToolError::ExecutionFailed {
+        message: if e.is_timeout() {
+            format!("{provider} search request timed out")
+        } else {
+            format!("{provider} search request failed: {e}")
+        },
+    })?;
+    let status = response.status();
+    if status == reqwest::StatusCode::TOO_MANY_REQUESTS {
+        return Err(ToolError::ExecutionFailed {
+            message: format!("{provider} rate-limited this request (HTTP 429)"),
+        });
+    }
+    if !status.is_success() {
+        return Err(ToolError::ExecutionFailed {
+            message: format!("{provider} search returned HTTP {status}"),
+        });
+    }
+    response
+        .json()
+        .await
+        .map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to parse {provider} search response: {e}"),
+        })
+}
+
+fn str_field<'a>(value: &'a Value, key: &str) -> &'a str {
+    value.get(key).and_then(Value::as_str).unwrap_or_default()
+}
+
+struct ExaBackend {
+    api_key: String,
+}
+
+#[async_trait]
+impl SearchBackend for ExaBackend {
+    fn name(&self) -> &'static str {
+        "exa"
+    }
+
+    async fn search(&self, request: &SearchRequest<'_>) -> Result<Vec<SearchResult>, ToolError> {
+        let mut body = json!({
+            "query": request.query,
+            "numResults": request.limit,
+            "contents": { "highlights": true },
+        });
+        if !request.domains.include.is_empty() {
+            body["includeDomains"] = json!(request.domains.include);
+        }
+        if !request.domains.exclude.is_empty() {
+            body["excludeDomains"] = json!(request.domains.exclude);
+        }
+        let response = http_client()?
+            .post(EXA_SEARCH_URL)
+            .header("x-api-key", &self.api_key)
+            .json(&body)
+            .send()
+            .await;
+        Ok(parse_exa(&read_json(response, "Exa").await?))
+    }
+}
+
+fn parse_exa(body: &Value) -> Vec<SearchResult> {
+    let Some(results) = body.get("results").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    results
+        .iter()
+        .map(|item| {
+            let snippet = item
+                .get("highlights")
+                .and_then(Value::as_array)
+                .map(|h| {
+                    h.iter()
+                        .filter_map(Value::as_str)
+                        .collect::<Vec<_>>()
+                        .join(" … ")
+                })
+                .filter(|s| !s.is_empty())
+                .unwrap_or_else(|| str_field(item, "text").to_string());
+            SearchResult::new(
+                str_field(item, "title"),
+                str_field(item, "url"),
+                snippet,
+                "exa",
+            )
+            .with_published(item.get("publishedDate").and_then(Value::as_str))
+            .with_score(item.get("score").and_then(Value::as_f64))
+        })
+        .collect()
+}
+
+struct BraveBackend {
+    api_key: String,
+}
+
+#[async_trait]
+impl SearchBackend for BraveBackend {
+    fn name(&self) -> &'static str {
+        "brave"
+    }
+
+    async fn search(&self, request: &SearchRequest<'_>) -> Result<Vec<SearchResult>, ToolError> {
+        // Brave caps `count` at 20; ask for extra so filtered and duplicate
+        // hits still leave `limit` results
+        let count = (request.limit * 2).min(20).to_string();
+        let response = http_client()?
+            .get(BRAVE_SEARCH_URL)
+            .header("accept", "application/json")
+            .header("x-subscription-token", &self.api_key)
+            .query(&[("q", request.query), ("count", count.as_str())])
+            .send()
+            .await;
+        Ok(parse_brave(&read_json(response, "Brave").await?))
+    }
+}
+
+fn parse_brave(body: &Value) -> Vec<SearchResult> {
+    let Some(results) = body.pointer("/web/results").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    results
+        .iter()
+        .map(|item| {
+            SearchResult::new(
+                super::web_search::strip_html(str_field(item, "title")),
+                str_field(item, "url"),
+                super::web_search::strip_html(str_field(item, "description")),
+                "brave",
+            )
+            .with_published(
+                item.get("page_age")
+                    .or_else(|| item.get("age"))
+                    .and_then(Value::as_str),
+            )
+        })
+        .collect()
+}
+
+struct TavilyBackend {
+    api_key: String,
+}
+
+#[async_trait]
+impl SearchBackend for TavilyBackend {
+    fn name(&self) -> &'static str {
+        "tavily"
+    }
+
+    async fn search(&self, request: &SearchRequest<'_>) -> Result<Vec<SearchResult>, ToolError> {
+        let body = json!({
+            "query": request.query,
+            "max_results": request.limit,
+            "include_domains": request.domains.include,
+            "exclude_domains": request.domains.exclude,
+        });
+        let response = http_client()?
+            .post(TAVILY_SEARCH_URL)
+            .bearer_auth(&self.api_key)
+            .json(&body)
+            .send()
+            .await;
+        Ok(parse_tavily(&read_json(response, "Tavily").await?))
+    }
+}
+
+fn parse_tavily(body: &Value) -> Vec<SearchResult> {
+    let Some(results) = body.get("results").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    results
+        .iter()
+        .map(|item| {
+            SearchResult::new(
+                str_field(item, "title"),
+                str_field(item, "url"),
+                str_field(item, "content"),
+                "tavily",
+            )
+            .with_published(item.get("published_date").and_then(Value::as_str))
+            .with_score(item.get("score").and_then(Value::as_f64))
+        })
+        .collect()
+}
+
+struct SearxngBackend {
+    base_url: String,
+}
+
+#[async_trait]
+impl SearchBackend for SearxngBackend {
+    fn name(&self) -> &'static str {
+        "searxng"
+    }
+
+    async fn search(&self, request: &SearchRequest<'_>) -> Result<Vec<SearchResult>, ToolError> {
+        let url = format!("{}/search", self.base_url.trim_end_matches('/'));
+        let response = http_client()?
+            .get(url)
+            .query(&[("q", request.query), ("format", "json")])
+            .send()
+            .await;
+        Ok(parse_searxng(&read_json(response, "SearXNG").await?))
+    }
+}
+
+fn parse_searxng(body: &Value) -> Vec<SearchResult> {
+    let Some(results) = body.get("results").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    results
+        .iter()
+        .map(|item| {
+            SearchResult::new(
+                str_field(item, "title"),
+                str_field(item, "url"),
+                str_field(item, "content"),
+                "searxng",
+            )
+            .with_published(item.get("publishedDate").and_then(Value::as_str))
+            .with_score(item.get("score").and_then(Value::as_f64))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finalize_filters_dedups_and_numbers_results() {
+        let results = vec![
+            SearchResult::new("Docs", "https://docs.rs/serde/", "a", "brave"),
+            SearchResult::new(
+                "Docs again",
+                "https://www.docs.rs/serde?utm_source=x",
+                "b",
+                "brave",
+            ),
+            SearchResult::new("Spam", "https://pinterest.com/pin/1", "c", "brave"),
+            SearchResult::new("Guide", "https://serde.rs/derive.html", "d", "brave"),
+            SearchResult::new("Blog", "https://blog.docs.rs/post", "e", "brave"),
+        ];
+        let domains = DomainFilter::new(
+            Vec::new(),
+            vec!["pinterest.com".to_string(), "*.docs.rs".to_string()],
+        );
+        // "*.docs.rs" normalizes to "docs.rs", which also covers subdomains
+        let finalized = finalize_results(results.clone(), &domains, 10);
+        assert_eq!(
+            finalized
+                .iter()
+                .map(|r| r.title.as_str())
+                .collect::<Vec<_>>(),
+            vec!["Guide"]
+        );
+
+        let domains = DomainFilter::new(Vec::new(), vec!["pinterest.com".to_string()]);
+        let finalized = finalize_results(results, &domains, 10);
+        assert_eq!(
+            finalized
+                .iter()
+                .map(|r| (r.citation, r.title.as_str()))
+                .collect::<Vec<_>>(),
+            vec![(1, "Docs"), (2, "Guide"), (3, "Blog")]
+        );
+    }
+
+    #[test]
+    fn include_domains_match_subdomains_only_on_label_boundaries() {
+        let domains = DomainFilter::new(vec!["rust-lang.org".to_string()], Vec::new());
+        assert!(domains.allows("rust-lang.org"));
+        assert!(domains.allows("doc.rust-lang.org"));
+        assert!(!domains.allows("notrust-lang.org"));
+        assert!(!domains.allows("example.com"));
+    }
+
+    #[test]
+    fn parses_backend_responses_with_citation_metadata() {
+        let brave = parse_brave(&json!({
+            "web": { "results": [{
+                "title": "<strong>Tokio</strong> docs",
+                "url": "https://www.tokio.rs/tokio/tutorial",
+                "description": "An async <strong>runtime</strong>",
+                "page_age": "2025-01-02T00:00:00"
+            }]}
+        }));
+        assert_eq!(brave[0].title, "Tokio docs");
+        assert_eq!(brave[0].snippet, "An async runtime");
+        assert_eq!(brave[0].domain, "tokio.rs");
+        assert_eq!(brave[0].published.as_deref(), Some("2025-01-02T00:00:00"));
+
+        let tavily = parse_tavily(&json!({
+            "results": [{"title": "T", "url": "https://a.dev/x", "content": "c", "score": 0.9}]
+        }));
+        assert_eq!(tavily[0].source, "tavily");
+        assert_eq!(tavily[0].score, Some(0.9));
+
+        let searxng = parse_searxng(&json!({
+            "results": [{"title": "S", "url": "https://b.dev/y", "content": "d"}]
+        }));
+        assert_eq!(searxng[0].snippet, "d");
+        assert_eq!(searxng[0].published, None);
+
+        let exa = parse_exa(&json!({
+            "results": [{"title": "E", "url": "https://c.dev/z", "highlights": ["h1", "h2"]}]
+        }));
+        assert_eq!(exa[0].snippet, "h1 … h2");
+    }
+}
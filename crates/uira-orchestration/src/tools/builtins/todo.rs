@@ -5,8 +5,8 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use uira_core::{
-    ApprovalRequirement, JsonSchema, SandboxPreference, TodoItem, TodoPriority, TodoStatus,
-    ToolOutput,
+    actionable_todos, validate_todos, ApprovalRequirement, JsonSchema, SandboxPreference,
+    TodoEstimate, TodoItem, TodoPriority, TodoStatus, ToolOutput,
 };
 
 use crate::tools::{Tool, ToolContext, ToolError};
@@ -73,6 +73,13 @@ impl TodoStore {
             .collect()
     }
 
+    /// Incomplete todos whose dependencies and subtasks are done, in the
+    /// order they should be worked on
+    pub async fn actionable_items(&self, session_id: &str) -> Vec<TodoItem> {
+        let todos = self.get(session_id).await;
+        actionable_todos(&todos).into_iter().cloned().collect()
+    }
+
     pub async fn update(&self, session_id: &str, todos: Vec<TodoItem>) {
         {
             let mut map = self.inner.write().await;
@@ -83,6 +90,21 @@ impl TodoStore {
         }
     }
 
+    /// Write a session's todos to disk again, reporting any failure
+    pub async fn flush(&self, session_id: &str) -> Result<(), std::io::Error> {
+        let Some(ref dir) = self.persist_dir else {
+            return Ok(());
+        };
+        let todos = {
+            let map = self.inner.read().await;
+            match map.get(session_id) {
+                Some(todos) => todos.clone(),
+                None => return Ok(()),
+            }
+        };
+        self.persist(dir, session_id, &todos).await
+    }
+
     async fn persist(
         &self,
         dir: &PathBuf,
@@ -238,6 +260,12 @@ struct TodoInputItem {
     content: String,
     status: String,
     priority: String,
+    #[serde(default)]
+    parent_id: Option<String>,
+    #[serde(default)]
+    depends_on: Vec<String>,
+    #[serde(default)]
+    estimate: Option<String>,
 }
 
 impl TodoInputItem {
@@ -256,6 +284,14 @@ impl TodoInputItem {
                 "low" => TodoPriority::Low,
                 _ => TodoPriority::Medium,
             },
+            parent_id: self.parent_id.filter(|id| !id.is_empty()),
+            depends_on: self.depends_on,
+            estimate: match self.estimate.as_deref() {
+                Some("small") => Some(TodoEstimate::Small),
+                Some("medium") => Some(TodoEstimate::Medium),
+                Some("large") => Some(TodoEstimate::Large),
+                _ => None,
+            },
         }
     }
 }
@@ -277,7 +313,8 @@ impl Tool for TodoWriteTool {
     }
 
     fn description(&self) -> &str {
-        "Use this tool to create and manage a structured task list for your current coding session."
+        "Use this tool to create and manage a structured task list for your current coding session. \
+         Break large tasks into subtasks with parent_id, and use depends_on for tasks that must wait on others."
     }
 
     fn schema(&self) -> JsonSchema {
@@ -304,6 +341,20 @@ impl Tool for TodoWriteTool {
                             "priority",
                             JsonSchema::string().description("Priority level: high, medium, low"),
                         )
+                        .property(
+                            "parent_id",
+                            JsonSchema::string()
+                                .description("Id of the parent todo when this is a subtask"),
+                        )
+                        .property(
+                            "depends_on",
+                            JsonSchema::array(JsonSchema::string())
+                                .description("Ids of todos that must be finished first"),
+                        )
+                        .property(
+                            "estimate",
+                            JsonSchema::string().description("Size estimate: small, medium, large"),
+                        )
                         .required(&["id", "content", "status", "priority"]),
                 )
                 .description("The updated todo list"),
@@ -340,18 +391,29 @@ impl Tool for TodoWriteTool {
             .into_iter()
             .map(|t| t.into_todo_item())
             .collect();
+        validate_todos(&todos).map_err(|message| ToolError::InvalidInput { message })?;
         let pending_count = todos
             .iter()
             .filter(|t| t.status != TodoStatus::Completed && t.status != TodoStatus::Cancelled)
             .count();
+        let blocked_count = todos
+            .iter()
+            .filter(|t| t.is_incomplete() && t.is_blocked(&todos))
+            .count();
 
         self.store.update(&ctx.session_id, todos.clone()).await;
 
         let output = serde_json::to_string_pretty(&todos).unwrap_or_default();
+        let blocked = if blocked_count > 0 {
+            format!(", {} blocked", blocked_count)
+        } else {
+            String::new()
+        };
         Ok(ToolOutput::text(format!(
-            "{} todos ({} remaining)\n{}",
+            "{} todos ({} remaining{})\n{}",
             todos.len(),
             pending_count,
+            blocked,
             output
         )))
     }
@@ -436,6 +498,7 @@ mod tests {
             content: "Fix bug".to_string(),
             status: TodoStatus::Pending,
             priority: TodoPriority::High,
+            ..Default::default()
         }];
         store.update("ses_123", items.clone()).await;
         let result = store.get("ses_123").await;
@@ -452,6 +515,7 @@ mod tests {
             content: "Write tests".to_string(),
             status: TodoStatus::InProgress,
             priority: TodoPriority::Medium,
+            ..Default::default()
         }];
         store.update("ses_456", items).await;
 
@@ -483,6 +547,47 @@ mod tests {
         assert_eq!(stored[1].status, TodoStatus::Completed);
     }
 
+    #[tokio::test]
+    async fn test_todo_write_tool_hierarchy() {
+        let store = TodoStore::new();
+        let tool = TodoWriteTool::new(store.clone());
+        let ctx = ToolContext::default();
+        let input = json!({
+            "todos": [
+                {"id": "1", "content": "Ship feature", "status": "pending", "priority": "high", "estimate": "large"},
+                {"id": "2", "content": "Write schema", "status": "in_progress", "priority": "medium", "parent_id": "1", "estimate": "small"},
+                {"id": "3", "content": "Wire UI", "status": "pending", "priority": "high", "parent_id": "1", "depends_on": ["2"]}
+            ]
+        });
+
+        let result = tool.execute(input, &ctx).await.unwrap();
+        assert!(result
+            .as_text()
+            .unwrap()
+            .contains("3 todos (3 remaining, 2 blocked)"));
+
+        let stored = store.get(&ctx.session_id).await;
+        assert_eq!(stored[1].parent_id.as_deref(), Some("1"));
+        assert_eq!(stored[2].depends_on, vec!["2".to_string()]);
+        assert_eq!(stored[0].estimate, Some(TodoEstimate::Large));
+
+        let actionable = store.actionable_items(&ctx.session_id).await;
+        assert_eq!(actionable.len(), 1);
+        assert_eq!(actionable[0].id, "2");
+
+        let cyclic = json!({
+            "todos": [
+                {"id": "a", "content": "A", "status": "pending", "priority": "high", "depends_on": ["b"]},
+                {"id": "b", "content": "B", "status": "pending", "priority": "high", "depends_on": ["a"]}
+            ]
+        });
+        assert!(matches!(
+            tool.execute(cyclic, &ctx).await,
+            Err(ToolError::InvalidInput { .. })
+        ));
+        assert_eq!(store.get(&ctx.session_id).await.len(), 3);
+    }
+
     #[tokio::test]
     async fn test_todo_read_tool() {
         let store = TodoStore::new();
@@ -492,12 +597,14 @@ mod tests {
                 content: "Task A".to_string(),
                 status: TodoStatus::Pending,
                 priority: TodoPriority::High,
+                ..Default::default()
             },
             TodoItem {
                 id: "2".to_string(),
                 content: "Task B".to_string(),
                 status: TodoStatus::InProgress,
                 priority: TodoPriority::Medium,
+                ..Default::default()
             },
         ];
         store.update("test_session", items).await;
@@ -520,6 +627,9 @@ mod tests {
             content: "test".to_string(),
             status: "in_progress".to_string(),
             priority: "high".to_string(),
+            parent_id: None,
+            depends_on: Vec::new(),
+            estimate: None,
         };
         let todo = item.into_todo_item();
         assert_eq!(todo.status, TodoStatus::InProgress);
@@ -533,6 +643,9 @@ mod tests {
             content: "test".to_string(),
             status: "unknown".to_string(),
             priority: "unknown".to_string(),
+            parent_id: None,
+            depends_on: Vec::new(),
+            estimate: None,
         };
         let todo = item.into_todo_item();
         assert_eq!(todo.status, TodoStatus::Pending);
@@ -548,6 +661,7 @@ mod tests {
             content: "Task".to_string(),
             status: TodoStatus::Pending,
             priority: TodoPriority::Medium,
+            ..Default::default()
         }];
         store.update("ses_aaa", items.clone()).await;
         store.update("ses_bbb", items).await;
@@ -567,6 +681,7 @@ mod tests {
             content: "Persisted task".to_string(),
             status: TodoStatus::InProgress,
             priority: TodoPriority::High,
+            ..Default::default()
         }];
         store.update("ses_xyz", items).await;
 
@@ -585,24 +700,28 @@ mod tests {
                 content: "Done".to_string(),
                 status: TodoStatus::Completed,
                 priority: TodoPriority::Low,
+                ..Default::default()
             },
             TodoItem {
                 id: "2".to_string(),
                 content: "Active".to_string(),
                 status: TodoStatus::InProgress,
                 priority: TodoPriority::High,
+                ..Default::default()
             },
             TodoItem {
                 id: "3".to_string(),
                 content: "Waiting".to_string(),
                 status: TodoStatus::Pending,
                 priority: TodoPriority::Medium,
+                ..Default::default()
             },
             TodoItem {
                 id: "4".to_string(),
                 content: "Skipped".to_string(),
                 status: TodoStatus::Cancelled,
                 priority: TodoPriority::Low,
+                ..Default::default()
             },
         ];
         store.update("ses_info", items).await;
@@ -626,6 +745,7 @@ mod tests {
             content: "To delete".to_string(),
             status: TodoStatus::Pending,
             priority: TodoPriority::Medium,
+            ..Default::default()
         }];
         store.update("ses_del", items).await;
 
@@ -653,6 +773,7 @@ mod tests {
             content: "Task".to_string(),
             status: TodoStatus::Pending,
             priority: TodoPriority::Medium,
+            ..Default::default()
         }];
 
         let persist_result = store
@@ -693,12 +814,14 @@ mod tests {
                 content: "Done".to_string(),
                 status: TodoStatus::Completed,
                 priority: TodoPriority::Medium,
+                ..Default::default()
             },
             TodoItem {
                 id: "2".to_string(),
                 content: "Pending".to_string(),
                 status: TodoStatus::Pending,
                 priority: TodoPriority::High,
+                ..Default::default()
             },
         ];
         store.update("ses_test", items).await;
@@ -709,6 +832,7 @@ mod tests {
             content: "Done".to_string(),
             status: TodoStatus::Completed,
             priority: TodoPriority::Medium,
+            ..Default::default()
         }];
         store.update("ses_done", all_done).await;
         assert!(!store.has_incomplete("ses_done").await);
@@ -725,24 +849,28 @@ mod tests {
                 content: "Done".to_string(),
                 status: TodoStatus::Completed,
                 priority: TodoPriority::Medium,
+                ..Default::default()
             },
             TodoItem {
                 id: "2".to_string(),
                 content: "Pending".to_string(),
                 status: TodoStatus::Pending,
                 priority: TodoPriority::High,
+                ..Default::default()
             },
             TodoItem {
                 id: "3".to_string(),
                 content: "Working".to_string(),
                 status: TodoStatus::InProgress,
                 priority: TodoPriority::High,
+                ..Default::default()
             },
             TodoItem {
                 id: "4".to_string(),
                 content: "Cancelled".to_string(),
                 status: TodoStatus::Cancelled,
                 priority: TodoPriority::Low,
+                ..Default::default()
             },
         ];
         store.update("ses_test", items).await;
@@ -760,18 +888,21 @@ mod tests {
                 content: "Done".to_string(),
                 status: TodoStatus::Completed,
                 priority: TodoPriority::Medium,
+                ..Default::default()
             },
             TodoItem {
                 id: "2".to_string(),
                 content: "Pending".to_string(),
                 status: TodoStatus::Pending,
                 priority: TodoPriority::High,
+                ..Default::default()
             },
             TodoItem {
                 id: "3".to_string(),
                 content: "Working".to_string(),
                 status: TodoStatus::InProgress,
                 priority: TodoPriority::High,
+                ..Default::default()
             },
         ];
         store.update("ses_test", items).await;
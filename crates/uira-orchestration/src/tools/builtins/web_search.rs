@@ -10,6 +10,13 @@ use std::time::{Duration, Instant};
 use tokio::sync::Mutex;
 use uira_core::{ApprovalRequirement, JsonSchema, SandboxPreference, ToolOutput};
 
+use super::fetch_cache::{now_secs, CachedPage, FetchCache};
+use super::readability;
+use super::robots::{RobotsRules, ROBOTS_USER_AGENT};
+use super::search_backends::{
+    backend_for, finalize_results, BackendConfig, DomainFilter, SearchRequest, SearchResult,
+    SUPPORTED_PROVIDERS,
+};
 use crate::tools::{Tool, ToolContext, ToolError};
 
 const DEFAULT_LIMIT: usize = 5;
@@ -21,9 +28,13 @@ const RATE_LIMIT_MAX_REQUESTS: usize = 10;
 const FETCH_DEFAULT_MAX_CHARS: usize = 10000;
 const FETCH_MAX_CHARS: usize = 50000;
 const FETCH_MAX_RESPONSE_BYTES: usize = 2 * 1024 * 1024;
+const FETCH_TIMEOUT_SECS: u64 = 20;
+const FETCH_CACHE_TTL_SECS: u64 = 900;
+const ROBOTS_TTL_SECS: u64 = 3600;
+const ROBOTS_MAX_BYTES: usize = 512 * 1024;
 const DEFAULT_PROVIDER: &str = "exa";
 const EXA_MCP_URL: &str = "https://mcp.exa.ai/mcp";
-const USER_AGENT: &str = "uira/0.1 (+https://github.com/junhoyeo/uira)";
+pub(crate) const USER_AGENT: &str = "uira/0.1 (+https://github.com/junhoyeo/uira)";
 const EXA_SEARCH_TIMEOUT_SECS: u64 = 25;
 const EXA_CODE_SEARCH_TIMEOUT_SECS: u64 = 30;
 const GREP_APP_MCP_URL: &str = "https://mcp.grep.app";
@@ -35,6 +46,7 @@ static STATE: Lazy<Mutex<WebState>> = Lazy::new(|| Mutex::new(WebState::new()));
 struct WebState {
     cache: HashMap<String, CachedResults>,
     request_times: VecDeque<Instant>,
+    robots: HashMap<String, (RobotsRules, Instant)>,
 }
 
 impl WebState {
@@ -42,6 +54,7 @@ impl WebState {
         Self {
             cache: HashMap::new(),
             request_times: VecDeque::new(),
+            robots: HashMap::new(),
         }
     }
 
@@ -83,6 +96,36 @@ struct RuntimeConfig {
     cache_ttl_secs: u64,
     rate_limit_max_requests: usize,
     rate_limit_window_secs: u64,
+    include_domains: Vec<String>,
+    exclude_domains: Vec<String>,
+    exa: BackendConfig,
+    brave: BackendConfig,
+    tavily: BackendConfig,
+    searxng: BackendConfig,
+    fetch: FetchRuntimeConfig,
+}
+
+#[derive(Debug, Clone)]
+struct FetchRuntimeConfig {
+    respect_robots: bool,
+    cache: bool,
+    cache_dir: Option<std::path::PathBuf>,
+    cache_ttl_secs: u64,
+    timeout_secs: u64,
+    max_response_bytes: usize,
+}
+
+impl Default for FetchRuntimeConfig {
+    fn default() -> Self {
+        Self {
+            respect_robots: true,
+            cache: true,
+            cache_dir: None,
+            cache_ttl_secs: FETCH_CACHE_TTL_SECS,
+            timeout_secs: FETCH_TIMEOUT_SECS,
+            max_response_bytes: FETCH_MAX_RESPONSE_BYTES,
+        }
+    }
 }
 
 impl Default for RuntimeConfig {
@@ -93,6 +136,13 @@ impl Default for RuntimeConfig {
             cache_ttl_secs: CACHE_TTL_SECS,
             rate_limit_max_requests: RATE_LIMIT_MAX_REQUESTS,
             rate_limit_window_secs: RATE_LIMIT_WINDOW_SECS,
+            include_domains: Vec::new(),
+            exclude_domains: Vec::new(),
+            exa: BackendConfig::default(),
+            brave: BackendConfig::default(),
+            tavily: BackendConfig::default(),
+            searxng: BackendConfig::default(),
+            fetch: FetchRuntimeConfig::default(),
         }
     }
 }
@@ -107,6 +157,24 @@ struct UiraConfigFile {
 struct ToolsConfig {
     #[serde(default)]
     web_search: Option<WebSearchConfig>,
+    #[serde(default)]
+    fetch_url: Option<FetchUrlConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FetchUrlConfig {
+    #[serde(default)]
+    respect_robots: Option<bool>,
+    #[serde(default)]
+    cache: Option<bool>,
+    #[serde(default)]
+    cache_dir: Option<String>,
+    #[serde(default)]
+    cache_ttl: Option<u64>,
+    #[serde(default)]
+    timeout_secs: Option<u64>,
+    #[serde(default)]
+    max_response_bytes: Option<usize>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -121,6 +189,18 @@ struct WebSearchConfig {
     rate_limit_max_requests: Option<usize>,
     #[serde(default)]
     rate_limit_window_secs: Option<u64>,
+    #[serde(default)]
+    include_domains: Vec<String>,
+    #[serde(default)]
+    exclude_domains: Vec<String>,
+    #[serde(default)]
+    exa: Option<BackendConfig>,
+    #[serde(default)]
+    brave: Option<BackendConfig>,
+    #[serde(default)]
+    tavily: Option<BackendConfig>,
+    #[serde(default)]
+    searxng: Option<BackendConfig>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -138,13 +218,10 @@ struct WebSearchInput {
     livecrawl: Option<String>,
     #[serde(default)]
     context_max_chars: Option<usize>,
-}
-
-#[derive(Debug, Serialize, Clone)]
-struct SearchResult {
-    title: String,
-    url: String,
-    snippet: String,
+    #[serde(default)]
+    include_domains: Vec<String>,
+    #[serde(default)]
+    exclude_domains: Vec<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -155,6 +232,8 @@ struct WebSearchOutput {
     provider: String,
     output: Option<String>,
     results: Vec<SearchResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    cite_as: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -209,15 +288,19 @@ struct FetchUrlInput {
     url: String,
     #[serde(default)]
     max_chars: Option<usize>,
+    #[serde(default)]
+    format: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
 struct FetchUrlOutput {
     url: String,
     content_type: String,
+    format: String,
     title: Option<String>,
     content: String,
     truncated: bool,
+    cached: bool,
 }
 
 pub struct WebSearchTool;
@@ -299,7 +382,7 @@ impl Tool for WebSearchTool {
     }
 
     fn description(&self) -> &str {
-        "Search the web using Exa hosted MCP for up-to-date documentation snippets, with DuckDuckGo fallback support."
+        "Search the web for up-to-date documentation. Results carry citation numbers, domains and publish dates; the backend (Exa, Brave, Tavily, SearXNG or DuckDuckGo) is configured in uira.yml, with DuckDuckGo as fallback."
     }
 
     fn schema(&self) -> JsonSchema {
@@ -333,6 +416,16 @@ impl Tool for WebSearchTool {
                     "Maximum characters for context optimized for LLMs (default: provider default)",
                 ),
             )
+            .property(
+                "include_domains",
+                JsonSchema::array(JsonSchema::string())
+                    .description("Only return results from these domains (and their subdomains)"),
+            )
+            .property(
+                "exclude_domains",
+                JsonSchema::array(JsonSchema::string())
+                    .description("Drop results from these domains (and their subdomains)"),
+            )
             .required(&["query"])
     }
 
@@ -354,6 +447,10 @@ impl Tool for WebSearchTool {
         false
     }
 
+    fn requires_network(&self) -> bool {
+        true
+    }
+
     async fn execute(
         &self,
         input: serde_json::Value,
@@ -366,14 +463,26 @@ impl Tool for WebSearchTool {
                     .to_string(),
             });
         }
-        if runtime.provider != "exa" && runtime.provider != "duckduckgo" {
+        if !SUPPORTED_PROVIDERS.contains(&runtime.provider.as_str()) {
             return Err(ToolError::ExecutionFailed {
                 message: format!(
-                    "Unsupported web_search provider '{}'; supported providers are 'exa' and 'duckduckgo'",
-                    runtime.provider
+                    "Unsupported web_search provider '{}'; supported providers are {}",
+                    runtime.provider,
+                    SUPPORTED_PROVIDERS
+                        .iter()
+                        .map(|p| format!("'{p}'"))
+                        .collect::<Vec<_>>()
+                        .join(", ")
                 ),
             });
         }
+        let backend = backend_for(
+            &runtime.provider,
+            &runtime.exa,
+            &runtime.brave,
+            &runtime.tavily,
+            &runtime.searxng,
+        )?;
 
         let input: WebSearchInput =
             serde_json::from_value(input).map_err(|e| ToolError::InvalidInput {
@@ -412,13 +521,28 @@ impl Tool for WebSearchTool {
             });
         }
 
-        let key = if runtime.provider == "exa" {
+        let domains = DomainFilter::new(
+            runtime
+                .include_domains
+                .iter()
+                .cloned()
+                .chain(input.include_domains),
+            runtime
+                .exclude_domains
+                .iter()
+                .cloned()
+                .chain(input.exclude_domains),
+        );
+
+        let key = if let Some(ref backend) = backend {
+            format!("{}:{query}:{limit}:{}", backend.name(), domains.cache_key())
+        } else if runtime.provider == "exa" {
             format!(
                 "exa:{query}:{num_results}:{search_type}:{livecrawl}:{}",
                 input.context_max_chars.unwrap_or(0)
             )
         } else {
-            format!("duckduckgo:{query}:{limit}")
+            format!("duckduckgo:{query}:{limit}:{}", domains.cache_key())
         };
 
         {
@@ -433,6 +557,7 @@ impl Tool for WebSearchTool {
                         cached: true,
                         provider: cached.provider.clone(),
                         output: cached.output.clone(),
+                        cite_as: citation_hint(&cached.results),
                         results: cached.results.clone(),
                     };
                     return serde_json::to_value(out)
@@ -451,7 +576,25 @@ impl Tool for WebSearchTool {
         }
 
         let provider = runtime.provider.clone();
-        let (results, output, effective_provider) = if provider == "exa" {
+        let (results, output, effective_provider) = if let Some(backend) = backend {
+            let request = SearchRequest {
+                query,
+                limit,
+                domains: &domains,
+            };
+            match backend.search(&request).await {
+                Ok(results) => (results, None, backend.name()),
+                Err(err) => {
+                    tracing::warn!(
+                        "{} web search failed for query '{}': {}. Falling back to DuckDuckGo.",
+                        backend.name(),
+                        query,
+                        err
+                    );
+                    (duckduckgo_search(query, limit).await?, None, "duckduckgo")
+                }
+            }
+        } else if provider == "exa" {
             match exa_search(
                 query,
                 num_results,
@@ -484,6 +627,7 @@ impl Tool for WebSearchTool {
         } else {
             (duckduckgo_search(query, limit).await?, None, "duckduckgo")
         };
+        let results = finalize_results(results, &domains, limit);
 
         let out = WebSearchOutput {
             query: query.to_string(),
@@ -491,6 +635,7 @@ impl Tool for WebSearchTool {
             cached: false,
             provider: effective_provider.to_string(),
             output,
+            cite_as: citation_hint(&results),
             results: results.clone(),
         };
 
@@ -557,6 +702,10 @@ impl Tool for CodeSearchTool {
         false
     }
 
+    fn requires_network(&self) -> bool {
+        true
+    }
+
     async fn execute(
         &self,
         input: serde_json::Value,
@@ -680,6 +829,10 @@ impl Tool for GrepAppTool {
         false
     }
 
+    fn requires_network(&self) -> bool {
+        true
+    }
+
     async fn execute(
         &self,
         input: serde_json::Value,
@@ -1052,7 +1205,7 @@ impl Tool for FetchUrlTool {
     }
 
     fn description(&self) -> &str {
-        "Fetch a URL and return cleaned text content."
+        "Fetch a URL and return its main content as markdown, with navigation and ads stripped. Respects robots.txt and caches pages on disk."
     }
 
     fn schema(&self) -> JsonSchema {
@@ -1063,6 +1216,12 @@ impl Tool for FetchUrlTool {
                 JsonSchema::number()
                     .description("Max returned characters (default 10000, max 50000)"),
             )
+            .property(
+                "format",
+                JsonSchema::string().description(
+                    "Output format: 'markdown' (default, main content only) or 'text' (whole page as plain text)",
+                ),
+            )
             .required(&["url"])
     }
 
@@ -1084,6 +1243,10 @@ impl Tool for FetchUrlTool {
         false
     }
 
+    fn requires_network(&self) -> bool {
+        true
+    }
+
     async fn execute(
         &self,
         input: serde_json::Value,
@@ -1105,12 +1268,44 @@ impl Tool for FetchUrlTool {
             .max_chars
             .unwrap_or(FETCH_DEFAULT_MAX_CHARS)
             .clamp(1, FETCH_MAX_CHARS);
+        let format = input.format.unwrap_or_else(|| "markdown".to_string());
+        if format != "markdown" && format != "text" {
+            return Err(ToolError::InvalidInput {
+                message: "format must be either 'markdown' or 'text'".to_string(),
+            });
+        }
 
         let url = reqwest::Url::parse(&input.url).map_err(|e| ToolError::InvalidInput {
             message: format!("Invalid URL: {e}"),
         })?;
         validate_fetch_url(&url).await?;
 
+        let fetch = &runtime.fetch;
+        let cache = fetch.cache.then(|| {
+            FetchCache::new(
+                fetch
+                    .cache_dir
+                    .clone()
+                    .unwrap_or_else(|| FetchCache::default_dir(&ctx.cwd)),
+            )
+        });
+        let cached = cache
+            .as_ref()
+            .and_then(|cache| cache.get(url.as_str(), &format));
+        if let Some(page) = cached.as_ref().filter(|p| p.is_fresh(fetch.cache_ttl_secs)) {
+            return fetch_output(page.clone(), max_chars, true);
+        }
+
+        if fetch.respect_robots && !robots_allow(&url, fetch.timeout_secs).await {
+            return Err(ToolError::PermissionDenied {
+                message: format!(
+                    "robots.txt for {} disallows fetching {}",
+                    host_of(&url),
+                    url
+                ),
+            });
+        }
+
         {
             let mut state = STATE.lock().await;
             state.cleanup(runtime.rate_limit_window_secs);
@@ -1122,7 +1317,7 @@ impl Tool for FetchUrlTool {
         }
 
         let client = reqwest::Client::builder()
-            .timeout(Duration::from_secs(20))
+            .timeout(Duration::from_secs(fetch.timeout_secs))
             .redirect(reqwest::redirect::Policy::none())
             .user_agent(USER_AGENT)
             .build()
@@ -1130,14 +1325,36 @@ impl Tool for FetchUrlTool {
                 message: format!("Failed to initialize HTTP client: {e}"),
             })?;
 
-        let response = client
-            .get(url)
+        let mut request = client.get(url.clone());
+        if let Some(page) = &cached {
+            if let Some(etag) = &page.etag {
+                request = request.header(reqwest::header::IF_NONE_MATCH, etag);
+            }
+            if let Some(last_modified) = &page.last_modified {
+                request = request.header(reqwest::header::IF_MODIFIED_SINCE, last_modified);
+            }
+        }
+
+        let response = request
             .send()
             .await
             .map_err(|e| ToolError::ExecutionFailed {
-                message: format!("Request failed: {e}"),
+                message: if e.is_timeout() {
+                    format!("Request timed out after {} seconds", fetch.timeout_secs)
+                } else {
+                    format!("Request failed: {e}")
+                },
             })?;
 
+        if response.status() == reqwest::StatusCode::NOT_MODIFIED {
+            if let (Some(cache), Some(mut page)) = (&cache, cached) {
+                page.fetched_at = now_secs();
+                if let Err(e) = cache.put(&page) {
+                    tracing::debug!("Failed to refresh fetch cache entry: {}", e);
+                }
+                return fetch_output(page, max_chars, true);
+            }
+        }
         if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS {
             return Err(ToolError::ExecutionFailed {
                 message: "URL fetch was rate-limited by remote server (HTTP 429)".to_string(),
@@ -1149,19 +1366,22 @@ impl Tool for FetchUrlTool {
             });
         }
 
-        let content_type = response
-            .headers()
-            .get(reqwest::header::CONTENT_TYPE)
-            .and_then(|v| v.to_str().ok())
-            .unwrap_or("application/octet-stream")
-            .to_string();
+        let headers = response.headers().clone();
+        let header = |name: reqwest::header::HeaderName| {
+            headers
+                .get(name)
+                .and_then(|v| v.to_str().ok())
+                .map(String::from)
+        };
+        let content_type = header(reqwest::header::CONTENT_TYPE)
+            .unwrap_or_else(|| "application/octet-stream".to_string());
 
         if let Some(len) = response.content_length() {
-            if len as usize > FETCH_MAX_RESPONSE_BYTES {
+            if len as usize > fetch.max_response_bytes {
                 return Err(ToolError::ExecutionFailed {
                     message: format!(
                         "Response too large: {} bytes exceeds limit of {} bytes",
-                        len, FETCH_MAX_RESPONSE_BYTES
+                        len, fetch.max_response_bytes
                     ),
                 });
             }
@@ -1174,43 +1394,146 @@ impl Tool for FetchUrlTool {
                 message: format!("Failed to read response body: {e}"),
             })?;
             body_bytes.extend_from_slice(&chunk);
-            if body_bytes.len() > FETCH_MAX_RESPONSE_BYTES {
+            if body_bytes.len() > fetch.max_response_bytes {
                 return Err(ToolError::ExecutionFailed {
                     message: format!(
                         "Response too large: exceeded limit of {} bytes",
-                        FETCH_MAX_RESPONSE_BYTES
+                        fetch.max_response_bytes
                     ),
                 });
             }
         }
 
         let body = String::from_utf8_lossy(&body_bytes).to_string();
-
-        let title = extract_title(&body);
-        let text = normalize_whitespace(&decode_html_entities(&strip_html_tags(&body)));
-        let truncated = text.chars().count() > max_chars;
-        let content = if truncated {
-            text.chars().take(max_chars).collect()
+        let is_html = content_type.contains("html") || body.trim_start().starts_with('<');
+
+        let mut directives: Vec<String> =
+            header(reqwest::header::HeaderName::from_static("x-robots-tag"))
+                .map(|v| v.split(',').map(|d| d.trim().to_lowercase()).collect())
+                .unwrap_or_default();
+        if is_html {
+            directives.extend(readability::meta_robots(&body));
+        }
+        let no_store = header(reqwest::header::CACHE_CONTROL)
+            .is_some_and(|v| v.to_lowercase().contains("no-store"))
+            || directives.iter().any(|d| d == "noarchive" || d == "none");
+
+        let (title, content) = if !is_html {
+            (None, body)
+        } else if format == "markdown" {
+            let page = readability::extract(&body, Some(&url));
+            (page.title, page.markdown)
         } else {
-            text
+            (
+                extract_title(&body),
+                normalize_whitespace(&decode_html_entities(&strip_html_tags(&body))),
+            )
         };
 
-        let out = FetchUrlOutput {
-            url: input.url,
+        let page = CachedPage {
+            url: url.to_string(),
+            format,
             content_type,
             title,
             content,
-            truncated,
+            etag: header(reqwest::header::ETAG),
+            last_modified: header(reqwest::header::LAST_MODIFIED),
+            fetched_at: now_secs(),
         };
-        serde_json::to_value(out)
-            .map(ToolOutput::json)
-            .map_err(|e| ToolError::ExecutionFailed {
-                message: format!("Failed to serialize output: {}", e),
-            })
+        if let Some(cache) = &cache {
+            if no_store {
+                cache.remove(&page.url, &page.format);
+            } else if let Err(e) = cache.put(&page) {
+                tracing::debug!("Failed to write fetch cache entry: {}", e);
+            }
+        }
+
+        fetch_output(page, max_chars, false)
+    }
+}
+
+fn fetch_output(page: CachedPage, max_chars: usize, cached: bool) -> Result<ToolOutput, ToolError> {
+    let truncated = page.content.chars().count() > max_chars;
+    let content = if truncated {
+        page.content.chars().take(max_chars).collect()
+    } else {
+        page.content
+    };
+
+    let out = FetchUrlOutput {
+        url: page.url,
+        content_type: page.content_type,
+        format: page.format,
+        title: page.title,
+        content,
+        truncated,
+        cached,
+    };
+    serde_json::to_value(out)
+        .map(ToolOutput::json)
+        .map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to serialize output: {}", e),
+        })
+}
+
+fn host_of(url: &reqwest::Url) -> String {
+    url.host_str().unwrap_or_default().to_string()
+}
+
+/// Whether robots.txt on `url`'s origin lets uira fetch it. Missing or
+/// unreachable robots.txt files allow everything.
+async fn robots_allow(url: &reqwest::Url, timeout_secs: u64) -> bool {
+    let origin = url.origin().ascii_serialization();
+    let mut path = url.path().to_string();
+    if let Some(query) = url.query() {
+        path.push('?');
+        path.push_str(query);
+    }
+
+    {
+        let state = STATE.lock().await;
+        if let Some((rules, fetched)) = state.robots.get(&origin) {
+            if fetched.elapsed() < Duration::from_secs(ROBOTS_TTL_SECS) {
+                return rules.allows(&path);
+            }
+        }
     }
+
+    let rules = match fetch_robots(&origin, timeout_secs).await {
+        Some(body) => RobotsRules::parse(&body, ROBOTS_USER_AGENT),
+        None => RobotsRules::default(),
+    };
+    let allowed = rules.allows(&path);
+    STATE
+        .lock()
+        .await
+        .robots
+        .insert(origin, (rules, Instant::now()));
+    allowed
 }
 
-fn extract_title(html: &str) -> Option<String> {
+async fn fetch_robots(origin: &str, timeout_secs: u64) -> Option<String> {
+    let client = reqwest::Client::builder()
+        .timeout(Duration::from_secs(timeout_secs.min(10)))
+        .user_agent(USER_AGENT)
+        .build()
+        .ok()?;
+    let response = client
+        .get(format!("{origin}/robots.txt"))
+        .send()
+        .await
+        .ok()?;
+    if !response.status().is_success() {
+        return None;
+    }
+    let body = response.bytes().await.ok()?;
+    if body.len() > ROBOTS_MAX_BYTES {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&body).to_string())
+}
+
+pub(crate) fn extract_title(html: &str) -> Option<String> {
     let title_re = Regex::new(r"(?is)<title[^>]*>(.*?)</title>").expect("valid regex");
     let title = title_re
         .captures(html)
@@ -1286,16 +1609,29 @@ async fn duckduckgo_search(query: &str, limit: usize) -> Result<Vec<SearchResult
             continue;
         }
 
-        out.push(SearchResult {
-            title: normalize_whitespace(&decode_html_entities(&strip_html_tags(title))),
+        out.push(SearchResult::new(
+            strip_html(title),
             url,
-            snippet: normalize_whitespace(&decode_html_entities(&strip_html_tags(snippet))),
-        });
+            strip_html(snippet),
+            "duckduckgo",
+        ));
     }
 
     Ok(out)
 }
 
+/// Plain text from an HTML fragment in a search result
+pub(crate) fn strip_html(input: &str) -> String {
+    normalize_whitespace(&decode_html_entities(&strip_html_tags(input)))
+}
+
+/// Tell the model how to cite structured results
+fn citation_hint(results: &[SearchResult]) -> Option<String> {
+    (!results.is_empty()).then(|| {
+        "Cite sources inline as [n] using each result's citation number, with the URL".to_string()
+    })
+}
+
 fn decode_ddg_url(url: &str) -> String {
     if let Ok(parsed) = reqwest::Url::parse(url) {
         if parsed.path() == "/l/" {
@@ -1316,7 +1652,7 @@ fn strip_html_tags(input: &str) -> String {
         .to_string()
 }
 
-fn decode_html_entities(input: &str) -> String {
+pub(crate) fn decode_html_entities(input: &str) -> String {
     input
         .replace("&amp;", "&")
         .replace("&lt;", "<")
@@ -1343,7 +1679,11 @@ fn load_runtime_config(cwd: &Path) -> RuntimeConfig {
     };
 
     let mut runtime = RuntimeConfig::default();
-    if let Some(web_search) = parsed.tools.and_then(|t| t.web_search) {
+    let (web_search, fetch_url) = parsed
+        .tools
+        .map(|t| (t.web_search, t.fetch_url))
+        .unwrap_or_default();
+    if let Some(web_search) = web_search {
         if let Some(enabled) = web_search.enabled {
             runtime.enabled = enabled;
         }
@@ -1359,6 +1699,33 @@ fn load_runtime_config(cwd: &Path) -> RuntimeConfig {
         if let Some(window_secs) = web_search.rate_limit_window_secs {
             runtime.rate_limit_window_secs = window_secs.max(1);
         }
+        runtime.include_domains = web_search.include_domains;
+        runtime.exclude_domains = web_search.exclude_domains;
+        runtime.exa = web_search.exa.unwrap_or_default();
+        runtime.brave = web_search.brave.unwrap_or_default();
+        runtime.tavily = web_search.tavily.unwrap_or_default();
+        runtime.searxng = web_search.searxng.unwrap_or_default();
+    }
+    if let Some(fetch_url) = fetch_url {
+        let fetch = &mut runtime.fetch;
+        if let Some(respect_robots) = fetch_url.respect_robots {
+            fetch.respect_robots = respect_robots;
+        }
+        if let Some(cache) = fetch_url.cache {
+            fetch.cache = cache;
+        }
+        if let Some(cache_dir) = fetch_url.cache_dir {
+            fetch.cache_dir = Some(cwd.join(uira_core::expand_env_string(&cache_dir)));
+        }
+        if let Some(cache_ttl) = fetch_url.cache_ttl {
+            fetch.cache_ttl_secs = cache_ttl;
+        }
+        if let Some(timeout_secs) = fetch_url.timeout_secs {
+            fetch.timeout_secs = timeout_secs.clamp(1, 120);
+        }
+        if let Some(max_bytes) = fetch_url.max_response_bytes {
+            fetch.max_response_bytes = max_bytes.max(1024);
+        }
     }
 
     runtime
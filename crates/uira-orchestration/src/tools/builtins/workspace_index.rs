@@ -0,0 +1,230 @@
+//! Structured queries against the background workspace index
+
+use async_trait::async_trait;
+use serde::Deserialize;
+use uira_core::{ApprovalRequirement, JsonSchema, ToolOutput};
+
+use crate::features::workspace_index::WorkspaceIndex;
+use crate::tools::{Tool, ToolContext, ToolError};
+
+const DEFAULT_LIMIT: usize = 50;
+const DEFAULT_TREE_DEPTH: usize = 2;
+
+/// Input for workspace_index tool
+#[derive(Debug, Deserialize)]
+struct WorkspaceIndexInput {
+    action: String,
+    #[serde(default)]
+    query: Option<String>,
+    #[serde(default)]
+    kind: Option<String>,
+    #[serde(default)]
+    path: Option<String>,
+    #[serde(default)]
+    depth: Option<usize>,
+    #[serde(default)]
+    limit: Option<usize>,
+}
+
+/// Looks up files, symbols and directory structure from the workspace index
+pub struct WorkspaceIndexTool;
+
+impl WorkspaceIndexTool {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for WorkspaceIndexTool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Tool for WorkspaceIndexTool {
+    fn name(&self) -> &str {
+        "workspace_index"
+    }
+
+    fn description(&self) -> &str {
+        "Query the pre-built workspace index instead of scanning the tree. Actions: files (find files by name, path fragment or glob), symbols (find where functions, types and classes are declared), semantic (files related to a natural-language query; needs embeddings), tree (directory layout under path), stats (index size and freshness). Much faster than glob/grep on large repositories; use grep for text inside files."
+    }
+
+    fn schema(&self) -> JsonSchema {
+        JsonSchema::object()
+            .property(
+                "action",
+                JsonSchema::string()
+                    .description("One of: files, symbols, semantic, tree, stats"),
+            )
+            .property(
+                "query",
+                JsonSchema::string().description(
+                    "File name, path fragment or glob (files), symbol name (symbols) or description (semantic)",
+                ),
+            )
+            .property(
+                "kind",
+                JsonSchema::string()
+                    .description("Only symbols of this kind, e.g. function, struct, class, trait"),
+            )
+            .property(
+                "path",
+                JsonSchema::string().description("Directory to list for tree (default: root)"),
+            )
+            .property(
+                "depth",
+                JsonSchema::number().description("Levels to expand for tree (default: 2)"),
+            )
+            .property(
+                "limit",
+                JsonSchema::number().description("Maximum results (default: 50)"),
+            )
+            .required(&["action"])
+    }
+
+    fn approval_requirement(&self, _input: &serde_json::Value) -> ApprovalRequirement {
+        // Read-only lookups
+        ApprovalRequirement::Skip {
+            bypass_sandbox: false,
+        }
+    }
+
+    async fn execute(
+        &self,
+        input: serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let input: WorkspaceIndexInput =
+            serde_json::from_value(input).map_err(|e| ToolError::InvalidInput {
+                message: e.to_string(),
+            })?;
+        let limit = input.limit.unwrap_or(DEFAULT_LIMIT).max(1);
+        let query = || {
+            input
+                .query
+                .as_deref()
+                .filter(|q| !q.trim().is_empty())
+                .ok_or_else(|| ToolError::InvalidInput {
+                    message: format!("'{}' needs a query", input.action),
+                })
+        };
+
+        let index = WorkspaceIndex::shared(&ctx.cwd);
+        // The watcher keeps the index current; without one, scan on demand
+        if !index.is_watching() {
+            index.ensure_fresh().await;
+        }
+
+        let output = match input.action.as_str() {
+            "files" => {
+                let matches = index.find_files(query()?, limit);
+                if matches.is_empty() {
+                    "No matching files".to_string()
+                } else {
+                    matches
+                        .into_iter()
+                        .map(|m| m.path)
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            }
+            "symbols" => {
+                let matches = index.find_symbols(query()?, input.kind.as_deref(), limit);
+                if matches.is_empty() {
+                    "No matching symbols".to_string()
+                } else {
+                    matches
+                        .into_iter()
+                        .map(|m| format!("{}:{} {} {}", m.path, m.line, m.kind, m.name))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            }
+            "semantic" => {
+                let matches = index
+                    .semantic_search(query()?, limit)
+                    .await
+                    .map_err(|message| ToolError::ExecutionFailed { message })?;
+                if matches.is_empty() {
+                    "No embedded files yet".to_string()
+                } else {
+                    matches
+                        .into_iter()
+                        .map(|m| format!("{:.3} {}", m.score, m.path))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                }
+            }
+            "tree" => {
+                let depth = input.depth.unwrap_or(DEFAULT_TREE_DEPTH);
+                let entries = index.tree(input.path.as_deref().unwrap_or(""), depth, limit);
+                if entries.is_empty() {
+                    "No indexed files under this path".to_string()
+                } else {
+                    entries.join("\n")
+                }
+            }
+            "stats" => serde_json::to_string_pretty(&index.stats()).map_err(|e| {
+                ToolError::ExecutionFailed {
+                    message: e.to_string(),
+                }
+            })?,
+            other => {
+                return Err(ToolError::InvalidInput {
+                    message: format!(
+                        "Unknown action '{}'; use files, symbols, semantic, tree or stats",
+                        other
+                    ),
+                })
+            }
+        };
+
+        Ok(ToolOutput::text(output))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn test_workspace_index_queries() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src")).unwrap();
+        std::fs::write(
+            dir.path().join("src/router.rs"),
+            "pub struct ToolRouter;\nfn route() {}\n",
+        )
+        .unwrap();
+
+        let tool = WorkspaceIndexTool::new();
+        let ctx = ToolContext {
+            cwd: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        let output = tool
+            .execute(json!({"action": "symbols", "query": "ToolRouter"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(
+            output.as_text().unwrap(),
+            "src/router.rs:1 struct ToolRouter"
+        );
+
+        let output = tool
+            .execute(json!({"action": "files", "query": "router"}), &ctx)
+            .await
+            .unwrap();
+        assert_eq!(output.as_text().unwrap(), "src/router.rs");
+
+        let err = tool
+            .execute(json!({"action": "files"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::InvalidInput { .. }));
+    }
+}
@@ -3,7 +3,6 @@
 use async_trait::async_trait;
 use serde::Deserialize;
 use similar::TextDiff;
-use std::path::Path;
 use tokio::fs;
 use uira_core::{ApprovalRequirement, JsonSchema, SandboxPreference, ToolOutput};
 
@@ -113,14 +112,17 @@ impl Tool for WriteTool {
     async fn execute(
         &self,
         input: serde_json::Value,
-        _ctx: &ToolContext,
+        ctx: &ToolContext,
     ) -> Result<ToolOutput, ToolError> {
         let input: WriteInput =
             serde_json::from_value(input).map_err(|e| ToolError::InvalidInput {
                 message: e.to_string(),
             })?;
 
-        let path = Path::new(&input.file_path);
+        let resolved = ctx.resolve_path(&input.file_path);
+        let path = resolved.as_path();
+        ctx.check_writable(path)?;
+        ctx.file_tracker.check_fresh(path)?;
 
         // Create parent directories if they don't exist
         if let Some(parent) = path.parent() {
@@ -148,6 +150,7 @@ impl Tool for WriteTool {
             .map_err(|e| ToolError::ExecutionFailed {
                 message: format!("Failed to write file: {}", e),
             })?;
+        ctx.file_tracker.record(path, input.content.as_bytes());
 
         let diff = TextDiff::from_lines(&old_content, &input.content);
         let unified = diff
@@ -168,6 +171,7 @@ impl Tool for WriteTool {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::tools::WorkspaceRoot;
     use serde_json::json;
     use tempfile::tempdir;
 
@@ -246,6 +250,38 @@ mod tests {
         assert_eq!(std::fs::read_to_string(&file_path).unwrap(), "after\n");
     }
 
+    #[tokio::test]
+    async fn test_write_into_workspace_root() {
+        let web = tempdir().unwrap();
+        let api = tempdir().unwrap();
+
+        let tool = WriteTool::new();
+        let ctx = ToolContext {
+            cwd: web.path().to_path_buf(),
+            ..Default::default()
+        }
+        .with_workspaces(vec![
+            WorkspaceRoot::new("api", api.path()),
+            WorkspaceRoot::new("vendor", web.path().join("vendor"))
+                .with_sandbox_policy(uira_security::SandboxPolicy::read_only()),
+        ]);
+
+        tool.execute(
+            json!({"file_path": "@api/src/lib.rs", "content": "pub fn api() {}\n"}),
+            &ctx,
+        )
+        .await
+        .unwrap();
+        assert!(api.path().join("src/lib.rs").exists());
+
+        let err = tool
+            .execute(json!({"file_path": "vendor/lib.js", "content": "x"}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::PermissionDenied { .. }));
+        assert!(!web.path().join("vendor/lib.js").exists());
+    }
+
     #[test]
     fn test_sensitive_file_detection() {
         assert!(WriteTool::is_sensitive_file(".env"));
@@ -0,0 +1,416 @@
+//! Plain-English explanations of risky shell commands
+//!
+//! Before a network or destructive Bash call is put to the user, the agent
+//! asks a small model what the command does and what it will change. This
+//! module builds that request, parses the reply, and produces a local
+//! explanation from the command itself when no model is configured or the
+//! model does not answer.
+
+use crate::tools::approval_policy::{classify_command, ToolClass};
+use crate::tools::ToolContext;
+
+/// What a command does, in one line, and what running it will change
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CommandExplanation {
+    pub summary: String,
+    pub effects: Vec<String>,
+}
+
+impl CommandExplanation {
+    /// Lines appended to the approval reason
+    pub fn for_approval(&self) -> String {
+        if self.effects.is_empty() {
+            return self.summary.clone();
+        }
+        format!("{}\nEffects: {}", self.summary, self.effects.join("; "))
+    }
+}
+
+/// The command of a Bash call worth explaining, with its class
+pub fn risky_command<'a>(
+    tool_name: &str,
+    input: &'a serde_json::Value,
+    ctx: &ToolContext,
+) -> Option<(&'a str, ToolClass)> {
+    if tool_name != "Bash" {
+        return None;
+    }
+    let command = input.get("command").and_then(|v| v.as_str())?;
+    let class = classify_command(command, ctx);
+    matches!(class, ToolClass::Network | ToolClass::Destructive).then_some((command, class))
+}
+
+/// Request sent to the explaining model
+pub fn explain_prompt(command: &str) -> String {
+    format!(
+        "A coding agent wants to run the shell command below and the user must approve it.\n\
+         Explain what it does in one short line of plain English, then list what running it \
+         will change (files deleted or modified, hosts contacted, processes stopped). \
+         Do not judge whether it should run.\n\n\
+         Reply exactly in this format:\n\
+         SUMMARY: <one line>\n\
+         EFFECTS:\n\
+         - <effect>\n\n\
+         Command:\n```sh\n{}\n```",
+        command
+    )
+}
+
+/// Parse the model's reply; `None` when it has no summary line
+pub fn parse_explanation(reply: &str) -> Option<CommandExplanation> {
+    let mut summary = None;
+    let mut effects = Vec::new();
+    let mut in_effects = false;
+    for line in reply.lines().map(str::trim) {
+        if let Some(rest) = strip_label(line, "SUMMARY:") {
+            summary = Some(rest.to_string());
+            in_effects = false;
+        } else if let Some(rest) = strip_label(line, "EFFECTS:") {
+            in_effects = true;
+            if !rest.is_empty() {
+                effects.push(rest.to_string());
+            }
+        } else if in_effects {
+            let effect = line.trim_start_matches(['-', '*']).trim();
+            if !effect.is_empty() {
+                effects.push(effect.to_string());
+            }
+        }
+    }
+    let summary = summary.filter(|summary| !summary.is_empty())?;
+    Some(CommandExplanation {
+        summary: first_line(&summary),
+        effects,
+    })
+}
+
+fn strip_label<'a>(line: &'a str, label: &str) -> Option<&'a str> {
+    let line = line.trim_start_matches(['*', '#']).trim_start();
+    line.get(..label.len())
+        .filter(|head| head.eq_ignore_ascii_case(label))
+        .map(|_| line[label.len()..].trim_matches(['*', ' ']))
+}
+
+fn first_line(text: &str) -> String {
+    text.lines().next().unwrap_or_default().trim().to_string()
+}
+
+/// Explain `command` from its words alone
+pub fn explain(command: &str) -> CommandExplanation {
+    let mut actions = Vec::new();
+    let mut effects = Vec::new();
+    if command.contains("$(") || command.contains('`') {
+        actions.push("runs the output of a command substitution".to_string());
+        effects.push("Runs commands that are only known once it executes".to_string());
+    }
+    for segment in command
+        .split(['\n', ';', '|', '&'])
+        .map(str::trim)
+        .filter(|segment| !segment.is_empty())
+    {
+        let (action, effect) = describe_segment(segment);
+        if !actions.contains(&action) {
+            actions.push(action);
+        }
+        if let Some(effect) = effect {
+            if !effects.contains(&effect) {
+                effects.push(effect);
+            }
+        }
+    }
+    if command.contains('|') && piped_into_shell(command) {
+        effects.push("Executes downloaded or generated code in a shell".to_string());
+    }
+
+    let summary = match actions.split_first() {
+        Some((first, [])) => capitalize(first),
+        Some((first, rest)) => format!("{}, then {}", capitalize(first), rest.join(", then ")),
+        None => "Runs an empty command".to_string(),
+    };
+    CommandExplanation { summary, effects }
+}
+
+/// One segment's action and, when it changes something, its effect
+fn describe_segment(segment: &str) -> (String, Option<String>) {
+    let words: Vec<&str> = segment
+        .split_whitespace()
+        .skip_while(|word| word.contains('=') && !word.starts_with('-'))
+        .collect();
+    let Some((&program, args)) = words.split_first() else {
+        return ("runs an empty command".to_string(), None);
+    };
+    let flags: Vec<&str> = args
+        .iter()
+        .copied()
+        .filter(|a| a.starts_with('-'))
+        .collect();
+    let operands: Vec<&str> = args
+        .iter()
+        .copied()
+        .filter(|a| !a.starts_with('-'))
+        .collect();
+    let targets = list(&operands);
+    let has_flag = |short: char, long: &str| {
+        flags
+            .iter()
+            .any(|flag| *flag == long || (!flag.starts_with("--") && flag[1..].contains(short)))
+    };
+
+    match program {
+        "sudo" | "doas" | "su" => {
+            let (action, _) = describe_segment(&args.join(" "));
+            (
+                format!("{} as root", action),
+                Some("Runs with administrator privileges".to_string()),
+            )
+        }
+        "rm" | "rmdir" | "shred" => {
+            let recursive = has_flag('r', "--recursive") || has_flag('R', "--recursive");
+            let action = if recursive {
+                format!("recursively deletes {}", targets)
+            } else {
+                format!("deletes {}", targets)
+            };
+            (
+                action,
+                Some(format!("Permanently removes {} from disk", targets)),
+            )
+        }
+        "chmod" | "chown" => {
+            let what = if program == "chmod" {
+                "permissions"
+            } else {
+                "ownership"
+            };
+            let files = list(operands.get(1..).unwrap_or_default());
+            (
+                format!("changes the {} of {}", what, files),
+                Some(format!("Changes who can access {}", files)),
+            )
+        }
+        "kill" | "killall" | "pkill" => (
+            format!("stops the processes {}", targets),
+            Some("Terminates running processes".to_string()),
+        ),
+        "dd" | "mkfs" | "truncate" => (
+            format!("overwrites data with {}", program),
+            Some("Overwrites file or disk contents irrecoverably".to_string()),
+        ),
+        "reboot" | "shutdown" => (
+            "restarts or powers off the machine".to_string(),
+            Some("Interrupts everything running on the machine".to_string()),
+        ),
+        "eval" | "exec" => (
+            "runs a dynamically built command".to_string(),
+            Some("Runs commands that are only known once it executes".to_string()),
+        ),
+        "git" => describe_git(&operands, &flags),
+        "curl" | "wget" => {
+            let url = operands
+                .iter()
+                .find(|a| a.contains("://"))
+                .copied()
+                .unwrap_or("a remote server");
+            let uploads = flags.iter().any(|f| {
+                matches!(
+                    *f,
+                    "-d" | "--data" | "-F" | "--form" | "-T" | "--upload-file"
+                )
+            }) || (flags.iter().any(|f| matches!(*f, "-X" | "--request"))
+                && args.iter().any(|a| matches!(*a, "POST" | "PUT" | "DELETE")));
+            if uploads {
+                (
+                    format!("sends data to {}", url),
+                    Some(format!("Sends a request that may change state on {}", url)),
+                )
+            } else {
+                (
+                    format!("downloads {}", url),
+                    Some(format!("Contacts {}", url)),
+                )
+            }
+        }
+        "ssh" | "scp" | "sftp" | "rsync" => (
+            format!("connects to {} over {}", targets, program),
+            Some("Reads or changes files on another machine".to_string()),
+        ),
+        "npm" | "pnpm" | "yarn" | "bun" | "pip" | "pip3" | "cargo" | "gem" | "go" | "docker" => {
+            let subcommand = operands.first().copied().unwrap_or_default();
+            let packages = list(operands.get(1..).unwrap_or_default());
+            match subcommand {
+                "publish" | "push" => (
+                    format!("publishes with {} {}", program, subcommand),
+                    Some("Uploads a release to a public registry".to_string()),
+                ),
+                "install" | "i" | "add" | "get" => (
+                    format!("installs {} with {}", packages, program),
+                    Some("Downloads packages, which may run their install scripts".to_string()),
+                ),
+                "login" => (
+                    format!("logs in to the {} registry", program),
+                    Some("Stores registry credentials".to_string()),
+                ),
+                _ => (
+                    format!("runs {} {}", program, subcommand),
+                    Some("Downloads from a package registry".to_string()),
+                ),
+            }
+        }
+        "find" if args.contains(&"-delete") => (
+            "deletes the files matched by find".to_string(),
+            Some("Permanently removes every matching file".to_string()),
+        ),
+        _ => (format!("runs {}", program), None),
+    }
+}
+
+fn describe_git(operands: &[&str], flags: &[&str]) -> (String, Option<String>) {
+    let subcommand = operands.first().copied().unwrap_or_default();
+    let force = flags
+        .iter()
+        .any(|f| matches!(*f, "-f" | "--force" | "--force-with-lease"));
+    match subcommand {
+        "push" if force => (
+            "force-pushes to the remote".to_string(),
+            Some("Overwrites commits on the remote branch".to_string()),
+        ),
+        "push" => (
+            "pushes commits to the remote".to_string(),
+            Some("Publishes local commits to the remote repository".to_string()),
+        ),
+        "pull" | "fetch" | "clone" => (
+            format!("runs git {} against the remote", subcommand),
+            Some("Downloads from the remote repository".to_string()),
+        ),
+        "reset" if flags.contains(&"--hard") => (
+            "hard-resets the working tree".to_string(),
+            Some("Discards uncommitted changes".to_string()),
+        ),
+        "clean" => (
+            "deletes untracked files".to_string(),
+            Some("Permanently removes files git does not track".to_string()),
+        ),
+        "branch" if flags.contains(&"-D") => (
+            "force-deletes a branch".to_string(),
+            Some("Deletes a branch even if it is not merged".to_string()),
+        ),
+        "rebase" => (
+            "rebases the current branch".to_string(),
+            Some("Rewrites the history of the current branch".to_string()),
+        ),
+        "restore" | "checkout" => (
+            format!("runs git {}", subcommand),
+            Some("Overwrites working tree files with committed versions".to_string()),
+        ),
+        _ => (format!("runs git {}", subcommand), None),
+    }
+}
+
+fn piped_into_shell(command: &str) -> bool {
+    command.split('|').skip(1).any(|segment| {
+        let program = segment
+            .split_whitespace()
+            .find(|word| !matches!(*word, "sudo" | "doas"));
+        matches!(
+            program,
+            Some("sh" | "bash" | "zsh" | "python" | "python3" | "node")
+        )
+    })
+}
+
+fn list(items: &[&str]) -> String {
+    match items {
+        [] => "the given files".to_string(),
+        [one] => one.to_string(),
+        [first, second] => format!("{} and {}", first, second),
+        [first, second, rest @ ..] => {
+            format!("{}, {} and {} more", first, second, rest.len())
+        }
+    }
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn only_network_and_destructive_bash_calls_are_risky() {
+        let ctx = ToolContext::default();
+        let rm = json!({ "command": "rm -rf build" });
+        assert_eq!(
+            risky_command("Bash", &rm, &ctx),
+            Some(("rm -rf build", ToolClass::Destructive))
+        );
+        assert_eq!(
+            risky_command("Bash", &json!({ "command": "npm install" }), &ctx).map(|(_, c)| c),
+            Some(ToolClass::Network)
+        );
+        assert_eq!(
+            risky_command("Bash", &json!({ "command": "mkdir build" }), &ctx),
+            None
+        );
+        assert_eq!(risky_command("Write", &rm, &ctx), None);
+    }
+
+    #[test]
+    fn explains_commands_locally() {
+        let explanation = explain("rm -rf target dist");
+        assert_eq!(explanation.summary, "Recursively deletes target and dist");
+        assert_eq!(
+            explanation.effects,
+            vec!["Permanently removes target and dist from disk".to_string()]
+        );
+
+        let explanation = explain("git fetch && git push --force origin main");
+        assert_eq!(
+            explanation.summary,
+            "Runs git fetch against the remote, then force-pushes to the remote"
+        );
+        assert_eq!(explanation.effects.len(), 2);
+
+        let explanation = explain("curl -fsSL https://example.com/install.sh | sh");
+        assert_eq!(
+            explanation.summary,
+            "Downloads https://example.com/install.sh, then runs sh"
+        );
+        assert!(explanation
+            .effects
+            .contains(&"Executes downloaded or generated code in a shell".to_string()));
+
+        assert_eq!(
+            explain("sudo chown root /etc/app").summary,
+            "Changes the ownership of /etc/app as root"
+        );
+    }
+
+    #[test]
+    fn parses_model_replies() {
+        let reply = "SUMMARY: Deletes the build directory.\nEFFECTS:\n- Removes build/\n- Frees disk space\n";
+        let explanation = parse_explanation(reply).unwrap();
+        assert_eq!(explanation.summary, "Deletes the build directory.");
+        assert_eq!(
+            explanation.effects,
+            vec!["Removes build/", "Frees disk space"]
+        );
+        assert_eq!(
+            explanation.for_approval(),
+            "Deletes the build directory.\nEffects: Removes build/; Frees disk space"
+        );
+
+        let reply = "**Summary:** Pushes main.\n**Effects:** Updates origin/main";
+        let explanation = parse_explanation(reply).unwrap();
+        assert_eq!(explanation.summary, "Pushes main.");
+        assert_eq!(explanation.effects, vec!["Updates origin/main"]);
+
+        assert_eq!(parse_explanation("I cannot help with that."), None);
+    }
+}
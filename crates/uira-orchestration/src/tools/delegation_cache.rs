@@ -0,0 +1,208 @@
+//! Result caching for repeated delegations
+//!
+//! Read-only agents like explore and librarian often get the same question
+//! several times in one session ("find where X is defined"). Their results
+//! are cached under a fingerprint of the agent, any model override, the
+//! normalized prompt and the working tree, so a repeat returns the earlier answer until the entry
+//! expires or the tree changes.
+
+use parking_lot::Mutex;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::Path;
+use std::time::{Duration, Instant, UNIX_EPOCH};
+
+/// Identifies a delegated task
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TaskFingerprint(String);
+
+impl TaskFingerprint {
+    /// Fingerprint of `agent`, running on `model_override` when the call
+    /// picked a model, answering `prompt` on a tree hashing to `tree_hash`.
+    /// The prompt is compared without whitespace differences; case is kept
+    /// since identifiers are case-sensitive.
+    pub fn new(agent: &str, model_override: Option<&str>, prompt: &str, tree_hash: &str) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(agent.as_bytes());
+        hasher.update(b"|");
+        hasher.update(model_override.unwrap_or_default().as_bytes());
+        hasher.update(b"|");
+        hasher.update(normalize_prompt(prompt).as_bytes());
+        hasher.update(b"|");
+        hasher.update(tree_hash.as_bytes());
+        Self(hex::encode(hasher.finalize()))
+    }
+}
+
+fn normalize_prompt(prompt: &str) -> String {
+    prompt.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+struct CachedResult {
+    output: String,
+    stored_at: Instant,
+}
+
+/// In-memory results of delegated tasks, expiring after a TTL
+pub struct DelegationCache {
+    entries: Mutex<HashMap<TaskFingerprint, CachedResult>>,
+}
+
+impl DelegationCache {
+    pub fn new() -> Self {
+        Self {
+            entries: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Result stored for `fingerprint` less than `ttl` ago
+    pub fn get(&self, fingerprint: &TaskFingerprint, ttl: Duration) -> Option<String> {
+        let mut entries = self.entries.lock();
+        entries.retain(|_, entry| entry.stored_at.elapsed() < ttl);
+        entries.get(fingerprint).map(|entry| entry.output.clone())
+    }
+
+    pub fn put(&self, fingerprint: TaskFingerprint, output: String) {
+        self.entries.lock().insert(
+            fingerprint,
+            CachedResult {
+                output,
+                stored_at: Instant::now(),
+            },
+        );
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.lock().len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for DelegationCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Hash of the working tree at `directory`: HEAD, uncommitted changes to
+/// tracked files, and the size and modification time of untracked files.
+/// `None` outside a git repository, where changes can't be detected cheaply.
+pub async fn workspace_tree_hash(directory: &Path) -> Option<String> {
+    let mut hasher = Sha256::new();
+    for args in [
+        &["rev-parse", "HEAD"][..],
+        &["diff", "HEAD"][..],
+        &["ls-files", "--others", "--exclude-standard", "-z"][..],
+    ] {
+        let output = tokio::process::Command::new("git")
+            .args(args)
+            .current_dir(directory)
+            .output()
+            .await
+            .ok()?;
+        if !output.status.success() {
+            return None;
+        }
+        hasher.update(&output.stdout);
+
+        if args[0] == "ls-files" {
+            for path in output.stdout.split(|b| *b == 0).filter(|p| !p.is_empty()) {
+                let path = directory.join(String::from_utf8_lossy(path).as_ref());
+                let Ok(metadata) = std::fs::metadata(&path) else {
+                    continue;
+                };
+                let modified = metadata
+                    .modified()
+                    .ok()
+                    .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                    .unwrap_or_default();
+                hasher.update(metadata.len().to_le_bytes());
+                hasher.update(modified.as_nanos().to_le_bytes());
+            }
+        }
+    }
+    Some(hex::encode(hasher.finalize()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fingerprint_ignores_whitespace_only() {
+        let a = TaskFingerprint::new("explore", None, "Find where  Parser is\ndefined", "tree");
+        let b = TaskFingerprint::new("explore", None, "Find where Parser is defined ", "tree");
+        assert_eq!(a, b);
+        assert_ne!(
+            a,
+            TaskFingerprint::new("explore", None, "Find where parser is defined", "tree")
+        );
+        assert_ne!(
+            a,
+            TaskFingerprint::new("librarian", None, "Find where Parser is defined", "tree")
+        );
+        assert_ne!(
+            a,
+            TaskFingerprint::new(
+                "explore",
+                Some("openrouter/qwen-coder"),
+                "Find where Parser is defined",
+                "tree"
+            )
+        );
+        assert_ne!(
+            a,
+            TaskFingerprint::new("explore", None, "Find where Parser is defined", "other")
+        );
+    }
+
+    #[test]
+    fn test_entries_expire_after_ttl() {
+        let cache = DelegationCache::new();
+        let fingerprint = TaskFingerprint::new("explore", None, "find x", "tree");
+        cache.put(fingerprint.clone(), "src/x.rs:10".to_string());
+
+        assert_eq!(
+            cache.get(&fingerprint, Duration::from_secs(60)).as_deref(),
+            Some("src/x.rs:10")
+        );
+        assert_eq!(cache.get(&fingerprint, Duration::ZERO), None);
+        assert!(cache.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_tree_hash_changes_with_the_tree() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+        };
+        if !git(&["init", "-q"]).status.success() {
+            return;
+        }
+        git(&["config", "user.email", "test@example.com"]);
+        git(&["config", "user.name", "test"]);
+        std::fs::write(dir.path().join("lib.rs"), "fn a() {}\n").unwrap();
+        git(&["add", "."]);
+        git(&["commit", "-qm", "init"]);
+
+        let clean = workspace_tree_hash(dir.path()).await.unwrap();
+        assert_eq!(workspace_tree_hash(dir.path()).await.unwrap(), clean);
+
+        std::fs::write(dir.path().join("lib.rs"), "fn b() {}\n").unwrap();
+        let edited = workspace_tree_hash(dir.path()).await.unwrap();
+        assert_ne!(edited, clean);
+
+        std::fs::write(dir.path().join("new.rs"), "fn c() {}\n").unwrap();
+        assert_ne!(workspace_tree_hash(dir.path()).await.unwrap(), edited);
+
+        let outside = tempfile::tempdir().unwrap();
+        assert_eq!(workspace_tree_hash(outside.path()).await, None);
+    }
+}
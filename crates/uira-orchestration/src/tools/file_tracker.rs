@@ -0,0 +1,217 @@
+//! Stale-read detection for files the agent has read
+//!
+//! Read records each file's modification time and content hash. Write and
+//! Edit refuse to touch a file that changed on disk since then, so the agent
+//! can't clobber edits made in the user's editor while it was working.
+//! Changes made by the agent's own file-writing tools, such as a `cargo fmt`
+//! run through Bash or an `lsp_rename`, are adopted as its current view
+//! instead; the orchestrator wraps those calls in [`FileTracker::fresh_files`]
+//! and [`FileTracker::adopt_changes`].
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+
+use crate::tools::ToolError;
+
+#[derive(Debug, Clone)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+    hash: u64,
+    /// Whether the agent has already been told this file changed
+    reported: bool,
+}
+
+/// Files read this session, shared by every tool call
+#[derive(Debug, Clone, Default)]
+pub struct FileTracker {
+    files: Arc<Mutex<HashMap<PathBuf, FileStamp>>>,
+}
+
+impl FileTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Remember `content` as the agent's current view of `path`
+    pub fn record(&self, path: &Path, content: &[u8]) {
+        let stamp = FileStamp {
+            modified: modified(path),
+            len: content.len() as u64,
+            hash: hash(content),
+            reported: false,
+        };
+        self.lock().insert(path.to_path_buf(), stamp);
+    }
+
+    /// Fail if `path` was read and has changed on disk since
+    pub fn check_fresh(&self, path: &Path) -> Result<(), ToolError> {
+        let Some(stamp) = self.lock().get(path).cloned() else {
+            return Ok(());
+        };
+        if !is_stale(path, &stamp) {
+            return Ok(());
+        }
+        Err(ToolError::ExecutionFailed {
+            message: format!(
+                "{} changed on disk since you last read it, possibly by the user. Read it again and redo your change on top of the current content.",
+                path.display()
+            ),
+        })
+    }
+
+    /// Read files whose content is still what the agent last saw. Take this
+    /// before running a command and pass it to [`Self::adopt_changes`]
+    /// afterwards.
+    pub fn fresh_files(&self) -> Vec<PathBuf> {
+        self.lock()
+            .iter()
+            .filter(|(path, stamp)| !is_stale(path, stamp))
+            .map(|(path, _)| path.clone())
+            .collect()
+    }
+
+    /// Record the current content of those `paths` that changed, since the
+    /// agent's own command changed them. Files the command deleted are
+    /// forgotten.
+    pub fn adopt_changes(&self, paths: &[PathBuf]) {
+        let mut files = self.lock();
+        for path in paths {
+            let Some(stamp) = files.get(path) else {
+                continue;
+            };
+            if !is_stale(path, stamp) {
+                continue;
+            }
+            match std::fs::read(path) {
+                Ok(content) => {
+                    let stamp = FileStamp {
+                        modified: modified(path),
+                        len: content.len() as u64,
+                        hash: hash(&content),
+                        reported: false,
+                    };
+                    files.insert(path.clone(), stamp);
+                }
+                Err(_) => {
+                    files.remove(path);
+                }
+            }
+        }
+    }
+
+    /// Files that changed on disk since they were read and haven't been
+    /// reported yet; each file is reported once until it's read again
+    pub fn take_changed(&self) -> Vec<PathBuf> {
+        let mut files = self.lock();
+        let mut changed: Vec<PathBuf> = files
+            .iter_mut()
+            .filter(|(path, stamp)| !stamp.reported && is_stale(path, stamp))
+            .map(|(path, stamp)| {
+                stamp.reported = true;
+                path.clone()
+            })
+            .collect();
+        changed.sort();
+        changed
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, HashMap<PathBuf, FileStamp>> {
+        self.files.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn is_stale(path: &Path, stamp: &FileStamp) -> bool {
+    let unchanged = std::fs::metadata(path).is_ok_and(|m| {
+        m.len() == stamp.len && stamp.modified.is_some() && m.modified().ok() == stamp.modified
+    });
+    if unchanged {
+        return false;
+    }
+    // The metadata moved (or the file is gone); only a content change counts
+    match std::fs::read(path) {
+        Ok(content) => hash(&content) != stamp.hash,
+        Err(_) => true,
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn hash(content: &[u8]) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detects_external_changes() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, "fn a() {}").unwrap();
+
+        let tracker = FileTracker::new();
+        tracker.record(&path, b"fn a() {}");
+        assert!(tracker.check_fresh(&path).is_ok());
+        assert!(tracker.take_changed().is_empty());
+
+        // Touching without changing content is fine
+        std::fs::write(&path, "fn a() {}").unwrap();
+        assert!(tracker.check_fresh(&path).is_ok());
+
+        std::fs::write(&path, "fn b() { todo!() }").unwrap();
+        let err = tracker.check_fresh(&path).unwrap_err().to_string();
+        assert!(err.contains("changed on disk since you last read it"));
+        assert_eq!(tracker.take_changed(), vec![path.clone()]);
+        // Reported once until read again
+        assert!(tracker.take_changed().is_empty());
+        assert!(tracker.check_fresh(&path).is_err());
+
+        tracker.record(&path, b"fn b() { todo!() }");
+        assert!(tracker.check_fresh(&path).is_ok());
+    }
+
+    #[test]
+    fn test_adopts_changes_from_agent_commands() {
+        let dir = tempfile::tempdir().unwrap();
+        let formatted = dir.path().join("fmt.rs");
+        let removed = dir.path().join("gone.rs");
+        let edited = dir.path().join("user.rs");
+        for path in [&formatted, &removed, &edited] {
+            std::fs::write(path, "fn a(){}").unwrap();
+        }
+
+        let tracker = FileTracker::new();
+        for path in [&formatted, &removed, &edited] {
+            tracker.record(path, b"fn a(){}");
+        }
+        // Changed by the user before the command ran
+        std::fs::write(&edited, "fn user() {}").unwrap();
+
+        let fresh = tracker.fresh_files();
+        assert_eq!(fresh.len(), 2);
+        std::fs::write(&formatted, "fn a() {}\n").unwrap();
+        std::fs::remove_file(&removed).unwrap();
+        tracker.adopt_changes(&fresh);
+
+        assert!(tracker.check_fresh(&formatted).is_ok());
+        assert!(tracker.check_fresh(&removed).is_ok());
+        assert!(tracker.check_fresh(&edited).is_err());
+        assert_eq!(tracker.take_changed(), vec![edited]);
+    }
+
+    #[test]
+    fn test_untracked_files_are_fresh() {
+        let tracker = FileTracker::new();
+        assert!(tracker.check_fresh(Path::new("/nonexistent/file")).is_ok());
+    }
+}
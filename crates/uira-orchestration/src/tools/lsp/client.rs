@@ -14,6 +14,12 @@ use tokio::sync::{Mutex, RwLock};
 /// Maximum Content-Length we'll accept (32MB) to prevent OOM
 const MAX_CONTENT_LENGTH: usize = 32 * 1024 * 1024;
 
+/// Most files a single workspace diagnostics call will sync
+const MAX_WORKSPACE_DIAGNOSTIC_FILES: usize = 200;
+
+/// Directories skipped when expanding a workspace diagnostics glob
+const SKIPPED_DIRS: &[&str] = &["target", "node_modules", ".git", "dist", "build"];
+
 /// LSP client that communicates with language servers
 #[derive(Clone)]
 pub struct LspClientImpl {
@@ -143,7 +149,7 @@ impl LspClientImpl {
                     ),
                 }
             })?),
-            capabilities: ClientCapabilities::default(),
+            capabilities: Self::client_capabilities(),
             ..Default::default()
         };
 
@@ -168,6 +174,41 @@ impl LspClientImpl {
         Ok(())
     }
 
+    /// Capabilities servers need before they return code actions with edits
+    fn client_capabilities() -> ClientCapabilities {
+        let kinds = [
+            CodeActionKind::EMPTY,
+            CodeActionKind::QUICKFIX,
+            CodeActionKind::REFACTOR,
+            CodeActionKind::REFACTOR_EXTRACT,
+            CodeActionKind::REFACTOR_INLINE,
+            CodeActionKind::REFACTOR_REWRITE,
+            CodeActionKind::SOURCE,
+            CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+        ];
+
+        ClientCapabilities {
+            text_document: Some(TextDocumentClientCapabilities {
+                code_action: Some(CodeActionClientCapabilities {
+                    code_action_literal_support: Some(CodeActionLiteralSupport {
+                        code_action_kind: CodeActionKindLiteralSupport {
+                            value_set: kinds.iter().map(|k| k.as_str().to_string()).collect(),
+                        },
+                    }),
+                    is_preferred_support: Some(true),
+                    disabled_support: Some(true),
+                    data_support: Some(true),
+                    resolve_support: Some(CodeActionCapabilityResolveSupport {
+                        properties: vec!["edit".to_string()],
+                    }),
+                    ..Default::default()
+                }),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
     async fn send_request(
         &self,
         process: &Arc<Mutex<ServerProcess>>,
@@ -460,6 +501,103 @@ impl LspClientImpl {
         Ok(())
     }
 
+    /// Wait until every uri in `file_uris` has published diagnostics, or `max_wait` passes
+    ///
+    /// Files that never report within the window are returned with no diagnostics.
+    async fn poll_for_all_diagnostics(
+        &self,
+        process: &Arc<Mutex<ServerProcess>>,
+        file_uris: &[String],
+        max_wait: Duration,
+    ) -> Result<HashMap<String, Vec<Diagnostic>>, ToolError> {
+        let deadline = tokio::time::Instant::now() + max_wait;
+        let collect = |proc: &ServerProcess| {
+            file_uris
+                .iter()
+                .map(|uri| {
+                    let diags = proc.diagnostics.get(uri).cloned().unwrap_or_default();
+                    (uri.clone(), diags)
+                })
+                .collect()
+        };
+
+        loop {
+            let mut proc = process.lock().await;
+            if file_uris
+                .iter()
+                .all(|uri| proc.diagnostics.contains_key(uri))
+            {
+                return Ok(collect(&proc));
+            }
+
+            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
+            if remaining.is_zero() {
+                return Ok(collect(&proc));
+            }
+            let message =
+                match tokio::time::timeout(remaining, Self::read_framed_message(&mut proc)).await {
+                    Ok(Ok(msg)) => msg,
+                    Ok(Err(_)) | Err(_) => return Ok(collect(&proc)),
+                };
+            if let Some(method) = message.get("method").and_then(Value::as_str) {
+                if method == "textDocument/publishDiagnostics" {
+                    Self::store_diagnostics(&mut proc, &message);
+                }
+                continue;
+            }
+            if let Some(id) = message.get("id").and_then(Value::as_i64) {
+                proc.pending_responses.insert(id, message);
+            }
+        }
+    }
+
+    /// Files for a workspace diagnostics run: `glob` matches, or every open document
+    async fn workspace_diagnostic_files(
+        &self,
+        glob_pattern: Option<&str>,
+    ) -> Result<Vec<PathBuf>, ToolError> {
+        let mut files = Vec::new();
+
+        match glob_pattern {
+            Some(pattern) => {
+                let full_pattern = self.root_path.join(pattern);
+                let paths = glob::glob(&full_pattern.to_string_lossy()).map_err(|e| {
+                    ToolError::InvalidInput {
+                        message: format!("Invalid glob '{}': {}", pattern, e),
+                    }
+                })?;
+                for path in paths.flatten() {
+                    let skipped = path
+                        .strip_prefix(&self.root_path)
+                        .unwrap_or(&path)
+                        .components()
+                        .any(|c| SKIPPED_DIRS.iter().any(|dir| c.as_os_str() == *dir));
+                    if path.is_file()
+                        && !skipped
+                        && self.detect_language(&path.to_string_lossy()).is_some()
+                    {
+                        files.push(path);
+                    }
+                }
+            }
+            None => {
+                let servers = self.servers.read().await;
+                for server in servers.values() {
+                    let proc = server.lock().await;
+                    files.extend(
+                        proc.opened_documents
+                            .keys()
+                            .filter_map(|uri| Url::parse(uri).ok()?.to_file_path().ok()),
+                    );
+                }
+            }
+        }
+
+        files.sort();
+        files.dedup();
+        Ok(files)
+    }
+
     async fn poll_for_diagnostics(
         &self,
         process: &Arc<Mutex<ServerProcess>>,
@@ -527,6 +665,11 @@ pub trait LspClient: Send + Sync {
     async fn prepare_rename(&self, params: Value) -> Result<ToolOutput, ToolError>;
     async fn rename(&self, params: Value) -> Result<ToolOutput, ToolError>;
     async fn hover(&self, params: Value) -> Result<ToolOutput, ToolError>;
+    async fn code_actions(&self, params: Value) -> Result<ToolOutput, ToolError>;
+    async fn format_range(&self, params: Value) -> Result<ToolOutput, ToolError>;
+    async fn call_hierarchy(&self, params: Value) -> Result<ToolOutput, ToolError>;
+    async fn implementations(&self, params: Value) -> Result<ToolOutput, ToolError>;
+    async fn workspace_diagnostics(&self, params: Value) -> Result<ToolOutput, ToolError>;
 }
 
 #[async_trait]
@@ -920,4 +1063,717 @@ impl LspClient for LspClientImpl {
             serde_json::to_string_pretty(&response["result"]).unwrap(),
         ))
     }
+
+    async fn code_actions(&self, params: Value) -> Result<ToolOutput, ToolError> {
+        let file_path = params["filePath"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing filePath parameter".to_string(),
+            })?;
+        let canonical_file = self.ensure_within_root(file_path)?;
+        let file_path = canonical_file.to_string_lossy().to_string();
+
+        let line = params["line"]
+            .as_u64()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing line parameter".to_string(),
+            })? as u32;
+
+        let character = params["character"]
+            .as_u64()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing character parameter".to_string(),
+            })? as u32;
+
+        let end_line = params["endLine"].as_u64().map_or(line, |l| l as u32);
+        let end_character = params["endCharacter"]
+            .as_u64()
+            .map_or(character, |c| c as u32);
+        let apply = params["apply"].as_u64().map(|i| i as usize);
+
+        let language =
+            self.detect_language(&file_path)
+                .ok_or_else(|| ToolError::ExecutionFailed {
+                    message: "Could not detect language from file extension".to_string(),
+                })?;
+
+        let server = self.get_or_start_server(&language).await?;
+        self.sync_document(&server, &file_path, &canonical_file, &language)
+            .await?;
+
+        // Quick fixes hang off diagnostics, so pass along the ones touching the range
+        let file_uri = Self::to_file_uri(&file_path)?;
+        {
+            let mut proc = server.lock().await;
+            proc.diagnostics.remove(&file_uri);
+        }
+        let start = super::utils::to_lsp_position(line, character);
+        let end = super::utils::to_lsp_position(end_line, end_character);
+        let diagnostics: Vec<Diagnostic> = self
+            .poll_for_diagnostics(&server, &file_uri, Duration::from_secs(2))
+            .await?
+            .into_iter()
+            .filter(|d| d.range.start.line <= end.0 && d.range.end.line >= start.0)
+            .collect();
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/codeAction",
+            "params": {
+                "textDocument": {
+                    "uri": file_uri,
+                },
+                "range": {
+                    "start": { "line": start.0, "character": start.1 },
+                    "end": { "line": end.0, "character": end.1 },
+                },
+                "context": {
+                    "diagnostics": diagnostics,
+                },
+            },
+        });
+
+        let response = self.send_request(&server, request).await?;
+        let actions: Vec<CodeActionOrCommand> =
+            serde_json::from_value(response["result"].clone()).unwrap_or_default();
+
+        let Some(index) = apply else {
+            if actions.is_empty() {
+                return Ok(ToolOutput::text(
+                    "No code actions available at this position.",
+                ));
+            }
+            let listing: Vec<String> = actions
+                .iter()
+                .enumerate()
+                .map(|(i, action)| match action {
+                    CodeActionOrCommand::Command(command) => {
+                        format!("[{}] {} (command)", i, command.title)
+                    }
+                    CodeActionOrCommand::CodeAction(action) => {
+                        let mut line = format!("[{}] {}", i, action.title);
+                        if let Some(kind) = &action.kind {
+                            line.push_str(&format!(" ({})", kind.as_str()));
+                        }
+                        if action.is_preferred == Some(true) {
+                            line.push_str(" [preferred]");
+                        }
+                        if let Some(disabled) = &action.disabled {
+                            line.push_str(&format!(" [disabled: {}]", disabled.reason));
+                        }
+                        line
+                    }
+                })
+                .collect();
+            return Ok(ToolOutput::text(format!(
+                "{}\n\nPass `apply` with an index to apply an action.",
+                listing.join("\n")
+            )));
+        };
+
+        let action = match actions.into_iter().nth(index) {
+            Some(CodeActionOrCommand::CodeAction(action)) => action,
+            Some(CodeActionOrCommand::Command(command)) => {
+                return Err(ToolError::ExecutionFailed {
+                    message: format!(
+                        "Action \"{}\" only runs server command `{}`, which is not supported",
+                        command.title, command.command
+                    ),
+                })
+            }
+            None => {
+                return Err(ToolError::InvalidInput {
+                    message: format!("No code action at index {}", index),
+                })
+            }
+        };
+
+        if let Some(disabled) = &action.disabled {
+            return Err(ToolError::ExecutionFailed {
+                message: format!(
+                    "Action \"{}\" is disabled: {}",
+                    action.title, disabled.reason
+                ),
+            });
+        }
+
+        // Servers may defer computing the edit until the action is resolved
+        let action = if action.edit.is_none() && action.data.is_some() {
+            let request = json!({
+                "jsonrpc": "2.0",
+                "id": 1,
+                "method": "codeAction/resolve",
+                "params": action,
+            });
+            let response = self.send_request(&server, request).await?;
+            serde_json::from_value(response["result"].clone()).map_err(|e| {
+                ToolError::ExecutionFailed {
+                    message: format!("Invalid codeAction/resolve response: {}", e),
+                }
+            })?
+        } else {
+            action
+        };
+
+        let Some(edit) = &action.edit else {
+            let reason = match &action.command {
+                Some(command) => format!(
+                    "only runs server command `{}`, which is not supported",
+                    command.command
+                ),
+                None => "has no edit".to_string(),
+            };
+            return Err(ToolError::ExecutionFailed {
+                message: format!("Action \"{}\" {}", action.title, reason),
+            });
+        };
+
+        let applied = super::edits::apply_workspace_edit(edit, &self.root_path)?;
+        for (path, _) in &applied {
+            let path_str = path.to_string_lossy().to_string();
+            if self.detect_language(&path_str).as_deref() == Some(language.as_str()) {
+                self.sync_document(&server, &path_str, path, &language)
+                    .await?;
+            }
+        }
+
+        let files: Vec<String> = applied
+            .iter()
+            .map(|(path, count)| format!("  {} ({} edit(s))", path.display(), count))
+            .collect();
+        Ok(ToolOutput::text(format!(
+            "Applied \"{}\":\n{}",
+            action.title,
+            files.join("\n")
+        )))
+    }
+
+    async fn format_range(&self, params: Value) -> Result<ToolOutput, ToolError> {
+        let file_path = params["filePath"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing filePath parameter".to_string(),
+            })?;
+        let canonical_file = self.ensure_within_root(file_path)?;
+        let file_path = canonical_file.to_string_lossy().to_string();
+
+        let start_line = params["startLine"].as_u64().map(|l| l as u32);
+        let end_line = params["endLine"].as_u64().map(|l| l as u32);
+        let options = json!({
+            "tabSize": params["tabSize"].as_u64().unwrap_or(4),
+            "insertSpaces": params["insertSpaces"].as_bool().unwrap_or(true),
+        });
+
+        let language =
+            self.detect_language(&file_path)
+                .ok_or_else(|| ToolError::ExecutionFailed {
+                    message: "Could not detect language from file extension".to_string(),
+                })?;
+
+        let server = self.get_or_start_server(&language).await?;
+        self.sync_document(&server, &file_path, &canonical_file, &language)
+            .await?;
+
+        let text_document = json!({ "uri": Self::to_file_uri(&file_path)? });
+        let (method, request_params) = match (start_line, end_line) {
+            (None, None) => (
+                "textDocument/formatting",
+                json!({
+                    "textDocument": text_document,
+                    "options": options,
+                }),
+            ),
+            (start, end) => {
+                let start = start.or(end).unwrap_or(1);
+                let end = end.unwrap_or(start).max(start);
+                (
+                    "textDocument/rangeFormatting",
+                    json!({
+                        "textDocument": text_document,
+                        // Up to the start of the line after `end`, so the whole last line is included
+                        "range": {
+                            "start": { "line": start.saturating_sub(1), "character": 0 },
+                            "end": { "line": end, "character": 0 },
+                        },
+                        "options": options,
+                    }),
+                )
+            }
+        };
+
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": request_params,
+        });
+
+        let response = self.send_request(&server, request).await?;
+        let edits: Vec<TextEdit> =
+            serde_json::from_value(response["result"].clone()).unwrap_or_default();
+        if edits.is_empty() {
+            return Ok(ToolOutput::text("Already formatted; no changes made."));
+        }
+
+        let text = tokio::fs::read_to_string(&canonical_file)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed {
+                message: format!("Failed to read file for formatting: {}", e),
+            })?;
+        let formatted = super::edits::apply_text_edits(&text, &edits)?;
+        tokio::fs::write(&canonical_file, formatted)
+            .await
+            .map_err(|e| ToolError::ExecutionFailed {
+                message: format!("Failed to write formatted file: {}", e),
+            })?;
+        self.sync_document(&server, &file_path, &canonical_file, &language)
+            .await?;
+
+        Ok(ToolOutput::text(format!(
+            "Formatted {} ({} edit(s))",
+            file_path,
+            edits.len()
+        )))
+    }
+
+    async fn call_hierarchy(&self, params: Value) -> Result<ToolOutput, ToolError> {
+        let file_path = params["filePath"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing filePath parameter".to_string(),
+            })?;
+        let canonical_file = self.ensure_within_root(file_path)?;
+        let file_path = canonical_file.to_string_lossy().to_string();
+
+        let line = params["line"]
+            .as_u64()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing line parameter".to_string(),
+            })? as u32;
+
+        let character = params["character"]
+            .as_u64()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing character parameter".to_string(),
+            })? as u32;
+
+        let direction = params["direction"].as_str().unwrap_or("both");
+        if !matches!(direction, "incoming" | "outgoing" | "both") {
+            return Err(ToolError::InvalidInput {
+                message: format!(
+                    "Invalid direction '{}': expected incoming, outgoing or both",
+                    direction
+                ),
+            });
+        }
+
+        let language =
+            self.detect_language(&file_path)
+                .ok_or_else(|| ToolError::ExecutionFailed {
+                    message: "Could not detect language from file extension".to_string(),
+                })?;
+
+        let server = self.get_or_start_server(&language).await?;
+        self.ensure_document_opened(&server, &file_path, &canonical_file, &language)
+            .await?;
+
+        let position = super::utils::to_lsp_position(line, character);
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/prepareCallHierarchy",
+            "params": {
+                "textDocument": {
+                    "uri": Self::to_file_uri(&file_path)?,
+                },
+                "position": {
+                    "line": position.0,
+                    "character": position.1,
+                },
+            },
+        });
+
+        let response = self.send_request(&server, request).await?;
+        let items: Vec<CallHierarchyItem> =
+            serde_json::from_value(response["result"].clone()).unwrap_or_default();
+        if items.is_empty() {
+            return Ok(ToolOutput::text("No call hierarchy item at this position."));
+        }
+
+        let mut sections = Vec::new();
+        for item in items {
+            let mut section = vec![format!(
+                "{} {}",
+                describe_symbol_kind(item.kind),
+                format_call_item(&item)
+            )];
+
+            if direction != "outgoing" {
+                let request = json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "callHierarchy/incomingCalls",
+                    "params": { "item": item },
+                });
+                let response = self.send_request(&server, request).await?;
+                let calls: Vec<CallHierarchyIncomingCall> =
+                    serde_json::from_value(response["result"].clone()).unwrap_or_default();
+                section.push(format!("Incoming calls ({}):", calls.len()));
+                section.extend(calls.iter().map(|call| {
+                    format!(
+                        "  <- {}{}",
+                        format_call_item(&call.from),
+                        format_call_sites(&call.from_ranges)
+                    )
+                }));
+            }
+
+            if direction != "incoming" {
+                let request = json!({
+                    "jsonrpc": "2.0",
+                    "id": 1,
+                    "method": "callHierarchy/outgoingCalls",
+                    "params": { "item": item },
+                });
+                let response = self.send_request(&server, request).await?;
+                let calls: Vec<CallHierarchyOutgoingCall> =
+                    serde_json::from_value(response["result"].clone()).unwrap_or_default();
+                section.push(format!("Outgoing calls ({}):", calls.len()));
+                section.extend(
+                    calls
+                        .iter()
+                        .map(|call| format!("  -> {}", format_call_item(&call.to))),
+                );
+            }
+
+            sections.push(section.join("\n"));
+        }
+
+        Ok(ToolOutput::text(sections.join("\n\n")))
+    }
+
+    async fn implementations(&self, params: Value) -> Result<ToolOutput, ToolError> {
+        let file_path = params["filePath"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing filePath parameter".to_string(),
+            })?;
+        let canonical_file = self.ensure_within_root(file_path)?;
+        let file_path = canonical_file.to_string_lossy().to_string();
+
+        let line = params["line"]
+            .as_u64()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing line parameter".to_string(),
+            })? as u32;
+
+        let character = params["character"]
+            .as_u64()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing character parameter".to_string(),
+            })? as u32;
+
+        let language =
+            self.detect_language(&file_path)
+                .ok_or_else(|| ToolError::ExecutionFailed {
+                    message: "Could not detect language from file extension".to_string(),
+                })?;
+
+        let server = self.get_or_start_server(&language).await?;
+        self.ensure_document_opened(&server, &file_path, &canonical_file, &language)
+            .await?;
+
+        let position = super::utils::to_lsp_position(line, character);
+        let request = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "textDocument/implementation",
+            "params": {
+                "textDocument": {
+                    "uri": Self::to_file_uri(&file_path)?,
+                },
+                "position": {
+                    "line": position.0,
+                    "character": position.1,
+                },
+            },
+        });
+
+        let response = self.send_request(&server, request).await?;
+
+        Ok(ToolOutput::text(
+            serde_json::to_string_pretty(&response["result"]).unwrap(),
+        ))
+    }
+
+    async fn workspace_diagnostics(&self, params: Value) -> Result<ToolOutput, ToolError> {
+        let glob_pattern = params["glob"].as_str();
+        let severity = params["severity"].as_str().unwrap_or("all");
+        let min_severity = match severity {
+            "error" => DiagnosticSeverity::ERROR,
+            "warning" => DiagnosticSeverity::WARNING,
+            "information" => DiagnosticSeverity::INFORMATION,
+            "hint" | "all" => DiagnosticSeverity::HINT,
+            other => {
+                return Err(ToolError::InvalidInput {
+                    message: format!(
+                        "Invalid severity '{}': expected error, warning, information, hint or all",
+                        other
+                    ),
+                })
+            }
+        };
+        let limit = params["limit"].as_u64().unwrap_or(50) as usize;
+        let max_wait = Duration::from_secs(params["timeoutSecs"].as_u64().unwrap_or(5));
+
+        let mut files = self.workspace_diagnostic_files(glob_pattern).await?;
+        if files.is_empty() {
+            return Ok(ToolOutput::text(
+                "No files to check. Pass a glob, or open files with other LSP tools first.",
+            ));
+        }
+        let skipped = files.len().saturating_sub(MAX_WORKSPACE_DIAGNOSTIC_FILES);
+        files.truncate(MAX_WORKSPACE_DIAGNOSTIC_FILES);
+
+        let mut by_language: HashMap<String, Vec<PathBuf>> = HashMap::new();
+        for file in files {
+            let canonical = self.ensure_within_root(&file.to_string_lossy())?;
+            if let Some(language) = self.detect_language(&canonical.to_string_lossy()) {
+                by_language.entry(language).or_default().push(canonical);
+            }
+        }
+
+        let mut results: Vec<(String, Vec<Diagnostic>)> = Vec::new();
+        for (language, files) in by_language {
+            let server = self.get_or_start_server(&language).await?;
+            let mut uris = Vec::with_capacity(files.len());
+            for file in &files {
+                let file_path = file.to_string_lossy().to_string();
+                self.sync_document(&server, &file_path, file, &language)
+                    .await?;
+                uris.push(Self::to_file_uri(&file_path)?);
+            }
+            {
+                let mut proc = server.lock().await;
+                for uri in &uris {
+                    proc.diagnostics.remove(uri);
+                }
+            }
+
+            let mut diagnostics = self
+                .poll_for_all_diagnostics(&server, &uris, max_wait)
+                .await?;
+            for (file, uri) in files.iter().zip(&uris) {
+                let display = file
+                    .strip_prefix(&self.root_path)
+                    .unwrap_or(file)
+                    .display()
+                    .to_string();
+                let diags = diagnostics
+                    .remove(uri)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter(|d| d.severity.unwrap_or(DiagnosticSeverity::ERROR) <= min_severity)
+                    .collect();
+                results.push((display, diags));
+            }
+        }
+        results.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut summary = summarize_diagnostics(&results, limit);
+        if skipped > 0 {
+            summary.push_str(&format!(
+                "\n\n{} more file(s) matched but were not checked; narrow the glob.",
+                skipped
+            ));
+        }
+        Ok(ToolOutput::text(summary))
+    }
+}
+
+/// Severity totals, then per-file counts and up to `limit` individual diagnostics
+fn summarize_diagnostics(results: &[(String, Vec<Diagnostic>)], limit: usize) -> String {
+    let label = |severity: Option<DiagnosticSeverity>| match severity {
+        Some(DiagnosticSeverity::WARNING) => "warning",
+        Some(DiagnosticSeverity::INFORMATION) => "info",
+        Some(DiagnosticSeverity::HINT) => "hint",
+        _ => "error",
+    };
+    let count_line = |diags: &[Diagnostic]| {
+        let mut counts: Vec<String> = Vec::new();
+        for name in ["error", "warning", "info", "hint"] {
+            let n = diags.iter().filter(|d| label(d.severity) == name).count();
+            if n > 0 {
+                counts.push(format!("{} {}{}", n, name, if n == 1 { "" } else { "s" }));
+            }
+        }
+        counts.join(", ")
+    };
+
+    let all: Vec<Diagnostic> = results.iter().flat_map(|(_, d)| d.clone()).collect();
+    let with_issues: Vec<_> = results.iter().filter(|(_, d)| !d.is_empty()).collect();
+    if with_issues.is_empty() {
+        return format!("No diagnostics in {} file(s).", results.len());
+    }
+
+    let mut out = vec![format!(
+        "{} across {} of {} file(s)",
+        count_line(&all),
+        with_issues.len(),
+        results.len()
+    )];
+    let mut shown = 0;
+    for (path, diags) in with_issues {
+        out.push(String::new());
+        out.push(format!("{}: {}", path, count_line(diags)));
+        for d in diags {
+            if shown >= limit {
+                break;
+            }
+            shown += 1;
+            out.push(format!(
+                "  [{}] {}:{} {}",
+                label(d.severity).to_uppercase(),
+                d.range.start.line + 1,
+                d.range.start.character,
+                d.message.replace('\n', " ")
+            ));
+        }
+    }
+    if all.len() > shown {
+        out.push(String::new());
+        out.push(format!(
+            "... {} more not shown (raise `limit`)",
+            all.len() - shown
+        ));
+    }
+    out.join("\n")
+}
+
+/// `name detail — path:line` for a call hierarchy item (1-indexed line)
+fn format_call_item(item: &CallHierarchyItem) -> String {
+    let path = item
+        .uri
+        .to_file_path()
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| item.uri.to_string());
+    let detail = item
+        .detail
+        .as_deref()
+        .filter(|d| !d.is_empty())
+        .map(|d| format!(" {}", d))
+        .unwrap_or_default();
+    format!(
+        "{}{} — {}:{}",
+        item.name,
+        detail,
+        path,
+        item.selection_range.start.line + 1
+    )
+}
+
+/// ` (lines 3, 9)` listing where a caller makes its calls
+fn format_call_sites(ranges: &[Range]) -> String {
+    if ranges.is_empty() {
+        return String::new();
+    }
+    let lines: Vec<String> = ranges
+        .iter()
+        .map(|r| (r.start.line + 1).to_string())
+        .collect();
+    format!(
+        " ({} {})",
+        if lines.len() == 1 { "line" } else { "lines" },
+        lines.join(", ")
+    )
+}
+
+fn describe_symbol_kind(kind: SymbolKind) -> &'static str {
+    match kind {
+        SymbolKind::FUNCTION => "function",
+        SymbolKind::METHOD => "method",
+        SymbolKind::CONSTRUCTOR => "constructor",
+        SymbolKind::CLASS => "class",
+        SymbolKind::STRUCT => "struct",
+        SymbolKind::INTERFACE => "interface",
+        SymbolKind::MODULE => "module",
+        SymbolKind::FIELD | SymbolKind::PROPERTY => "property",
+        SymbolKind::VARIABLE | SymbolKind::CONSTANT => "variable",
+        _ => "symbol",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(name: &str, detail: Option<&str>, line: u32) -> CallHierarchyItem {
+        let range = Range::new(Position::new(line, 0), Position::new(line, 4));
+        CallHierarchyItem {
+            name: name.to_string(),
+            kind: SymbolKind::FUNCTION,
+            tags: None,
+            detail: detail.map(String::from),
+            uri: Url::parse("file:///repo/src/lib.rs").unwrap(),
+            range,
+            selection_range: range,
+            data: None,
+        }
+    }
+
+    #[test]
+    fn test_format_call_item() {
+        assert_eq!(
+            format_call_item(&item("run", Some("fn run()"), 9)),
+            "run fn run() — /repo/src/lib.rs:10"
+        );
+        assert_eq!(
+            format_call_item(&item("main", None, 0)),
+            "main — /repo/src/lib.rs:1"
+        );
+    }
+
+    #[test]
+    fn test_summarize_diagnostics() {
+        let diag = |line, severity, message: &str| Diagnostic {
+            range: Range::new(Position::new(line, 2), Position::new(line, 5)),
+            severity: Some(severity),
+            message: message.to_string(),
+            ..Default::default()
+        };
+        let results = vec![
+            (
+                "src/a.rs".to_string(),
+                vec![
+                    diag(0, DiagnosticSeverity::ERROR, "mismatched types"),
+                    diag(4, DiagnosticSeverity::WARNING, "unused\nvariable"),
+                ],
+            ),
+            ("src/b.rs".to_string(), vec![]),
+        ];
+
+        assert_eq!(
+            summarize_diagnostics(&results, 50),
+            "1 error, 1 warning across 1 of 2 file(s)\n\n\
+             src/a.rs: 1 error, 1 warning\n\
+             \x20 [ERROR] 1:2 mismatched types\n\
+             \x20 [WARNING] 5:2 unused variable"
+        );
+        assert!(
+            summarize_diagnostics(&results, 1).ends_with("... 1 more not shown (raise `limit`)")
+        );
+        assert_eq!(
+            summarize_diagnostics(&results[1..], 50),
+            "No diagnostics in 1 file(s)."
+        );
+    }
+
+    #[test]
+    fn test_format_call_sites() {
+        let at = |line| Range::new(Position::new(line, 0), Position::new(line, 1));
+        assert_eq!(format_call_sites(&[]), "");
+        assert_eq!(format_call_sites(&[at(2)]), " (line 3)");
+        assert_eq!(format_call_sites(&[at(2), at(8)]), " (lines 3, 9)");
+    }
 }
@@ -0,0 +1,226 @@
+//! Applying LSP text and workspace edits to files on disk
+
+use crate::tools::types::ToolError;
+use lsp_types::{
+    DocumentChangeOperation, DocumentChanges, OneOf, Position, TextEdit, Url, WorkspaceEdit,
+};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// Apply `edits` to `text`
+///
+/// Positions are UTF-16 offsets as the LSP specification requires. Edits must not
+/// overlap; they are applied from the end of the document backwards.
+pub fn apply_text_edits(text: &str, edits: &[TextEdit]) -> Result<String, ToolError> {
+    let mut ranges = edits
+        .iter()
+        .map(|edit| {
+            let start = position_to_offset(text, edit.range.start)?;
+            let end = position_to_offset(text, edit.range.end)?;
+            if end < start {
+                return Err(invalid_edit("edit range ends before it starts"));
+            }
+            Ok((start, end, edit.new_text.as_str()))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    // Stable sort keeps same-position inserts in the order the server sent them
+    ranges.sort_by_key(|&(start, end, _)| (start, end));
+    for pair in ranges.windows(2) {
+        if pair[1].0 < pair[0].1 {
+            return Err(invalid_edit("overlapping edits"));
+        }
+    }
+
+    let mut result = text.to_string();
+    for (start, end, new_text) in ranges.into_iter().rev() {
+        result.replace_range(start..end, new_text);
+    }
+    Ok(result)
+}
+
+/// Apply a workspace edit, returning the number of edits made per file
+///
+/// Every touched file must resolve inside `root`. File create/rename/delete
+/// operations are rejected rather than partially applied.
+pub fn apply_workspace_edit(
+    edit: &WorkspaceEdit,
+    root: &Path,
+) -> Result<Vec<(PathBuf, usize)>, ToolError> {
+    let mut per_file: BTreeMap<PathBuf, Vec<TextEdit>> = BTreeMap::new();
+
+    if let Some(changes) = &edit.changes {
+        for (uri, edits) in changes {
+            per_file
+                .entry(uri_to_path(uri)?)
+                .or_default()
+                .extend(edits.iter().cloned());
+        }
+    }
+
+    let document_edits = match &edit.document_changes {
+        None => Vec::new(),
+        Some(DocumentChanges::Edits(edits)) => edits.iter().collect(),
+        Some(DocumentChanges::Operations(operations)) => operations
+            .iter()
+            .map(|op| match op {
+                DocumentChangeOperation::Edit(edit) => Ok(edit),
+                DocumentChangeOperation::Op(_) => Err(ToolError::ExecutionFailed {
+                    message: "Edits that create, rename or delete files are not supported"
+                        .to_string(),
+                }),
+            })
+            .collect::<Result<_, _>>()?,
+    };
+    for document_edit in document_edits {
+        per_file
+            .entry(uri_to_path(&document_edit.text_document.uri)?)
+            .or_default()
+            .extend(document_edit.edits.iter().map(|edit| match edit {
+                OneOf::Left(edit) => edit.clone(),
+                OneOf::Right(annotated) => annotated.text_edit.clone(),
+            }));
+    }
+
+    let canonical_root = root
+        .canonicalize()
+        .map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to resolve workspace root: {}", e),
+        })?;
+
+    // Compute every new file before writing any, so a bad edit leaves the tree untouched
+    let mut updates = Vec::with_capacity(per_file.len());
+    for (path, edits) in per_file {
+        let canonical = path
+            .canonicalize()
+            .map_err(|e| ToolError::ExecutionFailed {
+                message: format!("Failed to resolve {}: {}", path.display(), e),
+            })?;
+        if !canonical.starts_with(&canonical_root) {
+            return Err(ToolError::InvalidInput {
+                message: format!(
+                    "Edit targets a file outside the workspace: {}",
+                    path.display()
+                ),
+            });
+        }
+        let text = std::fs::read_to_string(&canonical).map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to read {}: {}", canonical.display(), e),
+        })?;
+        let updated = apply_text_edits(&text, &edits)?;
+        updates.push((canonical, updated, edits.len()));
+    }
+
+    let mut applied = Vec::with_capacity(updates.len());
+    for (path, text, count) in updates {
+        std::fs::write(&path, text).map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to write {}: {}", path.display(), e),
+        })?;
+        applied.push((path, count));
+    }
+    Ok(applied)
+}
+
+/// Byte offset of an LSP position; positions past a line's end clamp to it
+fn position_to_offset(text: &str, position: Position) -> Result<usize, ToolError> {
+    let mut line_start = 0;
+    for _ in 0..position.line {
+        match text[line_start..].find('\n') {
+            Some(newline) => line_start += newline + 1,
+            None => return Err(invalid_edit("edit position is past the end of the file")),
+        }
+    }
+
+    let line_end = text[line_start..]
+        .find('\n')
+        .map_or(text.len(), |i| line_start + i);
+    let mut units = 0;
+    for (index, ch) in text[line_start..line_end].char_indices() {
+        if units >= position.character as usize {
+            return Ok(line_start + index);
+        }
+        units += ch.len_utf16();
+    }
+    Ok(line_end)
+}
+
+fn uri_to_path(uri: &Url) -> Result<PathBuf, ToolError> {
+    uri.to_file_path().map_err(|_| ToolError::InvalidInput {
+        message: format!("Edit targets a non-file URI: {}", uri),
+    })
+}
+
+fn invalid_edit(reason: &str) -> ToolError {
+    ToolError::ExecutionFailed {
+        message: format!("Invalid edit from language server: {}", reason),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use lsp_types::Range;
+    use std::collections::HashMap;
+
+    fn edit(start: (u32, u32), end: (u32, u32), new_text: &str) -> TextEdit {
+        TextEdit {
+            range: Range::new(Position::new(start.0, start.1), Position::new(end.0, end.1)),
+            new_text: new_text.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_apply_text_edits() {
+        let text = "fn main() {\n    let x = 1;\n}\n";
+        let edits = vec![
+            edit((1, 8), (1, 9), "value"),
+            edit((0, 0), (0, 0), "use std::fmt;\n\n"),
+        ];
+        assert_eq!(
+            apply_text_edits(text, &edits).unwrap(),
+            "use std::fmt;\n\nfn main() {\n    let value = 1;\n}\n"
+        );
+    }
+
+    #[test]
+    fn test_utf16_positions() {
+        // "😀" is two UTF-16 units but four bytes
+        let text = "let s = \"😀\"; x\n";
+        let edits = vec![edit((0, 14), (0, 15), "y")];
+        assert_eq!(
+            apply_text_edits(text, &edits).unwrap(),
+            "let s = \"😀\"; y\n"
+        );
+    }
+
+    #[test]
+    fn test_rejects_overlapping_and_out_of_range_edits() {
+        let overlapping = vec![edit((0, 0), (0, 3), "a"), edit((0, 2), (0, 4), "b")];
+        assert!(apply_text_edits("abcdef", &overlapping).is_err());
+        assert!(apply_text_edits("abc", &[edit((3, 0), (3, 0), "x")]).is_err());
+    }
+
+    #[test]
+    fn test_apply_workspace_edit() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("a.rs");
+        std::fs::write(&file, "let a = 1;\n").unwrap();
+
+        let mut changes = HashMap::new();
+        changes.insert(
+            Url::from_file_path(&file).unwrap(),
+            vec![edit((0, 4), (0, 5), "b")],
+        );
+        let workspace_edit = WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        };
+
+        let applied = apply_workspace_edit(&workspace_edit, dir.path()).unwrap();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "let b = 1;\n");
+
+        let outside = tempfile::tempdir().unwrap();
+        assert!(apply_workspace_edit(&workspace_edit, outside.path()).is_err());
+    }
+}
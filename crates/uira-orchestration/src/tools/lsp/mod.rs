@@ -4,6 +4,7 @@
 //! with language servers. Tool definitions are provided by uira-mcp-server.
 
 pub mod client;
+pub mod edits;
 pub mod servers;
 pub mod utils;
 
@@ -1,10 +1,14 @@
 pub mod approval_cache;
+pub mod approval_policy;
 pub mod ast_grep;
 pub mod background_task;
 pub mod builtins;
+pub mod command_explainer;
 pub mod comment_hook;
 pub mod comment_shared;
 pub mod delegate_task;
+pub mod delegation_cache;
+pub mod file_tracker;
 pub mod lsp;
 pub mod orchestrator;
 pub mod output;
@@ -17,25 +21,32 @@ pub mod router;
 pub mod session_manager;
 pub mod traits;
 pub mod types;
+pub mod workspace;
 
 pub use approval_cache::{
     ApprovalCache, ApprovalCacheFile, ApprovalKey, CacheDecision, CachedApproval,
 };
+pub use approval_policy::{ApprovalPolicy, ToolClass};
 pub use builtins::{
     builtin_tools, create_builtin_router, register_builtins, register_builtins_with_todos,
-    register_builtins_without_todos, BashTool, CodeSearchTool, EditTool, FetchUrlTool, GlobTool,
-    GrepAppTool, GrepTool, MemoryForgetTool, MemoryProfileTool, MemorySearchTool, MemoryStoreTool,
-    ReadTool, TodoReadTool, TodoSessionInfo, TodoStore, TodoWriteTool, WebSearchTool, WriteTool,
+    register_builtins_without_todos, BashTool, ClipboardTool, CodeSearchTool, DocsLookupTool,
+    DocumentExtractTool, EditTool, FetchUrlTool, GlobTool, GrepAppTool, GrepTool, MemoryForgetTool,
+    MemoryProfileTool, MemorySearchTool, MemoryStoreTool, ReadTool, TodoReadTool, TodoSessionInfo,
+    TodoStore, TodoWriteTool, WebSearchTool, WorkspaceIndexTool, WriteTool,
 };
+pub use command_explainer::CommandExplanation;
 pub use comment_hook::CommentChecker;
+pub use file_tracker::FileTracker;
 pub use lsp::{LspClient, LspClientImpl, LspServerConfig};
 pub use orchestrator::{PendingApproval, RunOptions, ToolOrchestrator};
 pub use parallel::ToolCallRuntime;
 pub use provider::ToolProvider;
 pub use providers::{
-    AgentExecutor, AstToolProvider, DelegationToolProvider, LspToolProvider, McpToolProvider,
+    run_background_task, AgentExecutor, AstToolProvider, CiToolProvider, DelegationToolProvider,
+    GitHubToolProvider, LspToolProvider, McpToolProvider,
 };
 pub use registry::ToolRegistry;
 pub use router::ToolRouter;
 pub use traits::{BoxedTool, FunctionTool, Tool, ToolContext, ToolFuture, ToolHandler};
 pub use types::{ToolContent, ToolDefinition, ToolError, ToolInput, ToolOutput};
+pub use workspace::WorkspaceRoot;
@@ -3,10 +3,14 @@
 use std::sync::Arc;
 use tokio::sync::{mpsc, RwLock};
 use uira_core::{ApprovalRequirement, ReviewDecision, ToolOutput};
+use uira_security::PatternError;
 use uira_security::{Action as PermissionAction, PermissionEvaluator};
 use uira_security::{SandboxManager, SandboxPolicy, SandboxType};
 
+use crate::features::builtin_skills::{SkillPermissions, SkillScope};
+use crate::features::snapshots::SnapshotStore;
 use crate::tools::approval_cache::{ApprovalCache, ApprovalKey, CacheDecision};
+use crate::tools::approval_policy::{self, ApprovalPolicy, ToolClass};
 use crate::tools::comment_hook::CommentChecker;
 use crate::tools::{BoxedTool, ToolContext, ToolError, ToolRouter};
 
@@ -54,10 +58,15 @@ pub struct ToolOrchestrator {
     comment_checker: CommentChecker,
     permission_evaluator: Option<PermissionEvaluator>,
     approval_cache: Option<Arc<RwLock<ApprovalCache>>>,
+    approval_policy: Option<ApprovalPolicy>,
     approval_tx: mpsc::Sender<PendingApproval>,
     approval_rx: Option<mpsc::Receiver<PendingApproval>>,
     full_auto: bool,
     enable_comment_warnings: bool,
+    skill_scopes: std::sync::RwLock<Vec<SkillScope>>,
+    /// Snapshot the working tree before risky shell commands, keeping this
+    /// many snapshots
+    snapshot_limit: Option<usize>,
 }
 
 impl ToolOrchestrator {
@@ -79,9 +88,28 @@ impl ToolOrchestrator {
                     )
                 }
             }
+            "ast_lint" if input.get("fix").and_then(|v| v.as_bool()) == Some(true) => {
+                ApprovalRequirement::needs_approval(
+                    "ast_lint with fix=true writes files and requires explicit approval",
+                )
+            }
             "lsp_rename" => ApprovalRequirement::needs_approval(
                 "lsp_rename can modify files across the workspace",
             ),
+            "lsp_code_actions" if input.get("apply").is_some_and(|v| !v.is_null()) => {
+                ApprovalRequirement::needs_approval(
+                    "lsp_code_actions with apply can modify files across the workspace",
+                )
+            }
+            "lsp_format_range" => {
+                ApprovalRequirement::needs_approval("lsp_format_range rewrites the file")
+            }
+            "github_create_pr" => {
+                ApprovalRequirement::needs_approval("github_create_pr publishes a pull request")
+            }
+            "github_post_review" => {
+                ApprovalRequirement::needs_approval("github_post_review publishes a review")
+            }
             _ => ApprovalRequirement::skip(),
         }
     }
@@ -94,10 +122,13 @@ impl ToolOrchestrator {
             comment_checker: CommentChecker::new(),
             permission_evaluator: None,
             approval_cache: None,
+            approval_policy: None,
             approval_tx: tx,
             approval_rx: Some(rx),
             full_auto: false,
             enable_comment_warnings: true,
+            skill_scopes: std::sync::RwLock::new(Vec::new()),
+            snapshot_limit: None,
         }
     }
 
@@ -115,6 +146,37 @@ impl ToolOrchestrator {
         self.approval_cache.clone()
     }
 
+    /// Approve calls in the policy's tool classes without prompting
+    pub fn with_approval_policy(mut self, policy: ApprovalPolicy) -> Self {
+        self.approval_policy = Some(policy);
+        self
+    }
+
+    /// The class of a call that needs approval, if the approval policy lets
+    /// it run without prompting. Calls an `ask` permission rule matched
+    /// always prompt.
+    pub fn auto_approved_class(
+        &self,
+        tool_name: &str,
+        input: &serde_json::Value,
+        ctx: &ToolContext,
+    ) -> Option<ToolClass> {
+        let policy = self.approval_policy.as_ref()?;
+        if self.evaluate_permission(tool_name, input) == Some(PermissionAction::Ask) {
+            return None;
+        }
+        let requires_network = self
+            .router
+            .get(tool_name)
+            .is_some_and(|tool| tool.requires_network());
+        let class = approval_policy::classify(tool_name, input, requires_network, ctx);
+        if !policy.auto_approves(class) {
+            return None;
+        }
+        tracing::debug!(tool = %tool_name, class = %class, "approval_auto_approved");
+        Some(class)
+    }
+
     /// Set full-auto mode (skip all approvals)
     pub fn with_full_auto(mut self, full_auto: bool) -> Self {
         self.full_auto = full_auto;
@@ -127,6 +189,73 @@ impl ToolOrchestrator {
         self
     }
 
+    /// Snapshot the working tree before shell commands that need approval
+    pub fn with_snapshots(mut self, max_snapshots: usize) -> Self {
+        self.snapshot_limit = Some(max_snapshots);
+        self
+    }
+
+    /// Apply a skill's tool and permission restrictions until it is deactivated
+    ///
+    /// Skill restrictions can only narrow what the session allows: a skill deny
+    /// blocks the call even in full-auto mode, while anything else defers to the
+    /// session's own permission rules.
+    pub fn activate_skill(
+        &self,
+        skill: &str,
+        permissions: SkillPermissions,
+    ) -> Result<(), PatternError> {
+        let scope = SkillScope::new(skill, permissions)?;
+        let mut scopes = self.skill_scopes.write().unwrap_or_else(|e| e.into_inner());
+        scopes.retain(|s| s.skill() != skill);
+        scopes.push(scope);
+        tracing::debug!(skill = %skill, "skill_restrictions_activated");
+        Ok(())
+    }
+
+    /// Revert the restrictions applied by [`Self::activate_skill`]
+    pub fn deactivate_skill(&self, skill: &str) -> bool {
+        let mut scopes = self.skill_scopes.write().unwrap_or_else(|e| e.into_inner());
+        let before = scopes.len();
+        scopes.retain(|s| s.skill() != skill);
+        before != scopes.len()
+    }
+
+    /// Names of skills whose restrictions are currently applied
+    pub fn active_skills(&self) -> Vec<String> {
+        self.skill_scopes
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .iter()
+            .map(|s| s.skill().to_string())
+            .collect()
+    }
+
+    /// Most restrictive action any active skill takes on a tool call. A skill
+    /// `allow` only lifts that skill's own broader rules, so it never grants
+    /// anything and is reported as no opinion.
+    fn skill_action(&self, tool_name: &str, input: &serde_json::Value) -> Option<PermissionAction> {
+        let scopes = self.skill_scopes.read().unwrap_or_else(|e| e.into_inner());
+        scopes
+            .iter()
+            .filter_map(|scope| scope.evaluate(tool_name, input))
+            .filter(|action| *action != PermissionAction::Allow)
+            .max_by_key(|action| restrictiveness(*action))
+    }
+
+    /// Explain why an active skill denies a tool call, if one does
+    pub fn skill_denial_reason(
+        &self,
+        tool_name: &str,
+        input: &serde_json::Value,
+    ) -> Option<String> {
+        let scopes = self.skill_scopes.read().unwrap_or_else(|e| e.into_inner());
+        scopes
+            .iter()
+            .find(|scope| scope.evaluate(tool_name, input) == Some(PermissionAction::Deny))
+            .map(|scope| scope.denial_reason(tool_name))
+    }
+
     /// Take the approval receiver for handling in UI
     pub fn take_approval_receiver(&mut self) -> Option<mpsc::Receiver<PendingApproval>> {
         self.approval_rx.take()
@@ -150,6 +279,24 @@ impl ToolOrchestrator {
         ctx: &ToolContext,
         options: RunOptions,
     ) -> Result<ToolOutput, ToolError> {
+        if let Some(ref delegation) = ctx.delegation {
+            if let Err(err) = delegation.check(tool_name) {
+                tracing::warn!(tool = %tool_name, agent = %delegation.agent, "tool_not_permitted");
+                return Err(err);
+            }
+        }
+
+        let skill_action = self.skill_action(tool_name, &input);
+        if skill_action == Some(PermissionAction::Deny) {
+            let reason = self
+                .skill_denial_reason(tool_name, &input)
+                .unwrap_or_else(|| format!("{} is restricted by an active skill", tool_name));
+            tracing::warn!(tool = %tool_name, reason = %reason, "skill_permission_denied");
+            return Err(ToolError::PermissionDenied { message: reason });
+        }
+
+        self.router.check_offline(tool_name)?;
+
         // Check if tool is a direct tool or provider-backed
         let direct_tool = self.router.get(tool_name);
 
@@ -200,7 +347,12 @@ impl ToolOrchestrator {
                 match requirement {
                     ApprovalRequirement::Skip { .. } => {}
                     ApprovalRequirement::NeedsApproval { reason } => {
-                        if !self.full_auto && !ctx.full_auto {
+                        if !self.full_auto
+                            && !ctx.full_auto
+                            && self
+                                .auto_approved_class(tool_name, &provider_input, ctx)
+                                .is_none()
+                        {
                             let path = Self::extract_path_from_input(&provider_input);
 
                             if let Some(ref cache) = self.approval_cache {
@@ -261,7 +413,8 @@ impl ToolOrchestrator {
                 }
             }
 
-            return self.router.dispatch(tool_name, provider_input, ctx).await;
+            let dispatch = self.router.dispatch(tool_name, provider_input, ctx);
+            return Self::tracking_writes(tool_name, ctx, dispatch).await;
         }
 
         let tool = direct_tool.unwrap();
@@ -297,7 +450,7 @@ impl ToolOrchestrator {
                         ),
                     });
                 }
-                PermissionAction::Allow => {
+                PermissionAction::Allow if skill_action != Some(PermissionAction::Ask) => {
                     // Permission explicitly allowed - skip approval flow
                     // (unless tool itself has a Forbidden requirement)
                     let requirement = tool.approval_requirement(&input);
@@ -314,7 +467,7 @@ impl ToolOrchestrator {
                         self.execute_with_sandbox(tool, input, ctx).await
                     };
                 }
-                PermissionAction::Ask => {
+                PermissionAction::Allow | PermissionAction::Ask => {
                     // Fall through to approval flow
                 }
             }
@@ -332,7 +485,10 @@ impl ToolOrchestrator {
                     }
                 }
                 ApprovalRequirement::NeedsApproval { reason } => {
-                    if !self.full_auto && !ctx.full_auto {
+                    if !self.full_auto
+                        && !ctx.full_auto
+                        && self.auto_approved_class(tool_name, &input, ctx).is_none()
+                    {
                         let path = Self::extract_path_from_input(&input);
 
                         if let Some(ref cache) = self.approval_cache {
@@ -432,13 +588,79 @@ impl ToolOrchestrator {
         }
     }
 
+    /// Capture a snapshot before a shell command that wouldn't run without
+    /// approval, so its effects can be rolled back with `uira snapshots restore`
+    pub async fn snapshot_if_risky(
+        &self,
+        tool_name: &str,
+        input: &serde_json::Value,
+        cwd: &std::path::Path,
+    ) {
+        let Some(max_snapshots) = self.snapshot_limit else {
+            return;
+        };
+        if tool_name != "Bash"
+            || !matches!(
+                self.approval_requirement_for(tool_name, input),
+                ApprovalRequirement::NeedsApproval { .. }
+            )
+        {
+            return;
+        }
+        let Some(store) = SnapshotStore::open(cwd) else {
+            return;
+        };
+
+        let command: String = input
+            .get("command")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default()
+            .chars()
+            .take(60)
+            .collect();
+        let label = format!(
+            "before Bash: {}",
+            command.lines().next().unwrap_or_default()
+        );
+        let store = store.with_max_snapshots(max_snapshots);
+        let captured = tokio::task::spawn_blocking(move || store.capture(&label))
+            .await
+            .unwrap_or_else(|e| Err(std::io::Error::other(e)));
+        match captured {
+            Ok(Some(snapshot)) => {
+                tracing::debug!(snapshot = %snapshot.id, "snapshot_before_risky_command")
+            }
+            Ok(None) => {}
+            Err(e) => tracing::warn!(error = %e, "Failed to snapshot before risky command"),
+        }
+    }
+
     async fn execute_with_sandbox(
         &self,
         tool: &BoxedTool,
         input: serde_json::Value,
         ctx: &ToolContext,
     ) -> Result<ToolOutput, ToolError> {
-        self.execute_with_retry(tool, input, ctx, 0).await
+        self.snapshot_if_risky(tool.name(), &input, &ctx.cwd).await;
+        let run = self.execute_with_retry(tool, input, ctx, 0);
+        Self::tracking_writes(tool.name(), ctx, run).await
+    }
+
+    /// Run a tool call, adopting the changes a file-writing tool makes to
+    /// files the agent has read, so the next Edit or Write doesn't take them
+    /// for the user's
+    async fn tracking_writes(
+        tool_name: &str,
+        ctx: &ToolContext,
+        run: impl std::future::Future<Output = Result<ToolOutput, ToolError>>,
+    ) -> Result<ToolOutput, ToolError> {
+        if !approval_policy::writes_files(tool_name) {
+            return run.await;
+        }
+        let fresh_files = ctx.file_tracker.fresh_files();
+        let result = run.await;
+        ctx.file_tracker.adopt_changes(&fresh_files);
+        result
     }
 
     fn execute_with_retry<'a>(
@@ -505,6 +727,11 @@ impl ToolOrchestrator {
             env: ctx.env.clone(),
             sandbox_type: sandbox,
             sandbox_policy: ctx.sandbox_policy.clone(),
+            delegation: ctx.delegation.clone(),
+            workspaces: ctx.workspaces.clone(),
+            file_tracker: ctx.file_tracker.clone(),
+            cancellation: ctx.cancellation.clone(),
+            session_cancellation: ctx.session_cancellation.clone(),
         };
         tool.execute(input, &sandboxed_ctx).await
     }
@@ -515,7 +742,8 @@ impl ToolOrchestrator {
         input: serde_json::Value,
         ctx: &ToolContext,
     ) -> Result<ToolOutput, ToolError> {
-        tool.execute(input, ctx).await
+        self.snapshot_if_risky(tool.name(), &input, &ctx.cwd).await;
+        Self::tracking_writes(tool.name(), ctx, tool.execute(input, ctx)).await
     }
 
     fn extract_path_from_input(input: &serde_json::Value) -> String {
@@ -596,7 +824,7 @@ impl ToolOrchestrator {
         tool_name: &str,
         input: &serde_json::Value,
     ) -> Option<PermissionAction> {
-        self.permission_evaluator.as_ref().map(|evaluator| {
+        let session_action = self.permission_evaluator.as_ref().map(|evaluator| {
             let result = evaluator.evaluate_tool(tool_name, input);
             tracing::debug!(
                 tool = %tool_name,
@@ -606,7 +834,14 @@ impl ToolOrchestrator {
                 "agent_permission_evaluated"
             );
             result.action
-        })
+        });
+
+        match (session_action, self.skill_action(tool_name, input)) {
+            (Some(session), Some(skill)) => Some(std::cmp::max_by_key(session, skill, |a| {
+                restrictiveness(*a)
+            })),
+            (session, skill) => session.or(skill),
+        }
     }
 
     /// Check approval cache for a prior decision (for Agent-level integration)
@@ -667,9 +902,19 @@ impl ToolOrchestrator {
     }
 }
 
+fn restrictiveness(action: PermissionAction) -> u8 {
+    match action {
+        PermissionAction::Allow => 0,
+        PermissionAction::Ask => 1,
+        PermissionAction::Deny => 2,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agents::DelegationContext;
+    use crate::features::builtin_skills::SkillPermissionRule;
     use crate::tools::FunctionTool;
     use uira_core::JsonSchema;
 
@@ -697,4 +942,187 @@ mod tests {
             .unwrap();
         assert_eq!(result.as_text(), Some("safe"));
     }
+
+    #[tokio::test]
+    async fn test_delegated_session_rejects_unlisted_tools() {
+        let router = create_test_router();
+        let orchestrator =
+            ToolOrchestrator::new(router, SandboxPolicy::full_access()).with_full_auto(true);
+        let ctx = ToolContext::default().with_delegation(DelegationContext::for_agent(
+            "worker",
+            Some(vec!["Read".to_string()]),
+        ));
+
+        let err = orchestrator
+            .run("safe_tool", serde_json::json!({}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, ToolError::NotPermitted { ref agent, .. } if agent == "worker"));
+    }
+
+    #[tokio::test]
+    async fn test_ask_rule_prompts_for_auto_approvable_calls() {
+        let mut router = ToolRouter::new();
+        router.register(crate::tools::builtins::BashTool::new());
+        let policy = ApprovalPolicy::new([ToolClass::ReadOnly]);
+        let input = serde_json::json!({ "command": "grep -rn needle src" });
+        let ctx = ToolContext::default();
+
+        let unruled = ToolOrchestrator::new(Arc::new(router), SandboxPolicy::full_access())
+            .with_approval_policy(policy.clone());
+        assert_eq!(
+            unruled.auto_approved_class("Bash", &input, &ctx),
+            Some(ToolClass::ReadOnly)
+        );
+
+        let evaluator = PermissionEvaluator::with_rules(vec![uira_security::PermissionRule::new(
+            "shell:execute",
+            "grep*",
+            PermissionAction::Ask,
+        )])
+        .unwrap();
+        let mut orchestrator = unruled.with_permission_evaluator(evaluator);
+        assert_eq!(orchestrator.auto_approved_class("Bash", &input, &ctx), None);
+
+        let mut approvals = orchestrator.take_approval_receiver().unwrap();
+        let run = orchestrator.run("Bash", input, &ctx);
+        let prompt = async {
+            let pending = tokio::time::timeout(std::time::Duration::from_secs(5), approvals.recv())
+                .await
+                .expect("expected an approval prompt")
+                .unwrap();
+            assert_eq!(pending.tool_name, "Bash");
+            pending
+                .response_tx
+                .send(ReviewDecision::Deny { reason: None })
+                .unwrap();
+        };
+        let (result, ()) = tokio::join!(run, prompt);
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_files_changed_by_write_tools_stay_fresh() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("lib.rs");
+        std::fs::write(&path, "fn a(){}").unwrap();
+
+        let mut router = ToolRouter::new();
+        let target = path.clone();
+        router.register(FunctionTool::new(
+            "lsp_format_range",
+            "Formats a file",
+            JsonSchema::object(),
+            move |_| {
+                let target = target.clone();
+                async move {
+                    std::fs::write(&target, "fn a() {}\n").unwrap();
+                    Ok(ToolOutput::text("formatted"))
+                }
+            },
+        ));
+        router.register(crate::tools::builtins::BashTool::new());
+        let orchestrator = ToolOrchestrator::new(Arc::new(router), SandboxPolicy::full_access())
+            .with_full_auto(true);
+        let ctx = ToolContext {
+            cwd: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+
+        ctx.file_tracker.record(&path, b"fn a(){}");
+        orchestrator
+            .run("lsp_format_range", serde_json::json!({}), &ctx)
+            .await
+            .unwrap();
+        assert!(ctx.file_tracker.check_fresh(&path).is_ok());
+        assert!(ctx.file_tracker.take_changed().is_empty());
+
+        orchestrator
+            .run(
+                "Bash",
+                serde_json::json!({ "command": "printf 'fn b() {}\\n' > lib.rs" }),
+                &ctx,
+            )
+            .await
+            .unwrap();
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), "fn b() {}\n");
+        assert!(ctx.file_tracker.check_fresh(&path).is_ok());
+
+        // Changes made outside any tool call are still the user's
+        std::fs::write(&path, "fn user() {}").unwrap();
+        assert!(ctx.file_tracker.check_fresh(&path).is_err());
+    }
+
+    #[test]
+    fn test_skill_allow_never_widens_session_permissions() {
+        let evaluator = PermissionEvaluator::with_rules(vec![uira_security::PermissionRule::new(
+            "file:write",
+            "**",
+            PermissionAction::Ask,
+        )])
+        .unwrap();
+        let orchestrator =
+            ToolOrchestrator::new(create_test_router(), SandboxPolicy::full_access())
+                .with_permission_evaluator(evaluator);
+        let docs_only = SkillPermissions {
+            allowed_tools: None,
+            permissions: vec![
+                SkillPermissionRule {
+                    permission: "file:write".to_string(),
+                    pattern: "**".to_string(),
+                    action: PermissionAction::Deny,
+                },
+                SkillPermissionRule {
+                    permission: "file:write".to_string(),
+                    pattern: "docs/**".to_string(),
+                    action: PermissionAction::Allow,
+                },
+            ],
+        };
+        orchestrator
+            .activate_skill("docs-writer", docs_only)
+            .unwrap();
+
+        let write = |path: &str| {
+            orchestrator.evaluate_permission("Write", &serde_json::json!({ "file_path": path }))
+        };
+        assert_eq!(write("docs/guide.md"), Some(PermissionAction::Ask));
+        assert_eq!(write("src/main.rs"), Some(PermissionAction::Deny));
+    }
+
+    #[tokio::test]
+    async fn test_skill_restrictions_apply_in_full_auto_and_revert() {
+        let router = create_test_router();
+        let orchestrator =
+            ToolOrchestrator::new(router, SandboxPolicy::full_access()).with_full_auto(true);
+        let ctx = ToolContext::default();
+        let restricted = SkillPermissions {
+            allowed_tools: Some(vec!["Read".to_string()]),
+            permissions: Vec::new(),
+        };
+
+        orchestrator
+            .activate_skill("docs-writer", restricted)
+            .unwrap();
+        assert_eq!(orchestrator.active_skills(), vec!["docs-writer"]);
+        assert_eq!(
+            orchestrator.evaluate_permission("safe_tool", &serde_json::json!({})),
+            Some(PermissionAction::Deny)
+        );
+        let err = orchestrator
+            .run("safe_tool", serde_json::json!({}), &ctx)
+            .await
+            .unwrap_err();
+        assert!(err.to_string().contains("docs-writer"));
+
+        assert!(orchestrator.deactivate_skill("docs-writer"));
+        assert_eq!(
+            orchestrator.evaluate_permission("safe_tool", &serde_json::json!({})),
+            None
+        );
+        assert!(orchestrator
+            .run("safe_tool", serde_json::json!({}), &ctx)
+            .await
+            .is_ok());
+    }
 }
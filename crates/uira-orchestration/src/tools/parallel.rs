@@ -2,6 +2,7 @@
 
 use futures::future::join_all;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::RwLock;
 use uira_core::ToolOutput;
 
@@ -95,6 +96,11 @@ impl ToolCallRuntime {
                         env: ctx.env.clone(),
                         sandbox_type: ctx.sandbox_type,
                         sandbox_policy: ctx.sandbox_policy.clone(),
+                        delegation: ctx.delegation.clone(),
+                        workspaces: ctx.workspaces.clone(),
+                        file_tracker: ctx.file_tracker.clone(),
+                        cancellation: ctx.cancellation.clone(),
+                        session_cancellation: ctx.session_cancellation.clone(),
                     };
                     tokio::spawn(async move {
                         if let Some(ref orch) = orchestrator {
@@ -135,13 +141,27 @@ impl ToolCallRuntime {
         calls: Vec<(String, String, serde_json::Value)>, // (id, name, input)
         ctx: &ToolContext,
     ) -> Vec<(String, Result<ToolOutput, ToolError>)> {
+        self.execute_batch_timed(calls, ctx)
+            .await
+            .into_iter()
+            .map(|(id, result, _)| (id, result))
+            .collect()
+    }
+
+    /// Like [`Self::execute_batch_with_ids`], but also reports how long each
+    /// call took to execute (excluding time spent waiting for the lock)
+    pub async fn execute_batch_timed(
+        &self,
+        calls: Vec<(String, String, serde_json::Value)>, // (id, name, input)
+        ctx: &ToolContext,
+    ) -> Vec<(String, Result<ToolOutput, ToolError>, Duration)> {
         let (parallel, sequential): (Vec<_>, Vec<_>) = calls
             .into_iter()
             .enumerate()
             .partition(|(_, (_, name, _))| self.router.tool_supports_parallel(name));
 
         let total_count = parallel.len() + sequential.len();
-        let mut indexed_results: Vec<(usize, String, Result<ToolOutput, ToolError>)> =
+        let mut indexed_results: Vec<(usize, String, Result<ToolOutput, ToolError>, Duration)> =
             Vec::with_capacity(total_count);
 
         if !parallel.is_empty() {
@@ -159,13 +179,20 @@ impl ToolCallRuntime {
                         env: ctx.env.clone(),
                         sandbox_type: ctx.sandbox_type,
                         sandbox_policy: ctx.sandbox_policy.clone(),
+                        delegation: ctx.delegation.clone(),
+                        workspaces: ctx.workspaces.clone(),
+                        file_tracker: ctx.file_tracker.clone(),
+                        cancellation: ctx.cancellation.clone(),
+                        session_cancellation: ctx.session_cancellation.clone(),
                     };
                     let handle = tokio::spawn(async move {
-                        if let Some(ref orch) = orchestrator {
+                        let started = Instant::now();
+                        let result = if let Some(ref orch) = orchestrator {
                             orch.run(&name, input, &ctx).await
                         } else {
                             router.dispatch(&name, input, &ctx).await
-                        }
+                        };
+                        (result, started.elapsed())
                     });
                     ((idx, id), handle)
                 })
@@ -173,26 +200,30 @@ impl ToolCallRuntime {
 
             let join_results = join_all(handles).await;
             for ((idx, id), join_result) in metadata.into_iter().zip(join_results) {
-                let result = join_result.unwrap_or_else(|e| {
-                    Err(ToolError::ExecutionFailed {
-                        message: format!("Task panicked: {}", e),
-                    })
+                let (result, elapsed) = join_result.unwrap_or_else(|e| {
+                    (
+                        Err(ToolError::ExecutionFailed {
+                            message: format!("Task panicked: {}", e),
+                        }),
+                        Duration::ZERO,
+                    )
                 });
-                indexed_results.push((idx, id, result));
+                indexed_results.push((idx, id, result, elapsed));
             }
         }
 
         for (idx, (id, name, input)) in sequential {
             let _guard = self.parallel_lock.write().await;
+            let started = Instant::now();
             let result = self.dispatch_tool(&name, input, ctx).await;
-            indexed_results.push((idx, id, result));
+            indexed_results.push((idx, id, result, started.elapsed()));
         }
 
-        indexed_results.sort_by_key(|(idx, _, _)| *idx);
+        indexed_results.sort_by_key(|(idx, _, _, _)| *idx);
 
         indexed_results
             .into_iter()
-            .map(|(_, id, result)| (id, result))
+            .map(|(_, id, result, elapsed)| (id, result, elapsed))
             .collect()
     }
 }
@@ -316,4 +347,29 @@ mod tests {
         assert_eq!(results[1].0, "id_1");
         assert_eq!(results[2].0, "id_2");
     }
+
+    #[tokio::test]
+    async fn test_batch_timed_reports_durations() {
+        let mut router = ToolRouter::new();
+        router.register(
+            FunctionTool::new("slow_tool", "Sleeps", JsonSchema::object(), |_| async {
+                tokio::time::sleep(Duration::from_millis(30)).await;
+                Ok(ToolOutput::text("done"))
+            })
+            .with_parallel(true),
+        );
+        let runtime = ToolCallRuntime::new(Arc::new(router));
+        let ctx = ToolContext::default();
+
+        let calls = vec![
+            ("id_0".to_string(), "slow_tool".to_string(), json!({})),
+            ("id_1".to_string(), "missing_tool".to_string(), json!({})),
+        ];
+        let results = runtime.execute_batch_timed(calls, &ctx).await;
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].1.is_ok());
+        assert!(results[0].2 >= Duration::from_millis(30));
+        assert!(results[1].1.is_err());
+    }
 }
@@ -2,6 +2,7 @@ use crate::agents::planning_pipeline::{PlanningPipeline, PlanningStage};
 use crate::tools::types::{ToolDefinition, ToolError, ToolInput, ToolOutput};
 use serde::Deserialize;
 use serde_json::json;
+use std::path::Path;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -10,7 +11,12 @@ static PLAN_COUNTER: AtomicU64 = AtomicU64::new(0);
 
 #[derive(Debug, Clone, Deserialize)]
 struct PlanningParams {
-    request: String,
+    #[serde(default)]
+    request: Option<String>,
+    #[serde(default)]
+    plan_id: Option<String>,
+    #[serde(default)]
+    stage_output: Option<String>,
 }
 
 fn analyst_stage_prompt(request: &str, pipeline_context: &str) -> String {
@@ -61,30 +67,128 @@ Output format: Structured markdown with sections for each point above and a fina
     )
 }
 
+/// Build the prompt for the pipeline's current stage with prior outputs substituted
+fn resolved_stage_prompt(pipeline: &PlanningPipeline) -> String {
+    let context = pipeline.build_stage_prompt();
+    let prompt = match pipeline.current_stage() {
+        PlanningStage::Analysis => analyst_stage_prompt(&pipeline.request, &context),
+        PlanningStage::Planning => planner_stage_prompt(&pipeline.request, &context),
+        PlanningStage::Review => critic_stage_prompt(&pipeline.request, &context),
+    };
+
+    let analyst_output = pipeline.stages.first().map(|s| s.content.as_str());
+    let planner_output = pipeline.stages.get(1).map(|s| s.content.as_str());
+    prompt
+        .replace("{{analyst_output}}", analyst_output.unwrap_or_default())
+        .replace("{{planner_output}}", planner_output.unwrap_or_default())
+}
+
+fn pipeline_status(directory: &Path, pipeline: &PlanningPipeline) -> serde_json::Value {
+    let completed: Vec<_> = pipeline
+        .stages
+        .iter()
+        .map(|s| {
+            json!({
+                "stage": s.stage.agent_type(),
+                "version": s.version,
+                "artifact": pipeline.artifact_path(directory, s).to_string_lossy(),
+            })
+        })
+        .collect();
+
+    let next_stage = if pipeline.is_complete() {
+        serde_json::Value::Null
+    } else {
+        let stage = pipeline.current_stage();
+        json!({
+            "stage": stage.agent_type(),
+            "prompt": resolved_stage_prompt(pipeline),
+        })
+    };
+
+    json!({
+        "plan_id": pipeline.id,
+        "request": pipeline.request,
+        "artifact_dir": pipeline.artifact_dir(directory).to_string_lossy(),
+        "completed_stages": completed,
+        "next_stage": next_stage,
+        "complete": pipeline.is_complete(),
+        "approved": pipeline.is_approved(),
+        "instructions": "Delegate next_stage.prompt to the named agent, then call planning_pipeline again with this plan_id and the agent's output as stage_output."
+    })
+}
+
+/// Load a persisted pipeline, optionally record the current stage's output, and report status
+fn handle_existing_pipeline(
+    directory: &Path,
+    plan_id: &str,
+    stage_output: Option<String>,
+) -> Result<ToolOutput, ToolError> {
+    if !PlanningPipeline::is_valid_id(plan_id) {
+        return Err(ToolError::InvalidInput {
+            message: format!("Invalid plan_id '{}'", plan_id),
+        });
+    }
+    let mut pipeline =
+        PlanningPipeline::load(directory, plan_id).ok_or_else(|| ToolError::NotFound {
+            name: format!("Planning pipeline not found: {}", plan_id),
+        })?;
+
+    if let Some(output) = stage_output {
+        if pipeline.is_complete() {
+            return Err(ToolError::InvalidInput {
+                message: format!("Planning pipeline {} is already complete", plan_id),
+            });
+        }
+        pipeline.record_output(output);
+        pipeline
+            .save(directory)
+            .map_err(|e| ToolError::ExecutionFailed {
+                message: format!("Failed to persist planning artifacts: {}", e),
+            })?;
+    }
+
+    let json_response = serde_json::to_string_pretty(&pipeline_status(directory, &pipeline))
+        .map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to serialize planning response: {}", e),
+        })?;
+
+    Ok(ToolOutput::text(json_response))
+}
+
 async fn handle_planning(input: ToolInput) -> Result<ToolOutput, ToolError> {
     let params: PlanningParams =
         serde_json::from_value(input).map_err(|e| ToolError::InvalidInput {
             message: format!("Failed to parse planning parameters: {}", e),
         })?;
 
-    if params.request.trim().is_empty() {
+    let directory = std::env::current_dir().map_err(|e| ToolError::ExecutionFailed {
+        message: format!("Failed to resolve working directory: {}", e),
+    })?;
+
+    if let Some(plan_id) = params.plan_id.as_deref() {
+        return handle_existing_pipeline(&directory, plan_id, params.stage_output);
+    }
+
+    let request = params.request.unwrap_or_default();
+    if request.trim().is_empty() {
         return Err(ToolError::InvalidInput {
             message: "Planning request cannot be empty".to_string(),
         });
     }
 
-    let mut temp_pipeline = PlanningPipeline::new(&params.request);
+    let mut temp_pipeline = PlanningPipeline::new(&request);
 
     let analyst_context = temp_pipeline.build_stage_prompt();
-    let analyst_prompt = analyst_stage_prompt(&params.request, &analyst_context);
+    let analyst_prompt = analyst_stage_prompt(&request, &analyst_context);
 
     temp_pipeline.record_output("{{analyst_output}}".to_string());
     let planner_context = temp_pipeline.build_stage_prompt();
-    let planner_prompt = planner_stage_prompt(&params.request, &planner_context);
+    let planner_prompt = planner_stage_prompt(&request, &planner_context);
 
     temp_pipeline.record_output("{{planner_output}}".to_string());
     let critic_context = temp_pipeline.build_stage_prompt();
-    let critic_prompt = critic_stage_prompt(&params.request, &critic_context);
+    let critic_prompt = critic_stage_prompt(&request, &critic_context);
 
     let ts = SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -93,11 +197,19 @@ async fn handle_planning(input: ToolInput) -> Result<ToolOutput, ToolError> {
         })?
         .as_millis();
     let seq = PLAN_COUNTER.fetch_add(1, Ordering::Relaxed);
-    let plan_id = format!("{}-{}", ts, seq);
+    let plan_id = format!("plan-{}-{}", ts, seq);
+
+    let pipeline = PlanningPipeline::with_id(&plan_id, &request);
+    let artifact_dir = pipeline
+        .save(&directory)
+        .map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to persist planning pipeline: {}", e),
+        })?;
 
     let response = json!({
         "plan_id": plan_id,
-        "request": params.request,
+        "request": request,
+        "artifact_dir": artifact_dir.to_string_lossy(),
         "stages": [
             {
                 "stage": PlanningStage::Analysis.agent_type(),
@@ -130,7 +242,7 @@ async fn handle_planning(input: ToolInput) -> Result<ToolOutput, ToolError> {
             "{{planner_output}}": "Replace with the planner stage's actual output before passing to critic"
         },
         "substitution_contract": "Stage prompts contain {{analyst_output}} and {{planner_output}} template variables. Before executing each stage, replace these placeholders with the actual output from the referenced prior stage.",
-        "instructions": "Execute each stage in order. Pass the output of each stage as context to the next by substituting the template variables. The final plan is the critic-reviewed planner output."
+        "instructions": "Execute each stage in order. Pass the output of each stage as context to the next by substituting the template variables. After each stage, call planning_pipeline with this plan_id and the stage's output as stage_output so the artifact is persisted and the run can be resumed. The final plan is the critic-reviewed planner output."
     });
 
     let json_response =
@@ -144,16 +256,23 @@ async fn handle_planning(input: ToolInput) -> Result<ToolOutput, ToolError> {
 pub fn tool_definition() -> ToolDefinition {
     ToolDefinition::new(
         "planning_pipeline",
-        "Initiate a structured planning pipeline (Analyst -> Planner -> Critic). Returns stage prompts for delegation to specialized agents. Use this when a task requires thorough planning before implementation.",
+        "Initiate a structured planning pipeline (Analyst -> Planner -> Critic). Returns stage prompts for delegation to specialized agents. Use this when a task requires thorough planning before implementation. Pass plan_id (and stage_output) to record a completed stage or resume an interrupted pipeline.",
         json!({
             "type": "object",
             "properties": {
                 "request": {
                     "type": "string",
                     "description": "The user request or task to create a plan for"
+                },
+                "plan_id": {
+                    "type": "string",
+                    "description": "Existing pipeline to record a stage for or resume (from .uira/plans/)"
+                },
+                "stage_output": {
+                    "type": "string",
+                    "description": "Output of the pipeline's current stage; persisted as a versioned artifact"
                 }
-            },
-            "required": ["request"]
+            }
         }),
         Arc::new(|input| Box::pin(handle_planning(input))),
     )
@@ -1,5 +1,10 @@
-//! AST tool provider - ast-grep based code search and replace
+//! AST tool provider - ast-grep based code search, replace and project rule
+//! linting, tree-sitter declaration outlines, plus JS/TS module graph
+//! extraction via uira-oxc
 
+use crate::tools::ast_grep::outline::{self, OUTLINE_LANGUAGES};
+use crate::tools::ast_grep::rules::RULES_DIR;
+use crate::tools::ast_grep::{apply_fixes, RulePack};
 use crate::tools::provider::ToolProvider;
 use crate::tools::{ToolContext, ToolError};
 use ast_grep_language::{LanguageExt, SupportLang};
@@ -10,6 +15,7 @@ use std::collections::HashSet;
 use std::fs;
 use std::path::PathBuf;
 use uira_core::{JsonSchema, ToolOutput, ToolSpec};
+use uira_oxc::ModuleGraph;
 use walkdir::WalkDir;
 
 fn get_extensions_for_lang(lang: &str) -> &'static [&'static str] {
@@ -366,6 +372,213 @@ impl AstToolProvider {
             serde_json::to_string_pretty(&output).unwrap_or_default(),
         ))
     }
+
+    fn ast_lint(
+        &self,
+        root_path: &std::path::Path,
+        input: &Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let fix = input["fix"].as_bool().unwrap_or(false);
+
+        if fix && ctx.sandbox_type != uira_security::SandboxType::None {
+            return Err(ToolError::SandboxDenied {
+                message: "ast_lint fix mode is not available in sandboxed sessions; run without fix or disable sandbox for this run".to_string(),
+                retryable: false,
+            });
+        }
+
+        let mut pack = RulePack::load(root_path).map_err(|e| ToolError::ExecutionFailed {
+            message: e.to_string(),
+        })?;
+        if let Some(ids) = input["rules"].as_array() {
+            let ids: Vec<String> = ids
+                .iter()
+                .filter_map(|id| id.as_str())
+                .map(String::from)
+                .collect();
+            if !ids.is_empty() {
+                pack.select(&ids).map_err(|e| ToolError::InvalidInput {
+                    message: e.to_string(),
+                })?;
+            }
+        }
+        if pack.is_empty() {
+            return Ok(ToolOutput::text(format!("No rules found in {}", RULES_DIR)));
+        }
+
+        let mut files = Vec::new();
+        let mut seen = HashSet::new();
+        for lang in pack.languages() {
+            let lang_name = lang.to_string().to_lowercase();
+            for file in self.collect_files(root_path, input, &lang_name)? {
+                if seen.insert(file.clone()) {
+                    files.push(file);
+                }
+            }
+        }
+
+        let mut results = Vec::new();
+        let mut fixes_applied = 0;
+        let mut files_modified = 0;
+
+        for file_path in files {
+            let relative = file_path.strip_prefix(root_path).unwrap_or(&file_path);
+            let content = match fs::read_to_string(&file_path) {
+                Ok(c) => c,
+                Err(_) => continue,
+            };
+
+            let findings = pack.scan(relative, &content);
+            for finding in &findings {
+                let mut result = json!({
+                    "file": relative.to_string_lossy(),
+                    "line": finding.line,
+                    "column": finding.column,
+                    "rule": finding.rule_id,
+                    "severity": finding.severity.to_string(),
+                    "message": finding.message,
+                    "text": finding.text,
+                });
+                if let Some(note) = &finding.note {
+                    result["note"] = json!(note);
+                }
+                if let Some(fix) = &finding.fix {
+                    result["fix"] = json!(fix.replacement);
+                }
+                results.push(result);
+            }
+
+            if fix {
+                let (new_content, applied) = apply_fixes(&content, &findings);
+                if applied > 0 {
+                    fs::write(&file_path, &new_content).map_err(|e| {
+                        ToolError::ExecutionFailed {
+                            message: format!("Failed to write {}: {}", file_path.display(), e),
+                        }
+                    })?;
+                    fixes_applied += applied;
+                    files_modified += 1;
+                }
+            }
+        }
+
+        if results.is_empty() {
+            return Ok(ToolOutput::text(format!(
+                "No findings from {} rules",
+                pack.rules.len()
+            )));
+        }
+
+        let mut summary = format!("{} findings from {} rules", results.len(), pack.rules.len());
+        if fix {
+            summary.push_str(&format!(
+                "; applied {} fixes in {} files",
+                fixes_applied, files_modified
+            ));
+        }
+
+        let output = json!({
+            "summary": summary,
+            "findings": results,
+        });
+
+        Ok(ToolOutput::text(
+            serde_json::to_string_pretty(&output).unwrap_or_default(),
+        ))
+    }
+
+    fn code_outline(
+        &self,
+        root_path: &std::path::Path,
+        input: &Value,
+    ) -> Result<ToolOutput, ToolError> {
+        let path = input["path"]
+            .as_str()
+            .ok_or_else(|| ToolError::InvalidInput {
+                message: "Missing 'path' parameter".to_string(),
+            })?;
+        let full_path = root_path.join(path);
+        if !full_path.is_file() || !Self::is_path_within_root(&full_path, root_path) {
+            return Err(ToolError::InvalidInput {
+                message: format!("Not a file in the workspace: {}", path),
+            });
+        }
+
+        let lang = match input["lang"].as_str() {
+            Some(lang_str) => {
+                lang_str
+                    .parse::<SupportLang>()
+                    .map_err(|_| ToolError::InvalidInput {
+                        message: format!("Unsupported language: {}", lang_str),
+                    })?
+            }
+            None => {
+                outline::outline_language(&full_path).ok_or_else(|| ToolError::InvalidInput {
+                    message: format!(
+                        "No outline support for {}; supported: {}",
+                        path,
+                        OUTLINE_LANGUAGES.join(", ")
+                    ),
+                })?
+            }
+        };
+
+        let content = fs::read_to_string(&full_path).map_err(|e| ToolError::ExecutionFailed {
+            message: format!("Failed to read {}: {}", path, e),
+        })?;
+        let items = outline::outline(lang, &content).ok_or_else(|| ToolError::InvalidInput {
+            message: format!(
+                "No outline support for {}; supported: {}",
+                lang,
+                OUTLINE_LANGUAGES.join(", ")
+            ),
+        })?;
+
+        if items.is_empty() {
+            return Ok(ToolOutput::text(format!(
+                "No declarations found in {}",
+                path
+            )));
+        }
+        Ok(ToolOutput::text(format!(
+            "{} ({} lines)\n{}",
+            path,
+            content.lines().count(),
+            outline::render(&items)
+        )))
+    }
+
+    fn ast_dependencies(
+        &self,
+        root_path: &std::path::Path,
+        input: &Value,
+    ) -> Result<ToolOutput, ToolError> {
+        let paths: Vec<String> = input["paths"]
+            .as_array()
+            .map(|paths| {
+                paths
+                    .iter()
+                    .filter_map(|p| p.as_str())
+                    .filter(|p| Self::validate_path_input(p))
+                    .map(String::from)
+                    .collect()
+            })
+            .unwrap_or_default();
+        let include_calls = input["includeCalls"].as_bool().unwrap_or(true);
+
+        let mut graph = ModuleGraph::build(root_path);
+        graph.retain_paths(&paths);
+        if !include_calls {
+            graph.calls.clear();
+        }
+
+        if graph.modules.is_empty() {
+            return Ok(ToolOutput::text("No JS/TS modules found"));
+        }
+
+        Ok(ToolOutput::text(graph.to_json()))
+    }
 }
 
 impl Default for AstToolProvider {
@@ -441,11 +654,69 @@ impl ToolProvider for AstToolProvider {
                     )
                     .required(&["pattern", "rewrite", "lang"]),
             ),
+            ToolSpec::new(
+                "ast_lint",
+                "Run the project's ast-grep YAML rules from .uira/rules and report (or fix) violations",
+                JsonSchema::object()
+                    .property(
+                        "rules",
+                        JsonSchema::array(JsonSchema::string())
+                            .description("Optional rule ids to run (default: all rules)"),
+                    )
+                    .property(
+                        "paths",
+                        JsonSchema::array(JsonSchema::string())
+                            .description("Optional paths to lint"),
+                    )
+                    .property(
+                        "globs",
+                        JsonSchema::array(JsonSchema::string())
+                            .description("Optional glob patterns"),
+                    )
+                    .property(
+                        "fix",
+                        JsonSchema::boolean()
+                            .description("Apply the rules' fix templates (default: false)"),
+                    ),
+            ),
+            ToolSpec::new(
+                "code_outline",
+                "List a file's functions, types, classes and methods with line ranges, parsed with tree-sitter (Rust, TypeScript/JavaScript, Python, Go). Cheaper than reading the whole file or starting a language server.",
+                JsonSchema::object()
+                    .property(
+                        "path",
+                        JsonSchema::string().description("File to outline, relative to the workspace"),
+                    )
+                    .property(
+                        "lang",
+                        JsonSchema::string()
+                            .description("Override the language detected from the extension"),
+                    )
+                    .required(&["path"]),
+            ),
+            ToolSpec::new(
+                "ast_dependencies",
+                "Extract the JS/TS module graph: import edges, exported symbols, and approximate call relationships",
+                JsonSchema::object()
+                    .property(
+                        "paths",
+                        JsonSchema::array(JsonSchema::string())
+                            .description("Optional paths to limit reported modules to"),
+                    )
+                    .property(
+                        "includeCalls",
+                        JsonSchema::boolean()
+                            .description("Include call relationships (default: true)"),
+                    ),
+            ),
         ]
     }
 
     fn handles(&self, name: &str) -> bool {
-        matches!(name, "ast_search" | "ast_replace")
+        matches!(
+            name,
+            "ast_search" | "ast_replace" | "ast_lint" | "code_outline" | "ast_dependencies"
+        )
     }
 
     async fn execute(
@@ -459,6 +730,9 @@ impl ToolProvider for AstToolProvider {
         match name {
             "ast_search" => self.ast_search(&root_path, &input),
             "ast_replace" => self.ast_replace(&root_path, &input, ctx),
+            "ast_lint" => self.ast_lint(&root_path, &input, ctx),
+            "code_outline" => self.code_outline(&root_path, &input),
+            "ast_dependencies" => self.ast_dependencies(&root_path, &input),
             _ => Err(ToolError::NotFound {
                 name: name.to_string(),
             }),
@@ -475,6 +749,9 @@ mod tests {
         let provider = AstToolProvider::new();
         assert!(provider.handles("ast_search"));
         assert!(provider.handles("ast_replace"));
+        assert!(provider.handles("ast_lint"));
+        assert!(provider.handles("code_outline"));
+        assert!(provider.handles("ast_dependencies"));
         assert!(!provider.handles("lsp_goto_definition"));
         assert!(!provider.handles("read_file"));
     }
@@ -483,9 +760,40 @@ mod tests {
     fn test_ast_provider_specs() {
         let provider = AstToolProvider::new();
         let specs = provider.specs();
-        assert_eq!(specs.len(), 2);
+        assert_eq!(specs.len(), 5);
         assert!(specs.iter().any(|s| s.name == "ast_search"));
         assert!(specs.iter().any(|s| s.name == "ast_replace"));
+        assert!(specs.iter().any(|s| s.name == "ast_lint"));
+        assert!(specs.iter().any(|s| s.name == "code_outline"));
+        assert!(specs.iter().any(|s| s.name == "ast_dependencies"));
+    }
+
+    #[test]
+    fn test_code_outline() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("lib.rs"),
+            "pub struct Config;\n\nimpl Config {\n    pub fn load() -> Self {\n        Config\n    }\n}\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("notes.txt"), "plain").unwrap();
+
+        let provider = AstToolProvider::new();
+        let output = provider
+            .code_outline(dir.path(), &json!({"path": "lib.rs"}))
+            .unwrap();
+        assert_eq!(
+            output.as_text().unwrap(),
+            "lib.rs (7 lines)\nstruct Config [1-1]\nimpl Config [3-7]\n  method load [4-6]\n"
+        );
+
+        let err = provider
+            .code_outline(dir.path(), &json!({"path": "notes.txt"}))
+            .unwrap_err();
+        assert!(err.to_string().contains("No outline support"));
+        assert!(provider
+            .code_outline(dir.path(), &json!({"path": "../etc/passwd"}))
+            .is_err());
     }
 
     #[test]
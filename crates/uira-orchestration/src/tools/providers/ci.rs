@@ -0,0 +1,108 @@
+//! CI tool provider - failing job logs from GitHub Actions or Buildkite,
+//! reduced to failure signatures for the build-fixer agent
+
+use crate::tools::provider::ToolProvider;
+use crate::tools::{ToolContext, ToolError};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use uira_core::schema::{CiSettings, CodeHostSettings};
+use uira_core::{JsonSchema, ToolOutput, ToolSpec};
+use uira_github::{connect_ci, current_branch, origin_url, tools, CiProvider, GitHubError};
+
+fn tool_error(err: GitHubError) -> ToolError {
+    match err {
+        GitHubError::InvalidInput(message) => ToolError::InvalidInput { message },
+        err => ToolError::ExecutionFailed {
+            message: err.to_string(),
+        },
+    }
+}
+
+/// Provider for `ci_fetch_logs`, connecting to the configured CI service on
+/// first use
+pub struct CiToolProvider {
+    settings: CiSettings,
+    code_host: CodeHostSettings,
+    client: Mutex<Option<Arc<dyn CiProvider>>>,
+}
+
+impl CiToolProvider {
+    pub fn new(settings: CiSettings, code_host: CodeHostSettings) -> Self {
+        Self {
+            settings,
+            code_host,
+            client: Mutex::new(None),
+        }
+    }
+
+    fn get_client(&self, remote_url: Option<&str>) -> Result<Arc<dyn CiProvider>, ToolError> {
+        let mut client = self.client.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(client) = client.as_ref() {
+            return Ok(client.clone());
+        }
+        let created =
+            connect_ci(&self.settings, &self.code_host, remote_url).map_err(tool_error)?;
+        *client = Some(created.clone());
+        Ok(created)
+    }
+}
+
+#[async_trait]
+impl ToolProvider for CiToolProvider {
+    fn specs(&self) -> Vec<ToolSpec> {
+        vec![ToolSpec::new(
+            "ci_fetch_logs",
+            "Download the logs of the failed jobs in the newest finished CI build (GitHub Actions or Buildkite) and extract each failure's error line, file locations and log excerpt, ready to hand to the build-fixer agent",
+            JsonSchema::object()
+                .property(
+                    "branch",
+                    JsonSchema::string()
+                        .description("Branch whose builds to read (default: the checked-out branch)"),
+                )
+                .property(
+                    "job",
+                    JsonSchema::string()
+                        .description("Only failed jobs whose name contains this text"),
+                ),
+        )]
+    }
+
+    fn handles(&self, name: &str) -> bool {
+        name == "ci_fetch_logs"
+    }
+
+    async fn execute(
+        &self,
+        name: &str,
+        input: Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput, ToolError> {
+        if name != "ci_fetch_logs" {
+            return Err(ToolError::NotFound {
+                name: name.to_string(),
+            });
+        }
+        let remote_url = origin_url(&ctx.cwd).ok();
+        let client = self.get_client(remote_url.as_deref())?;
+        let branch = current_branch(&ctx.cwd);
+        tools::ci_fetch_logs(client.as_ref(), branch.as_deref(), &input)
+            .await
+            .map(ToolOutput::text)
+            .map_err(tool_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_ci_provider_specs() {
+        let provider = CiToolProvider::new(CiSettings::default(), CodeHostSettings::default());
+        let specs = provider.specs();
+        assert_eq!(specs.len(), 1);
+        assert!(provider.handles(&specs[0].name));
+        assert!(!provider.handles("github_fetch_issue"));
+    }
+}
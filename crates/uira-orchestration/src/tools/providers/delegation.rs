@@ -1,9 +1,21 @@
 //! Delegation tool provider - enables subagent orchestration
 
+use crate::agents::definitions::get_agent_definitions;
+use crate::agents::tool_restrictions::DelegationContext;
+use crate::agents::types::{AgentCategory, AgentCost, AgentPromptMetadata};
 use crate::features::background_agent::{
-    get_background_manager, BackgroundManager, BackgroundTaskConfig, BackgroundTaskStatus,
-    LaunchInput,
+    get_background_manager, BackgroundManager, BackgroundTask, BackgroundTaskConfig,
+    BackgroundTaskStatus, LaunchInput, TaskLogger,
 };
+use crate::features::builtin_skills::{create_builtin_skills, get_builtin_skill};
+use crate::features::consensus::panel_for;
+use crate::features::dynamic_prompt_builder::{
+    rank_agents, rank_skills, AvailableAgent, AvailableSkill, REGISTRY_LOOKUP_TOOL,
+};
+use crate::features::model_routing::{
+    plan_route, resolve_model_override, RoutingConfigOverrides, RoutingContext, RoutingStrategy,
+};
+use crate::tools::delegation_cache::{workspace_tree_hash, DelegationCache, TaskFingerprint};
 use crate::tools::planning;
 use crate::tools::provider::ToolProvider;
 use crate::tools::{ToolContext, ToolError};
@@ -11,11 +23,21 @@ use async_trait::async_trait;
 use once_cell::sync::Lazy;
 use serde_json::{json, Value};
 use std::sync::Arc;
+use std::time::Duration;
+use tokio_util::sync::CancellationToken;
 use uira_core::load_config;
+use uira_core::schema::{OutputContract, UiraConfig};
 use uira_core::{JsonSchema, ToolOutput, ToolSpec};
 
-static BACKGROUND_MANAGER: Lazy<Arc<BackgroundManager>> =
-    Lazy::new(|| get_background_manager(BackgroundTaskConfig::default()));
+static BACKGROUND_MANAGER: Lazy<Arc<BackgroundManager>> = Lazy::new(|| {
+    let config = load_config(None)
+        .map(|config| BackgroundTaskConfig::from_settings(&config.background_tasks))
+        .unwrap_or_default();
+    get_background_manager(config)
+});
+
+/// Results of foreground delegations, shared by every session in the process
+static DELEGATION_CACHE: Lazy<DelegationCache> = Lazy::new(DelegationCache::new);
 
 pub struct DelegationToolProvider {
     agent_executor: Option<Arc<dyn AgentExecutor>>,
@@ -23,13 +45,129 @@ pub struct DelegationToolProvider {
 
 #[async_trait]
 pub trait AgentExecutor: Send + Sync {
+    /// Run a subagent until it finishes or `cancellation` fires
     async fn execute(
         &self,
         prompt: &str,
         model: &str,
-        allowed_tools: Option<Vec<String>>,
+        delegation: DelegationContext,
         max_turns: Option<usize>,
+        cancellation: CancellationToken,
     ) -> Result<String, String>;
+
+    /// Like `execute`, streaming the subagent's activity to a background task log
+    async fn execute_logged(
+        &self,
+        prompt: &str,
+        model: &str,
+        delegation: DelegationContext,
+        max_turns: Option<usize>,
+        cancellation: CancellationToken,
+        log: TaskLogger,
+    ) -> Result<String, String> {
+        let result = self
+            .execute(prompt, model, delegation, max_turns, cancellation)
+            .await;
+        if let Ok(ref output) = result {
+            log.output(&format!("{output}\n"));
+        }
+        result
+    }
+}
+
+/// Run a launched background task on `executor`, retrying failed attempts
+/// under the manager's retry policy before failing the task for good.
+/// Cancelling the task through the manager cancels `cancellation`, and
+/// cancelling `cancellation` cancels the task.
+#[allow(clippy::too_many_arguments)]
+pub async fn run_background_task(
+    manager: &BackgroundManager,
+    task_id: &str,
+    executor: &dyn AgentExecutor,
+    prompt: &str,
+    model: &str,
+    delegation: DelegationContext,
+    max_turns: Option<usize>,
+    cancellation: CancellationToken,
+) -> Option<BackgroundTask> {
+    manager.attach_cancellation(task_id, cancellation.clone());
+    loop {
+        let result = executor
+            .execute_logged(
+                prompt,
+                model,
+                delegation.clone(),
+                max_turns,
+                cancellation.clone(),
+                manager.task_logger(task_id),
+            )
+            .await;
+        if cancellation.is_cancelled() {
+            return manager.cancel_task(task_id);
+        }
+
+        match result {
+            Ok(output) => return manager.complete_task(task_id, output),
+            Err(error) => {
+                let Some(delay) = manager.retry_delay(task_id, &error) else {
+                    return manager.fail_task(task_id, error);
+                };
+                tokio::time::sleep(delay).await;
+
+                // Cancelled while waiting to retry
+                let task = manager.get_task(task_id)?;
+                if cancellation.is_cancelled() {
+                    return manager.cancel_task(task_id);
+                }
+                if task.status.is_terminal() {
+                    return Some(task);
+                }
+            }
+        }
+    }
+}
+
+/// Input schema of `delegate_task`'s `outputContract` argument
+fn output_contract_schema() -> JsonSchema {
+    let mut schema = JsonSchema::object()
+        .description("Format the agent's final message must follow; it is checked and sent back for one repair before the task fails. Defaults to the agent's configured contract")
+        .with_properties(json!({
+            "format": { "type": "string", "enum": ["freeform", "unified-diff", "json"] },
+            "schema": { "type": "object", "description": "JSON Schema the result must match (json format only)" }
+        }))
+        .required(&["format"]);
+    schema.additional_properties = None;
+    schema
+}
+
+/// Configured model for `agent`, else one picked by the routing strategy
+fn route_model(config: Option<&UiraConfig>, agent: &str, prompt: &str) -> (String, Option<String>) {
+    if let Some(model) = config
+        .and_then(|config| config.agents.agents.get(agent))
+        .and_then(|agent_config| agent_config.model.clone())
+    {
+        return (model, None);
+    }
+
+    let settings = config
+        .map(|config| config.routing.clone())
+        .unwrap_or_default();
+    if settings.strategy == RoutingStrategy::Fixed {
+        return (uira_core::DEFAULT_ANTHROPIC_MODEL.to_string(), None);
+    }
+
+    let plan = plan_route(
+        RoutingContext {
+            task_prompt: prompt.to_string(),
+            agent_type: Some(agent.to_string()),
+            ..RoutingContext::default()
+        },
+        RoutingConfigOverrides::from_settings(&settings),
+    );
+    (
+        plan.decision.model,
+        plan.fallback.map(|fallback| fallback.model),
+    )
 }
 
 impl DelegationToolProvider {
@@ -45,21 +183,19 @@ impl DelegationToolProvider {
         }
     }
 
-    fn resolve_model(&self, agent: &str, explicit_model: Option<&str>) -> String {
+    /// Model to run `agent` on, plus the model to escalate to when
+    /// speculative routing is unsure about the task
+    fn resolve_model(
+        &self,
+        config: Option<&UiraConfig>,
+        agent: &str,
+        explicit_model: Option<&str>,
+        prompt: &str,
+    ) -> (String, Option<String>) {
         if let Some(model) = explicit_model {
-            return model.to_string();
+            return (model.to_string(), None);
         }
-
-        load_config(None)
-            .ok()
-            .and_then(|config| {
-                config
-                    .agents
-                    .agents
-                    .get(agent)
-                    .and_then(|agent_config| agent_config.model.clone())
-            })
-            .unwrap_or_else(|| uira_core::DEFAULT_ANTHROPIC_MODEL.to_string())
+        route_model(config, agent, prompt)
     }
 
     fn format_completion_result(
@@ -107,15 +243,66 @@ impl DelegationToolProvider {
                 message: "Missing 'prompt' parameter".to_string(),
             })?;
 
-        let model = self.resolve_model(agent, args["model"].as_str());
+        let config = load_config(None).ok();
+        let routing = config
+            .as_ref()
+            .map(|config| config.routing.clone())
+            .unwrap_or_default();
+        let explicit_model =
+            resolve_model_override(&routing, args["provider"].as_str(), args["model"].as_str())
+                .map_err(|message| ToolError::InvalidInput { message })?;
+        let (model, escalation_model) =
+            self.resolve_model(config.as_ref(), agent, explicit_model.as_deref(), prompt);
         let description = args["description"].as_str().unwrap_or(prompt);
 
+        let skill = args["skill"]
+            .as_str()
+            .map(|name| {
+                get_builtin_skill(name).ok_or_else(|| ToolError::InvalidInput {
+                    message: format!("Unknown skill '{}'", name),
+                })
+            })
+            .transpose()?;
+        let consensus = config.as_ref().and_then(|config| {
+            panel_for(
+                config,
+                agent,
+                skill.as_ref().and_then(|skill| skill.strategy),
+                &model,
+            )
+        });
+        let skill_prompt = skill.map(|skill| {
+            format!(
+                "<skill name=\"{}\">\n{}\n</skill>\n\n{}",
+                skill.name, skill.template, prompt
+            )
+        });
+        let prompt = skill_prompt.as_deref().unwrap_or(prompt);
+
         let allowed_tools: Option<Vec<String>> = args["allowedTools"].as_array().map(|arr| {
             arr.iter()
                 .filter_map(|v| v.as_str().map(String::from))
                 .collect()
         });
 
+        let output_contract = match args.get("outputContract") {
+            Some(contract) => Some(
+                serde_json::from_value::<OutputContract>(contract.clone()).map_err(|e| {
+                    ToolError::InvalidInput {
+                        message: format!("Invalid 'outputContract': {}", e),
+                    }
+                })?,
+            ),
+            None => config
+                .as_ref()
+                .and_then(|config| config.agents.agents.get(agent))
+                .and_then(|agent_config| agent_config.output_contract.clone()),
+        };
+
+        let delegation = DelegationContext::for_agent(agent, allowed_tools)
+            .with_escalation_model(escalation_model)
+            .with_consensus(consensus)
+            .with_output_contract(output_contract);
         let max_turns: Option<usize> = args["maxTurns"].as_u64().map(|n| n as usize);
 
         if run_in_background {
@@ -139,27 +326,22 @@ impl DelegationToolProvider {
                 let executor = executor.clone();
                 let prompt_owned = prompt.to_string();
                 let model_owned = model.clone();
-                let allowed_tools_owned = allowed_tools.clone();
                 let max_turns_owned = max_turns;
+                // Outlives this turn but not the parent session
+                let cancellation = ctx.session_cancellation.child_token();
 
                 let handle = tokio::spawn(async move {
-                    let result = executor
-                        .execute(
-                            &prompt_owned,
-                            &model_owned,
-                            allowed_tools_owned,
-                            max_turns_owned,
-                        )
-                        .await;
-
-                    match result {
-                        Ok(output) => {
-                            BACKGROUND_MANAGER.complete_task(&task_id, output);
-                        }
-                        Err(e) => {
-                            BACKGROUND_MANAGER.fail_task(&task_id, e);
-                        }
-                    }
+                    run_background_task(
+                        &BACKGROUND_MANAGER,
+                        &task_id,
+                        executor.as_ref(),
+                        &prompt_owned,
+                        &model_owned,
+                        delegation,
+                        max_turns_owned,
+                        cancellation,
+                    )
+                    .await;
                 });
 
                 let task_id_watcher = task.id.clone();
@@ -190,9 +372,47 @@ impl DelegationToolProvider {
         } else {
             match &self.agent_executor {
                 Some(executor) => {
+                    let cache_settings = config
+                        .as_ref()
+                        .map(|config| config.delegation_cache.clone())
+                        .unwrap_or_default();
+                    let ttl = Duration::from_secs(cache_settings.ttl_secs);
+                    let fingerprint = if cache_settings.enabled
+                        && cache_settings.agents.iter().any(|name| name == agent)
+                    {
+                        let contract = delegation
+                            .output_contract
+                            .as_ref()
+                            .and_then(|contract| serde_json::to_string(contract).ok())
+                            .unwrap_or_default();
+                        workspace_tree_hash(&ctx.cwd).await.map(|tree_hash| {
+                            TaskFingerprint::new(
+                                agent,
+                                explicit_model.as_deref(),
+                                &format!("{}\n{}", prompt, contract),
+                                &tree_hash,
+                            )
+                        })
+                    } else {
+                        None
+                    };
+                    if let Some(cached) = fingerprint
+                        .as_ref()
+                        .and_then(|fingerprint| DELEGATION_CACHE.get(fingerprint, ttl))
+                    {
+                        tracing::debug!(agent, "Reusing cached delegation result");
+                        return Ok(ToolOutput::text(cached));
+                    }
+
                     let subagent_session_id = format!("sub_{}", uuid::Uuid::new_v4());
                     let result = executor
-                        .execute(prompt, &model, allowed_tools, max_turns)
+                        .execute(
+                            prompt,
+                            &model,
+                            delegation,
+                            max_turns,
+                            ctx.cancellation.clone(),
+                        )
                         .await;
                     match result {
                         Ok(output) => {
@@ -202,6 +422,9 @@ impl DelegationToolProvider {
                                 &output,
                                 &subagent_session_id,
                             );
+                            if let Some(fingerprint) = fingerprint {
+                                DELEGATION_CACHE.put(fingerprint, formatted.clone());
+                            }
                             Ok(ToolOutput::text(formatted))
                         }
                         Err(e) => Err(ToolError::ExecutionFailed { message: e }),
@@ -381,6 +604,87 @@ impl DelegationToolProvider {
         }
     }
 
+    /// Search the full agent and skill registry, most relevant first
+    async fn agent_lookup(&self, args: Value) -> Result<ToolOutput, ToolError> {
+        let query = args["query"].as_str().unwrap_or_default().trim();
+        let limit = args["limit"].as_u64().unwrap_or(10) as usize;
+
+        let mut definitions: Vec<_> = get_agent_definitions(None).into_values().collect();
+        definitions.sort_by(|a, b| a.name.cmp(&b.name));
+        // Agents without prompt metadata are ranked on name and description alone
+        let agents: Vec<AvailableAgent> = definitions
+            .iter()
+            .map(|def| AvailableAgent {
+                name: def.name.clone(),
+                description: def.description.clone(),
+                metadata: def.metadata.clone().unwrap_or(AgentPromptMetadata {
+                    category: AgentCategory::Specialist,
+                    cost: AgentCost::Cheap,
+                    prompt_alias: None,
+                    triggers: Vec::new(),
+                    use_when: Vec::new(),
+                    avoid_when: Vec::new(),
+                    prompt_description: None,
+                    tools: Vec::new(),
+                }),
+            })
+            .collect();
+        let skills: Vec<AvailableSkill> = create_builtin_skills()
+            .into_iter()
+            .map(|skill| AvailableSkill {
+                name: skill.name,
+                description: skill.description,
+            })
+            .collect();
+
+        // With a query, only entries that matched at least one keyword are returned
+        let keep = |score: usize| query.is_empty() || score > 0;
+
+        let agent_matches: Vec<Value> = rank_agents(&agents, query)
+            .into_iter()
+            .filter(|(_, score)| keep(*score))
+            .take(limit)
+            .map(|(index, _)| {
+                let def = &definitions[index];
+                let mut entry = json!({
+                    "name": def.name,
+                    "description": def.description,
+                    "tools": def.tools,
+                });
+                if let Some(meta) = &def.metadata {
+                    entry["category"] = json!(meta.category);
+                    entry["cost"] = json!(meta.cost);
+                    entry["useWhen"] = json!(meta.use_when);
+                    entry["avoidWhen"] = json!(meta.avoid_when);
+                    entry["triggers"] =
+                        json!(meta.triggers.iter().map(|t| &t.trigger).collect::<Vec<_>>());
+                }
+                entry
+            })
+            .collect();
+
+        let skill_matches: Vec<Value> = rank_skills(&skills, query)
+            .into_iter()
+            .filter(|(_, score)| keep(*score))
+            .take(limit)
+            .map(|(index, _)| {
+                json!({
+                    "name": skills[index].name,
+                    "description": skills[index].description,
+                })
+            })
+            .collect();
+
+        Ok(ToolOutput::text(
+            serde_json::to_string_pretty(&json!({
+                "query": query,
+                "agents": agent_matches,
+                "skills": skill_matches,
+            }))
+            .unwrap(),
+        ))
+    }
+
     async fn planning_pipeline(&self, args: Value) -> Result<ToolOutput, ToolError> {
         let tool = planning::tool_definition();
         let output = tool.handler.call(args).await?;
@@ -412,10 +716,14 @@ impl ToolProvider for DelegationToolProvider {
                 JsonSchema::object()
                     .property("agent", JsonSchema::string().description("Agent name (e.g., 'explore', 'architect', 'executor')"))
                     .property("prompt", JsonSchema::string().description("Task/prompt for the agent to execute"))
-                    .property("model", JsonSchema::string().description("Override model (e.g., 'claude-sonnet-4-20250514'). Uses agent default if not specified"))
+                    .property("description", JsonSchema::string().description("Short description of the task, shown in task listings. Defaults to the prompt"))
+                    .property("provider", JsonSchema::string().description("Provider for 'model' (e.g., 'openrouter', 'openai'), to run a cheap task on another provider's model"))
+                    .property("model", JsonSchema::string().description("Override model (e.g., 'claude-sonnet-4-20250514' or 'openai/gpt-4o-mini'). Must be allowed by the routing policy. Uses agent default if not specified"))
                     .property("allowedTools", JsonSchema::array(JsonSchema::string()).description("Tools to allow (e.g., ['Read', 'Glob']). Defaults to agent's configured tools"))
                     .property("maxTurns", JsonSchema::number().description("Maximum turns before stopping. Uses agent default (100) if not specified"))
                     .property("runInBackground", JsonSchema::boolean().description("If true, runs in background and returns task_id"))
+                    .property("skill", JsonSchema::string().description("Skill whose instructions the agent follows (e.g., 'git-master'). Skills may set the execution strategy"))
+                    .property("outputContract", output_contract_schema())
                     .required(&["agent", "prompt"]),
             ),
             ToolSpec::new(
@@ -434,6 +742,13 @@ impl ToolProvider for DelegationToolProvider {
                     .property("taskId", JsonSchema::string().description("Task ID to cancel"))
                     .property("all", JsonSchema::boolean().description("If true, cancels ALL running tasks")),
             ),
+            ToolSpec::new(
+                REGISTRY_LOOKUP_TOOL,
+                "Search all available agents and skills, including ones not listed in your instructions. Returns the most relevant matches first.",
+                JsonSchema::object()
+                    .property("query", JsonSchema::string().description("Keywords describing the task (e.g., 'database migration'). Omit to list everything"))
+                    .property("limit", JsonSchema::number().description("Maximum agents and skills to return. Default: 10")),
+            ),
             ToolSpec::new(
                 planning_tool.name,
                 planning_tool.description,
@@ -445,7 +760,11 @@ impl ToolProvider for DelegationToolProvider {
     fn handles(&self, name: &str) -> bool {
         matches!(
             name,
-            "delegate_task" | "background_output" | "background_cancel" | "planning_pipeline"
+            "delegate_task"
+                | "background_output"
+                | "background_cancel"
+                | "planning_pipeline"
+                | REGISTRY_LOOKUP_TOOL
         )
     }
 
@@ -460,6 +779,7 @@ impl ToolProvider for DelegationToolProvider {
             "background_output" => self.background_output(input).await,
             "background_cancel" => self.background_cancel(input).await,
             "planning_pipeline" => self.planning_pipeline(input).await,
+            REGISTRY_LOOKUP_TOOL => self.agent_lookup(input).await,
             _ => Err(ToolError::NotFound {
                 name: name.to_string(),
             }),
@@ -478,6 +798,7 @@ mod tests {
         assert!(provider.handles("background_output"));
         assert!(provider.handles("background_cancel"));
         assert!(provider.handles("planning_pipeline"));
+        assert!(provider.handles("agent_lookup"));
         assert!(!provider.handles("lsp_goto_definition"));
         assert!(!provider.handles("read_file"));
     }
@@ -486,10 +807,134 @@ mod tests {
     fn test_delegation_provider_specs() {
         let provider = DelegationToolProvider::new();
         let specs = provider.specs();
-        assert_eq!(specs.len(), 4);
+        assert_eq!(specs.len(), 5);
         assert!(specs.iter().any(|s| s.name == "delegate_task"));
         assert!(specs.iter().any(|s| s.name == "background_output"));
         assert!(specs.iter().any(|s| s.name == "background_cancel"));
         assert!(specs.iter().any(|s| s.name == "planning_pipeline"));
+        assert!(specs.iter().any(|s| s.name == "agent_lookup"));
+    }
+
+    #[test]
+    fn test_route_model_follows_strategy() {
+        let prompt = "find where auth is implemented";
+        let (model, fallback) = route_model(None, "explore", prompt);
+        assert_eq!(model, uira_core::DEFAULT_ANTHROPIC_MODEL);
+        assert!(fallback.is_none());
+
+        let mut config = UiraConfig::default();
+        config.routing.strategy = RoutingStrategy::Speculative;
+        config.routing.confidence_threshold = 1.0;
+        let (model, fallback) = route_model(Some(&config), "explore", prompt);
+        assert!(model.contains("haiku"));
+        assert!(fallback.unwrap().contains("sonnet"));
+
+        config.agents.agents.insert(
+            "explore".to_string(),
+            serde_json::from_value(json!({"model": "openai/gpt-4o-mini"})).unwrap(),
+        );
+        let (model, fallback) = route_model(Some(&config), "explore", prompt);
+        assert_eq!(model, "openai/gpt-4o-mini");
+        assert!(fallback.is_none());
+    }
+
+    struct CountingExecutor(std::sync::atomic::AtomicUsize);
+
+    #[async_trait]
+    impl AgentExecutor for CountingExecutor {
+        async fn execute(
+            &self,
+            _prompt: &str,
+            _model: &str,
+            delegation: DelegationContext,
+            _max_turns: Option<usize>,
+            _cancellation: CancellationToken,
+        ) -> Result<String, String> {
+            let run = self.0.fetch_add(1, std::sync::atomic::Ordering::SeqCst) + 1;
+            Ok(format!("{} run {}", delegation.agent, run))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_read_only_delegations_reuse_cached_results() {
+        let dir = tempfile::tempdir().unwrap();
+        let git = |args: &[&str]| {
+            std::process::Command::new("git")
+                .args(args)
+                .current_dir(dir.path())
+                .output()
+                .unwrap()
+        };
+        if !git(&["init", "-q"]).status.success() {
+            return;
+        }
+        git(&[
+            "-c",
+            "user.name=test",
+            "-c",
+            "user.email=test@example.com",
+            "commit",
+            "-q",
+            "--allow-empty",
+            "-m",
+            "init",
+        ]);
+
+        let executor = Arc::new(CountingExecutor(Default::default()));
+        let provider = DelegationToolProvider::with_executor(executor.clone());
+        let ctx = ToolContext {
+            cwd: dir.path().to_path_buf(),
+            ..Default::default()
+        };
+        let delegate = |agent: &str, prompt: &str| {
+            provider.delegate_task(json!({ "agent": agent, "prompt": prompt }), &ctx)
+        };
+        let text = |output: ToolOutput| match output.content.first() {
+            Some(uira_core::ToolOutputContent::Text { text }) => text.clone(),
+            _ => panic!("expected text output"),
+        };
+
+        let prompt = format!("Find where {} is defined", uuid::Uuid::new_v4());
+        let first = text(delegate("explore", &prompt).await.unwrap());
+        assert!(first.contains("explore run 1"));
+        let again = text(
+            delegate("explore", &format!("  {}\n", prompt))
+                .await
+                .unwrap(),
+        );
+        assert_eq!(again, first);
+        assert_eq!(executor.0.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        // Identifiers are case-sensitive, so a differently cased prompt is a new task
+        let recased = text(delegate("explore", &prompt.to_lowercase()).await.unwrap());
+        assert!(recased.contains("explore run 2"));
+
+        std::fs::write(dir.path().join("new.rs"), "fn x() {}\n").unwrap();
+        let changed = text(delegate("explore", &prompt).await.unwrap());
+        assert!(changed.contains("explore run 3"));
+
+        delegate("executor", &prompt).await.unwrap();
+        delegate("executor", &prompt).await.unwrap();
+        assert_eq!(executor.0.load(std::sync::atomic::Ordering::SeqCst), 5);
+    }
+
+    #[tokio::test]
+    async fn test_agent_lookup_ranks_matches() {
+        let provider = DelegationToolProvider::new();
+        let output = provider
+            .agent_lookup(json!({ "query": "security audit", "limit": 3 }))
+            .await
+            .unwrap();
+        let text = match output.content.first() {
+            Some(uira_core::ToolOutputContent::Text { text }) => text.clone(),
+            _ => panic!("expected text output"),
+        };
+        let value: Value = serde_json::from_str(&text).unwrap();
+        let agents = value["agents"].as_array().unwrap();
+        assert!(!agents.is_empty() && agents.len() <= 3);
+        assert!(agents[0]["name"]
+            .as_str()
+            .unwrap()
+            .starts_with("security-reviewer"));
     }
 }
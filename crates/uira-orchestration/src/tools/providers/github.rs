@@ -0,0 +1,219 @@
+//! Code host tool provider - issues, pull requests and reviews on GitHub,
+//! GitLab or Gitea
+
+use crate::tools::provider::ToolProvider;
+use crate::tools::{ToolContext, ToolError};
+use async_trait::async_trait;
+use serde_json::Value;
+use std::sync::{Arc, Mutex};
+use uira_core::schema::CodeHostSettings;
+use uira_core::{JsonSchema, ToolOutput, ToolSpec};
+use uira_github::{connect, origin_url, tools, CodeHost, GitHubError, RepoRef};
+
+fn tool_error(err: GitHubError) -> ToolError {
+    match err {
+        GitHubError::InvalidInput(message) => ToolError::InvalidInput { message },
+        err => ToolError::ExecutionFailed {
+            message: err.to_string(),
+        },
+    }
+}
+
+fn repo_property() -> JsonSchema {
+    JsonSchema::string()
+        .description("Repository as owner/name (default: the origin remote of the workspace)")
+}
+
+/// Provider for code host tools, connecting to the workspace's host on
+/// first use
+pub struct GitHubToolProvider {
+    settings: CodeHostSettings,
+    client: Mutex<Option<Arc<dyn CodeHost>>>,
+}
+
+impl GitHubToolProvider {
+    pub fn new(settings: CodeHostSettings) -> Self {
+        Self {
+            settings,
+            client: Mutex::new(None),
+        }
+    }
+
+    /// Get or create the client for the host of the `origin` remote,
+    /// keeping it once the token resolves
+    fn get_client(&self, remote_url: Option<&str>) -> Result<Arc<dyn CodeHost>, ToolError> {
+        let mut client = self.client.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(client) = client.as_ref() {
+            return Ok(client.clone());
+        }
+        let created = connect(&self.settings, remote_url).map_err(tool_error)?;
+        *client = Some(created.clone());
+        Ok(created)
+    }
+}
+
+#[async_trait]
+impl ToolProvider for GitHubToolProvider {
+    fn specs(&self) -> Vec<ToolSpec> {
+        vec![
+            ToolSpec::new(
+                "github_fetch_issue",
+                "Fetch an issue or pull request (GitLab merge request) from GitHub, GitLab or Gitea with its description and comments; GitLab issues are tried before merge requests with the same number",
+                JsonSchema::object()
+                    .property("repo", repo_property())
+                    .property(
+                        "number",
+                        JsonSchema::number().description("Issue or pull request number"),
+                    )
+                    .property(
+                        "includeComments",
+                        JsonSchema::boolean().description("Include the comments (default true)"),
+                    )
+                    .required(&["number"]),
+            ),
+            ToolSpec::new(
+                "github_list_pr_comments",
+                "List the conversation and inline review comments on a pull request or GitLab merge request",
+                JsonSchema::object()
+                    .property("repo", repo_property())
+                    .property(
+                        "number",
+                        JsonSchema::number().description("Pull request number"),
+                    )
+                    .required(&["number"]),
+            ),
+            ToolSpec::new(
+                "github_create_pr",
+                "Open a pull request (GitLab merge request) from a branch that is already pushed",
+                JsonSchema::object()
+                    .property("repo", repo_property())
+                    .property(
+                        "title",
+                        JsonSchema::string().description("Pull request title"),
+                    )
+                    .property(
+                        "body",
+                        JsonSchema::string().description("Pull request description in Markdown"),
+                    )
+                    .property(
+                        "head",
+                        JsonSchema::string()
+                            .description("Branch with the changes (owner:branch for forks)"),
+                    )
+                    .property(
+                        "base",
+                        JsonSchema::string().description("Branch to merge into"),
+                    )
+                    .property(
+                        "draft",
+                        JsonSchema::boolean().description("Open as a draft (default false)"),
+                    )
+                    .required(&["title", "head", "base"]),
+            ),
+            ToolSpec::new(
+                "github_post_review",
+                "Post a review on a pull request or GitLab merge request, optionally with comments on changed lines",
+                JsonSchema::object()
+                    .property("repo", repo_property())
+                    .property(
+                        "number",
+                        JsonSchema::number().description("Pull request number"),
+                    )
+                    .property("body", JsonSchema::string().description("Review summary"))
+                    .property(
+                        "event",
+                        JsonSchema::string()
+                            .description("comment (default), approve or request_changes"),
+                    )
+                    .property(
+                        "comments",
+                        JsonSchema::array(
+                            JsonSchema::object()
+                                .property(
+                                    "path",
+                                    JsonSchema::string()
+                                        .description("File path relative to the repository"),
+                                )
+                                .property(
+                                    "line",
+                                    JsonSchema::number()
+                                        .description("Line in the new version of the file"),
+                                )
+                                .property("body", JsonSchema::string().description("Comment"))
+                                .required(&["path", "line", "body"]),
+                        )
+                        .description("Comments on lines of the diff"),
+                    )
+                    .required(&["number"]),
+            ),
+        ]
+    }
+
+    fn handles(&self, name: &str) -> bool {
+        matches!(
+            name,
+            "github_fetch_issue"
+                | "github_list_pr_comments"
+                | "github_create_pr"
+                | "github_post_review"
+        )
+    }
+
+    async fn execute(
+        &self,
+        name: &str,
+        input: Value,
+        ctx: &ToolContext,
+    ) -> Result<ToolOutput, ToolError> {
+        let remote_url = origin_url(&ctx.cwd).ok();
+        let workspace_repo = remote_url.as_deref().and_then(RepoRef::from_remote_url);
+        let repo = tools::input_repo(&input, workspace_repo.as_ref()).map_err(tool_error)?;
+        let client = self.get_client(remote_url.as_deref())?;
+
+        let result = match name {
+            "github_fetch_issue" => tools::fetch_issue(client.as_ref(), &repo, &input).await,
+            "github_list_pr_comments" => {
+                tools::list_pr_comments(client.as_ref(), &repo, &input).await
+            }
+            "github_create_pr" => tools::create_pr(client.as_ref(), &repo, &input).await,
+            "github_post_review" => tools::post_review(client.as_ref(), &repo, &input).await,
+            _ => {
+                return Err(ToolError::NotFound {
+                    name: name.to_string(),
+                })
+            }
+        };
+        result.map(ToolOutput::text).map_err(tool_error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_github_provider_specs() {
+        let provider = GitHubToolProvider::new(CodeHostSettings::default());
+        let specs = provider.specs();
+        assert_eq!(specs.len(), 4);
+        for spec in &specs {
+            assert!(provider.handles(&spec.name));
+        }
+        assert!(!provider.handles("lsp_hover"));
+    }
+
+    #[test]
+    fn test_github_errors_map_to_tool_errors() {
+        assert!(matches!(
+            tool_error(GitHubError::InvalidInput("missing 'number'".to_string())),
+            ToolError::InvalidInput { .. }
+        ));
+        assert!(matches!(
+            tool_error(GitHubError::MissingToken {
+                host: "GitHub",
+                hint: "set GH_TOKEN",
+            }),
+            ToolError::ExecutionFailed { .. }
+        ));
+    }
+}
@@ -137,6 +137,30 @@ impl ToolProvider for LspToolProvider {
                     )
                     .required(&["filePath"]),
             ),
+            ToolSpec::new(
+                "lsp_workspace_diagnostics",
+                "Summarize diagnostics across open files, or files matching a glob, using LSP",
+                JsonSchema::object()
+                    .property(
+                        "glob",
+                        JsonSchema::string()
+                            .description("Files to check relative to the workspace (e.g. src/**/*.rs); defaults to open files"),
+                    )
+                    .property(
+                        "severity",
+                        JsonSchema::string()
+                            .description("Minimum severity: error, warning, information, hint or all"),
+                    )
+                    .property(
+                        "limit",
+                        JsonSchema::number().description("Maximum diagnostics to list (default 50)"),
+                    )
+                    .property(
+                        "timeoutSecs",
+                        JsonSchema::number()
+                            .description("Seconds to wait for servers to report (default 5)"),
+                    ),
+            ),
             ToolSpec::new(
                 "lsp_hover",
                 "Get hover information for a symbol using LSP",
@@ -195,6 +219,104 @@ impl ToolProvider for LspToolProvider {
                     )
                     .required(&["filePath", "line", "character", "newName"]),
             ),
+            ToolSpec::new(
+                "lsp_code_actions",
+                "List code actions (quick fixes, refactors) at a position using LSP, or apply one by index",
+                JsonSchema::object()
+                    .property(
+                        "filePath",
+                        JsonSchema::string().description("Path to the file"),
+                    )
+                    .property(
+                        "line",
+                        JsonSchema::number().description("Line number (1-indexed)"),
+                    )
+                    .property(
+                        "character",
+                        JsonSchema::number().description("Character position (0-indexed)"),
+                    )
+                    .property(
+                        "endLine",
+                        JsonSchema::number().description("End line of the range (1-indexed)"),
+                    )
+                    .property(
+                        "endCharacter",
+                        JsonSchema::number().description("End character of the range (0-indexed)"),
+                    )
+                    .property(
+                        "apply",
+                        JsonSchema::number()
+                            .description("Index of the action to apply, as listed without it"),
+                    )
+                    .required(&["filePath", "line", "character"]),
+            ),
+            ToolSpec::new(
+                "lsp_format_range",
+                "Format a line range (or the whole file) using LSP",
+                JsonSchema::object()
+                    .property(
+                        "filePath",
+                        JsonSchema::string().description("Path to the file"),
+                    )
+                    .property(
+                        "startLine",
+                        JsonSchema::number().description("First line to format (1-indexed)"),
+                    )
+                    .property(
+                        "endLine",
+                        JsonSchema::number()
+                            .description("Last line to format (1-indexed, inclusive)"),
+                    )
+                    .property(
+                        "tabSize",
+                        JsonSchema::number().description("Spaces per indent level (default 4)"),
+                    )
+                    .property(
+                        "insertSpaces",
+                        JsonSchema::boolean().description("Indent with spaces (default true)"),
+                    )
+                    .required(&["filePath"]),
+            ),
+            ToolSpec::new(
+                "lsp_call_hierarchy",
+                "List incoming and/or outgoing calls of the function at a position using LSP",
+                JsonSchema::object()
+                    .property(
+                        "filePath",
+                        JsonSchema::string().description("Path to the file"),
+                    )
+                    .property(
+                        "line",
+                        JsonSchema::number().description("Line number (1-indexed)"),
+                    )
+                    .property(
+                        "character",
+                        JsonSchema::number().description("Character position (0-indexed)"),
+                    )
+                    .property(
+                        "direction",
+                        JsonSchema::string().description("incoming, outgoing or both (default)"),
+                    )
+                    .required(&["filePath", "line", "character"]),
+            ),
+            ToolSpec::new(
+                "lsp_implementations",
+                "Find implementations of a trait, interface or abstract method using LSP",
+                JsonSchema::object()
+                    .property(
+                        "filePath",
+                        JsonSchema::string().description("Path to the file"),
+                    )
+                    .property(
+                        "line",
+                        JsonSchema::number().description("Line number (1-indexed)"),
+                    )
+                    .property(
+                        "character",
+                        JsonSchema::number().description("Character position (0-indexed)"),
+                    )
+                    .required(&["filePath", "line", "character"]),
+            ),
         ]
     }
 
@@ -205,9 +327,14 @@ impl ToolProvider for LspToolProvider {
                 | "lsp_find_references"
                 | "lsp_symbols"
                 | "lsp_diagnostics"
+                | "lsp_workspace_diagnostics"
                 | "lsp_hover"
                 | "lsp_prepare_rename"
                 | "lsp_rename"
+                | "lsp_code_actions"
+                | "lsp_format_range"
+                | "lsp_call_hierarchy"
+                | "lsp_implementations"
         )
     }
 
@@ -224,9 +351,14 @@ impl ToolProvider for LspToolProvider {
             "lsp_find_references" => client.find_references(input).await,
             "lsp_symbols" => client.symbols(input).await,
             "lsp_diagnostics" => client.diagnostics(input).await,
+            "lsp_workspace_diagnostics" => client.workspace_diagnostics(input).await,
             "lsp_hover" => client.hover(input).await,
             "lsp_prepare_rename" => client.prepare_rename(input).await,
             "lsp_rename" => client.rename(input).await,
+            "lsp_code_actions" => client.code_actions(input).await,
+            "lsp_format_range" => client.format_range(input).await,
+            "lsp_call_hierarchy" => client.call_hierarchy(input).await,
+            "lsp_implementations" => client.implementations(input).await,
             _ => {
                 return Err(ToolError::NotFound {
                     name: name.to_string(),
@@ -256,13 +388,18 @@ mod tests {
     fn test_lsp_provider_specs() {
         let provider = LspToolProvider::new();
         let specs = provider.specs();
-        assert_eq!(specs.len(), 7);
+        assert_eq!(specs.len(), 12);
         assert!(specs.iter().any(|s| s.name == "lsp_goto_definition"));
         assert!(specs.iter().any(|s| s.name == "lsp_find_references"));
         assert!(specs.iter().any(|s| s.name == "lsp_symbols"));
         assert!(specs.iter().any(|s| s.name == "lsp_diagnostics"));
+        assert!(specs.iter().any(|s| s.name == "lsp_workspace_diagnostics"));
         assert!(specs.iter().any(|s| s.name == "lsp_hover"));
         assert!(specs.iter().any(|s| s.name == "lsp_prepare_rename"));
         assert!(specs.iter().any(|s| s.name == "lsp_rename"));
+        assert!(specs.iter().any(|s| s.name == "lsp_code_actions"));
+        assert!(specs.iter().any(|s| s.name == "lsp_format_range"));
+        assert!(specs.iter().any(|s| s.name == "lsp_call_hierarchy"));
+        assert!(specs.iter().any(|s| s.name == "lsp_implementations"));
     }
 }
@@ -85,7 +85,13 @@ impl ToolProvider for McpToolProvider {
 
         let result = self
             .runtime
-            .call_tool(&route.server_name, &route.tool_name, input, &ctx.cwd)
+            .call_tool(
+                &route.server_name,
+                &route.tool_name,
+                input,
+                &ctx.cwd,
+                &ctx.cancellation,
+            )
             .await
             .map_err(|e| ToolError::ExecutionFailed {
                 message: e.to_string(),
@@ -1,9 +1,13 @@
 pub mod ast;
+pub mod ci;
 pub mod delegation;
+pub mod github;
 pub mod lsp;
 pub mod mcp;
 
 pub use ast::AstToolProvider;
-pub use delegation::{AgentExecutor, DelegationToolProvider};
+pub use ci::CiToolProvider;
+pub use delegation::{run_background_task, AgentExecutor, DelegationToolProvider};
+pub use github::GitHubToolProvider;
 pub use lsp::LspToolProvider;
 pub use mcp::McpToolProvider;
@@ -11,6 +11,8 @@ use crate::tools::{BoxedTool, Tool, ToolContext, ToolError};
 pub struct ToolRouter {
     tools: HashMap<String, BoxedTool>,
     providers: Vec<Arc<dyn ToolProvider>>,
+    /// Hide and refuse tools that need the network
+    offline: bool,
 }
 
 impl ToolRouter {
@@ -18,6 +20,30 @@ impl ToolRouter {
         Self {
             tools: HashMap::new(),
             providers: Vec::new(),
+            offline: false,
+        }
+    }
+
+    /// Enable offline mode: network tools drop out of [`Self::specs`] and
+    /// calls to them fail with [`ToolError::UnavailableOffline`]
+    pub fn set_offline(&mut self, offline: bool) {
+        self.offline = offline;
+    }
+
+    pub fn is_offline(&self) -> bool {
+        self.offline
+    }
+
+    /// Fail with [`ToolError::UnavailableOffline`] if `name` needs the
+    /// network and the router is offline
+    pub fn check_offline(&self, name: &str) -> Result<(), ToolError> {
+        match self.tools.get(name) {
+            Some(tool) if self.offline && tool.requires_network() => {
+                Err(ToolError::UnavailableOffline {
+                    name: name.to_string(),
+                })
+            }
+            _ => Ok(()),
         }
     }
 
@@ -63,6 +89,12 @@ impl ToolRouter {
         input: serde_json::Value,
         ctx: &ToolContext,
     ) -> Result<ToolOutput, ToolError> {
+        if let Some(ref delegation) = ctx.delegation {
+            delegation.check(name)?;
+        }
+
+        self.check_offline(name)?;
+
         // First, try direct tools
         if let Some(tool) = self.tools.get(name) {
             return tool.execute(input, ctx).await;
@@ -102,6 +134,7 @@ impl ToolRouter {
         let mut specs: Vec<uira_core::ToolSpec> = self
             .tools
             .values()
+            .filter(|t| !(self.offline && t.requires_network()))
             .map(|t| uira_core::ToolSpec::new(t.name(), t.description(), t.schema()))
             .collect();
 
@@ -123,6 +156,7 @@ impl Default for ToolRouter {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::agents::DelegationContext;
     use crate::tools::FunctionTool;
     use serde_json::json;
     use uira_core::JsonSchema;
@@ -159,4 +193,58 @@ mod tests {
             .unwrap_err();
         assert!(matches!(err, ToolError::NotFound { .. }));
     }
+
+    #[tokio::test]
+    async fn test_router_enforces_delegated_agent_tools() {
+        let mut router = ToolRouter::new();
+        for name in ["Read", "Bash"] {
+            router.register(FunctionTool::new(
+                name,
+                "Test tool",
+                JsonSchema::object(),
+                |_input: serde_json::Value| async move { Ok(ToolOutput::text("ok")) },
+            ));
+        }
+
+        let ctx =
+            ToolContext::default().with_delegation(DelegationContext::for_agent("explore", None));
+        assert!(router.dispatch("Read", json!({}), &ctx).await.is_ok());
+
+        let err = router.dispatch("Bash", json!({}), &ctx).await.unwrap_err();
+        assert_eq!(
+            err,
+            ToolError::NotPermitted {
+                tool: "Bash".to_string(),
+                agent: "explore".to_string(),
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_router_offline_hides_and_refuses_network_tools() {
+        let mut router = ToolRouter::new();
+        crate::tools::builtins::register_builtins(&mut router);
+        router.set_offline(true);
+
+        let specs = router.specs();
+        assert!(specs.iter().any(|spec| spec.name == "Read"));
+        assert!(!specs.iter().any(|spec| spec.name == "web_search"));
+        assert!(!specs.iter().any(|spec| spec.name == "fetch_url"));
+
+        let err = router
+            .dispatch(
+                "web_search",
+                json!({"query": "rust"}),
+                &ToolContext::default(),
+            )
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err,
+            ToolError::UnavailableOffline {
+                name: "web_search".to_string()
+            }
+        );
+        assert_eq!(err.to_string(), "web_search is unavailable offline");
+    }
 }
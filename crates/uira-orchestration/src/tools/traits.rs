@@ -5,10 +5,14 @@ use serde_json::Value;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use tokio_util::sync::CancellationToken;
 use uira_core::{ApprovalRequirement, JsonSchema, SandboxPreference, ToolOutput};
 use uira_memory::MemorySystem;
 use uira_security::{SandboxPolicy, SandboxType};
 
+use crate::agents::DelegationContext;
+use crate::tools::file_tracker::FileTracker;
+use crate::tools::workspace::{self, WorkspaceRoot};
 use crate::tools::ToolError;
 
 /// Context passed to tool execution
@@ -20,6 +24,18 @@ pub struct ToolContext {
     pub env: std::collections::HashMap<String, String>,
     pub sandbox_type: SandboxType,
     pub sandbox_policy: SandboxPolicy,
+    /// Set when running inside a delegated subagent session
+    pub delegation: Option<DelegationContext>,
+    /// Extra roots addressable as `@<name>/...`
+    pub workspaces: Vec<WorkspaceRoot>,
+    /// Files read this session, for stale-read detection
+    pub file_tracker: FileTracker,
+    /// Cancelled when the turn is interrupted; tools stop their processes,
+    /// requests and subagents
+    pub cancellation: CancellationToken,
+    /// Cancelled when the session shuts down, for work that outlives the
+    /// turn such as background tasks
+    pub session_cancellation: CancellationToken,
 }
 
 impl Default for ToolContext {
@@ -32,6 +48,11 @@ impl Default for ToolContext {
             env: std::collections::HashMap::new(),
             sandbox_type: SandboxType::None,
             sandbox_policy: SandboxPolicy::default(),
+            delegation: None,
+            workspaces: Vec::new(),
+            file_tracker: FileTracker::new(),
+            cancellation: CancellationToken::new(),
+            session_cancellation: CancellationToken::new(),
         }
     }
 }
@@ -41,6 +62,56 @@ impl ToolContext {
         self.sandbox_type = sandbox_type;
         self
     }
+
+    pub fn with_delegation(mut self, delegation: DelegationContext) -> Self {
+        self.delegation = Some(delegation);
+        self
+    }
+
+    pub fn with_cancellation(
+        mut self,
+        turn: CancellationToken,
+        session: CancellationToken,
+    ) -> Self {
+        self.cancellation = turn;
+        self.session_cancellation = session;
+        self
+    }
+
+    pub fn with_workspaces(mut self, workspaces: Vec<WorkspaceRoot>) -> Self {
+        self.workspaces = workspaces;
+        self
+    }
+
+    /// Resolve a path argument against the workspace roots and `cwd`
+    pub fn resolve_path(&self, raw: &str) -> std::path::PathBuf {
+        workspace::resolve_path(raw, &self.cwd, &self.workspaces)
+    }
+
+    /// The sandbox policy of the innermost root containing `path`, else the
+    /// session policy
+    pub fn sandbox_policy_for(&self, path: &std::path::Path) -> &SandboxPolicy {
+        workspace::root_for(path, &self.workspaces)
+            .and_then(|root| root.sandbox_policy.as_ref())
+            .unwrap_or(&self.sandbox_policy)
+    }
+
+    /// Reject writes into a root whose own policy forbids them
+    pub fn check_writable(&self, path: &std::path::Path) -> Result<(), ToolError> {
+        let Some(root) = workspace::root_for(path, &self.workspaces) else {
+            return Ok(());
+        };
+        match &root.sandbox_policy {
+            Some(policy) if !policy.allows_write(path) => Err(ToolError::PermissionDenied {
+                message: format!(
+                    "Workspace @{} does not allow writing to {}",
+                    root.name,
+                    path.display()
+                ),
+            }),
+            _ => Ok(()),
+        }
+    }
 }
 
 /// The core Tool trait for implementing tools
@@ -78,6 +149,12 @@ pub trait Tool: Send + Sync {
     fn escalate_on_failure(&self) -> bool {
         false
     }
+
+    /// Whether the tool only works with network access; such tools are
+    /// hidden and refused in offline mode
+    fn requires_network(&self) -> bool {
+        false
+    }
 }
 
 /// A boxed tool for dynamic dispatch
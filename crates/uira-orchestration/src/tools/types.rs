@@ -4,6 +4,7 @@ use std::fmt;
 use std::future::Future;
 use std::pin::Pin;
 use std::sync::Arc;
+use uira_core::{ErrorKind, UiraError};
 
 pub type ToolInput = Value;
 
@@ -48,6 +49,12 @@ pub enum ToolError {
 
     #[error("permission denied: {message}")]
     PermissionDenied { message: String },
+
+    #[error("tool not permitted for this agent: {agent} cannot use {tool}")]
+    NotPermitted { tool: String, agent: String },
+
+    #[error("{name} is unavailable offline")]
+    UnavailableOffline { name: String },
 }
 
 impl ToolError {
@@ -74,6 +81,53 @@ impl ToolError {
             retryable: false,
         }
     }
+
+    /// Stable code for the shared error taxonomy
+    pub fn code(&self) -> &'static str {
+        match self {
+            ToolError::InvalidInput { .. } => "tool.invalid_input",
+            ToolError::AlreadyRegistered { .. } => "tool.already_registered",
+            ToolError::NotFound { .. } => "tool.not_found",
+            ToolError::NotImplemented { .. } => "tool.not_implemented",
+            ToolError::ExecutionFailed { .. } => "tool.execution_failed",
+            ToolError::SandboxDenied { .. } => "tool.sandbox_denied",
+            ToolError::PermissionDenied { .. } => "tool.permission_denied",
+            ToolError::NotPermitted { .. } => "tool.not_permitted",
+            ToolError::UnavailableOffline { .. } => "tool.unavailable_offline",
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            ToolError::SandboxDenied { .. }
+            | ToolError::PermissionDenied { .. }
+            | ToolError::NotPermitted { .. }
+            | ToolError::UnavailableOffline { .. } => ErrorKind::PolicyDenial,
+            ToolError::AlreadyRegistered { .. } | ToolError::NotImplemented { .. } => {
+                ErrorKind::Internal
+            }
+            _ => ErrorKind::ToolError,
+        }
+    }
+}
+
+impl From<&ToolError> for UiraError {
+    fn from(error: &ToolError) -> Self {
+        let uira_error = UiraError::new(error.kind(), error.code(), error.to_string())
+            .with_retryable(error.is_retryable());
+        match error {
+            ToolError::SandboxDenied { .. } => uira_error.with_remediation(
+                "Approve the command when prompted or relax the sandbox policy in uira.yml",
+            ),
+            ToolError::NotPermitted { agent, .. } => uira_error.with_remediation(format!(
+                "Delegate to an agent that may use this tool, or allow it for {agent} in uira.yml"
+            )),
+            ToolError::UnavailableOffline { .. } => {
+                uira_error.with_remediation("Run without --offline to use network tools")
+            }
+            _ => uira_error,
+        }
+    }
 }
 
 pub type ToolFuture = Pin<Box<dyn Future<Output = Result<ToolOutput, ToolError>> + Send + 'static>>;
@@ -171,4 +225,19 @@ mod tests {
             }
         );
     }
+
+    #[test]
+    fn policy_errors_classify_as_denials() {
+        let error = UiraError::from(&ToolError::sandbox_denied("network blocked"));
+        assert_eq!(error.kind, ErrorKind::PolicyDenial);
+        assert_eq!(error.code, "tool.sandbox_denied");
+        assert!(error.retryable);
+        assert!(error.remediation.is_some());
+
+        let error = UiraError::from(&ToolError::ExecutionFailed {
+            message: "exit 1".to_string(),
+        });
+        assert_eq!(error.kind, ErrorKind::ToolError);
+        assert!(error.remediation.is_none());
+    }
 }
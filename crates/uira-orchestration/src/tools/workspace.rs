@@ -0,0 +1,170 @@
+//! Additional workspace roots registered on a session
+//!
+//! A session works from one `cwd` but may span several roots (monorepo
+//! packages, a frontend and a backend checkout). Tools address a root with an
+//! `@<name>/` prefix, and each root can carry its own sandbox policy.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path, PathBuf};
+use uira_security::SandboxPolicy;
+
+/// A named directory the agent may read and write alongside `cwd`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WorkspaceRoot {
+    pub name: String,
+    pub path: PathBuf,
+    /// Overrides the session policy for paths under this root
+    #[serde(default)]
+    pub sandbox_policy: Option<SandboxPolicy>,
+}
+
+impl WorkspaceRoot {
+    pub fn new(name: impl Into<String>, path: impl Into<PathBuf>) -> Self {
+        Self {
+            name: name.into(),
+            path: normalize_path(&path.into()),
+            sandbox_policy: None,
+        }
+    }
+
+    pub fn with_sandbox_policy(mut self, policy: SandboxPolicy) -> Self {
+        self.sandbox_policy = Some(policy);
+        self
+    }
+
+    pub fn contains(&self, path: &Path) -> bool {
+        normalize_path(path).starts_with(normalize_path(&self.path))
+    }
+}
+
+/// Resolve a tool-supplied path: `@name/rest` maps into the named root,
+/// relative paths join `cwd`, absolute paths are kept
+pub fn resolve_path(raw: &str, cwd: &Path, roots: &[WorkspaceRoot]) -> PathBuf {
+    if let Some(rest) = raw.strip_prefix('@') {
+        let (name, tail) = rest.split_once('/').unwrap_or((rest, ""));
+        if let Some(root) = roots.iter().find(|root| root.name == name) {
+            return if tail.is_empty() {
+                root.path.clone()
+            } else {
+                normalize_path(&root.path.join(tail))
+            };
+        }
+    }
+
+    let path = Path::new(raw);
+    if path.is_absolute() {
+        normalize_path(path)
+    } else {
+        normalize_path(&cwd.join(path))
+    }
+}
+
+/// Lexically fold `.` and `..` so `../api/x` and `/work/api/x` compare equal
+pub fn normalize_path(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+
+    for comp in path.components() {
+        match comp {
+            Component::Prefix(p) => out.push(p.as_os_str()),
+            Component::RootDir => out.push(Path::new("/")),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                let popped = out.pop();
+                if !popped {
+                    out.push("..");
+                }
+            }
+            Component::Normal(s) => out.push(s),
+        }
+    }
+
+    out
+}
+
+/// The innermost root containing `path`, so nested packages win over the
+/// repository that holds them
+pub fn root_for<'a>(path: &Path, roots: &'a [WorkspaceRoot]) -> Option<&'a WorkspaceRoot> {
+    roots
+        .iter()
+        .filter(|root| root.contains(path))
+        .max_by_key(|root| root.path.components().count())
+}
+
+/// One line per root for the system prompt
+pub fn describe_roots(roots: &[WorkspaceRoot]) -> String {
+    let mut out = String::from(
+        "Workspace roots (address files as @<name>/<path>; relative paths resolve against the working directory):",
+    );
+    for root in roots {
+        let access = match &root.sandbox_policy {
+            Some(SandboxPolicy::ReadOnly) => " (read-only)",
+            Some(_) => " (restricted)",
+            None => "",
+        };
+        out.push_str(&format!(
+            "\n- @{}: {}{}",
+            root.name,
+            root.path.display(),
+            access
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn roots() -> Vec<WorkspaceRoot> {
+        vec![
+            WorkspaceRoot::new("web", "/work/web"),
+            WorkspaceRoot::new("api", "/work/api").with_sandbox_policy(SandboxPolicy::ReadOnly),
+            WorkspaceRoot::new("api-core", "/work/api/crates/core"),
+        ]
+    }
+
+    #[test]
+    fn test_resolve_named_root() {
+        let cwd = Path::new("/work/web");
+        assert_eq!(
+            resolve_path("@api/src/main.rs", cwd, &roots()),
+            PathBuf::from("/work/api/src/main.rs")
+        );
+        assert_eq!(
+            resolve_path("@api", cwd, &roots()),
+            PathBuf::from("/work/api")
+        );
+    }
+
+    #[test]
+    fn test_resolve_falls_back_to_cwd() {
+        let cwd = Path::new("/work/web");
+        assert_eq!(
+            resolve_path("src/app.tsx", cwd, &roots()),
+            PathBuf::from("/work/web/src/app.tsx")
+        );
+        assert_eq!(
+            resolve_path("/etc/hosts", cwd, &roots()),
+            PathBuf::from("/etc/hosts")
+        );
+        assert_eq!(
+            resolve_path("../api/./src", cwd, &roots()),
+            PathBuf::from("/work/api/src")
+        );
+        // Unknown names stay literal, e.g. scoped npm package directories
+        assert_eq!(
+            resolve_path("@types/node", cwd, &roots()),
+            PathBuf::from("/work/web/@types/node")
+        );
+    }
+
+    #[test]
+    fn test_root_for_prefers_innermost() {
+        let roots = roots();
+        let root = root_for(Path::new("/work/api/crates/core/lib.rs"), &roots).unwrap();
+        assert_eq!(root.name, "api-core");
+        let root = root_for(Path::new("/work/api/src/main.rs"), &roots).unwrap();
+        assert_eq!(root.name, "api");
+        assert!(root_for(Path::new("/tmp/x"), &roots).is_none());
+    }
+}
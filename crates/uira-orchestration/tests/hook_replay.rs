@@ -0,0 +1,27 @@
+//! Replays the checked-in hook fixtures so hook refactors cannot silently
+//! change a decision. After an intended change, refresh them with
+//! `uira-agent hooks test crates/uira-orchestration/tests/fixtures/hooks --update`.
+
+use std::path::PathBuf;
+
+use uira_orchestration::hooks::replay;
+
+#[tokio::test]
+async fn hook_fixtures_replay_unchanged() {
+    let dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/hooks");
+    let results = replay::replay(&[dir], false).await.unwrap();
+    assert!(!results.is_empty());
+
+    let failures: Vec<String> = results
+        .iter()
+        .filter(|result| !result.passed())
+        .map(|result| {
+            format!(
+                "{}: {}",
+                result.path.display(),
+                result.mismatches.join("; ")
+            )
+        })
+        .collect();
+    assert!(failures.is_empty(), "{}", failures.join("\n"));
+}
@@ -0,0 +1,314 @@
+//! JavaScript/TypeScript formatter powered by OXC
+//!
+//! Reprints each top-level statement through the OXC code generator while keeping
+//! the text between statements (comments and blank lines) from the original source.
+//! This is not a Prettier-compatible printer: `oxc_prettier` is not a dependency of
+//! this workspace, so line width is not enforced, and any top-level statement that
+//! contains a comment is left exactly as written.
+
+use crate::module_rules::is_module_file;
+use crate::resolver::strip_jsonc;
+use oxc::allocator::Allocator;
+use oxc::codegen::{Codegen, CodegenOptions, IndentChar};
+use oxc::parser::Parser;
+use oxc::span::{GetSpan, SourceType, Span};
+use serde::Deserialize;
+use similar::TextDiff;
+use std::fs;
+use std::path::Path;
+
+/// Config files looked up by [`FormatOptions::discover`]
+const CONFIG_FILES: &[&str] = &[".oxfmtrc.json", ".prettierrc", ".prettierrc.json"];
+
+/// Formatting options
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct FormatOptions {
+    /// Spaces per indentation level
+    pub tab_width: usize,
+    /// Indent with tabs instead of spaces
+    pub use_tabs: bool,
+    /// Prefer single quotes for string literals
+    pub single_quote: bool,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            tab_width: 2,
+            use_tabs: false,
+            single_quote: false,
+        }
+    }
+}
+
+impl FormatOptions {
+    /// Load options from `.oxfmtrc.json` or a JSON `.prettierrc` in `root` if present
+    ///
+    /// Unknown keys are ignored; falls back to defaults when no config loads.
+    pub fn discover(root: &Path) -> Self {
+        CONFIG_FILES
+            .iter()
+            .map(|name| root.join(name))
+            .filter(|path| path.is_file())
+            .find_map(|path| {
+                let raw = fs::read_to_string(path).ok()?;
+                serde_json::from_str(&strip_jsonc(&raw)).ok()
+            })
+            .unwrap_or_default()
+    }
+
+    fn codegen_options(&self) -> CodegenOptions {
+        let (indent_char, indent_width) = if self.use_tabs {
+            (IndentChar::Tab, 1)
+        } else {
+            (IndentChar::Space, self.tab_width)
+        };
+        CodegenOptions {
+            single_quote: self.single_quote,
+            indent_char,
+            indent_width,
+            ..CodegenOptions::default()
+        }
+    }
+}
+
+/// Code formatter
+#[derive(Debug, Clone, Default)]
+pub struct Formatter {
+    options: FormatOptions,
+}
+
+impl Formatter {
+    /// Create a formatter with the given options
+    pub fn new(options: FormatOptions) -> Self {
+        Self { options }
+    }
+
+    /// Whether `path` has an extension this formatter handles
+    pub fn supports(path: &Path) -> bool {
+        is_module_file(path)
+    }
+
+    /// Format source code, returning the formatted text
+    ///
+    /// Syntax is determined by the file extension (e.g., `.tsx` enables JSX).
+    pub fn format_source(&self, filename: &str, source: &str) -> Result<String, String> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(filename).unwrap_or_default();
+        let ret = Parser::new(&allocator, source, source_type).parse();
+
+        if !ret.errors.is_empty() {
+            return Err(format!(
+                "Parse errors: {}",
+                ret.errors
+                    .iter()
+                    .map(|e| e.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+
+        let program = &ret.program;
+        let mut items: Vec<Span> = program.directives.iter().map(|d| d.span).collect();
+        items.extend(program.body.iter().map(|s| s.span()));
+
+        let mut out = String::with_capacity(source.len());
+        let mut cursor = 0;
+        if let Some(hashbang) = &program.hashbang {
+            out.push_str(source[..hashbang.span.end as usize].trim_end());
+            out.push('\n');
+            cursor = hashbang.span.end as usize;
+        }
+
+        for span in items {
+            let (start, end) = (span.start as usize, span.end as usize);
+            push_gap(&mut out, &source[cursor..start]);
+            let original = &source[start..end];
+            let formatted = self.format_statement(original, source_type);
+            out.push_str(formatted.as_deref().unwrap_or(original).trim_end());
+            cursor = end;
+        }
+        push_gap(&mut out, &source[cursor..]);
+
+        // Every reprinted statement was reparsed in `format_statement`, and the
+        // gaps between them are original text, so the output needs no reparse
+        let mut formatted = out.trim_end().to_string();
+        if !formatted.is_empty() {
+            formatted.push('\n');
+        }
+        Ok(formatted)
+    }
+
+    /// Reprint a single statement, or `None` if that would lose comments or break it
+    fn format_statement(&self, text: &str, source_type: SourceType) -> Option<String> {
+        let allocator = Allocator::default();
+        let ret = Parser::new(&allocator, text, source_type).parse();
+        if !ret.errors.is_empty() {
+            return None;
+        }
+
+        let code = Codegen::new()
+            .with_options(self.options.codegen_options())
+            .build(&ret.program)
+            .code;
+
+        let reparsed = Parser::new(&allocator, &code, source_type).parse();
+        (reparsed.errors.is_empty()
+            && reparsed.program.comments.len() >= ret.program.comments.len())
+        .then_some(code)
+    }
+
+    /// Unified diff between `source` and its formatted form, or `None` if already formatted
+    pub fn check_source(&self, filename: &str, source: &str) -> Result<Option<String>, String> {
+        let formatted = self.format_source(filename, source)?;
+        if formatted == source {
+            return Ok(None);
+        }
+        Ok(Some(unified_diff(filename, source, &formatted)))
+    }
+
+    /// Format a file in place, returning whether it changed
+    pub fn format_file(&self, path: &Path) -> Result<bool, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let formatted = self.format_source(&path.to_string_lossy(), &source)?;
+        if formatted == source {
+            return Ok(false);
+        }
+        fs::write(path, formatted)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok(true)
+    }
+
+    /// Unified diff for a file that needs formatting, or `None` if already formatted
+    pub fn check_file(&self, path: &Path) -> Result<Option<String>, String> {
+        let source = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        self.check_source(&path.to_string_lossy(), &source)
+    }
+}
+
+/// Append the text between two statements, normalized
+///
+/// Comments are kept verbatim (minus trailing whitespace), a trailing comment stays on
+/// the previous statement's line, and runs of blank lines collapse to one.
+fn push_gap(out: &mut String, gap: &str) {
+    let mut lines = gap.split('\n');
+    let head = lines.next().unwrap_or("").trim();
+    if !head.is_empty() {
+        if !out.is_empty() && !out.ends_with('\n') {
+            out.push(' ');
+        }
+        out.push_str(head);
+    }
+
+    let mut lines: Vec<&str> = lines.collect();
+    // The last segment is the partial line before the next statement
+    let Some(tail) = lines.pop() else {
+        push_newline(out);
+        return;
+    };
+
+    let mut blank = false;
+    for line in lines {
+        let line = line.trim_end();
+        if line.is_empty() {
+            blank = true;
+            continue;
+        }
+        push_newline(out);
+        if blank && !out.is_empty() {
+            out.push('\n');
+        }
+        blank = false;
+        out.push_str(line);
+    }
+
+    push_newline(out);
+    if blank && !out.is_empty() {
+        out.push('\n');
+    }
+    let tail = tail.trim();
+    if !tail.is_empty() {
+        out.push_str(tail);
+        out.push(' ');
+    }
+}
+
+fn push_newline(out: &mut String) {
+    if !out.is_empty() && !out.ends_with('\n') {
+        out.push('\n');
+    }
+}
+
+/// Render a unified diff from `before` to `after`
+pub fn unified_diff(filename: &str, before: &str, after: &str) -> String {
+    let filename = filename.trim_start_matches("./");
+    TextDiff::from_lines(before, after)
+        .unified_diff()
+        .context_radius(3)
+        .header(&format!("a/{}", filename), &format!("b/{}", filename))
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_format_keeps_comments_and_blank_lines() {
+        let source = "#!/usr/bin/env node\n// header\nimport {b,a} from './x'\n\n\n\nconst y = x+1; // trailing\n/** Docs */\nexport function foo(n:number){\n  // inside\n  return n*2}\n";
+        let formatted = Formatter::default().format_source("a.ts", source).unwrap();
+        assert_eq!(
+            formatted,
+            "#!/usr/bin/env node\n// header\nimport { b, a } from \"./x\";\n\nconst y = x + 1; // trailing\n/** Docs */\nexport function foo(n: number) {\n  // inside\n  return n * 2;\n}\n"
+        );
+        assert_eq!(
+            Formatter::default().format_source("a.ts", &formatted),
+            Ok(formatted)
+        );
+    }
+
+    #[test]
+    fn test_format_options() {
+        let options = FormatOptions {
+            use_tabs: true,
+            single_quote: true,
+            ..FormatOptions::default()
+        };
+        let formatted = Formatter::new(options)
+            .format_source("a.jsx", "if (a) { f(\"x\", <b/>) }")
+            .unwrap();
+        assert_eq!(formatted, "if (a) {\n\tf('x', <b />);\n}\n");
+    }
+
+    #[test]
+    fn test_format_rejects_invalid_source() {
+        assert!(Formatter::default()
+            .format_source("a.ts", "const = ;")
+            .is_err());
+    }
+
+    #[test]
+    fn test_check_and_discover() {
+        let dir = TempDir::new().unwrap();
+        fs::write(
+            dir.path().join(".prettierrc"),
+            r#"{ "singleQuote": true, "semi": false, }"#,
+        )
+        .unwrap();
+        let file = dir.path().join("a.ts");
+        fs::write(&file, "let a = \"x\"\n").unwrap();
+
+        let formatter = Formatter::new(FormatOptions::discover(dir.path()));
+        let diff = formatter.check_file(&file).unwrap().unwrap();
+        assert!(diff.contains("-let a = \"x\""));
+        assert!(diff.contains("+let a = 'x';"));
+
+        assert!(formatter.format_file(&file).unwrap());
+        assert_eq!(fs::read_to_string(&file).unwrap(), "let a = 'x';\n");
+        assert_eq!(formatter.check_file(&file).unwrap(), None);
+    }
+}
@@ -0,0 +1,494 @@
+//! Dependency and call graph extraction
+//!
+//! Parses a JS/TS project into import edges, exported symbols, and approximate
+//! call relationships, serializable as JSON for agents exploring a codebase.
+
+use crate::module_rules::{
+    collect_project_files, is_module_file, line_col, ModuleCollector, Usage,
+};
+use crate::resolver::{self, ModuleResolver};
+use oxc::allocator::Allocator;
+use oxc::ast::ast::{
+    CallExpression, Class, Expression, Function, MethodDefinition, VariableDeclarator,
+};
+use oxc::ast_visit::{walk, Visit};
+use oxc::parser::Parser;
+use oxc::semantic::ScopeFlags;
+use oxc::span::SourceType;
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Caller name used for calls made at module top level
+const MODULE_SCOPE: &str = "<module>";
+
+/// Project module graph
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ModuleGraph {
+    /// Modules, keyed by path relative to the project root
+    pub modules: Vec<ModuleNode>,
+    /// Import edges between modules (and to external packages)
+    pub imports: Vec<ImportEdge>,
+    /// Approximate call relationships between functions
+    pub calls: Vec<CallEdge>,
+}
+
+/// A parsed module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ModuleNode {
+    pub path: String,
+    pub exports: Vec<ExportedSymbol>,
+    /// Functions and methods declared in the module
+    pub functions: Vec<String>,
+}
+
+/// A symbol exported from a module
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportedSymbol {
+    pub name: String,
+    /// function, class, variable, type, interface, enum, namespace, default, or reexport
+    pub kind: String,
+    pub line: u32,
+}
+
+/// An import of one module by another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ImportEdge {
+    pub from: String,
+    /// Resolved module path, `None` for external packages
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to: Option<String>,
+    pub specifier: String,
+    /// Imported names (`default`, `*` for namespace imports)
+    pub names: Vec<String>,
+    pub type_only: bool,
+    pub dynamic: bool,
+}
+
+/// A call from one function to another
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CallEdge {
+    pub module: String,
+    /// Calling function (`<module>` for top-level code)
+    pub caller: String,
+    pub callee: String,
+    /// Module defining the callee, when it could be determined
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callee_module: Option<String>,
+    pub line: u32,
+}
+
+impl ModuleGraph {
+    /// Build the graph for every JS/TS file under `root`
+    pub fn build(root: impl AsRef<Path>) -> Self {
+        let root = resolver::absolute(root.as_ref());
+        let files = collect_project_files(&root);
+        Self::build_files(&root, &files)
+    }
+
+    /// Build the graph for the given files, resolving imports relative to `root`
+    pub fn build_files(root: impl AsRef<Path>, files: &[PathBuf]) -> Self {
+        let root = resolver::absolute(root.as_ref());
+        let resolver = ModuleResolver::discover(&root);
+
+        let mut parsed: Vec<(ModuleNode, Vec<ImportEdge>, Vec<CallEdge>)> = files
+            .par_iter()
+            .filter(|path| is_module_file(path))
+            .filter_map(|path| {
+                let path = resolver::absolute(path);
+                let source = fs::read_to_string(&path).ok()?;
+                analyze(&root, &resolver, &path, &source)
+            })
+            .collect();
+        parsed.sort_by(|a, b| a.0.path.cmp(&b.0.path));
+
+        let mut graph = Self::default();
+        for (module, imports, calls) in parsed {
+            graph.modules.push(module);
+            graph.imports.extend(imports);
+            graph.calls.extend(calls);
+        }
+        graph
+    }
+
+    /// Keep only modules under one of `prefixes`, with their outgoing imports and calls
+    pub fn retain_paths(&mut self, prefixes: &[String]) {
+        if prefixes.is_empty() {
+            return;
+        }
+        let keep = |path: &str| {
+            prefixes
+                .iter()
+                .any(|p| Path::new(path).starts_with(p.trim_start_matches("./")))
+        };
+        self.modules.retain(|m| keep(&m.path));
+        self.imports.retain(|e| keep(&e.from));
+        self.calls.retain(|c| keep(&c.module));
+    }
+
+    /// Modules that import `path`
+    pub fn dependents(&self, path: &str) -> Vec<&str> {
+        let mut dependents: Vec<&str> = self
+            .imports
+            .iter()
+            .filter(|e| e.to.as_deref() == Some(path))
+            .map(|e| e.from.as_str())
+            .collect();
+        dependents.sort();
+        dependents.dedup();
+        dependents
+    }
+
+    /// Serialize the graph as pretty-printed JSON
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap_or_default()
+    }
+}
+
+fn analyze(
+    root: &Path,
+    resolver: &ModuleResolver,
+    path: &Path,
+    source: &str,
+) -> Option<(ModuleNode, Vec<ImportEdge>, Vec<CallEdge>)> {
+    let allocator = Allocator::default();
+    let source_type = SourceType::from_path(path).unwrap_or_default();
+    let ret = Parser::new(&allocator, source, source_type).parse();
+    if !ret.errors.is_empty() {
+        return None;
+    }
+
+    let display = |p: &Path| {
+        p.strip_prefix(root)
+            .unwrap_or(p)
+            .to_string_lossy()
+            .replace('\\', "/")
+    };
+    let module_path = display(path);
+
+    let mut module = ModuleCollector::default();
+    module.collect(&ret.program);
+    module.visit_program(&ret.program);
+    let mut collector = GraphCollector::new(source);
+    collector.visit_program(&ret.program);
+
+    let resolved: HashMap<&str, Option<String>> = module
+        .imports
+        .iter()
+        .map(|i| {
+            let target = resolver.resolve(path, &i.specifier).map(|p| display(&p));
+            (i.specifier.as_str(), target)
+        })
+        .collect();
+
+    let imports = module
+        .imports
+        .iter()
+        .map(|i| ImportEdge {
+            from: module_path.clone(),
+            to: resolved.get(i.specifier.as_str()).cloned().flatten(),
+            specifier: i.specifier.clone(),
+            names: match &i.usage {
+                Usage::Names(names) => names.clone(),
+                Usage::All => vec!["*".to_string()],
+            },
+            type_only: i.type_only,
+            dynamic: i.dynamic,
+        })
+        .collect();
+
+    let local_functions: HashSet<&str> = collector.functions.iter().map(String::as_str).collect();
+    let calls = collector
+        .calls
+        .iter()
+        .map(|call| {
+            let (callee, callee_module) = match module.bindings.get(&call.target) {
+                Some((specifier, imported)) => {
+                    let name = match (imported.as_str(), &call.member) {
+                        ("*", Some(member)) => member.clone(),
+                        ("default" | "*", _) => call.target.clone(),
+                        (name, Some(member)) => format!("{}.{}", name, member),
+                        (name, None) => name.to_string(),
+                    };
+                    (name, resolved.get(specifier.as_str()).cloned().flatten())
+                }
+                None => {
+                    let name = match &call.member {
+                        Some(member) => format!("{}.{}", call.target, member),
+                        None => call.target.clone(),
+                    };
+                    let local = local_functions.contains(name.as_str());
+                    (name, local.then(|| module_path.clone()))
+                }
+            };
+            CallEdge {
+                module: module_path.clone(),
+                caller: call.caller.clone(),
+                callee,
+                callee_module,
+                line: call.line,
+            }
+        })
+        .collect();
+
+    let exports = module
+        .exports
+        .iter()
+        .map(|e| ExportedSymbol {
+            name: e.name.clone(),
+            kind: e.kind.to_string(),
+            line: line_col(source, e.offset).0,
+        })
+        .collect();
+    let node = ModuleNode {
+        path: module_path,
+        exports,
+        functions: collector.functions,
+    };
+    Some((node, imports, calls))
+}
+
+struct RawCall {
+    caller: String,
+    /// Called identifier, or the object of a member call
+    target: String,
+    member: Option<String>,
+    line: u32,
+}
+
+/// Walks a program recording declared functions and calls; imports and
+/// exports come from [`ModuleCollector`]
+struct GraphCollector<'s> {
+    source: &'s str,
+    functions: Vec<String>,
+    calls: Vec<RawCall>,
+    scopes: Vec<String>,
+    classes: Vec<String>,
+}
+
+impl<'s> GraphCollector<'s> {
+    fn new(source: &'s str) -> Self {
+        Self {
+            source,
+            functions: Vec::new(),
+            calls: Vec::new(),
+            scopes: Vec::new(),
+            classes: Vec::new(),
+        }
+    }
+
+    fn line(&self, offset: u32) -> u32 {
+        line_col(self.source, offset).0
+    }
+
+    fn caller(&self) -> String {
+        self.scopes
+            .last()
+            .cloned()
+            .unwrap_or_else(|| MODULE_SCOPE.to_string())
+    }
+
+    fn enter_function(&mut self, name: String) {
+        if !self.functions.contains(&name) {
+            self.functions.push(name.clone());
+        }
+        self.scopes.push(name);
+    }
+}
+
+impl<'a> Visit<'a> for GraphCollector<'_> {
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        match &func.id {
+            Some(id) => {
+                self.enter_function(id.name.to_string());
+                walk::walk_function(self, func, flags);
+                self.scopes.pop();
+            }
+            None => walk::walk_function(self, func, flags),
+        }
+    }
+
+    fn visit_variable_declarator(&mut self, decl: &VariableDeclarator<'a>) {
+        let is_function = matches!(
+            decl.init,
+            Some(Expression::ArrowFunctionExpression(_) | Expression::FunctionExpression(_))
+        );
+        match decl.id.get_identifier_name() {
+            Some(name) if is_function => {
+                self.enter_function(name.to_string());
+                walk::walk_variable_declarator(self, decl);
+                self.scopes.pop();
+            }
+            _ => walk::walk_variable_declarator(self, decl),
+        }
+    }
+
+    fn visit_class(&mut self, class: &Class<'a>) {
+        let name = class
+            .id
+            .as_ref()
+            .map_or("<anonymous>".to_string(), |id| id.name.to_string());
+        self.classes.push(name);
+        walk::walk_class(self, class);
+        self.classes.pop();
+    }
+
+    fn visit_method_definition(&mut self, method: &MethodDefinition<'a>) {
+        match (self.classes.last(), method.key.static_name()) {
+            (Some(class), Some(key)) => {
+                self.enter_function(format!("{}.{}", class, key));
+                walk::walk_method_definition(self, method);
+                self.scopes.pop();
+            }
+            _ => walk::walk_method_definition(self, method),
+        }
+    }
+
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        let target = match &expr.callee {
+            Expression::Identifier(id) if id.name == "require" => None,
+            Expression::Identifier(id) => Some((id.name.to_string(), None)),
+            Expression::StaticMemberExpression(member) => match &member.object {
+                Expression::Identifier(obj) => {
+                    Some((obj.name.to_string(), Some(member.property.name.to_string())))
+                }
+                Expression::ThisExpression(_) => self
+                    .classes
+                    .last()
+                    .map(|class| (class.clone(), Some(member.property.name.to_string()))),
+                _ => None,
+            },
+            _ => None,
+        };
+
+        if let Some((target, member)) = target {
+            let line = self.line(expr.span.start);
+            self.calls.push(RawCall {
+                caller: self.caller(),
+                target,
+                member,
+                line,
+            });
+        }
+
+        walk::walk_call_expression(self, expr);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (rel, content) in files {
+            let path = dir.path().join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    #[test]
+    fn test_imports_and_exports() {
+        let dir = project(&[
+            (
+                "src/main.ts",
+                "import { greet } from './greet';\nimport type { Name } from './types';\nimport React from 'react';\nconst lazy = () => import('./lazy');\n",
+            ),
+            (
+                "src/greet.ts",
+                "export function greet(n: string) { return n; }\nexport class Greeter {}\nexport default 1;\n",
+            ),
+            ("src/types.ts", "export type Name = string;\nexport interface Person {}\n"),
+            ("src/lazy.ts", "export const value = 1;\n"),
+        ]);
+        let graph = ModuleGraph::build(dir.path());
+
+        assert_eq!(graph.modules.len(), 4);
+        let greet = graph
+            .modules
+            .iter()
+            .find(|m| m.path == "src/greet.ts")
+            .unwrap();
+        let kinds: Vec<_> = greet
+            .exports
+            .iter()
+            .map(|e| (e.name.as_str(), e.kind.as_str()))
+            .collect();
+        assert_eq!(
+            kinds,
+            vec![
+                ("greet", "function"),
+                ("Greeter", "class"),
+                ("default", "default")
+            ]
+        );
+
+        let from_main: Vec<_> = graph
+            .imports
+            .iter()
+            .filter(|e| e.from == "src/main.ts")
+            .collect();
+        assert_eq!(from_main.len(), 4);
+        assert_eq!(from_main[0].to.as_deref(), Some("src/greet.ts"));
+        assert!(from_main[1].type_only);
+        assert_eq!(from_main[2].to, None);
+        assert!(from_main[3].dynamic);
+        assert_eq!(graph.dependents("src/greet.ts"), vec!["src/main.ts"]);
+    }
+
+    #[test]
+    fn test_call_relationships() {
+        let dir = project(&[
+            (
+                "src/app.ts",
+                "import * as util from './util';\nimport { format } from './util';\nfunction helper() { return 1; }\nexport function run() {\n  helper();\n  util.parse();\n  format();\n}\nclass Service {\n  start() { this.stop(); }\n  stop() {}\n}\nrun();\n",
+            ),
+            (
+                "src/util.ts",
+                "export const parse = () => format();\nexport function format() {}\n",
+            ),
+        ]);
+        let graph = ModuleGraph::build(dir.path());
+
+        let calls: Vec<_> = graph
+            .calls
+            .iter()
+            .map(|c| {
+                (
+                    c.caller.as_str(),
+                    c.callee.as_str(),
+                    c.callee_module.as_deref(),
+                )
+            })
+            .collect();
+
+        assert!(calls.contains(&("run", "helper", Some("src/app.ts"))));
+        assert!(calls.contains(&("run", "parse", Some("src/util.ts"))));
+        assert!(calls.contains(&("run", "format", Some("src/util.ts"))));
+        assert!(calls.contains(&("Service.start", "Service.stop", Some("src/app.ts"))));
+        assert!(calls.contains(&("<module>", "run", Some("src/app.ts"))));
+        assert!(calls.contains(&("parse", "format", Some("src/util.ts"))));
+
+        let mut graph = graph;
+        graph.retain_paths(&["src/util.ts".to_string()]);
+        assert_eq!(graph.modules.len(), 1);
+        assert!(graph.calls.iter().all(|c| c.module == "src/util.ts"));
+    }
+
+    #[test]
+    fn test_retain_paths_matches_whole_components() {
+        let dir = project(&[
+            ("src/app/main.ts", "export const a = 1;\n"),
+            ("src/application.ts", "export const b = 2;\n"),
+        ]);
+        let mut graph = ModuleGraph::build(dir.path());
+        graph.retain_paths(&["./src/app/".to_string()]);
+        let paths: Vec<_> = graph.modules.iter().map(|m| m.path.as_str()).collect();
+        assert_eq!(paths, vec!["src/app/main.ts"]);
+    }
+}
@@ -3,17 +3,30 @@
 //! OXC-powered JavaScript/TypeScript tools for Uira.
 //!
 //! Provides native Rust implementations for:
+//! - **Formatting** - Format JS/TS code without external tools
 //! - **Linting** - Fast JS/TS linting with customizable rules
+//! - **Module rules** - Project-wide unused-export, cycle, and import-order checks
 //! - **Parsing** - Parse to AST and return as JSON
+//! - **Module graphs** - Import edges, exported symbols, and call relationships
 //! - **Transforming** - Transpile TypeScript/JSX to JavaScript
 //! - **Minifying** - Minify JavaScript code
 
+pub mod formatter;
+pub mod graph;
 pub mod linter;
 pub mod minifier;
+pub mod module_rules;
 pub mod parser;
+pub mod resolver;
+pub mod suppressions;
 pub mod transformer;
 
-pub use linter::{LintDiagnostic, LintRule, Linter, Severity};
+pub use formatter::{FormatOptions, Formatter};
+pub use graph::ModuleGraph;
+pub use linter::{LintConfig, LintDiagnostic, LintRule, Linter, RuleLevel, Severity};
 pub use minifier::Minifier;
+pub use module_rules::{ModuleLinter, ModuleRule};
 pub use parser::AstParser;
+pub use resolver::ModuleResolver;
+pub use suppressions::Suppressions;
 pub use transformer::Transformer;
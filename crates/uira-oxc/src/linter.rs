@@ -1,7 +1,9 @@
 //! JavaScript/TypeScript linting powered by OXC
 //!
-//! Fast linting with customizable rules.
+//! Fast linting with customizable rules, per-rule severity overrides, and
+//! `// uira-disable-next-line` suppressions.
 
+use crate::suppressions::Suppressions;
 use oxc::allocator::Allocator;
 use oxc::ast::ast::{
     AssignmentTarget, BindingPattern, CallExpression, Expression, VariableDeclarationKind,
@@ -12,9 +14,10 @@ use oxc::semantic::ScopeFlags;
 use oxc::span::SourceType;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::Path;
+use std::str::FromStr;
 
 /// Lint diagnostic representing a single issue
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -96,6 +99,27 @@ impl LintRule {
         Self::all()
     }
 
+    /// Rule name as reported in diagnostics and used in configuration
+    pub fn name(&self) -> &'static str {
+        match self {
+            LintRule::NoConsole => "no-console",
+            LintRule::NoDebugger => "no-debugger",
+            LintRule::NoAlert => "no-alert",
+            LintRule::NoEval => "no-eval",
+            LintRule::NoVar => "no-var",
+            LintRule::PreferConst => "prefer-const",
+            LintRule::NoUnusedVars => "no-unused-vars",
+            LintRule::NoEmptyFunction => "no-empty-function",
+            LintRule::NoDuplicateKeys => "no-duplicate-keys",
+            LintRule::NoParamReassign => "no-param-reassign",
+        }
+    }
+
+    /// Look up a rule by its name
+    pub fn from_name(name: &str) -> Option<LintRule> {
+        Self::all().into_iter().find(|rule| rule.name() == name)
+    }
+
     /// Get rule description
     pub fn description(&self) -> &'static str {
         match self {
@@ -113,16 +137,98 @@ impl LintRule {
     }
 }
 
+/// Configured level of a rule
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum RuleLevel {
+    Off,
+    Info,
+    #[serde(alias = "warn")]
+    Warning,
+    Error,
+}
+
+impl RuleLevel {
+    /// Severity reported for this level (`None` when the rule is off)
+    pub fn severity(self) -> Option<Severity> {
+        match self {
+            RuleLevel::Off => None,
+            RuleLevel::Info => Some(Severity::Info),
+            RuleLevel::Warning => Some(Severity::Warning),
+            RuleLevel::Error => Some(Severity::Error),
+        }
+    }
+}
+
+impl FromStr for RuleLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "off" => Ok(RuleLevel::Off),
+            "info" => Ok(RuleLevel::Info),
+            "warn" | "warning" => Ok(RuleLevel::Warning),
+            "error" => Ok(RuleLevel::Error),
+            other => Err(format!(
+                "Invalid rule level '{}' (expected off, info, warning, or error)",
+                other
+            )),
+        }
+    }
+}
+
+/// Per-rule levels, e.g. from `diagnostics.lint.rules` in uira.yml
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LintConfig {
+    #[serde(default)]
+    pub rules: HashMap<String, RuleLevel>,
+}
+
+impl LintConfig {
+    /// Build a config from rule name -> level strings
+    pub fn from_levels<K, V>(levels: impl IntoIterator<Item = (K, V)>) -> Result<Self, String>
+    where
+        K: Into<String>,
+        V: AsRef<str>,
+    {
+        let rules = levels
+            .into_iter()
+            .map(|(rule, level)| Ok((rule.into(), level.as_ref().parse()?)))
+            .collect::<Result<_, String>>()?;
+        Ok(Self { rules })
+    }
+
+    /// Configured level for a rule, if any
+    pub fn level(&self, rule: &str) -> Option<RuleLevel> {
+        self.rules.get(rule).copied()
+    }
+
+    /// Drop diagnostics of rules turned off and apply severity overrides
+    pub fn apply(&self, diagnostics: Vec<LintDiagnostic>) -> Vec<LintDiagnostic> {
+        if self.rules.is_empty() {
+            return diagnostics;
+        }
+        diagnostics
+            .into_iter()
+            .filter_map(|mut d| {
+                if let Some(level) = self.level(&d.rule) {
+                    d.severity = level.severity()?;
+                }
+                Some(d)
+            })
+            .collect()
+    }
+}
+
 /// The linter configuration and executor
 pub struct Linter {
     rules: HashSet<LintRule>,
+    config: LintConfig,
 }
 
 impl Default for Linter {
     fn default() -> Self {
-        Self {
-            rules: LintRule::recommended().into_iter().collect(),
-        }
+        Self::new(LintRule::recommended())
     }
 }
 
@@ -131,9 +237,26 @@ impl Linter {
     pub fn new(rules: Vec<LintRule>) -> Self {
         Self {
             rules: rules.into_iter().collect(),
+            config: LintConfig::default(),
         }
     }
 
+    /// Apply per-rule levels: `off` disables a rule, any other level enables it
+    /// with that severity
+    pub fn with_config(mut self, config: LintConfig) -> Self {
+        for (name, level) in &config.rules {
+            if let Some(rule) = LintRule::from_name(name) {
+                if *level == RuleLevel::Off {
+                    self.rules.remove(&rule);
+                } else {
+                    self.rules.insert(rule);
+                }
+            }
+        }
+        self.config = config;
+        self
+    }
+
     /// Create a linter with all rules enabled
     pub fn strict() -> Self {
         Self::new(LintRule::strict())
@@ -195,7 +318,8 @@ impl Linter {
         // Finalize to emit diagnostics for rules that require post-processing
         visitor.finalize();
 
-        Ok(visitor.diagnostics)
+        let diagnostics = self.config.apply(visitor.diagnostics);
+        Ok(Suppressions::from_comments(source, &ret.program.comments).apply(diagnostics))
     }
 }
 
@@ -627,6 +751,41 @@ mod tests {
         assert!(diagnostics[0].message.contains("never used"));
     }
 
+    #[test]
+    fn test_rule_config_overrides() {
+        let config = LintConfig::from_levels([
+            ("no-console", "error"),
+            ("no-var", "off"),
+            ("no-eval", "warn"),
+        ])
+        .unwrap();
+        let linter = Linter::default().with_config(config);
+        assert!(linter.has_rule(LintRule::NoConsole));
+        assert!(!linter.has_rule(LintRule::NoVar));
+
+        let diagnostics = linter
+            .lint_source("test.js", "var x = 1; console.log(x); eval(\"x\");")
+            .unwrap();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, "no-console");
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+        assert_eq!(diagnostics[1].rule, "no-eval");
+        assert_eq!(diagnostics[1].severity, Severity::Warning);
+
+        assert!(LintConfig::from_levels([("no-var", "loud")]).is_err());
+    }
+
+    #[test]
+    fn test_disable_next_line() {
+        let linter = Linter::new(vec![LintRule::NoConsole, LintRule::NoDebugger]);
+        let source = "// uira-disable-next-line no-console -- debug output\nconsole.log(1);\n/* uira-disable-next-line */\ndebugger;\n// uira-disable-next-line no-eval\nconsole.log(2);\n";
+        let diagnostics = linter.lint_source("test.js", source).unwrap();
+
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "no-console");
+        assert_eq!(diagnostics[0].line, 6);
+    }
+
     #[test]
     fn test_no_unused_vars_underscore() {
         let linter = Linter::new(vec![LintRule::NoUnusedVars]);
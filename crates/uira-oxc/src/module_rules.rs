@@ -0,0 +1,1070 @@
+//! Module-graph aware lint rules
+//!
+//! Rules that need to see how files import each other rather than one file
+//! at a time: unused exports, import cycles, and import ordering (with autofix).
+
+use crate::linter::{LintConfig, LintDiagnostic, RuleLevel, Severity};
+use crate::resolver::{self, ModuleResolver};
+use crate::suppressions::Suppressions;
+use oxc::allocator::Allocator;
+use oxc::ast::ast::{
+    Argument, CallExpression, Declaration, ExportDefaultDeclarationKind, Expression,
+    ImportDeclarationSpecifier, ImportExpression, Program, Statement,
+};
+use oxc::ast_visit::{walk, Visit};
+use oxc::parser::Parser;
+use oxc::span::{GetSpan, SourceType};
+use rayon::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Directories never scanned when building the project module graph
+const SKIP_DIRS: &[&str] = &["node_modules", "dist", "build", "out", "coverage", "target"];
+
+/// Node.js core modules, importable with or without the `node:` prefix
+const NODE_BUILTINS: &[&str] = &[
+    "assert",
+    "async_hooks",
+    "buffer",
+    "child_process",
+    "cluster",
+    "console",
+    "crypto",
+    "dgram",
+    "dns",
+    "events",
+    "fs",
+    "http",
+    "http2",
+    "https",
+    "module",
+    "net",
+    "os",
+    "path",
+    "perf_hooks",
+    "process",
+    "querystring",
+    "readline",
+    "stream",
+    "string_decoder",
+    "timers",
+    "tls",
+    "tty",
+    "url",
+    "util",
+    "v8",
+    "vm",
+    "worker_threads",
+    "zlib",
+];
+
+/// Available module-graph rules
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ModuleRule {
+    /// Disallow exports that no other module imports
+    NoUnusedExports,
+    /// Disallow import cycles between modules
+    NoCycle,
+    /// Enforce a consistent import order
+    ImportOrder,
+}
+
+impl ModuleRule {
+    /// Get all available rules
+    pub fn all() -> Vec<ModuleRule> {
+        vec![
+            ModuleRule::NoUnusedExports,
+            ModuleRule::NoCycle,
+            ModuleRule::ImportOrder,
+        ]
+    }
+
+    /// Rule name as reported in diagnostics
+    pub fn name(&self) -> &'static str {
+        match self {
+            ModuleRule::NoUnusedExports => "no-unused-exports",
+            ModuleRule::NoCycle => "no-cycle",
+            ModuleRule::ImportOrder => "import-order",
+        }
+    }
+
+    /// Look up a rule by its name
+    pub fn from_name(name: &str) -> Option<ModuleRule> {
+        Self::all().into_iter().find(|rule| rule.name() == name)
+    }
+
+    /// Get rule description
+    pub fn description(&self) -> &'static str {
+        match self {
+            ModuleRule::NoUnusedExports => "Disallow exports that are never imported",
+            ModuleRule::NoCycle => "Disallow circular imports between modules",
+            ModuleRule::ImportOrder => {
+                "Order imports: builtin, external, internal, parent, sibling, index"
+            }
+        }
+    }
+}
+
+/// Import groups, in the order they are expected to appear
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum ImportGroup {
+    Builtin,
+    External,
+    Internal,
+    Parent,
+    Sibling,
+    Index,
+}
+
+/// What a module takes from one of its dependencies
+#[derive(Debug, Clone)]
+pub(crate) enum Usage {
+    /// Specific export names (`default` for default imports)
+    Names(Vec<String>),
+    /// Namespace imports, `export *`, dynamic `import()` and `require()`
+    All,
+}
+
+#[derive(Debug)]
+pub(crate) struct ModuleImport {
+    pub(crate) specifier: String,
+    offset: u32,
+    pub(crate) usage: Usage,
+    /// `import type` edges are erased at runtime and never form cycles
+    pub(crate) type_only: bool,
+    /// Dynamic `import()` or `require()`
+    pub(crate) dynamic: bool,
+    resolved: Option<PathBuf>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ModuleExport {
+    pub(crate) name: String,
+    /// function, class, variable, type, interface, enum, namespace, default, or reexport
+    pub(crate) kind: &'static str,
+    pub(crate) offset: u32,
+}
+
+/// A top-level import statement considered for ordering
+#[derive(Debug, Clone)]
+struct OrderedImport {
+    specifier: String,
+    start: u32,
+    end: u32,
+}
+
+#[derive(Debug)]
+struct ModuleInfo {
+    path: PathBuf,
+    source: String,
+    imports: Vec<ModuleImport>,
+    exports: Vec<ModuleExport>,
+    /// Runs of adjacent imports that may be reordered among themselves
+    import_runs: Vec<Vec<OrderedImport>>,
+    suppressions: Suppressions,
+}
+
+/// Project-level linter for rules that span multiple modules
+pub struct ModuleLinter {
+    rules: HashSet<ModuleRule>,
+    root: PathBuf,
+    resolver: ModuleResolver,
+    config: LintConfig,
+}
+
+impl ModuleLinter {
+    /// Create a module linter for the project rooted at `root`
+    ///
+    /// Path aliases are read from the root `tsconfig.json`/`jsconfig.json` if present.
+    pub fn new(rules: Vec<ModuleRule>, root: impl AsRef<Path>) -> Self {
+        let root = resolver::absolute(root.as_ref());
+        Self {
+            rules: rules.into_iter().collect(),
+            resolver: ModuleResolver::discover(&root),
+            root,
+            config: LintConfig::default(),
+        }
+    }
+
+    /// Apply per-rule levels the same way as [`crate::Linter::with_config`]
+    pub fn with_config(mut self, config: LintConfig) -> Self {
+        for (name, level) in &config.rules {
+            if let Some(rule) = ModuleRule::from_name(name) {
+                if *level == RuleLevel::Off {
+                    self.rules.remove(&rule);
+                } else {
+                    self.rules.insert(rule);
+                }
+            }
+        }
+        self.config = config;
+        self
+    }
+
+    /// Use a custom resolver instead of the discovered tsconfig
+    pub fn with_resolver(mut self, resolver: ModuleResolver) -> Self {
+        self.resolver = resolver;
+        self
+    }
+
+    /// Check if a rule is enabled
+    pub fn has_rule(&self, rule: ModuleRule) -> bool {
+        self.rules.contains(&rule)
+    }
+
+    /// Lint `files` in the context of the whole project
+    ///
+    /// The project graph is built from every JS/TS file under the root, but
+    /// diagnostics are only reported for the requested files.
+    pub fn lint_files(&self, files: &[String]) -> Vec<LintDiagnostic> {
+        let requested: HashMap<PathBuf, &String> = files
+            .iter()
+            .filter(|f| is_module_file(Path::new(f)))
+            .map(|f| (resolver::absolute(Path::new(f)), f))
+            .collect();
+
+        if requested.is_empty() || self.rules.is_empty() {
+            return vec![];
+        }
+
+        let needs_graph =
+            self.has_rule(ModuleRule::NoUnusedExports) || self.has_rule(ModuleRule::NoCycle);
+
+        let mut paths: Vec<PathBuf> = requested.keys().cloned().collect();
+        if needs_graph {
+            let requested_paths: HashSet<&PathBuf> = requested.keys().collect();
+            paths.extend(
+                collect_project_files(&self.root)
+                    .into_iter()
+                    .filter(|p| !requested_paths.contains(p)),
+            );
+        }
+
+        let modules: Vec<ModuleInfo> = paths
+            .par_iter()
+            .filter_map(|path| {
+                let source = fs::read_to_string(path).ok()?;
+                self.analyze(path, source)
+            })
+            .collect();
+
+        let mut diagnostics = Vec::new();
+
+        if self.has_rule(ModuleRule::ImportOrder) {
+            for module in &modules {
+                if let Some(file) = requested.get(&module.path) {
+                    diagnostics.extend(self.check_import_order(file, module));
+                }
+            }
+        }
+        if self.has_rule(ModuleRule::NoUnusedExports) {
+            diagnostics.extend(check_unused_exports(&modules, &requested));
+        }
+        if self.has_rule(ModuleRule::NoCycle) {
+            diagnostics.extend(self.check_cycles(&modules, &requested));
+        }
+
+        let suppressions: HashMap<&str, &Suppressions> = modules
+            .iter()
+            .filter_map(|m| Some((requested.get(&m.path)?.as_str(), &m.suppressions)))
+            .collect();
+
+        self.config
+            .apply(diagnostics)
+            .into_iter()
+            .filter(|d| {
+                !suppressions
+                    .get(d.file.as_str())
+                    .is_some_and(|s| s.is_suppressed(d))
+            })
+            .collect()
+    }
+
+    /// Sort imports in `source`, returning the fixed source if anything moved
+    pub fn fix_source(&self, filename: &str, source: &str) -> Result<Option<String>, String> {
+        let path = resolver::absolute(Path::new(filename));
+        let module = self
+            .analyze(&path, source.to_string())
+            .ok_or_else(|| format!("Failed to parse {}", filename))?;
+
+        let mut replacements = Vec::new();
+        for run in &module.import_runs {
+            let sorted = self.sorted_run(&module.path, run);
+            for (slot, import) in run.iter().zip(&sorted) {
+                if slot.start != import.start {
+                    replacements.push((slot.start, slot.end, import.start, import.end));
+                }
+            }
+        }
+
+        if replacements.is_empty() {
+            return Ok(None);
+        }
+
+        let mut fixed = source.to_string();
+        replacements.sort_by_key(|r| std::cmp::Reverse(r.0));
+        for (start, end, from, to) in replacements {
+            fixed.replace_range(
+                start as usize..end as usize,
+                &source[from as usize..to as usize],
+            );
+        }
+        Ok(Some(fixed))
+    }
+
+    /// Sort imports in a file on disk, returning whether it was changed
+    pub fn fix_file(&self, path: &str) -> Result<bool, String> {
+        let source = fs::read_to_string(path).map_err(|e| format!("Failed to read file: {}", e))?;
+        match self.fix_source(path, &source)? {
+            Some(fixed) => {
+                fs::write(path, fixed).map_err(|e| format!("Failed to write file: {}", e))?;
+                Ok(true)
+            }
+            None => Ok(false),
+        }
+    }
+
+    fn analyze(&self, path: &Path, source: String) -> Option<ModuleInfo> {
+        let allocator = Allocator::default();
+        let source_type = SourceType::from_path(path).unwrap_or_default();
+        let ret = Parser::new(&allocator, &source, source_type).parse();
+        if !ret.errors.is_empty() {
+            return None;
+        }
+
+        let mut collector = ModuleCollector::default();
+        collector.collect(&ret.program);
+        collector.visit_program(&ret.program);
+
+        let imports = collector
+            .imports
+            .into_iter()
+            .map(|mut import| {
+                import.resolved = self.resolver.resolve(path, &import.specifier);
+                import
+            })
+            .collect();
+
+        Some(ModuleInfo {
+            path: path.to_path_buf(),
+            imports,
+            exports: collector.exports,
+            import_runs: collector.import_runs,
+            suppressions: Suppressions::from_comments(&source, &ret.program.comments),
+            source,
+        })
+    }
+
+    fn classify(&self, from: &Path, specifier: &str) -> ImportGroup {
+        let bare = specifier.strip_prefix("node:").unwrap_or(specifier);
+        let root_segment = bare.split('/').next().unwrap_or(bare);
+
+        if specifier.starts_with("node:") || NODE_BUILTINS.contains(&root_segment) {
+            ImportGroup::Builtin
+        } else if matches!(specifier, "." | "./" | "./index") || specifier.starts_with("./index.") {
+            ImportGroup::Index
+        } else if specifier.starts_with("./") {
+            ImportGroup::Sibling
+        } else if specifier == ".." || specifier.starts_with("../") {
+            ImportGroup::Parent
+        } else if self.resolver.resolve(from, specifier).is_some() {
+            ImportGroup::Internal
+        } else {
+            ImportGroup::External
+        }
+    }
+
+    fn sorted_run(&self, path: &Path, run: &[OrderedImport]) -> Vec<OrderedImport> {
+        let mut sorted = run.to_vec();
+        sorted.sort_by_cached_key(|import| {
+            (
+                self.classify(path, &import.specifier),
+                import.specifier.to_lowercase(),
+            )
+        });
+        sorted
+    }
+
+    fn check_import_order(&self, file: &str, module: &ModuleInfo) -> Vec<LintDiagnostic> {
+        let mut diagnostics = Vec::new();
+
+        for run in &module.import_runs {
+            let sorted = self.sorted_run(&module.path, run);
+            let misplaced = run
+                .iter()
+                .zip(&sorted)
+                .find(|(actual, expected)| actual.start != expected.start);
+
+            if let Some((actual, expected)) = misplaced {
+                let (line, column) = line_col(&module.source, expected.start);
+                diagnostics.push(LintDiagnostic {
+                    file: file.to_string(),
+                    line,
+                    column,
+                    message: format!(
+                        "Import of '{}' should come before '{}'",
+                        expected.specifier, actual.specifier
+                    ),
+                    rule: ModuleRule::ImportOrder.name().to_string(),
+                    severity: Severity::Warning,
+                    suggestion: Some(
+                        "Sort imports: builtin, external, internal, parent, sibling, index (autofixable)"
+                            .to_string(),
+                    ),
+                });
+            }
+        }
+
+        diagnostics
+    }
+
+    fn check_cycles(
+        &self,
+        modules: &[ModuleInfo],
+        requested: &HashMap<PathBuf, &String>,
+    ) -> Vec<LintDiagnostic> {
+        let index: HashMap<&Path, usize> = modules
+            .iter()
+            .enumerate()
+            .map(|(i, m)| (m.path.as_path(), i))
+            .collect();
+
+        let edges: Vec<Vec<(usize, usize)>> = modules
+            .iter()
+            .map(|module| {
+                module
+                    .imports
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, import)| !import.type_only)
+                    .filter_map(|(i, import)| {
+                        let target = index.get(import.resolved.as_deref()?)?;
+                        Some((*target, i))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        let mut diagnostics = Vec::new();
+
+        for component in strongly_connected(&edges) {
+            let members: HashSet<usize> = component.iter().copied().collect();
+
+            for &node in &component {
+                let module = &modules[node];
+                let Some(file) = requested.get(&module.path) else {
+                    continue;
+                };
+
+                let Some(&(target, import_idx)) =
+                    edges[node].iter().find(|(t, _)| members.contains(t))
+                else {
+                    continue;
+                };
+                if component.len() == 1 && target != node {
+                    continue;
+                }
+
+                let mut cycle = vec![node];
+                cycle.extend(shortest_path(&edges, &members, target, node));
+                let chain = cycle
+                    .iter()
+                    .map(|&i| self.display_path(&modules[i].path))
+                    .collect::<Vec<_>>()
+                    .join(" -> ");
+
+                let import = &module.imports[import_idx];
+                let (line, column) = line_col(&module.source, import.offset);
+                diagnostics.push(LintDiagnostic {
+                    file: file.to_string(),
+                    line,
+                    column,
+                    message: format!("Import cycle detected: {}", chain),
+                    rule: ModuleRule::NoCycle.name().to_string(),
+                    severity: Severity::Error,
+                    suggestion: Some(
+                        "Move shared code into a separate module or use a type-only import"
+                            .to_string(),
+                    ),
+                });
+            }
+        }
+
+        diagnostics
+    }
+
+    fn display_path(&self, path: &Path) -> String {
+        path.strip_prefix(&self.root)
+            .unwrap_or(path)
+            .display()
+            .to_string()
+    }
+}
+
+fn check_unused_exports(
+    modules: &[ModuleInfo],
+    requested: &HashMap<PathBuf, &String>,
+) -> Vec<LintDiagnostic> {
+    let mut used: HashMap<&Path, HashSet<&str>> = HashMap::new();
+    let mut fully_used: HashSet<&Path> = HashSet::new();
+
+    for module in modules {
+        for import in &module.imports {
+            let Some(target) = import.resolved.as_deref() else {
+                continue;
+            };
+            let names = used.entry(target).or_default();
+            match &import.usage {
+                Usage::All => {
+                    fully_used.insert(target);
+                }
+                Usage::Names(imported) => names.extend(imported.iter().map(String::as_str)),
+            }
+        }
+    }
+
+    let mut diagnostics = Vec::new();
+
+    for module in modules {
+        let Some(file) = requested.get(&module.path) else {
+            continue;
+        };
+        // Modules nobody imports are entry points (or dead files), not unused exports
+        let Some(names) = used.get(module.path.as_path()) else {
+            continue;
+        };
+        if fully_used.contains(module.path.as_path()) || is_declaration_file(&module.path) {
+            continue;
+        }
+
+        for export in &module.exports {
+            if export.name == "*" || names.contains(export.name.as_str()) {
+                continue;
+            }
+            let (line, column) = line_col(&module.source, export.offset);
+            diagnostics.push(LintDiagnostic {
+                file: file.to_string(),
+                line,
+                column,
+                message: format!("Export '{}' is never imported", export.name),
+                rule: ModuleRule::NoUnusedExports.name().to_string(),
+                severity: Severity::Warning,
+                suggestion: Some("Remove the export or the unused code".to_string()),
+            });
+        }
+    }
+
+    diagnostics
+}
+
+/// Tarjan's algorithm over `edges[node] = [(target, import index)]`
+///
+/// Returns only components that form a cycle (more than one node, or a self-import).
+fn strongly_connected(edges: &[Vec<(usize, usize)>]) -> Vec<Vec<usize>> {
+    struct State<'e> {
+        edges: &'e [Vec<(usize, usize)>],
+        index: Vec<Option<usize>>,
+        lowlink: Vec<usize>,
+        on_stack: Vec<bool>,
+        stack: Vec<usize>,
+        next: usize,
+        components: Vec<Vec<usize>>,
+    }
+
+    fn visit(state: &mut State, node: usize) {
+        state.index[node] = Some(state.next);
+        state.lowlink[node] = state.next;
+        state.next += 1;
+        state.stack.push(node);
+        state.on_stack[node] = true;
+
+        for &(target, _) in &state.edges[node] {
+            match state.index[target] {
+                None => {
+                    visit(state, target);
+                    state.lowlink[node] = state.lowlink[node].min(state.lowlink[target]);
+                }
+                Some(target_index) if state.on_stack[target] => {
+                    state.lowlink[node] = state.lowlink[node].min(target_index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(state.lowlink[node]) == state.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = state.stack.pop() {
+                state.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            let is_cycle =
+                component.len() > 1 || state.edges[node].iter().any(|(target, _)| *target == node);
+            if is_cycle {
+                component.reverse();
+                state.components.push(component);
+            }
+        }
+    }
+
+    let mut state = State {
+        edges,
+        index: vec![None; edges.len()],
+        lowlink: vec![0; edges.len()],
+        on_stack: vec![false; edges.len()],
+        stack: Vec::new(),
+        next: 0,
+        components: Vec::new(),
+    };
+
+    for node in 0..edges.len() {
+        if state.index[node].is_none() {
+            visit(&mut state, node);
+        }
+    }
+
+    state.components
+}
+
+/// Breadth-first path from `from` to `to` inside one strongly connected component
+fn shortest_path(
+    edges: &[Vec<(usize, usize)>],
+    members: &HashSet<usize>,
+    from: usize,
+    to: usize,
+) -> Vec<usize> {
+    let mut previous: HashMap<usize, usize> = HashMap::new();
+    let mut queue = VecDeque::from([from]);
+    let mut seen = HashSet::from([from]);
+
+    while let Some(node) = queue.pop_front() {
+        if node == to {
+            break;
+        }
+        for &(target, _) in &edges[node] {
+            if members.contains(&target) && seen.insert(target) {
+                previous.insert(target, node);
+                queue.push_back(target);
+            }
+        }
+    }
+
+    let mut path = vec![to];
+    let mut current = to;
+    while current != from {
+        match previous.get(&current) {
+            Some(&prev) => {
+                path.push(prev);
+                current = prev;
+            }
+            None => break,
+        }
+    }
+    path.reverse();
+    path
+}
+
+/// Collects imports, exports, and reorderable import runs from a program.
+/// Also used by the dependency graph.
+#[derive(Default)]
+pub(crate) struct ModuleCollector {
+    pub(crate) imports: Vec<ModuleImport>,
+    pub(crate) exports: Vec<ModuleExport>,
+    import_runs: Vec<Vec<OrderedImport>>,
+    /// Local binding -> (specifier, imported name)
+    pub(crate) bindings: HashMap<String, (String, String)>,
+}
+
+impl ModuleCollector {
+    /// Collect top-level import and export statements; visit the program
+    /// afterwards to pick up dynamic imports
+    pub(crate) fn collect(&mut self, program: &Program) {
+        let mut run: Vec<OrderedImport> = Vec::new();
+
+        for stmt in &program.body {
+            let Statement::ImportDeclaration(import) = stmt else {
+                self.flush_run(&mut run);
+                self.collect_export(stmt);
+                continue;
+            };
+
+            let specifier = import.source.value.to_string();
+            let specifiers = import.specifiers.as_ref().filter(|s| !s.is_empty());
+            for spec in specifiers.into_iter().flatten() {
+                let imported = match spec {
+                    ImportDeclarationSpecifier::ImportSpecifier(s) => s.imported.name().to_string(),
+                    ImportDeclarationSpecifier::ImportDefaultSpecifier(_) => "default".to_string(),
+                    ImportDeclarationSpecifier::ImportNamespaceSpecifier(_) => "*".to_string(),
+                };
+                self.bindings
+                    .insert(spec.local().name.to_string(), (specifier.clone(), imported));
+            }
+
+            let usage = match specifiers {
+                Some(specs)
+                    if specs.iter().any(|s| {
+                        matches!(s, ImportDeclarationSpecifier::ImportNamespaceSpecifier(_))
+                    }) =>
+                {
+                    Usage::All
+                }
+                Some(specs) => Usage::Names(
+                    specs
+                        .iter()
+                        .map(|s| match s {
+                            ImportDeclarationSpecifier::ImportSpecifier(spec) => {
+                                spec.imported.name().to_string()
+                            }
+                            _ => "default".to_string(),
+                        })
+                        .collect(),
+                ),
+                None => Usage::Names(vec![]),
+            };
+
+            self.imports.push(ModuleImport {
+                specifier: specifier.clone(),
+                offset: import.span.start,
+                usage,
+                type_only: import.import_kind.is_type(),
+                dynamic: false,
+                resolved: None,
+            });
+
+            // Side-effect imports are order-sensitive, so they split runs
+            if specifiers.is_none() {
+                self.flush_run(&mut run);
+            } else {
+                run.push(OrderedImport {
+                    specifier,
+                    start: import.span.start,
+                    end: import.span.end,
+                });
+            }
+        }
+
+        self.flush_run(&mut run);
+    }
+
+    fn flush_run(&mut self, run: &mut Vec<OrderedImport>) {
+        if run.len() > 1 {
+            self.import_runs.push(std::mem::take(run));
+        } else {
+            run.clear();
+        }
+    }
+
+    fn collect_export(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::ExportNamedDeclaration(export) => {
+                if let Some(decl) = &export.declaration {
+                    self.collect_declaration(decl);
+                }
+                let kind = if export.source.is_some() {
+                    "reexport"
+                } else {
+                    "variable"
+                };
+                for spec in &export.specifiers {
+                    self.exports.push(ModuleExport {
+                        name: spec.exported.name().to_string(),
+                        kind,
+                        offset: spec.span.start,
+                    });
+                }
+                if let Some(source) = &export.source {
+                    self.imports.push(ModuleImport {
+                        specifier: source.value.to_string(),
+                        offset: export.span.start,
+                        usage: Usage::Names(
+                            export
+                                .specifiers
+                                .iter()
+                                .map(|s| s.local.name().to_string())
+                                .collect(),
+                        ),
+                        type_only: export.export_kind.is_type(),
+                        dynamic: false,
+                        resolved: None,
+                    });
+                }
+            }
+            Statement::ExportDefaultDeclaration(export) => {
+                let kind = match &export.declaration {
+                    ExportDefaultDeclarationKind::FunctionDeclaration(_) => "function",
+                    ExportDefaultDeclarationKind::ClassDeclaration(_) => "class",
+                    _ => "default",
+                };
+                self.exports.push(ModuleExport {
+                    name: "default".to_string(),
+                    kind,
+                    offset: export.span.start,
+                });
+            }
+            Statement::ExportAllDeclaration(export) => {
+                // A bare `export *` is recorded as `*`: it forwards the
+                // target's names rather than exporting one of its own
+                self.exports.push(ModuleExport {
+                    name: export
+                        .exported
+                        .as_ref()
+                        .map_or("*".to_string(), |e| e.name().to_string()),
+                    kind: "namespace",
+                    offset: export.span.start,
+                });
+                self.imports.push(ModuleImport {
+                    specifier: export.source.value.to_string(),
+                    offset: export.span.start,
+                    usage: Usage::All,
+                    type_only: export.export_kind.is_type(),
+                    dynamic: false,
+                    resolved: None,
+                });
+            }
+            _ => {}
+        }
+    }
+
+    fn collect_declaration(&mut self, decl: &Declaration) {
+        let kind = match decl {
+            Declaration::VariableDeclaration(var) => {
+                for declarator in &var.declarations {
+                    for id in declarator.id.get_binding_identifiers() {
+                        self.exports.push(ModuleExport {
+                            name: id.name.to_string(),
+                            kind: "variable",
+                            offset: id.span.start,
+                        });
+                    }
+                }
+                return;
+            }
+            Declaration::FunctionDeclaration(_) => "function",
+            Declaration::ClassDeclaration(_) => "class",
+            Declaration::TSTypeAliasDeclaration(_) => "type",
+            Declaration::TSInterfaceDeclaration(_) => "interface",
+            Declaration::TSEnumDeclaration(_) => "enum",
+            _ => "namespace",
+        };
+        if let Some(id) = decl.id() {
+            self.exports.push(ModuleExport {
+                name: id.name.to_string(),
+                kind,
+                offset: decl.span().start,
+            });
+        }
+    }
+
+    fn push_dynamic(&mut self, specifier: &str, offset: u32) {
+        self.imports.push(ModuleImport {
+            specifier: specifier.to_string(),
+            offset,
+            usage: Usage::All,
+            type_only: false,
+            dynamic: true,
+            resolved: None,
+        });
+    }
+}
+
+impl<'a> Visit<'a> for ModuleCollector {
+    fn visit_import_expression(&mut self, expr: &ImportExpression<'a>) {
+        if let Expression::StringLiteral(lit) = &expr.source {
+            self.push_dynamic(&lit.value, expr.span.start);
+        }
+        walk::walk_import_expression(self, expr);
+    }
+
+    fn visit_call_expression(&mut self, expr: &CallExpression<'a>) {
+        if let Expression::Identifier(id) = &expr.callee {
+            if id.name == "require" {
+                if let Some(Argument::StringLiteral(lit)) = expr.arguments.first() {
+                    self.push_dynamic(&lit.value, expr.span.start);
+                }
+            }
+        }
+        walk::walk_call_expression(self, expr);
+    }
+}
+
+/// Recursively collect JS/TS files under `root`, skipping dependencies and build output
+pub(crate) fn collect_project_files(root: &Path) -> Vec<PathBuf> {
+    let mut files = Vec::new();
+    let mut dirs = vec![root.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        let Ok(entries) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+
+            if file_type.is_dir() {
+                if !name.starts_with('.') && !SKIP_DIRS.contains(&name.as_ref()) {
+                    dirs.push(path);
+                }
+            } else if file_type.is_file() && is_module_file(&path) {
+                files.push(path);
+            }
+        }
+    }
+
+    files
+}
+
+pub(crate) fn is_module_file(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|e| e.to_str()),
+        Some("js" | "jsx" | "ts" | "tsx" | "mjs" | "cjs" | "mts" | "cts")
+    )
+}
+
+fn is_declaration_file(path: &Path) -> bool {
+    path.file_name()
+        .and_then(|n| n.to_str())
+        .is_some_and(|n| n.ends_with(".d.ts") || n.ends_with(".d.mts") || n.ends_with(".d.cts"))
+}
+
+/// Convert a byte offset to 1-indexed line and column numbers
+pub(crate) fn line_col(source: &str, offset: u32) -> (u32, u32) {
+    let mut line = 1u32;
+    let mut col = 1u32;
+
+    for (i, ch) in source.char_indices() {
+        if i as u32 >= offset {
+            break;
+        }
+        if ch == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+
+    (line, col)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn project(files: &[(&str, &str)]) -> TempDir {
+        let dir = TempDir::new().unwrap();
+        for (rel, content) in files {
+            let path = dir.path().join(rel);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, content).unwrap();
+        }
+        dir
+    }
+
+    fn file(dir: &TempDir, rel: &str) -> String {
+        dir.path().join(rel).to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_no_unused_exports() {
+        let dir = project(&[
+            ("src/main.ts", "import { used } from './lib';\nused();\n"),
+            (
+                "src/lib.ts",
+                "export function used() {}\nexport const unused = 1;\n",
+            ),
+        ]);
+        let linter = ModuleLinter::new(vec![ModuleRule::NoUnusedExports], dir.path());
+
+        let diagnostics = linter.lint_files(&[file(&dir, "src/lib.ts"), file(&dir, "src/main.ts")]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "no-unused-exports");
+        assert!(diagnostics[0].message.contains("'unused'"));
+        assert_eq!(diagnostics[0].line, 2);
+    }
+
+    #[test]
+    fn test_no_cycle_through_tsconfig_alias() {
+        let dir = project(&[
+            (
+                "tsconfig.json",
+                r#"{ "compilerOptions": { "paths": { "@/*": ["src/*"] } } }"#,
+            ),
+            (
+                "src/a.ts",
+                "import { b } from '@/b';\nexport const a = () => b;\n",
+            ),
+            (
+                "src/b.ts",
+                "import { a } from './a';\nexport const b = () => a;\n",
+            ),
+            (
+                "src/types.ts",
+                "import type { a } from './a';\nexport type T = typeof a;\n",
+            ),
+        ]);
+        let linter = ModuleLinter::new(vec![ModuleRule::NoCycle], dir.path());
+
+        let diagnostics = linter.lint_files(&[file(&dir, "src/a.ts")]);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, "no-cycle");
+        assert!(diagnostics[0]
+            .message
+            .contains("src/a.ts -> src/b.ts -> src/a.ts"));
+
+        // Type-only imports never participate in cycles
+        assert!(linter.lint_files(&[file(&dir, "src/types.ts")]).is_empty());
+    }
+
+    #[test]
+    fn test_config_and_suppressions() {
+        let dir = project(&[
+            ("src/main.ts", "import { a } from './lib';\na();\n"),
+            (
+                "src/lib.ts",
+                "export const a = 1;\n// uira-disable-next-line no-unused-exports\nexport const b = 2;\nexport const c = 3;\n",
+            ),
+        ]);
+        let config = LintConfig::from_levels([("no-unused-exports", "error")]).unwrap();
+        let linter = ModuleLinter::new(vec![], dir.path()).with_config(config);
+
+        let diagnostics = linter.lint_files(&[file(&dir, "src/lib.ts")]);
+        assert_eq!(diagnostics.len(), 1);
+        assert!(diagnostics[0].message.contains("'c'"));
+        assert_eq!(diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_import_order_and_fix() {
+        let source = "import { b } from './b';\nimport fs from 'node:fs';\nimport React from 'react';\nimport './polyfill';\nimport { z } from '../z';\nimport { a } from '../a';\n";
+        let dir = project(&[("src/main.ts", source)]);
+        let linter = ModuleLinter::new(vec![ModuleRule::ImportOrder], dir.path());
+
+        let diagnostics = linter.lint_files(&[file(&dir, "src/main.ts")]);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0].message,
+            "Import of 'node:fs' should come before './b'"
+        );
+        assert_eq!(diagnostics[0].line, 2);
+
+        let fixed = linter
+            .fix_source(&file(&dir, "src/main.ts"), source)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            fixed,
+            "import fs from 'node:fs';\nimport React from 'react';\nimport { b } from './b';\nimport './polyfill';\nimport { a } from '../a';\nimport { z } from '../z';\n"
+        );
+        assert_eq!(linter.fix_source("main.ts", &fixed).unwrap(), None);
+    }
+}
@@ -0,0 +1,355 @@
+//! Module specifier resolution for JS/TS projects
+//!
+//! Resolves relative imports and tsconfig `baseUrl`/`paths` aliases to files on disk.
+
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+/// Extensions tried, in order, when a specifier omits one
+const EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "cts", "d.ts", "js", "jsx", "mjs", "cjs"];
+
+/// Config files looked up by [`ModuleResolver::discover`]
+const CONFIG_FILES: &[&str] = &["tsconfig.json", "jsconfig.json"];
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TsConfig {
+    extends: Option<String>,
+    #[serde(default)]
+    compiler_options: CompilerOptions,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompilerOptions {
+    base_url: Option<String>,
+    paths: Option<HashMap<String, Vec<String>>>,
+}
+
+/// Resolves import specifiers to files, honoring tsconfig path aliases
+#[derive(Debug, Clone, Default)]
+pub struct ModuleResolver {
+    /// Directory that non-relative specifiers and `paths` targets resolve against
+    base_url: Option<PathBuf>,
+    /// `paths` entries as (pattern, targets), most specific pattern first
+    paths: Vec<(String, Vec<String>)>,
+}
+
+impl ModuleResolver {
+    /// Create a resolver that only handles relative specifiers
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Load `tsconfig.json` (or `jsconfig.json`) from `root` if present
+    ///
+    /// Falls back to a relative-only resolver when no config exists or it fails to load.
+    pub fn discover(root: &Path) -> Self {
+        CONFIG_FILES
+            .iter()
+            .map(|name| root.join(name))
+            .find(|path| path.is_file())
+            .and_then(|path| Self::from_tsconfig(&path).ok())
+            .unwrap_or_default()
+    }
+
+    /// Load resolution settings from a tsconfig file, following relative `extends`
+    pub fn from_tsconfig(path: &Path) -> Result<Self, String> {
+        Self::load_tsconfig(&absolute(path), 0)
+    }
+
+    fn load_tsconfig(path: &Path, depth: usize) -> Result<Self, String> {
+        if depth > 8 {
+            return Err(format!(
+                "tsconfig extends chain too deep at {}",
+                path.display()
+            ));
+        }
+
+        let raw = fs::read_to_string(path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        let config: TsConfig = serde_json::from_str(&strip_jsonc(&raw))
+            .map_err(|e| format!("Failed to parse {}: {}", path.display(), e))?;
+        let dir = path.parent().unwrap_or(Path::new("."));
+
+        // Only relative `extends` are followed; package configs carry no project aliases
+        let mut resolver = match config.extends.as_deref() {
+            Some(parent) if parent.starts_with('.') => {
+                let parent = if parent.ends_with(".json") {
+                    parent.to_string()
+                } else {
+                    format!("{}.json", parent)
+                };
+                Self::load_tsconfig(&normalize(&dir.join(parent)), depth + 1)?
+            }
+            _ => Self::default(),
+        };
+
+        let options = config.compiler_options;
+        if let Some(base_url) = options.base_url {
+            resolver.base_url = Some(normalize(&dir.join(base_url)));
+        }
+        if let Some(paths) = options.paths {
+            // Without baseUrl, `paths` targets are relative to the config that declares them
+            if resolver.base_url.is_none() {
+                resolver.base_url = Some(dir.to_path_buf());
+            }
+            let mut paths: Vec<_> = paths.into_iter().collect();
+            paths.sort_by(|(a, _), (b, _)| {
+                pattern_prefix(b)
+                    .len()
+                    .cmp(&pattern_prefix(a).len())
+                    .then_with(|| a.cmp(b))
+            });
+            resolver.paths = paths;
+        }
+
+        Ok(resolver)
+    }
+
+    /// Whether the resolver has any alias configuration
+    pub fn has_aliases(&self) -> bool {
+        self.base_url.is_some() || !self.paths.is_empty()
+    }
+
+    /// Resolve `specifier` as imported from `from_file` to a file on disk
+    ///
+    /// Returns `None` for packages and anything else that does not map to a local file.
+    pub fn resolve(&self, from_file: &Path, specifier: &str) -> Option<PathBuf> {
+        if is_relative(specifier) {
+            let dir = from_file.parent().unwrap_or(Path::new("."));
+            return resolve_file(&absolute(&dir.join(specifier)));
+        }
+        self.resolve_alias(specifier)
+    }
+
+    fn resolve_alias(&self, specifier: &str) -> Option<PathBuf> {
+        let base_url = self.base_url.as_ref()?;
+
+        for (pattern, targets) in &self.paths {
+            let Some(captured) = match_pattern(pattern, specifier) else {
+                continue;
+            };
+            for target in targets {
+                let candidate = base_url.join(target.replacen('*', captured, 1));
+                if let Some(found) = resolve_file(&normalize(&candidate)) {
+                    return Some(found);
+                }
+            }
+        }
+
+        resolve_file(&normalize(&base_url.join(specifier)))
+    }
+}
+
+/// Whether a specifier is a relative path (`./x`, `../x`, `.`)
+pub fn is_relative(specifier: &str) -> bool {
+    specifier == "."
+        || specifier == ".."
+        || specifier.starts_with("./")
+        || specifier.starts_with("../")
+}
+
+/// Try `path` as a file, with each known extension, then as a directory index
+fn resolve_file(path: &Path) -> Option<PathBuf> {
+    if path.is_file() {
+        return Some(path.to_path_buf());
+    }
+
+    let file_name = path.file_name()?.to_str()?;
+
+    // TS ESM code imports `./foo.js` while the file on disk is `foo.ts`
+    for (js, ts) in [
+        ("js", &["ts", "tsx"][..]),
+        ("mjs", &["mts"]),
+        ("cjs", &["cts"]),
+    ] {
+        if let Some(stem) = file_name.strip_suffix(&format!(".{}", js)) {
+            for ext in ts {
+                let candidate = path.with_file_name(format!("{}.{}", stem, ext));
+                if candidate.is_file() {
+                    return Some(candidate);
+                }
+            }
+        }
+    }
+
+    for ext in EXTENSIONS {
+        let candidate = path.with_file_name(format!("{}.{}", file_name, ext));
+        if candidate.is_file() {
+            return Some(candidate);
+        }
+    }
+
+    if path.is_dir() {
+        for ext in EXTENSIONS {
+            let candidate = path.join(format!("index.{}", ext));
+            if candidate.is_file() {
+                return Some(candidate);
+            }
+        }
+    }
+
+    None
+}
+
+fn pattern_prefix(pattern: &str) -> &str {
+    pattern.split('*').next().unwrap_or(pattern)
+}
+
+/// Match a tsconfig `paths` pattern, returning the text captured by `*`
+fn match_pattern<'s>(pattern: &str, specifier: &'s str) -> Option<&'s str> {
+    match pattern.split_once('*') {
+        Some((prefix, suffix)) => specifier.strip_prefix(prefix)?.strip_suffix(suffix),
+        None => (pattern == specifier).then_some(""),
+    }
+}
+
+/// Make a path absolute against the current directory and normalize it
+pub(crate) fn absolute(path: &Path) -> PathBuf {
+    if path.is_absolute() {
+        normalize(path)
+    } else {
+        let cwd = std::env::current_dir().unwrap_or_default();
+        normalize(&cwd.join(path))
+    }
+}
+
+/// Lexically remove `.` and `..` components without touching the filesystem
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !normalized.pop() {
+                    normalized.push("..");
+                }
+            }
+            other => normalized.push(other.as_os_str()),
+        }
+    }
+    normalized
+}
+
+/// Strip comments and trailing commas so tsconfig files parse as plain JSON
+pub(crate) fn strip_jsonc(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut out = String::with_capacity(input.len());
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        match c {
+            '"' => {
+                out.push(c);
+                i += 1;
+                while i < chars.len() {
+                    out.push(chars[i]);
+                    if chars[i] == '\\' && i + 1 < chars.len() {
+                        out.push(chars[i + 1]);
+                        i += 2;
+                        continue;
+                    }
+                    i += 1;
+                    if chars[i - 1] == '"' {
+                        break;
+                    }
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'/') => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            }
+            '/' if chars.get(i + 1) == Some(&'*') => {
+                i += 2;
+                while i + 1 < chars.len() && !(chars[i] == '*' && chars[i + 1] == '/') {
+                    i += 1;
+                }
+                i += 2;
+            }
+            ',' => {
+                let next = chars[i + 1..].iter().find(|c| !c.is_whitespace());
+                if !matches!(next, Some('}') | Some(']')) {
+                    out.push(c);
+                }
+                i += 1;
+            }
+            _ => {
+                out.push(c);
+                i += 1;
+            }
+        }
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write(root: &Path, rel: &str, content: &str) -> PathBuf {
+        let path = root.join(rel);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_resolve_relative() {
+        let dir = TempDir::new().unwrap();
+        let main = write(dir.path(), "src/main.ts", "");
+        let util = write(dir.path(), "src/util.ts", "");
+        let index = write(dir.path(), "src/lib/index.tsx", "");
+
+        let resolver = ModuleResolver::new();
+        assert_eq!(resolver.resolve(&main, "./util"), Some(util.clone()));
+        assert_eq!(resolver.resolve(&main, "./util.js"), Some(util));
+        assert_eq!(resolver.resolve(&main, "./lib"), Some(index));
+        assert_eq!(resolver.resolve(&main, "react"), None);
+    }
+
+    #[test]
+    fn test_resolve_tsconfig_paths() {
+        let dir = TempDir::new().unwrap();
+        write(
+            dir.path(),
+            "tsconfig.base.json",
+            r#"{
+  // shared settings
+  "compilerOptions": {
+    "baseUrl": ".",
+    "paths": { "@/*": ["src/*"], "@utils": ["src/utils/index.ts"], },
+  },
+}"#,
+        );
+        write(
+            dir.path(),
+            "tsconfig.json",
+            r#"{ "extends": "./tsconfig.base", /* app */ "include": ["src"] }"#,
+        );
+        let main = write(dir.path(), "src/main.ts", "");
+        let button = write(dir.path(), "src/components/button.tsx", "");
+        let utils = write(dir.path(), "src/utils/index.ts", "");
+
+        let resolver = ModuleResolver::discover(dir.path());
+        assert!(resolver.has_aliases());
+        assert_eq!(resolver.resolve(&main, "@/components/button"), Some(button));
+        assert_eq!(resolver.resolve(&main, "@utils"), Some(utils.clone()));
+        assert_eq!(resolver.resolve(&main, "src/utils"), Some(utils));
+        assert_eq!(resolver.resolve(&main, "lodash"), None);
+    }
+
+    #[test]
+    fn test_strip_jsonc_keeps_strings() {
+        let stripped = strip_jsonc(r#"{"a": "http://x/*y*/", "b": [1,],} // c"#);
+        let value: serde_json::Value = serde_json::from_str(&stripped).unwrap();
+        assert_eq!(value["a"], "http://x/*y*/");
+        assert_eq!(value["b"], serde_json::json!([1]));
+    }
+}
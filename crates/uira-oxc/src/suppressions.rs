@@ -0,0 +1,85 @@
+//! Inline suppression comments
+//!
+//! `// uira-disable-next-line` silences every rule on the following line;
+//! `// uira-disable-next-line no-console, no-eval -- reason` only the listed ones.
+
+use crate::linter::LintDiagnostic;
+use oxc::ast::Comment;
+use std::collections::{HashMap, HashSet};
+
+const DISABLE_NEXT_LINE: &str = "uira-disable-next-line";
+
+/// Lines silenced by suppression comments in one file
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    /// Line -> suppressed rules (`None` = all rules)
+    lines: HashMap<u32, Option<HashSet<String>>>,
+}
+
+impl Suppressions {
+    /// Collect suppression directives from a parsed file's comments
+    pub fn from_comments(source: &str, comments: &[Comment]) -> Self {
+        let mut suppressions = Self::default();
+
+        for comment in comments {
+            let span = comment.content_span();
+            let Some(text) = source.get(span.start as usize..span.end as usize) else {
+                continue;
+            };
+            let Some(rest) = text.trim().strip_prefix(DISABLE_NEXT_LINE) else {
+                continue;
+            };
+            if rest.starts_with(|c: char| !c.is_whitespace()) {
+                continue;
+            }
+
+            let rest = rest.split_once("--").map_or(rest, |(rules, _)| rules);
+            let rules: HashSet<String> = rest
+                .split(|c: char| c == ',' || c.is_whitespace())
+                .filter(|r| !r.is_empty())
+                .map(String::from)
+                .collect();
+
+            let line = line_of(source, comment.span.end) + 1;
+            let entry = suppressions
+                .lines
+                .entry(line)
+                .or_insert(Some(HashSet::new()));
+            match (entry.as_mut(), rules.is_empty()) {
+                (Some(existing), false) => existing.extend(rules),
+                _ => *entry = None,
+            }
+        }
+
+        suppressions
+    }
+
+    /// Whether a diagnostic is silenced by a suppression comment
+    pub fn is_suppressed(&self, diagnostic: &LintDiagnostic) -> bool {
+        match self.lines.get(&diagnostic.line) {
+            Some(None) => true,
+            Some(Some(rules)) => rules.contains(&diagnostic.rule),
+            None => false,
+        }
+    }
+
+    /// Drop suppressed diagnostics
+    pub fn apply(&self, diagnostics: Vec<LintDiagnostic>) -> Vec<LintDiagnostic> {
+        if self.lines.is_empty() {
+            return diagnostics;
+        }
+        diagnostics
+            .into_iter()
+            .filter(|d| !self.is_suppressed(d))
+            .collect()
+    }
+}
+
+fn line_of(source: &str, offset: u32) -> u32 {
+    let end = (offset as usize).min(source.len());
+    source.as_bytes()[..end]
+        .iter()
+        .filter(|&&b| b == b'\n')
+        .count() as u32
+        + 1
+}
@@ -1,15 +1,15 @@
 //! Anthropic (Claude) client implementation
 
-use crate::auth::CredentialStore;
+use crate::auth::{CredentialStore, StoredCredential};
+use crate::token_refresh::{OAuthToken, RefreshedToken, TokenEndpoint};
+use crate::{model_list, ModelInfo, ModelList};
 use async_trait::async_trait;
-use chrono::Utc;
 use futures::StreamExt;
 use reqwest::Client;
 use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{Mutex, RwLock};
 use uira_core::{
     ContentBlock, ContentDelta, Message, MessageContent, MessageDelta, ModelResponse, Role,
     StopReason, StreamChunk, StreamError, StreamMessageStart, TokenUsage, ToolSpec,
@@ -17,21 +17,20 @@ use uira_core::{
 
 mod beta_features;
 mod error_classify;
-mod payload_log;
 mod response_handling;
 mod retry;
 mod turn_validation;
 
 pub use beta_features::BetaFeatures;
 pub use error_classify::classify_error;
-pub use payload_log::{PayloadLogEvent, PayloadLogger};
-pub use retry::{with_retry, RetryConfig};
+pub use response_handling::PartialJsonParser;
+pub use retry::{count_retries, with_retry, RetryConfig};
 pub use turn_validation::validate_anthropic_turns;
 
 use self::response_handling::{extract_retry_after, parse_error_body};
 use crate::{
     image::normalize_image_source, traits::ModelResult, traits::ResponseStream, ModelClient,
-    ProviderConfig, ProviderError,
+    PayloadLogger, ProviderConfig, ProviderError, ReasoningOverride,
 };
 
 const ANTHROPIC_VERSION: &str = "2023-06-01";
@@ -39,8 +38,6 @@ const DEFAULT_MAX_TOKENS: usize = 8192;
 const MAX_SSE_BUFFER: usize = 10 * 1024 * 1024; // 10MB SSE buffer cap
 const PROVIDER_NAME: &str = "anthropic";
 const ENV_ANTHROPIC_API_KEY: &str = "ANTHROPIC_API_KEY";
-/// Buffer time before token expiration to trigger refresh (5 minutes)
-const TOKEN_REFRESH_BUFFER_SECS: i64 = 300;
 /// OAuth client ID (same as Claude Code CLI)
 const OAUTH_CLIENT_ID: &str = "9d1c250a-e61b-44d9-88ed-5944d1962f5e";
 /// User agent to masquerade as Claude Code CLI
@@ -51,31 +48,84 @@ const TOOL_PREFIX: &str = "mcp_";
 const CLAUDE_CODE_IDENTITY: &str = "You are Claude Code, Anthropic's official CLI for Claude.";
 
 /// Credential source for the client
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 enum CredentialSource {
-    /// OAuth credentials from credential store
-    OAuth {
-        access_token: SecretString,
-        refresh_token: Option<SecretString>,
-        expires_at: Option<i64>,
-    },
+    /// OAuth credentials, shared with clones so they refresh once
+    OAuth(Arc<OAuthToken>),
     /// API key from config or environment variable
     ApiKey(SecretString),
 }
 
+/// Anthropic's OAuth token endpoint
+struct AnthropicTokenEndpoint {
+    client: Client,
+}
+
+#[async_trait]
+impl TokenEndpoint for AnthropicTokenEndpoint {
+    async fn refresh(&self, refresh_token: &SecretString) -> Result<RefreshedToken, ProviderError> {
+        #[derive(Serialize)]
+        struct TokenRefreshRequest<'a> {
+            grant_type: &'a str,
+            refresh_token: &'a str,
+            client_id: &'a str,
+        }
+
+        let response = self
+            .client
+            .post("https://console.anthropic.com/v1/oauth/token")
+            .header("Content-Type", "application/json")
+            .json(&TokenRefreshRequest {
+                grant_type: "refresh_token",
+                refresh_token: refresh_token.expose_secret(),
+                client_id: OAUTH_CLIENT_ID,
+            })
+            .send()
+            .await
+            .map_err(ProviderError::Network)?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            if status.is_server_error() {
+                return Err(ProviderError::Unavailable {
+                    provider: "anthropic".to_string(),
+                });
+            }
+            let body = response.text().await.unwrap_or_default();
+            return Err(ProviderError::Configuration(format!(
+                "Token refresh failed ({}): {}",
+                status, body
+            )));
+        }
+
+        #[derive(Deserialize)]
+        struct TokenResponse {
+            access_token: String,
+            refresh_token: Option<String>,
+            expires_in: Option<i64>,
+        }
+
+        let token_response: TokenResponse = response.json().await.map_err(|e| {
+            ProviderError::InvalidResponse(format!("Invalid token response: {}", e))
+        })?;
+
+        Ok(RefreshedToken {
+            access_token: token_response.access_token,
+            refresh_token: token_response.refresh_token,
+            expires_in: token_response.expires_in,
+        })
+    }
+}
+
 /// Anthropic API client
 pub struct AnthropicClient {
     client: Client,
     config: ProviderConfig,
-    /// Current credential source (wrapped in RwLock for token refresh)
-    credential: Arc<RwLock<CredentialSource>>,
-    refresh_lock: Mutex<()>,
+    credential: CredentialSource,
 }
 
 impl AnthropicClient {
     pub fn new(config: ProviderConfig) -> Result<Self, ProviderError> {
-        let credential = Self::load_credential(&config)?;
-
         let mut headers = reqwest::header::HeaderMap::new();
         headers.insert("anthropic-version", ANTHROPIC_VERSION.parse().unwrap());
         headers.insert("content-type", "application/json".parse().unwrap());
@@ -87,11 +137,12 @@ impl AnthropicClient {
             .timeout(timeout)
             .build()?;
 
+        let credential = Self::load_credential(&config, &client)?;
+
         Ok(Self {
             client,
             config,
-            credential: Arc::new(RwLock::new(credential)),
-            refresh_lock: Mutex::new(()),
+            credential,
         })
     }
 
@@ -102,58 +153,50 @@ impl AnthropicClient {
     }
 
     /// Wrap a key string as the correct credential source based on its format.
-    fn credential_from_key(key: SecretString) -> CredentialSource {
+    fn credential_from_key(key: SecretString, client: &Client) -> CredentialSource {
         if Self::is_oauth_token(key.expose_secret()) {
             tracing::debug!("Detected OAuth access token from key prefix");
-            CredentialSource::OAuth {
-                access_token: key,
-                refresh_token: None,
-                expires_at: None,
-            }
+            CredentialSource::OAuth(Arc::new(
+                OAuthToken::new(PROVIDER_NAME, key, None, None, Self::token_endpoint(client))
+                    .in_memory(),
+            ))
         } else {
             CredentialSource::ApiKey(key)
         }
     }
 
-    fn load_credential(config: &ProviderConfig) -> Result<CredentialSource, ProviderError> {
+    fn token_endpoint(client: &Client) -> Arc<dyn TokenEndpoint> {
+        Arc::new(AnthropicTokenEndpoint {
+            client: client.clone(),
+        })
+    }
+
+    fn load_credential(
+        config: &ProviderConfig,
+        client: &Client,
+    ) -> Result<CredentialSource, ProviderError> {
         // First check for API key / OAuth token (higher priority for reliability)
         if let Some(api_key) = &config.api_key {
             tracing::debug!("Using credential from config");
-            return Ok(Self::credential_from_key(api_key.clone()));
+            return Ok(Self::credential_from_key(api_key.clone(), client));
         }
 
         if let Ok(key) = std::env::var(ENV_ANTHROPIC_API_KEY) {
             tracing::debug!("Using credential from ANTHROPIC_API_KEY env var");
-            return Ok(Self::credential_from_key(SecretString::from(key)));
+            return Ok(Self::credential_from_key(SecretString::from(key), client));
         }
 
         // Fall back to stored credentials (OAuth or stored API key)
         tracing::debug!("No API key found, checking stored credentials");
         if let Ok(store) = CredentialStore::load() {
             if let Some(cred) = store.get(PROVIDER_NAME) {
-                use crate::auth::secrecy::ExposeSecret as AuthExposeSecret;
-                use crate::auth::StoredCredential;
-                match cred {
-                    StoredCredential::OAuth {
-                        access_token,
-                        refresh_token,
-                        expires_at,
-                    } => {
-                        return Ok(CredentialSource::OAuth {
-                            access_token: SecretString::from(
-                                access_token.expose_secret().to_string(),
-                            ),
-                            refresh_token: refresh_token
-                                .as_ref()
-                                .map(|t| SecretString::from(t.expose_secret().to_string())),
-                            expires_at: *expires_at,
-                        });
-                    }
-                    StoredCredential::ApiKey { key } => {
-                        return Ok(CredentialSource::ApiKey(SecretString::from(
-                            key.expose_secret().to_string(),
-                        )));
-                    }
+                if let StoredCredential::ApiKey { key } = cred {
+                    return Ok(CredentialSource::ApiKey(key.clone()));
+                }
+                if let Some(token) =
+                    OAuthToken::from_stored(PROVIDER_NAME, cred, Self::token_endpoint(client))
+                {
+                    return Ok(CredentialSource::OAuth(Arc::new(token)));
                 }
             }
         }
@@ -164,163 +207,10 @@ impl AnthropicClient {
         ))
     }
 
-    fn is_token_expired(expires_at: Option<i64>) -> bool {
-        match expires_at {
-            Some(exp) => {
-                let now = Utc::now().timestamp();
-                // Handle both seconds and milliseconds formats
-                let exp_secs = if exp > 1_000_000_000_000 {
-                    exp / 1000 // Convert milliseconds to seconds
-                } else {
-                    exp
-                };
-                now >= (exp_secs - TOKEN_REFRESH_BUFFER_SECS)
-            }
-            None => false,
-        }
-    }
-
-    async fn refresh_token_if_needed(&self) -> Result<(), ProviderError> {
-        let might_need_refresh = {
-            let credential = self.credential.read().await;
-
-            if let CredentialSource::OAuth { expires_at, .. } = &*credential {
-                Self::is_token_expired(*expires_at)
-            } else {
-                false
-            }
-        };
-
-        if !might_need_refresh {
-            return Ok(());
-        }
-
-        let _guard = self.refresh_lock.lock().await;
-
-        let (needs_refresh, refresh_token_opt) = {
-            let credential = self.credential.read().await;
-
-            if let CredentialSource::OAuth {
-                refresh_token,
-                expires_at,
-                ..
-            } = &*credential
-            {
-                (Self::is_token_expired(*expires_at), refresh_token.clone())
-            } else {
-                (false, None)
-            }
-        };
-
-        if needs_refresh {
-            if let Some(refresh_token) = refresh_token_opt {
-                self.do_token_refresh(&refresh_token).await?;
-            } else {
-                return Err(ProviderError::Configuration(
-                    "OAuth token expired and no refresh token available".into(),
-                ));
-            }
-        }
-
-        Ok(())
-    }
-
-    async fn do_token_refresh(&self, refresh_token: &SecretString) -> Result<(), ProviderError> {
-        #[derive(Serialize)]
-        struct TokenRefreshRequest<'a> {
-            grant_type: &'a str,
-            refresh_token: &'a str,
-            client_id: &'a str,
-        }
-
-        let response = self
-            .client
-            .post("https://console.anthropic.com/v1/oauth/token")
-            .header("Content-Type", "application/json")
-            .json(&TokenRefreshRequest {
-                grant_type: "refresh_token",
-                refresh_token: refresh_token.expose_secret(),
-                client_id: OAUTH_CLIENT_ID,
-            })
-            .send()
-            .await
-            .map_err(ProviderError::Network)?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-            if status.is_server_error() {
-                return Err(ProviderError::Unavailable {
-                    provider: "anthropic".to_string(),
-                });
-            }
-            let body = response.text().await.unwrap_or_default();
-            return Err(ProviderError::Configuration(format!(
-                "Token refresh failed ({}): {}",
-                status, body
-            )));
-        }
-
-        #[derive(Deserialize)]
-        struct TokenResponse {
-            access_token: String,
-            refresh_token: Option<String>,
-            expires_in: Option<i64>,
-        }
-
-        let token_response: TokenResponse = response.json().await.map_err(|e| {
-            ProviderError::InvalidResponse(format!("Invalid token response: {}", e))
-        })?;
-
-        let expires_at = token_response
-            .expires_in
-            .map(|exp| Utc::now().timestamp() + exp);
-
-        let mut credential = self.credential.write().await;
-        *credential = CredentialSource::OAuth {
-            access_token: SecretString::from(token_response.access_token.clone()),
-            refresh_token: token_response
-                .refresh_token
-                .clone()
-                .map(SecretString::from)
-                .or_else(|| Some(refresh_token.clone())),
-            expires_at,
-        };
-
-        let old_refresh_token_str = refresh_token.expose_secret().to_string();
-        if let Ok(mut store) = CredentialStore::load() {
-            use crate::auth::StoredCredential;
-            store.insert(
-                PROVIDER_NAME.to_string(),
-                StoredCredential::OAuth {
-                    access_token: crate::auth::secrecy::SecretString::from(
-                        token_response.access_token,
-                    ),
-                    refresh_token: token_response
-                        .refresh_token
-                        .clone()
-                        .map(crate::auth::secrecy::SecretString::from)
-                        .or_else(|| {
-                            Some(crate::auth::secrecy::SecretString::from(
-                                old_refresh_token_str.clone(),
-                            ))
-                        }),
-                    expires_at,
-                },
-            );
-            if let Err(e) = store.save() {
-                tracing::error!("Failed to save credential store after token refresh: {}", e);
-            }
-        }
-
-        Ok(())
-    }
-
     async fn get_auth_headers(&self) -> Result<Vec<(&'static str, String)>, ProviderError> {
-        self.refresh_token_if_needed().await?;
-
-        let credential = self.credential.read().await;
-        match &*credential {
-            CredentialSource::OAuth { access_token, .. } => {
+        match &self.credential {
+            CredentialSource::OAuth(token) => {
+                let access_token = token.access_token().await?;
                 let beta = BetaFeatures::oauth_default().to_header_value();
                 Ok(vec![
                     (
@@ -338,9 +228,23 @@ impl AnthropicClient {
         }
     }
 
-    async fn is_using_oauth(&self) -> bool {
-        let credential = self.credential.read().await;
-        matches!(&*credential, CredentialSource::OAuth { .. })
+    fn is_using_oauth(&self) -> bool {
+        matches!(self.credential, CredentialSource::OAuth(_))
+    }
+
+    /// Run `request`, refreshing a rejected OAuth token and trying once more
+    async fn with_token_refresh<T, F, Fut>(&self, request: F) -> Result<T, ProviderError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, ProviderError>>,
+    {
+        match &self.credential {
+            CredentialSource::OAuth(token) => token.retry_if_rejected(request).await,
+            CredentialSource::ApiKey(_) => {
+                let mut request = request;
+                request().await
+            }
+        }
     }
 
     fn build_request(
@@ -583,68 +487,70 @@ impl ModelClient for AnthropicClient {
             max_attempts: self.config.max_retries.unwrap_or(3),
             ..RetryConfig::default()
         };
-        let logger = PayloadLogger::from_env();
+        let logger = PayloadLogger::for_provider(PROVIDER_NAME);
 
-        with_retry(&retry_config, || async {
-            let auth_headers = self.get_auth_headers().await?;
-            let is_oauth = self.is_using_oauth().await;
+        self.with_token_refresh(|| {
+            with_retry(&retry_config, || async {
+                let auth_headers = self.get_auth_headers().await?;
+                let is_oauth = self.is_using_oauth();
 
-            let tools_for_request = if is_oauth {
-                Self::prefix_tool_names(tools)
-            } else {
-                tools.to_vec()
-            };
+                let tools_for_request = if is_oauth {
+                    Self::prefix_tool_names(tools)
+                } else {
+                    tools.to_vec()
+                };
 
-            let request =
-                self.build_request(&validated_messages, &tools_for_request, false, is_oauth)?;
-            let url = if is_oauth {
-                format!("{}/v1/messages?beta=true", self.base_url())
-            } else {
-                format!("{}/v1/messages", self.base_url())
-            };
-
-            tracing::debug!(
-                "AnthropicClient::chat: base_url={}, full_url={}, is_oauth={}",
-                self.base_url(),
-                url,
-                is_oauth
-            );
-
-            if let Ok(request_json) = serde_json::to_value(&request) {
-                logger.log_request(None, PROVIDER_NAME, &self.config.model, &request_json);
-            }
+                let request =
+                    self.build_request(&validated_messages, &tools_for_request, false, is_oauth)?;
+                let url = if is_oauth {
+                    format!("{}/v1/messages?beta=true", self.base_url())
+                } else {
+                    format!("{}/v1/messages", self.base_url())
+                };
 
-            let mut req = self.client.post(&url);
-            for (key, value) in &auth_headers {
-                req = req.header(*key, value);
-            }
-            let response = req.json(&request).send().await?;
+                tracing::debug!(
+                    "AnthropicClient::chat: base_url={}, full_url={}, is_oauth={}",
+                    self.base_url(),
+                    url,
+                    is_oauth
+                );
 
-            if !response.status().is_success() {
-                let status = response.status().as_u16();
-                let retry_after = extract_retry_after(response.headers());
-                let body = parse_error_body(response).await;
+                if let Ok(request_json) = serde_json::to_value(&request) {
+                    logger.log_request(None, PROVIDER_NAME, &self.config.model, &request_json);
+                }
 
-                let mut error = classify_error(PROVIDER_NAME, status, &body);
-                if let ProviderError::RateLimited {
-                    ref mut retry_after_ms,
-                } = error
-                {
-                    if let Some(ra) = retry_after {
-                        *retry_after_ms = ra;
+                let mut req = self.client.post(&url);
+                for (key, value) in &auth_headers {
+                    req = req.header(*key, value);
+                }
+                let response = req.json(&request).send().await?;
+
+                if !response.status().is_success() {
+                    let status = response.status().as_u16();
+                    let retry_after = extract_retry_after(response.headers());
+                    let body = parse_error_body(response).await;
+
+                    let mut error = classify_error(PROVIDER_NAME, status, &body);
+                    if let ProviderError::RateLimited {
+                        ref mut retry_after_ms,
+                    } = error
+                    {
+                        if let Some(ra) = retry_after {
+                            *retry_after_ms = ra;
+                        }
                     }
+                    return Err(error);
                 }
-                return Err(error);
-            }
 
-            let api_response: AnthropicResponse = response.json().await?;
-            let mut model_response = self.convert_response(api_response);
+                let api_response: AnthropicResponse = response.json().await?;
+                let mut model_response = self.convert_response(api_response);
 
-            if is_oauth {
-                Self::strip_tool_prefix_from_response(&mut model_response);
-            }
+                if is_oauth {
+                    Self::strip_tool_prefix_from_response(&mut model_response);
+                }
 
-            Ok(model_response)
+                Ok(model_response)
+            })
         })
         .await
     }
@@ -659,56 +565,63 @@ impl ModelClient for AnthropicClient {
             max_attempts: self.config.max_retries.unwrap_or(3),
             ..RetryConfig::default()
         };
-        let logger = PayloadLogger::from_env();
-
-        let (response, is_oauth) = with_retry(&retry_config, || async {
-            let auth_headers = self.get_auth_headers().await?;
-            let is_oauth = self.is_using_oauth().await;
-
-            let tools_for_request = if is_oauth {
-                Self::prefix_tool_names(tools)
-            } else {
-                tools.to_vec()
-            };
+        let logger = PayloadLogger::for_provider(PROVIDER_NAME);
+
+        let (response, is_oauth) = self
+            .with_token_refresh(|| {
+                with_retry(&retry_config, || async {
+                    let auth_headers = self.get_auth_headers().await?;
+                    let is_oauth = self.is_using_oauth();
+
+                    let tools_for_request = if is_oauth {
+                        Self::prefix_tool_names(tools)
+                    } else {
+                        tools.to_vec()
+                    };
 
-            let request =
-                self.build_request(&validated_messages, &tools_for_request, true, is_oauth)?;
-            let url = if is_oauth {
-                format!("{}/v1/messages?beta=true", self.base_url())
-            } else {
-                format!("{}/v1/messages", self.base_url())
-            };
+                    let request = self.build_request(
+                        &validated_messages,
+                        &tools_for_request,
+                        true,
+                        is_oauth,
+                    )?;
+                    let url = if is_oauth {
+                        format!("{}/v1/messages?beta=true", self.base_url())
+                    } else {
+                        format!("{}/v1/messages", self.base_url())
+                    };
 
-            if let Ok(request_json) = serde_json::to_value(&request) {
-                logger.log_request(None, PROVIDER_NAME, &self.config.model, &request_json);
-            }
+                    if let Ok(request_json) = serde_json::to_value(&request) {
+                        logger.log_request(None, PROVIDER_NAME, &self.config.model, &request_json);
+                    }
 
-            let mut req = self.client.post(&url);
-            for (key, value) in &auth_headers {
-                req = req.header(*key, value);
-            }
-            let response = req.json(&request).send().await?;
+                    let mut req = self.client.post(&url);
+                    for (key, value) in &auth_headers {
+                        req = req.header(*key, value);
+                    }
+                    let response = req.json(&request).send().await?;
 
-            if !response.status().is_success() {
-                let status = response.status().as_u16();
-                let retry_after = extract_retry_after(response.headers());
-                let body = parse_error_body(response).await;
+                    if !response.status().is_success() {
+                        let status = response.status().as_u16();
+                        let retry_after = extract_retry_after(response.headers());
+                        let body = parse_error_body(response).await;
 
-                let mut error = classify_error(PROVIDER_NAME, status, &body);
-                if let ProviderError::RateLimited {
-                    ref mut retry_after_ms,
-                } = error
-                {
-                    if let Some(ra) = retry_after {
-                        *retry_after_ms = ra;
+                        let mut error = classify_error(PROVIDER_NAME, status, &body);
+                        if let ProviderError::RateLimited {
+                            ref mut retry_after_ms,
+                        } = error
+                        {
+                            if let Some(ra) = retry_after {
+                                *retry_after_ms = ra;
+                            }
+                        }
+                        return Err(error);
                     }
-                }
-                return Err(error);
-            }
 
-            Ok((response, is_oauth))
-        })
-        .await?;
+                    Ok((response, is_oauth))
+                })
+            })
+            .await?;
 
         tracing::debug!("Starting SSE stream from Anthropic API");
         let byte_stream = response.bytes_stream();
@@ -827,6 +740,42 @@ impl ModelClient for AnthropicClient {
         Ok(Box::pin(stream))
     }
 
+    async fn list_models(&self) -> ModelResult<ModelList> {
+        #[derive(Deserialize)]
+        struct ModelsResponse {
+            data: Vec<AnthropicModel>,
+        }
+
+        #[derive(Deserialize)]
+        struct AnthropicModel {
+            id: String,
+            display_name: Option<String>,
+        }
+
+        let url = format!("{}/v1/models?limit=1000", self.base_url());
+        let (response, limits): (ModelsResponse, _) = self
+            .with_token_refresh(|| async {
+                let mut req = self.client.get(&url);
+                for (key, value) in self.get_auth_headers().await? {
+                    req = req.header(key, value);
+                }
+                model_list::fetch(PROVIDER_NAME, req).await
+            })
+            .await?;
+
+        Ok(ModelList {
+            models: response
+                .data
+                .into_iter()
+                .map(|model| ModelInfo {
+                    id: model.id,
+                    display_name: model.display_name,
+                })
+                .collect(),
+            limits,
+        })
+    }
+
     fn supports_tools(&self) -> bool {
         true
     }
@@ -843,6 +792,17 @@ impl ModelClient for AnthropicClient {
     fn provider(&self) -> &str {
         "anthropic"
     }
+
+    fn with_reasoning(&self, reasoning: &ReasoningOverride) -> Option<Arc<dyn ModelClient>> {
+        reasoning.budget_tokens?;
+        let mut config = self.config.clone();
+        config.apply_reasoning(reasoning);
+        Some(Arc::new(Self {
+            client: self.client.clone(),
+            config,
+            credential: self.credential.clone(),
+        }))
+    }
 }
 
 impl AnthropicClient {
@@ -1212,4 +1172,46 @@ mod tests {
             })
         );
     }
+
+    #[tokio::test]
+    async fn list_models_reports_models_and_limits() {
+        use crate::{ModelClient, ProviderConfig};
+        use wiremock::matchers::{header, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/models"))
+            .and(header("x-api-key", "sk-test"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .insert_header("anthropic-ratelimit-requests-remaining", "49")
+                    .set_body_json(serde_json::json!({
+                        "data": [
+                            {"id": "claude-test", "display_name": "Claude Test", "type": "model"}
+                        ],
+                        "has_more": false
+                    })),
+            )
+            .mount(&server)
+            .await;
+
+        let client = AnthropicClient::new(ProviderConfig {
+            api_key: Some("sk-test".into()),
+            base_url: Some(server.uri()),
+            model: "claude-test".to_string(),
+            ..ProviderConfig::default()
+        })
+        .unwrap();
+        let list = client.list_models().await.unwrap();
+        assert_eq!(list.models[0].id, "claude-test");
+        assert_eq!(list.models[0].display_name.as_deref(), Some("Claude Test"));
+        assert_eq!(
+            list.limits,
+            vec![(
+                "anthropic-ratelimit-requests-remaining".to_string(),
+                "49".to_string()
+            )]
+        );
+    }
 }
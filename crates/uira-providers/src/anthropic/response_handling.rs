@@ -1,6 +1,7 @@
 //! Utilities for handling Anthropic API responses
 
 use serde::Deserialize;
+use serde_json::Value;
 
 /// Extract retry-after delay from response headers
 /// Returns delay in milliseconds
@@ -64,10 +65,184 @@ impl std::fmt::Display for ErrorResponse {
     }
 }
 
+/// Incremental parser for tool-use arguments streamed as `input_json_delta`
+/// fragments
+///
+/// Each fragment is scanned once as it arrives. [`value`](Self::value) closes
+/// whatever is still open to give the arguments seen so far: every complete
+/// value, plus a string value that is still streaming, cut at its last
+/// complete character. Keys still waiting for a value and half-written
+/// numbers or literals are left out.
+#[derive(Debug, Clone, Default)]
+pub struct PartialJsonParser {
+    buffer: String,
+    stack: Vec<Frame>,
+    /// Longest prefix known to close into valid JSON, with its closers
+    checkpoint: Option<(usize, String)>,
+    string: Option<StringState>,
+    /// Inside a number or literal
+    in_scalar: bool,
+    completed_fields: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Frame {
+    Object { expecting_key: bool },
+    Array,
+}
+
+#[derive(Debug, Clone, Default)]
+struct StringState {
+    is_key: bool,
+    /// Start of an escape sequence not yet complete
+    escape_start: Option<usize>,
+    /// Start of a `\uXXXX` high surrogate still waiting for its pair
+    surrogate_start: Option<usize>,
+}
+
+impl PartialJsonParser {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append the next fragment
+    pub fn push(&mut self, fragment: &str) {
+        let offset = self.buffer.len();
+        self.buffer.push_str(fragment);
+        for (index, ch) in fragment.char_indices() {
+            self.scan(offset + index, ch);
+        }
+    }
+
+    /// Everything pushed so far
+    pub fn as_str(&self) -> &str {
+        &self.buffer
+    }
+
+    /// Number of values completed directly inside the top-level object,
+    /// which grows each time another argument has fully arrived
+    pub fn completed_fields(&self) -> usize {
+        self.completed_fields
+    }
+
+    /// The arguments seen so far, or `None` before anything parseable arrived
+    pub fn value(&self) -> Option<Value> {
+        let candidate = match &self.string {
+            Some(string) if !string.is_key => {
+                let end = string
+                    .surrogate_start
+                    .or(string.escape_start)
+                    .unwrap_or(self.buffer.len());
+                format!("{}\"{}", &self.buffer[..end], self.closers())
+            }
+            _ => {
+                let (len, closers) = self.checkpoint.as_ref()?;
+                format!("{}{}", &self.buffer[..*len], closers)
+            }
+        };
+        serde_json::from_str(&candidate).ok()
+    }
+
+    fn scan(&mut self, pos: usize, ch: char) {
+        if let Some(string) = &mut self.string {
+            if let Some(start) = string.escape_start {
+                let escape = &self.buffer[start..pos + ch.len_utf8()];
+                if !escape.starts_with("\\u") || escape.len() == 6 {
+                    string.escape_start = None;
+                    string.surrogate_start = u16::from_str_radix(escape.get(2..).unwrap_or(""), 16)
+                        .ok()
+                        .filter(|unit| (0xD800..0xDC00).contains(unit))
+                        .map(|_| start);
+                }
+                return;
+            }
+            match ch {
+                '\\' => string.escape_start = Some(pos),
+                '"' => {
+                    let is_key = string.is_key;
+                    self.string = None;
+                    if !is_key {
+                        self.value_end(pos + 1);
+                    }
+                }
+                _ => string.surrogate_start = None,
+            }
+            return;
+        }
+
+        if self.in_scalar {
+            if !(matches!(ch, ',' | '}' | ']') || ch.is_whitespace()) {
+                return;
+            }
+            self.in_scalar = false;
+            self.value_end(pos);
+        }
+
+        match ch {
+            '{' => {
+                self.stack.push(Frame::Object {
+                    expecting_key: true,
+                });
+                self.set_checkpoint(pos + 1);
+            }
+            '[' => {
+                self.stack.push(Frame::Array);
+                self.set_checkpoint(pos + 1);
+            }
+            '}' | ']' => {
+                self.stack.pop();
+                self.value_end(pos + 1);
+            }
+            '"' => {
+                let is_key = matches!(
+                    self.stack.last(),
+                    Some(Frame::Object {
+                        expecting_key: true
+                    })
+                );
+                self.string = Some(StringState {
+                    is_key,
+                    ..StringState::default()
+                });
+            }
+            ':' | ',' => {
+                if let Some(Frame::Object { expecting_key }) = self.stack.last_mut() {
+                    *expecting_key = ch == ',';
+                }
+            }
+            ch if ch.is_whitespace() => {}
+            _ => self.in_scalar = true,
+        }
+    }
+
+    fn value_end(&mut self, end: usize) {
+        self.set_checkpoint(end);
+        if matches!(self.stack.as_slice(), [Frame::Object { .. }]) {
+            self.completed_fields += 1;
+        }
+    }
+
+    fn set_checkpoint(&mut self, len: usize) {
+        self.checkpoint = Some((len, self.closers()));
+    }
+
+    fn closers(&self) -> String {
+        self.stack
+            .iter()
+            .rev()
+            .map(|frame| match frame {
+                Frame::Object { .. } => '}',
+                Frame::Array => ']',
+            })
+            .collect()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use reqwest::header::{HeaderMap, HeaderValue};
+    use serde_json::json;
 
     #[test]
     fn test_extract_retry_after_present() {
@@ -113,4 +288,77 @@ mod tests {
         assert!(display.contains("type: overloaded_error"));
         assert!(display.contains("message: Overloaded"));
     }
+
+    fn prefixes(json: &str) -> Vec<Option<Value>> {
+        let mut parser = PartialJsonParser::new();
+        json.chars()
+            .map(|ch| {
+                parser.push(&ch.to_string());
+                parser.value()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_partial_json_surfaces_fields_as_they_stream() {
+        let json =
+            r#"{"file_path": "src/lib.rs", "content": "fn main() {\n    println!(\"hi\");\n}"}"#;
+        let full: Value = serde_json::from_str(json).unwrap();
+        let content = full["content"].as_str().unwrap();
+
+        let mut saw_path_before_content = false;
+        for value in prefixes(json) {
+            let value = value.expect("every prefix after '{' parses");
+            if let Some(partial) = value.get("content").and_then(Value::as_str) {
+                assert!(content.starts_with(partial));
+                saw_path_before_content |= partial.is_empty() && value["file_path"] == "src/lib.rs";
+            }
+        }
+        assert!(saw_path_before_content);
+
+        let mut parser = PartialJsonParser::new();
+        parser.push(json);
+        assert_eq!(parser.value(), Some(full));
+        assert_eq!(parser.completed_fields(), 2);
+    }
+
+    #[test]
+    fn test_partial_json_never_splits_escapes() {
+        let json = r#"{"text": "caf\u00e9 \ud83d\ude00!", "tags": ["a", "b"]}"#;
+        let full: Value = serde_json::from_str(json).unwrap();
+        let text = full["text"].as_str().unwrap();
+
+        for value in prefixes(json) {
+            let value = value.expect("every prefix after '{' parses");
+            if let Some(partial) = value.get("text").and_then(Value::as_str) {
+                assert!(text.starts_with(partial), "{partial:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_partial_json_drops_incomplete_keys_and_scalars() {
+        let cases = [
+            (r#"{"#, json!({})),
+            (r#"{"path"#, json!({})),
+            (r#"{"path": "#, json!({})),
+            (r#"{"n": 12"#, json!({})),
+            (r#"{"n": 12,"#, json!({ "n": 12 })),
+            (
+                r#"{"n": 12, "items": [1, tr"#,
+                json!({ "n": 12, "items": [1] }),
+            ),
+            (
+                r#"{"n": 12, "items": [1, true], "nested": {"a""#,
+                json!({ "n": 12, "items": [1, true], "nested": {} }),
+            ),
+        ];
+        for (input, expected) in cases {
+            let mut parser = PartialJsonParser::new();
+            parser.push(input);
+            assert_eq!(parser.value(), Some(expected), "{input}");
+        }
+
+        assert_eq!(PartialJsonParser::new().value(), None);
+    }
 }
@@ -4,11 +4,29 @@
 //! initial connection establishment. It does NOT retry mid-stream errors.
 
 use crate::error::ProviderError;
+use std::cell::Cell;
 use std::future::Future;
 use std::time::{SystemTime, UNIX_EPOCH};
 use tokio::time::{sleep, Duration};
 use tracing::{debug, warn};
 
+tokio::task_local! {
+    static RETRY_COUNT: Cell<u32>;
+}
+
+/// Run `fut` and report how many retries [`with_retry`] performed inside it.
+///
+/// Retries are counted through a task-local, so only calls awaited on the
+/// same task as `fut` are observed.
+pub async fn count_retries<F: Future>(fut: F) -> (F::Output, u32) {
+    RETRY_COUNT
+        .scope(Cell::new(0), async move {
+            let output = fut.await;
+            (output, RETRY_COUNT.with(Cell::get))
+        })
+        .await
+}
+
 /// Configuration for retry behavior with exponential backoff
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -154,6 +172,7 @@ where
 
                 // Wait before retry
                 sleep(Duration::from_millis(delay_ms)).await;
+                let _ = RETRY_COUNT.try_with(|count| count.set(count.get() + 1));
 
                 attempt += 1;
             }
@@ -165,6 +184,34 @@ where
 mod tests {
     use super::*;
 
+    #[tokio::test]
+    async fn test_count_retries_observes_with_retry() {
+        let config = RetryConfig {
+            max_attempts: 3,
+            initial_delay_ms: 1,
+            max_delay_ms: 1,
+            backoff_multiplier: 1.0,
+            jitter_factor: 0.0,
+        };
+        let mut calls = 0;
+        let (result, retries) = count_retries(with_retry(&config, || {
+            calls += 1;
+            let attempt = calls;
+            async move {
+                if attempt < 3 {
+                    Err(ProviderError::Timeout {
+                        message: "slow".to_string(),
+                    })
+                } else {
+                    Ok(attempt)
+                }
+            }
+        }))
+        .await;
+        assert_eq!(result.unwrap(), 3);
+        assert_eq!(retries, 2);
+    }
+
     #[test]
     fn test_default_config() {
         let config = RetryConfig::default();
@@ -11,7 +11,7 @@ pub mod oauth_server;
 
 pub use error::{AuthError, Result};
 pub use pkce::{generate_pkce, PkceChallenge};
-pub use storage::CredentialStore;
+pub use storage::{CredentialBackend, CredentialStore, FileBackend, KeychainBackend};
 pub use traits::AuthProvider;
 pub use types::{AuthMethod, OAuthCallback, OAuthChallenge, OAuthTokens, StoredCredential};
 
@@ -1,46 +1,318 @@
+//! Credential storage
+//!
+//! Credentials live in the OS keychain (macOS Keychain, the freedesktop
+//! secret service, Windows Credential Manager) when one is reachable, one
+//! entry per provider, and in `~/.uira/auth.json` otherwise. Credentials left
+//! in the JSON file are moved into the keychain the first time it's
+//! available, and the file is removed. Set `UIRA_CREDENTIAL_STORE=file` to
+//! keep using the file.
+
 use crate::{AuthError, Result, StoredCredential};
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::path::PathBuf;
+use std::sync::Arc;
 use uira_core::atomic_write_secure;
 
 const UIRA_DIR: &str = ".uira";
 
-#[derive(Debug, Default, Serialize, Deserialize)]
-pub struct CredentialStore {
+/// Keychain service name credentials are stored under
+pub const KEYCHAIN_SERVICE: &str = "uira";
+
+/// Environment variable selecting the backend; `file` skips the keychain
+pub const CREDENTIAL_STORE_ENV: &str = "UIRA_CREDENTIAL_STORE";
+
+/// Keychain account holding the list of stored providers, since keychains
+/// can't be enumerated portably
+const KEYCHAIN_INDEX_ACCOUNT: &str = "providers";
+
+/// Keychain account prefix for a provider's credential
+const KEYCHAIN_PROVIDER_PREFIX: &str = "provider:";
+
+/// Where credentials are persisted
+pub trait CredentialBackend: Send + Sync {
+    /// Short name, e.g. `keychain` or `file`
+    fn name(&self) -> &'static str;
+
+    /// Human-readable location, for `uira auth status --where`
+    fn location(&self) -> String;
+
+    fn load(&self) -> Result<HashMap<String, StoredCredential>>;
+
+    fn save(&self, credentials: &HashMap<String, StoredCredential>) -> Result<()>;
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct CredentialFile {
     credentials: HashMap<String, StoredCredential>,
 }
 
-impl CredentialStore {
-    pub fn load() -> Result<Self> {
-        let path = Self::storage_path()?;
+/// Plain JSON file, readable only by the user
+#[derive(Debug, Clone)]
+pub struct FileBackend {
+    path: PathBuf,
+}
+
+impl FileBackend {
+    pub fn new(path: PathBuf) -> Self {
+        Self { path }
+    }
 
-        if !path.exists() {
-            return Ok(Self::default());
+    /// Delete the file, once its credentials live elsewhere
+    pub fn remove(&self) -> Result<()> {
+        match std::fs::remove_file(&self.path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(AuthError::StorageError(format!(
+                "Failed to remove {}: {}",
+                self.path.display(),
+                e
+            ))),
         }
+    }
+}
 
-        let content = std::fs::read_to_string(&path)
-            .map_err(|e| AuthError::StorageError(format!("Failed to read: {}", e)))?;
+impl CredentialBackend for FileBackend {
+    fn name(&self) -> &'static str {
+        "file"
+    }
 
-        let store: Self = serde_json::from_str(&content)?;
-        Ok(store)
+    fn location(&self) -> String {
+        self.path.display().to_string()
     }
 
-    pub fn save(&self) -> Result<()> {
-        let path = Self::storage_path()?;
+    fn load(&self) -> Result<HashMap<String, StoredCredential>> {
+        if !self.path.exists() {
+            return Ok(HashMap::new());
+        }
 
-        if let Some(parent) = path.parent() {
+        let content = std::fs::read_to_string(&self.path)
+            .map_err(|e| AuthError::StorageError(format!("Failed to read: {}", e)))?;
+
+        let file: CredentialFile = serde_json::from_str(&content)?;
+        Ok(file.credentials)
+    }
+
+    fn save(&self, credentials: &HashMap<String, StoredCredential>) -> Result<()> {
+        if let Some(parent) = self.path.parent() {
             std::fs::create_dir_all(parent)
                 .map_err(|e| AuthError::StorageError(format!("Failed to create dir: {}", e)))?;
         }
 
-        let content = serde_json::to_string_pretty(self)?;
+        let content = serde_json::to_string_pretty(&serde_json::json!({
+            "credentials": credentials
+        }))?;
 
-        atomic_write_secure(&path, content.as_bytes())
+        atomic_write_secure(&self.path, content.as_bytes())
             .map_err(|e| AuthError::StorageError(format!("Failed to write credentials: {}", e)))?;
 
         Ok(())
     }
+}
+
+/// OS keychain via the `keyring` crate, one entry per provider plus an index
+#[derive(Debug, Clone)]
+pub struct KeychainBackend {
+    service: String,
+}
+
+impl KeychainBackend {
+    /// The keychain for `service`, or `None` when no keychain is reachable
+    /// (e.g. no secret service on a headless Linux box)
+    pub fn probe(service: &str) -> Option<Self> {
+        let backend = Self {
+            service: service.to_string(),
+        };
+        match backend.read(KEYCHAIN_INDEX_ACCOUNT) {
+            Ok(_) => Some(backend),
+            Err(e) => {
+                tracing::debug!("OS keychain unavailable, using credential file: {}", e);
+                None
+            }
+        }
+    }
+
+    fn entry(&self, account: &str) -> Result<keyring::Entry> {
+        keyring::Entry::new(&self.service, account).map_err(keychain_error)
+    }
+
+    fn read(&self, account: &str) -> Result<Option<String>> {
+        match self.entry(account)?.get_password() {
+            Ok(secret) => Ok(Some(secret)),
+            Err(keyring::Error::NoEntry) => Ok(None),
+            Err(e) => Err(keychain_error(e)),
+        }
+    }
+
+    fn write(&self, account: &str, secret: &str) -> Result<()> {
+        self.entry(account)?
+            .set_password(secret)
+            .map_err(keychain_error)
+    }
+
+    fn delete(&self, account: &str) -> Result<()> {
+        match self.entry(account)?.delete_credential() {
+            Ok(()) | Err(keyring::Error::NoEntry) => Ok(()),
+            Err(e) => Err(keychain_error(e)),
+        }
+    }
+
+    fn providers(&self) -> Result<Vec<String>> {
+        match self.read(KEYCHAIN_INDEX_ACCOUNT)? {
+            Some(index) => Ok(serde_json::from_str(&index)?),
+            None => Ok(Vec::new()),
+        }
+    }
+}
+
+impl CredentialBackend for KeychainBackend {
+    fn name(&self) -> &'static str {
+        "keychain"
+    }
+
+    fn location(&self) -> String {
+        let keychain = if cfg!(target_os = "macos") {
+            "macOS Keychain"
+        } else if cfg!(target_os = "windows") {
+            "Windows Credential Manager"
+        } else {
+            "Secret Service"
+        };
+        format!("{} (service \"{}\")", keychain, self.service)
+    }
+
+    fn load(&self) -> Result<HashMap<String, StoredCredential>> {
+        let mut credentials = HashMap::new();
+        for provider in self.providers()? {
+            let account = format!("{}{}", KEYCHAIN_PROVIDER_PREFIX, provider);
+            if let Some(secret) = self.read(&account)? {
+                credentials.insert(provider, serde_json::from_str(&secret)?);
+            }
+        }
+        Ok(credentials)
+    }
+
+    fn save(&self, credentials: &HashMap<String, StoredCredential>) -> Result<()> {
+        for (provider, credential) in credentials {
+            let account = format!("{}{}", KEYCHAIN_PROVIDER_PREFIX, provider);
+            self.write(&account, &serde_json::to_string(credential)?)?;
+        }
+        for stale in self.providers()? {
+            if !credentials.contains_key(&stale) {
+                self.delete(&format!("{}{}", KEYCHAIN_PROVIDER_PREFIX, stale))?;
+            }
+        }
+
+        let mut index: Vec<&String> = credentials.keys().collect();
+        index.sort();
+        self.write(KEYCHAIN_INDEX_ACCOUNT, &serde_json::to_string(&index)?)
+    }
+}
+
+fn keychain_error(error: keyring::Error) -> AuthError {
+    AuthError::StorageError(format!("OS keychain: {}", error))
+}
+
+pub struct CredentialStore {
+    credentials: HashMap<String, StoredCredential>,
+    backend: Arc<dyn CredentialBackend>,
+}
+
+impl std::fmt::Debug for CredentialStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CredentialStore")
+            .field("credentials", &self.credentials)
+            .field("backend", &self.backend.name())
+            .finish()
+    }
+}
+
+impl CredentialStore {
+    /// Load from the OS keychain when reachable, migrating any credentials
+    /// left in the JSON file, and from the JSON file otherwise
+    pub fn load() -> Result<Self> {
+        let file = FileBackend::new(Self::storage_path()?);
+        if std::env::var(CREDENTIAL_STORE_ENV).is_ok_and(|v| v.eq_ignore_ascii_case("file")) {
+            return Self::open(Arc::new(file));
+        }
+
+        let Some(keychain) = KeychainBackend::probe(KEYCHAIN_SERVICE) else {
+            return Self::open(Arc::new(file));
+        };
+        match Self::open_migrating(Arc::new(keychain), &file) {
+            Ok(store) => Ok(store),
+            Err(e) => {
+                tracing::warn!("OS keychain failed, using credential file: {}", e);
+                Self::open(Arc::new(file))
+            }
+        }
+    }
+
+    /// Load from `backend`
+    pub fn open(backend: Arc<dyn CredentialBackend>) -> Result<Self> {
+        Ok(Self {
+            credentials: backend.load()?,
+            backend,
+        })
+    }
+
+    /// Load from `backend` after moving credentials from `legacy` into it.
+    /// The file's entries win, since they were written while `backend` was
+    /// unreachable. The file is removed once `backend` holds every provider.
+    pub fn open_migrating(
+        backend: Arc<dyn CredentialBackend>,
+        legacy: &FileBackend,
+    ) -> Result<Self> {
+        let mut credentials = backend.load()?;
+        let leftover = legacy.load()?;
+        if leftover.is_empty() {
+            return Ok(Self {
+                credentials,
+                backend,
+            });
+        }
+
+        let migrated: Vec<String> = leftover.keys().cloned().collect();
+        credentials.extend(leftover);
+        backend.save(&credentials)?;
+
+        let stored = backend.load()?;
+        if migrated
+            .iter()
+            .any(|provider| !stored.contains_key(provider))
+        {
+            return Err(AuthError::StorageError(format!(
+                "{} did not keep the migrated credentials",
+                backend.location()
+            )));
+        }
+        legacy.remove()?;
+        tracing::info!(
+            "Moved credentials for {} from {} to {}",
+            migrated.join(", "),
+            legacy.location(),
+            backend.location()
+        );
+
+        Ok(Self {
+            credentials: stored,
+            backend,
+        })
+    }
+
+    pub fn save(&self) -> Result<()> {
+        self.backend.save(&self.credentials)
+    }
+
+    /// Name of the backend in use, `keychain` or `file`
+    pub fn backend_name(&self) -> &'static str {
+        self.backend.name()
+    }
+
+    /// Where credentials are stored
+    pub fn location(&self) -> String {
+        self.backend.location()
+    }
 
     pub fn get(&self, provider: &str) -> Option<&StoredCredential> {
         self.credentials.get(provider)
@@ -79,3 +351,100 @@ impl CredentialStore {
         Ok(base_dir.join("auth.json"))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use secrecy::ExposeSecret;
+    use std::sync::Mutex;
+
+    /// Keychain stand-in holding serialized entries in memory
+    #[derive(Default)]
+    struct MemoryBackend {
+        entries: Mutex<HashMap<String, String>>,
+    }
+
+    impl CredentialBackend for MemoryBackend {
+        fn name(&self) -> &'static str {
+            "keychain"
+        }
+
+        fn location(&self) -> String {
+            "memory".to_string()
+        }
+
+        fn load(&self) -> Result<HashMap<String, StoredCredential>> {
+            let entries = self.entries.lock().unwrap();
+            entries
+                .iter()
+                .map(|(provider, json)| Ok((provider.clone(), serde_json::from_str(json)?)))
+                .collect()
+        }
+
+        fn save(&self, credentials: &HashMap<String, StoredCredential>) -> Result<()> {
+            let mut entries = self.entries.lock().unwrap();
+            entries.clear();
+            for (provider, credential) in credentials {
+                entries.insert(provider.clone(), serde_json::to_string(credential)?);
+            }
+            Ok(())
+        }
+    }
+
+    fn api_key(key: &str) -> StoredCredential {
+        StoredCredential::ApiKey { key: key.into() }
+    }
+
+    fn key_of(store: &CredentialStore, provider: &str) -> String {
+        match store.get(provider) {
+            Some(StoredCredential::ApiKey { key }) => key.expose_secret().to_string(),
+            other => panic!("unexpected credential: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_file_backend_round_trip() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("auth.json");
+        let backend = Arc::new(FileBackend::new(path.clone()));
+
+        let mut store = CredentialStore::open(backend.clone()).unwrap();
+        assert!(store.is_empty());
+        store.insert("openai".to_string(), api_key("sk-1"));
+        store.save().unwrap();
+
+        // Same layout as before the keychain existed
+        let json: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(json["credentials"]["openai"]["type"], "api");
+
+        let reloaded = CredentialStore::open(backend).unwrap();
+        assert_eq!(key_of(&reloaded, "openai"), "sk-1");
+        assert_eq!(reloaded.backend_name(), "file");
+    }
+
+    #[test]
+    fn test_migrates_file_credentials_into_keychain() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = FileBackend::new(dir.path().join("auth.json"));
+        let mut legacy = HashMap::new();
+        legacy.insert("anthropic".to_string(), api_key("file-key"));
+        file.save(&legacy).unwrap();
+
+        let keychain = Arc::new(MemoryBackend::default());
+        let mut existing = HashMap::new();
+        existing.insert("anthropic".to_string(), api_key("old-key"));
+        existing.insert("openai".to_string(), api_key("sk-keychain"));
+        keychain.save(&existing).unwrap();
+
+        let store = CredentialStore::open_migrating(keychain.clone(), &file).unwrap();
+        assert_eq!(store.backend_name(), "keychain");
+        assert_eq!(key_of(&store, "anthropic"), "file-key");
+        assert_eq!(key_of(&store, "openai"), "sk-keychain");
+        assert!(!dir.path().join("auth.json").exists());
+
+        // Nothing left to migrate the second time
+        let again = CredentialStore::open_migrating(keychain, &file).unwrap();
+        assert_eq!(again.len(), 2);
+    }
+}
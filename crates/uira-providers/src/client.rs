@@ -4,8 +4,8 @@ use std::sync::Arc;
 use uira_core::Provider;
 
 use crate::{
-    AnthropicClient, FriendliClient, GeminiClient, OllamaClient, OpenAIClient, OpenCodeClient,
-    ProviderConfig, ProviderError,
+    AnthropicClient, CachingModelClient, FriendliClient, GeminiClient, MockModelClient,
+    OllamaClient, OpenAIClient, OpenCodeClient, ProviderConfig, ProviderError,
 };
 
 /// Builder for creating model clients
@@ -25,7 +25,13 @@ impl ModelClientBuilder {
         self
     }
 
+    /// Build the client, honoring `UIRA_RECORDING` (see [`CachingModelClient`])
     pub fn build(self) -> Result<Arc<dyn crate::ModelClient>, ProviderError> {
+        let config = self.config.clone();
+        CachingModelClient::from_env(&config, || self.build_provider())
+    }
+
+    fn build_provider(self) -> Result<Arc<dyn crate::ModelClient>, ProviderError> {
         match self.config.provider {
             Provider::Anthropic => Ok(Arc::new(AnthropicClient::new(self.config)?)),
             Provider::OpenAI => Ok(Arc::new(OpenAIClient::new(self.config)?)),
@@ -33,6 +39,7 @@ impl ModelClientBuilder {
             Provider::Ollama => Ok(Arc::new(OllamaClient::new(self.config)?)),
             Provider::OpenCode => Ok(Arc::new(OpenCodeClient::new(self.config)?)),
             Provider::FriendliAI => Ok(Arc::new(FriendliClient::new(self.config)?)),
+            Provider::Mock => Ok(Arc::new(MockModelClient::from_env()?)),
             Provider::OpenRouter => {
                 let mut config = self.config;
                 config.base_url = Some("https://openrouter.ai/api/v1".to_string());
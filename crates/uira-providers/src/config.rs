@@ -317,6 +317,8 @@ pub struct ProviderConfig {
     /// Reasoning mode for providers that support it (e.g. FriendliAI chat_template_kwargs)
     /// Values: "off", "on", "interleaved", "preserved"
     pub reasoning_mode: Option<String>,
+    /// Reasoning effort for OpenAI reasoning models ("low", "medium", "high")
+    pub reasoning_effort: Option<String>,
     /// FriendliAI-specific configuration
     pub friendliai: Option<FriendliAIConfig>,
 }
@@ -336,6 +338,7 @@ impl Default for ProviderConfig {
             thinking_budget: None,
             friendliai: None,
             reasoning_mode: None,
+            reasoning_effort: None,
         }
     }
 }
@@ -416,4 +419,34 @@ impl ProviderConfig {
         self.reasoning_mode = Some(mode.into());
         self
     }
+
+    pub fn with_reasoning_effort(mut self, effort: impl Into<String>) -> Self {
+        self.reasoning_effort = Some(effort.into());
+        self
+    }
+
+    /// Apply provider-native reasoning controls on top of this config
+    pub fn apply_reasoning(&mut self, reasoning: &ReasoningOverride) {
+        if let Some(budget) = reasoning.budget_tokens {
+            self.enable_thinking = true;
+            self.thinking_budget = Some(budget);
+        }
+        if let Some(ref effort) = reasoning.effort {
+            self.reasoning_effort = Some(effort.clone());
+        }
+        if let Some(max_tokens) = reasoning.max_tokens {
+            self.max_tokens = Some(self.max_tokens.map_or(max_tokens, |m| m.max(max_tokens)));
+        }
+    }
+}
+
+/// Reasoning controls requested for a single turn (e.g. from think keywords)
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ReasoningOverride {
+    /// Thinking token budget (Anthropic extended thinking, Gemini thinkingBudget)
+    pub budget_tokens: Option<u32>,
+    /// Reasoning effort (OpenAI reasoning_effort)
+    pub effort: Option<String>,
+    /// Minimum output token limit needed to fit the thinking budget
+    pub max_tokens: Option<usize>,
 }
@@ -9,6 +9,7 @@
 //! See `anthropic/error_classify.rs` and `openai/error_classify.rs` for classifier implementations.
 
 use thiserror::Error;
+use uira_core::{ErrorKind, UiraError};
 
 #[derive(Debug, Error)]
 pub enum ProviderError {
@@ -74,4 +75,93 @@ impl ProviderError {
             _ => None,
         }
     }
+
+    /// Stable code for the shared error taxonomy
+    pub fn code(&self) -> &'static str {
+        match self {
+            Self::AuthenticationFailed(_) => "provider.authentication_failed",
+            Self::RateLimited { .. } => "provider.rate_limited",
+            Self::ContextExceeded { .. } => "provider.context_exceeded",
+            Self::Network(_) => "provider.network",
+            Self::InvalidResponse(_) => "provider.invalid_response",
+            Self::StreamError(_) => "provider.stream_error",
+            Self::Configuration(_) => "provider.configuration",
+            Self::Unavailable { .. } => "provider.unavailable",
+            Self::PaymentRequired { .. } => "provider.payment_required",
+            Self::Timeout { .. } => "provider.timeout",
+            Self::ImageError { .. } => "provider.image_error",
+            Self::MessageOrderingConflict => "provider.message_ordering_conflict",
+            Self::ToolCallInputMissing => "provider.tool_call_input_missing",
+        }
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        match self {
+            Self::ContextExceeded { .. } | Self::PaymentRequired { .. } => ErrorKind::Budget,
+            Self::Configuration(_) | Self::ImageError { .. } => ErrorKind::UserError,
+            Self::MessageOrderingConflict | Self::ToolCallInputMissing => ErrorKind::Internal,
+            _ => ErrorKind::ProviderError,
+        }
+    }
+
+    fn remediation(&self) -> Option<&'static str> {
+        match self {
+            Self::AuthenticationFailed(_) => {
+                Some("Run `uira-agent auth login` or check the provider API key")
+            }
+            Self::RateLimited { .. } => {
+                Some("Wait a moment and retry, or switch to another model with /model")
+            }
+            Self::ContextExceeded { .. } => {
+                Some("Start a fresh session with /new, or /fork from an earlier message")
+            }
+            Self::Network(_) | Self::Timeout { .. } => {
+                Some("Check your network connection and the provider base URL")
+            }
+            Self::Configuration(_) => Some("Check the provider settings in uira.yml"),
+            Self::Unavailable { .. } => Some("Retry later or switch to another provider"),
+            Self::PaymentRequired { .. } => {
+                Some("Check the billing and quota of your provider account")
+            }
+            Self::ImageError { .. } => Some("Resize or compress the image and try again"),
+            _ => None,
+        }
+    }
+}
+
+impl From<&ProviderError> for UiraError {
+    fn from(error: &ProviderError) -> Self {
+        let uira_error = UiraError::new(error.kind(), error.code(), error.to_string())
+            .with_retryable(error.is_retryable());
+        match error.remediation() {
+            Some(hint) => uira_error.with_remediation(hint),
+            None => uira_error,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_into_taxonomy() {
+        let error = UiraError::from(&ProviderError::RateLimited {
+            retry_after_ms: 1000,
+        });
+        assert_eq!(error.kind, ErrorKind::ProviderError);
+        assert_eq!(error.code, "provider.rate_limited");
+        assert!(error.retryable);
+
+        let error = UiraError::from(&ProviderError::ContextExceeded {
+            used: 210_000,
+            limit: 200_000,
+        });
+        assert_eq!(error.kind, ErrorKind::Budget);
+        assert!(!error.retryable);
+        assert!(error.remediation.unwrap().contains("/new"));
+
+        let error = UiraError::from(&ProviderError::AuthenticationFailed("bad key".into()));
+        assert!(error.remediation.unwrap().contains("auth login"));
+    }
 }
@@ -10,8 +10,8 @@ use uira_core::{
 };
 
 use crate::{
-    image::image_source_to_data_url, traits::ModelResult, traits::ResponseStream, ModelClient,
-    ProviderConfig, ProviderError,
+    image::image_source_to_data_url, payload_log, traits::ModelResult, traits::ResponseStream,
+    ModelClient, ProviderConfig, ProviderError,
 };
 
 const FRIENDLI_SERVERLESS_BASE_URL: &str = "https://api.friendli.ai/serverless/v1";
@@ -517,6 +517,7 @@ impl ModelClient for FriendliClient {
     async fn chat(&self, messages: &[Message], tools: &[ToolSpec]) -> ModelResult<ModelResponse> {
         let request = self.build_request(messages, tools, false);
         let url = format!("{}/chat/completions", self.base_url());
+        payload_log::log_request(PROVIDER_NAME, &self.config.model, &request);
 
         let response = self.client.post(&url).json(&request).send().await?;
         if !response.status().is_success() {
@@ -560,6 +561,7 @@ impl ModelClient for FriendliClient {
 
         let request = self.build_request(messages, tools, true);
         let url = format!("{}/chat/completions", self.base_url());
+        payload_log::log_request(PROVIDER_NAME, &self.config.model, &request);
 
         let response = self.client.post(&url).json(&request).send().await?;
         if !response.status().is_success() {
@@ -3,43 +3,89 @@
 use async_trait::async_trait;
 use futures::StreamExt;
 use reqwest::Client;
-use secrecy::ExposeSecret;
+use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
+use std::sync::Arc;
 use std::time::Duration;
 use uira_core::{
     ContentBlock, ContentDelta, ImageSource, Message, MessageContent, MessageDelta, ModelResponse,
-    Role, StopReason, StreamChunk, StreamMessageStart, TokenUsage, ToolSpec,
+    Role, StopReason, StreamChunk, StreamMessageStart, TokenUsage, ToolSpec, ENV_GEMINI_API_KEY,
+    ENV_GOOGLE_API_KEY,
 };
 
+use crate::auth::providers::GoogleAuth;
+use crate::auth::{CredentialStore, StoredCredential};
+use crate::token_refresh::{AuthProviderEndpoint, OAuthToken};
 use crate::{
-    image::normalize_image_source, traits::ModelResult, traits::ResponseStream, ModelClient,
-    ProviderConfig, ProviderError,
+    image::normalize_image_source, payload_log, traits::ModelResult, traits::ResponseStream,
+    ModelClient, ProviderConfig, ProviderError, ReasoningOverride,
 };
+use crate::{model_list, ModelInfo, ModelList};
 
 const DEFAULT_MAX_TOKENS: usize = 8192;
 const MAX_SSE_BUFFER: usize = 10 * 1024 * 1024;
+/// Credential store keys `uira-agent auth login` may have used
+const STORED_PROVIDER_NAMES: &[&str] = &["google", "gemini"];
+
+#[derive(Clone)]
+enum CredentialSource {
+    ApiKey(SecretString),
+    /// OAuth credentials, shared with clones so they refresh once
+    OAuth(Arc<OAuthToken>),
+}
 
 /// Google Gemini API client
 pub struct GeminiClient {
     client: Client,
     config: ProviderConfig,
+    credential: CredentialSource,
 }
 
 impl GeminiClient {
     pub fn new(config: ProviderConfig) -> Result<Self, ProviderError> {
-        let api_key = config
-            .api_key
-            .as_ref()
-            .ok_or_else(|| ProviderError::Configuration("API key required for Gemini".into()))?;
-
-        // Verify API key is valid format
-        let _ = api_key.expose_secret();
+        let credential = Self::load_credential(&config)?;
 
         let timeout = Duration::from_secs(config.timeout_seconds.unwrap_or(120));
 
         let client = Client::builder().timeout(timeout).build()?;
 
-        Ok(Self { client, config })
+        Ok(Self {
+            client,
+            config,
+            credential,
+        })
+    }
+
+    fn load_credential(config: &ProviderConfig) -> Result<CredentialSource, ProviderError> {
+        if let Some(api_key) = &config.api_key {
+            return Ok(CredentialSource::ApiKey(api_key.clone()));
+        }
+
+        if let Some(key) = [ENV_GEMINI_API_KEY, ENV_GOOGLE_API_KEY]
+            .iter()
+            .find_map(|var| std::env::var(var).ok())
+        {
+            return Ok(CredentialSource::ApiKey(SecretString::from(key)));
+        }
+
+        if let Ok(store) = CredentialStore::load() {
+            for name in STORED_PROVIDER_NAMES {
+                let Some(cred) = store.get(name) else {
+                    continue;
+                };
+                if let StoredCredential::ApiKey { key } = cred {
+                    return Ok(CredentialSource::ApiKey(key.clone()));
+                }
+                let endpoint = Arc::new(AuthProviderEndpoint(GoogleAuth::new()));
+                if let Some(token) = OAuthToken::from_stored(*name, cred, endpoint) {
+                    return Ok(CredentialSource::OAuth(Arc::new(token)));
+                }
+            }
+        }
+
+        Err(ProviderError::Configuration(
+            "API key required for Gemini".into(),
+        ))
     }
 
     fn build_request(&self, messages: &[Message], tools: &[ToolSpec]) -> GeminiRequest {
@@ -67,6 +113,9 @@ impl GeminiClient {
                 temperature: self.config.temperature,
                 top_p: None,
                 top_k: None,
+                thinking_config: self.config.enable_thinking.then_some(GeminiThinkingConfig {
+                    thinking_budget: self.config.thinking_budget,
+                }),
             }),
         }
     }
@@ -186,12 +235,82 @@ impl GeminiClient {
             .unwrap_or("https://generativelanguage.googleapis.com")
     }
 
-    fn api_key(&self) -> String {
-        self.config
-            .api_key
-            .as_ref()
-            .map(|k| k.expose_secret().to_string())
-            .unwrap_or_default()
+    async fn auth_header(&self) -> Result<(&'static str, String), ProviderError> {
+        match &self.credential {
+            CredentialSource::ApiKey(key) => {
+                Ok(("x-goog-api-key", key.expose_secret().to_string()))
+            }
+            CredentialSource::OAuth(token) => {
+                let access_token = token.access_token().await?;
+                Ok((
+                    "Authorization",
+                    format!("Bearer {}", access_token.expose_secret()),
+                ))
+            }
+        }
+    }
+
+    /// POST `request` to `url`, mapping error statuses to provider errors
+    async fn send(
+        &self,
+        url: &str,
+        request: &GeminiRequest,
+    ) -> Result<reqwest::Response, ProviderError> {
+        let (header, value) = self.auth_header().await?;
+        let response = self
+            .client
+            .post(url)
+            .header(header, value)
+            .json(request)
+            .send()
+            .await?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+
+            if status.as_u16() == 429 {
+                // Parse Retry-After header if present, otherwise default to 60s
+                let retry_after_ms = response
+                    .headers()
+                    .get("retry-after")
+                    .and_then(|v| v.to_str().ok())
+                    .and_then(|s| s.parse::<u64>().ok())
+                    .map(|secs| secs * 1000)
+                    .unwrap_or(60000);
+                return Err(ProviderError::RateLimited { retry_after_ms });
+            }
+
+            if status.is_server_error() {
+                return Err(ProviderError::Unavailable {
+                    provider: "gemini".to_string(),
+                });
+            }
+
+            let body = response.text().await.unwrap_or_default();
+            if status.as_u16() == 401 {
+                return Err(ProviderError::AuthenticationFailed(body));
+            }
+            return Err(ProviderError::InvalidResponse(format!(
+                "API error {}: {}",
+                status, body
+            )));
+        }
+
+        Ok(response)
+    }
+
+    /// Send `request`, refreshing a rejected OAuth token and trying once more
+    async fn send_with_token_refresh(
+        &self,
+        url: &str,
+        request: &GeminiRequest,
+    ) -> Result<reqwest::Response, ProviderError> {
+        match &self.credential {
+            CredentialSource::OAuth(token) => {
+                token.retry_if_rejected(|| self.send(url, request)).await
+            }
+            CredentialSource::ApiKey(_) => self.send(url, request).await,
+        }
     }
 
     fn convert_response(&self, response: GeminiResponse) -> ModelResponse {
@@ -333,42 +452,9 @@ impl ModelClient for GeminiClient {
             self.base_url(),
             self.config.model,
         );
+        payload_log::log_request("gemini", &self.config.model, &request);
 
-        let response = self
-            .client
-            .post(&url)
-            .header("x-goog-api-key", self.api_key())
-            .json(&request)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-
-            if status.as_u16() == 429 {
-                // Parse Retry-After header if present, otherwise default to 60s
-                let retry_after_ms = response
-                    .headers()
-                    .get("retry-after")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|s| s.parse::<u64>().ok())
-                    .map(|secs| secs * 1000)
-                    .unwrap_or(60000);
-                return Err(ProviderError::RateLimited { retry_after_ms });
-            }
-
-            if status.is_server_error() {
-                return Err(ProviderError::Unavailable {
-                    provider: "gemini".to_string(),
-                });
-            }
-
-            let body = response.text().await.unwrap_or_default();
-            return Err(ProviderError::InvalidResponse(format!(
-                "API error {}: {}",
-                status, body
-            )));
-        }
+        let response = self.send_with_token_refresh(&url, &request).await?;
 
         let api_response: GeminiResponse = response.json().await?;
         Ok(self.convert_response(api_response))
@@ -385,41 +471,9 @@ impl ModelClient for GeminiClient {
             self.base_url(),
             self.config.model,
         );
+        payload_log::log_request("gemini", &self.config.model, &request);
 
-        let response = self
-            .client
-            .post(&url)
-            .header("x-goog-api-key", self.api_key())
-            .json(&request)
-            .send()
-            .await?;
-
-        if !response.status().is_success() {
-            let status = response.status();
-
-            if status.as_u16() == 429 {
-                let retry_after_ms = response
-                    .headers()
-                    .get("retry-after")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|s| s.parse::<u64>().ok())
-                    .map(|secs| secs * 1000)
-                    .unwrap_or(60000);
-                return Err(ProviderError::RateLimited { retry_after_ms });
-            }
-
-            if status.is_server_error() {
-                return Err(ProviderError::Unavailable {
-                    provider: "gemini".to_string(),
-                });
-            }
-
-            let body = response.text().await.unwrap_or_default();
-            return Err(ProviderError::InvalidResponse(format!(
-                "API error {}: {}",
-                status, body
-            )));
-        }
+        let response = self.send_with_token_refresh(&url, &request).await?;
 
         let byte_stream = response.bytes_stream();
         let stream_id = format!("gemini_stream_{}", uuid::Uuid::new_v4());
@@ -524,6 +578,56 @@ impl ModelClient for GeminiClient {
         Ok(Box::pin(stream))
     }
 
+    async fn list_models(&self) -> ModelResult<ModelList> {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct ModelsResponse {
+            #[serde(default)]
+            models: Vec<GeminiModel>,
+        }
+
+        #[derive(Deserialize)]
+        #[serde(rename_all = "camelCase")]
+        struct GeminiModel {
+            name: String,
+            display_name: Option<String>,
+            #[serde(default)]
+            supported_generation_methods: Vec<String>,
+        }
+
+        let url = format!("{}/v1beta/models?pageSize=1000", self.base_url());
+        let fetch = || async {
+            let (header, value) = self.auth_header().await?;
+            model_list::fetch("gemini", self.client.get(&url).header(header, value)).await
+        };
+        let (response, limits): (ModelsResponse, _) = match &self.credential {
+            CredentialSource::OAuth(token) => token.retry_if_rejected(fetch).await?,
+            CredentialSource::ApiKey(_) => fetch().await?,
+        };
+
+        Ok(ModelList {
+            models: response
+                .models
+                .into_iter()
+                .filter(|model| {
+                    model
+                        .supported_generation_methods
+                        .iter()
+                        .any(|method| method == "generateContent")
+                })
+                .map(|model| ModelInfo {
+                    id: model
+                        .name
+                        .strip_prefix("models/")
+                        .unwrap_or(&model.name)
+                        .to_string(),
+                    display_name: model.display_name,
+                })
+                .collect(),
+            limits,
+        })
+    }
+
     fn supports_tools(&self) -> bool {
         true
     }
@@ -540,6 +644,17 @@ impl ModelClient for GeminiClient {
     fn provider(&self) -> &str {
         "gemini"
     }
+
+    fn with_reasoning(&self, reasoning: &ReasoningOverride) -> Option<Arc<dyn ModelClient>> {
+        reasoning.budget_tokens?;
+        let mut config = self.config.clone();
+        config.apply_reasoning(reasoning);
+        Some(Arc::new(Self {
+            client: self.client.clone(),
+            config,
+            credential: self.credential.clone(),
+        }))
+    }
 }
 
 // API request/response types
@@ -628,6 +743,15 @@ struct GeminiGenerationConfig {
     top_p: Option<f32>,
     #[serde(skip_serializing_if = "Option::is_none")]
     top_k: Option<i32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking_config: Option<GeminiThinkingConfig>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct GeminiThinkingConfig {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    thinking_budget: Option<u32>,
 }
 
 #[derive(Debug, Deserialize)]
@@ -16,26 +16,42 @@ mod error;
 mod friendli;
 mod gemini;
 mod image;
+mod mock;
+mod model_list;
 mod ollama;
 mod openai;
 mod opencode;
+pub mod payload_log;
+mod recording;
+mod token_refresh;
 mod traits;
+mod transport;
 
 pub use anthropic::classify_error;
 pub use anthropic::validate_anthropic_turns;
 pub use anthropic::AnthropicClient;
 pub use anthropic::BetaFeatures;
-pub use anthropic::{with_retry, PayloadLogEvent, PayloadLogger, RetryConfig};
+pub use anthropic::PartialJsonParser;
+pub use anthropic::{count_retries, with_retry, RetryConfig};
 pub use auth::*;
 pub use client::ModelClientBuilder;
 pub use config::ProviderConfig;
+pub use config::ReasoningOverride;
 pub use config::{FriendliAIConfig, FriendliEndpointType};
 pub use error::ProviderError;
 pub use friendli::FriendliClient;
 pub use gemini::GeminiClient;
+pub use mock::{
+    MockError, MockModelClient, MockScenario, MockToolCall, MockTurn, MOCK_SCENARIO_ENV,
+};
 pub use ollama::OllamaClient;
 pub use openai::classify_error as classify_openai_error;
 pub use openai::OpenAIClient;
 pub use opencode::OpenCodeClient;
+pub use payload_log::{redact_secrets, PayloadLogEvent, PayloadLogger};
+pub use recording::{
+    recording_dir, CachingModelClient, RecordingMode, RECORDING_DIR_ENV, RECORDING_ENV,
+};
 pub use secrecy::SecretString;
-pub use traits::{ModelClient, ModelResult, ResponseStream};
+pub use traits::{ModelClient, ModelInfo, ModelList, ModelResult, ResponseStream};
+pub use transport::ReqwestTransport;
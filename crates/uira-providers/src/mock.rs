@@ -0,0 +1,473 @@
+//! Scriptable mock provider for testing against adversarial model behavior
+//!
+//! A scenario is a list of turns played back in order, one per request:
+//!
+//! ```yaml
+//! name: flaky-reader
+//! turns:
+//!   - error: { kind: rate_limited, retry_after_ms: 10 }
+//!   - text: "Let me look at the file."
+//!     tool_calls:
+//!       - name: Read
+//!         input: { file_path: README.md }
+//!   - thinking: "The file is long."
+//!     text: "Here is a summary."
+//!     chunk_delay_ms: 50
+//!     fail_after_chunks: 4
+//! ```
+
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use uira_core::{
+    ContentBlock, ContentDelta, Message, MessageDelta, ModelResponse, StopReason, StreamChunk,
+    StreamMessageStart, TokenUsage, ToolSpec,
+};
+
+use crate::traits::ResponseStream;
+use crate::{ModelClient, ModelInfo, ModelList, ModelResult, ProviderError};
+
+/// Path to the scenario used when building `Provider::Mock` clients
+pub const MOCK_SCENARIO_ENV: &str = "UIRA_MOCK_SCENARIO";
+
+const DEFAULT_MOCK_MODEL: &str = "mock-model";
+
+fn default_model() -> String {
+    DEFAULT_MOCK_MODEL.to_string()
+}
+
+fn default_max_tokens() -> usize {
+    128_000
+}
+
+/// A scripted sequence of model turns
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockScenario {
+    #[serde(default)]
+    pub name: Option<String>,
+    #[serde(default = "default_model")]
+    pub model: String,
+    /// Context window reported by the client
+    #[serde(default = "default_max_tokens")]
+    pub max_tokens: usize,
+    #[serde(default)]
+    pub turns: Vec<MockTurn>,
+}
+
+impl MockScenario {
+    pub fn from_yaml(yaml: &str) -> Result<Self, ProviderError> {
+        serde_yaml_ng::from_str(yaml)
+            .map_err(|e| ProviderError::Configuration(format!("invalid mock scenario: {}", e)))
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ProviderError> {
+        let yaml = std::fs::read_to_string(path).map_err(|e| {
+            ProviderError::Configuration(format!(
+                "failed to read mock scenario {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        Self::from_yaml(&yaml)
+    }
+}
+
+/// One scripted response
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct MockTurn {
+    pub thinking: Option<String>,
+    pub text: Option<String>,
+    pub tool_calls: Vec<MockToolCall>,
+    /// Fail the request instead of responding
+    pub error: Option<MockError>,
+    /// Wait before responding (or failing)
+    pub delay_ms: u64,
+    /// Wait between streamed chunks
+    pub chunk_delay_ms: u64,
+    /// Break the stream with an error after this many chunks
+    pub fail_after_chunks: Option<usize>,
+    pub usage: Option<TokenUsage>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MockToolCall {
+    /// Defaults to `call_<turn>_<index>`
+    #[serde(default)]
+    pub id: Option<String>,
+    pub name: String,
+    #[serde(default = "empty_object")]
+    pub input: serde_json::Value,
+}
+
+fn empty_object() -> serde_json::Value {
+    serde_json::json!({})
+}
+
+/// Provider failures a turn can simulate
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum MockError {
+    RateLimited {
+        #[serde(default)]
+        retry_after_ms: u64,
+    },
+    Timeout {
+        #[serde(default)]
+        message: String,
+    },
+    Unavailable,
+    ContextExceeded {
+        used: u64,
+        limit: u64,
+    },
+    AuthenticationFailed {
+        #[serde(default)]
+        message: String,
+    },
+    InvalidResponse {
+        #[serde(default)]
+        message: String,
+    },
+}
+
+impl MockError {
+    fn to_provider_error(&self) -> ProviderError {
+        match self {
+            Self::RateLimited { retry_after_ms } => ProviderError::RateLimited {
+                retry_after_ms: *retry_after_ms,
+            },
+            Self::Timeout { message } => ProviderError::Timeout {
+                message: message.clone(),
+            },
+            Self::Unavailable => ProviderError::Unavailable {
+                provider: "mock".to_string(),
+            },
+            Self::ContextExceeded { used, limit } => ProviderError::ContextExceeded {
+                used: *used,
+                limit: *limit,
+            },
+            Self::AuthenticationFailed { message } => {
+                ProviderError::AuthenticationFailed(message.clone())
+            }
+            Self::InvalidResponse { message } => ProviderError::InvalidResponse(message.clone()),
+        }
+    }
+}
+
+/// Model client that plays back a [`MockScenario`]
+///
+/// Requests past the end of the script fail with `InvalidResponse`.
+pub struct MockModelClient {
+    scenario: MockScenario,
+    turns: Mutex<VecDeque<MockTurn>>,
+    requests: Mutex<Vec<Vec<Message>>>,
+}
+
+impl MockModelClient {
+    pub fn new(scenario: MockScenario) -> Self {
+        Self {
+            turns: Mutex::new(scenario.turns.iter().cloned().collect()),
+            requests: Mutex::new(Vec::new()),
+            scenario,
+        }
+    }
+
+    pub fn from_yaml(yaml: &str) -> Result<Self, ProviderError> {
+        MockScenario::from_yaml(yaml).map(Self::new)
+    }
+
+    pub fn from_file(path: &Path) -> Result<Self, ProviderError> {
+        MockScenario::from_file(path).map(Self::new)
+    }
+
+    /// Load the scenario named by `UIRA_MOCK_SCENARIO`
+    pub fn from_env() -> Result<Self, ProviderError> {
+        let path = std::env::var(MOCK_SCENARIO_ENV).map_err(|_| {
+            ProviderError::Configuration(format!(
+                "{} must point to a mock scenario file",
+                MOCK_SCENARIO_ENV
+            ))
+        })?;
+        Self::from_file(Path::new(&path))
+    }
+
+    /// Append a turn to the end of the script
+    pub fn push_turn(&self, turn: MockTurn) {
+        self.turns.lock().unwrap().push_back(turn);
+    }
+
+    /// Messages sent with each request so far
+    pub fn requests(&self) -> Vec<Vec<Message>> {
+        self.requests.lock().unwrap().clone()
+    }
+
+    pub fn call_count(&self) -> usize {
+        self.requests.lock().unwrap().len()
+    }
+
+    /// Turns not yet played
+    pub fn remaining(&self) -> usize {
+        self.turns.lock().unwrap().len()
+    }
+
+    /// Record the request and pop its turn, applying delay and scripted errors
+    async fn next_turn(&self, messages: &[Message]) -> ModelResult<(usize, MockTurn)> {
+        let (number, turn) = {
+            let mut requests = self.requests.lock().unwrap();
+            requests.push(messages.to_vec());
+            (requests.len(), self.turns.lock().unwrap().pop_front())
+        };
+        let turn = turn.ok_or_else(|| {
+            ProviderError::InvalidResponse(format!(
+                "mock scenario '{}' has no turn {}",
+                self.scenario.name.as_deref().unwrap_or("unnamed"),
+                number
+            ))
+        })?;
+
+        if turn.delay_ms > 0 {
+            tokio::time::sleep(Duration::from_millis(turn.delay_ms)).await;
+        }
+        if let Some(error) = &turn.error {
+            return Err(error.to_provider_error());
+        }
+        Ok((number, turn))
+    }
+
+    fn response(&self, number: usize, turn: &MockTurn) -> ModelResponse {
+        let mut content = Vec::new();
+        if let Some(thinking) = &turn.thinking {
+            content.push(ContentBlock::Thinking {
+                thinking: thinking.clone(),
+                signature: None,
+            });
+        }
+        if let Some(text) = &turn.text {
+            content.push(ContentBlock::text(text.clone()));
+        }
+        for (index, call) in turn.tool_calls.iter().enumerate() {
+            content.push(ContentBlock::tool_use(
+                call.id
+                    .clone()
+                    .unwrap_or_else(|| format!("call_{}_{}", number, index)),
+                call.name.clone(),
+                call.input.clone(),
+            ));
+        }
+
+        ModelResponse {
+            id: format!("mock_msg_{}", number),
+            model: self.scenario.model.clone(),
+            stop_reason: Some(if turn.tool_calls.is_empty() {
+                StopReason::EndTurn
+            } else {
+                StopReason::ToolUse
+            }),
+            content,
+            usage: turn.usage.clone().unwrap_or(TokenUsage {
+                input_tokens: 100,
+                output_tokens: 50,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+/// Break a response into the chunk sequence a streaming provider would send
+fn response_chunks(response: ModelResponse) -> Vec<StreamChunk> {
+    let mut chunks = vec![StreamChunk::MessageStart {
+        message: StreamMessageStart {
+            id: response.id.clone(),
+            model: response.model.clone(),
+            usage: TokenUsage::default(),
+        },
+    }];
+
+    for (index, block) in response.content.into_iter().enumerate() {
+        let (start, deltas) = match block {
+            ContentBlock::Text { text } => (
+                ContentBlock::text(""),
+                text.split_inclusive(' ')
+                    .map(|piece| ContentDelta::TextDelta {
+                        text: piece.to_string(),
+                    })
+                    .collect(),
+            ),
+            ContentBlock::Thinking { thinking, .. } => (
+                ContentBlock::Thinking {
+                    thinking: String::new(),
+                    signature: None,
+                },
+                vec![ContentDelta::ThinkingDelta { thinking }],
+            ),
+            ContentBlock::ToolUse { id, name, input } => (
+                ContentBlock::tool_use(id, name, empty_object()),
+                vec![ContentDelta::InputJsonDelta {
+                    partial_json: input.to_string(),
+                }],
+            ),
+            other => (other, Vec::new()),
+        };
+        chunks.push(StreamChunk::ContentBlockStart {
+            index,
+            content_block: start,
+        });
+        chunks.extend(
+            deltas
+                .into_iter()
+                .map(|delta| StreamChunk::ContentBlockDelta { index, delta }),
+        );
+        chunks.push(StreamChunk::ContentBlockStop { index });
+    }
+
+    chunks.push(StreamChunk::MessageDelta {
+        delta: MessageDelta {
+            stop_reason: response.stop_reason,
+        },
+        usage: Some(response.usage),
+    });
+    chunks.push(StreamChunk::MessageStop);
+    chunks
+}
+
+#[async_trait]
+impl ModelClient for MockModelClient {
+    async fn chat(&self, messages: &[Message], _tools: &[ToolSpec]) -> ModelResult<ModelResponse> {
+        let (number, turn) = self.next_turn(messages).await?;
+        Ok(self.response(number, &turn))
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: &[Message],
+        _tools: &[ToolSpec],
+    ) -> ModelResult<ResponseStream> {
+        let (number, turn) = self.next_turn(messages).await?;
+        let chunks = response_chunks(self.response(number, &turn));
+        let delay = Duration::from_millis(turn.chunk_delay_ms);
+        let fail_after = turn.fail_after_chunks;
+
+        let stream = async_stream::stream! {
+            for (sent, chunk) in chunks.into_iter().enumerate() {
+                if fail_after == Some(sent) {
+                    yield Err(ProviderError::StreamError(format!(
+                        "mock stream interrupted after {} chunks",
+                        sent
+                    )));
+                    return;
+                }
+                if !delay.is_zero() {
+                    tokio::time::sleep(delay).await;
+                }
+                yield Ok(chunk);
+            }
+        };
+        Ok(Box::pin(stream))
+    }
+
+    async fn list_models(&self) -> ModelResult<ModelList> {
+        Ok(ModelList {
+            models: vec![ModelInfo {
+                id: self.scenario.model.clone(),
+                display_name: self.scenario.name.clone(),
+            }],
+            limits: Vec::new(),
+        })
+    }
+
+    fn supports_tools(&self) -> bool {
+        true
+    }
+
+    fn max_tokens(&self) -> usize {
+        self.scenario.max_tokens
+    }
+
+    fn model(&self) -> &str {
+        &self.scenario.model
+    }
+
+    fn provider(&self) -> &str {
+        "mock"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use futures::StreamExt;
+
+    const SCENARIO: &str = r#"
+name: flaky
+turns:
+  - error: { kind: rate_limited, retry_after_ms: 5 }
+  - text: "Reading now"
+    tool_calls:
+      - name: Read
+        input: { file_path: README.md }
+  - thinking: "hmm"
+    text: "all done here"
+    fail_after_chunks: 3
+"#;
+
+    #[tokio::test]
+    async fn plays_scripted_turns_in_order() {
+        let client = MockModelClient::from_yaml(SCENARIO).unwrap();
+        let messages = vec![Message::user("go")];
+
+        let err = client.chat(&messages, &[]).await.unwrap_err();
+        assert!(matches!(
+            err,
+            ProviderError::RateLimited { retry_after_ms: 5 }
+        ));
+
+        let response = client.chat(&messages, &[]).await.unwrap();
+        assert_eq!(response.text(), "Reading now");
+        let calls = response.tool_calls();
+        assert_eq!(calls[0].id, "call_2_0");
+        assert_eq!(calls[0].input["file_path"], "README.md");
+        assert_eq!(response.stop_reason, Some(StopReason::ToolUse));
+
+        let chunks: Vec<_> = client
+            .chat_stream(&messages, &[])
+            .await
+            .unwrap()
+            .collect()
+            .await;
+        assert_eq!(chunks.len(), 4);
+        assert!(matches!(
+            chunks.last(),
+            Some(Err(ProviderError::StreamError(_)))
+        ));
+
+        assert_eq!(client.remaining(), 0);
+        assert!(client.chat(&messages, &[]).await.is_err());
+        assert_eq!(client.call_count(), 4);
+    }
+
+    #[test]
+    fn streams_text_as_deltas() {
+        let client = MockModelClient::new(MockScenario::from_yaml("turns: []").unwrap());
+        let turn = MockTurn {
+            text: Some("a b c".to_string()),
+            ..Default::default()
+        };
+        let chunks = response_chunks(client.response(1, &turn));
+        let text: String = chunks
+            .iter()
+            .filter_map(|c| match c {
+                StreamChunk::ContentBlockDelta {
+                    delta: ContentDelta::TextDelta { text },
+                    ..
+                } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "a b c");
+        assert!(matches!(chunks.last(), Some(StreamChunk::MessageStop)));
+    }
+}
@@ -0,0 +1,83 @@
+//! Shared plumbing for `ModelClient::list_models`
+
+use reqwest::header::HeaderMap;
+use reqwest::RequestBuilder;
+use serde::de::DeserializeOwned;
+
+use crate::{ModelResult, ProviderError};
+
+/// Send a model-listing request and parse the JSON body, keeping the
+/// rate-limit headers
+pub(crate) async fn fetch<T: DeserializeOwned>(
+    provider: &str,
+    request: RequestBuilder,
+) -> ModelResult<(T, Vec<(String, String)>)> {
+    let response = request.send().await?;
+    let status = response.status();
+    let limits = limit_headers(response.headers());
+
+    if !status.is_success() {
+        let body = response.text().await.unwrap_or_default();
+        return Err(match status.as_u16() {
+            401 | 403 => ProviderError::AuthenticationFailed(body),
+            429 => ProviderError::RateLimited {
+                retry_after_ms: 60_000,
+            },
+            _ if status.is_server_error() => ProviderError::Unavailable {
+                provider: provider.to_string(),
+            },
+            _ => ProviderError::InvalidResponse(format!("API error {}: {}", status, body)),
+        });
+    }
+
+    let body = response.json().await?;
+    Ok((body, limits))
+}
+
+/// Rate-limit and quota headers, e.g. `x-ratelimit-remaining-requests` or
+/// `anthropic-ratelimit-tokens-limit`
+pub(crate) fn limit_headers(headers: &HeaderMap) -> Vec<(String, String)> {
+    let mut limits: Vec<(String, String)> = headers
+        .iter()
+        .filter(|(name, _)| {
+            let name = name.as_str();
+            name.contains("ratelimit") || name.contains("quota") || name == "retry-after"
+        })
+        .filter_map(|(name, value)| Some((name.to_string(), value.to_str().ok()?.to_string())))
+        .collect();
+    limits.sort();
+    limits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+
+    #[test]
+    fn test_limit_headers_keeps_rate_limits_only() {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-type", HeaderValue::from_static("application/json"));
+        headers.insert(
+            "x-ratelimit-remaining-requests",
+            HeaderValue::from_static("499"),
+        );
+        headers.insert(
+            "anthropic-ratelimit-tokens-limit",
+            HeaderValue::from_static("80000"),
+        );
+        assert_eq!(
+            limit_headers(&headers),
+            vec![
+                (
+                    "anthropic-ratelimit-tokens-limit".to_string(),
+                    "80000".to_string()
+                ),
+                (
+                    "x-ratelimit-remaining-requests".to_string(),
+                    "499".to_string()
+                ),
+            ]
+        );
+    }
+}
@@ -11,9 +11,10 @@ use uira_core::{
 };
 
 use crate::{
-    image::normalize_image_source, traits::ModelResult, traits::ResponseStream, ModelClient,
-    ProviderConfig, ProviderError,
+    image::normalize_image_source, payload_log, traits::ModelResult, traits::ResponseStream,
+    ModelClient, ProviderConfig, ProviderError,
 };
+use crate::{model_list, ModelInfo, ModelList};
 
 const DEFAULT_MAX_TOKENS: usize = 4096;
 pub const DEFAULT_OLLAMA_URL: &str = "http://localhost:11434";
@@ -189,6 +190,7 @@ impl ModelClient for OllamaClient {
     async fn chat(&self, messages: &[Message], tools: &[ToolSpec]) -> ModelResult<ModelResponse> {
         let request = self.build_request(messages, tools, false);
         let url = format!("{}/api/chat", self.base_url());
+        payload_log::log_request("ollama", &self.config.model, &request);
 
         let response = self.client.post(&url).json(&request).send().await?;
 
@@ -212,6 +214,7 @@ impl ModelClient for OllamaClient {
     ) -> ModelResult<ResponseStream> {
         let request = self.build_request(messages, tools, true);
         let url = format!("{}/api/chat", self.base_url());
+        payload_log::log_request("ollama", &self.config.model, &request);
 
         let response = self.client.post(&url).json(&request).send().await?;
 
@@ -244,6 +247,35 @@ impl ModelClient for OllamaClient {
         Ok(Box::pin(stream))
     }
 
+    async fn list_models(&self) -> ModelResult<ModelList> {
+        #[derive(Deserialize)]
+        struct TagsResponse {
+            #[serde(default)]
+            models: Vec<OllamaModel>,
+        }
+
+        #[derive(Deserialize)]
+        struct OllamaModel {
+            name: String,
+        }
+
+        let url = format!("{}/api/tags", self.base_url());
+        let (response, limits): (TagsResponse, _) =
+            model_list::fetch("ollama", self.client.get(&url)).await?;
+
+        Ok(ModelList {
+            models: response
+                .models
+                .into_iter()
+                .map(|model| ModelInfo {
+                    id: model.name,
+                    display_name: None,
+                })
+                .collect(),
+            limits,
+        })
+    }
+
     fn supports_tools(&self) -> bool {
         // Ollama has limited tool support depending on the model
         // Some models like llama3.1 support tools
@@ -1,8 +1,9 @@
 //! OpenAI client implementation with OAuth (Codex) support
 
-use crate::auth::CredentialStore;
+use crate::auth::{CredentialStore, StoredCredential};
+use crate::token_refresh::{OAuthToken, RefreshedToken, TokenEndpoint};
+use crate::{model_list, ModelInfo, ModelList};
 use async_trait::async_trait;
-use chrono::Utc;
 use futures::StreamExt;
 use reqwest::header::CONTENT_TYPE;
 use reqwest::Client;
@@ -10,10 +11,9 @@ use secrecy::{ExposeSecret, SecretString};
 use serde::{Deserialize, Serialize};
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{Mutex, RwLock};
 use uira_core::{
-    ContentBlock, ContentDelta, Message, MessageContent, MessageDelta, ModelResponse, Role,
-    StopReason, StreamChunk, StreamMessageStart, TokenUsage, ToolSpec,
+    ContentBlock, ContentDelta, Message, MessageContent, MessageDelta, ModelResponse, Provider,
+    Role, StopReason, StreamChunk, StreamMessageStart, TokenUsage, ToolSpec,
 };
 
 mod error_classify;
@@ -22,15 +22,14 @@ pub use error_classify::classify_error;
 
 use crate::anthropic::{with_retry, RetryConfig};
 use crate::{
-    image::image_source_to_data_url, traits::ModelResult, traits::ResponseStream, ModelClient,
-    ProviderConfig, ProviderError,
+    image::image_source_to_data_url, payload_log, traits::ModelResult, traits::ResponseStream,
+    ModelClient, ProviderConfig, ProviderError, ReasoningOverride,
 };
 
 const DEFAULT_MAX_TOKENS: usize = 4096;
 const PROVIDER_NAME: &str = "openai";
 const ENV_OPENAI_API_KEY: &str = "OPENAI_API_KEY";
 const MAX_SSE_BUFFER: usize = 10 * 1024 * 1024;
-const TOKEN_REFRESH_BUFFER_SECS: i64 = 300;
 const OPENAI_OAUTH_TOKEN_URL: &str = "https://auth.openai.com/oauth/token";
 const CODEX_CLIENT_ID: &str = "app_EMoamEEZ73f0CkXaXp7hrann";
 
@@ -43,149 +42,35 @@ fn extract_retry_after(response: &reqwest::Response) -> Option<u64> {
         .map(|secs| secs * 1000)
 }
 
-#[derive(Debug, Clone)]
+#[derive(Clone)]
 enum CredentialSource {
+    /// OAuth credentials, shared with clones so they refresh once
     OAuth {
-        access_token: SecretString,
-        refresh_token: Option<SecretString>,
-        expires_at: Option<i64>,
-        account_id: Option<String>,
+        token: Arc<OAuthToken>,
+        endpoint: Arc<OpenAITokenEndpoint>,
     },
     ApiKey(SecretString),
 }
 
-pub struct OpenAIClient {
+/// The Codex OAuth token endpoint, which also reports the ChatGPT account
+struct OpenAITokenEndpoint {
     client: Client,
-    config: ProviderConfig,
-    credential: Arc<RwLock<CredentialSource>>,
-    refresh_lock: Mutex<()>,
+    /// Account from the latest ID token
+    account_id: std::sync::RwLock<Option<String>>,
 }
 
-impl OpenAIClient {
-    pub fn new(config: ProviderConfig) -> Result<Self, ProviderError> {
-        let credential = Self::load_credential(&config)?;
-
-        let mut headers = reqwest::header::HeaderMap::new();
-        headers.insert("content-type", "application/json".parse().unwrap());
-
-        let timeout = Duration::from_secs(config.timeout_seconds.unwrap_or(120));
-
-        let client = Client::builder()
-            .default_headers(headers)
-            .timeout(timeout)
-            .build()?;
-
-        Ok(Self {
-            client,
-            config,
-            credential: Arc::new(RwLock::new(credential)),
-            refresh_lock: Mutex::new(()),
-        })
-    }
-
-    fn load_credential(config: &ProviderConfig) -> Result<CredentialSource, ProviderError> {
-        if let Ok(store) = CredentialStore::load() {
-            if let Some(cred) = store.get(PROVIDER_NAME) {
-                use crate::auth::secrecy::ExposeSecret as AuthExposeSecret;
-                use crate::auth::StoredCredential;
-                match cred {
-                    StoredCredential::OAuth {
-                        access_token,
-                        refresh_token,
-                        expires_at,
-                    } => {
-                        return Ok(CredentialSource::OAuth {
-                            access_token: SecretString::from(
-                                access_token.expose_secret().to_string(),
-                            ),
-                            refresh_token: refresh_token
-                                .as_ref()
-                                .map(|t| SecretString::from(t.expose_secret().to_string())),
-                            expires_at: *expires_at,
-                            account_id: None,
-                        });
-                    }
-                    StoredCredential::ApiKey { key } => {
-                        return Ok(CredentialSource::ApiKey(SecretString::from(
-                            key.expose_secret().to_string(),
-                        )));
-                    }
-                }
-            }
-        }
-
-        if let Some(api_key) = &config.api_key {
-            return Ok(CredentialSource::ApiKey(api_key.clone()));
-        }
-
-        if let Ok(key) = std::env::var(ENV_OPENAI_API_KEY) {
-            return Ok(CredentialSource::ApiKey(SecretString::from(key)));
-        }
-
-        Err(ProviderError::Configuration(
-            "No OpenAI credentials found. Set OPENAI_API_KEY or authenticate via OAuth.".into(),
-        ))
-    }
-
-    fn is_token_expired(expires_at: Option<i64>) -> bool {
-        match expires_at {
-            Some(exp) => {
-                let now = Utc::now().timestamp();
-                let exp_secs = if exp > 1_000_000_000_000 {
-                    exp / 1000
-                } else {
-                    exp
-                };
-                now >= (exp_secs - TOKEN_REFRESH_BUFFER_SECS)
-            }
-            None => false,
-        }
-    }
-
-    async fn refresh_token_if_needed(&self) -> Result<(), ProviderError> {
-        let might_need_refresh = {
-            let credential = self.credential.read().await;
-            if let CredentialSource::OAuth { expires_at, .. } = &*credential {
-                Self::is_token_expired(*expires_at)
-            } else {
-                false
-            }
-        };
-
-        if !might_need_refresh {
-            return Ok(());
-        }
-
-        let _guard = self.refresh_lock.lock().await;
-
-        let (needs_refresh, refresh_token_opt) = {
-            let credential = self.credential.read().await;
-            if let CredentialSource::OAuth {
-                refresh_token,
-                expires_at,
-                ..
-            } = &*credential
-            {
-                (Self::is_token_expired(*expires_at), refresh_token.clone())
-            } else {
-                (false, None)
-            }
-        };
-
-        if needs_refresh {
-            if let Some(refresh_token) = refresh_token_opt {
-                self.do_token_refresh(&refresh_token).await?;
-            } else {
-                return Err(ProviderError::Configuration(
-                    "OAuth token expired and no refresh token available".into(),
-                ));
-            }
-        }
-
-        Ok(())
+impl OpenAITokenEndpoint {
+    fn account_id(&self) -> Option<String> {
+        self.account_id
+            .read()
+            .unwrap_or_else(|e| e.into_inner())
+            .clone()
     }
+}
 
-    async fn do_token_refresh(&self, refresh_token: &SecretString) -> Result<(), ProviderError> {
+#[async_trait]
+impl TokenEndpoint for OpenAITokenEndpoint {
+    async fn refresh(&self, refresh_token: &SecretString) -> Result<RefreshedToken, ProviderError> {
         let response = self
             .client
             .post(OPENAI_OAUTH_TOKEN_URL)
@@ -225,62 +110,91 @@ impl OpenAIClient {
             ProviderError::InvalidResponse(format!("Invalid token response: {}", e))
         })?;
 
-        let expires_at = token_response
-            .expires_in
-            .map(|exp| Utc::now().timestamp() + exp);
-
-        let account_id = token_response
+        if let Some(account_id) = token_response
             .id_token
-            .as_ref()
-            .and_then(|t| Self::extract_account_id_from_jwt(t));
+            .as_deref()
+            .and_then(OpenAIClient::extract_account_id_from_jwt)
+        {
+            *self.account_id.write().unwrap_or_else(|e| e.into_inner()) = Some(account_id);
+        }
 
-        let old_account_id = {
-            let cred = self.credential.read().await;
-            if let CredentialSource::OAuth { account_id, .. } = &*cred {
-                account_id.clone()
-            } else {
-                None
-            }
-        };
+        Ok(RefreshedToken {
+            access_token: token_response.access_token,
+            refresh_token: token_response.refresh_token,
+            expires_in: token_response.expires_in,
+        })
+    }
+}
 
-        let mut credential = self.credential.write().await;
-        *credential = CredentialSource::OAuth {
-            access_token: SecretString::from(token_response.access_token.clone()),
-            refresh_token: token_response
-                .refresh_token
-                .clone()
-                .map(SecretString::from)
-                .or_else(|| Some(refresh_token.clone())),
-            expires_at,
-            account_id: account_id.or(old_account_id),
-        };
+pub struct OpenAIClient {
+    client: Client,
+    config: ProviderConfig,
+    credential: CredentialSource,
+}
 
-        let old_refresh_token_str = refresh_token.expose_secret().to_string();
-        if let Ok(mut store) = CredentialStore::load() {
-            use crate::auth::StoredCredential;
-            store.insert(
-                PROVIDER_NAME.to_string(),
-                StoredCredential::OAuth {
-                    access_token: crate::auth::secrecy::SecretString::from(
-                        token_response.access_token,
-                    ),
-                    refresh_token: token_response
-                        .refresh_token
-                        .map(crate::auth::secrecy::SecretString::from)
-                        .or_else(|| {
-                            Some(crate::auth::secrecy::SecretString::from(
-                                old_refresh_token_str,
-                            ))
-                        }),
-                    expires_at,
-                },
-            );
-            if let Err(e) = store.save() {
-                tracing::error!("Failed to save credential store after token refresh: {}", e);
+impl OpenAIClient {
+    pub fn new(config: ProviderConfig) -> Result<Self, ProviderError> {
+        let mut headers = reqwest::header::HeaderMap::new();
+        headers.insert("content-type", "application/json".parse().unwrap());
+
+        let timeout = Duration::from_secs(config.timeout_seconds.unwrap_or(120));
+
+        let client = Client::builder()
+            .default_headers(headers)
+            .timeout(timeout)
+            .build()?;
+
+        let credential = Self::load_credential(&config, &client)?;
+
+        Ok(Self {
+            client,
+            config,
+            credential,
+        })
+    }
+
+    fn load_credential(
+        config: &ProviderConfig,
+        client: &Client,
+    ) -> Result<CredentialSource, ProviderError> {
+        // OpenAI-compatible servers (llama.cpp, custom endpoints) get their
+        // own key, never the stored OpenAI login
+        if config.provider != Provider::OpenAI {
+            if let Some(api_key) = &config.api_key {
+                return Ok(CredentialSource::ApiKey(api_key.clone()));
             }
         }
 
-        Ok(())
+        if let Ok(store) = CredentialStore::load() {
+            if let Some(cred) = store.get(PROVIDER_NAME) {
+                if let StoredCredential::ApiKey { key } = cred {
+                    return Ok(CredentialSource::ApiKey(key.clone()));
+                }
+                let endpoint = Arc::new(OpenAITokenEndpoint {
+                    client: client.clone(),
+                    account_id: std::sync::RwLock::new(None),
+                });
+                if let Some(token) = OAuthToken::from_stored(PROVIDER_NAME, cred, endpoint.clone())
+                {
+                    return Ok(CredentialSource::OAuth {
+                        token: Arc::new(token),
+                        endpoint,
+                    });
+                }
+            }
+        }
+
+        if let Some(api_key) = &config.api_key {
+            return Ok(CredentialSource::ApiKey(api_key.clone()));
+        }
+
+        if let Ok(key) = std::env::var(ENV_OPENAI_API_KEY) {
+            return Ok(CredentialSource::ApiKey(SecretString::from(key)));
+        }
+
+        Err(ProviderError::Configuration(
+            "No OpenAI credentials found. Set OPENAI_API_KEY or authenticate via OAuth.".into(),
+        ))
     }
 
     fn extract_account_id_from_jwt(token: &str) -> Option<String> {
@@ -325,22 +239,16 @@ impl OpenAIClient {
     }
 
     async fn get_auth_headers(&self) -> Result<Vec<(String, String)>, ProviderError> {
-        self.refresh_token_if_needed().await?;
-
-        let credential = self.credential.read().await;
-        match &*credential {
-            CredentialSource::OAuth {
-                access_token,
-                account_id,
-                ..
-            } => {
+        match &self.credential {
+            CredentialSource::OAuth { token, endpoint } => {
+                let access_token = token.access_token().await?;
                 let mut headers = vec![(
                     "Authorization".to_string(),
                     format!("Bearer {}", access_token.expose_secret()),
                 )];
 
-                if let Some(acc_id) = account_id {
-                    headers.push(("ChatGPT-Account-Id".to_string(), acc_id.clone()));
+                if let Some(acc_id) = endpoint.account_id() {
+                    headers.push(("ChatGPT-Account-Id".to_string(), acc_id));
                 }
 
                 Ok(headers)
@@ -352,6 +260,21 @@ impl OpenAIClient {
         }
     }
 
+    /// Run `request`, refreshing a rejected OAuth token and trying once more
+    async fn with_token_refresh<T, F, Fut>(&self, request: F) -> Result<T, ProviderError>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T, ProviderError>>,
+    {
+        match &self.credential {
+            CredentialSource::OAuth { token, .. } => token.retry_if_rejected(request).await,
+            CredentialSource::ApiKey(_) => {
+                let mut request = request;
+                request().await
+            }
+        }
+    }
+
     fn build_request(
         &self,
         messages: &[Message],
@@ -381,6 +304,7 @@ impl OpenAIClient {
             },
             stream: Some(stream),
             temperature: self.config.temperature,
+            reasoning_effort: self.config.reasoning_effort.clone(),
         }
     }
 
@@ -545,34 +469,37 @@ impl ModelClient for OpenAIClient {
     async fn chat(&self, messages: &[Message], tools: &[ToolSpec]) -> ModelResult<ModelResponse> {
         let retry_config = self.retry_config();
 
-        with_retry(&retry_config, || async {
-            let auth_headers = self.get_auth_headers().await?;
-            let request = self.build_request(messages, tools, false);
-            let url = format!("{}/v1/chat/completions", self.base_url());
+        self.with_token_refresh(|| {
+            with_retry(&retry_config, || async {
+                let auth_headers = self.get_auth_headers().await?;
+                let request = self.build_request(messages, tools, false);
+                let url = format!("{}/v1/chat/completions", self.base_url());
+                payload_log::log_request(PROVIDER_NAME, &self.config.model, &request);
 
-            let mut req_builder = self.client.post(&url).json(&request);
-            for (key, value) in &auth_headers {
-                req_builder = req_builder.header(key, value);
-            }
+                let mut req_builder = self.client.post(&url).json(&request);
+                for (key, value) in &auth_headers {
+                    req_builder = req_builder.header(key, value);
+                }
 
-            let response = req_builder.send().await?;
+                let response = req_builder.send().await?;
 
-            if !response.status().is_success() {
-                let status = response.status().as_u16();
-                let retry_after = extract_retry_after(&response);
-                let body = response.text().await.unwrap_or_default();
+                if !response.status().is_success() {
+                    let status = response.status().as_u16();
+                    let retry_after = extract_retry_after(&response);
+                    let body = response.text().await.unwrap_or_default();
 
-                let mut err = classify_error(status, &body);
-                if let ProviderError::RateLimited { retry_after_ms } = &mut err {
-                    if let Some(ra) = retry_after {
-                        *retry_after_ms = ra;
+                    let mut err = classify_error(status, &body);
+                    if let ProviderError::RateLimited { retry_after_ms } = &mut err {
+                        if let Some(ra) = retry_after {
+                            *retry_after_ms = ra;
+                        }
                     }
+                    return Err(err);
                 }
-                return Err(err);
-            }
 
-            let api_response: OpenAIResponse = response.json().await?;
-            Ok(self.convert_response(api_response))
+                let api_response: OpenAIResponse = response.json().await?;
+                Ok(self.convert_response(api_response))
+            })
         })
         .await
     }
@@ -584,35 +511,39 @@ impl ModelClient for OpenAIClient {
     ) -> ModelResult<ResponseStream> {
         let retry_config = self.retry_config();
 
-        let response = with_retry(&retry_config, || async {
-            let auth_headers = self.get_auth_headers().await?;
-            let request = self.build_request(messages, tools, true);
-            let url = format!("{}/v1/chat/completions", self.base_url());
-
-            let mut req_builder = self.client.post(&url).json(&request);
-            for (key, value) in &auth_headers {
-                req_builder = req_builder.header(key, value);
-            }
+        let response = self
+            .with_token_refresh(|| {
+                with_retry(&retry_config, || async {
+                    let auth_headers = self.get_auth_headers().await?;
+                    let request = self.build_request(messages, tools, true);
+                    let url = format!("{}/v1/chat/completions", self.base_url());
+                    payload_log::log_request(PROVIDER_NAME, &self.config.model, &request);
+
+                    let mut req_builder = self.client.post(&url).json(&request);
+                    for (key, value) in &auth_headers {
+                        req_builder = req_builder.header(key, value);
+                    }
 
-            let response = req_builder.send().await?;
+                    let response = req_builder.send().await?;
 
-            if !response.status().is_success() {
-                let status = response.status().as_u16();
-                let retry_after = extract_retry_after(&response);
-                let body = response.text().await.unwrap_or_default();
+                    if !response.status().is_success() {
+                        let status = response.status().as_u16();
+                        let retry_after = extract_retry_after(&response);
+                        let body = response.text().await.unwrap_or_default();
 
-                let mut err = classify_error(status, &body);
-                if let ProviderError::RateLimited { retry_after_ms } = &mut err {
-                    if let Some(ra) = retry_after {
-                        *retry_after_ms = ra;
+                        let mut err = classify_error(status, &body);
+                        if let ProviderError::RateLimited { retry_after_ms } = &mut err {
+                            if let Some(ra) = retry_after {
+                                *retry_after_ms = ra;
+                            }
+                        }
+                        return Err(err);
                     }
-                }
-                return Err(err);
-            }
 
-            Ok(response)
-        })
-        .await?;
+                    Ok(response)
+                })
+            })
+            .await?;
 
         let byte_stream = response.bytes_stream();
         let stream = async_stream::try_stream! {
@@ -683,6 +614,40 @@ impl ModelClient for OpenAIClient {
         Ok(Box::pin(stream))
     }
 
+    async fn list_models(&self) -> ModelResult<ModelList> {
+        #[derive(Deserialize)]
+        struct ModelsResponse {
+            data: Vec<OpenAIModel>,
+        }
+
+        #[derive(Deserialize)]
+        struct OpenAIModel {
+            id: String,
+        }
+
+        let url = format!("{}/v1/models", self.base_url());
+        let (response, limits): (ModelsResponse, _) = self
+            .with_token_refresh(|| async {
+                let mut req = self.client.get(&url);
+                for (key, value) in self.get_auth_headers().await? {
+                    req = req.header(key, value);
+                }
+                model_list::fetch(PROVIDER_NAME, req).await
+            })
+            .await?;
+
+        let mut models: Vec<ModelInfo> = response
+            .data
+            .into_iter()
+            .map(|model| ModelInfo {
+                id: model.id,
+                display_name: None,
+            })
+            .collect();
+        models.sort_by(|a, b| a.id.cmp(&b.id));
+        Ok(ModelList { models, limits })
+    }
+
     fn supports_tools(&self) -> bool {
         true
     }
@@ -698,6 +663,17 @@ impl ModelClient for OpenAIClient {
     fn provider(&self) -> &str {
         PROVIDER_NAME
     }
+
+    fn with_reasoning(&self, reasoning: &ReasoningOverride) -> Option<Arc<dyn ModelClient>> {
+        reasoning.effort.as_ref()?;
+        let mut config = self.config.clone();
+        config.apply_reasoning(reasoning);
+        Some(Arc::new(Self {
+            client: self.client.clone(),
+            config,
+            credential: self.credential.clone(),
+        }))
+    }
 }
 
 impl OpenAIClient {
@@ -791,6 +767,8 @@ struct OpenAIRequest {
     stream: Option<bool>,
     #[serde(skip_serializing_if = "Option::is_none")]
     temperature: Option<f32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    reasoning_effort: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
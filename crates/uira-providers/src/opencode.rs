@@ -16,9 +16,10 @@ use uira_core::{
 };
 
 use crate::{
-    image::image_source_to_data_url, traits::ModelResult, traits::ResponseStream, ModelClient,
-    ProviderConfig, ProviderError,
+    image::image_source_to_data_url, payload_log, traits::ModelResult, traits::ResponseStream,
+    ModelClient, ProviderConfig, ProviderError,
 };
+use crate::{model_list, ModelInfo, ModelList};
 
 const OPENCODE_ZEN_BASE_URL: &str = "https://opencode.ai/zen/v1";
 const DEFAULT_MAX_TOKENS: usize = 8192;
@@ -267,6 +268,7 @@ impl ModelClient for OpenCodeClient {
     async fn chat(&self, messages: &[Message], tools: &[ToolSpec]) -> ModelResult<ModelResponse> {
         let request = self.build_request(messages, tools, false);
         let url = format!("{}/chat/completions", self.base_url());
+        payload_log::log_request(PROVIDER_NAME, &self.config.model, &request);
 
         let response = self.client.post(&url).json(&request).send().await?;
 
@@ -312,6 +314,7 @@ impl ModelClient for OpenCodeClient {
 
         let request = self.build_request(messages, tools, true);
         let url = format!("{}/chat/completions", self.base_url());
+        payload_log::log_request(PROVIDER_NAME, &self.config.model, &request);
 
         let response = self.client.post(&url).json(&request).send().await?;
 
@@ -402,6 +405,34 @@ impl ModelClient for OpenCodeClient {
         Ok(Box::pin(stream))
     }
 
+    async fn list_models(&self) -> ModelResult<ModelList> {
+        #[derive(Deserialize)]
+        struct ModelsResponse {
+            data: Vec<OpenCodeModel>,
+        }
+
+        #[derive(Deserialize)]
+        struct OpenCodeModel {
+            id: String,
+        }
+
+        let url = format!("{}/models", self.base_url());
+        let (response, limits): (ModelsResponse, _) =
+            model_list::fetch(PROVIDER_NAME, self.client.get(&url)).await?;
+
+        Ok(ModelList {
+            models: response
+                .data
+                .into_iter()
+                .map(|model| ModelInfo {
+                    id: model.id,
+                    display_name: None,
+                })
+                .collect(),
+            limits,
+        })
+    }
+
     fn supports_tools(&self) -> bool {
         true
     }
@@ -0,0 +1,639 @@
+//! Optional JSONL request payload logging for debugging and auditing
+//!
+//! Configured through `providers.payload_log` and installed once per process
+//! with [`install`]; each provider then asks for a logger with
+//! [`PayloadLogger::for_provider`]. Environment variables override the config:
+//! `UIRA_PAYLOAD_LOG=true` and `UIRA_PAYLOAD_LOG_FILE=<path>` for every
+//! provider, and the older `UIRA_ANTHROPIC_PAYLOAD_LOG` /
+//! `UIRA_ANTHROPIC_PAYLOAD_LOG_FILE` for Anthropic only.
+//!
+//! Payloads are scrubbed before they are written: secret-looking keys and
+//! values are replaced with `[REDACTED]` and long strings (file contents,
+//! base64 images) are cut to `max_string_bytes`. The log rotates once it
+//! reaches `max_file_bytes`, keeping `max_files` older files that are gzipped
+//! when `compress` is set. Requests are tagged with the session they belong to
+//! when the call runs inside [`with_session`].
+
+use chrono::Utc;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, File, OpenOptions};
+use std::future::Future;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, RwLock};
+use tracing::warn;
+use uira_core::schema::PayloadLogSettings;
+
+const REDACTED: &str = "[REDACTED]";
+
+/// Object keys whose values are always redacted (compared case-insensitively)
+const SECRET_KEYS: &[&str] = &[
+    "api_key",
+    "apikey",
+    "x-api-key",
+    "token",
+    "access_token",
+    "refresh_token",
+    "id_token",
+    "authorization",
+    "secret",
+    "client_secret",
+    "password",
+];
+
+lazy_static! {
+    /// Credentials that show up inside otherwise harmless strings, such as a
+    /// key pasted into a prompt or echoed back in a tool result
+    static ref SECRET_VALUE: Regex = Regex::new(
+        r"(?:sk-[A-Za-z0-9_-]{16,}|gh[pousr]_[A-Za-z0-9]{20,}|xox[abprs]-[A-Za-z0-9-]{10,}|AKIA[0-9A-Z]{16}|AIza[0-9A-Za-z_-]{35}|(?i:bearer)\s+[A-Za-z0-9._~+/-]{16,}=*)"
+    )
+    .unwrap();
+}
+
+static INSTALLED: RwLock<Option<PayloadLogSettings>> = RwLock::new(None);
+
+/// Serializes appends and rotation across loggers in this process
+static WRITE_LOCK: Mutex<()> = Mutex::new(());
+
+tokio::task_local! {
+    static SESSION_ID: String;
+}
+
+/// Set the process-wide payload log settings used by
+/// [`PayloadLogger::for_provider`]
+pub fn install(settings: PayloadLogSettings) {
+    *INSTALLED.write().unwrap_or_else(|e| e.into_inner()) = Some(settings);
+}
+
+fn installed() -> PayloadLogSettings {
+    INSTALLED
+        .read()
+        .unwrap_or_else(|e| e.into_inner())
+        .clone()
+        .unwrap_or_default()
+}
+
+/// Run `future` with its provider requests attributed to `session_id`
+pub async fn with_session<F: Future>(session_id: impl Into<String>, future: F) -> F::Output {
+    SESSION_ID.scope(session_id.into(), future).await
+}
+
+/// Log `request` for `provider` when payload logging is enabled for it
+pub(crate) fn log_request<T: Serialize>(provider: &str, model_id: &str, request: &T) {
+    let logger = PayloadLogger::for_provider(provider);
+    if !logger.is_enabled() {
+        return;
+    }
+    if let Ok(payload) = serde_json::to_value(request) {
+        logger.log_request(None, provider, model_id, &payload);
+    }
+}
+
+/// Session set by the enclosing [`with_session`], if any
+pub fn current_session() -> Option<String> {
+    SESSION_ID.try_with(Clone::clone).ok()
+}
+
+fn env_flag(name: &str) -> Option<bool> {
+    std::env::var(name).ok().map(|v| v == "true" || v == "1")
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+/// Payload log event structure
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PayloadLogEvent {
+    pub ts: String,
+    pub stage: String, // "request" | "usage" | "error"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub session_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_id: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload_digest: Option<String>,
+    /// Scrubbed request body
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub payload: Option<serde_json::Value>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+pub struct PayloadLogger {
+    enabled: bool,
+    log_path: PathBuf,
+    settings: PayloadLogSettings,
+}
+
+impl PayloadLogger {
+    pub fn new(enabled: bool, path: Option<PathBuf>) -> Self {
+        let log_path = path.unwrap_or_else(Self::default_log_path);
+        Self {
+            enabled,
+            log_path,
+            settings: PayloadLogSettings::default(),
+        }
+    }
+
+    /// Logger for explicit settings, ignoring the provider filter and the
+    /// environment
+    pub fn with_settings(settings: PayloadLogSettings) -> Self {
+        let log_path = settings
+            .path
+            .as_deref()
+            .map(expand_home)
+            .unwrap_or_else(Self::default_log_path);
+        Self {
+            enabled: settings.enabled,
+            log_path,
+            settings,
+        }
+    }
+
+    /// Logger for `provider` from the installed settings and environment
+    pub fn for_provider(provider: &str) -> Self {
+        let settings = installed();
+        let selected = settings.providers.is_empty()
+            || settings
+                .providers
+                .iter()
+                .any(|p| p.eq_ignore_ascii_case(provider));
+        let mut enabled = settings.enabled && selected;
+        let mut logger = Self::with_settings(settings);
+
+        if provider == "anthropic" {
+            if let Some(flag) = env_flag("UIRA_ANTHROPIC_PAYLOAD_LOG") {
+                enabled = flag;
+            }
+            if let Ok(path) = std::env::var("UIRA_ANTHROPIC_PAYLOAD_LOG_FILE") {
+                logger.log_path = PathBuf::from(path);
+            }
+        }
+        if let Some(flag) = env_flag("UIRA_PAYLOAD_LOG") {
+            enabled = flag;
+        }
+        if let Ok(path) = std::env::var("UIRA_PAYLOAD_LOG_FILE") {
+            logger.log_path = PathBuf::from(path);
+        }
+
+        logger.enabled = enabled;
+        logger
+    }
+
+    /// Anthropic logger configured only from the environment
+    pub fn from_env() -> Self {
+        let enabled = env_flag("UIRA_ANTHROPIC_PAYLOAD_LOG").unwrap_or(false);
+
+        let log_path = std::env::var("UIRA_ANTHROPIC_PAYLOAD_LOG_FILE")
+            .ok()
+            .map(PathBuf::from);
+
+        Self::new(enabled, log_path)
+    }
+
+    /// Default log file: `~/.local/share/uira/logs/payloads.jsonl`
+    pub fn default_log_path() -> PathBuf {
+        let mut path = dirs::data_local_dir().unwrap_or_else(|| PathBuf::from("."));
+        path.push("uira");
+        path.push("logs");
+        path.push("payloads.jsonl");
+        path
+    }
+
+    /// Log file for `settings`, honoring `UIRA_PAYLOAD_LOG_FILE`
+    pub fn configured_path(settings: &PayloadLogSettings) -> PathBuf {
+        std::env::var("UIRA_PAYLOAD_LOG_FILE")
+            .map(PathBuf::from)
+            .unwrap_or_else(|_| {
+                settings
+                    .path
+                    .as_deref()
+                    .map(expand_home)
+                    .unwrap_or_else(Self::default_log_path)
+            })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.enabled
+    }
+
+    pub fn log_path(&self) -> &Path {
+        &self.log_path
+    }
+
+    /// Log a scrubbed request payload with a SHA-256 digest
+    ///
+    /// The digest covers the redacted but untruncated payload, so identical
+    /// requests hash the same even when their logged copies are cut short.
+    /// `session_id` falls back to the session set by [`with_session`].
+    pub fn log_request(
+        &self,
+        session_id: Option<String>,
+        provider: &str,
+        model_id: &str,
+        payload: &serde_json::Value,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut scrubbed = redact_secrets(payload);
+        let digest = self.compute_digest(&scrubbed);
+        truncate_strings(&mut scrubbed, self.settings.max_string_bytes);
+
+        let event = PayloadLogEvent {
+            ts: Utc::now().to_rfc3339(),
+            stage: "request".to_string(),
+            session_id: session_id.or_else(current_session),
+            provider: Some(provider.to_string()),
+            model_id: Some(model_id.to_string()),
+            payload_digest: Some(digest),
+            payload: Some(scrubbed),
+            error: None,
+        };
+
+        self.write_event(&event);
+    }
+
+    /// Log usage information
+    pub fn log_usage(
+        &self,
+        session_id: Option<String>,
+        provider: &str,
+        model_id: &str,
+        input_tokens: u64,
+        output_tokens: u64,
+    ) {
+        if !self.enabled {
+            return;
+        }
+
+        let usage_payload = serde_json::json!({
+            "input_tokens": input_tokens,
+            "output_tokens": output_tokens,
+        });
+
+        let digest = self.compute_digest(&usage_payload);
+
+        let event = PayloadLogEvent {
+            ts: Utc::now().to_rfc3339(),
+            stage: "usage".to_string(),
+            session_id: session_id.or_else(current_session),
+            provider: Some(provider.to_string()),
+            model_id: Some(model_id.to_string()),
+            payload_digest: Some(digest),
+            payload: Some(usage_payload),
+            error: None,
+        };
+
+        self.write_event(&event);
+    }
+
+    /// Log an error
+    pub fn log_error(&self, session_id: Option<String>, error: &str) {
+        if !self.enabled {
+            return;
+        }
+
+        let mut error = redact_string(error);
+        truncate_string(&mut error, self.settings.max_string_bytes);
+
+        let event = PayloadLogEvent {
+            ts: Utc::now().to_rfc3339(),
+            stage: "error".to_string(),
+            session_id: session_id.or_else(current_session),
+            provider: None,
+            model_id: None,
+            payload_digest: None,
+            payload: None,
+            error: Some(error),
+        };
+
+        self.write_event(&event);
+    }
+
+    /// Compute SHA-256 digest of payload
+    fn compute_digest(&self, payload: &serde_json::Value) -> String {
+        let json_str = serde_json::to_string(payload).unwrap_or_default();
+        let mut hasher = Sha256::new();
+        hasher.update(json_str.as_bytes());
+        let result = hasher.finalize();
+        hex::encode(result)
+    }
+
+    fn write_event(&self, event: &PayloadLogEvent) {
+        let Ok(json) = serde_json::to_string(event) else {
+            return;
+        };
+        let _guard = WRITE_LOCK.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let Some(parent) = self.log_path.parent() {
+            if let Err(e) = fs::create_dir_all(parent) {
+                warn!("Failed to create payload log directory: {}", e);
+                return;
+            }
+        }
+
+        let needs_rotation = fs::metadata(&self.log_path)
+            .map(|meta| meta.len() > 0 && meta.len() + json.len() as u64 > self.max_file_bytes())
+            .unwrap_or(false);
+        if needs_rotation {
+            if let Err(e) = self.rotate() {
+                warn!("Failed to rotate payload log: {}", e);
+            }
+        }
+
+        match OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.log_path)
+        {
+            Ok(mut file) => {
+                if let Err(e) = writeln!(file, "{}", json) {
+                    warn!("Failed to write payload log event: {}", e);
+                }
+            }
+            Err(e) => {
+                warn!("Failed to open payload log file: {}", e);
+            }
+        }
+    }
+
+    fn max_file_bytes(&self) -> u64 {
+        self.settings.max_file_bytes.max(1)
+    }
+
+    /// Shift `log.N` to `log.N+1`, dropping the oldest, then move the live
+    /// log to `log.1` (gzipped as `log.1.gz` when compressing)
+    fn rotate(&self) -> io::Result<()> {
+        let keep = self.settings.max_files;
+        if keep == 0 {
+            return fs::remove_file(&self.log_path);
+        }
+
+        for index in (1..=keep).rev() {
+            for compressed in [false, true] {
+                let from = rotated_path(&self.log_path, index, compressed);
+                if !from.exists() {
+                    continue;
+                }
+                if index == keep {
+                    fs::remove_file(&from)?;
+                } else {
+                    fs::rename(&from, rotated_path(&self.log_path, index + 1, compressed))?;
+                }
+            }
+        }
+
+        if self.settings.compress {
+            let target = rotated_path(&self.log_path, 1, true);
+            let mut encoder = GzEncoder::new(File::create(&target)?, Compression::default());
+            io::copy(&mut File::open(&self.log_path)?, &mut encoder)?;
+            encoder.finish()?;
+            fs::remove_file(&self.log_path)
+        } else {
+            fs::rename(&self.log_path, rotated_path(&self.log_path, 1, false))
+        }
+    }
+}
+
+/// Replace secret-looking keys and values with `[REDACTED]`, at any depth.
+/// Also used to scrub events before they leave the process, e.g. for
+/// shared gateway sessions.
+pub fn redact_secrets(payload: &serde_json::Value) -> serde_json::Value {
+    use serde_json::Value;
+
+    match payload {
+        Value::Object(obj) => Value::Object(
+            obj.iter()
+                .map(|(key, value)| {
+                    let lower = key.to_ascii_lowercase();
+                    let value = if SECRET_KEYS.contains(&lower.as_str()) {
+                        Value::String(REDACTED.to_string())
+                    } else {
+                        redact_secrets(value)
+                    };
+                    (key.clone(), value)
+                })
+                .collect(),
+        ),
+        Value::Array(items) => Value::Array(items.iter().map(redact_secrets).collect()),
+        Value::String(s) => Value::String(redact_string(s)),
+        other => other.clone(),
+    }
+}
+
+fn redact_string(value: &str) -> String {
+    SECRET_VALUE.replace_all(value, REDACTED).into_owned()
+}
+
+fn rotated_path(log_path: &Path, index: usize, compressed: bool) -> PathBuf {
+    let mut name = log_path.as_os_str().to_owned();
+    name.push(format!(".{}", index));
+    if compressed {
+        name.push(".gz");
+    }
+    PathBuf::from(name)
+}
+
+/// Cut strings longer than `max_bytes`, noting how much was dropped
+fn truncate_strings(value: &mut serde_json::Value, max_bytes: usize) {
+    use serde_json::Value;
+
+    match value {
+        Value::String(s) if s != REDACTED => truncate_string(s, max_bytes),
+        Value::Array(items) => {
+            for item in items {
+                truncate_strings(item, max_bytes);
+            }
+        }
+        Value::Object(obj) => {
+            for item in obj.values_mut() {
+                truncate_strings(item, max_bytes);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn truncate_string(s: &mut String, max_bytes: usize) {
+    if s.len() <= max_bytes {
+        return;
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    let dropped = s.len() - end;
+    s.truncate(end);
+    s.push_str(&format!("…[truncated {} bytes]", dropped));
+}
+
+/// Events from a payload log and its rotated files, oldest first
+///
+/// Lines that fail to parse are skipped, so a log cut off mid-write stays
+/// readable.
+pub fn read_events(log_path: &Path) -> io::Result<Vec<PayloadLogEvent>> {
+    let mut files = Vec::new();
+    let mut index = 1;
+    loop {
+        let plain = rotated_path(log_path, index, false);
+        let compressed = rotated_path(log_path, index, true);
+        match (compressed.exists(), plain.exists()) {
+            (true, _) => files.push(compressed),
+            (false, true) => files.push(plain),
+            (false, false) => break,
+        }
+        index += 1;
+    }
+    files.reverse();
+    if log_path.exists() {
+        files.push(log_path.to_path_buf());
+    }
+
+    let mut events = Vec::new();
+    for path in files {
+        let file = File::open(&path)?;
+        let reader: Box<dyn Read> = if path.extension().is_some_and(|ext| ext == "gz") {
+            Box::new(GzDecoder::new(file))
+        } else {
+            Box::new(file)
+        };
+        for line in BufReader::new(reader).lines() {
+            if let Ok(event) = serde_json::from_str::<PayloadLogEvent>(&line?) {
+                events.push(event);
+            }
+        }
+    }
+    Ok(events)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn logger(path: PathBuf, settings: PayloadLogSettings) -> PayloadLogger {
+        PayloadLogger::with_settings(PayloadLogSettings {
+            enabled: true,
+            path: Some(path.display().to_string()),
+            ..settings
+        })
+    }
+
+    #[test]
+    fn test_redact_secrets() {
+        let payload = serde_json::json!({
+            "model": "claude-3",
+            "api_key": "sk-ant-secret123",
+            "max_tokens": 1024,
+            "metadata": {"Authorization": "Bearer abc"},
+            "messages": [{"role": "user", "content": "my key is sk-proj-0123456789abcdefghij, thanks"}]
+        });
+
+        let redacted = redact_secrets(&payload);
+        assert_eq!(redacted["api_key"], "[REDACTED]");
+        assert_eq!(redacted["model"], "claude-3");
+        assert_eq!(redacted["max_tokens"], 1024);
+        assert_eq!(redacted["metadata"]["Authorization"], "[REDACTED]");
+        assert_eq!(
+            redacted["messages"][0]["content"],
+            "my key is [REDACTED], thanks"
+        );
+    }
+
+    #[test]
+    fn test_truncate_strings() {
+        let mut value = serde_json::json!({"content": "é".repeat(10), "n": 1});
+        truncate_strings(&mut value, 5);
+        assert_eq!(value["content"], "éé…[truncated 16 bytes]");
+        assert_eq!(value["n"], 1);
+    }
+
+    #[test]
+    fn test_compute_digest() {
+        let logger = PayloadLogger::new(false, Some(PathBuf::from("/tmp/test.jsonl")));
+
+        let payload = serde_json::json!({"test": "data"});
+        let digest = logger.compute_digest(&payload);
+
+        // SHA-256 produces 64 hex characters
+        assert_eq!(digest.len(), 64);
+    }
+
+    #[test]
+    fn test_disabled_by_default() {
+        let logger = PayloadLogger::new(false, Some(PathBuf::from("/tmp/test-disabled.jsonl")));
+        assert!(!logger.is_enabled());
+        assert!(!PayloadLogger::with_settings(PayloadLogSettings::default()).is_enabled());
+    }
+
+    #[tokio::test]
+    async fn test_logs_session_and_scrubbed_payload() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("payloads.jsonl");
+        let logger = logger(
+            path.clone(),
+            PayloadLogSettings {
+                max_string_bytes: 8,
+                ..Default::default()
+            },
+        );
+
+        let payload = serde_json::json!({"input": "a long file body", "api_key": "k"});
+        with_session("session-1", async {
+            logger.log_request(None, "openai", "gpt-4o", &payload);
+        })
+        .await;
+        logger.log_error(Some("session-2".to_string()), "boom");
+
+        let events = read_events(&path).unwrap();
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0].session_id.as_deref(), Some("session-1"));
+        assert_eq!(events[0].provider.as_deref(), Some("openai"));
+        let logged = events[0].payload.as_ref().unwrap();
+        assert_eq!(logged["input"], "a long f…[truncated 8 bytes]");
+        assert_eq!(logged["api_key"], "[REDACTED]");
+        assert_eq!(events[1].error.as_deref(), Some("boom"));
+    }
+
+    #[test]
+    fn test_rotates_and_reads_compressed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("payloads.jsonl");
+        let logger = logger(
+            path.clone(),
+            PayloadLogSettings {
+                max_file_bytes: 1,
+                max_files: 2,
+                ..Default::default()
+            },
+        );
+
+        for i in 0..4 {
+            logger.log_error(Some(format!("s{}", i)), "failed");
+        }
+
+        assert!(rotated_path(&path, 1, true).exists());
+        assert!(rotated_path(&path, 2, true).exists());
+        assert!(!rotated_path(&path, 3, true).exists());
+
+        let sessions: Vec<_> = read_events(&path)
+            .unwrap()
+            .into_iter()
+            .filter_map(|e| e.session_id)
+            .collect();
+        assert_eq!(sessions, vec!["s1", "s2", "s3"]);
+    }
+}
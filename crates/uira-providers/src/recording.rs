@@ -0,0 +1,396 @@
+//! Record and replay provider responses for deterministic tests
+//!
+//! [`CachingModelClient`] keys each request by a SHA-256 hash of the model,
+//! messages and tool specs. With `UIRA_RECORDING=record` it forwards requests
+//! to the wrapped client and writes the responses to disk; with
+//! `UIRA_RECORDING=replay` it serves them back without touching the network,
+//! so no API keys are needed.
+
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use uira_core::{Message, ModelResponse, StreamChunk, ToolSpec};
+
+use crate::traits::ResponseStream;
+use crate::{
+    ModelClient, ModelList, ModelResult, ProviderConfig, ProviderError, ReasoningOverride,
+};
+
+/// Selects the recording mode: `record`, `replay`, or unset for passthrough
+pub const RECORDING_ENV: &str = "UIRA_RECORDING";
+/// Overrides where recordings are stored (default: `.uira/recordings`)
+pub const RECORDING_DIR_ENV: &str = "UIRA_RECORDING_DIR";
+
+const DEFAULT_RECORDING_DIR: &str = ".uira/recordings";
+/// Context window reported by replay-only clients, which have no provider to ask
+const REPLAY_CONTEXT_WINDOW: usize = 200_000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordingMode {
+    /// Forward requests without recording
+    Off,
+    /// Forward requests and save each response
+    Record,
+    /// Serve saved responses; a missing recording is an error
+    Replay,
+}
+
+impl RecordingMode {
+    pub fn parse(value: &str) -> Self {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "record" => Self::Record,
+            "replay" => Self::Replay,
+            _ => Self::Off,
+        }
+    }
+
+    pub fn from_env() -> Self {
+        std::env::var(RECORDING_ENV)
+            .map(|v| Self::parse(&v))
+            .unwrap_or(Self::Off)
+    }
+}
+
+/// Recording directory from `UIRA_RECORDING_DIR`, or the default
+pub fn recording_dir() -> PathBuf {
+    std::env::var(RECORDING_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|_| PathBuf::from(DEFAULT_RECORDING_DIR))
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum Recording {
+    Chat { response: ModelResponse },
+    Stream { chunks: Vec<StreamChunk> },
+}
+
+#[derive(Serialize)]
+struct RequestKey<'a> {
+    kind: &'a str,
+    model: &'a str,
+    messages: &'a [Message],
+    tools: Vec<&'a ToolSpec>,
+}
+
+/// Model client wrapper that records or replays responses
+pub struct CachingModelClient {
+    inner: Option<Arc<dyn ModelClient>>,
+    dir: PathBuf,
+    mode: RecordingMode,
+    model: String,
+    provider: String,
+}
+
+impl CachingModelClient {
+    pub fn new(inner: Arc<dyn ModelClient>, dir: impl Into<PathBuf>, mode: RecordingMode) -> Self {
+        Self {
+            model: inner.model().to_string(),
+            provider: inner.provider().to_string(),
+            inner: Some(inner),
+            dir: dir.into(),
+            mode,
+        }
+    }
+
+    /// A replay-only client that needs no provider credentials
+    pub fn replay(dir: impl Into<PathBuf>, config: &ProviderConfig) -> Self {
+        Self {
+            inner: None,
+            dir: dir.into(),
+            mode: RecordingMode::Replay,
+            model: config.model.clone(),
+            provider: config.provider.to_string(),
+        }
+    }
+
+    /// Apply `UIRA_RECORDING` to a client about to be built from `config`
+    ///
+    /// Replay mode skips `build` entirely so missing credentials don't matter.
+    pub fn from_env<F>(
+        config: &ProviderConfig,
+        build: F,
+    ) -> Result<Arc<dyn ModelClient>, ProviderError>
+    where
+        F: FnOnce() -> Result<Arc<dyn ModelClient>, ProviderError>,
+    {
+        match RecordingMode::from_env() {
+            RecordingMode::Off => build(),
+            RecordingMode::Record => Ok(Arc::new(Self::new(
+                build()?,
+                recording_dir(),
+                RecordingMode::Record,
+            ))),
+            RecordingMode::Replay => Ok(Arc::new(Self::replay(recording_dir(), config))),
+        }
+    }
+
+    pub fn mode(&self) -> RecordingMode {
+        self.mode
+    }
+
+    fn request_hash(&self, kind: &str, messages: &[Message], tools: &[ToolSpec]) -> String {
+        // Tool registries iterate in hash order, so sort to keep keys stable
+        let mut tools: Vec<&ToolSpec> = tools.iter().collect();
+        tools.sort_by(|a, b| a.name.cmp(&b.name));
+        let key = RequestKey {
+            kind,
+            model: &self.model,
+            messages,
+            tools,
+        };
+        let bytes = serde_json::to_vec(&key).unwrap_or_default();
+        hex::encode(Sha256::digest(bytes))
+    }
+
+    fn path_for(&self, hash: &str) -> PathBuf {
+        self.dir.join(format!("{}.json", hash))
+    }
+
+    fn inner(&self) -> ModelResult<&Arc<dyn ModelClient>> {
+        self.inner.as_ref().ok_or_else(|| {
+            ProviderError::Configuration("replay-only client cannot forward requests".into())
+        })
+    }
+
+    fn load(&self, hash: &str) -> ModelResult<Recording> {
+        let path = self.path_for(hash);
+        let raw = std::fs::read_to_string(&path).map_err(|_| {
+            ProviderError::Configuration(format!(
+                "no recorded response at {}; rerun with {}=record",
+                path.display(),
+                RECORDING_ENV
+            ))
+        })?;
+        serde_json::from_str(&raw).map_err(|e| {
+            ProviderError::Configuration(format!("invalid recording {}: {}", path.display(), e))
+        })
+    }
+
+    fn save(&self, hash: &str, recording: &Recording) -> ModelResult<()> {
+        let write = |path: &Path| -> std::io::Result<()> {
+            std::fs::create_dir_all(&self.dir)?;
+            let json = serde_json::to_string_pretty(recording)?;
+            std::fs::write(path, json)
+        };
+        let path = self.path_for(hash);
+        write(&path).map_err(|e| {
+            ProviderError::Configuration(format!(
+                "failed to write recording {}: {}",
+                path.display(),
+                e
+            ))
+        })
+    }
+}
+
+#[async_trait]
+impl ModelClient for CachingModelClient {
+    async fn chat(&self, messages: &[Message], tools: &[ToolSpec]) -> ModelResult<ModelResponse> {
+        match self.mode {
+            RecordingMode::Off => self.inner()?.chat(messages, tools).await,
+            RecordingMode::Record => {
+                let response = self.inner()?.chat(messages, tools).await?;
+                let hash = self.request_hash("chat", messages, tools);
+                self.save(
+                    &hash,
+                    &Recording::Chat {
+                        response: response.clone(),
+                    },
+                )?;
+                Ok(response)
+            }
+            RecordingMode::Replay => {
+                let hash = self.request_hash("chat", messages, tools);
+                match self.load(&hash)? {
+                    Recording::Chat { response } => Ok(response),
+                    Recording::Stream { .. } => Err(ProviderError::Configuration(format!(
+                        "recording {} is a stream, expected a chat response",
+                        hash
+                    ))),
+                }
+            }
+        }
+    }
+
+    async fn chat_stream(
+        &self,
+        messages: &[Message],
+        tools: &[ToolSpec],
+    ) -> ModelResult<ResponseStream> {
+        let chunks = match self.mode {
+            RecordingMode::Off => return self.inner()?.chat_stream(messages, tools).await,
+            RecordingMode::Record => {
+                // Buffer the whole stream so only complete responses are recorded
+                let mut stream = self.inner()?.chat_stream(messages, tools).await?;
+                let mut chunks = Vec::new();
+                while let Some(chunk) = stream.next().await {
+                    chunks.push(chunk?);
+                }
+                let hash = self.request_hash("stream", messages, tools);
+                self.save(
+                    &hash,
+                    &Recording::Stream {
+                        chunks: chunks.clone(),
+                    },
+                )?;
+                chunks
+            }
+            RecordingMode::Replay => {
+                let hash = self.request_hash("stream", messages, tools);
+                match self.load(&hash)? {
+                    Recording::Stream { chunks } => chunks,
+                    Recording::Chat { .. } => {
+                        return Err(ProviderError::Configuration(format!(
+                            "recording {} is a chat response, expected a stream",
+                            hash
+                        )))
+                    }
+                }
+            }
+        };
+
+        Ok(Box::pin(futures::stream::iter(chunks.into_iter().map(Ok))))
+    }
+
+    async fn render_request(
+        &self,
+        messages: &[Message],
+        tools: &[ToolSpec],
+    ) -> ModelResult<String> {
+        self.inner()?.render_request(messages, tools).await
+    }
+
+    async fn list_models(&self) -> ModelResult<ModelList> {
+        self.inner()?.list_models().await
+    }
+
+    fn supports_tools(&self) -> bool {
+        self.inner.as_ref().is_none_or(|c| c.supports_tools())
+    }
+
+    fn max_tokens(&self) -> usize {
+        self.inner
+            .as_ref()
+            .map_or(REPLAY_CONTEXT_WINDOW, |c| c.max_tokens())
+    }
+
+    fn model(&self) -> &str {
+        &self.model
+    }
+
+    fn provider(&self) -> &str {
+        &self.provider
+    }
+
+    fn with_reasoning(&self, reasoning: &ReasoningOverride) -> Option<Arc<dyn ModelClient>> {
+        let inner = self.inner.as_ref()?.with_reasoning(reasoning)?;
+        Some(Arc::new(Self::new(inner, self.dir.clone(), self.mode)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use uira_core::{ContentBlock, StopReason, TokenUsage};
+
+    struct CountingClient {
+        calls: AtomicUsize,
+    }
+
+    fn response(text: &str) -> ModelResponse {
+        ModelResponse {
+            id: "msg_1".to_string(),
+            model: "test-model".to_string(),
+            content: vec![ContentBlock::Text {
+                text: text.to_string(),
+            }],
+            stop_reason: Some(StopReason::EndTurn),
+            usage: TokenUsage::default(),
+        }
+    }
+
+    #[async_trait]
+    impl ModelClient for CountingClient {
+        async fn chat(
+            &self,
+            messages: &[Message],
+            _tools: &[ToolSpec],
+        ) -> ModelResult<ModelResponse> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let text = messages
+                .last()
+                .and_then(|m| m.content.as_text())
+                .unwrap_or_default();
+            Ok(response(&format!("echo: {}", text)))
+        }
+
+        async fn chat_stream(
+            &self,
+            _messages: &[Message],
+            _tools: &[ToolSpec],
+        ) -> ModelResult<ResponseStream> {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let chunks = vec![Ok(StreamChunk::MessageStop)];
+            Ok(Box::pin(futures::stream::iter(chunks)))
+        }
+
+        fn supports_tools(&self) -> bool {
+            true
+        }
+
+        fn max_tokens(&self) -> usize {
+            1000
+        }
+
+        fn model(&self) -> &str {
+            "test-model"
+        }
+
+        fn provider(&self) -> &str {
+            "test"
+        }
+    }
+
+    #[tokio::test]
+    async fn records_then_replays_without_inner_client() {
+        let dir = tempfile::tempdir().unwrap();
+        let inner = Arc::new(CountingClient {
+            calls: AtomicUsize::new(0),
+        });
+        let recorder = CachingModelClient::new(inner.clone(), dir.path(), RecordingMode::Record);
+        let messages = vec![Message::user("hi")];
+
+        let recorded = recorder.chat(&messages, &[]).await.unwrap();
+        let stream = recorder.chat_stream(&messages, &[]).await.unwrap();
+        assert_eq!(stream.collect::<Vec<_>>().await.len(), 1);
+        assert_eq!(inner.calls.load(Ordering::SeqCst), 2);
+
+        let config = ProviderConfig {
+            model: "test-model".to_string(),
+            ..Default::default()
+        };
+        let replayer = CachingModelClient::replay(dir.path(), &config);
+        let replayed = replayer.chat(&messages, &[]).await.unwrap();
+        assert_eq!(replayed.text(), recorded.text());
+        let stream = replayer.chat_stream(&messages, &[]).await.unwrap();
+        assert!(matches!(
+            stream.collect::<Vec<_>>().await.as_slice(),
+            [Ok(StreamChunk::MessageStop)]
+        ));
+
+        let miss = replayer.chat(&[Message::user("other")], &[]).await;
+        assert!(matches!(miss, Err(ProviderError::Configuration(msg)) if msg.contains("record")));
+    }
+
+    #[test]
+    fn parses_recording_mode() {
+        assert_eq!(RecordingMode::parse("Replay"), RecordingMode::Replay);
+        assert_eq!(RecordingMode::parse("record"), RecordingMode::Record);
+        assert_eq!(RecordingMode::parse(""), RecordingMode::Off);
+    }
+}
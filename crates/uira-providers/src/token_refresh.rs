@@ -0,0 +1,398 @@
+//! OAuth access tokens that refresh themselves
+//!
+//! [`OAuthToken`] holds a provider's OAuth credentials and is shared by every
+//! copy of a client. It refreshes the access token shortly before it expires,
+//! and again when the API rejects it mid-session. Only one refresh runs at a
+//! time: concurrent requests wait for it and reuse the new token. Refreshed
+//! tokens are saved to the [`CredentialStore`], and a token another process
+//! already refreshed there is picked up instead of spending the refresh
+//! token twice.
+
+use std::future::Future;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use chrono::Utc;
+use secrecy::{ExposeSecret, SecretString};
+use tokio::sync::{Mutex, RwLock};
+
+use crate::auth::{AuthProvider, CredentialStore, StoredCredential};
+use crate::ProviderError;
+
+/// Refresh this long before the token expires
+const TOKEN_REFRESH_BUFFER_SECS: i64 = 300;
+
+/// Tokens returned by a provider's token endpoint
+#[derive(Debug)]
+pub struct RefreshedToken {
+    pub access_token: String,
+    /// A rotated refresh token; `None` keeps the current one
+    pub refresh_token: Option<String>,
+    pub expires_in: Option<i64>,
+}
+
+/// A provider's OAuth token endpoint
+#[async_trait]
+pub trait TokenEndpoint: Send + Sync {
+    async fn refresh(&self, refresh_token: &SecretString) -> Result<RefreshedToken, ProviderError>;
+}
+
+/// Token endpoint of an [`AuthProvider`] from the login flow
+pub struct AuthProviderEndpoint<P>(pub P);
+
+#[async_trait]
+impl<P: AuthProvider> TokenEndpoint for AuthProviderEndpoint<P> {
+    async fn refresh(&self, refresh_token: &SecretString) -> Result<RefreshedToken, ProviderError> {
+        let tokens = self
+            .0
+            .refresh_token(refresh_token.expose_secret())
+            .await
+            .map_err(|e| ProviderError::AuthenticationFailed(format!("token refresh: {}", e)))?;
+        // The login flow reports the lifetime in `expires_at`
+        Ok(RefreshedToken {
+            access_token: tokens.access_token,
+            refresh_token: tokens.refresh_token,
+            expires_in: tokens.expires_at,
+        })
+    }
+}
+
+#[derive(Clone)]
+struct TokenState {
+    access_token: SecretString,
+    refresh_token: Option<SecretString>,
+    expires_at: Option<i64>,
+}
+
+impl TokenState {
+    fn from_stored(credential: &StoredCredential) -> Option<Self> {
+        match credential {
+            StoredCredential::OAuth {
+                access_token,
+                refresh_token,
+                expires_at,
+            } => Some(Self {
+                access_token: access_token.clone(),
+                refresh_token: refresh_token.clone(),
+                expires_at: *expires_at,
+            }),
+            StoredCredential::ApiKey { .. } => None,
+        }
+    }
+
+    fn is_expiring(&self) -> bool {
+        let Some(exp) = self.expires_at else {
+            return false;
+        };
+        // Handle both seconds and milliseconds formats
+        let exp_secs = if exp > 1_000_000_000_000 {
+            exp / 1000
+        } else {
+            exp
+        };
+        Utc::now().timestamp() >= exp_secs - TOKEN_REFRESH_BUFFER_SECS
+    }
+
+    fn is(&self, access_token: &SecretString) -> bool {
+        self.access_token.expose_secret() == access_token.expose_secret()
+    }
+}
+
+/// A provider's OAuth credentials, refreshed on demand
+pub struct OAuthToken {
+    /// Credential store key
+    provider: String,
+    state: RwLock<TokenState>,
+    refresh_lock: Mutex<()>,
+    endpoint: Arc<dyn TokenEndpoint>,
+    /// Whether refreshed tokens are written back to the credential store
+    persist: bool,
+}
+
+impl OAuthToken {
+    /// Token loaded from the credential store under `provider`
+    pub fn new(
+        provider: impl Into<String>,
+        access_token: SecretString,
+        refresh_token: Option<SecretString>,
+        expires_at: Option<i64>,
+        endpoint: Arc<dyn TokenEndpoint>,
+    ) -> Self {
+        Self {
+            provider: provider.into(),
+            state: RwLock::new(TokenState {
+                access_token,
+                refresh_token,
+                expires_at,
+            }),
+            refresh_lock: Mutex::new(()),
+            endpoint,
+            persist: true,
+        }
+    }
+
+    /// OAuth credential stored under `provider`, if any
+    pub fn from_stored(
+        provider: impl Into<String>,
+        credential: &StoredCredential,
+        endpoint: Arc<dyn TokenEndpoint>,
+    ) -> Option<Self> {
+        let state = TokenState::from_stored(credential)?;
+        Some(Self::new(
+            provider,
+            state.access_token,
+            state.refresh_token,
+            state.expires_at,
+            endpoint,
+        ))
+    }
+
+    /// Keep refreshed tokens in memory only, for tokens that didn't come
+    /// from the credential store
+    pub fn in_memory(mut self) -> Self {
+        self.persist = false;
+        self
+    }
+
+    /// Current access token, refreshed first if it's about to expire
+    pub async fn access_token(&self) -> Result<SecretString, ProviderError> {
+        {
+            let state = self.state.read().await;
+            if !state.is_expiring() {
+                return Ok(state.access_token.clone());
+            }
+        }
+        self.refresh_where(TokenState::is_expiring).await
+    }
+
+    /// Replace an access token the API rejected. If another request already
+    /// replaced it, that token is returned without refreshing again.
+    pub async fn refresh_rejected(
+        &self,
+        rejected: &SecretString,
+    ) -> Result<SecretString, ProviderError> {
+        self.refresh_where(|state| state.is(rejected) || state.is_expiring())
+            .await
+    }
+
+    /// Run `operation`, and if the API rejects the access token, refresh it
+    /// and run `operation` once more
+    pub async fn retry_if_rejected<T, F, Fut>(&self, mut operation: F) -> Result<T, ProviderError>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = Result<T, ProviderError>>,
+    {
+        let used = self.state.read().await.access_token.clone();
+        match operation().await {
+            Err(ProviderError::AuthenticationFailed(reason)) => {
+                tracing::info!(
+                    provider = %self.provider,
+                    "OAuth token rejected ({}), refreshing",
+                    reason
+                );
+                if let Err(e) = self.refresh_rejected(&used).await {
+                    tracing::warn!(provider = %self.provider, "OAuth token refresh failed: {}", e);
+                    return Err(ProviderError::AuthenticationFailed(reason));
+                }
+                operation().await
+            }
+            result => result,
+        }
+    }
+
+    /// Refresh unless the token no longer matches `stale` once the refresh
+    /// lock is held
+    async fn refresh_where(
+        &self,
+        stale: impl Fn(&TokenState) -> bool,
+    ) -> Result<SecretString, ProviderError> {
+        let _guard = self.refresh_lock.lock().await;
+
+        let current = self.state.read().await.clone();
+        if !stale(&current) {
+            return Ok(current.access_token);
+        }
+
+        if let Some(stored) = self.stored_state().filter(|stored| !stale(stored)) {
+            tracing::debug!(provider = %self.provider, "Using OAuth token refreshed elsewhere");
+            *self.state.write().await = stored.clone();
+            return Ok(stored.access_token);
+        }
+
+        let refresh_token = current.refresh_token.clone().ok_or_else(|| {
+            ProviderError::Configuration(
+                "OAuth token expired and no refresh token available".into(),
+            )
+        })?;
+        let refreshed = self.endpoint.refresh(&refresh_token).await?;
+
+        let state = TokenState {
+            access_token: SecretString::from(refreshed.access_token),
+            refresh_token: refreshed
+                .refresh_token
+                .map(SecretString::from)
+                .or(Some(refresh_token)),
+            expires_at: refreshed
+                .expires_in
+                .map(|secs| Utc::now().timestamp() + secs),
+        };
+        *self.state.write().await = state.clone();
+        tracing::debug!(provider = %self.provider, "Refreshed OAuth token");
+
+        if self.persist {
+            self.save(&state);
+        }
+        Ok(state.access_token)
+    }
+
+    fn stored_state(&self) -> Option<TokenState> {
+        if !self.persist {
+            return None;
+        }
+        let store = CredentialStore::load().ok()?;
+        TokenState::from_stored(store.get(&self.provider)?)
+    }
+
+    fn save(&self, state: &TokenState) {
+        let result = CredentialStore::load().and_then(|mut store| {
+            store.insert(
+                self.provider.clone(),
+                StoredCredential::OAuth {
+                    access_token: state.access_token.clone(),
+                    refresh_token: state.refresh_token.clone(),
+                    expires_at: state.expires_at,
+                },
+            );
+            store.save()
+        });
+        if let Err(e) = result {
+            tracing::error!("Failed to save credential store after token refresh: {}", e);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::time::Duration;
+
+    /// Issues `access-N` for the Nth refresh
+    #[derive(Default)]
+    struct CountingEndpoint {
+        calls: AtomicUsize,
+    }
+
+    #[async_trait]
+    impl TokenEndpoint for CountingEndpoint {
+        async fn refresh(
+            &self,
+            refresh_token: &SecretString,
+        ) -> Result<RefreshedToken, ProviderError> {
+            assert_eq!(refresh_token.expose_secret(), "refresh");
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            let n = self.calls.fetch_add(1, Ordering::SeqCst) + 1;
+            Ok(RefreshedToken {
+                access_token: format!("access-{}", n),
+                refresh_token: None,
+                expires_in: Some(3600),
+            })
+        }
+    }
+
+    fn token(expires_at: Option<i64>, endpoint: Arc<CountingEndpoint>) -> OAuthToken {
+        OAuthToken::new(
+            "test",
+            SecretString::from("access-0"),
+            Some(SecretString::from("refresh")),
+            expires_at,
+            endpoint,
+        )
+        .in_memory()
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_requests_share_one_refresh() {
+        let endpoint = Arc::new(CountingEndpoint::default());
+        let token = token(Some(Utc::now().timestamp() - 10), endpoint.clone());
+
+        let (a, b, c) = tokio::join!(
+            token.access_token(),
+            token.access_token(),
+            token.access_token()
+        );
+        for access in [a, b, c] {
+            assert_eq!(access.unwrap().expose_secret(), "access-1");
+        }
+        assert_eq!(endpoint.calls.load(Ordering::SeqCst), 1);
+
+        // Fresh until the new expiry
+        assert_eq!(
+            token.access_token().await.unwrap().expose_secret(),
+            "access-1"
+        );
+        assert_eq!(endpoint.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_rejected_token_is_refreshed_once() {
+        let endpoint = Arc::new(CountingEndpoint::default());
+        let token = token(None, endpoint.clone());
+        let rejected = token.access_token().await.unwrap();
+
+        let (a, b) = tokio::join!(
+            token.refresh_rejected(&rejected),
+            token.refresh_rejected(&rejected)
+        );
+        assert_eq!(a.unwrap().expose_secret(), "access-1");
+        assert_eq!(b.unwrap().expose_secret(), "access-1");
+        assert_eq!(endpoint.calls.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_retry_if_rejected_runs_again_with_new_token() {
+        let endpoint = Arc::new(CountingEndpoint::default());
+        let token = token(None, endpoint.clone());
+
+        let attempts = AtomicUsize::new(0);
+        let result = token
+            .retry_if_rejected(|| async {
+                let access = token.access_token().await?;
+                if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                    return Err(ProviderError::AuthenticationFailed("expired".into()));
+                }
+                Ok(access.expose_secret().to_string())
+            })
+            .await;
+        assert_eq!(result.unwrap(), "access-1");
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn test_expired_token_without_refresh_token_fails() {
+        let token = OAuthToken::new(
+            "test",
+            SecretString::from("access-0"),
+            None,
+            Some(Utc::now().timestamp() - 10),
+            Arc::new(CountingEndpoint::default()),
+        )
+        .in_memory();
+        assert!(matches!(
+            token.access_token().await,
+            Err(ProviderError::Configuration(_))
+        ));
+
+        // A rejected token keeps the API's error
+        let result: Result<(), _> = token
+            .retry_if_rejected(|| async {
+                Err(ProviderError::AuthenticationFailed(
+                    "invalid x-api-key".into(),
+                ))
+            })
+            .await;
+        assert!(
+            matches!(result, Err(ProviderError::AuthenticationFailed(reason)) if reason == "invalid x-api-key")
+        );
+    }
+}
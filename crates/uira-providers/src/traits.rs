@@ -3,9 +3,10 @@
 use async_trait::async_trait;
 use futures::Stream;
 use std::pin::Pin;
+use std::sync::Arc;
 use uira_core::{Message, ModelResponse, StreamChunk, ToolSpec};
 
-use crate::ProviderError;
+use crate::{ProviderError, ReasoningOverride};
 
 /// Result type for model operations
 pub type ModelResult<T> = Result<T, ProviderError>;
@@ -13,6 +14,23 @@ pub type ModelResult<T> = Result<T, ProviderError>;
 /// Stream of response chunks
 pub type ResponseStream = Pin<Box<dyn Stream<Item = Result<StreamChunk, ProviderError>> + Send>>;
 
+/// A model the provider offers
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModelInfo {
+    /// Identifier to request the model by
+    pub id: String,
+    /// Human-readable name, when the provider reports one
+    pub display_name: Option<String>,
+}
+
+/// Models available to the configured credentials
+#[derive(Debug, Clone, Default)]
+pub struct ModelList {
+    pub models: Vec<ModelInfo>,
+    /// Rate-limit and quota headers from the listing response, by name
+    pub limits: Vec<(String, String)>,
+}
+
 /// Trait for model clients
 #[async_trait]
 pub trait ModelClient: Send + Sync {
@@ -37,6 +55,15 @@ pub trait ModelClient: Send + Sync {
         ))
     }
 
+    /// List the models available to this client's credentials. Doubles as
+    /// a cheap authenticated call for credential checks.
+    async fn list_models(&self) -> ModelResult<ModelList> {
+        Err(ProviderError::Configuration(format!(
+            "Model listing is not available for {}",
+            self.provider()
+        )))
+    }
+
     /// Check if this client supports tool use
     fn supports_tools(&self) -> bool;
 
@@ -48,4 +75,10 @@ pub trait ModelClient: Send + Sync {
 
     /// Get the provider name
     fn provider(&self) -> &str;
+
+    /// Build a copy of this client with provider-native reasoning controls applied.
+    /// Returns None when the provider has no such controls.
+    fn with_reasoning(&self, _reasoning: &ReasoningOverride) -> Option<Arc<dyn ModelClient>> {
+        None
+    }
 }
@@ -0,0 +1,99 @@
+//! reqwest-backed transport for the runtime-free models in uira-agent-core
+
+use async_trait::async_trait;
+use reqwest::{Client, Method};
+use uira_agent_core::{HttpRequest, HttpResponse, Transport, TransportError};
+
+#[derive(Debug, Clone, Default)]
+pub struct ReqwestTransport {
+    client: Client,
+}
+
+impl ReqwestTransport {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_client(client: Client) -> Self {
+        Self { client }
+    }
+}
+
+#[async_trait]
+impl Transport for ReqwestTransport {
+    async fn send(&self, request: HttpRequest) -> Result<HttpResponse, TransportError> {
+        let method = Method::from_bytes(request.method.as_bytes())
+            .map_err(|e| TransportError::Request(e.to_string()))?;
+        let mut builder = self.client.request(method, &request.url);
+        for (name, value) in &request.headers {
+            builder = builder.header(name, value);
+        }
+        if let Some(body) = request.body {
+            builder = builder.body(body);
+        }
+
+        let response = builder.send().await.map_err(|e| {
+            if e.is_timeout() {
+                TransportError::Timeout
+            } else {
+                TransportError::Request(e.to_string())
+            }
+        })?;
+        let status = response.status().as_u16();
+        let headers = response
+            .headers()
+            .iter()
+            .filter_map(|(name, value)| {
+                value
+                    .to_str()
+                    .ok()
+                    .map(|value| (name.to_string(), value.to_string()))
+            })
+            .collect();
+        let body = response
+            .bytes()
+            .await
+            .map_err(|e| TransportError::Request(e.to_string()))?;
+
+        Ok(HttpResponse {
+            status,
+            headers,
+            body: body.to_vec(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uira_agent_core::{AnthropicModel, Model};
+    use uira_core::Message;
+    use wiremock::matchers::{body_partial_json, header, method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_drives_core_anthropic_model() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/messages"))
+            .and(header("x-api-key", "sk-test"))
+            .and(body_partial_json(
+                serde_json::json!({"model": "claude-test"}),
+            ))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "id": "msg_1",
+                "model": "claude-test",
+                "content": [{"type": "text", "text": "hello"}],
+                "stop_reason": "end_turn",
+                "usage": {"input_tokens": 3, "output_tokens": 1}
+            })))
+            .mount(&server)
+            .await;
+
+        let model = AnthropicModel::new(ReqwestTransport::new(), "sk-test", "claude-test")
+            .with_base_url(server.uri());
+        let response = model.complete(&[Message::user("hi")], &[]).await.unwrap();
+        assert_eq!(response.text(), "hello");
+        assert_eq!(response.usage.input_tokens, 3);
+    }
+}
@@ -0,0 +1,27 @@
+"""Python bindings for the Uira agent harness."""
+
+from ._uira import (
+    Agent,
+    EventIterator,
+    __version__,
+    check_goals,
+    detect_keyword,
+    detect_keywords,
+    execute_hooks,
+    explain_routing,
+    list_hooks,
+    route_task,
+)
+
+__all__ = [
+    "Agent",
+    "EventIterator",
+    "__version__",
+    "check_goals",
+    "detect_keyword",
+    "detect_keywords",
+    "execute_hooks",
+    "explain_routing",
+    "list_hooks",
+    "route_task",
+]
@@ -0,0 +1,42 @@
+from os import PathLike
+from typing import Any, Iterator, Mapping, Optional, Sequence, Union
+
+__version__: str
+
+def detect_keywords(prompt: str, agent: Optional[str] = None) -> list[tuple[str, str]]: ...
+def detect_keyword(prompt: str, agent: Optional[str] = None) -> Optional[str]: ...
+def route_task(
+    prompt: str,
+    agent_type: Optional[str] = None,
+    previous_failures: Optional[int] = None,
+    conversation_turns: Optional[int] = None,
+    explicit_model: Optional[str] = None,
+) -> dict[str, Any]: ...
+def explain_routing(prompt: str, agent_type: Optional[str] = None) -> str: ...
+def execute_hooks(
+    event: str,
+    input: Optional[Mapping[str, Any]] = None,
+    directory: Optional[str] = None,
+    session_id: Optional[str] = None,
+) -> dict[str, Any]: ...
+def list_hooks() -> list[str]: ...
+def check_goals(
+    goals: Sequence[Mapping[str, Any]],
+    project_root: Optional[Union[str, PathLike[str]]] = None,
+) -> dict[str, Any]: ...
+
+class EventIterator(Iterator[dict[str, Any]]):
+    def __iter__(self) -> EventIterator: ...
+    def __next__(self) -> dict[str, Any]: ...
+    def result(self) -> dict[str, Any]: ...
+
+class Agent:
+    def __init__(
+        self,
+        model: str,
+        provider: str = "anthropic",
+        working_directory: Optional[Union[str, PathLike[str]]] = None,
+        base_url: Optional[str] = None,
+    ) -> None: ...
+    def run(self, prompt: str) -> dict[str, Any]: ...
+    def stream(self, prompt: str) -> EventIterator: ...
@@ -0,0 +1,141 @@
+use std::path::PathBuf;
+
+use futures::StreamExt;
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use tokio::task::JoinHandle;
+use uira_agent::{AgentConfig, AgentLoopError, EventStream};
+use uira_core::{ExecutionResult, Provider};
+use uira_providers::{ModelClientBuilder, ProviderConfig};
+
+use crate::convert::{parse_str, to_py};
+use crate::runtime::{block_on, runtime};
+
+/// Handle for running the agent loop from Python
+///
+/// Every `run` or `stream` call starts a fresh full-auto session: there is
+/// no one to answer approval prompts from inside a Python service.
+#[pyclass(name = "Agent", module = "uira._uira")]
+pub(crate) struct PyAgent {
+    provider: Provider,
+    model: String,
+    working_directory: Option<PathBuf>,
+    base_url: Option<String>,
+}
+
+impl PyAgent {
+    fn build(&self) -> PyResult<uira_agent::Agent> {
+        let provider_config = ProviderConfig {
+            provider: self.provider,
+            model: self.model.clone(),
+            base_url: self.base_url.clone(),
+            ..Default::default()
+        };
+        let client = ModelClientBuilder::new()
+            .with_config(provider_config)
+            .build()
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+        let mut config = AgentConfig::new().full_auto().with_model(&self.model);
+        if let Some(path) = &self.working_directory {
+            config = config.with_working_directory(path);
+        }
+
+        let _guard = runtime().enter();
+        Ok(uira_agent::Agent::new(config, client))
+    }
+}
+
+#[pymethods]
+impl PyAgent {
+    #[new]
+    #[pyo3(signature = (model, provider="anthropic", working_directory=None, base_url=None))]
+    fn new(
+        model: String,
+        provider: &str,
+        working_directory: Option<PathBuf>,
+        base_url: Option<String>,
+    ) -> PyResult<Self> {
+        if model.trim().is_empty() {
+            return Err(PyValueError::new_err("model must not be empty"));
+        }
+        Ok(Self {
+            provider: parse_str(&provider.to_ascii_lowercase(), "provider")?,
+            model,
+            working_directory,
+            base_url,
+        })
+    }
+
+    /// Run `prompt` to completion and return the execution result as a dict
+    fn run(&self, py: Python<'_>, prompt: String) -> PyResult<PyObject> {
+        let mut agent = self.build()?;
+        let result = block_on(py, async move { agent.run(&prompt).await })
+            .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+        to_py(py, &result)
+    }
+
+    /// Start `prompt` in the background and iterate over its events
+    fn stream(&self, prompt: String) -> PyResult<EventIterator> {
+        let (mut agent, events) = self.build()?.with_event_stream();
+        let handle = runtime().spawn(async move { agent.run(&prompt).await });
+        Ok(EventIterator {
+            events,
+            handle: Some(handle),
+            result: None,
+        })
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "Agent(model={:?}, provider={:?})",
+            self.model,
+            self.provider.to_string()
+        )
+    }
+}
+
+/// Iterator over the events of a streamed run, as dicts tagged by `type`
+#[pyclass(module = "uira._uira")]
+pub(crate) struct EventIterator {
+    events: EventStream,
+    handle: Option<JoinHandle<Result<ExecutionResult, AgentLoopError>>>,
+    result: Option<Result<ExecutionResult, String>>,
+}
+
+#[pymethods]
+impl EventIterator {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&mut self, py: Python<'_>) -> PyResult<Option<PyObject>> {
+        let events = &mut self.events;
+        match block_on(py, events.next()) {
+            Some(event) => to_py(py, &event).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// Wait for the run to finish and return its execution result.
+    /// Events not consumed yet are drained and dropped.
+    fn result(&mut self, py: Python<'_>) -> PyResult<PyObject> {
+        if let Some(handle) = self.handle.take() {
+            let events = &mut self.events;
+            let joined = block_on(py, async move {
+                while events.next().await.is_some() {}
+                handle.await
+            });
+            self.result = Some(match joined {
+                Ok(result) => result.map_err(|e| e.to_string()),
+                Err(e) => Err(format!("Agent task failed: {e}")),
+            });
+        }
+
+        match &self.result {
+            Some(Ok(result)) => to_py(py, result),
+            Some(Err(error)) => Err(PyRuntimeError::new_err(error.clone())),
+            None => Err(PyRuntimeError::new_err("Agent run has no result")),
+        }
+    }
+}
@@ -0,0 +1,65 @@
+//! Conversions between Python objects and serde values
+//!
+//! Values go through Python's own `json` module so dicts, lists and
+//! scalars map exactly as they would for a JSON API client.
+
+use pyo3::exceptions::{PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+
+pub(crate) fn to_py<T: Serialize>(py: Python<'_>, value: &T) -> PyResult<PyObject> {
+    let text = serde_json::to_string(value)
+        .map_err(|e| PyRuntimeError::new_err(format!("Failed to serialize result: {e}")))?;
+    let loaded = py.import("json")?.call_method1("loads", (text,))?;
+    Ok(loaded.unbind())
+}
+
+pub(crate) fn from_py<T: DeserializeOwned>(value: &Bound<'_, PyAny>, what: &str) -> PyResult<T> {
+    let text: String = value
+        .py()
+        .import("json")?
+        .call_method1("dumps", (value,))?
+        .extract()?;
+    serde_json::from_str(&text).map_err(|e| PyValueError::new_err(format!("Invalid {what}: {e}")))
+}
+
+pub(crate) fn parse_str<T: DeserializeOwned>(value: &str, what: &str) -> PyResult<T> {
+    serde_json::from_value(serde_json::Value::String(value.to_string()))
+        .map_err(|_| PyValueError::new_err(format!("Unknown {what}: {value}")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::{json, Value};
+    use uira_orchestration::hooks::HookEvent;
+
+    #[test]
+    fn test_round_trips_through_python_objects() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let value = json!({"prompt": "hi", "tags": ["a", 1, null], "nested": {"ok": true}});
+            let object = to_py(py, &value).unwrap();
+            let back: Value = from_py(object.bind(py), "value").unwrap();
+            assert_eq!(back, value);
+        });
+    }
+
+    #[test]
+    fn test_from_py_reports_invalid_shape() {
+        pyo3::prepare_freethreaded_python();
+        Python::with_gil(|py| {
+            let object = to_py(py, &json!("not a list")).unwrap();
+            let err = from_py::<Vec<String>>(object.bind(py), "goals").unwrap_err();
+            assert!(err.to_string().contains("Invalid goals"));
+        });
+    }
+
+    #[test]
+    fn test_parse_str_uses_serde_names() {
+        let event: HookEvent = parse_str("user-prompt-submit", "hook event").unwrap();
+        assert_eq!(event, HookEvent::UserPromptSubmit);
+        assert!(parse_str::<HookEvent>("UserPromptSubmit", "hook event").is_err());
+    }
+}
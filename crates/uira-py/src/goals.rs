@@ -0,0 +1,30 @@
+use std::path::PathBuf;
+
+use pyo3::prelude::*;
+use uira_core::schema::GoalConfig;
+use uira_orchestration::GoalRunner;
+
+use crate::convert::{from_py, to_py};
+use crate::runtime::block_on;
+
+/// Run each goal's command once and compare its score to the target.
+/// `goals` takes the same fields as the `goals` section of `uira.yml`;
+/// returns the verification result as a dict.
+#[pyfunction]
+#[pyo3(signature = (goals, project_root=None))]
+pub(crate) fn check_goals(
+    py: Python<'_>,
+    goals: &Bound<'_, PyAny>,
+    project_root: Option<PathBuf>,
+) -> PyResult<PyObject> {
+    let goals: Vec<GoalConfig> = from_py(goals, "goals")?;
+    let goals: Vec<GoalConfig> = goals.into_iter().filter(|goal| goal.enabled).collect();
+    let project_root = match project_root {
+        Some(root) => root,
+        None => std::env::current_dir()?,
+    };
+
+    let runner = GoalRunner::new(project_root);
+    let result = block_on(py, runner.check_all(&goals));
+    to_py(py, &result)
+}
@@ -0,0 +1,42 @@
+use pyo3::exceptions::PyRuntimeError;
+use pyo3::prelude::*;
+use uira_orchestration::default_hooks;
+use uira_orchestration::hooks::{HookContext, HookEvent, HookInput};
+
+use crate::convert::{from_py, parse_str, to_py};
+use crate::runtime::block_on;
+
+/// Run the built-in hooks registered for `event` (e.g.
+/// `"user-prompt-submit"`, `"stop"`) against `input` and return the
+/// combined hook output as a dict with `continue`, `message` and `reason`.
+#[pyfunction]
+#[pyo3(signature = (event, input=None, directory=None, session_id=None))]
+pub(crate) fn execute_hooks(
+    py: Python<'_>,
+    event: &str,
+    input: Option<&Bound<'_, PyAny>>,
+    directory: Option<String>,
+    session_id: Option<String>,
+) -> PyResult<PyObject> {
+    let event: HookEvent = parse_str(event, "hook event")?;
+    let input: HookInput = match input {
+        Some(input) => from_py(input, "hook input")?,
+        None => HookInput::default(),
+    };
+    let directory = directory.unwrap_or_else(|| input.get_directory());
+    let session_id = session_id.or_else(|| input.session_id.clone());
+    let context = HookContext::new(session_id, directory, None);
+
+    let registry = default_hooks();
+    let output = block_on(py, registry.execute_hooks(event, &input, &context))
+        .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+    to_py(py, &output)
+}
+
+/// Names of the built-in hooks, sorted
+#[pyfunction]
+pub(crate) fn list_hooks() -> Vec<String> {
+    let mut names = default_hooks().list_hooks();
+    names.sort();
+    names
+}
@@ -0,0 +1,19 @@
+use pyo3::prelude::*;
+use uira_orchestration::KeywordDetector;
+
+/// Every magic keyword in `prompt`, as `(name, message)` pairs in
+/// detection order. Code blocks and inline code are ignored.
+#[pyfunction]
+#[pyo3(signature = (prompt, agent=None))]
+pub(crate) fn detect_keywords(prompt: &str, agent: Option<&str>) -> Vec<(&'static str, String)> {
+    KeywordDetector::new().detect_all(prompt, agent)
+}
+
+/// Message for the highest-priority keyword in `prompt`, if any
+#[pyfunction]
+#[pyo3(signature = (prompt, agent=None))]
+pub(crate) fn detect_keyword(prompt: &str, agent: Option<&str>) -> Option<String> {
+    KeywordDetector::new()
+        .detect(prompt, agent)
+        .and_then(|output| output.message)
+}
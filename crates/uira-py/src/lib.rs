@@ -0,0 +1,34 @@
+//! Python bindings for Uira
+//!
+//! Exposes keyword detection, model routing, hook execution, goal checks
+//! and the streaming agent loop as the `uira._uira` extension module, so
+//! Python services can embed uira without going through the Node bridge.
+//! Structured values cross the boundary as plain dicts and lists with the
+//! same field names as the JSON protocol. Build the wheel with `maturin`
+//! from this directory; the `uira` package in `python/` re-exports the
+//! module and ships its type stubs.
+
+mod agent;
+mod convert;
+mod goals;
+mod hooks;
+mod keywords;
+mod routing;
+mod runtime;
+
+use pyo3::prelude::*;
+
+#[pymodule]
+fn _uira(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add("__version__", env!("CARGO_PKG_VERSION"))?;
+    m.add_function(wrap_pyfunction!(keywords::detect_keywords, m)?)?;
+    m.add_function(wrap_pyfunction!(keywords::detect_keyword, m)?)?;
+    m.add_function(wrap_pyfunction!(routing::route_task, m)?)?;
+    m.add_function(wrap_pyfunction!(routing::explain_routing, m)?)?;
+    m.add_function(wrap_pyfunction!(hooks::execute_hooks, m)?)?;
+    m.add_function(wrap_pyfunction!(hooks::list_hooks, m)?)?;
+    m.add_function(wrap_pyfunction!(goals::check_goals, m)?)?;
+    m.add_class::<agent::PyAgent>()?;
+    m.add_class::<agent::EventIterator>()?;
+    Ok(())
+}
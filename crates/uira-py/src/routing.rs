@@ -0,0 +1,59 @@
+use pyo3::prelude::*;
+use uira_orchestration::model_routing::{self, RoutingConfigOverrides, RoutingContext};
+use uira_orchestration::ModelType;
+
+use crate::convert::{parse_str, to_py};
+
+fn routing_context(
+    prompt: &str,
+    agent_type: Option<String>,
+    previous_failures: Option<u32>,
+    conversation_turns: Option<u32>,
+    explicit_model: Option<&str>,
+) -> PyResult<RoutingContext> {
+    let explicit_model = explicit_model
+        .map(|model| parse_str::<ModelType>(model, "model"))
+        .transpose()?;
+    Ok(RoutingContext {
+        task_prompt: prompt.to_string(),
+        agent_type,
+        previous_failures,
+        conversation_turns,
+        explicit_model,
+        ..RoutingContext::default()
+    })
+}
+
+/// Pick a model tier for `prompt` with the default routing rules.
+/// Returns the routing decision as a dict.
+#[pyfunction]
+#[pyo3(signature = (prompt, agent_type=None, previous_failures=None, conversation_turns=None, explicit_model=None))]
+pub(crate) fn route_task(
+    py: Python<'_>,
+    prompt: &str,
+    agent_type: Option<String>,
+    previous_failures: Option<u32>,
+    conversation_turns: Option<u32>,
+    explicit_model: Option<&str>,
+) -> PyResult<PyObject> {
+    let context = routing_context(
+        prompt,
+        agent_type,
+        previous_failures,
+        conversation_turns,
+        explicit_model,
+    )?;
+    let decision = model_routing::route_task(context, RoutingConfigOverrides::default());
+    to_py(py, &decision)
+}
+
+/// Human-readable breakdown of the signals behind a routing decision
+#[pyfunction]
+#[pyo3(signature = (prompt, agent_type=None))]
+pub(crate) fn explain_routing(prompt: &str, agent_type: Option<String>) -> PyResult<String> {
+    let context = routing_context(prompt, agent_type, None, None, None)?;
+    Ok(model_routing::explain_routing(
+        context,
+        RoutingConfigOverrides::default(),
+    ))
+}
@@ -0,0 +1,28 @@
+use std::future::Future;
+use std::sync::OnceLock;
+
+use pyo3::prelude::*;
+use tokio::runtime::Runtime;
+
+static RUNTIME: OnceLock<Runtime> = OnceLock::new();
+
+/// Process-wide runtime shared by every binding call
+pub(crate) fn runtime() -> &'static Runtime {
+    RUNTIME.get_or_init(|| {
+        tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_name("uira-py")
+            .build()
+            .expect("failed to start the uira tokio runtime")
+    })
+}
+
+/// Run a future to completion with the GIL released, so other Python
+/// threads keep running while hooks, goals or the agent are busy.
+pub(crate) fn block_on<F>(py: Python<'_>, future: F) -> F::Output
+where
+    F: Future + Send,
+    F::Output: Send,
+{
+    py.allow_threads(|| runtime().block_on(future))
+}
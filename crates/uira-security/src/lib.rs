@@ -2,9 +2,9 @@ pub mod permissions;
 pub mod sandbox;
 
 pub use permissions::{
-    build_evaluator_from_rules, expand_path, normalize_path, Action, CompiledRule, ConfigAction,
-    ConfigRule, EvaluationResult, EvaluatorBuilder, Pattern, PatternError, Permission,
-    PermissionEvaluator, PermissionRule,
+    build_evaluator_from_rules, expand_path, normalize_path, shell_write_targets, Action,
+    CompiledRule, ConfigAction, ConfigRule, EvaluationResult, EvaluatorBuilder, Pattern,
+    PatternError, Permission, PermissionEvaluator, PermissionRule,
 };
 pub use sandbox::{
     is_dangerous_command, is_safe_command, SandboxError, SandboxManager, SandboxPolicy, SandboxType,
@@ -1,4 +1,4 @@
-use super::{Action, PatternError, PermissionEvaluator, PermissionRule};
+use super::{Action, EvaluatorBuilder, PatternError, PermissionEvaluator, PermissionRule};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum ConfigAction {
@@ -23,13 +23,15 @@ pub struct ConfigRule {
     pub permission: String,
     pub pattern: String,
     pub action: ConfigAction,
+    pub priority: i32,
     pub comment: Option<String>,
 }
 
 impl From<ConfigRule> for PermissionRule {
     fn from(config: ConfigRule) -> Self {
         let mut rule =
-            PermissionRule::new(&config.permission, &config.pattern, config.action.into());
+            PermissionRule::new(&config.permission, &config.pattern, config.action.into())
+                .with_priority(config.priority);
         if let Some(name) = config.name {
             rule = rule.with_name(name);
         }
@@ -43,8 +45,9 @@ impl From<ConfigRule> for PermissionRule {
 pub fn build_evaluator_from_rules(
     rules: Vec<ConfigRule>,
 ) -> Result<PermissionEvaluator, PatternError> {
-    let permission_rules: Vec<PermissionRule> = rules.into_iter().map(Into::into).collect();
-    PermissionEvaluator::with_rules(permission_rules)
+    EvaluatorBuilder::new()
+        .rules(rules.into_iter().map(Into::into))
+        .build()
 }
 
 #[cfg(test)]
@@ -58,6 +61,7 @@ mod tests {
             permission: "file:write".to_string(),
             pattern: "src/**".to_string(),
             action: ConfigAction::Allow,
+            priority: 0,
             comment: None,
         };
 
@@ -75,6 +79,7 @@ mod tests {
                 permission: "file:read".to_string(),
                 pattern: "**".to_string(),
                 action: ConfigAction::Allow,
+                priority: 0,
                 comment: None,
             },
             ConfigRule {
@@ -82,6 +87,7 @@ mod tests {
                 permission: "file:write".to_string(),
                 pattern: "**".to_string(),
                 action: ConfigAction::Ask,
+                priority: 0,
                 comment: None,
             },
         ];
@@ -2,8 +2,15 @@
 //!
 //! Evaluates permissions against a set of rules.
 //! Default action is Allow (per user preference in plan).
+//!
+//! With a workspace root, relative patterns such as `src/**` are scoped to
+//! the workspace: they match paths inside it however the tool spelled them,
+//! absolute or relative. Files a shell command writes are held to the same
+//! `file:write` rules as `Write` and `Edit`.
+
+use std::path::{Component, Path, PathBuf};
 
-use super::pattern::PatternError;
+use super::pattern::{expand_path, PatternError};
 use super::rule::{CompiledRule, PermissionRule};
 use super::types::{Action, Permission};
 
@@ -59,18 +66,21 @@ impl EvaluationResult {
 
 /// Permission evaluator that checks permissions against rules
 ///
-/// Rules are evaluated in order, with later rules overriding earlier ones.
-/// If no rule matches, the default action is Allow.
+/// The matching rule with the highest priority wins; among equal
+/// priorities later rules override earlier ones. If no rule matches, the
+/// default action is Allow.
 #[derive(Debug, Default)]
 pub struct PermissionEvaluator {
     /// Compiled rules for efficient matching
     rules: Vec<CompiledRule>,
+    /// Root that relative patterns are scoped to
+    workspace_root: Option<PathBuf>,
 }
 
 impl PermissionEvaluator {
     /// Create a new empty evaluator
     pub fn new() -> Self {
-        Self { rules: Vec::new() }
+        Self::default()
     }
 
     /// Create an evaluator with the given rules
@@ -80,7 +90,16 @@ impl PermissionEvaluator {
             .map(CompiledRule::compile)
             .collect::<Result<Vec<_>, _>>()?;
 
-        Ok(Self { rules: compiled })
+        Ok(Self {
+            rules: compiled,
+            workspace_root: None,
+        })
+    }
+
+    /// Scope relative patterns to `root`
+    pub fn with_workspace_root(mut self, root: impl AsRef<Path>) -> Self {
+        self.workspace_root = Some(lexical_normalize(root.as_ref()));
+        self
     }
 
     /// Add a rule to the evaluator
@@ -92,15 +111,17 @@ impl PermissionEvaluator {
     /// Evaluate a permission for a given path
     ///
     /// Returns the action to take based on the rules.
-    /// Later rules override earlier ones (last match wins).
+    /// The highest-priority matching rule wins, then the last one.
     /// Default action is Allow if no rules match.
     pub fn evaluate(&self, permission: &str, path: &str) -> EvaluationResult {
-        // Find the last matching rule (later rules override earlier)
+        let forms = self.path_forms(path);
         let matched = self
             .rules
             .iter()
-            .rev()
-            .find(|r| r.matches(permission, path));
+            .enumerate()
+            .filter(|(_, r)| forms.iter().any(|form| r.matches(permission, form)))
+            .max_by_key(|(index, r)| (r.priority(), *index))
+            .map(|(_, r)| r);
 
         match matched {
             Some(rule) => {
@@ -121,12 +142,53 @@ impl PermissionEvaluator {
 
     /// Evaluate a permission for a tool and input
     ///
-    /// Extracts the path from common tool input formats.
+    /// Extracts the path from common tool input formats. For shell commands
+    /// the files the command writes are also checked as `file:write`, and
+    /// the most restrictive matching result wins.
     pub fn evaluate_tool(&self, tool_name: &str, input: &serde_json::Value) -> EvaluationResult {
         let permission = Permission::from_tool_name(tool_name);
         let path = extract_path_from_input(input);
+        let result = self.evaluate(permission.as_str(), &path);
+        if permission != Permission::ShellExecute {
+            return result;
+        }
+
+        shell_write_targets(&path)
+            .iter()
+            .map(|target| self.evaluate(Permission::FileWrite.as_str(), target))
+            .filter(|write| write.matched_rule.is_some())
+            .fold(result, |most, write| {
+                if restrictiveness(write.action) > restrictiveness(most.action) {
+                    write
+                } else {
+                    most
+                }
+            })
+    }
+
+    /// The spellings of `path` rules are matched against: as given, and
+    /// with a workspace root also absolute and relative to the root.
+    /// Commands and URLs are only matched as given.
+    fn path_forms(&self, path: &str) -> Vec<String> {
+        let mut forms = vec![path.to_string()];
+        let Some(root) = &self.workspace_root else {
+            return forms;
+        };
+        if path.is_empty() || path.contains("://") || path.contains(char::is_whitespace) {
+            return forms;
+        }
 
-        self.evaluate(permission.as_str(), &path)
+        let expanded = expand_path(path).unwrap_or_else(|_| path.to_string());
+        let absolute = lexical_normalize(&root.join(expanded));
+        if let Ok(relative) = absolute.strip_prefix(root) {
+            let relative = relative.to_string_lossy().replace('\\', "/");
+            if !relative.is_empty() {
+                forms.push(relative);
+            }
+        }
+        forms.push(absolute.to_string_lossy().to_string());
+        forms.dedup();
+        forms
     }
 
     /// Get the number of rules
@@ -164,6 +226,11 @@ fn extract_path_from_input(input: &serde_json::Value) -> String {
         }
     }
 
+    // Notebook edits name their file separately
+    if let Some(path) = input.get("notebook_path").and_then(|v| v.as_str()) {
+        return path.to_string();
+    }
+
     // For bash/shell commands, use the command itself
     if let Some(command) = input.get("command").and_then(|v| v.as_str()) {
         return command.to_string();
@@ -173,15 +240,145 @@ fn extract_path_from_input(input: &serde_json::Value) -> String {
     input.to_string()
 }
 
+fn restrictiveness(action: Action) -> u8 {
+    match action {
+        Action::Allow => 0,
+        Action::Ask => 1,
+        Action::Deny => 2,
+    }
+}
+
+/// Resolve `.` and `..` without touching the filesystem
+fn lexical_normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Programs whose every operand is a file they modify
+const WRITING_PROGRAMS: &[&str] = &[
+    "rm", "rmdir", "unlink", "shred", "touch", "mkdir", "truncate", "tee", "mv",
+];
+
+/// Programs that only write their last operand
+const COPYING_PROGRAMS: &[&str] = &["cp", "ln", "install"];
+
+/// Files a shell command writes: redirection targets, and the operands of
+/// programs known to modify them. Quoting is not parsed, so this is a
+/// best-effort list for rule matching, not a sandbox.
+pub fn shell_write_targets(command: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+    for segment in command.split(['\n', ';', '|', '&']) {
+        let words: Vec<&str> = segment
+            .split_whitespace()
+            .map(|word| word.trim_matches(['"', '\'']))
+            .collect();
+
+        let mut operands = Vec::new();
+        let mut redirect = false;
+        for word in &words {
+            if redirect {
+                targets.push(word.to_string());
+                redirect = false;
+                continue;
+            }
+            let target = word.trim_start_matches(|c: char| c.is_ascii_digit());
+            if let Some(target) = target
+                .strip_prefix(">>")
+                .or_else(|| target.strip_prefix('>'))
+            {
+                if target.is_empty() {
+                    redirect = true;
+                } else if !target.starts_with('&') {
+                    targets.push(target.to_string());
+                }
+                continue;
+            }
+            operands.push(*word);
+        }
+
+        let mut program_words = operands.iter().copied().skip_while(|word| {
+            matches!(*word, "sudo" | "doas" | "env" | "command")
+                || (word.contains('=') && !word.starts_with('-'))
+        });
+        let Some(program) = program_words.next() else {
+            continue;
+        };
+        let args: Vec<&str> = program_words.collect();
+        let files: Vec<&str> = args
+            .iter()
+            .copied()
+            .filter(|arg| !arg.starts_with('-'))
+            .collect();
+
+        let written: &[&str] = match program {
+            p if WRITING_PROGRAMS.contains(&p) => &files,
+            p if COPYING_PROGRAMS.contains(&p) => files.last().map_or(&[], std::slice::from_ref),
+            "chmod" | "chown" | "chgrp" => files.get(1..).unwrap_or_default(),
+            "sed" | "perl" if args.iter().any(|arg| arg.starts_with("-i")) => {
+                files.get(1..).unwrap_or_default()
+            }
+            "sort" => {
+                let outputs = args.iter().enumerate().filter_map(|(i, arg)| match *arg {
+                    "-o" | "--output" => args.get(i + 1).copied(),
+                    arg => arg
+                        .strip_prefix("--output=")
+                        .or_else(|| arg.strip_prefix("-o").filter(|rest| !rest.is_empty())),
+                });
+                targets.extend(outputs.map(str::to_string));
+                &[]
+            }
+            "tar" | "unzip" => {
+                // Extraction writes under the directory given to `-C` or `-d`
+                let flag = if program == "tar" { "-C" } else { "-d" };
+                let directories = args.iter().enumerate().filter_map(|(i, arg)| {
+                    if *arg == flag || *arg == "--directory" {
+                        args.get(i + 1).copied()
+                    } else {
+                        arg.strip_prefix("--directory=")
+                    }
+                });
+                targets.extend(directories.map(str::to_string));
+                &[]
+            }
+            "dd" => {
+                targets.extend(
+                    args.iter()
+                        .filter_map(|arg| arg.strip_prefix("of="))
+                        .map(str::to_string),
+                );
+                &[]
+            }
+            _ => &[],
+        };
+        targets.extend(written.iter().map(|file| file.to_string()));
+    }
+    targets.retain(|target| !target.is_empty() && target != "/dev/null");
+    targets.dedup();
+    targets
+}
+
 /// Builder for creating an evaluator with a fluent API
 pub struct EvaluatorBuilder {
     rules: Vec<PermissionRule>,
+    workspace_root: Option<PathBuf>,
 }
 
 impl EvaluatorBuilder {
     /// Create a new builder
     pub fn new() -> Self {
-        Self { rules: Vec::new() }
+        Self {
+            rules: Vec::new(),
+            workspace_root: None,
+        }
     }
 
     /// Add a rule
@@ -190,6 +387,18 @@ impl EvaluatorBuilder {
         self
     }
 
+    /// Add rules in order
+    pub fn rules(mut self, rules: impl IntoIterator<Item = PermissionRule>) -> Self {
+        self.rules.extend(rules);
+        self
+    }
+
+    /// Scope relative patterns such as `src/**` to `root`
+    pub fn workspace_root(mut self, root: impl Into<PathBuf>) -> Self {
+        self.workspace_root = Some(root.into());
+        self
+    }
+
     /// Allow all reads
     pub fn allow_reads(self) -> Self {
         self.rule(PermissionRule::allow_all_reads())
@@ -212,7 +421,11 @@ impl EvaluatorBuilder {
 
     /// Build the evaluator
     pub fn build(self) -> Result<PermissionEvaluator, PatternError> {
-        PermissionEvaluator::with_rules(self.rules)
+        let evaluator = PermissionEvaluator::with_rules(self.rules)?;
+        Ok(match self.workspace_root {
+            Some(root) => evaluator.with_workspace_root(root),
+            None => evaluator,
+        })
     }
 }
 
@@ -329,6 +542,118 @@ mod tests {
         assert!(extract_path_from_input(&input).contains("unknown"));
     }
 
+    fn scoped_evaluator() -> PermissionEvaluator {
+        EvaluatorBuilder::new()
+            .rule(PermissionRule::new("file:write", "**", Action::Ask))
+            .rule(PermissionRule::new("file:write", "src/**", Action::Allow))
+            .rule(PermissionRule::new(
+                "file:write",
+                "migrations/**",
+                Action::Deny,
+            ))
+            .workspace_root("/work/app")
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_workspace_scoped_patterns() {
+        let evaluator = scoped_evaluator();
+        let write = |path: &str| {
+            evaluator
+                .evaluate_tool("Write", &serde_json::json!({ "file_path": path }))
+                .action
+        };
+
+        assert_eq!(write("src/main.rs"), Action::Allow);
+        assert_eq!(write("/work/app/src/main.rs"), Action::Allow);
+        assert_eq!(write("./migrations/001.sql"), Action::Deny);
+        assert_eq!(write("/work/app/src/../migrations/001.sql"), Action::Deny);
+        assert_eq!(write("/other/src/main.rs"), Action::Ask);
+        assert_eq!(write("../app2/src/main.rs"), Action::Ask);
+
+        let edit = evaluator.evaluate_tool(
+            "MultiEdit",
+            &serde_json::json!({ "file_path": "/work/app/migrations/002.sql" }),
+        );
+        assert!(edit.is_denied());
+    }
+
+    #[test]
+    fn test_priority_overrides_order() {
+        let evaluator = EvaluatorBuilder::new()
+            .rule(
+                PermissionRule::new("file:write", "src/generated/**", Action::Deny)
+                    .with_priority(10),
+            )
+            .rule(PermissionRule::new("file:write", "src/**", Action::Allow))
+            .build()
+            .unwrap();
+
+        let result = evaluator.evaluate("file:write", "src/generated/api.rs");
+        assert!(result.is_denied());
+        assert_eq!(
+            result.matched_rule.as_deref(),
+            Some("file:write:src/generated/**")
+        );
+        assert!(evaluator.evaluate("file:write", "src/lib.rs").is_allowed());
+    }
+
+    #[test]
+    fn test_shell_write_targets() {
+        assert_eq!(
+            shell_write_targets("rm -rf build dist && ls src"),
+            vec!["build", "dist"]
+        );
+        assert_eq!(
+            shell_write_targets("cargo test 2>&1 | tee log.txt > out.txt"),
+            vec!["out.txt", "log.txt"]
+        );
+        assert_eq!(shell_write_targets("echo hi >>notes.md"), vec!["notes.md"]);
+        assert_eq!(
+            shell_write_targets("cp a.rs b.rs src/; sudo chmod 644 x.sh"),
+            vec!["src/", "x.sh"]
+        );
+        assert_eq!(
+            shell_write_targets("sed -i 's/a/b/' src/lib.rs"),
+            vec!["src/lib.rs"]
+        );
+        assert_eq!(
+            shell_write_targets("sort -o ~/.profile a.txt; sort --output=b.txt a.txt"),
+            vec!["~/.profile", "b.txt"]
+        );
+        assert_eq!(
+            shell_write_targets("tar -xzf a.tgz -C /opt && unzip b.zip -d out"),
+            vec!["/opt", "out"]
+        );
+        assert!(shell_write_targets("cat migrations/001.sql > /dev/null").is_empty());
+    }
+
+    #[test]
+    fn test_shell_commands_follow_file_write_rules() {
+        let evaluator = EvaluatorBuilder::new()
+            .rule(PermissionRule::new("shell:execute", "**", Action::Ask))
+            .rule(PermissionRule::new("file:write", "src/**", Action::Allow))
+            .rule(PermissionRule::new(
+                "file:write",
+                "migrations/**",
+                Action::Deny,
+            ))
+            .workspace_root("/work/app")
+            .build()
+            .unwrap();
+        let bash = |command: &str| {
+            evaluator.evaluate_tool("Bash", &serde_json::json!({ "command": command }))
+        };
+
+        let result = bash("rm /work/app/migrations/001.sql");
+        assert!(result.is_denied());
+        assert_eq!(result.path, "/work/app/migrations/001.sql");
+        assert!(bash("echo x > migrations/new.sql").is_denied());
+        assert!(bash("echo x > src/new.rs").needs_approval());
+        assert!(bash("cat migrations/001.sql").needs_approval());
+    }
+
     #[test]
     fn test_evaluation_result_methods() {
         let result = EvaluationResult::default_action("file:read".into(), "/path".into());
@@ -2,7 +2,12 @@
 //!
 //! This crate provides a flexible permission system based on glob patterns.
 //! Permissions are evaluated against a set of rules, with the last matching
-//! rule taking precedence. The default action (when no rules match) is Allow.
+//! rule taking precedence unless another matching rule has a higher
+//! `priority`. The default action (when no rules match) is Allow.
+//!
+//! Given a workspace root, relative patterns like `migrations/**` are scoped
+//! to the workspace, and the files a shell command writes are checked
+//! against the same `file:write` rules as `Write` and `Edit`.
 //!
 //! # Architecture
 //!
@@ -78,7 +83,7 @@ pub mod rule;
 pub mod types;
 
 pub use config::{build_evaluator_from_rules, ConfigAction, ConfigRule};
-pub use evaluator::{EvaluationResult, EvaluatorBuilder, PermissionEvaluator};
+pub use evaluator::{shell_write_targets, EvaluationResult, EvaluatorBuilder, PermissionEvaluator};
 pub use pattern::{expand_path, normalize_path, Pattern, PatternError};
 pub use rule::{CompiledRule, PermissionRule};
 pub use types::{Action, Permission};
@@ -38,6 +38,7 @@ impl Pattern {
         let expanded = expand_path(pattern)?;
         let glob = GlobBuilder::new(&expanded)
             .literal_separator(true)
+            .case_insensitive(cfg!(windows))
             .build()?;
         let matcher = glob.compile_matcher();
 
@@ -55,10 +56,11 @@ impl Pattern {
 
     /// Check if this pattern matches a path with expansion
     pub fn matches_expanded(&self, path: &str) -> bool {
-        if let Ok(expanded_path) = expand_path(path) {
-            self.matcher.is_match(&expanded_path)
+        let expanded_path = expand_path(path).unwrap_or_else(|_| path.to_string());
+        if cfg!(windows) {
+            self.matcher.is_match(windows_glob_path(&expanded_path))
         } else {
-            self.matcher.is_match(path)
+            self.matcher.is_match(&expanded_path)
         }
     }
 
@@ -86,26 +88,51 @@ pub fn expand_path(path: &str) -> Result<String, PatternError> {
     if result.starts_with("~/") {
         let home = dirs::home_dir()
             .ok_or_else(|| PatternError::ExpansionFailed("could not find home directory".into()))?;
-        result = format!("{}{}", home.display(), &result[1..]);
+        result = format!("{}{}", glob_dir(&home), &result[1..]);
     }
 
     // Expand $HOME
     if result.contains("$HOME") {
         let home = dirs::home_dir()
             .ok_or_else(|| PatternError::ExpansionFailed("could not find home directory".into()))?;
-        result = result.replace("$HOME", &home.display().to_string());
+        result = result.replace("$HOME", &glob_dir(&home));
     }
 
     // Expand $CWD
     if result.contains("$CWD") {
         let cwd = std::env::current_dir()
             .map_err(|e| PatternError::ExpansionFailed(format!("could not get cwd: {}", e)))?;
-        result = result.replace("$CWD", &cwd.display().to_string());
+        result = result.replace("$CWD", &glob_dir(&cwd));
     }
 
     Ok(result)
 }
 
+/// A directory as it appears inside a glob; on Windows separators become `/`
+/// so `$CWD/**` keeps `**` as a wildcard instead of an escaped `\*`
+fn glob_dir(dir: &std::path::Path) -> String {
+    let dir = dir.display().to_string();
+    if cfg!(windows) {
+        windows_glob_path(&dir)
+    } else {
+        dir
+    }
+}
+
+/// Rewrite a Windows path into the form patterns are written in: the
+/// verbatim `\\?\` prefix (from `canonicalize`) is dropped and `\` becomes `/`
+///
+/// `\\?\C:\Users\me\repo` → `C:/Users/me/repo`,
+/// `\\?\UNC\server\share` → `//server/share`
+pub fn windows_glob_path(path: &str) -> String {
+    let path = if let Some(rest) = path.strip_prefix(r"\\?\UNC\") {
+        format!(r"\\{rest}")
+    } else {
+        path.strip_prefix(r"\\?\").unwrap_or(path).to_string()
+    };
+    path.replace('\\', "/")
+}
+
 /// Normalize a path for matching
 ///
 /// Removes trailing slashes and normalizes separators
@@ -217,6 +244,23 @@ mod tests {
         assert!(!matches_any("docs/readme.md", &patterns));
     }
 
+    #[test]
+    fn test_windows_glob_path() {
+        assert_eq!(
+            windows_glob_path(r"\\?\C:\Users\me\repo\src\main.rs"),
+            "C:/Users/me/repo/src/main.rs"
+        );
+        assert_eq!(
+            windows_glob_path(r"\\?\UNC\server\share\file"),
+            "//server/share/file"
+        );
+        assert_eq!(windows_glob_path(r"src\lib.rs"), "src/lib.rs");
+        assert_eq!(windows_glob_path("src/lib.rs"), "src/lib.rs");
+
+        let pattern = Pattern::new("C:/Users/me/repo/**").unwrap();
+        assert!(pattern.matches(&windows_glob_path(r"\\?\C:\Users\me\repo\a\b.rs")));
+    }
+
     #[test]
     fn test_invalid_pattern() {
         let result = Pattern::new("[invalid");
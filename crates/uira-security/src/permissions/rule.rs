@@ -23,6 +23,11 @@ pub struct PermissionRule {
     /// Action to take when this rule matches
     pub action: Action,
 
+    /// Rules with a higher priority win over lower ones that also match;
+    /// among equal priorities the last matching rule wins
+    #[serde(default)]
+    pub priority: i32,
+
     /// Optional comment explaining this rule
     #[serde(default)]
     pub comment: Option<String>,
@@ -36,10 +41,17 @@ impl PermissionRule {
             permission: permission.into(),
             pattern: pattern.into(),
             action,
+            priority: 0,
             comment: None,
         }
     }
 
+    /// Set the priority of this rule
+    pub fn with_priority(mut self, priority: i32) -> Self {
+        self.priority = priority;
+        self
+    }
+
     /// Add a name to this rule
     pub fn with_name(mut self, name: impl Into<String>) -> Self {
         self.name = Some(name.into());
@@ -87,6 +99,11 @@ impl CompiledRule {
         self.rule.action
     }
 
+    /// Get the priority of this rule
+    pub fn priority(&self) -> i32 {
+        self.rule.priority
+    }
+
     /// Get the original rule
     pub fn rule(&self) -> &PermissionRule {
         &self.rule
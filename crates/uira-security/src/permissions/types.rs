@@ -59,7 +59,9 @@ impl Permission {
     pub fn from_tool_name(name: &str) -> Self {
         match name.to_lowercase().as_str() {
             "read" | "read_file" | "file_read" => Permission::FileRead,
-            "write" | "write_file" | "file_write" | "edit" => Permission::FileWrite,
+            "write" | "write_file" | "file_write" | "edit" | "multiedit" | "notebookedit" => {
+                Permission::FileWrite
+            }
             "delete" | "remove" | "rm" => Permission::FileDelete,
             "bash" | "shell" | "exec" | "execute" => Permission::ShellExecute,
             "fetch" | "http" | "request" | "web_search" | "fetch_url" => Permission::NetworkAccess,
@@ -0,0 +1,110 @@
+//! Windows AppContainer profile generation (stub)
+//!
+//! An AppContainer process can only touch paths whose ACLs grant its package
+//! SID, and only reaches the network through declared capabilities. This
+//! module derives that profile from a [`SandboxPolicy`]; launching the
+//! process inside the container (`CreateAppContainerProfile`, ACL grants,
+//! `PROC_THREAD_ATTRIBUTE_SECURITY_CAPABILITIES`) is not implemented yet, so
+//! commands are reported as unsandboxable rather than run unrestricted.
+
+use std::path::PathBuf;
+
+use serde::{Deserialize, Serialize};
+
+use super::SandboxPolicy;
+
+/// AppContainer profile name registered for sandboxed commands
+pub const PROFILE_NAME: &str = "uira.sandbox";
+
+/// Well-known capability granting outbound network access
+pub const INTERNET_CLIENT: &str = "internetClient";
+
+/// Capabilities and path grants for an AppContainer launch
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AppContainerProfile {
+    pub name: String,
+    pub capabilities: Vec<String>,
+    /// Paths granted read/execute to the container SID
+    pub readable: Vec<PathBuf>,
+    /// Paths granted read/write to the container SID
+    pub writable: Vec<PathBuf>,
+    /// Writable paths the grant must not reach (explicit deny ACEs)
+    pub denied: Vec<PathBuf>,
+}
+
+/// Generate an AppContainer profile from a sandbox policy
+///
+/// Returns `None` for [`SandboxPolicy::FullAccess`], which needs no container.
+pub fn generate_profile(policy: &SandboxPolicy) -> Option<AppContainerProfile> {
+    let mut profile = AppContainerProfile {
+        name: PROFILE_NAME.to_string(),
+        capabilities: Vec::new(),
+        readable: Vec::new(),
+        writable: Vec::new(),
+        denied: Vec::new(),
+    };
+
+    match policy {
+        SandboxPolicy::FullAccess => return None,
+        SandboxPolicy::ReadOnly => {}
+        SandboxPolicy::WorkspaceWrite {
+            workspace,
+            protected_paths,
+        } => {
+            profile.writable.push(workspace.clone());
+            profile.denied.extend(protected_paths.iter().cloned());
+        }
+        SandboxPolicy::Custom {
+            readable,
+            writable,
+            executable,
+            ..
+        } => {
+            profile.readable.extend(readable.iter().cloned());
+            profile.readable.extend(executable.iter().cloned());
+            profile.writable.extend(writable.iter().cloned());
+        }
+    }
+
+    if policy.allows_network() {
+        profile.capabilities.push(INTERNET_CLIENT.to_string());
+    }
+
+    Some(profile)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_full_access_needs_no_container() {
+        assert!(generate_profile(&SandboxPolicy::full_access()).is_none());
+    }
+
+    #[test]
+    fn test_workspace_write_profile() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            workspace: PathBuf::from(r"C:\repo"),
+            protected_paths: vec![PathBuf::from(r"C:\repo\.git")],
+        };
+        let profile = generate_profile(&policy).unwrap();
+        assert_eq!(profile.name, PROFILE_NAME);
+        assert_eq!(profile.writable, vec![PathBuf::from(r"C:\repo")]);
+        assert_eq!(profile.denied, vec![PathBuf::from(r"C:\repo\.git")]);
+        assert!(profile.capabilities.is_empty());
+    }
+
+    #[test]
+    fn test_custom_network_capability() {
+        let policy = SandboxPolicy::Custom {
+            readable: vec![PathBuf::from(r"C:\tools")],
+            writable: vec![],
+            executable: vec![PathBuf::from(r"C:\bin")],
+            network: true,
+        };
+        let profile = generate_profile(&policy).unwrap();
+        assert_eq!(profile.capabilities, vec![INTERNET_CLIENT]);
+        assert_eq!(profile.readable.len(), 2);
+    }
+}
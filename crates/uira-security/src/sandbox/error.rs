@@ -1,6 +1,7 @@
 //! Sandbox error types
 
 use thiserror::Error;
+use uira_core::{ErrorKind, UiraError};
 
 #[derive(Debug, Error)]
 pub enum SandboxError {
@@ -25,3 +26,33 @@ pub enum SandboxError {
     #[error("io error: {0}")]
     Io(#[from] std::io::Error),
 }
+
+impl From<&SandboxError> for UiraError {
+    fn from(error: &SandboxError) -> Self {
+        let (kind, code) = match error {
+            SandboxError::NotAvailable => (ErrorKind::Internal, "sandbox.not_available"),
+            SandboxError::SetupFailed(_) => (ErrorKind::Internal, "sandbox.setup_failed"),
+            SandboxError::ExecutionDenied { .. } => {
+                (ErrorKind::PolicyDenial, "sandbox.execution_denied")
+            }
+            SandboxError::PolicyViolation(_) => {
+                (ErrorKind::PolicyDenial, "sandbox.policy_violation")
+            }
+            SandboxError::CommandNotAllowed { .. } => {
+                (ErrorKind::PolicyDenial, "sandbox.command_not_allowed")
+            }
+            SandboxError::PathAccessDenied { .. } => {
+                (ErrorKind::PolicyDenial, "sandbox.path_access_denied")
+            }
+            SandboxError::Io(_) => (ErrorKind::Internal, "sandbox.io"),
+        };
+        let uira_error = UiraError::new(kind, code, error.to_string());
+        if kind == ErrorKind::PolicyDenial {
+            uira_error.with_remediation(
+                "Adjust the sandbox policy in uira.yml if this action should be allowed",
+            )
+        } else {
+            uira_error
+        }
+    }
+}
@@ -82,7 +82,11 @@ impl SandboxManager {
                 {
                     self.wrap_landlock(cmd)
                 }
-                #[cfg(not(any(target_os = "macos", target_os = "linux")))]
+                #[cfg(windows)]
+                {
+                    self.wrap_appcontainer(cmd)
+                }
+                #[cfg(not(any(target_os = "macos", target_os = "linux", windows)))]
                 {
                     Err(SandboxError::NotAvailable)
                 }
@@ -115,6 +119,22 @@ impl SandboxManager {
         Ok(())
     }
 
+    #[cfg(windows)]
+    fn wrap_appcontainer(&self, _cmd: &mut Command) -> Result<(), SandboxError> {
+        use super::appcontainer;
+
+        let Some(profile) = appcontainer::generate_profile(&self.policy) else {
+            return Ok(());
+        };
+        // Refuse instead of running unrestricted until the launcher exists
+        tracing::warn!(
+            profile = %profile.name,
+            writable = ?profile.writable,
+            "AppContainer sandbox is not implemented yet; command not sandboxed"
+        );
+        Err(SandboxError::NotAvailable)
+    }
+
     #[cfg(target_os = "linux")]
     fn wrap_landlock(&self, cmd: &mut Command) -> Result<(), SandboxError> {
         use super::landlock;
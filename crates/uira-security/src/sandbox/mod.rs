@@ -3,7 +3,8 @@
 //! This crate provides sandboxing capabilities for secure command execution:
 //! - macOS: Seatbelt (sandbox-exec)
 //! - Linux: Landlock + seccomp
-//! - Windows: Restricted tokens (future)
+//! - Windows: AppContainer (profile generation only; launching is not yet
+//!   implemented)
 
 mod error;
 mod manager;
@@ -16,6 +17,9 @@ mod landlock;
 #[cfg(target_os = "macos")]
 mod seatbelt;
 
+#[cfg(any(windows, test))]
+mod appcontainer;
+
 pub use error::SandboxError;
 pub use manager::SandboxManager;
 pub use policy::{SandboxPolicy, SandboxType};
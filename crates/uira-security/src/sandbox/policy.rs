@@ -1,7 +1,7 @@
 //! Sandbox policy definitions
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// Type of sandbox to use
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
@@ -67,6 +67,24 @@ impl SandboxPolicy {
             _ => false,
         }
     }
+
+    /// Whether the policy permits writing to `path`
+    pub fn allows_write(&self, path: &Path) -> bool {
+        match self {
+            Self::ReadOnly => false,
+            Self::WorkspaceWrite {
+                workspace,
+                protected_paths,
+            } => {
+                path.starts_with(workspace)
+                    && !protected_paths
+                        .iter()
+                        .any(|protected| path.starts_with(protected))
+            }
+            Self::FullAccess => true,
+            Self::Custom { writable, .. } => writable.iter().any(|dir| path.starts_with(dir)),
+        }
+    }
 }
 
 #[cfg(test)]
@@ -84,4 +102,17 @@ mod tests {
         let policy = SandboxPolicy::full_access();
         assert!(!policy.is_restrictive());
     }
+
+    #[test]
+    fn test_allows_write() {
+        let policy = SandboxPolicy::WorkspaceWrite {
+            workspace: PathBuf::from("/repo"),
+            protected_paths: vec![PathBuf::from("/repo/.git")],
+        };
+        assert!(policy.allows_write(Path::new("/repo/src/main.rs")));
+        assert!(!policy.allows_write(Path::new("/repo/.git/config")));
+        assert!(!policy.allows_write(Path::new("/other/file")));
+        assert!(!SandboxPolicy::read_only().allows_write(Path::new("/repo/a")));
+        assert!(SandboxPolicy::full_access().allows_write(Path::new("/repo/a")));
+    }
 }
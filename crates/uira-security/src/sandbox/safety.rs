@@ -6,8 +6,14 @@ pub fn is_dangerous_command(cmd: &[String]) -> bool {
         return false;
     }
 
-    let program = cmd[0].as_str();
+    // Windows resolves `DEL`, `cmd.exe` and `cmd` to the same thing
+    let program = cmd[0].to_ascii_lowercase();
+    let program = program.strip_suffix(".exe").unwrap_or(&program);
     let args: Vec<&str> = cmd[1..].iter().map(|s| s.as_str()).collect();
+    let has_switch = |names: &[&str]| {
+        args.iter()
+            .any(|a| names.iter().any(|name| a.eq_ignore_ascii_case(name)))
+    };
 
     match program {
         // Destructive file operations
@@ -53,6 +59,16 @@ pub fn is_dangerous_command(cmd: &[String]) -> bool {
         "bash" | "sh" | "zsh" => args.contains(&"-c"),
         "eval" => true,
 
+        // Windows destructive file and disk operations
+        "del" | "erase" => has_switch(&["/s", "/q", "/f"]),
+        "rd" => true,
+        "format" | "diskpart" => true,
+        "remove-item" | "ri" => has_switch(&["-recurse", "-force", "-r"]),
+
+        // Windows shell execution
+        "cmd" => has_switch(&["/c", "/k"]),
+        "powershell" | "pwsh" => has_switch(&["-c", "-command", "-e", "-enc", "-encodedcommand"]),
+
         // Environment manipulation
         "export" => args.iter().any(|a| a.contains("PATH=")),
 
@@ -144,6 +160,19 @@ mod tests {
         assert!(is_dangerous_command(&cmd("dd if=/dev/zero of=/dev/sda")));
     }
 
+    #[test]
+    fn test_dangerous_windows_commands() {
+        assert!(is_dangerous_command(&cmd("del /S /Q build")));
+        assert!(!is_dangerous_command(&cmd("del notes.txt")));
+        assert!(is_dangerous_command(&cmd("CMD.EXE /c echo hi")));
+        assert!(is_dangerous_command(&cmd(
+            "powershell.exe -EncodedCommand AAAA"
+        )));
+        assert!(is_dangerous_command(&cmd("Remove-Item -Recurse dist")));
+        assert!(!is_dangerous_command(&cmd("pwsh -File build.ps1")));
+        assert!(is_dangerous_command(&cmd("bash.exe -c ls")));
+    }
+
     #[test]
     fn test_safe_commands() {
         assert!(is_safe_command(&cmd("ls -la")));
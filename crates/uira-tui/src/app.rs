@@ -18,7 +18,7 @@ use std::io::{Stdout, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 use std::sync::Arc;
-use std::time::{SystemTime, UNIX_EPOCH};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 use tokio::process::Command as TokioCommand;
 use tokio::sync::{mpsc, oneshot};
 use uira_agent::{
@@ -27,16 +27,18 @@ use uira_agent::{
 };
 use uira_core::Provider;
 use uira_core::{
-    schema::SidebarConfig, ENV_ANTHROPIC_API_KEY, ENV_GEMINI_API_KEY, ENV_GOOGLE_API_KEY,
-    ENV_OPENAI_API_KEY, UIRA_DIR,
+    schema::{NotificationSettings, SidebarConfig},
+    ENV_ANTHROPIC_API_KEY, ENV_GEMINI_API_KEY, ENV_GOOGLE_API_KEY, ENV_OPENAI_API_KEY, UIRA_DIR,
 };
 use uira_core::{
-    AgentState, ContentBlock, ImageSource, Item, Message, MessageContent, Role, ThreadEvent,
-    TodoItem, TodoPriority, TodoStatus,
+    todo_tree, AgentState, ContentBlock, ImageSource, Item, Message, MessageContent, Role,
+    ThreadEvent, TodoItem, TodoPriority, TodoStatus,
 };
+use uira_orchestration::hooks::{AutopilotConfig, AutopilotHook};
+use uira_orchestration::{ModelRegistry, Notifier};
 use uira_providers::{
-    AnthropicClient, FriendliAIConfig, FriendliClient, FriendliEndpointType, GeminiClient,
-    ModelClient, OllamaClient, OpenAIClient, OpenCodeClient, ProviderConfig, SecretString,
+    FriendliAIConfig, FriendliEndpointType, ModelClient, ModelClientBuilder, ProviderConfig,
+    SecretString,
 };
 use unicode_width::UnicodeWidthChar;
 
@@ -48,12 +50,12 @@ use crate::views::{
     dialog_provider, dialog_session_list, dialog_session_rename, dialog_status, dialog_subagent,
     dialog_tag, dialog_theme_list, dialog_timeline, ApprovalOverlay, ApprovalRequest, ChatView,
     CommandPalette, ModelSelector, PaletteAction, QuestionPrompt, QuestionPromptAction,
-    ToastManager, ToastVariant, INLINE_APPROVAL_HEIGHT, MODEL_GROUPS,
+    ToastManager, ToastVariant, INLINE_APPROVAL_HEIGHT,
 };
 use crate::widgets::autocomplete::{AutocompleteMode, AutocompleteState, SlashCommand};
 use crate::widgets::dialog::DialogStack;
 use crate::widgets::diff::WrapMode;
-use crate::widgets::hud::{self, BackgroundTaskRegistry};
+use crate::widgets::hud::{self, AutopilotProgress, BackgroundTaskRegistry};
 use crate::widgets::ChatMessage;
 use crate::widgets::LogoImage;
 use crate::{AppEvent, FrecencyStore, Theme, ThemeOverrides};
@@ -617,6 +619,25 @@ fn format_gh_error(stderr: &str) -> String {
     }
 }
 
+/// Status line for a tool call whose arguments are still streaming, once
+/// the argument it names has arrived
+fn tool_preview_status(name: &str, input: &serde_json::Value) -> Option<String> {
+    let arg = |keys: &[&str]| {
+        keys.iter()
+            .find_map(|key| input.get(*key).and_then(|value| value.as_str()))
+    };
+    let (verb, target) = match name {
+        "Write" => ("Writing", arg(&["file_path", "filePath", "path"])?),
+        "Edit" | "MultiEdit" => ("Editing", arg(&["file_path", "filePath", "path"])?),
+        "Read" => ("Reading", arg(&["file_path", "filePath", "path"])?),
+        "Bash" => ("Running", arg(&["command"])?),
+        _ => return None,
+    };
+    let target = target.lines().next().unwrap_or_default();
+    let target: String = target.chars().take(60).collect();
+    Some(format!("{} {}...", verb, target))
+}
+
 fn extract_task_id(output: &str) -> Option<String> {
     let json = serde_json::from_str::<serde_json::Value>(output).ok()?;
     for key in ["task_id", "taskId", "id"] {
@@ -718,6 +739,59 @@ fn format_branch_list(branches: &[BranchInfo]) -> String {
     lines.join("\n")
 }
 
+/// Lines of a background task log shown by `/logs`
+const TASK_LOG_TAIL_LINES: usize = 200;
+
+fn format_task_log(task_id: &str, status: &str, content: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+    let skipped = lines.len().saturating_sub(max_lines);
+
+    let mut output = format!("Log for {} ({}):", task_id, status);
+    if skipped > 0 {
+        output.push_str(&format!("\n... {} earlier line(s) omitted", skipped));
+    }
+    if lines.is_empty() {
+        output.push_str("\n(no output yet)");
+    }
+    for line in &lines[skipped..] {
+        output.push('\n');
+        output.push_str(line);
+    }
+    output
+}
+
+/// Upper bound on fetching one provider's model list for the selector
+const MODEL_LISTING_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Fetch `provider`'s models in the background and report them as
+/// [`AppEvent::ModelsListed`]; providers without credentials or a listing
+/// API keep their built-in models
+fn spawn_model_listing(provider: String, event_tx: mpsc::Sender<AppEvent>) {
+    tokio::spawn(async move {
+        let client = match create_client_for_model(&format!("{}/", provider), None) {
+            Ok(client) => client,
+            Err(e) => {
+                tracing::debug!("Not listing {} models: {}", provider, e);
+                return;
+            }
+        };
+        let models = match tokio::time::timeout(MODEL_LISTING_TIMEOUT, client.list_models()).await {
+            Ok(Ok(list)) => list.models.into_iter().map(|model| model.id).collect(),
+            Ok(Err(e)) => {
+                tracing::debug!("Listing {} models failed: {}", provider, e);
+                return;
+            }
+            Err(_) => {
+                tracing::debug!("Listing {} models timed out", provider);
+                return;
+            }
+        };
+        let _ = event_tx
+            .send(AppEvent::ModelsListed { provider, models })
+            .await;
+    });
+}
+
 /// Create a model client from a "provider/model" string (e.g., "anthropic/claude-sonnet-4")
 fn create_client_for_model(
     model_str: &str,
@@ -740,8 +814,9 @@ fn create_client_for_model(
                 ..Default::default()
             };
 
-            AnthropicClient::new(config)
-                .map(|c| Arc::new(c) as Arc<dyn ModelClient>)
+            ModelClientBuilder::new()
+                .with_config(config)
+                .build()
                 .map_err(|e| e.to_string())
         }
         "openai" => {
@@ -756,8 +831,9 @@ fn create_client_for_model(
                 ..Default::default()
             };
 
-            OpenAIClient::new(config)
-                .map(|c| Arc::new(c) as Arc<dyn ModelClient>)
+            ModelClientBuilder::new()
+                .with_config(config)
+                .build()
                 .map_err(|e| e.to_string())
         }
         "google" | "gemini" => {
@@ -773,8 +849,9 @@ fn create_client_for_model(
                 ..Default::default()
             };
 
-            GeminiClient::new(config)
-                .map(|c| Arc::new(c) as Arc<dyn ModelClient>)
+            ModelClientBuilder::new()
+                .with_config(config)
+                .build()
                 .map_err(|e| e.to_string())
         }
         "ollama" => {
@@ -788,8 +865,9 @@ fn create_client_for_model(
                 ..Default::default()
             };
 
-            OllamaClient::new(config)
-                .map(|c| Arc::new(c) as Arc<dyn ModelClient>)
+            ModelClientBuilder::new()
+                .with_config(config)
+                .build()
                 .map_err(|e| e.to_string())
         }
         "opencode" => {
@@ -804,8 +882,9 @@ fn create_client_for_model(
                 ..Default::default()
             };
 
-            OpenCodeClient::new(config)
-                .map(|c| Arc::new(c) as Arc<dyn ModelClient>)
+            ModelClientBuilder::new()
+                .with_config(config)
+                .build()
                 .map_err(|e| e.to_string())
         }
         "friendliai" => {
@@ -857,8 +936,20 @@ fn create_client_for_model(
                 friendliai: Some(friendli_config),
                 ..Default::default()
             };
-            FriendliClient::new(config)
-                .map(|c| Arc::new(c) as Arc<dyn ModelClient>)
+            ModelClientBuilder::new()
+                .with_config(config)
+                .build()
+                .map_err(|e| e.to_string())
+        }
+        "mock" => {
+            let config = ProviderConfig {
+                provider: Provider::Mock,
+                model: model.to_string(),
+                ..Default::default()
+            };
+            ModelClientBuilder::new()
+                .with_config(config)
+                .build()
                 .map_err(|e| e.to_string())
         }
         _ => Err(format!("Unknown provider: {}", provider)),
@@ -994,6 +1085,7 @@ pub struct App {
     current_branch: String,
     pub session_stack: SessionStack,
     pub task_registry: BackgroundTaskRegistry,
+    autopilot_progress: Option<AutopilotProgress>,
     todos: Vec<TodoItem>,
     show_todo_sidebar: bool,
     todo_list_state: ListState,
@@ -1036,6 +1128,9 @@ pub struct App {
     git_summary_last_update: std::time::Instant,
     logo_image: LogoImage,
     show_logo: bool,
+    notifier: Notifier,
+    /// When the current agent run started, for completion notifications
+    run_started_at: Option<std::time::Instant>,
 }
 
 impl App {
@@ -1479,6 +1574,7 @@ impl App {
             current_branch: get_git_branch(),
             session_stack: SessionStack::new(),
             task_registry: BackgroundTaskRegistry::new(),
+            autopilot_progress: None,
             todos: Vec::new(),
             show_todo_sidebar,
             todo_list_state: ListState::default(),
@@ -1514,6 +1610,8 @@ impl App {
             git_summary_last_update: std::time::Instant::now(),
             logo_image,
             show_logo: true,
+            notifier: Notifier::default(),
+            run_started_at: None,
         }
     }
 
@@ -1543,6 +1641,10 @@ impl App {
         self.keybinds = keybinds;
     }
 
+    pub fn configure_notifications(&mut self, settings: NotificationSettings) {
+        self.notifier = Notifier::new(settings);
+    }
+
     pub fn set_show_logo(&mut self, show: bool) {
         self.show_logo = show;
     }
@@ -1652,16 +1754,32 @@ impl App {
         self.working_directory = working_directory.clone();
 
         let executor = executor.map(|exec| exec as Arc<_>);
-        let (agent, event_stream) = Agent::new_with_executor(config, client, executor)
+        let explainer = config
+            .explain_commands
+            .model
+            .as_deref()
+            .filter(|_| config.explain_commands.enabled)
+            .and_then(|model| match create_client_for_model(model, None) {
+                Ok(client) => Some(client),
+                Err(e) => {
+                    tracing::warn!("Failed to create command explainer {}: {}", model, e);
+                    None
+                }
+            });
+        let mut agent = Agent::new_with_executor(config, client, executor)
             .with_session_recording()
-            .map_err(|e| std::io::Error::other(e.to_string()))?
-            .with_event_stream();
+            .map_err(|e| std::io::Error::other(e.to_string()))?;
+        if let Some(explainer) = explainer {
+            agent = agent.with_command_explainer(explainer);
+        }
+        let (agent, event_stream) = agent.with_event_stream();
         let memory_system = agent.session().memory_system.clone();
         let mut event_system = uira_agent::create_event_system(working_directory, memory_system);
         event_system.start();
 
         let agent = agent.with_event_system(&event_system);
         let (mut agent, input_tx, approval_rx, command_tx) = agent.with_interactive();
+        let session_cancel = agent.control().cancel_signal();
 
         self.agent_input_tx = Some(input_tx);
         self.agent_command_tx = Some(command_tx);
@@ -1688,17 +1806,16 @@ impl App {
             }
         });
 
-        self.run(terminal).await
+        let result = self.run(terminal).await;
+        // Stop subagents, background tasks and commands still running
+        session_cancel.shutdown();
+        result
     }
 
     fn render(&mut self, frame: &mut ratatui::Frame) {
         self.update_terminal_title();
         let area = frame.area();
-        let hud_height = if self.task_registry.has_running_tasks() {
-            1
-        } else {
-            0
-        };
+        let hud_height = if self.hud_visible() { 1 } else { 0 };
         let session_header_height = if self.session_stack.is_in_child() {
             1
         } else {
@@ -1909,12 +2026,24 @@ impl App {
         frame.render_widget(status, area);
     }
 
+    fn hud_visible(&self) -> bool {
+        self.autopilot_progress.is_some() || self.task_registry.has_running_tasks()
+    }
+
     fn render_hud(&self, frame: &mut ratatui::Frame, area: Rect) {
-        if !self.task_registry.has_running_tasks() {
+        if !self.hud_visible() {
             return;
         }
 
-        let hud_line = hud::render_hud_line(&self.task_registry);
+        let mut hud_line = hud::render_hud_line(&self.task_registry);
+        if let Some(progress) = &self.autopilot_progress {
+            let mut spans = hud::render_autopilot_spans(progress);
+            if !hud_line.spans.is_empty() {
+                spans.push(Span::raw("  "));
+            }
+            spans.extend(hud_line.spans);
+            hud_line = Line::from(spans);
+        }
         let hud_paragraph = Paragraph::new(hud_line).style(Style::default().bg(self.theme.bg));
         frame.render_widget(hud_paragraph, area);
     }
@@ -2161,30 +2290,7 @@ impl App {
                     Style::default().fg(self.theme.text_muted),
                 )));
             } else {
-                for todo in &self.todos {
-                    let (indicator, status_color) = match todo.status {
-                        TodoStatus::Completed => ("✓", self.theme.success),
-                        TodoStatus::InProgress => ("•", self.theme.warning),
-                        TodoStatus::Cancelled => ("✗", self.theme.borders),
-                        TodoStatus::Pending => (" ", self.theme.borders),
-                    };
-
-                    let (priority_marker, color) = match (todo.status, todo.priority) {
-                        (TodoStatus::Completed, _) | (TodoStatus::Cancelled, _) => {
-                            ("", status_color)
-                        }
-                        (_, TodoPriority::High) => ("⚡", self.theme.error),
-                        (_, TodoPriority::Medium) => ("•", self.theme.warning),
-                        (_, TodoPriority::Low) => ("", status_color),
-                    };
-
-                    let prefix = format!("  [{}] {}", indicator, priority_marker);
-
-                    lines.push(Line::from(vec![
-                        Span::styled(prefix, Style::default().fg(color)),
-                        Span::styled(todo.content.clone(), Style::default().fg(color)),
-                    ]));
-                }
+                lines.extend(self.todo_sidebar_lines());
             }
             lines.push(Line::from(""));
         }
@@ -2314,6 +2420,50 @@ impl App {
             + (output_tokens as f64 / 1_000_000.0) * output_per_million
     }
 
+    /// Todo entries for the sidebar, nested under their parents
+    fn todo_sidebar_lines(&self) -> Vec<Line<'static>> {
+        todo_tree(&self.todos)
+            .into_iter()
+            .map(|(depth, todo)| {
+                let blocked = todo.is_incomplete() && todo.is_blocked(&self.todos);
+                let (indicator, status_color) = match todo.status {
+                    TodoStatus::Completed => ("✓", self.theme.success),
+                    TodoStatus::InProgress => ("•", self.theme.warning),
+                    TodoStatus::Cancelled => ("✗", self.theme.borders),
+                    TodoStatus::Pending if blocked => ("⧗", self.theme.text_muted),
+                    TodoStatus::Pending => (" ", self.theme.borders),
+                };
+
+                let (priority_marker, color) = match (todo.status, todo.priority) {
+                    (TodoStatus::Completed, _) | (TodoStatus::Cancelled, _) => ("", status_color),
+                    _ if blocked => ("", status_color),
+                    (_, TodoPriority::High) => ("⚡", self.theme.error),
+                    (_, TodoPriority::Medium) => ("•", self.theme.warning),
+                    (_, TodoPriority::Low) => ("", status_color),
+                };
+
+                let prefix = format!(
+                    "  {}[{}] {}",
+                    "  ".repeat(depth),
+                    indicator,
+                    priority_marker
+                );
+
+                let mut spans = vec![
+                    Span::styled(prefix, Style::default().fg(color)),
+                    Span::styled(todo.content.clone(), Style::default().fg(color)),
+                ];
+                if let Some(estimate) = todo.estimate {
+                    spans.push(Span::styled(
+                        format!(" ({})", estimate),
+                        Style::default().fg(self.theme.text_muted),
+                    ));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    }
+
     fn ensure_todo_selection(&mut self) {
         if self.todos.is_empty() {
             self.todo_list_state.select(None);
@@ -2531,11 +2681,7 @@ impl App {
     fn handle_mouse_click_event(&mut self, column: u16, row: u16) {
         let terminal_size = crossterm::terminal::size().unwrap_or((80, 24));
         let area = Rect::new(0, 0, terminal_size.0, terminal_size.1);
-        let hud_height = if self.task_registry.has_running_tasks() {
-            1
-        } else {
-            0
-        };
+        let hud_height = if self.hud_visible() { 1 } else { 0 };
         let session_header_height = if self.session_stack.is_in_child() {
             1
         } else {
@@ -3478,7 +3624,8 @@ impl App {
             return;
         }
 
-        if input.starts_with('/') {
+        // `/uira:*` commands and skills are expanded by the agent
+        if input.starts_with('/') && !input.starts_with("/uira:") {
             self.handle_slash_command(&input);
             return;
         }
@@ -3909,6 +4056,83 @@ impl App {
         }
     }
 
+    fn run_autopilot_command(&mut self, args: &[&str]) {
+        let directory = self.working_directory.clone();
+        let result = match args {
+            [] => {
+                let message = match AutopilotHook::read_state(&directory) {
+                    Some(state) => format!(
+                        "Autopilot {}: {} (iteration {}/{}){}",
+                        if state.active { "running" } else { "stopped" },
+                        state.phase.as_str(),
+                        state.iteration,
+                        state.max_iterations,
+                        state
+                            .pending_phase
+                            .map(|p| format!(", awaiting approval to start {}", p.as_str()))
+                            .unwrap_or_default()
+                    ),
+                    None => "Usage: /autopilot <task> | approve | cancel".to_string(),
+                };
+                self.chat_view.push_message("system", message, None);
+                return;
+            }
+            ["approve"] => AutopilotHook::approve(&directory),
+            ["cancel"] => AutopilotHook::cancel(&directory, Some("cancelled by user"))
+                .ok_or_else(|| "autopilot is not running".to_string()),
+            task => {
+                let settings = uira_core::config::load_config(None)
+                    .map(|config| config.autopilot)
+                    .unwrap_or_default();
+                let config = AutopilotConfig {
+                    max_iterations: settings.max_iterations,
+                    require_approval: settings.require_approval,
+                };
+                AutopilotHook::start(&directory, &task.join(" "), None, Some(config))
+            }
+        };
+
+        let state = match result {
+            Ok(state) => state,
+            Err(err) => {
+                self.chat_view
+                    .push_message("error", format!("Autopilot: {}", err), None);
+                return;
+            }
+        };
+
+        self.autopilot_progress = Some(AutopilotProgress {
+            phase: state.phase.as_str().to_string(),
+            pending: None,
+            iteration: state.iteration,
+            max_iterations: state.max_iterations,
+        });
+        if !state.active {
+            self.chat_view
+                .push_message("system", "Autopilot cancelled".to_string(), None);
+            return;
+        }
+
+        self.chat_view.push_message(
+            "system",
+            format!("Autopilot: starting {}", state.phase.as_str()),
+            None,
+        );
+        if let Some(ref tx) = self.agent_input_tx {
+            let tx = tx.clone();
+            let prompt = AutopilotHook::get_phase_prompt(&state);
+            tokio::spawn(async move {
+                if tx.send(Message::user(prompt)).await.is_err() {
+                    tracing::warn!("Agent input channel closed");
+                }
+            });
+            self.status = format!("Autopilot {}...", state.phase.as_str());
+            self.set_agent_state(AgentState::Thinking);
+        } else {
+            self.status = "No agent connected".to_string();
+        }
+    }
+
     fn undo_last_message_pair(&mut self) {
         let assistant_index = self
             .chat_view
@@ -3992,6 +4216,13 @@ impl App {
         }
     }
 
+    fn notify_run_finished(&mut self, summary: &str, success: bool) {
+        if let Some(started_at) = self.run_started_at.take() {
+            self.notifier
+                .run_finished(summary, started_at.elapsed(), success);
+        }
+    }
+
     fn copy_session_transcript(&mut self) {
         if self.chat_view.messages.is_empty() {
             self.status = "No messages to copy".to_string();
@@ -4041,7 +4272,10 @@ impl App {
                     ("/exit, /quit, /q".into(), "Exit the application".into()),
                     ("/auth, /status".into(), "Show current status".into()),
                     ("/models".into(), "List available models".into()),
-                    ("/model <name>".into(), "Switch to a different model".into()),
+                    (
+                        "/model [name]".into(),
+                        "Pick a model, or switch to <name>".into(),
+                    ),
                     (
                         "/render".into(),
                         "Render conversation as raw prompt text".into(),
@@ -4063,6 +4297,18 @@ impl App {
                     ("/provider".into(), "Open provider dialog".into()),
                     ("/export".into(), "Open export dialog".into()),
                     ("/subagent".into(), "Open subagent dialog".into()),
+                    (
+                        "/logs <task-id>".into(),
+                        "Show a background task's log".into(),
+                    ),
+                    (
+                        "/autopilot <task>".into(),
+                        "Run a task through plan, execute, verify".into(),
+                    ),
+                    (
+                        "/autopilot approve|cancel".into(),
+                        "Start the next autopilot phase or stop".into(),
+                    ),
                     ("/timeline".into(), "Open timeline dialog".into()),
                     ("/fork-timeline".into(), "Open fork confirmation".into()),
                     ("/rename".into(), "Rename current session".into()),
@@ -4078,7 +4324,15 @@ impl App {
                     ("/fork [name]".into(), "Fork session".into()),
                     ("/switch <branch>".into(), "Switch to branch".into()),
                     ("/branches".into(), "List branches".into()),
+                    (
+                        "/merge-notes <branch>".into(),
+                        "Merge a branch's notepad into this one".into(),
+                    ),
                     ("/tree".into(), "Show branch tree".into()),
+                    (
+                        "/uira:<command> [args]".into(),
+                        "Run a command or skill".into(),
+                    ),
                     ("/review [file]".into(), "Review staged changes".into()),
                     ("/wrap".into(), "Toggle diff wrap mode".into()),
                     ("/share".into(), "Share session to GitHub Gist".into()),
@@ -4582,9 +4836,30 @@ impl App {
             "/branches" => {
                 self.list_branches();
             }
+            "/merge-notes" => {
+                if let Some(branch_name) = parts.get(1) {
+                    self.merge_branch_notes(branch_name);
+                } else {
+                    self.chat_view
+                        .messages
+                        .push(ChatMessage::new("system", "Usage: /merge-notes <branch>"));
+                }
+            }
+            "/logs" => {
+                if let Some(task_id) = parts.get(1) {
+                    self.show_task_log(task_id);
+                } else {
+                    self.chat_view
+                        .messages
+                        .push(ChatMessage::new("system", "Usage: /logs <task-id>"));
+                }
+            }
             "/tree" => {
                 self.show_branch_tree();
             }
+            "/autopilot" => {
+                self.run_autopilot_command(&parts[1..]);
+            }
             "/share" => match parse_share_command(&parts) {
                 Ok(options) => self.share_session(options),
                 Err(err) => {
@@ -4594,26 +4869,18 @@ impl App {
                     ));
                 }
             },
-            "/models" => {
-                self.model_selector.open(self.current_model.clone());
-            }
+            "/models" => self.open_model_selector(),
             "/model" => {
                 if let Some(model_name) = parts.get(1) {
-                    let full_model = if model_name.contains('/') {
-                        (*model_name).to_string()
-                    } else {
-                        MODEL_GROUPS
-                            .iter()
-                            .find(|group| group.models.contains(model_name))
-                            .map(|group| format!("{}/{}", group.provider, model_name))
-                            .unwrap_or_else(|| (*model_name).to_string())
+                    let provider = self.model_selector.provider_of(model_name);
+                    let full_model = match provider {
+                        Some(provider) if !model_name.contains('/') => {
+                            format!("{}/{}", provider, model_name)
+                        }
+                        _ => (*model_name).to_string(),
                     };
 
-                    let is_known = MODEL_GROUPS
-                        .iter()
-                        .any(|group| group.models.iter().any(|m| full_model.ends_with(m)));
-
-                    if is_known || model_name.contains('/') {
+                    if provider.is_some() || model_name.contains('/') {
                         self.switch_model(&full_model);
                     } else {
                         self.chat_view.push_message(
@@ -4626,15 +4893,7 @@ impl App {
                         );
                     }
                 } else {
-                    let current = self
-                        .current_model
-                        .as_deref()
-                        .unwrap_or("(default from config)");
-                    self.chat_view.push_message(
-                        "system",
-                        format!("Current model: {}\nUsage: /model <name>", current),
-                        None,
-                    );
+                    self.open_model_selector();
                 }
             }
             "/theme" => {
@@ -4772,6 +5031,9 @@ impl App {
                 self.status = message.clone();
                 self.chat_view.push_message("system", message, None);
             }
+            AppEvent::ModelsListed { provider, models } => {
+                self.model_selector.set_models(&provider, models);
+            }
             AppEvent::RenderResult(text) => {
                 self.status = "Render complete".to_string();
                 self.chat_view.push_message("render", text, None);
@@ -4909,6 +5171,7 @@ impl App {
         match event {
             ThreadEvent::ThreadStarted { thread_id } => {
                 self.session_id = Some(thread_id);
+                self.run_started_at = Some(std::time::Instant::now());
                 self.set_agent_state(AgentState::Thinking);
                 self.status = "Agent started".to_string();
             }
@@ -4941,6 +5204,13 @@ impl App {
                 self.chat_view
                     .append_thinking_delta(&thinking, MAX_STREAMING_BUFFER_SIZE);
             }
+            ThreadEvent::ToolInputPreview {
+                tool_name, input, ..
+            } => {
+                if let Some(status) = tool_preview_status(&tool_name, &input) {
+                    self.status = status;
+                }
+            }
             ThreadEvent::ItemStarted { item } => match item {
                 Item::ToolCall {
                     id,
@@ -5064,14 +5334,35 @@ impl App {
                         self.chat_view.push_message("assistant", buffer, None);
                     }
                 }
+
+                let summary = self
+                    .chat_view
+                    .messages
+                    .iter()
+                    .rev()
+                    .find(|message| message.role == "assistant")
+                    .map(|message| message.content.clone())
+                    .unwrap_or_else(|| "Run complete".to_string());
+                self.notify_run_finished(&summary, true);
             }
             ThreadEvent::ThreadCancelled => {
                 self.set_agent_state(AgentState::Cancelled);
                 self.status = "Cancelled".to_string();
+                self.run_started_at = None;
             }
-            ThreadEvent::Error { message, .. } => {
+            ThreadEvent::Error {
+                message,
+                recoverable,
+                detail,
+            } => {
                 self.set_agent_state(AgentState::Failed);
                 self.status = format!("Error: {}", message);
+                if !recoverable {
+                    self.notify_run_finished(&message, false);
+                }
+                let message = detail
+                    .map(|detail| detail.display_with_hint())
+                    .unwrap_or(message);
                 self.chat_view.push_message("error", message, None);
             }
             // Goal Verification Events
@@ -5131,6 +5422,30 @@ impl App {
                 );
                 self.set_agent_state(AgentState::Complete);
             }
+            ThreadEvent::AutopilotPhaseChanged {
+                from,
+                to,
+                iteration,
+                max_iterations,
+                awaiting_approval,
+            } => {
+                let message = if awaiting_approval {
+                    format!(
+                        "Autopilot finished {}. Run /autopilot approve to start {}, or /autopilot cancel.",
+                        from, to
+                    )
+                } else {
+                    format!("Autopilot: {} → {}", from, to)
+                };
+                self.chat_view.push_message("system", message, None);
+                self.autopilot_progress = Some(AutopilotProgress::on_phase_changed(
+                    &from,
+                    &to,
+                    iteration,
+                    max_iterations,
+                    awaiting_approval,
+                ));
+            }
             ThreadEvent::BackgroundTaskSpawned {
                 task_id,
                 description,
@@ -5164,6 +5479,13 @@ impl App {
                 task_id, success, ..
             } => {
                 self.task_registry.on_completed(&task_id, success);
+                let description = self
+                    .task_registry
+                    .get(&task_id)
+                    .map(|task| task.description.clone())
+                    .unwrap_or_else(|| task_id.clone());
+                self.notifier
+                    .background_task_finished(&description, success);
                 let status = if success { "completed" } else { "failed" };
                 self.chat_view.push_message(
                     "system",
@@ -5350,7 +5672,19 @@ impl App {
         }
     }
 
+    /// Open the model picker and fetch the catalogs of providers not
+    /// listed yet
+    fn open_model_selector(&mut self) {
+        self.model_selector.open(self.current_model.clone());
+        for provider in self.model_selector.take_unlisted_providers() {
+            spawn_model_listing(provider, self.event_tx.clone());
+        }
+    }
+
     fn infer_max_context_tokens(model: &str) -> usize {
+        if let Some(metadata) = ModelRegistry::new().metadata(model) {
+            return metadata.context_window;
+        }
         let lower = model.to_ascii_lowercase();
         if lower.contains("haiku") || lower.contains("sonnet") || lower.contains("opus") {
             200_000
@@ -5533,6 +5867,68 @@ impl App {
         }
     }
 
+    fn merge_branch_notes(&mut self, branch_name: &str) {
+        self.status = format!("Merging notes from '{}'...", branch_name);
+
+        if let Some(ref tx) = self.agent_command_tx {
+            let tx = tx.clone();
+            let event_tx = self.event_tx.clone();
+            let branch_name = branch_name.to_string();
+
+            tokio::spawn(async move {
+                let (response_tx, response_rx) = tokio::sync::oneshot::channel();
+                if tx
+                    .send(AgentCommand::MergeNotes {
+                        branch_name,
+                        response_tx,
+                    })
+                    .await
+                    .is_err()
+                {
+                    tracing::warn!("Failed to send merge notes command");
+                    return;
+                }
+
+                let event = match response_rx.await {
+                    Ok(Ok(message)) => AppEvent::Info(message),
+                    Ok(Err(e)) => AppEvent::Error(format!("Merge failed: {}", e)),
+                    Err(_) => AppEvent::Error("Merge notes response channel closed".to_string()),
+                };
+                let _ = event_tx.send(event).await;
+            });
+        } else {
+            self.chat_view.messages.push(ChatMessage::new(
+                "error",
+                "No agent connected. Cannot merge notes.",
+            ));
+        }
+    }
+
+    fn show_task_log(&mut self, task_id: &str) {
+        use uira_orchestration::background_agent::{get_background_manager, BackgroundTaskConfig};
+
+        let tasks = get_background_manager(BackgroundTaskConfig::default());
+        let message = match tasks.get_task(task_id) {
+            Some(task) => match tasks.read_log(&task.id, 0) {
+                Ok((content, _)) => ChatMessage::new(
+                    "system",
+                    format_task_log(
+                        &task.id,
+                        task.status.as_str(),
+                        &content,
+                        TASK_LOG_TAIL_LINES,
+                    ),
+                ),
+                Err(e) => ChatMessage::new(
+                    "error",
+                    format!("Failed to read log for {}: {}", task.id, e),
+                ),
+            },
+            None => ChatMessage::new("error", format!("Task not found: {}", task_id)),
+        };
+        self.chat_view.messages.push(message);
+    }
+
     fn list_branches(&mut self) {
         self.status = "Loading branches...".to_string();
 
@@ -5646,6 +6042,18 @@ mod tests {
     use std::io::Write;
     use tempfile::NamedTempFile;
 
+    #[test]
+    fn format_task_log_keeps_the_tail() {
+        let log = format_task_log("bg_1", "running", "a\nb\nc\n", 2);
+        assert_eq!(
+            log,
+            "Log for bg_1 (running):\n... 1 earlier line(s) omitted\nb\nc"
+        );
+
+        let empty = format_task_log("bg_1", "queued", "", 2);
+        assert_eq!(empty, "Log for bg_1 (queued):\n(no output yet)");
+    }
+
     #[test]
     fn parse_review_target_defaults_to_staged() {
         assert_eq!(parse_review_target(&[]).unwrap(), ReviewTarget::Staged);
@@ -5747,6 +6155,34 @@ mod tests {
         assert!(app.show_todo_sidebar);
     }
 
+    #[test]
+    fn todo_sidebar_nests_subtasks_and_marks_blocked() {
+        let mut app = App::new();
+        app.todos = vec![
+            TodoItem {
+                id: "1".to_string(),
+                content: "Release".to_string(),
+                ..Default::default()
+            },
+            TodoItem {
+                id: "2".to_string(),
+                content: "Changelog".to_string(),
+                parent_id: Some("1".to_string()),
+                estimate: Some(uira_core::TodoEstimate::Small),
+                ..Default::default()
+            },
+        ];
+
+        let rendered: Vec<String> = app
+            .todo_sidebar_lines()
+            .iter()
+            .map(|line| line.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+
+        assert_eq!(rendered[0], "  [⧗] Release");
+        assert_eq!(rendered[1], "    [ ] •Changelog (S)");
+    }
+
     #[test]
     fn sidebar_sections_can_start_collapsed_from_config() {
         let app = App::new_with_sidebar(SidebarConfig {
@@ -5897,6 +6333,57 @@ mod tests {
         assert_eq!(app.cursor_pos, 6);
     }
 
+    #[test]
+    fn streaming_tool_input_sets_status() {
+        let mut app = App::new();
+
+        app.handle_agent_event(ThreadEvent::ToolInputPreview {
+            tool_call_id: "tc_1".to_string(),
+            tool_name: "Write".to_string(),
+            input: serde_json::json!({ "file_path": "src/lib.rs" }),
+        });
+        assert_eq!(app.status, "Writing src/lib.rs...");
+
+        app.handle_agent_event(ThreadEvent::ToolInputPreview {
+            tool_call_id: "tc_2".to_string(),
+            tool_name: "Glob".to_string(),
+            input: serde_json::json!({ "pattern": "**/*.rs" }),
+        });
+        assert_eq!(app.status, "Writing src/lib.rs...");
+    }
+
+    #[test]
+    fn autopilot_phase_events_drive_hud_progress() {
+        let mut app = App::new();
+        assert!(!app.hud_visible());
+
+        app.handle_agent_event(ThreadEvent::AutopilotPhaseChanged {
+            from: "planning".to_string(),
+            to: "executing".to_string(),
+            iteration: 2,
+            max_iterations: 10,
+            awaiting_approval: true,
+        });
+
+        assert!(app.hud_visible());
+        let progress = app.autopilot_progress.clone().unwrap();
+        assert_eq!(progress.phase, "planning");
+        assert_eq!(progress.pending.as_deref(), Some("executing"));
+        let last = app.chat_view.messages.last().unwrap();
+        assert!(last.content.contains("/autopilot approve"));
+
+        app.handle_agent_event(ThreadEvent::AutopilotPhaseChanged {
+            from: "planning".to_string(),
+            to: "executing".to_string(),
+            iteration: 2,
+            max_iterations: 10,
+            awaiting_approval: false,
+        });
+        let progress = app.autopilot_progress.clone().unwrap();
+        assert_eq!(progress.phase, "executing");
+        assert!(!progress.awaiting_approval());
+    }
+
     #[test]
     fn think_without_args_reports_status_and_usage() {
         let mut app = App::new();
@@ -18,6 +18,11 @@ pub enum AppEvent {
     SessionRenamed(String),
     ScrollToMessage(usize),
     ProviderChanged(String),
+    /// Models a provider's API reported, for the model selector
+    ModelsListed {
+        provider: String,
+        models: Vec<String>,
+    },
     ForkConfirmed {
         from_index: usize,
         confirmed: bool,
@@ -370,11 +370,14 @@ impl Widget for InlineApprovalPrompt<'_> {
             .clone()
             .unwrap_or_else(|| build_preview(&self.request.tool_name, &self.request.input));
 
+        // Risky commands carry an explanation and their effects below the reason
+        let reason_lines: Vec<&str> = self.request.reason.lines().take(3).collect();
+
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
                 Constraint::Length(1), // Context (filepath/command)
-                Constraint::Length(1), // Reason
+                Constraint::Length(reason_lines.len().max(1) as u16), // Reason
                 Constraint::Min(3),
                 Constraint::Length(1), // Buttons
                 Constraint::Length(1), // Help text
@@ -389,8 +392,13 @@ impl Widget for InlineApprovalPrompt<'_> {
         );
         context_para.render(chunks[0], buf);
 
-        let reason = Paragraph::new(format!("  {}", self.request.reason))
-            .style(Style::default().fg(self.theme.fg));
+        let reason = Paragraph::new(
+            reason_lines
+                .iter()
+                .map(|line| Line::from(format!("  {}", line)))
+                .collect::<Vec<_>>(),
+        )
+        .style(Style::default().fg(self.theme.fg));
         reason.render(chunks[1], buf);
 
         let preview_lines: Vec<&str> = preview.lines().collect();
@@ -688,4 +696,37 @@ mod tests {
         overlay.handle_key(KeyCode::Char('y'));
         assert!(!overlay.is_active());
     }
+
+    #[test]
+    fn test_inline_prompt_shows_command_explanation() {
+        let (tx, _rx) = oneshot::channel();
+        let request = ApprovalRequest {
+            id: "req1".to_string(),
+            tool_name: "Bash".to_string(),
+            input: serde_json::json!({"command": "rm -rf build"}),
+            reason: "Executes command\nRecursively deletes build\nEffects: Permanently removes build from disk"
+                .to_string(),
+            diff_preview: None,
+            response_tx: tx,
+        };
+        let theme = Theme::default();
+        let area = Rect::new(0, 0, 80, INLINE_APPROVAL_HEIGHT);
+        let mut buf = Buffer::empty(area);
+        InlineApprovalPrompt {
+            request: &request,
+            selected: 0,
+            scroll: 0,
+            queue_count: 0,
+            theme: &theme,
+        }
+        .render(area, &mut buf);
+
+        let rows: Vec<String> = (0..area.height)
+            .map(|y| (0..area.width).map(|x| buf[(x, y)].symbol()).collect())
+            .collect();
+        assert!(rows[2].contains("Executes command"));
+        assert!(rows[3].contains("Recursively deletes build"));
+        assert!(rows[4].contains("Effects: Permanently removes build from disk"));
+        assert!(rows.iter().any(|row| row.contains("$ rm -rf build")));
+    }
 }
@@ -1,14 +1,17 @@
 //! Model selector overlay for choosing AI models
 
+use std::collections::HashSet;
+
 use crossterm::event::KeyCode;
 use ratatui::{
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, Paragraph},
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 use uira_core::{DEFAULT_ANTHROPIC_MODEL, DEFAULT_OPENAI_MODEL};
+use uira_orchestration::{ModelMetadata, ModelRegistry};
 
 use crate::Theme;
 
@@ -61,9 +64,15 @@ pub const MODEL_GROUPS: &[ModelGroup] = &[
 ];
 pub struct ModelSelector {
     active: bool,
+    /// Provider and model names, seeded from [`MODEL_GROUPS`] and replaced
+    /// by each provider's own listing once it arrives
+    groups: Vec<(String, Vec<String>)>,
+    /// Providers whose models were listed (or requested) already
+    listed: HashSet<String>,
     group_index: usize,
     model_index: usize,
     current_model: Option<String>,
+    registry: ModelRegistry,
     theme: Theme,
 }
 
@@ -71,9 +80,20 @@ impl ModelSelector {
     pub fn new() -> Self {
         Self {
             active: false,
+            groups: MODEL_GROUPS
+                .iter()
+                .map(|group| {
+                    (
+                        group.provider.to_string(),
+                        group.models.iter().map(|m| m.to_string()).collect(),
+                    )
+                })
+                .collect(),
+            listed: HashSet::new(),
             group_index: 0,
             model_index: 0,
             current_model: None,
+            registry: ModelRegistry::new(),
             theme: Theme::default(),
         }
     }
@@ -84,13 +104,16 @@ impl ModelSelector {
 
     pub fn open(&mut self, current_model: Option<String>) {
         self.active = true;
-        self.current_model = current_model.clone();
+        self.current_model = current_model;
+        self.select_current();
+    }
 
-        if let Some(ref model) = current_model {
-            for (gi, group) in MODEL_GROUPS.iter().enumerate() {
-                for (mi, m) in group.models.iter().enumerate() {
-                    let full_name = format!("{}/{}", group.provider, m);
-                    if *m == model || full_name == *model {
+    fn select_current(&mut self) {
+        if let Some(ref model) = self.current_model {
+            for (gi, (provider, models)) in self.groups.iter().enumerate() {
+                for (mi, m) in models.iter().enumerate() {
+                    let full_name = format!("{}/{}", provider, m);
+                    if m == model || full_name == *model {
                         self.group_index = gi;
                         self.model_index = mi;
                         return;
@@ -103,6 +126,55 @@ impl ModelSelector {
         self.model_index = 0;
     }
 
+    /// Providers whose models haven't been listed yet; each is returned
+    /// once, so callers can fetch them in the background
+    pub fn take_unlisted_providers(&mut self) -> Vec<String> {
+        let providers: Vec<String> = self
+            .groups
+            .iter()
+            .map(|(provider, _)| provider.clone())
+            .filter(|provider| !self.listed.contains(provider))
+            .collect();
+        self.listed.extend(providers.iter().cloned());
+        providers
+    }
+
+    /// Replace `provider`'s models with what its API reported
+    pub fn set_models(&mut self, provider: &str, models: Vec<String>) {
+        if models.is_empty() {
+            return;
+        }
+        let selected = self
+            .selected_model()
+            .map(|(p, m)| (p.to_string(), m.to_string()));
+        match self.groups.iter_mut().find(|(p, _)| p == provider) {
+            Some((_, group_models)) => *group_models = models,
+            None => self.groups.push((provider.to_string(), models)),
+        }
+
+        // Keep the cursor on the same model if it's still listed
+        let reselected = selected.and_then(|(p, m)| {
+            let gi = self.groups.iter().position(|(gp, _)| *gp == p)?;
+            let mi = self.groups[gi].1.iter().position(|gm| *gm == m)?;
+            Some((gi, mi))
+        });
+        match reselected {
+            Some((gi, mi)) => {
+                self.group_index = gi;
+                self.model_index = mi;
+            }
+            None => self.select_current(),
+        }
+    }
+
+    /// Provider listing `model`, as typed after `/model`
+    pub fn provider_of(&self, model: &str) -> Option<&str> {
+        self.groups
+            .iter()
+            .find(|(_, models)| models.iter().any(|m| m == model))
+            .map(|(provider, _)| provider.as_str())
+    }
+
     pub fn close(&mut self) {
         self.active = false;
     }
@@ -111,13 +183,14 @@ impl ModelSelector {
         self.active
     }
 
-    pub fn selected_model(&self) -> Option<(&'static str, &'static str)> {
-        MODEL_GROUPS.get(self.group_index).and_then(|group| {
-            group
-                .models
-                .get(self.model_index)
-                .map(|model| (group.provider, *model))
-        })
+    pub fn selected_model(&self) -> Option<(&str, &str)> {
+        self.groups
+            .get(self.group_index)
+            .and_then(|(provider, models)| {
+                models
+                    .get(self.model_index)
+                    .map(|model| (provider.as_str(), model.as_str()))
+            })
     }
 
     pub fn handle_key(&mut self, key: KeyCode) -> Option<String> {
@@ -135,15 +208,15 @@ impl ModelSelector {
                     self.model_index -= 1;
                 } else if self.group_index > 0 {
                     self.group_index -= 1;
-                    self.model_index = MODEL_GROUPS[self.group_index].models.len() - 1;
+                    self.model_index = self.groups[self.group_index].1.len().saturating_sub(1);
                 }
                 None
             }
             KeyCode::Down | KeyCode::Char('j') => {
-                let current_group = &MODEL_GROUPS[self.group_index];
-                if self.model_index < current_group.models.len() - 1 {
+                let current_group = &self.groups[self.group_index].1;
+                if self.model_index + 1 < current_group.len() {
                     self.model_index += 1;
-                } else if self.group_index < MODEL_GROUPS.len() - 1 {
+                } else if self.group_index + 1 < self.groups.len() {
                     self.group_index += 1;
                     self.model_index = 0;
                 }
@@ -157,7 +230,7 @@ impl ModelSelector {
                 None
             }
             KeyCode::Right | KeyCode::Char('l') => {
-                if self.group_index < MODEL_GROUPS.len() - 1 {
+                if self.group_index + 1 < self.groups.len() {
                     self.group_index += 1;
                     self.model_index = 0;
                 }
@@ -165,8 +238,9 @@ impl ModelSelector {
             }
             KeyCode::Enter => {
                 if let Some((provider, model)) = self.selected_model() {
+                    let selected = format!("{}/{}", provider, model);
                     self.close();
-                    Some(format!("{}/{}", provider, model))
+                    Some(selected)
                 } else {
                     None
                 }
@@ -202,21 +276,25 @@ impl ModelSelector {
             .split(inner);
 
         let mut items = Vec::new();
-        for (gi, group) in MODEL_GROUPS.iter().enumerate() {
+        let mut selected_row = 0;
+        for (gi, (provider, models)) in self.groups.iter().enumerate() {
             items.push(ListItem::new(Line::from(Span::styled(
-                format!("  {}", group.provider.to_uppercase()),
+                format!("  {}", provider.to_uppercase()),
                 Style::default()
                     .fg(self.theme.warning)
                     .add_modifier(Modifier::BOLD),
             ))));
 
-            for (mi, model) in group.models.iter().enumerate() {
+            for (mi, model) in models.iter().enumerate() {
                 let is_selected = gi == self.group_index && mi == self.model_index;
-                let full_name = format!("{}/{}", group.provider, model);
+                if is_selected {
+                    selected_row = items.len();
+                }
+                let full_name = format!("{}/{}", provider, model);
                 let is_current = self
                     .current_model
                     .as_deref()
-                    .map(|cm| cm == *model || cm == full_name)
+                    .map(|cm| cm == model || cm == full_name)
                     .unwrap_or(false);
 
                 let prefix = if is_current { "→ " } else { "  " };
@@ -233,15 +311,20 @@ impl ModelSelector {
                     Style::default().fg(self.theme.fg)
                 };
 
-                items.push(ListItem::new(Line::from(Span::styled(
-                    format!("    {}{}", prefix, model),
-                    style,
-                ))));
+                let mut spans = vec![Span::styled(format!("    {}{}", prefix, model), style)];
+                if let Some(metadata) = self.registry.metadata(model) {
+                    spans.push(Span::styled(
+                        format!("  {}", describe_metadata(&metadata)),
+                        Style::default().fg(self.theme.borders),
+                    ));
+                }
+                items.push(ListItem::new(Line::from(spans)));
             }
         }
 
-        let list = List::new(items);
-        frame.render_widget(list, chunks[0]);
+        // Scroll so the selection stays visible in long provider listings
+        let mut state = ListState::default().with_selected(Some(selected_row));
+        frame.render_stateful_widget(List::new(items), chunks[0], &mut state);
 
         let help = Paragraph::new("↑↓/jk: navigate | ←→/hl: group | Enter: select | Esc: cancel")
             .style(Style::default().fg(self.theme.borders))
@@ -250,8 +333,60 @@ impl ModelSelector {
     }
 }
 
+/// Context window and price per 1M tokens, e.g. `200K · $3/$15`
+pub fn describe_metadata(metadata: &ModelMetadata) -> String {
+    let window = metadata.context_window;
+    let context = if window >= 1_000_000 {
+        format!("{:.1}M", window as f64 / 1_000_000.0).replace(".0M", "M")
+    } else {
+        format!("{}K", window / 1000)
+    };
+    match (metadata.input_price, metadata.output_price) {
+        (Some(input), Some(output)) => format!("{} · ${}/${}", context, input, output),
+        _ => context,
+    }
+}
+
 impl Default for ModelSelector {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listed_models_replace_the_built_in_ones() {
+        let mut selector = ModelSelector::new();
+        selector.open(Some("openai/gpt-4o-mini".to_string()));
+        assert_eq!(selector.selected_model(), Some(("openai", "gpt-4o-mini")));
+
+        let providers = selector.take_unlisted_providers();
+        assert!(providers.contains(&"openai".to_string()));
+        assert!(selector.take_unlisted_providers().is_empty());
+
+        selector.set_models(
+            "openai",
+            vec!["gpt-4o-mini".to_string(), "gpt-5".to_string()],
+        );
+        // The cursor stays on the same model
+        assert_eq!(selector.selected_model(), Some(("openai", "gpt-4o-mini")));
+        selector.handle_key(KeyCode::Down);
+        assert_eq!(selector.provider_of("gpt-5"), Some("openai"));
+        assert_eq!(
+            selector.handle_key(KeyCode::Enter),
+            Some("openai/gpt-5".to_string())
+        );
+    }
+
+    #[test]
+    fn describes_context_window_and_price() {
+        let registry = ModelRegistry::new();
+        let sonnet = registry.metadata("claude-sonnet-4-20250514").unwrap();
+        assert_eq!(describe_metadata(&sonnet), "200K · $3/$15");
+        let flash = registry.metadata("gemini-2.0-flash").unwrap();
+        assert_eq!(describe_metadata(&flash), "1M · $0.1/$0.4");
+    }
+}
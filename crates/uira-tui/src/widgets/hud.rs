@@ -66,6 +66,10 @@ impl BackgroundTaskRegistry {
         }
     }
 
+    pub fn get(&self, task_id: &str) -> Option<&BackgroundTaskInfo> {
+        self.tasks.get(task_id)
+    }
+
     pub fn running_count(&self) -> usize {
         self.tasks
             .values()
@@ -148,6 +152,115 @@ pub fn render_hud_line(registry: &BackgroundTaskRegistry) -> Line<'static> {
     Line::from(spans)
 }
 
+/// Autopilot phases in the order the progress widget shows them
+const AUTOPILOT_STEPS: [(&str, &str); 4] = [
+    ("planning", "plan"),
+    ("executing", "execute"),
+    ("verifying", "verify"),
+    ("complete", "done"),
+];
+
+/// Autopilot progress as reported by `AutopilotPhaseChanged` events
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AutopilotProgress {
+    /// Phase in progress, or the one just finished while awaiting approval
+    pub phase: String,
+    /// Phase waiting for user approval before it starts
+    pub pending: Option<String>,
+    pub iteration: u32,
+    pub max_iterations: u32,
+}
+
+impl AutopilotProgress {
+    pub fn on_phase_changed(
+        from: &str,
+        to: &str,
+        iteration: u32,
+        max_iterations: u32,
+        awaiting_approval: bool,
+    ) -> Self {
+        if awaiting_approval {
+            Self {
+                phase: from.to_string(),
+                pending: Some(to.to_string()),
+                iteration,
+                max_iterations,
+            }
+        } else {
+            Self {
+                phase: to.to_string(),
+                pending: None,
+                iteration,
+                max_iterations,
+            }
+        }
+    }
+
+    pub fn awaiting_approval(&self) -> bool {
+        self.pending.is_some()
+    }
+}
+
+pub fn render_autopilot_spans(progress: &AutopilotProgress) -> Vec<Span<'static>> {
+    let mut spans = vec![Span::styled(
+        "autopilot ",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )];
+
+    let current = AUTOPILOT_STEPS
+        .iter()
+        .position(|(phase, _)| *phase == progress.phase);
+
+    for (index, (_, label)) in AUTOPILOT_STEPS.iter().enumerate() {
+        if index > 0 {
+            spans.push(Span::styled(" → ", Style::default().fg(Color::DarkGray)));
+        }
+        let style = match current {
+            // A finished phase waiting on approval counts as done
+            Some(current)
+                if index < current || (index == current && progress.awaiting_approval()) =>
+            {
+                Style::default().fg(Color::Green)
+            }
+            Some(current) if index == current && progress.phase == "complete" => Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+            Some(current) if index == current => Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+            _ => Style::default().fg(Color::DarkGray),
+        };
+        spans.push(Span::styled(label.to_string(), style));
+    }
+
+    if current.is_none() {
+        // Failed or cancelled runs have no place on the track
+        spans.push(Span::styled(
+            format!(" ({})", progress.phase),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    spans.push(Span::styled(
+        format!(" [{}/{}]", progress.iteration, progress.max_iterations),
+        Style::default().fg(Color::Gray),
+    ));
+
+    if let Some(pending) = &progress.pending {
+        spans.push(Span::styled(
+            format!(
+                " awaiting approval to start {} (/autopilot approve)",
+                pending
+            ),
+            Style::default().fg(Color::Yellow),
+        ));
+    }
+
+    spans
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -215,4 +328,36 @@ mod tests {
         assert!(rendered.contains('A'));
         assert!(rendered.contains('x'));
     }
+
+    fn span_for<'a>(spans: &'a [Span<'static>], content: &str) -> &'a Span<'static> {
+        spans.iter().find(|span| span.content == content).unwrap()
+    }
+
+    #[test]
+    fn test_autopilot_progress_highlights_current_phase() {
+        let progress = AutopilotProgress::on_phase_changed("planning", "executing", 3, 10, false);
+        assert!(!progress.awaiting_approval());
+
+        let spans = render_autopilot_spans(&progress);
+        assert_eq!(span_for(&spans, "plan").style.fg, Some(Color::Green));
+        assert_eq!(span_for(&spans, "execute").style.fg, Some(Color::Yellow));
+        assert_eq!(span_for(&spans, "verify").style.fg, Some(Color::DarkGray));
+        assert!(spans.iter().any(|span| span.content == " [3/10]"));
+    }
+
+    #[test]
+    fn test_autopilot_progress_awaiting_approval() {
+        let progress = AutopilotProgress::on_phase_changed("planning", "executing", 2, 10, true);
+        assert_eq!(progress.phase, "planning");
+        assert_eq!(progress.pending.as_deref(), Some("executing"));
+
+        let spans = render_autopilot_spans(&progress);
+        assert_eq!(span_for(&spans, "plan").style.fg, Some(Color::Green));
+        assert_eq!(span_for(&spans, "execute").style.fg, Some(Color::DarkGray));
+        let rendered = spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect::<String>();
+        assert!(rendered.contains("awaiting approval to start executing"));
+    }
 }
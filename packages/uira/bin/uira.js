@@ -107,7 +107,9 @@ const cargoPkg = binaryName === 'uira-agent' ? 'uira-cli' : 'uira-commit-hook-cl
 const binary = getBinaryPath(binaryName);
 if (binary && existsSync(binary)) {
   try {
-    execFileSync(binary, args, { stdio: 'inherit' });
+    // Lets uira-agent warn when it no longer matches this package's napi addon
+    const env = { ...process.env, UIRA_NPM_PACKAGE_VERSION: require('../package.json').version };
+    execFileSync(binary, args, { stdio: 'inherit', env });
   } catch (e) {
     if (e.status !== undefined) {
       process.exit(e.status);
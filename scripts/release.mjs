@@ -19,8 +19,10 @@
  *   publish-npm-hook                      Publish @uiradev/hook
  *   release-notes <version>               Generate release notes (stdout)
  *   finalize <version>                    Commit version bump, tag, create GitHub release
+ *   release-key                           Generate the ed25519 key pair that signs release checksums
  */
 
+import * as crypto from "node:crypto";
 import * as fs from "node:fs";
 import * as path from "node:path";
 import { execFileSync } from "node:child_process";
@@ -654,6 +656,7 @@ function finalize(version) {
   if (fs.existsSync(releaseDir)) {
     log("Generating checksums...");
     run("sh", ["-c", "sha256sum * > SHA256SUMS.txt"], { cwd: releaseDir });
+    signChecksums(releaseDir);
   }
 
   // 4. Generate release notes to temp file
@@ -701,6 +704,34 @@ function finalize(version) {
   console.log(`\nRelease v${version} finalized.`);
 }
 
+// ── release signing ──────────────────────────────────────────────────────────
+
+/**
+ * Sign SHA256SUMS.txt with the ed25519 key in RELEASE_SIGNING_KEY (PKCS#8 PEM).
+ * `uira-agent self-update` verifies the signature against the public key the
+ * build workflow compiled in from UIRA_RELEASE_PUBLIC_KEY.
+ */
+function signChecksums(releaseDir) {
+  const key = process.env.RELEASE_SIGNING_KEY;
+  if (!key) {
+    log("RELEASE_SIGNING_KEY not set; skipping checksum signature (self-update will refuse this release)");
+    return;
+  }
+  const sums = fs.readFileSync(path.join(releaseDir, "SHA256SUMS.txt"));
+  const signature = crypto.sign(null, sums, crypto.createPrivateKey(key));
+  writeText(path.join(releaseDir, "SHA256SUMS.txt.sig"), `${signature.toString("base64")}\n`);
+  log("Signed SHA256SUMS.txt");
+}
+
+function releaseKey() {
+  const { publicKey, privateKey } = crypto.generateKeyPairSync("ed25519");
+  // The raw 32-byte key is the tail of the SPKI encoding
+  const raw = publicKey.export({ format: "der", type: "spki" }).subarray(-32);
+  console.log("RELEASE_SIGNING_KEY (repository secret):\n");
+  console.log(privateKey.export({ format: "pem", type: "pkcs8" }));
+  console.log(`UIRA_RELEASE_PUBLIC_KEY (repository variable): ${raw.toString("base64")}`);
+}
+
 /** Same as releaseNotes but returns the string instead of printing. */
 function captureReleaseNotes(version) {
   const prevTag = run("git", ["describe", "--tags", "--abbrev=0", "HEAD^"], {
@@ -789,6 +820,7 @@ const commands = {
   "publish-npm-alias": publishNpmAlias,
   "release-notes": releaseNotes,
   finalize,
+  "release-key": releaseKey,
 };
 
 function main() {
@@ -808,6 +840,7 @@ function main() {
     console.log("  publish-npm-alias                   Publish uira (unscoped alias)");
     console.log("  release-notes <version>             Generate release notes");
     console.log("  finalize <version>                  Commit, tag, GitHub release");
+    console.log("  release-key                         Generate the checksum signing key pair");
     process.exit(0);
   }
 